@@ -0,0 +1,194 @@
+use std::{
+    fs::File,
+    io::{self, Read},
+    os::fd::AsFd,
+    sync::Arc,
+};
+
+use crate::{
+    connection::Connection,
+    error::{Result, anyhow},
+    protocol::{
+        data_device::DataDevice,
+        data_device_manager::DataDeviceManager,
+        data_source::DataSource,
+        seat::{Seat, SeatState},
+    },
+    sync::Lock,
+    sys,
+};
+
+/// The MIME types text is offered and requested as, in order of preference.
+///
+/// `UTF8_STRING` is the name used by X11 clients running through Xwayland.
+pub const TEXT_MIME_TYPES: [&str; 3] = ["text/plain;charset=utf-8", "UTF8_STRING", "text/plain"];
+
+/// The size of the chunk read from a transfer pipe at once.
+const READ_CHUNK_LEN: usize = 4096;
+
+/// Copy-and-paste of plain text through the selection of a seat.
+///
+/// Wraps the data device of the seat and takes care of the data source, the
+/// offered MIME types and the pipe transfer, so text is copied or pasted in
+/// a single call.
+pub struct Clipboard {
+    /// The connection the clipboard uses.
+    conn: Connection,
+    /// The manager creating the data sources.
+    manager: DataDeviceManager,
+    /// The data device of the seat, which tracks the selection.
+    device: DataDevice,
+    /// The state of the seat, which provides the serial for `set_selection`.
+    seat: Arc<SeatState>,
+    /// The source of the text this client put on the clipboard, if any.
+    source: Lock<Option<DataSource>>,
+}
+
+impl Clipboard {
+    /// Binds the data device manager and creates the data device of `seat`.
+    ///
+    /// The current selection is announced when a surface of the client gains
+    /// keyboard focus, so [`Clipboard::get_text`] returns `None` until then.
+    pub fn new(conn: &Connection, seat: &Seat) -> Result<Clipboard> {
+        let manager = DataDeviceManager::bind(conn)?;
+        let device = manager.get_data_device(seat)?;
+
+        Ok(Clipboard {
+            conn: conn.clone(),
+            manager,
+            device,
+            seat: seat.state(),
+            source: Lock::new(None),
+        })
+    }
+
+    /// Puts `text` on the clipboard.
+    ///
+    /// The compositor only accepts the change in response to a recent input
+    /// event, so this is meant to be called from an input handler, such as
+    /// the one of a key press.
+    pub fn set_text(&self, text: &str) -> Result<()> {
+        let source = self
+            .manager
+            .create_data_source(&TEXT_MIME_TYPES, text.as_bytes().to_vec())?;
+        self.device
+            .set_selection(Some(&source), self.seat.serial())?;
+
+        self.replace_source(Some(source))?;
+        self.conn.flush()
+    }
+
+    /// Clears the clipboard.
+    pub fn clear(&self) -> Result<()> {
+        self.device.set_selection(None, self.seat.serial())?;
+
+        self.replace_source(None)?;
+        self.conn.flush()
+    }
+
+    /// Returns the text on the clipboard.
+    ///
+    /// Blocks until the owner of the selection sent the whole text; events
+    /// keep being dispatched meanwhile.
+    ///
+    /// # Returns
+    /// `None` if the clipboard is empty, its content is not text, or its
+    /// content is not known yet.
+    ///
+    /// # Errors
+    /// Returns an error if the transfer fails or the text is not valid UTF-8.
+    pub fn get_text(&self) -> Result<Option<String>> {
+        // Text copied by this client is answered directly: the transfer would
+        // otherwise need this thread to write and read the pipe at once.
+        if let Some(data) = self.source.borrow().as_ref().and_then(DataSource::data) {
+            return Ok(Some(String::from_utf8(data.to_vec())?));
+        }
+
+        let Some(offer) = self.device.selection() else {
+            return Ok(None);
+        };
+        let Some(mime_type) = TEXT_MIME_TYPES
+            .into_iter()
+            .find(|mime_type| offer.has_mime_type(mime_type))
+        else {
+            return Ok(None);
+        };
+
+        let pipe = File::from(offer.receive(mime_type)?);
+        sys::set_nonblocking(pipe.as_fd())?;
+
+        let data = self.read_to_end(pipe)?;
+        let text =
+            String::from_utf8(data).map_err(|_| anyhow!("Clipboard text is not valid UTF-8"))?;
+
+        Ok(Some(text))
+    }
+
+    /// Like [`Clipboard::get_text`], but returns right away and delivers the
+    /// text to `on_done` from a later [`Connection::dispatch`], once the owner
+    /// of the selection sent all of it.
+    ///
+    /// `on_done` receives `None` if the clipboard is empty, its content is
+    /// not text, or its content is not known yet, and an error if the
+    /// transfer fails or the text is not valid UTF-8. It is invoked right
+    /// away when no transfer is needed.
+    pub fn get_text_async<F>(&self, on_done: F) -> Result<()>
+    where
+        F: FnOnce(&Connection, Result<Option<String>>) + Send + 'static,
+    {
+        if let Some(data) = self.source.borrow().as_ref().and_then(DataSource::data) {
+            let text = String::from_utf8(data.to_vec()).map_err(Into::into);
+            on_done(&self.conn, text.map(Some));
+            return Ok(());
+        }
+
+        let mime_type = self.device.selection().and_then(|offer| {
+            TEXT_MIME_TYPES
+                .into_iter()
+                .find(|mime_type| offer.has_mime_type(mime_type))
+                .map(|mime_type| (offer, mime_type))
+        });
+        let Some((offer, mime_type)) = mime_type else {
+            on_done(&self.conn, Ok(None));
+            return Ok(());
+        };
+
+        offer.receive_async(mime_type, move |conn, data| {
+            let text = data.and_then(|data| {
+                String::from_utf8(data).map_err(|_| anyhow!("Clipboard text is not valid UTF-8"))
+            });
+            on_done(conn, text.map(Some));
+        })
+    }
+
+    /// Reads a non-blocking pipe until end of file, dispatching events while
+    /// waiting for data.
+    fn read_to_end(&self, mut pipe: File) -> Result<Vec<u8>> {
+        let mut data = Vec::new();
+        let mut chunk = [0u8; READ_CHUNK_LEN];
+
+        loop {
+            if !self.conn.dispatch_with(Some(pipe.as_fd()))? {
+                continue;
+            }
+
+            loop {
+                match pipe.read(&mut chunk) {
+                    Ok(0) => return Ok(data),
+                    Ok(read_len) => data.extend_from_slice(&chunk[..read_len]),
+                    Err(error) if error.kind() == io::ErrorKind::WouldBlock => break,
+                    Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(error) => return Err(error.into()),
+                }
+            }
+        }
+    }
+
+    /// Replaces the source owned by the clipboard, destroying the previous one.
+    fn replace_source(&self, source: Option<DataSource>) -> Result<()> {
+        match self.source.replace(source) {
+            Some(previous) => previous.destroy(),
+            None => Ok(()),
+        }
+    }
+}
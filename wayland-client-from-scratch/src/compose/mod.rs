@@ -0,0 +1,330 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+};
+
+use crate::{
+    error::{Context, Result},
+    keysym::Keysym,
+};
+
+/// The built-in subset of the Compose table, in the Compose file format.
+const BUILTIN_TABLE: &str = include_str!("builtin.compose");
+
+/// The Compose file of the most common locale, used by
+/// [`ComposeTable::system`] when the user has no file of their own.
+const SYSTEM_COMPOSE_FILE: &str = "/usr/share/X11/locale/en_US.UTF-8/Compose";
+
+/// A table of compose sequences, mapping sequences of keysyms to the text
+/// they produce.
+///
+/// Sequences start either with a dead key, such as `dead_acute` followed by
+/// `a` for `á`, or with the Compose key (`Multi_key`), such as `Multi_key`
+/// `o` `c` for `©`.
+#[derive(Debug, Clone, Default)]
+pub struct ComposeTable {
+    /// The text of each complete sequence.
+    sequences: HashMap<Vec<Keysym>, String>,
+    /// Every proper prefix of the sequences, which keep a sequence going.
+    prefixes: HashSet<Vec<Keysym>>,
+}
+
+impl ComposeTable {
+    /// Returns the built-in table, covering the dead keys and the Compose
+    /// key sequences of the common Latin-1 accents and symbols.
+    pub fn builtin() -> ComposeTable {
+        ComposeTable::parse(BUILTIN_TABLE)
+    }
+
+    /// Loads the table of the user, like libX11 and xkbcommon do.
+    ///
+    /// Tries the file named by `$XCOMPOSEFILE`, then `~/.XCompose`, then
+    /// the system table of the `en_US.UTF-8` locale, and falls back to the
+    /// built-in table if none can be read.
+    pub fn system() -> ComposeTable {
+        let user_files = [
+            std::env::var_os("XCOMPOSEFILE").map(Into::into),
+            std::env::var_os("HOME").map(|home| Path::new(&home).join(".XCompose")),
+        ];
+
+        user_files
+            .into_iter()
+            .flatten()
+            .chain([SYSTEM_COMPOSE_FILE.into()])
+            .find_map(|path| ComposeTable::from_file(&path).ok())
+            .unwrap_or_else(ComposeTable::builtin)
+    }
+
+    /// Reads a table in the Compose file format, see [`ComposeTable::parse`].
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read.
+    pub fn from_file(path: &Path) -> Result<ComposeTable> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read Compose file {}", path.display()))?;
+
+        Ok(ComposeTable::parse(&text))
+    }
+
+    /// Parses a table in the Compose file format of libX11:
+    ///
+    /// ```text
+    /// <dead_acute> <a> : "á" aacute # LATIN SMALL LETTER A WITH ACUTE
+    /// ```
+    ///
+    /// Lines that cannot be used are skipped rather than rejected, as
+    /// system tables contain many keysyms unknown to [`Keysym::from_name`].
+    /// This includes `include` directives, which are not followed.
+    pub fn parse(text: &str) -> ComposeTable {
+        let mut table = ComposeTable::default();
+
+        for line in text.lines() {
+            if let Some((sequence, output)) = parse_line(line) {
+                table.insert(sequence, output);
+            }
+        }
+
+        table
+    }
+
+    /// Adds a sequence, replacing an earlier one with the same keysyms.
+    pub fn insert(&mut self, sequence: Vec<Keysym>, output: String) {
+        for len in 1..sequence.len() {
+            self.prefixes.insert(sequence[..len].to_vec());
+        }
+        self.sequences.insert(sequence, output);
+    }
+
+    /// Returns the number of sequences in the table.
+    pub fn len(&self) -> usize {
+        self.sequences.len()
+    }
+
+    /// Returns `true` if the table has no sequence.
+    pub fn is_empty(&self) -> bool {
+        self.sequences.is_empty()
+    }
+}
+
+/// Parses one line of a Compose file into its sequence and output text.
+fn parse_line(line: &str) -> Option<(Vec<Keysym>, String)> {
+    let (lhs, rhs) = line.split_once(':')?;
+
+    let mut sequence = Vec::new();
+    let mut rest = lhs.trim();
+    while let Some(token) = rest.strip_prefix('<') {
+        let (name, tail) = token.split_once('>')?;
+        sequence.push(Keysym::from_name(name)?);
+        rest = tail.trim_start();
+    }
+    if !rest.is_empty() || sequence.is_empty() {
+        return None;
+    }
+
+    let rhs = rhs.trim_start();
+    let output = match rhs.strip_prefix('"') {
+        Some(quoted) => parse_string(quoted)?,
+        None => {
+            let name = rhs.split_whitespace().next()?;
+            Keysym::from_name(name)?.to_char()?.to_string()
+        }
+    };
+
+    Some((sequence, output))
+}
+
+/// Parses the remainder of a quoted string after its opening quote,
+/// resolving the backslash escapes of the Compose format.
+fn parse_string(quoted: &str) -> Option<String> {
+    let mut output = String::new();
+    let mut chars = quoted.chars();
+
+    loop {
+        match chars.next()? {
+            '"' => return Some(output),
+            '\\' => match chars.next()? {
+                'n' => output.push('\n'),
+                'r' => output.push('\r'),
+                't' => output.push('\t'),
+                c @ ('x' | 'X') => {
+                    let hex: String = chars.clone().take_while(char::is_ascii_hexdigit).collect();
+                    let Some(byte) = u8::from_str_radix(&hex, 16).ok() else {
+                        output.push(c);
+                        continue;
+                    };
+                    chars.nth(hex.len() - 1);
+                    output.push(char::from(byte));
+                }
+                c => output.push(c),
+            },
+            c => output.push(c),
+        }
+    }
+}
+
+/// The outcome of feeding a keysym to a [`Compose`] engine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ComposeStatus {
+    /// The keysym is not part of a sequence and should be handled as usual.
+    Passthrough,
+    /// The keysym started or continued a sequence; nothing should be typed yet.
+    Composing,
+    /// The keysym completed a sequence producing this text.
+    Composed(String),
+    /// The keysym does not continue the sequence in progress, which was
+    /// abandoned along with the keysym.
+    Cancelled,
+}
+
+/// A compose sequence engine, layered on the keysyms of key presses.
+///
+/// Feed it the keysym of every pressed or repeated key, as resolved by
+/// [`FallbackKeymap`](crate::keysym::FallbackKeymap) or the `xkb` feature,
+/// and type the text it returns instead of the keysym's own character while
+/// a sequence is in progress.
+#[derive(Debug, Clone)]
+pub struct Compose {
+    /// The sequences the engine recognizes.
+    table: ComposeTable,
+    /// The keysyms of the sequence in progress.
+    pending: Vec<Keysym>,
+}
+
+impl Compose {
+    /// Creates an engine recognizing the sequences of `table`.
+    pub fn new(table: ComposeTable) -> Compose {
+        Compose {
+            table,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Returns `true` while a sequence is in progress.
+    pub fn is_composing(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Abandons the sequence in progress, e.g. when the keyboard focus is lost.
+    pub fn reset(&mut self) {
+        self.pending.clear();
+    }
+
+    /// Feeds the keysym of a pressed key to the engine.
+    ///
+    /// Modifier keys never interrupt a sequence, so Shift can be used to
+    /// type the uppercase letter completing it.
+    pub fn feed(&mut self, keysym: Keysym) -> ComposeStatus {
+        if keysym.is_modifier() {
+            return if self.is_composing() {
+                ComposeStatus::Composing
+            } else {
+                ComposeStatus::Passthrough
+            };
+        }
+
+        self.pending.push(keysym);
+
+        if let Some(output) = self.table.sequences.get(&self.pending) {
+            let output = output.clone();
+            self.pending.clear();
+            return ComposeStatus::Composed(output);
+        }
+
+        if self.table.prefixes.contains(&self.pending) {
+            return ComposeStatus::Composing;
+        }
+
+        let started = self.pending.len() > 1;
+        self.pending.clear();
+
+        if started {
+            ComposeStatus::Cancelled
+        } else {
+            ComposeStatus::Passthrough
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the keysyms of the given names.
+    fn keysyms(names: &[&str]) -> Vec<Keysym> {
+        names
+            .iter()
+            .map(|name| Keysym::from_name(name).unwrap())
+            .collect()
+    }
+
+    /// Feeds the keysyms of the given names and returns the last status.
+    fn feed_all(compose: &mut Compose, names: &[&str]) -> ComposeStatus {
+        keysyms(names)
+            .into_iter()
+            .map(|keysym| compose.feed(keysym))
+            .last()
+            .unwrap()
+    }
+
+    #[test]
+    fn builtin_composes_dead_keys_and_multi_key() {
+        let mut compose = Compose::new(ComposeTable::builtin());
+
+        assert_eq!(compose.feed(Keysym::DEAD_ACUTE), ComposeStatus::Composing);
+        assert_eq!(
+            compose.feed(Keysym::from_char('e')),
+            ComposeStatus::Composed("é".into())
+        );
+        assert_eq!(
+            feed_all(&mut compose, &["Multi_key", "o", "c"]),
+            ComposeStatus::Composed("©".into())
+        );
+        assert!(!compose.is_composing());
+    }
+
+    #[test]
+    fn modifiers_do_not_interrupt_sequences() {
+        let mut compose = Compose::new(ComposeTable::builtin());
+
+        compose.feed(Keysym::DEAD_DIAERESIS);
+        assert_eq!(compose.feed(Keysym::SHIFT_L), ComposeStatus::Composing);
+        assert_eq!(
+            compose.feed(Keysym::from_char('U')),
+            ComposeStatus::Composed("Ü".into())
+        );
+        assert_eq!(compose.feed(Keysym::SHIFT_L), ComposeStatus::Passthrough);
+    }
+
+    #[test]
+    fn unknown_sequences_are_cancelled() {
+        let mut compose = Compose::new(ComposeTable::builtin());
+
+        assert_eq!(
+            compose.feed(Keysym::from_char('q')),
+            ComposeStatus::Passthrough
+        );
+        assert_eq!(
+            feed_all(&mut compose, &["dead_tilde", "q"]),
+            ComposeStatus::Cancelled
+        );
+        assert!(!compose.is_composing());
+    }
+
+    #[test]
+    fn parse_handles_comments_escapes_and_unknown_keysyms() {
+        let table = ComposeTable::parse(concat!(
+            "# comment\n",
+            "include \"%L\"\n",
+            "<Multi_key> <numbersign> <numbersign> : \"#\\\"\\x41\" # hashes\n",
+            "<Multi_key> <Cyrillic_ghe> : \"ґ\"\n",
+            "<Multi_key> <U20AC> : EuroSign\n",
+            "<dead_grave> <a> : agrave\n",
+        ));
+
+        assert_eq!(table.len(), 1);
+        assert_eq!(
+            table.sequences[&keysyms(&["Multi_key", "numbersign", "numbersign"])],
+            "#\"A"
+        );
+    }
+}
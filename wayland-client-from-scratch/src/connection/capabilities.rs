@@ -0,0 +1,204 @@
+use std::fmt::{self, Display};
+
+use crate::protocol::{WlObjectId, registry::event::global::Global};
+
+/// A summary of the protocols advertised by the compositor, returned by
+/// [`Connection::capabilities`](crate::connection::Connection::capabilities).
+///
+/// Every field holds the highest version advertised for the global the
+/// crate can bind, or `None` if the compositor does not advertise it, so
+/// applications can branch on the available protocols up front instead of
+/// attempting a bind and handling the error. Seats and outputs may be
+/// advertised more than once and are counted instead.
+///
+/// The summary is a snapshot: it only reflects the globals received so far,
+/// which are complete after the first
+/// [`Connection::roundtrip`](crate::connection::Connection::roundtrip).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Capabilities {
+    /// `wl_compositor`
+    pub compositor: Option<u32>,
+    /// `wl_subcompositor`
+    pub subcompositor: Option<u32>,
+    /// `wl_shm`
+    pub shm: Option<u32>,
+    /// `wl_data_device_manager`
+    pub data_device_manager: Option<u32>,
+    /// `wl_fixes`
+    pub fixes: Option<u32>,
+    /// `xdg_wm_base`
+    pub xdg_wm_base: Option<u32>,
+    /// `zxdg_decoration_manager_v1`
+    pub xdg_decoration: Option<u32>,
+    /// `zxdg_output_manager_v1`
+    pub xdg_output: Option<u32>,
+    /// `zwp_pointer_gestures_v1`
+    pub pointer_gestures: Option<u32>,
+    /// `zwp_pointer_constraints_v1`
+    pub pointer_constraints: Option<u32>,
+    /// `zwp_relative_pointer_manager_v1`
+    pub relative_pointer: Option<u32>,
+    /// `wp_pointer_warp_v1`
+    pub pointer_warp: Option<u32>,
+    /// `zwp_idle_inhibit_manager_v1`
+    pub idle_inhibit: Option<u32>,
+    /// `wp_fractional_scale_manager_v1`
+    pub fractional_scale: Option<u32>,
+    /// `wp_viewporter`
+    pub viewporter: Option<u32>,
+    /// `wp_presentation`
+    pub presentation: Option<u32>,
+    /// `wp_commit_timing_manager_v1`
+    pub commit_timing: Option<u32>,
+    /// `wp_fifo_manager_v1`
+    pub fifo: Option<u32>,
+    /// `wp_linux_drm_syncobj_manager_v1`
+    pub linux_drm_syncobj: Option<u32>,
+    /// `zwp_linux_explicit_synchronization_v1`
+    pub linux_explicit_sync: Option<u32>,
+    /// `wp_cursor_shape_manager_v1`
+    pub cursor_shape: Option<u32>,
+    /// `ext_output_image_capture_source_manager_v1`
+    pub output_image_capture_source: Option<u32>,
+    /// `ext_image_copy_capture_manager_v1`
+    pub image_copy_capture: Option<u32>,
+    /// `wp_color_manager_v1`
+    pub color_manager: Option<u32>,
+    /// The number of `wl_seat` globals.
+    pub seats: usize,
+    /// The number of `wl_output` globals.
+    pub outputs: usize,
+}
+
+impl Capabilities {
+    /// Summarizes a set of globals, e.g. the ones of
+    /// [`Connection::globals`](crate::connection::Connection::globals).
+    pub fn from_globals(globals: &[Global]) -> Self {
+        let mut caps = Capabilities::default();
+        for global in globals {
+            let version = global.version.get();
+            let slot = match global.interface.as_str() {
+                "wl_seat" => {
+                    caps.seats += 1;
+                    continue;
+                }
+                "wl_output" => {
+                    caps.outputs += 1;
+                    continue;
+                }
+                interface => match caps.slot(interface) {
+                    Some(slot) => slot,
+                    None => continue,
+                },
+            };
+            *slot = Some(slot.map_or(version, |v| v.max(version)));
+        }
+        caps
+    }
+
+    /// Returns the version advertised for `interface`, or `None` if it is
+    /// not advertised or is not a global.
+    ///
+    /// Seats and outputs are counted rather than versioned, so this returns
+    /// `None` for them; use [`seats`](Self::seats) and
+    /// [`outputs`](Self::outputs) instead.
+    pub fn version(&self, interface: WlObjectId) -> Option<u32> {
+        let name = interface.interface_name();
+        self.versions()
+            .into_iter()
+            .find(|(interface, _)| *interface == name)
+            .and_then(|(_, version)| version)
+    }
+
+    /// Returns `true` if the compositor advertises `interface` (seats and
+    /// outputs included).
+    pub fn has(&self, interface: WlObjectId) -> bool {
+        match interface {
+            WlObjectId::Seat => self.seats > 0,
+            WlObjectId::Output => self.outputs > 0,
+            _ => self.version(interface).is_some(),
+        }
+    }
+
+    /// Returns the versioned globals with their protocol names.
+    fn versions(&self) -> [(&'static str, Option<u32>); 24] {
+        [
+            ("wl_compositor", self.compositor),
+            ("wl_subcompositor", self.subcompositor),
+            ("wl_shm", self.shm),
+            ("wl_data_device_manager", self.data_device_manager),
+            ("wl_fixes", self.fixes),
+            ("xdg_wm_base", self.xdg_wm_base),
+            ("zxdg_decoration_manager_v1", self.xdg_decoration),
+            ("zxdg_output_manager_v1", self.xdg_output),
+            ("zwp_pointer_gestures_v1", self.pointer_gestures),
+            ("zwp_pointer_constraints_v1", self.pointer_constraints),
+            ("zwp_relative_pointer_manager_v1", self.relative_pointer),
+            ("wp_pointer_warp_v1", self.pointer_warp),
+            ("zwp_idle_inhibit_manager_v1", self.idle_inhibit),
+            ("wp_fractional_scale_manager_v1", self.fractional_scale),
+            ("wp_viewporter", self.viewporter),
+            ("wp_presentation", self.presentation),
+            ("wp_commit_timing_manager_v1", self.commit_timing),
+            ("wp_fifo_manager_v1", self.fifo),
+            ("wp_linux_drm_syncobj_manager_v1", self.linux_drm_syncobj),
+            (
+                "zwp_linux_explicit_synchronization_v1",
+                self.linux_explicit_sync,
+            ),
+            ("wp_cursor_shape_manager_v1", self.cursor_shape),
+            (
+                "ext_output_image_capture_source_manager_v1",
+                self.output_image_capture_source,
+            ),
+            ("ext_image_copy_capture_manager_v1", self.image_copy_capture),
+            ("wp_color_manager_v1", self.color_manager),
+        ]
+    }
+
+    /// Returns the field holding the version of the global `interface`.
+    fn slot(&mut self, interface: &str) -> Option<&mut Option<u32>> {
+        let slot = match interface {
+            "wl_compositor" => &mut self.compositor,
+            "wl_subcompositor" => &mut self.subcompositor,
+            "wl_shm" => &mut self.shm,
+            "wl_data_device_manager" => &mut self.data_device_manager,
+            "wl_fixes" => &mut self.fixes,
+            "xdg_wm_base" => &mut self.xdg_wm_base,
+            "zxdg_decoration_manager_v1" => &mut self.xdg_decoration,
+            "zxdg_output_manager_v1" => &mut self.xdg_output,
+            "zwp_pointer_gestures_v1" => &mut self.pointer_gestures,
+            "zwp_pointer_constraints_v1" => &mut self.pointer_constraints,
+            "zwp_relative_pointer_manager_v1" => &mut self.relative_pointer,
+            "wp_pointer_warp_v1" => &mut self.pointer_warp,
+            "zwp_idle_inhibit_manager_v1" => &mut self.idle_inhibit,
+            "wp_fractional_scale_manager_v1" => &mut self.fractional_scale,
+            "wp_viewporter" => &mut self.viewporter,
+            "wp_presentation" => &mut self.presentation,
+            "wp_commit_timing_manager_v1" => &mut self.commit_timing,
+            "wp_fifo_manager_v1" => &mut self.fifo,
+            "wp_linux_drm_syncobj_manager_v1" => &mut self.linux_drm_syncobj,
+            "zwp_linux_explicit_synchronization_v1" => &mut self.linux_explicit_sync,
+            "wp_cursor_shape_manager_v1" => &mut self.cursor_shape,
+            "ext_output_image_capture_source_manager_v1" => &mut self.output_image_capture_source,
+            "ext_image_copy_capture_manager_v1" => &mut self.image_copy_capture,
+            "wp_color_manager_v1" => &mut self.color_manager,
+            _ => return None,
+        };
+        Some(slot)
+    }
+}
+
+impl Display for Capabilities {
+    /// Lists the advertised protocols one per line, e.g. for a startup log.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "seats: {}, outputs: {}", self.seats, self.outputs)?;
+        for (interface, version) in self.versions() {
+            match version {
+                Some(version) => writeln!(f, "{interface}: v{version}")?,
+                None => writeln!(f, "{interface}: -")?,
+            }
+        }
+        Ok(())
+    }
+}
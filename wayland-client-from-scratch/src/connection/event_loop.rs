@@ -0,0 +1,388 @@
+use std::{
+    collections::VecDeque,
+    io,
+    os::fd::{AsFd, OwnedFd},
+    sync::{
+        Arc, Mutex, MutexGuard, PoisonError,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Instant,
+};
+
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::{
+        encoder::{Encode, Encoder},
+        message::WlMessageHeader,
+    },
+    sys,
+};
+
+/// A closure invoked once by the dispatching thread when its timer expires.
+pub(crate) type TimerCallback = Box<dyn FnOnce(&Connection) + Send>;
+
+/// A closure invoked once by the dispatching thread at the end of a dispatch.
+pub(crate) type DeferredCallback = Box<dyn FnOnce(&Connection) + Send>;
+
+/// Identifies a timer added with [`LoopHandle::add_timer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimerId(u64);
+
+/// A pending timer.
+struct Timer {
+    /// The ID returned when the timer was added.
+    id: TimerId,
+    /// The instant the timer expires at.
+    deadline: Instant,
+    /// The closure invoked when the timer expires.
+    callback: TimerCallback,
+}
+
+/// A request sent from another thread with [`LoopHandle::send_request`],
+/// waiting for the dispatching thread to queue it.
+pub(crate) struct RemoteRequest {
+    /// The header of the request.
+    pub(crate) header: WlMessageHeader,
+    /// The encoded request, header included.
+    pub(crate) bytes: Vec<u8>,
+    /// The descriptors passed along with the request.
+    pub(crate) fds: Vec<OwnedFd>,
+}
+
+/// The state of the event loop of a connection shared with its
+/// [`LoopHandle`]s, which may live on other threads.
+struct LoopShared {
+    /// The write end of the wake-up pipe.
+    wake_write: OwnedFd,
+    /// The pending timers, in no particular order.
+    timers: Mutex<Vec<Timer>>,
+    /// The ID of the next timer.
+    next_timer_id: AtomicU64,
+    /// The deferred callbacks, in the order they were deferred.
+    deferred: Mutex<VecDeque<DeferredCallback>>,
+    /// The requests sent from other threads, in the order they were sent.
+    requests: Mutex<VecDeque<RemoteRequest>>,
+}
+
+impl LoopShared {
+    /// Locks the pending timers.
+    ///
+    /// Callbacks never run under the lock, so a poisoned lock still holds
+    /// consistent data and is used as is.
+    fn timers(&self) -> MutexGuard<'_, Vec<Timer>> {
+        self.timers.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Locks the deferred callbacks, ignoring poisoning like [`LoopShared::timers`].
+    fn deferred(&self) -> MutexGuard<'_, VecDeque<DeferredCallback>> {
+        self.deferred.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Locks the requests sent from other threads, ignoring poisoning like
+    /// [`LoopShared::timers`].
+    fn requests(&self) -> MutexGuard<'_, VecDeque<RemoteRequest>> {
+        self.requests.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Writes to the wake-up pipe.
+    fn wake(&self) {
+        // A full pipe already holds a pending wake-up
+        loop {
+            match sys::write(self.wake_write.as_fd(), &[1]) {
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                _ => return,
+            }
+        }
+    }
+}
+
+/// The parts of the event loop of a connection that only the dispatching
+/// thread touches.
+pub(crate) struct EventLoop {
+    /// The read end of the wake-up pipe, polled along with the socket.
+    wake_read: Arc<OwnedFd>,
+    /// The state shared with the loop handles.
+    shared: Arc<LoopShared>,
+}
+
+impl EventLoop {
+    /// Creates the wake-up pipe of a new connection.
+    ///
+    /// Both ends are non-blocking: a wake-up never blocks the waking thread,
+    /// and draining the pipe never blocks the dispatching one.
+    pub(crate) fn new() -> io::Result<EventLoop> {
+        let (wake_read, wake_write) = sys::pipe()?;
+        sys::set_nonblocking(wake_read.as_fd())?;
+        sys::set_nonblocking(wake_write.as_fd())?;
+
+        Ok(EventLoop {
+            wake_read: Arc::new(wake_read),
+            shared: Arc::new(LoopShared {
+                wake_write,
+                timers: Mutex::new(Vec::new()),
+                next_timer_id: AtomicU64::new(0),
+                deferred: Mutex::new(VecDeque::new()),
+                requests: Mutex::new(VecDeque::new()),
+            }),
+        })
+    }
+
+    /// Returns a new handle to the loop.
+    pub(crate) fn handle(&self) -> LoopHandle {
+        LoopHandle {
+            shared: self.shared.clone(),
+        }
+    }
+
+    /// Returns the descriptor that becomes readable when the loop is woken
+    /// up, which the dispatching thread polls without borrowing the loop.
+    pub(crate) fn wake_fd(&self) -> Arc<OwnedFd> {
+        self.wake_read.clone()
+    }
+
+    /// Returns the earliest deadline of the pending timers.
+    pub(crate) fn next_deadline(&self) -> Option<Instant> {
+        self.shared
+            .timers()
+            .iter()
+            .map(|timer| timer.deadline)
+            .min()
+    }
+
+    /// Removes the timers expired at `now` and returns their callbacks,
+    /// earliest deadline first.
+    pub(crate) fn take_expired(&self, now: Instant) -> Vec<TimerCallback> {
+        let mut timers = self.shared.timers();
+
+        let (mut expired, pending): (Vec<Timer>, Vec<Timer>) = std::mem::take(&mut *timers)
+            .into_iter()
+            .partition(|timer| timer.deadline <= now);
+        *timers = pending;
+
+        expired.sort_by_key(|timer| timer.deadline);
+        expired.into_iter().map(|timer| timer.callback).collect()
+    }
+
+    /// Returns `true` if callbacks were deferred since the last dispatch.
+    pub(crate) fn has_deferred(&self) -> bool {
+        !self.shared.deferred().is_empty()
+    }
+
+    /// Removes the deferred callbacks and returns them in the order they
+    /// were deferred.
+    pub(crate) fn take_deferred(&self) -> VecDeque<DeferredCallback> {
+        std::mem::take(&mut *self.shared.deferred())
+    }
+
+    /// Removes the requests sent from other threads and returns them in the
+    /// order they were sent.
+    pub(crate) fn take_requests(&self) -> VecDeque<RemoteRequest> {
+        std::mem::take(&mut *self.shared.requests())
+    }
+
+    /// Drops the requests sent from other threads to `object_id`, which is
+    /// being assigned to a new object: they were all sent to the object
+    /// that held the ID before, possibly of another interface.
+    pub(crate) fn discard_requests_to(&self, object_id: u32) {
+        let mut requests = self.shared.requests();
+        if !requests.is_empty() {
+            requests.retain(|request| request.header.object_id != object_id);
+        }
+    }
+
+    /// Consumes the pending wake-ups, so the next poll blocks again.
+    pub(crate) fn clear_wake(&self) {
+        let mut buf = [0u8; 64];
+        loop {
+            match sys::read(self.wake_read.as_fd(), &mut buf) {
+                Ok(0) => return,
+                Ok(_) => continue,
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(_) => return,
+            }
+        }
+    }
+}
+
+/// A handle to the event loop of a [`Connection`](crate::connection::Connection)
+/// that can be sent to other threads.
+///
+/// Obtained with [`Connection::loop_handle`](crate::connection::Connection::loop_handle).
+/// Clones refer to the same loop. See the threading model of the
+/// [`Connection`](crate::connection::Connection#threads) for what other
+/// threads can do through it.
+#[derive(Clone)]
+pub struct LoopHandle {
+    /// The state shared with the event loop.
+    shared: Arc<LoopShared>,
+}
+
+impl LoopHandle {
+    /// Interrupts the [`Connection::dispatch`](crate::connection::Connection::dispatch)
+    /// blocking on the connection, or makes the next one return right away.
+    ///
+    /// Meant for worker threads that finished some background work, such as
+    /// rendering a frame, and need the dispatching thread to act on it.
+    /// Wake-ups sent before the loop got to run are coalesced into one.
+    pub fn wake(&self) {
+        self.shared.wake();
+    }
+
+    /// Invokes `callback` from the dispatching thread once `deadline` passed.
+    ///
+    /// The callback runs at the end of the first [`Connection::dispatch`]
+    /// after the deadline, which cuts its wait short for it. Timers with the
+    /// same deadline run in the order they were added. A callback wanting
+    /// to run again, such as one animating a cursor, adds a new timer.
+    ///
+    /// Adding a timer that expires before the others wakes up the loop, so
+    /// a dispatch blocking on another thread picks up the new deadline.
+    pub fn add_timer<F>(&self, deadline: Instant, callback: F) -> TimerId
+    where
+        F: FnOnce(&Connection) + Send + 'static,
+    {
+        let id = TimerId(self.shared.next_timer_id.fetch_add(1, Ordering::Relaxed));
+
+        let mut timers = self.shared.timers();
+        let is_earliest = timers.iter().all(|timer| deadline < timer.deadline);
+        timers.push(Timer {
+            id,
+            deadline,
+            callback: Box::new(callback),
+        });
+        drop(timers);
+
+        if is_earliest {
+            self.shared.wake();
+        }
+
+        id
+    }
+
+    /// Invokes `callback` from the dispatching thread once the current
+    /// [`Connection::dispatch`] handled its whole batch of events.
+    ///
+    /// Lets event handlers schedule work they must not do while other
+    /// handlers may still run, such as committing a surface once after all
+    /// the configure-related events of a batch. Callbacks run in the order
+    /// they were deferred, after the timers; those deferred by a deferred
+    /// callback run at the end of the next dispatch, which does not wait
+    /// for events. Deferring from another thread wakes up the loop.
+    pub fn defer<F>(&self, callback: F)
+    where
+        F: FnOnce(&Connection) + Send + 'static,
+    {
+        let mut deferred = self.shared.deferred();
+        let was_empty = deferred.is_empty();
+        deferred.push_back(Box::new(callback));
+        drop(deferred);
+
+        if was_empty {
+            self.shared.wake();
+        }
+    }
+
+    /// Sends a request from any thread: it is encoded right away on the
+    /// calling thread, and written by the dispatching thread on its next
+    /// [`Connection::flush`], which the loop is woken up for.
+    ///
+    /// Requests sent from one thread keep their order. Relative to the
+    /// requests of the dispatching thread, they go out after those queued
+    /// before the flush that picks them up. A request to an object that is
+    /// no longer alive by then is dropped, as the compositor would fail the
+    /// whole connection for it; so is a request to an object whose ID was
+    /// reassigned to a new object since, which may implement another
+    /// interface. A render thread may thus keep committing a surface the
+    /// dispatching thread just destroyed, as long as it stops using the ID
+    /// before it could learn about the object reusing it.
+    ///
+    /// `object_id` and `opcode` are given as with the request functions of
+    /// the [`protocol`](crate::protocol) modules, e.g.
+    /// `handle.send_request(surface_id, surface::request::Opcode::Commit, &())`.
+    ///
+    /// # Errors
+    /// Returns an error if the request is too long to be encoded.
+    pub fn send_request(
+        &self,
+        object_id: u32,
+        opcode: impl Into<u16>,
+        args: &impl Encode,
+    ) -> Result<()> {
+        self.send_request_with_fds(object_id, opcode, args, Vec::new())
+    }
+
+    /// Like [`LoopHandle::send_request`], for a request carrying file
+    /// descriptors, which are closed once they have been sent.
+    ///
+    /// # Errors
+    /// Returns an error if the request is too long to be encoded.
+    pub fn send_request_with_fds(
+        &self,
+        object_id: u32,
+        opcode: impl Into<u16>,
+        args: &impl Encode,
+        fds: Vec<OwnedFd>,
+    ) -> Result<()> {
+        let mut encoder = Encoder::new(object_id, opcode.into());
+        args.encode(&mut encoder);
+        let header = encoder.header();
+        let bytes = encoder.finish()?.to_vec();
+
+        let mut requests = self.shared.requests();
+        let was_empty = requests.is_empty();
+        requests.push_back(RemoteRequest { header, bytes, fds });
+        drop(requests);
+
+        if was_empty {
+            self.shared.wake();
+        }
+
+        Ok(())
+    }
+
+    /// Cancels a timer that has not expired yet.
+    ///
+    /// # Returns
+    /// `true` if the timer was pending, `false` if it already ran or was cancelled.
+    pub fn cancel_timer(&self, id: TimerId) -> bool {
+        let mut timers = self.shared.timers();
+        let len = timers.len();
+        timers.retain(|timer| timer.id != id);
+        timers.len() != len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wake_is_coalesced_until_cleared() {
+        let event_loop = EventLoop::new().unwrap();
+        let handle = event_loop.handle();
+
+        std::thread::spawn(move || {
+            for _ in 0..100_000 {
+                handle.wake();
+            }
+        })
+        .join()
+        .unwrap();
+
+        let ready = sys::poll(
+            &[(event_loop.wake_fd().as_fd(), sys::Interest::Readable)],
+            None,
+        )
+        .unwrap();
+        assert_eq!(ready, [true]);
+
+        event_loop.clear_wake();
+        let ready = sys::poll(
+            &[(event_loop.wake_fd().as_fd(), sys::Interest::Readable)],
+            Some(std::time::Duration::ZERO),
+        )
+        .unwrap();
+        assert_eq!(ready, [false]);
+    }
+}
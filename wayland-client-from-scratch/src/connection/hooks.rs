@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use crate::{
+    connection::Connection,
+    protocol::{WlObjectId, message::WlMessage},
+    sync::Lock,
+};
+
+/// A closure run before an event is dispatched, see
+/// [`Connection::add_pre_dispatch_hook`].
+pub(crate) type PreDispatchHook =
+    Box<dyn FnMut(&Connection, WlObjectId, &WlMessage) -> HookAction + Send>;
+
+/// A closure run after an event of an interface was dispatched, see
+/// [`Connection::add_post_dispatch_hook`].
+pub(crate) type PostDispatchHook = Box<dyn FnMut(&Connection, &WlMessage) + Send>;
+
+/// What a pre-dispatch hook decides for the event it saw.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookAction {
+    /// The event goes on to the next hook, then to its handler.
+    Continue,
+    /// The event is dropped: later hooks, its handler and the post-dispatch
+    /// hooks do not see it.
+    Veto,
+}
+
+/// The ID of a hook, to remove it with
+/// [`Connection::remove_hook`](crate::connection::Connection::remove_hook).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HookId(u64);
+
+/// The hooks of a connection, in the order they were added.
+#[derive(Default)]
+pub(crate) struct Hooks {
+    /// The ID of the next hook.
+    next_id: u64,
+    /// The hooks run before every event.
+    pre: Vec<(HookId, Arc<Lock<PreDispatchHook>>)>,
+    /// The hooks run after the events of one interface.
+    post: Vec<(HookId, WlObjectId, Arc<Lock<PostDispatchHook>>)>,
+}
+
+impl Hooks {
+    /// Adds a hook run before every event.
+    pub(crate) fn add_pre(&mut self, hook: PreDispatchHook) -> HookId {
+        let id = self.allocate();
+        self.pre.push((id, Arc::new(Lock::new(hook))));
+        id
+    }
+
+    /// Adds a hook run after the events of `interface`.
+    pub(crate) fn add_post(&mut self, interface: WlObjectId, hook: PostDispatchHook) -> HookId {
+        let id = self.allocate();
+        self.post.push((id, interface, Arc::new(Lock::new(hook))));
+        id
+    }
+
+    /// Removes the hook `id`, returning it so it is dropped by the caller.
+    pub(crate) fn remove(&mut self, id: HookId) -> Option<Arc<dyn std::any::Any + Send + Sync>> {
+        if let Some(index) = self.pre.iter().position(|(i, _)| *i == id) {
+            return Some(self.pre.remove(index).1);
+        }
+        let index = self.post.iter().position(|(i, _, _)| *i == id)?;
+        Some(self.post.remove(index).2)
+    }
+
+    /// Returns `true` if the hook `id` was not removed.
+    pub(crate) fn contains(&self, id: HookId) -> bool {
+        self.pre.iter().any(|(i, _)| *i == id) || self.post.iter().any(|(i, _, _)| *i == id)
+    }
+
+    /// Returns the hooks run before every event.
+    pub(crate) fn pre(&self) -> Vec<(HookId, Arc<Lock<PreDispatchHook>>)> {
+        self.pre.clone()
+    }
+
+    /// Returns the hooks run after the events of `interface`.
+    pub(crate) fn post(&self, interface: WlObjectId) -> Vec<(HookId, Arc<Lock<PostDispatchHook>>)> {
+        self.post
+            .iter()
+            .filter(|(_, hooked, _)| *hooked == interface)
+            .map(|(id, _, hook)| (*id, hook.clone()))
+            .collect()
+    }
+
+    /// Returns a new hook ID.
+    fn allocate(&mut self) -> HookId {
+        let id = HookId(self.next_id);
+        self.next_id += 1;
+        id
+    }
+}
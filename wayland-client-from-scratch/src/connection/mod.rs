@@ -0,0 +1,3313 @@
+pub mod capabilities;
+pub mod event_loop;
+pub mod hooks;
+pub mod object_map;
+pub mod record;
+pub mod stats;
+
+use std::{
+    any::{Any, type_name},
+    collections::{BTreeMap, HashMap, VecDeque},
+    fmt::Display,
+    io::{self, Write},
+    os::{
+        fd::{AsFd, BorrowedFd, OwnedFd},
+        unix::net::UnixStream,
+    },
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    error::{Result, anyhow},
+    outputs::{self, Monitor},
+    protocol::{
+        WlObjectId,
+        arena::EventArena,
+        buffer, callback, color_management, data_device, data_offer, data_source, display,
+        encoder::{Encode, Encoder},
+        fractional_scale, image_copy_capture, keyboard, layer_shell, linux_explicit_sync,
+        message::{WL_MESSAGE_HEADER_LEN, WL_MESSAGE_MAX_LEN, WlMessage, WlMessageHeader},
+        output, pointer, pointer_constraints, pointer_gestures, presentation,
+        registry::{self, Registry, event::global::Global},
+        relative_pointer, seat, session_lock, shm, surface, touch,
+        types::{Utf8Mode, WlNewId, WlString, WlUInt, WlUntypedNewId},
+        xdg_decoration, xdg_output, xdg_popup, xdg_surface, xdg_toplevel, xdg_wm_base,
+    },
+    sync::{Lock, LockCell},
+    sys::{self, Interest},
+};
+
+use capabilities::Capabilities;
+use event_loop::{EventLoop, LoopHandle};
+use hooks::{HookAction, HookId, Hooks};
+use object_map::{ObjectDump, ObjectEntry, ObjectInfo, ObjectMap};
+use record::{Direction, RecordFormat, Recorder, Recording, Replayer};
+use stats::ConnectionStats;
+
+/// The size of the chunk read from the socket on every dispatch.
+const READ_CHUNK_LEN: usize = 4096;
+
+/// A closure invoked once when a `wl_callback` fires, receiving the callback data.
+pub(crate) type CallbackFn = Box<dyn FnOnce(&Connection, u32) + Send>;
+
+/// A closure receiving the events of a single object, in a form suited for applications.
+pub(crate) type Listener<E> = Box<dyn FnMut(&Connection, E) + Send>;
+
+/// A closure notified of the globals announced and withdrawn by the registry.
+pub(crate) type GlobalWatcher = Box<dyn FnMut(&Connection, GlobalChange<'_>) + Send>;
+
+/// A change of the globals advertised by the registry, delivered to the
+/// closures registered with [`Connection::watch_globals`].
+#[derive(Clone, Copy)]
+pub(crate) enum GlobalChange<'a> {
+    /// The global was announced by `wl_registry.global`.
+    Added(&'a Global),
+    /// The global was withdrawn by `wl_registry.global_remove`.
+    Removed(&'a Global),
+}
+
+/// A closure invoked when a watched descriptor is ready (or hung up).
+///
+/// Returns `true` to keep watching the descriptor, which is closed otherwise.
+pub(crate) type FdCallback = Box<dyn FnMut(&Connection, BorrowedFd<'_>) -> bool + Send>;
+
+/// A closure rebuilding the objects of the application on the connection
+/// that replaced a lost one, see [`Connection::set_reconnect_handler`].
+pub(crate) type ReconnectFn = Box<dyn FnMut(&Connection) -> Result<()> + Send>;
+
+/// A closure told about the events dropped by
+/// [`UnknownObjectPolicy::LogAndSkip`], see
+/// [`Connection::set_unknown_object_logger`].
+pub(crate) type UnknownObjectLogger = Box<dyn FnMut(&Connection, &UnknownObject) + Send>;
+
+/// The error returned when the compositor closed the connection, usually
+/// because it exited or restarted.
+///
+/// Recovered with `error.is::<Disconnected>()`. Unless a reconnect handler
+/// is set with [`Connection::set_reconnect_handler`], the connection is
+/// unusable from then on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Disconnected;
+
+impl Display for Disconnected {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Compositor closed the connection")
+    }
+}
+
+impl std::error::Error for Disconnected {}
+
+/// The error returned by [`Connection::roundtrip_timeout`] when the
+/// compositor did not answer in time.
+///
+/// It is wrapped in an [`Error`](crate::error::Error), so callers tell it apart from
+/// other failures with `error.is::<Timeout>()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timeout {
+    /// The time waited for the compositor.
+    pub duration: Duration,
+}
+
+impl Display for Timeout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Compositor did not answer the roundtrip within {:?}",
+            self.duration
+        )
+    }
+}
+
+impl std::error::Error for Timeout {}
+
+/// The error returned by [`Connection::dispatch`] for an event that the
+/// interface of its target object does not define.
+///
+/// Like [`Timeout`], it is wrapped in an [`Error`](crate::error::Error) and recovered with
+/// `error.downcast_ref::<InvalidEvent>()`. Whether such events fail the
+/// dispatch is set with [`Connection::set_invalid_event_policy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidEvent {
+    /// The interface has no event with this opcode.
+    UnknownOpcode {
+        /// The ID of the target object.
+        object_id: u32,
+        /// The interface of the target object.
+        interface: WlObjectId,
+        /// The opcode of the event.
+        opcode: u16,
+    },
+    /// The event was introduced in a later version of the interface than
+    /// the one the object was created with.
+    VersionMismatch {
+        /// The ID of the target object.
+        object_id: u32,
+        /// The interface of the target object.
+        interface: WlObjectId,
+        /// The opcode of the event.
+        opcode: u16,
+        /// The interface version the event was introduced in.
+        since: u32,
+        /// The version of the object.
+        version: u32,
+    },
+}
+
+impl Display for InvalidEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvalidEvent::UnknownOpcode {
+                object_id,
+                interface,
+                opcode,
+            } => write!(
+                f,
+                "Unknown {} event opcode {} (object {})",
+                interface.interface_name(),
+                opcode,
+                object_id
+            ),
+            InvalidEvent::VersionMismatch {
+                object_id,
+                interface,
+                opcode,
+                since,
+                version,
+            } => write!(
+                f,
+                "{} event opcode {} needs version {}, object {} has version {}",
+                interface.interface_name(),
+                opcode,
+                since,
+                object_id,
+                version
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InvalidEvent {}
+
+/// What [`Connection::dispatch`] does with an [`InvalidEvent`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InvalidEventPolicy {
+    /// Fail the dispatch with the [`InvalidEvent`] error.
+    #[default]
+    Error,
+    /// Drop the event and keep dispatching.
+    ///
+    /// A dropped event cannot claim the file descriptors it may carry, so
+    /// the descriptors of later events may be mismatched; only use this
+    /// with compositors known to send no such events.
+    Skip,
+}
+
+/// The error returned by [`Connection::dispatch`] for an event whose target
+/// object ID is not registered, or was already deleted with
+/// `wl_display.delete_id`.
+///
+/// Recovered with `error.downcast_ref::<UnknownObject>()`. Whether such
+/// events fail the dispatch is set with [`Connection::set_unknown_object_policy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownObject {
+    /// The ID the event targets.
+    pub object_id: u32,
+    /// The opcode of the event.
+    pub opcode: u16,
+}
+
+impl Display for UnknownObject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Event opcode {} for unknown object {}",
+            self.opcode, self.object_id
+        )
+    }
+}
+
+impl std::error::Error for UnknownObject {}
+
+/// What [`Connection::dispatch`] does with an event for an [`UnknownObject`].
+///
+/// Like with [`InvalidEventPolicy::Skip`], an event that is not dispatched
+/// right away cannot claim the file descriptors it may carry, so the
+/// descriptors of later events may be mismatched.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnknownObjectPolicy {
+    /// Fail the dispatch with the [`UnknownObject`] error.
+    #[default]
+    Error,
+    /// Pass the [`UnknownObject`] error to the logger set with
+    /// [`Connection::set_unknown_object_logger`], if any, drop the event and
+    /// keep dispatching.
+    LogAndSkip,
+    /// Hold the event back until an object with its ID is registered, then
+    /// dispatch it before the later events of that object.
+    ///
+    /// At most [`MAX_QUEUED_EVENTS`] events are held back; past that the
+    /// dispatch fails with the [`UnknownObject`] error.
+    Queue,
+}
+
+/// The number of events [`UnknownObjectPolicy::Queue`] holds back at most.
+pub const MAX_QUEUED_EVENTS: usize = 256;
+
+/// How long [`SendMode::Blocking`] waits for the compositor to make room
+/// in the socket buffer before giving up with [`SendBufferFull`].
+pub const SEND_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The default number of bytes [`SendMode::NonBlocking`] keeps queued.
+pub const DEFAULT_SEND_BUFFER_LIMIT: usize = 1 << 20;
+
+/// The error returned by [`Connection::flush`] when the compositor does not
+/// read requests fast enough for the socket buffer to take them.
+///
+/// Recovered with `error.downcast_ref::<SendBufferFull>()`. The requests
+/// that could not be written stay queued for the next flush.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SendBufferFull {
+    /// The number of bytes of requests still queued.
+    pub queued: usize,
+}
+
+impl Display for SendBufferFull {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Socket buffer full, {} bytes of requests are still queued",
+            self.queued
+        )
+    }
+}
+
+impl std::error::Error for SendBufferFull {}
+
+/// What [`Connection::flush`] does when the socket buffer is full.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SendMode {
+    /// Wait for the compositor to read, for up to [`SEND_TIMEOUT`] without
+    /// progress, then fail with [`SendBufferFull`].
+    #[default]
+    Blocking,
+    /// Keep the unwritten requests queued and return, failing with
+    /// [`SendBufferFull`] only once more than the limit set with
+    /// [`Connection::set_send_buffer_limit`] is queued.
+    ///
+    /// [`Connection::dispatch`] writes the queued requests as the socket
+    /// drains.
+    NonBlocking,
+}
+
+/// The identity of the process at the other end of a connection, returned
+/// by [`Connection::peer_credentials`].
+///
+/// The kernel records the credentials when the socket is connected, so
+/// the compositor cannot forge them; they stay those of the process that
+/// accepted the connection even if it later changes its identity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeerCredentials {
+    /// The process ID of the compositor.
+    pub pid: i32,
+    /// The user ID the compositor runs as.
+    pub uid: u32,
+    /// The group ID the compositor runs as.
+    pub gid: u32,
+}
+
+impl Display for PeerCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "pid {}, uid {}, gid {}", self.pid, self.uid, self.gid)
+    }
+}
+
+/// The mutable state shared by every handle to a connection.
+struct ConnectionState {
+    /// The Unix socket connected to the compositor, shared with the
+    /// dispatching thread while it polls.
+    stream: Arc<UnixStream>,
+    /// All live protocol objects and the ID allocator.
+    objects: ObjectMap,
+    /// The ID of the registry created when the connection was established,
+    /// `None` once it was destroyed with `wl_fixes.destroy_registry`.
+    registry_id: Option<u32>,
+    /// Globals currently advertised by the registry, keyed by their numeric name.
+    globals: BTreeMap<u32, Global>,
+    /// The closures notified of global changes, with the ID they were
+    /// registered under.
+    global_watchers: Vec<(u64, Arc<Lock<GlobalWatcher>>)>,
+    /// The ID of the next global watcher.
+    next_global_watcher: u64,
+    /// The closures run around the dispatch of every event.
+    hooks: Hooks,
+    /// Pending closures for `wl_callback` objects, keyed by callback object ID.
+    callbacks: HashMap<u32, CallbackFn>,
+    /// Client-side state of live `wl_buffer` objects.
+    buffers: HashMap<u32, BufferState>,
+    /// Per-object state owned by the proxies, keyed by object ID.
+    object_data: HashMap<u32, Arc<dyn Any + Send + Sync>>,
+    /// Application data attached with [`Connection::set_user_data`], keyed
+    /// by object ID.
+    user_data: HashMap<u32, UserData>,
+    /// Application listeners, each a boxed [`Listener`], keyed by object ID.
+    listeners: HashMap<u32, Box<dyn Any + Send>>,
+    /// Serialized requests waiting to be written to the socket.
+    out_buf: Vec<u8>,
+    /// File descriptors to pass along with the queued requests, in order.
+    out_fds: VecDeque<OutFd>,
+    /// What flushing does when the socket buffer is full.
+    send_mode: SendMode,
+    /// The number of bytes [`SendMode::NonBlocking`] keeps queued.
+    send_buffer_limit: usize,
+    /// The largest event accepted in bytes, header included.
+    max_message_len: usize,
+    /// Received bytes that do not form a complete message yet.
+    in_buf: Vec<u8>,
+    /// The chunk the arguments of the last batch of events were copied to.
+    arena: EventArena,
+    /// Received file descriptors not yet claimed by an event handler.
+    in_fds: VecDeque<OwnedFd>,
+    /// How the strings of received events are decoded.
+    utf8_mode: Utf8Mode,
+    /// What dispatching does with events the target interface does not define.
+    invalid_event_policy: InvalidEventPolicy,
+    /// What dispatching does with events for unregistered object IDs.
+    unknown_object_policy: UnknownObjectPolicy,
+    /// Events held back by [`UnknownObjectPolicy::Queue`], in arrival order.
+    queued_events: VecDeque<WlMessage>,
+    /// The closure told about events dropped by
+    /// [`UnknownObjectPolicy::LogAndSkip`].
+    unknown_object_logger: Option<Arc<Lock<UnknownObjectLogger>>>,
+    /// Descriptors polled along with the socket, such as clipboard pipes.
+    fd_watches: Vec<FdWatch>,
+    /// The wake-up pipe and the state shared with the loop handles.
+    event_loop: EventLoop,
+    /// The recording the traffic is written to, if any.
+    recorder: Option<Recorder>,
+    /// Received descriptors not yet counted on a recorded event.
+    unrecorded_fds: usize,
+    /// The recorded compositor answering the requests instead of the
+    /// socket, for a connection created with [`Connection::replay`].
+    replayer: Option<Replayer>,
+    /// The traffic counters returned by [`Connection::stats`].
+    stats: ConnectionStats,
+    /// The socket dialed by [`Connection::connect`], dialed again to
+    /// reconnect. `None` for connections wrapping a given socket.
+    socket_path: Option<PathBuf>,
+    /// The closure rebuilding the objects after a reconnect, if reconnecting
+    /// is enabled.
+    reconnect: Option<ReconnectFn>,
+    /// The connection that replaced this one after the compositor closed it.
+    successor: Option<Connection>,
+}
+
+/// Application data attached to an object with [`Connection::set_user_data`].
+struct UserData {
+    /// The data itself.
+    value: Arc<dyn Any + Send + Sync>,
+    /// The name of its type, for [`Connection::dump_objects`].
+    type_name: &'static str,
+}
+
+/// A file descriptor queued along with the request carrying it.
+struct OutFd {
+    /// The offset of the request in the outgoing buffer.
+    offset: usize,
+    /// The descriptor, closed once it has been sent.
+    fd: OwnedFd,
+}
+
+/// A descriptor polled along with the socket, with the callback invoked
+/// when it becomes ready.
+struct FdWatch {
+    /// The watched descriptor, closed when the watch ends.
+    fd: OwnedFd,
+    /// Whether the descriptor is watched for reading or writing.
+    interest: Interest,
+    /// The callback deciding whether the watch goes on.
+    callback: FdCallback,
+}
+
+impl ConnectionState {
+    /// Writes all queued requests to the socket, see [`Connection::flush`].
+    fn flush(&mut self) -> Result<()> {
+        if let Some(replayer) = &mut self.replayer {
+            self.out_fds.clear();
+            return replayer.receive(&mut self.out_buf);
+        }
+
+        let ConnectionState {
+            stream,
+            out_buf,
+            out_fds,
+            send_mode,
+            send_buffer_limit,
+            ..
+        } = self;
+
+        // The wait of blocking mode is bounded since the last progress
+        let mut give_up_at = Instant::now() + SEND_TIMEOUT;
+
+        while !out_buf.is_empty() {
+            // At most `MAX_FDS_OUT` descriptors go out at once, and the write
+            // stops before the first request whose descriptors must wait
+            let batch_len = out_fds.len().min(sys::MAX_FDS_OUT);
+            let chunk_len = out_fds
+                .get(batch_len)
+                .map_or(out_buf.len(), |next| next.offset);
+            if chunk_len == 0 {
+                return Err(anyhow!(
+                    "Request carries more than {} file descriptors",
+                    sys::MAX_FDS_OUT
+                ));
+            }
+            let fds: Vec<BorrowedFd<'_>> = out_fds
+                .iter()
+                .take(batch_len)
+                .map(|out_fd| out_fd.fd.as_fd())
+                .collect();
+
+            match sys::send_with_fds(stream.as_fd(), &out_buf[..chunk_len], &fds) {
+                Ok(0) => return Err(Disconnected.into()),
+                Ok(written) => {
+                    out_buf.drain(..written);
+                    // The descriptors went out with the first byte written.
+                    out_fds.drain(..batch_len);
+                    for out_fd in out_fds.iter_mut() {
+                        out_fd.offset -= written;
+                    }
+                    give_up_at = Instant::now() + SEND_TIMEOUT;
+                }
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(error) if is_disconnect(&error) => return Err(Disconnected.into()),
+                Err(error) if error.kind() == io::ErrorKind::WouldBlock => {
+                    let remaining = give_up_at.saturating_duration_since(Instant::now());
+                    match send_mode {
+                        SendMode::NonBlocking => break,
+                        SendMode::Blocking if !remaining.is_zero() => {
+                            sys::poll(&[(stream.as_fd(), Interest::Writable)], Some(remaining))?;
+                        }
+                        SendMode::Blocking => {
+                            return Err(SendBufferFull {
+                                queued: out_buf.len(),
+                            }
+                            .into());
+                        }
+                    }
+                }
+                Err(error) => return Err(error.into()),
+            }
+        }
+
+        if out_buf.len() > *send_buffer_limit {
+            return Err(SendBufferFull {
+                queued: out_buf.len(),
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for ConnectionState {
+    /// Sends the requests still queued, such as the destructors sent by the
+    /// proxies dropped last, before the socket is closed.
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+/// Client-side bookkeeping for a live `wl_buffer`.
+struct BufferState {
+    /// The busy flag shared with the `Buffer` handle, cleared on release.
+    busy: Arc<LockCell<bool>>,
+    /// Set when the handle was dropped while the compositor still used the
+    /// buffer, so `wl_buffer.destroy` is sent on release instead.
+    destroy_on_release: bool,
+}
+
+/// A connection to a Wayland compositor.
+///
+/// The connection owns the socket, the object map and the queue of outgoing
+/// requests. It is a cheap handle: clones refer to the same underlying
+/// connection, which lets protocol objects keep a reference to the connection
+/// they were created on.
+///
+/// Requests are buffered until [`Connection::flush`] is called, either
+/// explicitly or implicitly by [`Connection::dispatch`] and [`Connection::roundtrip`].
+///
+/// # Threads
+/// Like libwayland, a connection and its proxies are `Send` and `Sync`:
+/// requests can be sent from any thread while another one dispatches. The
+/// locking model is:
+///
+/// - the state of the connection, i.e. the object map, the listeners and
+///   the outgoing buffer, is behind a single lock, held for as long as a
+///   request is queued, an event is decoded or [`Connection::flush`] writes
+///   to the socket, but never while waiting for events nor while a handler
+///   runs;
+/// - proxies keep their own state behind locks of their own, held just as
+///   briefly;
+/// - one thread at a time dispatches. Others calling
+///   [`Connection::dispatch`] wait for it to return, while those calling
+///   [`Connection::roundtrip`] return as soon as their sync callback ran,
+///   whichever thread dispatched it. A roundtrip waiting for another
+///   thread only checks its timeout once that thread returns.
+///
+/// The locks are re-entrant on the thread holding them, so handlers may
+/// send requests, create objects and even dispatch, as on a single thread.
+///
+/// Handlers run on the dispatching thread, hence the `Send` bound on the
+/// closures given to the crate. Requests sent from other threads are only
+/// written by the next flush: a thread blocked in [`Connection::dispatch`]
+/// does not see them otherwise, see [`LoopHandle::wake`].
+///
+/// A [`LoopHandle`], obtained with [`Connection::loop_handle`], gives other
+/// threads access to the event loop itself:
+///
+/// - run code on the dispatching thread with [`LoopHandle::defer`] and
+///   [`LoopHandle::add_timer`];
+/// - queue requests to existing objects with [`LoopHandle::send_request`],
+///   written by the dispatching thread;
+/// - interrupt a blocking dispatch with [`LoopHandle::wake`].
+///
+/// Each of these takes a lock of its own for as long as it appends to a
+/// queue, which the dispatching thread drains.
+#[derive(Clone)]
+pub struct Connection {
+    state: Arc<Lock<ConnectionState>>,
+    /// Held by the thread dispatching the connection, so that events are
+    /// read and handled by one thread at a time.
+    dispatching: Arc<Lock<()>>,
+}
+
+impl Connection {
+    /// Connects to the compositor named by `$WAYLAND_DISPLAY`, see
+    /// [`Connection::connect_to_display`].
+    ///
+    /// # Errors
+    /// Returns an error if `$WAYLAND_DISPLAY` is unset or the socket cannot
+    /// be connected.
+    pub fn connect() -> Result<Connection> {
+        let wayland_display = std::env::var("WAYLAND_DISPLAY")?;
+
+        Connection::connect_to_display(&wayland_display)
+    }
+
+    /// Connects to the display `name`, such as `wayland-1`, whose socket
+    /// is in `$XDG_RUNTIME_DIR` unless `name` is an absolute path.
+    ///
+    /// Connections share no state, so an application can be connected to
+    /// several displays at once, e.g. a nested compositor talking to its
+    /// host; each connection is dispatched on its own.
+    ///
+    /// # Errors
+    /// Returns an error if `name` is relative and `$XDG_RUNTIME_DIR` is
+    /// unset, or if the socket cannot be connected.
+    pub fn connect_to_display(name: &str) -> Result<Connection> {
+        if Path::new(name).is_absolute() {
+            return Connection::connect_to(name);
+        }
+
+        let xdg_runtime_dir = std::env::var("XDG_RUNTIME_DIR")?;
+        Connection::connect_to(Path::new(&xdg_runtime_dir).join(name))
+    }
+
+    /// Connects to the compositor listening on the socket at `path`.
+    ///
+    /// Unlike connections created with [`Connection::from_stream`], the
+    /// connection knows its socket, so it can be re-established with
+    /// [`Connection::reconnect`].
+    ///
+    /// # Errors
+    /// Returns an error if the socket cannot be connected.
+    pub fn connect_to(path: impl AsRef<Path>) -> Result<Connection> {
+        let stream = UnixStream::connect(path.as_ref())?;
+
+        let connection = Connection::from_stream(stream)?;
+        connection.state.borrow_mut().socket_path = Some(path.as_ref().to_path_buf());
+
+        Ok(connection)
+    }
+
+    /// Enables reconnecting: when [`Connection::dispatch`] finds that the
+    /// compositor closed the connection, it calls
+    /// [`Connection::reconnect`] instead of failing with [`Disconnected`],
+    /// and `handler` gets the new connection to rebuild the objects of the
+    /// application on, once the globals of the new compositor are known.
+    ///
+    /// Meant for long-running clients such as panels and clipboard managers
+    /// that should survive a restart of the compositor. Dispatching through
+    /// the old connection keeps working, as it is forwarded to the new one,
+    /// but everything else, binding globals included, has to use the
+    /// connection given to `handler`.
+    ///
+    /// Only connections created with [`Connection::connect`] or
+    /// [`Connection::connect_to`] can reconnect.
+    pub fn set_reconnect_handler<F>(&self, handler: F)
+    where
+        F: FnMut(&Connection) -> Result<()> + Send + 'static,
+    {
+        // The replaced handler is dropped after the connection is released
+        let _replaced = self.state.borrow_mut().reconnect.replace(Box::new(handler));
+    }
+
+    /// Disables reconnecting, see [`Connection::set_reconnect_handler`].
+    pub fn clear_reconnect_handler(&self) {
+        let _removed = self.state.borrow_mut().reconnect.take();
+    }
+
+    /// Dials the socket of the connection again, discovers the globals of
+    /// the compositor listening there and calls the reconnect handler, if
+    /// any, with the new connection.
+    ///
+    /// The new connection takes over the settings, the statistics, the
+    /// watched descriptors and the event loop of this one, so timers and
+    /// [`LoopHandle`]s keep working. The objects do not survive: the
+    /// compositor forgot them, so proxies of this connection are detached
+    /// and their requests, including those sent when they are dropped, are
+    /// discarded. A recording in progress ends here.
+    ///
+    /// # Returns
+    /// The new connection; the same one if this connection was already
+    /// replaced.
+    ///
+    /// # Errors
+    /// Returns an error if the connection was not created by
+    /// [`Connection::connect`] or [`Connection::connect_to`], if the socket
+    /// cannot be connected, or if the reconnect handler fails.
+    pub fn reconnect(&self) -> Result<Connection> {
+        if let Some(successor) = self.successor() {
+            return Ok(successor);
+        }
+
+        let (socket_path, recorder) = {
+            let mut state = self.state.borrow_mut();
+            let socket_path = state
+                .socket_path
+                .clone()
+                .ok_or_else(|| anyhow!("Cannot reconnect a connection created from a socket"))?;
+            (socket_path, state.recorder.take())
+        };
+        if let Some(recorder) = recorder {
+            recorder.finish()?;
+        }
+
+        let connection = Connection::connect_to(&socket_path)?;
+        {
+            let mut old = self.state.borrow_mut();
+            let old = &mut *old;
+            let mut state = connection.state.borrow_mut();
+            state.send_mode = old.send_mode;
+            state.send_buffer_limit = old.send_buffer_limit;
+            state.max_message_len = old.max_message_len;
+            state.utf8_mode = old.utf8_mode;
+            state.invalid_event_policy = old.invalid_event_policy;
+            state.unknown_object_policy = old.unknown_object_policy;
+            state.unknown_object_logger = old.unknown_object_logger.take();
+            state.stats = std::mem::take(&mut old.stats);
+            state.fd_watches = std::mem::take(&mut old.fd_watches);
+            state.hooks = std::mem::take(&mut old.hooks);
+            std::mem::swap(&mut state.event_loop, &mut old.event_loop);
+            // Requests sent from other threads name objects of the old compositor
+            drop(state.event_loop.take_requests());
+        }
+        connection.roundtrip()?;
+
+        // From here on requests to this connection are discarded, so the
+        // proxies dropped along with the state below cannot reach the
+        // new compositor
+        let (handler, _released) = {
+            let mut old = self.state.borrow_mut();
+            old.successor = Some(connection.clone());
+            old.out_buf.clear();
+            old.out_fds.clear();
+            (
+                old.reconnect.take(),
+                (
+                    std::mem::take(&mut old.callbacks),
+                    std::mem::take(&mut old.buffers),
+                    std::mem::take(&mut old.object_data),
+                    std::mem::take(&mut old.user_data),
+                    std::mem::take(&mut old.listeners),
+                    std::mem::take(&mut old.queued_events),
+                    std::mem::take(&mut old.in_fds),
+                ),
+            )
+        };
+
+        if let Some(mut handler) = handler {
+            let result = handler(&connection);
+            // The handler may have installed another one meanwhile
+            connection
+                .state
+                .borrow_mut()
+                .reconnect
+                .get_or_insert(handler);
+            result?;
+        }
+
+        Ok(connection)
+    }
+
+    /// Returns the connection that replaced this one, following the chain
+    /// of reconnects to the latest.
+    fn successor(&self) -> Option<Connection> {
+        let successor = self.state.borrow().successor.clone()?;
+        Some(successor.successor().unwrap_or(successor))
+    }
+
+    /// Wraps an already connected socket and creates the registry.
+    ///
+    /// The socket is switched to non-blocking mode and gets the
+    /// close-on-exec flag, like every descriptor the crate creates.
+    ///
+    /// The `wl_display.get_registry` request is queued immediately, so the
+    /// globals are known after the first [`Connection::roundtrip`].
+    pub fn from_stream(stream: UnixStream) -> Result<Connection> {
+        // Full socket buffers are handled by `flush` according to the send mode
+        sys::set_nonblocking(stream.as_fd())?;
+        // Sockets inherited from a parent may lack the flag, and children
+        // spawned by the client must not keep the connection open
+        sys::set_cloexec(stream.as_fd())?;
+
+        let mut objects = ObjectMap::new();
+        let registry_id = objects.allocate(WlObjectId::Registry, 1)?;
+
+        let connection = Connection {
+            state: Arc::new(Lock::new(ConnectionState {
+                stream: Arc::new(stream),
+                objects,
+                registry_id: Some(registry_id),
+                globals: BTreeMap::new(),
+                global_watchers: Vec::new(),
+                next_global_watcher: 0,
+                hooks: Hooks::default(),
+                callbacks: HashMap::new(),
+                buffers: HashMap::new(),
+                object_data: HashMap::new(),
+                user_data: HashMap::new(),
+                listeners: HashMap::new(),
+                out_buf: Vec::new(),
+                out_fds: VecDeque::new(),
+                send_mode: SendMode::default(),
+                send_buffer_limit: DEFAULT_SEND_BUFFER_LIMIT,
+                max_message_len: WL_MESSAGE_MAX_LEN,
+                in_buf: Vec::new(),
+                arena: EventArena::default(),
+                in_fds: VecDeque::new(),
+                utf8_mode: Utf8Mode::default(),
+                invalid_event_policy: InvalidEventPolicy::default(),
+                unknown_object_policy: UnknownObjectPolicy::default(),
+                queued_events: VecDeque::new(),
+                unknown_object_logger: None,
+                fd_watches: Vec::new(),
+                event_loop: EventLoop::new()?,
+                recorder: None,
+                unrecorded_fds: 0,
+                replayer: None,
+                stats: ConnectionStats::default(),
+                socket_path: None,
+                reconnect: None,
+                successor: None,
+            })),
+            dispatching: Arc::new(Lock::new(())),
+        };
+
+        display::request::get_registry(&connection, WlNewId(registry_id))?;
+
+        Ok(connection)
+    }
+
+    /// Creates a connection whose compositor is a [`Recording`], for
+    /// regression tests built from real compositor sessions.
+    ///
+    /// The recorded events are fed through the dispatcher as the client
+    /// sends the requests that preceded them in the recording, as fast as
+    /// it dispatches: the recorded timestamps are ignored. Requests are not
+    /// written anywhere; each one must target the object and use the opcode
+    /// of the recorded request at its position, or flushing fails with a
+    /// [`ReplayDiverged`](record::ReplayDiverged) error. The client must
+    /// therefore create its objects in the same order as the recorded one.
+    ///
+    /// Received file descriptors are replaced by `/dev/null`, since their
+    /// content is not recorded. Dispatching fails once no recorded event is
+    /// left for the requests sent, instead of waiting forever.
+    ///
+    /// # Errors
+    /// Returns an error if the connection cannot be set up.
+    pub fn replay(recording: Recording) -> Result<Connection> {
+        // The socket only serves as a descriptor that never becomes readable
+        let (client, compositor) = UnixStream::pair()?;
+        let connection = Connection::from_stream(client)?;
+        connection.state.borrow_mut().replayer = Some(Replayer::new(recording, compositor));
+
+        Ok(connection)
+    }
+
+    /// Returns `true` if both handles refer to the same connection.
+    ///
+    /// Objects only exist on the connection they were created on, so
+    /// applications connected to several displays at once can use this to
+    /// tell their proxies apart.
+    pub fn same_connection(&self, other: &Connection) -> bool {
+        Arc::ptr_eq(&self.state, &other.state)
+    }
+
+    /// Returns the process ID, user ID and group ID of the compositor, read
+    /// from the socket with `SO_PEERCRED`.
+    ///
+    /// Lets diagnostics tools name the compositor process, and sandbox
+    /// helpers check that the socket they were handed belongs to the
+    /// expected user before trusting it. A connection created with
+    /// [`Connection::replay`] reports the current process.
+    ///
+    /// # Errors
+    /// Returns an error if the credentials cannot be read from the socket.
+    pub fn peer_credentials(&self) -> Result<PeerCredentials> {
+        let (pid, uid, gid) = sys::peer_credentials(self.state.borrow().stream.as_fd())?;
+
+        Ok(PeerCredentials { pid, uid, gid })
+    }
+
+    /// Checks that an object passed as a request argument was created on
+    /// this connection, where its ID would otherwise name another object
+    /// or none at all.
+    ///
+    /// # Errors
+    /// Returns an error if `other`, the connection of the object, is not
+    /// this one.
+    pub(crate) fn check_same_connection(
+        &self,
+        other: &Connection,
+        interface: WlObjectId,
+        id: u32,
+    ) -> Result<()> {
+        if self.same_connection(other) {
+            return Ok(());
+        }
+
+        Err(anyhow!(
+            "{}#{id} belongs to another connection",
+            interface.interface_name()
+        ))
+    }
+
+    /// Returns a snapshot of the globals currently advertised by the compositor.
+    ///
+    /// Once the registry is destroyed with
+    /// [`Fixes::destroy_registry`](crate::protocol::fixes::Fixes::destroy_registry),
+    /// this is the last set of globals it advertised.
+    pub fn globals(&self) -> Vec<Global> {
+        self.state.borrow().globals.values().cloned().collect()
+    }
+
+    /// Returns the highest version of `interface` advertised by the
+    /// compositor, or `None` if it does not advertise it.
+    ///
+    /// `interface` is the protocol name, e.g. `"zwlr_layer_shell_v1"`, so
+    /// protocols this crate does not implement can be checked as well. The
+    /// globals are only complete after the first [`roundtrip`](Self::roundtrip).
+    pub fn supports(&self, interface: &str) -> Option<u32> {
+        self.state
+            .borrow()
+            .globals
+            .values()
+            .filter(|global| global.interface.as_str() == interface)
+            .map(|global| global.version.get())
+            .max()
+    }
+
+    /// Returns the monitors of the outputs bound on the connection that
+    /// announced their properties, ordered by global name.
+    ///
+    /// Each [`Monitor`] merges the properties of a `wl_output` with the
+    /// ones of its `zxdg_output_v1`, if one was created for it, e.g. by
+    /// [`Outputs`](crate::outputs::Outputs). Outputs that are not bound are
+    /// not listed.
+    pub fn monitors(&self) -> Vec<Monitor> {
+        outputs::monitors(self)
+    }
+
+    /// Returns a typed summary of the protocols advertised by the compositor,
+    /// see [`Capabilities`].
+    pub fn capabilities(&self) -> Capabilities {
+        Capabilities::from_globals(&self.globals())
+    }
+
+    /// Returns the registry of the connection.
+    ///
+    /// # Errors
+    /// Returns an error if the registry was destroyed with
+    /// [`Fixes::destroy_registry`](crate::protocol::fixes::Fixes::destroy_registry).
+    pub fn registry(&self) -> Result<Registry> {
+        let id = self
+            .state
+            .borrow()
+            .registry_id
+            .ok_or_else(|| anyhow!("The registry was destroyed"))?;
+
+        Ok(Registry::new(id, self))
+    }
+
+    /// Returns a handle to the event loop of the connection, which other
+    /// threads use to wake up a blocking [`Connection::dispatch`].
+    pub fn loop_handle(&self) -> LoopHandle {
+        self.state.borrow().event_loop.handle()
+    }
+
+    /// Sets how the strings of received events, such as output names and
+    /// MIME types, are decoded.
+    ///
+    /// With the default [`Utf8Mode::Lossy`] invalid UTF-8 is replaced, with
+    /// [`Utf8Mode::Strict`] it makes dispatching fail with a decode error.
+    pub fn set_utf8_mode(&self, mode: Utf8Mode) {
+        self.state.borrow_mut().utf8_mode = mode;
+    }
+
+    /// Sets what dispatching does with events whose opcode the interface of
+    /// the target object does not define, in the version of the object.
+    ///
+    /// With the default [`InvalidEventPolicy::Error`] they make dispatching
+    /// fail with an [`InvalidEvent`] error.
+    pub fn set_invalid_event_policy(&self, policy: InvalidEventPolicy) {
+        self.state.borrow_mut().invalid_event_policy = policy;
+    }
+
+    /// Sets what dispatching does with events for object IDs that are not
+    /// registered, such as IDs already deleted with `wl_display.delete_id`.
+    ///
+    /// With the default [`UnknownObjectPolicy::Error`] they make dispatching
+    /// fail with an [`UnknownObject`] error.
+    pub fn set_unknown_object_policy(&self, policy: UnknownObjectPolicy) {
+        self.state.borrow_mut().unknown_object_policy = policy;
+    }
+
+    /// Sets the closure told about every event dropped by
+    /// [`UnknownObjectPolicy::LogAndSkip`], e.g. to forward it to the
+    /// logging of the application. Without one, the events are dropped
+    /// silently.
+    pub fn set_unknown_object_logger<F>(&self, logger: F)
+    where
+        F: FnMut(&Connection, &UnknownObject) + Send + 'static,
+    {
+        let logger: UnknownObjectLogger = Box::new(logger);
+        // The replaced logger is dropped after the connection is released
+        let _replaced = self
+            .state
+            .borrow_mut()
+            .unknown_object_logger
+            .replace(Arc::new(Lock::new(logger)));
+    }
+
+    /// Registers a closure run before every event is dispatched, with the
+    /// interface of its target object.
+    ///
+    /// Hooks run in the order they were added, after the target object was
+    /// looked up and before the event is validated, so they also see events
+    /// the invalid event policy would reject. Returning [`HookAction::Veto`]
+    /// drops the event, which neither reaches its handler nor counts in the
+    /// [`stats`](Self::stats), and closes the descriptors it carries.
+    ///
+    /// The `wl_display` events, `wl_callback.done` and `wl_buffer.release`
+    /// cannot be vetoed: the connection relies on them to free object IDs,
+    /// report protocol errors, finish roundtrips and reuse buffers, so they
+    /// are handled whatever the hooks return.
+    ///
+    /// # Returns
+    /// The ID to pass to [`remove_hook`](Self::remove_hook).
+    pub fn add_pre_dispatch_hook(
+        &self,
+        hook: impl FnMut(&Connection, WlObjectId, &WlMessage) -> HookAction + Send + 'static,
+    ) -> HookId {
+        self.state.borrow_mut().hooks.add_pre(Box::new(hook))
+    }
+
+    /// Registers a closure run after every event of `interface` was
+    /// handled, including the listeners it notified.
+    ///
+    /// Events whose handler fails are not passed to the hook.
+    ///
+    /// # Returns
+    /// The ID to pass to [`remove_hook`](Self::remove_hook).
+    pub fn add_post_dispatch_hook(
+        &self,
+        interface: WlObjectId,
+        hook: impl FnMut(&Connection, &WlMessage) + Send + 'static,
+    ) -> HookId {
+        self.state
+            .borrow_mut()
+            .hooks
+            .add_post(interface, Box::new(hook))
+    }
+
+    /// Unregisters a hook registered with
+    /// [`add_pre_dispatch_hook`](Self::add_pre_dispatch_hook) or
+    /// [`add_post_dispatch_hook`](Self::add_post_dispatch_hook).
+    ///
+    /// A hook may remove itself or others while it runs.
+    pub fn remove_hook(&self, id: HookId) {
+        // Like in `remove_listener`, the closure is dropped afterwards
+        let _removed = self.state.borrow_mut().hooks.remove(id);
+    }
+
+    /// Sets what flushing does when the socket buffer is full.
+    ///
+    /// With the default [`SendMode::Blocking`] it waits a bounded time for
+    /// the compositor to read; event loops that must never block use
+    /// [`SendMode::NonBlocking`].
+    pub fn set_send_mode(&self, mode: SendMode) {
+        self.state.borrow_mut().send_mode = mode;
+    }
+
+    /// Sets the number of bytes of requests [`SendMode::NonBlocking`] keeps
+    /// queued before flushing fails with [`SendBufferFull`], by default
+    /// [`DEFAULT_SEND_BUFFER_LIMIT`].
+    pub fn set_send_buffer_limit(&self, limit: usize) {
+        self.state.borrow_mut().send_buffer_limit = limit;
+    }
+
+    /// Sets the size in bytes, header included, of the largest event
+    /// accepted, by default [`WL_MESSAGE_MAX_LEN`], which is also the
+    /// upper bound. Sizes below [`WL_MESSAGE_HEADER_LEN`] are raised to it,
+    /// so events without arguments are always accepted.
+    ///
+    /// A larger event makes dispatching fail with
+    /// [`InvalidHeader::TooLarge`](crate::protocol::message::InvalidHeader::TooLarge),
+    /// like events whose size is malformed fail with
+    /// [`InvalidHeader::Malformed`](crate::protocol::message::InvalidHeader::Malformed). Both are fatal, as the
+    /// stream cannot be split into events anymore.
+    pub fn set_max_message_len(&self, len: usize) {
+        self.state.borrow_mut().max_message_len =
+            len.clamp(WL_MESSAGE_HEADER_LEN, WL_MESSAGE_MAX_LEN);
+    }
+
+    /// Attaches application data to an object, replacing any data attached
+    /// before.
+    ///
+    /// Event handlers get the data back with [`Connection::user_data`] from
+    /// the object IDs in the events, e.g. to find the window a `wl_surface`
+    /// belongs to without a map of their own. The data is shared, so mutable
+    /// state goes in a `Mutex` or an atomic. It is dropped once the
+    /// compositor confirmed the deletion of the object.
+    ///
+    /// # Errors
+    /// Returns an error if no live object has the ID `id`.
+    pub fn set_user_data<T: Any + Send + Sync>(&self, id: u32, data: T) -> Result<()> {
+        let replaced = {
+            let mut state = self.state.borrow_mut();
+            if !state.objects.is_live(id) {
+                return Err(anyhow!("Cannot attach user data to unknown object {id}"));
+            }
+            state.user_data.insert(
+                id,
+                UserData {
+                    value: Arc::new(data),
+                    type_name: type_name::<T>(),
+                },
+            )
+        };
+        // Like in `remove_object`, the replaced data is dropped afterwards
+        drop(replaced);
+
+        Ok(())
+    }
+
+    /// Returns the application data attached to an object, if there is data
+    /// of type `T`.
+    pub fn user_data<T: Any + Send + Sync>(&self, id: u32) -> Option<Arc<T>> {
+        let data = self.state.borrow().user_data.get(&id)?.value.clone();
+        data.downcast::<T>().ok()
+    }
+
+    /// Detaches the application data of an object and returns it, if there
+    /// is data of type `T`; data of another type stays attached.
+    pub fn take_user_data<T: Any + Send + Sync>(&self, id: u32) -> Option<Arc<T>> {
+        let mut state = self.state.borrow_mut();
+        if !state.user_data.get(&id)?.value.is::<T>() {
+            return None;
+        }
+        state.user_data.remove(&id)?.value.downcast::<T>().ok()
+    }
+
+    /// Returns a snapshot of the live objects of the connection, with the
+    /// type of the application data attached to each.
+    ///
+    /// Meant for diagnostics: a dump taken when a protocol error names an
+    /// object shows what it was, and dumps taken over time show proxies that
+    /// are never destroyed. Objects whose destructor request was sent are not
+    /// listed, even before the compositor confirms their deletion.
+    pub fn dump_objects(&self) -> ObjectDump {
+        let state = self.state.borrow();
+        let objects = state
+            .objects
+            .live()
+            .into_iter()
+            .map(|(id, entry)| ObjectInfo {
+                id,
+                interface: entry.interface,
+                version: entry.version,
+                user_data: state.user_data.get(&id).map(|data| data.type_name),
+            })
+            .collect();
+
+        ObjectDump::new(objects)
+    }
+
+    /// Returns the traffic counters of the connection since it was created
+    /// or since the last [`Connection::reset_stats`].
+    ///
+    /// Counting is always on and only costs a few additions per message, so
+    /// the protocol overhead of an application can be checked in production
+    /// without tracing every message.
+    pub fn stats(&self) -> ConnectionStats {
+        self.state.borrow().stats.clone()
+    }
+
+    /// Sets all traffic counters back to zero, e.g. to measure a single frame.
+    pub fn reset_stats(&self) {
+        self.state.borrow_mut().stats = ConnectionStats::default();
+    }
+
+    /// Starts recording every request and event of the connection to
+    /// `writer`, replacing any recording in progress.
+    ///
+    /// The messages are written in `format`, with timestamps counted from
+    /// now. Requests are recorded as they are queued, starting with those
+    /// still queued, and events as they are read. Passed file descriptors
+    /// are only counted.
+    ///
+    /// A recording started right after connecting, before the first flush,
+    /// covers the whole session and can be replayed with [`Connection::replay`].
+    ///
+    /// # Errors
+    /// Returns an error if the header cannot be written, or if finishing
+    /// the replaced recording fails.
+    pub fn record_to(
+        &self,
+        writer: impl Write + Send + 'static,
+        format: RecordFormat,
+    ) -> Result<()> {
+        let mut recorder = Recorder::new(Box::new(writer), format)?;
+        let previous = {
+            let mut state = self.state.borrow_mut();
+            let mut offset = 0;
+            while offset + WL_MESSAGE_HEADER_LEN <= state.out_buf.len() {
+                let header = WlMessageHeader::try_from(&state.out_buf[offset..])?;
+                let end = offset + usize::from(header.size);
+                // The rest of a request partly written by a non-blocking
+                // flush has no header to start from
+                if header.validate_size(WL_MESSAGE_MAX_LEN).is_err() || end > state.out_buf.len() {
+                    break;
+                }
+                let fds = state
+                    .out_fds
+                    .iter()
+                    .filter(|out_fd| out_fd.offset == offset)
+                    .count();
+                recorder.record(Direction::Outbound, &state.out_buf[offset..end], fds);
+                offset = end;
+            }
+            state.unrecorded_fds = 0;
+            state.recorder.replace(recorder)
+        };
+        if let Some(previous) = previous {
+            previous.finish()?;
+        }
+
+        Ok(())
+    }
+
+    /// Starts recording the connection to the file at `path`, see
+    /// [`Connection::record_to`].
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be created.
+    pub fn record_to_file(&self, path: impl AsRef<Path>, format: RecordFormat) -> Result<()> {
+        self.record_to(record::create_file(path.as_ref())?, format)
+    }
+
+    /// Stops the recording in progress, if any, and flushes it.
+    ///
+    /// Dropping the connection stops the recording as well, ignoring errors.
+    ///
+    /// # Errors
+    /// Returns the first error met while writing the recording.
+    pub fn stop_recording(&self) -> Result<()> {
+        let recorder = self.state.borrow_mut().recorder.take();
+        match recorder {
+            Some(recorder) => Ok(recorder.finish()?),
+            None => Ok(()),
+        }
+    }
+
+    /// Decodes a received string according to the mode set with
+    /// [`Connection::set_utf8_mode`].
+    ///
+    /// # Errors
+    /// Returns an error in [`Utf8Mode::Strict`] if the string is not valid
+    /// UTF-8.
+    pub(crate) fn decode_string(&self, string: &WlString) -> Result<String> {
+        let mode = self.state.borrow().utf8_mode;
+        Ok(string.decode(mode)?.into_owned())
+    }
+
+    /// Writes all queued requests to the socket.
+    ///
+    /// Queued file descriptors are attached to the first chunk written, so
+    /// they arrive no later than the requests referring to them. Like with
+    /// libwayland, a chunk carries at most 28 descriptors; the requests after
+    /// them go out with their own descriptors in the following chunks.
+    ///
+    /// Short writes are continued and writes interrupted by a signal are
+    /// retried. What happens when the socket buffer is full depends on the
+    /// [`SendMode`] set with [`Connection::set_send_mode`].
+    ///
+    /// # Errors
+    /// Returns a [`SendBufferFull`] error if the requests could not be
+    /// written in time or too many of them are queued.
+    pub fn flush(&self) -> Result<()> {
+        self.queue_remote_requests();
+        self.state.borrow_mut().flush()
+    }
+
+    /// Queues the requests sent from other threads with
+    /// [`LoopHandle::send_request`], dropping those to objects that are no
+    /// longer alive.
+    fn queue_remote_requests(&self) {
+        let requests = self.state.borrow().event_loop.take_requests();
+        for request in requests {
+            if self
+                .state
+                .borrow()
+                .objects
+                .is_live(request.header.object_id)
+            {
+                self.queue(request.header, &request.bytes, request.fds);
+            }
+        }
+    }
+
+    /// Flushes pending requests, blocks until events arrive and dispatches them.
+    ///
+    /// Every complete message received is routed to the handler of the
+    /// interface implemented by its target object. Incomplete trailing bytes
+    /// are kept until the next call.
+    ///
+    /// While a key is held on a keyboard with key repeat enabled, the wait is
+    /// cut short at the next repeat deadline and the repeated key event is
+    /// delivered even if the compositor sent nothing.
+    ///
+    /// Descriptors watched by the crate, such as the pipes of clipboard
+    /// transfers started with [`Clipboard::get_text_async`](crate::clipboard::Clipboard::get_text_async),
+    /// are polled too and serviced after the events.
+    ///
+    /// Timers added with [`LoopHandle::add_timer`] cut the wait short too
+    /// and run after the events, followed by the callbacks deferred with
+    /// [`LoopHandle::defer`]. A call to [`LoopHandle::wake`] from any thread makes the
+    /// wait return early, even if nothing else happened.
+    pub fn dispatch(&self) -> Result<()> {
+        self.dispatch_with(None).map(|_| ())
+    }
+
+    /// Like [`Connection::dispatch`], but also wakes up when `extra` becomes readable.
+    ///
+    /// Lets blocking helpers, such as clipboard reads, wait for their own
+    /// descriptor while events keep being dispatched.
+    ///
+    /// # Returns
+    /// `true` if `extra` is readable (or hung up).
+    pub(crate) fn dispatch_with(&self, extra: Option<BorrowedFd<'_>>) -> Result<bool> {
+        self.dispatch_until(extra, None)
+    }
+
+    /// Like [`Connection::dispatch_with`], but stops waiting at `deadline`
+    /// even if no event arrived.
+    ///
+    /// Once replaced by a reconnect, the connection dispatches its successor.
+    fn dispatch_until(
+        &self,
+        extra: Option<BorrowedFd<'_>>,
+        deadline: Option<Instant>,
+    ) -> Result<bool> {
+        if let Some(successor) = self.successor() {
+            return successor.dispatch_until(extra, deadline);
+        }
+
+        // Other threads dispatching meanwhile wait for this dispatch to end
+        let _dispatching = self.dispatching.borrow();
+        match self.dispatch_connected(extra, deadline) {
+            Err(error) if error.is::<Disconnected>() && self.state.borrow().reconnect.is_some() => {
+                self.reconnect()?;
+                Ok(false)
+            }
+            result => result,
+        }
+    }
+
+    /// Dispatches the events of the connection, see [`Connection::dispatch_until`].
+    fn dispatch_connected(
+        &self,
+        extra: Option<BorrowedFd<'_>>,
+        deadline: Option<Instant>,
+    ) -> Result<bool> {
+        self.flush()?;
+
+        let (next_timer, has_deferred, replay_ready) = {
+            let state = self.state.borrow();
+            (
+                state.event_loop.next_deadline(),
+                state.event_loop.has_deferred(),
+                state.replayer.as_ref().map(Replayer::has_events),
+            )
+        };
+        let wake_up = [keyboard::next_repeat_deadline(self), next_timer, deadline]
+            .into_iter()
+            .flatten()
+            .min();
+        // A replay never receives anything else, so waiting would never end
+        if replay_ready == Some(false) && wake_up.is_none() && !has_deferred {
+            return Err(anyhow!("Recording has no event left for the requests sent"));
+        }
+        // Pending deferred callbacks run at the end of this dispatch, which
+        // must not wait for events first
+        let timeout = if has_deferred || replay_ready == Some(true) {
+            Some(Duration::ZERO)
+        } else {
+            wake_up.map(|wake_up| wake_up.saturating_duration_since(Instant::now()))
+        };
+        // The state is not borrowed while waiting, so other threads can
+        // send requests meanwhile. The watches are taken along and put back
+        // before those added meanwhile.
+        let (stream, wake_fd, watches, writable) = {
+            let mut state = self.state.borrow_mut();
+            (
+                state.stream.clone(),
+                state.event_loop.wake_fd(),
+                std::mem::take(&mut state.fd_watches),
+                !state.out_buf.is_empty(),
+            )
+        };
+        let mut fds = vec![
+            (stream.as_fd(), Interest::Readable),
+            (wake_fd.as_fd(), Interest::Readable),
+        ];
+        fds.extend(extra.map(|fd| (fd, Interest::Readable)));
+        fds.extend(
+            watches
+                .iter()
+                .map(|watch| (watch.fd.as_fd(), watch.interest)),
+        );
+        // Requests left queued by a non-blocking flush go out as the socket
+        // drains
+        if writable {
+            fds.push((stream.as_fd(), Interest::Writable));
+        }
+        let polled = sys::poll(&fds, timeout);
+        drop(fds);
+        let watches_start = 2 + usize::from(extra.is_some());
+        let watches_end = watches_start + watches.len();
+        {
+            let mut state = self.state.borrow_mut();
+            let added = std::mem::replace(&mut state.fd_watches, watches);
+            state.fd_watches.extend(added);
+        }
+        let ready = polled?;
+
+        if ready[1] {
+            self.state.borrow().event_loop.clear_wake();
+            // The wake-up may come from requests sent by another thread
+            self.flush()?;
+        }
+
+        if ready[0] || replay_ready == Some(true) {
+            for message in self.read_messages()? {
+                self.dispatch_message(message)?;
+            }
+        }
+        self.dispatch_queued_events()?;
+
+        keyboard::dispatch_key_repeats(self, Instant::now());
+        self.dispatch_fd_watches(&ready[watches_start..watches_end]);
+        if ready.len() > watches_end {
+            self.flush()?;
+        }
+        self.dispatch_timers(Instant::now());
+        self.dispatch_deferred();
+
+        Ok(extra.is_some() && ready[2])
+    }
+
+    /// Invokes the callbacks of the timers expired at `now`.
+    ///
+    /// Timers the callbacks add are left for the next dispatch, even if
+    /// they already expired.
+    fn dispatch_timers(&self, now: Instant) {
+        let expired = self.state.borrow().event_loop.take_expired(now);
+        for callback in expired {
+            callback(self);
+        }
+    }
+
+    /// Invokes the callbacks deferred until the end of the dispatch.
+    ///
+    /// Callbacks deferred meanwhile are left for the next dispatch.
+    fn dispatch_deferred(&self) {
+        let deferred = self.state.borrow().event_loop.take_deferred();
+        for callback in deferred {
+            callback(self);
+        }
+    }
+
+    /// Invokes the callbacks of the watched descriptors that are ready, in
+    /// the order they were polled, and ends the watches they decline.
+    ///
+    /// Watches added meanwhile are kept after the existing ones, so `ready`
+    /// still lines up with the watches it was polled for.
+    fn dispatch_fd_watches(&self, ready: &[bool]) {
+        let watches = std::mem::take(&mut self.state.borrow_mut().fd_watches);
+
+        let mut kept = Vec::with_capacity(watches.len());
+        for (index, mut watch) in watches.into_iter().enumerate() {
+            let is_ready = ready.get(index).copied().unwrap_or(false);
+            if is_ready && !(watch.callback)(self, watch.fd.as_fd()) {
+                // Dropping the watch closes the descriptor
+                continue;
+            }
+            kept.push(watch);
+        }
+
+        let mut state = self.state.borrow_mut();
+        kept.append(&mut state.fd_watches);
+        state.fd_watches = kept;
+    }
+
+    /// Polls `fd` along with the socket and invokes `callback` every time it
+    /// becomes readable during a dispatch, until the callback returns `false`.
+    ///
+    /// The connection owns the descriptor and closes it when the watch ends.
+    pub(crate) fn watch_readable(&self, fd: OwnedFd, callback: FdCallback) {
+        self.watch_fd(fd, Interest::Readable, callback);
+    }
+
+    /// Polls `fd` along with the socket and invokes `callback` every time it
+    /// becomes writable during a dispatch, until the callback returns `false`.
+    ///
+    /// The connection owns the descriptor and closes it when the watch ends.
+    pub(crate) fn watch_writable(&self, fd: OwnedFd, callback: FdCallback) {
+        self.watch_fd(fd, Interest::Writable, callback);
+    }
+
+    /// Adds a watch on `fd` for the given interest.
+    fn watch_fd(&self, fd: OwnedFd, interest: Interest, callback: FdCallback) {
+        self.state.borrow_mut().fd_watches.push(FdWatch {
+            fd,
+            interest,
+            callback,
+        });
+    }
+
+    /// Blocks until the compositor has processed every request sent so far.
+    ///
+    /// Sends `wl_display.sync` and dispatches events until the returned
+    /// callback fires, so all events caused by earlier requests have been
+    /// handled when this returns.
+    pub fn roundtrip(&self) -> Result<()> {
+        self.roundtrip_until(None).map(|_| ())
+    }
+
+    /// Like [`Connection::roundtrip`], but gives up once `timeout` elapsed,
+    /// so tools do not hang forever on a compositor that stopped responding.
+    ///
+    /// Events received before the timeout are dispatched as usual. The sync
+    /// callback stays registered, so a late answer is ignored.
+    ///
+    /// # Errors
+    /// Returns a [`Timeout`] error if the compositor did not answer in time,
+    /// or any error [`Connection::roundtrip`] returns.
+    pub fn roundtrip_timeout(&self, timeout: Duration) -> Result<()> {
+        if self.roundtrip_until(Some(Instant::now() + timeout))? {
+            Ok(())
+        } else {
+            Err(Timeout { duration: timeout }.into())
+        }
+    }
+
+    /// Sends `wl_display.sync` and dispatches events until its callback
+    /// fires or `deadline` passes.
+    ///
+    /// # Returns
+    /// `true` if the callback fired, `false` if the deadline passed first.
+    fn roundtrip_until(&self, deadline: Option<Instant>) -> Result<bool> {
+        if let Some(successor) = self.successor() {
+            return successor.roundtrip_until(deadline);
+        }
+
+        let done = Arc::new(LockCell::new(false));
+
+        let callback_id = self.new_object(WlObjectId::Callback, 1)?;
+        let done_flag = done.clone();
+        self.add_callback(callback_id, Box::new(move |_, _| done_flag.set(true)));
+        display::request::sync(self, WlNewId(callback_id))?;
+        // Another thread may be blocked dispatching, and must see the sync
+        self.flush()?;
+
+        while !done.get() {
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                return Ok(false);
+            }
+            // A thread dispatching meanwhile may run the callback, after
+            // which there is nothing left to wait for
+            let Some(_dispatching) = self.dispatching.borrow_unless(|| done.get()) else {
+                break;
+            };
+            if done.get() {
+                break;
+            }
+            self.dispatch_until(None, deadline)?;
+            // The sync was lost with the old compositor, and the new
+            // connection was synced when it was set up
+            if self.state.borrow().successor.is_some() {
+                return Ok(true);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Encodes a request from its arguments and queues it to be written on
+    /// the next flush.
+    ///
+    /// Nothing is allocated on the way to the outgoing buffer unless the
+    /// request outgrows the inline buffer of the [`Encoder`], which keeps the
+    /// requests sent every frame off the heap.
+    ///
+    /// # Errors
+    /// Returns an error if the request is too long to be encoded.
+    pub(crate) fn send_request(
+        &self,
+        object_id: u32,
+        opcode: impl Into<u16>,
+        args: &impl Encode,
+    ) -> Result<()> {
+        self.send_request_with_fds(object_id, opcode, args, Vec::new())
+    }
+
+    /// Sends the destructor of an object whose handle is being dropped.
+    ///
+    /// `Drop` cannot return errors, so the one returned by `destroy` is
+    /// discarded. Destructors are short enough to always encode, and a
+    /// broken connection is reported by the next flush or dispatch.
+    pub(crate) fn destroy_on_drop(&self, destroy: impl FnOnce(&Connection) -> Result<()>) {
+        let _ = destroy(self);
+    }
+
+    /// Like [`Connection::send_request`], for a request carrying file
+    /// descriptors, which are closed once they have been sent.
+    ///
+    /// # Errors
+    /// Returns an error if the request is too long to be encoded.
+    pub(crate) fn send_request_with_fds(
+        &self,
+        object_id: u32,
+        opcode: impl Into<u16>,
+        args: &impl Encode,
+        fds: Vec<OwnedFd>,
+    ) -> Result<()> {
+        let mut encoder = Encoder::new(object_id, opcode.into());
+        args.encode(&mut encoder);
+        let header = encoder.header();
+        self.queue(header, encoder.finish()?, fds);
+
+        Ok(())
+    }
+
+    /// Appends a serialized request and its descriptors to the outgoing
+    /// buffer, recording it if a recording is in progress.
+    fn queue(&self, header: WlMessageHeader, bytes: &[u8], fds: Vec<OwnedFd>) {
+        let mut state = self.state.borrow_mut();
+        // Proxies of a replaced connection are detached from the compositor
+        if state.successor.is_some() {
+            return;
+        }
+        if let Some(recorder) = &mut state.recorder {
+            recorder.record(Direction::Outbound, bytes, fds.len());
+        }
+        let offset = state.out_buf.len();
+        state.out_buf.extend_from_slice(bytes);
+        let queued = state.out_buf.len();
+        state.stats.record_sent(bytes.len(), fds.len(), queued);
+        state
+            .out_fds
+            .extend(fds.into_iter().map(|fd| OutFd { offset, fd }));
+        drop(state);
+        self.track_destructor(header);
+    }
+
+    /// Marks the target of a queued request as destroyed if the request is
+    /// a destructor of its interface.
+    ///
+    /// Client-side objects stay known until the compositor confirms their
+    /// deletion; server-side objects are forgotten right away.
+    fn track_destructor(&self, header: WlMessageHeader) {
+        let id = header.object_id;
+        let forget = {
+            let mut state = self.state.borrow_mut();
+            match state.objects.get(id) {
+                Some(entry) if entry.interface.is_destructor(header.opcode) => {
+                    state.objects.mark_destroyed(id)
+                }
+                _ => false,
+            }
+        };
+
+        if forget {
+            self.remove_object(id);
+        }
+    }
+
+    /// Allocates an ID for a new client-side object.
+    ///
+    /// Requests other threads sent to an object that held the ID before
+    /// are dropped, see [`LoopHandle::send_request`].
+    pub(crate) fn new_object(&self, interface: WlObjectId, version: u32) -> Result<u32> {
+        let mut state = self.state.borrow_mut();
+        let id = state.objects.allocate(interface, version)?;
+        state.event_loop.discard_requests_to(id);
+
+        Ok(id)
+    }
+
+    /// Records an object the compositor created with a `new_id` event argument.
+    ///
+    /// Like [`Connection::new_object`], drops the requests other threads
+    /// sent to an object that held the ID before.
+    pub(crate) fn insert_object(&self, id: u32, interface: WlObjectId, version: u32) -> Result<()> {
+        let mut state = self.state.borrow_mut();
+        state.objects.insert(id, interface, version)?;
+        state.event_loop.discard_requests_to(id);
+
+        Ok(())
+    }
+
+    /// Looks up an object, including one destroyed but not deleted yet.
+    pub(crate) fn object(&self, id: u32) -> Option<ObjectEntry> {
+        self.state.borrow().objects.get(id)
+    }
+
+    /// Forgets an object once the compositor confirmed its deletion.
+    ///
+    /// The closures and state of the object are dropped after the connection
+    /// is released, as they may own proxies whose destructors use it.
+    pub(crate) fn remove_object(&self, id: u32) {
+        let _removed = {
+            let mut state = self.state.borrow_mut();
+            state.objects.remove(id);
+            state.buffers.remove(&id);
+            (
+                state.callbacks.remove(&id),
+                state.object_data.remove(&id),
+                state.user_data.remove(&id),
+                state.listeners.remove(&id),
+            )
+        };
+    }
+
+    /// Returns the IDs of all live objects implementing `interface`.
+    pub(crate) fn objects_of(&self, interface: WlObjectId) -> Vec<u32> {
+        self.state.borrow().objects.ids_of(interface)
+    }
+
+    /// Attaches proxy state to an object, replacing any previous state.
+    ///
+    /// The state is dropped when the object is removed.
+    pub(crate) fn set_object_data<T: Any + Send + Sync>(&self, id: u32, data: Arc<T>) {
+        // Like in `remove_object`, the replaced state is dropped afterwards
+        let _replaced = self.state.borrow_mut().object_data.insert(id, data);
+    }
+
+    /// Returns the proxy state attached to an object, if it has the expected type.
+    pub(crate) fn object_data<T: Any + Send + Sync>(&self, id: u32) -> Option<Arc<T>> {
+        let data = self.state.borrow().object_data.get(&id)?.clone();
+        data.downcast::<T>().ok()
+    }
+
+    /// Registers the listener receiving the events of an object.
+    pub(crate) fn set_listener<E: 'static>(&self, id: u32, listener: Listener<E>) {
+        // Like in `remove_object`, the replaced listener is dropped afterwards
+        let _replaced = self
+            .state
+            .borrow_mut()
+            .listeners
+            .insert(id, Box::new(listener));
+    }
+
+    /// Unregisters the listener of an object, so events still in flight are ignored.
+    pub(crate) fn remove_listener(&self, id: u32) {
+        // Like in `remove_object`, the listener is dropped afterwards
+        let _removed = self.state.borrow_mut().listeners.remove(&id);
+    }
+
+    /// Delivers an event to the listener of an object, if it has one.
+    ///
+    /// The listener is taken out of the connection while it runs, so it may
+    /// issue requests or replace itself. It is put back afterwards unless it
+    /// was replaced or the object was destroyed in the meantime.
+    pub(crate) fn emit<E: 'static>(&self, id: u32, event: E) {
+        let listener = {
+            let mut state = self.state.borrow_mut();
+            // Events still in flight for a destroyed object are dropped
+            if !state.objects.is_live(id) {
+                return;
+            }
+            state.listeners.remove(&id)
+        };
+        let Some(listener) = listener else {
+            return;
+        };
+
+        let listener = match listener.downcast::<Listener<E>>() {
+            Ok(mut typed) => {
+                typed(self, event);
+                typed as Box<dyn Any + Send>
+            }
+            Err(listener) => listener,
+        };
+
+        // A listener that was replaced meanwhile, or whose object was
+        // deleted, is dropped on return, once the state is released
+        let mut state = self.state.borrow_mut();
+        if state.objects.is_live(id) && !state.listeners.contains_key(&id) {
+            state.listeners.insert(id, listener);
+        }
+    }
+
+    /// Claims the next file descriptor received from the compositor.
+    ///
+    /// Descriptors arrive in the order of the `fd` arguments of the events
+    /// carrying them, so handlers take them while decoding those events.
+    ///
+    /// # Errors
+    /// Returns an error if the compositor did not send a descriptor.
+    pub(crate) fn take_fd(&self) -> Result<OwnedFd> {
+        self.state
+            .borrow_mut()
+            .in_fds
+            .pop_front()
+            .ok_or_else(|| anyhow!("Event is missing its file descriptor"))
+    }
+
+    /// Binds the advertised global implementing `interface`.
+    ///
+    /// The bound version is the lower of `max_version` and the version
+    /// advertised by the compositor.
+    ///
+    /// # Returns
+    /// The new object ID and the negotiated version.
+    ///
+    /// # Errors
+    /// Returns an error if the compositor does not advertise the interface.
+    pub(crate) fn bind(&self, interface: WlObjectId, max_version: u32) -> Result<(u32, u32)> {
+        let name = self
+            .state
+            .borrow()
+            .globals
+            .values()
+            .find(|global| global.interface.as_str() == interface.interface_name())
+            .map(|global| global.name.get())
+            .ok_or_else(|| {
+                anyhow!(
+                    "Compositor does not advertise {}",
+                    interface.interface_name()
+                )
+            })?;
+
+        self.bind_global(interface, name, max_version)
+    }
+
+    /// Binds the advertised global with the numeric `name`, for interfaces
+    /// with several globals such as `wl_output`.
+    ///
+    /// # Returns
+    /// The new object ID and the negotiated version.
+    ///
+    /// # Errors
+    /// Returns an error if no global with this name is advertised, or if it
+    /// does not implement `interface`.
+    pub(crate) fn bind_global(
+        &self,
+        interface: WlObjectId,
+        name: u32,
+        max_version: u32,
+    ) -> Result<(u32, u32)> {
+        let (version, registry_id) = {
+            let state = self.state.borrow();
+            let global = state
+                .globals
+                .get(&name)
+                .filter(|global| global.interface.as_str() == interface.interface_name())
+                .ok_or_else(|| {
+                    anyhow!(
+                        "Compositor does not advertise {} global {name}",
+                        interface.interface_name()
+                    )
+                })?;
+
+            let registry_id = state.registry_id.ok_or_else(|| {
+                anyhow!(
+                    "Cannot bind {} global {name}: the registry was destroyed",
+                    interface.interface_name()
+                )
+            })?;
+
+            (global.version.get().min(max_version), registry_id)
+        };
+
+        let id = self.new_object(interface, version)?;
+        registry::request::bind(
+            self,
+            registry_id,
+            WlUInt(name),
+            WlUntypedNewId::new(interface.interface_name(), version, id)?,
+        )?;
+
+        Ok((id, version))
+    }
+
+    /// Marks the registry as destroyed, so no further request uses it.
+    ///
+    /// The object itself stays in the object map until the compositor
+    /// confirms the deletion with `wl_display.delete_id`, so events still in
+    /// flight for it are handled as usual.
+    ///
+    /// # Returns
+    /// The ID of the registry to destroy.
+    ///
+    /// # Errors
+    /// Returns an error if the registry was already destroyed.
+    pub(crate) fn destroy_registry(&self) -> Result<u32> {
+        self.state
+            .borrow_mut()
+            .registry_id
+            .take()
+            .ok_or_else(|| anyhow!("The registry was already destroyed"))
+    }
+
+    /// Registers the closure to run when the `wl_callback` with the given ID fires.
+    pub(crate) fn add_callback(&self, id: u32, callback: CallbackFn) {
+        // Like in `remove_object`, the replaced callback is dropped afterwards
+        let _replaced = self.state.borrow_mut().callbacks.insert(id, callback);
+    }
+
+    /// Removes and returns the closure registered for a `wl_callback`.
+    pub(crate) fn take_callback(&self, id: u32) -> Option<CallbackFn> {
+        self.state.borrow_mut().callbacks.remove(&id)
+    }
+
+    /// Registers the busy flag of a new buffer so release events can clear it.
+    pub(crate) fn track_buffer(&self, id: u32, busy: Arc<LockCell<bool>>) {
+        self.state.borrow_mut().buffers.insert(
+            id,
+            BufferState {
+                busy,
+                destroy_on_release: false,
+            },
+        );
+    }
+
+    /// Marks a live buffer as in use by the compositor until it is released.
+    pub(crate) fn mark_buffer_busy(&self, id: u32) {
+        let state = self.state.borrow();
+        if !state.objects.is_live(id) {
+            return;
+        }
+        if let Some(buffer) = state.buffers.get(&id) {
+            buffer.busy.set(true);
+        }
+    }
+
+    /// Marks a buffer as no longer used by the compositor.
+    ///
+    /// # Returns
+    /// `true` if the buffer handle was dropped while busy, in which case the
+    /// caller must now destroy the buffer.
+    pub(crate) fn release_buffer(&self, id: u32) -> bool {
+        let mut state = self.state.borrow_mut();
+        let Some(buffer) = state.buffers.get(&id) else {
+            return false;
+        };
+
+        buffer.busy.set(false);
+        if buffer.destroy_on_release {
+            state.buffers.remove(&id);
+            return true;
+        }
+
+        false
+    }
+
+    /// Postpones the destruction of a dropped buffer until the compositor releases it.
+    pub(crate) fn destroy_buffer_on_release(&self, id: u32) {
+        if let Some(buffer) = self.state.borrow_mut().buffers.get_mut(&id) {
+            buffer.destroy_on_release = true;
+        }
+    }
+
+    /// Records a global announced by the registry.
+    pub(crate) fn insert_global(&self, global: Global) {
+        self.state
+            .borrow_mut()
+            .globals
+            .insert(global.name.get(), global);
+    }
+
+    /// Forgets a global withdrawn by the registry.
+    ///
+    /// # Returns
+    /// The global, or `None` if it was not advertised.
+    pub(crate) fn remove_global(&self, name: u32) -> Option<Global> {
+        self.state.borrow_mut().globals.remove(&name)
+    }
+
+    /// Registers a closure notified whenever the registry announces or
+    /// withdraws a global, such as a plugged in monitor.
+    ///
+    /// # Returns
+    /// The ID to pass to [`unwatch_globals`](Self::unwatch_globals).
+    pub(crate) fn watch_globals(&self, watcher: GlobalWatcher) -> u64 {
+        let mut state = self.state.borrow_mut();
+        let id = state.next_global_watcher;
+        state.next_global_watcher += 1;
+        state
+            .global_watchers
+            .push((id, Arc::new(Lock::new(watcher))));
+        id
+    }
+
+    /// Unregisters a closure registered with
+    /// [`watch_globals`](Self::watch_globals).
+    pub(crate) fn unwatch_globals(&self, id: u64) {
+        // Like in `remove_listener`, the closure is dropped afterwards
+        let _removed = {
+            let mut state = self.state.borrow_mut();
+            let index = state.global_watchers.iter().position(|(i, _)| *i == id);
+            index.map(|index| state.global_watchers.remove(index))
+        };
+    }
+
+    /// Notifies the global watchers of a change.
+    ///
+    /// The watchers may register or unregister watchers while they run; a
+    /// watcher unregistered by an earlier one is skipped, one registered
+    /// meanwhile only sees the next change.
+    pub(crate) fn notify_globals(&self, change: GlobalChange<'_>) {
+        let watchers = self.state.borrow().global_watchers.clone();
+
+        for (id, watcher) in watchers {
+            let registered = self
+                .state
+                .borrow()
+                .global_watchers
+                .iter()
+                .any(|(i, _)| *i == id);
+            if !registered {
+                continue;
+            }
+            // A watcher dispatching events that reach itself again is not re-entered
+            let Ok(mut watcher) = watcher.try_borrow_mut() else {
+                continue;
+            };
+            watcher(self, change);
+        }
+    }
+
+    /// Reads from the socket and splits the received bytes into complete messages.
+    fn read_messages(&self) -> Result<Vec<WlMessage>> {
+        let mut state = self.state.borrow_mut();
+        let state = &mut *state;
+
+        if let Some(replayer) = &mut state.replayer {
+            let (events, fds) = replayer.take_events()?;
+            state.stats.fds_received += fds.len() as u64;
+            state.in_fds.extend(fds);
+            return events
+                .into_iter()
+                .map(|event| {
+                    if let Some(recorder) = &mut state.recorder {
+                        recorder.record(Direction::Inbound, event.bytes(), event.fds());
+                    }
+                    state.stats.record_received(1, event.bytes().len());
+                    WlMessage::try_from(event.bytes())
+                })
+                .collect();
+        }
+
+        let mut read_buf = [0u8; READ_CHUNK_LEN];
+        let mut fds = Vec::new();
+        let read_len = match sys::recv_with_fds(state.stream.as_fd(), &mut read_buf, &mut fds) {
+            Ok(read_len) => read_len,
+            // Readiness reported by poll may be spurious
+            Err(error) if error.kind() == io::ErrorKind::WouldBlock => return Ok(Vec::new()),
+            Err(error) if is_disconnect(&error) => return Err(Disconnected.into()),
+            Err(error) => return Err(error.into()),
+        };
+        if state.recorder.is_some() {
+            state.unrecorded_fds += fds.len();
+        }
+        state.stats.fds_received += fds.len() as u64;
+        state.in_fds.extend(fds);
+        if read_len == 0 {
+            return Err(Disconnected.into());
+        }
+        state.in_buf.extend_from_slice(&read_buf[..read_len]);
+
+        let mut headers = Vec::new();
+        let mut consumed = 0;
+
+        while state.in_buf.len() - consumed >= WL_MESSAGE_HEADER_LEN {
+            let remaining = &state.in_buf[consumed..];
+            let header = WlMessageHeader::try_from(&remaining[..WL_MESSAGE_HEADER_LEN])?;
+            let message_len = header.size as usize;
+
+            header.validate_size(state.max_message_len)?;
+            if remaining.len() < message_len {
+                break;
+            }
+
+            if let Some(recorder) = &mut state.recorder {
+                let fds = std::mem::take(&mut state.unrecorded_fds);
+                recorder.record(Direction::Inbound, &remaining[..message_len], fds);
+            }
+            headers.push((header, consumed));
+            consumed += message_len;
+        }
+
+        if headers.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        state.stats.record_received(headers.len(), consumed);
+
+        // The complete messages of the batch share a single arena chunk
+        let ConnectionState { in_buf, arena, .. } = state;
+        let chunk = arena.reset(&in_buf[..consumed]);
+        in_buf.drain(..consumed);
+
+        Ok(headers
+            .into_iter()
+            .map(|(header, offset)| {
+                let data = offset + WL_MESSAGE_HEADER_LEN..offset + header.size as usize;
+                WlMessage {
+                    header,
+                    data: EventArena::slice(&chunk, data),
+                }
+            })
+            .collect())
+    }
+
+    /// Dispatches the events held back by [`UnknownObjectPolicy::Queue`]
+    /// whose target object got registered since they arrived.
+    fn dispatch_queued_events(&self) -> Result<()> {
+        let ready: VecDeque<WlMessage> = {
+            let mut state = self.state.borrow_mut();
+            let state = &mut *state;
+            let (ready, pending) = std::mem::take(&mut state.queued_events)
+                .into_iter()
+                .partition(|message| state.objects.get(message.header.object_id).is_some());
+            state.queued_events = pending;
+            ready
+        };
+
+        for message in ready {
+            self.dispatch_message(message)?;
+        }
+
+        Ok(())
+    }
+
+    /// Takes the events held back for the object `id`, which must be
+    /// dispatched before any newer event of the object.
+    fn take_queued_events(&self, id: u32) -> VecDeque<WlMessage> {
+        let mut state = self.state.borrow_mut();
+        if state.queued_events.is_empty() {
+            return VecDeque::new();
+        }
+
+        let (taken, pending) = std::mem::take(&mut state.queued_events)
+            .into_iter()
+            .partition(|message| message.header.object_id == id);
+        state.queued_events = pending;
+        taken
+    }
+
+    /// Applies the [`UnknownObjectPolicy`] to an event for an unregistered ID.
+    fn handle_unknown_object(&self, message: WlMessage) -> Result<()> {
+        let error = UnknownObject {
+            object_id: message.header.object_id,
+            opcode: message.header.opcode,
+        };
+
+        let mut state = self.state.borrow_mut();
+        match state.unknown_object_policy {
+            UnknownObjectPolicy::Error => Err(error.into()),
+            UnknownObjectPolicy::LogAndSkip => {
+                let logger = state.unknown_object_logger.clone();
+                drop(state);
+                // A logger dispatching events that reach itself again is not re-entered
+                if let Some(logger) = logger
+                    && let Ok(mut logger) = logger.try_borrow_mut()
+                {
+                    logger(self, &error);
+                }
+                Ok(())
+            }
+            UnknownObjectPolicy::Queue if state.queued_events.len() >= MAX_QUEUED_EVENTS => {
+                Err(error.into())
+            }
+            UnknownObjectPolicy::Queue => {
+                state.queued_events.push_back(message);
+                state.stats.max_queued_events =
+                    state.stats.max_queued_events.max(state.queued_events.len());
+                Ok(())
+            }
+        }
+    }
+
+    /// Routes a single event to the handler of its target object's interface.
+    fn dispatch_message(&self, message: WlMessage) -> Result<()> {
+        let Some(object) = self.object(message.header.object_id) else {
+            return self.handle_unknown_object(message);
+        };
+
+        for queued in self.take_queued_events(message.header.object_id) {
+            self.dispatch_message(queued)?;
+        }
+
+        if self.run_pre_dispatch_hooks(object.interface, &message) == HookAction::Veto
+            && !is_bookkeeping_event(object.interface, message.header.opcode)
+        {
+            self.skip_event(object.interface, &message);
+            return Ok(());
+        }
+
+        if let Err(error) = validate_event(&message.header, object) {
+            let policy = self.state.borrow().invalid_event_policy;
+            return match policy {
+                InvalidEventPolicy::Error => Err(error.into()),
+                InvalidEventPolicy::Skip => {
+                    self.skip_event(object.interface, &message);
+                    Ok(())
+                }
+            };
+        }
+        self.state
+            .borrow_mut()
+            .stats
+            .record_dispatch(object.interface);
+
+        let post_hooks = self.state.borrow().hooks.post(object.interface);
+        if post_hooks.is_empty() {
+            return self.handle_message(object.interface, message);
+        }
+        self.handle_message(object.interface, message.clone())?;
+        self.run_post_dispatch_hooks(post_hooks, &message);
+
+        Ok(())
+    }
+
+    /// Drops an event without handling it.
+    ///
+    /// The descriptors it carries are closed, so that the next events taking
+    /// descriptors do not receive them instead.
+    fn skip_event(&self, interface: WlObjectId, message: &WlMessage) {
+        let mut state = self.state.borrow_mut();
+        let count = interface
+            .event_fd_count(message.header.opcode)
+            .min(state.in_fds.len());
+        state.in_fds.drain(..count);
+    }
+
+    /// Runs the pre-dispatch hooks on an event until one vetoes it.
+    ///
+    /// Like global watchers, a hook removed by an earlier one is skipped and
+    /// one added meanwhile only sees the next event.
+    fn run_pre_dispatch_hooks(&self, interface: WlObjectId, message: &WlMessage) -> HookAction {
+        let hooks = self.state.borrow().hooks.pre();
+
+        for (id, hook) in hooks {
+            if !self.state.borrow().hooks.contains(id) {
+                continue;
+            }
+            // A hook dispatching events that reach itself again is not re-entered
+            let Ok(mut hook) = hook.try_borrow_mut() else {
+                continue;
+            };
+            if hook(self, interface, message) == HookAction::Veto {
+                return HookAction::Veto;
+            }
+        }
+
+        HookAction::Continue
+    }
+
+    /// Runs the post-dispatch hooks of the interface of a handled event.
+    fn run_post_dispatch_hooks(
+        &self,
+        hooks: Vec<(HookId, Arc<Lock<hooks::PostDispatchHook>>)>,
+        message: &WlMessage,
+    ) {
+        for (id, hook) in hooks {
+            if !self.state.borrow().hooks.contains(id) {
+                continue;
+            }
+            let Ok(mut hook) = hook.try_borrow_mut() else {
+                continue;
+            };
+            hook(self, message);
+        }
+    }
+
+    /// Passes a validated event to the handler of its target's interface.
+    fn handle_message(&self, interface: WlObjectId, message: WlMessage) -> Result<()> {
+        match interface {
+            WlObjectId::Display => display::event::handle_wl_display_event(self, message),
+            WlObjectId::Registry => registry::event::handle_wl_registry_event(self, message),
+            WlObjectId::Callback => callback::event::handle_wl_callback_event(self, message),
+            WlObjectId::Surface => surface::event::handle_wl_surface_event(self, message),
+            WlObjectId::Shm => shm::event::handle_wl_shm_event(self, message),
+            WlObjectId::Buffer => buffer::event::handle_wl_buffer_event(self, message),
+            WlObjectId::DataOffer => data_offer::event::handle_wl_data_offer_event(self, message),
+            WlObjectId::DataSource => {
+                data_source::event::handle_wl_data_source_event(self, message)
+            }
+            WlObjectId::DataDevice => {
+                data_device::event::handle_wl_data_device_event(self, message)
+            }
+            WlObjectId::Output => output::event::handle_wl_output_event(self, message),
+            WlObjectId::Seat => seat::event::handle_wl_seat_event(self, message),
+            WlObjectId::Pointer => pointer::event::handle_wl_pointer_event(self, message),
+            WlObjectId::Keyboard => keyboard::event::handle_wl_keyboard_event(self, message),
+            WlObjectId::Touch => touch::event::handle_wl_touch_event(self, message),
+            WlObjectId::PointerGestureSwipe => {
+                pointer_gestures::event::handle_zwp_pointer_gesture_swipe_event(self, message)
+            }
+            WlObjectId::PointerGesturePinch => {
+                pointer_gestures::event::handle_zwp_pointer_gesture_pinch_event(self, message)
+            }
+            WlObjectId::PointerGestureHold => {
+                pointer_gestures::event::handle_zwp_pointer_gesture_hold_event(self, message)
+            }
+            WlObjectId::XdgWmBase => xdg_wm_base::event::handle_xdg_wm_base_event(self, message),
+            WlObjectId::XdgSurface => xdg_surface::event::handle_xdg_surface_event(self, message),
+            WlObjectId::XdgToplevel => {
+                xdg_toplevel::event::handle_xdg_toplevel_event(self, message)
+            }
+            WlObjectId::XdgPopup => xdg_popup::event::handle_xdg_popup_event(self, message),
+            WlObjectId::FractionalScale => {
+                fractional_scale::event::handle_wp_fractional_scale_event(self, message)
+            }
+            WlObjectId::Presentation => {
+                presentation::event::handle_wp_presentation_event(self, message)
+            }
+            WlObjectId::PresentationFeedback => {
+                presentation::event::handle_wp_presentation_feedback_event(self, message)
+            }
+            WlObjectId::ToplevelDecoration => {
+                xdg_decoration::event::handle_zxdg_toplevel_decoration_event(self, message)
+            }
+            WlObjectId::LayerSurface => {
+                layer_shell::event::handle_zwlr_layer_surface_event(self, message)
+            }
+            WlObjectId::SessionLock => {
+                session_lock::event::handle_ext_session_lock_event(self, message)
+            }
+            WlObjectId::SessionLockSurface => {
+                session_lock::event::handle_ext_session_lock_surface_event(self, message)
+            }
+            WlObjectId::LinuxBufferRelease => {
+                linux_explicit_sync::event::handle_zwp_linux_buffer_release_event(self, message)
+            }
+            WlObjectId::ImageCopyCaptureSession => {
+                image_copy_capture::event::handle_ext_image_copy_capture_session_event(
+                    self, message,
+                )
+            }
+            WlObjectId::ImageCopyCaptureFrame => {
+                image_copy_capture::event::handle_ext_image_copy_capture_frame_event(self, message)
+            }
+            WlObjectId::LockedPointer => {
+                pointer_constraints::event::handle_zwp_locked_pointer_event(self, message)
+            }
+            WlObjectId::RelativePointer => {
+                relative_pointer::event::handle_zwp_relative_pointer_event(self, message)
+            }
+            WlObjectId::XdgOutput => xdg_output::event::handle_zxdg_output_event(self, message),
+            WlObjectId::ColorManager => {
+                color_management::event::handle_wp_color_manager_event(self, message)
+            }
+            WlObjectId::ColorManagementSurfaceFeedback => {
+                color_management::event::handle_wp_color_management_surface_feedback_event(
+                    self, message,
+                )
+            }
+            WlObjectId::ImageDescription => {
+                color_management::event::handle_wp_image_description_event(self, message)
+            }
+            interface => Err(anyhow!(
+                "No event handler for {} (object {})",
+                interface.interface_name(),
+                message.header.object_id
+            )),
+        }
+    }
+}
+
+/// Returns `true` if a socket error means the compositor closed the connection.
+fn is_disconnect(error: &io::Error) -> bool {
+    matches!(
+        error.kind(),
+        io::ErrorKind::BrokenPipe | io::ErrorKind::ConnectionReset
+    )
+}
+
+/// Returns `true` for the events that keep the object and buffer
+/// bookkeeping of the connection in sync with the compositor.
+///
+/// Hooks see them but cannot veto them: dropping a `wl_display.delete_id`
+/// would leak its ID, a `wl_display.error` would go unreported, and a
+/// dropped `wl_callback.done` or `wl_buffer.release` would leave roundtrips
+/// waiting and buffers busy forever.
+fn is_bookkeeping_event(interface: WlObjectId, opcode: u16) -> bool {
+    match interface {
+        WlObjectId::Display => true,
+        WlObjectId::Callback => matches!(
+            callback::event::Opcode::try_from(opcode),
+            Ok(callback::event::Opcode::Done)
+        ),
+        WlObjectId::Buffer => matches!(
+            buffer::event::Opcode::try_from(opcode),
+            Ok(buffer::event::Opcode::Release)
+        ),
+        _ => false,
+    }
+}
+
+/// Checks that the interface of the target object defines the event, in the
+/// version the object was created with.
+fn validate_event(header: &WlMessageHeader, object: ObjectEntry) -> Result<(), InvalidEvent> {
+    let (object_id, interface, opcode) = (header.object_id, object.interface, header.opcode);
+
+    let Some(since) = interface.event_since(opcode) else {
+        return Err(InvalidEvent::UnknownOpcode {
+            object_id,
+            interface,
+            opcode,
+        });
+    };
+    if since > object.version {
+        return Err(InvalidEvent::VersionMismatch {
+            object_id,
+            interface,
+            opcode,
+            since,
+            version: object.version,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+
+    use super::*;
+    use crate::protocol::message::InvalidHeader;
+    use crate::protocol::types::WlArray;
+    use crate::server::HeadlessCompositor;
+
+    #[test]
+    fn connections_and_proxies_are_send_and_sync() {
+        fn send_sync<T: Send + Sync>() {}
+        send_sync::<Connection>();
+        send_sync::<crate::protocol::surface::Surface>();
+        send_sync::<crate::window::Window>();
+    }
+
+    #[test]
+    fn requests_race_a_dispatching_thread() {
+        use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+        use crate::protocol::compositor::Compositor;
+
+        let compositor = HeadlessCompositor::spawn().unwrap();
+        let conn = compositor.connect().unwrap();
+        let wl_compositor = Compositor::bind(&conn).unwrap();
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let dispatcher = std::thread::spawn({
+            let (conn, stop) = (conn.clone(), stop.clone());
+            move || {
+                while !stop.load(Ordering::SeqCst) {
+                    conn.dispatch().unwrap();
+                }
+            }
+        });
+
+        // Each thread creates and commits surfaces, flushing or roundtripping
+        // while the dispatcher waits for and handles their frame callbacks
+        let frames = Arc::new(AtomicUsize::new(0));
+        let workers: Vec<_> = (0..4)
+            .map(|worker| {
+                let (conn, wl_compositor, frames) =
+                    (conn.clone(), wl_compositor.clone(), frames.clone());
+                std::thread::spawn(move || {
+                    let mut surfaces = Vec::new();
+                    for i in 0..25 {
+                        let surface = wl_compositor.create_surface().unwrap();
+                        let frames = frames.clone();
+                        surface
+                            .request_frame(move |_, _| {
+                                frames.fetch_add(1, Ordering::SeqCst);
+                            })
+                            .unwrap();
+                        surface.commit().unwrap();
+                        surface.commit().unwrap();
+                        if (worker + i) % 2 == 0 {
+                            conn.roundtrip().unwrap();
+                        } else {
+                            conn.flush().unwrap();
+                        }
+                        surfaces.push(surface);
+                    }
+                    conn.roundtrip().unwrap();
+                    surfaces
+                })
+            })
+            .collect();
+        let surfaces: Vec<_> = workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap())
+            .collect();
+
+        stop.store(true, Ordering::SeqCst);
+        conn.loop_handle().wake();
+        dispatcher.join().unwrap();
+
+        assert_eq!(frames.load(Ordering::SeqCst), 100);
+        let committed = compositor.surfaces();
+        assert_eq!(committed.len(), surfaces.len());
+        assert!(committed.iter().all(|surface| surface.commits == 2));
+    }
+
+    #[test]
+    fn watched_fd_is_serviced_until_declined() {
+        let (client, _compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+
+        let (read, write) = sys::pipe().unwrap();
+        let mut write = std::fs::File::from(write);
+        write.write_all(b"ping").unwrap();
+
+        let received = Arc::new(Lock::new(Vec::new()));
+        let sink = received.clone();
+        conn.watch_readable(
+            read,
+            Box::new(move |_, fd| {
+                let mut buf = [0u8; 16];
+                let read_len = sys::read(fd, &mut buf).unwrap();
+                sink.borrow_mut().extend_from_slice(&buf[..read_len]);
+                read_len > 0
+            }),
+        );
+
+        conn.dispatch().unwrap();
+        assert_eq!(*received.borrow(), b"ping");
+
+        drop(write);
+        conn.dispatch().unwrap();
+        assert!(conn.state.borrow().fd_watches.is_empty());
+    }
+
+    #[test]
+    fn supports_reports_the_highest_advertised_version() {
+        let (client, _compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+
+        for (name, interface, version) in [
+            (1, "wl_compositor", 6),
+            (2, "wl_output", 4),
+            (3, "wl_output", 3),
+            (4, "zwlr_layer_shell_v1", 4),
+            (5, "wp_viewporter", 1),
+        ] {
+            conn.insert_global(Global {
+                name: WlUInt(name),
+                interface: WlString::new(interface).unwrap(),
+                version: WlUInt(version),
+            });
+        }
+
+        assert_eq!(conn.supports("zwlr_layer_shell_v1"), Some(4));
+        assert_eq!(conn.supports("wl_output"), Some(4));
+        assert_eq!(conn.supports("xdg_wm_base"), None);
+
+        let caps = conn.capabilities();
+        assert_eq!(caps.compositor, Some(6));
+        assert_eq!(caps.outputs, 2);
+        assert!(caps.has(WlObjectId::Viewporter));
+        assert!(caps.has(WlObjectId::Output));
+        assert!(!caps.has(WlObjectId::Seat));
+        assert_eq!(caps.version(WlObjectId::XdgWmBase), None);
+    }
+
+    #[test]
+    fn wake_interrupts_blocking_dispatch() {
+        let (client, _compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+
+        let handle = conn.loop_handle();
+        let worker = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(20));
+            handle.wake();
+        });
+
+        // Nothing arrives on the socket, so only the wake-up ends the wait
+        conn.dispatch().unwrap();
+        worker.join().unwrap();
+    }
+
+    #[test]
+    fn timers_run_in_deadline_order_unless_cancelled() {
+        let (client, _compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let handle = conn.loop_handle();
+        let now = Instant::now();
+        for (delay, label) in [(20, "late"), (10, "early"), (15, "cancelled")] {
+            let sender = sender.clone();
+            let id = handle.add_timer(now + Duration::from_millis(delay), move |_| {
+                sender.send(label).unwrap();
+            });
+            if label == "cancelled" {
+                assert!(handle.cancel_timer(id));
+            }
+        }
+
+        while Instant::now() < now + Duration::from_millis(20) {
+            conn.dispatch().unwrap();
+        }
+        assert_eq!(receiver.try_iter().collect::<Vec<_>>(), ["early", "late"]);
+    }
+
+    #[test]
+    fn deferred_callbacks_run_once_per_dispatch() {
+        let (client, _compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let handle = conn.loop_handle();
+        for label in ["first", "second"] {
+            let sender = sender.clone();
+            handle.defer(move |conn| {
+                sender.send(label).unwrap();
+                let sender = sender.clone();
+                conn.loop_handle()
+                    .defer(move |_| sender.send("nested").unwrap());
+            });
+        }
+
+        conn.dispatch().unwrap();
+        assert_eq!(receiver.try_iter().collect::<Vec<_>>(), ["first", "second"]);
+
+        conn.dispatch().unwrap();
+        assert_eq!(
+            receiver.try_iter().collect::<Vec<_>>(),
+            ["nested", "nested"]
+        );
+    }
+
+    #[test]
+    fn dropping_the_connection_flushes_queued_requests() {
+        let (client, mut compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+
+        drop(conn);
+
+        let mut received = Vec::new();
+        compositor.read_to_end(&mut received).unwrap();
+        let header =
+            WlMessageHeader::decode(&received, crate::protocol::endian::Endian::NATIVE).unwrap();
+        assert_eq!(header.object_id, 1);
+        assert_eq!(header.size as usize, received.len());
+    }
+
+    #[test]
+    fn non_blocking_flush_queues_up_to_the_limit() {
+        let (client, compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+        conn.set_send_mode(SendMode::NonBlocking);
+        conn.set_send_buffer_limit(1 << 20);
+
+        // Far more than the socket buffer takes while nobody reads
+        let request = [0u8; 4096];
+        for _ in 0..256 {
+            conn.send_request(1, 0u16, &WlArray::new(&request)).unwrap();
+        }
+        conn.flush().unwrap();
+        let queued = conn.state.borrow().out_buf.len();
+        assert!(queued > 0);
+
+        conn.set_send_buffer_limit(queued - 1);
+        let error = conn.flush().unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<SendBufferFull>(),
+            Some(&SendBufferFull { queued })
+        );
+        drop(compositor);
+    }
+
+    #[test]
+    fn descriptors_are_sent_in_batches() {
+        let (client, compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+        conn.flush().unwrap();
+        let mut buf = [0u8; 4096];
+        sys::recv_with_fds(compositor.as_fd(), &mut buf, &mut Vec::new()).unwrap();
+
+        for _ in 0..sys::MAX_FDS_OUT + 2 {
+            let (read, _write) = sys::pipe().unwrap();
+            conn.send_request_with_fds(1, 0u16, &(), vec![read])
+                .unwrap();
+        }
+        conn.flush().unwrap();
+
+        let mut batches = Vec::new();
+        let mut received = 0;
+        while received < (sys::MAX_FDS_OUT + 2) * WL_MESSAGE_HEADER_LEN {
+            let mut fds = Vec::new();
+            received += sys::recv_with_fds(compositor.as_fd(), &mut buf, &mut fds).unwrap();
+            batches.push(fds.len());
+        }
+        assert_eq!(batches, [sys::MAX_FDS_OUT, 2]);
+    }
+
+    #[test]
+    fn malformed_and_oversized_headers_fail_the_dispatch() {
+        let (client, mut compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+        conn.set_max_message_len(16);
+
+        // `wl_display.delete_id` is 12 bytes long, and fits
+        let bytes: Vec<u8> = WlMessage::new(1, 1, &2u32.to_ne_bytes()).unwrap().into();
+        compositor.write_all(&bytes).unwrap();
+        conn.dispatch().unwrap();
+
+        let bytes: Vec<u8> = WlMessage::new(1, 1, &[0; 12]).unwrap().into();
+        compositor.write_all(&bytes).unwrap();
+        let error = conn.dispatch().unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<InvalidHeader>(),
+            Some(InvalidHeader::TooLarge {
+                size: 20,
+                max_len: 16,
+                ..
+            })
+        ));
+
+        // Limits below the header length still accept events without arguments
+        let (client, mut compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+        conn.set_max_message_len(0);
+        conn.set_unknown_object_policy(UnknownObjectPolicy::LogAndSkip);
+        let bytes: Vec<u8> = WlMessage::new(3, 0, &[]).unwrap().into();
+        compositor.write_all(&bytes).unwrap();
+        conn.dispatch().unwrap();
+
+        let bytes: Vec<u8> = WlMessage::new(1, 1, &2u32.to_ne_bytes()).unwrap().into();
+        compositor.write_all(&bytes).unwrap();
+        let error = conn.dispatch().unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<InvalidHeader>(),
+            Some(InvalidHeader::TooLarge {
+                size: 12,
+                max_len: WL_MESSAGE_HEADER_LEN,
+                ..
+            })
+        ));
+
+        let (client, mut compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+        let malformed = WlMessageHeader {
+            object_id: 1,
+            opcode: 1,
+            size: 10,
+        };
+        compositor
+            .write_all(&malformed.encode(crate::protocol::endian::Endian::NATIVE))
+            .unwrap();
+        let error = conn.dispatch().unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<InvalidHeader>(),
+            Some(InvalidHeader::Malformed { size: 10, .. })
+        ));
+    }
+
+    #[test]
+    fn invalid_events_follow_the_policy() {
+        let (client, mut compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+
+        let unknown = WlMessageHeader {
+            object_id: 1,
+            opcode: 9,
+            size: WL_MESSAGE_HEADER_LEN as u16,
+        };
+        let bytes = unknown.encode(crate::protocol::endian::Endian::NATIVE);
+
+        compositor.write_all(&bytes).unwrap();
+        let error = conn.dispatch().unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<InvalidEvent>(),
+            Some(InvalidEvent::UnknownOpcode { opcode: 9, .. })
+        ));
+
+        conn.set_invalid_event_policy(InvalidEventPolicy::Skip);
+        compositor.write_all(&bytes).unwrap();
+        conn.dispatch().unwrap();
+    }
+
+    #[test]
+    fn events_for_unknown_objects_follow_the_policy() {
+        let (client, mut compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+
+        // `wl_callback.done` for the next client ID, which is not allocated yet
+        let bytes: Vec<u8> = WlMessage::new(3, 0, &7u32.to_ne_bytes()).unwrap().into();
+
+        compositor.write_all(&bytes).unwrap();
+        let error = conn.dispatch().unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<UnknownObject>(),
+            Some(&UnknownObject {
+                object_id: 3,
+                opcode: 0
+            })
+        );
+
+        conn.set_unknown_object_policy(UnknownObjectPolicy::LogAndSkip);
+        compositor.write_all(&bytes).unwrap();
+        conn.dispatch().unwrap();
+        let skipped = Arc::new(Lock::new(Vec::new()));
+        conn.set_unknown_object_logger({
+            let skipped = skipped.clone();
+            move |_, error| skipped.borrow_mut().push(*error)
+        });
+        compositor.write_all(&bytes).unwrap();
+        conn.dispatch().unwrap();
+        assert_eq!(
+            *skipped.borrow(),
+            [UnknownObject {
+                object_id: 3,
+                opcode: 0
+            }]
+        );
+
+        conn.set_unknown_object_policy(UnknownObjectPolicy::Queue);
+        compositor.write_all(&bytes).unwrap();
+        conn.dispatch().unwrap();
+
+        let data = Arc::new(LockCell::new(None));
+        let id = conn.new_object(WlObjectId::Callback, 1).unwrap();
+        assert_eq!(id, 3);
+        conn.add_callback(id, {
+            let data = data.clone();
+            Box::new(move |_, callback_data| data.set(Some(callback_data)))
+        });
+
+        conn.loop_handle().wake();
+        conn.dispatch().unwrap();
+        assert_eq!(data.get(), Some(7));
+    }
+
+    #[test]
+    fn user_data_lives_until_the_object_is_deleted() {
+        let (client, mut compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+
+        let id = conn.new_object(WlObjectId::Surface, 1).unwrap();
+        assert!(conn.set_user_data(id + 1, "window").is_err());
+        conn.set_user_data(id, "window").unwrap();
+        assert_eq!(conn.user_data::<u32>(id), None);
+        assert_eq!(conn.user_data::<&str>(id).as_deref(), Some(&"window"));
+
+        assert_eq!(conn.take_user_data::<u32>(id), None);
+        assert_eq!(conn.take_user_data::<&str>(id).as_deref(), Some(&"window"));
+        assert_eq!(conn.user_data::<&str>(id), None);
+
+        conn.set_user_data(id, 42u32).unwrap();
+        // `wl_display.delete_id` for the surface
+        let bytes: Vec<u8> = WlMessage::new(1, 1, &id.to_ne_bytes()).unwrap().into();
+        compositor.write_all(&bytes).unwrap();
+        conn.dispatch().unwrap();
+        assert_eq!(conn.user_data::<u32>(id), None);
+    }
+
+    #[test]
+    fn lost_connection_is_replaced_by_the_reconnect_handler() {
+        let path = std::env::temp_dir().join(format!("wayland-reconnect-{}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = std::os::unix::net::UnixListener::bind(&path).unwrap();
+
+        let conn = Connection::connect_to(&path).unwrap();
+        let (first, _) = listener.accept().unwrap();
+        let rebuilt = Arc::new(LockCell::new(0));
+        let counter = rebuilt.clone();
+        conn.set_reconnect_handler(move |conn| {
+            assert_eq!(conn.globals()[0].interface.as_str(), "wl_compositor");
+            counter.set(counter.get() + 1);
+            Ok(())
+        });
+
+        // The restarted compositor advertises a global and answers the sync
+        // of the new connection, whose callback gets ID 3
+        let compositor = std::thread::spawn(move || {
+            let (mut second, _) = listener.accept().unwrap();
+            let mut global = 1u32.to_ne_bytes().to_vec();
+            global.extend(WlString::new("wl_compositor").unwrap().to_bytes());
+            global.extend(6u32.to_ne_bytes());
+            for (id, opcode, data) in [(2, 0, global), (3, 0, 0u32.to_ne_bytes().to_vec())] {
+                let bytes: Vec<u8> = WlMessage::new(id, opcode, &data).unwrap().into();
+                second.write_all(&bytes).unwrap();
+            }
+            second
+        });
+
+        let surface = conn.new_object(WlObjectId::Surface, 6).unwrap();
+        drop(first);
+        conn.dispatch().unwrap();
+        let _second = compositor.join().unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(rebuilt.get(), 1);
+        let replacement = conn.reconnect().unwrap();
+        assert_eq!(replacement.globals().len(), 1);
+        // Requests of the old proxies are discarded
+        conn.send_request(surface, 0u16, &()).unwrap();
+        assert!(conn.state.borrow().out_buf.is_empty());
+    }
+
+    #[test]
+    fn connections_to_two_compositors_are_independent() {
+        let (host_compositor, nested_compositor) = (
+            HeadlessCompositor::spawn().unwrap(),
+            HeadlessCompositor::spawn().unwrap(),
+        );
+        let host = host_compositor.connect().unwrap();
+        let nested = nested_compositor.connect().unwrap();
+        nested.roundtrip().unwrap();
+        host.roundtrip().unwrap();
+
+        // Both advertise the same globals, each to its own client
+        let names = |conn: &Connection| {
+            let mut names: Vec<u32> = conn
+                .globals()
+                .iter()
+                .map(|global| global.name.get())
+                .collect();
+            names.sort();
+            names
+        };
+        assert!(!host.same_connection(&nested));
+        assert_eq!(names(&host), [1, 2, 3]);
+        assert_eq!(names(&nested), names(&host));
+        assert_eq!(
+            host.new_object(WlObjectId::Surface, 1).unwrap(),
+            nested.new_object(WlObjectId::Surface, 1).unwrap()
+        );
+        assert!(
+            host.check_same_connection(&nested, WlObjectId::Surface, 3)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn requests_from_other_threads_go_out_in_order() {
+        let (client, mut compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+        conn.flush().unwrap();
+        let surface = conn.new_object(WlObjectId::Surface, 6).unwrap();
+        let destroyed = conn.new_object(WlObjectId::Surface, 6).unwrap();
+        conn.send_request(destroyed, 0u16, &()).unwrap();
+        conn.flush().unwrap();
+
+        let senders: Vec<_> = (0..4u32)
+            .map(|thread| {
+                let handle = conn.loop_handle();
+                std::thread::spawn(move || {
+                    for sequence in 0..100u32 {
+                        let args = WlArray::new(&[thread as u8, sequence as u8]);
+                        handle.send_request(surface, 6u16, &args).unwrap();
+                        // Dropped, as the compositor would reject it
+                        handle.send_request(destroyed, 6u16, &()).unwrap();
+                    }
+                })
+            })
+            .collect();
+        // Keep dispatching while the threads send, without blocking past
+        // the last wake-up
+        while senders.iter().any(|sender| !sender.is_finished()) {
+            let deadline = Instant::now() + Duration::from_millis(10);
+            conn.dispatch_until(None, Some(deadline)).unwrap();
+        }
+        for sender in senders {
+            sender.join().unwrap();
+        }
+        conn.flush().unwrap();
+        drop(conn);
+
+        let mut received = Vec::new();
+        compositor.read_to_end(&mut received).unwrap();
+        // Skip `wl_display.get_registry` and the destructor sent before the
+        // threads started
+        let mut offset = 2 * WL_MESSAGE_HEADER_LEN + 4;
+        let mut next_sequence = [0u8; 4];
+        while offset < received.len() {
+            let header = WlMessageHeader::decode(
+                &received[offset..],
+                crate::protocol::endian::Endian::NATIVE,
+            )
+            .unwrap();
+            assert_eq!(header.object_id, surface);
+            let (thread, sequence) = (received[offset + 12], received[offset + 13]);
+            assert_eq!(sequence, next_sequence[thread as usize]);
+            next_sequence[thread as usize] += 1;
+            offset += header.size as usize;
+        }
+        assert_eq!(next_sequence, [100; 4]);
+    }
+
+    #[test]
+    fn requests_from_other_threads_never_reach_a_reused_id() {
+        let (client, mut compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+        conn.flush().unwrap();
+        let mut discarded = vec![0; 64];
+        let _ = compositor.read(&mut discarded).unwrap();
+
+        let surface = conn.new_object(WlObjectId::Surface, 6).unwrap();
+        conn.send_request(surface, 0u16, &()).unwrap();
+        conn.flush().unwrap();
+        let _ = compositor.read(&mut discarded).unwrap();
+
+        let delete_id: Vec<u8> = WlMessage::new(1, 1, &surface.to_ne_bytes()).unwrap().into();
+        compositor.write_all(&delete_id).unwrap();
+        conn.dispatch().unwrap();
+        // `wl_surface.commit` from a thread that missed the destruction,
+        // picked up only once the ID was reused
+        conn.loop_handle().send_request(surface, 6u16, &()).unwrap();
+        let buffer = conn.new_object(WlObjectId::Buffer, 1).unwrap();
+        assert_eq!(buffer, surface);
+
+        // Opcode 6 does not exist on `wl_buffer`
+        conn.flush().unwrap();
+        drop(conn);
+        let mut received = Vec::new();
+        compositor.read_to_end(&mut received).unwrap();
+        assert!(received.is_empty());
+    }
+
+    #[test]
+    fn closed_connection_fails_without_reconnect_handler() {
+        let (client, compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+        drop(compositor);
+
+        assert!(conn.dispatch().unwrap_err().is::<Disconnected>());
+        assert!(conn.reconnect().is_err());
+    }
+
+    #[test]
+    fn stats_count_traffic_in_both_directions() {
+        let (client, mut compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+        // Leave out `wl_display.get_registry`, queued when connecting
+        conn.flush().unwrap();
+        conn.reset_stats();
+
+        let (read, _write) = sys::pipe().unwrap();
+        conn.send_request_with_fds(1, 0u16, &(), vec![read])
+            .unwrap();
+        conn.flush().unwrap();
+
+        // Two `wl_display.delete_id` events for unused IDs
+        for id in [10u32, 11] {
+            let bytes: Vec<u8> = WlMessage::new(1, 1, &id.to_ne_bytes()).unwrap().into();
+            compositor.write_all(&bytes).unwrap();
+        }
+        conn.dispatch().unwrap();
+
+        let stats = conn.stats();
+        assert_eq!(
+            (stats.messages_sent, stats.bytes_sent, stats.fds_sent),
+            (1, 8, 1)
+        );
+        assert_eq!((stats.messages_received, stats.bytes_received), (2, 24));
+        assert_eq!(stats.dispatched_to(WlObjectId::Display), 2);
+        assert_eq!(stats.max_send_queue, 8);
+    }
+
+    #[test]
+    fn hooks_see_every_event_and_can_veto_it() {
+        let (client, mut compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+        let shm = conn.new_object(WlObjectId::Shm, 1).unwrap();
+        let seen = Arc::new(Lock::new(Vec::new()));
+        let handled = Arc::new(Lock::new(Vec::new()));
+
+        let pre = conn.add_pre_dispatch_hook({
+            let seen = seen.clone();
+            move |_, interface, message| {
+                seen.borrow_mut().push((interface, message.opcode()));
+                if message.args() == 11u32.to_ne_bytes() {
+                    HookAction::Veto
+                } else {
+                    HookAction::Continue
+                }
+            }
+        });
+        conn.add_post_dispatch_hook(WlObjectId::Shm, {
+            let handled = handled.clone();
+            move |_, message| handled.borrow_mut().push(message.args().to_vec())
+        });
+
+        // Three `wl_shm.format` events
+        for format in [10u32, 11, 12] {
+            let bytes: Vec<u8> = WlMessage::new(shm, 0, &format.to_ne_bytes())
+                .unwrap()
+                .into();
+            compositor.write_all(&bytes).unwrap();
+        }
+        conn.dispatch().unwrap();
+
+        assert_eq!(seen.borrow().len(), 3);
+        assert!(
+            seen.borrow()
+                .iter()
+                .all(|&seen| seen == (WlObjectId::Shm, 0))
+        );
+        assert_eq!(
+            *handled.borrow(),
+            [10u32.to_ne_bytes().to_vec(), 12u32.to_ne_bytes().to_vec()]
+        );
+        assert_eq!(conn.stats().dispatched_to(WlObjectId::Shm), 2);
+
+        conn.remove_hook(pre);
+        let bytes: Vec<u8> = WlMessage::new(shm, 0, &11u32.to_ne_bytes()).unwrap().into();
+        compositor.write_all(&bytes).unwrap();
+        conn.dispatch().unwrap();
+
+        assert_eq!(seen.borrow().len(), 3);
+        assert_eq!(handled.borrow().len(), 3);
+    }
+
+    #[test]
+    fn vetoed_events_close_their_fds_and_bookkeeping_is_never_vetoed() {
+        let (client, compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+        let keyboard = conn.new_object(WlObjectId::Keyboard, 1).unwrap();
+        let buffer = conn.new_object(WlObjectId::Buffer, 1).unwrap();
+        conn.add_pre_dispatch_hook(|_, _, _| HookAction::Veto);
+
+        // A `wl_keyboard.keymap` event carrying its fd
+        let (read, _write) = sys::pipe().unwrap();
+        let keymap: Vec<u8> =
+            WlMessage::new(keyboard, 0, &[1u32, 0].map(u32::to_ne_bytes).concat())
+                .unwrap()
+                .into();
+        sys::send_with_fds(compositor.as_fd(), &keymap, &[read.as_fd()]).unwrap();
+        conn.dispatch().unwrap();
+
+        assert!(conn.state.borrow().in_fds.is_empty());
+        assert_eq!(conn.stats().dispatched_to(WlObjectId::Keyboard), 0);
+
+        // `wl_buffer.release` and `wl_display.delete_id` still go through
+        let release: Vec<u8> = WlMessage::new(buffer, 0, &[]).unwrap().into();
+        let delete_id: Vec<u8> = WlMessage::new(1, 1, &buffer.to_ne_bytes()).unwrap().into();
+        sys::send_with_fds(compositor.as_fd(), &[release, delete_id].concat(), &[]).unwrap();
+        conn.dispatch().unwrap();
+
+        assert_eq!(conn.stats().dispatched_to(WlObjectId::Buffer), 1);
+        assert_eq!(conn.stats().dispatched_to(WlObjectId::Display), 1);
+    }
+
+    #[test]
+    fn dump_lists_live_objects_with_their_user_data() {
+        let (client, _compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+
+        let surface = conn.new_object(WlObjectId::Surface, 6).unwrap();
+        let buffer = conn.new_object(WlObjectId::Buffer, 1).unwrap();
+        conn.set_user_data(surface, 7u32).unwrap();
+        conn.send_request(buffer, 0u16, &()).unwrap();
+
+        let dump = conn.dump_objects();
+        let objects: Vec<_> = dump
+            .objects()
+            .iter()
+            .map(|object| (object.id, object.interface, object.user_data))
+            .collect();
+        assert_eq!(
+            objects,
+            [
+                (1, WlObjectId::Display, None),
+                (2, WlObjectId::Registry, None),
+                (surface, WlObjectId::Surface, Some("u32")),
+            ]
+        );
+        assert_eq!(
+            format!("{dump:?}"),
+            "3 live objects\n  wl_display#1 v1\n  wl_registry#2 v1\n  wl_surface#3 v6 [u32]"
+        );
+    }
+
+    #[test]
+    fn events_newer_than_the_object_are_rejected() {
+        let keyboard = ObjectEntry {
+            interface: WlObjectId::Keyboard,
+            version: 3,
+        };
+        let repeat_info = WlMessageHeader {
+            object_id: 5,
+            opcode: 5,
+            size: 16,
+        };
+
+        assert_eq!(
+            validate_event(&repeat_info, keyboard),
+            Err(InvalidEvent::VersionMismatch {
+                object_id: 5,
+                interface: WlObjectId::Keyboard,
+                opcode: 5,
+                since: 4,
+                version: 3,
+            })
+        );
+        assert_eq!(
+            validate_event(
+                &repeat_info,
+                ObjectEntry {
+                    version: 4,
+                    ..keyboard
+                }
+            ),
+            Ok(())
+        );
+    }
+
+    /// A recording destination that stays readable after being handed over.
+    #[derive(Clone, Default)]
+    struct SharedBuf(Arc<Lock<Vec<u8>>>);
+
+    impl Write for SharedBuf {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn recorded_sessions_replay_through_the_dispatcher() {
+        let (client, mut compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+        let recorded = SharedBuf::default();
+        conn.record_to(recorded.clone(), RecordFormat::Pcapng)
+            .unwrap();
+
+        // `wl_registry.global` for `wl_shm`, then `wl_callback.done` for the sync
+        let mut global = [1u32, 7].map(u32::to_ne_bytes).concat();
+        global.extend_from_slice(b"wl_shm\0\0");
+        global.extend_from_slice(&1u32.to_ne_bytes());
+        for (object_id, data) in [(2, global), (3, 0u32.to_ne_bytes().to_vec())] {
+            let bytes: Vec<u8> = WlMessage::new(object_id, 0, &data).unwrap().into();
+            compositor.write_all(&bytes).unwrap();
+        }
+        conn.roundtrip().unwrap();
+        conn.stop_recording().unwrap();
+
+        let recording = Recording::read_pcapng(recorded.0.borrow().as_slice()).unwrap();
+        assert_eq!(recording.messages().len(), 4);
+
+        let replay = Connection::replay(recording.clone()).unwrap();
+        replay.roundtrip().unwrap();
+        let names = |conn: &Connection| -> Vec<u32> {
+            conn.globals()
+                .iter()
+                .map(|global| global.name.get())
+                .collect()
+        };
+        assert_eq!(names(&replay), [1]);
+        assert_eq!(names(&replay), names(&conn));
+        assert!(replay.dispatch().is_err());
+
+        let replay = Connection::replay(recording).unwrap();
+        replay.send_request(5, 3u16, &()).unwrap();
+        let error = replay.flush().unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<record::ReplayDiverged>(),
+            Some(&record::ReplayDiverged {
+                index: 1,
+                expected: Some((1, 0)),
+                sent: (5, 3),
+            })
+        );
+    }
+
+    #[test]
+    fn writable_fd_is_closed_when_declined() {
+        let (client, _compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+
+        let (read, write) = sys::pipe().unwrap();
+        conn.watch_writable(
+            write,
+            Box::new(|_, fd| {
+                sys::write(fd, b"pong").unwrap();
+                false
+            }),
+        );
+
+        conn.dispatch().unwrap();
+        assert!(conn.state.borrow().fd_watches.is_empty());
+
+        let mut received = Vec::new();
+        std::fs::File::from(read)
+            .read_to_end(&mut received)
+            .unwrap();
+        assert_eq!(received, b"pong");
+    }
+
+    #[test]
+    fn peer_credentials_name_the_process_at_the_other_end() {
+        use std::os::unix::fs::MetadataExt;
+
+        let (client, _compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+
+        let credentials = conn.peer_credentials().unwrap();
+        let process = std::fs::metadata("/proc/self").unwrap();
+        assert_eq!(credentials.pid, std::process::id() as i32);
+        assert_eq!(
+            (credentials.uid, credentials.gid),
+            (process.uid(), process.gid())
+        );
+    }
+}
@@ -0,0 +1,291 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{self, Debug, Formatter},
+};
+
+use crate::{
+    error::{Result, anyhow},
+    protocol::WlObjectId,
+};
+
+/// The object ID reserved for the `wl_display` singleton.
+pub const WL_DISPLAY_ID: u32 = 1;
+
+/// The highest object ID a client may allocate.
+///
+/// IDs from `0xFF000000` upwards are reserved for objects created by the server.
+pub(crate) const WL_CLIENT_ID_MAX: u32 = 0xFEFF_FFFF;
+
+/// Bookkeeping for a single live protocol object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ObjectEntry {
+    /// The interface implemented by the object.
+    pub interface: WlObjectId,
+    /// The interface version the object was created with.
+    pub version: u32,
+}
+
+/// A live protocol object, as listed by
+/// [`Connection::dump_objects`](crate::connection::Connection::dump_objects).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ObjectInfo {
+    /// The object ID.
+    pub id: u32,
+    /// The interface implemented by the object.
+    pub interface: WlObjectId,
+    /// The interface version the object was created with.
+    pub version: u32,
+    /// The type name of the application data attached with
+    /// [`Connection::set_user_data`](crate::connection::Connection::set_user_data),
+    /// if any.
+    pub user_data: Option<&'static str>,
+}
+
+/// A snapshot of the live objects of a connection, in ascending ID order.
+///
+/// The [`Debug`] output lists one object per line, in the
+/// `interface#id` notation of `WAYLAND_DEBUG`, so a dump can be logged as is
+/// when looking for leaked proxies or the object behind an "invalid object"
+/// error:
+///
+/// ```text
+/// 3 live objects
+///   wl_display#1 v1
+///   wl_registry#2 v1
+///   wl_surface#3 v6 [my_app::Window]
+/// ```
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct ObjectDump {
+    /// The objects, sorted by ID.
+    objects: Vec<ObjectInfo>,
+}
+
+impl ObjectDump {
+    /// Wraps the objects of a snapshot, sorted by ID.
+    pub(crate) fn new(objects: Vec<ObjectInfo>) -> ObjectDump {
+        ObjectDump { objects }
+    }
+
+    /// Returns the objects, in ascending ID order.
+    pub fn objects(&self) -> &[ObjectInfo] {
+        &self.objects
+    }
+
+    /// Returns the number of live objects.
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    /// Returns `true` if there are no live objects.
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    /// Returns the live objects implementing `interface`.
+    pub fn of(&self, interface: WlObjectId) -> impl Iterator<Item = &ObjectInfo> {
+        self.objects
+            .iter()
+            .filter(move |object| object.interface == interface)
+    }
+}
+
+impl IntoIterator for ObjectDump {
+    type Item = ObjectInfo;
+    type IntoIter = std::vec::IntoIter<ObjectInfo>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.objects.into_iter()
+    }
+}
+
+impl Debug for ObjectDump {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} live objects", self.objects.len())?;
+        for object in &self.objects {
+            write!(
+                f,
+                "\n  {}#{} v{}",
+                object.interface.interface_name(),
+                object.id,
+                object.version
+            )?;
+            if let Some(user_data) = object.user_data {
+                write!(f, " [{user_data}]")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Maps object IDs to the interface they implement and hands out new IDs.
+///
+/// IDs are only returned to the free list once the compositor confirms the
+/// deletion with `wl_display.delete_id`, so that events still in flight for a
+/// destroyed object can never be routed to a new object reusing its ID.
+/// Until then, a client object whose destructor request was sent stays in
+/// the map as a zombie: events are still routed to its interface, but it no
+/// longer counts as live.
+pub(crate) struct ObjectMap {
+    /// All objects the client currently knows about, keyed by object ID.
+    objects: HashMap<u32, ObjectEntry>,
+    /// Client IDs whose destructor request was sent, awaiting `delete_id`.
+    zombies: HashSet<u32>,
+    /// Client IDs released by `delete_id` that may be handed out again.
+    free_ids: Vec<u32>,
+    /// The next never-used client ID.
+    next_id: u32,
+}
+
+impl ObjectMap {
+    /// Creates a map that only contains the `wl_display` singleton.
+    pub(crate) fn new() -> Self {
+        let mut objects = HashMap::new();
+        objects.insert(
+            WL_DISPLAY_ID,
+            ObjectEntry {
+                interface: WlObjectId::Display,
+                version: 1,
+            },
+        );
+
+        Self {
+            objects,
+            zombies: HashSet::new(),
+            free_ids: Vec::new(),
+            next_id: WL_DISPLAY_ID + 1,
+        }
+    }
+
+    /// Allocates an ID for a new client-created object and records its interface.
+    ///
+    /// # Errors
+    /// Returns an error if the client ID space is exhausted.
+    pub(crate) fn allocate(&mut self, interface: WlObjectId, version: u32) -> Result<u32> {
+        let id = match self.free_ids.pop() {
+            Some(id) => id,
+            None => {
+                if self.next_id > WL_CLIENT_ID_MAX {
+                    return Err(anyhow!("Client object ID space exhausted"));
+                }
+                let id = self.next_id;
+                self.next_id += 1;
+                id
+            }
+        };
+
+        self.objects.insert(id, ObjectEntry { interface, version });
+
+        Ok(id)
+    }
+
+    /// Records an object created by the compositor through a `new_id` event argument.
+    ///
+    /// # Errors
+    /// Returns an error if `id` is outside the server ID range.
+    pub(crate) fn insert(&mut self, id: u32, interface: WlObjectId, version: u32) -> Result<()> {
+        if id <= WL_CLIENT_ID_MAX {
+            return Err(anyhow!(
+                "Compositor created {} with client-side ID {}",
+                interface.interface_name(),
+                id
+            ));
+        }
+
+        self.objects.insert(id, ObjectEntry { interface, version });
+
+        Ok(())
+    }
+
+    /// Looks up the entry of a live object.
+    pub(crate) fn get(&self, id: u32) -> Option<ObjectEntry> {
+        self.objects.get(&id).copied()
+    }
+
+    /// Returns `true` if the object exists and was not destroyed.
+    pub(crate) fn is_live(&self, id: u32) -> bool {
+        self.objects.contains_key(&id) && !self.zombies.contains(&id)
+    }
+
+    /// Returns the IDs of all live objects implementing `interface`, in ascending order.
+    pub(crate) fn ids_of(&self, interface: WlObjectId) -> Vec<u32> {
+        let mut ids: Vec<u32> = self
+            .objects
+            .iter()
+            .filter(|(id, entry)| entry.interface == interface && !self.zombies.contains(id))
+            .map(|(id, _)| *id)
+            .collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Returns all live objects, in ascending ID order.
+    pub(crate) fn live(&self) -> Vec<(u32, ObjectEntry)> {
+        let mut objects: Vec<(u32, ObjectEntry)> = self
+            .objects
+            .iter()
+            .filter(|(id, _)| !self.zombies.contains(id))
+            .map(|(id, entry)| (*id, *entry))
+            .collect();
+        objects.sort_unstable_by_key(|(id, _)| *id);
+        objects
+    }
+
+    /// Records that a destructor request was sent for an object.
+    ///
+    /// # Returns
+    /// `true` if the object must be forgotten right away: the compositor
+    /// only confirms the deletion of client-side IDs with `delete_id`, so
+    /// server-side objects never become zombies.
+    pub(crate) fn mark_destroyed(&mut self, id: u32) -> bool {
+        if id > WL_CLIENT_ID_MAX {
+            return true;
+        }
+
+        if self.objects.contains_key(&id) {
+            self.zombies.insert(id);
+        }
+        false
+    }
+
+    /// Forgets an object after the compositor acknowledged its deletion.
+    ///
+    /// Client-side IDs become available for reuse; server-side IDs are simply dropped.
+    pub(crate) fn remove(&mut self, id: u32) {
+        self.zombies.remove(&id);
+        if self.objects.remove(&id).is_some() && id <= WL_CLIENT_ID_MAX {
+            self.free_ids.push(id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn destroyed_ids_are_reused_only_after_deletion() {
+        let mut objects = ObjectMap::new();
+        let id = objects.allocate(WlObjectId::Surface, 6).unwrap();
+
+        assert!(!objects.mark_destroyed(id));
+        assert!(!objects.is_live(id));
+        assert!(objects.get(id).is_some());
+        assert!(objects.ids_of(WlObjectId::Surface).is_empty());
+        assert_ne!(objects.allocate(WlObjectId::Surface, 6).unwrap(), id);
+
+        objects.remove(id);
+        assert_eq!(objects.allocate(WlObjectId::Buffer, 1).unwrap(), id);
+        assert!(objects.is_live(id));
+    }
+
+    #[test]
+    fn server_objects_are_forgotten_on_destruction() {
+        let mut objects = ObjectMap::new();
+        objects
+            .insert(WL_CLIENT_ID_MAX + 1, WlObjectId::DataOffer, 3)
+            .unwrap();
+
+        assert!(objects.mark_destroyed(WL_CLIENT_ID_MAX + 1));
+    }
+}
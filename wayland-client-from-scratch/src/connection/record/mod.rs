@@ -0,0 +1,625 @@
+pub mod pcapng;
+
+use std::{
+    collections::VecDeque,
+    fmt::Display,
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Read, Write},
+    os::{fd::OwnedFd, unix::net::UnixStream},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    error::{Result, anyhow},
+    protocol::{
+        endian::Endian,
+        message::{WL_MESSAGE_HEADER_LEN, WL_MESSAGE_MAX_LEN, WlMessage, WlMessageHeader},
+    },
+};
+
+/// The start of the first line of a recording, followed by the byte order
+/// of the recorded messages.
+const RECORDING_MAGIC: &str = "# wayland-recording v1";
+
+/// The file format a [`Recording`] is written in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum RecordFormat {
+    /// Lines of hexadecimal messages, see [`Recording`].
+    #[default]
+    Text,
+    /// A pcapng capture, see [`Recording::write_pcapng`].
+    Pcapng,
+}
+
+/// Which way a recorded message travelled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// A request sent by the client.
+    Outbound,
+    /// An event received from the compositor.
+    Inbound,
+}
+
+impl Direction {
+    /// Returns the tag of the direction in the recording format.
+    fn tag(self) -> &'static str {
+        match self {
+            Direction::Outbound => "out",
+            Direction::Inbound => "in",
+        }
+    }
+}
+
+/// A message of a [`Recording`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedMessage {
+    /// Which way the message travelled.
+    direction: Direction,
+    /// The time since the recording started.
+    timestamp: Duration,
+    /// The whole message in the wire format, header included.
+    bytes: Vec<u8>,
+    /// The number of file descriptors passed along.
+    fds: usize,
+}
+
+impl RecordedMessage {
+    /// Creates a message from its wire `bytes`, header included.
+    ///
+    /// # Errors
+    /// Returns an error if the bytes are not exactly one message.
+    pub fn new(
+        direction: Direction,
+        timestamp: Duration,
+        bytes: Vec<u8>,
+        fds: usize,
+    ) -> Result<RecordedMessage> {
+        let parsed = WlMessage::try_from(bytes.as_slice())?;
+        if usize::from(parsed.header.size) != bytes.len() {
+            return Err(anyhow!(
+                "Recorded message is {} bytes long, but its header declares {}",
+                bytes.len(),
+                parsed.header.size
+            ));
+        }
+
+        Ok(RecordedMessage {
+            direction,
+            timestamp,
+            bytes,
+            fds,
+        })
+    }
+
+    /// Returns which way the message travelled.
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// Returns the time since the recording started: when a request was
+    /// queued, or when an event was read from the socket.
+    pub fn timestamp(&self) -> Duration {
+        self.timestamp
+    }
+
+    /// Returns the whole message in the wire format, header included.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Returns the number of file descriptors passed along, whose content is
+    /// not recorded.
+    ///
+    /// Received descriptors are counted on the first message completed by
+    /// the read that carried them, at or before the event they belong to.
+    pub fn fds(&self) -> usize {
+        self.fds
+    }
+
+    /// Returns the ID of the object the message was sent to or by.
+    pub fn object_id(&self) -> u32 {
+        self.header().object_id
+    }
+
+    /// Returns the opcode of the request or event.
+    pub fn opcode(&self) -> u16 {
+        self.header().opcode
+    }
+
+    /// Decodes the header, which [`RecordedMessage::new`] or the recorder
+    /// made sure is complete.
+    fn header(&self) -> WlMessageHeader {
+        WlMessageHeader::decode(&self.bytes, Endian::NATIVE)
+            .expect("recorded messages hold a complete header")
+    }
+}
+
+impl Display for RecordedMessage {
+    /// Formats the message as a line of the recording format, without the
+    /// line break: the timestamp in seconds, the direction, the number of
+    /// descriptors and the bytes in hexadecimal.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}.{:06} {} {} ",
+            self.timestamp.as_secs(),
+            self.timestamp.subsec_micros(),
+            self.direction.tag(),
+            self.fds
+        )?;
+        for byte in &self.bytes {
+            write!(f, "{byte:02x}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The wire traffic of a connection, recorded with
+/// [`Connection::record_to`](crate::connection::Connection::record_to) and
+/// replayed with [`Connection::replay`](crate::connection::Connection::replay).
+///
+/// Recordings are text files, so they can be reviewed and checked in along
+/// with the tests replaying them:
+///
+/// ```text
+/// # wayland-recording v1 little-endian
+/// 0.000012 out 0 01000000 00000c00 02000000
+/// 0.000480 in 0 02000000 00000c00 07000000
+/// ```
+///
+/// After the header line, every line is a message: the time since the
+/// recording started in seconds, `out` for requests or `in` for events,
+/// the number of file descriptors passed along, and the message bytes in
+/// hexadecimal. Whitespace within the bytes and lines starting with `#`
+/// are ignored.
+///
+/// To share traces with external tools, recordings are also written as
+/// pcapng captures with [`Recording::write_pcapng`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Recording {
+    /// The messages, in the order they were recorded.
+    messages: Vec<RecordedMessage>,
+}
+
+impl Recording {
+    /// Creates an empty recording.
+    pub fn new() -> Recording {
+        Recording::default()
+    }
+
+    /// Returns the messages, in the order they were recorded.
+    pub fn messages(&self) -> &[RecordedMessage] {
+        &self.messages
+    }
+
+    /// Appends a message.
+    pub fn push(&mut self, message: RecordedMessage) {
+        self.messages.push(message);
+    }
+
+    /// Loads the recording stored at `path`, in either [`RecordFormat`].
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read or is not a recording
+    /// made on a host of the same byte order.
+    pub fn load(path: impl AsRef<Path>) -> Result<Recording> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let block_type = pcapng::SECTION_HEADER_BLOCK.to_ne_bytes();
+        if reader.fill_buf()?.starts_with(&block_type) {
+            Recording::read_pcapng(reader)
+        } else {
+            Recording::read(reader)
+        }
+    }
+
+    /// Parses a recording from `reader`.
+    ///
+    /// # Errors
+    /// Returns an error if reading fails, a line is malformed, or the
+    /// messages were recorded on a host of the other byte order.
+    pub fn read(reader: impl BufRead) -> Result<Recording> {
+        let mut lines = reader.lines();
+
+        let header = lines.next().transpose()?.unwrap_or_default();
+        let byte_order = header
+            .strip_prefix(RECORDING_MAGIC)
+            .ok_or_else(|| anyhow!("Not a Wayland recording: {header:?}"))?
+            .trim();
+        if byte_order != byte_order_name(Endian::NATIVE) {
+            return Err(anyhow!(
+                "Recording has {byte_order} messages, but this host is {}",
+                byte_order_name(Endian::NATIVE)
+            ));
+        }
+
+        let mut recording = Recording::new();
+        for (index, line) in lines.enumerate() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            // The header is line 1
+            let message =
+                parse_line(line).map_err(|error| anyhow!("Line {}: {error}", index + 2))?;
+            recording.push(message);
+        }
+
+        Ok(recording)
+    }
+
+    /// Writes the recording to `writer` in the format [`Recording::read`] parses.
+    ///
+    /// # Errors
+    /// Returns an error if writing fails.
+    pub fn write(&self, mut writer: impl Write) -> io::Result<()> {
+        write_header(&mut writer)?;
+        for message in &self.messages {
+            writeln!(writer, "{message}")?;
+        }
+        writer.flush()
+    }
+
+    /// Parses a pcapng capture written by [`Recording::write_pcapng`].
+    ///
+    /// Packets of interfaces of other link types and blocks other than the
+    /// section headers, interface descriptions and enhanced packets are
+    /// skipped, so captures edited by external tools still load.
+    ///
+    /// # Errors
+    /// Returns an error if reading fails, a block is malformed, or the
+    /// capture was made on a host of the other byte order.
+    pub fn read_pcapng(mut reader: impl Read) -> Result<Recording> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        pcapng::read(&bytes)
+    }
+
+    /// Writes the recording to `writer` as a pcapng capture.
+    ///
+    /// The capture has a single section, in the byte order of the messages,
+    /// with one interface of link type [`pcapng::LINKTYPE_WAYLAND`] whose
+    /// timestamps are in microseconds since the start of the recording.
+    /// Every message is an Enhanced Packet Block, whose `epb_flags` option
+    /// tells the direction, carrying a packet made of:
+    ///
+    /// - Byte 0: the direction, 0 for requests and 1 for events
+    /// - Bytes 1-3: zero
+    /// - Bytes 4-7: the number of file descriptors passed along (u32)
+    /// - Bytes 8-: the message, header included
+    ///
+    /// # Errors
+    /// Returns an error if writing fails.
+    pub fn write_pcapng(&self, mut writer: impl Write) -> io::Result<()> {
+        pcapng::write_header(&mut writer)?;
+        for message in &self.messages {
+            pcapng::write_message(&mut writer, message)?;
+        }
+        writer.flush()
+    }
+}
+
+/// Returns the name of a byte order in the recording header.
+fn byte_order_name(endian: Endian) -> &'static str {
+    match endian {
+        Endian::Little => "little-endian",
+        Endian::Big => "big-endian",
+    }
+}
+
+/// Writes the header line of a recording.
+fn write_header(writer: &mut impl Write) -> io::Result<()> {
+    writeln!(
+        writer,
+        "{RECORDING_MAGIC} {}",
+        byte_order_name(Endian::NATIVE)
+    )
+}
+
+/// Parses a message line of a recording.
+fn parse_line(line: &str) -> Result<RecordedMessage> {
+    let mut fields = line.split_whitespace();
+    let mut next = |name: &str| {
+        fields
+            .next()
+            .ok_or_else(|| anyhow!("Missing {name} in {line:?}"))
+    };
+
+    let timestamp = next("timestamp")?;
+    let (secs, micros) = timestamp.split_once('.').unwrap_or((timestamp, "0"));
+    let timestamp = Duration::from_secs(secs.parse()?) + Duration::from_micros(micros.parse()?);
+
+    let direction = match next("direction")? {
+        "out" => Direction::Outbound,
+        "in" => Direction::Inbound,
+        other => return Err(anyhow!("Invalid direction {other:?}")),
+    };
+    let fds = next("descriptor count")?.parse()?;
+
+    let mut hex = String::new();
+    for field in fields {
+        hex.push_str(field);
+    }
+    if hex.is_empty() || !hex.is_ascii() || !hex.len().is_multiple_of(2) {
+        return Err(anyhow!("Invalid message bytes {hex:?}"));
+    }
+    let bytes = (0..hex.len())
+        .step_by(2)
+        .map(|start| u8::from_str_radix(&hex[start..start + 2], 16))
+        .collect::<Result<Vec<u8>, _>>()?;
+
+    RecordedMessage::new(direction, timestamp, bytes, fds)
+}
+
+/// Writes the messages of a connection to a recording as they go by.
+pub(crate) struct Recorder {
+    /// Where the recording is written.
+    writer: Box<dyn Write + Send>,
+    /// The format the recording is written in.
+    format: RecordFormat,
+    /// When the recording started.
+    start: Instant,
+    /// The first write error, after which nothing more is written.
+    error: Option<io::Error>,
+}
+
+impl Recorder {
+    /// Starts a recording written to `writer` in `format`.
+    ///
+    /// # Errors
+    /// Returns an error if the header cannot be written.
+    pub(crate) fn new(writer: Box<dyn Write + Send>, format: RecordFormat) -> io::Result<Recorder> {
+        let mut writer = writer;
+        match format {
+            RecordFormat::Text => write_header(&mut writer)?,
+            RecordFormat::Pcapng => pcapng::write_header(&mut writer)?,
+        }
+
+        Ok(Recorder {
+            writer,
+            format,
+            start: Instant::now(),
+            error: None,
+        })
+    }
+
+    /// Records a message and the number of descriptors passed along.
+    ///
+    /// A write error stops the recording; it is reported by
+    /// [`Recorder::finish`] rather than failing the traffic being recorded.
+    pub(crate) fn record(&mut self, direction: Direction, bytes: &[u8], fds: usize) {
+        if self.error.is_some() {
+            return;
+        }
+
+        let message = RecordedMessage {
+            direction,
+            timestamp: self.start.elapsed(),
+            bytes: bytes.to_vec(),
+            fds,
+        };
+        let result = match self.format {
+            RecordFormat::Text => writeln!(self.writer, "{message}"),
+            RecordFormat::Pcapng => pcapng::write_message(&mut self.writer, &message),
+        };
+        if let Err(error) = result {
+            self.error = Some(error);
+        }
+    }
+
+    /// Flushes the recording.
+    ///
+    /// # Errors
+    /// Returns the first error met while recording, or the flush error.
+    pub(crate) fn finish(mut self) -> io::Result<()> {
+        match self.error.take() {
+            Some(error) => Err(error),
+            None => self.writer.flush(),
+        }
+    }
+}
+
+/// Opens `path` for a recording, replacing any existing file.
+pub(crate) fn create_file(path: &Path) -> io::Result<Box<dyn Write + Send>> {
+    Ok(Box::new(BufWriter::new(File::create(path)?)))
+}
+
+/// The error returned while replaying a [`Recording`] when the client sends
+/// a request other than the recorded one.
+///
+/// Only the target object and the opcode are compared, so arguments that
+/// naturally differ between runs, such as timestamps, do not fail a replay.
+/// It is wrapped in an [`Error`](crate::error::Error), so callers tell it apart from
+/// other failures with `error.is::<ReplayDiverged>()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReplayDiverged {
+    /// The position of the request among the requests of the recording.
+    pub index: usize,
+    /// The object ID and opcode of the recorded request, `None` past the
+    /// end of the recording.
+    pub expected: Option<(u32, u16)>,
+    /// The object ID and opcode of the request sent instead.
+    pub sent: (u32, u16),
+}
+
+impl Display for ReplayDiverged {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (object_id, opcode) = self.sent;
+        write!(
+            f,
+            "Replay diverged at request {}: sent opcode {opcode} to object {object_id}, ",
+            self.index
+        )?;
+        match self.expected {
+            Some((object_id, opcode)) => {
+                write!(f, "recorded opcode {opcode} to object {object_id}")
+            }
+            None => write!(f, "past the end of the recording"),
+        }
+    }
+}
+
+impl std::error::Error for ReplayDiverged {}
+
+/// The compositor side of a replayed connection, which answers the requests
+/// of the client with the recorded events.
+pub(crate) struct Replayer {
+    /// The events not delivered yet, each with the number of requests sent
+    /// before it was received.
+    events: VecDeque<(usize, RecordedMessage)>,
+    /// The headers of the recorded requests, in order.
+    requests: Vec<(u32, u16)>,
+    /// The number of requests the client sent so far.
+    sent: usize,
+    /// The other end of the socket of the connection, kept open so that
+    /// the socket never hangs up.
+    _peer: UnixStream,
+}
+
+impl Replayer {
+    /// Creates the compositor side replaying `recording` to the connection
+    /// whose socket is connected to `peer`.
+    pub(crate) fn new(recording: Recording, peer: UnixStream) -> Replayer {
+        let mut events = VecDeque::new();
+        let mut requests = Vec::new();
+
+        for message in recording.messages {
+            match message.direction {
+                Direction::Outbound => requests.push((message.object_id(), message.opcode())),
+                Direction::Inbound => events.push_back((requests.len(), message)),
+            }
+        }
+
+        Replayer {
+            events,
+            requests,
+            sent: 0,
+            _peer: peer,
+        }
+    }
+
+    /// Takes the requests flushed by the client instead of writing them to
+    /// the socket, checking them against the recorded ones.
+    ///
+    /// # Errors
+    /// Returns a [`ReplayDiverged`] error at the first request that differs.
+    pub(crate) fn receive(&mut self, out_buf: &mut Vec<u8>) -> Result<()> {
+        let bytes = std::mem::take(out_buf);
+
+        let mut offset = 0;
+        while offset + WL_MESSAGE_HEADER_LEN <= bytes.len() {
+            let header = WlMessageHeader::try_from(&bytes[offset..])?;
+            header.validate_size(WL_MESSAGE_MAX_LEN)?;
+            let sent = (header.object_id, header.opcode);
+            let expected = self.requests.get(self.sent).copied();
+            if expected != Some(sent) {
+                return Err(ReplayDiverged {
+                    index: self.sent,
+                    expected,
+                    sent,
+                }
+                .into());
+            }
+
+            self.sent += 1;
+            offset += usize::from(header.size);
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if events can be delivered for the requests sent so far.
+    pub(crate) fn has_events(&self) -> bool {
+        self.events
+            .front()
+            .is_some_and(|(after, _)| *after <= self.sent)
+    }
+
+    /// Takes the events that can be delivered, with a placeholder for every
+    /// descriptor they carried.
+    ///
+    /// The placeholders are `/dev/null`: handlers reading them get no data
+    /// and handlers mapping them get an error, rather than the content of
+    /// files that were not recorded.
+    ///
+    /// # Errors
+    /// Returns an error if a placeholder cannot be opened.
+    pub(crate) fn take_events(&mut self) -> Result<(Vec<RecordedMessage>, Vec<OwnedFd>)> {
+        let mut events = Vec::new();
+        let mut fds = Vec::new();
+
+        while self.has_events() {
+            let Some((_, event)) = self.events.pop_front() else {
+                break;
+            };
+            for _ in 0..event.fds {
+                fds.push(File::open("/dev/null")?.into());
+            }
+            events.push(event);
+        }
+
+        Ok((events, fds))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recordings_round_trip_through_both_formats() {
+        let mut recording = Recording::new();
+        let sync: Vec<u8> = WlMessage::new(1, 0, &2u32.to_ne_bytes()).unwrap().into();
+        let done: Vec<u8> = WlMessage::new(2, 0, &7u32.to_ne_bytes()).unwrap().into();
+        recording.push(
+            RecordedMessage::new(Direction::Outbound, Duration::from_micros(12), sync, 0).unwrap(),
+        );
+        recording.push(
+            RecordedMessage::new(
+                Direction::Inbound,
+                Duration::from_micros(1_000_480),
+                done,
+                1,
+            )
+            .unwrap(),
+        );
+
+        let mut text = Vec::new();
+        recording.write(&mut text).unwrap();
+        assert_eq!(Recording::read(text.as_slice()).unwrap(), recording);
+
+        let mut capture = Vec::new();
+        recording.write_pcapng(&mut capture).unwrap();
+        assert_eq!(capture.len() % 4, 0);
+        assert_eq!(
+            Recording::read_pcapng(capture.as_slice()).unwrap(),
+            recording
+        );
+        assert!(Recording::read_pcapng(text.as_slice()).is_err());
+
+        let bad = format!("{RECORDING_MAGIC} little-endian\n0.0 in 0 0100\n");
+        assert!(Recording::read(bad.as_bytes()).is_err());
+        assert!(Recording::read("not a recording\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn recorded_messages_hold_exactly_one_message() {
+        let sync: Vec<u8> = WlMessage::new(1, 0, &2u32.to_ne_bytes()).unwrap().into();
+        let message =
+            RecordedMessage::new(Direction::Outbound, Duration::ZERO, sync.clone(), 0).unwrap();
+        assert_eq!((message.object_id(), message.opcode()), (1, 0));
+        assert_eq!(message.bytes(), sync.as_slice());
+
+        for bytes in [
+            Vec::new(),
+            sync[..4].to_vec(),
+            sync[..8].to_vec(),
+            [sync.clone(), sync].concat(),
+        ] {
+            assert!(RecordedMessage::new(Direction::Inbound, Duration::ZERO, bytes, 0).is_err());
+        }
+    }
+}
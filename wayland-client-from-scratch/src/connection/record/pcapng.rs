@@ -0,0 +1,257 @@
+use std::{
+    io::{self, Write},
+    time::Duration,
+};
+
+use crate::error::{Result, anyhow};
+
+use super::{Direction, RecordedMessage, Recording};
+
+/// The link type of the captured interface, `LINKTYPE_USER0`, which pcapng
+/// reserves for private use.
+pub const LINKTYPE_WAYLAND: u16 = 147;
+
+/// The length of the pseudo-header preceding every message in a packet.
+pub const PSEUDO_HEADER_LEN: usize = 8;
+
+/// The type of the Section Header Block, the same in both byte orders.
+pub(super) const SECTION_HEADER_BLOCK: u32 = 0x0A0D_0D0A;
+
+/// The type of the Interface Description Block.
+const INTERFACE_DESCRIPTION_BLOCK: u32 = 1;
+
+/// The type of the Enhanced Packet Block.
+const ENHANCED_PACKET_BLOCK: u32 = 6;
+
+/// The magic of the Section Header Block telling the byte order of the section.
+const BYTE_ORDER_MAGIC: u32 = 0x1A2B_3C4D;
+
+/// The option ending the option list of a block.
+const OPT_ENDOFOPT: u16 = 0;
+
+/// The `if_name` option of the Interface Description Block.
+const OPT_IF_NAME: u16 = 2;
+
+/// The `if_tsresol` option of the Interface Description Block.
+const OPT_IF_TSRESOL: u16 = 9;
+
+/// The `epb_flags` option of the Enhanced Packet Block.
+const OPT_EPB_FLAGS: u16 = 2;
+
+/// The direction bits of `epb_flags` for inbound packets.
+const EPB_FLAGS_INBOUND: u32 = 1;
+
+/// The direction bits of `epb_flags` for outbound packets.
+const EPB_FLAGS_OUTBOUND: u32 = 2;
+
+/// The length of the fixed fields of a block: its type and its length,
+/// which is repeated at the end.
+const BLOCK_OVERHEAD: usize = 12;
+
+/// Pads `len` to the 32-bit alignment of pcapng blocks and options.
+fn padded(len: usize) -> usize {
+    len.next_multiple_of(4)
+}
+
+/// Appends an option to the option list of a block body.
+fn push_option(body: &mut Vec<u8>, code: u16, value: &[u8]) {
+    body.extend_from_slice(&code.to_ne_bytes());
+    body.extend_from_slice(&(value.len() as u16).to_ne_bytes());
+    body.extend_from_slice(value);
+    body.resize(padded(body.len()), 0);
+}
+
+/// Writes a block whose body is already padded.
+fn write_block(writer: &mut impl Write, block_type: u32, body: &[u8]) -> io::Result<()> {
+    let len = ((BLOCK_OVERHEAD + body.len()) as u32).to_ne_bytes();
+    writer.write_all(&block_type.to_ne_bytes())?;
+    writer.write_all(&len)?;
+    writer.write_all(body)?;
+    writer.write_all(&len)
+}
+
+/// Writes the Section Header Block and the Interface Description Block of
+/// the Wayland connection, which start every capture.
+///
+/// Sections are written in the native byte order, which is that of the
+/// recorded messages.
+pub(super) fn write_header(writer: &mut impl Write) -> io::Result<()> {
+    let mut section = Vec::new();
+    section.extend_from_slice(&BYTE_ORDER_MAGIC.to_ne_bytes());
+    // Version 1.0, and a section length left unspecified
+    section.extend_from_slice(&1u16.to_ne_bytes());
+    section.extend_from_slice(&0u16.to_ne_bytes());
+    section.extend_from_slice(&(-1i64).to_ne_bytes());
+    write_block(writer, SECTION_HEADER_BLOCK, &section)?;
+
+    let mut interface = Vec::new();
+    interface.extend_from_slice(&LINKTYPE_WAYLAND.to_ne_bytes());
+    interface.extend_from_slice(&0u16.to_ne_bytes());
+    // No snapshot length limit
+    interface.extend_from_slice(&0u32.to_ne_bytes());
+    push_option(&mut interface, OPT_IF_NAME, b"wayland");
+    // Microseconds, the default, stated for readers that do not know it
+    push_option(&mut interface, OPT_IF_TSRESOL, &[6]);
+    push_option(&mut interface, OPT_ENDOFOPT, &[]);
+    write_block(writer, INTERFACE_DESCRIPTION_BLOCK, &interface)
+}
+
+/// Writes a message as an Enhanced Packet Block.
+pub(super) fn write_message(writer: &mut impl Write, message: &RecordedMessage) -> io::Result<()> {
+    let (direction, flags) = match message.direction {
+        Direction::Outbound => (0u8, EPB_FLAGS_OUTBOUND),
+        Direction::Inbound => (1u8, EPB_FLAGS_INBOUND),
+    };
+    let packet_len = (PSEUDO_HEADER_LEN + message.bytes.len()) as u32;
+    let timestamp = message.timestamp.as_micros() as u64;
+
+    let mut body = Vec::with_capacity(32 + padded(packet_len as usize));
+    body.extend_from_slice(&0u32.to_ne_bytes());
+    body.extend_from_slice(&((timestamp >> 32) as u32).to_ne_bytes());
+    body.extend_from_slice(&(timestamp as u32).to_ne_bytes());
+    body.extend_from_slice(&packet_len.to_ne_bytes());
+    body.extend_from_slice(&packet_len.to_ne_bytes());
+
+    body.extend_from_slice(&[direction, 0, 0, 0]);
+    body.extend_from_slice(&(message.fds as u32).to_ne_bytes());
+    body.extend_from_slice(&message.bytes);
+    body.resize(padded(body.len()), 0);
+
+    push_option(&mut body, OPT_EPB_FLAGS, &flags.to_ne_bytes());
+    push_option(&mut body, OPT_ENDOFOPT, &[]);
+    write_block(writer, ENHANCED_PACKET_BLOCK, &body)
+}
+
+/// Reads the 32-bit word at `offset`, which the caller checked is in bounds.
+fn word(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_ne_bytes([
+        bytes[offset],
+        bytes[offset + 1],
+        bytes[offset + 2],
+        bytes[offset + 3],
+    ])
+}
+
+/// Returns the duration of one timestamp unit given by an `if_tsresol` value.
+fn resolution(tsresol: u8) -> Result<Duration> {
+    let exponent = u32::from(tsresol & 0x7F);
+    let units_per_second = if tsresol & 0x80 == 0 {
+        10u64.checked_pow(exponent)
+    } else {
+        1u64.checked_shl(exponent)
+    };
+
+    match units_per_second {
+        Some(units) if units <= 1_000_000_000 => Ok(Duration::from_nanos(1_000_000_000 / units)),
+        _ => Err(anyhow!("Unsupported timestamp resolution {tsresol:#x}")),
+    }
+}
+
+/// Returns the `if_tsresol` value among the options of an Interface
+/// Description Block body, 6 (microseconds) if there is none.
+fn find_tsresol(options: &[u8]) -> u8 {
+    let mut offset = 0;
+    while offset + 4 <= options.len() {
+        let code = u16::from_ne_bytes([options[offset], options[offset + 1]]);
+        let len = usize::from(u16::from_ne_bytes([
+            options[offset + 2],
+            options[offset + 3],
+        ]));
+        let value = offset + 4;
+        if code == OPT_ENDOFOPT || value + len > options.len() {
+            break;
+        }
+        if code == OPT_IF_TSRESOL && len == 1 {
+            return options[value];
+        }
+        offset = value + padded(len);
+    }
+
+    6
+}
+
+/// Parses a pcapng capture of Wayland connections.
+///
+/// Packets of interfaces of other link types and blocks of other types are
+/// skipped.
+pub(super) fn read(bytes: &[u8]) -> Result<Recording> {
+    let mut recording = Recording::new();
+    // The timestamp resolution of each interface of the current section,
+    // `None` for interfaces of other link types
+    let mut interfaces: Vec<Option<Duration>> = Vec::new();
+    let mut offset = 0;
+
+    while offset < bytes.len() {
+        if bytes.len() - offset < BLOCK_OVERHEAD {
+            return Err(anyhow!("Truncated pcapng block at byte {offset}"));
+        }
+        let block_type = word(bytes, offset);
+
+        if block_type == SECTION_HEADER_BLOCK {
+            if bytes.len() - offset < BLOCK_OVERHEAD + 4 {
+                return Err(anyhow!("Truncated pcapng section header at byte {offset}"));
+            }
+            match word(bytes, offset + 8) {
+                BYTE_ORDER_MAGIC => {}
+                magic if magic == BYTE_ORDER_MAGIC.swap_bytes() => {
+                    return Err(anyhow!(
+                        "Capture was made on a host of the other byte order"
+                    ));
+                }
+                _ => return Err(anyhow!("Not a pcapng capture")),
+            }
+            interfaces.clear();
+        } else if offset == 0 {
+            return Err(anyhow!("Not a pcapng capture"));
+        }
+
+        let len = word(bytes, offset + 4) as usize;
+        if len < BLOCK_OVERHEAD || !len.is_multiple_of(4) || len > bytes.len() - offset {
+            return Err(anyhow!(
+                "Invalid pcapng block length {len} at byte {offset}"
+            ));
+        }
+        let body = &bytes[offset + 8..offset + len - 4];
+
+        match block_type {
+            INTERFACE_DESCRIPTION_BLOCK if body.len() >= 8 => {
+                let link_type = u16::from_ne_bytes([body[0], body[1]]);
+                interfaces.push(if link_type == LINKTYPE_WAYLAND {
+                    Some(resolution(find_tsresol(&body[8..]))?)
+                } else {
+                    None
+                });
+            }
+            ENHANCED_PACKET_BLOCK if body.len() >= 20 => {
+                let interface = word(body, 0) as usize;
+                let Some(Some(unit)) = interfaces.get(interface).copied() else {
+                    offset += len;
+                    continue;
+                };
+                let timestamp = (u64::from(word(body, 4)) << 32) | u64::from(word(body, 8));
+                let captured = word(body, 12) as usize;
+                if captured < PSEUDO_HEADER_LEN || captured > body.len() - 20 {
+                    return Err(anyhow!("Invalid packet length {captured} at byte {offset}"));
+                }
+
+                let packet = &body[20..20 + captured];
+                let direction = match packet[0] {
+                    0 => Direction::Outbound,
+                    1 => Direction::Inbound,
+                    other => return Err(anyhow!("Invalid packet direction {other}")),
+                };
+                recording.push(RecordedMessage::new(
+                    direction,
+                    Duration::from_nanos(timestamp.saturating_mul(unit.as_nanos() as u64)),
+                    packet[PSEUDO_HEADER_LEN..].to_vec(),
+                    word(packet, 4) as usize,
+                )?);
+            }
+            _ => {}
+        }
+
+        offset += len;
+    }
+
+    Ok(recording)
+}
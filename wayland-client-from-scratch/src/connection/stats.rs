@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+
+use crate::protocol::WlObjectId;
+
+/// Counters of the traffic of a connection, returned by
+/// [`Connection::stats`](crate::connection::Connection::stats).
+///
+/// Requests are counted when they are queued, events when they are read
+/// from the socket, so the counters do not depend on how often the
+/// connection is flushed or dispatched. Bytes include the message headers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConnectionStats {
+    /// The number of requests queued.
+    pub messages_sent: u64,
+    /// The number of bytes of the queued requests.
+    pub bytes_sent: u64,
+    /// The number of file descriptors passed along with the requests.
+    pub fds_sent: u64,
+    /// The number of events received.
+    pub messages_received: u64,
+    /// The number of bytes of the received events.
+    pub bytes_received: u64,
+    /// The number of file descriptors received along with the events.
+    pub fds_received: u64,
+    /// The number of events dispatched to each interface, not counting the
+    /// events skipped for being unknown or invalid.
+    pub dispatched: HashMap<WlObjectId, u64>,
+    /// The largest number of bytes of requests waiting to be flushed.
+    pub max_send_queue: usize,
+    /// The largest number of events held back at once by
+    /// [`UnknownObjectPolicy::Queue`](crate::connection::UnknownObjectPolicy::Queue).
+    pub max_queued_events: usize,
+}
+
+impl ConnectionStats {
+    /// Returns the number of events dispatched to objects implementing
+    /// `interface`.
+    pub fn dispatched_to(&self, interface: WlObjectId) -> u64 {
+        self.dispatched.get(&interface).copied().unwrap_or(0)
+    }
+
+    /// Counts a request of `len` bytes carrying `fds` descriptors, after
+    /// which `queued` bytes wait to be flushed.
+    pub(crate) fn record_sent(&mut self, len: usize, fds: usize, queued: usize) {
+        self.messages_sent += 1;
+        self.bytes_sent += len as u64;
+        self.fds_sent += fds as u64;
+        self.max_send_queue = self.max_send_queue.max(queued);
+    }
+
+    /// Counts `messages` events totalling `len` bytes.
+    pub(crate) fn record_received(&mut self, messages: usize, len: usize) {
+        self.messages_received += messages as u64;
+        self.bytes_received += len as u64;
+    }
+
+    /// Counts an event dispatched to an object implementing `interface`.
+    pub(crate) fn record_dispatch(&mut self, interface: WlObjectId) {
+        *self.dispatched.entry(interface).or_default() += 1;
+    }
+}
@@ -0,0 +1,915 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use crate::{
+    connection::{Connection, event_loop::LoopHandle},
+    error::{Context, Result, anyhow},
+    geometry::Rect,
+    protocol::{
+        WlObjectId,
+        buffer::Buffer,
+        compositor::Compositor,
+        cursor_shape::{CursorShapeDevice, CursorShapeManager},
+        pointer::Pointer,
+        shm::{Shm, event::format::Format},
+        shm_pool::ShmPool,
+        surface::{Surface, request},
+        types::{WlInt, WlObject},
+    },
+};
+
+/// The theme used when `$XCURSOR_THEME` is unset.
+pub const DEFAULT_CURSOR_THEME: &str = "default";
+
+/// The nominal cursor size used when `$XCURSOR_SIZE` is unset or invalid.
+pub const DEFAULT_CURSOR_SIZE: u32 = 24;
+
+/// The directories searched for themes when `$XCURSOR_PATH` is unset, `~`
+/// standing for `$HOME`. This is the default path of libXcursor.
+const DEFAULT_SEARCH_PATH: &str =
+    "~/.local/share/icons:~/.icons:/usr/share/icons:/usr/share/pixmaps";
+
+/// The X11 cursor font names of the CSS cursors, for themes that predate
+/// the CSS names.
+const LEGACY_NAMES: &[(&str, &str)] = &[
+    ("default", "left_ptr"),
+    ("context-menu", "left_ptr"),
+    ("help", "question_arrow"),
+    ("pointer", "hand2"),
+    ("progress", "left_ptr_watch"),
+    ("wait", "watch"),
+    ("cell", "plus"),
+    ("crosshair", "cross"),
+    ("text", "xterm"),
+    ("alias", "dnd-link"),
+    ("copy", "dnd-copy"),
+    ("move", "fleur"),
+    ("no-drop", "dnd-no-drop"),
+    ("not-allowed", "crossed_circle"),
+    ("grab", "hand1"),
+    ("grabbing", "fleur"),
+    ("e-resize", "right_side"),
+    ("n-resize", "top_side"),
+    ("ne-resize", "top_right_corner"),
+    ("nw-resize", "top_left_corner"),
+    ("s-resize", "bottom_side"),
+    ("se-resize", "bottom_right_corner"),
+    ("sw-resize", "bottom_left_corner"),
+    ("w-resize", "left_side"),
+    ("ew-resize", "sb_h_double_arrow"),
+    ("ns-resize", "sb_v_double_arrow"),
+    ("col-resize", "sb_h_double_arrow"),
+    ("row-resize", "sb_v_double_arrow"),
+    ("all-scroll", "fleur"),
+];
+
+/// The magic bytes starting every XCursor file.
+const XCURSOR_MAGIC: &[u8; 4] = b"Xcur";
+
+/// The chunk type of an image in an XCursor file.
+const XCURSOR_IMAGE_TYPE: u32 = 0xfffd_0002;
+
+/// The size in bytes of the header of an image chunk.
+const XCURSOR_IMAGE_HEADER_LEN: usize = 36;
+
+/// The largest width or height of an XCursor image.
+const XCURSOR_IMAGE_MAX_SIZE: u32 = 0x7fff;
+
+/// The number of bytes per pixel of cursor images.
+const CURSOR_BYTES_PER_PIXEL: u32 = 4;
+
+/// One image of a cursor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CursorFrame {
+    /// The width of the image in pixels.
+    pub width: u32,
+    /// The height of the image in pixels.
+    pub height: u32,
+    /// The horizontal position of the hotspot within the image.
+    pub hotspot_x: u32,
+    /// The vertical position of the hotspot within the image.
+    pub hotspot_y: u32,
+    /// How long the image is shown before the next one of an animated
+    /// cursor. Meaningless for cursors with a single image.
+    pub delay: Duration,
+    /// The pixels, row after row without padding, in the premultiplied
+    /// [`Format::Argb8888`] layout `wl_shm` buffers use.
+    pub pixels: Vec<u8>,
+}
+
+/// A cursor of a theme, made of one image or of the frames of an animation
+/// such as the busy or wait cursors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cursor {
+    /// The name the cursor was looked up by.
+    name: String,
+    /// The nominal size of the images, the closest the file had to the
+    /// requested one.
+    size: u32,
+    /// The images, in animation order.
+    frames: Vec<CursorFrame>,
+}
+
+impl Cursor {
+    /// Parses the images of nominal size closest to `size` out of the
+    /// contents of an XCursor file.
+    ///
+    /// A file holds the cursor at several nominal sizes, each made of one or
+    /// more frames; the frames of the selected size are kept in the order
+    /// of the file, which is the animation order.
+    ///
+    /// # Errors
+    /// Returns an error if the data is not an XCursor file, is truncated or
+    /// holds no image.
+    pub fn parse(name: &str, data: &[u8], size: u32) -> Result<Cursor> {
+        if data.get(..4) != Some(XCURSOR_MAGIC) {
+            return Err(anyhow!("Cursor {} is not an XCursor file", name));
+        }
+        let header_len = read_u32(data, 4)? as usize;
+        let toc_len = read_u32(data, 12)? as usize;
+
+        // Table of contents entries: type, nominal size, position
+        let mut images = Vec::new();
+        for entry in 0..toc_len {
+            let offset = header_len + entry * 12;
+            if read_u32(data, offset)? == XCURSOR_IMAGE_TYPE {
+                images.push((
+                    read_u32(data, offset + 4)?,
+                    read_u32(data, offset + 8)? as usize,
+                ));
+            }
+        }
+
+        let nominal = images
+            .iter()
+            .map(|&(nominal, _)| nominal)
+            .min_by_key(|&nominal| nominal.abs_diff(size))
+            .ok_or_else(|| anyhow!("Cursor {} holds no image", name))?;
+
+        let frames = images
+            .iter()
+            .filter(|&&(image_size, _)| image_size == nominal)
+            .map(|&(_, position)| parse_image(data, position))
+            .collect::<Result<Vec<_>>>()
+            .with_context(|| format!("Failed to parse cursor {}", name))?;
+
+        Ok(Cursor {
+            name: name.to_string(),
+            size: nominal,
+            frames,
+        })
+    }
+
+    /// Returns the name the cursor was looked up by.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the nominal size of the images, which may differ from the
+    /// requested size when the theme does not provide it.
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    /// Returns the images of the cursor, in animation order.
+    pub fn frames(&self) -> &[CursorFrame] {
+        &self.frames
+    }
+
+    /// Returns `true` if the cursor has several frames to cycle through.
+    pub fn is_animated(&self) -> bool {
+        self.frames.len() > 1
+    }
+
+    /// Returns the duration of one cycle of the animation, zero for a
+    /// cursor with a single image.
+    pub fn duration(&self) -> Duration {
+        if !self.is_animated() {
+            return Duration::ZERO;
+        }
+        self.frames.iter().map(|frame| frame.delay).sum()
+    }
+
+    /// Returns the index of the frame shown `elapsed` after the animation
+    /// started, the animation looping forever.
+    pub fn frame_at(&self, elapsed: Duration) -> usize {
+        let duration = self.duration();
+        if duration.is_zero() {
+            return 0;
+        }
+
+        let mut time = Duration::from_nanos((elapsed.as_nanos() % duration.as_nanos()) as u64);
+        for (index, frame) in self.frames.iter().enumerate() {
+            if time < frame.delay {
+                return index;
+            }
+            time -= frame.delay;
+        }
+
+        self.frames.len() - 1
+    }
+}
+
+/// Reads a little-endian `u32` at `offset`.
+fn read_u32(data: &[u8], offset: usize) -> Result<u32> {
+    let bytes = offset
+        .checked_add(4)
+        .and_then(|end| data.get(offset..end))
+        .ok_or_else(|| anyhow!("XCursor file truncated at offset {}", offset))?;
+
+    Ok(u32::from_le_bytes(bytes.try_into()?))
+}
+
+/// Parses the image chunk at `position`.
+fn parse_image(data: &[u8], position: usize) -> Result<CursorFrame> {
+    // Chunk header: length, type, nominal size, version, then the image
+    // header: width, height, hotspot, delay in milliseconds
+    let width = read_u32(data, position + 16)?;
+    let height = read_u32(data, position + 20)?;
+    let hotspot_x = read_u32(data, position + 24)?;
+    let hotspot_y = read_u32(data, position + 28)?;
+    let delay = read_u32(data, position + 32)?;
+
+    if width == 0
+        || height == 0
+        || width > XCURSOR_IMAGE_MAX_SIZE
+        || height > XCURSOR_IMAGE_MAX_SIZE
+    {
+        return Err(anyhow!("Invalid cursor image size {}x{}", width, height));
+    }
+    if hotspot_x > width || hotspot_y > height {
+        return Err(anyhow!(
+            "Cursor hotspot {},{} lies outside the {}x{} image",
+            hotspot_x,
+            hotspot_y,
+            width,
+            height
+        ));
+    }
+
+    let start = position + XCURSOR_IMAGE_HEADER_LEN;
+    let len = (width * height * CURSOR_BYTES_PER_PIXEL) as usize;
+    let pixels = data
+        .get(start..start + len)
+        .ok_or_else(|| anyhow!("XCursor file truncated in a {}x{} image", width, height))?;
+
+    Ok(CursorFrame {
+        width,
+        height,
+        hotspot_x,
+        hotspot_y,
+        delay: Duration::from_millis(delay.into()),
+        pixels: pixels.to_vec(),
+    })
+}
+
+/// An XCursor theme, looking cursors up by name.
+///
+/// Cursors are searched in the `cursors` directory of the theme in each
+/// directory of the search path, then in the themes it inherits from as
+/// listed by the `Inherits` key of its `index.theme`. Loaded cursors are
+/// cached, so looking the same cursor up again is cheap.
+#[derive(Debug, Clone)]
+pub struct CursorTheme {
+    /// The name of the theme.
+    name: String,
+    /// The nominal size of the cursors to load.
+    size: u32,
+    /// The directories holding themes, in search order.
+    search_path: Vec<PathBuf>,
+    /// The cursors looked up so far, `None` for those not found.
+    cache: HashMap<String, Option<Arc<Cursor>>>,
+}
+
+impl CursorTheme {
+    /// Loads the theme selected by `$XCURSOR_THEME` at the size given by
+    /// `$XCURSOR_SIZE`, the variables compositors set for their clients,
+    /// falling back to [`DEFAULT_CURSOR_THEME`] and [`DEFAULT_CURSOR_SIZE`].
+    pub fn from_env() -> CursorTheme {
+        let name =
+            std::env::var("XCURSOR_THEME").unwrap_or_else(|_| DEFAULT_CURSOR_THEME.to_string());
+        let size = std::env::var("XCURSOR_SIZE")
+            .ok()
+            .and_then(|size| size.parse().ok())
+            .filter(|&size| size > 0)
+            .unwrap_or(DEFAULT_CURSOR_SIZE);
+
+        CursorTheme::load(&name, size)
+    }
+
+    /// Loads the theme `name`, searched in the directories of
+    /// `$XCURSOR_PATH` or in the default ones of libXcursor.
+    pub fn load(name: &str, size: u32) -> CursorTheme {
+        let search_path =
+            std::env::var("XCURSOR_PATH").unwrap_or_else(|_| DEFAULT_SEARCH_PATH.to_string());
+        let home = std::env::var_os("HOME").map(PathBuf::from);
+
+        let search_path = search_path
+            .split(':')
+            .filter(|dir| !dir.is_empty())
+            .filter_map(|dir| match dir.strip_prefix("~/") {
+                Some(rest) => home.as_ref().map(|home| home.join(rest)),
+                None => Some(PathBuf::from(dir)),
+            })
+            .collect();
+
+        CursorTheme::with_search_path(name, size, search_path)
+    }
+
+    /// Loads the theme `name`, searched in the directories of `search_path`.
+    pub fn with_search_path(name: &str, size: u32, search_path: Vec<PathBuf>) -> CursorTheme {
+        CursorTheme {
+            name: name.to_string(),
+            size,
+            search_path,
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Returns the name of the theme.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the nominal size of the cursors loaded from the theme.
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    /// Returns the same theme at `scale` times its size, for cursor
+    /// surfaces with a buffer scale of `scale`.
+    pub fn scaled(&self, scale: u32) -> CursorTheme {
+        CursorTheme::with_search_path(&self.name, self.size * scale, self.search_path.clone())
+    }
+
+    /// Looks up the cursor `name`, such as `left_ptr` or `watch`.
+    ///
+    /// # Returns
+    /// `None` if neither the theme nor the themes it inherits from provide
+    /// the cursor.
+    ///
+    /// # Errors
+    /// Returns an error if the cursor file cannot be read or parsed.
+    pub fn cursor(&mut self, name: &str) -> Result<Option<Arc<Cursor>>> {
+        if let Some(cursor) = self.cache.get(name) {
+            return Ok(cursor.clone());
+        }
+
+        let cursor = match self.find(name) {
+            Some(path) => {
+                let data = fs::read(&path)
+                    .with_context(|| format!("Failed to read cursor {}", path.display()))?;
+                Some(Arc::new(Cursor::parse(name, &data, self.size)?))
+            }
+            None => None,
+        };
+        self.cache.insert(name.to_string(), cursor.clone());
+
+        Ok(cursor)
+    }
+
+    /// Returns the path of the file of the cursor `name`, searching the
+    /// theme and then the themes it inherits from, breadth first.
+    fn find(&self, name: &str) -> Option<PathBuf> {
+        let mut visited = HashSet::new();
+        let mut themes = vec![self.name.clone()];
+
+        while !themes.is_empty() {
+            let mut inherited = Vec::new();
+            for theme in themes {
+                if !visited.insert(theme.clone()) {
+                    continue;
+                }
+                for dir in &self.search_path {
+                    let path = dir.join(&theme).join("cursors").join(name);
+                    if path.is_file() {
+                        return Some(path);
+                    }
+                }
+                for dir in &self.search_path {
+                    inherited.extend(read_inherits(&dir.join(&theme).join("index.theme")));
+                }
+            }
+            themes = inherited;
+        }
+
+        None
+    }
+}
+
+/// Returns the themes listed by the `Inherits` key of an `index.theme`
+/// file, none if the file cannot be read.
+fn read_inherits(path: &Path) -> Vec<String> {
+    let Ok(contents) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+
+    contents
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("Inherits"))
+        .filter_map(|rest| rest.trim_start().strip_prefix('='))
+        .flat_map(|themes| themes.split([',', ';']))
+        .map(|theme| theme.trim().to_string())
+        .filter(|theme| !theme.is_empty())
+        .collect()
+}
+
+/// Returns the names a theme may give the cursor `name`: the name itself,
+/// then its X11 cursor font name if it is a CSS cursor name.
+fn theme_names(name: &str) -> impl Iterator<Item = &str> {
+    std::iter::once(name).chain(
+        LEGACY_NAMES
+            .iter()
+            .filter(move |&&(css, _)| css == name)
+            .map(|&(_, legacy)| legacy),
+    )
+}
+
+/// The frames of a running animation, shared with its timers.
+struct AnimationFrames {
+    /// The object ID of the cursor surface.
+    surface: u32,
+    /// The object ID and size of the buffer of each frame.
+    buffers: Vec<(u32, i32, i32)>,
+    /// How long each frame is shown.
+    delays: Vec<Duration>,
+    /// The generation the animation was started in; the timers of older
+    /// generations stop instead of swapping frames.
+    generation: Arc<AtomicU64>,
+}
+
+/// A cursor ready to be shown by a pointer, animating on its own when the
+/// cursor has several frames.
+///
+/// Every frame is uploaded once to a `wl_shm` buffer, so frame swaps only
+/// attach and commit. The swaps are driven by timers of the event loop of
+/// the connection ([`LoopHandle::add_timer`]), which requires the
+/// connection to be dispatched for the animation to advance. Each timer
+/// is set from the deadline of the previous one, so the animation does not
+/// drift when dispatching runs late.
+///
+/// The cursor surface is assigned to a pointer with
+/// [`Pointer::set_cursor`](crate::protocol::pointer::Pointer::set_cursor),
+/// using the hotspot of [`AnimatedCursor::hotspot`]. Dropping the value
+/// stops the animation and destroys the surface.
+pub struct AnimatedCursor {
+    /// The surface the frames are attached to.
+    surface: Surface,
+    /// The buffer of each frame.
+    buffers: Vec<Buffer>,
+    /// The pool holding the buffers.
+    _pool: ShmPool,
+    /// The cursor being shown.
+    cursor: Arc<Cursor>,
+    /// Incremented to stop the timers of a running animation.
+    generation: Arc<AtomicU64>,
+}
+
+impl AnimatedCursor {
+    /// Creates the surface of `cursor` and uploads its frames.
+    ///
+    /// Nothing is shown until [`AnimatedCursor::start`] is called.
+    ///
+    /// # Errors
+    /// Returns an error if the surface or the buffers cannot be created.
+    pub fn new(compositor: &Compositor, shm: &Shm, cursor: Arc<Cursor>) -> Result<AnimatedCursor> {
+        let len = cursor.frames.iter().map(|frame| frame.pixels.len()).sum();
+        let mut pool = shm.create_pool(len)?;
+
+        let mut offset = 0;
+        let mut buffers = Vec::with_capacity(cursor.frames.len());
+        for frame in &cursor.frames {
+            pool.data_mut()[offset..offset + frame.pixels.len()].copy_from_slice(&frame.pixels);
+            buffers.push(pool.create_buffer(
+                offset,
+                frame.width as i32,
+                frame.height as i32,
+                (frame.width * CURSOR_BYTES_PER_PIXEL) as i32,
+                Format::Argb8888,
+            )?);
+            offset += frame.pixels.len();
+        }
+
+        Ok(AnimatedCursor {
+            surface: compositor.create_surface()?,
+            buffers,
+            _pool: pool,
+            cursor,
+            generation: Arc::new(AtomicU64::new(0)),
+        })
+    }
+
+    /// Returns the surface to pass to `wl_pointer.set_cursor`.
+    pub fn surface(&self) -> &Surface {
+        &self.surface
+    }
+
+    /// Returns the cursor being shown.
+    pub fn cursor(&self) -> &Arc<Cursor> {
+        &self.cursor
+    }
+
+    /// Returns the hotspot of the first frame, to pass to
+    /// `wl_pointer.set_cursor`. The frames of an animation share their
+    /// hotspot in practice.
+    pub fn hotspot(&self) -> (i32, i32) {
+        let frame = &self.cursor.frames[0];
+        (frame.hotspot_x as i32, frame.hotspot_y as i32)
+    }
+
+    /// Shows the first frame and, for an animated cursor, starts cycling
+    /// through the others. Starting a running animation restarts it.
+    ///
+    /// # Errors
+    /// Returns an error if the first frame cannot be committed.
+    pub fn start(&self) -> Result<()> {
+        let generation = self.generation.fetch_add(1, Ordering::Relaxed) + 1;
+
+        let frame = &self.cursor.frames[0];
+        self.surface.attach(Some(&self.buffers[0]))?;
+        self.surface
+            .damage(Rect::new(0, 0, frame.width as i32, frame.height as i32))?;
+        self.surface.commit()?;
+
+        if self.cursor.is_animated() {
+            let frames = Arc::new(AnimationFrames {
+                surface: self.surface.id(),
+                buffers: self
+                    .buffers
+                    .iter()
+                    .zip(&self.cursor.frames)
+                    .map(|(buffer, frame)| (buffer.id(), frame.width as i32, frame.height as i32))
+                    .collect(),
+                delays: self.cursor.frames.iter().map(|frame| frame.delay).collect(),
+                generation: self.generation.clone(),
+            });
+            let deadline = Instant::now() + frames.delays[0];
+            schedule_frame(
+                self.surface.connection().loop_handle(),
+                frames,
+                generation,
+                1,
+                deadline,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Stops the animation, leaving the current frame shown.
+    pub fn stop(&self) {
+        self.generation.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl Drop for AnimatedCursor {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// The cursors of a pointer set by name with
+/// [`Pointer::set_named_cursor`](crate::protocol::pointer::Pointer::set_named_cursor),
+/// whose objects are created on first use.
+#[derive(Default)]
+pub(crate) struct NamedCursor {
+    /// The cursor shape device of the pointer once looked for, `None`
+    /// inside if the compositor does not offer cursor shapes.
+    shape_device: Option<Option<CursorShapeDevice>>,
+    /// The theme set by the application, or the one of the environment
+    /// once loaded.
+    theme: Option<CursorTheme>,
+    /// The theme scaled for the buffer scale of the cursor surfaces, with
+    /// that scale.
+    scaled: Option<(u32, CursorTheme)>,
+    /// The globals cursor surfaces are created with, once bound.
+    globals: Option<(Compositor, Shm)>,
+    /// The cursor shown by the pointer, with its name and buffer scale.
+    current: Option<(String, u32, AnimatedCursor)>,
+}
+
+impl NamedCursor {
+    /// Replaces the theme cursors are loaded from.
+    pub(crate) fn set_theme(&mut self, theme: CursorTheme) {
+        self.theme = Some(theme);
+        self.scaled = None;
+        self.current = None;
+    }
+
+    /// Returns the cursor shape device of `pointer`, created on first use,
+    /// or `None` if the compositor does not offer cursor shapes.
+    pub(crate) fn shape_device(&mut self, pointer: &Pointer) -> Result<Option<&CursorShapeDevice>> {
+        if self.shape_device.is_none() {
+            let conn = pointer.connection();
+            let device = if conn.capabilities().has(WlObjectId::CursorShapeManager) {
+                let manager = CursorShapeManager::bind(conn)?;
+                let device = manager.get_pointer(pointer)?;
+                // Devices outlive the manager
+                manager.destroy()?;
+                Some(device)
+            } else {
+                None
+            };
+            self.shape_device = Some(device);
+        }
+
+        Ok(self.shape_device.as_ref().and_then(Option::as_ref))
+    }
+
+    /// Returns the surface showing the theme cursor `name` at the buffer
+    /// scale `scale`, with its hotspot in surface-local coordinates. The
+    /// surface is created and its animation started unless it already
+    /// shows the cursor.
+    ///
+    /// # Errors
+    /// Returns an error if the theme has no such cursor, or the cursor
+    /// cannot be loaded or uploaded.
+    pub(crate) fn surface(
+        &mut self,
+        conn: &Connection,
+        name: &str,
+        scale: u32,
+    ) -> Result<(&Surface, (i32, i32))> {
+        let shown = self
+            .current
+            .as_ref()
+            .is_some_and(|(current, current_scale, _)| current == name && *current_scale == scale);
+        if !shown {
+            let theme = self.theme.get_or_insert_with(CursorTheme::from_env);
+            if self
+                .scaled
+                .as_ref()
+                .is_none_or(|(scaled, _)| *scaled != scale)
+            {
+                self.scaled = Some((scale, theme.scaled(scale)));
+            }
+            let scaled = &mut self
+                .scaled
+                .as_mut()
+                .expect("the scaled theme was just set")
+                .1;
+
+            let mut cursor = None;
+            for candidate in theme_names(name) {
+                cursor = scaled.cursor(candidate)?;
+                if cursor.is_some() {
+                    break;
+                }
+            }
+            let cursor = cursor.ok_or_else(|| {
+                anyhow!("Cursor theme {} has no cursor named {name}", scaled.name())
+            })?;
+
+            // Buffers must be a whole number of surface pixels
+            let fits = cursor
+                .frames()
+                .iter()
+                .all(|frame| frame.width % scale == 0 && frame.height % scale == 0);
+            if !fits {
+                return self.surface(conn, name, 1);
+            }
+
+            let (compositor, shm) = match &self.globals {
+                Some(globals) => globals,
+                None => self
+                    .globals
+                    .insert((Compositor::bind(conn)?, Shm::bind(conn)?)),
+            };
+            let animated = AnimatedCursor::new(compositor, shm, cursor)?;
+            if scale != 1 {
+                animated.surface().set_buffer_scale(scale as i32)?;
+            }
+            animated.start()?;
+            self.current = Some((name.to_string(), scale, animated));
+        }
+
+        let (_, scale, animated) = self.current.as_ref().expect("the cursor was just set");
+        let (hotspot_x, hotspot_y) = animated.hotspot();
+        let scale = *scale as i32;
+
+        Ok((animated.surface(), (hotspot_x / scale, hotspot_y / scale)))
+    }
+
+    /// Drops the cursor surface, once the pointer shows a cursor shape.
+    pub(crate) fn clear_surface(&mut self) {
+        self.current = None;
+    }
+}
+
+/// Sets the timer swapping to frame `index` at `deadline`, which sets the
+/// timer of the next frame in turn.
+fn schedule_frame(
+    handle: LoopHandle,
+    frames: Arc<AnimationFrames>,
+    generation: u64,
+    index: usize,
+    deadline: Instant,
+) {
+    let next_handle = handle.clone();
+    handle.add_timer(deadline, move |conn| {
+        if frames.generation.load(Ordering::Relaxed) != generation
+            || !is_surface(conn, frames.surface)
+        {
+            return;
+        }
+
+        // Timers cannot report errors; a frame that fails to show is skipped
+        let _ = show_frame(conn, &frames, index);
+
+        let next = (index + 1) % frames.buffers.len();
+        let next_deadline = deadline + frames.delays[index];
+        schedule_frame(next_handle, frames, generation, next, next_deadline);
+    });
+}
+
+/// Returns `true` if `id` still names a surface on `conn`, which stops the
+/// animations left behind by a reconnection.
+fn is_surface(conn: &Connection, id: u32) -> bool {
+    conn.object(id)
+        .is_some_and(|object| object.interface == WlObjectId::Surface)
+}
+
+/// Attaches and commits the buffer of frame `index`.
+fn show_frame(conn: &Connection, frames: &AnimationFrames, index: usize) -> Result<()> {
+    let (buffer, width, height) = frames.buffers[index];
+
+    request::attach(conn, frames.surface, WlObject(buffer), WlInt(0), WlInt(0))?;
+    request::damage(
+        conn,
+        frames.surface,
+        WlInt(0),
+        WlInt(0),
+        WlInt(width),
+        WlInt(height),
+    )?;
+    request::commit(conn, frames.surface)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        os::unix::net::UnixStream,
+    };
+
+    use super::*;
+    use crate::protocol::{
+        endian::Endian,
+        message::{WL_MESSAGE_HEADER_LEN, WlMessage, WlMessageHeader},
+        registry::event::global::Global,
+        seat::SeatState,
+        types::{WlString, WlUInt},
+    };
+
+    /// Builds an XCursor file holding `images` of (nominal size, width,
+    /// delay), each filled with its index.
+    fn xcursor(images: &[(u32, u32, u32)]) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(XCURSOR_MAGIC);
+        for value in [16, 0x1_0000, images.len() as u32] {
+            data.extend_from_slice(&u32::to_le_bytes(value));
+        }
+
+        let mut position = 16 + images.len() * 12;
+        for &(size, width, _) in images {
+            for value in [XCURSOR_IMAGE_TYPE, size, position as u32] {
+                data.extend_from_slice(&u32::to_le_bytes(value));
+            }
+            position += XCURSOR_IMAGE_HEADER_LEN + (width * width * 4) as usize;
+        }
+        for (index, &(size, width, delay)) in images.iter().enumerate() {
+            for value in [36, XCURSOR_IMAGE_TYPE, size, 1, width, width, 1, 2, delay] {
+                data.extend_from_slice(&u32::to_le_bytes(value));
+            }
+            data.extend(std::iter::repeat_n(
+                index as u8,
+                (width * width * 4) as usize,
+            ));
+        }
+
+        data
+    }
+
+    #[test]
+    fn parse_selects_the_closest_size_and_keeps_frame_order() {
+        let data = xcursor(&[(24, 4, 50), (48, 8, 0), (24, 4, 150)]);
+
+        let cursor = Cursor::parse("watch", &data, 30).unwrap();
+        assert_eq!(cursor.size(), 24);
+        assert_eq!(cursor.frames().len(), 2);
+        assert_eq!(cursor.frames()[1].pixels[0], 2);
+        assert_eq!(
+            (cursor.frames()[0].hotspot_x, cursor.frames()[0].hotspot_y),
+            (1, 2)
+        );
+        assert_eq!(cursor.duration(), Duration::from_millis(200));
+
+        assert_eq!(cursor.frame_at(Duration::from_millis(49)), 0);
+        assert_eq!(cursor.frame_at(Duration::from_millis(50)), 1);
+        assert_eq!(cursor.frame_at(Duration::from_millis(260)), 1);
+
+        let still = Cursor::parse("watch", &data, 48).unwrap();
+        assert!(!still.is_animated());
+        assert_eq!(still.frame_at(Duration::from_secs(1)), 0);
+    }
+
+    #[test]
+    fn parse_rejects_truncated_files() {
+        let data = xcursor(&[(24, 4, 0)]);
+
+        assert!(Cursor::parse("left_ptr", &data[..data.len() - 1], 24).is_err());
+        assert!(Cursor::parse("left_ptr", b"PNG", 24).is_err());
+    }
+
+    #[test]
+    fn theme_falls_back_to_inherited_themes() {
+        let root = std::env::temp_dir().join(format!("cursor-theme-{}", std::process::id()));
+        let cursors = root.join("base").join("cursors");
+        fs::create_dir_all(&cursors).unwrap();
+        fs::write(cursors.join("left_ptr"), xcursor(&[(24, 4, 0)])).unwrap();
+        fs::create_dir_all(root.join("custom")).unwrap();
+        fs::write(
+            root.join("custom").join("index.theme"),
+            "[Icon Theme]\nInherits = missing,base\n",
+        )
+        .unwrap();
+
+        let mut theme = CursorTheme::with_search_path("custom", 24, vec![root.clone()]);
+        let cursor = theme.cursor("left_ptr").unwrap().unwrap();
+        assert_eq!(cursor.name(), "left_ptr");
+        assert!(theme.cursor("text").unwrap().is_none());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn named_cursor_falls_back_to_the_scaled_theme() {
+        let root = std::env::temp_dir().join(format!("named-cursor-{}", std::process::id()));
+        let cursors = root.join("legacy").join("cursors");
+        fs::create_dir_all(&cursors).unwrap();
+        fs::write(cursors.join("xterm"), xcursor(&[(24, 4, 0), (48, 8, 0)])).unwrap();
+
+        let (client, mut compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+        for (name, interface, version) in [(1, "wl_compositor", 6), (2, "wl_shm", 1)] {
+            conn.insert_global(Global {
+                name: WlUInt(name),
+                interface: WlString::new(interface).unwrap(),
+                version: WlUInt(version),
+            });
+        }
+        let surface = Surface::new(conn.new_object(WlObjectId::Surface, 6).unwrap(), &conn);
+        surface.state().preferred_scale.set(Some(2));
+        let pointer_id = conn.new_object(WlObjectId::Pointer, 9).unwrap();
+        let pointer = Pointer::new(
+            pointer_id,
+            9,
+            &conn,
+            Arc::new(SeatState::default()),
+            Box::new(|_, _| {}),
+        );
+        pointer.set_cursor_theme(CursorTheme::with_search_path(
+            "legacy",
+            24,
+            vec![root.clone()],
+        ));
+
+        let data: Vec<u8> = [5, surface.id(), 0, 0]
+            .iter()
+            .flat_map(|arg| arg.to_ne_bytes())
+            .collect();
+        let enter: Vec<u8> = WlMessage::new(pointer_id, 0, &data).unwrap().into();
+        compositor.write_all(&enter).unwrap();
+        conn.dispatch().unwrap();
+        pointer.set_named_cursor("text").unwrap();
+        assert!(pointer.set_named_cursor("zoom-in").is_err());
+        fs::remove_dir_all(root).unwrap();
+
+        drop((pointer, surface, conn));
+        let mut received = Vec::new();
+        compositor.read_to_end(&mut received).unwrap();
+        let mut set_cursor = None;
+        while !received.is_empty() {
+            let header = WlMessageHeader::decode(&received, Endian::NATIVE).unwrap();
+            let size = header.size as usize;
+            if header.object_id == pointer_id && header.opcode == 0 {
+                let args: Vec<u32> = received[WL_MESSAGE_HEADER_LEN..size]
+                    .chunks(4)
+                    .map(|arg| u32::from_ne_bytes(arg.try_into().unwrap()))
+                    .collect();
+                set_cursor = Some((args[0], args[2], args[3]));
+            }
+            received.drain(..size);
+        }
+        // The hotspot (1, 2) of the 48 pixel image, in surface coordinates
+        assert_eq!(set_cursor, Some((5, 0, 1)));
+    }
+}
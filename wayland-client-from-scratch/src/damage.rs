@@ -0,0 +1,175 @@
+use crate::{error::Result, geometry::Rect, protocol::surface::Surface};
+
+/// The number of separate rectangles kept before everything is collapsed
+/// into a single bounding box.
+///
+/// Past this point the per-request overhead of many small damage requests
+/// outweighs the pixels saved by being precise.
+const MAX_DAMAGE_RECTS: usize = 16;
+
+/// Accumulates damaged regions of a buffer between two commits.
+///
+/// Rectangles are clipped to the buffer bounds as they are added, and
+/// overlapping rectangles are merged into their bounding box, so the tracker
+/// always holds a small set of disjoint rectangles. [`DamageTracker::apply`]
+/// turns that set into `wl_surface.damage_buffer` requests and starts over.
+///
+/// All coordinates are buffer coordinates.
+pub struct DamageTracker {
+    /// The current, disjoint set of damaged rectangles.
+    rects: Vec<Rect>,
+    /// The bounds of the buffer the damage applies to.
+    bounds: Rect,
+}
+
+impl DamageTracker {
+    /// Creates a tracker for a buffer of the given size.
+    ///
+    /// The whole buffer starts out damaged, since nothing has been presented yet.
+    pub fn new(width: i32, height: i32) -> DamageTracker {
+        let mut tracker = DamageTracker {
+            rects: Vec::new(),
+            bounds: Rect::new(0, 0, width, height),
+        };
+        tracker.damage_all();
+
+        tracker
+    }
+
+    /// Changes the buffer size, which damages the whole new buffer.
+    pub fn resize(&mut self, width: i32, height: i32) {
+        self.bounds = Rect::new(0, 0, width, height);
+        self.damage_all();
+    }
+
+    /// Marks the whole buffer as damaged.
+    pub fn damage_all(&mut self) {
+        self.rects.clear();
+        if !self.bounds.is_empty() {
+            self.rects.push(self.bounds);
+        }
+    }
+
+    /// Marks a rectangle as damaged.
+    ///
+    /// The rectangle is clipped to the buffer bounds and merged with every
+    /// rectangle it overlaps.
+    pub fn add(&mut self, rect: Rect) {
+        let mut merged = rect.intersection(&self.bounds);
+        if merged.is_empty() {
+            return;
+        }
+
+        // Absorbing a rectangle grows the merged box, which may make it
+        // overlap rectangles that were checked earlier, so loop until stable.
+        loop {
+            let before = self.rects.len();
+            self.rects.retain(|existing| {
+                if existing.intersects(&merged) {
+                    merged = merged.union(existing);
+                    false
+                } else {
+                    true
+                }
+            });
+
+            if self.rects.len() == before {
+                break;
+            }
+        }
+
+        self.rects.push(merged);
+
+        if self.rects.len() > MAX_DAMAGE_RECTS {
+            let bounding = self
+                .rects
+                .iter()
+                .fold(Rect::default(), |acc, rect| acc.union(rect));
+            self.rects.clear();
+            self.rects.push(bounding);
+        }
+    }
+
+    /// Returns `true` if nothing was damaged since the last apply.
+    pub fn is_empty(&self) -> bool {
+        self.rects.is_empty()
+    }
+
+    /// Returns the accumulated damage.
+    pub fn rects(&self) -> &[Rect] {
+        &self.rects
+    }
+
+    /// Removes and returns the accumulated damage.
+    pub fn take(&mut self) -> Vec<Rect> {
+        std::mem::take(&mut self.rects)
+    }
+
+    /// Sends one `wl_surface.damage_buffer` request per accumulated rectangle
+    /// and clears the tracker.
+    ///
+    /// The damage becomes part of the pending surface state and is applied by
+    /// the next commit.
+    pub fn apply(&mut self, surface: &Surface) -> Result<()> {
+        for rect in self.take() {
+            surface.damage_buffer(rect)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns a tracker for a 100x100 buffer with no damage yet.
+    fn undamaged() -> DamageTracker {
+        let mut tracker = DamageTracker::new(100, 100);
+        tracker.take();
+
+        tracker
+    }
+
+    #[test]
+    fn damage_is_clipped_to_the_buffer() {
+        let mut tracker = undamaged();
+        tracker.add(Rect::new(-10, 90, 20, 20));
+        tracker.add(Rect::new(100, 0, 5, 5));
+        tracker.add(Rect::new(20, 20, 0, 10));
+        assert_eq!(tracker.rects(), [Rect::new(0, 90, 10, 10)]);
+
+        tracker.resize(10, 10);
+        assert_eq!(tracker.take(), [Rect::new(0, 0, 10, 10)]);
+        assert!(tracker.is_empty());
+    }
+
+    #[test]
+    fn merging_repeats_until_no_rectangle_overlaps() {
+        let mut tracker = undamaged();
+        tracker.add(Rect::new(0, 9, 10, 10));
+        tracker.add(Rect::new(20, 0, 10, 10));
+        tracker.add(Rect::new(50, 50, 5, 5));
+        assert_eq!(tracker.rects().len(), 3);
+
+        // Only overlaps the second rectangle, whose union with it then
+        // overlaps the first one, checked before
+        tracker.add(Rect::new(5, 5, 20, 2));
+        assert_eq!(
+            tracker.rects(),
+            [Rect::new(50, 50, 5, 5), Rect::new(0, 0, 30, 19)]
+        );
+    }
+
+    #[test]
+    fn too_many_rectangles_collapse_into_their_bounding_box() {
+        let mut tracker = undamaged();
+        for i in 0..MAX_DAMAGE_RECTS as i32 {
+            tracker.add(Rect::new(i * 2, i, 1, 1));
+        }
+        assert_eq!(tracker.rects().len(), MAX_DAMAGE_RECTS);
+
+        tracker.add(Rect::new(90, 90, 1, 1));
+        assert_eq!(tracker.rects(), [Rect::new(0, 0, 91, 91)]);
+    }
+}
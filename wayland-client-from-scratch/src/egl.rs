@@ -0,0 +1,93 @@
+use crate::{
+    error::{Result, anyhow},
+    protocol::surface::Surface,
+};
+
+/// The size state of a native window for `EGL_PLATFORM_WAYLAND`, the
+/// equivalent of `wl_egl_window`.
+///
+/// The window ties a surface to the size a renderer draws at, and keeps the
+/// offset to apply with the next buffer after a resize.
+///
+/// # Limitations
+/// The window cannot be handed to an EGL driver. Mesa and other EGL
+/// implementations talk to the compositor through libwayland-client: the EGL
+/// display must be created from a libwayland `wl_display`, and the driver
+/// dereferences the window's `surface` field as a libwayland `wl_surface`
+/// proxy. This crate speaks the protocol itself and has no such proxy to
+/// fill in, and a libwayland display sharing the socket would allocate
+/// object IDs clashing with the connection's, so the window only tracks the
+/// size state and has no C layout.
+///
+/// The same holds for Vulkan: `VkWaylandSurfaceCreateInfoKHR` of
+/// `VK_KHR_wayland_surface` takes libwayland `wl_display` and `wl_surface`
+/// pointers, which the driver calls libwayland on, so no `VkSurfaceKHR`
+/// can be created for a surface of this crate. Vulkan renderers present
+/// by copying their frames into the buffers of a
+/// [`Swapchain`](crate::swapchain::Swapchain) instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EglWindow {
+    /// The object ID of the surface the window renders to.
+    surface_id: u32,
+    /// The size to render at.
+    width: i32,
+    height: i32,
+    /// The offset of the content relative to the previous buffer.
+    dx: i32,
+    dy: i32,
+}
+
+impl EglWindow {
+    /// Creates a native window of `width` x `height` pixels for `surface`.
+    ///
+    /// # Errors
+    /// Returns an error if the size is not strictly positive.
+    pub fn new(surface: &Surface, width: i32, height: i32) -> Result<EglWindow> {
+        if width <= 0 || height <= 0 {
+            return Err(anyhow!("Invalid EGL window size {}x{}", width, height));
+        }
+
+        Ok(EglWindow {
+            surface_id: surface.id(),
+            width,
+            height,
+            dx: 0,
+            dy: 0,
+        })
+    }
+
+    /// Returns the object ID of the surface the window renders to.
+    pub fn surface_id(&self) -> u32 {
+        self.surface_id
+    }
+
+    /// Returns the size to render at.
+    pub fn size(&self) -> (i32, i32) {
+        (self.width, self.height)
+    }
+
+    /// Returns the offset of the content relative to the previous buffer,
+    /// to pass to [`Surface::offset`] along with the next buffer.
+    pub fn offset(&self) -> (i32, i32) {
+        (self.dx, self.dy)
+    }
+
+    /// Changes the size to render at, like `wl_egl_window_resize`.
+    ///
+    /// `dx` and `dy` move the content relative to the previous buffer, see
+    /// [`EglWindow::offset`].
+    ///
+    /// # Errors
+    /// Returns an error if the size is not strictly positive.
+    pub fn resize(&mut self, width: i32, height: i32, dx: i32, dy: i32) -> Result<()> {
+        if width <= 0 || height <= 0 {
+            return Err(anyhow!("Invalid EGL window size {}x{}", width, height));
+        }
+
+        self.width = width;
+        self.height = height;
+        self.dx = dx;
+        self.dy = dy;
+        Ok(())
+    }
+}
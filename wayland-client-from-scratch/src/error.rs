@@ -0,0 +1,183 @@
+//! The error type returned throughout the crate.
+//!
+//! With the default `anyhow` feature, [`Error`] and [`Result`] are those of
+//! the `anyhow` crate, so errors of the crate mix freely with the errors of
+//! applications built on `anyhow`. Building with `--no-default-features`
+//! drops the dependency for an error enum of the crate offering the part of
+//! the `anyhow` API the crate uses: [`anyhow!`](crate::error::anyhow),
+//! [`Context`], `Error::is` and `Error::downcast_ref`. Code written against
+//! these paths builds either way.
+
+#[cfg(feature = "anyhow")]
+pub use anyhow::{Context, Error, Result, anyhow};
+
+#[cfg(not(feature = "anyhow"))]
+pub use crate::__error_anyhow as anyhow;
+
+#[cfg(not(feature = "anyhow"))]
+pub use minimal::{Context, Error, Result};
+
+/// Builds an [`Error`] from a format string, like `anyhow::anyhow!`.
+#[cfg(not(feature = "anyhow"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __error_anyhow {
+    ($($arg:tt)*) => {
+        $crate::error::Error::msg(::std::format!($($arg)*))
+    };
+}
+
+#[cfg(not(feature = "anyhow"))]
+mod minimal {
+    use std::{
+        error::Error as StdError,
+        fmt::{self, Debug, Display, Formatter},
+    };
+
+    /// A `Result` defaulting to the error type of the crate.
+    pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+    /// The error type of the crate when built without `anyhow`.
+    ///
+    /// Like `anyhow::Error`, it converts from any error type with `?` and
+    /// does not implement [`std::error::Error`] itself, which the blanket
+    /// conversion would conflict with.
+    pub enum Error {
+        /// An error described by a message only, built with
+        /// [`anyhow!`](crate::error::anyhow).
+        Message(String),
+        /// An error of another type, such as an I/O error or one of the
+        /// typed errors of the crate.
+        Source(Box<dyn StdError + Send + Sync>),
+        /// An error with a description of what was being done when it
+        /// happened, added with [`Context`].
+        Context {
+            /// What was being done.
+            context: String,
+            /// The error that happened.
+            source: Box<Error>,
+        },
+    }
+
+    impl Error {
+        /// Creates an error from a message.
+        pub fn msg(message: impl Display) -> Error {
+            Error::Message(message.to_string())
+        }
+
+        /// Wraps the error with a description of what was being done.
+        pub fn context(self, context: impl Display) -> Error {
+            Error::Context {
+                context: context.to_string(),
+                source: Box::new(self),
+            }
+        }
+
+        /// Returns `true` if the error, or the error it adds context to, is
+        /// of type `E`.
+        pub fn is<E: StdError + 'static>(&self) -> bool {
+            self.downcast_ref::<E>().is_some()
+        }
+
+        /// Returns the error, or the error it adds context to, if it is of
+        /// type `E`.
+        pub fn downcast_ref<E: StdError + 'static>(&self) -> Option<&E> {
+            match self {
+                Error::Message(_) => None,
+                Error::Source(source) => source.downcast_ref(),
+                Error::Context { source, .. } => source.downcast_ref(),
+            }
+        }
+    }
+
+    impl<E: StdError + Send + Sync + 'static> From<E> for Error {
+        fn from(error: E) -> Error {
+            Error::Source(Box::new(error))
+        }
+    }
+
+    impl Display for Error {
+        /// Writes the outermost description, or the whole chain separated
+        /// by colons with the alternate flag, like `anyhow`.
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            match self {
+                Error::Message(message) => f.write_str(message),
+                Error::Source(source) => Display::fmt(source, f),
+                Error::Context { context, source } if f.alternate() => {
+                    write!(f, "{context}: {source:#}")
+                }
+                Error::Context { context, .. } => f.write_str(context),
+            }
+        }
+    }
+
+    impl Debug for Error {
+        /// Writes the error and its causes, as printed when `main` fails.
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            write!(f, "{self}")?;
+
+            let mut cause = match self {
+                Error::Context { source, .. } => Some(&**source),
+                _ => None,
+            };
+            if cause.is_some() {
+                write!(f, "\n\nCaused by:")?;
+            }
+            while let Some(error) = cause {
+                write!(f, "\n    {error}")?;
+                cause = match error {
+                    Error::Context { source, .. } => Some(source),
+                    _ => None,
+                };
+            }
+
+            Ok(())
+        }
+    }
+
+    /// Adds a description of what was being done to the error of a
+    /// `Result`, like `anyhow::Context`.
+    pub trait Context<T> {
+        /// Wraps the error with `context`.
+        fn context<C: Display>(self, context: C) -> Result<T>;
+
+        /// Wraps the error with the context returned by `context`, which is
+        /// only called on error.
+        fn with_context<C: Display, F: FnOnce() -> C>(self, context: F) -> Result<T>;
+    }
+
+    impl<T, E: Into<Error>> Context<T> for std::result::Result<T, E> {
+        fn context<C: Display>(self, context: C) -> Result<T> {
+            self.map_err(|error| error.into().context(context))
+        }
+
+        fn with_context<C: Display, F: FnOnce() -> C>(self, context: F) -> Result<T> {
+            self.map_err(|error| error.into().context(context()))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::io;
+
+        use super::*;
+
+        #[test]
+        fn context_keeps_the_source_reachable() {
+            let result: std::result::Result<(), io::Error> =
+                Err(io::Error::from(io::ErrorKind::BrokenPipe));
+            let error = result.context("Failed to flush").unwrap_err();
+
+            assert!(error.is::<io::Error>());
+            assert_eq!(error.to_string(), "Failed to flush");
+            assert_eq!(format!("{error:#}"), "Failed to flush: broken pipe");
+            assert_eq!(
+                format!(
+                    "{:?}",
+                    crate::error::anyhow!("Closed {}", 1).context("Dispatch")
+                ),
+                "Dispatch\n\nCaused by:\n    Closed 1"
+            );
+        }
+    }
+}
@@ -0,0 +1,123 @@
+//! A fence abstraction shared by the explicit synchronization protocols.
+//!
+//! Compositors synchronize buffer access either through DRM syncobj
+//! timelines (`wp_linux_drm_syncobj_manager_v1`) or, for those that do not
+//! offer it yet, through sync_file fences
+//! (`zwp_linux_explicit_synchronization_v1`). [`Fence`] represents either
+//! kind, and [`ExplicitSync`] lets renderers set acquire fences and wait
+//! for buffer releases without caring which protocol the compositor
+//! offers.
+
+use std::{
+    os::fd::{AsFd, OwnedFd},
+    sync::Arc,
+    time::Duration,
+};
+
+use crate::{
+    connection::Connection,
+    error::{Context, Result, anyhow},
+    protocol::linux_drm_syncobj::SyncobjTimeline,
+    sys,
+};
+
+/// A synchronization point that signals once some GPU or compositor work
+/// has finished.
+pub enum Fence {
+    /// A fence that has already signaled.
+    Signaled,
+    /// A sync_file, as exported by `EGL_ANDROID_native_fence_sync` or
+    /// Vulkan, which becomes readable once it signals.
+    SyncFile(OwnedFd),
+    /// A point on a DRM syncobj timeline.
+    TimelinePoint {
+        /// The timeline the point is on.
+        timeline: Arc<SyncobjTimeline>,
+        /// The point value.
+        point: u64,
+    },
+}
+
+impl Fence {
+    /// Tells whether the fence has signaled, without blocking.
+    ///
+    /// # Returns
+    /// `None` for timeline points, whose state can only be queried through
+    /// DRM.
+    pub fn is_signaled(&self) -> Option<bool> {
+        match self {
+            Fence::Signaled => Some(true),
+            Fence::SyncFile(fd) => Some(poll_sync_file(fd, Some(Duration::ZERO)).unwrap_or(false)),
+            Fence::TimelinePoint { .. } => None,
+        }
+    }
+
+    /// Waits for the fence to signal, forever if `timeout` is `None`.
+    ///
+    /// # Returns
+    /// `true` if the fence signaled, `false` if the timeout expired first.
+    ///
+    /// # Errors
+    /// Returns an error if polling the sync_file fails, or for timeline
+    /// points, which can only be waited for through DRM.
+    pub fn wait(&self, timeout: Option<Duration>) -> Result<bool> {
+        match self {
+            Fence::Signaled => Ok(true),
+            Fence::SyncFile(fd) => {
+                poll_sync_file(fd, timeout).context("Failed to wait for a fence")
+            }
+            Fence::TimelinePoint { timeline, point } => Err(anyhow!(
+                "Cannot wait for point {point} of wp_linux_drm_syncobj_timeline_v1 {} without DRM",
+                timeline.id()
+            )),
+        }
+    }
+}
+
+/// Waits for the sync_file `fd` to become readable, which it does once it
+/// signals.
+fn poll_sync_file(fd: &OwnedFd, timeout: Option<Duration>) -> std::io::Result<bool> {
+    let ready = sys::poll(&[(fd.as_fd(), sys::Interest::Readable)], timeout)?;
+    Ok(ready[0])
+}
+
+/// The handler receiving the release fence of a buffer.
+pub type ReleaseHandler = Box<dyn FnOnce(&Connection, Fence) + Send>;
+
+/// The explicit synchronization state of a surface, whichever protocol
+/// backs it.
+///
+/// Like all surface state, fences and release requests apply to the next
+/// commit, which may carry at most one of each.
+pub trait ExplicitSync {
+    /// Sets the fence signaling that the buffer attached by the next commit
+    /// is ready.
+    ///
+    /// # Errors
+    /// Returns an error if the protocol does not take this kind of fence.
+    fn set_acquire_fence(&self, fence: Fence) -> Result<()>;
+
+    /// Asks for the fence signaling that the compositor released the buffer
+    /// attached by the next commit, which `handler` receives. The buffer
+    /// must not be reused before the fence signals.
+    fn request_release(&self, handler: ReleaseHandler) -> Result<()>;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Write, os::unix::net::UnixStream};
+
+    use super::*;
+
+    #[test]
+    fn sync_file_signals_once_readable() {
+        let (reader, mut writer) = UnixStream::pair().unwrap();
+        let fence = Fence::SyncFile(reader.into());
+        assert_eq!(fence.is_signaled(), Some(false));
+        assert!(!fence.wait(Some(Duration::from_millis(1))).unwrap());
+
+        writer.write_all(&[1]).unwrap();
+        assert_eq!(fence.is_signaled(), Some(true));
+        assert!(fence.wait(None).unwrap());
+    }
+}
@@ -0,0 +1,102 @@
+/// An axis-aligned rectangle in integer coordinates.
+///
+/// Rectangles are half-open: a rectangle covers the pixels from `x` up to,
+/// but not including, `x + width` (and likewise vertically). A rectangle with
+/// a non-positive width or height is empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Rect {
+    /// The left edge.
+    pub x: i32,
+    /// The top edge.
+    pub y: i32,
+    /// The horizontal extent.
+    pub width: i32,
+    /// The vertical extent.
+    pub height: i32,
+}
+
+impl Rect {
+    /// Creates a rectangle from its origin and size.
+    pub const fn new(x: i32, y: i32, width: i32, height: i32) -> Rect {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Returns the exclusive right edge.
+    pub fn right(&self) -> i32 {
+        self.x.saturating_add(self.width)
+    }
+
+    /// Returns the exclusive bottom edge.
+    pub fn bottom(&self) -> i32 {
+        self.y.saturating_add(self.height)
+    }
+
+    /// Returns `true` if the rectangle covers no pixels.
+    pub fn is_empty(&self) -> bool {
+        self.width <= 0 || self.height <= 0
+    }
+
+    /// Returns the number of pixels covered by the rectangle.
+    pub fn area(&self) -> i64 {
+        if self.is_empty() {
+            0
+        } else {
+            self.width as i64 * self.height as i64
+        }
+    }
+
+    /// Returns `true` if both rectangles share at least one pixel.
+    pub fn intersects(&self, other: &Rect) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    /// Returns the overlapping part of both rectangles, which may be empty.
+    pub fn intersection(&self, other: &Rect) -> Rect {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+
+        Rect {
+            x,
+            y,
+            width: self.right().min(other.right()) - x,
+            height: self.bottom().min(other.bottom()) - y,
+        }
+    }
+
+    /// Returns the smallest rectangle containing both rectangles.
+    ///
+    /// Empty rectangles do not contribute to the result.
+    pub fn union(&self, other: &Rect) -> Rect {
+        if self.is_empty() {
+            return *other;
+        }
+        if other.is_empty() {
+            return *self;
+        }
+
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+
+        Rect {
+            x,
+            y,
+            width: self.right().max(other.right()) - x,
+            height: self.bottom().max(other.bottom()) - y,
+        }
+    }
+}
+
+impl std::fmt::Display for Rect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Rect {{ x: {}, y: {}, width: {}, height: {} }}",
+            self.x, self.y, self.width, self.height
+        )
+    }
+}
@@ -0,0 +1,664 @@
+use std::{
+    f64::consts::PI,
+    time::{Duration, Instant},
+};
+
+use crate::protocol::touch::{
+    TouchFrame,
+    event::{down::Down, motion::Motion, up::Up},
+};
+
+/// The distance a point can travel and still be a tap or a long press, in
+/// surface-local units.
+pub const DEFAULT_TAP_SLOP: f64 = 10.0;
+
+/// The longest contact recognized as a tap.
+pub const DEFAULT_TAP_TIMEOUT: Duration = Duration::from_millis(300);
+
+/// The contact duration after which a still point is a long press.
+pub const DEFAULT_LONG_PRESS_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// The shortest distance recognized as a swipe, in surface-local units.
+pub const DEFAULT_SWIPE_MIN_DISTANCE: f64 = 50.0;
+
+/// The lowest average speed recognized as a swipe, in surface-local units
+/// per second.
+pub const DEFAULT_SWIPE_MIN_VELOCITY: f64 = 300.0;
+
+/// The change of the distance between two points that starts a pinch, in
+/// surface-local units.
+pub const DEFAULT_PINCH_SLOP: f64 = 10.0;
+
+/// The thresholds of a [`GestureRecognizer`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GestureConfig {
+    /// The distance a point can travel and still be a tap or a long press.
+    pub tap_slop: f64,
+    /// The longest contact recognized as a tap.
+    pub tap_timeout: Duration,
+    /// The contact duration after which a still point is a long press.
+    pub long_press_timeout: Duration,
+    /// The shortest distance between the down and up positions of a swipe.
+    pub swipe_min_distance: f64,
+    /// The lowest average speed of a swipe, in units per second.
+    pub swipe_min_velocity: f64,
+    /// The change of the distance between two points that starts a pinch.
+    pub pinch_slop: f64,
+}
+
+impl Default for GestureConfig {
+    fn default() -> Self {
+        GestureConfig {
+            tap_slop: DEFAULT_TAP_SLOP,
+            tap_timeout: DEFAULT_TAP_TIMEOUT,
+            long_press_timeout: DEFAULT_LONG_PRESS_TIMEOUT,
+            swipe_min_distance: DEFAULT_SWIPE_MIN_DISTANCE,
+            swipe_min_velocity: DEFAULT_SWIPE_MIN_VELOCITY,
+            pinch_slop: DEFAULT_PINCH_SLOP,
+        }
+    }
+}
+
+/// The dominant direction of a swipe, in surface-local coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SwipeDirection {
+    /// Towards negative x.
+    Left,
+    /// Towards positive x.
+    Right,
+    /// Towards negative y.
+    Up,
+    /// Towards positive y.
+    Down,
+}
+
+/// The stage of a continuous gesture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GesturePhase {
+    /// The gesture was recognized.
+    Begin,
+    /// The points moved.
+    Update,
+    /// A point went up, ending the gesture.
+    End,
+    /// The gesture was interrupted by another point or by the compositor
+    /// cancelling the touch sequence; its effect should be reverted.
+    Cancel,
+}
+
+/// A gesture recognized by a [`GestureRecognizer`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Gesture {
+    /// A single point went down and up quickly without moving.
+    Tap {
+        /// The surface the point touched.
+        surface: u32,
+        /// The surface-local position of the point.
+        position: (f64, f64),
+    },
+    /// A single point stayed down without moving. Its up is not a tap.
+    LongPress {
+        /// The surface the point touched.
+        surface: u32,
+        /// The surface-local position of the point.
+        position: (f64, f64),
+    },
+    /// A single point moved quickly and went up.
+    Swipe {
+        /// The surface the point touched.
+        surface: u32,
+        /// The surface-local position the point went down at.
+        start: (f64, f64),
+        /// The surface-local position the point went up at.
+        end: (f64, f64),
+        /// The dominant direction of the motion.
+        direction: SwipeDirection,
+        /// The average speed of the point, in units per second.
+        velocity: f64,
+    },
+    /// Two points moved closer or apart.
+    Pinch {
+        /// The surface the points touched.
+        surface: u32,
+        /// The stage of the pinch.
+        phase: GesturePhase,
+        /// The surface-local midpoint of the points.
+        center: (f64, f64),
+        /// The distance between the points relative to when the second
+        /// point went down.
+        scale: f64,
+        /// The clockwise rotation of the line between the points since the
+        /// second point went down, in radians within `(-π, π]`.
+        rotation: f64,
+    },
+}
+
+/// A point in contact during a gesture.
+#[derive(Debug, Clone, Copy)]
+struct Contact {
+    /// The ID of the touch point.
+    id: i32,
+    /// The position the point went down at.
+    start: (f64, f64),
+    /// The last known position.
+    position: (f64, f64),
+}
+
+/// The touch sequence being recognized, from the first point down to the
+/// last point up.
+#[derive(Debug, Clone, Copy)]
+enum Sequence {
+    /// No point is in contact.
+    Idle,
+    /// One point is in contact: a tap, long press or swipe.
+    Single {
+        /// The surface the point touched.
+        surface: u32,
+        /// The point.
+        contact: Contact,
+        /// When the point went down.
+        down_at: Instant,
+        /// Whether the point left the tap slop.
+        moved: bool,
+        /// Whether the long press was reported.
+        long_pressed: bool,
+    },
+    /// Two points are in contact on the same surface: a pinch.
+    Pair {
+        /// The surface the points touched.
+        surface: u32,
+        /// The points, in the order they went down.
+        contacts: [Contact; 2],
+        /// Whether the pinch began.
+        pinching: bool,
+    },
+    /// The sequence is no gesture, and is ignored until every point is up.
+    Ignored,
+}
+
+/// Recognizes taps, long presses, swipes and two-finger pinches from the
+/// frames of a [`Touch`](crate::protocol::touch::Touch).
+///
+/// Feed every [`TouchFrame`] to [`GestureRecognizer::handle_frame`] as it
+/// arrives. One sequence, from the first point down to the last point up,
+/// produces at most one tap, long press or swipe, or one pinch: a third
+/// point, a point on another surface or a point down after a long press
+/// makes the rest of the sequence ignored.
+///
+/// Long presses fire while no event arrives, and the recognizer does not
+/// own a timer: like [`KeyRepeat`](crate::key_repeat::KeyRepeat), the event
+/// loop sleeps until the next [`GestureRecognizer::deadline`] at most and
+/// collects the long press with [`GestureRecognizer::poll`].
+#[derive(Debug, Clone)]
+pub struct GestureRecognizer {
+    /// The thresholds.
+    config: GestureConfig,
+    /// The sequence being recognized.
+    sequence: Sequence,
+}
+
+impl Default for GestureRecognizer {
+    fn default() -> Self {
+        GestureRecognizer::new(GestureConfig::default())
+    }
+}
+
+impl GestureRecognizer {
+    /// Creates a recognizer using the `config` thresholds.
+    pub fn new(config: GestureConfig) -> GestureRecognizer {
+        GestureRecognizer {
+            config,
+            sequence: Sequence::Idle,
+        }
+    }
+
+    /// Returns the thresholds.
+    pub fn config(&self) -> &GestureConfig {
+        &self.config
+    }
+
+    /// Replaces the thresholds, which apply to the sequence in progress too.
+    pub fn set_config(&mut self, config: GestureConfig) {
+        self.config = config;
+    }
+
+    /// Returns the gestures recognized from a frame received at `now`, in
+    /// order, including a long press that was due and not polled yet.
+    pub fn handle_frame(&mut self, frame: &TouchFrame, now: Instant) -> Vec<Gesture> {
+        let mut gestures: Vec<Gesture> = self.poll(now).into_iter().collect();
+
+        if frame.cancelled {
+            gestures.extend(self.cancel());
+            return gestures;
+        }
+
+        for down in &frame.down {
+            gestures.extend(self.down(down, now));
+        }
+        if !frame.motion.is_empty() {
+            for motion in &frame.motion {
+                self.motion(motion);
+            }
+            gestures.extend(self.pinch_motion());
+        }
+        for up in &frame.up {
+            gestures.extend(self.up(up, now));
+        }
+
+        if frame.points.is_empty() {
+            self.sequence = Sequence::Idle;
+        }
+
+        gestures
+    }
+
+    /// Forgets the sequence in progress, returning the cancellation of the
+    /// pinch that began, if any.
+    pub fn cancel(&mut self) -> Option<Gesture> {
+        let gesture = match self.sequence {
+            Sequence::Pair {
+                surface,
+                contacts,
+                pinching: true,
+            } => Some(pinch(surface, GesturePhase::Cancel, &contacts)),
+            _ => None,
+        };
+
+        self.sequence = Sequence::Idle;
+        gesture
+    }
+
+    /// Returns when the point in contact becomes a long press, if it can.
+    pub fn deadline(&self) -> Option<Instant> {
+        match self.sequence {
+            Sequence::Single {
+                down_at,
+                moved: false,
+                long_pressed: false,
+                ..
+            } => Some(down_at + self.config.long_press_timeout),
+            _ => None,
+        }
+    }
+
+    /// Returns the long press due at `now`, if any.
+    pub fn poll(&mut self, now: Instant) -> Option<Gesture> {
+        if self.deadline().is_none_or(|deadline| now < deadline) {
+            return None;
+        }
+
+        let Sequence::Single {
+            surface,
+            contact,
+            ref mut long_pressed,
+            ..
+        } = self.sequence
+        else {
+            return None;
+        };
+        *long_pressed = true;
+
+        Some(Gesture::LongPress {
+            surface,
+            position: contact.position,
+        })
+    }
+
+    /// Applies a point going down.
+    fn down(&mut self, down: &Down, now: Instant) -> Option<Gesture> {
+        let position = (down.x.to_f64(), down.y.to_f64());
+        let contact = Contact {
+            id: down.id.get(),
+            start: position,
+            position,
+        };
+        let (sequence, gesture) = match self.sequence {
+            Sequence::Idle => (
+                Sequence::Single {
+                    surface: down.surface.get(),
+                    contact,
+                    down_at: now,
+                    moved: false,
+                    long_pressed: false,
+                },
+                None,
+            ),
+            Sequence::Single {
+                surface,
+                contact: first,
+                long_pressed: false,
+                ..
+            } if surface == down.surface.get() => (
+                Sequence::Pair {
+                    surface,
+                    contacts: [first, contact],
+                    pinching: false,
+                },
+                None,
+            ),
+            Sequence::Pair { .. } => (Sequence::Ignored, self.cancel()),
+            _ => (Sequence::Ignored, None),
+        };
+
+        self.sequence = sequence;
+        gesture
+    }
+
+    /// Applies the motion of a point.
+    fn motion(&mut self, motion: &Motion) {
+        let id = motion.id.get();
+        let position = (motion.x.to_f64(), motion.y.to_f64());
+        let tap_slop = self.config.tap_slop;
+
+        match &mut self.sequence {
+            Sequence::Single { contact, moved, .. } if contact.id == id => {
+                contact.position = position;
+                if distance(contact.start, position) > tap_slop {
+                    *moved = true;
+                }
+            }
+            Sequence::Pair { contacts, .. } => {
+                for contact in contacts.iter_mut().filter(|contact| contact.id == id) {
+                    contact.position = position;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns the pinch update after the points of a frame moved, beginning
+    /// the pinch once their distance changed enough.
+    fn pinch_motion(&mut self) -> Option<Gesture> {
+        let pinch_slop = self.config.pinch_slop;
+        let Sequence::Pair {
+            surface,
+            contacts,
+            ref mut pinching,
+        } = self.sequence
+        else {
+            return None;
+        };
+
+        let phase = if *pinching {
+            GesturePhase::Update
+        } else {
+            let [first, second] = contacts;
+            let start = distance(first.start, second.start);
+            let current = distance(first.position, second.position);
+            if (current - start).abs() < pinch_slop {
+                return None;
+            }
+            *pinching = true;
+            GesturePhase::Begin
+        };
+
+        Some(pinch(surface, phase, &contacts))
+    }
+
+    /// Applies a point going up.
+    fn up(&mut self, up: &Up, now: Instant) -> Option<Gesture> {
+        let id = up.id.get();
+        let (sequence, gesture) = match self.sequence {
+            Sequence::Single {
+                surface,
+                contact,
+                down_at,
+                moved,
+                long_pressed,
+            } if contact.id == id => {
+                let elapsed = now.saturating_duration_since(down_at);
+                let gesture = if long_pressed {
+                    None
+                } else if !moved {
+                    (elapsed <= self.config.tap_timeout).then_some(Gesture::Tap {
+                        surface,
+                        position: contact.position,
+                    })
+                } else {
+                    self.swipe(surface, &contact, elapsed)
+                };
+                (Sequence::Idle, gesture)
+            }
+            Sequence::Pair {
+                surface,
+                contacts,
+                pinching,
+            } if contacts.iter().any(|contact| contact.id == id) => {
+                let gesture = pinching.then(|| pinch(surface, GesturePhase::End, &contacts));
+                (Sequence::Ignored, gesture)
+            }
+            sequence => (sequence, None),
+        };
+
+        self.sequence = sequence;
+        gesture
+    }
+
+    /// Returns the swipe of a point in contact for `elapsed`, if it was far
+    /// and fast enough.
+    fn swipe(&self, surface: u32, contact: &Contact, elapsed: Duration) -> Option<Gesture> {
+        let length = distance(contact.start, contact.position);
+        let velocity = if elapsed.is_zero() {
+            f64::INFINITY
+        } else {
+            length / elapsed.as_secs_f64()
+        };
+        if length < self.config.swipe_min_distance || velocity < self.config.swipe_min_velocity {
+            return None;
+        }
+
+        let (dx, dy) = (
+            contact.position.0 - contact.start.0,
+            contact.position.1 - contact.start.1,
+        );
+        let direction = match (dx.abs() >= dy.abs(), dx < 0.0, dy < 0.0) {
+            (true, true, _) => SwipeDirection::Left,
+            (true, false, _) => SwipeDirection::Right,
+            (false, _, true) => SwipeDirection::Up,
+            (false, _, false) => SwipeDirection::Down,
+        };
+
+        Some(Gesture::Swipe {
+            surface,
+            start: contact.start,
+            end: contact.position,
+            direction,
+            velocity,
+        })
+    }
+}
+
+/// Returns the pinch of two points at their last known positions.
+fn pinch(surface: u32, phase: GesturePhase, [first, second]: &[Contact; 2]) -> Gesture {
+    let start = distance(first.start, second.start);
+    let current = distance(first.position, second.position);
+    let scale = if start > 0.0 { current / start } else { 1.0 };
+
+    let mut rotation = angle(first.position, second.position) - angle(first.start, second.start);
+    if rotation > PI {
+        rotation -= 2.0 * PI;
+    } else if rotation <= -PI {
+        rotation += 2.0 * PI;
+    }
+
+    Gesture::Pinch {
+        surface,
+        phase,
+        center: (
+            (first.position.0 + second.position.0) / 2.0,
+            (first.position.1 + second.position.1) / 2.0,
+        ),
+        scale,
+        rotation,
+    }
+}
+
+/// Returns the distance between two positions.
+fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (b.0 - a.0).hypot(b.1 - a.1)
+}
+
+/// Returns the angle of the line from `a` to `b`, clockwise from the x axis
+/// since y points down.
+fn angle(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (b.1 - a.1).atan2(b.0 - a.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::types::{WlFixed, WlInt, WlObject, WlUInt};
+
+    const SURFACE: u32 = 3;
+
+    fn down(id: i32, x: f64, y: f64) -> Down {
+        Down {
+            serial: WlUInt(1),
+            time: WlUInt(0),
+            surface: WlObject(SURFACE),
+            id: WlInt(id),
+            x: WlFixed::from_f64(x),
+            y: WlFixed::from_f64(y),
+        }
+    }
+
+    fn motion(id: i32, x: f64, y: f64) -> Motion {
+        Motion {
+            time: WlUInt(0),
+            id: WlInt(id),
+            x: WlFixed::from_f64(x),
+            y: WlFixed::from_f64(y),
+        }
+    }
+
+    fn up(id: i32) -> Up {
+        Up {
+            serial: WlUInt(2),
+            time: WlUInt(0),
+            id: WlInt(id),
+        }
+    }
+
+    /// Builds a frame, with a non-empty point list unless `released`.
+    fn frame(down: Vec<Down>, motion: Vec<Motion>, up: Vec<Up>, released: bool) -> TouchFrame {
+        let points = if released {
+            Vec::new()
+        } else {
+            vec![crate::protocol::touch::TouchPoint {
+                id: 0,
+                surface: SURFACE,
+                position: (0.0, 0.0),
+            }]
+        };
+        TouchFrame {
+            down,
+            up,
+            motion,
+            points,
+            ..TouchFrame::default()
+        }
+    }
+
+    #[test]
+    fn tap_long_press_and_swipe() {
+        let mut recognizer = GestureRecognizer::default();
+        let t0 = Instant::now();
+        let ms = Duration::from_millis;
+
+        recognizer.handle_frame(&frame(vec![down(0, 10.0, 10.0)], vec![], vec![], false), t0);
+        let gestures =
+            recognizer.handle_frame(&frame(vec![], vec![], vec![up(0)], true), t0 + ms(100));
+        assert_eq!(
+            gestures,
+            [Gesture::Tap {
+                surface: SURFACE,
+                position: (10.0, 10.0)
+            }]
+        );
+
+        recognizer.handle_frame(&frame(vec![down(1, 10.0, 10.0)], vec![], vec![], false), t0);
+        assert_eq!(recognizer.deadline(), Some(t0 + DEFAULT_LONG_PRESS_TIMEOUT));
+        assert_eq!(recognizer.poll(t0 + ms(100)), None);
+        assert!(matches!(
+            recognizer.poll(t0 + ms(600)),
+            Some(Gesture::LongPress { .. })
+        ));
+        let gestures =
+            recognizer.handle_frame(&frame(vec![], vec![], vec![up(1)], true), t0 + ms(700));
+        assert!(gestures.is_empty());
+
+        recognizer.handle_frame(&frame(vec![down(2, 10.0, 10.0)], vec![], vec![], false), t0);
+        recognizer.handle_frame(
+            &frame(vec![], vec![motion(2, 10.0, 110.0)], vec![], false),
+            t0 + ms(50),
+        );
+        assert_eq!(recognizer.deadline(), None);
+        let gestures =
+            recognizer.handle_frame(&frame(vec![], vec![], vec![up(2)], true), t0 + ms(100));
+        assert_eq!(
+            gestures,
+            [Gesture::Swipe {
+                surface: SURFACE,
+                start: (10.0, 10.0),
+                end: (10.0, 110.0),
+                direction: SwipeDirection::Down,
+                velocity: 1000.0,
+            }]
+        );
+    }
+
+    #[test]
+    fn pinch_begins_past_the_slop_and_ends_on_up() {
+        let mut recognizer = GestureRecognizer::default();
+        let t0 = Instant::now();
+
+        let gestures = recognizer.handle_frame(
+            &frame(
+                vec![down(0, 0.0, 0.0), down(1, 100.0, 0.0)],
+                vec![],
+                vec![],
+                false,
+            ),
+            t0,
+        );
+        assert!(gestures.is_empty());
+
+        let gestures = recognizer.handle_frame(
+            &frame(vec![], vec![motion(1, 105.0, 0.0)], vec![], false),
+            t0,
+        );
+        assert!(gestures.is_empty());
+
+        let gestures = recognizer.handle_frame(
+            &frame(vec![], vec![motion(1, 0.0, 200.0)], vec![], false),
+            t0,
+        );
+        let [
+            Gesture::Pinch {
+                phase: GesturePhase::Begin,
+                center,
+                scale,
+                rotation,
+                ..
+            },
+        ] = gestures[..]
+        else {
+            panic!("expected a pinch begin, got {gestures:?}");
+        };
+        assert_eq!((center, scale), ((0.0, 100.0), 2.0));
+        assert!((rotation - PI / 2.0).abs() < 1e-9);
+
+        let gestures = recognizer.handle_frame(&frame(vec![], vec![], vec![up(0)], false), t0);
+        assert!(matches!(
+            gestures[..],
+            [Gesture::Pinch {
+                phase: GesturePhase::End,
+                ..
+            }]
+        ));
+
+        // The remaining point is no tap
+        let gestures = recognizer.handle_frame(&frame(vec![], vec![], vec![up(1)], true), t0);
+        assert!(gestures.is_empty());
+    }
+}
@@ -0,0 +1,189 @@
+use std::path::Path;
+
+use crate::{
+    error::{Context, Result},
+    pixel::PixelFormat,
+};
+
+/// The signature every PNG file starts with.
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+/// The largest payload of a stored deflate block.
+const MAX_STORED_BLOCK_LEN: usize = u16::MAX as usize;
+
+/// The pixels of a captured frame or shm buffer, as laid out in memory.
+#[derive(Debug, Clone, Copy)]
+pub struct FrameData<'a> {
+    /// The raw pixel bytes, `height` rows of `stride` bytes.
+    pub pixels: &'a [u8],
+    /// The width of the frame in pixels.
+    pub width: u32,
+    /// The height of the frame in pixels.
+    pub height: u32,
+    /// The number of bytes between the starts of two consecutive rows.
+    pub stride: usize,
+    /// The layout of each pixel.
+    pub format: PixelFormat,
+    /// Whether the rows are stored bottom to top, as capture protocols
+    /// report with their `y_invert` flag.
+    pub y_invert: bool,
+}
+
+impl FrameData<'_> {
+    /// Encodes the frame as an 8-bit RGBA PNG image.
+    ///
+    /// Rows are written top to bottom whatever their order in memory, and
+    /// premultiplied alpha is converted to the straight alpha PNG uses.
+    /// The image data is stored uncompressed, which keeps the encoder
+    /// free of dependencies at the cost of larger files.
+    ///
+    /// # Errors
+    /// Returns an error if the pixels or the stride are too small for the frame.
+    pub fn to_png(&self) -> Result<Vec<u8>> {
+        let rgba = self
+            .format
+            .to_rgba(self.pixels, self.width, self.height, self.stride)?;
+
+        // Each scanline starts with its filter type, 0 meaning unfiltered
+        let row_len = self.width as usize * 4;
+        let mut scanlines = Vec::with_capacity((row_len + 1) * self.height as usize);
+        let mut push_row = |row: &[u8]| {
+            scanlines.push(0);
+            scanlines.extend_from_slice(row);
+        };
+        if self.y_invert {
+            rgba.chunks_exact(row_len.max(1))
+                .rev()
+                .for_each(&mut push_row);
+        } else {
+            rgba.chunks_exact(row_len.max(1)).for_each(&mut push_row);
+        }
+
+        let mut header = Vec::with_capacity(13);
+        header.extend_from_slice(&self.width.to_be_bytes());
+        header.extend_from_slice(&self.height.to_be_bytes());
+        // 8 bits per channel, RGBA, deflate, no filter method, no interlacing
+        header.extend_from_slice(&[8, 6, 0, 0, 0]);
+
+        let mut png = PNG_SIGNATURE.to_vec();
+        write_chunk(&mut png, b"IHDR", &header);
+        write_chunk(&mut png, b"IDAT", &zlib_stored(&scanlines));
+        write_chunk(&mut png, b"IEND", &[]);
+
+        Ok(png)
+    }
+
+    /// Encodes the frame with [`FrameData::to_png`] and writes it to `path`.
+    ///
+    /// # Errors
+    /// Returns an error if the frame cannot be encoded or the file cannot
+    /// be written.
+    pub fn save_png(&self, path: &Path) -> Result<()> {
+        let png = self.to_png()?;
+        std::fs::write(path, png)
+            .with_context(|| format!("Failed to write PNG file {}", path.display()))
+    }
+}
+
+/// Appends a PNG chunk with its length and CRC.
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// Wraps `data` in a zlib stream of stored, uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let blocks = data.len().div_ceil(MAX_STORED_BLOCK_LEN).max(1);
+    let mut zlib = Vec::with_capacity(data.len() + blocks * 5 + 6);
+
+    // Deflate with a 32 KiB window, no preset dictionary
+    zlib.extend_from_slice(&[0x78, 0x01]);
+
+    let mut chunks = data.chunks(MAX_STORED_BLOCK_LEN).peekable();
+    if chunks.peek().is_none() {
+        zlib.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(chunk) = chunks.next() {
+        let is_final = chunks.peek().is_none();
+        let len = chunk.len() as u16;
+        zlib.push(u8::from(is_final));
+        zlib.extend_from_slice(&len.to_le_bytes());
+        zlib.extend_from_slice(&(!len).to_le_bytes());
+        zlib.extend_from_slice(chunk);
+    }
+
+    zlib.extend_from_slice(&adler32(data).to_be_bytes());
+    zlib
+}
+
+/// Computes the CRC-32 of `data`, as used by PNG chunks.
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = u32::MAX;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+
+    !crc
+}
+
+/// Computes the Adler-32 checksum of `data`, as used by zlib streams.
+fn adler32(data: &[u8]) -> u32 {
+    const MODULUS: u32 = 65521;
+
+    let (mut a, mut b) = (1u32, 0u32);
+    // Sums stay below 2^32 for chunks of this size before being reduced
+    for chunk in data.chunks(5552) {
+        for &byte in chunk {
+            a += u32::from(byte);
+            b += a;
+        }
+        a %= MODULUS;
+        b %= MODULUS;
+    }
+
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksums_match_reference_values() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn png_rows_honor_stride_and_y_invert() {
+        // Two rows of one XRGB pixel each, plus four bytes of row padding
+        let pixels = [0, 0, 255, 0, 9, 9, 9, 9, 255, 0, 0, 0];
+        let frame = FrameData {
+            pixels: &pixels,
+            width: 1,
+            height: 2,
+            stride: 8,
+            format: PixelFormat::Xrgb8888,
+            y_invert: true,
+        };
+
+        let png = frame.to_png().unwrap();
+        assert_eq!(png[..8], PNG_SIGNATURE);
+        assert_eq!(&png[12..16], b"IHDR");
+        assert!(png.ends_with(&[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]));
+
+        // The IDAT chunk follows IHDR: length, type, zlib header, block header
+        let idat = &png[33..];
+        assert_eq!(&idat[4..8], b"IDAT");
+        let scanlines = &idat[8 + 2 + 5..][..10];
+        assert_eq!(scanlines, [0, 0, 0, 255, 255, 0, 255, 0, 0, 255]);
+    }
+}
@@ -0,0 +1,295 @@
+use std::sync::{Arc, Weak};
+
+use crate::{
+    connection::Connection,
+    error::{Error, Result, anyhow},
+    protocol::{
+        keyboard::{Keyboard, KeyboardEvent},
+        pointer::{Pointer, PointerFrame},
+        seat::{Capability, Seat, SeatEvent},
+        touch::{Touch, TouchFrame},
+    },
+    sync::Lock,
+};
+
+/// An input event delivered to the handlers of an [`Input`], along with
+/// the seat it came from.
+pub enum InputEvent {
+    /// The pointer input of one `wl_pointer.frame`.
+    Pointer(PointerFrame),
+    /// A keyboard event, including synthesized key repeats.
+    Keyboard(KeyboardEvent),
+    /// The touch input of one `wl_touch.frame`.
+    Touch(TouchFrame),
+    /// The seat gained a capability, such as a plugged in keyboard, and
+    /// its device was created.
+    DeviceAdded(Capability),
+    /// The seat lost a capability, such as an unplugged keyboard, and its
+    /// device was released. No event of the device follows.
+    DeviceRemoved(Capability),
+}
+
+/// The handler receiving the input events of one or all seats.
+type InputHandler = Box<dyn FnMut(&Seat, InputEvent) + Send>;
+
+/// The input devices of one seat.
+struct SeatInput {
+    /// The seat the devices belong to.
+    seat: Seat,
+    /// The pointer, while the seat has the capability.
+    pointer: Lock<Option<Pointer>>,
+    /// The keyboard, while the seat has the capability.
+    keyboard: Lock<Option<Keyboard>>,
+    /// The touch device, while the seat has the capability.
+    touch: Lock<Option<Touch>>,
+    /// The handler registered for this seat only, taking precedence over
+    /// the handler of the [`Input`].
+    handler: Lock<Option<InputHandler>>,
+}
+
+/// The state of an [`Input`], shared with the handlers of the devices.
+struct InputInner {
+    /// The bound seats, in the order their globals were announced.
+    seats: Vec<Arc<SeatInput>>,
+    /// The handler receiving the events of the seats without a handler of
+    /// their own, taken out while it runs.
+    handler: Lock<Option<InputHandler>>,
+    /// The error raised while creating or releasing the devices of a seat,
+    /// returned by [`Input::take_error`].
+    error: Lock<Option<Error>>,
+}
+
+/// The input devices of every seat of the compositor.
+///
+/// Most sessions have a single seat, but a compositor may advertise several
+/// `wl_seat` globals, each a group of devices used by one user with its own
+/// pointer position and keyboard focus. `Input` binds all of them and
+/// creates independent pointer, keyboard and touch devices for each as soon
+/// as the seat announces the capability.
+///
+/// Capabilities change at runtime when devices are plugged in or out: the
+/// device of a new capability is created, the device of a lost one is
+/// released, and the handler is notified with [`InputEvent::DeviceAdded`]
+/// and [`InputEvent::DeviceRemoved`]. Every event is delivered along
+/// with the [`Seat`] it came from, to the handler registered for that seat
+/// with [`Input::set_seat_handler`], or to the handler given to
+/// [`Input::new`] otherwise.
+///
+/// `Input` is a cheap handle; clones refer to the same devices.
+#[derive(Clone)]
+pub struct Input {
+    inner: Arc<InputInner>,
+}
+
+impl Input {
+    /// Binds every seat advertised by the compositor.
+    ///
+    /// The capabilities of the seats, and with them the devices, are known
+    /// after the next [`Connection::roundtrip`].
+    pub fn new<F>(conn: &Connection, handler: F) -> Result<Input>
+    where
+        F: FnMut(&Seat, InputEvent) + Send + 'static,
+    {
+        let seats = Seat::bind_all(conn)?
+            .into_iter()
+            .map(|seat| {
+                Arc::new(SeatInput {
+                    seat,
+                    pointer: Lock::new(None),
+                    keyboard: Lock::new(None),
+                    touch: Lock::new(None),
+                    handler: Lock::new(None),
+                })
+            })
+            .collect();
+
+        let inner = Arc::new(InputInner {
+            seats,
+            handler: Lock::new(Some(Box::new(handler))),
+            error: Lock::new(None),
+        });
+
+        for seat_input in &inner.seats {
+            let input = Arc::downgrade(&inner);
+            let weak_seat = Arc::downgrade(seat_input);
+            seat_input.seat.set_event_handler(move |_, event| {
+                let SeatEvent::Capabilities(_) = event else {
+                    return;
+                };
+                let (Some(inner), Some(seat_input)) = (input.upgrade(), weak_seat.upgrade()) else {
+                    return;
+                };
+
+                match update_devices(&input, &seat_input) {
+                    Ok(changes) => {
+                        for change in changes {
+                            deliver(&input, &weak_seat, change);
+                        }
+                    }
+                    Err(error) => {
+                        inner.error.replace(Some(error));
+                    }
+                }
+            });
+        }
+
+        Ok(Input { inner })
+    }
+
+    /// Returns the bound seats.
+    pub fn seats(&self) -> Vec<Seat> {
+        self.inner
+            .seats
+            .iter()
+            .map(|seat_input| seat_input.seat.clone())
+            .collect()
+    }
+
+    /// Delivers the events of the seat with the object ID `seat_id` to
+    /// `handler` instead of the handler given to [`Input::new`].
+    ///
+    /// # Errors
+    /// Returns an error if no bound seat has this ID.
+    pub fn set_seat_handler<F>(&self, seat_id: u32, handler: F) -> Result<()>
+    where
+        F: FnMut(&Seat, InputEvent) + Send + 'static,
+    {
+        let seat_input = self
+            .inner
+            .seats
+            .iter()
+            .find(|seat_input| seat_input.seat.id() == seat_id)
+            .ok_or_else(|| anyhow!("Seat {seat_id} is not bound by this input"))?;
+
+        seat_input.handler.replace(Some(Box::new(handler)));
+
+        Ok(())
+    }
+
+    /// Returns the error raised while creating or releasing the devices of
+    /// a seat, if any, as this happens while dispatching events.
+    pub fn take_error(&self) -> Result<()> {
+        match self.inner.error.take() {
+            Some(error) => Err(error.context("Failed to create input devices")),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Creates the devices of the capabilities a seat gained and releases the
+/// devices of the capabilities it lost.
+///
+/// # Returns
+/// The [`InputEvent::DeviceAdded`] and [`InputEvent::DeviceRemoved`] events
+/// announcing the changes.
+fn update_devices(
+    input: &Weak<InputInner>,
+    seat_input: &Arc<SeatInput>,
+) -> Result<Vec<InputEvent>> {
+    let seat = &seat_input.seat;
+    let mut changes = Vec::new();
+
+    let change = sync_device(
+        &seat_input.pointer,
+        seat.has_capability(Capability::Pointer),
+        || {
+            let (input, weak_seat) = (input.clone(), Arc::downgrade(seat_input));
+            seat.get_pointer(move |_, frame| {
+                deliver(&input, &weak_seat, InputEvent::Pointer(frame));
+            })
+        },
+        Pointer::release,
+    )?;
+    changes.extend(change.map(|added| device_change(Capability::Pointer, added)));
+
+    let change = sync_device(
+        &seat_input.keyboard,
+        seat.has_capability(Capability::Keyboard),
+        || {
+            let (input, weak_seat) = (input.clone(), Arc::downgrade(seat_input));
+            seat.get_keyboard(move |_, event| {
+                deliver(&input, &weak_seat, InputEvent::Keyboard(event));
+            })
+        },
+        Keyboard::release,
+    )?;
+    changes.extend(change.map(|added| device_change(Capability::Keyboard, added)));
+
+    let change = sync_device(
+        &seat_input.touch,
+        seat.has_capability(Capability::Touch),
+        || {
+            let (input, weak_seat) = (input.clone(), Arc::downgrade(seat_input));
+            seat.get_touch(move |_, frame| {
+                deliver(&input, &weak_seat, InputEvent::Touch(frame));
+            })
+        },
+        Touch::release,
+    )?;
+    changes.extend(change.map(|added| device_change(Capability::Touch, added)));
+
+    Ok(changes)
+}
+
+/// Creates or releases the device in `slot` so it exists exactly when the
+/// seat has its capability.
+///
+/// # Returns
+/// `Some(true)` if the device was created, `Some(false)` if it was
+/// released, `None` if nothing changed.
+fn sync_device<D>(
+    slot: &Lock<Option<D>>,
+    capable: bool,
+    create: impl FnOnce() -> Result<D>,
+    release: impl FnOnce(D) -> Result<()>,
+) -> Result<Option<bool>> {
+    let exists = slot.borrow().is_some();
+
+    match (capable, exists) {
+        (true, false) => {
+            let device = create()?;
+            slot.replace(Some(device));
+            Ok(Some(true))
+        }
+        (false, true) => {
+            if let Some(device) = slot.take() {
+                release(device)?;
+            }
+            Ok(Some(false))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Returns the event announcing that the device of `capability` was added
+/// or removed.
+fn device_change(capability: Capability, added: bool) -> InputEvent {
+    if added {
+        InputEvent::DeviceAdded(capability)
+    } else {
+        InputEvent::DeviceRemoved(capability)
+    }
+}
+
+/// Delivers `event` to the handler of the seat, or to the handler of the
+/// input if the seat has none.
+///
+/// The handler is taken out while it runs, so it may call back into the
+/// input.
+fn deliver(input: &Weak<InputInner>, seat_input: &Weak<SeatInput>, event: InputEvent) {
+    let (Some(inner), Some(seat_input)) = (input.upgrade(), seat_input.upgrade()) else {
+        return;
+    };
+
+    let slot = if seat_input.handler.borrow().is_some() {
+        &seat_input.handler
+    } else {
+        &inner.handler
+    };
+    let Some(mut handler) = slot.borrow_mut().take() else {
+        return;
+    };
+
+    handler(&seat_input.seat, event);
+    slot.borrow_mut().get_or_insert(handler);
+}
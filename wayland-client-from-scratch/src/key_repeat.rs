@@ -0,0 +1,238 @@
+use std::time::{Duration, Instant};
+
+/// The repeat rate used until the compositor sends `wl_keyboard.repeat_info`,
+/// in characters per second.
+pub const DEFAULT_REPEAT_RATE: i32 = 25;
+
+/// The repeat delay used until the compositor sends `wl_keyboard.repeat_info`,
+/// in milliseconds.
+pub const DEFAULT_REPEAT_DELAY: i32 = 600;
+
+/// Linux evdev codes of the modifier and lock keys, which never repeat.
+///
+/// Without a keymap interpreter the per-key repeat flag of the XKB keymap is
+/// unknown, so the keys every common layout marks as non-repeating are
+/// listed here instead.
+const NON_REPEATING_KEYS: [u32; 11] = [
+    29,  // KEY_LEFTCTRL
+    42,  // KEY_LEFTSHIFT
+    54,  // KEY_RIGHTSHIFT
+    56,  // KEY_LEFTALT
+    58,  // KEY_CAPSLOCK
+    69,  // KEY_NUMLOCK
+    70,  // KEY_SCROLLLOCK
+    97,  // KEY_RIGHTCTRL
+    100, // KEY_RIGHTALT
+    125, // KEY_LEFTMETA
+    126, // KEY_RIGHTMETA
+];
+
+/// A key event synthesized by [`KeyRepeat`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RepeatedKey {
+    /// The serial of the press that started the repeat.
+    pub serial: u32,
+    /// The press timestamp advanced by the time elapsed since the press, in milliseconds.
+    pub time: u32,
+    /// The Linux evdev code of the repeated key.
+    pub key: u32,
+}
+
+/// The key currently being repeated.
+#[derive(Debug, Clone, Copy)]
+struct HeldKey {
+    /// The key, serial and timestamp of the press.
+    press: RepeatedKey,
+    /// When the press was received.
+    pressed_at: Instant,
+    /// When the next repeat is due.
+    next: Instant,
+}
+
+/// Client-side key repeat, driven by `wl_keyboard.repeat_info`.
+///
+/// Wayland compositors only report presses and releases; clients repeat held
+/// keys themselves. Only the most recently pressed key repeats, starting
+/// `delay` milliseconds after the press and then `rate` times per second,
+/// until it is released or the keyboard focus is lost.
+///
+/// The engine does not own a timer: the event loop asks for the next
+/// [`KeyRepeat::deadline`], sleeps until then at most, and collects the due
+/// repeat with [`KeyRepeat::poll`].
+#[derive(Debug, Clone)]
+pub struct KeyRepeat {
+    /// Repeats per second, 0 disabling key repeat.
+    rate: i32,
+    /// Milliseconds between the press and the first repeat.
+    delay: i32,
+    /// The key being repeated, if any.
+    held: Option<HeldKey>,
+}
+
+impl Default for KeyRepeat {
+    fn default() -> Self {
+        KeyRepeat::new()
+    }
+}
+
+impl KeyRepeat {
+    /// Creates an engine using the default rate and delay.
+    pub fn new() -> KeyRepeat {
+        KeyRepeat {
+            rate: DEFAULT_REPEAT_RATE,
+            delay: DEFAULT_REPEAT_DELAY,
+            held: None,
+        }
+    }
+
+    /// Returns the number of repeats per second, 0 if key repeat is disabled.
+    pub fn rate(&self) -> i32 {
+        self.rate
+    }
+
+    /// Returns the delay before the first repeat, in milliseconds.
+    pub fn delay(&self) -> i32 {
+        self.delay
+    }
+
+    /// Applies the values of a `wl_keyboard.repeat_info` event.
+    ///
+    /// A rate of 0 (or less) disables key repeat and stops the current repeat.
+    /// A key already being repeated keeps its schedule until its next repeat.
+    pub fn set_repeat_info(&mut self, rate: i32, delay: i32) {
+        self.rate = rate.max(0);
+        self.delay = delay.max(0);
+
+        if self.rate == 0 {
+            self.held = None;
+        }
+    }
+
+    /// Records a key press received at `now`.
+    ///
+    /// The pressed key replaces any key being repeated. Modifier and lock
+    /// keys stop the current repeat without starting a new one.
+    pub fn press(&mut self, key: u32, serial: u32, time: u32, now: Instant) {
+        if self.rate == 0 || NON_REPEATING_KEYS.contains(&key) {
+            self.held = None;
+            return;
+        }
+
+        self.held = Some(HeldKey {
+            press: RepeatedKey { serial, time, key },
+            pressed_at: now,
+            next: now + Duration::from_millis(self.delay as u64),
+        });
+    }
+
+    /// Records a key release, stopping the repeat if it is for the repeated key.
+    pub fn release(&mut self, key: u32) {
+        if self.held.is_some_and(|held| held.press.key == key) {
+            self.held = None;
+        }
+    }
+
+    /// Stops any repeat, for instance when the keyboard focus is lost.
+    pub fn cancel(&mut self) {
+        self.held = None;
+    }
+
+    /// Returns the key being repeated, if any.
+    pub fn held_key(&self) -> Option<u32> {
+        self.held.map(|held| held.press.key)
+    }
+
+    /// Returns when the next repeat is due, if a key is being repeated.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.held.map(|held| held.next)
+    }
+
+    /// Returns the repeat due at `now`, if any, and schedules the next one.
+    ///
+    /// At most one repeat is produced per call. If the event loop fell behind
+    /// by more than a repeat interval, the missed repeats are dropped rather
+    /// than delivered in a burst.
+    pub fn poll(&mut self, now: Instant) -> Option<RepeatedKey> {
+        let rate = self.rate;
+        let held = self.held.as_mut()?;
+        if now < held.next || rate <= 0 {
+            return None;
+        }
+
+        let interval = Duration::from_secs(1) / rate as u32;
+        held.next += interval;
+        if held.next <= now {
+            held.next = now + interval;
+        }
+
+        let elapsed = now.duration_since(held.pressed_at).as_millis() as u32;
+
+        Some(RepeatedKey {
+            time: held.press.time.wrapping_add(elapsed),
+            ..held.press
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeats_start_after_the_delay_at_the_rate() {
+        let mut repeat = KeyRepeat::new();
+        repeat.set_repeat_info(10, 500);
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+
+        repeat.press(30, 4, 1000, start);
+        assert_eq!(repeat.held_key(), Some(30));
+        assert_eq!(repeat.deadline(), Some(at(500)));
+        assert_eq!(repeat.poll(at(499)), None);
+
+        let first = RepeatedKey {
+            serial: 4,
+            time: 1500,
+            key: 30,
+        };
+        assert_eq!(repeat.poll(at(500)), Some(first));
+        assert_eq!(repeat.poll(at(500)), None);
+        assert_eq!(repeat.deadline(), Some(at(600)));
+        assert_eq!(repeat.poll(at(600)).map(|key| key.time), Some(1600));
+
+        // Falling behind drops the missed repeats
+        assert_eq!(repeat.poll(at(950)).map(|key| key.time), Some(1950));
+        assert_eq!(repeat.deadline(), Some(at(1050)));
+        assert_eq!(repeat.poll(at(1000)), None);
+    }
+
+    #[test]
+    fn releases_and_modifiers_stop_the_repeat() {
+        let mut repeat = KeyRepeat::new();
+        let start = Instant::now();
+        let delay = Duration::from_millis(DEFAULT_REPEAT_DELAY as u64);
+
+        repeat.press(30, 1, 0, start);
+        repeat.press(31, 2, 0, start);
+        repeat.release(30);
+        assert_eq!(repeat.held_key(), Some(31));
+        repeat.release(31);
+        assert_eq!(repeat.held_key(), None);
+        assert_eq!(repeat.deadline(), None);
+        assert_eq!(repeat.poll(start + delay), None);
+
+        repeat.press(30, 3, 0, start);
+        repeat.press(42, 4, 0, start);
+        assert_eq!(repeat.held_key(), None);
+
+        repeat.press(30, 5, 0, start);
+        repeat.cancel();
+        assert_eq!(repeat.poll(start + delay), None);
+
+        repeat.press(30, 6, 0, start);
+        repeat.set_repeat_info(0, 100);
+        assert_eq!(repeat.held_key(), None);
+        repeat.press(30, 7, 0, start);
+        assert_eq!(repeat.poll(start + delay), None);
+    }
+}
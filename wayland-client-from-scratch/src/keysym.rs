@@ -0,0 +1,403 @@
+use crate::protocol::keyboard::event::modifiers::Modifiers;
+
+/// The offset of keysyms encoding a Unicode code point directly, used for
+/// characters without a legacy keysym.
+const UNICODE_OFFSET: u32 = 0x0100_0000;
+
+/// The names of the non-alphanumeric keysyms understood by
+/// [`Keysym::from_name`], as used in Compose files.
+const NAMED_KEYSYMS: [(&str, Keysym); 51] = [
+    ("space", Keysym(0x20)),
+    ("exclam", Keysym(0x21)),
+    ("quotedbl", Keysym(0x22)),
+    ("numbersign", Keysym(0x23)),
+    ("dollar", Keysym(0x24)),
+    ("percent", Keysym(0x25)),
+    ("ampersand", Keysym(0x26)),
+    ("apostrophe", Keysym(0x27)),
+    ("parenleft", Keysym(0x28)),
+    ("parenright", Keysym(0x29)),
+    ("asterisk", Keysym(0x2a)),
+    ("plus", Keysym(0x2b)),
+    ("comma", Keysym(0x2c)),
+    ("minus", Keysym(0x2d)),
+    ("period", Keysym(0x2e)),
+    ("slash", Keysym(0x2f)),
+    ("colon", Keysym(0x3a)),
+    ("semicolon", Keysym(0x3b)),
+    ("less", Keysym(0x3c)),
+    ("equal", Keysym(0x3d)),
+    ("greater", Keysym(0x3e)),
+    ("question", Keysym(0x3f)),
+    ("at", Keysym(0x40)),
+    ("bracketleft", Keysym(0x5b)),
+    ("backslash", Keysym(0x5c)),
+    ("bracketright", Keysym(0x5d)),
+    ("asciicircum", Keysym(0x5e)),
+    ("underscore", Keysym(0x5f)),
+    ("grave", Keysym(0x60)),
+    ("braceleft", Keysym(0x7b)),
+    ("bar", Keysym(0x7c)),
+    ("braceright", Keysym(0x7d)),
+    ("asciitilde", Keysym(0x7e)),
+    ("BackSpace", Keysym::BACKSPACE),
+    ("Tab", Keysym::TAB),
+    ("Return", Keysym::RETURN),
+    ("Escape", Keysym::ESCAPE),
+    ("Multi_key", Keysym::MULTI_KEY),
+    ("dead_grave", Keysym::DEAD_GRAVE),
+    ("dead_acute", Keysym::DEAD_ACUTE),
+    ("dead_circumflex", Keysym::DEAD_CIRCUMFLEX),
+    ("dead_tilde", Keysym::DEAD_TILDE),
+    ("dead_macron", Keysym(0xfe54)),
+    ("dead_breve", Keysym(0xfe55)),
+    ("dead_abovedot", Keysym(0xfe56)),
+    ("dead_diaeresis", Keysym::DEAD_DIAERESIS),
+    ("dead_abovering", Keysym(0xfe58)),
+    ("dead_doubleacute", Keysym(0xfe59)),
+    ("dead_caron", Keysym(0xfe5a)),
+    ("dead_cedilla", Keysym::DEAD_CEDILLA),
+    ("dead_ogonek", Keysym(0xfe5c)),
+];
+
+/// The modifier mask bit of Shift in `wl_keyboard.modifiers`.
+///
+/// XKB keymaps always place the eight real modifiers first, in a fixed
+/// order, so the bits of Shift and Lock do not depend on the keymap.
+const MOD_SHIFT: u32 = 1 << 0;
+/// The modifier mask bit of Lock (Caps Lock) in `wl_keyboard.modifiers`.
+const MOD_LOCK: u32 = 1 << 1;
+
+/// An X11 keysym, the symbol a key produces in the active layout.
+///
+/// Printable Latin-1 keysyms share their value with the Unicode code point
+/// of the character, so `Keysym('a' as u32)` is the keysym of `a`; other
+/// characters are encoded as `0x01000000` plus their code point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Keysym(pub u32);
+
+impl Keysym {
+    /// The `BackSpace` keysym.
+    pub const BACKSPACE: Keysym = Keysym(0xff08);
+    /// The `Tab` keysym.
+    pub const TAB: Keysym = Keysym(0xff09);
+    /// The `Return` keysym.
+    pub const RETURN: Keysym = Keysym(0xff0d);
+    /// The `Escape` keysym.
+    pub const ESCAPE: Keysym = Keysym(0xff1b);
+    /// The `Home` keysym.
+    pub const HOME: Keysym = Keysym(0xff50);
+    /// The `Left` keysym.
+    pub const LEFT: Keysym = Keysym(0xff51);
+    /// The `Up` keysym.
+    pub const UP: Keysym = Keysym(0xff52);
+    /// The `Right` keysym.
+    pub const RIGHT: Keysym = Keysym(0xff53);
+    /// The `Down` keysym.
+    pub const DOWN: Keysym = Keysym(0xff54);
+    /// The `Page_Up` keysym.
+    pub const PAGE_UP: Keysym = Keysym(0xff55);
+    /// The `Page_Down` keysym.
+    pub const PAGE_DOWN: Keysym = Keysym(0xff56);
+    /// The `End` keysym.
+    pub const END: Keysym = Keysym(0xff57);
+    /// The `Insert` keysym.
+    pub const INSERT: Keysym = Keysym(0xff63);
+    /// The `KP_Enter` keysym.
+    pub const KP_ENTER: Keysym = Keysym(0xff8d);
+    /// The `F1` keysym; `F2` to `F12` follow consecutively.
+    pub const F1: Keysym = Keysym(0xffbe);
+    /// The `F12` keysym.
+    pub const F12: Keysym = Keysym(0xffc9);
+    /// The `Shift_L` keysym.
+    pub const SHIFT_L: Keysym = Keysym(0xffe1);
+    /// The `Shift_R` keysym.
+    pub const SHIFT_R: Keysym = Keysym(0xffe2);
+    /// The `Control_L` keysym.
+    pub const CONTROL_L: Keysym = Keysym(0xffe3);
+    /// The `Control_R` keysym.
+    pub const CONTROL_R: Keysym = Keysym(0xffe4);
+    /// The `Caps_Lock` keysym.
+    pub const CAPS_LOCK: Keysym = Keysym(0xffe5);
+    /// The `Alt_L` keysym.
+    pub const ALT_L: Keysym = Keysym(0xffe9);
+    /// The `Alt_R` keysym.
+    pub const ALT_R: Keysym = Keysym(0xffea);
+    /// The `Super_L` keysym.
+    pub const SUPER_L: Keysym = Keysym(0xffeb);
+    /// The `Super_R` keysym.
+    pub const SUPER_R: Keysym = Keysym(0xffec);
+    /// The `Delete` keysym.
+    pub const DELETE: Keysym = Keysym(0xffff);
+    /// The `Multi_key` keysym, the Compose key starting compose sequences.
+    pub const MULTI_KEY: Keysym = Keysym(0xff20);
+    /// The `dead_grave` keysym.
+    pub const DEAD_GRAVE: Keysym = Keysym(0xfe50);
+    /// The `dead_acute` keysym.
+    pub const DEAD_ACUTE: Keysym = Keysym(0xfe51);
+    /// The `dead_circumflex` keysym.
+    pub const DEAD_CIRCUMFLEX: Keysym = Keysym(0xfe52);
+    /// The `dead_tilde` keysym.
+    pub const DEAD_TILDE: Keysym = Keysym(0xfe53);
+    /// The `dead_diaeresis` keysym.
+    pub const DEAD_DIAERESIS: Keysym = Keysym(0xfe57);
+    /// The `dead_cedilla` keysym.
+    pub const DEAD_CEDILLA: Keysym = Keysym(0xfe5b);
+
+    /// Returns the keysym typing `c`.
+    pub fn from_char(c: char) -> Keysym {
+        match c as u32 {
+            code @ (0x20..=0x7e | 0xa0..=0xff) => Keysym(code),
+            code => Keysym(UNICODE_OFFSET + code),
+        }
+    }
+
+    /// Looks up a keysym by its name, as written in Compose files.
+    ///
+    /// Understands single letters and digits, Unicode names like `U20AC`,
+    /// hexadecimal values like `0xfe51`, and the names of the ASCII
+    /// punctuation, dead keys and `Multi_key`.
+    pub fn from_name(name: &str) -> Option<Keysym> {
+        let mut chars = name.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return c.is_ascii_alphanumeric().then(|| Keysym::from_char(c));
+        }
+
+        if let Some(hex) = name.strip_prefix("0x") {
+            return u32::from_str_radix(hex, 16).ok().map(Keysym);
+        }
+
+        if let Some(hex) = name.strip_prefix('U')
+            && hex.len() >= 4
+            && let Ok(code) = u32::from_str_radix(hex, 16)
+        {
+            return char::from_u32(code).map(Keysym::from_char);
+        }
+
+        NAMED_KEYSYMS
+            .iter()
+            .find(|(keysym_name, _)| *keysym_name == name)
+            .map(|(_, keysym)| *keysym)
+    }
+
+    /// Returns `true` for the keysyms of modifier keys, such as Shift or Caps
+    /// Lock, which do not interrupt compose sequences.
+    pub fn is_modifier(self) -> bool {
+        matches!(self.0, 0xffe1..=0xffee | 0xfe01..=0xfe0f)
+    }
+
+    /// Returns the character the keysym types, if any.
+    ///
+    /// Covers the printable Latin-1 range, the Unicode keysyms and the
+    /// control characters of the editing keys, like `xkb_keysym_to_utf32`
+    /// does for them.
+    pub fn to_char(self) -> Option<char> {
+        match self {
+            Keysym(0x20..=0x7e) | Keysym(0xa0..=0xff) => char::from_u32(self.0),
+            Keysym(UNICODE_OFFSET..) => char::from_u32(self.0 - UNICODE_OFFSET),
+            Keysym::BACKSPACE => Some('\u{8}'),
+            Keysym::TAB => Some('\t'),
+            Keysym::RETURN | Keysym::KP_ENTER => Some('\r'),
+            Keysym::ESCAPE => Some('\u{1b}'),
+            Keysym::DELETE => Some('\u{7f}'),
+            _ => None,
+        }
+    }
+}
+
+/// Translates Linux evdev key codes to keysyms of the US QWERTY layout.
+///
+/// A fallback for simple tools that only need to react to a few keys, such
+/// as Escape, Enter or the arrows, without interpreting the XKB keymap sent
+/// by the compositor. Keys outside the main block, navigation cluster and
+/// function row are not mapped, and the user's actual layout is ignored.
+///
+/// Feed it the `wl_keyboard.modifiers` events to apply Shift and Caps Lock.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FallbackKeymap {
+    /// Whether Shift is held or latched.
+    shift: bool,
+    /// Whether Caps Lock is on.
+    caps_lock: bool,
+}
+
+impl FallbackKeymap {
+    /// Creates a keymap with no modifier active.
+    pub fn new() -> FallbackKeymap {
+        FallbackKeymap::default()
+    }
+
+    /// Updates the Shift and Caps Lock state from a `wl_keyboard.modifiers` event.
+    pub fn update_modifiers(&mut self, modifiers: &Modifiers) {
+        let active = modifiers.mods_depressed.get() | modifiers.mods_latched.get();
+        self.shift = active & MOD_SHIFT != 0;
+        self.caps_lock = (active | modifiers.mods_locked.get()) & MOD_LOCK != 0;
+    }
+
+    /// Returns the keysym of the key with the evdev code `keycode`, as sent
+    /// by `wl_keyboard.key`, or `None` if the key is not in the table.
+    ///
+    /// Caps Lock only affects letters, like in the XKB US layout.
+    pub fn keysym(&self, keycode: u32) -> Option<Keysym> {
+        let (base, shifted) = us_keysyms(keycode)?;
+
+        let is_letter = char::from_u32(base).is_some_and(|c| c.is_ascii_lowercase());
+        let shift = if is_letter {
+            self.shift != self.caps_lock
+        } else {
+            self.shift
+        };
+
+        Some(Keysym(if shift { shifted } else { base }))
+    }
+
+    /// Returns the character typed by the key with the evdev code `keycode`, if any.
+    pub fn key_char(&self, keycode: u32) -> Option<char> {
+        self.keysym(keycode)?.to_char()
+    }
+}
+
+/// Returns the unshifted and shifted keysyms of an evdev key code in the
+/// US layout.
+fn us_keysyms(keycode: u32) -> Option<(u32, u32)> {
+    /// The keys of the number row and the letter rows, indexed from
+    /// `KEY_1` (2) to `KEY_SLASH` (53); `\0` marks non-character keys.
+    const MAIN_BLOCK: [(u8, u8); 52] = [
+        (b'1', b'!'),
+        (b'2', b'@'),
+        (b'3', b'#'),
+        (b'4', b'$'),
+        (b'5', b'%'),
+        (b'6', b'^'),
+        (b'7', b'&'),
+        (b'8', b'*'),
+        (b'9', b'('),
+        (b'0', b')'),
+        (b'-', b'_'),
+        (b'=', b'+'),
+        (0, 0), // KEY_BACKSPACE
+        (0, 0), // KEY_TAB
+        (b'q', b'Q'),
+        (b'w', b'W'),
+        (b'e', b'E'),
+        (b'r', b'R'),
+        (b't', b'T'),
+        (b'y', b'Y'),
+        (b'u', b'U'),
+        (b'i', b'I'),
+        (b'o', b'O'),
+        (b'p', b'P'),
+        (b'[', b'{'),
+        (b']', b'}'),
+        (0, 0), // KEY_ENTER
+        (0, 0), // KEY_LEFTCTRL
+        (b'a', b'A'),
+        (b's', b'S'),
+        (b'd', b'D'),
+        (b'f', b'F'),
+        (b'g', b'G'),
+        (b'h', b'H'),
+        (b'j', b'J'),
+        (b'k', b'K'),
+        (b'l', b'L'),
+        (b';', b':'),
+        (b'\'', b'"'),
+        (b'`', b'~'),
+        (0, 0), // KEY_LEFTSHIFT
+        (b'\\', b'|'),
+        (b'z', b'Z'),
+        (b'x', b'X'),
+        (b'c', b'C'),
+        (b'v', b'V'),
+        (b'b', b'B'),
+        (b'n', b'N'),
+        (b'm', b'M'),
+        (b',', b'<'),
+        (b'.', b'>'),
+        (b'/', b'?'),
+    ];
+
+    let keysym = match keycode {
+        1 => Keysym::ESCAPE,
+        14 => Keysym::BACKSPACE,
+        15 => Keysym::TAB,
+        28 => Keysym::RETURN,
+        29 => Keysym::CONTROL_L,
+        42 => Keysym::SHIFT_L,
+        54 => Keysym::SHIFT_R,
+        56 => Keysym::ALT_L,
+        57 => Keysym(b' '.into()),
+        58 => Keysym::CAPS_LOCK,
+        59..=68 => Keysym(Keysym::F1.0 + keycode - 59),
+        87 => Keysym(Keysym::F1.0 + 10),
+        88 => Keysym::F12,
+        96 => Keysym::KP_ENTER,
+        97 => Keysym::CONTROL_R,
+        100 => Keysym::ALT_R,
+        102 => Keysym::HOME,
+        103 => Keysym::UP,
+        104 => Keysym::PAGE_UP,
+        105 => Keysym::LEFT,
+        106 => Keysym::RIGHT,
+        107 => Keysym::END,
+        108 => Keysym::DOWN,
+        109 => Keysym::PAGE_DOWN,
+        110 => Keysym::INSERT,
+        111 => Keysym::DELETE,
+        125 => Keysym::SUPER_L,
+        126 => Keysym::SUPER_R,
+        2..=53 => {
+            let (base, shifted) = MAIN_BLOCK[keycode as usize - 2];
+            return Some((base.into(), shifted.into()));
+        }
+        _ => return None,
+    };
+
+    Some((keysym.0, keysym.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::types::WlUInt;
+
+    /// Returns a `wl_keyboard.modifiers` event with the given masks.
+    fn modifiers(depressed: u32, locked: u32) -> Modifiers {
+        Modifiers {
+            serial: WlUInt(0),
+            mods_depressed: WlUInt(depressed),
+            mods_latched: WlUInt(0),
+            mods_locked: WlUInt(locked),
+            group: WlUInt(0),
+        }
+    }
+
+    #[test]
+    fn maps_common_keys() {
+        let keymap = FallbackKeymap::new();
+
+        assert_eq!(keymap.keysym(1), Some(Keysym::ESCAPE));
+        assert_eq!(keymap.keysym(28), Some(Keysym::RETURN));
+        assert_eq!(keymap.keysym(103), Some(Keysym::UP));
+        assert_eq!(keymap.keysym(87), Some(Keysym(0xffc8)));
+        assert_eq!(keymap.key_char(30), Some('a'));
+        assert_eq!(keymap.key_char(57), Some(' '));
+        assert_eq!(keymap.keysym(240), None);
+    }
+
+    #[test]
+    fn applies_shift_and_caps_lock() {
+        let mut keymap = FallbackKeymap::new();
+
+        keymap.update_modifiers(&modifiers(MOD_SHIFT, 0));
+        assert_eq!(keymap.key_char(30), Some('A'));
+        assert_eq!(keymap.key_char(2), Some('!'));
+
+        keymap.update_modifiers(&modifiers(0, MOD_LOCK));
+        assert_eq!(keymap.key_char(30), Some('A'));
+        assert_eq!(keymap.key_char(2), Some('1'));
+
+        keymap.update_modifiers(&modifiers(MOD_SHIFT, MOD_LOCK));
+        assert_eq!(keymap.key_char(30), Some('a'));
+    }
+}
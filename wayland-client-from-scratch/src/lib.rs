@@ -0,0 +1,33 @@
+pub mod clipboard;
+pub mod compose;
+pub mod connection;
+pub mod cursor;
+pub mod damage;
+pub mod egl;
+pub mod error;
+pub mod fence;
+pub mod geometry;
+pub mod gesture;
+#[cfg(feature = "image-export")]
+pub mod image_export;
+pub mod input;
+pub mod key_repeat;
+pub mod keysym;
+pub mod outputs;
+pub mod pacer;
+pub mod pixel;
+pub mod pointer_capture;
+#[cfg(feature = "portal")]
+pub mod portal;
+pub mod presentation_stats;
+pub mod protocol;
+pub mod scale;
+pub mod scroll;
+pub mod server;
+pub mod subsurface;
+pub mod swapchain;
+mod sync;
+mod sys;
+pub mod window;
+#[cfg(feature = "xkb")]
+pub mod xkb;
@@ -1,23 +1,16 @@
-mod protocol;
+use wayland_client_from_scratch::{connection::Connection, error::Result};
 
-use std::os::unix::net::UnixStream;
+fn main() -> Result<()> {
+    let conn = Connection::connect()?;
+    conn.roundtrip()?;
 
-use crate::protocol::{display, types::WlNewId};
+    println!("compositor: {}", conn.peer_credentials()?);
 
-fn connect_to_wayland_socket() -> anyhow::Result<UnixStream> {
-    let xdg_runtime_dir = std::env::var("XDG_RUNTIME_DIR")?;
-    let wayland_display = std::env::var("WAYLAND_DISPLAY")?;
+    for global in conn.globals() {
+        println!("{global}");
+    }
 
-    let socket_path = format!("{xdg_runtime_dir}/{wayland_display}");
-
-    let stream = UnixStream::connect(socket_path)?;
-
-    Ok(stream)
-}
-
-fn main() -> anyhow::Result<()> {
-    let mut stream = connect_to_wayland_socket()?;
-    display::request::get_registry(&mut stream, WlNewId(1))?;
+    print!("{}", conn.capabilities());
 
     Ok(())
 }
@@ -0,0 +1,520 @@
+use std::sync::{Arc, Weak};
+
+use crate::{
+    connection::{Connection, GlobalChange},
+    error::{Error, Result},
+    geometry::Rect,
+    protocol::{
+        WlObjectId,
+        output::{Output, OutputEvent, OutputInfo, OutputState, Transform},
+        xdg_output::{XdgOutput, XdgOutputInfo, XdgOutputManager, XdgOutputState},
+    },
+    sync::{Lock, LockCell},
+};
+
+/// A monitor, merging the properties announced by a `wl_output` with the
+/// ones of its `zxdg_output_v1`, if any.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Monitor {
+    /// The numeric name of the `wl_output` global.
+    pub global_name: u32,
+    /// The name of the output, such as `DP-1`, from `wl_output` version 4
+    /// or else from the xdg output.
+    pub name: Option<String>,
+    /// The manufacturer.
+    pub make: String,
+    /// The model.
+    pub model: String,
+    /// The area of the output in the global compositor space.
+    ///
+    /// Taken from the xdg output when it announced its logical size, which
+    /// accounts for fractional scaling; otherwise derived from the position,
+    /// the current mode, the transform and the integer scale.
+    pub logical_rect: Rect,
+    /// The physical width and height in millimeters, 0 if not meaningful.
+    pub physical_mm: (i32, i32),
+    /// The refresh rate of the current mode in mHz, 0 if not known.
+    pub refresh_mhz: i32,
+    /// The integer scale factor.
+    pub scale: i32,
+    /// The transform applied to buffers shown on the output.
+    pub transform: Transform,
+}
+
+impl Monitor {
+    /// Merges the properties of the output bound from `global_name` with
+    /// the ones of its xdg output.
+    fn merge(global_name: u32, output: OutputInfo, xdg_output: Option<XdgOutputInfo>) -> Monitor {
+        let logical_rect = match &xdg_output {
+            Some(xdg) if xdg.width > 0 && xdg.height > 0 => {
+                Rect::new(xdg.x, xdg.y, xdg.width, xdg.height)
+            }
+            _ => {
+                let (width, height) = output
+                    .mode
+                    .map(|mode| output.transform.buffer_size(mode.width, mode.height))
+                    .unwrap_or_default();
+                let scale = output.scale.max(1);
+                Rect::new(output.x, output.y, width / scale, height / scale)
+            }
+        };
+
+        Monitor {
+            global_name,
+            name: output.name.or_else(|| xdg_output.and_then(|xdg| xdg.name)),
+            make: output.make,
+            model: output.model,
+            logical_rect,
+            physical_mm: (output.physical_width, output.physical_height),
+            refresh_mhz: output.mode.map_or(0, |mode| mode.refresh_mhz),
+            scale: output.scale,
+            transform: output.transform,
+        }
+    }
+}
+
+/// Returns the monitors of every output bound on `conn` that announced its
+/// properties, ordered by global name.
+///
+/// Outputs bound more than once count once.
+pub(crate) fn monitors(conn: &Connection) -> Vec<Monitor> {
+    let xdg_outputs: Vec<Arc<XdgOutputState>> = conn
+        .objects_of(WlObjectId::XdgOutput)
+        .into_iter()
+        .filter_map(|id| conn.object_data::<XdgOutputState>(id))
+        .collect();
+
+    let mut monitors: Vec<Monitor> = Vec::new();
+    for output_id in conn.objects_of(WlObjectId::Output) {
+        let Some(state) = conn.object_data::<OutputState>(output_id) else {
+            continue;
+        };
+        let global_name = state.global_name();
+        if !state.is_announced() || monitors.iter().any(|m| m.global_name == global_name) {
+            continue;
+        }
+
+        let xdg_output = xdg_outputs
+            .iter()
+            .find(|xdg| xdg.output() == output_id)
+            .map(|xdg| xdg.info());
+        monitors.push(Monitor::merge(global_name, state.info(), xdg_output));
+    }
+
+    monitors.sort_by_key(|monitor| monitor.global_name);
+    monitors
+}
+
+/// An output hotplug event delivered to the handler of an [`Outputs`],
+/// along with the output it concerns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OutputsEvent {
+    /// An output was plugged in, or was already present when the
+    /// [`Outputs`] was created, and announced its properties.
+    OutputAdded(OutputInfo),
+    /// The properties of an output changed, e.g. its mode or scale.
+    OutputChanged(OutputInfo),
+    /// The output with this global name was unplugged and released. No
+    /// event of the output follows.
+    OutputRemoved(u32),
+}
+
+/// The handler receiving the events of every output.
+type OutputsHandler = Box<dyn FnMut(&Output, OutputsEvent) + Send>;
+
+/// A bound output and whether the handler was told about it.
+struct OutputEntry {
+    /// The xdg output describing the output, if the compositor supports
+    /// them. Declared first so it is destroyed before the output.
+    xdg_output: Option<XdgOutput>,
+    /// The bound output.
+    output: Output,
+    /// Whether [`OutputsEvent::OutputAdded`] was delivered, i.e. the
+    /// output announced its properties.
+    announced: LockCell<bool>,
+}
+
+/// The state of an [`Outputs`], shared with the handlers of the outputs.
+struct OutputsInner {
+    /// The connection the outputs are bound on.
+    conn: Connection,
+    /// The xdg output manager, if the compositor advertises it.
+    xdg_manager: Option<XdgOutputManager>,
+    /// The bound outputs, in the order their globals were announced.
+    outputs: Lock<Vec<Arc<OutputEntry>>>,
+    /// The handler, taken out while it runs.
+    handler: Lock<Option<OutputsHandler>>,
+    /// The error raised while binding a new output, returned by
+    /// [`Outputs::take_error`].
+    error: Lock<Option<Error>>,
+    /// The ID of the global watcher binding and releasing the outputs.
+    watcher: LockCell<u64>,
+}
+
+/// Every output of the compositor, kept up to date as monitors are plugged
+/// in and out.
+///
+/// `Outputs` binds the `wl_output` globals already advertised, then binds
+/// every output announced later by `wl_registry.global` and releases the
+/// ones withdrawn by `wl_registry.global_remove`. The handler sees
+/// [`OutputsEvent::OutputAdded`] once an output has announced its
+/// properties, [`OutputsEvent::OutputChanged`] on every later batch of
+/// changes and [`OutputsEvent::OutputRemoved`] when it goes away, so the
+/// list returned by [`Outputs::outputs`] always matches the events
+/// delivered so far. This is what bars and wallpaper tools need to follow
+/// monitor hotplug.
+///
+/// If the compositor advertises `zxdg_output_manager_v1`, every output also
+/// gets an xdg output, and [`Outputs::monitors`] merges both into
+/// [`Monitor`]s.
+///
+/// `Outputs` is a cheap handle; clones refer to the same outputs. The
+/// outputs are released once the last handle is dropped.
+#[derive(Clone)]
+pub struct Outputs {
+    inner: Arc<OutputsInner>,
+}
+
+impl Outputs {
+    /// Binds every output advertised by the compositor and follows the
+    /// ones added and removed afterwards.
+    ///
+    /// The properties of the outputs, and with them the
+    /// [`OutputsEvent::OutputAdded`] events, arrive with the next
+    /// [`Connection::roundtrip`].
+    pub fn new<F>(conn: &Connection, handler: F) -> Result<Outputs>
+    where
+        F: FnMut(&Output, OutputsEvent) + Send + 'static,
+    {
+        let xdg_manager = match conn.supports(WlObjectId::XdgOutputManager.interface_name()) {
+            Some(_) => Some(XdgOutputManager::bind(conn)?),
+            None => None,
+        };
+        let inner = Arc::new(OutputsInner {
+            conn: conn.clone(),
+            xdg_manager,
+            outputs: Lock::new(Vec::new()),
+            handler: Lock::new(Some(Box::new(handler))),
+            error: Lock::new(None),
+            watcher: LockCell::new(0),
+        });
+
+        for output in Output::bind_all(conn)? {
+            add_output(&inner, output)?;
+        }
+
+        let weak = Arc::downgrade(&inner);
+        let watcher = conn.watch_globals(Box::new(move |conn, change| {
+            let Some(inner) = weak.upgrade() else {
+                return;
+            };
+            match change {
+                GlobalChange::Added(global)
+                    if global.interface.as_str() == WlObjectId::Output.interface_name() =>
+                {
+                    let added = Output::bind(conn, global.name.get())
+                        .and_then(|output| add_output(&inner, output));
+                    if let Err(error) = added {
+                        inner.error.replace(Some(error));
+                    }
+                }
+                GlobalChange::Removed(global) => remove_output(&inner, global.name.get()),
+                GlobalChange::Added(_) => {}
+            }
+        }));
+        inner.watcher.set(watcher);
+
+        Ok(Outputs { inner })
+    }
+
+    /// Returns the global name and the properties of every output that
+    /// announced them, in the order their globals were announced.
+    pub fn outputs(&self) -> Vec<(u32, OutputInfo)> {
+        self.inner
+            .outputs
+            .borrow()
+            .iter()
+            .filter(|entry| entry.announced.get())
+            .map(|entry| (entry.output.global_name(), entry.output.info()))
+            .collect()
+    }
+
+    /// Returns the properties of the output with the global name
+    /// `global_name`, if it is bound and announced them.
+    pub fn info(&self, global_name: u32) -> Option<OutputInfo> {
+        self.inner
+            .outputs
+            .borrow()
+            .iter()
+            .find(|entry| entry.announced.get() && entry.output.global_name() == global_name)
+            .map(|entry| entry.output.info())
+    }
+
+    /// Returns the monitors of the outputs that announced their properties,
+    /// in the order their globals were announced.
+    ///
+    /// Where the compositor supports `zxdg_output_manager_v1`, every output
+    /// gets an xdg output, so the logical areas account for fractional
+    /// scaling.
+    pub fn monitors(&self) -> Vec<Monitor> {
+        self.inner
+            .outputs
+            .borrow()
+            .iter()
+            .filter(|entry| entry.announced.get())
+            .map(|entry| {
+                Monitor::merge(
+                    entry.output.global_name(),
+                    entry.output.info(),
+                    entry.xdg_output.as_ref().map(XdgOutput::info),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the error raised while binding a new output, if any, as
+    /// this happens while dispatching events.
+    pub fn take_error(&self) -> Result<()> {
+        match self.inner.error.take() {
+            Some(error) => Err(error.context("Failed to bind a new output")),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for OutputsInner {
+    fn drop(&mut self) {
+        self.conn.unwatch_globals(self.watcher.get());
+
+        if let Some(manager) = self.xdg_manager.take() {
+            self.conn.destroy_on_drop(|_| manager.destroy());
+        }
+    }
+}
+
+/// Starts tracking `output`, announcing it once its properties are known.
+fn add_output(inner: &Arc<OutputsInner>, output: Output) -> Result<()> {
+    let xdg_output = match &inner.xdg_manager {
+        Some(manager) => Some(manager.get_xdg_output(&output)?),
+        None => None,
+    };
+    let entry = Arc::new(OutputEntry {
+        xdg_output,
+        output,
+        announced: LockCell::new(false),
+    });
+
+    let (weak_inner, weak_entry) = (Arc::downgrade(inner), Arc::downgrade(&entry));
+    entry.output.set_event_handler(move |_, event| {
+        let OutputEvent::Done(info) = event;
+        let Some(entry) = weak_entry.upgrade() else {
+            return;
+        };
+        let event = if entry.announced.replace(true) {
+            OutputsEvent::OutputChanged(info)
+        } else {
+            OutputsEvent::OutputAdded(info)
+        };
+        deliver(&weak_inner, &entry, event);
+    });
+
+    inner.outputs.borrow_mut().push(entry);
+
+    Ok(())
+}
+
+/// Stops tracking the output bound from the global `global_name`, if any,
+/// and releases it.
+fn remove_output(inner: &Arc<OutputsInner>, global_name: u32) {
+    let entry = {
+        let mut outputs = inner.outputs.borrow_mut();
+        let Some(index) = outputs
+            .iter()
+            .position(|entry| entry.output.global_name() == global_name)
+        else {
+            return;
+        };
+        outputs.remove(index)
+    };
+
+    // An output removed before announcing its properties was never added
+    if entry.announced.get() {
+        deliver(
+            &Arc::downgrade(inner),
+            &entry,
+            OutputsEvent::OutputRemoved(global_name),
+        );
+    }
+}
+
+/// Delivers `event` to the handler.
+///
+/// The handler is taken out while it runs, so it may call back into the
+/// outputs.
+fn deliver(inner: &Weak<OutputsInner>, entry: &OutputEntry, event: OutputsEvent) {
+    let Some(inner) = inner.upgrade() else {
+        return;
+    };
+    let Some(mut handler) = inner.handler.borrow_mut().take() else {
+        return;
+    };
+
+    handler(&entry.output, event);
+    inner.handler.borrow_mut().get_or_insert(handler);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Write, os::unix::net::UnixStream};
+
+    use super::*;
+    use crate::protocol::{
+        message::WlMessage,
+        output::OutputState,
+        registry::event::global::Global,
+        types::{WlString, WlUInt},
+    };
+
+    /// Sends a `wl_registry` event from the compositor side.
+    fn send_registry_event(
+        compositor: &mut UnixStream,
+        conn: &Connection,
+        opcode: u16,
+        data: &[u8],
+    ) {
+        let registry = conn.registry().unwrap().id();
+        let msg: Vec<u8> = WlMessage::new(registry, opcode, data).unwrap().into();
+        compositor.write_all(&msg).unwrap();
+        conn.dispatch().unwrap();
+    }
+
+    /// Sends `wl_output.scale` and `wl_output.done` to the output bound
+    /// from `global_name`.
+    fn send_scale(compositor: &mut UnixStream, conn: &Connection, global_name: u32, scale: i32) {
+        let output = conn
+            .objects_of(WlObjectId::Output)
+            .into_iter()
+            .find(|&id| {
+                conn.object_data::<OutputState>(id)
+                    .is_some_and(|state| state.global_name() == global_name)
+            })
+            .unwrap();
+        let mut msg: Vec<u8> = WlMessage::new(output, 3, &scale.to_ne_bytes())
+            .unwrap()
+            .into();
+        msg.extend(Vec::<u8>::from(WlMessage::new(output, 2, &[]).unwrap()));
+        compositor.write_all(&msg).unwrap();
+        conn.dispatch().unwrap();
+    }
+
+    #[test]
+    fn monitors_merge_xdg_output_properties() {
+        let (client, mut compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+        for (name, interface, version) in [(1, "wl_output", 4), (2, "zxdg_output_manager_v1", 3)] {
+            conn.insert_global(Global {
+                name: WlUInt(name),
+                interface: WlString::new(interface).unwrap(),
+                version: WlUInt(version),
+            });
+        }
+        let outputs = Outputs::new(&conn, |_, _| {}).unwrap();
+        let output = conn.objects_of(WlObjectId::Output)[0];
+        let xdg_output = conn.objects_of(WlObjectId::XdgOutput)[0];
+
+        // A 2560x1440 mode shown at a fractional scale of 1.5, then the
+        // wl_output.done ending the batch of both objects (version 3)
+        let int_pair = |a: i32, b: i32| [a.to_ne_bytes(), b.to_ne_bytes()].concat();
+        // flags (current), width, height, refresh
+        let mode: Vec<u8> = [1u32, 2560, 1440, 60000]
+            .iter()
+            .flat_map(|value| value.to_ne_bytes())
+            .collect();
+        let mut msg = Vec::new();
+        for (object, opcode, data) in [
+            (xdg_output, 0, int_pair(100, 0)),
+            (xdg_output, 1, int_pair(1707, 960)),
+            (output, 1, mode),
+            (output, 3, 2i32.to_ne_bytes().to_vec()),
+            (output, 4, WlString::new("DP-1").unwrap().to_bytes()),
+            (output, 2, Vec::new()),
+        ] {
+            msg.extend(Vec::<u8>::from(
+                WlMessage::new(object, opcode, &data).unwrap(),
+            ));
+        }
+        compositor.write_all(&msg).unwrap();
+        conn.dispatch().unwrap();
+
+        let monitors = conn.monitors();
+        assert_eq!(monitors, outputs.monitors());
+        assert_eq!(monitors.len(), 1);
+        assert_eq!(monitors[0].name.as_deref(), Some("DP-1"));
+        assert_eq!(monitors[0].logical_rect, Rect::new(100, 0, 1707, 960));
+        assert_eq!(monitors[0].refresh_mhz, 60000);
+        assert_eq!(monitors[0].scale, 2);
+
+        // Without an xdg output, the area is derived from the integer scale
+        let info = outputs.info(1).unwrap();
+        let fallback = Monitor::merge(1, info, None);
+        assert_eq!(fallback.logical_rect, Rect::new(0, 0, 1280, 720));
+    }
+
+    #[test]
+    fn outputs_follow_registry_hotplug() {
+        let (client, mut compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+        conn.insert_global(Global {
+            name: WlUInt(1),
+            interface: WlString::new("wl_output").unwrap(),
+            version: WlUInt(4),
+        });
+
+        let events = Arc::new(Lock::new(Vec::new()));
+        let sink = events.clone();
+        let outputs = Outputs::new(&conn, move |output, event| {
+            sink.borrow_mut().push((output.global_name(), event));
+        })
+        .unwrap();
+        send_scale(&mut compositor, &conn, 1, 1);
+
+        // A monitor is plugged in
+        let mut global = 7u32.to_ne_bytes().to_vec();
+        global.extend(WlString::new("wl_output").unwrap().to_bytes());
+        global.extend(3u32.to_ne_bytes());
+        send_registry_event(&mut compositor, &conn, 0, &global);
+        send_scale(&mut compositor, &conn, 7, 2);
+        send_scale(&mut compositor, &conn, 7, 3);
+        assert_eq!(outputs.outputs().len(), 2);
+        assert_eq!(outputs.info(7).unwrap().scale, 3);
+
+        // The first one is unplugged
+        send_registry_event(&mut compositor, &conn, 1, &1u32.to_ne_bytes());
+        assert_eq!(outputs.outputs().len(), 1);
+        assert!(outputs.info(1).is_none());
+
+        let events: Vec<(u32, OutputsEvent)> = events.take();
+        let kinds: Vec<(u32, &str)> = events
+            .iter()
+            .map(|(name, event)| {
+                let kind = match event {
+                    OutputsEvent::OutputAdded(_) => "added",
+                    OutputsEvent::OutputChanged(_) => "changed",
+                    OutputsEvent::OutputRemoved(_) => "removed",
+                };
+                (*name, kind)
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            [(1, "added"), (7, "added"), (7, "changed"), (1, "removed")]
+        );
+        assert_eq!(conn.objects_of(WlObjectId::Output).len(), 1);
+
+        // Dropping the last handle releases the outputs and stops watching
+        drop(outputs);
+        assert!(conn.objects_of(WlObjectId::Output).is_empty());
+        send_registry_event(&mut compositor, &conn, 0, &global);
+        assert!(conn.objects_of(WlObjectId::Output).is_empty());
+    }
+}
@@ -0,0 +1,282 @@
+use std::{
+    collections::VecDeque,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::{
+        presentation::{Presentation, PresentationFeedbackEvent},
+        surface::Surface,
+    },
+    sync::{Lock, LockCell},
+};
+
+/// The time added to the render time estimate to absorb scheduling jitter.
+pub const DEFAULT_RENDER_MARGIN: Duration = Duration::from_millis(1);
+
+/// The number of measured render times the estimate covers.
+const RENDER_TIME_SAMPLES: usize = 16;
+
+/// When the frame [`Pacer::wait_for_next_frame`] released is expected on screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameTiming {
+    /// The predicted presentation time of the frame, on the presentation
+    /// clock, or `None` without a refresh rate to predict it from.
+    pub target: Option<Duration>,
+    /// The refresh interval of the output the last frame was presented on,
+    /// `None` until known or when the refresh rate is variable.
+    pub refresh: Option<Duration>,
+}
+
+/// The state of a [`Pacer`] updated by the frame callbacks and the
+/// presentation feedback.
+#[derive(Default)]
+struct PacerState {
+    /// Whether the frame callback of the last commit has not fired yet.
+    frame_pending: LockCell<bool>,
+    /// The presentation time and refresh interval of the last presented frame.
+    last_presented: LockCell<Option<(Duration, Option<Duration>)>>,
+    /// When the frame being rendered was released to the application.
+    render_start: LockCell<Option<Instant>>,
+    /// The last measured render times, oldest first.
+    render_times: Lock<VecDeque<Duration>>,
+}
+
+/// Schedules the rendering of game-like clients that redraw continuously.
+///
+/// Frame callbacks only tell when the compositor is ready for a new frame,
+/// so a client rendering right away finishes long before the next vertical
+/// blank and shows stale input. With `wp_presentation` feedback, the pacer
+/// predicts the next vblank from the last presentation and its refresh
+/// interval, and holds the frame back until just enough time is left to
+/// render it, which minimizes the latency between input and display.
+///
+/// The time needed to render is the longest of the last frames, measured
+/// from [`Pacer::wait_for_next_frame`] returning to [`Pacer::commit`], plus
+/// a margin; [`Pacer::set_render_time`] fixes it instead.
+///
+/// ```no_run
+/// # fn draw(_: &wayland_client_from_scratch::pacer::FrameTiming) {}
+/// # fn run(
+/// #     conn: &wayland_client_from_scratch::connection::Connection,
+/// #     presentation: &wayland_client_from_scratch::protocol::presentation::Presentation,
+/// #     surface: &wayland_client_from_scratch::protocol::surface::Surface,
+/// # ) -> wayland_client_from_scratch::error::Result<()> {
+/// use wayland_client_from_scratch::pacer::Pacer;
+///
+/// let pacer = Pacer::new(conn, Some(presentation));
+/// loop {
+///     let timing = pacer.wait_for_next_frame()?;
+///     draw(&timing);
+///     pacer.commit(surface)?;
+/// }
+/// # }
+/// ```
+pub struct Pacer {
+    /// The connection the paced surface was created on.
+    conn: Connection,
+    /// The presentation object feedback is requested from, if bound.
+    presentation: Option<Presentation>,
+    /// The state shared with the frame and feedback handlers.
+    state: Arc<PacerState>,
+    /// The render time set with [`Pacer::set_render_time`].
+    render_time: LockCell<Option<Duration>>,
+    /// The time added to the measured render time.
+    margin: LockCell<Duration>,
+}
+
+impl Pacer {
+    /// Creates a pacer for the frames of a surface created on `conn`.
+    ///
+    /// Without `presentation`, frames are paced by the frame callbacks alone.
+    pub fn new(conn: &Connection, presentation: Option<&Presentation>) -> Pacer {
+        Pacer {
+            conn: conn.clone(),
+            presentation: presentation.cloned(),
+            state: Arc::new(PacerState::default()),
+            render_time: LockCell::new(None),
+            margin: LockCell::new(DEFAULT_RENDER_MARGIN),
+        }
+    }
+
+    /// Fixes the time reserved to render a frame, or goes back to measuring
+    /// it with `None`.
+    pub fn set_render_time(&self, render_time: Option<Duration>) {
+        self.render_time.set(render_time);
+    }
+
+    /// Sets the time added to the measured render time, which defaults to
+    /// [`DEFAULT_RENDER_MARGIN`].
+    pub fn set_render_margin(&self, margin: Duration) {
+        self.margin.set(margin);
+    }
+
+    /// Returns the time reserved to render the next frame.
+    pub fn render_time(&self) -> Duration {
+        self.render_time.get().unwrap_or_else(|| {
+            estimate_render_time(&self.state.render_times.borrow(), self.margin.get())
+        })
+    }
+
+    /// Blocks until it is time to render the next frame, dispatching events
+    /// meanwhile.
+    ///
+    /// Waits for the frame callback of the last [`Pacer::commit`], then
+    /// until the predicted vblank is only [`Pacer::render_time`] away. The
+    /// first frame is released right away. Like frame callbacks, this waits
+    /// for as long as the compositor does not show the surface.
+    ///
+    /// # Errors
+    /// Returns an error if dispatching events or reading the presentation
+    /// clock fails.
+    pub fn wait_for_next_frame(&self) -> Result<FrameTiming> {
+        while self.state.frame_pending.get() {
+            self.conn.dispatch()?;
+        }
+
+        let last_presented = self.state.last_presented.get();
+        let refresh = last_presented.and_then(|(_, refresh)| refresh);
+        let mut target = None;
+
+        if let (Some(presentation), Some((presented, Some(refresh)))) =
+            (&self.presentation, last_presented)
+        {
+            let render_time = self.render_time();
+            let now = presentation.now()?;
+            let vblank = next_vblank(presented, refresh, now + render_time);
+            target = Some(vblank);
+
+            let start = vblank.saturating_sub(render_time);
+            if start > now {
+                self.sleep_until(Instant::now() + (start - now))?;
+            }
+        }
+
+        self.state.render_start.set(Some(Instant::now()));
+
+        Ok(FrameTiming { target, refresh })
+    }
+
+    /// Commits `surface` with the frame rendered since the last
+    /// [`Pacer::wait_for_next_frame`], requesting the frame callback and
+    /// presentation feedback the next wait relies on.
+    ///
+    /// # Errors
+    /// Returns an error if a request cannot be sent or the commit is rejected.
+    pub fn commit(&self, surface: &Surface) -> Result<()> {
+        if let Some(render_start) = self.state.render_start.take() {
+            let mut render_times = self.state.render_times.borrow_mut();
+            if render_times.len() == RENDER_TIME_SAMPLES {
+                render_times.pop_front();
+            }
+            render_times.push_back(render_start.elapsed());
+        }
+
+        let state = Arc::downgrade(&self.state);
+        surface.request_frame(move |_, _| {
+            if let Some(state) = state.upgrade() {
+                state.frame_pending.set(false);
+            }
+        })?;
+
+        if let Some(presentation) = &self.presentation {
+            let state = Arc::downgrade(&self.state);
+            presentation.feedback(surface, move |_, event| {
+                if let (Some(state), PresentationFeedbackEvent::Presented(presented)) =
+                    (state.upgrade(), event)
+                {
+                    state
+                        .last_presented
+                        .set(Some((presented.timestamp(), presented.refresh())));
+                }
+            })?;
+        }
+
+        surface.commit()?;
+        self.state.frame_pending.set(true);
+
+        Ok(())
+    }
+
+    /// Dispatches events until `deadline`, using a timer of the event loop.
+    fn sleep_until(&self, deadline: Instant) -> Result<()> {
+        let expired = Arc::new(AtomicBool::new(false));
+        let timer = self.conn.loop_handle().add_timer(deadline, {
+            let expired = expired.clone();
+            move |_| expired.store(true, Ordering::Relaxed)
+        });
+
+        while !expired.load(Ordering::Relaxed) {
+            if let Err(error) = self.conn.dispatch() {
+                self.conn.loop_handle().cancel_timer(timer);
+                return Err(error);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the first vblank at or after `earliest`, counting refresh
+/// intervals from the one at `presented`.
+fn next_vblank(presented: Duration, refresh: Duration, earliest: Duration) -> Duration {
+    // The next frame cannot be shown on the vblank that showed the last one
+    if refresh.is_zero() || earliest <= presented {
+        return presented + refresh;
+    }
+
+    let elapsed = (earliest - presented).as_nanos();
+    let cycles = elapsed.div_ceil(refresh.as_nanos()).max(1);
+    presented + Duration::from_nanos((cycles * refresh.as_nanos()) as u64)
+}
+
+/// Returns the longest of the measured `render_times` plus `margin`.
+fn estimate_render_time(render_times: &VecDeque<Duration>, margin: Duration) -> Duration {
+    render_times.iter().copied().max().unwrap_or_default() + margin
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_vblank_is_aligned_on_the_last_presentation() {
+        let refresh = Duration::from_micros(16_667);
+        let presented = Duration::from_secs(10);
+
+        assert_eq!(
+            next_vblank(presented, refresh, presented),
+            presented + refresh
+        );
+        assert_eq!(
+            next_vblank(presented, refresh, presented + Duration::from_millis(1)),
+            presented + refresh
+        );
+        assert_eq!(
+            next_vblank(presented, refresh, presented + refresh * 2),
+            presented + refresh * 2
+        );
+        assert_eq!(
+            next_vblank(presented, refresh, presented + Duration::from_millis(40)),
+            presented + refresh * 3
+        );
+    }
+
+    #[test]
+    fn render_time_is_the_worst_recent_frame() {
+        let margin = Duration::from_millis(1);
+        assert_eq!(estimate_render_time(&VecDeque::new(), margin), margin);
+
+        let render_times = [3, 7, 5].map(Duration::from_millis).into();
+        assert_eq!(
+            estimate_render_time(&render_times, margin),
+            Duration::from_millis(8)
+        );
+    }
+}
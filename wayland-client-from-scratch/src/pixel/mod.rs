@@ -0,0 +1,500 @@
+mod simd;
+
+use crate::{
+    error::{Result, anyhow},
+    protocol::shm::event::format::Format,
+};
+
+use simd::Swizzle;
+
+/// The number of bytes of a pixel of an RGBA image.
+const RGBA_BYTES_PER_PIXEL: usize = 4;
+
+/// The layout of the pixels of a shared memory buffer.
+///
+/// Names follow the DRM fourcc convention used by `wl_shm`: the channels are
+/// listed from the most to the least significant bits of a little-endian
+/// word, so a [`PixelFormat::Argb8888`] pixel is stored as the bytes
+/// `B, G, R, A`. Formats with an alpha channel hold premultiplied alpha, as
+/// compositors expect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PixelFormat {
+    /// 32-bit `A:R:G:B`, stored as `B, G, R, A`.
+    Argb8888,
+    /// 32-bit `x:R:G:B`, stored as `B, G, R, x`.
+    Xrgb8888,
+    /// 32-bit `A:B:G:R`, stored as `R, G, B, A`.
+    Abgr8888,
+    /// 32-bit `x:B:G:R`, stored as `R, G, B, x`.
+    Xbgr8888,
+    /// 32-bit `R:G:B:A`, stored as `A, B, G, R`.
+    Rgba8888,
+    /// 32-bit `R:G:B:x`, stored as `x, B, G, R`.
+    Rgbx8888,
+    /// 32-bit `B:G:R:A`, stored as `A, R, G, B`.
+    Bgra8888,
+    /// 32-bit `B:G:R:x`, stored as `x, R, G, B`.
+    Bgrx8888,
+    /// 24-bit `R:G:B`, stored as `B, G, R`.
+    Rgb888,
+    /// 24-bit `B:G:R`, stored as `R, G, B`.
+    Bgr888,
+    /// 16-bit `R:G:B` with 5, 6 and 5 bits, stored as a little-endian word.
+    Rgb565,
+}
+
+impl PixelFormat {
+    /// Returns the format of a `wl_shm` format code, if it is one of the
+    /// formats the converters handle.
+    pub fn from_shm(format: Format) -> Option<PixelFormat> {
+        match format {
+            Format::Argb8888 => Some(PixelFormat::Argb8888),
+            Format::Xrgb8888 => Some(PixelFormat::Xrgb8888),
+            Format::Abgr8888 => Some(PixelFormat::Abgr8888),
+            Format::Xbgr8888 => Some(PixelFormat::Xbgr8888),
+            Format::Rgba8888 => Some(PixelFormat::Rgba8888),
+            Format::Rgbx8888 => Some(PixelFormat::Rgbx8888),
+            Format::Bgra8888 => Some(PixelFormat::Bgra8888),
+            Format::Bgrx8888 => Some(PixelFormat::Bgrx8888),
+            Format::Rgb888 => Some(PixelFormat::Rgb888),
+            Format::Bgr888 => Some(PixelFormat::Bgr888),
+            Format::Rgb565 => Some(PixelFormat::Rgb565),
+            _ => None,
+        }
+    }
+
+    /// Returns the `wl_shm` format code to create buffers of this format with.
+    pub fn to_shm(self) -> Format {
+        match self {
+            PixelFormat::Argb8888 => Format::Argb8888,
+            PixelFormat::Xrgb8888 => Format::Xrgb8888,
+            PixelFormat::Abgr8888 => Format::Abgr8888,
+            PixelFormat::Xbgr8888 => Format::Xbgr8888,
+            PixelFormat::Rgba8888 => Format::Rgba8888,
+            PixelFormat::Rgbx8888 => Format::Rgbx8888,
+            PixelFormat::Bgra8888 => Format::Bgra8888,
+            PixelFormat::Bgrx8888 => Format::Bgrx8888,
+            PixelFormat::Rgb888 => Format::Rgb888,
+            PixelFormat::Bgr888 => Format::Bgr888,
+            PixelFormat::Rgb565 => Format::Rgb565,
+        }
+    }
+
+    /// Returns the number of bytes a pixel takes.
+    pub fn bytes_per_pixel(self) -> usize {
+        match self {
+            PixelFormat::Rgb888 | PixelFormat::Bgr888 => 3,
+            PixelFormat::Rgb565 => 2,
+            _ => 4,
+        }
+    }
+
+    /// Returns the number of bits a pixel takes.
+    pub fn bits_per_pixel(self) -> usize {
+        self.bytes_per_pixel() * 8
+    }
+
+    /// Returns the number of bytes of a row of `width` pixels, without padding.
+    pub fn stride(self, width: u32) -> usize {
+        width as usize * self.bytes_per_pixel()
+    }
+
+    /// Returns `true` if the format has an alpha channel.
+    pub fn has_alpha(self) -> bool {
+        matches!(
+            self,
+            PixelFormat::Argb8888
+                | PixelFormat::Abgr8888
+                | PixelFormat::Rgba8888
+                | PixelFormat::Bgra8888
+        )
+    }
+
+    /// Returns the byte offsets of the red, green, blue and alpha channels
+    /// of the byte-aligned formats; `None` for [`PixelFormat::Rgb565`].
+    ///
+    /// The alpha offset of a format without alpha is that of its padding
+    /// byte, if any.
+    fn channel_offsets(self) -> Option<[Option<usize>; 4]> {
+        let offsets = match self {
+            PixelFormat::Argb8888 | PixelFormat::Xrgb8888 => [2, 1, 0, 3],
+            PixelFormat::Abgr8888 | PixelFormat::Xbgr8888 => [0, 1, 2, 3],
+            PixelFormat::Rgba8888 | PixelFormat::Rgbx8888 => [3, 2, 1, 0],
+            PixelFormat::Bgra8888 | PixelFormat::Bgrx8888 => [1, 2, 3, 0],
+            PixelFormat::Rgb888 => return Some([Some(2), Some(1), Some(0), None]),
+            PixelFormat::Bgr888 => return Some([Some(0), Some(1), Some(2), None]),
+            PixelFormat::Rgb565 => return None,
+        };
+
+        Some(offsets.map(Some))
+    }
+
+    /// Returns the rearrangements of the bytes of a pixel from RGBA into
+    /// this format and back, for the 32-bit formats only.
+    fn swizzles(self) -> Option<(Swizzle, Swizzle)> {
+        let [Some(r_at), Some(g_at), Some(b_at), Some(a_at)] = self.channel_offsets()? else {
+            return None;
+        };
+
+        let to_rgba = [r_at, g_at, b_at, a_at];
+        let mut from_rgba = [0; 4];
+        for (channel, offset) in to_rgba.into_iter().enumerate() {
+            from_rgba[offset] = channel;
+        }
+        let padded = !self.has_alpha();
+
+        Some((
+            Swizzle {
+                order: from_rgba,
+                opaque: padded.then_some(a_at),
+            },
+            Swizzle {
+                order: to_rgba,
+                opaque: padded.then_some(3),
+            },
+        ))
+    }
+
+    /// Writes one straight-alpha RGBA pixel into `dst`, which holds
+    /// exactly one pixel of this format.
+    fn encode_pixel(self, [r, g, b, a]: [u8; 4], dst: &mut [u8]) {
+        let Some([r_at, g_at, b_at, a_at]) = self.channel_offsets() else {
+            let rgb565 =
+                ((u16::from(r) >> 3) << 11) | ((u16::from(g) >> 2) << 5) | (u16::from(b) >> 3);
+            dst.copy_from_slice(&rgb565.to_le_bytes());
+            return;
+        };
+
+        let (r, g, b, a) = if self.has_alpha() {
+            (premultiply(r, a), premultiply(g, a), premultiply(b, a), a)
+        } else {
+            (r, g, b, u8::MAX)
+        };
+        for (offset, value) in [(r_at, r), (g_at, g), (b_at, b), (a_at, a)] {
+            if let Some(offset) = offset {
+                dst[offset] = value;
+            }
+        }
+    }
+
+    /// Reads one pixel of this format from `src` as straight-alpha RGBA.
+    fn decode_pixel(self, src: &[u8]) -> [u8; 4] {
+        let Some([r_at, g_at, b_at, a_at]) = self.channel_offsets() else {
+            let rgb565 = u16::from_le_bytes([src[0], src[1]]);
+            let expand = |value: u16, bits: u32| {
+                let value = value as u32;
+                ((value << (8 - bits)) | (value >> (2 * bits - 8))) as u8
+            };
+            return [
+                expand(rgb565 >> 11, 5),
+                expand((rgb565 >> 5) & 0x3f, 6),
+                expand(rgb565 & 0x1f, 5),
+                u8::MAX,
+            ];
+        };
+
+        let channel = |offset: Option<usize>| offset.map_or(u8::MAX, |offset| src[offset]);
+        let (r, g, b) = (channel(r_at), channel(g_at), channel(b_at));
+        if !self.has_alpha() {
+            return [r, g, b, u8::MAX];
+        }
+
+        let a = channel(a_at);
+        [
+            unpremultiply(r, a),
+            unpremultiply(g, a),
+            unpremultiply(b, a),
+            a,
+        ]
+    }
+
+    /// Converts a tightly packed, straight-alpha RGBA image of `width` x
+    /// `height` pixels, as produced by most image crates, into `dst`.
+    ///
+    /// `dst` holds rows of `dst_stride` bytes in this format, such as the
+    /// pixels of a [`SwapchainBuffer`](crate::swapchain::SwapchainBuffer).
+    /// Formats without alpha drop it; formats with alpha premultiply it.
+    ///
+    /// The 32-bit formats are converted with SIMD instructions where the
+    /// CPU has them, see [`PixelFormat::to_rgba`].
+    ///
+    /// # Errors
+    /// Returns an error if `rgba` does not hold the image, or if `dst` or
+    /// its stride are too small for it.
+    pub fn copy_from_rgba(
+        self,
+        rgba: &[u8],
+        width: u32,
+        height: u32,
+        dst: &mut [u8],
+        dst_stride: usize,
+    ) -> Result<()> {
+        check_image(
+            rgba,
+            RGBA_BYTES_PER_PIXEL,
+            width,
+            height,
+            width as usize * RGBA_BYTES_PER_PIXEL,
+        )?;
+        check_image(dst, self.bytes_per_pixel(), width, height, dst_stride)?;
+        if width == 0 || height == 0 {
+            return Ok(());
+        }
+
+        let bpp = self.bytes_per_pixel();
+        let swizzle = self.swizzles().map(|(from_rgba, _)| from_rgba);
+        for (src_row, dst_row) in rgba
+            .chunks_exact(width as usize * RGBA_BYTES_PER_PIXEL)
+            .zip(dst.chunks_mut(dst_stride))
+            .take(height as usize)
+        {
+            let dst_row = &mut dst_row[..self.stride(width)];
+            let converted = swizzle.map_or(0, |swizzle| {
+                simd::convert(src_row, dst_row, swizzle, self.has_alpha())
+            });
+
+            for (src, dst) in src_row
+                .chunks_exact(RGBA_BYTES_PER_PIXEL)
+                .zip(dst_row.chunks_exact_mut(bpp))
+                .skip(converted)
+            {
+                self.encode_pixel([src[0], src[1], src[2], src[3]], dst);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Converts `height` rows of `src_stride` bytes in this format into a
+    /// tightly packed, straight-alpha RGBA image of `width` x `height`
+    /// pixels.
+    ///
+    /// The 32-bit formats are converted four pixels at a time with SSE2 on
+    /// x86-64 and NEON on AArch64, and pixel by pixel elsewhere, with the
+    /// same results. Unpremultiplying is skipped for opaque pixels, so
+    /// opaque frames, such as most captured screens, convert fastest.
+    ///
+    /// # Errors
+    /// Returns an error if `src` or its stride are too small for the image.
+    pub fn to_rgba(
+        self,
+        src: &[u8],
+        width: u32,
+        height: u32,
+        src_stride: usize,
+    ) -> Result<Vec<u8>> {
+        check_image(src, self.bytes_per_pixel(), width, height, src_stride)?;
+
+        let bpp = self.bytes_per_pixel();
+        let row_len = width as usize * RGBA_BYTES_PER_PIXEL;
+        let swizzle = self.swizzles().map(|(_, to_rgba)| to_rgba);
+        let mut rgba = vec![0; row_len * height as usize];
+        if height == 0 || row_len == 0 {
+            return Ok(rgba);
+        }
+
+        for (src_row, dst_row) in src.chunks(src_stride).zip(rgba.chunks_exact_mut(row_len)) {
+            let src_row = &src_row[..self.stride(width)];
+            let converted =
+                swizzle.map_or(0, |swizzle| simd::convert(src_row, dst_row, swizzle, false));
+            if self.has_alpha() {
+                let (vectorized, _) = dst_row.split_at_mut(converted * RGBA_BYTES_PER_PIXEL);
+                for pixel in vectorized.chunks_exact_mut(RGBA_BYTES_PER_PIXEL) {
+                    let a = pixel[3];
+                    if a != u8::MAX {
+                        for channel in &mut pixel[..3] {
+                            *channel = unpremultiply(*channel, a);
+                        }
+                    }
+                }
+            }
+
+            for (src, dst) in src_row
+                .chunks_exact(bpp)
+                .zip(dst_row.chunks_exact_mut(RGBA_BYTES_PER_PIXEL))
+                .skip(converted)
+            {
+                dst.copy_from_slice(&self.decode_pixel(src));
+            }
+        }
+
+        Ok(rgba)
+    }
+}
+
+/// Checks that `data` holds `height` rows of `stride` bytes, each with room
+/// for `width` pixels of `bpp` bytes; the last row may omit its padding.
+fn check_image(data: &[u8], bpp: usize, width: u32, height: u32, stride: usize) -> Result<()> {
+    let row_len = width as usize * bpp;
+    if height == 0 || width == 0 {
+        return Ok(());
+    }
+    if stride < row_len {
+        return Err(anyhow!(
+            "Stride of {} bytes is too small for {} pixels of {} bytes",
+            stride,
+            width,
+            bpp
+        ));
+    }
+
+    let len = stride * (height as usize - 1) + row_len;
+    if data.len() < len {
+        return Err(anyhow!(
+            "Image of {}x{} pixels needs {} bytes, got {}",
+            width,
+            height,
+            len,
+            data.len()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Scales a straight color channel by its alpha.
+fn premultiply(channel: u8, alpha: u8) -> u8 {
+    ((u16::from(channel) * u16::from(alpha) + 127) / 255) as u8
+}
+
+/// Undoes [`premultiply`], clamping channels brighter than their alpha.
+fn unpremultiply(channel: u8, alpha: u8) -> u8 {
+    if alpha == 0 {
+        return 0;
+    }
+
+    ((u16::from(channel) * 255 + u16::from(alpha) / 2) / u16::from(alpha)).min(255) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Opaque red, green and blue, then a half-transparent white.
+    const RGBA: [u8; 16] = [
+        255, 0, 0, 255, 0, 255, 0, 255, 0, 0, 255, 255, 255, 255, 255, 128,
+    ];
+
+    #[test]
+    fn formats_store_channels_in_little_endian_order() {
+        let mut argb = [0u8; 16];
+        PixelFormat::Argb8888
+            .copy_from_rgba(&RGBA, 4, 1, &mut argb, 16)
+            .unwrap();
+        assert_eq!(&argb[..4], [0, 0, 255, 255]);
+        assert_eq!(&argb[12..], [128, 128, 128, 128]);
+
+        let mut rgb565 = [0u8; 8];
+        PixelFormat::Rgb565
+            .copy_from_rgba(&RGBA, 4, 1, &mut rgb565, 8)
+            .unwrap();
+        assert_eq!(&rgb565[..2], 0xf800u16.to_le_bytes());
+    }
+
+    #[test]
+    fn opaque_pixels_round_trip_through_every_format() {
+        let opaque = &RGBA[..12];
+        for format in [
+            PixelFormat::Argb8888,
+            PixelFormat::Xrgb8888,
+            PixelFormat::Abgr8888,
+            PixelFormat::Xbgr8888,
+            PixelFormat::Rgba8888,
+            PixelFormat::Rgbx8888,
+            PixelFormat::Bgra8888,
+            PixelFormat::Bgrx8888,
+            PixelFormat::Rgb888,
+            PixelFormat::Bgr888,
+            PixelFormat::Rgb565,
+        ] {
+            // One padding byte per row checks that the stride is honored
+            let stride = format.stride(3) + 1;
+            let mut pixels = vec![0u8; stride * 2];
+            let image = [opaque, opaque].concat();
+            format
+                .copy_from_rgba(&image, 3, 2, &mut pixels, stride)
+                .unwrap();
+
+            assert_eq!(
+                format.to_rgba(&pixels, 3, 2, stride).unwrap(),
+                image,
+                "{format:?}"
+            );
+            assert_eq!(PixelFormat::from_shm(format.to_shm()), Some(format));
+        }
+    }
+
+    #[test]
+    fn vectorized_rows_match_the_pixel_by_pixel_conversion() {
+        // Nine pixels make two vectorized chunks and one pixel left over
+        let rgba: Vec<u8> = (0..9u8)
+            .flat_map(|i| {
+                [
+                    i * 29,
+                    255 - i * 13,
+                    i * 7 + 100,
+                    [0, 1, 128, 254, 255][i as usize % 5],
+                ]
+            })
+            .collect();
+
+        for format in [
+            PixelFormat::Argb8888,
+            PixelFormat::Xrgb8888,
+            PixelFormat::Abgr8888,
+            PixelFormat::Xbgr8888,
+            PixelFormat::Rgba8888,
+            PixelFormat::Rgbx8888,
+            PixelFormat::Bgra8888,
+            PixelFormat::Bgrx8888,
+        ] {
+            let mut pixels = vec![0u8; format.stride(9)];
+            format
+                .copy_from_rgba(&rgba, 9, 1, &mut pixels, format.stride(9))
+                .unwrap();
+
+            let mut expected = vec![0u8; format.stride(9)];
+            for (src, dst) in rgba.chunks_exact(4).zip(expected.chunks_exact_mut(4)) {
+                format.encode_pixel([src[0], src[1], src[2], src[3]], dst);
+            }
+            assert_eq!(pixels, expected, "{format:?}");
+
+            let decoded: Vec<u8> = expected
+                .chunks_exact(4)
+                .flat_map(|src| format.decode_pixel(src))
+                .collect();
+            assert_eq!(
+                format.to_rgba(&pixels, 9, 1, format.stride(9)).unwrap(),
+                decoded,
+                "{format:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn short_buffers_are_rejected() {
+        let mut dst = [0u8; 15];
+        assert!(
+            PixelFormat::Xrgb8888
+                .copy_from_rgba(&RGBA, 4, 1, &mut dst, 16)
+                .is_err()
+        );
+        assert!(PixelFormat::Xrgb8888.to_rgba(&RGBA, 2, 2, 4).is_err());
+    }
+
+    #[test]
+    fn empty_images_are_copied_without_chunking() {
+        for format in [PixelFormat::Argb8888, PixelFormat::Rgb565] {
+            format.copy_from_rgba(&[], 0, 1, &mut [], 0).unwrap();
+            format.copy_from_rgba(&[], 4, 0, &mut [], 0).unwrap();
+        }
+    }
+
+    #[test]
+    fn empty_images_convert_to_empty_rgba() {
+        for format in [PixelFormat::Argb8888, PixelFormat::Rgb565] {
+            assert!(format.to_rgba(&[], 4, 0, 0).unwrap().is_empty());
+            assert!(format.to_rgba(&[], 0, 4, 0).unwrap().is_empty());
+        }
+    }
+}
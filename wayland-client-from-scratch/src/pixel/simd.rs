@@ -0,0 +1,271 @@
+//! Vectorized conversion of 32-bit pixels, four at a time.
+//!
+//! The channels of the 32-bit formats only differ in their byte order, so
+//! converting between them rearranges the bytes of each pixel, and fills
+//! the padding byte of the formats without alpha. Converting from straight
+//! alpha also premultiplies the color channels. Both are done with SSE2 on
+//! x86-64 and NEON on AArch64, which every CPU of these architectures has;
+//! elsewhere nothing is vectorized and the scalar code converts every pixel.
+
+/// The number of bytes of a pixel.
+const PIXEL_LEN: usize = 4;
+
+/// The number of bytes converted at once.
+const CHUNK_LEN: usize = 16;
+
+/// A rearrangement of the bytes of 32-bit pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct Swizzle {
+    /// The byte of the source pixel each byte of the destination pixel is
+    /// taken from.
+    pub(super) order: [usize; 4],
+    /// The byte of the destination pixel set to 0xff instead, the padding
+    /// of a format without alpha.
+    pub(super) opaque: Option<usize>,
+}
+
+/// Converts the pixels of `src` into `dst` as far as whole chunks of four
+/// pixels go.
+///
+/// With `premultiply`, `src` holds straight-alpha RGBA pixels whose color
+/// channels are scaled by their alpha before the bytes are rearranged.
+///
+/// # Returns
+/// The number of pixels converted, left for the scalar code to finish.
+pub(super) fn convert(src: &[u8], dst: &mut [u8], swizzle: Swizzle, premultiply: bool) -> usize {
+    let Some(kernel) = arch::Kernel::new(swizzle, premultiply) else {
+        return 0;
+    };
+
+    let mut converted = 0;
+    for (src, dst) in src
+        .chunks_exact(CHUNK_LEN)
+        .zip(dst.chunks_exact_mut(CHUNK_LEN))
+    {
+        let (Ok(src), Ok(dst)) = (src.try_into(), dst.try_into()) else {
+            unreachable!("chunks are exactly {CHUNK_LEN} bytes long");
+        };
+        kernel.run(src, dst);
+        converted += CHUNK_LEN / PIXEL_LEN;
+    }
+
+    converted
+}
+
+#[cfg(all(target_arch = "x86_64", target_feature = "sse2"))]
+mod arch {
+    use std::arch::x86_64::{
+        __m128i, _mm_add_epi16, _mm_and_si128, _mm_andnot_si128, _mm_cvtsi32_si128,
+        _mm_loadu_si128, _mm_mullo_epi16, _mm_or_si128, _mm_packus_epi16, _mm_set1_epi16,
+        _mm_set1_epi32, _mm_setzero_si128, _mm_shufflehi_epi16, _mm_shufflelo_epi16, _mm_sll_epi32,
+        _mm_srl_epi32, _mm_srli_epi16, _mm_storeu_si128, _mm_unpackhi_epi8, _mm_unpacklo_epi8,
+    };
+
+    use super::{CHUNK_LEN, Swizzle};
+
+    /// The SSE2 conversion of a swizzle.
+    ///
+    /// SSE2 has no byte shuffle, so each byte is moved within its 32-bit
+    /// lane by a pair of shifts.
+    pub(super) struct Kernel {
+        /// The right and left shift counts moving each source byte to its
+        /// destination, `None` when the order is unchanged.
+        shifts: Option<[(__m128i, __m128i); 4]>,
+        /// The bytes set in every destination pixel.
+        fill: __m128i,
+        /// Whether the color channels are premultiplied first.
+        premultiply: bool,
+    }
+
+    impl Kernel {
+        pub(super) fn new(swizzle: Swizzle, premultiply: bool) -> Option<Kernel> {
+            // SAFETY: the module is only compiled with SSE2 enabled.
+            unsafe { Kernel::new_sse2(swizzle, premultiply) }
+        }
+
+        pub(super) fn run(&self, src: &[u8; CHUNK_LEN], dst: &mut [u8; CHUNK_LEN]) {
+            // SAFETY: the module is only compiled with SSE2 enabled.
+            unsafe { self.run_sse2(src, dst) }
+        }
+
+        #[target_feature(enable = "sse2")]
+        fn new_sse2(swizzle: Swizzle, premultiply: bool) -> Option<Kernel> {
+            let shifts = (swizzle.order != [0, 1, 2, 3]).then(|| {
+                let mut shifts = [(_mm_setzero_si128(), _mm_setzero_si128()); 4];
+                for (to, (shift, from)) in shifts.iter_mut().zip(swizzle.order).enumerate() {
+                    *shift = (
+                        _mm_cvtsi32_si128(from as i32 * 8),
+                        _mm_cvtsi32_si128(to as i32 * 8),
+                    );
+                }
+                shifts
+            });
+            let fill = match swizzle.opaque {
+                Some(byte) => _mm_set1_epi32((0xff_u32 << (byte * 8)) as i32),
+                None => _mm_setzero_si128(),
+            };
+
+            Some(Kernel {
+                shifts,
+                fill,
+                premultiply,
+            })
+        }
+
+        #[target_feature(enable = "sse2")]
+        fn run_sse2(&self, src: &[u8; CHUNK_LEN], dst: &mut [u8; CHUNK_LEN]) {
+            // SAFETY: `src` is 16 readable bytes, and the load is unaligned.
+            let mut pixels = unsafe { _mm_loadu_si128(src.as_ptr().cast()) };
+            if self.premultiply {
+                pixels = premultiply(pixels);
+            }
+            if let Some(shifts) = &self.shifts {
+                let byte = _mm_set1_epi32(0xff);
+                let mut moved = _mm_setzero_si128();
+                for (from, to) in shifts {
+                    let channel = _mm_and_si128(_mm_srl_epi32(pixels, *from), byte);
+                    moved = _mm_or_si128(moved, _mm_sll_epi32(channel, *to));
+                }
+                pixels = moved;
+            }
+            pixels = _mm_or_si128(pixels, self.fill);
+            // SAFETY: `dst` is 16 writable bytes, and the store is unaligned.
+            unsafe { _mm_storeu_si128(dst.as_mut_ptr().cast(), pixels) };
+        }
+    }
+
+    /// Scales the color channels of four RGBA pixels by their alpha, with
+    /// the rounding of the scalar `premultiply`.
+    #[target_feature(enable = "sse2")]
+    fn premultiply(pixels: __m128i) -> __m128i {
+        let zero = _mm_setzero_si128();
+        let low = scale(_mm_unpacklo_epi8(pixels, zero));
+        let high = scale(_mm_unpackhi_epi8(pixels, zero));
+        let scaled = _mm_packus_epi16(low, high);
+
+        let alpha = _mm_set1_epi32(0xff00_0000_u32 as i32);
+        _mm_or_si128(
+            _mm_andnot_si128(alpha, scaled),
+            _mm_and_si128(alpha, pixels),
+        )
+    }
+
+    /// Scales two pixels widened to 16-bit channels by their alpha.
+    #[target_feature(enable = "sse2")]
+    fn scale(half: __m128i) -> __m128i {
+        // Every channel times the alpha of its pixel, the last channel, plus
+        // 128; then divided by 255 with (t + t / 256) / 256
+        let alpha = _mm_shufflehi_epi16::<0xff>(_mm_shufflelo_epi16::<0xff>(half));
+        let t = _mm_add_epi16(_mm_mullo_epi16(half, alpha), _mm_set1_epi16(128));
+        _mm_srli_epi16::<8>(_mm_add_epi16(t, _mm_srli_epi16::<8>(t)))
+    }
+}
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+mod arch {
+    use std::arch::aarch64::{
+        uint8x16_t, vaddq_u16, vbslq_u8, vcombine_u8, vdupq_n_u16, vget_high_u8, vget_low_u8,
+        vld1q_u8, vmovn_u16, vmull_u8, vorrq_u8, vqtbl1q_u8, vshrq_n_u16, vst1q_u8,
+    };
+
+    use super::{CHUNK_LEN, PIXEL_LEN, Swizzle};
+
+    /// The NEON conversion of a swizzle, a table lookup moving the bytes.
+    pub(super) struct Kernel {
+        /// The source byte of each destination byte of the chunk.
+        table: uint8x16_t,
+        /// The bytes set in every destination pixel.
+        fill: uint8x16_t,
+        /// Whether the color channels are premultiplied first.
+        premultiply: bool,
+    }
+
+    impl Kernel {
+        pub(super) fn new(swizzle: Swizzle, premultiply: bool) -> Option<Kernel> {
+            // SAFETY: the module is only compiled with NEON enabled.
+            unsafe { Kernel::new_neon(swizzle, premultiply) }
+        }
+
+        pub(super) fn run(&self, src: &[u8; CHUNK_LEN], dst: &mut [u8; CHUNK_LEN]) {
+            // SAFETY: the module is only compiled with NEON enabled.
+            unsafe { self.run_neon(src, dst) }
+        }
+
+        #[target_feature(enable = "neon")]
+        fn new_neon(swizzle: Swizzle, premultiply: bool) -> Option<Kernel> {
+            let mut table = [0u8; CHUNK_LEN];
+            let mut fill = [0u8; CHUNK_LEN];
+            for (index, (entry, fill)) in table.iter_mut().zip(&mut fill).enumerate() {
+                let (pixel, byte) = (index / PIXEL_LEN, index % PIXEL_LEN);
+                *entry = (pixel * PIXEL_LEN + swizzle.order[byte]) as u8;
+                if swizzle.opaque == Some(byte) {
+                    *fill = 0xff;
+                }
+            }
+
+            Some(Kernel {
+                table: load(&table),
+                fill: load(&fill),
+                premultiply,
+            })
+        }
+
+        #[target_feature(enable = "neon")]
+        fn run_neon(&self, src: &[u8; CHUNK_LEN], dst: &mut [u8; CHUNK_LEN]) {
+            let mut pixels = load(src);
+            if self.premultiply {
+                pixels = premultiply(pixels);
+            }
+            pixels = vorrq_u8(vqtbl1q_u8(pixels, self.table), self.fill);
+            // SAFETY: `dst` is 16 writable bytes.
+            unsafe { vst1q_u8(dst.as_mut_ptr(), pixels) };
+        }
+    }
+
+    /// Loads a chunk of bytes into a vector.
+    #[target_feature(enable = "neon")]
+    fn load(bytes: &[u8; CHUNK_LEN]) -> uint8x16_t {
+        // SAFETY: `bytes` is 16 readable bytes.
+        unsafe { vld1q_u8(bytes.as_ptr()) }
+    }
+
+    /// Scales the color channels of four RGBA pixels by their alpha, with
+    /// the rounding of the scalar `premultiply`.
+    #[target_feature(enable = "neon")]
+    fn premultiply(pixels: uint8x16_t) -> uint8x16_t {
+        let broadcast = load(&[3, 3, 3, 3, 7, 7, 7, 7, 11, 11, 11, 11, 15, 15, 15, 15]);
+        let alpha = vqtbl1q_u8(pixels, broadcast);
+        let round = vdupq_n_u16(128);
+        // Each channel times its alpha plus 128, then divided by 255 with
+        // (t + t / 256) / 256
+        let low = vaddq_u16(vmull_u8(vget_low_u8(pixels), vget_low_u8(alpha)), round);
+        let high = vaddq_u16(vmull_u8(vget_high_u8(pixels), vget_high_u8(alpha)), round);
+        let low = vmovn_u16(vshrq_n_u16::<8>(vaddq_u16(low, vshrq_n_u16::<8>(low))));
+        let high = vmovn_u16(vshrq_n_u16::<8>(vaddq_u16(high, vshrq_n_u16::<8>(high))));
+        let scaled = vcombine_u8(low, high);
+
+        let mask = load(&[0, 0, 0, 0xff, 0, 0, 0, 0xff, 0, 0, 0, 0xff, 0, 0, 0, 0xff]);
+        vbslq_u8(mask, pixels, scaled)
+    }
+}
+
+#[cfg(not(any(
+    all(target_arch = "x86_64", target_feature = "sse2"),
+    all(target_arch = "aarch64", target_feature = "neon")
+)))]
+mod arch {
+    use super::{CHUNK_LEN, Swizzle};
+
+    /// No conversion is vectorized without SSE2 or NEON.
+    pub(super) enum Kernel {}
+
+    impl Kernel {
+        pub(super) fn new(_swizzle: Swizzle, _premultiply: bool) -> Option<Kernel> {
+            None
+        }
+
+        pub(super) fn run(&self, _src: &[u8; CHUNK_LEN], _dst: &mut [u8; CHUNK_LEN]) {
+            match *self {}
+        }
+    }
+}
@@ -0,0 +1,384 @@
+use std::sync::{Arc, Weak};
+
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::{
+        pointer::{Pointer, PointerState, request as pointer_request},
+        pointer_constraints::{Lifetime, LockedPointer, LockedPointerEvent, PointerConstraints},
+        relative_pointer::{
+            RelativePointer, RelativePointerManager, event::relative_motion::RelativeMotion,
+        },
+        surface::{Surface, SurfaceRole},
+        types::{WlInt, WlObject, WlUInt},
+    },
+    sync::{Lock, LockCell},
+};
+
+/// An event delivered to the handler of a [`PointerCapture`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CaptureEvent {
+    /// The compositor locked the pointer and the cursor was hidden.
+    Locked,
+    /// The pointer moved by `(dx, dy)`, with acceleration applied, and by
+    /// `(dx_unaccel, dy_unaccel)` before it, e.g. for camera controls that
+    /// want raw input.
+    Motion {
+        /// The horizontal motion, with acceleration applied.
+        dx: f64,
+        /// The vertical motion, with acceleration applied.
+        dy: f64,
+        /// The horizontal motion before acceleration.
+        dx_unaccel: f64,
+        /// The vertical motion before acceleration.
+        dy_unaccel: f64,
+    },
+    /// The capture ended because the surface lost the pointer focus. The
+    /// lock was released and the cursor restored; no event follows.
+    Released,
+}
+
+/// The handler receiving the events of a pointer capture.
+type CaptureHandler = Box<dyn FnMut(CaptureEvent) + Send>;
+
+/// The state of a capture, shared with the handlers of its protocol objects.
+struct CaptureState {
+    /// The connection the capture was started on.
+    conn: Connection,
+    /// The object ID of the captured pointer.
+    pointer: u32,
+    /// The pointer lock, `None` once released.
+    lock: Lock<Option<LockedPointer>>,
+    /// The relative motion reporting, `None` once released.
+    relative: Lock<Option<RelativePointer>>,
+    /// Whether the compositor activated the lock.
+    locked: LockCell<bool>,
+    /// The cursor surface and hotspot shown again once released.
+    restore_cursor: LockCell<Option<(u32, i32, i32)>>,
+    /// The handler receiving the capture events, taken out while it runs.
+    handler: Lock<Option<CaptureHandler>>,
+}
+
+impl CaptureState {
+    /// Releases the lock and relative motion reporting and shows the cursor
+    /// again, returning `false` if the capture was already released.
+    fn release(&self) -> Result<bool> {
+        let lock = self.lock.take();
+        let relative = self.relative.take();
+        if lock.is_none() && relative.is_none() {
+            return Ok(false);
+        }
+        drop(lock);
+        drop(relative);
+
+        let restore = self
+            .restore_cursor
+            .get()
+            .filter(|&(surface, _, _)| self.conn.object(surface).is_some());
+        if self.locked.replace(false)
+            && let Some((surface, hotspot_x, hotspot_y)) = restore
+        {
+            self.set_cursor(surface, hotspot_x, hotspot_y)?;
+        }
+
+        Ok(true)
+    }
+
+    /// Sets the cursor of the pointer, if it is on a surface of the client.
+    fn set_cursor(&self, surface: u32, hotspot_x: i32, hotspot_y: i32) -> Result<()> {
+        let Some(serial) = self
+            .conn
+            .object_data::<PointerState>(self.pointer)
+            .and_then(|state| state.focused_enter_serial())
+        else {
+            return Ok(());
+        };
+
+        pointer_request::set_cursor(
+            &self.conn,
+            self.pointer,
+            WlUInt(serial),
+            WlObject(surface),
+            WlInt(hotspot_x),
+            WlInt(hotspot_y),
+        )
+    }
+}
+
+/// A pointer captured for games and other applications steering with the
+/// mouse: the pointer is locked in place on a surface, the cursor hidden,
+/// and its motion reported as relative deltas that do not stop at screen
+/// edges.
+///
+/// Combines `zwp_pointer_constraints_v1`, `zwp_relative_pointer_v1` and
+/// `wl_pointer.set_cursor`. The lock activates once the compositor sees fit,
+/// usually while the surface has the pointer focus, which is announced with
+/// [`CaptureEvent::Locked`]. When the surface loses the focus, the capture
+/// ends with [`CaptureEvent::Released`]; a new one must be started to
+/// capture the pointer again, e.g. on the next click.
+///
+/// Dropping the guard ends the capture too. Either way the cursor set with
+/// [`PointerCapture::restore_cursor`] is shown again; without one, the
+/// cursor stays hidden until the application sets one.
+pub struct PointerCapture {
+    state: Arc<CaptureState>,
+}
+
+impl PointerCapture {
+    /// Captures `pointer` on `surface`; `handler` receives the
+    /// [`CaptureEvent`]s.
+    ///
+    /// A [`Window`](crate::window::Window) captures the pointer with
+    /// [`Window::capture_pointer`](crate::window::Window::capture_pointer).
+    ///
+    /// # Errors
+    /// Returns an error if a protocol object cannot be created, e.g.
+    /// because the surface already constrains the pointer.
+    pub fn new<F>(
+        surface: &Surface,
+        pointer: &Pointer,
+        constraints: &PointerConstraints,
+        relative_pointers: &RelativePointerManager,
+        handler: F,
+    ) -> Result<PointerCapture>
+    where
+        F: FnMut(CaptureEvent) + Send + 'static,
+    {
+        let state = Arc::new(CaptureState {
+            conn: surface.connection().clone(),
+            pointer: pointer.id(),
+            lock: Lock::new(None),
+            relative: Lock::new(None),
+            locked: LockCell::new(false),
+            restore_cursor: LockCell::new(None),
+            handler: Lock::new(Some(Box::new(handler))),
+        });
+
+        let weak = Arc::downgrade(&state);
+        let relative = relative_pointers.get_relative_pointer(pointer, move |_, motion| {
+            emit(&weak, motion_event(motion));
+        })?;
+        state.relative.replace(Some(relative));
+
+        let weak = Arc::downgrade(&state);
+        let lock = constraints.lock_pointer(
+            surface,
+            pointer,
+            None,
+            Lifetime::Oneshot,
+            move |_, event| {
+                let Some(state) = weak.upgrade() else {
+                    return;
+                };
+                match event {
+                    LockedPointerEvent::Locked => {
+                        state.locked.set(true);
+                        // Listeners cannot report errors; hiding the cursor is best effort
+                        let _ = state.set_cursor(0, 0, 0);
+                        emit(&weak, CaptureEvent::Locked);
+                    }
+                    // A oneshot lock is defunct once deactivated
+                    LockedPointerEvent::Unlocked => {
+                        if let Ok(true) = state.release() {
+                            emit(&weak, CaptureEvent::Released);
+                        }
+                    }
+                }
+            },
+        )?;
+        state.lock.replace(Some(lock));
+
+        Ok(PointerCapture { state })
+    }
+
+    /// Returns `true` while the compositor keeps the pointer locked.
+    pub fn is_locked(&self) -> bool {
+        self.state.locked.get()
+    }
+
+    /// Returns `true` until the capture was released.
+    pub fn is_active(&self) -> bool {
+        self.state.lock.borrow().is_some()
+    }
+
+    /// Sets the cursor shown again once the capture ends: `surface`, with
+    /// its hotspot at `(hotspot_x, hotspot_y)`. The surface is given the
+    /// cursor role.
+    ///
+    /// # Errors
+    /// Returns an error if the surface already has another role.
+    pub fn restore_cursor(&self, surface: &Surface, hotspot_x: i32, hotspot_y: i32) -> Result<()> {
+        surface
+            .state()
+            .assign_role(surface.id(), SurfaceRole::Cursor)?;
+        self.state
+            .restore_cursor
+            .set(Some((surface.id(), hotspot_x, hotspot_y)));
+        Ok(())
+    }
+
+    /// Ends the capture, releasing the pointer and restoring the cursor.
+    ///
+    /// Dropping the guard does the same.
+    pub fn release(self) -> Result<()> {
+        self.state.release()?;
+        Ok(())
+    }
+}
+
+impl Drop for PointerCapture {
+    fn drop(&mut self) {
+        self.state.handler.borrow_mut().take();
+
+        // Queuing requests cannot fail; errors only surface on flush.
+        let _ = self.state.release();
+    }
+}
+
+/// Converts a relative motion event to the capture event reporting it.
+fn motion_event(motion: RelativeMotion) -> CaptureEvent {
+    CaptureEvent::Motion {
+        dx: motion.dx.to_f64(),
+        dy: motion.dy.to_f64(),
+        dx_unaccel: motion.dx_unaccel.to_f64(),
+        dy_unaccel: motion.dy_unaccel.to_f64(),
+    }
+}
+
+/// Delivers `event` to the handler of the capture, if the capture still
+/// exists.
+fn emit(capture: &Weak<CaptureState>, event: CaptureEvent) {
+    let Some(state) = capture.upgrade() else {
+        return;
+    };
+    let Some(mut handler) = state.handler.take() else {
+        return;
+    };
+
+    handler(event);
+
+    let mut slot = state.handler.borrow_mut();
+    if slot.is_none() {
+        *slot = Some(handler);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        os::unix::net::UnixStream,
+    };
+
+    use super::*;
+    use crate::protocol::{
+        WlObjectId,
+        endian::Endian,
+        message::{WL_MESSAGE_HEADER_LEN, WlMessage, WlMessageHeader},
+        registry::event::global::Global,
+        seat::SeatState,
+        types::{WlFixed, WlString},
+    };
+
+    /// Sends an event with 32-bit arguments to the client.
+    fn send(compositor: &mut UnixStream, object_id: u32, opcode: u16, args: &[i32]) {
+        let data: Vec<u8> = args.iter().flat_map(|arg| arg.to_ne_bytes()).collect();
+        let bytes: Vec<u8> = WlMessage::new(object_id, opcode, &data).unwrap().into();
+        compositor.write_all(&bytes).unwrap();
+    }
+
+    #[test]
+    fn capture_hides_the_cursor_until_focus_is_lost() {
+        let (client, mut compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+        for (name, interface) in [
+            (1, WlObjectId::PointerConstraints),
+            (2, WlObjectId::RelativePointerManager),
+        ] {
+            conn.insert_global(Global {
+                name: WlUInt(name),
+                interface: WlString::new(interface.interface_name()).unwrap(),
+                version: WlUInt(1),
+            });
+        }
+        let constraints = PointerConstraints::bind(&conn).unwrap();
+        let relative_pointers = RelativePointerManager::bind(&conn).unwrap();
+
+        let surface = Surface::new(conn.new_object(WlObjectId::Surface, 6).unwrap(), &conn);
+        let cursor = Surface::new(conn.new_object(WlObjectId::Surface, 6).unwrap(), &conn);
+        let pointer_id = conn.new_object(WlObjectId::Pointer, 9).unwrap();
+        let seat = Arc::new(SeatState::default());
+        let pointer = Pointer::new(pointer_id, 9, &conn, seat, Box::new(|_, _| {}));
+
+        let events = Arc::new(Lock::new(Vec::new()));
+        let sink = events.clone();
+        let capture = PointerCapture::new(
+            &surface,
+            &pointer,
+            &constraints,
+            &relative_pointers,
+            move |event| sink.borrow_mut().push(event),
+        )
+        .unwrap();
+        capture.restore_cursor(&cursor, 3, 4).unwrap();
+        let relative_id = pointer_id + 1;
+        let lock_id = pointer_id + 2;
+
+        // `wl_pointer.enter` with serial 7, then the lock activates
+        send(
+            &mut compositor,
+            pointer_id,
+            0,
+            &[7, surface.id() as i32, 0, 0],
+        );
+        send(&mut compositor, lock_id, 0, &[]);
+        let motion = [WlFixed::from_f64(1.5).0, WlFixed::from_f64(-2.0).0];
+        send(
+            &mut compositor,
+            relative_id,
+            0,
+            &[0, 0, motion[0], motion[1], 0, 0],
+        );
+        conn.dispatch().unwrap();
+        assert!(capture.is_locked());
+
+        send(&mut compositor, lock_id, 1, &[]);
+        conn.dispatch().unwrap();
+        assert!(!capture.is_active());
+        assert_eq!(
+            *events.borrow(),
+            [
+                CaptureEvent::Locked,
+                CaptureEvent::Motion {
+                    dx: 1.5,
+                    dy: -2.0,
+                    dx_unaccel: 0.0,
+                    dy_unaccel: 0.0
+                },
+                CaptureEvent::Released,
+            ]
+        );
+
+        let cursor_id = cursor.id();
+        drop(capture);
+        drop((pointer, cursor, surface, constraints, relative_pointers));
+        drop(conn);
+
+        let mut received = Vec::new();
+        compositor.read_to_end(&mut received).unwrap();
+        let mut received = &received[..];
+        let mut cursors = Vec::new();
+        while !received.is_empty() {
+            let header = WlMessageHeader::decode(received, Endian::NATIVE).unwrap();
+            if header.object_id == pointer_id && header.opcode == 0 {
+                let args: Vec<i32> = received[WL_MESSAGE_HEADER_LEN..header.size as usize]
+                    .chunks_exact(4)
+                    .map(|word| i32::from_ne_bytes(word.try_into().unwrap()))
+                    .collect();
+                cursors.push(args);
+            }
+            received = &received[header.size as usize..];
+        }
+        assert_eq!(cursors, [vec![7, 0, 0, 0], vec![7, cursor_id as i32, 3, 4]]);
+    }
+}
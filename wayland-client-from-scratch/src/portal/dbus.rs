@@ -0,0 +1,929 @@
+//! A minimal D-Bus client, enough to call the methods of the desktop portals
+//! and wait for the signals carrying their responses.
+//!
+//! Only the session bus over a Unix socket is supported, authenticated with
+//! the `EXTERNAL` mechanism. Messages are sent in little-endian byte order
+//! and received in either.
+
+use std::{
+    collections::VecDeque,
+    io::{self, Read, Write},
+    os::{
+        linux::net::SocketAddrExt,
+        unix::{
+            fs::MetadataExt,
+            net::{SocketAddr, UnixStream},
+        },
+    },
+    time::{Duration, Instant},
+};
+
+use crate::error::{Context, Result, anyhow};
+
+/// The well-known name, object path and interface of the bus itself.
+const BUS_NAME: &str = "org.freedesktop.DBus";
+const BUS_PATH: &str = "/org/freedesktop/DBus";
+
+/// The largest message accepted, as specified by D-Bus.
+const MAX_MESSAGE_SIZE: usize = 128 * 1024 * 1024;
+
+/// A value of the D-Bus type system.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+    Byte(u8),
+    Bool(bool),
+    Int16(i16),
+    UInt16(u16),
+    Int32(i32),
+    UInt32(u32),
+    Int64(i64),
+    UInt64(u64),
+    Double(f64),
+    String(String),
+    ObjectPath(String),
+    Signature(String),
+    /// An index into the descriptors of the message.
+    UnixFd(u32),
+    /// An array of values of the element signature, kept for empty arrays.
+    Array(String, Vec<Value>),
+    Struct(Vec<Value>),
+    DictEntry(Box<Value>, Box<Value>),
+    Variant(Box<Value>),
+}
+
+impl Value {
+    /// Returns the signature of the value.
+    pub(crate) fn signature(&self) -> String {
+        match self {
+            Value::Byte(_) => "y".into(),
+            Value::Bool(_) => "b".into(),
+            Value::Int16(_) => "n".into(),
+            Value::UInt16(_) => "q".into(),
+            Value::Int32(_) => "i".into(),
+            Value::UInt32(_) => "u".into(),
+            Value::Int64(_) => "x".into(),
+            Value::UInt64(_) => "t".into(),
+            Value::Double(_) => "d".into(),
+            Value::String(_) => "s".into(),
+            Value::ObjectPath(_) => "o".into(),
+            Value::Signature(_) => "g".into(),
+            Value::UnixFd(_) => "h".into(),
+            Value::Array(element, _) => format!("a{element}"),
+            Value::Struct(fields) => {
+                let fields: String = fields.iter().map(Value::signature).collect();
+                format!("({fields})")
+            }
+            Value::DictEntry(key, value) => {
+                format!("{{{}{}}}", key.signature(), value.signature())
+            }
+            Value::Variant(_) => "v".into(),
+        }
+    }
+
+    /// Builds an `a{sv}` dictionary, the options argument of portal methods.
+    pub(crate) fn dict(entries: Vec<(&str, Value)>) -> Value {
+        let entries = entries
+            .into_iter()
+            .map(|(key, value)| {
+                Value::DictEntry(
+                    Box::new(Value::String(key.into())),
+                    Box::new(Value::Variant(Box::new(value))),
+                )
+            })
+            .collect();
+        Value::Array("{sv}".into(), entries)
+    }
+
+    /// Looks `key` up in an `a{sv}` dictionary, returning the value inside
+    /// the variant.
+    pub(crate) fn lookup(&self, key: &str) -> Option<&Value> {
+        let Value::Array(_, entries) = self else {
+            return None;
+        };
+        entries.iter().find_map(|entry| match entry {
+            Value::DictEntry(k, v) if matches!(&**k, Value::String(s) if s == key) => match &**v {
+                Value::Variant(value) => Some(&**value),
+                value => Some(value),
+            },
+            _ => None,
+        })
+    }
+
+    /// Returns the string of a string, object path or signature.
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) | Value::ObjectPath(s) | Value::Signature(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of an unsigned 32-bit integer.
+    pub(crate) fn as_u32(&self) -> Option<u32> {
+        match self {
+            Value::UInt32(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+/// The type of a message, the second byte of its header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MessageType {
+    MethodCall = 1,
+    MethodReturn = 2,
+    Error = 3,
+    Signal = 4,
+}
+
+impl TryFrom<u8> for MessageType {
+    type Error = crate::error::Error;
+
+    fn try_from(value: u8) -> Result<MessageType> {
+        match value {
+            1 => Ok(MessageType::MethodCall),
+            2 => Ok(MessageType::MethodReturn),
+            3 => Ok(MessageType::Error),
+            4 => Ok(MessageType::Signal),
+            _ => Err(anyhow!("Invalid D-Bus message type: {value}")),
+        }
+    }
+}
+
+/// The codes of the header fields of a message.
+mod field {
+    pub const PATH: u8 = 1;
+    pub const INTERFACE: u8 = 2;
+    pub const MEMBER: u8 = 3;
+    pub const ERROR_NAME: u8 = 4;
+    pub const REPLY_SERIAL: u8 = 5;
+    pub const DESTINATION: u8 = 6;
+    pub const SENDER: u8 = 7;
+    pub const SIGNATURE: u8 = 8;
+}
+
+/// A D-Bus message.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(crate) struct Message {
+    /// The type of the message, `None` only while it is built.
+    pub(crate) kind: Option<MessageType>,
+    /// The serial the sender gave the message.
+    pub(crate) serial: u32,
+    /// The object the call is for, or the signal comes from.
+    pub(crate) path: Option<String>,
+    /// The interface of the method or signal.
+    pub(crate) interface: Option<String>,
+    /// The name of the method or signal.
+    pub(crate) member: Option<String>,
+    /// The name of the error of an error reply.
+    pub(crate) error_name: Option<String>,
+    /// The serial of the call a reply answers.
+    pub(crate) reply_serial: Option<u32>,
+    /// The name of the connection the message is for.
+    pub(crate) destination: Option<String>,
+    /// The unique name of the connection that sent the message.
+    pub(crate) sender: Option<String>,
+    /// The arguments of the message.
+    pub(crate) body: Vec<Value>,
+}
+
+impl Message {
+    /// Builds a method call of `interface.member` on the object `path` of
+    /// the connection `destination`.
+    pub(crate) fn method_call(
+        destination: &str,
+        path: &str,
+        interface: &str,
+        member: &str,
+        body: Vec<Value>,
+    ) -> Message {
+        Message {
+            kind: Some(MessageType::MethodCall),
+            path: Some(path.into()),
+            interface: Some(interface.into()),
+            member: Some(member.into()),
+            destination: Some(destination.into()),
+            body,
+            ..Message::default()
+        }
+    }
+
+    /// Returns `true` if the message is the signal `interface.member` sent
+    /// by the object `path`.
+    pub(crate) fn is_signal(&self, path: &str, interface: &str, member: &str) -> bool {
+        self.kind == Some(MessageType::Signal)
+            && self.path.as_deref() == Some(path)
+            && self.interface.as_deref() == Some(interface)
+            && self.member.as_deref() == Some(member)
+    }
+
+    /// Serializes the message in little-endian byte order.
+    pub(crate) fn encode(&self) -> Result<Vec<u8>> {
+        let kind = self
+            .kind
+            .ok_or_else(|| anyhow!("D-Bus message has no type"))?;
+
+        let mut body = Writer::default();
+        for value in &self.body {
+            body.write(value);
+        }
+        let signature: String = self.body.iter().map(Value::signature).collect();
+
+        let mut fields = Vec::new();
+        let mut push = |code: u8, value: Value| {
+            fields.push(Value::Struct(vec![
+                Value::Byte(code),
+                Value::Variant(Box::new(value)),
+            ]));
+        };
+        if let Some(path) = &self.path {
+            push(field::PATH, Value::ObjectPath(path.clone()));
+        }
+        if let Some(interface) = &self.interface {
+            push(field::INTERFACE, Value::String(interface.clone()));
+        }
+        if let Some(member) = &self.member {
+            push(field::MEMBER, Value::String(member.clone()));
+        }
+        if let Some(error_name) = &self.error_name {
+            push(field::ERROR_NAME, Value::String(error_name.clone()));
+        }
+        if let Some(reply_serial) = self.reply_serial {
+            push(field::REPLY_SERIAL, Value::UInt32(reply_serial));
+        }
+        if let Some(destination) = &self.destination {
+            push(field::DESTINATION, Value::String(destination.clone()));
+        }
+        if let Some(sender) = &self.sender {
+            push(field::SENDER, Value::String(sender.clone()));
+        }
+        if !signature.is_empty() {
+            push(field::SIGNATURE, Value::Signature(signature));
+        }
+
+        let mut message = Writer::default();
+        message.write(&Value::Byte(b'l'));
+        message.write(&Value::Byte(kind as u8));
+        message.write(&Value::Byte(0));
+        message.write(&Value::Byte(1));
+        message.write(&Value::UInt32(body.buf.len() as u32));
+        message.write(&Value::UInt32(self.serial));
+        message.write(&Value::Array("(yv)".into(), fields));
+        message.align(8);
+        message.buf.extend_from_slice(&body.buf);
+
+        if message.buf.len() > MAX_MESSAGE_SIZE {
+            return Err(anyhow!("D-Bus message too large"));
+        }
+        Ok(message.buf)
+    }
+
+    /// Returns the length of the message starting with the 16 bytes of
+    /// `header`.
+    fn length(header: &[u8; 16]) -> Result<usize> {
+        let big_endian = endianness(header[0])?;
+        let read = |at: usize| {
+            let bytes = header[at..at + 4].try_into().unwrap();
+            if big_endian {
+                u32::from_be_bytes(bytes)
+            } else {
+                u32::from_le_bytes(bytes)
+            }
+        };
+        let body = read(4) as usize;
+        let fields = read(12) as usize;
+
+        let length = (16 + fields).next_multiple_of(8) + body;
+        if length > MAX_MESSAGE_SIZE {
+            return Err(anyhow!("D-Bus message too large: {length} bytes"));
+        }
+        Ok(length)
+    }
+
+    /// Parses a complete message.
+    pub(crate) fn decode(buf: &[u8]) -> Result<Message> {
+        if buf.len() < 16 {
+            return Err(anyhow!("D-Bus message too short"));
+        }
+        let mut reader = Reader {
+            buf,
+            pos: 0,
+            big_endian: endianness(buf[0])?,
+        };
+        reader.pos = 1;
+        let kind = MessageType::try_from(reader.byte()?)?;
+        let _flags = reader.byte()?;
+        let version = reader.byte()?;
+        if version != 1 {
+            return Err(anyhow!("Unsupported D-Bus protocol version {version}"));
+        }
+        let body_len = reader.u32()? as usize;
+        let serial = reader.u32()?;
+
+        let mut message = Message {
+            kind: Some(kind),
+            serial,
+            ..Message::default()
+        };
+        let mut signature = String::new();
+        let Value::Array(_, fields) = reader.read("a(yv)")? else {
+            unreachable!("arrays are read as arrays");
+        };
+        for field in fields {
+            let Value::Struct(field) = field else {
+                continue;
+            };
+            let [Value::Byte(code), Value::Variant(value)] = &field[..] else {
+                continue;
+            };
+            let text = value.as_str().map(str::to_owned);
+            match *code {
+                field::PATH => message.path = text,
+                field::INTERFACE => message.interface = text,
+                field::MEMBER => message.member = text,
+                field::ERROR_NAME => message.error_name = text,
+                field::REPLY_SERIAL => message.reply_serial = value.as_u32(),
+                field::DESTINATION => message.destination = text,
+                field::SENDER => message.sender = text,
+                field::SIGNATURE => signature = text.unwrap_or_default(),
+                // Unknown fields must be ignored
+                _ => {}
+            }
+        }
+        reader.align(8)?;
+
+        let end = reader.pos + body_len;
+        if end != buf.len() {
+            return Err(anyhow!("D-Bus message length mismatch"));
+        }
+        // Alignment in the body is relative to the start of the message,
+        // which it keeps as the header is padded to 8 bytes.
+        let mut rest = signature.as_str();
+        while !rest.is_empty() {
+            let (ty, tail) = split_type(rest)?;
+            message.body.push(reader.read(ty)?);
+            rest = tail;
+        }
+        if reader.pos != end {
+            return Err(anyhow!("D-Bus message body does not match its signature"));
+        }
+
+        Ok(message)
+    }
+}
+
+/// Returns `true` for big-endian messages, given the first header byte.
+fn endianness(byte: u8) -> Result<bool> {
+    match byte {
+        b'l' => Ok(false),
+        b'B' => Ok(true),
+        _ => Err(anyhow!("Invalid D-Bus endianness flag: {byte:#x}")),
+    }
+}
+
+/// Returns the alignment of the values of the type starting with `code`.
+fn alignment(code: u8) -> usize {
+    match code {
+        b'n' | b'q' => 2,
+        b'b' | b'i' | b'u' | b'h' | b's' | b'o' | b'a' => 4,
+        b'x' | b't' | b'd' | b'(' | b'{' => 8,
+        _ => 1,
+    }
+}
+
+/// Splits the first complete type off the signature `sig`.
+fn split_type(sig: &str) -> Result<(&str, &str)> {
+    let bytes = sig.as_bytes();
+    let mut depth = 0usize;
+    for (i, &byte) in bytes.iter().enumerate() {
+        match byte {
+            b'a' => continue,
+            b'(' | b'{' => depth += 1,
+            b')' | b'}' => {
+                depth = depth
+                    .checked_sub(1)
+                    .ok_or_else(|| anyhow!("Invalid D-Bus signature: {sig}"))?;
+            }
+            _ => {}
+        }
+        if depth == 0 {
+            return Ok(sig.split_at(i + 1));
+        }
+    }
+    Err(anyhow!("Invalid D-Bus signature: {sig}"))
+}
+
+/// Serializes values in little-endian byte order.
+#[derive(Default)]
+struct Writer {
+    buf: Vec<u8>,
+}
+
+impl Writer {
+    /// Pads the buffer with zeroes to a multiple of `alignment`.
+    fn align(&mut self, alignment: usize) {
+        let len = self.buf.len().next_multiple_of(alignment);
+        self.buf.resize(len, 0);
+    }
+
+    fn write(&mut self, value: &Value) {
+        let signature = value.signature();
+        self.align(alignment(signature.as_bytes()[0]));
+        match value {
+            Value::Byte(v) => self.buf.push(*v),
+            Value::Bool(v) => self.buf.extend(u32::from(*v).to_le_bytes()),
+            Value::Int16(v) => self.buf.extend(v.to_le_bytes()),
+            Value::UInt16(v) => self.buf.extend(v.to_le_bytes()),
+            Value::Int32(v) => self.buf.extend(v.to_le_bytes()),
+            Value::UInt32(v) | Value::UnixFd(v) => self.buf.extend(v.to_le_bytes()),
+            Value::Int64(v) => self.buf.extend(v.to_le_bytes()),
+            Value::UInt64(v) => self.buf.extend(v.to_le_bytes()),
+            Value::Double(v) => self.buf.extend(v.to_le_bytes()),
+            Value::String(s) | Value::ObjectPath(s) => {
+                self.buf.extend((s.len() as u32).to_le_bytes());
+                self.buf.extend(s.as_bytes());
+                self.buf.push(0);
+            }
+            Value::Signature(s) => self.signature(s),
+            Value::Array(element, values) => {
+                let len_at = self.buf.len();
+                self.buf.extend(0u32.to_le_bytes());
+                // The padding before the first element is not counted
+                self.align(alignment(element.as_bytes()[0]));
+                let start = self.buf.len();
+                for value in values {
+                    self.write(value);
+                }
+                let len = (self.buf.len() - start) as u32;
+                self.buf[len_at..len_at + 4].copy_from_slice(&len.to_le_bytes());
+            }
+            Value::Struct(fields) => {
+                for field in fields {
+                    self.write(field);
+                }
+            }
+            Value::DictEntry(key, value) => {
+                self.write(key);
+                self.write(value);
+            }
+            Value::Variant(value) => {
+                self.signature(&value.signature());
+                self.write(value);
+            }
+        }
+    }
+
+    fn signature(&mut self, sig: &str) {
+        self.buf.push(sig.len() as u8);
+        self.buf.extend(sig.as_bytes());
+        self.buf.push(0);
+    }
+}
+
+/// Parses values from a message.
+struct Reader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+    big_endian: bool,
+}
+
+impl Reader<'_> {
+    /// Skips the padding up to a multiple of `alignment`.
+    fn align(&mut self, alignment: usize) -> Result<()> {
+        let pos = self.pos.next_multiple_of(alignment);
+        if pos > self.buf.len() {
+            return Err(anyhow!("D-Bus message truncated"));
+        }
+        self.pos = pos;
+        Ok(())
+    }
+
+    fn take<const N: usize>(&mut self) -> Result<[u8; N]> {
+        self.align(N)?;
+        let bytes = self
+            .buf
+            .get(self.pos..self.pos + N)
+            .ok_or_else(|| anyhow!("D-Bus message truncated"))?;
+        self.pos += N;
+        let mut bytes: [u8; N] = bytes.try_into().unwrap();
+        if self.big_endian {
+            bytes.reverse();
+        }
+        Ok(bytes)
+    }
+
+    fn byte(&mut self) -> Result<u8> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.take()?))
+    }
+
+    /// Reads `len` bytes followed by a nul byte as UTF-8.
+    fn text(&mut self, len: usize) -> Result<String> {
+        let bytes = self
+            .buf
+            .get(self.pos..self.pos + len + 1)
+            .ok_or_else(|| anyhow!("D-Bus message truncated"))?;
+        self.pos += len + 1;
+        let text = std::str::from_utf8(&bytes[..len]).context("Invalid D-Bus string")?;
+        Ok(text.to_owned())
+    }
+
+    /// Reads a value of the single complete type `ty`.
+    fn read(&mut self, ty: &str) -> Result<Value> {
+        let value = match ty.as_bytes()[0] {
+            b'y' => Value::Byte(self.byte()?),
+            b'b' => Value::Bool(self.u32()? != 0),
+            b'n' => Value::Int16(i16::from_le_bytes(self.take()?)),
+            b'q' => Value::UInt16(u16::from_le_bytes(self.take()?)),
+            b'i' => Value::Int32(i32::from_le_bytes(self.take()?)),
+            b'u' => Value::UInt32(self.u32()?),
+            b'h' => Value::UnixFd(self.u32()?),
+            b'x' => Value::Int64(i64::from_le_bytes(self.take()?)),
+            b't' => Value::UInt64(u64::from_le_bytes(self.take()?)),
+            b'd' => Value::Double(f64::from_le_bytes(self.take()?)),
+            b's' => {
+                let len = self.u32()? as usize;
+                Value::String(self.text(len)?)
+            }
+            b'o' => {
+                let len = self.u32()? as usize;
+                Value::ObjectPath(self.text(len)?)
+            }
+            b'g' => {
+                let len = self.byte()? as usize;
+                Value::Signature(self.text(len)?)
+            }
+            b'v' => {
+                let len = self.byte()? as usize;
+                let sig = self.text(len)?;
+                let (inner, rest) = split_type(&sig)?;
+                if !rest.is_empty() {
+                    return Err(anyhow!("Invalid D-Bus variant signature: {sig}"));
+                }
+                Value::Variant(Box::new(self.read(inner)?))
+            }
+            b'a' => {
+                let element = &ty[1..];
+                let len = self.u32()? as usize;
+                self.align(alignment(element.as_bytes()[0]))?;
+                let end = self.pos + len;
+                if end > self.buf.len() {
+                    return Err(anyhow!("D-Bus message truncated"));
+                }
+                let mut values = Vec::new();
+                while self.pos < end {
+                    values.push(self.read(element)?);
+                }
+                Value::Array(element.into(), values)
+            }
+            b'(' => {
+                self.align(8)?;
+                let mut rest = &ty[1..ty.len() - 1];
+                let mut fields = Vec::new();
+                while !rest.is_empty() {
+                    let (field, tail) = split_type(rest)?;
+                    fields.push(self.read(field)?);
+                    rest = tail;
+                }
+                Value::Struct(fields)
+            }
+            b'{' => {
+                self.align(8)?;
+                let (key, value) = split_type(&ty[1..ty.len() - 1])?;
+                Value::DictEntry(Box::new(self.read(key)?), Box::new(self.read(value)?))
+            }
+            code => return Err(anyhow!("Unsupported D-Bus type: {}", code as char)),
+        };
+        Ok(value)
+    }
+}
+
+/// The error returned when a D-Bus method call fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct MethodError {
+    /// The D-Bus error name, e.g. `org.freedesktop.DBus.Error.ServiceUnknown`.
+    pub(crate) name: String,
+    /// The message of the error, if any.
+    pub(crate) message: String,
+}
+
+impl std::fmt::Display for MethodError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.name, self.message)
+    }
+}
+
+impl std::error::Error for MethodError {}
+
+/// A connection to a message bus.
+pub(crate) struct Bus {
+    /// The socket to the bus.
+    stream: UnixStream,
+    /// The serial of the last message sent.
+    serial: u32,
+    /// The unique name the bus assigned to the connection.
+    unique_name: String,
+    /// The messages received while waiting for another one.
+    queue: VecDeque<Message>,
+}
+
+impl Bus {
+    /// Connects to the session bus given by `$DBUS_SESSION_BUS_ADDRESS`,
+    /// or by default the `bus` socket of `$XDG_RUNTIME_DIR`.
+    pub(crate) fn session() -> Result<Bus> {
+        let address = match std::env::var("DBUS_SESSION_BUS_ADDRESS") {
+            Ok(address) => address,
+            Err(_) => {
+                let runtime_dir = std::env::var("XDG_RUNTIME_DIR")
+                    .context("Neither DBUS_SESSION_BUS_ADDRESS nor XDG_RUNTIME_DIR is set")?;
+                format!("unix:path={runtime_dir}/bus")
+            }
+        };
+
+        let stream = connect(&address)?;
+        Bus::from_stream(stream)
+    }
+
+    /// Authenticates on `stream` and registers with the bus.
+    pub(crate) fn from_stream(mut stream: UnixStream) -> Result<Bus> {
+        let uid = std::fs::metadata("/proc/self")
+            .context("Failed to find the user ID")?
+            .uid();
+        let hex_uid: String = uid
+            .to_string()
+            .bytes()
+            .map(|b| format!("{b:02x}"))
+            .collect();
+        stream.write_all(format!("\0AUTH EXTERNAL {hex_uid}\r\n").as_bytes())?;
+
+        let reply = read_line(&mut stream)?;
+        if !reply.starts_with("OK ") {
+            return Err(anyhow!("D-Bus authentication failed: {reply}"));
+        }
+        stream.write_all(b"BEGIN\r\n")?;
+
+        let mut bus = Bus {
+            stream,
+            serial: 0,
+            unique_name: String::new(),
+            queue: VecDeque::new(),
+        };
+        let reply = bus.call(
+            Message::method_call(BUS_NAME, BUS_PATH, BUS_NAME, "Hello", Vec::new()),
+            None,
+        )?;
+        bus.unique_name = reply
+            .first()
+            .and_then(Value::as_str)
+            .ok_or_else(|| anyhow!("Invalid reply to D-Bus Hello"))?
+            .to_owned();
+
+        Ok(bus)
+    }
+
+    /// Returns the unique name the bus assigned to the connection, such as
+    /// `:1.42`.
+    pub(crate) fn unique_name(&self) -> &str {
+        &self.unique_name
+    }
+
+    /// Asks the bus to route the signals matching `rule` to the connection.
+    pub(crate) fn add_match(&mut self, rule: &str) -> Result<()> {
+        let body = vec![Value::String(rule.into())];
+        self.call(
+            Message::method_call(BUS_NAME, BUS_PATH, BUS_NAME, "AddMatch", body),
+            None,
+        )?;
+        Ok(())
+    }
+
+    /// Calls a method and waits for its reply, until `deadline` if set.
+    ///
+    /// # Returns
+    /// The arguments of the reply.
+    ///
+    /// # Errors
+    /// Returns a [`MethodError`] if the method failed, or an error if the
+    /// reply did not arrive in time or the connection failed.
+    pub(crate) fn call(
+        &mut self,
+        mut message: Message,
+        deadline: Option<Instant>,
+    ) -> Result<Vec<Value>> {
+        self.serial += 1;
+        message.serial = self.serial;
+        self.stream.write_all(&message.encode()?)?;
+
+        let serial = self.serial;
+        let reply = self.wait(deadline, |reply| reply.reply_serial == Some(serial))?;
+        match reply.kind {
+            Some(MessageType::Error) => Err(MethodError {
+                name: reply.error_name.unwrap_or_default(),
+                message: reply
+                    .body
+                    .first()
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_owned(),
+            }
+            .into()),
+            _ => Ok(reply.body),
+        }
+    }
+
+    /// Waits for the signal `interface.member` of the object `path`, until
+    /// `deadline` if set.
+    pub(crate) fn wait_signal(
+        &mut self,
+        path: &str,
+        interface: &str,
+        member: &str,
+        deadline: Option<Instant>,
+    ) -> Result<Message> {
+        self.wait(deadline, |message| {
+            message.is_signal(path, interface, member)
+        })
+    }
+
+    /// Returns the first message, queued or received, accepted by `filter`;
+    /// the others are queued.
+    fn wait(
+        &mut self,
+        deadline: Option<Instant>,
+        filter: impl Fn(&Message) -> bool,
+    ) -> Result<Message> {
+        if let Some(index) = self.queue.iter().position(&filter) {
+            return Ok(self.queue.remove(index).unwrap());
+        }
+
+        loop {
+            let message = self.receive(deadline)?;
+            if filter(&message) {
+                return Ok(message);
+            }
+            self.queue.push_back(message);
+        }
+    }
+
+    /// Receives the next message, until `deadline` if set.
+    fn receive(&mut self, deadline: Option<Instant>) -> Result<Message> {
+        let timeout = match deadline {
+            Some(deadline) => Some(
+                deadline
+                    .checked_duration_since(Instant::now())
+                    .filter(|timeout| !timeout.is_zero())
+                    .ok_or_else(|| anyhow!("Timed out waiting for a D-Bus message"))?,
+            ),
+            None => None,
+        };
+        self.stream.set_read_timeout(timeout)?;
+
+        let mut header = [0; 16];
+        self.stream.read_exact(&mut header).map_err(timeout_error)?;
+        let mut buf = vec![0; Message::length(&header)?];
+        buf[..16].copy_from_slice(&header);
+        self.stream
+            .read_exact(&mut buf[16..])
+            .map_err(timeout_error)?;
+
+        Message::decode(&buf)
+    }
+}
+
+/// Describes a read that timed out as such.
+fn timeout_error(error: io::Error) -> crate::error::Error {
+    match error.kind() {
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => {
+            anyhow!("Timed out waiting for a D-Bus message")
+        }
+        _ => error.into(),
+    }
+}
+
+/// Reads a line of the authentication protocol, without its `\r\n`.
+fn read_line(stream: &mut UnixStream) -> Result<String> {
+    let mut line = Vec::new();
+    let mut byte = [0];
+    while !line.ends_with(b"\r\n") {
+        if stream.read(&mut byte)? == 0 {
+            return Err(anyhow!("D-Bus connection closed during authentication"));
+        }
+        line.push(byte[0]);
+        if line.len() > 16 * 1024 {
+            return Err(anyhow!("D-Bus authentication line too long"));
+        }
+    }
+    line.truncate(line.len() - 2);
+    String::from_utf8(line).context("Invalid D-Bus authentication reply")
+}
+
+/// Connects to the first reachable Unix socket of a D-Bus server address,
+/// such as `unix:path=/run/user/1000/bus`.
+fn connect(address: &str) -> Result<UnixStream> {
+    let mut last_error = None;
+    for entry in address.split(';') {
+        let Some(params) = entry.strip_prefix("unix:") else {
+            continue;
+        };
+        for param in params.split(',') {
+            let result = match param.split_once('=') {
+                Some(("path", path)) => UnixStream::connect(unescape(path)?),
+                Some(("abstract", name)) => {
+                    SocketAddr::from_abstract_name(unescape(name)?.as_bytes())
+                        .and_then(|addr| UnixStream::connect_addr(&addr))
+                }
+                _ => continue,
+            };
+            match result {
+                Ok(stream) => return Ok(stream),
+                Err(error) => last_error = Some(error),
+            }
+        }
+    }
+
+    match last_error {
+        Some(error) => Err(error).context(format!("Failed to connect to the D-Bus at {address}")),
+        None => Err(anyhow!("No supported D-Bus address in {address}")),
+    }
+}
+
+/// Decodes the `%xx` escapes of a value of a D-Bus address.
+pub(crate) fn unescape(value: &str) -> Result<String> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = value
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .ok_or_else(|| anyhow!("Invalid escape in {value}"))?;
+            decoded.push(hex);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).context("Invalid escaped string")
+}
+
+/// Returns the instant `timeout` from now; `None` waits forever.
+pub(crate) fn deadline(timeout: Option<Duration>) -> Option<Instant> {
+    timeout.map(|timeout| Instant::now() + timeout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messages_survive_a_roundtrip() {
+        let message = Message {
+            serial: 7,
+            ..Message::method_call(
+                "org.freedesktop.portal.Desktop",
+                "/org/freedesktop/portal/desktop",
+                "org.freedesktop.portal.Screenshot",
+                "Screenshot",
+                vec![
+                    Value::String(String::new()),
+                    Value::dict(vec![
+                        ("handle_token", Value::String("t1".into())),
+                        ("interactive", Value::Bool(true)),
+                        ("scale", Value::Double(1.5)),
+                    ]),
+                ],
+            )
+        };
+
+        let bytes = message.encode().unwrap();
+        assert_eq!(bytes.len() % 8, 0);
+        assert_eq!(
+            Message::length(&bytes[..16].try_into().unwrap()).unwrap(),
+            bytes.len()
+        );
+
+        let decoded = Message::decode(&bytes).unwrap();
+        assert_eq!(decoded, message);
+        assert_eq!(
+            decoded.body[1].lookup("interactive"),
+            Some(&Value::Bool(true))
+        );
+        assert_eq!(decoded.body[1].lookup("missing"), None);
+    }
+
+    #[test]
+    fn signatures_split_into_complete_types() {
+        assert_eq!(split_type("ua{sv}").unwrap(), ("u", "a{sv}"));
+        assert_eq!(split_type("a{sv}").unwrap(), ("a{sv}", ""));
+        assert_eq!(split_type("(ya(ii))s").unwrap(), ("(ya(ii))", "s"));
+        assert!(split_type("a(i").is_err());
+        assert_eq!(
+            unescape("/run/user/1000/b%75s").unwrap(),
+            "/run/user/1000/bus"
+        );
+    }
+}
@@ -0,0 +1,346 @@
+//! A fallback through the `xdg-desktop-portal` D-Bus service for taking
+//! screenshots, for compositors that do not let clients capture outputs
+//! with Wayland protocols, such as GNOME's.
+//!
+//! [`CaptureBackend::select`] tells whether the compositor offers the
+//! `ext-image-copy-capture` protocols or the portal has to be used;
+//! [`screenshot`] asks the `org.freedesktop.portal.Screenshot` portal for a
+//! screenshot, which it saves to a file. The portal may show a dialog and
+//! asks the user for permission, so the call can take a while.
+//!
+//! Screencasts are not covered: the `ScreenCast` portal hands out PipeWire
+//! streams, which need a PipeWire client to read.
+
+mod dbus;
+
+use std::{
+    fmt::{self, Display},
+    path::PathBuf,
+    sync::atomic::{AtomicU32, Ordering},
+    time::Duration,
+};
+
+use crate::{
+    connection::Connection,
+    error::{Context, Result, anyhow},
+    protocol::WlObjectId,
+};
+
+use dbus::{Bus, Message, Value};
+
+/// The bus name and object path of the portal service.
+const PORTAL_NAME: &str = "org.freedesktop.portal.Desktop";
+const PORTAL_PATH: &str = "/org/freedesktop/portal/desktop";
+
+/// The interface of the objects reporting the outcome of portal calls.
+const REQUEST_INTERFACE: &str = "org.freedesktop.portal.Request";
+
+/// The interface of the screenshot portal.
+const SCREENSHOT_INTERFACE: &str = "org.freedesktop.portal.Screenshot";
+
+/// Tells apart the requests of the process, see [`request_token`].
+static NEXT_TOKEN: AtomicU32 = AtomicU32::new(0);
+
+/// How screenshots can be taken on a compositor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureBackend {
+    /// The compositor advertises the `ext_output_image_capture_source_manager_v1`
+    /// and `ext_image_copy_capture_manager_v1` globals, see
+    /// [`ImageCopyCaptureManager`](crate::protocol::image_copy_capture::ImageCopyCaptureManager).
+    ImageCopyCapture,
+    /// The compositor offers no capture protocol, so screenshots go through
+    /// the portal with [`screenshot`].
+    Portal,
+}
+
+impl CaptureBackend {
+    /// Picks the backend for the compositor of `conn`, preferring the
+    /// Wayland protocols, which need no user interaction.
+    ///
+    /// The globals are complete after the first
+    /// [`Connection::roundtrip`].
+    pub fn select(conn: &Connection) -> CaptureBackend {
+        let capabilities = conn.capabilities();
+        if capabilities.has(WlObjectId::ImageCopyCaptureManager)
+            && capabilities.has(WlObjectId::OutputImageCaptureSourceManager)
+        {
+            CaptureBackend::ImageCopyCapture
+        } else {
+            CaptureBackend::Portal
+        }
+    }
+}
+
+/// Options of [`screenshot`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScreenshotOptions {
+    /// Lets the user pick what to capture, e.g. a window or a region,
+    /// instead of capturing the whole screen.
+    pub interactive: bool,
+    /// Makes the dialog the portal may show modal.
+    pub modal: bool,
+    /// How long to wait for the portal, `None` to wait until the user
+    /// answers its dialog.
+    pub timeout: Option<Duration>,
+}
+
+impl Default for ScreenshotOptions {
+    fn default() -> Self {
+        ScreenshotOptions {
+            interactive: false,
+            modal: true,
+            timeout: None,
+        }
+    }
+}
+
+/// A screenshot taken by the portal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Screenshot {
+    /// The URI of the image, usually a `file://` URI of a PNG file.
+    pub uri: String,
+}
+
+impl Screenshot {
+    /// Returns the path of the image, if it is a local file.
+    pub fn path(&self) -> Option<PathBuf> {
+        let path = self.uri.strip_prefix("file://")?;
+        // File URIs escape the bytes that are not allowed in URIs as %xx.
+        dbus::unescape(path).ok().map(PathBuf::from)
+    }
+}
+
+/// The error returned when the user cancelled a portal request, e.g. by
+/// closing its dialog.
+///
+/// Recovered with `error.is::<PortalCancelled>()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PortalCancelled;
+
+impl Display for PortalCancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "The portal request was cancelled")
+    }
+}
+
+impl std::error::Error for PortalCancelled {}
+
+/// Takes a screenshot with the `org.freedesktop.portal.Screenshot` portal
+/// of the session bus.
+///
+/// # Errors
+/// Returns [`PortalCancelled`] if the user cancelled the request, or an
+/// error if the session bus or the portal is unavailable, the portal
+/// failed or did not answer within the timeout.
+pub fn screenshot(options: &ScreenshotOptions) -> Result<Screenshot> {
+    let mut bus = Bus::session().context("Failed to connect to the session bus")?;
+    screenshot_on(&mut bus, options)
+}
+
+/// Takes a screenshot through the portal reached on `bus`.
+fn screenshot_on(bus: &mut Bus, options: &ScreenshotOptions) -> Result<Screenshot> {
+    let token = request_token();
+    let parameters = Value::dict(vec![
+        ("handle_token", Value::String(token.clone())),
+        ("interactive", Value::Bool(options.interactive)),
+        ("modal", Value::Bool(options.modal)),
+    ]);
+    let results = request(
+        bus,
+        &token,
+        SCREENSHOT_INTERFACE,
+        "Screenshot",
+        vec![Value::String(String::new()), parameters],
+        options.timeout,
+    )?;
+
+    let uri = results
+        .lookup("uri")
+        .and_then(Value::as_str)
+        .ok_or_else(|| anyhow!("The screenshot portal returned no URI"))?;
+    Ok(Screenshot {
+        uri: uri.to_owned(),
+    })
+}
+
+/// Returns a new `handle_token`, which names the request object of a call.
+fn request_token() -> String {
+    let serial = NEXT_TOKEN.fetch_add(1, Ordering::Relaxed);
+    format!("wayland_from_scratch_{}_{serial}", std::process::id())
+}
+
+/// Calls the portal method `interface.method` and waits for the response
+/// carried by the `Response` signal of its request object.
+///
+/// # Returns
+/// The `a{sv}` results of a successful request.
+fn request(
+    bus: &mut Bus,
+    token: &str,
+    interface: &str,
+    method: &str,
+    arguments: Vec<Value>,
+    timeout: Option<Duration>,
+) -> Result<Value> {
+    let deadline = dbus::deadline(timeout);
+
+    // The response may come before the reply to the call, so the signal is
+    // subscribed to first, on the path the request object will have.
+    let sender = bus.unique_name().trim_start_matches(':').replace('.', "_");
+    let expected = format!("{PORTAL_PATH}/request/{sender}/{token}");
+    bus.add_match(&response_rule(&expected))?;
+
+    let message = Message::method_call(PORTAL_NAME, PORTAL_PATH, interface, method, arguments);
+    let reply = bus
+        .call(message, deadline)
+        .with_context(|| format!("Failed to call {interface}.{method}"))?;
+    let handle = reply
+        .first()
+        .and_then(Value::as_str)
+        .ok_or_else(|| anyhow!("Invalid reply to {interface}.{method}"))?
+        .to_owned();
+    // Portals older than version 0.9 pick the path themselves
+    if handle != expected {
+        bus.add_match(&response_rule(&handle))?;
+    }
+
+    let response = bus.wait_signal(&handle, REQUEST_INTERFACE, "Response", deadline)?;
+    match &response.body[..] {
+        [Value::UInt32(0), results] => Ok(results.clone()),
+        [Value::UInt32(1), ..] => Err(PortalCancelled.into()),
+        [Value::UInt32(code), ..] => Err(anyhow!(
+            "The portal failed to handle {interface}.{method} (response {code})"
+        )),
+        _ => Err(anyhow!("Invalid response to {interface}.{method}")),
+    }
+}
+
+/// Returns the match rule of the `Response` signal of the request object
+/// `path`.
+fn response_rule(path: &str) -> String {
+    format!("type='signal',interface='{REQUEST_INTERFACE}',member='Response',path='{path}'")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        os::unix::net::UnixStream,
+        thread,
+    };
+
+    use super::*;
+    use crate::protocol::{
+        registry::event::global::Global,
+        types::{WlString, WlUInt},
+    };
+
+    /// Reads a message sent to the fake bus.
+    fn read_message(stream: &mut UnixStream) -> Message {
+        let mut header = [0; 16];
+        stream.read_exact(&mut header).unwrap();
+        let body = u32::from_le_bytes(header[4..8].try_into().unwrap()) as usize;
+        let fields = u32::from_le_bytes(header[12..16].try_into().unwrap()) as usize;
+        let mut buf = vec![0; (16 + fields).next_multiple_of(8) + body];
+        buf[..16].copy_from_slice(&header);
+        stream.read_exact(&mut buf[16..]).unwrap();
+        Message::decode(&buf).unwrap()
+    }
+
+    /// Answers `call` with `body`.
+    fn reply(stream: &mut UnixStream, call: &Message, body: Vec<Value>) {
+        let reply = Message {
+            kind: Some(dbus::MessageType::MethodReturn),
+            serial: call.serial + 1000,
+            reply_serial: Some(call.serial),
+            body,
+            ..Message::default()
+        };
+        stream.write_all(&reply.encode().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn screenshot_waits_for_the_response_signal() {
+        let (client, mut server) = UnixStream::pair().unwrap();
+        let portal = thread::spawn(move || {
+            let mut auth = Vec::new();
+            let mut byte = [0];
+            while !auth.ends_with(b"\r\n") {
+                server.read_exact(&mut byte).unwrap();
+                auth.push(byte[0]);
+            }
+            assert!(auth.starts_with(b"\0AUTH EXTERNAL "));
+            server.write_all(b"OK 0123456789abcdef\r\n").unwrap();
+            let mut begin = [0; 7];
+            server.read_exact(&mut begin).unwrap();
+            assert_eq!(&begin, b"BEGIN\r\n");
+
+            let hello = read_message(&mut server);
+            assert_eq!(hello.member.as_deref(), Some("Hello"));
+            reply(&mut server, &hello, vec![Value::String(":1.42".into())]);
+
+            let add_match = read_message(&mut server);
+            assert_eq!(add_match.member.as_deref(), Some("AddMatch"));
+            reply(&mut server, &add_match, Vec::new());
+
+            let call = read_message(&mut server);
+            assert_eq!(call.member.as_deref(), Some("Screenshot"));
+            let token = call.body[1]
+                .lookup("handle_token")
+                .unwrap()
+                .as_str()
+                .unwrap();
+            let handle = format!("{PORTAL_PATH}/request/1_42/{token}");
+
+            // The response overtakes the reply, as it can with a real bus
+            let response = Message {
+                kind: Some(dbus::MessageType::Signal),
+                serial: 1,
+                path: Some(handle.clone()),
+                interface: Some(REQUEST_INTERFACE.into()),
+                member: Some("Response".into()),
+                body: vec![
+                    Value::UInt32(0),
+                    Value::dict(vec![(
+                        "uri",
+                        Value::String("file:///tmp/Screenshot%20from%20now.png".into()),
+                    )]),
+                ],
+                ..Message::default()
+            };
+            server.write_all(&response.encode().unwrap()).unwrap();
+            reply(&mut server, &call, vec![Value::ObjectPath(handle)]);
+        });
+
+        let mut bus = Bus::from_stream(client).unwrap();
+        let screenshot = screenshot_on(&mut bus, &ScreenshotOptions::default()).unwrap();
+        portal.join().unwrap();
+
+        assert_eq!(
+            screenshot.path(),
+            Some(PathBuf::from("/tmp/Screenshot from now.png"))
+        );
+    }
+
+    #[test]
+    fn portal_is_selected_without_capture_globals() {
+        let (client, _compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+        conn.insert_global(Global {
+            name: WlUInt(1),
+            interface: WlString::new("ext_image_copy_capture_manager_v1").unwrap(),
+            version: WlUInt(1),
+        });
+        assert_eq!(CaptureBackend::select(&conn), CaptureBackend::Portal);
+
+        conn.insert_global(Global {
+            name: WlUInt(2),
+            interface: WlString::new("ext_output_image_capture_source_manager_v1").unwrap(),
+            version: WlUInt(1),
+        });
+        assert_eq!(
+            CaptureBackend::select(&conn),
+            CaptureBackend::ImageCopyCapture
+        );
+    }
+}
@@ -0,0 +1,282 @@
+use std::{collections::VecDeque, sync::Arc, time::Duration};
+
+use crate::{
+    error::Result,
+    protocol::{
+        presentation::{
+            Presentation, PresentationFeedbackEvent,
+            event::presented::{PresentationFlags, Presented},
+        },
+        surface::Surface,
+    },
+    sync::Lock,
+};
+
+/// The number of presented frames the statistics cover by default.
+pub const DEFAULT_STATS_WINDOW: usize = 120;
+
+/// Aggregates `wp_presentation` feedback into frame timing statistics,
+/// for performance overlays and frame pacing diagnostics.
+///
+/// Call [`PresentationStats::track`] right before each commit of the
+/// surface; the statistics update as the compositor reports when each
+/// commit turned into light. Intervals, the detected refresh rate and the
+/// latency percentiles cover the last frames of the window, while the
+/// frame counters cover everything since the last reset.
+pub struct PresentationStats {
+    /// The presentation object feedback is requested from.
+    presentation: Presentation,
+    /// The samples, shared with the feedback handlers.
+    samples: Arc<Lock<Samples>>,
+}
+
+impl PresentationStats {
+    /// Creates statistics over the last [`DEFAULT_STATS_WINDOW`] frames.
+    pub fn new(presentation: &Presentation) -> PresentationStats {
+        PresentationStats::with_window(presentation, DEFAULT_STATS_WINDOW)
+    }
+
+    /// Creates statistics over the last `window` presented frames.
+    pub fn with_window(presentation: &Presentation, window: usize) -> PresentationStats {
+        PresentationStats {
+            presentation: presentation.clone(),
+            samples: Arc::new(Lock::new(Samples::new(window.max(1)))),
+        }
+    }
+
+    /// Requests feedback on the next commit of `surface`, measuring its
+    /// latency from now.
+    pub fn track(&self, surface: &Surface) -> Result<()> {
+        let submitted = self.presentation.now()?;
+        let samples = Arc::downgrade(&self.samples);
+
+        self.presentation.feedback(surface, move |_, event| {
+            let Some(samples) = samples.upgrade() else {
+                return;
+            };
+            match event {
+                PresentationFeedbackEvent::Presented(presented) => {
+                    samples.borrow_mut().record_presented(submitted, &presented);
+                }
+                PresentationFeedbackEvent::Discarded => samples.borrow_mut().discarded += 1,
+                PresentationFeedbackEvent::SyncOutput(_) => {}
+            }
+        })
+    }
+
+    /// Returns the intervals between consecutive presented frames of the
+    /// window, oldest first.
+    pub fn frame_intervals(&self) -> Vec<Duration> {
+        self.samples.borrow().intervals().collect()
+    }
+
+    /// Returns the average interval between presented frames of the window.
+    pub fn mean_frame_interval(&self) -> Option<Duration> {
+        let samples = self.samples.borrow();
+        let count = samples.intervals().count();
+        let total: Duration = samples.intervals().sum();
+        (count > 0).then(|| total / count as u32)
+    }
+
+    /// Returns the refresh interval of the output: the one predicted by the
+    /// compositor, or else the median frame interval of the window.
+    pub fn refresh_interval(&self) -> Option<Duration> {
+        let samples = self.samples.borrow();
+        samples.refresh.or_else(|| {
+            let mut intervals: Vec<Duration> = samples.intervals().collect();
+            intervals.sort_unstable();
+            intervals.get(intervals.len() / 2).copied()
+        })
+    }
+
+    /// Returns the refresh rate of the output in Hz, see
+    /// [`PresentationStats::refresh_interval`].
+    pub fn refresh_rate(&self) -> Option<f64> {
+        self.refresh_interval()
+            .filter(|interval| !interval.is_zero())
+            .map(|interval| 1.0 / interval.as_secs_f64())
+    }
+
+    /// Returns the number of frames presented since the last reset.
+    pub fn presented_frames(&self) -> u64 {
+        self.samples.borrow().presented
+    }
+
+    /// Returns the number of frames the compositor discarded since the
+    /// last reset, which were replaced by a later commit before being shown.
+    pub fn discarded_frames(&self) -> u64 {
+        self.samples.borrow().discarded
+    }
+
+    /// Returns the number of refresh cycles that presented no new frame
+    /// between two presented frames since the last reset.
+    ///
+    /// Cycles are counted with the vertical retrace counter when the
+    /// compositor synchronizes to it, and from the refresh interval otherwise.
+    pub fn missed_frames(&self) -> u64 {
+        self.samples.borrow().missed
+    }
+
+    /// Returns the latency from [`PresentationStats::track`] to the
+    /// presentation that `percentile` percent of the frames of the window
+    /// do not exceed, e.g. `50.0` for the median and `99.0` for the tail.
+    pub fn latency_percentile(&self, percentile: f64) -> Option<Duration> {
+        let mut latencies: Vec<Duration> = self
+            .samples
+            .borrow()
+            .frames
+            .iter()
+            .map(|frame| frame.latency)
+            .collect();
+        if latencies.is_empty() {
+            return None;
+        }
+        latencies.sort_unstable();
+
+        // Nearest-rank method
+        let rank = (percentile.clamp(0.0, 100.0) / 100.0 * latencies.len() as f64).ceil();
+        Some(latencies[(rank as usize).saturating_sub(1)])
+    }
+
+    /// Forgets every sample and counter.
+    pub fn reset(&self) {
+        let mut samples = self.samples.borrow_mut();
+        *samples = Samples::new(samples.window);
+    }
+}
+
+/// A presented frame.
+struct Frame {
+    /// The time the frame turned into light, on the presentation clock.
+    timestamp: Duration,
+    /// The vertical retrace counter, if the presentation was synchronized to it.
+    sequence: Option<u64>,
+    /// The time from the submission to the presentation.
+    latency: Duration,
+}
+
+/// The samples and counters of [`PresentationStats`].
+struct Samples {
+    /// The number of frames kept.
+    window: usize,
+    /// The last presented frames, oldest first.
+    frames: VecDeque<Frame>,
+    /// The refresh interval last predicted by the compositor.
+    refresh: Option<Duration>,
+    /// The number of presented frames.
+    presented: u64,
+    /// The number of discarded frames.
+    discarded: u64,
+    /// The number of refresh cycles without a new frame.
+    missed: u64,
+}
+
+impl Samples {
+    /// Creates empty samples keeping `window` frames.
+    fn new(window: usize) -> Samples {
+        Samples {
+            window,
+            frames: VecDeque::with_capacity(window),
+            refresh: None,
+            presented: 0,
+            discarded: 0,
+            missed: 0,
+        }
+    }
+
+    /// Returns the intervals between consecutive frames, oldest first.
+    fn intervals(&self) -> impl Iterator<Item = Duration> + '_ {
+        self.frames
+            .iter()
+            .zip(self.frames.iter().skip(1))
+            .map(|(previous, frame)| frame.timestamp.saturating_sub(previous.timestamp))
+    }
+
+    /// Records a frame submitted at `submitted` and then presented.
+    fn record_presented(&mut self, submitted: Duration, presented: &Presented) {
+        if let Some(refresh) = presented.refresh() {
+            self.refresh = Some(refresh);
+        }
+
+        let frame = Frame {
+            timestamp: presented.timestamp(),
+            sequence: presented
+                .flags()
+                .contains(PresentationFlags::VSYNC)
+                .then(|| presented.sequence()),
+            latency: presented.timestamp().saturating_sub(submitted),
+        };
+
+        if let Some(previous) = self.frames.back() {
+            self.missed += match (previous.sequence, frame.sequence) {
+                (Some(previous), Some(sequence)) => sequence.saturating_sub(previous + 1),
+                _ => self.refresh.map_or(0, |refresh| {
+                    let interval = frame.timestamp.saturating_sub(previous.timestamp);
+                    let cycles = (interval.as_secs_f64() / refresh.as_secs_f64()).round();
+                    (cycles as u64).saturating_sub(1)
+                }),
+            };
+        }
+
+        self.presented += 1;
+        if self.frames.len() == self.window {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(frame);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::types::WlUInt;
+
+    /// The refresh interval of a 60 Hz output, in nanoseconds.
+    const REFRESH_NS: u32 = 16_666_667;
+
+    /// Builds a presented event at `sequence` refresh cycles of 60 Hz.
+    fn presented(sequence: u32, flags: PresentationFlags, refresh: u32) -> Presented {
+        let nanos = u64::from(sequence) * u64::from(REFRESH_NS);
+        Presented {
+            tv_sec_hi: WlUInt(0),
+            tv_sec_lo: WlUInt((nanos / 1_000_000_000) as u32),
+            tv_nsec: WlUInt((nanos % 1_000_000_000) as u32),
+            refresh: WlUInt(refresh),
+            seq_hi: WlUInt(0),
+            seq_lo: WlUInt(sequence),
+            flags: WlUInt(flags.bits()),
+        }
+    }
+
+    #[test]
+    fn missed_frames_use_the_retrace_counter_or_the_refresh() {
+        let mut samples = Samples::new(DEFAULT_STATS_WINDOW);
+        for sequence in [1, 2, 4, 5] {
+            let event = presented(sequence, PresentationFlags::VSYNC, REFRESH_NS);
+            samples.record_presented(event.timestamp() - Duration::from_millis(5), &event);
+        }
+        assert_eq!(samples.missed, 1);
+
+        // Without vsync, the gap is measured in predicted refresh cycles
+        let event = presented(8, PresentationFlags::empty(), REFRESH_NS);
+        samples.record_presented(event.timestamp(), &event);
+        assert_eq!((samples.presented, samples.missed), (5, 3));
+    }
+
+    #[test]
+    fn window_keeps_the_last_frames() {
+        let mut samples = Samples::new(3);
+        for sequence in 1..=10 {
+            let event = presented(sequence, PresentationFlags::VSYNC, 0);
+            samples.record_presented(event.timestamp(), &event);
+        }
+
+        assert_eq!(samples.frames.len(), 3);
+        assert_eq!(samples.refresh, None);
+        assert!(
+            samples
+                .intervals()
+                .all(|interval| interval.as_nanos() == u128::from(REFRESH_NS))
+        );
+    }
+}
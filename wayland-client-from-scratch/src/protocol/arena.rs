@@ -0,0 +1,123 @@
+use std::{
+    fmt::{self, Debug, Formatter},
+    ops::{Deref, Range},
+    sync::Arc,
+};
+
+/// The arguments of a received event, borrowed from the [`EventArena`] chunk
+/// its batch was decoded into.
+///
+/// Cloning only bumps a reference count. The chunk lives as long as any
+/// event of the batch does, e.g. one held back by
+/// [`UnknownObjectPolicy::Queue`](crate::connection::UnknownObjectPolicy::Queue).
+#[derive(Clone)]
+pub struct EventBytes {
+    /// The bytes of the whole batch.
+    chunk: Arc<Vec<u8>>,
+    /// The position of the arguments in the chunk.
+    range: Range<usize>,
+}
+
+impl EventBytes {
+    /// Wraps bytes decoded outside of an arena, such as test events or
+    /// replayed ones, in a chunk of their own.
+    pub(crate) fn copy_from(bytes: &[u8]) -> EventBytes {
+        EventBytes {
+            chunk: Arc::new(bytes.to_vec()),
+            range: 0..bytes.len(),
+        }
+    }
+}
+
+impl Deref for EventBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.chunk[self.range.clone()]
+    }
+}
+
+impl AsRef<[u8]> for EventBytes {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl Debug for EventBytes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+/// The buffer the events of a dispatch batch are decoded into.
+///
+/// Every complete message read from the socket is copied once into a single
+/// chunk, and each event only keeps a range of it, so a batch costs one
+/// allocation at most instead of one per event. The chunk is reset and
+/// reused by the next batch once all events of the previous one have been
+/// dropped, which for continuous pointer motion means the steady state does
+/// not allocate at all.
+#[derive(Default)]
+pub(crate) struct EventArena {
+    /// The chunk of the current batch.
+    chunk: Arc<Vec<u8>>,
+}
+
+impl EventArena {
+    /// Replaces the contents of the arena with the bytes of a new batch and
+    /// returns the chunk holding them.
+    ///
+    /// The previous chunk is reused if no event points into it anymore;
+    /// otherwise it is left to those events and a new one is allocated.
+    pub(crate) fn reset(&mut self, bytes: &[u8]) -> Arc<Vec<u8>> {
+        match Arc::get_mut(&mut self.chunk) {
+            Some(chunk) => {
+                chunk.clear();
+                chunk.extend_from_slice(bytes);
+            }
+            None => {
+                let mut chunk = Vec::with_capacity(bytes.len().max(self.chunk.capacity()));
+                chunk.extend_from_slice(bytes);
+                self.chunk = Arc::new(chunk);
+            }
+        }
+
+        self.chunk.clone()
+    }
+
+    /// Returns the bytes of `range` in `chunk`, as returned by
+    /// [`EventArena::reset`].
+    pub(crate) fn slice(chunk: &Arc<Vec<u8>>, range: Range<usize>) -> EventBytes {
+        EventBytes {
+            chunk: chunk.clone(),
+            range,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunk_is_reused_once_the_batch_is_dropped() {
+        let mut arena = EventArena::default();
+
+        let chunk = arena.reset(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        let first = EventArena::slice(&chunk, 4..8);
+        drop(chunk);
+        let address = first.chunk.as_ptr();
+
+        // An event of the previous batch is alive, so its bytes stay intact
+        let chunk = arena.reset(&[9; 8]);
+        assert_eq!(*first, [5, 6, 7, 8]);
+        assert_ne!(chunk.as_ptr(), address);
+        drop(chunk);
+
+        let address = arena.chunk.as_ptr();
+        drop(first);
+        let chunk = arena.reset(&[10; 4]);
+        assert_eq!(chunk.as_ptr(), address);
+        assert_eq!(*EventArena::slice(&chunk, 0..4), [10; 4]);
+    }
+}
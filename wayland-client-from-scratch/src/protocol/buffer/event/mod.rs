@@ -0,0 +1,72 @@
+pub mod release;
+
+use crate::{
+    connection::Connection,
+    error::{Error, Result, anyhow},
+    protocol::message::WlMessage,
+};
+
+/// Represents the event types that can be emitted by a Wayland buffer object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    /// The compositor no longer uses the buffer.
+    Release = 0,
+}
+
+impl TryFrom<u16> for Opcode {
+    type Error = Error;
+
+    /// Attempts to convert a raw opcode value into a structured `WlBufferEvent`.
+    fn try_from(value: u16) -> Result<Opcode> {
+        match value {
+            0 => Ok(Opcode::Release),
+            _ => Err(anyhow!("Invalid wl_buffer event opcode: {}", value)),
+        }
+    }
+}
+
+impl Opcode {
+    /// Returns the interface version the event was introduced in.
+    pub fn since(self) -> u32 {
+        match self {
+            Opcode::Release => 1,
+        }
+    }
+}
+
+/// A decoded `wl_buffer` event, with its arguments.
+#[derive(Debug)]
+pub enum Event {
+    /// The compositor no longer uses the buffer.
+    Release,
+}
+
+impl Event {
+    /// Decodes a `wl_buffer` event from its message.
+    ///
+    /// # Errors
+    /// Returns an error if the opcode is unknown or the arguments are malformed.
+    pub fn decode(_conn: &Connection, msg: &WlMessage) -> Result<Event> {
+        Ok(match Opcode::try_from(msg.header.opcode)? {
+            Opcode::Release => Event::Release,
+        })
+    }
+
+    /// Returns the opcode of the event.
+    pub fn opcode(&self) -> Opcode {
+        match self {
+            Event::Release => Opcode::Release,
+        }
+    }
+}
+
+/// Dispatches incoming Wayland buffer events to their appropriate handler functions.
+///
+/// # Event Routing
+/// * `Release` events are routed to `release::handle_wl_buffer_release`
+pub fn handle_wl_buffer_event(conn: &Connection, msg: WlMessage) -> Result<()> {
+    // Route the event to the appropriate handler based on type
+    match Event::decode(conn, &msg)? {
+        Event::Release => release::handle_wl_buffer_release(conn, msg.header.object_id),
+    }
+}
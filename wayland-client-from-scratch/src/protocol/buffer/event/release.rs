@@ -0,0 +1,25 @@
+use crate::{connection::Connection, error::Result, protocol::buffer::request};
+
+/// Handles a `wl_buffer.release` event.
+///
+/// The compositor no longer reads from the buffer, so the client may reuse or
+/// destroy it. If the buffer handle was dropped while the buffer was busy, the
+/// postponed `wl_buffer.destroy` request is sent now.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="release">
+///   <description summary="compositor releases buffer">
+///     Sent when this wl_buffer is no longer used by the compositor.
+///     The client is now free to reuse or destroy this buffer and its
+///     backing storage.
+///   </description>
+/// </event>
+/// ```
+pub(super) fn handle_wl_buffer_release(conn: &Connection, buffer_id: u32) -> Result<()> {
+    if conn.release_buffer(buffer_id) {
+        request::destroy(conn, buffer_id)?;
+    }
+
+    Ok(())
+}
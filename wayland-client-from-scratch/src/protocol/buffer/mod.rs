@@ -0,0 +1,86 @@
+pub mod event;
+pub mod request;
+
+use std::sync::Arc;
+
+use crate::{connection::Connection, sync::LockCell};
+
+/// A `wl_buffer`, the content that can be attached to a surface.
+///
+/// A buffer is busy from the moment a surface commit carrying it is sent
+/// until the compositor sends `wl_buffer.release`; its memory must not be
+/// written to in between. A buffer attached but replaced before the commit
+/// never reaches the compositor and stays free.
+///
+/// Dropping the handle destroys the buffer. If the compositor is still using
+/// it, `wl_buffer.destroy` is postponed until the buffer is released, so the
+/// compositor never loses the content it is displaying.
+pub struct Buffer {
+    /// The object ID of the buffer.
+    id: u32,
+    /// The connection the buffer was created on.
+    conn: Connection,
+    /// The byte offset of the buffer within its pool.
+    offset: usize,
+    /// The number of pool bytes covered by the buffer.
+    len: usize,
+    /// Whether the compositor may currently read from the buffer.
+    busy: Arc<LockCell<bool>>,
+}
+
+impl Buffer {
+    /// Wraps a buffer object that was just created on `conn`.
+    pub(crate) fn new(id: u32, conn: &Connection, offset: usize, len: usize) -> Buffer {
+        let busy = Arc::new(LockCell::new(false));
+        conn.track_buffer(id, busy.clone());
+
+        Buffer {
+            id,
+            conn: conn.clone(),
+            offset,
+            len,
+            busy,
+        }
+    }
+
+    /// Returns the connection the buffer was created on.
+    pub(crate) fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    /// Returns the object ID of the buffer.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the byte offset of the buffer within its pool.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the number of pool bytes covered by the buffer.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the buffer covers no memory.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `true` while the compositor may read from the buffer.
+    pub fn is_busy(&self) -> bool {
+        self.busy.get()
+    }
+}
+
+impl Drop for Buffer {
+    fn drop(&mut self) {
+        if self.is_busy() {
+            self.conn.destroy_buffer_on_release(self.id);
+        } else {
+            self.conn
+                .destroy_on_drop(|conn| request::destroy(conn, self.id));
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use crate::{connection::Connection, error::Result, wl_request_opcode};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to a Wayland buffer object.
+    Opcode {
+        /// Destroys the buffer and invalidates its object ID.
+        Destroy = 0 (destructor),
+    }
+}
+
+/// Sends a `wl_buffer.destroy` request to the compositor.
+pub fn destroy(conn: &Connection, buffer: u32) -> Result<()> {
+    conn.send_request(buffer, Opcode::Destroy, &())?;
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result, anyhow},
+    protocol::types::{WL_TYPE_UINT_LEN, WlUInt},
+};
+
+/// Represents a `wl_callback.done` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="done" type="destructor">
+///   <description summary="done event">
+///     Notify the client when the related request is done.
+///   </description>
+///   <arg name="callback_data" type="uint" summary="request-specific data for the callback"/>
+/// </event>
+/// ```
+#[derive(Debug)]
+pub struct Done {
+    /// Request-specific data, the current time in milliseconds for frame callbacks.
+    pub callback_data: WlUInt,
+}
+
+impl TryFrom<&[u8]> for Done {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Done` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `callback_data` (u32) - Request-specific data
+    fn try_from(buf: &[u8]) -> Result<Done> {
+        if buf.len() < WL_TYPE_UINT_LEN {
+            return Err(anyhow!(
+                "Buffer too short for WlCallbackDone callback_data: expected {} bytes, got {}",
+                WL_TYPE_UINT_LEN,
+                buf.len()
+            ));
+        }
+        let callback_data = WlUInt::from_bytes(buf[..WL_TYPE_UINT_LEN].try_into()?);
+
+        Ok(Done { callback_data })
+    }
+}
+
+/// Handles a `wl_callback.done` event.
+///
+/// Runs the closure registered for the callback object, if any. The closure is
+/// removed before it is invoked, so it may freely issue new requests, including
+/// registering another callback. The compositor destroys the callback object
+/// itself and confirms with `wl_display.delete_id`.
+pub(super) fn handle_wl_callback_done(
+    conn: &Connection,
+    callback_id: u32,
+    done: Done,
+) -> Result<()> {
+    if let Some(callback) = conn.take_callback(callback_id) {
+        callback(conn, done.callback_data.get());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+pub mod done;
+
+use crate::{
+    connection::Connection,
+    error::{Error, Result, anyhow},
+    protocol::message::WlMessage,
+};
+
+/// Represents the event types that can be emitted by a Wayland callback object.
+///
+/// A callback object is a single-shot notification: it is created by requests
+/// such as `wl_display.sync` or `wl_surface.frame`, fires exactly once and is
+/// destroyed by the compositor right after.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    /// Notifies the client that the related request is done.
+    ///
+    /// # Event Arguments
+    /// - `callback_data`: Request-specific data, e.g. a timestamp in milliseconds
+    Done = 0,
+}
+
+impl TryFrom<u16> for Opcode {
+    type Error = Error;
+
+    /// Attempts to convert a raw opcode value into a structured `WlCallbackEvent`.
+    fn try_from(value: u16) -> Result<Opcode> {
+        match value {
+            0 => Ok(Opcode::Done),
+            _ => Err(anyhow!("Invalid wl_callback event opcode: {}", value)),
+        }
+    }
+}
+
+impl Opcode {
+    /// Returns the interface version the event was introduced in.
+    pub fn since(self) -> u32 {
+        match self {
+            Opcode::Done => 1,
+        }
+    }
+}
+
+/// A decoded `wl_callback` event, with its arguments.
+#[derive(Debug)]
+pub enum Event {
+    /// Notifies the client that the related request is done.
+    Done(done::Done),
+}
+
+impl Event {
+    /// Decodes a `wl_callback` event from its message.
+    ///
+    /// # Errors
+    /// Returns an error if the opcode is unknown or the arguments are malformed.
+    pub fn decode(_conn: &Connection, msg: &WlMessage) -> Result<Event> {
+        let buf: &[u8] = &msg.data;
+
+        Ok(match Opcode::try_from(msg.header.opcode)? {
+            Opcode::Done => Event::Done(done::Done::try_from(buf)?),
+        })
+    }
+
+    /// Returns the opcode of the event.
+    pub fn opcode(&self) -> Opcode {
+        match self {
+            Event::Done(_) => Opcode::Done,
+        }
+    }
+}
+
+/// Dispatches incoming Wayland callback events to their appropriate handler functions.
+///
+/// # Arguments
+/// * `conn` - The connection the event was received on
+/// * `msg` - The complete Wayland message containing both header and payload data
+///
+/// # Event Routing
+/// * `Done` events are routed to `done::handle_wl_callback_done`
+pub fn handle_wl_callback_event(conn: &Connection, msg: WlMessage) -> Result<()> {
+    // Route the event to the appropriate handler based on type
+    match Event::decode(conn, &msg)? {
+        Event::Done(event) => done::handle_wl_callback_done(conn, msg.header.object_id, event),
+    }
+}
@@ -0,0 +1,93 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        color_management::{ImageDescriptionEvent, ImageDescriptionState, ImageDescriptionStatus},
+        types::{WL_TYPE_UINT_LEN, WlString, WlUInt},
+    },
+    wl_enum,
+};
+
+wl_enum! {
+    /// Why the compositor could not create an image description.
+    FailureCause {
+        /// The interface version is too low for the description.
+        LowVersion = 0,
+        /// The description is not supported by the compositor.
+        Unsupported = 1,
+        /// An error of the operating system, such as a failed file read.
+        OperatingSystem = 2,
+        /// The output the description was requested for is gone.
+        NoOutput = 3,
+    }
+}
+
+/// Represents a `wp_image_description_v1.failed` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="failed">
+///   <description summary="graceful error on creating the image description">
+///     If creating a wp_image_description_v1 object fails for a reason that is
+///     not defined as a protocol error, this event is sent.
+///
+///     The requests that create image description objects define whether and
+///     when this can occur. Only such creation requests can trigger this event.
+///     This event cannot be triggered after the image description was
+///     successfully formed.
+///
+///     Once this event has been sent, the wp_image_description_v1 object will
+///     never become ready and it can only be destroyed.
+///   </description>
+///   <arg name="cause" type="uint" enum="cause"
+///        summary="generic reason"/>
+///   <arg name="msg" type="string"
+///        summary="ad hoc human-readable explanation"/>
+/// </event>
+/// ```
+#[derive(Debug)]
+pub struct Failed {
+    /// Why the creation failed, as a `FailureCause` value.
+    pub cause: WlUInt,
+    /// A human-readable explanation.
+    pub msg: WlString,
+}
+
+impl TryFrom<&[u8]> for Failed {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Failed` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `cause` (u32) - Why the creation failed
+    /// - Bytes 4+: `msg` (WlString) - The explanation with length prefix
+    fn try_from(buf: &[u8]) -> Result<Failed> {
+        Ok(Failed {
+            cause: WlUInt::read(buf, 0)?,
+            msg: WlString::try_from(buf.get(WL_TYPE_UINT_LEN..).unwrap_or_default())?,
+        })
+    }
+}
+
+/// Handles a `wp_image_description_v1.failed` event by recording the
+/// failure and notifying the listener.
+///
+/// Causes unknown to this crate are reported as [`FailureCause::Unsupported`].
+pub(super) fn handle_wp_image_description_failed(
+    conn: &Connection,
+    image_description_id: u32,
+    event: Failed,
+) -> Result<()> {
+    let cause = FailureCause::try_from(event.cause.get()).unwrap_or(FailureCause::Unsupported);
+    let message = conn.decode_string(&event.msg)?;
+
+    if let Some(state) = conn.object_data::<ImageDescriptionState>(image_description_id) {
+        state.status.replace(ImageDescriptionStatus::Failed(cause));
+    }
+    conn.emit(
+        image_description_id,
+        ImageDescriptionEvent::Failed { cause, message },
+    );
+
+    Ok(())
+}
@@ -0,0 +1,347 @@
+pub mod failed;
+pub mod preferred_changed;
+pub mod ready;
+pub mod supported_feature;
+pub mod supported_intent;
+pub mod supported_primaries_named;
+pub mod supported_tf_named;
+
+use crate::{
+    connection::Connection,
+    error::{Error, Result, anyhow},
+    protocol::{color_management::ColorManagerState, message::WlMessage},
+};
+
+/// Represents the event types that can be emitted by a `wp_color_manager_v1` object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    /// Announces a supported rendering intent.
+    ///
+    /// # Event Arguments
+    /// - `render_intent`: The rendering intent
+    SupportedIntent = 0,
+
+    /// Announces a supported feature.
+    ///
+    /// # Event Arguments
+    /// - `feature`: The feature
+    SupportedFeature = 1,
+
+    /// Announces a supported named transfer function.
+    ///
+    /// # Event Arguments
+    /// - `tf`: The transfer function
+    SupportedTfNamed = 2,
+
+    /// Announces supported named primaries.
+    ///
+    /// # Event Arguments
+    /// - `primaries`: The primaries
+    SupportedPrimariesNamed = 3,
+
+    /// Ends the list of supported values.
+    Done = 4,
+}
+
+impl TryFrom<u16> for Opcode {
+    type Error = Error;
+
+    /// Attempts to convert a raw opcode value into a structured `WpColorManagerEvent`.
+    fn try_from(value: u16) -> Result<Opcode> {
+        match value {
+            0 => Ok(Opcode::SupportedIntent),
+            1 => Ok(Opcode::SupportedFeature),
+            2 => Ok(Opcode::SupportedTfNamed),
+            3 => Ok(Opcode::SupportedPrimariesNamed),
+            4 => Ok(Opcode::Done),
+            _ => Err(anyhow!(
+                "Invalid wp_color_manager_v1 event opcode: {}",
+                value
+            )),
+        }
+    }
+}
+
+impl Opcode {
+    /// Returns the interface version the event was introduced in.
+    pub fn since(self) -> u32 {
+        match self {
+            Opcode::SupportedIntent
+            | Opcode::SupportedFeature
+            | Opcode::SupportedTfNamed
+            | Opcode::SupportedPrimariesNamed
+            | Opcode::Done => 1,
+        }
+    }
+}
+
+/// Represents the event types that can be emitted by a
+/// `wp_color_management_surface_feedback_v1` object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedbackOpcode {
+    /// Announces that the preferred image description of the surface changed.
+    ///
+    /// # Event Arguments
+    /// - `identity`: The identity of the new preferred image description
+    PreferredChanged = 0,
+}
+
+impl TryFrom<u16> for FeedbackOpcode {
+    type Error = Error;
+
+    /// Attempts to convert a raw opcode value into a structured
+    /// `WpColorManagementSurfaceFeedbackEvent`.
+    fn try_from(value: u16) -> Result<FeedbackOpcode> {
+        match value {
+            0 => Ok(FeedbackOpcode::PreferredChanged),
+            _ => Err(anyhow!(
+                "Invalid wp_color_management_surface_feedback_v1 event opcode: {}",
+                value
+            )),
+        }
+    }
+}
+
+impl FeedbackOpcode {
+    /// Returns the interface version the event was introduced in.
+    pub fn since(self) -> u32 {
+        match self {
+            FeedbackOpcode::PreferredChanged => 1,
+        }
+    }
+}
+
+/// Represents the event types that can be emitted by a `wp_image_description_v1` object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageDescriptionOpcode {
+    /// Announces that the image description could not be created.
+    ///
+    /// # Event Arguments
+    /// - `cause`: Why the creation failed
+    /// - `msg`: A human-readable explanation
+    Failed = 0,
+
+    /// Announces that the image description is ready to be used.
+    ///
+    /// # Event Arguments
+    /// - `identity`: The identity of the image description record
+    Ready = 1,
+}
+
+impl TryFrom<u16> for ImageDescriptionOpcode {
+    type Error = Error;
+
+    /// Attempts to convert a raw opcode value into a structured `WpImageDescriptionEvent`.
+    fn try_from(value: u16) -> Result<ImageDescriptionOpcode> {
+        match value {
+            0 => Ok(ImageDescriptionOpcode::Failed),
+            1 => Ok(ImageDescriptionOpcode::Ready),
+            _ => Err(anyhow!(
+                "Invalid wp_image_description_v1 event opcode: {}",
+                value
+            )),
+        }
+    }
+}
+
+impl ImageDescriptionOpcode {
+    /// Returns the interface version the event was introduced in.
+    pub fn since(self) -> u32 {
+        match self {
+            ImageDescriptionOpcode::Failed | ImageDescriptionOpcode::Ready => 1,
+        }
+    }
+}
+
+/// A decoded `wp_color_manager_v1` event, with its arguments.
+#[derive(Debug)]
+pub enum Event {
+    /// Announces a supported rendering intent.
+    SupportedIntent(supported_intent::SupportedIntent),
+    /// Announces a supported feature.
+    SupportedFeature(supported_feature::SupportedFeature),
+    /// Announces a supported named transfer function.
+    SupportedTfNamed(supported_tf_named::SupportedTfNamed),
+    /// Announces supported named primaries.
+    SupportedPrimariesNamed(supported_primaries_named::SupportedPrimariesNamed),
+    /// Ends the list of supported values.
+    Done,
+}
+
+impl Event {
+    /// Decodes a `wp_color_manager_v1` event from its message.
+    ///
+    /// # Errors
+    /// Returns an error if the opcode is unknown or the arguments are malformed.
+    pub fn decode(_conn: &Connection, msg: &WlMessage) -> Result<Event> {
+        let buf: &[u8] = &msg.data;
+
+        Ok(match Opcode::try_from(msg.header.opcode)? {
+            Opcode::SupportedIntent => {
+                Event::SupportedIntent(supported_intent::SupportedIntent::try_from(buf)?)
+            }
+            Opcode::SupportedFeature => {
+                Event::SupportedFeature(supported_feature::SupportedFeature::try_from(buf)?)
+            }
+            Opcode::SupportedTfNamed => {
+                Event::SupportedTfNamed(supported_tf_named::SupportedTfNamed::try_from(buf)?)
+            }
+            Opcode::SupportedPrimariesNamed => Event::SupportedPrimariesNamed(
+                supported_primaries_named::SupportedPrimariesNamed::try_from(buf)?,
+            ),
+            Opcode::Done => Event::Done,
+        })
+    }
+
+    /// Returns the opcode of the event.
+    pub fn opcode(&self) -> Opcode {
+        match self {
+            Event::SupportedIntent(_) => Opcode::SupportedIntent,
+            Event::SupportedFeature(_) => Opcode::SupportedFeature,
+            Event::SupportedTfNamed(_) => Opcode::SupportedTfNamed,
+            Event::SupportedPrimariesNamed(_) => Opcode::SupportedPrimariesNamed,
+            Event::Done => Opcode::Done,
+        }
+    }
+}
+
+/// Dispatches incoming `wp_color_manager_v1` events to their appropriate handler functions.
+///
+/// # Event Routing
+/// * `SupportedIntent` events are routed to `supported_intent::handle_wp_color_manager_supported_intent`
+/// * `SupportedFeature` events are routed to `supported_feature::handle_wp_color_manager_supported_feature`
+/// * `SupportedTfNamed` events are routed to `supported_tf_named::handle_wp_color_manager_supported_tf_named`
+/// * `SupportedPrimariesNamed` events are routed to `supported_primaries_named::handle_wp_color_manager_supported_primaries_named`
+/// * `Done` events apply the supported values through `ColorManagerState::done`
+pub fn handle_wp_color_manager_event(conn: &Connection, msg: WlMessage) -> Result<()> {
+    let manager_id = msg.header.object_id;
+
+    // Route the event to the appropriate handler based on type
+    match Event::decode(conn, &msg)? {
+        Event::SupportedIntent(event) => {
+            supported_intent::handle_wp_color_manager_supported_intent(conn, manager_id, event)
+        }
+        Event::SupportedFeature(event) => {
+            supported_feature::handle_wp_color_manager_supported_feature(conn, manager_id, event)
+        }
+        Event::SupportedTfNamed(event) => {
+            supported_tf_named::handle_wp_color_manager_supported_tf_named(conn, manager_id, event)
+        }
+        Event::SupportedPrimariesNamed(event) => {
+            supported_primaries_named::handle_wp_color_manager_supported_primaries_named(
+                conn, manager_id, event,
+            )
+        }
+        Event::Done => {
+            if let Some(state) = conn.object_data::<ColorManagerState>(manager_id) {
+                state.done();
+            }
+            Ok(())
+        }
+    }
+}
+
+/// A decoded `wp_color_management_surface_feedback_v1` event, with its arguments.
+#[derive(Debug)]
+pub enum FeedbackEvent {
+    /// Announces that the preferred image description of the surface changed.
+    PreferredChanged(preferred_changed::PreferredChanged),
+}
+
+impl FeedbackEvent {
+    /// Decodes a `wp_color_management_surface_feedback_v1` event from its message.
+    ///
+    /// # Errors
+    /// Returns an error if the opcode is unknown or the arguments are malformed.
+    pub fn decode(_conn: &Connection, msg: &WlMessage) -> Result<FeedbackEvent> {
+        let buf: &[u8] = &msg.data;
+
+        Ok(match FeedbackOpcode::try_from(msg.header.opcode)? {
+            FeedbackOpcode::PreferredChanged => {
+                FeedbackEvent::PreferredChanged(preferred_changed::PreferredChanged::try_from(buf)?)
+            }
+        })
+    }
+
+    /// Returns the opcode of the event.
+    pub fn opcode(&self) -> FeedbackOpcode {
+        match self {
+            FeedbackEvent::PreferredChanged(_) => FeedbackOpcode::PreferredChanged,
+        }
+    }
+}
+
+/// Dispatches incoming `wp_color_management_surface_feedback_v1` events to
+/// their appropriate handler functions.
+///
+/// # Event Routing
+/// * `PreferredChanged` events are routed to `preferred_changed::handle_wp_color_management_surface_feedback_preferred_changed`
+pub fn handle_wp_color_management_surface_feedback_event(
+    conn: &Connection,
+    msg: WlMessage,
+) -> Result<()> {
+    // Route the event to the appropriate handler based on type
+    match FeedbackEvent::decode(conn, &msg)? {
+        FeedbackEvent::PreferredChanged(event) => {
+            preferred_changed::handle_wp_color_management_surface_feedback_preferred_changed(
+                conn,
+                msg.header.object_id,
+                event,
+            )
+        }
+    }
+}
+
+/// A decoded `wp_image_description_v1` event, with its arguments.
+#[derive(Debug)]
+pub enum DescriptionEvent {
+    /// Announces that the image description could not be created.
+    Failed(failed::Failed),
+    /// Announces that the image description is ready to be used.
+    Ready(ready::Ready),
+}
+
+impl DescriptionEvent {
+    /// Decodes a `wp_image_description_v1` event from its message.
+    ///
+    /// # Errors
+    /// Returns an error if the opcode is unknown or the arguments are malformed.
+    pub fn decode(_conn: &Connection, msg: &WlMessage) -> Result<DescriptionEvent> {
+        let buf: &[u8] = &msg.data;
+
+        Ok(match ImageDescriptionOpcode::try_from(msg.header.opcode)? {
+            ImageDescriptionOpcode::Failed => {
+                DescriptionEvent::Failed(failed::Failed::try_from(buf)?)
+            }
+            ImageDescriptionOpcode::Ready => DescriptionEvent::Ready(ready::Ready::try_from(buf)?),
+        })
+    }
+
+    /// Returns the opcode of the event.
+    pub fn opcode(&self) -> ImageDescriptionOpcode {
+        match self {
+            DescriptionEvent::Failed(_) => ImageDescriptionOpcode::Failed,
+            DescriptionEvent::Ready(_) => ImageDescriptionOpcode::Ready,
+        }
+    }
+}
+
+/// Dispatches incoming `wp_image_description_v1` events to their
+/// appropriate handler functions.
+///
+/// # Event Routing
+/// * `Failed` events are routed to `failed::handle_wp_image_description_failed`
+/// * `Ready` events are routed to `ready::handle_wp_image_description_ready`
+pub fn handle_wp_image_description_event(conn: &Connection, msg: WlMessage) -> Result<()> {
+    let image_description_id = msg.header.object_id;
+
+    // Route the event to the appropriate handler based on type
+    match DescriptionEvent::decode(conn, &msg)? {
+        DescriptionEvent::Failed(event) => {
+            failed::handle_wp_image_description_failed(conn, image_description_id, event)
+        }
+        DescriptionEvent::Ready(event) => {
+            ready::handle_wp_image_description_ready(conn, image_description_id, event)
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::types::WlUInt,
+};
+
+/// Represents a `wp_color_management_surface_feedback_v1.preferred_changed` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="preferred_changed">
+///   <description summary="the preferred image description changed">
+///     The preferred image description is the one which likely has the most
+///     performance and/or quality benefits for the compositor if used by the
+///     client for its wl_surface contents. This event is sent whenever the
+///     compositor changes the wl_surface's preferred image description.
+///
+///     This event sends the identity of the new preferred state as the argument,
+///     so clients who are aware of the image description already can reuse it.
+///     Otherwise, if the client client wants to know what the preferred image
+///     description is, it shall use the get_preferred request.
+///
+///     The preferred image description is not automatically used for anything.
+///     It is only a hint, and clients may set any valid image description with
+///     set_image_description, but there might be performance and color accuracy
+///     improvements by providing the wl_surface contents in the preferred
+///     image description. Therefore clients that can, should render according
+///     to the preferred image description
+///   </description>
+///   <arg name="identity" type="uint" summary="image description id number"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PreferredChanged {
+    /// The identity of the preferred image description, comparable with
+    /// the one of [`ImageDescriptionEvent::Ready`](crate::protocol::color_management::ImageDescriptionEvent::Ready).
+    pub identity: WlUInt,
+}
+
+impl TryFrom<&[u8]> for PreferredChanged {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `PreferredChanged` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `identity` (u32) - The identity of the image description
+    fn try_from(buf: &[u8]) -> Result<PreferredChanged> {
+        Ok(PreferredChanged {
+            identity: WlUInt::read(buf, 0)?,
+        })
+    }
+}
+
+/// Handles a `wp_color_management_surface_feedback_v1.preferred_changed`
+/// event by notifying the listener.
+pub(super) fn handle_wp_color_management_surface_feedback_preferred_changed(
+    conn: &Connection,
+    feedback_id: u32,
+    event: PreferredChanged,
+) -> Result<()> {
+    conn.emit(feedback_id, event);
+
+    Ok(())
+}
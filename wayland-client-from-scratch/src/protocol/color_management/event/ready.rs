@@ -0,0 +1,84 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        color_management::{ImageDescriptionEvent, ImageDescriptionState, ImageDescriptionStatus},
+        types::WlUInt,
+    },
+};
+
+/// Represents a `wp_image_description_v1.ready` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="ready">
+///   <description summary="indication that the object is ready to be used">
+///     Once this event has been sent, the wp_image_description_v1 object is
+///     deemed "ready". Ready objects can be used to send requests and can be
+///     used in other requests as defined.
+///
+///     Every ready wp_image_description_v1 protocol object refers to an
+///     underlying image description record in the compositor. Multiple protocol
+///     objects may end up referring to the same record. Clients may identify
+///     these "copies" by comparing their id numbers: if the numbers from two
+///     protocol objects are identical, the protocol objects refer to the same
+///     image description record. Two different image description records
+///     cannot have the same id number simultaneously. The id number does not
+///     change during the lifetime of the image description record.
+///
+///     The id number is valid only as long as the protocol object is alive. If
+///     all protocol objects referring to the same image description record are
+///     destroyed, the id number may be recycled for a different image
+///     description record.
+///
+///     Image description id number is not a protocol object id. Zero is
+///     reserved as an invalid id number. It shall not be possible for a client
+///     to refer to an image description by its id number in protocol. The id
+///     numbers might not be portable between Wayland connections. A compositor
+///     shall not send an invalid id number.
+///
+///     This identity allows clients to de-duplicate image description records
+///     and avoid get_information request if they already have the image
+///     description information.
+///   </description>
+///   <arg name="identity" type="uint" summary="the id number"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ready {
+    /// The identity of the image description record.
+    pub identity: WlUInt,
+}
+
+impl TryFrom<&[u8]> for Ready {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Ready` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `identity` (u32) - The identity of the image description
+    fn try_from(buf: &[u8]) -> Result<Ready> {
+        Ok(Ready {
+            identity: WlUInt::read(buf, 0)?,
+        })
+    }
+}
+
+/// Handles a `wp_image_description_v1.ready` event by recording the
+/// identity and notifying the listener.
+pub(super) fn handle_wp_image_description_ready(
+    conn: &Connection,
+    image_description_id: u32,
+    event: Ready,
+) -> Result<()> {
+    let identity = event.identity.get();
+
+    if let Some(state) = conn.object_data::<ImageDescriptionState>(image_description_id) {
+        state
+            .status
+            .replace(ImageDescriptionStatus::Ready(identity));
+    }
+    conn.emit(image_description_id, ImageDescriptionEvent::Ready(identity));
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        color_management::{ColorManagerState, Feature},
+        types::WlUInt,
+    },
+};
+
+/// Represents a `wp_color_manager_v1.supported_feature` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="supported_feature">
+///   <description summary="supported features">
+///     When this object is created, it shall immediately send this event once
+///     for each compositor supported feature listed in the enumeration.
+///
+///     A compositor must not advertise features that are deprecated in the
+///     bound version of the interface.
+///   </description>
+///   <arg name="feature" type="uint" enum="feature"
+///        summary="supported feature"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SupportedFeature {
+    /// The supported feature, as a `Feature` value.
+    pub feature: WlUInt,
+}
+
+impl TryFrom<&[u8]> for SupportedFeature {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `SupportedFeature` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `feature` (u32) - The feature
+    fn try_from(buf: &[u8]) -> Result<SupportedFeature> {
+        Ok(SupportedFeature {
+            feature: WlUInt::read(buf, 0)?,
+        })
+    }
+}
+
+/// Handles a `wp_color_manager_v1.supported_feature` event by recording the value as
+/// pending until `done`.
+///
+/// Values unknown to this crate are skipped, as they cannot be requested.
+pub(super) fn handle_wp_color_manager_supported_feature(
+    conn: &Connection,
+    manager_id: u32,
+    event: SupportedFeature,
+) -> Result<()> {
+    let Ok(value) = Feature::try_from(event.feature.get()) else {
+        return Ok(());
+    };
+
+    if let Some(state) = conn.object_data::<ColorManagerState>(manager_id) {
+        state.pending.borrow_mut().features.push(value);
+    }
+
+    Ok(())
+}
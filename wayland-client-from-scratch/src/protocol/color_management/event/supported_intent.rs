@@ -0,0 +1,64 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        color_management::{ColorManagerState, RenderIntent},
+        types::WlUInt,
+    },
+};
+
+/// Represents a `wp_color_manager_v1.supported_intent` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="supported_intent">
+///   <description summary="supported rendering intent">
+///     When this object is created, it shall immediately send this event once
+///     for each rendering intent the compositor supports.
+///
+///     A compositor must not advertise intents that are deprecated in the
+///     bound version of the interface.
+///   </description>
+///   <arg name="render_intent" type="uint" enum="render_intent"
+///        summary="rendering intent"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SupportedIntent {
+    /// The supported rendering intent, as a `RenderIntent` value.
+    pub render_intent: WlUInt,
+}
+
+impl TryFrom<&[u8]> for SupportedIntent {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `SupportedIntent` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `render_intent` (u32) - The rendering intent
+    fn try_from(buf: &[u8]) -> Result<SupportedIntent> {
+        Ok(SupportedIntent {
+            render_intent: WlUInt::read(buf, 0)?,
+        })
+    }
+}
+
+/// Handles a `wp_color_manager_v1.supported_intent` event by recording the value as
+/// pending until `done`.
+///
+/// Values unknown to this crate are skipped, as they cannot be requested.
+pub(super) fn handle_wp_color_manager_supported_intent(
+    conn: &Connection,
+    manager_id: u32,
+    event: SupportedIntent,
+) -> Result<()> {
+    let Ok(value) = RenderIntent::try_from(event.render_intent.get()) else {
+        return Ok(());
+    };
+
+    if let Some(state) = conn.object_data::<ColorManagerState>(manager_id) {
+        state.pending.borrow_mut().intents.push(value);
+    }
+
+    Ok(())
+}
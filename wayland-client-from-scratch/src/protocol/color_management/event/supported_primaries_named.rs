@@ -0,0 +1,65 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        color_management::{ColorManagerState, Primaries},
+        types::WlUInt,
+    },
+};
+
+/// Represents a `wp_color_manager_v1.supported_primaries_named` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="supported_primaries_named">
+///   <description summary="supported named primaries">
+///     When this object is created, it shall immediately send this event once
+///     for each named set of primaries the compositor supports with the
+///     parametric image description creator.
+///
+///     A compositor must not advertise names that are deprecated in the
+///     bound version of the interface.
+///   </description>
+///   <arg name="primaries" type="uint" enum="primaries"
+///        summary="Named color primaries"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SupportedPrimariesNamed {
+    /// The supported primaries, as a `Primaries` value.
+    pub primaries: WlUInt,
+}
+
+impl TryFrom<&[u8]> for SupportedPrimariesNamed {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `SupportedPrimariesNamed` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `primaries` (u32) - The primaries
+    fn try_from(buf: &[u8]) -> Result<SupportedPrimariesNamed> {
+        Ok(SupportedPrimariesNamed {
+            primaries: WlUInt::read(buf, 0)?,
+        })
+    }
+}
+
+/// Handles a `wp_color_manager_v1.supported_primaries_named` event by recording the value as
+/// pending until `done`.
+///
+/// Values unknown to this crate are skipped, as they cannot be requested.
+pub(super) fn handle_wp_color_manager_supported_primaries_named(
+    conn: &Connection,
+    manager_id: u32,
+    event: SupportedPrimariesNamed,
+) -> Result<()> {
+    let Ok(value) = Primaries::try_from(event.primaries.get()) else {
+        return Ok(());
+    };
+
+    if let Some(state) = conn.object_data::<ColorManagerState>(manager_id) {
+        state.pending.borrow_mut().primaries.push(value);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        color_management::{ColorManagerState, TransferFunction},
+        types::WlUInt,
+    },
+};
+
+/// Represents a `wp_color_manager_v1.supported_tf_named` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="supported_tf_named">
+///   <description summary="supported named transfer characteristic">
+///     When this object is created, it shall immediately send this event once
+///     for each named transfer function the compositor supports with the
+///     parametric image description creator.
+///
+///     A compositor must not advertise transfer functions that are deprecated
+///     in the bound version of the interface.
+///   </description>
+///   <arg name="tf" type="uint" enum="transfer_function"
+///        summary="Named transfer function"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SupportedTfNamed {
+    /// The supported transfer function, as a `TransferFunction` value.
+    pub tf: WlUInt,
+}
+
+impl TryFrom<&[u8]> for SupportedTfNamed {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `SupportedTfNamed` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `tf` (u32) - The transfer function
+    fn try_from(buf: &[u8]) -> Result<SupportedTfNamed> {
+        Ok(SupportedTfNamed {
+            tf: WlUInt::read(buf, 0)?,
+        })
+    }
+}
+
+/// Handles a `wp_color_manager_v1.supported_tf_named` event by recording the value as
+/// pending until `done`.
+///
+/// Values unknown to this crate are skipped, as they cannot be requested.
+pub(super) fn handle_wp_color_manager_supported_tf_named(
+    conn: &Connection,
+    manager_id: u32,
+    event: SupportedTfNamed,
+) -> Result<()> {
+    let Ok(value) = TransferFunction::try_from(event.tf.get()) else {
+        return Ok(());
+    };
+
+    if let Some(state) = conn.object_data::<ColorManagerState>(manager_id) {
+        state.pending.borrow_mut().transfer_functions.push(value);
+    }
+
+    Ok(())
+}
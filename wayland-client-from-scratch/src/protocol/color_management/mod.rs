@@ -0,0 +1,1122 @@
+pub mod event;
+pub mod request;
+
+use std::{fs::File, io::Write, os::fd::OwnedFd, sync::Arc};
+
+use crate::{
+    connection::{Connection, Listener},
+    error::{Context, Result, anyhow},
+    protocol::{
+        WlObjectId,
+        surface::Surface,
+        types::{WlInt, WlNewId, WlObject, WlUInt},
+    },
+    sync::{Lock, LockCell},
+    sys, wl_enum,
+};
+
+use event::{failed::FailureCause, preferred_changed::PreferredChanged};
+
+/// The highest `wp_color_manager_v1` version this crate implements.
+pub const WP_COLOR_MANAGER_VERSION: u32 = 1;
+
+/// The largest ICC profile `wp_image_description_creator_icc_v1` accepts.
+pub const MAX_ICC_PROFILE_SIZE: u32 = 32 * 1024 * 1024;
+
+wl_enum! {
+    /// How colors are mapped from the color volume of a surface to the one
+    /// of an output, see `wp_color_manager_v1.render_intent`.
+    RenderIntent {
+        /// Perceptual mapping, as defined by ICC.
+        Perceptual = 0,
+        /// Media-relative colorimetric mapping.
+        Relative = 1,
+        /// Saturation-preserving mapping.
+        Saturation = 2,
+        /// ICC-absolute colorimetric mapping.
+        Absolute = 3,
+        /// Media-relative colorimetric mapping with black point compensation.
+        RelativeBpc = 4,
+    }
+}
+
+wl_enum! {
+    /// An optional feature of the compositor, see `wp_color_manager_v1.feature`.
+    Feature {
+        /// Image descriptions from ICC v2 and v4 files.
+        IccV2V4 = 0,
+        /// Image descriptions from parameters.
+        Parametric = 1,
+        /// Primaries given by chromaticity coordinates.
+        SetPrimaries = 2,
+        /// Transfer functions given by a power curve exponent.
+        SetTfPower = 3,
+        /// Custom primary color volume luminances.
+        SetLuminances = 4,
+        /// Mastering display primaries.
+        SetMasteringDisplayPrimaries = 5,
+        /// Target color volumes exceeding the primary color volume.
+        ExtendedTargetVolume = 6,
+        /// The Windows scRGB image description.
+        WindowsScrgb = 7,
+    }
+}
+
+wl_enum! {
+    /// A named set of color primaries and white point, see
+    /// `wp_color_manager_v1.primaries`.
+    Primaries {
+        /// BT.709 / sRGB.
+        Srgb = 1,
+        /// BT.470 System M.
+        PalM = 2,
+        /// BT.601 625-line (PAL).
+        Pal = 3,
+        /// BT.601 525-line (NTSC).
+        Ntsc = 4,
+        /// Generic film with illuminant C.
+        GenericFilm = 5,
+        /// BT.2020 / BT.2100.
+        Bt2020 = 6,
+        /// CIE 1931 XYZ.
+        Cie1931Xyz = 7,
+        /// DCI-P3 with the DCI white point.
+        DciP3 = 8,
+        /// Display P3, DCI-P3 primaries with a D65 white point.
+        DisplayP3 = 9,
+        /// Adobe RGB (1998).
+        AdobeRgb = 10,
+    }
+}
+
+wl_enum! {
+    /// A named transfer function, see `wp_color_manager_v1.transfer_function`.
+    TransferFunction {
+        /// BT.1886 display transfer characteristic.
+        Bt1886 = 1,
+        /// Gamma 2.2 power curve.
+        Gamma22 = 2,
+        /// Gamma 2.8 power curve.
+        Gamma28 = 3,
+        /// SMPTE ST 240.
+        St240 = 4,
+        /// Extended linear.
+        ExtLinear = 5,
+        /// Logarithmic with a 100:1 range.
+        Log100 = 6,
+        /// Logarithmic with a 316.22777:1 range.
+        Log316 = 7,
+        /// IEC 61966-2-4 (xvYCC).
+        Xvycc = 8,
+        /// IEC 61966-2-1 (sRGB).
+        Srgb = 9,
+        /// Extended sRGB.
+        ExtSrgb = 10,
+        /// SMPTE ST 2084, the perceptual quantizer (PQ) of HDR10.
+        St2084Pq = 11,
+        /// SMPTE ST 428-1.
+        St428 = 12,
+        /// ARIB STD-B67, hybrid log-gamma (HLG).
+        Hlg = 13,
+    }
+}
+
+/// What the compositor supports, announced by `wp_color_manager_v1` right
+/// after binding.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ColorSupport {
+    /// The supported rendering intents.
+    pub intents: Vec<RenderIntent>,
+    /// The supported optional features.
+    pub features: Vec<Feature>,
+    /// The named transfer functions parametric image descriptions may use.
+    pub transfer_functions: Vec<TransferFunction>,
+    /// The named primaries parametric image descriptions may use.
+    pub primaries: Vec<Primaries>,
+}
+
+impl ColorSupport {
+    /// Returns `true` if `feature` is supported.
+    pub fn has_feature(&self, feature: Feature) -> bool {
+        self.features.contains(&feature)
+    }
+
+    /// Returns `true` if surfaces may use `intent`.
+    pub fn has_intent(&self, intent: RenderIntent) -> bool {
+        self.intents.contains(&intent)
+    }
+}
+
+/// State of a color manager updated by its events.
+#[derive(Default)]
+pub(crate) struct ColorManagerState {
+    /// The values announced since binding, until `done`.
+    pub(crate) pending: Lock<ColorSupport>,
+    /// The values applied by `done`.
+    support: Lock<Option<ColorSupport>>,
+}
+
+impl ColorManagerState {
+    /// Applies the announced values.
+    pub(crate) fn done(&self) {
+        let support = self.pending.take();
+        self.support.replace(Some(support));
+    }
+}
+
+/// The state of an image description, as reported by its events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageDescriptionStatus {
+    /// Neither `ready` nor `failed` arrived yet.
+    Pending,
+    /// The description can be used; its identity tells apart descriptions
+    /// referring to the same record in the compositor.
+    Ready(u32),
+    /// The description could not be created and can only be destroyed.
+    Failed(FailureCause),
+}
+
+/// An event delivered to the handler of an [`ImageDescription`]; each
+/// description receives exactly one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImageDescriptionEvent {
+    /// The description is ready, with its identity.
+    Ready(u32),
+    /// The description could not be created.
+    Failed {
+        /// Why the creation failed.
+        cause: FailureCause,
+        /// A human-readable explanation.
+        message: String,
+    },
+}
+
+/// State of an image description updated by its events.
+pub(crate) struct ImageDescriptionState {
+    /// Whether the description is ready or failed.
+    pub(crate) status: LockCell<ImageDescriptionStatus>,
+}
+
+/// The transfer characteristic of an image description.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Transfer {
+    /// A named transfer function, e.g. PQ or HLG for HDR content.
+    Named(TransferFunction),
+    /// A power curve with this exponent, between 1.0 and 10.0; requires
+    /// [`Feature::SetTfPower`].
+    Power(f64),
+}
+
+impl From<TransferFunction> for Transfer {
+    fn from(tf: TransferFunction) -> Transfer {
+        Transfer::Named(tf)
+    }
+}
+
+/// A color volume given by the CIE 1931 xy chromaticity coordinates of its
+/// primaries and white point.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Chromaticities {
+    /// The red primary.
+    pub red: (f64, f64),
+    /// The green primary.
+    pub green: (f64, f64),
+    /// The blue primary.
+    pub blue: (f64, f64),
+    /// The white point.
+    pub white: (f64, f64),
+}
+
+impl Chromaticities {
+    /// The BT.2020 primaries with a D65 white point, the container of most
+    /// HDR video.
+    pub const BT2020: Chromaticities = Chromaticities {
+        red: (0.708, 0.292),
+        green: (0.170, 0.797),
+        blue: (0.131, 0.046),
+        white: (0.3127, 0.3290),
+    };
+
+    /// The DCI-P3 primaries with a D65 white point, the usual mastering
+    /// display of HDR video.
+    pub const DISPLAY_P3: Chromaticities = Chromaticities {
+        red: (0.680, 0.320),
+        green: (0.265, 0.690),
+        blue: (0.150, 0.060),
+        white: (0.3127, 0.3290),
+    };
+
+    /// Returns the coordinates multiplied by 1 million, as sent on the wire.
+    fn to_wire(self) -> [WlInt; 8] {
+        let [(r_x, r_y), (g_x, g_y), (b_x, b_y), (w_x, w_y)] =
+            [self.red, self.green, self.blue, self.white];
+        [r_x, r_y, g_x, g_y, b_x, b_y, w_x, w_y].map(|c| WlInt((c * 1_000_000.0).round() as i32))
+    }
+}
+
+/// The color primaries and white point of an image description.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColorPrimaries {
+    /// A named set of primaries.
+    Named(Primaries),
+    /// Primaries given by their coordinates; requires
+    /// [`Feature::SetPrimaries`].
+    Custom(Chromaticities),
+}
+
+impl From<Primaries> for ColorPrimaries {
+    fn from(primaries: Primaries) -> ColorPrimaries {
+        ColorPrimaries::Named(primaries)
+    }
+}
+
+/// The luminance range of the primary color volume, overriding the one
+/// implied by the transfer function; requires [`Feature::SetLuminances`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Luminances {
+    /// The minimum luminance in cd/m², with a precision of 0.0001.
+    pub min: f64,
+    /// The maximum luminance in cd/m².
+    pub max: u32,
+    /// The luminance of reference white in cd/m², e.g. 203 for PQ content.
+    pub reference: u32,
+}
+
+/// The display an HDR video was mastered on, as described by SMPTE ST 2086.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MasteringDisplay {
+    /// The primaries of the display; requires
+    /// [`Feature::SetMasteringDisplayPrimaries`].
+    pub primaries: Option<Chromaticities>,
+    /// The minimum luminance of the display in cd/m², with a precision of
+    /// 0.0001.
+    pub min_luminance: f64,
+    /// The maximum luminance of the display in cd/m².
+    pub max_luminance: u32,
+}
+
+/// The parameters of an image description created with
+/// [`ColorManager::create_parametric`].
+///
+/// The transfer characteristic and primaries are required; the other
+/// values are optional HDR metadata which the compositor may use to tone
+/// map the content to the outputs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageDescriptionParams {
+    /// The transfer characteristic the content is encoded with.
+    pub transfer: Transfer,
+    /// The primaries and white point of the content.
+    pub primaries: ColorPrimaries,
+    /// The luminance range of the content, if it differs from the one of
+    /// the transfer characteristic.
+    pub luminances: Option<Luminances>,
+    /// The display the content was mastered on.
+    pub mastering: Option<MasteringDisplay>,
+    /// The maximum content light level (MaxCLL) in cd/m², as defined by
+    /// CTA-861-H.
+    pub max_cll: Option<u32>,
+    /// The maximum frame-average light level (MaxFALL) in cd/m², as
+    /// defined by CTA-861-H.
+    pub max_fall: Option<u32>,
+}
+
+impl ImageDescriptionParams {
+    /// Describes content encoded with `transfer` in the color space of
+    /// `primaries`, without HDR metadata.
+    pub fn new(
+        transfer: impl Into<Transfer>,
+        primaries: impl Into<ColorPrimaries>,
+    ) -> ImageDescriptionParams {
+        ImageDescriptionParams {
+            transfer: transfer.into(),
+            primaries: primaries.into(),
+            luminances: None,
+            mastering: None,
+            max_cll: None,
+            max_fall: None,
+        }
+    }
+
+    /// Describes sRGB content, what surfaces without an image description
+    /// are assumed to contain.
+    pub fn srgb() -> ImageDescriptionParams {
+        ImageDescriptionParams::new(TransferFunction::Srgb, Primaries::Srgb)
+    }
+
+    /// Describes HDR10 content: BT.2020 primaries with the PQ transfer
+    /// function, mastered on `mastering`.
+    ///
+    /// `max_cll` and `max_fall` are the light levels stored with the video,
+    /// if known.
+    pub fn hdr10(
+        mastering: MasteringDisplay,
+        max_cll: Option<u32>,
+        max_fall: Option<u32>,
+    ) -> ImageDescriptionParams {
+        ImageDescriptionParams {
+            mastering: Some(mastering),
+            max_cll,
+            max_fall,
+            ..ImageDescriptionParams::new(TransferFunction::St2084Pq, Primaries::Bt2020)
+        }
+    }
+
+    /// Describes HLG content: BT.2020 primaries with the hybrid log-gamma
+    /// transfer function, which needs no metadata.
+    pub fn hlg() -> ImageDescriptionParams {
+        ImageDescriptionParams::new(TransferFunction::Hlg, Primaries::Bt2020)
+    }
+
+    /// Checks the parameters against the rules of the protocol and, if
+    /// known, the values the compositor supports.
+    fn validate(&self, support: Option<&ColorSupport>) -> Result<()> {
+        let requires = |feature: Feature| match support {
+            Some(support) if !support.has_feature(feature) => {
+                Err(anyhow!("The compositor does not support {feature}"))
+            }
+            _ => Ok(()),
+        };
+
+        requires(Feature::Parametric)?;
+        match self.transfer {
+            Transfer::Named(tf) => {
+                if let Some(support) = support
+                    && !support.transfer_functions.contains(&tf)
+                {
+                    return Err(anyhow!("The compositor does not support {tf}"));
+                }
+            }
+            Transfer::Power(exponent) => {
+                requires(Feature::SetTfPower)?;
+                if !(1.0..=10.0).contains(&exponent) {
+                    return Err(anyhow!(
+                        "Transfer function exponent must be between 1.0 and 10.0, got {exponent}"
+                    ));
+                }
+            }
+        }
+        match self.primaries {
+            ColorPrimaries::Named(primaries) => {
+                if let Some(support) = support
+                    && !support.primaries.contains(&primaries)
+                {
+                    return Err(anyhow!("The compositor does not support {primaries}"));
+                }
+            }
+            ColorPrimaries::Custom(_) => requires(Feature::SetPrimaries)?,
+        }
+        if let Some(luminances) = self.luminances {
+            requires(Feature::SetLuminances)?;
+            luminance_to_wire(luminances.min)?;
+            if f64::from(luminances.max) <= luminances.min
+                || f64::from(luminances.reference) <= luminances.min
+            {
+                return Err(anyhow!(
+                    "Maximum and reference luminances must exceed the minimum, got {luminances:?}"
+                ));
+            }
+        }
+        if let Some(mastering) = self.mastering {
+            if mastering.primaries.is_some() {
+                requires(Feature::SetMasteringDisplayPrimaries)?;
+            }
+            luminance_to_wire(mastering.min_luminance)?;
+            if f64::from(mastering.max_luminance) <= mastering.min_luminance {
+                return Err(anyhow!(
+                    "Mastering display maximum luminance must exceed the minimum, got {mastering:?}"
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sends the parameters to the parametric creator `creator`.
+    fn send(&self, conn: &Connection, creator: u32) -> Result<()> {
+        match self.transfer {
+            Transfer::Named(tf) => request::set_tf_named(conn, creator, WlUInt(tf as u32))?,
+            Transfer::Power(exponent) => {
+                request::set_tf_power(conn, creator, WlUInt((exponent * 10_000.0).round() as u32))?
+            }
+        }
+        match self.primaries {
+            ColorPrimaries::Named(primaries) => {
+                request::set_primaries_named(conn, creator, WlUInt(primaries as u32))?
+            }
+            ColorPrimaries::Custom(primaries) => {
+                request::set_primaries(conn, creator, primaries.to_wire(), false)?
+            }
+        }
+        if let Some(luminances) = self.luminances {
+            request::set_luminances(
+                conn,
+                creator,
+                WlUInt(luminance_to_wire(luminances.min)?),
+                WlUInt(luminances.max),
+                WlUInt(luminances.reference),
+            )?;
+        }
+        if let Some(mastering) = self.mastering {
+            if let Some(primaries) = mastering.primaries {
+                request::set_primaries(conn, creator, primaries.to_wire(), true)?;
+            }
+            request::set_mastering_luminance(
+                conn,
+                creator,
+                WlUInt(luminance_to_wire(mastering.min_luminance)?),
+                WlUInt(mastering.max_luminance),
+            )?;
+        }
+        if let Some(max_cll) = self.max_cll {
+            request::set_max_cll(conn, creator, WlUInt(max_cll))?;
+        }
+        if let Some(max_fall) = self.max_fall {
+            request::set_max_fall(conn, creator, WlUInt(max_fall))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Converts a minimum luminance in cd/m² to the 0.0001 cd/m² units sent on
+/// the wire.
+fn luminance_to_wire(luminance: f64) -> Result<u32> {
+    let scaled = (luminance * 10_000.0).round();
+    if !(0.0..=f64::from(u32::MAX)).contains(&scaled) {
+        return Err(anyhow!("Minimum luminance out of range: {luminance}"));
+    }
+    Ok(scaled as u32)
+}
+
+/// A bound `wp_color_manager_v1` global, which lets applications tag
+/// surfaces with the color space of their content.
+///
+/// The compositor announces what it supports right after binding; the
+/// [`ColorSupport`] is known after the next roundtrip. Requests relying on
+/// something the compositor does not support are refused with an error
+/// once it is known, instead of raising a protocol error.
+#[derive(Clone)]
+pub struct ColorManager {
+    /// The object ID of the bound manager.
+    id: u32,
+    /// The negotiated interface version.
+    version: u32,
+    /// The connection the manager was bound on.
+    conn: Connection,
+    /// The state shared with the event handlers.
+    state: Arc<ColorManagerState>,
+}
+
+impl ColorManager {
+    /// Binds the `wp_color_manager_v1` global advertised by the compositor.
+    pub fn bind(conn: &Connection) -> Result<ColorManager> {
+        let (id, version) = conn.bind(WlObjectId::ColorManager, WP_COLOR_MANAGER_VERSION)?;
+
+        let state = Arc::new(ColorManagerState::default());
+        conn.set_object_data(id, state.clone());
+
+        Ok(ColorManager {
+            id,
+            version,
+            conn: conn.clone(),
+            state,
+        })
+    }
+
+    /// Returns the object ID of the manager.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns what the compositor supports, once announced.
+    pub fn support(&self) -> Option<ColorSupport> {
+        self.state.support.borrow().clone()
+    }
+
+    /// Creates the color management object of `surface`, which sets the
+    /// image description of its content.
+    ///
+    /// A surface can only have one color management object at a time.
+    pub fn get_surface(&self, surface: &Surface) -> Result<ColorManagementSurface> {
+        self.conn
+            .check_same_connection(surface.connection(), WlObjectId::Surface, surface.id())?;
+
+        let id = self
+            .conn
+            .new_object(WlObjectId::ColorManagementSurface, self.version)?;
+        request::get_surface(&self.conn, self.id, WlNewId(id), WlObject(surface.id()))?;
+
+        Ok(ColorManagementSurface {
+            id,
+            conn: self.conn.clone(),
+            manager: self.state.clone(),
+        })
+    }
+
+    /// Subscribes to the image description the compositor prefers for
+    /// `surface`, usually the one of the output it is shown on.
+    ///
+    /// `handler` receives the identity of the preferred description
+    /// whenever it changes; [`SurfaceFeedback::get_preferred`] creates it.
+    pub fn get_surface_feedback<F>(&self, surface: &Surface, handler: F) -> Result<SurfaceFeedback>
+    where
+        F: FnMut(&Connection, PreferredChanged) + Send + 'static,
+    {
+        self.conn
+            .check_same_connection(surface.connection(), WlObjectId::Surface, surface.id())?;
+
+        let id = self
+            .conn
+            .new_object(WlObjectId::ColorManagementSurfaceFeedback, self.version)?;
+        request::get_surface_feedback(&self.conn, self.id, WlNewId(id), WlObject(surface.id()))?;
+
+        let listener: Listener<PreferredChanged> = Box::new(handler);
+        self.conn.set_listener(id, listener);
+
+        Ok(SurfaceFeedback {
+            id,
+            version: self.version,
+            conn: self.conn.clone(),
+        })
+    }
+
+    /// Creates an image description from `params`.
+    ///
+    /// The description is ready, or failed, after the next roundtrip.
+    ///
+    /// # Errors
+    /// Returns an error if `params` break the rules of the protocol, e.g.
+    /// a luminance range is empty, or if the compositor announced that it
+    /// does not support parametric descriptions or a value of `params`.
+    pub fn create_parametric(&self, params: &ImageDescriptionParams) -> Result<ImageDescription> {
+        params.validate(self.support().as_ref())?;
+
+        let creator = self
+            .conn
+            .new_object(WlObjectId::ImageDescriptionCreatorParams, self.version)?;
+        request::create_parametric_creator(&self.conn, self.id, WlNewId(creator))?;
+        params.send(&self.conn, creator)?;
+
+        let id = self.new_image_description()?;
+        request::create_parametric(&self.conn, creator, WlNewId(id))?;
+
+        Ok(self.image_description(id))
+    }
+
+    /// Creates an image description from the ICC profile found in `file`
+    /// at `offset`, `length` bytes long, e.g. a monitor profile or the one
+    /// embedded in a document.
+    ///
+    /// The profile must be a version 2 or 4, three channel profile of the
+    /// Display or ColorSpace class; the description fails otherwise. The
+    /// compositor reads the file until the description is ready or failed,
+    /// so it must not change meanwhile.
+    ///
+    /// # Errors
+    /// Returns an error if the compositor announced that it does not
+    /// support [`Feature::IccV2V4`], `file` is not a regular file, `length`
+    /// is zero or exceeds [`MAX_ICC_PROFILE_SIZE`], or the profile does not
+    /// lie within the file.
+    pub fn create_icc(&self, file: &File, offset: u32, length: u32) -> Result<ImageDescription> {
+        if let Some(support) = self.support()
+            && !support.has_feature(Feature::IccV2V4)
+        {
+            return Err(anyhow!("The compositor does not support ICC profiles"));
+        }
+        if length == 0 || length > MAX_ICC_PROFILE_SIZE {
+            return Err(anyhow!(
+                "ICC profile size must be between 1 and {MAX_ICC_PROFILE_SIZE} bytes, got {length}"
+            ));
+        }
+        let metadata = file
+            .metadata()
+            .context("Failed to query the ICC profile file")?;
+        // The compositor seeks in the file, which rules out pipes and sockets.
+        if !metadata.is_file() {
+            return Err(anyhow!("ICC profile must be read from a regular file"));
+        }
+        if u64::from(offset) + u64::from(length) > metadata.len() {
+            return Err(anyhow!(
+                "ICC profile at {offset}+{length} exceeds the file of {} bytes",
+                metadata.len()
+            ));
+        }
+        let fd = OwnedFd::from(file.try_clone()?);
+
+        let creator = self
+            .conn
+            .new_object(WlObjectId::ImageDescriptionCreatorIcc, self.version)?;
+        request::create_icc_creator(&self.conn, self.id, WlNewId(creator))?;
+        request::set_icc_file(&self.conn, creator, fd, WlUInt(offset), WlUInt(length))?;
+
+        let id = self.new_image_description()?;
+        request::create_icc(&self.conn, creator, WlNewId(id))?;
+
+        Ok(self.image_description(id))
+    }
+
+    /// Like [`create_icc`](Self::create_icc), for a profile held in memory,
+    /// which is copied into a memfd.
+    ///
+    /// # Errors
+    /// See [`create_icc`](Self::create_icc); also returns an error if the
+    /// memfd cannot be created.
+    pub fn create_icc_from_bytes(&self, profile: &[u8]) -> Result<ImageDescription> {
+        let length = u32::try_from(profile.len())
+            .ok()
+            .filter(|&length| length <= MAX_ICC_PROFILE_SIZE)
+            .ok_or_else(|| anyhow!("ICC profile of {} bytes is too large", profile.len()))?;
+
+        let mut file = File::from(sys::memfd_create("icc_profile")?);
+        file.write_all(profile)
+            .context("Failed to copy the ICC profile")?;
+        self.create_icc(&file, 0, length)
+    }
+
+    /// Creates the image description of the Windows scRGB color space:
+    /// sRGB primaries with an extended linear transfer function, where 1.0
+    /// is 80 cd/m².
+    ///
+    /// # Errors
+    /// Returns an error if the compositor announced that it does not
+    /// support [`Feature::WindowsScrgb`].
+    pub fn create_windows_scrgb(&self) -> Result<ImageDescription> {
+        if let Some(support) = self.support()
+            && !support.has_feature(Feature::WindowsScrgb)
+        {
+            return Err(anyhow!("The compositor does not support Windows scRGB"));
+        }
+
+        let id = self.new_image_description()?;
+        request::create_windows_scrgb(&self.conn, self.id, WlNewId(id))?;
+
+        Ok(self.image_description(id))
+    }
+
+    /// Destroys the manager; existing objects keep working.
+    pub fn destroy(self) -> Result<()> {
+        request::destroy(&self.conn, self.id)
+    }
+
+    /// Allocates the ID of a new image description.
+    fn new_image_description(&self) -> Result<u32> {
+        self.conn
+            .new_object(WlObjectId::ImageDescription, self.version)
+    }
+
+    /// Wraps the image description `id`, just created.
+    fn image_description(&self, id: u32) -> ImageDescription {
+        new_image_description(&self.conn, id)
+    }
+}
+
+/// Wraps the image description `id`, just created, with a pending status.
+fn new_image_description(conn: &Connection, id: u32) -> ImageDescription {
+    let state = Arc::new(ImageDescriptionState {
+        status: LockCell::new(ImageDescriptionStatus::Pending),
+    });
+    conn.set_object_data(id, state.clone());
+
+    ImageDescription {
+        id,
+        conn: conn.clone(),
+        state,
+    }
+}
+
+/// A `wp_color_management_surface_v1`, which sets the image description
+/// of the content of a surface.
+///
+/// The image description is double-buffered state, applied by the next
+/// commit of the surface. Destroying the object unsets it.
+pub struct ColorManagementSurface {
+    /// The object ID of the color management surface.
+    id: u32,
+    /// The connection the object was created on.
+    conn: Connection,
+    /// The state of the manager, telling the supported rendering intents.
+    manager: Arc<ColorManagerState>,
+}
+
+impl ColorManagementSurface {
+    /// Returns the object ID of the color management surface.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Sets the image description of the content of the surface, and how
+    /// its colors are mapped to the outputs.
+    ///
+    /// # Errors
+    /// Returns an error if `description` is not ready, or if the compositor
+    /// announced that it does not support `intent`.
+    pub fn set_image_description(
+        &self,
+        description: &ImageDescription,
+        intent: RenderIntent,
+    ) -> Result<()> {
+        self.conn.check_same_connection(
+            &description.conn,
+            WlObjectId::ImageDescription,
+            description.id,
+        )?;
+        if !description.is_ready() {
+            return Err(anyhow!("Image description {} is not ready", description.id));
+        }
+        if let Some(support) = self.manager.support.borrow().as_ref()
+            && !support.has_intent(intent)
+        {
+            return Err(anyhow!("The compositor does not support {intent}"));
+        }
+
+        request::set_image_description(
+            &self.conn,
+            self.id,
+            WlObject(description.id),
+            WlUInt(intent as u32),
+        )
+    }
+
+    /// Unsets the image description, so the content is assumed to be sRGB
+    /// again.
+    pub fn unset_image_description(&self) -> Result<()> {
+        request::unset_image_description(&self.conn, self.id)
+    }
+
+    /// Destroys the object, unsetting the image description on the next
+    /// commit.
+    ///
+    /// Dropping the handle does the same.
+    pub fn destroy(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl Drop for ColorManagementSurface {
+    fn drop(&mut self) {
+        self.conn
+            .destroy_on_drop(|conn| request::destroy_surface(conn, self.id));
+    }
+}
+
+/// A `wp_color_management_surface_feedback_v1`, reporting the image
+/// description the compositor prefers for a surface.
+pub struct SurfaceFeedback {
+    /// The object ID of the feedback object.
+    id: u32,
+    /// The negotiated interface version.
+    version: u32,
+    /// The connection the object was created on.
+    conn: Connection,
+}
+
+impl SurfaceFeedback {
+    /// Returns the object ID of the feedback object.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Creates the image description the compositor currently prefers for
+    /// the surface.
+    pub fn get_preferred(&self) -> Result<ImageDescription> {
+        self.preferred(false)
+    }
+
+    /// Like [`get_preferred`](Self::get_preferred), but the compositor
+    /// describes the preference with parameters rather than an ICC file.
+    pub fn get_preferred_parametric(&self) -> Result<ImageDescription> {
+        self.preferred(true)
+    }
+
+    /// Destroys the feedback object.
+    ///
+    /// Dropping the handle does the same.
+    pub fn destroy(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+
+    /// Sends `get_preferred` or `get_preferred_parametric`.
+    fn preferred(&self, parametric: bool) -> Result<ImageDescription> {
+        let id = self
+            .conn
+            .new_object(WlObjectId::ImageDescription, self.version)?;
+        request::get_preferred(&self.conn, self.id, WlNewId(id), parametric)?;
+
+        Ok(new_image_description(&self.conn, id))
+    }
+}
+
+impl Drop for SurfaceFeedback {
+    fn drop(&mut self) {
+        self.conn.remove_listener(self.id);
+
+        self.conn
+            .destroy_on_drop(|conn| request::destroy_feedback(conn, self.id));
+    }
+}
+
+/// A `wp_image_description_v1`, the color space of some content.
+///
+/// A description becomes ready or fails after it was created; only ready
+/// descriptions can be set on surfaces.
+pub struct ImageDescription {
+    /// The object ID of the image description.
+    id: u32,
+    /// The connection the description was created on.
+    conn: Connection,
+    /// The state shared with the event handlers.
+    state: Arc<ImageDescriptionState>,
+}
+
+impl ImageDescription {
+    /// Returns the object ID of the image description.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns whether the description is ready or failed.
+    pub fn status(&self) -> ImageDescriptionStatus {
+        self.state.status.get()
+    }
+
+    /// Returns `true` once the description can be set on surfaces.
+    pub fn is_ready(&self) -> bool {
+        matches!(self.status(), ImageDescriptionStatus::Ready(_))
+    }
+
+    /// Returns the identity of the ready description, which tells apart
+    /// descriptions referring to the same record in the compositor.
+    pub fn identity(&self) -> Option<u32> {
+        match self.status() {
+            ImageDescriptionStatus::Ready(identity) => Some(identity),
+            _ => None,
+        }
+    }
+
+    /// Registers the handler receiving the outcome of the creation,
+    /// replacing any previous one.
+    pub fn set_event_handler<F>(&self, handler: F)
+    where
+        F: FnMut(&Connection, ImageDescriptionEvent) + Send + 'static,
+    {
+        let listener: Listener<ImageDescriptionEvent> = Box::new(handler);
+        self.conn.set_listener(self.id, listener);
+    }
+
+    /// Destroys the image description; surfaces using it keep their
+    /// color space.
+    ///
+    /// Dropping the handle does the same.
+    pub fn destroy(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl Drop for ImageDescription {
+    fn drop(&mut self) {
+        self.conn.remove_listener(self.id);
+
+        self.conn
+            .destroy_on_drop(|conn| request::destroy_image_description(conn, self.id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        os::unix::net::UnixStream,
+    };
+
+    use super::*;
+    use crate::protocol::{
+        endian::Endian,
+        message::{WlMessage, WlMessageHeader},
+        registry::event::global::Global,
+        types::WlString,
+    };
+
+    /// Sends the events `(object, opcode, argument)` and dispatches them.
+    fn send(conn: &Connection, compositor: &mut UnixStream, events: &[(u32, u16, Option<u32>)]) {
+        let mut msg = Vec::new();
+        for &(object, opcode, argument) in events {
+            let data: Vec<u8> = argument
+                .map(|a| a.to_ne_bytes().to_vec())
+                .unwrap_or_default();
+            msg.extend(Vec::<u8>::from(
+                WlMessage::new(object, opcode, &data).unwrap(),
+            ));
+        }
+        compositor.write_all(&msg).unwrap();
+        conn.dispatch().unwrap();
+    }
+
+    #[test]
+    fn parametric_description_follows_the_announced_support() {
+        let (client, mut compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+        conn.insert_global(Global {
+            name: WlUInt(1),
+            interface: WlString::new("wp_color_manager_v1").unwrap(),
+            version: WlUInt(1),
+        });
+        let manager = ColorManager::bind(&conn).unwrap();
+        let id = manager.id();
+        send(
+            &conn,
+            &mut compositor,
+            &[
+                (id, 0, Some(RenderIntent::Perceptual as u32)),
+                (id, 1, Some(Feature::Parametric as u32)),
+                (id, 2, Some(TransferFunction::Srgb as u32)),
+                (id, 2, Some(TransferFunction::St2084Pq as u32)),
+                (id, 3, Some(Primaries::Srgb as u32)),
+                (id, 3, Some(Primaries::Bt2020 as u32)),
+                (id, 4, None),
+            ],
+        );
+
+        let support = manager.support().unwrap();
+        assert!(support.has_feature(Feature::Parametric));
+        assert!(!support.has_feature(Feature::WindowsScrgb));
+        assert!(manager.create_windows_scrgb().is_err());
+        let hlg = ImageDescriptionParams::new(TransferFunction::Hlg, Primaries::Bt2020);
+        assert!(manager.create_parametric(&hlg).is_err());
+
+        let surface = Surface::new(conn.new_object(WlObjectId::Surface, 6).unwrap(), &conn);
+        let color_surface = manager.get_surface(&surface).unwrap();
+        let pq = ImageDescriptionParams::new(TransferFunction::St2084Pq, Primaries::Bt2020);
+        let description = manager.create_parametric(&pq).unwrap();
+        assert_eq!(description.status(), ImageDescriptionStatus::Pending);
+        assert!(
+            color_surface
+                .set_image_description(&description, RenderIntent::Perceptual)
+                .is_err()
+        );
+
+        send(&conn, &mut compositor, &[(description.id(), 1, Some(7))]);
+        assert_eq!(description.identity(), Some(7));
+        assert!(
+            color_surface
+                .set_image_description(&description, RenderIntent::Perceptual)
+                .is_ok()
+        );
+        assert!(
+            color_surface
+                .set_image_description(&description, RenderIntent::Absolute)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn hdr10_metadata_is_sent_to_the_creator() {
+        let (client, mut compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+        conn.insert_global(Global {
+            name: WlUInt(1),
+            interface: WlString::new("wp_color_manager_v1").unwrap(),
+            version: WlUInt(1),
+        });
+        let manager = ColorManager::bind(&conn).unwrap();
+        let id = manager.id();
+        send(
+            &conn,
+            &mut compositor,
+            &[
+                (id, 0, Some(RenderIntent::Perceptual as u32)),
+                (id, 1, Some(Feature::Parametric as u32)),
+                (id, 2, Some(TransferFunction::St2084Pq as u32)),
+                (id, 3, Some(Primaries::Bt2020 as u32)),
+                (id, 4, None),
+            ],
+        );
+
+        let mut mastering = MasteringDisplay {
+            primaries: Some(Chromaticities::DISPLAY_P3),
+            min_luminance: 0.005,
+            max_luminance: 1000,
+        };
+        let unsupported = ImageDescriptionParams::hdr10(mastering, None, None);
+        assert!(manager.create_parametric(&unsupported).is_err());
+        let power = ImageDescriptionParams::new(Transfer::Power(0.5), Primaries::Bt2020);
+        assert!(manager.create_parametric(&power).is_err());
+        mastering.primaries = None;
+        mastering.max_luminance = 0;
+        let empty = ImageDescriptionParams::hdr10(mastering, None, None);
+        assert!(manager.create_parametric(&empty).is_err());
+
+        mastering.max_luminance = 1000;
+        let params = ImageDescriptionParams::hdr10(mastering, Some(1000), Some(400));
+        let description = manager.create_parametric(&params).unwrap();
+        drop((description, manager));
+        drop(conn);
+
+        let mut received = Vec::new();
+        compositor.read_to_end(&mut received).unwrap();
+        let mut received = &received[..];
+        let mut creator = None;
+        let mut requests = Vec::new();
+        while !received.is_empty() {
+            let header = WlMessageHeader::decode(received, Endian::NATIVE).unwrap();
+            let size = header.size as usize;
+            let args: Vec<u32> = received[8..size]
+                .chunks(4)
+                .map(|c| u32::from_ne_bytes(c.try_into().unwrap()))
+                .collect();
+            if header.object_id == id && header.opcode == 5 {
+                creator = Some(args[0]);
+            } else if Some(header.object_id) == creator {
+                requests.push((header.opcode, args));
+            }
+            received = &received[size..];
+        }
+        // The setters come before create, the destructor of the creator
+        let opcodes: Vec<u16> = requests.iter().map(|(opcode, _)| *opcode).collect();
+        assert_eq!(opcodes, [1, 3, 7, 8, 9, 0]);
+        assert_eq!(requests[0].1, [TransferFunction::St2084Pq as u32]);
+        assert_eq!(requests[1].1, [Primaries::Bt2020 as u32]);
+        assert_eq!(requests[2].1, [50, 1000]);
+        assert_eq!(requests[3].1, [1000]);
+        assert_eq!(requests[4].1, [400]);
+    }
+
+    #[test]
+    fn icc_profile_must_lie_within_the_file() {
+        let (client, mut compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+        conn.insert_global(Global {
+            name: WlUInt(1),
+            interface: WlString::new("wp_color_manager_v1").unwrap(),
+            version: WlUInt(1),
+        });
+        let manager = ColorManager::bind(&conn).unwrap();
+        let id = manager.id();
+        send(
+            &conn,
+            &mut compositor,
+            &[(id, 1, Some(Feature::IccV2V4 as u32)), (id, 4, None)],
+        );
+
+        let mut file = File::from(sys::memfd_create("test").unwrap());
+        file.write_all(&[0; 128]).unwrap();
+        assert!(manager.create_icc(&file, 0, 0).is_err());
+        assert!(manager.create_icc(&file, 64, 65).is_err());
+        assert!(
+            manager
+                .create_icc(&file, 0, MAX_ICC_PROFILE_SIZE + 1)
+                .is_err()
+        );
+        assert!(manager.create_icc_from_bytes(&[]).is_err());
+
+        let description = manager.create_icc(&file, 64, 64).unwrap();
+        assert_eq!(description.status(), ImageDescriptionStatus::Pending);
+        send(&conn, &mut compositor, &[(description.id(), 1, Some(3))]);
+        assert_eq!(description.identity(), Some(3));
+    }
+}
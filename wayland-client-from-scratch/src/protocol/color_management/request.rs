@@ -0,0 +1,851 @@
+use std::os::fd::OwnedFd;
+
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::types::{WlInt, WlNewId, WlObject, WlUInt},
+    wl_request_opcode, wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to the `wp_color_manager_v1` object.
+    Opcode {
+        /// Destroys the manager; existing objects keep working.
+        Destroy = 0 (destructor),
+
+        /// Creates the color management object of an output.
+        GetOutput = 1,
+
+        /// Creates the color management object of a surface.
+        GetSurface = 2,
+
+        /// Creates the color management feedback object of a surface.
+        GetSurfaceFeedback = 3,
+
+        /// Creates a creator of image descriptions from ICC files.
+        CreateIccCreator = 4,
+
+        /// Creates a creator of image descriptions from parameters.
+        CreateParametricCreator = 5,
+
+        /// Creates the image description of the Windows scRGB color space.
+        CreateWindowsScrgb = 6,
+    }
+}
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to a `wp_color_management_surface_v1` object.
+    SurfaceOpcode {
+        /// Destroys the object and unsets the image description of the surface.
+        Destroy = 0 (destructor),
+
+        /// Sets the image description of the surface.
+        SetImageDescription = 1,
+
+        /// Unsets the image description of the surface.
+        UnsetImageDescription = 2,
+    }
+}
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to a
+    /// `wp_color_management_surface_feedback_v1` object.
+    FeedbackOpcode {
+        /// Destroys the feedback object.
+        Destroy = 0 (destructor),
+
+        /// Creates the image description the compositor prefers for the surface.
+        GetPreferred = 1,
+
+        /// Creates the preferred image description in parametric form.
+        GetPreferredParametric = 2,
+    }
+}
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to a
+    /// `wp_image_description_creator_params_v1` object.
+    ParamsOpcode {
+        /// Creates the image description and destroys the creator.
+        Create = 0 (destructor),
+
+        /// Sets a named transfer function.
+        SetTfNamed = 1,
+
+        /// Sets a power curve transfer function.
+        SetTfPower = 2,
+
+        /// Sets named primaries.
+        SetPrimariesNamed = 3,
+
+        /// Sets primaries by chromaticity coordinates.
+        SetPrimaries = 4,
+
+        /// Sets the primary color volume luminance range.
+        SetLuminances = 5,
+
+        /// Sets the primaries of the mastering display.
+        SetMasteringDisplayPrimaries = 6,
+
+        /// Sets the luminance range of the mastering display.
+        SetMasteringLuminance = 7,
+
+        /// Sets the maximum content light level.
+        SetMaxCll = 8,
+
+        /// Sets the maximum frame-average light level.
+        SetMaxFall = 9,
+    }
+}
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to a
+    /// `wp_image_description_creator_icc_v1` object.
+    IccOpcode {
+        /// Creates the image description and destroys the creator.
+        Create = 0 (destructor),
+
+        /// Sets the ICC profile file.
+        SetIccFile = 1,
+    }
+}
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to a `wp_image_description_v1` object.
+    ImageDescriptionOpcode {
+        /// Destroys the image description.
+        Destroy = 0 (destructor),
+
+        /// Creates an object listing the parameters of the image description.
+        GetInformation = 1,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wp_color_manager_v1.get_surface` and
+    /// `wp_color_manager_v1.get_surface_feedback` requests.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="get_surface">
+    ///   <description summary="create a color management interface for a wl_surface">
+    ///     If a wp_color_management_surface_v1 object already exists for the given
+    ///     wl_surface, the protocol error surface_exists is raised.
+    ///
+    ///     This creates a new color wp_color_management_surface_v1 object for the
+    ///     given wl_surface.
+    ///
+    ///     See the wp_color_management_surface_v1 interface for more details.
+    ///   </description>
+    ///   <arg name="id" type="new_id" interface="wp_color_management_surface_v1"/>
+    ///   <arg name="surface" type="object" interface="wl_surface"/>
+    /// </request>
+    ///
+    /// <request name="get_surface_feedback">
+    ///   <description summary="create a color management feedback interface">
+    ///     This creates a new color wp_color_management_surface_feedback_v1 object
+    ///     for the given wl_surface.
+    ///
+    ///     See the wp_color_management_surface_feedback_v1 interface for more
+    ///     details.
+    ///   </description>
+    ///   <arg name="id" type="new_id" interface="wp_color_management_surface_feedback_v1"/>
+    ///   <arg name="surface" type="object" interface="wl_surface"/>
+    /// </request>
+    /// ```
+    GetSurfaceParam {
+        /// The object ID to assign to the new object.
+        id: WlNewId,
+        /// The surface the object is created for.
+        surface: WlObject,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the requests creating an object with no other argument,
+    /// such as `wp_color_manager_v1.create_parametric_creator` or
+    /// `wp_image_description_creator_params_v1.create`.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="create_parametric_creator">
+    ///   <description summary="make a new params based image description creator object">
+    ///     Makes a new parametric image description creator object.
+    ///
+    ///     This request can be used when the compositor advertises
+    ///     wp_color_manager_v1.feature.parametric.
+    ///     Otherwise this request raises the protocol error unsupported_feature.
+    ///   </description>
+    ///   <arg name="obj" type="new_id" interface="wp_image_description_creator_params_v1"
+    ///        summary="the new creator object"/>
+    /// </request>
+    ///
+    /// <request name="create" type="destructor">
+    ///   <description summary="Create the image description object using params">
+    ///     Create an image description object based on the parameters previously
+    ///     set on this object.
+    ///
+    ///     The completeness of the parameter set is verified. If the set is not
+    ///     complete, the protocol error incomplete_set is raised. For the
+    ///     definition of a complete set, see the description of this interface.
+    ///
+    ///     This request destroys the wp_image_description_creator_params_v1
+    ///     object.
+    ///   </description>
+    ///   <arg name="image_description" type="new_id" interface="wp_image_description_v1"/>
+    /// </request>
+    /// ```
+    NewObjectParam {
+        /// The object ID to assign to the new object.
+        id: WlNewId,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wp_color_management_surface_v1.set_image_description` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_image_description">
+    ///   <description summary="set the surface image description">
+    ///     If this protocol object is inert, the protocol error inert is raised.
+    ///
+    ///     Set the image description of the underlying surface. The image
+    ///     description and rendering intent are double-buffered state, see
+    ///     wl_surface.commit.
+    ///
+    ///     It is the client's responsibility to understand the image description
+    ///     it sets on a surface, and to provide content that matches that image
+    ///     description. Compositors might convert images to match their own or any
+    ///     other image descriptions.
+    ///
+    ///     Image descriptions which are not ready (see wp_image_description_v1)
+    ///     are forbidden in this request, and in such case the protocol error
+    ///     image_description is raised.
+    ///
+    ///     All image descriptions which are ready (see wp_image_description_v1)
+    ///     are allowed and must always be accepted by the compositor.
+    ///
+    ///     A rendering intent provides the client's preference on how content
+    ///     colors should be mapped to each output. The render_intent value must
+    ///     be one advertised by the compositor with
+    ///     wp_color_manager_v1.render_intent event, otherwise the protocol error
+    ///     render_intent is raised.
+    ///   </description>
+    ///   <arg name="image_description" type="object" interface="wp_image_description_v1"/>
+    ///   <arg name="render_intent" type="uint" enum="wp_color_manager_v1.render_intent"
+    ///        summary="rendering intent"/>
+    /// </request>
+    /// ```
+    SetImageDescriptionParam {
+        /// The ready image description to use.
+        image_description: WlObject,
+        /// How colors are mapped to the outputs, as a `RenderIntent` value.
+        render_intent: WlUInt,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wp_image_description_creator_params_v1.set_tf_named` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_tf_named">
+    ///   <description summary="named transfer characteristic">
+    ///     Sets the transfer characteristic using explicitly enumerated named
+    ///     functions.
+    ///
+    ///     When the resulting image description is attached to an image, the
+    ///     content should be encoded and decoded according to the industry
+    ///     standard practices for the transfer characteristic.
+    ///
+    ///     Only names advertised with wp_color_manager_v1 event supported_tf_named
+    ///     are allowed. Other values shall raise the protocol error invalid_tf.
+    ///
+    ///     If transfer characteristic has already been set on this object, the
+    ///     protocol error already_set is raised.
+    ///   </description>
+    ///   <arg name="tf" type="uint" enum="wp_color_manager_v1.transfer_function"
+    ///        summary="named transfer function"/>
+    /// </request>
+    /// ```
+    SetTfNamedParam {
+        /// The transfer function, as a `TransferFunction` value.
+        tf: WlUInt,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wp_image_description_creator_params_v1.set_primaries_named` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_primaries_named">
+    ///   <description summary="named primaries">
+    ///     Sets the color primaries and white point using explicitly named sets.
+    ///     This describes the primary color volume which is the basis for color
+    ///     value encoding.
+    ///
+    ///     Only names advertised with wp_color_manager_v1 event
+    ///     supported_primaries_named are allowed. Other values shall raise the
+    ///     protocol error invalid_primaries_named.
+    ///
+    ///     If primaries have already been set on this object, the protocol error
+    ///     already_set is raised.
+    ///   </description>
+    ///   <arg name="primaries" type="uint" enum="wp_color_manager_v1.primaries"
+    ///        summary="named primaries"/>
+    /// </request>
+    /// ```
+    SetPrimariesNamedParam {
+        /// The primaries, as a `Primaries` value.
+        primaries: WlUInt,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wp_image_description_creator_params_v1.set_tf_power` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_tf_power">
+    ///   <description summary="transfer characteristic as a power curve">
+    ///     Sets the color component transfer characteristic to a power curve
+    ///     with the given exponent. Negative values are handled by mirroring the
+    ///     positive half of the curve through the origin. The valid domain and
+    ///     range of the curve are all finite real numbers. This curve represents
+    ///     the conversion from electrical to optical color channel values.
+    ///
+    ///     The curve exponent shall be multiplied by 10000 to get the argument
+    ///     eexp value to carry the precision of 4 decimals.
+    ///
+    ///     The curve exponent must be at least 1.0 and at most 10.0. Otherwise
+    ///     the protocol error invalid_tf is raised.
+    ///
+    ///     If transfer characteristic has already been set on this object, the
+    ///     protocol error already_set is raised.
+    ///
+    ///     This request can be used when the compositor advertises
+    ///     wp_color_manager_v1.feature.set_tf_power. Otherwise this request raises
+    ///     the protocol error unsupported_feature.
+    ///   </description>
+    ///   <arg name="eexp" type="uint" summary="the exponent * 10000"/>
+    /// </request>
+    /// ```
+    SetTfPowerParam {
+        /// The exponent of the curve, multiplied by 10000.
+        eexp: WlUInt,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wp_image_description_creator_params_v1.set_primaries`
+    /// and `set_mastering_display_primaries` requests.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_primaries">
+    ///   <description summary="primaries as chromaticity coordinates">
+    ///     Sets the color primaries and white point using CIE 1931 xy
+    ///     chromaticity coordinates. This describes the primary color volume
+    ///     which is the basis for color value encoding.
+    ///
+    ///     Each coordinate value is multiplied by 1 million to get the argument
+    ///     value to carry precision of 6 decimals.
+    ///
+    ///     If primaries have already been set on this object, the protocol error
+    ///     already_set is raised.
+    ///
+    ///     This request can be used if the compositor advertises
+    ///     wp_color_manager_v1.feature.set_primaries. Otherwise this request raises
+    ///     the protocol error unsupported_feature.
+    ///   </description>
+    ///   <arg name="r_x" type="int" summary="Red x * 1M"/>
+    ///   <arg name="r_y" type="int" summary="Red y * 1M"/>
+    ///   <arg name="g_x" type="int" summary="Green x * 1M"/>
+    ///   <arg name="g_y" type="int" summary="Green y * 1M"/>
+    ///   <arg name="b_x" type="int" summary="Blue x * 1M"/>
+    ///   <arg name="b_y" type="int" summary="Blue y * 1M"/>
+    ///   <arg name="w_x" type="int" summary="White x * 1M"/>
+    ///   <arg name="w_y" type="int" summary="White y * 1M"/>
+    /// </request>
+    /// ```
+    SetPrimariesParam {
+        /// The x coordinate of the red primary, multiplied by 1 million.
+        r_x: WlInt,
+        /// The y coordinate of the red primary, multiplied by 1 million.
+        r_y: WlInt,
+        /// The x coordinate of the green primary, multiplied by 1 million.
+        g_x: WlInt,
+        /// The y coordinate of the green primary, multiplied by 1 million.
+        g_y: WlInt,
+        /// The x coordinate of the blue primary, multiplied by 1 million.
+        b_x: WlInt,
+        /// The y coordinate of the blue primary, multiplied by 1 million.
+        b_y: WlInt,
+        /// The x coordinate of the white point, multiplied by 1 million.
+        w_x: WlInt,
+        /// The y coordinate of the white point, multiplied by 1 million.
+        w_y: WlInt,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wp_image_description_creator_params_v1.set_luminances` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_luminances">
+    ///   <description summary="primary color volume luminance range and reference white">
+    ///     Sets the primary color volume luminance range and the reference white
+    ///     luminance level. These values include the minimum display emission
+    ///     and ambient flare luminances, assumed to be optically additive and have
+    ///     the chromaticity of the primary color volume white point.
+    ///
+    ///     The minimum luminance is multiplied by 10000 to get the argument
+    ///     'min_lum' value and carries precision of 4 decimals. The maximum
+    ///     luminance and reference white luminance values are unscaled.
+    ///
+    ///     Min_lum and max_lum specify the minimum and maximum luminances of the
+    ///     primary color volume. Reference_lum specifies the reference white
+    ///     luminance level.
+    ///
+    ///     max_lum and reference_lum must be greater than min_lum. Otherwise the
+    ///     protocol error invalid_luminance is raised.
+    ///
+    ///     This request can be used if the compositor advertises
+    ///     wp_color_manager_v1.feature.set_luminances. Otherwise this request
+    ///     raises the protocol error unsupported_feature.
+    ///   </description>
+    ///   <arg name="min_lum" type="uint" summary="minimum luminance (cd/m²) * 10000"/>
+    ///   <arg name="max_lum" type="uint" summary="maximum luminance (cd/m²)"/>
+    ///   <arg name="reference_lum" type="uint" summary="reference white luminance (cd/m²)"/>
+    /// </request>
+    /// ```
+    SetLuminancesParam {
+        /// The minimum luminance in cd/m², multiplied by 10000.
+        min_lum: WlUInt,
+        /// The maximum luminance in cd/m².
+        max_lum: WlUInt,
+        /// The reference white luminance in cd/m².
+        reference_lum: WlUInt,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wp_image_description_creator_params_v1.set_mastering_luminance` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_mastering_luminance">
+    ///   <description summary="display mastering luminance range">
+    ///     Sets the luminance range that was used during the content mastering
+    ///     process as the minimum and maximum absolute luminance L. These values
+    ///     include the minimum display emission and ambient flare luminances,
+    ///     assumed to be optically additive and have the chromaticity of the
+    ///     primary color volume white point. This should be
+    ///     compatible with the SMPTE ST 2086 definition of HDR static metadata.
+    ///
+    ///     The minimum luminance is multiplied by 10000 to get the argument
+    ///     'min_lum' value and carries precision of 4 decimals. The maximum
+    ///     luminance is unscaled.
+    ///
+    ///     The mastering luminance range is undefined by default.
+    ///
+    ///     If max L is less than or equal to min L, the protocol error
+    ///     invalid_luminance is raised.
+    ///   </description>
+    ///   <arg name="min_lum" type="uint" summary="min L (cd/m²) * 10000"/>
+    ///   <arg name="max_lum" type="uint" summary="max L (cd/m²)"/>
+    /// </request>
+    /// ```
+    SetMasteringLuminanceParam {
+        /// The minimum luminance in cd/m², multiplied by 10000.
+        min_lum: WlUInt,
+        /// The maximum luminance in cd/m².
+        max_lum: WlUInt,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wp_image_description_creator_params_v1.set_max_cll`
+    /// and `set_max_fall` requests.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_max_cll">
+    ///   <description summary="maximum content light level">
+    ///     Sets the maximum content light level (max_cll) as defined by CTA-861-H.
+    ///
+    ///     max_cll is undefined by default.
+    ///   </description>
+    ///   <arg name="max_cll" type="uint" summary="Maximum content light level (cd/m²)"/>
+    /// </request>
+    /// <request name="set_max_fall">
+    ///   <description summary="maximum frame-average light level">
+    ///     Sets the maximum frame-average light level (max_fall) as defined by
+    ///     CTA-861-H.
+    ///
+    ///     max_fall is undefined by default.
+    ///   </description>
+    ///   <arg name="max_fall" type="uint" summary="Maximum frame-average light level (cd/m²)"/>
+    /// </request>
+    /// ```
+    LightLevelParam {
+        /// The light level in cd/m².
+        level: WlUInt,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wp_image_description_creator_icc_v1.set_icc_file` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_icc_file">
+    ///   <description summary="set the ICC profile file">
+    ///     Sets the ICC profile file to be used as the basis of the image
+    ///     description.
+    ///
+    ///     The data shall be found through the given fd at the given offset, having
+    ///     the given length. The fd must be seekable and readable. Violating these
+    ///     requirements raises the bad_fd protocol error.
+    ///
+    ///     If reading the data fails due to an error independent of the client, the
+    ///     compositor shall send the wp_image_description_v1.failed event on the
+    ///     created wp_image_description_v1 with the cause operating_system.
+    ///
+    ///     The maximum size of the ICC profile is 32 MB. If length is greater than
+    ///     that or zero, the protocol error bad_size is raised. If offset + length
+    ///     exceeds the file size, the protocol error out_of_file is raised.
+    ///
+    ///     A compositor may read the file at any time starting from this request
+    ///     and only until whichever happens first:
+    ///     - If create request was issued, the wp_image_description_v1 object
+    ///       delivers either failed or ready event; or
+    ///     - if create request was not issued, this
+    ///       wp_image_description_creator_icc_v1 object is destroyed.
+    ///
+    ///     A compositor shall not modify the contents of the file, and the fd may
+    ///     be sealed for writes and size changes. The client must ensure to its
+    ///     best ability that the data does not change while the compositor is
+    ///     reading it.
+    ///
+    ///     The data must represent a valid ICC profile. The ICC profile version
+    ///     must be 2 or 4, it must be a 3 channel profile and the class must be
+    ///     Display or ColorSpace. Violating these requirements will not result in a
+    ///     protocol error, but will eventually send the
+    ///     wp_image_description_v1.failed event on the created
+    ///     wp_image_description_v1 with the cause unsupported.
+    ///
+    ///     If the ICC file has already been set on this object, the protocol error
+    ///     already_set is raised.
+    ///   </description>
+    ///   <arg name="icc_profile" type="fd" summary="ICC profile"/>
+    ///   <arg name="offset" type="uint" summary="byte offset in fd to start of ICC data"/>
+    ///   <arg name="length" type="uint" summary="length of ICC data in bytes"/>
+    /// </request>
+    /// ```
+    SetIccFileParam {
+        /// The byte offset of the profile in the file.
+        offset: WlUInt,
+        /// The length of the profile in bytes.
+        length: WlUInt,
+    }
+}
+
+/// Sends a `wp_color_manager_v1.destroy` request to the compositor.
+pub fn destroy(conn: &Connection, manager: u32) -> Result<()> {
+    conn.send_request(manager, Opcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends a `wp_color_manager_v1.get_surface` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `manager` - The ID of the bound color manager
+/// * `id` - The object ID to assign to the new color management surface
+/// * `surface` - The surface to manage the color space of
+pub fn get_surface(conn: &Connection, manager: u32, id: WlNewId, surface: WlObject) -> Result<()> {
+    conn.send_request(
+        manager,
+        Opcode::GetSurface,
+        &GetSurfaceParam::new(id, surface),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `wp_color_manager_v1.get_surface_feedback` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `manager` - The ID of the bound color manager
+/// * `id` - The object ID to assign to the new feedback object
+/// * `surface` - The surface to report the preferred image description of
+pub fn get_surface_feedback(
+    conn: &Connection,
+    manager: u32,
+    id: WlNewId,
+    surface: WlObject,
+) -> Result<()> {
+    conn.send_request(
+        manager,
+        Opcode::GetSurfaceFeedback,
+        &GetSurfaceParam::new(id, surface),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `wp_color_manager_v1.create_parametric_creator` request to the compositor.
+pub fn create_parametric_creator(conn: &Connection, manager: u32, id: WlNewId) -> Result<()> {
+    conn.send_request(
+        manager,
+        Opcode::CreateParametricCreator,
+        &NewObjectParam::new(id),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `wp_color_manager_v1.create_icc_creator` request to the compositor.
+pub fn create_icc_creator(conn: &Connection, manager: u32, id: WlNewId) -> Result<()> {
+    conn.send_request(manager, Opcode::CreateIccCreator, &NewObjectParam::new(id))?;
+
+    Ok(())
+}
+
+/// Sends a `wp_color_manager_v1.create_windows_scrgb` request to the compositor.
+pub fn create_windows_scrgb(conn: &Connection, manager: u32, id: WlNewId) -> Result<()> {
+    conn.send_request(
+        manager,
+        Opcode::CreateWindowsScrgb,
+        &NewObjectParam::new(id),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `wp_color_management_surface_v1.destroy` request to the compositor.
+pub fn destroy_surface(conn: &Connection, surface: u32) -> Result<()> {
+    conn.send_request(surface, SurfaceOpcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends a `wp_color_management_surface_v1.set_image_description` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `surface` - The ID of the color management surface
+/// * `image_description` - The ready image description to use
+/// * `render_intent` - How colors are mapped to the outputs
+pub fn set_image_description(
+    conn: &Connection,
+    surface: u32,
+    image_description: WlObject,
+    render_intent: WlUInt,
+) -> Result<()> {
+    conn.send_request(
+        surface,
+        SurfaceOpcode::SetImageDescription,
+        &SetImageDescriptionParam::new(image_description, render_intent),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `wp_color_management_surface_v1.unset_image_description` request to the compositor.
+pub fn unset_image_description(conn: &Connection, surface: u32) -> Result<()> {
+    conn.send_request(surface, SurfaceOpcode::UnsetImageDescription, &())?;
+
+    Ok(())
+}
+
+/// Sends a `wp_color_management_surface_feedback_v1.destroy` request to the compositor.
+pub fn destroy_feedback(conn: &Connection, feedback: u32) -> Result<()> {
+    conn.send_request(feedback, FeedbackOpcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends a `wp_color_management_surface_feedback_v1.get_preferred` request
+/// (or `get_preferred_parametric` if `parametric` is set) to the compositor.
+pub fn get_preferred(
+    conn: &Connection,
+    feedback: u32,
+    id: WlNewId,
+    parametric: bool,
+) -> Result<()> {
+    let opcode = if parametric {
+        FeedbackOpcode::GetPreferredParametric
+    } else {
+        FeedbackOpcode::GetPreferred
+    };
+    conn.send_request(feedback, opcode, &NewObjectParam::new(id))?;
+
+    Ok(())
+}
+
+/// Sends a `wp_image_description_creator_params_v1.create` request to the compositor.
+pub fn create_parametric(conn: &Connection, creator: u32, id: WlNewId) -> Result<()> {
+    conn.send_request(creator, ParamsOpcode::Create, &NewObjectParam::new(id))?;
+
+    Ok(())
+}
+
+/// Sends a `wp_image_description_creator_params_v1.set_tf_named` request to the compositor.
+pub fn set_tf_named(conn: &Connection, creator: u32, tf: WlUInt) -> Result<()> {
+    conn.send_request(creator, ParamsOpcode::SetTfNamed, &SetTfNamedParam::new(tf))?;
+
+    Ok(())
+}
+
+/// Sends a `wp_image_description_creator_params_v1.set_primaries_named` request to the compositor.
+pub fn set_primaries_named(conn: &Connection, creator: u32, primaries: WlUInt) -> Result<()> {
+    conn.send_request(
+        creator,
+        ParamsOpcode::SetPrimariesNamed,
+        &SetPrimariesNamedParam::new(primaries),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `wp_image_description_creator_params_v1.set_tf_power` request to the compositor.
+pub fn set_tf_power(conn: &Connection, creator: u32, eexp: WlUInt) -> Result<()> {
+    conn.send_request(
+        creator,
+        ParamsOpcode::SetTfPower,
+        &SetTfPowerParam::new(eexp),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `wp_image_description_creator_params_v1.set_primaries` request
+/// (or `set_mastering_display_primaries` if `mastering` is set) to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `creator` - The ID of the parametric creator
+/// * `coordinates` - The red, green, blue and white x and y coordinates,
+///   multiplied by 1 million
+/// * `mastering` - Whether the primaries are the ones of the mastering display
+pub fn set_primaries(
+    conn: &Connection,
+    creator: u32,
+    coordinates: [WlInt; 8],
+    mastering: bool,
+) -> Result<()> {
+    let opcode = if mastering {
+        ParamsOpcode::SetMasteringDisplayPrimaries
+    } else {
+        ParamsOpcode::SetPrimaries
+    };
+    let [r_x, r_y, g_x, g_y, b_x, b_y, w_x, w_y] = coordinates;
+    conn.send_request(
+        creator,
+        opcode,
+        &SetPrimariesParam::new(r_x, r_y, g_x, g_y, b_x, b_y, w_x, w_y),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `wp_image_description_creator_params_v1.set_luminances` request to the compositor.
+pub fn set_luminances(
+    conn: &Connection,
+    creator: u32,
+    min_lum: WlUInt,
+    max_lum: WlUInt,
+    reference_lum: WlUInt,
+) -> Result<()> {
+    conn.send_request(
+        creator,
+        ParamsOpcode::SetLuminances,
+        &SetLuminancesParam::new(min_lum, max_lum, reference_lum),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `wp_image_description_creator_params_v1.set_mastering_luminance` request to the compositor.
+pub fn set_mastering_luminance(
+    conn: &Connection,
+    creator: u32,
+    min_lum: WlUInt,
+    max_lum: WlUInt,
+) -> Result<()> {
+    conn.send_request(
+        creator,
+        ParamsOpcode::SetMasteringLuminance,
+        &SetMasteringLuminanceParam::new(min_lum, max_lum),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `wp_image_description_creator_params_v1.set_max_cll` request to the compositor.
+pub fn set_max_cll(conn: &Connection, creator: u32, max_cll: WlUInt) -> Result<()> {
+    conn.send_request(
+        creator,
+        ParamsOpcode::SetMaxCll,
+        &LightLevelParam::new(max_cll),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `wp_image_description_creator_params_v1.set_max_fall` request to the compositor.
+pub fn set_max_fall(conn: &Connection, creator: u32, max_fall: WlUInt) -> Result<()> {
+    conn.send_request(
+        creator,
+        ParamsOpcode::SetMaxFall,
+        &LightLevelParam::new(max_fall),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `wp_image_description_creator_icc_v1.set_icc_file` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `creator` - The ID of the ICC creator
+/// * `fd` - The file holding the profile, closed once sent
+/// * `offset` - The byte offset of the profile in the file
+/// * `length` - The length of the profile in bytes
+pub fn set_icc_file(
+    conn: &Connection,
+    creator: u32,
+    fd: OwnedFd,
+    offset: WlUInt,
+    length: WlUInt,
+) -> Result<()> {
+    conn.send_request_with_fds(
+        creator,
+        IccOpcode::SetIccFile,
+        &SetIccFileParam::new(offset, length),
+        vec![fd],
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `wp_image_description_creator_icc_v1.create` request to the compositor.
+pub fn create_icc(conn: &Connection, creator: u32, id: WlNewId) -> Result<()> {
+    conn.send_request(creator, IccOpcode::Create, &NewObjectParam::new(id))?;
+
+    Ok(())
+}
+
+/// Sends a `wp_image_description_v1.destroy` request to the compositor.
+pub fn destroy_image_description(conn: &Connection, image_description: u32) -> Result<()> {
+    conn.send_request(image_description, ImageDescriptionOpcode::Destroy, &())?;
+
+    Ok(())
+}
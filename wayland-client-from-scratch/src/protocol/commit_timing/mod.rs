@@ -0,0 +1,141 @@
+pub mod request;
+
+use std::time::Duration;
+
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::{
+        WlObjectId,
+        surface::Surface,
+        types::{WlNewId, WlObject, WlUInt},
+    },
+};
+
+/// The highest `wp_commit_timing_manager_v1` version this crate implements.
+pub const WP_COMMIT_TIMING_MANAGER_VERSION: u32 = 1;
+
+/// A bound `wp_commit_timing_manager_v1` global, which lets surfaces delay
+/// their commits until a given presentation time.
+#[derive(Clone)]
+pub struct CommitTimingManager {
+    /// The object ID of the bound manager.
+    id: u32,
+    /// The negotiated interface version.
+    version: u32,
+    /// The connection the manager was bound on.
+    conn: Connection,
+}
+
+impl CommitTimingManager {
+    /// Binds the `wp_commit_timing_manager_v1` global advertised by the compositor.
+    pub fn bind(conn: &Connection) -> Result<CommitTimingManager> {
+        let (id, version) = conn.bind(
+            WlObjectId::CommitTimingManager,
+            WP_COMMIT_TIMING_MANAGER_VERSION,
+        )?;
+
+        Ok(CommitTimingManager {
+            id,
+            version,
+            conn: conn.clone(),
+        })
+    }
+
+    /// Returns the object ID of the manager.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Creates the commit timer of `surface`. A surface can only have one
+    /// commit timer at a time.
+    pub fn get_timer(&self, surface: &Surface) -> Result<CommitTimer> {
+        self.conn
+            .check_same_connection(surface.connection(), WlObjectId::Surface, surface.id())?;
+
+        let id = self
+            .conn
+            .new_object(WlObjectId::CommitTimer, self.version)?;
+        request::get_timer(&self.conn, self.id, WlNewId(id), WlObject(surface.id()))?;
+
+        Ok(CommitTimer {
+            id,
+            conn: self.conn.clone(),
+        })
+    }
+
+    /// Destroys the manager; existing commit timers keep working.
+    pub fn destroy(self) -> Result<()> {
+        request::destroy(&self.conn, self.id)
+    }
+}
+
+/// A `wp_commit_timer_v1`, which holds back the content updates of a
+/// surface until a target time.
+pub struct CommitTimer {
+    /// The object ID of the commit timer.
+    id: u32,
+    /// The connection the commit timer was created on.
+    conn: Connection,
+}
+
+impl CommitTimer {
+    /// Returns the object ID of the commit timer.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Asks the compositor not to present the next commit of the surface
+    /// before `timestamp`, on the clock announced by
+    /// [`Presentation::clock_id`](crate::protocol::presentation::Presentation::clock_id).
+    ///
+    /// Only one timestamp may be set per commit; a second one before the
+    /// commit is a protocol error. Targeting the vblank predicted from
+    /// presentation feedback lets a client render ahead without the
+    /// content showing early.
+    pub fn set_timestamp(&self, timestamp: Duration) -> Result<()> {
+        let (tv_sec_hi, tv_sec_lo, tv_nsec) = split_timestamp(timestamp);
+
+        request::set_timestamp(
+            &self.conn,
+            self.id,
+            WlUInt(tv_sec_hi),
+            WlUInt(tv_sec_lo),
+            WlUInt(tv_nsec),
+        )
+    }
+
+    /// Destroys the commit timer; timestamps already committed still apply.
+    ///
+    /// Dropping the handle does the same.
+    pub fn destroy(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl Drop for CommitTimer {
+    fn drop(&mut self) {
+        self.conn
+            .destroy_on_drop(|conn| request::destroy_timer(conn, self.id));
+    }
+}
+
+/// Splits `timestamp` into the high and low 32 bits of its seconds and its
+/// nanoseconds, as `timespec` values are sent on the wire.
+fn split_timestamp(timestamp: Duration) -> (u32, u32, u32) {
+    let secs = timestamp.as_secs();
+    ((secs >> 32) as u32, secs as u32, timestamp.subsec_nanos())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timestamps_are_split_like_timespecs() {
+        let timestamp = Duration::new((7 << 32) | 42, 999_999_999);
+        assert_eq!(split_timestamp(timestamp), (7, 42, 999_999_999));
+    }
+}
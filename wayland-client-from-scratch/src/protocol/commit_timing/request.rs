@@ -0,0 +1,139 @@
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::types::{WlNewId, WlObject, WlUInt},
+    wl_request_opcode, wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to the
+    /// `wp_commit_timing_manager_v1` object.
+    Opcode {
+        /// Destroys the manager; existing commit timers keep working.
+        Destroy = 0 (destructor),
+
+        /// Creates the commit timer of a surface.
+        GetTimer = 1,
+    }
+}
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to a `wp_commit_timer_v1` object.
+    TimerOpcode {
+        /// Sets the earliest presentation time of the next commit.
+        SetTimestamp = 0,
+
+        /// Destroys the commit timer.
+        Destroy = 1 (destructor),
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wp_commit_timing_manager_v1.get_timer` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="get_timer">
+    ///   <description summary="request commit timer interface for surface">
+    ///     Establish a timing controller for a surface.
+    ///
+    ///     Only one commit timer can be created for a surface, or a
+    ///     commit_timer_exists protocol error will be generated.
+    ///   </description>
+    ///   <arg name="id" type="new_id" interface="wp_commit_timer_v1"/>
+    ///   <arg name="surface" type="object" interface="wl_surface"/>
+    /// </request>
+    /// ```
+    GetTimerParam {
+        /// The object ID to assign to the new commit timer.
+        id: WlNewId,
+        /// The surface whose commits are timed.
+        surface: WlObject,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wp_commit_timer_v1.set_timestamp` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_timestamp">
+    ///   <description summary="Specify time the following commit takes effect">
+    ///     Provide a timing constraint for a surface content update.
+    ///
+    ///     A set_timestamp request may be made before a wl_surface.commit to
+    ///     tell the compositor that the content is intended to be presented
+    ///     after a specific time.
+    ///
+    ///     If a timestamp already exists on the surface, a timestamp_exists
+    ///     error is generated.
+    ///
+    ///     Requesting set_timestamp after the commit_timer object's surface is
+    ///     destroyed will generate a "surface_destroyed" error.
+    ///   </description>
+    ///   <arg name="tv_sec_hi" type="uint"
+    ///        summary="high 32 bits of the seconds part of target time"/>
+    ///   <arg name="tv_sec_lo" type="uint"
+    ///        summary="low 32 bits of the seconds part of target time"/>
+    ///   <arg name="tv_nsec" type="uint"
+    ///        summary="nanoseconds part of target time"/>
+    /// </request>
+    /// ```
+    SetTimestampParam {
+        /// The high 32 bits of the seconds of the target time.
+        tv_sec_hi: WlUInt,
+        /// The low 32 bits of the seconds of the target time.
+        tv_sec_lo: WlUInt,
+        /// The nanoseconds of the target time, below one second.
+        tv_nsec: WlUInt,
+    }
+}
+
+/// Sends a `wp_commit_timing_manager_v1.destroy` request to the compositor.
+pub fn destroy(conn: &Connection, manager: u32) -> Result<()> {
+    conn.send_request(manager, Opcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends a `wp_commit_timing_manager_v1.get_timer` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `manager` - The ID of the bound commit timing manager
+/// * `id` - The object ID to assign to the new commit timer
+/// * `surface` - The surface whose commits are timed
+pub fn get_timer(conn: &Connection, manager: u32, id: WlNewId, surface: WlObject) -> Result<()> {
+    conn.send_request(manager, Opcode::GetTimer, &GetTimerParam::new(id, surface))?;
+
+    Ok(())
+}
+
+/// Sends a `wp_commit_timer_v1.set_timestamp` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `timer` - The ID of the commit timer
+/// * `tv_sec_hi`, `tv_sec_lo`, `tv_nsec` - The target time on the presentation clock
+pub fn set_timestamp(
+    conn: &Connection,
+    timer: u32,
+    tv_sec_hi: WlUInt,
+    tv_sec_lo: WlUInt,
+    tv_nsec: WlUInt,
+) -> Result<()> {
+    conn.send_request(
+        timer,
+        TimerOpcode::SetTimestamp,
+        &SetTimestampParam::new(tv_sec_hi, tv_sec_lo, tv_nsec),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `wp_commit_timer_v1.destroy` request to the compositor.
+pub fn destroy_timer(conn: &Connection, timer: u32) -> Result<()> {
+    conn.send_request(timer, TimerOpcode::Destroy, &())?;
+
+    Ok(())
+}
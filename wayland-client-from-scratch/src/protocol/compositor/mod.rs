@@ -0,0 +1,61 @@
+pub mod request;
+
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::{WlObjectId, region::Region, surface::Surface, types::WlNewId},
+};
+
+/// The highest `wl_compositor` version this crate implements.
+pub const WL_COMPOSITOR_VERSION: u32 = 6;
+
+/// A bound `wl_compositor` global, the factory for surfaces.
+#[derive(Clone)]
+pub struct Compositor {
+    /// The object ID of the bound compositor.
+    id: u32,
+    /// The negotiated interface version.
+    version: u32,
+    /// The connection the compositor was bound on.
+    conn: Connection,
+}
+
+impl Compositor {
+    /// Binds the `wl_compositor` global advertised by the compositor.
+    ///
+    /// The globals must be known, i.e. a [`Connection::roundtrip`] must have
+    /// completed after connecting.
+    pub fn bind(conn: &Connection) -> Result<Compositor> {
+        let (id, version) = conn.bind(WlObjectId::Compositor, WL_COMPOSITOR_VERSION)?;
+
+        Ok(Compositor {
+            id,
+            version,
+            conn: conn.clone(),
+        })
+    }
+
+    /// Returns the object ID of the compositor.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Creates a new surface.
+    ///
+    /// Surfaces inherit the version of the compositor that created them.
+    pub fn create_surface(&self) -> Result<Surface> {
+        let id = self.conn.new_object(WlObjectId::Surface, self.version)?;
+        request::create_surface(&self.conn, self.id, WlNewId(id))?;
+
+        Ok(Surface::new(id, &self.conn))
+    }
+
+    /// Creates a new, empty region, to be set as the opaque or input region
+    /// of a surface.
+    pub fn create_region(&self) -> Result<Region> {
+        let id = self.conn.new_object(WlObjectId::Region, self.version)?;
+        request::create_region(&self.conn, self.id, WlNewId(id))?;
+
+        Ok(Region::new(id, &self.conn))
+    }
+}
@@ -0,0 +1,83 @@
+use crate::{
+    connection::Connection, error::Result, protocol::types::WlNewId, wl_request_opcode,
+    wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to the Wayland compositor object.
+    Opcode {
+        /// Asks the compositor to create a new surface.
+        CreateSurface = 0,
+
+        /// Asks the compositor to create a new region.
+        CreateRegion = 1,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wl_compositor.create_surface` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="create_surface">
+    ///   <description summary="create new surface">
+    ///     Ask the compositor to create a new surface.
+    ///   </description>
+    ///   <arg name="id" type="new_id" interface="wl_surface" summary="the new surface"/>
+    /// </request>
+    /// ```
+    CreateSurfaceParam {
+        /// The object ID to assign to the newly created wl_surface object.
+        id: WlNewId,
+    }
+}
+
+/// Sends a `wl_compositor.create_surface` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `compositor` - The ID of the bound wl_compositor object
+/// * `id` - The object ID to assign to the new surface
+pub fn create_surface(conn: &Connection, compositor: u32, id: WlNewId) -> Result<()> {
+    conn.send_request(
+        compositor,
+        Opcode::CreateSurface,
+        &CreateSurfaceParam::new(id),
+    )?;
+
+    Ok(())
+}
+
+wl_request_param! {
+    /// Parameters for the `wl_compositor.create_region` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="create_region">
+    ///   <description summary="create new region">
+    ///     Ask the compositor to create a new region.
+    ///   </description>
+    ///   <arg name="id" type="new_id" interface="wl_region" summary="the new region"/>
+    /// </request>
+    /// ```
+    CreateRegionParam {
+        /// The object ID to assign to the newly created wl_region object.
+        id: WlNewId,
+    }
+}
+
+/// Sends a `wl_compositor.create_region` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `compositor` - The ID of the bound wl_compositor object
+/// * `id` - The object ID to assign to the new region
+pub fn create_region(conn: &Connection, compositor: u32, id: WlNewId) -> Result<()> {
+    conn.send_request(
+        compositor,
+        Opcode::CreateRegion,
+        &CreateRegionParam::new(id),
+    )?;
+
+    Ok(())
+}
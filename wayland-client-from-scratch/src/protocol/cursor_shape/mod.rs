@@ -0,0 +1,276 @@
+pub mod request;
+
+use crate::{
+    connection::Connection,
+    error::{Result, anyhow},
+    protocol::{
+        WlObjectId,
+        pointer::Pointer,
+        types::{WlNewId, WlObject, WlUInt},
+    },
+    wl_enum,
+};
+
+/// The highest `wp_cursor_shape_manager_v1` version this crate implements.
+pub const WP_CURSOR_SHAPE_MANAGER_VERSION: u32 = 2;
+
+wl_enum! {
+    /// A cursor shape drawn by the compositor, see
+    /// `wp_cursor_shape_device_v1.shape`. The shapes are the cursors of the
+    /// CSS specification, named by [`Shape::name`].
+    Shape {
+        /// The default cursor, usually an arrow.
+        Default = 1,
+        /// A context menu is available for the object under the cursor.
+        ContextMenu = 2,
+        /// Help is available for the object under the cursor.
+        Help = 3,
+        /// The cursor is over a link, usually a pointing hand.
+        Pointer = 4,
+        /// The program is busy, but the user can still interact with it.
+        Progress = 5,
+        /// The program is busy and the user cannot interact with it.
+        Wait = 6,
+        /// A cell or set of cells may be selected.
+        Cell = 7,
+        /// A simple crosshair.
+        Crosshair = 8,
+        /// The text may be selected, usually an I-beam.
+        Text = 9,
+        /// The vertical text may be selected.
+        VerticalText = 10,
+        /// An alias or shortcut is to be created.
+        Alias = 11,
+        /// Something is to be copied.
+        Copy = 12,
+        /// Something is to be moved.
+        Move = 13,
+        /// The dragged item cannot be dropped at the current location.
+        NoDrop = 14,
+        /// The requested action will not be carried out.
+        NotAllowed = 15,
+        /// Something can be grabbed.
+        Grab = 16,
+        /// Something is being grabbed.
+        Grabbing = 17,
+        /// The right edge is to be moved.
+        EResize = 18,
+        /// The top edge is to be moved.
+        NResize = 19,
+        /// The top-right corner is to be moved.
+        NeResize = 20,
+        /// The top-left corner is to be moved.
+        NwResize = 21,
+        /// The bottom edge is to be moved.
+        SResize = 22,
+        /// The bottom-right corner is to be moved.
+        SeResize = 23,
+        /// The bottom-left corner is to be moved.
+        SwResize = 24,
+        /// The left edge is to be moved.
+        WResize = 25,
+        /// A bidirectional horizontal resize cursor.
+        EwResize = 26,
+        /// A bidirectional vertical resize cursor.
+        NsResize = 27,
+        /// A bidirectional resize cursor along the top-right to bottom-left diagonal.
+        NeswResize = 28,
+        /// A bidirectional resize cursor along the top-left to bottom-right diagonal.
+        NwseResize = 29,
+        /// The column may be resized horizontally.
+        ColResize = 30,
+        /// The row may be resized vertically.
+        RowResize = 31,
+        /// Something can be scrolled in any direction.
+        AllScroll = 32,
+        /// Something can be zoomed in.
+        ZoomIn = 33,
+        /// Something can be zoomed out.
+        ZoomOut = 34,
+        /// The drop target asks which action to take, since version 2.
+        DndAsk = 35,
+        /// Something can be resized in any direction, since version 2.
+        AllResize = 36,
+    }
+}
+
+impl Shape {
+    /// Returns the CSS name of the shape, such as `text` or `ew-resize`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Shape::Default => "default",
+            Shape::ContextMenu => "context-menu",
+            Shape::Help => "help",
+            Shape::Pointer => "pointer",
+            Shape::Progress => "progress",
+            Shape::Wait => "wait",
+            Shape::Cell => "cell",
+            Shape::Crosshair => "crosshair",
+            Shape::Text => "text",
+            Shape::VerticalText => "vertical-text",
+            Shape::Alias => "alias",
+            Shape::Copy => "copy",
+            Shape::Move => "move",
+            Shape::NoDrop => "no-drop",
+            Shape::NotAllowed => "not-allowed",
+            Shape::Grab => "grab",
+            Shape::Grabbing => "grabbing",
+            Shape::EResize => "e-resize",
+            Shape::NResize => "n-resize",
+            Shape::NeResize => "ne-resize",
+            Shape::NwResize => "nw-resize",
+            Shape::SResize => "s-resize",
+            Shape::SeResize => "se-resize",
+            Shape::SwResize => "sw-resize",
+            Shape::WResize => "w-resize",
+            Shape::EwResize => "ew-resize",
+            Shape::NsResize => "ns-resize",
+            Shape::NeswResize => "nesw-resize",
+            Shape::NwseResize => "nwse-resize",
+            Shape::ColResize => "col-resize",
+            Shape::RowResize => "row-resize",
+            Shape::AllScroll => "all-scroll",
+            Shape::ZoomIn => "zoom-in",
+            Shape::ZoomOut => "zoom-out",
+            Shape::DndAsk => "dnd-ask",
+            Shape::AllResize => "all-resize",
+        }
+    }
+
+    /// Returns the shape with the CSS name `name`, if any.
+    pub fn from_name(name: &str) -> Option<Shape> {
+        (Shape::Default as u32..=Shape::AllResize as u32)
+            .filter_map(|value| Shape::try_from(value).ok())
+            .find(|shape| shape.name() == name)
+    }
+
+    /// Returns the interface version the shape was introduced in.
+    pub fn since(self) -> u32 {
+        match self {
+            Shape::DndAsk | Shape::AllResize => 2,
+            _ => 1,
+        }
+    }
+}
+
+/// A bound `wp_cursor_shape_manager_v1` global, which lets clients pick
+/// cursors by shape and have the compositor draw them, instead of loading
+/// cursor themes and attaching cursor surfaces.
+#[derive(Clone)]
+pub struct CursorShapeManager {
+    /// The object ID of the bound global.
+    id: u32,
+    /// The negotiated interface version.
+    version: u32,
+    /// The connection the global was bound on.
+    conn: Connection,
+}
+
+impl CursorShapeManager {
+    /// Binds the `wp_cursor_shape_manager_v1` global advertised by the compositor.
+    pub fn bind(conn: &Connection) -> Result<CursorShapeManager> {
+        let (id, version) = conn.bind(
+            WlObjectId::CursorShapeManager,
+            WP_CURSOR_SHAPE_MANAGER_VERSION,
+        )?;
+
+        Ok(CursorShapeManager {
+            id,
+            version,
+            conn: conn.clone(),
+        })
+    }
+
+    /// Returns the object ID of the global.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Creates the cursor shape device setting the cursor of `pointer`.
+    pub fn get_pointer(&self, pointer: &Pointer) -> Result<CursorShapeDevice> {
+        self.conn
+            .check_same_connection(pointer.connection(), WlObjectId::Pointer, pointer.id())?;
+
+        let id = self
+            .conn
+            .new_object(WlObjectId::CursorShapeDevice, self.version)?;
+        request::get_pointer(&self.conn, self.id, WlNewId(id), WlObject(pointer.id()))?;
+
+        Ok(CursorShapeDevice {
+            id,
+            version: self.version,
+            conn: self.conn.clone(),
+        })
+    }
+
+    /// Destroys the manager; existing devices keep working.
+    pub fn destroy(self) -> Result<()> {
+        request::destroy(&self.conn, self.id)
+    }
+}
+
+/// A `wp_cursor_shape_device_v1`, which sets the cursor of a pointer to a
+/// [`Shape`].
+///
+/// The device becomes inert once the seat loses its pointer.
+pub struct CursorShapeDevice {
+    /// The object ID of the device.
+    id: u32,
+    /// The negotiated interface version.
+    version: u32,
+    /// The connection the device was created on.
+    conn: Connection,
+}
+
+impl CursorShapeDevice {
+    /// Returns the object ID of the device.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the negotiated interface version, which limits the shapes
+    /// available.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Shows `shape` as the cursor while the pointer is on the surfaces of
+    /// the client, replacing any cursor surface.
+    ///
+    /// `serial` is the serial of the latest enter event of the pointer,
+    /// see [`Pointer::enter_serial`]; the compositor ignores the request
+    /// otherwise.
+    ///
+    /// # Errors
+    /// Returns an error, without sending the request, if the shape is
+    /// newer than the negotiated version, which is a protocol error.
+    pub fn set_shape(&self, serial: u32, shape: Shape) -> Result<()> {
+        if shape.since() > self.version {
+            return Err(anyhow!(
+                "wp_cursor_shape_device_v1 {}: {} requires version {}, got {}",
+                self.id,
+                shape.name(),
+                shape.since(),
+                self.version
+            ));
+        }
+
+        request::set_shape(&self.conn, self.id, WlUInt(serial), WlUInt(shape as u32))
+    }
+
+    /// Destroys the device.
+    ///
+    /// Dropping the handle does the same.
+    pub fn destroy(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl Drop for CursorShapeDevice {
+    fn drop(&mut self) {
+        self.conn
+            .destroy_on_drop(|conn| request::destroy_device(conn, self.id));
+    }
+}
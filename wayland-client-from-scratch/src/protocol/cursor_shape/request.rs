@@ -0,0 +1,146 @@
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::types::{WlNewId, WlObject, WlUInt},
+    wl_request_opcode, wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to the `wp_cursor_shape_manager_v1` object.
+    Opcode {
+        /// Destroys the cursor shape manager.
+        Destroy = 0 (destructor),
+
+        /// Creates the cursor shape device of a pointer.
+        GetPointer = 1,
+
+        /// Creates the cursor shape device of a tablet tool.
+        GetTabletToolV2 = 2,
+    }
+}
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to the `wp_cursor_shape_device_v1` object.
+    DeviceOpcode {
+        /// Destroys the cursor shape device.
+        Destroy = 0 (destructor),
+
+        /// Sets the cursor to a shape.
+        SetShape = 1,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wp_cursor_shape_manager_v1.get_pointer` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="get_pointer">
+    ///   <description summary="manage the cursor shape of a pointer device">
+    ///     Obtain a wp_cursor_shape_device_v1 for a wl_pointer object.
+    ///
+    ///     When the pointer capability is removed from the wl_seat, the
+    ///     wp_cursor_shape_device_v1 object becomes inert.
+    ///   </description>
+    ///   <arg name="cursor_shape_device" type="new_id" interface="wp_cursor_shape_device_v1"/>
+    ///   <arg name="pointer" type="object" interface="wl_pointer"/>
+    /// </request>
+    /// ```
+    GetPointerParam {
+        /// The ID of the new cursor shape device.
+        cursor_shape_device: WlNewId,
+        /// The pointer whose cursor the device sets.
+        pointer: WlObject,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wp_cursor_shape_device_v1.set_shape` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_shape">
+    ///   <description summary="set device cursor to the shape">
+    ///     Sets the device cursor to the specified shape. The compositor will
+    ///     change the cursor image based on the specified shape.
+    ///
+    ///     The cursor actually changes only if the input device focus is one of
+    ///     the requesting client's surfaces. If any, the previous cursor image
+    ///     (surface or shape) is replaced.
+    ///
+    ///     The "shape" argument must be a valid enum entry, otherwise the
+    ///     invalid_shape protocol error is raised.
+    ///
+    ///     This is similar to the wl_pointer.set_cursor and
+    ///     zwp_tablet_tool_v2.set_cursor requests, but this request accepts a
+    ///     shape instead of contents in the form of a surface. Clients can mix
+    ///     set_cursor and set_shape requests.
+    ///
+    ///     The serial parameter must match the latest wl_pointer.enter or
+    ///     zwp_tablet_tool_v2.proximity_in serial number sent to the client.
+    ///     Otherwise the request will be ignored.
+    ///   </description>
+    ///   <arg name="serial" type="uint" summary="serial number of the enter event"/>
+    ///   <arg name="shape" type="uint" enum="shape"/>
+    /// </request>
+    /// ```
+    SetShapeParam {
+        /// The serial of the latest enter event of the pointer.
+        serial: WlUInt,
+        /// The shape, a [`Shape`](super::Shape) value.
+        shape: WlUInt,
+    }
+}
+
+/// Sends a `wp_cursor_shape_manager_v1.destroy` request to the compositor.
+pub fn destroy(conn: &Connection, manager: u32) -> Result<()> {
+    conn.send_request(manager, Opcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends a `wp_cursor_shape_manager_v1.get_pointer` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `manager` - The ID of the bound cursor shape manager
+/// * `cursor_shape_device` - The ID of the new cursor shape device
+/// * `pointer` - The pointer whose cursor the device sets
+pub fn get_pointer(
+    conn: &Connection,
+    manager: u32,
+    cursor_shape_device: WlNewId,
+    pointer: WlObject,
+) -> Result<()> {
+    conn.send_request(
+        manager,
+        Opcode::GetPointer,
+        &GetPointerParam::new(cursor_shape_device, pointer),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `wp_cursor_shape_device_v1.destroy` request to the compositor.
+pub fn destroy_device(conn: &Connection, device: u32) -> Result<()> {
+    conn.send_request(device, DeviceOpcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends a `wp_cursor_shape_device_v1.set_shape` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `device` - The ID of the cursor shape device
+/// * `serial` - The serial of the latest enter event of the pointer
+/// * `shape` - The shape to show
+pub fn set_shape(conn: &Connection, device: u32, serial: WlUInt, shape: WlUInt) -> Result<()> {
+    conn.send_request(
+        device,
+        DeviceOpcode::SetShape,
+        &SetShapeParam::new(serial, shape),
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{data_offer::DataOffer, types::WlNewId},
+};
+
+/// Represents a `wl_data_device.data_offer` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="data_offer">
+///   <description summary="introduce a new wl_data_offer">
+///     The data_offer event introduces a new wl_data_offer object,
+///     which will subsequently be used in either the
+///     data_device.enter event (for drag-and-drop) or the
+///     data_device.selection event (for selections).  Immediately
+///     following the data_device.data_offer event, the new data_offer
+///     object will send out data_offer.offer events to describe the
+///     mime types it offers.
+///   </description>
+///   <arg name="id" type="new_id" interface="wl_data_offer" summary="the new data_offer object"/>
+/// </event>
+/// ```
+#[derive(Debug)]
+pub struct DataOfferEvent {
+    /// The ID of the new data offer, allocated by the compositor.
+    pub id: WlNewId,
+}
+
+impl TryFrom<&[u8]> for DataOfferEvent {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `DataOfferEvent`.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `id` (u32) - The ID of the new data offer
+    fn try_from(buf: &[u8]) -> Result<DataOfferEvent> {
+        Ok(DataOfferEvent {
+            id: WlNewId::read(buf, 0)?,
+        })
+    }
+}
+
+/// Handles a `wl_data_device.data_offer` event by registering the new offer.
+pub(super) fn handle_wl_data_device_data_offer(
+    conn: &Connection,
+    device_id: u32,
+    event: DataOfferEvent,
+) -> Result<()> {
+    let version = conn.object(device_id).map_or(1, |object| object.version);
+
+    DataOffer::introduce(conn, event.id.get(), version)
+}
@@ -0,0 +1,84 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        data_device::{DataDeviceState, replace_offer},
+        types::{
+            WL_TYPE_FIXED_LEN, WL_TYPE_OBJECT_LEN, WL_TYPE_UINT_LEN, WlFixed, WlObject, WlUInt,
+        },
+    },
+};
+
+/// Represents a `wl_data_device.enter` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="enter">
+///   <description summary="initiate drag-and-drop session">
+///     This event is sent when an active drag-and-drop pointer enters
+///     a surface owned by the client.  The position of the pointer at
+///     enter time is provided by the x and y arguments, in surface-local
+///     coordinates.
+///   </description>
+///   <arg name="serial" type="uint" summary="serial number of the enter event"/>
+///   <arg name="surface" type="object" interface="wl_surface" summary="client surface entered"/>
+///   <arg name="x" type="fixed" summary="surface-local x coordinate"/>
+///   <arg name="y" type="fixed" summary="surface-local y coordinate"/>
+///   <arg name="id" type="object" interface="wl_data_offer" allow-null="true"
+///        summary="source data_offer object"/>
+/// </event>
+/// ```
+#[derive(Debug)]
+pub struct Enter {
+    /// The serial number of the enter event.
+    pub serial: WlUInt,
+    /// The surface entered by the drag.
+    pub surface: WlObject,
+    /// The surface-local x coordinate.
+    pub x: WlFixed,
+    /// The surface-local y coordinate.
+    pub y: WlFixed,
+    /// The offer of the dragged data, or 0 for a drag without data.
+    pub id: WlObject,
+}
+
+impl TryFrom<&[u8]> for Enter {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Enter` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `serial` (u32) - The serial number of the event
+    /// - Bytes 4-7: `surface` (u32) - The surface entered
+    /// - Bytes 8-11: `x` (fixed) - The x coordinate
+    /// - Bytes 12-15: `y` (fixed) - The y coordinate
+    /// - Bytes 16-19: `id` (u32) - The data offer, or 0
+    fn try_from(buf: &[u8]) -> Result<Enter> {
+        let x_pos = WL_TYPE_UINT_LEN + WL_TYPE_OBJECT_LEN;
+        let id_pos = x_pos + 2 * WL_TYPE_FIXED_LEN;
+
+        Ok(Enter {
+            serial: WlUInt::read(buf, 0)?,
+            surface: WlObject::read(buf, WL_TYPE_UINT_LEN)?,
+            x: WlFixed::read(buf, x_pos)?,
+            y: WlFixed::read(buf, x_pos + WL_TYPE_FIXED_LEN)?,
+            id: WlObject::read(buf, id_pos)?,
+        })
+    }
+}
+
+/// Handles a `wl_data_device.enter` event by recording the offer of the drag.
+///
+/// Any offer left over from a previous drag is destroyed.
+pub(super) fn handle_wl_data_device_enter(
+    conn: &Connection,
+    device_id: u32,
+    enter: Enter,
+) -> Result<()> {
+    let Some(state) = conn.object_data::<DataDeviceState>(device_id) else {
+        return Ok(());
+    };
+
+    let offer = Some(enter.id.get()).filter(|&id| id != 0);
+    replace_offer(conn, &state.drag, offer)
+}
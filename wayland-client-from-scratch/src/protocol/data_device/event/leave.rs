@@ -0,0 +1,27 @@
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::data_device::{DataDeviceState, replace_offer},
+};
+
+/// Handles a `wl_data_device.leave` event by destroying the offer of the drag.
+///
+/// The event has no arguments.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="leave">
+///   <description summary="end drag-and-drop session">
+///     This event is sent when the drag-and-drop pointer leaves the
+///     surface and the session ends.  The client must destroy the
+///     wl_data_offer introduced at enter time at this point.
+///   </description>
+/// </event>
+/// ```
+pub(super) fn handle_wl_data_device_leave(conn: &Connection, device_id: u32) -> Result<()> {
+    let Some(state) = conn.object_data::<DataDeviceState>(device_id) else {
+        return Ok(());
+    };
+
+    replace_offer(conn, &state.drag, None)
+}
@@ -0,0 +1,152 @@
+pub mod data_offer;
+pub mod enter;
+pub mod leave;
+pub mod motion;
+pub mod selection;
+
+use crate::{
+    connection::Connection,
+    error::{Error, Result, anyhow},
+    protocol::message::WlMessage,
+};
+
+/// Represents the event types that can be emitted by a Wayland data device object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    /// Introduces a new data offer, used by a following `Enter` or `Selection`.
+    ///
+    /// # Event Arguments
+    /// - `id`: The new data offer
+    DataOffer = 0,
+
+    /// A drag-and-drop pointer entered a surface of the client.
+    ///
+    /// # Event Arguments
+    /// - `serial`: The serial number of the event
+    /// - `surface`: The surface entered
+    /// - `x`, `y`: The surface-local position
+    /// - `id`: The offer of the dragged data, or null
+    Enter = 1,
+
+    /// The drag-and-drop pointer left the surface.
+    Leave = 2,
+
+    /// The drag-and-drop pointer moved within the surface.
+    ///
+    /// # Event Arguments
+    /// - `time`: The timestamp in milliseconds
+    /// - `x`, `y`: The surface-local position
+    Motion = 3,
+
+    /// The dragged data was dropped on the surface.
+    Drop = 4,
+
+    /// Announces the offer of a new selection.
+    ///
+    /// # Event Arguments
+    /// - `id`: The selection data offer, or null
+    Selection = 5,
+}
+
+impl TryFrom<u16> for Opcode {
+    type Error = Error;
+
+    /// Attempts to convert a raw opcode value into a structured `WlDataDeviceEvent`.
+    fn try_from(value: u16) -> Result<Opcode> {
+        match value {
+            0 => Ok(Opcode::DataOffer),
+            1 => Ok(Opcode::Enter),
+            2 => Ok(Opcode::Leave),
+            3 => Ok(Opcode::Motion),
+            4 => Ok(Opcode::Drop),
+            5 => Ok(Opcode::Selection),
+            _ => Err(anyhow!("Invalid wl_data_device event opcode: {}", value)),
+        }
+    }
+}
+
+impl Opcode {
+    /// Returns the interface version the event was introduced in.
+    pub fn since(self) -> u32 {
+        match self {
+            Opcode::DataOffer
+            | Opcode::Enter
+            | Opcode::Leave
+            | Opcode::Motion
+            | Opcode::Drop
+            | Opcode::Selection => 1,
+        }
+    }
+}
+
+/// A decoded `wl_data_device` event, with its arguments.
+#[derive(Debug)]
+pub enum Event {
+    /// Introduces a new data offer, used by a following `Enter` or `Selection`.
+    DataOffer(data_offer::DataOfferEvent),
+    /// A drag-and-drop pointer entered a surface of the client.
+    Enter(enter::Enter),
+    /// The drag-and-drop pointer left the surface.
+    Leave,
+    /// The drag-and-drop pointer moved within the surface.
+    Motion(motion::Motion),
+    /// The dragged data was dropped on the surface.
+    Drop,
+    /// Announces the offer of a new selection.
+    Selection(selection::Selection),
+}
+
+impl Event {
+    /// Decodes a `wl_data_device` event from its message.
+    ///
+    /// # Errors
+    /// Returns an error if the opcode is unknown or the arguments are malformed.
+    pub fn decode(_conn: &Connection, msg: &WlMessage) -> Result<Event> {
+        let buf: &[u8] = &msg.data;
+
+        Ok(match Opcode::try_from(msg.header.opcode)? {
+            Opcode::DataOffer => Event::DataOffer(data_offer::DataOfferEvent::try_from(buf)?),
+            Opcode::Enter => Event::Enter(enter::Enter::try_from(buf)?),
+            Opcode::Leave => Event::Leave,
+            Opcode::Motion => Event::Motion(motion::Motion::try_from(buf)?),
+            Opcode::Drop => Event::Drop,
+            Opcode::Selection => Event::Selection(selection::Selection::try_from(buf)?),
+        })
+    }
+
+    /// Returns the opcode of the event.
+    pub fn opcode(&self) -> Opcode {
+        match self {
+            Event::DataOffer(_) => Opcode::DataOffer,
+            Event::Enter(_) => Opcode::Enter,
+            Event::Leave => Opcode::Leave,
+            Event::Motion(_) => Opcode::Motion,
+            Event::Drop => Opcode::Drop,
+            Event::Selection(_) => Opcode::Selection,
+        }
+    }
+}
+
+/// Dispatches incoming Wayland data device events to their appropriate handler functions.
+///
+/// # Event Routing
+/// * `DataOffer` events are routed to `data_offer::handle_wl_data_device_data_offer`
+/// * `Enter` and `Leave` events track the offer of the current drag
+/// * `Selection` events are routed to `selection::handle_wl_data_device_selection`
+/// * `Motion` and `Drop` events are ignored, as dropping is not supported yet
+pub fn handle_wl_data_device_event(conn: &Connection, msg: WlMessage) -> Result<()> {
+    let device_id = msg.header.object_id;
+
+    // Route the event to the appropriate handler based on type
+    match Event::decode(conn, &msg)? {
+        Event::DataOffer(event) => {
+            data_offer::handle_wl_data_device_data_offer(conn, device_id, event)
+        }
+        Event::Enter(event) => enter::handle_wl_data_device_enter(conn, device_id, event),
+        Event::Leave => leave::handle_wl_data_device_leave(conn, device_id),
+        Event::Selection(event) => {
+            selection::handle_wl_data_device_selection(conn, device_id, event)
+        }
+        Event::Motion(_) | Event::Drop => Ok(()),
+    }
+}
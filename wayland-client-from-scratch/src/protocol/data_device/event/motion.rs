@@ -0,0 +1,48 @@
+use crate::{
+    error::{Error, Result},
+    protocol::types::{WL_TYPE_FIXED_LEN, WL_TYPE_UINT_LEN, WlFixed, WlUInt},
+};
+
+/// Represents a `wl_data_device.motion` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="motion">
+///   <description summary="drag-and-drop session motion">
+///     This event is sent when the drag-and-drop pointer moves within
+///     the currently focused surface. The new position of the pointer
+///     is provided by the x and y arguments, in surface-local
+///     coordinates.
+///   </description>
+///   <arg name="time" type="uint" summary="timestamp with millisecond granularity"/>
+///   <arg name="x" type="fixed" summary="surface-local x coordinate"/>
+///   <arg name="y" type="fixed" summary="surface-local y coordinate"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Motion {
+    /// The timestamp with millisecond granularity.
+    pub time: WlUInt,
+    /// The surface-local x coordinate.
+    pub x: WlFixed,
+    /// The surface-local y coordinate.
+    pub y: WlFixed,
+}
+
+impl TryFrom<&[u8]> for Motion {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Motion` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `time` (u32) - The timestamp
+    /// - Bytes 4-7: `x` (fixed) - The x coordinate
+    /// - Bytes 8-11: `y` (fixed) - The y coordinate
+    fn try_from(buf: &[u8]) -> Result<Motion> {
+        Ok(Motion {
+            time: WlUInt::read(buf, 0)?,
+            x: WlFixed::read(buf, WL_TYPE_UINT_LEN)?,
+            y: WlFixed::read(buf, WL_TYPE_UINT_LEN + WL_TYPE_FIXED_LEN)?,
+        })
+    }
+}
@@ -0,0 +1,66 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        data_device::{DataDeviceState, replace_offer},
+        types::WlObject,
+    },
+};
+
+/// Represents a `wl_data_device.selection` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="selection">
+///   <description summary="advertise new selection">
+///     The selection event is sent out to notify the client of a new
+///     wl_data_offer for the selection for this device.  The
+///     data_device.data_offer and the data_offer.offer events are
+///     sent out immediately before this event to introduce the data
+///     offer object.  The selection event is sent to a client
+///     immediately before receiving keyboard focus and when a new
+///     selection is set while the client has keyboard focus.  The
+///     data_offer is valid until a new data_offer or NULL is received
+///     or until the client loses keyboard focus.  Switching surface with
+///     keyboard focus within the same client doesn't mean a new selection
+///     will be sent.  The client must destroy the previous selection
+///     data_offer, if any, upon receiving this event.
+///   </description>
+///   <arg name="id" type="object" interface="wl_data_offer" allow-null="true"
+///        summary="selection data_offer object"/>
+/// </event>
+/// ```
+#[derive(Debug)]
+pub struct Selection {
+    /// The offer of the new selection, or 0 if the selection was cleared.
+    pub id: WlObject,
+}
+
+impl TryFrom<&[u8]> for Selection {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Selection` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `id` (u32) - The selection data offer, or 0
+    fn try_from(buf: &[u8]) -> Result<Selection> {
+        Ok(Selection {
+            id: WlObject::read(buf, 0)?,
+        })
+    }
+}
+
+/// Handles a `wl_data_device.selection` event by recording the new selection
+/// offer and destroying the previous one.
+pub(super) fn handle_wl_data_device_selection(
+    conn: &Connection,
+    device_id: u32,
+    selection: Selection,
+) -> Result<()> {
+    let Some(state) = conn.object_data::<DataDeviceState>(device_id) else {
+        return Ok(());
+    };
+
+    let offer = Some(selection.id.get()).filter(|&id| id != 0);
+    replace_offer(conn, &state.selection, offer)
+}
@@ -0,0 +1,114 @@
+pub mod event;
+pub mod request;
+
+use std::sync::Arc;
+
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::{
+        data_offer::{self, DataOffer},
+        data_source::DataSource,
+        types::{WlObject, WlUInt},
+    },
+    sync::LockCell,
+};
+
+/// State of a data device updated by its events.
+#[derive(Default)]
+pub(crate) struct DataDeviceState {
+    /// The offer of the current selection, if any.
+    selection: LockCell<Option<u32>>,
+    /// The offer of the drag-and-drop operation over the client's surfaces, if any.
+    drag: LockCell<Option<u32>>,
+}
+
+/// Stores `offer` in `slot`, destroying the offer it replaces.
+fn replace_offer(
+    conn: &Connection,
+    slot: &LockCell<Option<u32>>,
+    offer: Option<u32>,
+) -> Result<()> {
+    match slot.replace(offer) {
+        Some(previous) if Some(previous) != offer => data_offer::destroy(conn, previous),
+        _ => Ok(()),
+    }
+}
+
+/// A `wl_data_device`, the clipboard and drag-and-drop endpoint of a seat.
+///
+/// Created with
+/// [`DataDeviceManager::get_data_device`](crate::protocol::data_device_manager::DataDeviceManager::get_data_device).
+pub struct DataDevice {
+    /// The object ID of the data device.
+    id: u32,
+    /// The interface version, inherited from the manager.
+    version: u32,
+    /// The connection the data device was created on.
+    conn: Connection,
+    /// The state shared with the event handlers.
+    state: Arc<DataDeviceState>,
+}
+
+impl DataDevice {
+    /// Wraps a data device object that was just created on `conn`.
+    pub(crate) fn new(id: u32, version: u32, conn: &Connection) -> DataDevice {
+        let state = Arc::new(DataDeviceState::default());
+        conn.set_object_data(id, state.clone());
+
+        DataDevice {
+            id,
+            version,
+            conn: conn.clone(),
+            state,
+        }
+    }
+
+    /// Returns the object ID of the data device.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the offer of the current selection.
+    ///
+    /// The compositor only announces the selection while a surface of the
+    /// client has keyboard focus.
+    pub fn selection(&self) -> Option<DataOffer> {
+        let id = self.state.selection.get()?;
+        Some(DataOffer::from_id(&self.conn, id))
+    }
+
+    /// Sets the selection to `source`, or clears it.
+    ///
+    /// # Arguments
+    /// * `source` - The new selection, or `None` to clear it
+    /// * `serial` - The serial of the input event that triggered the change
+    pub fn set_selection(&self, source: Option<&DataSource>, serial: u32) -> Result<()> {
+        let source = source.map_or(0, DataSource::id);
+        request::set_selection(&self.conn, self.id, WlObject(source), WlUInt(serial))
+    }
+
+    /// Releases the data device, destroying its offers.
+    ///
+    /// Before version 2 the data device cannot be released and the object is
+    /// kept alive on the compositor side.
+    ///
+    /// Dropping the handle does the same.
+    pub fn release(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl Drop for DataDevice {
+    fn drop(&mut self) {
+        let _ = replace_offer(&self.conn, &self.state.selection, None);
+        let _ = replace_offer(&self.conn, &self.state.drag, None);
+
+        if self.version >= 2 {
+            self.conn
+                .destroy_on_drop(|conn| request::release(conn, self.id));
+        }
+    }
+}
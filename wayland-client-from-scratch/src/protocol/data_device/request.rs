@@ -0,0 +1,78 @@
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::types::{WlObject, WlUInt},
+    wl_request_opcode, wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to a Wayland data device object.
+    Opcode {
+        /// Starts a drag-and-drop operation.
+        StartDrag = 0,
+
+        /// Sets the selection, or clears it.
+        SetSelection = 1,
+
+        /// Destroys the data device (since version 2).
+        Release = 2 (destructor),
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wl_data_device.set_selection` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_selection">
+    ///   <description summary="copy data to the selection">
+    ///     This request asks the compositor to set the selection
+    ///     to the data from the source on behalf of the client.
+    ///
+    ///     To unset the selection, set the source to NULL.
+    ///
+    ///     The given source may not be used in any further set_selection or
+    ///     start_drag requests. Attempting to reuse a previously-used source
+    ///     may send a used_source error.
+    ///   </description>
+    ///   <arg name="source" type="object" interface="wl_data_source" allow-null="true"
+    ///        summary="data source for the selection"/>
+    ///   <arg name="serial" type="uint" summary="serial number of the event that triggered this request"/>
+    /// </request>
+    /// ```
+    SetSelectionParam {
+        /// The data source for the selection, or 0 to clear it.
+        source: WlObject,
+        /// The serial of the input event that triggered the request.
+        serial: WlUInt,
+    }
+}
+
+/// Sends a `wl_data_device.set_selection` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `device` - The ID of the data device
+/// * `source` - The data source for the selection, or 0 to clear it
+/// * `serial` - The serial of the input event that triggered the request
+pub fn set_selection(
+    conn: &Connection,
+    device: u32,
+    source: WlObject,
+    serial: WlUInt,
+) -> Result<()> {
+    conn.send_request(
+        device,
+        Opcode::SetSelection,
+        &SetSelectionParam::new(source, serial),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `wl_data_device.release` request to the compositor.
+pub fn release(conn: &Connection, device: u32) -> Result<()> {
+    conn.send_request(device, Opcode::Release, &())?;
+
+    Ok(())
+}
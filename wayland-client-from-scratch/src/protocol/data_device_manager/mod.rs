@@ -0,0 +1,89 @@
+pub mod request;
+
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::{
+        WlObjectId,
+        data_device::DataDevice,
+        data_source::{DataSource, DataSourceContent},
+        seat::Seat,
+        types::{WlNewId, WlObject},
+    },
+};
+
+/// The highest `wl_data_device_manager` version this crate implements.
+pub const WL_DATA_DEVICE_MANAGER_VERSION: u32 = 3;
+
+/// A bound `wl_data_device_manager` global, the entry point for copy-and-paste
+/// and drag-and-drop.
+pub struct DataDeviceManager {
+    /// The object ID of the bound manager.
+    id: u32,
+    /// The negotiated interface version.
+    version: u32,
+    /// The connection the manager was bound on.
+    conn: Connection,
+}
+
+impl DataDeviceManager {
+    /// Binds the `wl_data_device_manager` global advertised by the compositor.
+    pub fn bind(conn: &Connection) -> Result<DataDeviceManager> {
+        let (id, version) = conn.bind(
+            WlObjectId::DataDeviceManager,
+            WL_DATA_DEVICE_MANAGER_VERSION,
+        )?;
+
+        Ok(DataDeviceManager {
+            id,
+            version,
+            conn: conn.clone(),
+        })
+    }
+
+    /// Returns the object ID of the manager.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Creates a data source offering `data` under each of `mime_types`.
+    ///
+    /// The same bytes are sent whichever of the MIME types a client asks for.
+    pub fn create_data_source(&self, mime_types: &[&str], data: Vec<u8>) -> Result<DataSource> {
+        let id = self.conn.new_object(WlObjectId::DataSource, self.version)?;
+        request::create_data_source(&self.conn, self.id, WlNewId(id))?;
+
+        DataSource::new(
+            id,
+            &self.conn,
+            mime_types,
+            DataSourceContent::Data(data.into()),
+        )
+    }
+
+    /// Creates a data source offering the data produced by `writer` under
+    /// each of `mime_types`.
+    ///
+    /// `writer` is invoked with the MIME type of every request and returns
+    /// the data to send. The data is written from later dispatches as the
+    /// receiving client drains it, so large payloads do not block the event
+    /// loop. An error returned by `writer` closes the transfer without data.
+    pub fn create_data_source_with<F>(&self, mime_types: &[&str], writer: F) -> Result<DataSource>
+    where
+        F: FnMut(&str) -> Result<Vec<u8>> + Send + 'static,
+    {
+        let id = self.conn.new_object(WlObjectId::DataSource, self.version)?;
+        request::create_data_source(&self.conn, self.id, WlNewId(id))?;
+
+        let content = DataSourceContent::Writer(Box::new(writer));
+        DataSource::new(id, &self.conn, mime_types, content)
+    }
+
+    /// Creates the data device of `seat`, which tracks its selection.
+    pub fn get_data_device(&self, seat: &Seat) -> Result<DataDevice> {
+        let id = self.conn.new_object(WlObjectId::DataDevice, self.version)?;
+        request::get_data_device(&self.conn, self.id, WlNewId(id), WlObject(seat.id()))?;
+
+        Ok(DataDevice::new(id, self.version, &self.conn))
+    }
+}
@@ -0,0 +1,89 @@
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::types::{WlNewId, WlObject},
+    wl_request_opcode, wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to the `wl_data_device_manager` object.
+    Opcode {
+        /// Creates a new `wl_data_source`.
+        CreateDataSource = 0,
+
+        /// Creates the `wl_data_device` of a seat.
+        GetDataDevice = 1,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wl_data_device_manager.create_data_source` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="create_data_source">
+    ///   <description summary="create a new data source">
+    ///     Create a new data source.
+    ///   </description>
+    ///   <arg name="id" type="new_id" interface="wl_data_source" summary="data source to create"/>
+    /// </request>
+    /// ```
+    CreateDataSourceParam {
+        /// The object ID to assign to the new data source.
+        id: WlNewId,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wl_data_device_manager.get_data_device` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="get_data_device">
+    ///   <description summary="create a new data device">
+    ///     Create a new data device for a given seat.
+    ///   </description>
+    ///   <arg name="id" type="new_id" interface="wl_data_device" summary="data device to create"/>
+    ///   <arg name="seat" type="object" interface="wl_seat" summary="seat associated with the data device"/>
+    /// </request>
+    /// ```
+    GetDataDeviceParam {
+        /// The object ID to assign to the new data device.
+        id: WlNewId,
+        /// The seat the data device belongs to.
+        seat: WlObject,
+    }
+}
+
+/// Sends a `wl_data_device_manager.create_data_source` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `manager` - The ID of the bound data device manager
+/// * `id` - The object ID to assign to the new data source
+pub fn create_data_source(conn: &Connection, manager: u32, id: WlNewId) -> Result<()> {
+    conn.send_request(
+        manager,
+        Opcode::CreateDataSource,
+        &CreateDataSourceParam::new(id),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `wl_data_device_manager.get_data_device` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `manager` - The ID of the bound data device manager
+/// * `id` - The object ID to assign to the new data device
+/// * `seat` - The seat the data device belongs to
+pub fn get_data_device(conn: &Connection, manager: u32, id: WlNewId, seat: WlObject) -> Result<()> {
+    conn.send_request(
+        manager,
+        Opcode::GetDataDevice,
+        &GetDataDeviceParam::new(id, seat),
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,106 @@
+pub mod action;
+pub mod offer;
+pub mod source_actions;
+
+use crate::{
+    connection::Connection,
+    error::{Error, Result, anyhow},
+    protocol::message::WlMessage,
+};
+
+/// Represents the event types that can be emitted by a Wayland data offer object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    /// A MIME type the data is offered as.
+    ///
+    /// # Event Arguments
+    /// - `mime_type`: The offered MIME type
+    Offer = 0,
+
+    /// The drag-and-drop actions supported by the source (since version 3).
+    ///
+    /// # Event Arguments
+    /// - `source_actions`: The actions offered by the source
+    SourceActions = 1,
+
+    /// The drag-and-drop action selected by the compositor (since version 3).
+    ///
+    /// # Event Arguments
+    /// - `dnd_action`: The selected action
+    Action = 2,
+}
+
+impl TryFrom<u16> for Opcode {
+    type Error = Error;
+
+    /// Attempts to convert a raw opcode value into a structured `WlDataOfferEvent`.
+    fn try_from(value: u16) -> Result<Opcode> {
+        match value {
+            0 => Ok(Opcode::Offer),
+            1 => Ok(Opcode::SourceActions),
+            2 => Ok(Opcode::Action),
+            _ => Err(anyhow!("Invalid wl_data_offer event opcode: {}", value)),
+        }
+    }
+}
+
+impl Opcode {
+    /// Returns the interface version the event was introduced in.
+    pub fn since(self) -> u32 {
+        match self {
+            Opcode::Offer => 1,
+            Opcode::SourceActions | Opcode::Action => 3,
+        }
+    }
+}
+
+/// A decoded `wl_data_offer` event, with its arguments.
+#[derive(Debug)]
+pub enum Event {
+    /// A MIME type the data is offered as.
+    Offer(offer::Offer),
+    /// The drag-and-drop actions supported by the source (since version 3).
+    SourceActions(source_actions::SourceActions),
+    /// The drag-and-drop action selected by the compositor (since version 3).
+    Action(action::Action),
+}
+
+impl Event {
+    /// Decodes a `wl_data_offer` event from its message.
+    ///
+    /// # Errors
+    /// Returns an error if the opcode is unknown or the arguments are malformed.
+    pub fn decode(_conn: &Connection, msg: &WlMessage) -> Result<Event> {
+        let buf: &[u8] = &msg.data;
+
+        Ok(match Opcode::try_from(msg.header.opcode)? {
+            Opcode::Offer => Event::Offer(offer::Offer::try_from(buf)?),
+            Opcode::SourceActions => {
+                Event::SourceActions(source_actions::SourceActions::try_from(buf)?)
+            }
+            Opcode::Action => Event::Action(action::Action::try_from(buf)?),
+        })
+    }
+
+    /// Returns the opcode of the event.
+    pub fn opcode(&self) -> Opcode {
+        match self {
+            Event::Offer(_) => Opcode::Offer,
+            Event::SourceActions(_) => Opcode::SourceActions,
+            Event::Action(_) => Opcode::Action,
+        }
+    }
+}
+
+/// Dispatches incoming Wayland data offer events to their appropriate handler functions.
+///
+/// # Event Routing
+/// * `Offer` events are routed to `offer::handle_wl_data_offer_offer`
+/// * Drag-and-drop action events are ignored
+pub fn handle_wl_data_offer_event(conn: &Connection, msg: WlMessage) -> Result<()> {
+    // Route the event to the appropriate handler based on type
+    match Event::decode(conn, &msg)? {
+        Event::Offer(event) => offer::handle_wl_data_offer_offer(conn, msg.header.object_id, event),
+        Event::SourceActions(_) | Event::Action(_) => Ok(()),
+    }
+}
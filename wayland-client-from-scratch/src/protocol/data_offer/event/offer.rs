@@ -0,0 +1,52 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{data_offer::DataOfferState, types::WlString},
+};
+
+/// Represents a `wl_data_offer.offer` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="offer">
+///   <description summary="advertise offered mime type">
+///     Sent immediately after creating the wl_data_offer object.  One
+///     event per offered mime type.
+///   </description>
+///   <arg name="mime_type" type="string" summary="offered mime type"/>
+/// </event>
+/// ```
+#[derive(Debug)]
+pub struct Offer {
+    /// The offered MIME type.
+    pub mime_type: WlString,
+}
+
+impl TryFrom<&[u8]> for Offer {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Offer` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0+: `mime_type` (WlString) - The offered MIME type with length prefix
+    fn try_from(buf: &[u8]) -> Result<Offer> {
+        Ok(Offer {
+            mime_type: buf.try_into()?,
+        })
+    }
+}
+
+/// Handles a `wl_data_offer.offer` event by recording the offered MIME type.
+pub(super) fn handle_wl_data_offer_offer(
+    conn: &Connection,
+    offer_id: u32,
+    event: Offer,
+) -> Result<()> {
+    let mime_type = conn.decode_string(&event.mime_type)?;
+
+    if let Some(state) = conn.object_data::<DataOfferState>(offer_id) {
+        state.mime_types.borrow_mut().push(mime_type);
+    }
+
+    Ok(())
+}
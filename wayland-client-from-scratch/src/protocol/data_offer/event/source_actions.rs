@@ -0,0 +1,39 @@
+use crate::{
+    error::{Error, Result},
+    protocol::types::WlUInt,
+};
+
+/// Represents a `wl_data_offer.source_actions` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="source_actions" since="3">
+///   <description summary="notify the source-side available actions">
+///     This event indicates the actions offered by the data source. It
+///     will be sent immediately after creating the wl_data_offer object,
+///     or anytime the source side changes its offered actions through
+///     wl_data_source.set_actions.
+///   </description>
+///   <arg name="source_actions" type="uint" summary="actions offered by the data source"
+///        enum="wl_data_device_manager.dnd_action"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceActions {
+    /// The actions offered by the source, a `wl_data_device_manager.dnd_action` bitfield.
+    pub source_actions: WlUInt,
+}
+
+impl TryFrom<&[u8]> for SourceActions {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `SourceActions` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `source_actions` (u32) - The offered actions
+    fn try_from(buf: &[u8]) -> Result<SourceActions> {
+        Ok(SourceActions {
+            source_actions: WlUInt::read(buf, 0)?,
+        })
+    }
+}
@@ -0,0 +1,149 @@
+pub mod event;
+pub mod request;
+
+use std::{
+    io,
+    os::fd::{AsFd, BorrowedFd, OwnedFd},
+    sync::Arc,
+};
+
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{WlObjectId, types::WlString},
+    sync::Lock,
+    sys,
+};
+
+/// The size of the chunk read from a transfer pipe at once.
+const READ_CHUNK_LEN: usize = 4096;
+
+/// State of a data offer updated by its events.
+#[derive(Default)]
+pub(crate) struct DataOfferState {
+    /// The MIME types announced so far, in order.
+    mime_types: Lock<Vec<String>>,
+}
+
+/// A `wl_data_offer`, data another client offers through the selection or
+/// drag-and-drop.
+///
+/// Offers are created by the compositor and owned by the
+/// [`DataDevice`](crate::protocol::data_device::DataDevice) that announced
+/// them; this handle stops working once the device replaces the offer.
+#[derive(Clone)]
+pub struct DataOffer {
+    /// The object ID of the data offer.
+    id: u32,
+    /// The connection the data offer lives on.
+    conn: Connection,
+}
+
+impl DataOffer {
+    /// Registers a data offer the compositor just announced with `wl_data_device.data_offer`.
+    pub(crate) fn introduce(conn: &Connection, id: u32, version: u32) -> Result<()> {
+        conn.insert_object(id, WlObjectId::DataOffer, version)?;
+        conn.set_object_data(id, Arc::new(DataOfferState::default()));
+
+        Ok(())
+    }
+
+    /// Wraps a live data offer.
+    pub(crate) fn from_id(conn: &Connection, id: u32) -> DataOffer {
+        DataOffer {
+            id,
+            conn: conn.clone(),
+        }
+    }
+
+    /// Returns the object ID of the data offer.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the MIME types the data is offered as.
+    pub fn mime_types(&self) -> Vec<String> {
+        self.conn
+            .object_data::<DataOfferState>(self.id)
+            .map(|state| state.mime_types.borrow().clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns `true` if the data is offered as `mime_type`.
+    pub fn has_mime_type(&self, mime_type: &str) -> bool {
+        self.mime_types().iter().any(|offered| offered == mime_type)
+    }
+
+    /// Requests the data as `mime_type`.
+    ///
+    /// # Returns
+    /// The read end of the pipe the source client writes the data to; the
+    /// transfer is complete when reading reaches end of file. The request is
+    /// only queued, so the connection must be flushed before reading.
+    pub fn receive(&self, mime_type: &str) -> Result<OwnedFd> {
+        let (read, write) = sys::pipe()?;
+        request::receive(&self.conn, self.id, WlString::new(mime_type)?, write)?;
+
+        Ok(read)
+    }
+
+    /// Requests the data as `mime_type` and reads it in the background.
+    ///
+    /// The pipe is polled by [`Connection::dispatch`] along with the socket
+    /// and drained with non-blocking reads, so the event loop never stalls
+    /// on a slow source client. `on_done` receives the whole data once the
+    /// source closes the pipe, or the error that interrupted the transfer.
+    pub fn receive_async<F>(&self, mime_type: &str, on_done: F) -> Result<()>
+    where
+        F: FnOnce(&Connection, Result<Vec<u8>>) + Send + 'static,
+    {
+        let read = self.receive(mime_type)?;
+        sys::set_nonblocking(read.as_fd())?;
+
+        let mut data = Vec::new();
+        let mut on_done = Some(on_done);
+        self.conn.watch_readable(
+            read,
+            Box::new(move |conn, fd| {
+                let result = match read_available(fd, &mut data) {
+                    Ok(false) => return true,
+                    Ok(true) => Ok(std::mem::take(&mut data)),
+                    Err(error) => Err(Error::from(error).context("Failed to receive data")),
+                };
+
+                if let Some(on_done) = on_done.take() {
+                    on_done(conn, result);
+                }
+                false
+            }),
+        );
+
+        Ok(())
+    }
+}
+
+/// Appends everything currently readable from a non-blocking pipe to `data`.
+///
+/// # Returns
+/// `true` once the writer closed the pipe, `false` if more data may follow.
+fn read_available(fd: BorrowedFd<'_>, data: &mut Vec<u8>) -> io::Result<bool> {
+    let mut chunk = [0u8; READ_CHUNK_LEN];
+
+    loop {
+        match sys::read(fd, &mut chunk) {
+            Ok(0) => return Ok(true),
+            Ok(read_len) => data.extend_from_slice(&chunk[..read_len]),
+            Err(error) if error.kind() == io::ErrorKind::WouldBlock => return Ok(false),
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+/// Destroys a data offer.
+///
+/// Offers are created by the compositor, so no `wl_display.delete_id`
+/// confirms their destruction and the connection forgets them right away.
+pub(crate) fn destroy(conn: &Connection, id: u32) -> Result<()> {
+    request::destroy(conn, id)
+}
@@ -0,0 +1,87 @@
+use std::os::fd::OwnedFd;
+
+use crate::{
+    connection::Connection, error::Result, protocol::types::WlString, wl_request_opcode,
+    wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to a Wayland data offer object.
+    Opcode {
+        /// Accepts one of the offered MIME types during drag-and-drop.
+        Accept = 0,
+
+        /// Requests the data in one of the offered MIME types.
+        Receive = 1,
+
+        /// Destroys the data offer.
+        Destroy = 2 (destructor),
+
+        /// Notifies the source that a drag-and-drop operation finished (since version 3).
+        Finish = 3,
+
+        /// Sets the drag-and-drop actions accepted by the client (since version 3).
+        SetActions = 4,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wl_data_offer.receive` request.
+    ///
+    /// The `fd` argument is not part of the payload: file descriptors travel
+    /// out of band as ancillary data of the socket message.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="receive">
+    ///   <description summary="request that the data is transferred">
+    ///     To transfer the offered data, the client issues this request
+    ///     and indicates the mime type it wants to receive.  The transfer
+    ///     happens through the passed file descriptor (typically created
+    ///     with the pipe system call).  The source client writes the data
+    ///     in the mime type representation requested and then closes the
+    ///     file descriptor.
+    ///
+    ///     The receiving client reads from the read end of the pipe until
+    ///     EOF and then closes its end, at which point the transfer is
+    ///     complete.
+    ///
+    ///     This request may happen multiple times for different mime types,
+    ///     both before and after wl_data_device.drop. Drag-and-drop destination
+    ///     clients may preemptively fetch data or examine it more closely to
+    ///     determine acceptance.
+    ///   </description>
+    ///   <arg name="mime_type" type="string" summary="mime type desired by receiver"/>
+    ///   <arg name="fd" type="fd" summary="file descriptor for data transfer"/>
+    /// </request>
+    /// ```
+    ReceiveParam {
+        /// The MIME type the data is requested as.
+        mime_type: WlString,
+    }
+}
+
+/// Sends a `wl_data_offer.receive` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `offer` - The ID of the data offer
+/// * `mime_type` - One of the MIME types announced by the offer
+/// * `fd` - The write end of the pipe the data is sent through, closed once sent
+pub fn receive(conn: &Connection, offer: u32, mime_type: WlString, fd: OwnedFd) -> Result<()> {
+    conn.send_request_with_fds(
+        offer,
+        Opcode::Receive,
+        &ReceiveParam::new(mime_type),
+        vec![fd],
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `wl_data_offer.destroy` request to the compositor.
+pub fn destroy(conn: &Connection, offer: u32) -> Result<()> {
+    conn.send_request(offer, Opcode::Destroy, &())?;
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use crate::{
+    error::{Error, Result},
+    protocol::types::WlUInt,
+};
+
+/// Represents a `wl_data_source.action` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="action" since="3">
+///   <description summary="notify the selected action">
+///     This event indicates the action selected by the compositor after
+///     matching the source/destination side actions. Only one action (or
+///     none) will be offered here.
+///
+///     Clients can trigger cursor surface changes from this point, so
+///     they reflect the current action.
+///   </description>
+///   <arg name="dnd_action" type="uint" summary="action selected by the compositor"
+///        enum="wl_data_device_manager.dnd_action"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Action {
+    /// The action selected by the compositor, a single
+    /// `wl_data_device_manager.dnd_action` value or none.
+    pub dnd_action: WlUInt,
+}
+
+impl TryFrom<&[u8]> for Action {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Action` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `dnd_action` (u32) - The selected action
+    fn try_from(buf: &[u8]) -> Result<Action> {
+        Ok(Action {
+            dnd_action: WlUInt::read(buf, 0)?,
+        })
+    }
+}
@@ -0,0 +1,50 @@
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::data_source::{DataSourceState, request},
+};
+
+/// Handles a `wl_data_source.cancelled` event by marking the source as
+/// replaced and destroying it.
+///
+/// The event has no arguments. The data of the source is dropped, which
+/// releases whatever a writer closure captured; transfers already in
+/// progress still run to completion.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="cancelled">
+///   <description summary="selection was cancelled">
+///     This data source is no longer valid. There are several reasons why
+///     this could happen:
+///
+///     - The data source has been replaced by another data source.
+///     - The drag-and-drop operation was performed, but the drop destination
+///       did not accept any of the mime types offered through
+///       wl_data_source.target.
+///     - The drag-and-drop operation was performed, but the drop destination
+///       did not select any of the actions present in the mask offered through
+///       wl_data_source.action.
+///     - The drag-and-drop operation was performed but didn't happen over a
+///       surface.
+///     - The compositor cancelled the drag-and-drop operation (e.g. compositor
+///       dependent timeouts to avoid stale drag-and-drop transfers).
+///
+///     The client should clean up and destroy this data source.
+///   </description>
+/// </event>
+/// ```
+pub(super) fn handle_wl_data_source_cancelled(conn: &Connection, source_id: u32) -> Result<()> {
+    let Some(state) = conn.object_data::<DataSourceState>(source_id) else {
+        return Ok(());
+    };
+
+    state.cancelled.set(true);
+    state.content.borrow_mut().take();
+
+    if !state.destroyed.replace(true) {
+        request::destroy(conn, source_id)?;
+    }
+
+    Ok(())
+}
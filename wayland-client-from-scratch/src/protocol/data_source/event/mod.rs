@@ -0,0 +1,139 @@
+pub mod action;
+pub mod cancelled;
+pub mod send;
+pub mod target;
+
+use crate::{
+    connection::Connection,
+    error::{Error, Result, anyhow},
+    protocol::message::WlMessage,
+};
+
+/// Represents the event types that can be emitted by a Wayland data source object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    /// A target accepted a MIME type, or none, during drag-and-drop.
+    ///
+    /// # Event Arguments
+    /// - `mime_type`: The accepted MIME type, or null
+    Target = 0,
+
+    /// A client requests the data.
+    ///
+    /// # Event Arguments
+    /// - `mime_type`: The MIME type to send the data as
+    /// - `fd`: The file descriptor to write the data to
+    Send = 1,
+
+    /// The source is no longer valid.
+    Cancelled = 2,
+
+    /// The drag-and-drop drop was performed (since version 3).
+    DndDropPerformed = 3,
+
+    /// The drag-and-drop operation finished (since version 3).
+    DndFinished = 4,
+
+    /// The compositor selected a drag-and-drop action (since version 3).
+    ///
+    /// # Event Arguments
+    /// - `dnd_action`: The selected action
+    Action = 5,
+}
+
+impl TryFrom<u16> for Opcode {
+    type Error = Error;
+
+    /// Attempts to convert a raw opcode value into a structured `WlDataSourceEvent`.
+    fn try_from(value: u16) -> Result<Opcode> {
+        match value {
+            0 => Ok(Opcode::Target),
+            1 => Ok(Opcode::Send),
+            2 => Ok(Opcode::Cancelled),
+            3 => Ok(Opcode::DndDropPerformed),
+            4 => Ok(Opcode::DndFinished),
+            5 => Ok(Opcode::Action),
+            _ => Err(anyhow!("Invalid wl_data_source event opcode: {}", value)),
+        }
+    }
+}
+
+impl Opcode {
+    /// Returns the interface version the event was introduced in.
+    pub fn since(self) -> u32 {
+        match self {
+            Opcode::Target | Opcode::Send | Opcode::Cancelled => 1,
+            Opcode::DndDropPerformed | Opcode::DndFinished | Opcode::Action => 3,
+        }
+    }
+}
+
+/// A decoded `wl_data_source` event, with its arguments.
+#[derive(Debug)]
+pub enum Event {
+    /// A target accepted a MIME type, or none, during drag-and-drop.
+    Target(target::Target),
+    /// A client requests the data.
+    Send(send::Send),
+    /// The source is no longer valid.
+    Cancelled,
+    /// The drag-and-drop drop was performed (since version 3).
+    DndDropPerformed,
+    /// The drag-and-drop operation finished (since version 3).
+    DndFinished,
+    /// The compositor selected a drag-and-drop action (since version 3).
+    Action(action::Action),
+}
+
+impl Event {
+    /// Decodes a `wl_data_source` event from its message.
+    ///
+    /// The file descriptors the event carries are taken from those
+    /// received on `conn`, in order.
+    ///
+    /// # Errors
+    /// Returns an error if the opcode is unknown or the arguments are malformed.
+    pub fn decode(conn: &Connection, msg: &WlMessage) -> Result<Event> {
+        let buf: &[u8] = &msg.data;
+
+        Ok(match Opcode::try_from(msg.header.opcode)? {
+            Opcode::Target => Event::Target(target::Target::try_from(buf)?),
+            Opcode::Send => Event::Send(send::Send::parse(buf, conn.take_fd()?)?),
+            Opcode::Cancelled => Event::Cancelled,
+            Opcode::DndDropPerformed => Event::DndDropPerformed,
+            Opcode::DndFinished => Event::DndFinished,
+            Opcode::Action => Event::Action(action::Action::try_from(buf)?),
+        })
+    }
+
+    /// Returns the opcode of the event.
+    pub fn opcode(&self) -> Opcode {
+        match self {
+            Event::Target(_) => Opcode::Target,
+            Event::Send(_) => Opcode::Send,
+            Event::Cancelled => Opcode::Cancelled,
+            Event::DndDropPerformed => Opcode::DndDropPerformed,
+            Event::DndFinished => Opcode::DndFinished,
+            Event::Action(_) => Opcode::Action,
+        }
+    }
+}
+
+/// Dispatches incoming Wayland data source events to their appropriate handler functions.
+///
+/// # Event Routing
+/// * `Send` events are routed to `send::handle_wl_data_source_send`
+/// * `Cancelled` events are routed to `cancelled::handle_wl_data_source_cancelled`
+/// * Drag-and-drop events are ignored, as sources are only used for the selection
+pub fn handle_wl_data_source_event(conn: &Connection, msg: WlMessage) -> Result<()> {
+    let source_id = msg.header.object_id;
+
+    // Route the event to the appropriate handler based on type
+    match Event::decode(conn, &msg)? {
+        Event::Send(event) => send::handle_wl_data_source_send(conn, source_id, event),
+        Event::Cancelled => cancelled::handle_wl_data_source_cancelled(conn, source_id),
+        Event::Target(_) | Event::DndDropPerformed | Event::DndFinished | Event::Action(_) => {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use std::{
+    io,
+    os::fd::{AsFd, OwnedFd},
+    sync::Arc,
+};
+
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::{
+        data_source::{DataSourceContent, DataSourceState},
+        types::WlString,
+    },
+    sys,
+};
+
+/// Represents a `wl_data_source.send` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="send">
+///   <description summary="send the data">
+///     Request for data from the client.  Send the data as the
+///     specified mime type over the passed file descriptor, then
+///     close it.
+///   </description>
+///   <arg name="mime_type" type="string" summary="mime type for the data"/>
+///   <arg name="fd" type="fd" summary="file descriptor for the data"/>
+/// </event>
+/// ```
+#[derive(Debug)]
+pub struct Send {
+    /// The MIME type the data is requested as.
+    pub mime_type: WlString,
+    /// The write end of the pipe to send the data through.
+    pub fd: OwnedFd,
+}
+
+impl Send {
+    /// Parses the event arguments and takes ownership of the passed file descriptor.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0+: `mime_type` (WlString) - The requested MIME type with length prefix
+    ///
+    /// The `fd` argument travels out of band as ancillary data.
+    pub(crate) fn parse(buf: &[u8], fd: OwnedFd) -> Result<Send> {
+        Ok(Send {
+            mime_type: buf.try_into()?,
+            fd,
+        })
+    }
+}
+
+/// Handles a `wl_data_source.send` event by writing the source data to the
+/// passed file descriptor and closing it.
+///
+/// The descriptor is switched to non-blocking mode and written from the
+/// following dispatches whenever it is writable, so a slow receiver never
+/// stalls the event loop. Write errors and writer errors are ignored: they
+/// only close the transfer early, which must not take down this connection.
+pub(super) fn handle_wl_data_source_send(
+    conn: &Connection,
+    source_id: u32,
+    send: Send,
+) -> Result<()> {
+    let mime_type = conn.decode_string(&send.mime_type)?;
+
+    let Some(state) = conn.object_data::<DataSourceState>(source_id) else {
+        return Ok(());
+    };
+
+    if !state.mime_types.contains(&mime_type) {
+        return Ok(());
+    }
+
+    let data: Arc<[u8]> = match state.content.borrow_mut().as_mut() {
+        Some(DataSourceContent::Data(data)) => data.clone(),
+        Some(DataSourceContent::Writer(writer)) => match writer(&mime_type) {
+            Ok(data) => data.into(),
+            Err(_) => return Ok(()),
+        },
+        None => return Ok(()),
+    };
+
+    if sys::set_nonblocking(send.fd.as_fd()).is_err() {
+        return Ok(());
+    }
+
+    let mut written = 0;
+    conn.watch_writable(
+        send.fd,
+        Box::new(move |_, fd| {
+            while written < data.len() {
+                match sys::write(fd, &data[written..]) {
+                    Ok(len) => written += len,
+                    Err(error) if error.kind() == io::ErrorKind::WouldBlock => return true,
+                    Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                    Err(_) => return false,
+                }
+            }
+            false
+        }),
+    );
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use crate::{
+    error::{Error, Result},
+    protocol::types::{WlString, WlUInt},
+};
+
+/// Represents a `wl_data_source.target` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="target">
+///   <description summary="a target accepts an offered mime type">
+///     Sent when a target accepts pointer_focus or motion events.  If
+///     a target does not accept any of the offered types, type is NULL.
+///
+///     Used for feedback during drag-and-drop.
+///   </description>
+///   <arg name="mime_type" type="string" allow-null="true" summary="mime type accepted by the target"/>
+/// </event>
+/// ```
+#[derive(Debug)]
+pub struct Target {
+    /// The MIME type accepted by the target, or `None` if it accepts none.
+    pub mime_type: Option<WlString>,
+}
+
+impl TryFrom<&[u8]> for Target {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Target` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: the length of `mime_type`, 0 for a null string
+    /// - Bytes 4+: `mime_type` (WlString) - The accepted MIME type, if not null
+    fn try_from(buf: &[u8]) -> Result<Target> {
+        let mime_type = match WlUInt::read(buf, 0)?.get() {
+            0 => None,
+            _ => Some(buf.try_into()?),
+        };
+
+        Ok(Target { mime_type })
+    }
+}
@@ -0,0 +1,122 @@
+pub mod event;
+pub mod request;
+
+use std::sync::Arc;
+
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::types::WlString,
+    sync::{Lock, LockCell},
+};
+
+/// A closure producing the data of a source in the requested MIME type.
+pub(crate) type DataWriter = Box<dyn FnMut(&str) -> Result<Vec<u8>> + Send>;
+
+/// Where the data sent to requesting clients comes from.
+pub(crate) enum DataSourceContent {
+    /// The same bytes, whichever MIME type is requested.
+    Data(Arc<[u8]>),
+    /// Data produced on demand for each request.
+    Writer(DataWriter),
+}
+
+/// State of a data source shared with its event handlers.
+pub(crate) struct DataSourceState {
+    /// The MIME types the data is offered as.
+    mime_types: Vec<String>,
+    /// The data sent to requesting clients, dropped once the source is cancelled.
+    content: Lock<Option<DataSourceContent>>,
+    /// Set once the compositor stopped using the source.
+    cancelled: LockCell<bool>,
+    /// Set once the `destroy` request was sent.
+    destroyed: LockCell<bool>,
+}
+
+/// A `wl_data_source`, data this client offers to other clients.
+///
+/// Created with
+/// [`DataDeviceManager::create_data_source`](crate::protocol::data_device_manager::DataDeviceManager::create_data_source).
+pub struct DataSource {
+    /// The object ID of the data source.
+    id: u32,
+    /// The connection the data source was created on.
+    conn: Connection,
+    /// The state shared with the event handlers.
+    state: Arc<DataSourceState>,
+}
+
+impl DataSource {
+    /// Wraps a data source object that was just created on `conn` and
+    /// offers each of `mime_types`.
+    pub(crate) fn new(
+        id: u32,
+        conn: &Connection,
+        mime_types: &[&str],
+        content: DataSourceContent,
+    ) -> Result<DataSource> {
+        for mime_type in mime_types {
+            request::offer(conn, id, WlString::new(mime_type)?)?;
+        }
+
+        let state = Arc::new(DataSourceState {
+            mime_types: mime_types
+                .iter()
+                .map(|mime_type| mime_type.to_string())
+                .collect(),
+            content: Lock::new(Some(content)),
+            cancelled: LockCell::new(false),
+            destroyed: LockCell::new(false),
+        });
+        conn.set_object_data(id, state.clone());
+
+        Ok(DataSource {
+            id,
+            conn: conn.clone(),
+            state,
+        })
+    }
+
+    /// Returns the object ID of the data source.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the data sent to requesting clients.
+    ///
+    /// # Returns
+    /// `None` if the data is produced on demand, or once the source was cancelled.
+    pub fn data(&self) -> Option<Arc<[u8]>> {
+        match self.state.content.borrow().as_ref()? {
+            DataSourceContent::Data(data) => Some(data.clone()),
+            DataSourceContent::Writer(_) => None,
+        }
+    }
+
+    /// Returns `true` once the compositor stopped using the source, typically
+    /// because another client took over the selection.
+    ///
+    /// A cancelled source was already destroyed; transfers in progress still
+    /// run to completion.
+    pub fn is_cancelled(&self) -> bool {
+        self.state.cancelled.get()
+    }
+
+    /// Destroys the data source, unless it was destroyed when it got cancelled.
+    ///
+    /// Dropping the handle does the same.
+    pub fn destroy(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl Drop for DataSource {
+    fn drop(&mut self) {
+        if !self.state.destroyed.replace(true) {
+            self.conn
+                .destroy_on_drop(|conn| request::destroy(conn, self.id));
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use crate::{
+    connection::Connection, error::Result, protocol::types::WlString, wl_request_opcode,
+    wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to a Wayland data source object.
+    Opcode {
+        /// Adds a MIME type to the set offered by the source.
+        Offer = 0,
+
+        /// Destroys the data source.
+        Destroy = 1 (destructor),
+
+        /// Sets the drag-and-drop actions supported by the source (since version 3).
+        SetActions = 2,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wl_data_source.offer` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="offer">
+    ///   <description summary="add an offered mime type">
+    ///     This request adds a mime type to the set of mime types
+    ///     advertised to targets.  Can be called several times to offer
+    ///     multiple types.
+    ///   </description>
+    ///   <arg name="mime_type" type="string" summary="mime type offered by the data source"/>
+    /// </request>
+    /// ```
+    OfferParam {
+        /// The MIME type offered by the data source.
+        mime_type: WlString,
+    }
+}
+
+/// Sends a `wl_data_source.offer` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `source` - The ID of the data source
+/// * `mime_type` - The MIME type to offer
+pub fn offer(conn: &Connection, source: u32, mime_type: WlString) -> Result<()> {
+    conn.send_request(source, Opcode::Offer, &OfferParam::new(mime_type))?;
+
+    Ok(())
+}
+
+/// Sends a `wl_data_source.destroy` request to the compositor.
+pub fn destroy(conn: &Connection, source: u32) -> Result<()> {
+    conn.send_request(source, Opcode::Destroy, &())?;
+
+    Ok(())
+}
@@ -1,3 +1,57 @@
-pub(super) fn handle_wl_display_delete_id(_buf: &[u8]) -> anyhow::Result<()> {
-    todo!()
+use crate::{
+    connection::Connection,
+    error::{Error, Result, anyhow},
+    protocol::types::{WL_TYPE_UINT_LEN, WlUInt},
+};
+
+/// Represents a `wl_display.delete_id` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="delete_id">
+///   <description summary="acknowledge object ID deletion">
+///     This event is used internally by the object ID management
+///     logic. When a client deletes an object that it had created,
+///     the server will send this event to acknowledge that it has
+///     seen the delete request. When the client receives this event,
+///     it will know that it can safely reuse the object ID.
+///   </description>
+///   <arg name="id" type="uint" summary="deleted object ID"/>
+/// </event>
+/// ```
+#[derive(Debug)]
+pub struct DeleteId {
+    /// The ID of the object whose deletion the compositor acknowledges.
+    pub id: WlUInt,
+}
+
+impl TryFrom<&[u8]> for DeleteId {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `DeleteId`.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `id` (u32) - The deleted object ID
+    fn try_from(buf: &[u8]) -> Result<DeleteId> {
+        if buf.len() < WL_TYPE_UINT_LEN {
+            return Err(anyhow!(
+                "Buffer too short for WlDisplayDeleteId id: expected {} bytes, got {}",
+                WL_TYPE_UINT_LEN,
+                buf.len()
+            ));
+        }
+        let id = WlUInt::from_bytes(buf[..WL_TYPE_UINT_LEN].try_into()?);
+
+        Ok(DeleteId { id })
+    }
+}
+
+/// Handles a `wl_display.delete_id` event.
+///
+/// Removes the object from the connection's object map, which makes its ID
+/// available for reuse by the next object the client creates.
+pub(super) fn handle_wl_display_delete_id(conn: &Connection, delete_id: DeleteId) -> Result<()> {
+    conn.remove_object(delete_id.id.get());
+
+    Ok(())
 }
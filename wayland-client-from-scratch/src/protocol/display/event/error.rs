@@ -1,6 +1,5 @@
-use anyhow::anyhow;
-
 use crate::{
+    error::{Result, anyhow},
     protocol::types::{WL_TYPE_ENUM_LEN, WL_TYPE_OBJECT_LEN, WlEnum, WlObject, WlString},
     wl_enum,
 };
@@ -35,6 +34,7 @@ wl_enum! {
 /// This structure contains all the information from a wl_display.error event,
 /// including the object where the error occurred, the specific error code,
 /// and a human-readable message for debugging purposes.
+#[derive(Debug)]
 pub struct Error {
     /// The object ID where the error occurred, typically the target of a failed request.
     object_id: WlObject,
@@ -62,7 +62,7 @@ impl std::fmt::Display for Error {
 }
 
 impl TryFrom<&[u8]> for Error {
-    type Error = anyhow::Error;
+    type Error = crate::error::Error;
 
     /// Parses a raw byte buffer into a structured `WlDisplayError`.
     ///
@@ -71,7 +71,7 @@ impl TryFrom<&[u8]> for Error {
     ///
     /// # Returns
     /// * `Ok(WlDisplayError)` if the buffer contains valid error data
-    /// * `Err(anyhow::Error)` if the buffer is malformed or incomplete
+    /// * `Err(crate::error::Error)` if the buffer is malformed or incomplete
     ///
     /// # Buffer Layout
     /// The error event data is structured as:
@@ -88,7 +88,7 @@ impl TryFrom<&[u8]> for Error {
     ///   <arg name="message" type="string" summary="error description"/>
     /// </event>
     /// ```
-    fn try_from(buf: &[u8]) -> anyhow::Result<Error> {
+    fn try_from(buf: &[u8]) -> Result<Error> {
         // Extract object_id(WlObject) from buffer
         if buf.len() < WL_TYPE_OBJECT_LEN {
             return Err(anyhow!(
@@ -136,7 +136,7 @@ impl TryFrom<&[u8]> for Error {
 /// * `buf` - The raw byte buffer containing the error event data
 ///
 /// # Returns
-/// * `Err(anyhow::Error)` - Always returns an error since these are fatal conditions
+/// * `Err(crate::error::Error)` - Always returns an error since these are fatal conditions
 ///
 /// # Behavior
 /// - Parses the error event into structured data
@@ -147,9 +147,7 @@ impl TryFrom<&[u8]> for Error {
 /// According to the Wayland specification, error events are fatal and non-recoverable.
 /// When a client receives this event, it should typically terminate the connection
 /// as the protocol state may be compromised.
-pub(super) fn handle_wl_display_error(buf: &[u8]) -> anyhow::Result<()> {
-    let error = Error::try_from(buf)?;
-
+pub(super) fn handle_wl_display_error(error: Error) -> Result<()> {
     // Propagate the error to signal that the connection should be terminated
     Err(anyhow!("Fatal Wayland protocol error: {}", error))
 }
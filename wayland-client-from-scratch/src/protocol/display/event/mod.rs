@@ -1,9 +1,11 @@
 pub mod delete_id;
 pub mod error;
 
-use anyhow::anyhow;
-
-use crate::protocol::message::WlMessage;
+use crate::{
+    connection::Connection,
+    error::{Error, Result, anyhow},
+    protocol::message::WlMessage,
+};
 
 /// Represents the event types that can be emitted by the Wayland display object.
 ///
@@ -11,7 +13,7 @@ use crate::protocol::message::WlMessage;
 /// internal Wayland protocol features. It serves as the entry point for clients to
 /// connect to the compositor and manage protocol-level operations.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum Event {
+pub enum Opcode {
     /// Indicates a fatal (non-recoverable) error has occurred in the protocol.
     ///
     /// This event is sent when a serious error occurs, typically in response to a
@@ -36,8 +38,8 @@ pub enum Event {
     DeleteId = 1,
 }
 
-impl TryFrom<u16> for Event {
-    type Error = anyhow::Error;
+impl TryFrom<u16> for Opcode {
+    type Error = Error;
 
     /// Attempts to convert a raw opcode value into a structured `WlDisplayEvent`.
     ///
@@ -46,20 +48,61 @@ impl TryFrom<u16> for Event {
     ///
     /// # Returns
     /// * `Ok(WlDisplayEvent)` if the opcode corresponds to a known display event type
-    /// * `Err(anyhow::Error)` if the opcode is unrecognized
+    /// * `Err(Error)` if the opcode is unrecognized
     ///
     /// # Protocol Context
     /// The display object uses opcode 0 for error notifications and opcode 1 for
     /// delete ID acknowledgments as defined in the Wayland core protocol specification.
-    fn try_from(value: u16) -> anyhow::Result<Event> {
+    fn try_from(value: u16) -> Result<Opcode> {
         match value {
-            0 => Ok(Event::Error),
-            1 => Ok(Event::DeleteId),
+            0 => Ok(Opcode::Error),
+            1 => Ok(Opcode::DeleteId),
             _ => Err(anyhow!("Invalid wl_display event opcode: {}", value)),
         }
     }
 }
 
+impl Opcode {
+    /// Returns the interface version the event was introduced in.
+    pub fn since(self) -> u32 {
+        match self {
+            Opcode::Error | Opcode::DeleteId => 1,
+        }
+    }
+}
+
+/// A decoded `wl_display` event, with its arguments.
+#[derive(Debug)]
+pub enum Event {
+    /// Indicates a fatal (non-recoverable) error has occurred in the protocol.
+    Error(error::Error),
+    /// Acknowledges object ID deletion and allows safe ID reuse.
+    DeleteId(delete_id::DeleteId),
+}
+
+impl Event {
+    /// Decodes a `wl_display` event from its message.
+    ///
+    /// # Errors
+    /// Returns an error if the opcode is unknown or the arguments are malformed.
+    pub fn decode(_conn: &Connection, msg: &WlMessage) -> Result<Event> {
+        let buf: &[u8] = &msg.data;
+
+        Ok(match Opcode::try_from(msg.header.opcode)? {
+            Opcode::Error => Event::Error(error::Error::try_from(buf)?),
+            Opcode::DeleteId => Event::DeleteId(delete_id::DeleteId::try_from(buf)?),
+        })
+    }
+
+    /// Returns the opcode of the event.
+    pub fn opcode(&self) -> Opcode {
+        match self {
+            Event::Error(_) => Opcode::Error,
+            Event::DeleteId(_) => Opcode::DeleteId,
+        }
+    }
+}
+
 /// Dispatches incoming Wayland display events to their appropriate handler functions.
 ///
 /// This function serves as the main entry point for processing events targeted at
@@ -67,11 +110,12 @@ impl TryFrom<u16> for Event {
 /// that affect the entire client-compositor connection.
 ///
 /// # Arguments
+/// * `conn` - The connection the event was received on
 /// * `msg` - The complete Wayland message containing both header and payload data
 ///
 /// # Returns
 /// * `Ok(())` if the event was successfully processed
-/// * `Err(anyhow::Error)` if the event opcode is invalid or event processing fails
+/// * `Err(Error)` if the event opcode is invalid or event processing fails
 ///
 /// # Event Routing
 /// * `Error` events are routed to `error::handle_wl_display_error` for fatal error handling
@@ -84,13 +128,10 @@ impl TryFrom<u16> for Event {
 /// - It enables synchronization between client and server via `sync`
 /// - It manages object ID lifecycle and error reporting
 ///   Events on this object typically indicate critical connection state changes.
-pub fn handle_wl_display_event(msg: WlMessage) -> anyhow::Result<()> {
-    // Decode the event type from the message opcode
-    let event_code: Event = msg.header.opcode.try_into()?;
-
+pub fn handle_wl_display_event(conn: &Connection, msg: WlMessage) -> Result<()> {
     // Route the event to the appropriate handler based on type
-    match event_code {
-        Event::Error => error::handle_wl_display_error(&msg.data),
-        Event::DeleteId => delete_id::handle_wl_display_delete_id(&msg.data),
+    match Event::decode(conn, &msg)? {
+        Event::Error(event) => error::handle_wl_display_error(event),
+        Event::DeleteId(event) => delete_id::handle_wl_display_delete_id(conn, event),
     }
 }
@@ -1,23 +1,13 @@
 use crate::{
+    connection::Connection,
+    error::Result,
     protocol::{
         WlObjectId,
-        message::{WlMessage, WlMessageIter},
-        registry::event::handle_wl_registry_event,
         types::{WlNewId, WlString},
     },
     wl_request_opcode, wl_request_param,
 };
 
-use super::event::handle_wl_display_event;
-
-use std::{
-    convert::TryInto,
-    io::{Read, Write},
-    os::unix::net::UnixStream,
-};
-
-use anyhow::anyhow;
-
 wl_request_opcode! {
     /// Represents the request types that can be sent to the Wayland display object.
     ///
@@ -94,25 +84,48 @@ wl_request_param! {
     }
 }
 
-/// Sends a `wl_display.get_registry` request to the compositor and processes the response.
+/// Sends a `wl_display.sync` request to the compositor.
+///
+/// The compositor answers with a `wl_callback.done` event on the new callback
+/// object once every request sent before this one has been processed, which
+/// makes it the building block of [`Connection::roundtrip`].
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `new_id` - The object ID to assign to the newly created wl_callback object
+///
+/// # Returns
+/// * `Ok(())` if the request was queued
+/// * `Err(Error)` if the request could not be serialized
+pub fn sync(conn: &Connection, new_id: WlNewId) -> Result<()> {
+    conn.send_request(
+        WlObjectId::Display.into(),
+        Opcode::Sync,
+        &SyncParam::new(new_id),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `wl_display.get_registry` request to the compositor.
 ///
 /// This function implements the core bootstrap sequence for Wayland clients. It requests
 /// the global registry object from the display, which provides access to all available
 /// global interfaces offered by the compositor.
 ///
 /// # Arguments
-/// * `stream` - The Unix socket stream connected to the Wayland compositor
+/// * `conn` - The connection to queue the request on
 /// * `new_id` - The object ID to assign to the newly created registry object
 ///
 /// # Returns
-/// * `Ok(())` if the request was successfully sent and all response events processed
-/// * `Err(anyhow::Error)` if any I/O operation fails or protocol errors occur
+/// * `Ok(())` if the request was queued
+/// * `Err(Error)` if the request could not be serialized
 ///
 /// # Protocol Sequence
 /// 1. Serializes the `get_registry` request with the specified new object ID
-/// 2. Sends the request message to the compositor via the Unix socket
-/// 3. Reads the compositor's response (typically a burst of global advertisement events)
-/// 4. Processes all incoming events, routing them to appropriate handlers
+/// 2. Queues the request message on the connection
+/// 3. The compositor's response (a burst of global advertisement events) is
+///    processed by the registry event handlers on the next dispatch
 ///
 /// # Expected Response Events
 /// After a successful `get_registry` request, the compositor will typically send:
@@ -137,68 +150,13 @@ wl_request_param! {
 ///        summary="global registry object"/>
 /// </request>
 /// ```
-pub fn get_registry(stream: &mut UnixStream, new_id: WlNewId) -> anyhow::Result<()> {
-    // Serialize get_registry request parameters into protocol format
-    let register_data: Vec<u8> = RequestParam::new(new_id).into();
-
-    // Construct the complete Wayland protocol message
-    let message = WlMessage::new(
+pub fn get_registry(conn: &Connection, new_id: WlNewId) -> Result<()> {
+    // Encode the request straight into the outgoing buffer
+    conn.send_request(
         WlObjectId::Display.into(),
-        Opcode::GetRegistry.into(),
-        &register_data,
-    );
-
-    // Send the message to the compositor over the Unix socket
-    let write_buf: Vec<u8> = message.into();
-    let written_len = stream.write(&write_buf)?;
-
-    // Verify the entire message was transmitted successfully
-    if write_buf.len() != written_len {
-        return Err(anyhow!(
-            "Failed to write complete wl_display_get_registry message: expected {} bytes, wrote {} bytes",
-            write_buf.len(),
-            written_len
-        ));
-    }
-
-    // Read compositor response containing events and potential errors
-    // Uses a fixed buffer size that should accommodate typical initial global bursts
-    let mut read_buf: [u8; 4096] = [0; 4096];
-    let read_len = stream.read(&mut read_buf)?;
-
-    // Process all incoming events using a message iterator
-    // The iterator handles message boundaries and buffer management
-    let mut event_iter = WlMessageIter::new(read_buf[..read_len].into());
-    loop {
-        let event = event_iter.next();
-        if event.is_none() {
-            break;
-        }
-
-        let event = event.unwrap();
-        let event_object: WlObjectId = event.header.object_id.try_into()?;
-
-        // Route events to appropriate handlers based on the target object type
-        match event_object {
-            WlObjectId::Display => {
-                // Handle display-level events (errors, sync callbacks, etc.)
-                handle_wl_display_event(event)?
-            }
-            WlObjectId::Registry => {
-                // Handle registry events (global advertisements, removals)
-                // This is the primary expected response to get_registry
-                handle_wl_registry_event(event)?
-            }
-            _ => {
-                // Unexpected object type - this may indicate a protocol violation
-                // or an extension interface we haven't implemented yet
-                unimplemented!(
-                    "Unexpected object type in get_registry response: {:?}",
-                    event_object as u32
-                )
-            }
-        }
-    }
+        Opcode::GetRegistry,
+        &RequestParam::new(new_id),
+    )?;
 
     Ok(())
 }
@@ -0,0 +1,294 @@
+use crate::{
+    error::{Result, anyhow},
+    protocol::{
+        endian::Endian,
+        message::{WL_MESSAGE_HEADER_LEN, WL_MESSAGE_MAX_LEN, WlMessageHeader},
+        types::WlFixed,
+    },
+};
+
+/// The number of bytes an [`Encoder`] holds before allocating, header
+/// included, which covers every request with fixed-size arguments and
+/// most requests with short strings.
+pub const INLINE_CAPACITY: usize = 128;
+
+/// A value that writes itself as the arguments of a request.
+///
+/// Implemented by the wire types and by the request parameters generated
+/// with [`wl_request_param!`](crate::wl_request_param), whose fields are
+/// encoded in order. `()` encodes requests without arguments.
+pub trait Encode {
+    /// Appends the wire representation of the value to `encoder`.
+    fn encode(&self, encoder: &mut Encoder);
+}
+
+impl Encode for () {
+    fn encode(&self, _encoder: &mut Encoder) {}
+}
+
+/// Implements [`Encode`] for a tuple of wire types, encoded in order, for
+/// messages without a parameter struct of their own.
+macro_rules! encode_tuple {
+    ($($arg:ident),+) => {
+        impl<$($arg: Encode),+> Encode for ($($arg,)+) {
+            #[allow(non_snake_case)]
+            fn encode(&self, encoder: &mut Encoder) {
+                let ($($arg,)+) = self;
+                $($arg.encode(encoder);)+
+            }
+        }
+    };
+}
+
+encode_tuple!(A);
+encode_tuple!(A, B);
+encode_tuple!(A, B, C);
+encode_tuple!(A, B, C, D);
+
+/// Serializes a request into a buffer held on the stack.
+///
+/// Arguments are appended in the native byte order, see [`Endian`], after
+/// room for the header, which [`Encoder::finish`] fills in once the size is
+/// known. Up to [`INLINE_CAPACITY`] bytes no allocation happens, so the
+/// requests sent every frame, such as `wl_surface.attach`, `damage_buffer`
+/// and `commit`, are queued without touching the heap; longer requests
+/// spill over to a vector.
+///
+/// # Buffer Layout
+/// - Bytes 0-3: the object ID
+/// - Bytes 4-7: the size (upper 16 bits) and the opcode (lower 16 bits)
+/// - Bytes 8-: the arguments, each padded to 32 bits
+pub struct Encoder {
+    /// The bytes while they fit.
+    inline: [u8; INLINE_CAPACITY],
+    /// The number of bytes written, header included.
+    len: usize,
+    /// The bytes once they outgrew the inline buffer, empty until then.
+    spilled: Vec<u8>,
+    /// The object the request is sent to.
+    object_id: u32,
+    /// The opcode of the request.
+    opcode: u16,
+}
+
+impl Encoder {
+    /// Starts a request with the given opcode to the object `object_id`.
+    pub fn new(object_id: u32, opcode: u16) -> Encoder {
+        Encoder {
+            inline: [0; INLINE_CAPACITY],
+            len: WL_MESSAGE_HEADER_LEN,
+            spilled: Vec::new(),
+            object_id,
+            opcode,
+        }
+    }
+
+    /// Returns the number of bytes written, header included.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no argument was written.
+    pub fn is_empty(&self) -> bool {
+        self.len == WL_MESSAGE_HEADER_LEN
+    }
+
+    /// Appends raw bytes, spilling over to the heap when they do not fit.
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        let end = self.len + bytes.len();
+        if self.spilled.is_empty() && end <= INLINE_CAPACITY {
+            self.inline[self.len..end].copy_from_slice(bytes);
+        } else {
+            if self.spilled.is_empty() {
+                self.spilled.reserve(end.next_power_of_two());
+                self.spilled.extend_from_slice(&self.inline[..self.len]);
+            }
+            self.spilled.extend_from_slice(bytes);
+        }
+        self.len = end;
+    }
+
+    /// Appends zeroes up to the next 32-bit boundary.
+    fn pad(&mut self) {
+        let padding = self.len.next_multiple_of(4) - self.len;
+        self.write_bytes(&[0; 3][..padding]);
+    }
+
+    /// Appends a 32-bit word.
+    fn write_word(&mut self, value: u32) {
+        self.write_bytes(&Endian::NATIVE.encode_u32(value));
+    }
+
+    /// Appends a `uint` argument.
+    pub fn write_uint(&mut self, value: u32) {
+        self.write_word(value);
+    }
+
+    /// Appends an `int` argument.
+    pub fn write_int(&mut self, value: i32) {
+        self.write_word(value as u32);
+    }
+
+    /// Appends a `fixed` argument.
+    pub fn write_fixed(&mut self, value: WlFixed) {
+        self.write_word(value.get() as u32);
+    }
+
+    /// Appends an `object` argument, 0 for a null object.
+    pub fn write_object(&mut self, id: u32) {
+        self.write_word(id);
+    }
+
+    /// Appends a `new_id` argument.
+    pub fn write_new_id(&mut self, id: u32) {
+        self.write_word(id);
+    }
+
+    /// Appends a `new_id` argument whose interface is not fixed by the
+    /// request: the interface name, the version and the ID.
+    ///
+    /// # Errors
+    /// Returns an error if `interface` contains a NUL byte.
+    pub fn write_untyped_new_id(&mut self, interface: &str, version: u32, id: u32) -> Result<()> {
+        self.write_string(interface)?;
+        self.write_uint(version);
+        self.write_new_id(id);
+
+        Ok(())
+    }
+
+    /// Appends a `string` argument: its length including the NUL
+    /// terminator, its bytes, the terminator and the padding.
+    ///
+    /// # Errors
+    /// Returns an error if `value` contains a NUL byte, which the receiver
+    /// would take for the end of the string.
+    pub fn write_string(&mut self, value: &str) -> Result<()> {
+        if let Some(offset) = value.bytes().position(|byte| byte == 0) {
+            return Err(anyhow!(
+                "Cannot encode a string with an interior NUL at byte offset {offset}"
+            ));
+        }
+
+        self.write_word((value.len() + 1) as u32);
+        self.write_bytes(value.as_bytes());
+        self.write_bytes(&[0]);
+        self.pad();
+
+        Ok(())
+    }
+
+    /// Appends an `array` argument: its length, its bytes and the padding.
+    pub fn write_array(&mut self, value: &[u8]) {
+        self.write_word(value.len() as u32);
+        self.write_bytes(value);
+        self.pad();
+    }
+
+    /// Appends bytes already in the wire format, such as an encoded string.
+    pub(crate) fn write_raw(&mut self, bytes: &[u8]) {
+        self.write_bytes(bytes);
+    }
+
+    /// Fills in the header and returns the complete request.
+    ///
+    /// # Errors
+    /// Returns an error if the request exceeds [`WL_MESSAGE_MAX_LEN`], which
+    /// the 16-bit size field of the header cannot describe.
+    pub fn finish(&mut self) -> Result<&[u8]> {
+        if self.len > WL_MESSAGE_MAX_LEN {
+            return Err(anyhow!(
+                "Message for object {} (opcode {}) is {} bytes long, \
+                 exceeding the limit of {WL_MESSAGE_MAX_LEN} bytes",
+                self.object_id,
+                self.opcode,
+                self.len
+            ));
+        }
+
+        let header = WlMessageHeader {
+            object_id: self.object_id,
+            opcode: self.opcode,
+            size: self.len as u16,
+        }
+        .encode(Endian::NATIVE);
+
+        let bytes = if self.spilled.is_empty() {
+            &mut self.inline[..self.len]
+        } else {
+            &mut self.spilled[..]
+        };
+        bytes[..WL_MESSAGE_HEADER_LEN].copy_from_slice(&header);
+
+        Ok(bytes)
+    }
+
+    /// Returns the header of the request with the arguments written so far.
+    pub(crate) fn header(&self) -> WlMessageHeader {
+        WlMessageHeader {
+            object_id: self.object_id,
+            opcode: self.opcode,
+            size: self.len as u16,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::{
+        message::WlMessage,
+        types::{WlArray, WlString, WlUntypedNewId},
+    };
+
+    #[test]
+    fn encoder_matches_the_message_serializer() {
+        let mut encoder = Encoder::new(7, 3);
+        encoder.write_int(-2);
+        encoder.write_string("xdg").unwrap();
+        encoder.write_array(&[1, 2, 3, 4, 5]);
+        encoder.write_fixed(WlFixed::from_f64(1.5));
+
+        let mut data = (-2i32).to_ne_bytes().to_vec();
+        data.extend(WlString::new("xdg").unwrap().to_bytes());
+        data.extend(Vec::<u8>::from(WlArray::new(&[1, 2, 3, 4, 5])));
+        data.extend(384i32.to_ne_bytes());
+        let expected: Vec<u8> = WlMessage::new(7, 3, &data).unwrap().into();
+
+        assert_eq!(encoder.finish().unwrap(), expected);
+    }
+
+    #[test]
+    fn long_requests_spill_over_to_the_heap() {
+        let title = "a".repeat(INLINE_CAPACITY);
+        let mut encoder = Encoder::new(9, 2);
+        encoder.write_string(&title).unwrap();
+
+        let data = WlString::new(&title).unwrap().to_bytes();
+        let expected: Vec<u8> = WlMessage::new(9, 2, &data).unwrap().into();
+        assert_eq!(encoder.finish().unwrap(), expected);
+
+        let mut encoder = Encoder::new(9, 2);
+        encoder.write_array(&vec![0; WL_MESSAGE_MAX_LEN]);
+        assert!(encoder.finish().is_err());
+    }
+
+    #[test]
+    fn untyped_new_ids_round_trip() {
+        let mut encoder = Encoder::new(2, 0);
+        encoder.write_untyped_new_id("wl_seat", 9, 12).unwrap();
+        let bytes = encoder.finish().unwrap().to_vec();
+
+        let mut expected = Encoder::new(2, 0);
+        WlUntypedNewId::new("wl_seat", 9, 12)
+            .unwrap()
+            .encode(&mut expected);
+        assert_eq!(bytes, expected.finish().unwrap());
+
+        let decoded = WlUntypedNewId::try_from(&bytes[WL_MESSAGE_HEADER_LEN..]).unwrap();
+        assert_eq!(decoded.interface().as_str(), "wl_seat");
+        assert_eq!((decoded.version(), decoded.id()), (9, 12));
+
+        assert!(encoder.write_untyped_new_id("wl\0seat", 9, 12).is_err());
+    }
+}
@@ -0,0 +1,113 @@
+/// The byte order of values on the wire.
+///
+/// Wayland runs over a Unix socket between processes on the same host, so
+/// the protocol has no byte order of its own: every value, including the
+/// header words, is encoded in the native byte order of the host, and there
+/// is no negotiation. All encoding and decoding in this crate goes through
+/// [`Endian::NATIVE`], so big-endian hosts work like little-endian ones.
+///
+/// The other byte order only matters for bytes produced on another host,
+/// such as recorded traces, whose headers can be decoded with an explicit
+/// [`Endian`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Endian {
+    /// Least significant byte first, as on x86 and most ARM hosts.
+    Little,
+    /// Most significant byte first.
+    Big,
+}
+
+impl Endian {
+    /// The byte order of the host, used on the wire.
+    pub(crate) const NATIVE: Endian = if cfg!(target_endian = "big") {
+        Endian::Big
+    } else {
+        Endian::Little
+    };
+
+    /// Returns the opposite byte order, to build foreign-endian fixtures.
+    #[cfg(test)]
+    pub(crate) fn swapped(self) -> Endian {
+        match self {
+            Endian::Little => Endian::Big,
+            Endian::Big => Endian::Little,
+        }
+    }
+
+    /// Encodes a 32-bit word in this byte order.
+    pub(crate) fn encode_u32(self, value: u32) -> [u8; 4] {
+        match self {
+            Endian::Little => value.to_le_bytes(),
+            Endian::Big => value.to_be_bytes(),
+        }
+    }
+
+    /// Decodes a 32-bit word encoded in this byte order.
+    pub(crate) fn decode_u32(self, bytes: [u8; 4]) -> u32 {
+        match self {
+            Endian::Little => u32::from_le_bytes(bytes),
+            Endian::Big => u32::from_be_bytes(bytes),
+        }
+    }
+
+    /// Decodes the 32-bit word at the start of `bytes`.
+    ///
+    /// # Panics
+    /// Panics if `bytes` is shorter than 4 bytes; callers check lengths first.
+    pub(crate) fn decode_u32_at(self, bytes: &[u8]) -> u32 {
+        self.decode_u32([bytes[0], bytes[1], bytes[2], bytes[3]])
+    }
+
+    /// Decodes the 64-bit value at the start of `bytes`, such as a `dev_t`
+    /// or a DRM format modifier carried in an array.
+    ///
+    /// # Panics
+    /// Panics if `bytes` is shorter than 8 bytes; callers check lengths first.
+    pub(crate) fn decode_u64_at(self, bytes: &[u8]) -> u64 {
+        let bytes = [
+            bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7],
+        ];
+        match self {
+            Endian::Little => u64::from_le_bytes(bytes),
+            Endian::Big => u64::from_be_bytes(bytes),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_in_both_orders() {
+        assert_eq!(Endian::Little.encode_u32(0x0102_0304), [4, 3, 2, 1]);
+        assert_eq!(Endian::Big.encode_u32(0x0102_0304), [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn native_matches_host() {
+        assert_eq!(
+            Endian::NATIVE.encode_u32(0x0102_0304),
+            0x0102_0304u32.to_ne_bytes()
+        );
+    }
+
+    #[test]
+    fn round_trips_in_both_orders() {
+        for endian in [Endian::Little, Endian::Big] {
+            let bytes = endian.encode_u32(0xDEAD_BEEF);
+            assert_eq!(endian.decode_u32(bytes), 0xDEAD_BEEF);
+            assert_eq!(endian.swapped().decode_u32(bytes), 0xEFBE_ADDE);
+        }
+    }
+
+    #[test]
+    fn decodes_64_bit_values() {
+        let bytes = 0x0102_0304_0506_0708u64.to_ne_bytes();
+        assert_eq!(Endian::NATIVE.decode_u64_at(&bytes), 0x0102_0304_0506_0708);
+        assert_eq!(
+            Endian::NATIVE.swapped().decode_u64_at(&bytes),
+            0x0807_0605_0403_0201
+        );
+    }
+}
@@ -0,0 +1,116 @@
+pub mod request;
+
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::{
+        WlObjectId,
+        surface::Surface,
+        types::{WlNewId, WlObject},
+    },
+};
+
+/// The highest `wp_fifo_manager_v1` version this crate implements.
+pub const WP_FIFO_MANAGER_VERSION: u32 = 1;
+
+/// A bound `wp_fifo_manager_v1` global, which lets surfaces queue their
+/// content updates so each one is shown for at least one refresh cycle.
+#[derive(Clone)]
+pub struct FifoManager {
+    /// The object ID of the bound manager.
+    id: u32,
+    /// The negotiated interface version.
+    version: u32,
+    /// The connection the manager was bound on.
+    conn: Connection,
+}
+
+impl FifoManager {
+    /// Binds the `wp_fifo_manager_v1` global advertised by the compositor.
+    pub fn bind(conn: &Connection) -> Result<FifoManager> {
+        let (id, version) = conn.bind(WlObjectId::FifoManager, WP_FIFO_MANAGER_VERSION)?;
+
+        Ok(FifoManager {
+            id,
+            version,
+            conn: conn.clone(),
+        })
+    }
+
+    /// Returns the object ID of the manager.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Creates the fifo object of `surface`. A surface can only have one
+    /// fifo object at a time.
+    pub fn get_fifo(&self, surface: &Surface) -> Result<Fifo> {
+        self.conn
+            .check_same_connection(surface.connection(), WlObjectId::Surface, surface.id())?;
+
+        let id = self.conn.new_object(WlObjectId::Fifo, self.version)?;
+        request::get_fifo(&self.conn, self.id, WlNewId(id), WlObject(surface.id()))?;
+
+        Ok(Fifo {
+            id,
+            conn: self.conn.clone(),
+        })
+    }
+
+    /// Destroys the manager; existing fifo objects keep working.
+    pub fn destroy(self) -> Result<()> {
+        request::destroy(&self.conn, self.id)
+    }
+}
+
+/// A `wp_fifo_v1`, the first-in first-out constraints of the content
+/// updates of a surface.
+///
+/// Committing every frame with both [`Fifo::set_barrier`] and
+/// [`Fifo::wait_barrier`] gives the behavior of a FIFO swap interval: each
+/// update waits for the previous one to have been latched for a refresh
+/// cycle, without blocking the client. Like all surface state, both apply
+/// on the next commit of the surface.
+pub struct Fifo {
+    /// The object ID of the fifo object.
+    id: u32,
+    /// The connection the fifo object was created on.
+    conn: Connection,
+}
+
+impl Fifo {
+    /// Returns the object ID of the fifo object.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Sets a barrier on the surface when the next commit is applied,
+    /// cleared at the following latching deadline.
+    pub fn set_barrier(&self) -> Result<()> {
+        request::set_barrier(&self.conn, self.id)
+    }
+
+    /// Holds the next commit back while a barrier is set on the surface.
+    ///
+    /// Compositors may ignore the constraint while the surface is hidden,
+    /// so clients still throttle with frame callbacks or timestamps.
+    pub fn wait_barrier(&self) -> Result<()> {
+        request::wait_barrier(&self.conn, self.id)
+    }
+
+    /// Destroys the fifo object; committed constraints still apply.
+    ///
+    /// Dropping the handle does the same.
+    pub fn destroy(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl Drop for Fifo {
+    fn drop(&mut self) {
+        self.conn
+            .destroy_on_drop(|conn| request::destroy_fifo(conn, self.id));
+    }
+}
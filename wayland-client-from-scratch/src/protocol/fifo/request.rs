@@ -0,0 +1,150 @@
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::types::{WlNewId, WlObject},
+    wl_request_opcode, wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to the `wp_fifo_manager_v1` object.
+    Opcode {
+        /// Destroys the manager; existing fifo objects keep working.
+        Destroy = 0 (destructor),
+
+        /// Creates the fifo object of a surface.
+        GetFifo = 1,
+    }
+}
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to a `wp_fifo_v1` object.
+    FifoOpcode {
+        /// Sets a barrier the next commit clears once it is presented.
+        SetBarrier = 0,
+
+        /// Makes the next commit wait for the barrier to be cleared.
+        WaitBarrier = 1,
+
+        /// Destroys the fifo object.
+        Destroy = 2 (destructor),
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wp_fifo_manager_v1.get_fifo` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="get_fifo">
+    ///   <description summary="request fifo interface for surface">
+    ///     Establish a fifo object for a surface that may be used to add
+    ///     display refresh constraints to content updates.
+    ///
+    ///     Only one such object may exist for a surface and attempting
+    ///     to create more than one will result in an already_exists
+    ///     protocol error. If a surface is acted on by multiple software
+    ///     components, general best practice is that only the component
+    ///     performing wl_surface.attach operations should use this protocol.
+    ///   </description>
+    ///   <arg name="id" type="new_id" interface="wp_fifo_v1"/>
+    ///   <arg name="surface" type="object" interface="wl_surface"/>
+    /// </request>
+    /// ```
+    GetFifoParam {
+        /// The object ID to assign to the new fifo object.
+        id: WlNewId,
+        /// The surface whose content updates are constrained.
+        surface: WlObject,
+    }
+}
+
+/// Sends a `wp_fifo_manager_v1.destroy` request to the compositor.
+pub fn destroy(conn: &Connection, manager: u32) -> Result<()> {
+    conn.send_request(manager, Opcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends a `wp_fifo_manager_v1.get_fifo` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `manager` - The ID of the bound fifo manager
+/// * `id` - The object ID to assign to the new fifo object
+/// * `surface` - The surface whose content updates are constrained
+pub fn get_fifo(conn: &Connection, manager: u32, id: WlNewId, surface: WlObject) -> Result<()> {
+    conn.send_request(manager, Opcode::GetFifo, &GetFifoParam::new(id, surface))?;
+
+    Ok(())
+}
+
+/// Sends a `wp_fifo_v1.set_barrier` request to the compositor.
+///
+/// # Specification Reference
+/// ```xml
+/// <request name="set_barrier">
+///   <description summary="sets the start point for a fifo constraint">
+///     When the content update containing the "set_barrier" is applied,
+///     it sets a "fifo_barrier" condition on the surface associated with
+///     the fifo object. The condition is cleared immediately after the
+///     following latching deadline for non-tearing presentation.
+///
+///     The compositor may clear the condition early if it must do so to
+///     ensure client forward progress assumptions.
+///
+///     To wait for this condition to clear, use the "wait_barrier" request.
+///
+///     "set_barrier" is double-buffered state, see wl_surface.commit.
+///
+///     Requesting set_barrier after the fifo object's surface is
+///     destroyed will generate a "surface_destroyed" error.
+///   </description>
+/// </request>
+/// ```
+pub fn set_barrier(conn: &Connection, fifo: u32) -> Result<()> {
+    conn.send_request(fifo, FifoOpcode::SetBarrier, &())?;
+
+    Ok(())
+}
+
+/// Sends a `wp_fifo_v1.wait_barrier` request to the compositor.
+///
+/// # Specification Reference
+/// ```xml
+/// <request name="wait_barrier">
+///   <description summary="adds a fifo constraint to a content update">
+///     Indicate that this content update is not ready while a
+///     "fifo_barrier" condition is present on the surface.
+///
+///     This means that when the content update containing "set_barrier"
+///     was made active at a latching deadline, it will be active for
+///     at least one refresh cycle. A content update which is allowed to
+///     tear might become active after a latching deadline if no content
+///     update became active at the deadline.
+///
+///     The constraint must be ignored if the surface is a subsurface in
+///     synchronized mode. If the surface is not being updated by the
+///     compositor (off-screen, occluded) the compositor may ignore the
+///     constraint. Clients must use an additional mechanism such as
+///     frame callbacks or timestamps to ensure throttling occurs under
+///     all conditions.
+///
+///     "wait_barrier" is double-buffered state, see wl_surface.commit.
+///
+///     Requesting "wait_barrier" after the fifo object's surface is
+///     destroyed will generate a "surface_destroyed" error.
+///   </description>
+/// </request>
+/// ```
+pub fn wait_barrier(conn: &Connection, fifo: u32) -> Result<()> {
+    conn.send_request(fifo, FifoOpcode::WaitBarrier, &())?;
+
+    Ok(())
+}
+
+/// Sends a `wp_fifo_v1.destroy` request to the compositor.
+pub fn destroy_fifo(conn: &Connection, fifo: u32) -> Result<()> {
+    conn.send_request(fifo, FifoOpcode::Destroy, &())?;
+
+    Ok(())
+}
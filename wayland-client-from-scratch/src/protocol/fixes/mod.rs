@@ -0,0 +1,70 @@
+pub mod request;
+
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::{WlObjectId, types::WlObject},
+};
+
+/// The highest `wl_fixes` version this crate implements.
+pub const WL_FIXES_VERSION: u32 = 1;
+
+/// A bound `wl_fixes` global, which adds requests missing from the core
+/// interfaces.
+pub struct Fixes {
+    /// The object ID of the bound `wl_fixes` object.
+    id: u32,
+    /// The connection the object was bound on.
+    conn: Connection,
+}
+
+impl Fixes {
+    /// Binds the `wl_fixes` global advertised by the compositor.
+    pub fn bind(conn: &Connection) -> Result<Fixes> {
+        let (id, _) = conn.bind(WlObjectId::Fixes, WL_FIXES_VERSION)?;
+
+        Ok(Fixes {
+            id,
+            conn: conn.clone(),
+        })
+    }
+
+    /// Returns the object ID of the `wl_fixes` object.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Destroys the registry of the connection, which `wl_registry` cannot
+    /// do on its own.
+    ///
+    /// A long-running client that bound every global it needs can drop the
+    /// registry, so the compositor stops tracking it and sending it global
+    /// announcements. Afterwards [`Connection::globals`] keeps returning the
+    /// globals known at this point, and binding a global fails on the
+    /// client side instead of referencing a dead registry.
+    ///
+    /// # Errors
+    /// Returns an error if the registry was already destroyed.
+    pub fn destroy_registry(&self) -> Result<()> {
+        let registry_id = self.conn.destroy_registry()?;
+
+        request::destroy_registry(&self.conn, self.id, WlObject(registry_id))
+    }
+
+    /// Destroys the `wl_fixes` object. Registries destroyed through it stay
+    /// destroyed.
+    ///
+    /// Dropping the handle does the same.
+    pub fn destroy(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl Drop for Fixes {
+    fn drop(&mut self) {
+        self.conn
+            .destroy_on_drop(|conn| request::destroy(conn, self.id));
+    }
+}
@@ -0,0 +1,65 @@
+use crate::{
+    connection::Connection, error::Result, protocol::types::WlObject, wl_request_opcode,
+    wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to the `wl_fixes` object.
+    Opcode {
+        /// Destroys the `wl_fixes` object.
+        Destroy = 0 (destructor),
+
+        /// Destroys a `wl_registry` object.
+        DestroyRegistry = 1,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wl_fixes.destroy_registry` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="destroy_registry">
+    ///   <description summary="destroy a wl_registry">
+    ///     This request destroys a wl_registry object.
+    ///
+    ///     The client should no longer use the wl_registry after making this
+    ///     request.
+    ///
+    ///     The compositor will emit a wl_display.delete_id event with the object
+    ///     ID of the registry and will no longer emit any events on the registry.
+    ///     The client should re-use the object ID once it receives the
+    ///     wl_display.delete_id event.
+    ///   </description>
+    ///   <arg name="registry" type="object" interface="wl_registry"
+    ///     summary="the registry to destroy"/>
+    /// </request>
+    /// ```
+    DestroyRegistryParam {
+        /// The registry to destroy.
+        registry: WlObject,
+    }
+}
+
+/// Sends a `wl_fixes.destroy` request to the compositor.
+pub fn destroy(conn: &Connection, fixes: u32) -> Result<()> {
+    conn.send_request(fixes, Opcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends a `wl_fixes.destroy_registry` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `fixes` - The ID of the bound `wl_fixes` object
+/// * `registry` - The registry to destroy
+pub fn destroy_registry(conn: &Connection, fixes: u32, registry: WlObject) -> Result<()> {
+    conn.send_request(
+        fixes,
+        Opcode::DestroyRegistry,
+        &DestroyRegistryParam::new(registry),
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,88 @@
+pub mod preferred_scale;
+
+use crate::{
+    connection::Connection,
+    error::{Error, Result, anyhow},
+    protocol::message::WlMessage,
+};
+
+/// Represents the event types that can be emitted by a `wp_fractional_scale_v1` object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    /// Announces the preferred scale of the surface.
+    ///
+    /// # Event Arguments
+    /// - `scale`: The scale multiplied by 120
+    PreferredScale = 0,
+}
+
+impl TryFrom<u16> for Opcode {
+    type Error = Error;
+
+    /// Attempts to convert a raw opcode value into a structured `WpFractionalScaleEvent`.
+    fn try_from(value: u16) -> Result<Opcode> {
+        match value {
+            0 => Ok(Opcode::PreferredScale),
+            _ => Err(anyhow!(
+                "Invalid wp_fractional_scale_v1 event opcode: {}",
+                value
+            )),
+        }
+    }
+}
+
+impl Opcode {
+    /// Returns the interface version the event was introduced in.
+    pub fn since(self) -> u32 {
+        match self {
+            Opcode::PreferredScale => 1,
+        }
+    }
+}
+
+/// A decoded `wp_fractional_scale_v1` event, with its arguments.
+#[derive(Debug)]
+pub enum Event {
+    /// Announces the preferred scale of the surface.
+    PreferredScale(preferred_scale::PreferredScale),
+}
+
+impl Event {
+    /// Decodes a `wp_fractional_scale_v1` event from its message.
+    ///
+    /// # Errors
+    /// Returns an error if the opcode is unknown or the arguments are malformed.
+    pub fn decode(_conn: &Connection, msg: &WlMessage) -> Result<Event> {
+        let buf: &[u8] = &msg.data;
+
+        Ok(match Opcode::try_from(msg.header.opcode)? {
+            Opcode::PreferredScale => {
+                Event::PreferredScale(preferred_scale::PreferredScale::try_from(buf)?)
+            }
+        })
+    }
+
+    /// Returns the opcode of the event.
+    pub fn opcode(&self) -> Opcode {
+        match self {
+            Event::PreferredScale(_) => Opcode::PreferredScale,
+        }
+    }
+}
+
+/// Dispatches incoming `wp_fractional_scale_v1` events to their appropriate handler functions.
+///
+/// # Event Routing
+/// * `PreferredScale` events are routed to `preferred_scale::handle_wp_fractional_scale_preferred_scale`
+pub fn handle_wp_fractional_scale_event(conn: &Connection, msg: WlMessage) -> Result<()> {
+    // Route the event to the appropriate handler based on type
+    match Event::decode(conn, &msg)? {
+        Event::PreferredScale(event) => {
+            preferred_scale::handle_wp_fractional_scale_preferred_scale(
+                conn,
+                msg.header.object_id,
+                event,
+            )
+        }
+    }
+}
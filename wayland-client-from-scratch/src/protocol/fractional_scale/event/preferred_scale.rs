@@ -0,0 +1,50 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::types::WlUInt,
+};
+
+/// Represents a `wp_fractional_scale_v1.preferred_scale` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="preferred_scale">
+///   <description summary="notify of new preferred scale">
+///     Notification of a new preferred scale for this surface that the
+///     compositor suggests that the client should use.
+///
+///     The sent scale is the numerator of a fraction with a denominator of 120.
+///   </description>
+///   <arg name="scale" type="uint" summary="the new preferred scale"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PreferredScale {
+    /// The preferred scale multiplied by 120.
+    pub scale: WlUInt,
+}
+
+impl TryFrom<&[u8]> for PreferredScale {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `PreferredScale` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `scale` (u32) - The preferred scale multiplied by 120
+    fn try_from(buf: &[u8]) -> Result<PreferredScale> {
+        Ok(PreferredScale {
+            scale: WlUInt::read(buf, 0)?,
+        })
+    }
+}
+
+/// Handles a `wp_fractional_scale_v1.preferred_scale` event by notifying the listener.
+pub(super) fn handle_wp_fractional_scale_preferred_scale(
+    conn: &Connection,
+    fractional_scale_id: u32,
+    event: PreferredScale,
+) -> Result<()> {
+    conn.emit(fractional_scale_id, event);
+
+    Ok(())
+}
@@ -0,0 +1,114 @@
+pub mod event;
+pub mod request;
+
+use crate::{
+    connection::{Connection, Listener},
+    error::Result,
+    protocol::{
+        WlObjectId,
+        surface::Surface,
+        types::{WlNewId, WlObject},
+    },
+};
+
+use event::preferred_scale::PreferredScale;
+
+/// The highest `wp_fractional_scale_manager_v1` version this crate implements.
+pub const WP_FRACTIONAL_SCALE_MANAGER_VERSION: u32 = 1;
+
+/// The denominator of the scales sent by `wp_fractional_scale_v1`.
+pub const FRACTIONAL_SCALE_DENOMINATOR: u32 = 120;
+
+/// A bound `wp_fractional_scale_manager_v1` global, which lets the
+/// compositor suggest non-integer scales for surfaces.
+#[derive(Clone)]
+pub struct FractionalScaleManager {
+    /// The object ID of the bound manager.
+    id: u32,
+    /// The negotiated interface version.
+    version: u32,
+    /// The connection the manager was bound on.
+    conn: Connection,
+}
+
+impl FractionalScaleManager {
+    /// Binds the `wp_fractional_scale_manager_v1` global advertised by the compositor.
+    pub fn bind(conn: &Connection) -> Result<FractionalScaleManager> {
+        let (id, version) = conn.bind(
+            WlObjectId::FractionalScaleManager,
+            WP_FRACTIONAL_SCALE_MANAGER_VERSION,
+        )?;
+
+        Ok(FractionalScaleManager {
+            id,
+            version,
+            conn: conn.clone(),
+        })
+    }
+
+    /// Returns the object ID of the manager.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Subscribes to the preferred fractional scale of `surface`.
+    ///
+    /// `handler` receives the scale multiplied by
+    /// [`FRACTIONAL_SCALE_DENOMINATOR`]. A surface can only have one
+    /// fractional scale object at a time.
+    pub fn get_fractional_scale<F>(&self, surface: &Surface, handler: F) -> Result<FractionalScale>
+    where
+        F: FnMut(&Connection, PreferredScale) + Send + 'static,
+    {
+        let id = self
+            .conn
+            .new_object(WlObjectId::FractionalScale, self.version)?;
+        request::get_fractional_scale(&self.conn, self.id, WlNewId(id), WlObject(surface.id()))?;
+
+        let listener: Listener<PreferredScale> = Box::new(handler);
+        self.conn.set_listener(id, listener);
+
+        Ok(FractionalScale {
+            id,
+            conn: self.conn.clone(),
+        })
+    }
+
+    /// Destroys the manager; existing fractional scale objects keep working.
+    pub fn destroy(self) -> Result<()> {
+        request::destroy(&self.conn, self.id)
+    }
+}
+
+/// A `wp_fractional_scale_v1`, reporting the preferred scale of a surface.
+pub struct FractionalScale {
+    /// The object ID of the fractional scale object.
+    id: u32,
+    /// The connection the object was created on.
+    conn: Connection,
+}
+
+impl FractionalScale {
+    /// Returns the object ID of the fractional scale object.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Destroys the object, so the surface no longer gets fractional scales.
+    ///
+    /// Dropping the handle does the same.
+    pub fn destroy(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl Drop for FractionalScale {
+    fn drop(&mut self) {
+        self.conn.remove_listener(self.id);
+
+        self.conn
+            .destroy_on_drop(|conn| request::destroy_fractional_scale(conn, self.id));
+    }
+}
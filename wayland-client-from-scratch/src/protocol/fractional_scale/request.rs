@@ -0,0 +1,87 @@
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::types::{WlNewId, WlObject},
+    wl_request_opcode, wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to the `wp_fractional_scale_manager_v1` object.
+    Opcode {
+        /// Destroys the manager; existing fractional scale objects keep working.
+        Destroy = 0 (destructor),
+
+        /// Creates the fractional scale object of a surface.
+        GetFractionalScale = 1,
+    }
+}
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to a `wp_fractional_scale_v1` object.
+    FractionalScaleOpcode {
+        /// Destroys the fractional scale object.
+        Destroy = 0 (destructor),
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wp_fractional_scale_manager_v1.get_fractional_scale` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="get_fractional_scale">
+    ///   <description summary="extend surface interface for scale information">
+    ///     Create an add-on object for the the wl_surface to let the compositor
+    ///     request fractional scales. If the given wl_surface already has a
+    ///     wp_fractional_scale_v1 object associated, the fractional_scale_exists
+    ///     protocol error is raised.
+    ///   </description>
+    ///   <arg name="id" type="new_id" interface="wp_fractional_scale_v1"
+    ///        summary="the new surface scale info interface id"/>
+    ///   <arg name="surface" type="object" interface="wl_surface"
+    ///        summary="the surface"/>
+    /// </request>
+    /// ```
+    GetFractionalScaleParam {
+        /// The object ID to assign to the new fractional scale object.
+        id: WlNewId,
+        /// The surface the scale is reported for.
+        surface: WlObject,
+    }
+}
+
+/// Sends a `wp_fractional_scale_manager_v1.destroy` request to the compositor.
+pub fn destroy(conn: &Connection, manager: u32) -> Result<()> {
+    conn.send_request(manager, Opcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends a `wp_fractional_scale_manager_v1.get_fractional_scale` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `manager` - The ID of the bound manager
+/// * `id` - The object ID to assign to the new fractional scale object
+/// * `surface` - The surface the scale is reported for
+pub fn get_fractional_scale(
+    conn: &Connection,
+    manager: u32,
+    id: WlNewId,
+    surface: WlObject,
+) -> Result<()> {
+    conn.send_request(
+        manager,
+        Opcode::GetFractionalScale,
+        &GetFractionalScaleParam::new(id, surface),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `wp_fractional_scale_v1.destroy` request to the compositor.
+pub fn destroy_fractional_scale(conn: &Connection, fractional_scale: u32) -> Result<()> {
+    conn.send_request(fractional_scale, FractionalScaleOpcode::Destroy, &())?;
+
+    Ok(())
+}
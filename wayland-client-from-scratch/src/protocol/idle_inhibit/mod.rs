@@ -0,0 +1,222 @@
+pub mod request;
+
+use std::sync::Arc;
+
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::{
+        WlObjectId,
+        surface::Surface,
+        types::{WlNewId, WlObject},
+    },
+    sync::LockCell,
+};
+
+/// The highest `zwp_idle_inhibit_manager_v1` version this crate implements.
+pub const ZWP_IDLE_INHIBIT_MANAGER_VERSION: u32 = 1;
+
+/// A bound `zwp_idle_inhibit_manager_v1` global, which lets clients keep
+/// the screen from blanking, e.g. while a video plays.
+#[derive(Clone)]
+pub struct IdleInhibitManager {
+    /// The object ID of the bound manager.
+    id: u32,
+    /// The negotiated interface version.
+    version: u32,
+    /// The connection the manager was bound on.
+    conn: Connection,
+}
+
+impl IdleInhibitManager {
+    /// Binds the `zwp_idle_inhibit_manager_v1` global advertised by the compositor.
+    pub fn bind(conn: &Connection) -> Result<IdleInhibitManager> {
+        let (id, version) = conn.bind(
+            WlObjectId::IdleInhibitManager,
+            ZWP_IDLE_INHIBIT_MANAGER_VERSION,
+        )?;
+
+        Ok(IdleInhibitManager {
+            id,
+            version,
+            conn: conn.clone(),
+        })
+    }
+
+    /// Returns the object ID of the manager.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Keeps the system awake while `surface` is visible, until the
+    /// returned guard is dropped.
+    pub fn inhibit(&self, surface: &Surface) -> Result<InhibitGuard> {
+        self.conn
+            .check_same_connection(surface.connection(), WlObjectId::Surface, surface.id())?;
+
+        let state = Arc::new(InhibitState {
+            manager: self.clone(),
+            surface: surface.id(),
+            inhibitor: LockCell::new(0),
+            stale: LockCell::new(false),
+        });
+        state.create()?;
+        surface
+            .state()
+            .inhibitors
+            .borrow_mut()
+            .push(Arc::downgrade(&state));
+
+        Ok(InhibitGuard { state })
+    }
+
+    /// Destroys the manager; existing inhibitors keep working.
+    pub fn destroy(self) -> Result<()> {
+        request::destroy(&self.conn, self.id)
+    }
+}
+
+/// The inhibitor of an [`InhibitGuard`], shared with the surface it is tied
+/// to so it can be created again when the surface is mapped again.
+pub(crate) struct InhibitState {
+    /// The manager the inhibitors are created from.
+    manager: IdleInhibitManager,
+    /// The object ID of the surface inhibiting idling.
+    surface: u32,
+    /// The object ID of the current `zwp_idle_inhibitor_v1`.
+    inhibitor: LockCell<u32>,
+    /// Whether the surface was unmapped since the inhibitor was created.
+    stale: LockCell<bool>,
+}
+
+impl InhibitState {
+    /// Creates a new inhibitor for the surface.
+    fn create(&self) -> Result<()> {
+        let conn = &self.manager.conn;
+        let id = conn.new_object(WlObjectId::IdleInhibitor, self.manager.version)?;
+        request::create_inhibitor(conn, self.manager.id, WlNewId(id), WlObject(self.surface))?;
+        self.inhibitor.set(id);
+
+        Ok(())
+    }
+
+    /// Destroys the current inhibitor.
+    fn destroy(&self) -> Result<()> {
+        match self.inhibitor.replace(0) {
+            0 => Ok(()),
+            id => request::destroy_inhibitor(&self.manager.conn, id),
+        }
+    }
+
+    /// Follows a commit that mapped or unmapped the surface, replacing the
+    /// inhibitor once the surface is mapped again after being unmapped.
+    ///
+    /// Compositors ignore inhibitors of unmapped surfaces; a fresh one
+    /// makes sure the inhibition applies again on every compositor.
+    pub(crate) fn mapped_changed(&self, mapped: bool) -> Result<()> {
+        if !mapped {
+            self.stale.set(true);
+            return Ok(());
+        }
+        if !self.stale.replace(false) {
+            return Ok(());
+        }
+
+        self.destroy()?;
+        self.create()
+    }
+}
+
+/// Keeps the system from idling, e.g. blanking the screen or suspending,
+/// while the surface it is tied to is visible.
+///
+/// Created with [`IdleInhibitManager::inhibit`]. The `zwp_idle_inhibitor_v1`
+/// is re-created automatically when the surface is mapped again after being
+/// unmapped, such as a window hidden and shown again. Dropping the guard
+/// destroys it and lets the system idle again.
+pub struct InhibitGuard {
+    state: Arc<InhibitState>,
+}
+
+impl InhibitGuard {
+    /// Returns the object ID of the current `zwp_idle_inhibitor_v1`, which
+    /// changes when the surface is remapped.
+    pub fn id(&self) -> u32 {
+        self.state.inhibitor.get()
+    }
+
+    /// Returns the object ID of the surface the guard is tied to.
+    pub fn surface(&self) -> u32 {
+        self.state.surface
+    }
+
+    /// Lets the system idle again.
+    ///
+    /// Dropping the guard does the same.
+    pub fn release(self) -> Result<()> {
+        self.state.destroy()
+    }
+}
+
+impl Drop for InhibitGuard {
+    fn drop(&mut self) {
+        self.state
+            .manager
+            .conn
+            .destroy_on_drop(|_| self.state.destroy());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Read, os::unix::net::UnixStream};
+
+    use super::*;
+    use crate::protocol::{
+        endian::Endian,
+        message::WlMessageHeader,
+        registry::event::global::Global,
+        types::{WlString, WlUInt},
+    };
+
+    #[test]
+    fn inhibitor_is_replaced_when_the_surface_is_remapped() {
+        let (client, mut compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+        conn.insert_global(Global {
+            name: WlUInt(1),
+            interface: WlString::new("zwp_idle_inhibit_manager_v1").unwrap(),
+            version: WlUInt(1),
+        });
+        let manager = IdleInhibitManager::bind(&conn).unwrap();
+        let surface = Surface::new(conn.new_object(WlObjectId::Surface, 6).unwrap(), &conn);
+
+        let guard = manager.inhibit(&surface).unwrap();
+        let first = guard.id();
+        let state = guard.state.clone();
+        state.mapped_changed(true).unwrap();
+        assert_eq!(guard.id(), first);
+
+        state.mapped_changed(false).unwrap();
+        state.mapped_changed(true).unwrap();
+        let second = guard.id();
+        assert_ne!(second, first);
+
+        drop((state, guard, surface, manager));
+        drop(conn);
+
+        let mut received = Vec::new();
+        compositor.read_to_end(&mut received).unwrap();
+        let mut received = &received[..];
+        let mut inhibitor_requests = Vec::new();
+        while !received.is_empty() {
+            let header = WlMessageHeader::decode(received, Endian::NATIVE).unwrap();
+            if [first, second].contains(&header.object_id) {
+                inhibitor_requests.push(header.object_id);
+            }
+            received = &received[header.size as usize..];
+        }
+        // Each inhibitor is destroyed once: the first on remap, the second on drop
+        assert_eq!(inhibitor_requests, [first, second]);
+    }
+}
@@ -0,0 +1,83 @@
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::types::{WlNewId, WlObject},
+    wl_request_opcode, wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to the `zwp_idle_inhibit_manager_v1` object.
+    Opcode {
+        /// Destroys the manager; existing inhibitors keep working.
+        Destroy = 0 (destructor),
+
+        /// Creates an inhibitor keeping the system awake while a surface is visible.
+        CreateInhibitor = 1,
+    }
+}
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to a `zwp_idle_inhibitor_v1` object.
+    InhibitorOpcode {
+        /// Destroys the inhibitor, letting the system idle again.
+        Destroy = 0 (destructor),
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `zwp_idle_inhibit_manager_v1.create_inhibitor` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="create_inhibitor">
+    ///   <description summary="create a new inhibitor object">
+    ///     Create a new inhibitor object associated with the given surface.
+    ///   </description>
+    ///   <arg name="id" type="new_id" interface="zwp_idle_inhibitor_v1"/>
+    ///   <arg name="surface" type="object" interface="wl_surface"
+    ///        summary="the surface that inhibits the idle behavior"/>
+    /// </request>
+    /// ```
+    CreateInhibitorParam {
+        /// The object ID to assign to the new inhibitor.
+        id: WlNewId,
+        /// The surface inhibiting idling while visible.
+        surface: WlObject,
+    }
+}
+
+/// Sends a `zwp_idle_inhibit_manager_v1.destroy` request to the compositor.
+pub fn destroy(conn: &Connection, manager: u32) -> Result<()> {
+    conn.send_request(manager, Opcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends a `zwp_idle_inhibit_manager_v1.create_inhibitor` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `manager` - The ID of the bound idle inhibit manager
+/// * `id` - The object ID to assign to the new inhibitor
+/// * `surface` - The surface inhibiting idling while visible
+pub fn create_inhibitor(
+    conn: &Connection,
+    manager: u32,
+    id: WlNewId,
+    surface: WlObject,
+) -> Result<()> {
+    conn.send_request(
+        manager,
+        Opcode::CreateInhibitor,
+        &CreateInhibitorParam::new(id, surface),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `zwp_idle_inhibitor_v1.destroy` request to the compositor.
+pub fn destroy_inhibitor(conn: &Connection, inhibitor: u32) -> Result<()> {
+    conn.send_request(inhibitor, InhibitorOpcode::Destroy, &())?;
+
+    Ok(())
+}
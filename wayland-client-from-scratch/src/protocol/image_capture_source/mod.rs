@@ -0,0 +1,104 @@
+pub mod request;
+
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::{
+        WlObjectId,
+        output::Output,
+        types::{WlNewId, WlObject},
+    },
+};
+
+/// The highest `ext_output_image_capture_source_manager_v1` version this
+/// crate implements.
+pub const EXT_OUTPUT_IMAGE_CAPTURE_SOURCE_MANAGER_VERSION: u32 = 1;
+
+/// A bound `ext_output_image_capture_source_manager_v1` global, which
+/// creates capture sources for outputs.
+#[derive(Clone)]
+pub struct OutputImageCaptureSourceManager {
+    /// The object ID of the bound manager.
+    id: u32,
+    /// The negotiated interface version.
+    version: u32,
+    /// The connection the manager was bound on.
+    conn: Connection,
+}
+
+impl OutputImageCaptureSourceManager {
+    /// Binds the `ext_output_image_capture_source_manager_v1` global
+    /// advertised by the compositor.
+    pub fn bind(conn: &Connection) -> Result<OutputImageCaptureSourceManager> {
+        let (id, version) = conn.bind(
+            WlObjectId::OutputImageCaptureSourceManager,
+            EXT_OUTPUT_IMAGE_CAPTURE_SOURCE_MANAGER_VERSION,
+        )?;
+
+        Ok(OutputImageCaptureSourceManager {
+            id,
+            version,
+            conn: conn.clone(),
+        })
+    }
+
+    /// Returns the object ID of the manager.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Creates a source showing the same content as `output`, minus the
+    /// elements the compositor hides from captures.
+    pub fn create_source(&self, output: &Output) -> Result<ImageCaptureSource> {
+        let id = self
+            .conn
+            .new_object(WlObjectId::ImageCaptureSource, self.version)?;
+        request::create_output_source(&self.conn, self.id, WlNewId(id), WlObject(output.id()))?;
+
+        Ok(ImageCaptureSource {
+            id,
+            conn: self.conn.clone(),
+        })
+    }
+
+    /// Destroys the manager; existing sources keep working.
+    pub fn destroy(self) -> Result<()> {
+        request::destroy_output_manager(&self.conn, self.id)
+    }
+}
+
+/// An `ext_image_capture_source_v1`, an opaque description of something
+/// that can be captured, such as an output.
+///
+/// Passed to
+/// [`ImageCopyCaptureManager::create_session`](crate::protocol::image_copy_capture::ImageCopyCaptureManager::create_session)
+/// to capture its content.
+pub struct ImageCaptureSource {
+    /// The object ID of the source.
+    id: u32,
+    /// The connection the source was created on.
+    conn: Connection,
+}
+
+impl ImageCaptureSource {
+    /// Returns the object ID of the source.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Destroys the source; sessions already created from it are not affected.
+    ///
+    /// Dropping the handle does the same.
+    pub fn destroy(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl Drop for ImageCaptureSource {
+    fn drop(&mut self) {
+        self.conn
+            .destroy_on_drop(|conn| request::destroy(conn, self.id));
+    }
+}
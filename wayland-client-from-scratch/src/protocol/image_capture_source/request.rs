@@ -0,0 +1,89 @@
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::types::{WlNewId, WlObject},
+    wl_request_opcode, wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to an
+    /// `ext_image_capture_source_v1` object.
+    Opcode {
+        /// Destroys the source; sessions created from it keep their content.
+        Destroy = 0 (destructor),
+    }
+}
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to the
+    /// `ext_output_image_capture_source_manager_v1` object.
+    OutputManagerOpcode {
+        /// Creates a source capturing the content of an output.
+        CreateSource = 0,
+
+        /// Destroys the manager; existing sources keep working.
+        Destroy = 1 (destructor),
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `ext_output_image_capture_source_manager_v1.create_source` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="create_source">
+    ///   <description summary="create source">
+    ///     Creates a source object for an output. Images captured from this source
+    ///     will show the same content as the output. Some elements may be omitted,
+    ///     such as cursors and overlays that have been marked as transparent to
+    ///     capturing.
+    ///   </description>
+    ///   <arg name="source" type="new_id" interface="ext_image_capture_source_v1"/>
+    ///   <arg name="output" type="object" interface="wl_output"/>
+    /// </request>
+    /// ```
+    CreateSourceParam {
+        /// The object ID to assign to the new source.
+        source: WlNewId,
+        /// The output to capture.
+        output: WlObject,
+    }
+}
+
+/// Sends an `ext_image_capture_source_v1.destroy` request to the compositor.
+pub fn destroy(conn: &Connection, source: u32) -> Result<()> {
+    conn.send_request(source, Opcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends an `ext_output_image_capture_source_manager_v1.create_source` request
+/// to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `manager` - The ID of the bound output source manager
+/// * `source` - The object ID to assign to the new source
+/// * `output` - The output to capture
+pub fn create_output_source(
+    conn: &Connection,
+    manager: u32,
+    source: WlNewId,
+    output: WlObject,
+) -> Result<()> {
+    conn.send_request(
+        manager,
+        OutputManagerOpcode::CreateSource,
+        &CreateSourceParam::new(source, output),
+    )?;
+
+    Ok(())
+}
+
+/// Sends an `ext_output_image_capture_source_manager_v1.destroy` request to
+/// the compositor.
+pub fn destroy_output_manager(conn: &Connection, manager: u32) -> Result<()> {
+    conn.send_request(manager, OutputManagerOpcode::Destroy, &())?;
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        image_copy_capture::SessionState,
+        types::{WL_TYPE_UINT_LEN, WlUInt},
+    },
+};
+
+/// Represents an `ext_image_copy_capture_session_v1.buffer_size` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="buffer_size">
+///   <description summary="image capture source dimensions">
+///     Provides the dimensions of the source image in buffer pixel coordinates.
+///
+///     The client must attach buffers that match this size.
+///   </description>
+///   <arg name="width" type="uint" summary="buffer width"/>
+///   <arg name="height" type="uint" summary="buffer height"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BufferSize {
+    /// The width buffers must have, in pixels.
+    pub width: WlUInt,
+    /// The height buffers must have, in pixels.
+    pub height: WlUInt,
+}
+
+impl TryFrom<&[u8]> for BufferSize {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `BufferSize` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `width` (u32) - The buffer width
+    /// - Bytes 4-7: `height` (u32) - The buffer height
+    fn try_from(buf: &[u8]) -> Result<BufferSize> {
+        Ok(BufferSize {
+            width: WlUInt::read(buf, 0)?,
+            height: WlUInt::read(buf, WL_TYPE_UINT_LEN)?,
+        })
+    }
+}
+
+/// Handles an `ext_image_copy_capture_session_v1.buffer_size` event by
+/// recording the size in the pending constraints.
+pub(super) fn handle_ext_image_copy_capture_session_buffer_size(
+    conn: &Connection,
+    session_id: u32,
+    event: BufferSize,
+) -> Result<()> {
+    if let Some(state) = conn.object_data::<SessionState>(session_id) {
+        let mut pending = state.pending.borrow_mut();
+        pending.width = event.width.get();
+        pending.height = event.height.get();
+    }
+
+    Ok(())
+}
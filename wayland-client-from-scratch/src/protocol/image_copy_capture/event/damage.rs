@@ -0,0 +1,81 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    geometry::Rect,
+    protocol::{
+        image_copy_capture::FrameState,
+        types::{WL_TYPE_INT_LEN, WlInt},
+    },
+};
+
+/// Represents an `ext_image_copy_capture_frame_v1.damage` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="damage">
+///   <description summary="buffer damaged region">
+///     This event is sent before the ready event. It may be generated multiple
+///     times to describe a region.
+///
+///     The first captured frame in a session will always carry full damage.
+///     Subsequent frames' damaged regions describe which parts of the buffer
+///     have changed since the last ready event.
+///
+///     These coordinates originate in the upper left corner of the buffer.
+///   </description>
+///   <arg name="x" type="int" summary="damage x coordinate"/>
+///   <arg name="y" type="int" summary="damage y coordinate"/>
+///   <arg name="width" type="int" summary="damage width"/>
+///   <arg name="height" type="int" summary="damage height"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Damage {
+    /// The left edge of the damaged region.
+    pub x: WlInt,
+    /// The top edge of the damaged region.
+    pub y: WlInt,
+    /// The width of the damaged region.
+    pub width: WlInt,
+    /// The height of the damaged region.
+    pub height: WlInt,
+}
+
+impl TryFrom<&[u8]> for Damage {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Damage` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `x` (i32) - The left edge
+    /// - Bytes 4-7: `y` (i32) - The top edge
+    /// - Bytes 8-11: `width` (i32) - The width
+    /// - Bytes 12-15: `height` (i32) - The height
+    fn try_from(buf: &[u8]) -> Result<Damage> {
+        Ok(Damage {
+            x: WlInt::read(buf, 0)?,
+            y: WlInt::read(buf, WL_TYPE_INT_LEN)?,
+            width: WlInt::read(buf, 2 * WL_TYPE_INT_LEN)?,
+            height: WlInt::read(buf, 3 * WL_TYPE_INT_LEN)?,
+        })
+    }
+}
+
+/// Handles an `ext_image_copy_capture_frame_v1.damage` event by adding the
+/// region to the pending frame.
+pub(super) fn handle_ext_image_copy_capture_frame_damage(
+    conn: &Connection,
+    frame_id: u32,
+    event: Damage,
+) -> Result<()> {
+    if let Some(state) = conn.object_data::<FrameState>(frame_id) {
+        state.pending.borrow_mut().damage.push(Rect::new(
+            event.x.get(),
+            event.y.get(),
+            event.width.get(),
+            event.height.get(),
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result, anyhow},
+    protocol::{endian::Endian, image_copy_capture::SessionState, types::WlArray},
+};
+
+/// Represents an `ext_image_copy_capture_session_v1.dmabuf_device` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="dmabuf_device">
+///   <description summary="dma-buf device">
+///     This event advertises the device buffers must be allocated on for
+///     dma-buf buffers.
+///
+///     In general the device is a DRM node. The DRM node type (primary vs.
+///     render) is unspecified. Clients must not rely on the compositor sending
+///     a particular node type. Clients cannot check two devices for equality
+///     by comparing the dev_t value.
+///   </description>
+///   <arg name="device" type="array" summary="device dev_t value"/>
+/// </event>
+/// ```
+#[derive(Debug)]
+pub struct DmabufDevice {
+    /// The `dev_t` of the device, in native byte order.
+    pub device: WlArray,
+}
+
+impl DmabufDevice {
+    /// Returns the `dev_t` of the device.
+    ///
+    /// # Errors
+    /// Returns an error if the array does not hold a 64-bit `dev_t`.
+    pub fn device(&self) -> Result<u64> {
+        let bytes = self.device.as_slice();
+        if bytes.len() != size_of::<u64>() {
+            return Err(anyhow!(
+                "dmabuf_device: expected an 8-byte dev_t, got {} bytes",
+                bytes.len()
+            ));
+        }
+
+        Ok(Endian::NATIVE.decode_u64_at(bytes))
+    }
+}
+
+impl TryFrom<&[u8]> for DmabufDevice {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `DmabufDevice` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0+: `device` (WlArray) - The `dev_t` of the device
+    fn try_from(buf: &[u8]) -> Result<DmabufDevice> {
+        Ok(DmabufDevice {
+            device: WlArray::try_from(buf)?,
+        })
+    }
+}
+
+/// Handles an `ext_image_copy_capture_session_v1.dmabuf_device` event by
+/// recording the device in the pending constraints.
+pub(super) fn handle_ext_image_copy_capture_session_dmabuf_device(
+    conn: &Connection,
+    session_id: u32,
+    event: DmabufDevice,
+) -> Result<()> {
+    let device = event.device()?;
+
+    if let Some(state) = conn.object_data::<SessionState>(session_id) {
+        state.pending.borrow_mut().dmabuf_device = Some(device);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        endian::Endian,
+        image_copy_capture::{DmabufConstraint, SessionState},
+        types::{WL_TYPE_UINT_LEN, WlArray, WlUInt},
+    },
+};
+
+/// Represents an `ext_image_copy_capture_session_v1.dmabuf_format` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="dmabuf_format">
+///   <description summary="dma-buf format">
+///     Provides the format that must be used for dma-buf buffers.
+///
+///     The client may choose any of the modifiers advertised in the array of
+///     64-bit unsigned integers.
+///
+///     This event may be emitted multiple times, in which case the client may
+///     choose any given format.
+///   </description>
+///   <arg name="format" type="uint" summary="drm format code"/>
+///   <arg name="modifiers" type="array" summary="drm format modifiers"/>
+/// </event>
+/// ```
+#[derive(Debug)]
+pub struct DmabufFormat {
+    /// The DRM fourcc code of the format.
+    pub format: WlUInt,
+    /// The accepted modifiers, as native-endian 64-bit values.
+    pub modifiers: WlArray,
+}
+
+impl DmabufFormat {
+    /// Returns the accepted DRM format modifiers.
+    pub fn modifiers(&self) -> Vec<u64> {
+        self.modifiers
+            .as_slice()
+            .chunks_exact(size_of::<u64>())
+            .map(|modifier| Endian::NATIVE.decode_u64_at(modifier))
+            .collect()
+    }
+}
+
+impl TryFrom<&[u8]> for DmabufFormat {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `DmabufFormat` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `format` (u32) - The DRM fourcc code
+    /// - Bytes 4+: `modifiers` (WlArray) - The accepted modifiers
+    fn try_from(buf: &[u8]) -> Result<DmabufFormat> {
+        Ok(DmabufFormat {
+            format: WlUInt::read(buf, 0)?,
+            modifiers: WlArray::try_from(buf.get(WL_TYPE_UINT_LEN..).unwrap_or_default())?,
+        })
+    }
+}
+
+/// Handles an `ext_image_copy_capture_session_v1.dmabuf_format` event by
+/// adding the format to the pending constraints.
+pub(super) fn handle_ext_image_copy_capture_session_dmabuf_format(
+    conn: &Connection,
+    session_id: u32,
+    event: DmabufFormat,
+) -> Result<()> {
+    if let Some(state) = conn.object_data::<SessionState>(session_id) {
+        state
+            .pending
+            .borrow_mut()
+            .dmabuf_formats
+            .push(DmabufConstraint {
+                format: event.format.get(),
+                modifiers: event.modifiers(),
+            });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::image_copy_capture::{CaptureSessionEvent, SessionState},
+};
+
+/// Handles an `ext_image_copy_capture_session_v1.done` event by applying
+/// the pending constraints and notifying the listener.
+///
+/// Each batch describes the constraints in full, so the next one starts
+/// from scratch.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="done">
+///   <description summary="all constraints have been sent">
+///     This event is sent once when all buffer constraint events have been
+///     sent.
+///
+///     The compositor must always end a batch of buffer constraint events with
+///     this event, regardless of whether it sends the initial constraints or
+///     an update.
+///   </description>
+/// </event>
+/// ```
+pub(super) fn handle_ext_image_copy_capture_session_done(
+    conn: &Connection,
+    session_id: u32,
+) -> Result<()> {
+    let Some(state) = conn.object_data::<SessionState>(session_id) else {
+        return Ok(());
+    };
+
+    let constraints = state.pending.take();
+    *state.constraints.borrow_mut() = Some(constraints.clone());
+    conn.emit(session_id, CaptureSessionEvent::Constraints(constraints));
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{image_copy_capture::CaptureFrameEvent, types::WlUInt},
+    wl_enum,
+};
+
+wl_enum! {
+    /// Why an `ext_image_copy_capture_frame_v1` capture failed.
+    FailureReason {
+        /// An unspecified runtime error; the capture may be retried.
+        Unknown = 0,
+        /// The buffer does not match the constraints of the session; retry
+        /// once new constraints arrived.
+        BufferConstraints = 1,
+        /// The session stopped; no further capture will succeed.
+        Stopped = 2,
+    }
+}
+
+/// Represents an `ext_image_copy_capture_frame_v1.failed` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="failed">
+///   <description summary="capture failed">
+///     This event indicates that the attempted frame copy has failed.
+///
+///     After receiving this event, the client must destroy the object.
+///   </description>
+///   <arg name="reason" type="uint" enum="failure_reason"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Failed {
+    /// Why the copy failed, as a `FailureReason` value.
+    pub reason: WlUInt,
+}
+
+impl TryFrom<&[u8]> for Failed {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Failed` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `reason` (u32) - The failure reason
+    fn try_from(buf: &[u8]) -> Result<Failed> {
+        Ok(Failed {
+            reason: WlUInt::read(buf, 0)?,
+        })
+    }
+}
+
+/// Handles an `ext_image_copy_capture_frame_v1.failed` event by notifying the listener.
+///
+/// Reasons unknown to this crate are reported as [`FailureReason::Unknown`].
+pub(super) fn handle_ext_image_copy_capture_frame_failed(
+    conn: &Connection,
+    frame_id: u32,
+    event: Failed,
+) -> Result<()> {
+    let reason = FailureReason::try_from(event.reason.get()).unwrap_or(FailureReason::Unknown);
+
+    conn.emit(frame_id, CaptureFrameEvent::Failed(reason));
+
+    Ok(())
+}
@@ -0,0 +1,328 @@
+pub mod buffer_size;
+pub mod damage;
+pub mod dmabuf_device;
+pub mod dmabuf_format;
+pub mod done;
+pub mod failed;
+pub mod presentation_time;
+pub mod ready;
+pub mod shm_format;
+pub mod stopped;
+pub mod transform;
+
+use crate::{
+    connection::Connection,
+    error::{Error, Result, anyhow},
+    protocol::message::WlMessage,
+};
+
+/// Represents the event types that can be emitted by an
+/// `ext_image_copy_capture_session_v1` object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionOpcode {
+    /// Announces the size buffers must have.
+    ///
+    /// # Event Arguments
+    /// - `width`, `height`: The buffer size in pixels
+    BufferSize = 0,
+
+    /// Announces a format shared-memory buffers may use.
+    ///
+    /// # Event Arguments
+    /// - `format`: The `wl_shm` format code
+    ShmFormat = 1,
+
+    /// Announces the device dma-buf buffers must be allocated on.
+    ///
+    /// # Event Arguments
+    /// - `device`: The `dev_t` of the device
+    DmabufDevice = 2,
+
+    /// Announces a format dma-buf buffers may use.
+    ///
+    /// # Event Arguments
+    /// - `format`: The DRM fourcc code
+    /// - `modifiers`: The accepted 64-bit modifiers
+    DmabufFormat = 3,
+
+    /// Ends a batch of buffer constraints.
+    Done = 4,
+
+    /// Announces that the session stopped.
+    Stopped = 5,
+}
+
+impl TryFrom<u16> for SessionOpcode {
+    type Error = Error;
+
+    /// Attempts to convert a raw opcode value into a structured `ExtImageCopyCaptureSessionEvent`.
+    fn try_from(value: u16) -> Result<SessionOpcode> {
+        match value {
+            0 => Ok(SessionOpcode::BufferSize),
+            1 => Ok(SessionOpcode::ShmFormat),
+            2 => Ok(SessionOpcode::DmabufDevice),
+            3 => Ok(SessionOpcode::DmabufFormat),
+            4 => Ok(SessionOpcode::Done),
+            5 => Ok(SessionOpcode::Stopped),
+            _ => Err(anyhow!(
+                "Invalid ext_image_copy_capture_session_v1 event opcode: {}",
+                value
+            )),
+        }
+    }
+}
+
+impl SessionOpcode {
+    /// Returns the interface version the event was introduced in.
+    pub fn since(self) -> u32 {
+        match self {
+            SessionOpcode::BufferSize
+            | SessionOpcode::ShmFormat
+            | SessionOpcode::DmabufDevice
+            | SessionOpcode::DmabufFormat
+            | SessionOpcode::Done
+            | SessionOpcode::Stopped => 1,
+        }
+    }
+}
+
+/// Represents the event types that can be emitted by an
+/// `ext_image_copy_capture_frame_v1` object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameOpcode {
+    /// Announces the transform applied to the buffer contents.
+    ///
+    /// # Event Arguments
+    /// - `transform`: The `wl_output.transform` value
+    Transform = 0,
+
+    /// Announces a region of the buffer that changed.
+    ///
+    /// # Event Arguments
+    /// - `x`, `y`, `width`, `height`: The damaged region
+    Damage = 1,
+
+    /// Announces when the content was presented.
+    ///
+    /// # Event Arguments
+    /// - `tv_sec_hi`, `tv_sec_lo`, `tv_nsec`: The presentation timestamp
+    PresentationTime = 2,
+
+    /// Announces that the frame was copied.
+    Ready = 3,
+
+    /// Announces that the copy failed.
+    ///
+    /// # Event Arguments
+    /// - `reason`: Why the copy failed
+    Failed = 4,
+}
+
+impl TryFrom<u16> for FrameOpcode {
+    type Error = Error;
+
+    /// Attempts to convert a raw opcode value into a structured `ExtImageCopyCaptureFrameEvent`.
+    fn try_from(value: u16) -> Result<FrameOpcode> {
+        match value {
+            0 => Ok(FrameOpcode::Transform),
+            1 => Ok(FrameOpcode::Damage),
+            2 => Ok(FrameOpcode::PresentationTime),
+            3 => Ok(FrameOpcode::Ready),
+            4 => Ok(FrameOpcode::Failed),
+            _ => Err(anyhow!(
+                "Invalid ext_image_copy_capture_frame_v1 event opcode: {}",
+                value
+            )),
+        }
+    }
+}
+
+impl FrameOpcode {
+    /// Returns the interface version the event was introduced in.
+    pub fn since(self) -> u32 {
+        match self {
+            FrameOpcode::Transform
+            | FrameOpcode::Damage
+            | FrameOpcode::PresentationTime
+            | FrameOpcode::Ready
+            | FrameOpcode::Failed => 1,
+        }
+    }
+}
+
+/// A decoded `ext_image_copy_capture_session_v1` event, with its arguments.
+#[derive(Debug)]
+pub enum SessionEvent {
+    /// Announces the size buffers must have.
+    BufferSize(buffer_size::BufferSize),
+    /// Announces a format shared-memory buffers may use.
+    ShmFormat(shm_format::ShmFormat),
+    /// Announces the device dma-buf buffers must be allocated on.
+    DmabufDevice(dmabuf_device::DmabufDevice),
+    /// Announces a format dma-buf buffers may use.
+    DmabufFormat(dmabuf_format::DmabufFormat),
+    /// Ends a batch of buffer constraints.
+    Done,
+    /// Announces that the session stopped.
+    Stopped,
+}
+
+impl SessionEvent {
+    /// Decodes a `ext_image_copy_capture_session_v1` event from its message.
+    ///
+    /// # Errors
+    /// Returns an error if the opcode is unknown or the arguments are malformed.
+    pub fn decode(_conn: &Connection, msg: &WlMessage) -> Result<SessionEvent> {
+        let buf: &[u8] = &msg.data;
+
+        Ok(match SessionOpcode::try_from(msg.header.opcode)? {
+            SessionOpcode::BufferSize => {
+                SessionEvent::BufferSize(buffer_size::BufferSize::try_from(buf)?)
+            }
+            SessionOpcode::ShmFormat => {
+                SessionEvent::ShmFormat(shm_format::ShmFormat::try_from(buf)?)
+            }
+            SessionOpcode::DmabufDevice => {
+                SessionEvent::DmabufDevice(dmabuf_device::DmabufDevice::try_from(buf)?)
+            }
+            SessionOpcode::DmabufFormat => {
+                SessionEvent::DmabufFormat(dmabuf_format::DmabufFormat::try_from(buf)?)
+            }
+            SessionOpcode::Done => SessionEvent::Done,
+            SessionOpcode::Stopped => SessionEvent::Stopped,
+        })
+    }
+
+    /// Returns the opcode of the event.
+    pub fn opcode(&self) -> SessionOpcode {
+        match self {
+            SessionEvent::BufferSize(_) => SessionOpcode::BufferSize,
+            SessionEvent::ShmFormat(_) => SessionOpcode::ShmFormat,
+            SessionEvent::DmabufDevice(_) => SessionOpcode::DmabufDevice,
+            SessionEvent::DmabufFormat(_) => SessionOpcode::DmabufFormat,
+            SessionEvent::Done => SessionOpcode::Done,
+            SessionEvent::Stopped => SessionOpcode::Stopped,
+        }
+    }
+}
+
+/// Dispatches incoming `ext_image_copy_capture_session_v1` events to their
+/// appropriate handler functions.
+///
+/// # Event Routing
+/// * `BufferSize` events are routed to `buffer_size::handle_ext_image_copy_capture_session_buffer_size`
+/// * `ShmFormat` events are routed to `shm_format::handle_ext_image_copy_capture_session_shm_format`
+/// * `DmabufDevice` events are routed to `dmabuf_device::handle_ext_image_copy_capture_session_dmabuf_device`
+/// * `DmabufFormat` events are routed to `dmabuf_format::handle_ext_image_copy_capture_session_dmabuf_format`
+/// * `Done` events are routed to `done::handle_ext_image_copy_capture_session_done`
+/// * `Stopped` events are routed to `stopped::handle_ext_image_copy_capture_session_stopped`
+pub fn handle_ext_image_copy_capture_session_event(
+    conn: &Connection,
+    msg: WlMessage,
+) -> Result<()> {
+    let session_id = msg.header.object_id;
+
+    // Route the event to the appropriate handler based on type
+    match SessionEvent::decode(conn, &msg)? {
+        SessionEvent::BufferSize(event) => {
+            buffer_size::handle_ext_image_copy_capture_session_buffer_size(conn, session_id, event)
+        }
+        SessionEvent::ShmFormat(event) => {
+            shm_format::handle_ext_image_copy_capture_session_shm_format(conn, session_id, event)
+        }
+        SessionEvent::DmabufDevice(event) => {
+            dmabuf_device::handle_ext_image_copy_capture_session_dmabuf_device(
+                conn, session_id, event,
+            )
+        }
+        SessionEvent::DmabufFormat(event) => {
+            dmabuf_format::handle_ext_image_copy_capture_session_dmabuf_format(
+                conn, session_id, event,
+            )
+        }
+        SessionEvent::Done => done::handle_ext_image_copy_capture_session_done(conn, session_id),
+        SessionEvent::Stopped => {
+            stopped::handle_ext_image_copy_capture_session_stopped(conn, session_id)
+        }
+    }
+}
+
+/// A decoded `ext_image_copy_capture_frame_v1` event, with its arguments.
+#[derive(Debug)]
+pub enum FrameEvent {
+    /// Announces the transform applied to the buffer contents.
+    Transform(transform::FrameTransform),
+    /// Announces a region of the buffer that changed.
+    Damage(damage::Damage),
+    /// Announces when the content was presented.
+    PresentationTime(presentation_time::PresentationTime),
+    /// Announces that the frame was copied.
+    Ready,
+    /// Announces that the copy failed.
+    Failed(failed::Failed),
+}
+
+impl FrameEvent {
+    /// Decodes a `ext_image_copy_capture_frame_v1` event from its message.
+    ///
+    /// # Errors
+    /// Returns an error if the opcode is unknown or the arguments are malformed.
+    pub fn decode(_conn: &Connection, msg: &WlMessage) -> Result<FrameEvent> {
+        let buf: &[u8] = &msg.data;
+
+        Ok(match FrameOpcode::try_from(msg.header.opcode)? {
+            FrameOpcode::Transform => {
+                FrameEvent::Transform(transform::FrameTransform::try_from(buf)?)
+            }
+            FrameOpcode::Damage => FrameEvent::Damage(damage::Damage::try_from(buf)?),
+            FrameOpcode::PresentationTime => {
+                FrameEvent::PresentationTime(presentation_time::PresentationTime::try_from(buf)?)
+            }
+            FrameOpcode::Ready => FrameEvent::Ready,
+            FrameOpcode::Failed => FrameEvent::Failed(failed::Failed::try_from(buf)?),
+        })
+    }
+
+    /// Returns the opcode of the event.
+    pub fn opcode(&self) -> FrameOpcode {
+        match self {
+            FrameEvent::Transform(_) => FrameOpcode::Transform,
+            FrameEvent::Damage(_) => FrameOpcode::Damage,
+            FrameEvent::PresentationTime(_) => FrameOpcode::PresentationTime,
+            FrameEvent::Ready => FrameOpcode::Ready,
+            FrameEvent::Failed(_) => FrameOpcode::Failed,
+        }
+    }
+}
+
+/// Dispatches incoming `ext_image_copy_capture_frame_v1` events to their
+/// appropriate handler functions.
+///
+/// # Event Routing
+/// * `Transform` events are routed to `transform::handle_ext_image_copy_capture_frame_transform`
+/// * `Damage` events are routed to `damage::handle_ext_image_copy_capture_frame_damage`
+/// * `PresentationTime` events are routed to `presentation_time::handle_ext_image_copy_capture_frame_presentation_time`
+/// * `Ready` events are routed to `ready::handle_ext_image_copy_capture_frame_ready`
+/// * `Failed` events are routed to `failed::handle_ext_image_copy_capture_frame_failed`
+pub fn handle_ext_image_copy_capture_frame_event(conn: &Connection, msg: WlMessage) -> Result<()> {
+    let frame_id = msg.header.object_id;
+
+    // Route the event to the appropriate handler based on type
+    match FrameEvent::decode(conn, &msg)? {
+        FrameEvent::Transform(event) => {
+            transform::handle_ext_image_copy_capture_frame_transform(conn, frame_id, event)
+        }
+        FrameEvent::Damage(event) => {
+            damage::handle_ext_image_copy_capture_frame_damage(conn, frame_id, event)
+        }
+        FrameEvent::PresentationTime(event) => {
+            presentation_time::handle_ext_image_copy_capture_frame_presentation_time(
+                conn, frame_id, event,
+            )
+        }
+        FrameEvent::Ready => ready::handle_ext_image_copy_capture_frame_ready(conn, frame_id),
+        FrameEvent::Failed(event) => {
+            failed::handle_ext_image_copy_capture_frame_failed(conn, frame_id, event)
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use std::time::Duration;
+
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{image_copy_capture::FrameState, types::WlUInt},
+};
+
+/// Represents an `ext_image_copy_capture_frame_v1.presentation_time` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="presentation_time">
+///   <description summary="presentation time of the frame">
+///     This event indicates the time at which the frame is presented to the
+///     output in system monotonic time. This event is sent before the ready
+///     event.
+///
+///     The timestamp is expressed as tv_sec_hi, tv_sec_lo, tv_nsec triples,
+///     each component being an unsigned 32-bit value. Whole seconds are in
+///     tv_sec which is a 64-bit value combined from tv_sec_hi and tv_sec_lo,
+///     and the additional fractional part in tv_nsec as nanoseconds. Hence,
+///     for valid timestamps tv_nsec must be in [0, 999999999].
+///   </description>
+///   <arg name="tv_sec_hi" type="uint"
+///        summary="high 32 bits of the seconds part of the timestamp"/>
+///   <arg name="tv_sec_lo" type="uint"
+///        summary="low 32 bits of the seconds part of the timestamp"/>
+///   <arg name="tv_nsec" type="uint"
+///        summary="nanoseconds part of the timestamp"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PresentationTime {
+    /// The high 32 bits of the seconds of the timestamp.
+    pub tv_sec_hi: WlUInt,
+    /// The low 32 bits of the seconds of the timestamp.
+    pub tv_sec_lo: WlUInt,
+    /// The nanoseconds of the timestamp.
+    pub tv_nsec: WlUInt,
+}
+
+impl PresentationTime {
+    /// Returns the presentation time on the monotonic clock.
+    pub fn timestamp(&self) -> Duration {
+        let secs = u64::from(self.tv_sec_hi.get()) << 32 | u64::from(self.tv_sec_lo.get());
+        Duration::new(secs, self.tv_nsec.get())
+    }
+}
+
+impl TryFrom<&[u8]> for PresentationTime {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `PresentationTime` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `tv_sec_hi` (u32) - The high 32 bits of the seconds
+    /// - Bytes 4-7: `tv_sec_lo` (u32) - The low 32 bits of the seconds
+    /// - Bytes 8-11: `tv_nsec` (u32) - The nanoseconds
+    fn try_from(buf: &[u8]) -> Result<PresentationTime> {
+        Ok(PresentationTime {
+            tv_sec_hi: WlUInt::read(buf, 0)?,
+            tv_sec_lo: WlUInt::read(buf, 4)?,
+            tv_nsec: WlUInt::read(buf, 8)?,
+        })
+    }
+}
+
+/// Handles an `ext_image_copy_capture_frame_v1.presentation_time` event by
+/// recording the timestamp in the pending frame.
+pub(super) fn handle_ext_image_copy_capture_frame_presentation_time(
+    conn: &Connection,
+    frame_id: u32,
+    event: PresentationTime,
+) -> Result<()> {
+    if let Some(state) = conn.object_data::<FrameState>(frame_id) {
+        state.pending.borrow_mut().presentation_time = Some(event.timestamp());
+    }
+
+    Ok(())
+}
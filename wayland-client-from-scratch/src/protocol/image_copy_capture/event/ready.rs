@@ -0,0 +1,35 @@
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::image_copy_capture::{CaptureFrameEvent, FrameState},
+};
+
+/// Handles an `ext_image_copy_capture_frame_v1.ready` event by delivering
+/// the accumulated frame information to the listener.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="ready">
+///   <description summary="frame is available for reading">
+///     Called as soon as the frame is copied, indicating it is available
+///     for reading.
+///
+///     The buffer may be re-used by the client after this event.
+///
+///     After receiving this event, the client must destroy the object.
+///   </description>
+/// </event>
+/// ```
+pub(super) fn handle_ext_image_copy_capture_frame_ready(
+    conn: &Connection,
+    frame_id: u32,
+) -> Result<()> {
+    let frame = conn
+        .object_data::<FrameState>(frame_id)
+        .map(|state| state.pending.take())
+        .unwrap_or_default();
+
+    conn.emit(frame_id, CaptureFrameEvent::Ready(frame));
+
+    Ok(())
+}
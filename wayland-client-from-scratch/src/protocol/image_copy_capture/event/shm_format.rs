@@ -0,0 +1,60 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{image_copy_capture::SessionState, types::WlUInt},
+};
+
+/// Represents an `ext_image_copy_capture_session_v1.shm_format` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="shm_format">
+///   <description summary="shm buffer format">
+///     Provides the format that must be used for shared-memory buffers.
+///
+///     This event may be emitted multiple times, in which case the client may
+///     choose any given format.
+///   </description>
+///   <arg name="format" type="uint" enum="wl_shm.format" summary="shm format"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShmFormat {
+    /// The `wl_shm` format code.
+    pub format: WlUInt,
+}
+
+impl TryFrom<&[u8]> for ShmFormat {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `ShmFormat` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `format` (u32) - The `wl_shm` format code
+    fn try_from(buf: &[u8]) -> Result<ShmFormat> {
+        Ok(ShmFormat {
+            format: WlUInt::read(buf, 0)?,
+        })
+    }
+}
+
+/// Handles an `ext_image_copy_capture_session_v1.shm_format` event by
+/// adding the format to the pending constraints.
+///
+/// The code is kept as is, so formats unknown to this crate can still be
+/// matched by clients that know them.
+pub(super) fn handle_ext_image_copy_capture_session_shm_format(
+    conn: &Connection,
+    session_id: u32,
+    event: ShmFormat,
+) -> Result<()> {
+    if let Some(state) = conn.object_data::<SessionState>(session_id) {
+        state
+            .pending
+            .borrow_mut()
+            .shm_formats
+            .push(event.format.get());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use crate::{
+    connection::Connection, error::Result, protocol::image_copy_capture::CaptureSessionEvent,
+};
+
+/// Handles an `ext_image_copy_capture_session_v1.stopped` event by notifying the listener.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="stopped">
+///   <description summary="session is no longer available">
+///     This event indicates that the capture session has stopped and is no
+///     longer available. This can happen in a number of cases, e.g. when the
+///     underlying source is destroyed, if the user decides to end the image
+///     capture, or if an unrecoverable runtime error has occurred.
+///
+///     The client should destroy the session after receiving this event.
+///   </description>
+/// </event>
+/// ```
+pub(super) fn handle_ext_image_copy_capture_session_stopped(
+    conn: &Connection,
+    session_id: u32,
+) -> Result<()> {
+    conn.emit(session_id, CaptureSessionEvent::Stopped);
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{image_copy_capture::FrameState, output::event::geometry::Transform, types::WlUInt},
+};
+
+/// Represents an `ext_image_copy_capture_frame_v1.transform` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="transform">
+///   <description summary="buffer transform">
+///     This event is sent before the ready event and holds the transform that
+///     the compositor has applied to the buffer contents.
+///   </description>
+///   <arg name="transform" type="uint" enum="wl_output.transform"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameTransform {
+    /// The transform applied to the buffer contents, as a `Transform` value.
+    pub transform: WlUInt,
+}
+
+impl TryFrom<&[u8]> for FrameTransform {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `FrameTransform` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `transform` (u32) - The `wl_output.transform` value
+    fn try_from(buf: &[u8]) -> Result<FrameTransform> {
+        Ok(FrameTransform {
+            transform: WlUInt::read(buf, 0)?,
+        })
+    }
+}
+
+/// Handles an `ext_image_copy_capture_frame_v1.transform` event by
+/// recording the transform in the pending frame.
+pub(super) fn handle_ext_image_copy_capture_frame_transform(
+    conn: &Connection,
+    frame_id: u32,
+    event: FrameTransform,
+) -> Result<()> {
+    if let Some(state) = conn.object_data::<FrameState>(frame_id) {
+        state.pending.borrow_mut().transform =
+            Transform::try_from(event.transform.get()).unwrap_or(Transform::Normal);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,429 @@
+pub mod event;
+pub mod request;
+
+use std::{sync::Arc, time::Duration};
+
+use crate::{
+    connection::{Connection, Listener},
+    error::{Result, anyhow},
+    geometry::Rect,
+    protocol::{
+        WlObjectId,
+        buffer::Buffer,
+        image_capture_source::ImageCaptureSource,
+        output::event::geometry::Transform,
+        shm::event::format::Format,
+        types::{WlInt, WlNewId, WlObject, WlUInt},
+    },
+    sync::Lock,
+    wl_bitfield,
+};
+
+use event::failed::FailureReason;
+
+/// The highest `ext_image_copy_capture_manager_v1` version this crate implements.
+pub const EXT_IMAGE_COPY_CAPTURE_MANAGER_VERSION: u32 = 1;
+
+wl_bitfield! {
+    /// Options of a capture session, see
+    /// [`ImageCopyCaptureManager::create_session`].
+    CaptureOptions {
+        /// Composite the cursors onto the captured frames.
+        PAINT_CURSORS = 1,
+    }
+}
+
+/// A dma-buf format a capture session accepts, with its modifiers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DmabufConstraint {
+    /// The DRM fourcc code of the format.
+    pub format: u32,
+    /// The DRM format modifiers the buffer may use with the format.
+    pub modifiers: Vec<u64>,
+}
+
+/// The buffers a capture session can copy frames into, announced by a
+/// batch of constraint events ending with `done`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BufferConstraints {
+    /// The width buffers must have, in pixels.
+    pub width: u32,
+    /// The height buffers must have, in pixels.
+    pub height: u32,
+    /// The `wl_shm` format codes shared-memory buffers may use.
+    pub shm_formats: Vec<u32>,
+    /// The `dev_t` of the device dma-buf buffers must be allocated on.
+    pub dmabuf_device: Option<u64>,
+    /// The formats dma-buf buffers may use.
+    pub dmabuf_formats: Vec<DmabufConstraint>,
+}
+
+impl BufferConstraints {
+    /// Returns `true` if shared-memory buffers of `format` are accepted.
+    pub fn supports_shm_format(&self, format: Format) -> bool {
+        self.shm_formats.contains(&(format as u32))
+    }
+}
+
+/// An event delivered to the handler of a [`CaptureSession`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CaptureSessionEvent {
+    /// The buffers frames can be copied into, sent after the session is
+    /// created and whenever they change, e.g. when the output is resized.
+    /// Frames attached to buffers not matching them fail.
+    Constraints(BufferConstraints),
+    /// The session stopped for good, e.g. because the source went away or
+    /// the user ended the capture; the session should be destroyed.
+    Stopped,
+}
+
+/// A frame copied into the attached buffer, delivered with
+/// [`CaptureFrameEvent::Ready`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapturedFrame {
+    /// The transform the compositor applied to the buffer contents.
+    pub transform: Transform,
+    /// The regions of the buffer that changed since the previous frame of
+    /// the session, in buffer coordinates. The first frame is fully damaged.
+    pub damage: Vec<Rect>,
+    /// When the content was presented, on the monotonic clock, if known.
+    pub presentation_time: Option<Duration>,
+}
+
+impl Default for CapturedFrame {
+    fn default() -> Self {
+        CapturedFrame {
+            transform: Transform::Normal,
+            damage: Vec::new(),
+            presentation_time: None,
+        }
+    }
+}
+
+/// An event delivered to the handler of a [`CaptureFrame`]; each frame
+/// receives exactly one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CaptureFrameEvent {
+    /// The frame was copied and the buffer can be read.
+    Ready(CapturedFrame),
+    /// The copy failed and the buffer contents are undefined.
+    Failed(FailureReason),
+}
+
+/// State of a capture session updated by its events.
+#[derive(Default)]
+pub(crate) struct SessionState {
+    /// The constraints accumulated since the last `done` event.
+    pub(crate) pending: Lock<BufferConstraints>,
+    /// The constraints of the last complete batch.
+    pub(crate) constraints: Lock<Option<BufferConstraints>>,
+}
+
+/// State of a capture frame updated by its events.
+#[derive(Default)]
+pub(crate) struct FrameState {
+    /// The frame information accumulated until `ready`.
+    pub(crate) pending: Lock<CapturedFrame>,
+}
+
+/// A bound `ext_image_copy_capture_manager_v1` global, which copies the
+/// content of capture sources into client buffers.
+///
+/// Together with sources such as those of
+/// [`OutputImageCaptureSourceManager`](crate::protocol::image_capture_source::OutputImageCaptureSourceManager),
+/// it replaces `zwlr_screencopy_manager_v1`.
+#[derive(Clone)]
+pub struct ImageCopyCaptureManager {
+    /// The object ID of the bound manager.
+    id: u32,
+    /// The negotiated interface version.
+    version: u32,
+    /// The connection the manager was bound on.
+    conn: Connection,
+}
+
+impl ImageCopyCaptureManager {
+    /// Binds the `ext_image_copy_capture_manager_v1` global advertised by
+    /// the compositor.
+    pub fn bind(conn: &Connection) -> Result<ImageCopyCaptureManager> {
+        let (id, version) = conn.bind(
+            WlObjectId::ImageCopyCaptureManager,
+            EXT_IMAGE_COPY_CAPTURE_MANAGER_VERSION,
+        )?;
+
+        Ok(ImageCopyCaptureManager {
+            id,
+            version,
+            conn: conn.clone(),
+        })
+    }
+
+    /// Returns the object ID of the manager.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Starts a session capturing `source`.
+    ///
+    /// `handler` first receives the [`BufferConstraints`] of the session,
+    /// after which frames can be captured with [`CaptureSession::create_frame`].
+    pub fn create_session<F>(
+        &self,
+        source: &ImageCaptureSource,
+        options: CaptureOptions,
+        handler: F,
+    ) -> Result<CaptureSession>
+    where
+        F: FnMut(&Connection, CaptureSessionEvent) + Send + 'static,
+    {
+        let id = self
+            .conn
+            .new_object(WlObjectId::ImageCopyCaptureSession, self.version)?;
+        request::create_session(
+            &self.conn,
+            self.id,
+            WlNewId(id),
+            WlObject(source.id()),
+            WlUInt(options.bits()),
+        )?;
+
+        let state = Arc::new(SessionState::default());
+        self.conn.set_object_data(id, state.clone());
+        let listener: Listener<CaptureSessionEvent> = Box::new(handler);
+        self.conn.set_listener(id, listener);
+
+        Ok(CaptureSession {
+            id,
+            version: self.version,
+            conn: self.conn.clone(),
+            state,
+        })
+    }
+
+    /// Destroys the manager; existing sessions keep working.
+    pub fn destroy(self) -> Result<()> {
+        request::destroy(&self.conn, self.id)
+    }
+}
+
+/// An `ext_image_copy_capture_session_v1`, which captures frames of a
+/// source one at a time.
+pub struct CaptureSession {
+    /// The object ID of the session.
+    id: u32,
+    /// The interface version, inherited from the manager.
+    version: u32,
+    /// The connection the session was created on.
+    conn: Connection,
+    /// The buffer constraints, shared with the event handler.
+    state: Arc<SessionState>,
+}
+
+impl CaptureSession {
+    /// Returns the object ID of the session.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the buffer constraints last announced by the compositor, if any.
+    pub fn constraints(&self) -> Option<BufferConstraints> {
+        self.state.constraints.borrow().clone()
+    }
+
+    /// Creates the frame the next capture is made with.
+    ///
+    /// A session has at most one frame at a time: the previous frame must
+    /// have been dropped before. `handler` receives the outcome of the capture.
+    pub fn create_frame<F>(&self, handler: F) -> Result<CaptureFrame>
+    where
+        F: FnMut(&Connection, CaptureFrameEvent) + Send + 'static,
+    {
+        let id = self
+            .conn
+            .new_object(WlObjectId::ImageCopyCaptureFrame, self.version)?;
+        request::create_frame(&self.conn, self.id, WlNewId(id))?;
+
+        self.conn
+            .set_object_data(id, Arc::new(FrameState::default()));
+        let listener: Listener<CaptureFrameEvent> = Box::new(handler);
+        self.conn.set_listener(id, listener);
+
+        Ok(CaptureFrame {
+            id,
+            conn: self.conn.clone(),
+        })
+    }
+
+    /// Destroys the session.
+    ///
+    /// Dropping the handle does the same.
+    pub fn destroy(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl Drop for CaptureSession {
+    fn drop(&mut self) {
+        self.conn
+            .destroy_on_drop(|conn| request::destroy_session(conn, self.id));
+    }
+}
+
+/// An `ext_image_copy_capture_frame_v1`, a single copy of the content of a
+/// capture session into a client buffer.
+///
+/// Attach a buffer matching the [`BufferConstraints`] of the session,
+/// optionally declare which parts of it changed since it was last captured
+/// into, then [`CaptureFrame::capture`]. Drop the frame once it reported
+/// its outcome.
+pub struct CaptureFrame {
+    /// The object ID of the frame.
+    id: u32,
+    /// The connection the frame was created on.
+    conn: Connection,
+}
+
+impl CaptureFrame {
+    /// Returns the object ID of the frame.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Sets the buffer the frame is copied into, replacing any previous one.
+    ///
+    /// Unlike buffers attached to surfaces, the compositor does not release
+    /// the buffer: it can be read and reused once the frame is ready.
+    pub fn attach_buffer(&self, buffer: &Buffer) -> Result<()> {
+        self.conn
+            .check_same_connection(buffer.connection(), WlObjectId::Buffer, buffer.id())?;
+
+        request::attach_buffer(&self.conn, self.id, WlObject(buffer.id()))
+    }
+
+    /// Declares that `rect` of the attached buffer changed since it was last
+    /// captured into, in buffer coordinates.
+    ///
+    /// The compositor may skip copying the parts of the buffer that were
+    /// not damaged, so clients reusing buffers across frames save copies.
+    ///
+    /// # Errors
+    /// Returns an error if the rectangle is empty or has a negative origin.
+    pub fn damage_buffer(&self, rect: Rect) -> Result<()> {
+        if rect.x < 0 || rect.y < 0 || rect.width <= 0 || rect.height <= 0 {
+            return Err(anyhow!(
+                "ext_image_copy_capture_frame_v1 {}: invalid buffer damage {},{} {}x{}",
+                self.id,
+                rect.x,
+                rect.y,
+                rect.width,
+                rect.height
+            ));
+        }
+
+        request::damage_buffer(
+            &self.conn,
+            self.id,
+            WlInt(rect.x),
+            WlInt(rect.y),
+            WlInt(rect.width),
+            WlInt(rect.height),
+        )
+    }
+
+    /// Starts the copy into the attached buffer.
+    ///
+    /// Apart from the first frame of the session, the compositor may wait
+    /// for the content to change before copying. Can only be called once.
+    pub fn capture(&self) -> Result<()> {
+        request::capture(&self.conn, self.id)
+    }
+
+    /// Destroys the frame, cancelling the capture if it is still pending.
+    ///
+    /// Dropping the handle does the same.
+    pub fn destroy(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl Drop for CaptureFrame {
+    fn drop(&mut self) {
+        self.conn
+            .destroy_on_drop(|conn| request::destroy_frame(conn, self.id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{os::unix::net::UnixStream, sync::Arc};
+
+    use super::*;
+    use crate::protocol::message::WlMessage;
+
+    /// Encodes 32-bit words the way they are sent on the wire.
+    fn words(words: &[u32]) -> Vec<u8> {
+        words.iter().flat_map(|word| word.to_ne_bytes()).collect()
+    }
+
+    #[test]
+    fn constraint_batches_replace_each_other() {
+        let (client, _compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+
+        let id = conn
+            .new_object(WlObjectId::ImageCopyCaptureSession, 1)
+            .unwrap();
+        conn.set_object_data(id, Arc::new(SessionState::default()));
+        let events = Arc::new(Lock::new(Vec::new()));
+        let listener: Listener<CaptureSessionEvent> = Box::new({
+            let events = events.clone();
+            move |_, event| events.borrow_mut().push(event)
+        });
+        conn.set_listener(id, listener);
+
+        let mut dmabuf_format = words(&[0x3432_5258, 16]);
+        dmabuf_format.extend_from_slice(&0u64.to_ne_bytes());
+        dmabuf_format.extend_from_slice(&0x0100_0000_0000_0001u64.to_ne_bytes());
+        let batches = [
+            vec![
+                (0, words(&[1920, 1080])),
+                (1, words(&[1])),
+                (3, dmabuf_format),
+                (4, Vec::new()),
+            ],
+            vec![(0, words(&[1280, 720])), (4, Vec::new())],
+        ];
+        for (opcode, data) in batches.into_iter().flatten() {
+            let message = WlMessage::new(id, opcode, &data).unwrap();
+            event::handle_ext_image_copy_capture_session_event(&conn, message).unwrap();
+        }
+
+        let first = BufferConstraints {
+            width: 1920,
+            height: 1080,
+            shm_formats: vec![1],
+            dmabuf_device: None,
+            dmabuf_formats: vec![DmabufConstraint {
+                format: 0x3432_5258,
+                modifiers: vec![0, 0x0100_0000_0000_0001],
+            }],
+        };
+        let second = BufferConstraints {
+            width: 1280,
+            height: 720,
+            ..BufferConstraints::default()
+        };
+        assert!(first.supports_shm_format(Format::Xrgb8888));
+        assert_eq!(
+            *events.borrow(),
+            [
+                CaptureSessionEvent::Constraints(first),
+                CaptureSessionEvent::Constraints(second)
+            ]
+        );
+    }
+}
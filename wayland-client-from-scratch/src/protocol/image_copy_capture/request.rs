@@ -0,0 +1,294 @@
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::types::{WlInt, WlNewId, WlObject, WlUInt},
+    wl_request_opcode, wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to the
+    /// `ext_image_copy_capture_manager_v1` object.
+    Opcode {
+        /// Creates a capture session for a source.
+        CreateSession = 0,
+
+        /// Creates a session capturing the cursor over a source.
+        CreatePointerCursorSession = 1,
+
+        /// Destroys the manager; existing sessions keep working.
+        Destroy = 2 (destructor),
+    }
+}
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to an
+    /// `ext_image_copy_capture_session_v1` object.
+    SessionOpcode {
+        /// Creates the frame the next capture is made with.
+        CreateFrame = 0,
+
+        /// Destroys the session.
+        Destroy = 1 (destructor),
+    }
+}
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to an
+    /// `ext_image_copy_capture_frame_v1` object.
+    FrameOpcode {
+        /// Destroys the frame.
+        Destroy = 0 (destructor),
+
+        /// Sets the buffer the frame is copied into.
+        AttachBuffer = 1,
+
+        /// Marks a region of the buffer as changed since its last capture.
+        DamageBuffer = 2,
+
+        /// Starts the capture.
+        Capture = 3,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `ext_image_copy_capture_manager_v1.create_session` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="create_session">
+    ///   <description summary="capture an image capture source">
+    ///     Create a capturing session for an image capture source.
+    ///
+    ///     If the paint_cursors option is set, cursors shall be composited onto
+    ///     the captured frame. The cursor must not be composited onto the frame
+    ///     if this flag is not set.
+    ///
+    ///     If the options bitfield is invalid, the invalid_option protocol error
+    ///     is sent.
+    ///   </description>
+    ///   <arg name="session" type="new_id" interface="ext_image_copy_capture_session_v1"/>
+    ///   <arg name="source" type="object" interface="ext_image_capture_source_v1"/>
+    ///   <arg name="options" type="uint" enum="options"/>
+    /// </request>
+    /// ```
+    CreateSessionParam {
+        /// The object ID to assign to the new session.
+        session: WlNewId,
+        /// The source to capture.
+        source: WlObject,
+        /// The capture options, see `CaptureOptions`.
+        options: WlUInt,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `ext_image_copy_capture_session_v1.create_frame` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="create_frame">
+    ///   <description summary="create a frame">
+    ///     Create a capture frame for this session.
+    ///
+    ///     At most one frame object can exist for a given session at any time. If
+    ///     a client sends a create_frame request before a previous frame object
+    ///     has been destroyed, the duplicate_frame protocol error is raised.
+    ///   </description>
+    ///   <arg name="frame" type="new_id" interface="ext_image_copy_capture_frame_v1"/>
+    /// </request>
+    /// ```
+    CreateFrameParam {
+        /// The object ID to assign to the new frame.
+        frame: WlNewId,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `ext_image_copy_capture_frame_v1.attach_buffer` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="attach_buffer">
+    ///   <description summary="attach buffer to session">
+    ///     Attach a buffer to the session.
+    ///
+    ///     The wl_buffer.release request is unused.
+    ///
+    ///     The new buffer replaces any previously attached buffer.
+    ///
+    ///     This request must not be sent after capture, or else the
+    ///     already_captured protocol error is raised.
+    ///   </description>
+    ///   <arg name="buffer" type="object" interface="wl_buffer"/>
+    /// </request>
+    /// ```
+    AttachBufferParam {
+        /// The buffer to copy the frame into.
+        buffer: WlObject,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `ext_image_copy_capture_frame_v1.damage_buffer` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="damage_buffer">
+    ///   <description summary="damage buffer">
+    ///     Apply damage to the buffer which is to be captured next. This request
+    ///     may be sent multiple times to describe a region.
+    ///
+    ///     The client indicates the accumulated damage since this wl_buffer was
+    ///     last captured. During capture, the server will use this information to
+    ///     avoid needlessly copying the whole buffer. The client must not change
+    ///     the content of the buffer outside of the damaged region.
+    ///
+    ///     The damage is in buffer coordinates.
+    ///
+    ///     If the damage rectangle is invalid (e.g. width or height not positive),
+    ///     the invalid_buffer_damage protocol error is raised.
+    ///
+    ///     This request must not be sent after capture, or else the
+    ///     already_captured protocol error is raised.
+    ///   </description>
+    ///   <arg name="x" type="int" summary="region x coordinate"/>
+    ///   <arg name="y" type="int" summary="region y coordinate"/>
+    ///   <arg name="width" type="int" summary="region width"/>
+    ///   <arg name="height" type="int" summary="region height"/>
+    /// </request>
+    /// ```
+    DamageBufferParam {
+        /// The left edge of the damaged region.
+        x: WlInt,
+        /// The top edge of the damaged region.
+        y: WlInt,
+        /// The width of the damaged region.
+        width: WlInt,
+        /// The height of the damaged region.
+        height: WlInt,
+    }
+}
+
+/// Sends an `ext_image_copy_capture_manager_v1.create_session` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `manager` - The ID of the bound copy capture manager
+/// * `session` - The object ID to assign to the new session
+/// * `source` - The source to capture
+/// * `options` - The capture options
+pub fn create_session(
+    conn: &Connection,
+    manager: u32,
+    session: WlNewId,
+    source: WlObject,
+    options: WlUInt,
+) -> Result<()> {
+    conn.send_request(
+        manager,
+        Opcode::CreateSession,
+        &CreateSessionParam::new(session, source, options),
+    )?;
+
+    Ok(())
+}
+
+/// Sends an `ext_image_copy_capture_manager_v1.destroy` request to the compositor.
+pub fn destroy(conn: &Connection, manager: u32) -> Result<()> {
+    conn.send_request(manager, Opcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends an `ext_image_copy_capture_session_v1.create_frame` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `session` - The ID of the capture session
+/// * `frame` - The object ID to assign to the new frame
+pub fn create_frame(conn: &Connection, session: u32, frame: WlNewId) -> Result<()> {
+    conn.send_request(
+        session,
+        SessionOpcode::CreateFrame,
+        &CreateFrameParam::new(frame),
+    )?;
+
+    Ok(())
+}
+
+/// Sends an `ext_image_copy_capture_session_v1.destroy` request to the compositor.
+pub fn destroy_session(conn: &Connection, session: u32) -> Result<()> {
+    conn.send_request(session, SessionOpcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends an `ext_image_copy_capture_frame_v1.destroy` request to the compositor.
+pub fn destroy_frame(conn: &Connection, frame: u32) -> Result<()> {
+    conn.send_request(frame, FrameOpcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends an `ext_image_copy_capture_frame_v1.attach_buffer` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `frame` - The ID of the capture frame
+/// * `buffer` - The buffer to copy the frame into
+pub fn attach_buffer(conn: &Connection, frame: u32, buffer: WlObject) -> Result<()> {
+    conn.send_request(
+        frame,
+        FrameOpcode::AttachBuffer,
+        &AttachBufferParam::new(buffer),
+    )?;
+
+    Ok(())
+}
+
+/// Sends an `ext_image_copy_capture_frame_v1.damage_buffer` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `frame` - The ID of the capture frame
+/// * `x`, `y`, `width`, `height` - The damaged region, in buffer coordinates
+pub fn damage_buffer(
+    conn: &Connection,
+    frame: u32,
+    x: WlInt,
+    y: WlInt,
+    width: WlInt,
+    height: WlInt,
+) -> Result<()> {
+    conn.send_request(
+        frame,
+        FrameOpcode::DamageBuffer,
+        &DamageBufferParam::new(x, y, width, height),
+    )?;
+
+    Ok(())
+}
+
+/// Sends an `ext_image_copy_capture_frame_v1.capture` request to the compositor.
+///
+/// # Specification Reference
+/// ```xml
+/// <request name="capture">
+///   <description summary="capture a frame">
+///     Capture a frame.
+///
+///     Unless this is the first successful captured frame performed in this
+///     session, the compositor may wait an indefinite amount of time for the
+///     source content to change before performing the copy.
+///
+///     This request may only be sent once, or else the already_captured
+///     protocol error is raised. A buffer must be attached before this request
+///     is sent, or else the no_buffer protocol error is raised.
+///   </description>
+/// </request>
+/// ```
+pub fn capture(conn: &Connection, frame: u32) -> Result<()> {
+    conn.send_request(frame, FrameOpcode::Capture, &())?;
+
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        endian::Endian,
+        keyboard::{KeyboardEvent, KeyboardState},
+        seat::KeyboardFocus,
+        types::{WL_TYPE_OBJECT_LEN, WL_TYPE_UINT_LEN, WlArray, WlObject, WlUInt},
+    },
+};
+
+/// Represents a `wl_keyboard.enter` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="enter">
+///   <description summary="enter event">
+///     Notification that this seat's keyboard focus is on a certain
+///     surface.
+///
+///     The compositor must send the wl_keyboard.modifiers event after this
+///     event.
+///
+///     In the wl_keyboard logical state, this event sets the active surface to
+///     the surface argument and the keys currently logically down to the keys
+///     in the keys argument. The compositor must not send this event if the
+///     wl_keyboard already had an active surface immediately before this event.
+///   </description>
+///   <arg name="serial" type="uint" summary="serial number of the enter event"/>
+///   <arg name="surface" type="object" interface="wl_surface" summary="surface gaining keyboard focus"/>
+///   <arg name="keys" type="array" summary="the keys currently logically down"/>
+/// </event>
+/// ```
+#[derive(Debug)]
+pub struct Enter {
+    /// The serial number of the enter event.
+    pub serial: WlUInt,
+    /// The surface gaining keyboard focus.
+    pub surface: WlObject,
+    /// The keys currently logically down, as native-endian 32-bit keycodes.
+    pub keys: WlArray,
+}
+
+impl Enter {
+    /// Returns the Linux evdev codes of the keys held when the focus was gained.
+    pub fn keys(&self) -> Vec<u32> {
+        self.keys
+            .as_slice()
+            .chunks_exact(WL_TYPE_UINT_LEN)
+            .map(|key| Endian::NATIVE.decode_u32_at(key))
+            .collect()
+    }
+}
+
+impl TryFrom<&[u8]> for Enter {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Enter` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `serial` (u32) - The serial number of the event
+    /// - Bytes 4-7: `surface` (u32) - The surface gaining focus
+    /// - Bytes 8+: `keys` (WlArray) - The keys currently down
+    fn try_from(buf: &[u8]) -> Result<Enter> {
+        let serial = WlUInt::read(buf, 0)?;
+        let surface = WlObject::read(buf, WL_TYPE_UINT_LEN)?;
+        let keys_start_pos = WL_TYPE_UINT_LEN + WL_TYPE_OBJECT_LEN;
+        let keys = WlArray::try_from(buf.get(keys_start_pos..).unwrap_or_default())?;
+
+        Ok(Enter {
+            serial,
+            surface,
+            keys,
+        })
+    }
+}
+
+/// Handles a `wl_keyboard.enter` event.
+///
+/// Keys already held when the focus arrives are not repeated, so any pending
+/// repeat is cancelled before the listener is notified. The seat records the
+/// new focus and announces it after the listener.
+pub(super) fn handle_wl_keyboard_enter(
+    conn: &Connection,
+    keyboard_id: u32,
+    enter: Enter,
+) -> Result<()> {
+    let focus = KeyboardFocus {
+        surface: enter.surface.get(),
+        serial: enter.serial.get(),
+        pressed: enter.keys(),
+    };
+    let state = conn.object_data::<KeyboardState>(keyboard_id);
+    if let Some(state) = &state {
+        state.repeat.borrow_mut().cancel();
+        state.seat.set_serial(enter.serial.get());
+    }
+
+    conn.emit(keyboard_id, KeyboardEvent::Enter(enter));
+
+    if let Some(state) = state {
+        state.seat.focus_keyboard(conn, focus);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,120 @@
+use std::time::Instant;
+
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        keyboard::{KeyboardEvent, KeyboardState},
+        types::{WL_TYPE_UINT_LEN, WlEnum, WlUInt},
+    },
+    wl_enum,
+};
+
+wl_enum! {
+    /// Physical state of a key.
+    KeyState {
+        /// The key is not pressed.
+        Released = 0,
+        /// The key is pressed.
+        Pressed = 1,
+        /// The key was repeated while held.
+        ///
+        /// This crate synthesizes these events from `wl_keyboard.repeat_info`.
+        Repeated = 2,
+    }
+}
+
+/// Represents a `wl_keyboard.key` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="key">
+///   <description summary="key event">
+///     A key was pressed or released.
+///     The time argument is a timestamp with millisecond
+///     granularity, with an undefined base.
+///
+///     The key is a platform-specific key code that can be interpreted
+///     by feeding it to the keyboard mapping (see the keymap event).
+///
+///     If this event produces a change in modifiers, then the resulting
+///     wl_keyboard.modifiers event must be sent after this event.
+///   </description>
+///   <arg name="serial" type="uint" summary="serial number of the key event"/>
+///   <arg name="time" type="uint" summary="timestamp with millisecond granularity"/>
+///   <arg name="key" type="uint" summary="key that produced the event"/>
+///   <arg name="state" type="uint" enum="key_state" summary="physical state of the key"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Key {
+    /// The serial number of the key event; for repeats, that of the press.
+    pub serial: WlUInt,
+    /// The timestamp with millisecond granularity.
+    pub time: WlUInt,
+    /// The Linux evdev code of the key.
+    pub key: WlUInt,
+    /// Whether the key was pressed, released or repeated.
+    pub state: KeyState,
+}
+
+impl TryFrom<&[u8]> for Key {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Key` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `serial` (u32) - The serial number of the event
+    /// - Bytes 4-7: `time` (u32) - The timestamp
+    /// - Bytes 8-11: `key` (u32) - The key code
+    /// - Bytes 12-15: `state` (u32) - The key state
+    fn try_from(buf: &[u8]) -> Result<Key> {
+        Ok(Key {
+            serial: WlUInt::read(buf, 0)?,
+            time: WlUInt::read(buf, WL_TYPE_UINT_LEN)?,
+            key: WlUInt::read(buf, 2 * WL_TYPE_UINT_LEN)?,
+            state: WlEnum::read(buf, 3 * WL_TYPE_UINT_LEN)?.get().try_into()?,
+        })
+    }
+}
+
+/// Handles a `wl_keyboard.key` event.
+///
+/// Presses start repeating the key and releases stop it before the listener
+/// sees the event; both update the keys held on the focused surface.
+pub(super) fn handle_wl_keyboard_key(conn: &Connection, keyboard_id: u32, key: Key) -> Result<()> {
+    let state = conn.object_data::<KeyboardState>(keyboard_id);
+
+    if let Some(state) = &state {
+        state.seat.set_serial(key.serial.get());
+
+        if key.state != KeyState::Repeated {
+            state
+                .seat
+                .update_pressed_key(key.key.get(), key.state == KeyState::Pressed);
+        }
+
+        let mut repeat = state.repeat.borrow_mut();
+        match key.state {
+            KeyState::Pressed => repeat.press(
+                key.key.get(),
+                key.serial.get(),
+                key.time.get(),
+                Instant::now(),
+            ),
+            KeyState::Released => repeat.release(key.key.get()),
+            KeyState::Repeated => {}
+        }
+    }
+
+    conn.emit(keyboard_id, KeyboardEvent::Key(key));
+
+    #[cfg(feature = "xkb")]
+    if key.state == KeyState::Pressed
+        && let Some(symbol) = state.and_then(|state| state.key_symbol(key.key.get()))
+    {
+        conn.emit(keyboard_id, KeyboardEvent::Symbol(symbol));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use std::os::fd::OwnedFd;
+
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::{
+        keyboard::KeyboardEvent,
+        types::{WL_TYPE_ENUM_LEN, WlEnum, WlUInt},
+    },
+    wl_enum,
+};
+
+wl_enum! {
+    /// Formats of the keymap sent with `wl_keyboard.keymap`.
+    KeymapFormat {
+        /// No keymap; the client must understand how to interpret the raw keycode.
+        NoKeymap = 0,
+        /// A libxkbcommon compatible, null-terminated string.
+        XkbV1 = 1,
+    }
+}
+
+/// Represents a `wl_keyboard.keymap` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="keymap">
+///   <description summary="keyboard mapping">
+///     This event provides a file descriptor to the client which can be
+///     memory-mapped in read-only mode to provide a keyboard mapping
+///     description.
+///
+///     From version 7 onwards, the fd must be mapped with MAP_PRIVATE by
+///     the recipient, as MAP_SHARED may fail.
+///   </description>
+///   <arg name="format" type="uint" enum="keymap_format" summary="keymap format"/>
+///   <arg name="fd" type="fd" summary="keymap file descriptor"/>
+///   <arg name="size" type="uint" summary="keymap size, in bytes"/>
+/// </event>
+/// ```
+#[derive(Debug)]
+pub struct Keymap {
+    /// The format of the keymap.
+    pub format: KeymapFormat,
+    /// The file holding the keymap, to be mapped read-only and private.
+    pub fd: OwnedFd,
+    /// The size of the keymap in bytes.
+    pub size: WlUInt,
+}
+
+impl Keymap {
+    /// Parses the event arguments, combining them with the received descriptor.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `format` (u32) - The keymap format
+    /// - Bytes 4-7: `size` (u32) - The keymap size; the `fd` travels out of band
+    pub fn parse(buf: &[u8], fd: OwnedFd) -> Result<Keymap> {
+        let format = WlEnum::read(buf, 0)?.get().try_into()?;
+        let size = WlUInt::read(buf, WL_TYPE_ENUM_LEN)?;
+
+        Ok(Keymap { format, fd, size })
+    }
+}
+
+/// Handles a `wl_keyboard.keymap` event by compiling the keymap, with the
+/// `xkb` feature, and passing it to the listener.
+pub(super) fn handle_wl_keyboard_keymap(
+    conn: &Connection,
+    keyboard_id: u32,
+    keymap: Keymap,
+) -> Result<()> {
+    #[cfg(feature = "xkb")]
+    if let Some(state) = conn.object_data::<crate::protocol::keyboard::KeyboardState>(keyboard_id) {
+        let xkb = match keymap.format {
+            KeymapFormat::XkbV1 => Some(crate::xkb::XkbState::from_keymap(&keymap)?),
+            KeymapFormat::NoKeymap => None,
+        };
+        state.xkb.replace(xkb);
+    }
+
+    conn.emit(keyboard_id, KeyboardEvent::Keymap(keymap));
+
+    Ok(())
+}
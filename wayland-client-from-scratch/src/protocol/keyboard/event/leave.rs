@@ -0,0 +1,77 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        keyboard::{KeyboardEvent, KeyboardState},
+        types::{WL_TYPE_UINT_LEN, WlObject, WlUInt},
+    },
+};
+
+/// Represents a `wl_keyboard.leave` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="leave">
+///   <description summary="leave event">
+///     Notification that this seat's keyboard focus is no longer on
+///     a certain surface.
+///
+///     The leave notification is sent before the enter notification
+///     for the new focus.
+///
+///     In the wl_keyboard logical state, this event resets all values to their
+///     defaults. The compositor must not send this event if the active surface
+///     of the wl_keyboard was not equal to the surface argument immediately
+///     before this event.
+///   </description>
+///   <arg name="serial" type="uint" summary="serial number of the leave event"/>
+///   <arg name="surface" type="object" interface="wl_surface" summary="surface that lost keyboard focus"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Leave {
+    /// The serial number of the leave event.
+    pub serial: WlUInt,
+    /// The surface that lost keyboard focus.
+    pub surface: WlObject,
+}
+
+impl TryFrom<&[u8]> for Leave {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Leave` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `serial` (u32) - The serial number of the event
+    /// - Bytes 4-7: `surface` (u32) - The surface losing focus
+    fn try_from(buf: &[u8]) -> Result<Leave> {
+        Ok(Leave {
+            serial: WlUInt::read(buf, 0)?,
+            surface: WlObject::read(buf, WL_TYPE_UINT_LEN)?,
+        })
+    }
+}
+
+/// Handles a `wl_keyboard.leave` event.
+///
+/// Losing the focus stops key repeat: the release of the held key will be
+/// delivered to another client, if at all. The seat forgets the focus and
+/// announces it after the listener.
+pub(super) fn handle_wl_keyboard_leave(
+    conn: &Connection,
+    keyboard_id: u32,
+    leave: Leave,
+) -> Result<()> {
+    let state = conn.object_data::<KeyboardState>(keyboard_id);
+    if let Some(state) = &state {
+        state.repeat.borrow_mut().cancel();
+    }
+
+    conn.emit(keyboard_id, KeyboardEvent::Leave(leave));
+
+    if let Some(state) = state {
+        state.seat.unfocus_keyboard(conn, leave.surface.get());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,168 @@
+pub mod enter;
+pub mod key;
+pub mod keymap;
+pub mod leave;
+pub mod modifiers;
+pub mod repeat_info;
+
+use crate::{
+    connection::Connection,
+    error::{Error, Result, anyhow},
+    protocol::message::WlMessage,
+};
+
+/// Represents the event types that can be emitted by a Wayland keyboard object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    /// Provides the keyboard mapping as a file descriptor.
+    ///
+    /// # Event Arguments
+    /// - `format`: The keymap format
+    /// - `fd`: The file holding the keymap
+    /// - `size`: The keymap size in bytes
+    Keymap = 0,
+
+    /// The keyboard focus entered a surface.
+    ///
+    /// # Event Arguments
+    /// - `serial`: The serial number of the event
+    /// - `surface`: The surface gaining focus
+    /// - `keys`: The keys currently held down
+    Enter = 1,
+
+    /// The keyboard focus left a surface.
+    ///
+    /// # Event Arguments
+    /// - `serial`: The serial number of the event
+    /// - `surface`: The surface losing focus
+    Leave = 2,
+
+    /// A key was pressed or released.
+    ///
+    /// # Event Arguments
+    /// - `serial`: The serial number of the event
+    /// - `time`: The timestamp in milliseconds
+    /// - `key`: The key code
+    /// - `state`: The physical key state
+    Key = 3,
+
+    /// The modifier or layout state changed.
+    ///
+    /// # Event Arguments
+    /// - `serial`: The serial number of the event
+    /// - `mods_depressed`, `mods_latched`, `mods_locked`: The modifier masks
+    /// - `group`: The keyboard layout
+    Modifiers = 4,
+
+    /// The key repeat rate and delay (since version 4).
+    ///
+    /// # Event Arguments
+    /// - `rate`: Repeats per second
+    /// - `delay`: Milliseconds before the first repeat
+    RepeatInfo = 5,
+}
+
+impl TryFrom<u16> for Opcode {
+    type Error = Error;
+
+    /// Attempts to convert a raw opcode value into a structured `WlKeyboardEvent`.
+    fn try_from(value: u16) -> Result<Opcode> {
+        match value {
+            0 => Ok(Opcode::Keymap),
+            1 => Ok(Opcode::Enter),
+            2 => Ok(Opcode::Leave),
+            3 => Ok(Opcode::Key),
+            4 => Ok(Opcode::Modifiers),
+            5 => Ok(Opcode::RepeatInfo),
+            _ => Err(anyhow!("Invalid wl_keyboard event opcode: {}", value)),
+        }
+    }
+}
+
+impl Opcode {
+    /// Returns the interface version the event was introduced in.
+    pub fn since(self) -> u32 {
+        match self {
+            Opcode::Keymap | Opcode::Enter | Opcode::Leave | Opcode::Key | Opcode::Modifiers => 1,
+            Opcode::RepeatInfo => 4,
+        }
+    }
+}
+
+/// A decoded `wl_keyboard` event, with its arguments.
+#[derive(Debug)]
+pub enum Event {
+    /// Provides the keyboard mapping as a file descriptor.
+    Keymap(keymap::Keymap),
+    /// The keyboard focus entered a surface.
+    Enter(enter::Enter),
+    /// The keyboard focus left a surface.
+    Leave(leave::Leave),
+    /// A key was pressed or released.
+    Key(key::Key),
+    /// The modifier or layout state changed.
+    Modifiers(modifiers::Modifiers),
+    /// The key repeat rate and delay (since version 4).
+    RepeatInfo(repeat_info::RepeatInfo),
+}
+
+impl Event {
+    /// Decodes a `wl_keyboard` event from its message.
+    ///
+    /// The file descriptors the event carries are taken from those
+    /// received on `conn`, in order.
+    ///
+    /// # Errors
+    /// Returns an error if the opcode is unknown or the arguments are malformed.
+    pub fn decode(conn: &Connection, msg: &WlMessage) -> Result<Event> {
+        let buf: &[u8] = &msg.data;
+
+        Ok(match Opcode::try_from(msg.header.opcode)? {
+            Opcode::Keymap => Event::Keymap(keymap::Keymap::parse(buf, conn.take_fd()?)?),
+            Opcode::Enter => Event::Enter(enter::Enter::try_from(buf)?),
+            Opcode::Leave => Event::Leave(leave::Leave::try_from(buf)?),
+            Opcode::Key => Event::Key(key::Key::try_from(buf)?),
+            Opcode::Modifiers => Event::Modifiers(modifiers::Modifiers::try_from(buf)?),
+            Opcode::RepeatInfo => Event::RepeatInfo(repeat_info::RepeatInfo::try_from(buf)?),
+        })
+    }
+
+    /// Returns the opcode of the event.
+    pub fn opcode(&self) -> Opcode {
+        match self {
+            Event::Keymap(_) => Opcode::Keymap,
+            Event::Enter(_) => Opcode::Enter,
+            Event::Leave(_) => Opcode::Leave,
+            Event::Key(_) => Opcode::Key,
+            Event::Modifiers(_) => Opcode::Modifiers,
+            Event::RepeatInfo(_) => Opcode::RepeatInfo,
+        }
+    }
+}
+
+/// Dispatches incoming Wayland keyboard events to their appropriate handler functions.
+///
+/// # Event Routing
+/// * `Keymap` events are routed to `keymap::handle_wl_keyboard_keymap`
+/// * `Enter` events are routed to `enter::handle_wl_keyboard_enter`
+/// * `Leave` events are routed to `leave::handle_wl_keyboard_leave`
+/// * `Key` events are routed to `key::handle_wl_keyboard_key`
+/// * `Modifiers` events are routed to `modifiers::handle_wl_keyboard_modifiers`
+/// * `RepeatInfo` events are routed to `repeat_info::handle_wl_keyboard_repeat_info`
+pub fn handle_wl_keyboard_event(conn: &Connection, msg: WlMessage) -> Result<()> {
+    let keyboard_id = msg.header.object_id;
+
+    // Route the event to the appropriate handler based on type
+    match Event::decode(conn, &msg)? {
+        Event::Keymap(event) => keymap::handle_wl_keyboard_keymap(conn, keyboard_id, event),
+        Event::Enter(event) => enter::handle_wl_keyboard_enter(conn, keyboard_id, event),
+        Event::Leave(event) => leave::handle_wl_keyboard_leave(conn, keyboard_id, event),
+        Event::Key(event) => key::handle_wl_keyboard_key(conn, keyboard_id, event),
+        Event::Modifiers(event) => {
+            modifiers::handle_wl_keyboard_modifiers(conn, keyboard_id, event)
+        }
+        Event::RepeatInfo(event) => {
+            repeat_info::handle_wl_keyboard_repeat_info(conn, keyboard_id, event)
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        keyboard::KeyboardEvent,
+        types::{WL_TYPE_UINT_LEN, WlUInt},
+    },
+};
+
+/// Represents a `wl_keyboard.modifiers` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="modifiers">
+///   <description summary="modifier and group state">
+///     Notifies clients that the modifier and/or group state has
+///     changed, and it should update its local state.
+///   </description>
+///   <arg name="serial" type="uint" summary="serial number of the modifiers event"/>
+///   <arg name="mods_depressed" type="uint" summary="depressed modifiers"/>
+///   <arg name="mods_latched" type="uint" summary="latched modifiers"/>
+///   <arg name="mods_locked" type="uint" summary="locked modifiers"/>
+///   <arg name="group" type="uint" summary="keyboard layout"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Modifiers {
+    /// The serial number of the modifiers event.
+    pub serial: WlUInt,
+    /// The modifiers physically held down.
+    pub mods_depressed: WlUInt,
+    /// The modifiers latched until the next key press.
+    pub mods_latched: WlUInt,
+    /// The modifiers locked on, such as Caps Lock.
+    pub mods_locked: WlUInt,
+    /// The active keyboard layout.
+    pub group: WlUInt,
+}
+
+impl TryFrom<&[u8]> for Modifiers {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Modifiers` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `serial` (u32) - The serial number of the event
+    /// - Bytes 4-7: `mods_depressed` (u32) - The depressed modifier mask
+    /// - Bytes 8-11: `mods_latched` (u32) - The latched modifier mask
+    /// - Bytes 12-15: `mods_locked` (u32) - The locked modifier mask
+    /// - Bytes 16-19: `group` (u32) - The keyboard layout
+    fn try_from(buf: &[u8]) -> Result<Modifiers> {
+        Ok(Modifiers {
+            serial: WlUInt::read(buf, 0)?,
+            mods_depressed: WlUInt::read(buf, WL_TYPE_UINT_LEN)?,
+            mods_latched: WlUInt::read(buf, 2 * WL_TYPE_UINT_LEN)?,
+            mods_locked: WlUInt::read(buf, 3 * WL_TYPE_UINT_LEN)?,
+            group: WlUInt::read(buf, 4 * WL_TYPE_UINT_LEN)?,
+        })
+    }
+}
+
+/// Handles a `wl_keyboard.modifiers` event by updating the compiled keymap,
+/// with the `xkb` feature, and passing it to the listener.
+pub(super) fn handle_wl_keyboard_modifiers(
+    conn: &Connection,
+    keyboard_id: u32,
+    modifiers: Modifiers,
+) -> Result<()> {
+    #[cfg(feature = "xkb")]
+    if let Some(state) = conn.object_data::<crate::protocol::keyboard::KeyboardState>(keyboard_id)
+        && let Some(xkb) = state.xkb.borrow_mut().as_mut()
+    {
+        xkb.update_modifiers(&modifiers);
+    }
+
+    conn.emit(keyboard_id, KeyboardEvent::Modifiers(modifiers));
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        keyboard::{KeyboardEvent, KeyboardState},
+        types::{WL_TYPE_INT_LEN, WlInt},
+    },
+};
+
+/// Represents a `wl_keyboard.repeat_info` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="repeat_info" since="4">
+///   <description summary="repeat rate and delay">
+///     Informs the client about the keyboard's repeat rate and delay.
+///
+///     This event is sent as soon as the wl_keyboard object has been created,
+///     and is guaranteed to be received by the client before any key press
+///     event.
+///
+///     Negative values for either rate or delay are illegal. A rate of zero
+///     will disable any repeating (regardless of the value of delay).
+///
+///     This event can be sent later on as well with a new value if necessary,
+///     so clients should continue listening for the event past the creation
+///     of wl_keyboard.
+///   </description>
+///   <arg name="rate" type="int" summary="the rate of repeating keys in characters per second"/>
+///   <arg name="delay" type="int" summary="delay in milliseconds since key down until repeating starts"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RepeatInfo {
+    /// Repeated characters per second, 0 disabling key repeat.
+    pub rate: WlInt,
+    /// Milliseconds between a key press and the first repeat.
+    pub delay: WlInt,
+}
+
+impl TryFrom<&[u8]> for RepeatInfo {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `RepeatInfo` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `rate` (i32) - The repeat rate
+    /// - Bytes 4-7: `delay` (i32) - The repeat delay
+    fn try_from(buf: &[u8]) -> Result<RepeatInfo> {
+        Ok(RepeatInfo {
+            rate: WlInt::read(buf, 0)?,
+            delay: WlInt::read(buf, WL_TYPE_INT_LEN)?,
+        })
+    }
+}
+
+/// Handles a `wl_keyboard.repeat_info` event by reconfiguring key repeat.
+pub(super) fn handle_wl_keyboard_repeat_info(
+    conn: &Connection,
+    keyboard_id: u32,
+    repeat_info: RepeatInfo,
+) -> Result<()> {
+    if let Some(state) = conn.object_data::<KeyboardState>(keyboard_id) {
+        state
+            .repeat
+            .borrow_mut()
+            .set_repeat_info(repeat_info.rate.get(), repeat_info.delay.get());
+    }
+
+    conn.emit(keyboard_id, KeyboardEvent::RepeatInfo(repeat_info));
+
+    Ok(())
+}
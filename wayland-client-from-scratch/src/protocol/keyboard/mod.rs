@@ -0,0 +1,176 @@
+pub mod event;
+pub mod request;
+
+use std::{sync::Arc, time::Instant};
+
+#[cfg(feature = "xkb")]
+use crate::xkb::{KeySymbol, XkbState};
+use crate::{
+    connection::{Connection, Listener},
+    error::Result,
+    key_repeat::KeyRepeat,
+    protocol::{WlObjectId, seat::SeatState, types::WlUInt},
+    sync::Lock,
+};
+
+use event::{
+    enter::Enter,
+    key::{Key, KeyState},
+    keymap::Keymap,
+    leave::Leave,
+    modifiers::Modifiers,
+    repeat_info::RepeatInfo,
+};
+
+/// An event delivered to the handler of a [`Keyboard`].
+pub enum KeyboardEvent {
+    /// The keymap to interpret key codes with.
+    Keymap(Keymap),
+    /// The keyboard focus entered a surface.
+    Enter(Enter),
+    /// The keyboard focus left a surface.
+    Leave(Leave),
+    /// A key was pressed, released or repeated.
+    ///
+    /// Events with [`KeyState::Repeated`] are synthesized by the client
+    /// while a key is held.
+    Key(Key),
+    /// The modifier or layout state changed.
+    Modifiers(Modifiers),
+    /// The compositor changed the key repeat settings.
+    RepeatInfo(RepeatInfo),
+    /// The keysym and text of a pressed or repeated key, resolved with the
+    /// keymap of the compositor, following the [`KeyboardEvent::Key`] event
+    /// of the press or repeat.
+    #[cfg(feature = "xkb")]
+    Symbol(KeySymbol),
+}
+
+/// State of a keyboard updated by its events.
+pub(crate) struct KeyboardState {
+    /// The key repeat engine fed by key, focus and repeat info events.
+    repeat: Lock<KeyRepeat>,
+    /// The state of the seat the keyboard belongs to, which records event serials.
+    pub(crate) seat: Arc<SeatState>,
+    /// The compiled keymap, once received.
+    #[cfg(feature = "xkb")]
+    pub(crate) xkb: Lock<Option<XkbState>>,
+}
+
+impl KeyboardState {
+    /// Resolves a pressed or repeated key with the compiled keymap, if any.
+    #[cfg(feature = "xkb")]
+    pub(crate) fn key_symbol(&self, key: u32) -> Option<KeySymbol> {
+        self.xkb.borrow().as_ref().map(|xkb| xkb.key_symbol(key))
+    }
+}
+
+/// A `wl_keyboard`, the keyboards of a seat.
+///
+/// Created with [`Seat::get_keyboard`](crate::protocol::seat::Seat::get_keyboard).
+pub struct Keyboard {
+    /// The object ID of the keyboard.
+    id: u32,
+    /// The interface version, inherited from the seat.
+    version: u32,
+    /// The connection the keyboard was created on.
+    conn: Connection,
+    /// The state shared with the event handlers.
+    state: Arc<KeyboardState>,
+}
+
+impl Keyboard {
+    /// Wraps a keyboard object that was just created on `conn`.
+    pub(crate) fn new(
+        id: u32,
+        version: u32,
+        conn: &Connection,
+        seat: Arc<SeatState>,
+        handler: Listener<KeyboardEvent>,
+    ) -> Keyboard {
+        let state = Arc::new(KeyboardState {
+            repeat: Lock::new(KeyRepeat::new()),
+            seat,
+            #[cfg(feature = "xkb")]
+            xkb: Lock::new(None),
+        });
+        conn.set_object_data(id, state.clone());
+        conn.set_listener(id, handler);
+
+        Keyboard {
+            id,
+            version,
+            conn: conn.clone(),
+            state,
+        }
+    }
+
+    /// Returns the object ID of the keyboard.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the current key repeat rate (per second) and delay (in milliseconds).
+    pub fn repeat_info(&self) -> (i32, i32) {
+        let repeat = self.state.repeat.borrow();
+        (repeat.rate(), repeat.delay())
+    }
+
+    /// Releases the keyboard.
+    ///
+    /// Before version 3 the keyboard cannot be released and the object is
+    /// kept alive on the compositor side.
+    ///
+    /// Dropping the handle does the same.
+    pub fn release(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl Drop for Keyboard {
+    fn drop(&mut self) {
+        self.state.repeat.borrow_mut().cancel();
+        self.conn.remove_listener(self.id);
+
+        if self.version >= 3 {
+            self.conn
+                .destroy_on_drop(|conn| request::release(conn, self.id));
+        }
+    }
+}
+
+/// Returns the earliest key repeat deadline among the keyboards of `conn`.
+pub(crate) fn next_repeat_deadline(conn: &Connection) -> Option<Instant> {
+    conn.objects_of(WlObjectId::Keyboard)
+        .into_iter()
+        .filter_map(|id| conn.object_data::<KeyboardState>(id))
+        .filter_map(|state| state.repeat.borrow().deadline())
+        .min()
+}
+
+/// Delivers the key repeats due at `now` to the keyboard handlers.
+pub(crate) fn dispatch_key_repeats(conn: &Connection, now: Instant) {
+    for id in conn.objects_of(WlObjectId::Keyboard) {
+        let Some(state) = conn.object_data::<KeyboardState>(id) else {
+            continue;
+        };
+
+        let repeated = state.repeat.borrow_mut().poll(now);
+        if let Some(repeated) = repeated {
+            let key = Key {
+                serial: WlUInt(repeated.serial),
+                time: WlUInt(repeated.time),
+                key: WlUInt(repeated.key),
+                state: KeyState::Repeated,
+            };
+            conn.emit(id, KeyboardEvent::Key(key));
+
+            #[cfg(feature = "xkb")]
+            if let Some(symbol) = state.key_symbol(repeated.key) {
+                conn.emit(id, KeyboardEvent::Symbol(symbol));
+            }
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use crate::{connection::Connection, error::Result, wl_request_opcode};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to a Wayland keyboard object.
+    Opcode {
+        /// Releases the keyboard object (since version 3).
+        Release = 0 (destructor),
+    }
+}
+
+/// Sends a `wl_keyboard.release` request to the compositor.
+pub fn release(conn: &Connection, keyboard: u32) -> Result<()> {
+    conn.send_request(keyboard, Opcode::Release, &())?;
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        layer_shell::{LayerConfigure, LayerSurfaceEvent, LayerSurfaceState},
+        types::{WL_TYPE_UINT_LEN, WlUInt},
+    },
+};
+
+/// Represents a `zwlr_layer_surface_v1.configure` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="configure">
+///   <description summary="suggest a surface change">
+///     The configure event asks the client to resize its surface.
+///
+///     Clients should arrange their surface for the new states, and then send
+///     an ack_configure request with the serial sent in this configure event at
+///     some point before committing the new surface.
+///
+///     The client is free to dismiss all but the last configure event it
+///     received.
+///
+///     The width and height arguments specify the size of the window in
+///     surface-local coordinates.
+///
+///     The size is a hint, in the sense that the client is free to ignore it if
+///     it doesn't resize, pick a smaller size (to satisfy aspect ratio or
+///     resize in steps of NxM pixels). If the client picks a smaller size and
+///     is anchored to two opposite anchors (e.g. 'top' and 'bottom'), the
+///     surface will be centered on this axis.
+///
+///     If the width or height arguments are zero, it means the client should
+///     decide its own window dimension.
+///   </description>
+///   <arg name="serial" type="uint"/>
+///   <arg name="width" type="uint"/>
+///   <arg name="height" type="uint"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Configure {
+    /// The serial to acknowledge.
+    pub serial: WlUInt,
+    /// The suggested width, 0 if left to the client.
+    pub width: WlUInt,
+    /// The suggested height, 0 if left to the client.
+    pub height: WlUInt,
+}
+
+impl TryFrom<&[u8]> for Configure {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Configure` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `serial` (u32) - The serial to acknowledge
+    /// - Bytes 4-7: `width` (u32) - The suggested width
+    /// - Bytes 8-11: `height` (u32) - The suggested height
+    fn try_from(buf: &[u8]) -> Result<Configure> {
+        Ok(Configure {
+            serial: WlUInt::read(buf, 0)?,
+            width: WlUInt::read(buf, WL_TYPE_UINT_LEN)?,
+            height: WlUInt::read(buf, 2 * WL_TYPE_UINT_LEN)?,
+        })
+    }
+}
+
+/// Handles a `zwlr_layer_surface_v1.configure` event by recording it as the
+/// latest configuration before notifying the listener.
+pub(super) fn handle_zwlr_layer_surface_configure(
+    conn: &Connection,
+    layer_surface_id: u32,
+    configure: Configure,
+) -> Result<()> {
+    if let Some(state) = conn.object_data::<LayerSurfaceState>(layer_surface_id) {
+        *state.ready.borrow_mut() = Some(LayerConfigure::from(configure));
+    }
+
+    conn.emit(layer_surface_id, LayerSurfaceEvent::Configure(configure));
+
+    Ok(())
+}
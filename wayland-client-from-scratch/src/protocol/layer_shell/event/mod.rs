@@ -0,0 +1,98 @@
+pub mod configure;
+
+use crate::{
+    connection::Connection,
+    error::{Error, Result, anyhow},
+    protocol::{layer_shell::LayerSurfaceEvent, message::WlMessage},
+};
+
+/// Represents the event types that can be emitted by a `zwlr_layer_surface_v1` object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    /// Suggests a surface size.
+    ///
+    /// # Event Arguments
+    /// - `serial`: The serial to acknowledge
+    /// - `width`, `height`: The suggested size, 0 where left to the client
+    Configure = 0,
+
+    /// The compositor removed the surface, which should be destroyed.
+    Closed = 1,
+}
+
+impl TryFrom<u16> for Opcode {
+    type Error = Error;
+
+    /// Attempts to convert a raw opcode value into a structured `ZwlrLayerSurfaceEvent`.
+    fn try_from(value: u16) -> Result<Opcode> {
+        match value {
+            0 => Ok(Opcode::Configure),
+            1 => Ok(Opcode::Closed),
+            _ => Err(anyhow!(
+                "Invalid zwlr_layer_surface_v1 event opcode: {}",
+                value
+            )),
+        }
+    }
+}
+
+impl Opcode {
+    /// Returns the interface version the event was introduced in.
+    pub fn since(self) -> u32 {
+        match self {
+            Opcode::Configure | Opcode::Closed => 1,
+        }
+    }
+}
+
+/// A decoded `zwlr_layer_surface_v1` event, with its arguments.
+#[derive(Debug)]
+pub enum Event {
+    /// Suggests a surface size.
+    Configure(configure::Configure),
+    /// The compositor removed the surface, which should be destroyed.
+    Closed,
+}
+
+impl Event {
+    /// Decodes a `zwlr_layer_surface_v1` event from its message.
+    ///
+    /// # Errors
+    /// Returns an error if the opcode is unknown or the arguments are malformed.
+    pub fn decode(_conn: &Connection, msg: &WlMessage) -> Result<Event> {
+        let buf: &[u8] = &msg.data;
+
+        Ok(match Opcode::try_from(msg.header.opcode)? {
+            Opcode::Configure => Event::Configure(configure::Configure::try_from(buf)?),
+            Opcode::Closed => Event::Closed,
+        })
+    }
+
+    /// Returns the opcode of the event.
+    pub fn opcode(&self) -> Opcode {
+        match self {
+            Event::Configure(_) => Opcode::Configure,
+            Event::Closed => Opcode::Closed,
+        }
+    }
+}
+
+/// Dispatches incoming `zwlr_layer_surface_v1` events to their appropriate handler functions.
+///
+/// # Event Routing
+/// * `Configure` events are routed to `configure::handle_zwlr_layer_surface_configure`
+/// * `Closed` events are forwarded to the listener directly
+pub fn handle_zwlr_layer_surface_event(conn: &Connection, msg: WlMessage) -> Result<()> {
+    let layer_surface_id = msg.header.object_id;
+
+    // Route the event to the appropriate handler based on type
+    match Event::decode(conn, &msg)? {
+        Event::Configure(event) => {
+            configure::handle_zwlr_layer_surface_configure(conn, layer_surface_id, event)
+        }
+        Event::Closed => {
+            conn.emit(layer_surface_id, LayerSurfaceEvent::Closed);
+            Ok(())
+        }
+    }
+}
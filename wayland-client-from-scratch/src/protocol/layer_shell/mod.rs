@@ -0,0 +1,453 @@
+pub mod event;
+pub mod request;
+
+use std::sync::Arc;
+
+use crate::{
+    connection::{Connection, Listener},
+    error::{Result, anyhow},
+    protocol::{
+        WlObjectId,
+        output::Output,
+        surface::{Surface, SurfaceRole, SurfaceState},
+        types::{WlInt, WlNewId, WlObject, WlString, WlUInt},
+        xdg_popup::XdgPopup,
+    },
+    sync::Lock,
+    wl_bitfield, wl_enum,
+};
+
+use event::configure::Configure;
+
+/// The highest `zwlr_layer_shell_v1` version this crate implements.
+pub const ZWLR_LAYER_SHELL_VERSION: u32 = 4;
+
+/// The first `zwlr_layer_surface_v1` version supporting `set_layer`.
+const ZWLR_LAYER_SURFACE_SET_LAYER_SINCE: u32 = 2;
+
+/// The first `zwlr_layer_shell_v1` version supporting `destroy`.
+const ZWLR_LAYER_SHELL_DESTROY_SINCE: u32 = 3;
+
+wl_enum! {
+    /// The stacking layer of a layer surface, see `zwlr_layer_shell_v1.layer`.
+    ///
+    /// Layers are drawn in this order, each above the previous one; windows
+    /// sit between [`Layer::Bottom`] and [`Layer::Top`].
+    Layer {
+        /// Below everything, e.g. a wallpaper.
+        Background = 0,
+        /// Below windows, e.g. desktop widgets.
+        Bottom = 1,
+        /// Above windows, e.g. a panel.
+        Top = 2,
+        /// Above fullscreen windows, e.g. a notification or a lock screen.
+        Overlay = 3,
+    }
+}
+
+wl_enum! {
+    /// How a layer surface receives keyboard focus, see
+    /// `zwlr_layer_surface_v1.keyboard_interactivity`.
+    KeyboardInteractivity {
+        /// The surface never gets keyboard focus.
+        None = 0,
+        /// The surface takes keyboard focus while shown on the top or
+        /// overlay layer.
+        Exclusive = 1,
+        /// The surface gets keyboard focus when the user gives it, like a
+        /// window (since version 4).
+        OnDemand = 2,
+    }
+}
+
+wl_bitfield! {
+    /// The output edges a layer surface is anchored to, see
+    /// `zwlr_layer_surface_v1.anchor`.
+    Anchor {
+        /// The top edge.
+        TOP = 1,
+        /// The bottom edge.
+        BOTTOM = 2,
+        /// The left edge.
+        LEFT = 4,
+        /// The right edge.
+        RIGHT = 8,
+    }
+}
+
+/// An event delivered to the handler of a [`LayerSurface`].
+pub enum LayerSurfaceEvent {
+    /// The compositor suggests a size for the surface.
+    Configure(Configure),
+    /// The compositor removed the surface, e.g. because its output was
+    /// unplugged; it should be destroyed.
+    Closed,
+}
+
+/// The size requested by a `zwlr_layer_surface_v1.configure` event.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LayerConfigure {
+    /// The serial acknowledging the configuration.
+    pub serial: u32,
+    /// The suggested width, 0 if left to the client.
+    pub width: u32,
+    /// The suggested height, 0 if left to the client.
+    pub height: u32,
+}
+
+impl From<Configure> for LayerConfigure {
+    fn from(configure: Configure) -> LayerConfigure {
+        LayerConfigure {
+            serial: configure.serial.get(),
+            width: configure.width.get(),
+            height: configure.height.get(),
+        }
+    }
+}
+
+/// State of a layer surface updated by its events.
+#[derive(Default)]
+pub(crate) struct LayerSurfaceState {
+    /// The latest configuration not taken by the application yet.
+    pub(crate) ready: Lock<Option<LayerConfigure>>,
+}
+
+/// A bound `zwlr_layer_shell_v1` global, which turns surfaces into desktop
+/// shell components such as panels, wallpapers and notifications.
+#[derive(Clone)]
+pub struct LayerShell {
+    /// The object ID of the bound global.
+    id: u32,
+    /// The negotiated interface version.
+    version: u32,
+    /// The connection the global was bound on.
+    conn: Connection,
+}
+
+impl LayerShell {
+    /// Binds the `zwlr_layer_shell_v1` global advertised by the compositor.
+    pub fn bind(conn: &Connection) -> Result<LayerShell> {
+        let (id, version) = conn.bind(WlObjectId::LayerShell, ZWLR_LAYER_SHELL_VERSION)?;
+
+        Ok(LayerShell {
+            id,
+            version,
+            conn: conn.clone(),
+        })
+    }
+
+    /// Returns the object ID of the global.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Gives `surface` the layer surface role.
+    ///
+    /// The surface must then be committed without a buffer, after setting
+    /// its size and anchors; the compositor answers with a configure, taken
+    /// with [`LayerSurface::take_configure`], before the first buffer.
+    ///
+    /// # Arguments
+    /// * `output` - The output to show the surface on, or `None` to let the
+    ///   compositor choose
+    /// * `layer` - The layer the surface is stacked in
+    /// * `namespace` - The purpose of the surface, such as `"panel"`
+    /// * `handler` - Receives the events of the layer surface
+    ///
+    /// # Errors
+    /// Returns an error if the surface already has another role or a buffer.
+    pub fn get_layer_surface<F>(
+        &self,
+        surface: &Surface,
+        output: Option<&Output>,
+        layer: Layer,
+        namespace: &str,
+        handler: F,
+    ) -> Result<LayerSurface>
+    where
+        F: FnMut(&Connection, LayerSurfaceEvent) + Send + 'static,
+    {
+        self.conn
+            .check_same_connection(surface.connection(), WlObjectId::Surface, surface.id())?;
+        if let Some(output) = output {
+            self.conn.check_same_connection(
+                output.connection(),
+                WlObjectId::Output,
+                output.id(),
+            )?;
+        }
+
+        let surface_state = surface.state();
+        if surface_state.has_buffer() {
+            return Err(anyhow!(
+                "wl_surface {} must not have a buffer when its layer surface is created",
+                surface.id()
+            ));
+        }
+        surface_state.assign_role(surface.id(), SurfaceRole::LayerSurface)?;
+
+        let id = self
+            .conn
+            .new_object(WlObjectId::LayerSurface, self.version)?;
+        request::get_layer_surface(
+            &self.conn,
+            self.id,
+            WlNewId(id),
+            WlObject(surface.id()),
+            WlObject(output.map_or(0, Output::id)),
+            WlUInt(layer as u32),
+            WlString::new(namespace)?,
+        )?;
+
+        let state = Arc::new(LayerSurfaceState::default());
+        self.conn.set_object_data(id, state.clone());
+        let listener: Listener<LayerSurfaceEvent> = Box::new(handler);
+        self.conn.set_listener(id, listener);
+
+        Ok(LayerSurface {
+            id,
+            version: self.version,
+            conn: self.conn.clone(),
+            state,
+            surface: surface_state,
+        })
+    }
+
+    /// Destroys the global; existing layer surfaces keep working.
+    ///
+    /// Compositors older than version 3 have no destructor, so nothing is
+    /// sent to them.
+    pub fn destroy(self) -> Result<()> {
+        if self.version < ZWLR_LAYER_SHELL_DESTROY_SINCE {
+            return Ok(());
+        }
+
+        request::destroy(&self.conn, self.id)
+    }
+}
+
+/// A `zwlr_layer_surface_v1`, a surface stacked in a layer of an output and
+/// placed by its anchors rather than by the user.
+///
+/// Created with [`LayerShell::get_layer_surface`]. Size, anchors, margins
+/// and keyboard interactivity are double-buffered and apply on the next
+/// commit of the surface.
+pub struct LayerSurface {
+    /// The object ID of the layer surface.
+    id: u32,
+    /// The interface version, inherited from `zwlr_layer_shell_v1`.
+    version: u32,
+    /// The connection the layer surface was created on.
+    conn: Connection,
+    /// The state shared with the event handlers.
+    state: Arc<LayerSurfaceState>,
+    /// The state of the underlying `wl_surface`.
+    surface: Arc<SurfaceState>,
+}
+
+impl LayerSurface {
+    /// Returns the object ID of the layer surface.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the negotiated interface version.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Sets the size of the surface; a zero dimension is left to the
+    /// compositor, which requires anchoring to both opposite edges.
+    pub fn set_size(&self, width: u32, height: u32) -> Result<()> {
+        request::set_size(&self.conn, self.id, WlUInt(width), WlUInt(height))
+    }
+
+    /// Anchors the surface to `anchor`, centering it along the edges it is
+    /// not anchored to.
+    pub fn set_anchor(&self, anchor: Anchor) -> Result<()> {
+        request::set_anchor(&self.conn, self.id, WlUInt(anchor.bits()))
+    }
+
+    /// Asks the compositor to keep `zone` surface-local units from the
+    /// anchor edge clear of other surfaces, such as maximized windows.
+    ///
+    /// 0 lets other surfaces move the surface out of their exclusive zone,
+    /// -1 keeps it in place regardless.
+    pub fn set_exclusive_zone(&self, zone: i32) -> Result<()> {
+        request::set_exclusive_zone(&self.conn, self.id, WlInt(zone))
+    }
+
+    /// Sets the distance between the surface and the edges it is anchored to.
+    pub fn set_margin(&self, top: i32, right: i32, bottom: i32, left: i32) -> Result<()> {
+        request::set_margin(
+            &self.conn,
+            self.id,
+            WlInt(top),
+            WlInt(right),
+            WlInt(bottom),
+            WlInt(left),
+        )
+    }
+
+    /// Sets how the surface receives keyboard focus.
+    pub fn set_keyboard_interactivity(&self, interactivity: KeyboardInteractivity) -> Result<()> {
+        request::set_keyboard_interactivity(&self.conn, self.id, WlUInt(interactivity as u32))
+    }
+
+    /// Makes the surface the parent of `popup`, which must have been created
+    /// without a parent and not been committed yet.
+    pub fn get_popup(&self, popup: &XdgPopup) -> Result<()> {
+        request::get_popup(&self.conn, self.id, WlObject(popup.id()))
+    }
+
+    /// Moves the surface to another layer.
+    ///
+    /// # Errors
+    /// Returns an error if the compositor is older than version 2.
+    pub fn set_layer(&self, layer: Layer) -> Result<()> {
+        if self.version < ZWLR_LAYER_SURFACE_SET_LAYER_SINCE {
+            return Err(anyhow!(
+                "zwlr_layer_surface_v1.set_layer requires version {}, layer surface {} has version {}",
+                ZWLR_LAYER_SURFACE_SET_LAYER_SINCE,
+                self.id,
+                self.version
+            ));
+        }
+
+        request::set_layer(&self.conn, self.id, WlUInt(layer as u32))
+    }
+
+    /// Returns the latest size requested by the compositor that was not
+    /// taken yet, without taking it.
+    pub fn pending_configure(&self) -> Option<LayerConfigure> {
+        *self.state.ready.borrow()
+    }
+
+    /// Takes the latest size requested by the compositor.
+    ///
+    /// Its serial is acknowledged automatically on the next
+    /// [`Surface::commit`].
+    pub fn take_configure(&self) -> Option<LayerConfigure> {
+        let configure = self.state.ready.take()?;
+
+        let (id, serial) = (self.id, configure.serial);
+        self.surface.ack_on_commit(Box::new(move |conn| {
+            request::ack_configure(conn, id, WlUInt(serial))
+        }));
+
+        Some(configure)
+    }
+
+    /// Destroys the layer surface, unmapping it.
+    ///
+    /// Dropping the handle does the same.
+    pub fn destroy(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl Drop for LayerSurface {
+    fn drop(&mut self) {
+        self.conn.remove_listener(self.id);
+        self.surface.cancel_ack();
+
+        self.conn
+            .destroy_on_drop(|conn| request::destroy_surface(conn, self.id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        os::unix::net::UnixStream,
+    };
+
+    use super::*;
+    use crate::protocol::{
+        endian::Endian,
+        message::{WlMessage, WlMessageHeader},
+        registry::event::global::Global,
+        shell_role::{RoleConfigure, ShellRole},
+    };
+
+    /// Takes the configure of `role` the way shell-agnostic code does.
+    fn take_size<R: ShellRole>(role: &R, current: (i32, i32)) -> Option<(i32, i32)> {
+        Some(role.take_configure()?.apply_size(current))
+    }
+
+    #[test]
+    fn taken_configures_are_acked_before_the_next_commit() {
+        let (client, mut compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+        conn.insert_global(Global {
+            name: WlUInt(1),
+            interface: WlString::new("zwlr_layer_shell_v1").unwrap(),
+            version: WlUInt(4),
+        });
+        let shell = LayerShell::bind(&conn).unwrap();
+        let surface = Surface::new(conn.new_object(WlObjectId::Surface, 6).unwrap(), &conn);
+        let layer = shell
+            .get_layer_surface(&surface, None, Layer::Top, "panel", |_, _| {})
+            .unwrap();
+        let id = layer.id();
+
+        // Two configures of 30 and 40 pixels high, only the last one is acked
+        for (serial, height) in [(7u32, 30u32), (8, 40)] {
+            let args = [serial, 0, height].map(u32::to_ne_bytes).concat();
+            let bytes: Vec<u8> = WlMessage::new(id, 0, &args).unwrap().into();
+            compositor.write_all(&bytes).unwrap();
+        }
+        conn.dispatch().unwrap();
+
+        assert_eq!(take_size(&layer, (1920, 10)), Some((1920, 40)));
+        assert_eq!(take_size(&layer, (1920, 10)), None);
+        surface.commit().unwrap();
+        // Nothing is left to acknowledge on the next commit
+        surface.commit().unwrap();
+
+        // A configure taken by a layer surface destroyed since is not acked
+        let args = [9u32, 0, 50].map(u32::to_ne_bytes).concat();
+        let bytes: Vec<u8> = WlMessage::new(id, 0, &args).unwrap().into();
+        compositor.write_all(&bytes).unwrap();
+        conn.dispatch().unwrap();
+        assert!(layer.take_configure().is_some());
+        drop(layer);
+        surface.commit().unwrap();
+
+        let surface_id = surface.id();
+        drop((surface, shell));
+        drop(conn);
+
+        let mut received = Vec::new();
+        compositor.read_to_end(&mut received).unwrap();
+        let mut received = &received[..];
+        let mut requests = Vec::new();
+        while !received.is_empty() {
+            let header = WlMessageHeader::decode(received, Endian::NATIVE).unwrap();
+            let size = header.size as usize;
+            if header.object_id == id {
+                let serial = received[8..size].get(..4).map(|serial| serial.to_vec());
+                requests.push((header.object_id, header.opcode, serial));
+            } else if header.object_id == surface_id {
+                requests.push((header.object_id, header.opcode, None));
+            }
+            received = &received[size..];
+        }
+        let ack = request::SurfaceOpcode::AckConfigure as u16;
+        let destroy = request::SurfaceOpcode::Destroy as u16;
+        let commit = crate::protocol::surface::request::Opcode::Commit as u16;
+        assert_eq!(
+            requests[..5],
+            [
+                (id, ack, Some(8u32.to_ne_bytes().to_vec())),
+                (surface_id, commit, None),
+                (surface_id, commit, None),
+                (id, destroy, None),
+                (surface_id, commit, None),
+            ]
+        );
+    }
+}
@@ -0,0 +1,495 @@
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::types::{WlInt, WlNewId, WlObject, WlString, WlUInt},
+    wl_request_opcode, wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to the `zwlr_layer_shell_v1` object.
+    Opcode {
+        /// Creates a layer surface for a surface.
+        GetLayerSurface = 0,
+
+        /// Destroys the layer shell (since version 3).
+        Destroy = 1 (destructor),
+    }
+}
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to a `zwlr_layer_surface_v1` object.
+    SurfaceOpcode {
+        /// Sets the size of the surface.
+        SetSize = 0,
+
+        /// Anchors the surface to edges of the output.
+        SetAnchor = 1,
+
+        /// Reserves an area of the output for the surface.
+        SetExclusiveZone = 2,
+
+        /// Sets the distance to the anchor edges.
+        SetMargin = 3,
+
+        /// Sets how the surface receives keyboard focus.
+        SetKeyboardInteractivity = 4,
+
+        /// Makes an `xdg_popup` a child of the surface.
+        GetPopup = 5,
+
+        /// Acknowledges a configure event.
+        AckConfigure = 6,
+
+        /// Destroys the layer surface, unmapping it.
+        Destroy = 7 (destructor),
+
+        /// Moves the surface to another layer (since version 2).
+        SetLayer = 8,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `zwlr_layer_shell_v1.get_layer_surface` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="get_layer_surface">
+    ///   <description summary="create a layer_surface from a surface">
+    ///     Create a layer surface for an existing surface. This assigns the role of
+    ///     layer_surface, or raises a protocol error if another role is already
+    ///     assigned.
+    ///
+    ///     Creating a layer surface from a wl_surface which has a buffer attached
+    ///     or committed is a client error, and any attempts by a client to attach
+    ///     or manipulate a buffer prior to the first layer_surface.configure call
+    ///     must also be treated as errors.
+    ///
+    ///     You may pass NULL for output to allow the compositor to decide which
+    ///     output to use. Generally this will be the one that the user most
+    ///     recently interacted with.
+    ///
+    ///     Clients can specify a namespace that defines the purpose of the layer
+    ///     surface.
+    ///   </description>
+    ///   <arg name="id" type="new_id" interface="zwlr_layer_surface_v1"/>
+    ///   <arg name="surface" type="object" interface="wl_surface"/>
+    ///   <arg name="output" type="object" interface="wl_output" allow-null="true"/>
+    ///   <arg name="layer" type="uint" enum="layer" summary="layer to add this surface to"/>
+    ///   <arg name="namespace" type="string" summary="namespace for the layer surface"/>
+    /// </request>
+    /// ```
+    GetLayerSurfaceParam {
+        /// The ID of the new layer surface.
+        id: WlNewId,
+        /// The surface given the layer surface role.
+        surface: WlObject,
+        /// The output to show the surface on, or 0 to let the compositor choose.
+        output: WlObject,
+        /// The layer of the surface, a [`Layer`](super::Layer) value.
+        layer: WlUInt,
+        /// The purpose of the surface, such as `"panel"` or `"wallpaper"`.
+        namespace: WlString,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `zwlr_layer_surface_v1.set_size` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_size">
+    ///   <description summary="sets the size of the surface">
+    ///     Sets the size of the surface in surface-local coordinates. The
+    ///     compositor will display the surface centered with respect to its
+    ///     anchors.
+    ///
+    ///     If you pass 0 for either value, the compositor will assign it and
+    ///     inform you of the assignment in the configure event. You must set your
+    ///     anchor to opposite edges in the dimensions you omit; not doing so is a
+    ///     protocol error. Both values are 0 by default.
+    ///
+    ///     Size is double-buffered, see wl_surface.commit.
+    ///   </description>
+    ///   <arg name="width" type="uint"/>
+    ///   <arg name="height" type="uint"/>
+    /// </request>
+    /// ```
+    SetSizeParam {
+        /// The width, or 0 to let the compositor choose.
+        width: WlUInt,
+        /// The height, or 0 to let the compositor choose.
+        height: WlUInt,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `zwlr_layer_surface_v1.set_anchor` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_anchor">
+    ///   <description summary="configures the anchor point of the surface">
+    ///     Requests that the compositor anchor the surface to the specified edges
+    ///     and corners. If two orthogonal edges are specified (e.g. 'top' and
+    ///     'left'), then the anchor point will be the intersection of the edges
+    ///     (e.g. the top left corner of the output); otherwise the anchor point
+    ///     will be centered on that edge, or in the center if none is specified.
+    ///
+    ///     Anchor is double-buffered, see wl_surface.commit.
+    ///   </description>
+    ///   <arg name="anchor" type="uint" enum="anchor"/>
+    /// </request>
+    /// ```
+    SetAnchorParam {
+        /// The edges to anchor to, an [`Anchor`](super::Anchor) value.
+        anchor: WlUInt,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `zwlr_layer_surface_v1.set_exclusive_zone` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_exclusive_zone">
+    ///   <description summary="configures the exclusive geometry of this surface">
+    ///     Requests that the compositor avoids occluding an area with other
+    ///     surfaces. The compositor's use of this information is
+    ///     implementation-dependent - do not assume that this region will not
+    ///     actually be occluded.
+    ///
+    ///     A positive value is only meaningful if the surface is anchored to one
+    ///     edge or an edge and both perpendicular edges. If the surface is not
+    ///     anchored, anchored to only two perpendicular edges (a corner), anchored
+    ///     to only two parallel edges or anchored to all edges, a positive value
+    ///     will be treated the same as zero.
+    ///
+    ///     A negative value indicates that the surface does not want to be moved
+    ///     to accommodate other surfaces' exclusive zones.
+    ///
+    ///     Exclusive zone is double-buffered, see wl_surface.commit.
+    ///   </description>
+    ///   <arg name="zone" type="int"/>
+    /// </request>
+    /// ```
+    SetExclusiveZoneParam {
+        /// The distance from the anchor edge to keep clear, or -1 to ignore
+        /// the exclusive zones of other surfaces.
+        zone: WlInt,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `zwlr_layer_surface_v1.set_margin` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_margin">
+    ///   <description summary="sets a margin from the anchor point">
+    ///     Requests that the surface be placed some distance away from the anchor
+    ///     point on the output, in surface-local coordinates. Setting this value
+    ///     for edges you are not anchored to has no effect.
+    ///
+    ///     The exclusive zone includes the margin.
+    ///
+    ///     Margin is double-buffered, see wl_surface.commit.
+    ///   </description>
+    ///   <arg name="top" type="int"/>
+    ///   <arg name="right" type="int"/>
+    ///   <arg name="bottom" type="int"/>
+    ///   <arg name="left" type="int"/>
+    /// </request>
+    /// ```
+    SetMarginParam {
+        /// The distance to the top edge.
+        top: WlInt,
+        /// The distance to the right edge.
+        right: WlInt,
+        /// The distance to the bottom edge.
+        bottom: WlInt,
+        /// The distance to the left edge.
+        left: WlInt,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `zwlr_layer_surface_v1.set_keyboard_interactivity` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_keyboard_interactivity">
+    ///   <description summary="requests keyboard events">
+    ///     Set how keyboard events are delivered to this surface. By default,
+    ///     layer shell surfaces do not receive keyboard events; this request can
+    ///     be used to change this.
+    ///
+    ///     Keyboard interactivity is double-buffered, see wl_surface.commit.
+    ///   </description>
+    ///   <arg name="keyboard_interactivity" type="uint" enum="keyboard_interactivity"/>
+    /// </request>
+    /// ```
+    SetKeyboardInteractivityParam {
+        /// How the surface receives keyboard focus, a
+        /// [`KeyboardInteractivity`](super::KeyboardInteractivity) value.
+        keyboard_interactivity: WlUInt,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `zwlr_layer_surface_v1.get_popup` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="get_popup">
+    ///   <description summary="assign this layer_surface as an xdg_popup parent">
+    ///     This assigns an xdg_popup's parent to this layer_surface.  This popup
+    ///     should have been created via xdg_surface::get_popup with the parent set
+    ///     to NULL, and this request must be invoked before committing the popup's
+    ///     initial state.
+    ///
+    ///     See the documentation of xdg_popup for more details about what an
+    ///     xdg_popup is and how it is used.
+    ///   </description>
+    ///   <arg name="popup" type="object" interface="xdg_popup"/>
+    /// </request>
+    /// ```
+    GetPopupParam {
+        /// The popup to parent to the layer surface.
+        popup: WlObject,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `zwlr_layer_surface_v1.ack_configure` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="ack_configure">
+    ///   <description summary="ack a configure event">
+    ///     When a configure event is received, if a client commits the
+    ///     surface in response to the configure event, then the client
+    ///     must make an ack_configure request sometime before the commit
+    ///     request, passing along the serial of the configure event.
+    ///
+    ///     If the client receives multiple configure events before it
+    ///     can respond to one, it only has to ack the last configure event.
+    ///
+    ///     A client is not required to commit immediately after sending
+    ///     an ack_configure request - it may even ack_configure several times
+    ///     before its next surface commit.
+    ///   </description>
+    ///   <arg name="serial" type="uint" summary="the serial from the configure event"/>
+    /// </request>
+    /// ```
+    AckConfigureParam {
+        /// The serial of the acknowledged configure event.
+        serial: WlUInt,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `zwlr_layer_surface_v1.set_layer` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_layer" since="2">
+    ///   <description summary="change the layer of the surface">
+    ///     Change the layer that the surface is rendered on.
+    ///
+    ///     Layer is double-buffered, see wl_surface.commit.
+    ///   </description>
+    ///   <arg name="layer" type="uint" enum="zwlr_layer_shell_v1.layer" summary="layer to move this surface to"/>
+    /// </request>
+    /// ```
+    SetLayerParam {
+        /// The new layer, a [`Layer`](super::Layer) value.
+        layer: WlUInt,
+    }
+}
+
+/// Sends a `zwlr_layer_shell_v1.get_layer_surface` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `shell` - The ID of the bound layer shell
+/// * `id` - The ID of the new layer surface
+/// * `surface` - The surface given the layer surface role
+/// * `output` - The output to show the surface on, `WlObject(0)` to let the compositor choose
+/// * `layer` - The layer of the surface
+/// * `namespace` - The purpose of the surface
+pub fn get_layer_surface(
+    conn: &Connection,
+    shell: u32,
+    id: WlNewId,
+    surface: WlObject,
+    output: WlObject,
+    layer: WlUInt,
+    namespace: WlString,
+) -> Result<()> {
+    conn.send_request(
+        shell,
+        Opcode::GetLayerSurface,
+        &GetLayerSurfaceParam::new(id, surface, output, layer, namespace),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `zwlr_layer_shell_v1.destroy` request to the compositor.
+pub fn destroy(conn: &Connection, shell: u32) -> Result<()> {
+    conn.send_request(shell, Opcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends a `zwlr_layer_surface_v1.set_size` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `layer_surface` - The ID of the layer surface
+/// * `width` - The width, 0 to let the compositor choose
+/// * `height` - The height, 0 to let the compositor choose
+pub fn set_size(
+    conn: &Connection,
+    layer_surface: u32,
+    width: WlUInt,
+    height: WlUInt,
+) -> Result<()> {
+    conn.send_request(
+        layer_surface,
+        SurfaceOpcode::SetSize,
+        &SetSizeParam::new(width, height),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `zwlr_layer_surface_v1.set_anchor` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `layer_surface` - The ID of the layer surface
+/// * `anchor` - The edges to anchor to
+pub fn set_anchor(conn: &Connection, layer_surface: u32, anchor: WlUInt) -> Result<()> {
+    conn.send_request(
+        layer_surface,
+        SurfaceOpcode::SetAnchor,
+        &SetAnchorParam::new(anchor),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `zwlr_layer_surface_v1.set_exclusive_zone` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `layer_surface` - The ID of the layer surface
+/// * `zone` - The distance from the anchor edge to keep clear
+pub fn set_exclusive_zone(conn: &Connection, layer_surface: u32, zone: WlInt) -> Result<()> {
+    conn.send_request(
+        layer_surface,
+        SurfaceOpcode::SetExclusiveZone,
+        &SetExclusiveZoneParam::new(zone),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `zwlr_layer_surface_v1.set_margin` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `layer_surface` - The ID of the layer surface
+/// * `top`, `right`, `bottom`, `left` - The distances to the edges
+pub fn set_margin(
+    conn: &Connection,
+    layer_surface: u32,
+    top: WlInt,
+    right: WlInt,
+    bottom: WlInt,
+    left: WlInt,
+) -> Result<()> {
+    conn.send_request(
+        layer_surface,
+        SurfaceOpcode::SetMargin,
+        &SetMarginParam::new(top, right, bottom, left),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `zwlr_layer_surface_v1.set_keyboard_interactivity` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `layer_surface` - The ID of the layer surface
+/// * `keyboard_interactivity` - How the surface receives keyboard focus
+pub fn set_keyboard_interactivity(
+    conn: &Connection,
+    layer_surface: u32,
+    keyboard_interactivity: WlUInt,
+) -> Result<()> {
+    conn.send_request(
+        layer_surface,
+        SurfaceOpcode::SetKeyboardInteractivity,
+        &SetKeyboardInteractivityParam::new(keyboard_interactivity),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `zwlr_layer_surface_v1.get_popup` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `layer_surface` - The ID of the layer surface
+/// * `popup` - The popup to parent to the layer surface
+pub fn get_popup(conn: &Connection, layer_surface: u32, popup: WlObject) -> Result<()> {
+    conn.send_request(
+        layer_surface,
+        SurfaceOpcode::GetPopup,
+        &GetPopupParam::new(popup),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `zwlr_layer_surface_v1.ack_configure` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `layer_surface` - The ID of the layer surface
+/// * `serial` - The serial of the acknowledged configure event
+pub fn ack_configure(conn: &Connection, layer_surface: u32, serial: WlUInt) -> Result<()> {
+    conn.send_request(
+        layer_surface,
+        SurfaceOpcode::AckConfigure,
+        &AckConfigureParam::new(serial),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `zwlr_layer_surface_v1.destroy` request to the compositor.
+pub fn destroy_surface(conn: &Connection, layer_surface: u32) -> Result<()> {
+    conn.send_request(layer_surface, SurfaceOpcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends a `zwlr_layer_surface_v1.set_layer` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `layer_surface` - The ID of the layer surface
+/// * `layer` - The new layer
+pub fn set_layer(conn: &Connection, layer_surface: u32, layer: WlUInt) -> Result<()> {
+    conn.send_request(
+        layer_surface,
+        SurfaceOpcode::SetLayer,
+        &SetLayerParam::new(layer),
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,286 @@
+pub mod request;
+
+use std::{os::fd::OwnedFd, sync::Arc};
+
+use crate::{
+    connection::Connection,
+    error::{Result, anyhow},
+    fence::{ExplicitSync, Fence, ReleaseHandler},
+    protocol::{
+        WlObjectId,
+        surface::Surface,
+        types::{WlNewId, WlObject, WlUInt},
+    },
+    sync::Lock,
+};
+
+/// The highest `wp_linux_drm_syncobj_manager_v1` version this crate implements.
+pub const WP_LINUX_DRM_SYNCOBJ_MANAGER_VERSION: u32 = 1;
+
+/// A bound `wp_linux_drm_syncobj_manager_v1` global, which lets GPU clients
+/// synchronize their dma-buf buffers with the compositor explicitly, through
+/// DRM syncobj timelines, instead of relying on implicit fences.
+#[derive(Clone)]
+pub struct LinuxDrmSyncobjManager {
+    /// The object ID of the bound manager.
+    id: u32,
+    /// The negotiated interface version.
+    version: u32,
+    /// The connection the manager was bound on.
+    conn: Connection,
+}
+
+impl LinuxDrmSyncobjManager {
+    /// Binds the `wp_linux_drm_syncobj_manager_v1` global advertised by the compositor.
+    pub fn bind(conn: &Connection) -> Result<LinuxDrmSyncobjManager> {
+        let (id, version) = conn.bind(
+            WlObjectId::LinuxDrmSyncobjManager,
+            WP_LINUX_DRM_SYNCOBJ_MANAGER_VERSION,
+        )?;
+
+        Ok(LinuxDrmSyncobjManager {
+            id,
+            version,
+            conn: conn.clone(),
+        })
+    }
+
+    /// Returns the object ID of the manager.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Creates the explicit synchronization object of `surface`.
+    ///
+    /// A surface can only have one, and graphics APIs such as EGL or Vulkan
+    /// may already have created it for the surfaces they present to.
+    pub fn get_surface(&self, surface: &Surface) -> Result<SyncobjSurface> {
+        self.conn
+            .check_same_connection(surface.connection(), WlObjectId::Surface, surface.id())?;
+
+        let id = self
+            .conn
+            .new_object(WlObjectId::LinuxDrmSyncobjSurface, self.version)?;
+        request::get_surface(&self.conn, self.id, WlNewId(id), WlObject(surface.id()))?;
+
+        Ok(SyncobjSurface {
+            id,
+            conn: self.conn.clone(),
+            release: Lock::new(None),
+        })
+    }
+
+    /// Imports the DRM syncobj timeline `fd`, as exported by
+    /// `drmSyncobjHandleToFD` or a graphics API.
+    ///
+    /// The descriptor is closed once sent. A descriptor the compositor cannot
+    /// import is a protocol error.
+    pub fn import_timeline(&self, fd: OwnedFd) -> Result<SyncobjTimeline> {
+        let id = self
+            .conn
+            .new_object(WlObjectId::LinuxDrmSyncobjTimeline, self.version)?;
+        request::import_timeline(&self.conn, self.id, WlNewId(id), fd)?;
+
+        Ok(SyncobjTimeline {
+            id,
+            conn: self.conn.clone(),
+        })
+    }
+
+    /// Destroys the manager; existing timelines and surfaces keep working.
+    pub fn destroy(self) -> Result<()> {
+        request::destroy(&self.conn, self.id)
+    }
+}
+
+/// A `wp_linux_drm_syncobj_timeline_v1`, a DRM syncobj timeline shared
+/// with the compositor.
+pub struct SyncobjTimeline {
+    /// The object ID of the timeline.
+    id: u32,
+    /// The connection the timeline was imported on.
+    conn: Connection,
+}
+
+impl SyncobjTimeline {
+    /// Returns the object ID of the timeline.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Destroys the timeline; points already committed are still signaled.
+    ///
+    /// Dropping the handle does the same.
+    pub fn destroy(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl Drop for SyncobjTimeline {
+    fn drop(&mut self) {
+        self.conn
+            .destroy_on_drop(|conn| request::destroy_timeline(conn, self.id));
+    }
+}
+
+/// A `wp_linux_drm_syncobj_surface_v1`, the explicit synchronization state
+/// of a surface.
+///
+/// Once created, every commit attaching a buffer must carry both an
+/// acquire and a release point, and only dma-buf buffers may be attached.
+/// Like all surface state, the points apply on the next commit.
+pub struct SyncobjSurface {
+    /// The object ID of the synchronization object.
+    id: u32,
+    /// The connection the object was created on.
+    conn: Connection,
+    /// The timeline and point [`ExplicitSync::request_release`] uses next.
+    release: Lock<Option<(Arc<SyncobjTimeline>, u64)>>,
+}
+
+impl SyncobjSurface {
+    /// Returns the object ID of the synchronization object.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Sets the point of `timeline` that signals the attached buffer is
+    /// ready, typically when the GPU finished rendering into it.
+    pub fn set_acquire_point(&self, timeline: &SyncobjTimeline, point: u64) -> Result<()> {
+        request::set_acquire_point(
+            &self.conn,
+            self.id,
+            WlObject(timeline.id()),
+            WlUInt((point >> 32) as u32),
+            WlUInt(point as u32),
+        )
+    }
+
+    /// Sets the point of `timeline` the compositor signals once it no
+    /// longer uses the attached buffer, after which it can be reused.
+    ///
+    /// Compositors may signal release points out of order, so each buffer
+    /// should have a release timeline of its own.
+    pub fn set_release_point(&self, timeline: &SyncobjTimeline, point: u64) -> Result<()> {
+        request::set_release_point(
+            &self.conn,
+            self.id,
+            WlObject(timeline.id()),
+            WlUInt((point >> 32) as u32),
+            WlUInt(point as u32),
+        )
+    }
+
+    /// Sets both the acquire and the release point of the next commit.
+    ///
+    /// # Errors
+    /// Returns an error, without sending anything, if both points are on
+    /// the same timeline and the release point does not come after the
+    /// acquire point, which the compositor would reject at commit time.
+    pub fn set_points(
+        &self,
+        acquire: (&SyncobjTimeline, u64),
+        release: (&SyncobjTimeline, u64),
+    ) -> Result<()> {
+        check_points((acquire.0.id(), acquire.1), (release.0.id(), release.1))?;
+
+        self.set_acquire_point(acquire.0, acquire.1)?;
+        self.set_release_point(release.0, release.1)
+    }
+
+    /// Sets the timeline and point the next
+    /// [`ExplicitSync::request_release`] releases the buffer on; later
+    /// calls use the following points of the same timeline.
+    ///
+    /// Set it again with the timeline of the buffer whenever another
+    /// buffer gets attached.
+    pub fn set_release_timeline(&self, timeline: Arc<SyncobjTimeline>, point: u64) {
+        *self.release.borrow_mut() = Some((timeline, point));
+    }
+
+    /// Destroys the synchronization object, going back to implicit
+    /// synchronization from the next commit.
+    ///
+    /// Dropping the handle does the same.
+    pub fn destroy(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl ExplicitSync for SyncobjSurface {
+    /// Sets a [`Fence::TimelinePoint`] as acquire point.
+    ///
+    /// Every commit attaching a buffer needs an acquire point, so a
+    /// [`Fence::Signaled`] or [`Fence::SyncFile`] is rejected: turning them
+    /// into timeline points takes DRM.
+    fn set_acquire_fence(&self, fence: Fence) -> Result<()> {
+        match fence {
+            Fence::TimelinePoint { timeline, point } => self.set_acquire_point(&timeline, point),
+            Fence::Signaled | Fence::SyncFile(_) => Err(anyhow!(
+                "wp_linux_drm_syncobj_surface_v1 {} only takes timeline points",
+                self.id
+            )),
+        }
+    }
+
+    /// Sets the release point set by [`SyncobjSurface::set_release_timeline`]
+    /// and passes it to `handler` right away, as timeline points are known
+    /// before the compositor signals them.
+    fn request_release(&self, handler: ReleaseHandler) -> Result<()> {
+        let (timeline, point) = {
+            let mut release = self.release.borrow_mut();
+            let (timeline, point) = release.as_mut().ok_or_else(|| {
+                anyhow!(
+                    "wp_linux_drm_syncobj_surface_v1 {} has no release timeline",
+                    self.id
+                )
+            })?;
+            let current = *point;
+            *point += 1;
+            (Arc::clone(timeline), current)
+        };
+
+        self.set_release_point(&timeline, point)?;
+        handler(&self.conn, Fence::TimelinePoint { timeline, point });
+
+        Ok(())
+    }
+}
+
+impl Drop for SyncobjSurface {
+    fn drop(&mut self) {
+        self.conn
+            .destroy_on_drop(|conn| request::destroy_surface(conn, self.id));
+    }
+}
+
+/// Checks that an acquire and a release point, given as timeline object ID
+/// and point value, do not conflict.
+fn check_points(acquire: (u32, u64), release: (u32, u64)) -> Result<()> {
+    if acquire.0 == release.0 && acquire.1 >= release.1 {
+        return Err(anyhow!(
+            "wp_linux_drm_syncobj_timeline_v1 {}: release point {} must come after acquire point {}",
+            acquire.0,
+            release.1,
+            acquire.1
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn points_on_one_timeline_must_be_ordered() {
+        assert!(check_points((5, 1), (5, 2)).is_ok());
+        assert!(check_points((5, 2), (5, 2)).is_err());
+        assert!(check_points((5, 3), (6, 1)).is_ok());
+    }
+}
@@ -0,0 +1,251 @@
+use std::os::fd::OwnedFd;
+
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::types::{WlNewId, WlObject, WlUInt},
+    wl_request_opcode, wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to the
+    /// `wp_linux_drm_syncobj_manager_v1` object.
+    Opcode {
+        /// Destroys the manager; existing objects keep working.
+        Destroy = 0 (destructor),
+
+        /// Creates the explicit synchronization object of a surface.
+        GetSurface = 1,
+
+        /// Imports a DRM syncobj timeline.
+        ImportTimeline = 2,
+    }
+}
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to a
+    /// `wp_linux_drm_syncobj_timeline_v1` object.
+    TimelineOpcode {
+        /// Destroys the timeline.
+        Destroy = 0 (destructor),
+    }
+}
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to a
+    /// `wp_linux_drm_syncobj_surface_v1` object.
+    SurfaceOpcode {
+        /// Destroys the synchronization object of the surface.
+        Destroy = 0 (destructor),
+
+        /// Sets the point signaled when the buffer is ready to be read.
+        SetAcquirePoint = 1,
+
+        /// Sets the point the compositor signals once done with the buffer.
+        SetReleasePoint = 2,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wp_linux_drm_syncobj_manager_v1.get_surface` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="get_surface">
+    ///   <description summary="extend surface interface for explicit synchronization">
+    ///     Instantiate an interface extension for the given wl_surface to provide
+    ///     explicit synchronization.
+    ///
+    ///     If the given wl_surface already has an explicit synchronization object
+    ///     associated, the surface_exists protocol error is raised.
+    ///
+    ///     Graphics APIs, like EGL or Vulkan, that manage the buffer queue and
+    ///     commits of a wl_surface themselves, are likely to be using this
+    ///     extension internally. If a client is using such an API for a
+    ///     wl_surface, it should not directly use this extension on that surface,
+    ///     to avoid raising a surface_exists protocol error.
+    ///   </description>
+    ///   <arg name="id" type="new_id" interface="wp_linux_drm_syncobj_surface_v1"
+    ///     summary="the new synchronization surface object id"/>
+    ///   <arg name="surface" type="object" interface="wl_surface"
+    ///     summary="the surface"/>
+    /// </request>
+    /// ```
+    GetSurfaceParam {
+        /// The object ID to assign to the new synchronization object.
+        id: WlNewId,
+        /// The surface to synchronize explicitly.
+        surface: WlObject,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wp_linux_drm_syncobj_manager_v1.import_timeline` request.
+    ///
+    /// The descriptor is sent out of band, see [`import_timeline`].
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="import_timeline">
+    ///   <description summary="import a DRM syncobj timeline">
+    ///     Import a DRM synchronization object timeline.
+    ///
+    ///     If the FD cannot be imported, the invalid_timeline error is raised.
+    ///   </description>
+    ///   <arg name="id" type="new_id" interface="wp_linux_drm_syncobj_timeline_v1"/>
+    ///   <arg name="fd" type="fd" summary="drm_syncobj file descriptor"/>
+    /// </request>
+    /// ```
+    ImportTimelineParam {
+        /// The object ID to assign to the new timeline.
+        id: WlNewId,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wp_linux_drm_syncobj_surface_v1.set_acquire_point`
+    /// and `set_release_point` requests, which share their arguments.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_acquire_point">
+    ///   <description summary="set the acquire timeline point">
+    ///     Set the timeline point that must be signalled before the compositor may
+    ///     sample from the buffer attached with wl_surface.attach.
+    ///
+    ///     The 64-bit unsigned value combined from point_hi and point_lo is the
+    ///     point value.
+    ///
+    ///     The acquire point is double-buffered state, and will be applied on the
+    ///     next wl_surface.commit request for the associated surface. Thus, it
+    ///     applies only to the buffer that is attached to the surface at commit
+    ///     time.
+    ///
+    ///     If an acquire point has already been attached during the same commit
+    ///     cycle, the new point replaces the old one.
+    ///
+    ///     If the associated wl_surface was destroyed, a no_surface error is
+    ///     raised.
+    ///
+    ///     If at surface commit time there is a pending acquire timeline point set
+    ///     but no pending buffer attached, a no_buffer error is raised. If at
+    ///     surface commit time there is a pending buffer attached but no pending
+    ///     acquire timeline point set, the no_acquire_point protocol error is
+    ///     raised.
+    ///   </description>
+    ///   <arg name="timeline" type="object" interface="wp_linux_drm_syncobj_timeline_v1"/>
+    ///   <arg name="point_hi" type="uint" summary="high 32 bits of the point value"/>
+    ///   <arg name="point_lo" type="uint" summary="low 32 bits of the point value"/>
+    /// </request>
+    /// ```
+    SetPointParam {
+        /// The timeline the point belongs to.
+        timeline: WlObject,
+        /// The high 32 bits of the point value.
+        point_hi: WlUInt,
+        /// The low 32 bits of the point value.
+        point_lo: WlUInt,
+    }
+}
+
+/// Sends a `wp_linux_drm_syncobj_manager_v1.destroy` request to the compositor.
+pub fn destroy(conn: &Connection, manager: u32) -> Result<()> {
+    conn.send_request(manager, Opcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends a `wp_linux_drm_syncobj_manager_v1.get_surface` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `manager` - The ID of the bound syncobj manager
+/// * `id` - The object ID to assign to the new synchronization object
+/// * `surface` - The surface to synchronize explicitly
+pub fn get_surface(conn: &Connection, manager: u32, id: WlNewId, surface: WlObject) -> Result<()> {
+    conn.send_request(
+        manager,
+        Opcode::GetSurface,
+        &GetSurfaceParam::new(id, surface),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `wp_linux_drm_syncobj_manager_v1.import_timeline` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `manager` - The ID of the bound syncobj manager
+/// * `id` - The object ID to assign to the new timeline
+/// * `fd` - The DRM syncobj descriptor, closed once sent
+pub fn import_timeline(conn: &Connection, manager: u32, id: WlNewId, fd: OwnedFd) -> Result<()> {
+    conn.send_request_with_fds(
+        manager,
+        Opcode::ImportTimeline,
+        &ImportTimelineParam::new(id),
+        vec![fd],
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `wp_linux_drm_syncobj_timeline_v1.destroy` request to the compositor.
+pub fn destroy_timeline(conn: &Connection, timeline: u32) -> Result<()> {
+    conn.send_request(timeline, TimelineOpcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends a `wp_linux_drm_syncobj_surface_v1.destroy` request to the compositor.
+pub fn destroy_surface(conn: &Connection, surface: u32) -> Result<()> {
+    conn.send_request(surface, SurfaceOpcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends a `wp_linux_drm_syncobj_surface_v1.set_acquire_point` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `surface` - The ID of the synchronization object of the surface
+/// * `timeline` - The timeline the point belongs to
+/// * `point_hi`, `point_lo` - The point value
+pub fn set_acquire_point(
+    conn: &Connection,
+    surface: u32,
+    timeline: WlObject,
+    point_hi: WlUInt,
+    point_lo: WlUInt,
+) -> Result<()> {
+    conn.send_request(
+        surface,
+        SurfaceOpcode::SetAcquirePoint,
+        &SetPointParam::new(timeline, point_hi, point_lo),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `wp_linux_drm_syncobj_surface_v1.set_release_point` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `surface` - The ID of the synchronization object of the surface
+/// * `timeline` - The timeline the point belongs to
+/// * `point_hi`, `point_lo` - The point value
+pub fn set_release_point(
+    conn: &Connection,
+    surface: u32,
+    timeline: WlObject,
+    point_hi: WlUInt,
+    point_lo: WlUInt,
+) -> Result<()> {
+    conn.send_request(
+        surface,
+        SurfaceOpcode::SetReleasePoint,
+        &SetPointParam::new(timeline, point_hi, point_lo),
+    )?;
+
+    Ok(())
+}
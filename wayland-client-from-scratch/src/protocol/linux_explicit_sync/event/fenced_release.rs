@@ -0,0 +1,42 @@
+use std::os::fd::OwnedFd;
+
+use crate::{connection::Connection, error::Result, fence::Fence};
+
+/// Represents a `zwp_linux_buffer_release_v1.fenced_release` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="fenced_release" type="destructor">
+///   <description summary="release buffer with fence">
+///     Sent when the compositor has finalised its usage of the associated
+///     buffer for the relevant commit, providing a dma_fence which will be
+///     signaled when all operations by the compositor on that buffer for that
+///     commit have finished.
+///
+///     Once the fence has signaled, and assuming the associated buffer is not
+///     pending release from other wl_surface.commit requests, no additional
+///     explicit or implicit synchronization is required to safely reuse or
+///     destroy the buffer.
+///
+///     This event destroys the zwp_linux_buffer_release_v1 object.
+///   </description>
+///   <arg name="fence" type="fd" summary="fence for last operation on buffer"/>
+/// </event>
+/// ```
+#[derive(Debug)]
+pub struct FencedRelease {
+    /// The sync_file signaled once the compositor is done with the buffer.
+    pub fence: OwnedFd,
+}
+
+/// Handles a `zwp_linux_buffer_release_v1.fenced_release` event by passing
+/// the fence to the listener.
+pub(super) fn handle_zwp_linux_buffer_release_fenced_release(
+    conn: &Connection,
+    release_id: u32,
+    event: FencedRelease,
+) -> Result<()> {
+    conn.emit(release_id, Fence::SyncFile(event.fence));
+
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use crate::{connection::Connection, error::Result, fence::Fence};
+
+/// Handles a `zwp_linux_buffer_release_v1.immediate_release` event by
+/// passing an already signaled fence to the listener.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="immediate_release" type="destructor">
+///   <description summary="release buffer immediately">
+///     Sent when the compositor has finalised its usage of the associated
+///     buffer for the relevant commit, and either performed no operations
+///     using it, or has a guarantee that all its operations on that buffer for
+///     that commit have finished.
+///
+///     Once this event is received, and assuming the associated buffer is not
+///     pending release from other wl_surface.commit requests, no additional
+///     explicit or implicit synchronization is required to safely reuse or
+///     destroy the buffer.
+///
+///     This event destroys the zwp_linux_buffer_release_v1 object.
+///   </description>
+/// </event>
+/// ```
+pub(super) fn handle_zwp_linux_buffer_release_immediate_release(
+    conn: &Connection,
+    release_id: u32,
+) -> Result<()> {
+    conn.emit(release_id, Fence::Signaled);
+
+    Ok(())
+}
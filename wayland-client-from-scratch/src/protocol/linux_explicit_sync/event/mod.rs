@@ -0,0 +1,101 @@
+pub mod fenced_release;
+pub mod immediate_release;
+
+use crate::{
+    connection::Connection,
+    error::{Error, Result, anyhow},
+    protocol::message::WlMessage,
+};
+
+/// Represents the event types that can be emitted by a
+/// `zwp_linux_buffer_release_v1` object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReleaseOpcode {
+    /// Announces that the buffer is released once the fence signals.
+    ///
+    /// # Event Arguments
+    /// - `fence`: The fence for the last operation on the buffer (passed as a file descriptor)
+    FencedRelease = 0,
+
+    /// Announces that the buffer is released right away.
+    ImmediateRelease = 1,
+}
+
+impl TryFrom<u16> for ReleaseOpcode {
+    type Error = Error;
+
+    /// Attempts to convert a raw opcode value into a structured `ZwpLinuxBufferReleaseEvent`.
+    fn try_from(value: u16) -> Result<ReleaseOpcode> {
+        match value {
+            0 => Ok(ReleaseOpcode::FencedRelease),
+            1 => Ok(ReleaseOpcode::ImmediateRelease),
+            _ => Err(anyhow!(
+                "Invalid zwp_linux_buffer_release_v1 event opcode: {}",
+                value
+            )),
+        }
+    }
+}
+
+impl ReleaseOpcode {
+    /// Returns the interface version the event was introduced in.
+    pub fn since(self) -> u32 {
+        match self {
+            ReleaseOpcode::FencedRelease | ReleaseOpcode::ImmediateRelease => 1,
+        }
+    }
+}
+
+/// A decoded `zwp_linux_buffer_release_v1` event.
+#[derive(Debug)]
+pub enum ReleaseEvent {
+    /// Announces that the buffer is released once the fence signals.
+    FencedRelease(fenced_release::FencedRelease),
+    /// Announces that the buffer is released right away.
+    ImmediateRelease,
+}
+
+impl ReleaseEvent {
+    /// Decodes a `zwp_linux_buffer_release_v1` event from its message,
+    /// claiming the fence descriptor of `fenced_release` from `conn`.
+    ///
+    /// # Errors
+    /// Returns an error if the opcode is unknown or the fence is missing.
+    pub fn decode(conn: &Connection, msg: &WlMessage) -> Result<ReleaseEvent> {
+        Ok(match ReleaseOpcode::try_from(msg.header.opcode)? {
+            ReleaseOpcode::FencedRelease => {
+                ReleaseEvent::FencedRelease(fenced_release::FencedRelease {
+                    fence: conn.take_fd()?,
+                })
+            }
+            ReleaseOpcode::ImmediateRelease => ReleaseEvent::ImmediateRelease,
+        })
+    }
+
+    /// Returns the opcode of the event.
+    pub fn opcode(&self) -> ReleaseOpcode {
+        match self {
+            ReleaseEvent::FencedRelease(_) => ReleaseOpcode::FencedRelease,
+            ReleaseEvent::ImmediateRelease => ReleaseOpcode::ImmediateRelease,
+        }
+    }
+}
+
+/// Dispatches incoming `zwp_linux_buffer_release_v1` events to their appropriate handler functions.
+///
+/// # Event Routing
+/// * `FencedRelease` events are routed to `fenced_release::handle_zwp_linux_buffer_release_fenced_release`
+/// * `ImmediateRelease` events are routed to `immediate_release::handle_zwp_linux_buffer_release_immediate_release`
+pub fn handle_zwp_linux_buffer_release_event(conn: &Connection, msg: WlMessage) -> Result<()> {
+    let release_id = msg.header.object_id;
+
+    // Route the event to the appropriate handler based on type
+    match ReleaseEvent::decode(conn, &msg)? {
+        ReleaseEvent::FencedRelease(event) => {
+            fenced_release::handle_zwp_linux_buffer_release_fenced_release(conn, release_id, event)
+        }
+        ReleaseEvent::ImmediateRelease => {
+            immediate_release::handle_zwp_linux_buffer_release_immediate_release(conn, release_id)
+        }
+    }
+}
@@ -0,0 +1,236 @@
+pub mod event;
+pub mod request;
+
+use std::os::fd::OwnedFd;
+
+use crate::{
+    connection::{Connection, Listener},
+    error::{Result, anyhow},
+    fence::{ExplicitSync, Fence, ReleaseHandler},
+    protocol::{
+        WlObjectId,
+        surface::Surface,
+        types::{WlNewId, WlObject},
+    },
+};
+
+/// The highest `zwp_linux_explicit_synchronization_v1` version this crate implements.
+pub const ZWP_LINUX_EXPLICIT_SYNCHRONIZATION_VERSION: u32 = 2;
+
+/// A bound `zwp_linux_explicit_synchronization_v1` global, the older
+/// explicit synchronization protocol based on sync_file fences.
+///
+/// Compositors offering
+/// [`LinuxDrmSyncobjManager`](crate::protocol::linux_drm_syncobj::LinuxDrmSyncobjManager)
+/// should be synchronized with it instead; this protocol is for those that
+/// do not offer it yet. Both implement [`ExplicitSync`].
+#[derive(Clone)]
+pub struct LinuxExplicitSynchronization {
+    /// The object ID of the bound global.
+    id: u32,
+    /// The negotiated interface version.
+    version: u32,
+    /// The connection the global was bound on.
+    conn: Connection,
+}
+
+impl LinuxExplicitSynchronization {
+    /// Binds the `zwp_linux_explicit_synchronization_v1` global advertised by the compositor.
+    pub fn bind(conn: &Connection) -> Result<LinuxExplicitSynchronization> {
+        let (id, version) = conn.bind(
+            WlObjectId::LinuxExplicitSynchronization,
+            ZWP_LINUX_EXPLICIT_SYNCHRONIZATION_VERSION,
+        )?;
+
+        Ok(LinuxExplicitSynchronization {
+            id,
+            version,
+            conn: conn.clone(),
+        })
+    }
+
+    /// Returns the object ID of the global.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Creates the explicit synchronization object of `surface`.
+    ///
+    /// A surface can only have one, and graphics APIs such as EGL or Vulkan
+    /// may already have created it for the surfaces they present to.
+    pub fn get_synchronization(&self, surface: &Surface) -> Result<SurfaceSynchronization> {
+        self.conn
+            .check_same_connection(surface.connection(), WlObjectId::Surface, surface.id())?;
+
+        let id = self
+            .conn
+            .new_object(WlObjectId::LinuxSurfaceSynchronization, self.version)?;
+        request::get_synchronization(&self.conn, self.id, WlNewId(id), WlObject(surface.id()))?;
+
+        Ok(SurfaceSynchronization {
+            id,
+            version: self.version,
+            conn: self.conn.clone(),
+        })
+    }
+
+    /// Destroys the global; existing synchronization objects keep working.
+    pub fn destroy(self) -> Result<()> {
+        request::destroy(&self.conn, self.id)
+    }
+}
+
+/// A `zwp_linux_surface_synchronization_v1`, the explicit synchronization
+/// state of a surface.
+///
+/// Fences and release requests only apply to dma-buf buffers, and like all
+/// surface state, to the next commit; each commit may carry at most one
+/// acquire fence and one release request.
+pub struct SurfaceSynchronization {
+    /// The object ID of the synchronization object.
+    id: u32,
+    /// The negotiated interface version.
+    version: u32,
+    /// The connection the object was created on.
+    conn: Connection,
+}
+
+impl SurfaceSynchronization {
+    /// Returns the object ID of the synchronization object.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Sets the sync_file `fence` signaling that the buffer attached by the
+    /// next commit is ready, typically when the GPU finished rendering into
+    /// it. The descriptor is closed once sent.
+    pub fn set_acquire_fence(&self, fence: OwnedFd) -> Result<()> {
+        request::set_acquire_fence(&self.conn, self.id, fence)
+    }
+
+    /// Asks to be told when the compositor releases the buffer attached by
+    /// the next commit: `handler` receives a [`Fence::SyncFile`] to wait
+    /// for before reusing the buffer, or [`Fence::Signaled`] if it can be
+    /// reused right away.
+    pub fn get_release<F>(&self, handler: F) -> Result<()>
+    where
+        F: FnOnce(&Connection, Fence) + Send + 'static,
+    {
+        let id = self
+            .conn
+            .new_object(WlObjectId::LinuxBufferRelease, self.version)?;
+        request::get_release(&self.conn, self.id, WlNewId(id))?;
+
+        // The release object is destroyed by the compositor along with the
+        // one event it sends.
+        let mut handler = Some(handler);
+        let listener: Listener<Fence> = Box::new(move |conn, fence| {
+            if let Some(handler) = handler.take() {
+                handler(conn, fence);
+            }
+        });
+        self.conn.set_listener(id, listener);
+
+        Ok(())
+    }
+
+    /// Destroys the synchronization object, going back to implicit
+    /// synchronization from the next commit. Pending releases are still
+    /// reported.
+    ///
+    /// Dropping the handle does the same.
+    pub fn destroy(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl ExplicitSync for SurfaceSynchronization {
+    /// Sets a [`Fence::SyncFile`]; [`Fence::Signaled`] sets nothing, as
+    /// there is nothing to wait for.
+    fn set_acquire_fence(&self, fence: Fence) -> Result<()> {
+        match fence {
+            Fence::Signaled => Ok(()),
+            Fence::SyncFile(fd) => SurfaceSynchronization::set_acquire_fence(self, fd),
+            Fence::TimelinePoint { .. } => Err(anyhow!(
+                "zwp_linux_explicit_synchronization_v1 only takes sync_file fences"
+            )),
+        }
+    }
+
+    fn request_release(&self, handler: ReleaseHandler) -> Result<()> {
+        self.get_release(handler)
+    }
+}
+
+impl Drop for SurfaceSynchronization {
+    fn drop(&mut self) {
+        self.conn
+            .destroy_on_drop(|conn| request::destroy_synchronization(conn, self.id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        os::unix::net::UnixStream,
+        sync::Arc,
+    };
+
+    use super::*;
+    use crate::{
+        protocol::{
+            endian::Endian,
+            message::{WlMessage, WlMessageHeader},
+            registry::event::global::Global,
+            types::{WlString, WlUInt},
+        },
+        sync::Lock,
+    };
+
+    #[test]
+    fn immediate_release_hands_out_a_signaled_fence() {
+        let (client, mut compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+        conn.insert_global(Global {
+            name: WlUInt(1),
+            interface: WlString::new("zwp_linux_explicit_synchronization_v1").unwrap(),
+            version: WlUInt(2),
+        });
+        let explicit_sync = LinuxExplicitSynchronization::bind(&conn).unwrap();
+        let surface = Surface::new(conn.new_object(WlObjectId::Surface, 6).unwrap(), &conn);
+        let sync = explicit_sync.get_synchronization(&surface).unwrap();
+
+        let released = Arc::new(Lock::new(Vec::new()));
+        let sink = Arc::clone(&released);
+        let sync_ref: &dyn ExplicitSync = &sync;
+        sync_ref
+            .request_release(Box::new(move |_, fence| {
+                sink.borrow_mut().push(fence.is_signaled())
+            }))
+            .unwrap();
+        assert!(sync_ref.set_acquire_fence(Fence::Signaled).is_ok());
+
+        // The release object takes the next ID of the fresh connection
+        let release = sync.id() + 1;
+        let msg: Vec<u8> = WlMessage::new(release, 1, &[]).unwrap().into();
+        compositor.write_all(&msg).unwrap();
+        conn.dispatch().unwrap();
+        assert_eq!(*released.borrow(), [Some(true)]);
+
+        drop((sync, surface, explicit_sync, conn));
+        let mut received = Vec::new();
+        compositor.read_to_end(&mut received).unwrap();
+        let mut requests = Vec::new();
+        while !received.is_empty() {
+            let header = WlMessageHeader::decode(&received, Endian::NATIVE).unwrap();
+            requests.push((header.object_id, header.opcode));
+            received.drain(..header.size as usize);
+        }
+        let sync_id = release - 1;
+        assert!(requests.contains(&(sync_id, request::SurfaceOpcode::GetRelease as u16)));
+        assert!(requests.contains(&(sync_id, request::SurfaceOpcode::Destroy as u16)));
+    }
+}
@@ -0,0 +1,165 @@
+use std::os::fd::OwnedFd;
+
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::types::{WlNewId, WlObject},
+    wl_request_opcode, wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to the
+    /// `zwp_linux_explicit_synchronization_v1` object.
+    Opcode {
+        /// Destroys the global; existing objects keep working.
+        Destroy = 0 (destructor),
+
+        /// Creates the explicit synchronization object of a surface.
+        GetSynchronization = 1,
+    }
+}
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to a
+    /// `zwp_linux_surface_synchronization_v1` object.
+    SurfaceOpcode {
+        /// Destroys the synchronization object of the surface.
+        Destroy = 0 (destructor),
+
+        /// Sets the fence signaled when the buffer is ready to be read.
+        SetAcquireFence = 1,
+
+        /// Requests the release events of the buffer of the next commit.
+        GetRelease = 2,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `zwp_linux_explicit_synchronization_v1.get_synchronization` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="get_synchronization">
+    ///   <description summary="extend surface interface for explicit synchronization">
+    ///     Instantiate an interface extension for the given wl_surface to provide
+    ///     explicit synchronization.
+    ///
+    ///     If the given wl_surface already has an explicit synchronization object
+    ///     associated, the synchronization_exists protocol error is raised.
+    ///
+    ///     Graphics APIs, like EGL or Vulkan, that manage the buffer queue and
+    ///     commits of a wl_surface themselves, are likely to be using this
+    ///     extension internally. If a client is using such an API for a
+    ///     wl_surface, it should not directly use this extension on that surface,
+    ///     to avoid raising a synchronization_exists protocol error.
+    ///   </description>
+    ///   <arg name="id" type="new_id" interface="zwp_linux_surface_synchronization_v1"
+    ///        summary="the new synchronization interface id"/>
+    ///   <arg name="surface" type="object" interface="wl_surface"
+    ///        summary="the surface"/>
+    /// </request>
+    /// ```
+    GetSynchronizationParam {
+        /// The object ID to assign to the new synchronization object.
+        id: WlNewId,
+        /// The surface to synchronize explicitly.
+        surface: WlObject,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `zwp_linux_surface_synchronization_v1.get_release` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="get_release">
+    ///   <description summary="release fence for last-attached buffer">
+    ///     Create a listener for the release of the buffer attached by the
+    ///     client with wl_surface.attach. See zwp_linux_buffer_release_v1
+    ///     documentation for more information.
+    ///
+    ///     The release object is double-buffered state. If the associated
+    ///     wl_surface was destroyed, a no_surface error is raised.
+    ///
+    ///     If a zwp_linux_buffer_release_v1 object has already been requested for
+    ///     the surface in the current commit, a duplicate_release error is raised.
+    ///
+    ///     If the associated wl_surface does not have a buffer attached in the
+    ///     current commit, a no_buffer error is raised.
+    ///
+    ///     If the associated wl_buffer is not a dmabuf buffer, an
+    ///     unsupported_buffer error is raised.
+    ///   </description>
+    ///   <arg name="release" type="new_id" interface="zwp_linux_buffer_release_v1"
+    ///        summary="new zwp_linux_buffer_release_v1 object"/>
+    /// </request>
+    /// ```
+    GetReleaseParam {
+        /// The object ID to assign to the new release object.
+        release: WlNewId,
+    }
+}
+
+/// Sends a `zwp_linux_explicit_synchronization_v1.destroy` request to the compositor.
+pub fn destroy(conn: &Connection, manager: u32) -> Result<()> {
+    conn.send_request(manager, Opcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends a `zwp_linux_explicit_synchronization_v1.get_synchronization` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `manager` - The ID of the bound global
+/// * `id` - The object ID to assign to the new synchronization object
+/// * `surface` - The surface to synchronize explicitly
+pub fn get_synchronization(
+    conn: &Connection,
+    manager: u32,
+    id: WlNewId,
+    surface: WlObject,
+) -> Result<()> {
+    conn.send_request(
+        manager,
+        Opcode::GetSynchronization,
+        &GetSynchronizationParam::new(id, surface),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `zwp_linux_surface_synchronization_v1.destroy` request to the compositor.
+pub fn destroy_synchronization(conn: &Connection, synchronization: u32) -> Result<()> {
+    conn.send_request(synchronization, SurfaceOpcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends a `zwp_linux_surface_synchronization_v1.set_acquire_fence` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `synchronization` - The ID of the synchronization object
+/// * `fence` - The sync_file signaled once the buffer is ready, closed once sent
+pub fn set_acquire_fence(conn: &Connection, synchronization: u32, fence: OwnedFd) -> Result<()> {
+    conn.send_request_with_fds(
+        synchronization,
+        SurfaceOpcode::SetAcquireFence,
+        &(),
+        vec![fence],
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `zwp_linux_surface_synchronization_v1.get_release` request to the compositor.
+pub fn get_release(conn: &Connection, synchronization: u32, release: WlNewId) -> Result<()> {
+    conn.send_request(
+        synchronization,
+        SurfaceOpcode::GetRelease,
+        &GetReleaseParam::new(release),
+    )?;
+
+    Ok(())
+}
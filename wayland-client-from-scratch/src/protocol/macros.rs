@@ -10,24 +10,45 @@ macro_rules! wl_primitive_type {
         pub struct $name(pub $ty);
 
         impl $name {
-            /// Returns the raw bytes of the value in native endianness.
+            /// Returns the raw bytes of the value in the wire byte order,
+            /// see [`Endian`](crate::protocol::endian::Endian).
             pub fn as_bytes(&self) -> [u8; std::mem::size_of::<$ty>()] {
-                self.0.to_ne_bytes()
+                $crate::protocol::endian::Endian::NATIVE.encode_u32(self.0 as u32)
             }
 
-            pub fn to_bytes(&self) -> Vec<u8> {
+            /// Returns the raw bytes of the value as an owned buffer.
+            pub fn to_bytes(self) -> Vec<u8> {
                 self.as_bytes().to_vec()
             }
 
-            /// Creates a new instance from raw bytes in native endianness.
+            /// Creates a new instance from raw bytes in the wire byte order.
             pub fn from_bytes(bytes: [u8; std::mem::size_of::<$ty>()]) -> Self {
-                Self(<$ty>::from_ne_bytes(bytes))
+                Self($crate::protocol::endian::Endian::NATIVE.decode_u32(bytes) as $ty)
             }
 
             pub fn get(&self) -> $ty {
                 self.0
             }
 
+            /// Reads a value from the event arguments starting at `offset`.
+            ///
+            /// # Errors
+            /// Returns an error if `buf` ends before the value does.
+            #[allow(dead_code)]
+            pub(crate) fn read(buf: &[u8], offset: usize) -> $crate::error::Result<Self> {
+                let end = offset + size_of::<$ty>();
+                if buf.len() < end {
+                    return Err($crate::error::anyhow!(
+                        "Buffer too short for {}: expected {} bytes, got {}",
+                        stringify!($name),
+                        end,
+                        buf.len()
+                    ));
+                }
+
+                Ok(Self::from_bytes(buf[offset..end].try_into()?))
+            }
+
             #[allow(dead_code)]
             pub const fn type_size() -> usize {
                 size_of::<$ty>()
@@ -40,6 +61,12 @@ macro_rules! wl_primitive_type {
             }
         }
 
+        impl $crate::protocol::encoder::Encode for $name {
+            fn encode(&self, encoder: &mut $crate::protocol::encoder::Encoder) {
+                encoder.write_raw(&self.as_bytes());
+            }
+        }
+
         impl From<$name> for $ty {
             fn from(value: $name) -> $ty {
                 value.0
@@ -67,7 +94,7 @@ macro_rules! wl_enum {
     ) => {
         $(#[$meta])*
         #[repr(u32)]
-        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
         pub enum $name {
             $(
                 $(#[$variant_meta])*
@@ -86,15 +113,146 @@ macro_rules! wl_enum {
         }
 
         impl TryFrom<u32> for $name {
-            type Error = anyhow::Error;
+            type Error = $crate::error::Error;
 
-            fn try_from(value: u32) -> anyhow::Result<Self> {
+            fn try_from(value: u32) -> $crate::error::Result<Self> {
                 match value {
                     $(
                         $value => Ok($name::$variant),
                     )*
-                    _ => Err(anyhow::anyhow!("Invalid {} value: {}", stringify!($name), value)),
+                    _ => Err($crate::error::anyhow!("Invalid {} value: {}", stringify!($name), value)),
+                }
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! wl_bitfield {
+    (
+        $(#[$meta:meta])*
+        $name:ident {
+            $(
+                $(#[$flag_meta:meta])*
+                $flag:ident = $value:expr
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct $name(u32);
+
+        impl $name {
+            $(
+                $(#[$flag_meta])*
+                pub const $flag: $name = $name($value);
+            )*
+
+            /// All flags known to this crate, with their names.
+            const FLAGS: &'static [(&'static str, $name)] = &[
+                $((stringify!($flag), $name::$flag),)*
+            ];
+
+            /// Returns a value with no flag set.
+            pub const fn empty() -> Self {
+                $name(0)
+            }
+
+            /// Creates a value from its wire representation.
+            ///
+            /// Bits unknown to this crate are kept, so newer compositors
+            /// sending additional flags do not cause decode errors.
+            pub const fn from_bits(bits: u32) -> Self {
+                $name(bits)
+            }
+
+            /// Returns the wire representation of the value.
+            pub const fn bits(&self) -> u32 {
+                self.0
+            }
+
+            /// Returns `true` if no flag is set.
+            pub const fn is_empty(&self) -> bool {
+                self.0 == 0
+            }
+
+            /// Returns `true` if all flags of `other` are set.
+            pub const fn contains(&self, other: $name) -> bool {
+                self.0 & other.0 == other.0
+            }
+
+            /// Sets the flags of `other`.
+            pub fn insert(&mut self, other: $name) {
+                self.0 |= other.0;
+            }
+
+            /// Clears the flags of `other`.
+            pub fn remove(&mut self, other: $name) {
+                self.0 &= !other.0;
+            }
+        }
+
+        impl From<u32> for $name {
+            fn from(bits: u32) -> Self {
+                $name::from_bits(bits)
+            }
+        }
+
+        impl From<$name> for u32 {
+            fn from(value: $name) -> u32 {
+                value.bits()
+            }
+        }
+
+        impl std::ops::BitOr for $name {
+            type Output = $name;
+
+            fn bitor(self, other: $name) -> $name {
+                $name(self.0 | other.0)
+            }
+        }
+
+        impl std::ops::BitOrAssign for $name {
+            fn bitor_assign(&mut self, other: $name) {
+                self.insert(other);
+            }
+        }
+
+        impl std::ops::BitAnd for $name {
+            type Output = $name;
+
+            fn bitand(self, other: $name) -> $name {
+                $name(self.0 & other.0)
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            /// Lists the set flags, e.g. `Name(A | B)`, with unknown bits
+            /// in hexadecimal.
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}(", stringify!($name))?;
+
+                let mut remaining = self.0;
+                let mut first = true;
+                for (flag_name, flag) in $name::FLAGS {
+                    if flag.0 != 0 && self.contains(*flag) {
+                        if !first {
+                            write!(f, " | ")?;
+                        }
+                        write!(f, "{flag_name}")?;
+                        remaining &= !flag.0;
+                        first = false;
+                    }
                 }
+
+                if remaining != 0 {
+                    if !first {
+                        write!(f, " | ")?;
+                    }
+                    write!(f, "{remaining:#x}")?;
+                }
+
+                write!(f, ")")
             }
         }
     };
@@ -102,12 +260,18 @@ macro_rules! wl_enum {
 
 #[macro_export]
 macro_rules! wl_request_opcode {
+    (@destructor destructor) => {
+        true
+    };
+    (@destructor) => {
+        false
+    };
     (
         $(#[$meta:meta])*
         $name:ident {
             $(
                 $(#[$variant_meta:meta])*
-                $variant:ident = $value:expr
+                $variant:ident = $value:literal $(($kind:ident))?
             ),* $(,)?
         }
     ) => {
@@ -121,6 +285,23 @@ macro_rules! wl_request_opcode {
             )*
         }
 
+        impl $name {
+            /// Returns the request with the given opcode, if the interface has one.
+            pub fn from_opcode(opcode: u16) -> Option<$name> {
+                match opcode {
+                    $($value => Some($name::$variant),)*
+                    _ => None,
+                }
+            }
+
+            /// Returns `true` if the request destroys the object it is sent to.
+            pub fn is_destructor(self) -> bool {
+                match self {
+                    $($name::$variant => $crate::wl_request_opcode!(@destructor $($kind)?),)*
+                }
+            }
+        }
+
         impl From<$name> for u16 {
             fn from(value: $name) -> u16 {
                 value as u16
@@ -150,7 +331,7 @@ macro_rules! wl_request_param {
 
         impl $name {
             /// Creates new parameters with the specified values.
-            #[allow(unused)]
+            #[allow(unused, clippy::too_many_arguments)]
             pub(super) fn new($($field: $ty),*) -> Self {
                 Self {
                     $($field),*
@@ -158,6 +339,15 @@ macro_rules! wl_request_param {
             }
         }
 
+        impl $crate::protocol::encoder::Encode for $name {
+            /// Writes the parameters in the order of the request arguments.
+            fn encode(&self, encoder: &mut $crate::protocol::encoder::Encoder) {
+                $(
+                    $crate::protocol::encoder::Encode::encode(&self.$field, encoder);
+                )*
+            }
+        }
+
         impl From<$name> for Vec<u8> {
             /// Serializes the parameters into the Wayland wire format.
             fn from(args: $name) -> Vec<u8> {
@@ -170,3 +360,56 @@ macro_rules! wl_request_param {
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    wl_bitfield! {
+        /// A bitfield for testing.
+        Flags {
+            /// The first flag.
+            A = 1,
+            /// The second flag.
+            B = 2,
+            /// The third flag.
+            C = 8,
+        }
+    }
+
+    #[test]
+    fn bitfield_contains_and_insert() {
+        let mut flags = Flags::from_bits(0b1001);
+        assert!(flags.contains(Flags::A | Flags::C));
+        assert!(!flags.contains(Flags::B));
+
+        flags.insert(Flags::B);
+        flags.remove(Flags::A);
+        assert_eq!(flags, Flags::B | Flags::C);
+        assert!(Flags::empty().is_empty());
+    }
+
+    #[test]
+    fn bitfield_keeps_unknown_bits() {
+        let flags = Flags::from(0x31);
+
+        assert_eq!(u32::from(flags), 0x31);
+        assert_eq!(flags.to_string(), "Flags(A | 0x30)");
+    }
+
+    #[test]
+    fn bitfield_display_lists_set_flags() {
+        assert_eq!((Flags::A | Flags::C).to_string(), "Flags(A | C)");
+        assert_eq!(Flags::empty().to_string(), "Flags()");
+    }
+
+    #[test]
+    fn request_opcodes_know_their_destructors() {
+        use crate::protocol::{WlObjectId, surface::request::Opcode};
+
+        assert_eq!(Opcode::from_opcode(6), Some(Opcode::Commit));
+        assert_eq!(Opcode::from_opcode(42), None);
+        assert!(Opcode::Destroy.is_destructor());
+        assert!(!Opcode::Commit.is_destructor());
+        assert!(WlObjectId::Keyboard.is_destructor(0));
+        assert!(!WlObjectId::Seat.is_destructor(0));
+    }
+}
@@ -1,6 +1,9 @@
 use std::fmt::{self, Display, Formatter};
 
-use anyhow::anyhow;
+use crate::{
+    error::{Error, Result, anyhow},
+    protocol::{arena::EventBytes, endian::Endian},
+};
 
 /// The fixed size of a Wayland message header in bytes (8 bytes).
 ///
@@ -9,6 +12,73 @@ use anyhow::anyhow;
 /// - Combined size (upper 16 bits) and opcode (lower 16 bits)
 pub const WL_MESSAGE_HEADER_LEN: usize = size_of::<u32>() + size_of::<u16>() + size_of::<u16>();
 
+/// The largest size of a Wayland message in bytes, header included, as
+/// limited by the 16-bit size field of the header and the 32-bit alignment
+/// of every message.
+pub const WL_MESSAGE_MAX_LEN: usize = u16::MAX as usize & !(WL_MESSAGE_ALIGN - 1);
+
+/// The alignment of every message size in bytes, as all arguments are
+/// encoded in 32-bit words.
+const WL_MESSAGE_ALIGN: usize = size_of::<u32>();
+
+/// The error returned for a received message whose header declares a size
+/// no well-formed message has, after which the stream cannot be split into
+/// messages anymore.
+///
+/// It is wrapped in an [`Error`], so callers tell it apart from other
+/// failures with `error.downcast_ref::<InvalidHeader>()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidHeader {
+    /// The size is smaller than the header or not a multiple of 4 bytes.
+    Malformed {
+        /// The ID of the target object.
+        object_id: u32,
+        /// The opcode of the message.
+        opcode: u16,
+        /// The declared size of the message.
+        size: u16,
+    },
+    /// The size exceeds the largest message accepted.
+    TooLarge {
+        /// The ID of the target object.
+        object_id: u32,
+        /// The opcode of the message.
+        opcode: u16,
+        /// The declared size of the message.
+        size: u16,
+        /// The largest size accepted.
+        max_len: usize,
+    },
+}
+
+impl Display for InvalidHeader {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidHeader::Malformed {
+                object_id,
+                opcode,
+                size,
+            } => write!(
+                f,
+                "Invalid message size {size} for object {object_id} (opcode {opcode}): \
+                 must be at least {WL_MESSAGE_HEADER_LEN} and a multiple of {WL_MESSAGE_ALIGN}"
+            ),
+            InvalidHeader::TooLarge {
+                object_id,
+                opcode,
+                size,
+                max_len,
+            } => write!(
+                f,
+                "Message for object {object_id} (opcode {opcode}) is {size} bytes long, \
+                 exceeding the limit of {max_len} bytes"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InvalidHeader {}
+
 /// Represents the header of a Wayland protocol message.
 ///
 /// Contains routing information and metadata for interpreting Wayland messages.
@@ -28,36 +98,58 @@ impl WlMessageHeader {
     fn message_len(&self) -> usize {
         self.size as usize
     }
-}
 
-impl From<WlMessageHeader> for Vec<u8> {
-    /// Serializes the header into the Wayland wire format.
+    /// Checks that the declared size can belong to a well-formed message of
+    /// at most `max_len` bytes.
     ///
-    /// Produces an 8-byte vector with native endian encoding:
-    /// - Bytes 0-3: object_id
-    /// - Bytes 4-5: opcode  
-    /// - Bytes 6-7: size
-    fn from(header: WlMessageHeader) -> Vec<u8> {
-        let mut bytes = Vec::with_capacity(8);
+    /// # Errors
+    /// Returns an error if the size is smaller than the header or not a
+    /// multiple of 4 bytes, which means the stream is corrupted, or if it
+    /// exceeds `max_len`.
+    pub(crate) fn validate_size(&self, max_len: usize) -> Result<(), InvalidHeader> {
+        let (object_id, opcode, size) = (self.object_id, self.opcode, self.size);
+        let message_len = self.message_len();
+        if message_len < WL_MESSAGE_HEADER_LEN || !message_len.is_multiple_of(WL_MESSAGE_ALIGN) {
+            return Err(InvalidHeader::Malformed {
+                object_id,
+                opcode,
+                size,
+            });
+        }
+        if message_len > max_len {
+            return Err(InvalidHeader::TooLarge {
+                object_id,
+                opcode,
+                size,
+                max_len,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Encodes the header as its two 32-bit words in the byte order `endian`.
+    ///
+    /// The size and opcode share the second word, so on the wire their byte
+    /// positions depend on the byte order.
+    pub(crate) fn encode(&self, endian: Endian) -> [u8; WL_MESSAGE_HEADER_LEN] {
+        let mut bytes = [0; WL_MESSAGE_HEADER_LEN];
+        let size_opcode = (u32::from(self.size) << 16) | u32::from(self.opcode);
 
-        bytes.extend_from_slice(&header.object_id.to_ne_bytes());
-        bytes.extend_from_slice(&header.opcode.to_ne_bytes());
-        bytes.extend_from_slice(&header.size.to_ne_bytes());
+        bytes[0..4].copy_from_slice(&endian.encode_u32(self.object_id));
+        bytes[4..8].copy_from_slice(&endian.encode_u32(size_opcode));
 
         bytes
     }
-}
 
-impl TryFrom<&[u8]> for WlMessageHeader {
-    type Error = anyhow::Error;
-
-    /// Deserializes a header from the wire format.
+    /// Decodes a header encoded in the byte order `endian`.
+    ///
+    /// The wire always uses [`Endian::NATIVE`]; other byte orders are only
+    /// useful for bytes recorded on a host of the other endianness.
     ///
     /// # Errors
-    /// Returns an error if:
-    /// - Buffer is shorter than 8 bytes
-    /// - Buffer contains invalid data
-    fn try_from(buf: &[u8]) -> anyhow::Result<Self> {
+    /// Returns an error if the buffer is shorter than 8 bytes.
+    pub(crate) fn decode(buf: &[u8], endian: Endian) -> Result<Self> {
         if buf.len() < WL_MESSAGE_HEADER_LEN {
             return Err(anyhow!(
                 "Buffer too short for WlMessageHeader: expected {} bytes, got {}",
@@ -66,18 +158,43 @@ impl TryFrom<&[u8]> for WlMessageHeader {
             ));
         }
 
-        let object_id = u32::from_ne_bytes(buf[0..4].try_into()?);
-        let opcode = u16::from_ne_bytes(buf[4..6].try_into()?);
-        let size = u16::from_ne_bytes(buf[6..8].try_into()?);
+        let object_id = endian.decode_u32_at(&buf[0..4]);
+        let size_opcode = endian.decode_u32_at(&buf[4..8]);
 
         Ok(WlMessageHeader {
             object_id,
-            opcode,
-            size,
+            opcode: (size_opcode & 0xFFFF) as u16,
+            size: (size_opcode >> 16) as u16,
         })
     }
 }
 
+impl From<WlMessageHeader> for Vec<u8> {
+    /// Serializes the header into the Wayland wire format.
+    ///
+    /// Produces an 8-byte vector of two 32-bit words in the native byte
+    /// order, see [`Endian`]:
+    /// - Bytes 0-3: object_id
+    /// - Bytes 4-7: size (upper 16 bits) and opcode (lower 16 bits)
+    fn from(header: WlMessageHeader) -> Vec<u8> {
+        header.encode(Endian::NATIVE).to_vec()
+    }
+}
+
+impl TryFrom<&[u8]> for WlMessageHeader {
+    type Error = Error;
+
+    /// Deserializes a header from the wire format, in the native byte order.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Buffer is shorter than 8 bytes
+    /// - Buffer contains invalid data
+    fn try_from(buf: &[u8]) -> Result<Self> {
+        WlMessageHeader::decode(buf, Endian::NATIVE)
+    }
+}
+
 impl Display for WlMessageHeader {
     /// Formats the header for human-readable display.
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
@@ -90,26 +207,65 @@ impl Display for WlMessageHeader {
 }
 
 /// A complete Wayland protocol message containing header and data.
+///
+/// Cloning is cheap: the payload is shared, not copied.
+#[derive(Clone)]
 pub struct WlMessage {
     /// The message header with routing and metadata.
     pub(crate) header: WlMessageHeader,
     /// The message payload data.
-    pub(crate) data: Vec<u8>,
+    pub(crate) data: EventBytes,
 }
 
 impl WlMessage {
     /// Creates a new Wayland message.
     ///
     /// The size field is automatically calculated as header length plus data length.
-    pub fn new(object_id: u32, opcode: u16, data: &[u8]) -> WlMessage {
-        WlMessage {
+    ///
+    /// # Errors
+    /// Returns an error if the message would exceed [`WL_MESSAGE_MAX_LEN`],
+    /// e.g. because of a very long string argument, or if the data is not
+    /// a whole number of 32-bit words. Such a message cannot be encoded, and
+    /// truncating its size would corrupt the stream.
+    pub fn new(object_id: u32, opcode: u16, data: &[u8]) -> Result<WlMessage> {
+        let message_len = data.len() + WL_MESSAGE_HEADER_LEN;
+        if message_len > WL_MESSAGE_MAX_LEN {
+            return Err(anyhow!(
+                "Message for object {object_id} (opcode {opcode}) is {message_len} bytes long, \
+                 exceeding the limit of {WL_MESSAGE_MAX_LEN} bytes"
+            ));
+        }
+        if !data.len().is_multiple_of(WL_MESSAGE_ALIGN) {
+            return Err(anyhow!(
+                "Message for object {object_id} (opcode {opcode}) has {} bytes of data, \
+                 which is not a multiple of {WL_MESSAGE_ALIGN}",
+                data.len()
+            ));
+        }
+
+        Ok(WlMessage {
             header: WlMessageHeader {
                 object_id,
                 opcode,
-                size: (data.len() + WL_MESSAGE_HEADER_LEN) as u16,
+                size: message_len as u16,
             },
-            data: data.to_vec(),
-        }
+            data: EventBytes::copy_from(data),
+        })
+    }
+
+    /// Returns the ID of the object the message targets or originates from.
+    pub fn object_id(&self) -> u32 {
+        self.header.object_id
+    }
+
+    /// Returns the opcode of the request or event.
+    pub fn opcode(&self) -> u16 {
+        self.header.opcode
+    }
+
+    /// Returns the encoded arguments, without the header.
+    pub fn args(&self) -> &[u8] {
+        &self.data
     }
 }
 
@@ -127,16 +283,20 @@ impl From<WlMessage> for Vec<u8> {
 }
 
 impl TryFrom<&[u8]> for WlMessage {
-    type Error = anyhow::Error;
+    type Error = Error;
 
     /// Deserializes a complete message from wire format.
     ///
+    /// Only the bytes covered by the declared message size are consumed;
+    /// bytes after them, such as the following messages, are ignored.
+    ///
     /// # Errors
     /// Returns an error if:
     /// - Buffer is shorter than header length
-    /// - Buffer length doesn't match declared message size
+    /// - Buffer is shorter than the declared message size
+    /// - Declared size is smaller than the header or not a multiple of 4
     /// - Header contains invalid data
-    fn try_from(buf: &[u8]) -> anyhow::Result<WlMessage> {
+    fn try_from(buf: &[u8]) -> Result<WlMessage> {
         if buf.len() < WL_MESSAGE_HEADER_LEN {
             return Err(anyhow!(
                 "Buffer too short for WlMessage header: expected at least {} bytes, got {}",
@@ -146,6 +306,7 @@ impl TryFrom<&[u8]> for WlMessage {
         }
 
         let header: WlMessageHeader = buf[..WL_MESSAGE_HEADER_LEN].try_into()?;
+        header.validate_size(WL_MESSAGE_MAX_LEN)?;
 
         if buf.len() < header.message_len() {
             return Err(anyhow!(
@@ -157,7 +318,7 @@ impl TryFrom<&[u8]> for WlMessage {
 
         Ok(WlMessage {
             header,
-            data: buf[WL_MESSAGE_HEADER_LEN..].to_vec(),
+            data: EventBytes::copy_from(&buf[WL_MESSAGE_HEADER_LEN..header.message_len()]),
         })
     }
 }
@@ -193,6 +354,10 @@ impl WlMessageIter {
     pub fn new(buffer: Vec<u8>) -> WlMessageIter {
         Self { buffer }
     }
+}
+
+impl Iterator for WlMessageIter {
+    type Item = WlMessage;
 
     /// Attempts to parse the next complete message from the buffer.
     ///
@@ -200,7 +365,7 @@ impl WlMessageIter {
     /// Returns `None` if the buffer contains insufficient or invalid data.
     ///
     /// On success, the parsed message is removed from the internal buffer.
-    pub fn next(&mut self) -> Option<WlMessage> {
+    fn next(&mut self) -> Option<WlMessage> {
         // Check if we have enough data for at least a header
         if self.buffer.len() < WL_MESSAGE_HEADER_LEN {
             self.buffer.clear();
@@ -231,3 +396,141 @@ impl WlMessageIter {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Serializes a message built from the given fields.
+    fn encode(object_id: u32, opcode: u16, data: &[u8]) -> Vec<u8> {
+        WlMessage::new(object_id, opcode, data).unwrap().into()
+    }
+
+    #[test]
+    fn try_from_stops_at_declared_size() {
+        let mut buf = encode(3, 1, &[1, 2, 3, 4]);
+        buf.extend(encode(4, 2, &[5, 6, 7, 8, 9, 10, 11, 12]));
+
+        let message = WlMessage::try_from(buf.as_slice()).unwrap();
+
+        assert_eq!(message.header.object_id, 3);
+        assert_eq!(message.header.opcode, 1);
+        assert_eq!(message.header.size, 12);
+        assert_eq!(*message.data, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn try_from_rejects_truncated_message() {
+        let buf = encode(3, 1, &[1, 2, 3, 4]);
+
+        assert!(WlMessage::try_from(&buf[..10]).is_err());
+    }
+
+    #[test]
+    fn iter_splits_concatenated_messages() {
+        let mut buf = encode(3, 0, &[]);
+        buf.extend(encode(4, 1, &[1, 2, 3, 4]));
+        buf.extend(encode(5, 2, &[5, 6, 7, 8, 9, 10, 11, 12]));
+
+        let messages: Vec<WlMessage> = WlMessageIter::new(buf).collect();
+
+        let fields: Vec<(u32, u16, Vec<u8>)> = messages
+            .into_iter()
+            .map(|message| {
+                (
+                    message.header.object_id,
+                    message.header.opcode,
+                    message.data.to_vec(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                (3, 0, vec![]),
+                (4, 1, vec![1, 2, 3, 4]),
+                (5, 2, vec![5, 6, 7, 8, 9, 10, 11, 12]),
+            ]
+        );
+    }
+
+    #[test]
+    fn iter_stops_at_incomplete_message() {
+        let mut buf = encode(3, 1, &[1, 2, 3, 4]);
+        let next = encode(4, 2, &[5, 6, 7, 8]);
+        buf.extend_from_slice(&next[..10]);
+
+        let messages: Vec<WlMessage> = WlMessageIter::new(buf).collect();
+
+        assert_eq!(messages.len(), 1);
+        assert_eq!(*messages[0].data, [1, 2, 3, 4]);
+    }
+
+    /// A `wl_display.sync` request (object 1, opcode 0, new_id 2) recorded
+    /// on a little-endian host.
+    const SYNC_LITTLE: [u8; 12] = [1, 0, 0, 0, 0, 0, 12, 0, 2, 0, 0, 0];
+    /// The same request recorded on a big-endian host.
+    const SYNC_BIG: [u8; 12] = [0, 0, 0, 1, 0, 12, 0, 0, 0, 0, 0, 2];
+
+    #[test]
+    fn header_layout_in_both_orders() {
+        let header = WlMessageHeader {
+            object_id: 1,
+            opcode: 0,
+            size: 12,
+        };
+
+        assert_eq!(header.encode(Endian::Little), SYNC_LITTLE[..8]);
+        assert_eq!(header.encode(Endian::Big), SYNC_BIG[..8]);
+    }
+
+    #[test]
+    fn header_decodes_swapped_fixture() {
+        let (native, swapped) = match Endian::NATIVE {
+            Endian::Little => (SYNC_LITTLE, SYNC_BIG),
+            Endian::Big => (SYNC_BIG, SYNC_LITTLE),
+        };
+
+        let header = WlMessageHeader::try_from(native.as_slice()).unwrap();
+        let foreign = WlMessageHeader::decode(&swapped, Endian::NATIVE.swapped()).unwrap();
+
+        assert_eq!(header, foreign);
+        assert_eq!((header.object_id, header.opcode, header.size), (1, 0, 12));
+    }
+
+    #[test]
+    fn message_encodes_native_fixture() {
+        let native = match Endian::NATIVE {
+            Endian::Little => SYNC_LITTLE,
+            Endian::Big => SYNC_BIG,
+        };
+
+        assert_eq!(encode(1, 0, &Endian::NATIVE.encode_u32(2)), native);
+    }
+
+    #[test]
+    fn header_sizes_are_validated() {
+        let header = |size| WlMessageHeader {
+            object_id: 3,
+            opcode: 1,
+            size,
+        };
+
+        assert!(header(8).validate_size(WL_MESSAGE_MAX_LEN).is_ok());
+        for size in [0, 4, 6, 13] {
+            assert!(matches!(
+                header(size).validate_size(WL_MESSAGE_MAX_LEN),
+                Err(InvalidHeader::Malformed { .. })
+            ));
+        }
+        assert_eq!(
+            header(64).validate_size(32),
+            Err(InvalidHeader::TooLarge {
+                object_id: 3,
+                opcode: 1,
+                size: 64,
+                max_len: 32
+            })
+        );
+    }
+}
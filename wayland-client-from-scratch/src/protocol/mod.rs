@@ -1,11 +1,73 @@
-use anyhow::anyhow;
+//! The Wayland interfaces implemented by the crate, one module each.
+//!
+//! The `Event::decode` function of every interface takes the connection the
+//! message was received on, from which the file descriptors carried by the
+//! event are taken in order. Interfaces whose events carry none take it as
+//! well, so every event is decoded the same way.
 
+pub mod arena;
+pub mod buffer;
+pub mod callback;
+pub mod color_management;
+pub mod commit_timing;
+pub mod compositor;
+pub mod cursor_shape;
+pub mod data_device;
+pub mod data_device_manager;
+pub mod data_offer;
+pub mod data_source;
 pub mod display;
+pub mod encoder;
+
+use crate::error::{Error, Result, anyhow};
+pub(crate) mod endian;
+pub mod fifo;
+pub mod fixes;
+pub mod fractional_scale;
+pub mod idle_inhibit;
+pub mod image_capture_source;
+pub mod image_copy_capture;
+pub mod keyboard;
+pub mod layer_shell;
+pub mod linux_drm_syncobj;
+pub mod linux_explicit_sync;
 pub mod macros;
 pub mod message;
+pub mod output;
+pub mod pointer;
+pub mod pointer_constraints;
+pub mod pointer_gestures;
+pub mod pointer_warp;
+pub mod presentation;
+pub mod region;
 pub mod registry;
+pub mod relative_pointer;
+pub mod seat;
+pub mod session_lock;
+pub mod shell_role;
+pub mod shm;
+pub mod shm_pool;
+pub mod subcompositor;
+pub mod surface;
+pub mod touch;
 pub mod types;
+pub mod viewporter;
+pub mod xdg_decoration;
+pub mod xdg_output;
+pub mod xdg_popup;
+pub mod xdg_positioner;
+pub mod xdg_surface;
+pub mod xdg_toplevel;
+pub mod xdg_wm_base;
 
+/// Identifies the interface implemented by a protocol object.
+///
+/// The discriminants of the core interfaces follow the order in which they are
+/// declared in `wayland.xml`; extension interfaces are numbered after them in
+/// the order they were added. Only `Display` doubles as a fixed object ID (the display is
+/// always object 1); every other object gets its ID from the connection's
+/// object map and is tagged with one of these variants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum WlObjectId {
     Display = 1,
     Registry = 2,
@@ -30,6 +92,397 @@ pub enum WlObjectId {
     SubCompositor = 21,
     SubSurface = 22,
     Fixes = 23,
+    PointerGestures = 24,
+    PointerGestureSwipe = 25,
+    PointerGesturePinch = 26,
+    PointerGestureHold = 27,
+    XdgWmBase = 28,
+    XdgSurface = 29,
+    XdgToplevel = 30,
+    XdgPositioner = 31,
+    XdgPopup = 32,
+    FractionalScaleManager = 33,
+    FractionalScale = 34,
+    Viewporter = 35,
+    Viewport = 36,
+    Presentation = 37,
+    PresentationFeedback = 38,
+    CommitTimingManager = 39,
+    CommitTimer = 40,
+    FifoManager = 41,
+    Fifo = 42,
+    PointerWarp = 43,
+    ImageCaptureSource = 44,
+    OutputImageCaptureSourceManager = 45,
+    ImageCopyCaptureManager = 46,
+    ImageCopyCaptureSession = 47,
+    ImageCopyCaptureFrame = 48,
+    LinuxDrmSyncobjManager = 49,
+    LinuxDrmSyncobjTimeline = 50,
+    LinuxDrmSyncobjSurface = 51,
+    PointerConstraints = 52,
+    LockedPointer = 53,
+    RelativePointerManager = 54,
+    RelativePointer = 55,
+    IdleInhibitManager = 56,
+    IdleInhibitor = 57,
+    XdgOutputManager = 58,
+    XdgOutput = 59,
+    ColorManager = 60,
+    ColorManagementSurface = 61,
+    ColorManagementSurfaceFeedback = 62,
+    ImageDescriptionCreatorParams = 63,
+    ImageDescription = 64,
+    ImageDescriptionCreatorIcc = 65,
+    LinuxExplicitSynchronization = 66,
+    LinuxSurfaceSynchronization = 67,
+    LinuxBufferRelease = 68,
+    CursorShapeManager = 69,
+    CursorShapeDevice = 70,
+    DecorationManager = 71,
+    ToplevelDecoration = 72,
+    LayerShell = 73,
+    LayerSurface = 74,
+    SessionLockManager = 75,
+    SessionLock = 76,
+    SessionLockSurface = 77,
+}
+
+impl WlObjectId {
+    /// Returns the protocol name of the interface, as advertised by `wl_registry.global`.
+    pub fn interface_name(&self) -> &'static str {
+        match self {
+            WlObjectId::Display => "wl_display",
+            WlObjectId::Registry => "wl_registry",
+            WlObjectId::Callback => "wl_callback",
+            WlObjectId::Compositor => "wl_compositor",
+            WlObjectId::ShmPool => "wl_shm_pool",
+            WlObjectId::Shm => "wl_shm",
+            WlObjectId::Buffer => "wl_buffer",
+            WlObjectId::DataOffer => "wl_data_offer",
+            WlObjectId::DataSource => "wl_data_source",
+            WlObjectId::DataDevice => "wl_data_device",
+            WlObjectId::DataDeviceManager => "wl_data_device_manager",
+            WlObjectId::Shell => "wl_shell",
+            WlObjectId::ShellSurface => "wl_shell_surface",
+            WlObjectId::Surface => "wl_surface",
+            WlObjectId::Seat => "wl_seat",
+            WlObjectId::Pointer => "wl_pointer",
+            WlObjectId::Keyboard => "wl_keyboard",
+            WlObjectId::Touch => "wl_touch",
+            WlObjectId::Output => "wl_output",
+            WlObjectId::Region => "wl_region",
+            WlObjectId::SubCompositor => "wl_subcompositor",
+            WlObjectId::SubSurface => "wl_subsurface",
+            WlObjectId::Fixes => "wl_fixes",
+            WlObjectId::PointerGestures => "zwp_pointer_gestures_v1",
+            WlObjectId::PointerGestureSwipe => "zwp_pointer_gesture_swipe_v1",
+            WlObjectId::PointerGesturePinch => "zwp_pointer_gesture_pinch_v1",
+            WlObjectId::PointerGestureHold => "zwp_pointer_gesture_hold_v1",
+            WlObjectId::XdgWmBase => "xdg_wm_base",
+            WlObjectId::XdgSurface => "xdg_surface",
+            WlObjectId::XdgToplevel => "xdg_toplevel",
+            WlObjectId::XdgPositioner => "xdg_positioner",
+            WlObjectId::XdgPopup => "xdg_popup",
+            WlObjectId::FractionalScaleManager => "wp_fractional_scale_manager_v1",
+            WlObjectId::FractionalScale => "wp_fractional_scale_v1",
+            WlObjectId::Viewporter => "wp_viewporter",
+            WlObjectId::Viewport => "wp_viewport",
+            WlObjectId::Presentation => "wp_presentation",
+            WlObjectId::PresentationFeedback => "wp_presentation_feedback",
+            WlObjectId::CommitTimingManager => "wp_commit_timing_manager_v1",
+            WlObjectId::CommitTimer => "wp_commit_timer_v1",
+            WlObjectId::FifoManager => "wp_fifo_manager_v1",
+            WlObjectId::Fifo => "wp_fifo_v1",
+            WlObjectId::PointerWarp => "wp_pointer_warp_v1",
+            WlObjectId::ImageCaptureSource => "ext_image_capture_source_v1",
+            WlObjectId::OutputImageCaptureSourceManager => {
+                "ext_output_image_capture_source_manager_v1"
+            }
+            WlObjectId::ImageCopyCaptureManager => "ext_image_copy_capture_manager_v1",
+            WlObjectId::ImageCopyCaptureSession => "ext_image_copy_capture_session_v1",
+            WlObjectId::ImageCopyCaptureFrame => "ext_image_copy_capture_frame_v1",
+            WlObjectId::LinuxDrmSyncobjManager => "wp_linux_drm_syncobj_manager_v1",
+            WlObjectId::LinuxDrmSyncobjTimeline => "wp_linux_drm_syncobj_timeline_v1",
+            WlObjectId::LinuxDrmSyncobjSurface => "wp_linux_drm_syncobj_surface_v1",
+            WlObjectId::PointerConstraints => "zwp_pointer_constraints_v1",
+            WlObjectId::LockedPointer => "zwp_locked_pointer_v1",
+            WlObjectId::RelativePointerManager => "zwp_relative_pointer_manager_v1",
+            WlObjectId::RelativePointer => "zwp_relative_pointer_v1",
+            WlObjectId::IdleInhibitManager => "zwp_idle_inhibit_manager_v1",
+            WlObjectId::IdleInhibitor => "zwp_idle_inhibitor_v1",
+            WlObjectId::XdgOutputManager => "zxdg_output_manager_v1",
+            WlObjectId::XdgOutput => "zxdg_output_v1",
+            WlObjectId::ColorManager => "wp_color_manager_v1",
+            WlObjectId::ColorManagementSurface => "wp_color_management_surface_v1",
+            WlObjectId::ColorManagementSurfaceFeedback => "wp_color_management_surface_feedback_v1",
+            WlObjectId::ImageDescriptionCreatorParams => "wp_image_description_creator_params_v1",
+            WlObjectId::ImageDescription => "wp_image_description_v1",
+            WlObjectId::ImageDescriptionCreatorIcc => "wp_image_description_creator_icc_v1",
+            WlObjectId::LinuxExplicitSynchronization => "zwp_linux_explicit_synchronization_v1",
+            WlObjectId::LinuxSurfaceSynchronization => "zwp_linux_surface_synchronization_v1",
+            WlObjectId::LinuxBufferRelease => "zwp_linux_buffer_release_v1",
+            WlObjectId::CursorShapeManager => "wp_cursor_shape_manager_v1",
+            WlObjectId::CursorShapeDevice => "wp_cursor_shape_device_v1",
+            WlObjectId::DecorationManager => "zxdg_decoration_manager_v1",
+            WlObjectId::ToplevelDecoration => "zxdg_toplevel_decoration_v1",
+            WlObjectId::LayerShell => "zwlr_layer_shell_v1",
+            WlObjectId::LayerSurface => "zwlr_layer_surface_v1",
+            WlObjectId::SessionLockManager => "ext_session_lock_manager_v1",
+            WlObjectId::SessionLock => "ext_session_lock_v1",
+            WlObjectId::SessionLockSurface => "ext_session_lock_surface_v1",
+        }
+    }
+
+    /// Returns the interface version the event with the given opcode was
+    /// introduced in, or `None` if the interface has no such event.
+    ///
+    /// Interfaces whose events are not implemented have no event.
+    pub fn event_since(&self, opcode: u16) -> Option<u32> {
+        macro_rules! since {
+            ($event:ty) => {
+                <$event>::try_from(opcode).ok().map(<$event>::since)
+            };
+        }
+
+        match self {
+            WlObjectId::Display => since!(display::event::Opcode),
+            WlObjectId::Registry => since!(registry::event::Opcode),
+            WlObjectId::Callback => since!(callback::event::Opcode),
+            WlObjectId::Shm => since!(shm::event::Opcode),
+            WlObjectId::Buffer => since!(buffer::event::Opcode),
+            WlObjectId::DataOffer => since!(data_offer::event::Opcode),
+            WlObjectId::DataSource => since!(data_source::event::Opcode),
+            WlObjectId::DataDevice => since!(data_device::event::Opcode),
+            WlObjectId::Surface => since!(surface::event::Opcode),
+            WlObjectId::Seat => since!(seat::event::Opcode),
+            WlObjectId::Pointer => since!(pointer::event::Opcode),
+            WlObjectId::Keyboard => since!(keyboard::event::Opcode),
+            WlObjectId::Touch => since!(touch::event::Opcode),
+            WlObjectId::Output => since!(output::event::Opcode),
+            WlObjectId::PointerGestureSwipe | WlObjectId::PointerGesturePinch => {
+                since!(pointer_gestures::event::Opcode)
+            }
+            WlObjectId::PointerGestureHold => since!(pointer_gestures::event::HoldOpcode),
+            WlObjectId::XdgWmBase => since!(xdg_wm_base::event::Opcode),
+            WlObjectId::XdgSurface => since!(xdg_surface::event::Opcode),
+            WlObjectId::XdgToplevel => since!(xdg_toplevel::event::Opcode),
+            WlObjectId::XdgPopup => since!(xdg_popup::event::Opcode),
+            WlObjectId::FractionalScale => since!(fractional_scale::event::Opcode),
+            WlObjectId::Presentation => since!(presentation::event::Opcode),
+            WlObjectId::PresentationFeedback => since!(presentation::event::FeedbackOpcode),
+            WlObjectId::ImageCopyCaptureSession => {
+                since!(image_copy_capture::event::SessionOpcode)
+            }
+            WlObjectId::ImageCopyCaptureFrame => since!(image_copy_capture::event::FrameOpcode),
+            WlObjectId::LockedPointer => since!(pointer_constraints::event::LockedPointerOpcode),
+            WlObjectId::RelativePointer => since!(relative_pointer::event::Opcode),
+            WlObjectId::XdgOutput => since!(xdg_output::event::Opcode),
+            WlObjectId::ColorManager => since!(color_management::event::Opcode),
+            WlObjectId::ColorManagementSurfaceFeedback => {
+                since!(color_management::event::FeedbackOpcode)
+            }
+            WlObjectId::ImageDescription => {
+                since!(color_management::event::ImageDescriptionOpcode)
+            }
+            WlObjectId::LinuxBufferRelease => {
+                since!(linux_explicit_sync::event::ReleaseOpcode)
+            }
+            WlObjectId::ToplevelDecoration => since!(xdg_decoration::event::Opcode),
+            WlObjectId::LayerSurface => since!(layer_shell::event::Opcode),
+            WlObjectId::SessionLock => since!(session_lock::event::LockOpcode),
+            WlObjectId::SessionLockSurface => since!(session_lock::event::SurfaceOpcode),
+            WlObjectId::Compositor
+            | WlObjectId::ShmPool
+            | WlObjectId::DataDeviceManager
+            | WlObjectId::Shell
+            | WlObjectId::ShellSurface
+            | WlObjectId::Region
+            | WlObjectId::SubCompositor
+            | WlObjectId::SubSurface
+            | WlObjectId::Fixes
+            | WlObjectId::PointerGestures
+            | WlObjectId::XdgPositioner
+            | WlObjectId::FractionalScaleManager
+            | WlObjectId::Viewporter
+            | WlObjectId::Viewport
+            | WlObjectId::CommitTimingManager
+            | WlObjectId::CommitTimer
+            | WlObjectId::FifoManager
+            | WlObjectId::Fifo
+            | WlObjectId::PointerWarp
+            | WlObjectId::ImageCaptureSource
+            | WlObjectId::OutputImageCaptureSourceManager
+            | WlObjectId::ImageCopyCaptureManager
+            | WlObjectId::LinuxDrmSyncobjManager
+            | WlObjectId::LinuxDrmSyncobjTimeline
+            | WlObjectId::LinuxDrmSyncobjSurface
+            | WlObjectId::PointerConstraints
+            | WlObjectId::RelativePointerManager
+            | WlObjectId::IdleInhibitManager
+            | WlObjectId::IdleInhibitor
+            | WlObjectId::XdgOutputManager
+            | WlObjectId::ColorManagementSurface
+            | WlObjectId::ImageDescriptionCreatorParams
+            | WlObjectId::ImageDescriptionCreatorIcc
+            | WlObjectId::LinuxExplicitSynchronization
+            | WlObjectId::LinuxSurfaceSynchronization
+            | WlObjectId::CursorShapeManager
+            | WlObjectId::CursorShapeDevice
+            | WlObjectId::DecorationManager
+            | WlObjectId::LayerShell
+            | WlObjectId::SessionLockManager => None,
+        }
+    }
+
+    /// Returns the number of file descriptors the event with the given
+    /// opcode carries, which are received alongside the message.
+    pub fn event_fd_count(&self, opcode: u16) -> usize {
+        let carries_fd = match self {
+            WlObjectId::Keyboard => matches!(
+                keyboard::event::Opcode::try_from(opcode),
+                Ok(keyboard::event::Opcode::Keymap)
+            ),
+            WlObjectId::DataSource => matches!(
+                data_source::event::Opcode::try_from(opcode),
+                Ok(data_source::event::Opcode::Send)
+            ),
+            WlObjectId::LinuxBufferRelease => matches!(
+                linux_explicit_sync::event::ReleaseOpcode::try_from(opcode),
+                Ok(linux_explicit_sync::event::ReleaseOpcode::FencedRelease)
+            ),
+            _ => false,
+        };
+
+        usize::from(carries_fd)
+    }
+
+    /// Returns `true` if the request with the given opcode destroys objects
+    /// implementing the interface, as marked `type="destructor"` in the
+    /// protocol XML.
+    ///
+    /// Interfaces whose requests are not implemented have no destructor.
+    pub fn is_destructor(&self, opcode: u16) -> bool {
+        macro_rules! destructor {
+            ($opcode:ty) => {
+                <$opcode>::from_opcode(opcode).is_some_and(<$opcode>::is_destructor)
+            };
+        }
+
+        match self {
+            WlObjectId::Display => destructor!(display::request::Opcode),
+            WlObjectId::Registry => destructor!(registry::request::Opcode),
+            WlObjectId::Compositor => destructor!(compositor::request::Opcode),
+            WlObjectId::ShmPool => destructor!(shm_pool::request::Opcode),
+            WlObjectId::Shm => destructor!(shm::request::Opcode),
+            WlObjectId::Buffer => destructor!(buffer::request::Opcode),
+            WlObjectId::DataOffer => destructor!(data_offer::request::Opcode),
+            WlObjectId::DataSource => destructor!(data_source::request::Opcode),
+            WlObjectId::DataDevice => destructor!(data_device::request::Opcode),
+            WlObjectId::DataDeviceManager => destructor!(data_device_manager::request::Opcode),
+            WlObjectId::Surface => destructor!(surface::request::Opcode),
+            WlObjectId::Region => destructor!(region::request::Opcode),
+            WlObjectId::Seat => destructor!(seat::request::Opcode),
+            WlObjectId::Pointer => destructor!(pointer::request::Opcode),
+            WlObjectId::Keyboard => destructor!(keyboard::request::Opcode),
+            WlObjectId::Touch => destructor!(touch::request::Opcode),
+            WlObjectId::Output => destructor!(output::request::Opcode),
+            WlObjectId::Fixes => destructor!(fixes::request::Opcode),
+            WlObjectId::PointerGestures => destructor!(pointer_gestures::request::Opcode),
+            WlObjectId::PointerGestureSwipe
+            | WlObjectId::PointerGesturePinch
+            | WlObjectId::PointerGestureHold => {
+                destructor!(pointer_gestures::request::GestureOpcode)
+            }
+            WlObjectId::XdgWmBase => destructor!(xdg_wm_base::request::Opcode),
+            WlObjectId::XdgSurface => destructor!(xdg_surface::request::Opcode),
+            WlObjectId::XdgToplevel => destructor!(xdg_toplevel::request::Opcode),
+            WlObjectId::XdgPositioner => destructor!(xdg_positioner::request::Opcode),
+            WlObjectId::XdgPopup => destructor!(xdg_popup::request::Opcode),
+            WlObjectId::FractionalScaleManager => destructor!(fractional_scale::request::Opcode),
+            WlObjectId::FractionalScale => {
+                destructor!(fractional_scale::request::FractionalScaleOpcode)
+            }
+            WlObjectId::SubCompositor => destructor!(subcompositor::request::Opcode),
+            WlObjectId::SubSurface => destructor!(subcompositor::request::SubsurfaceOpcode),
+            WlObjectId::Viewporter => destructor!(viewporter::request::Opcode),
+            WlObjectId::Viewport => destructor!(viewporter::request::ViewportOpcode),
+            WlObjectId::Presentation => destructor!(presentation::request::Opcode),
+            WlObjectId::CommitTimingManager => destructor!(commit_timing::request::Opcode),
+            WlObjectId::CommitTimer => destructor!(commit_timing::request::TimerOpcode),
+            WlObjectId::FifoManager => destructor!(fifo::request::Opcode),
+            WlObjectId::Fifo => destructor!(fifo::request::FifoOpcode),
+            WlObjectId::PointerWarp => destructor!(pointer_warp::request::Opcode),
+            WlObjectId::ImageCaptureSource => destructor!(image_capture_source::request::Opcode),
+            WlObjectId::OutputImageCaptureSourceManager => {
+                destructor!(image_capture_source::request::OutputManagerOpcode)
+            }
+            WlObjectId::ImageCopyCaptureManager => destructor!(image_copy_capture::request::Opcode),
+            WlObjectId::ImageCopyCaptureSession => {
+                destructor!(image_copy_capture::request::SessionOpcode)
+            }
+            WlObjectId::ImageCopyCaptureFrame => {
+                destructor!(image_copy_capture::request::FrameOpcode)
+            }
+            WlObjectId::LinuxDrmSyncobjManager => destructor!(linux_drm_syncobj::request::Opcode),
+            WlObjectId::LinuxDrmSyncobjTimeline => {
+                destructor!(linux_drm_syncobj::request::TimelineOpcode)
+            }
+            WlObjectId::LinuxDrmSyncobjSurface => {
+                destructor!(linux_drm_syncobj::request::SurfaceOpcode)
+            }
+            WlObjectId::PointerConstraints => destructor!(pointer_constraints::request::Opcode),
+            WlObjectId::LockedPointer => {
+                destructor!(pointer_constraints::request::LockedPointerOpcode)
+            }
+            WlObjectId::RelativePointerManager => destructor!(relative_pointer::request::Opcode),
+            WlObjectId::RelativePointer => {
+                destructor!(relative_pointer::request::RelativePointerOpcode)
+            }
+            WlObjectId::IdleInhibitManager => destructor!(idle_inhibit::request::Opcode),
+            WlObjectId::IdleInhibitor => destructor!(idle_inhibit::request::InhibitorOpcode),
+            WlObjectId::XdgOutputManager => destructor!(xdg_output::request::Opcode),
+            WlObjectId::XdgOutput => destructor!(xdg_output::request::XdgOutputOpcode),
+            WlObjectId::ColorManager => destructor!(color_management::request::Opcode),
+            WlObjectId::ColorManagementSurface => {
+                destructor!(color_management::request::SurfaceOpcode)
+            }
+            WlObjectId::ColorManagementSurfaceFeedback => {
+                destructor!(color_management::request::FeedbackOpcode)
+            }
+            WlObjectId::ImageDescriptionCreatorParams => {
+                destructor!(color_management::request::ParamsOpcode)
+            }
+            WlObjectId::ImageDescription => {
+                destructor!(color_management::request::ImageDescriptionOpcode)
+            }
+            WlObjectId::ImageDescriptionCreatorIcc => {
+                destructor!(color_management::request::IccOpcode)
+            }
+            WlObjectId::LinuxExplicitSynchronization => {
+                destructor!(linux_explicit_sync::request::Opcode)
+            }
+            WlObjectId::LinuxSurfaceSynchronization => {
+                destructor!(linux_explicit_sync::request::SurfaceOpcode)
+            }
+            WlObjectId::CursorShapeManager => destructor!(cursor_shape::request::Opcode),
+            WlObjectId::CursorShapeDevice => destructor!(cursor_shape::request::DeviceOpcode),
+            WlObjectId::DecorationManager => destructor!(xdg_decoration::request::Opcode),
+            WlObjectId::ToplevelDecoration => {
+                destructor!(xdg_decoration::request::DecorationOpcode)
+            }
+            WlObjectId::LayerShell => destructor!(layer_shell::request::Opcode),
+            WlObjectId::LayerSurface => destructor!(layer_shell::request::SurfaceOpcode),
+            WlObjectId::SessionLockManager => destructor!(session_lock::request::Opcode),
+            WlObjectId::SessionLock => destructor!(session_lock::request::LockOpcode),
+            WlObjectId::SessionLockSurface => destructor!(session_lock::request::SurfaceOpcode),
+            WlObjectId::Callback
+            | WlObjectId::Shell
+            | WlObjectId::ShellSurface
+            | WlObjectId::PresentationFeedback
+            | WlObjectId::LinuxBufferRelease => false,
+        }
+    }
 }
 
 impl From<WlObjectId> for u32 {
@@ -39,8 +492,8 @@ impl From<WlObjectId> for u32 {
 }
 
 impl TryFrom<u32> for WlObjectId {
-    type Error = anyhow::Error;
-    fn try_from(value: u32) -> anyhow::Result<Self> {
+    type Error = Error;
+    fn try_from(value: u32) -> Result<Self> {
         match value {
             1 => Ok(WlObjectId::Display),
             2 => Ok(WlObjectId::Registry),
@@ -65,6 +518,60 @@ impl TryFrom<u32> for WlObjectId {
             21 => Ok(WlObjectId::SubCompositor),
             22 => Ok(WlObjectId::SubSurface),
             23 => Ok(WlObjectId::Fixes),
+            24 => Ok(WlObjectId::PointerGestures),
+            25 => Ok(WlObjectId::PointerGestureSwipe),
+            26 => Ok(WlObjectId::PointerGesturePinch),
+            27 => Ok(WlObjectId::PointerGestureHold),
+            28 => Ok(WlObjectId::XdgWmBase),
+            29 => Ok(WlObjectId::XdgSurface),
+            30 => Ok(WlObjectId::XdgToplevel),
+            31 => Ok(WlObjectId::XdgPositioner),
+            32 => Ok(WlObjectId::XdgPopup),
+            33 => Ok(WlObjectId::FractionalScaleManager),
+            34 => Ok(WlObjectId::FractionalScale),
+            35 => Ok(WlObjectId::Viewporter),
+            36 => Ok(WlObjectId::Viewport),
+            37 => Ok(WlObjectId::Presentation),
+            38 => Ok(WlObjectId::PresentationFeedback),
+            39 => Ok(WlObjectId::CommitTimingManager),
+            40 => Ok(WlObjectId::CommitTimer),
+            41 => Ok(WlObjectId::FifoManager),
+            42 => Ok(WlObjectId::Fifo),
+            43 => Ok(WlObjectId::PointerWarp),
+            44 => Ok(WlObjectId::ImageCaptureSource),
+            45 => Ok(WlObjectId::OutputImageCaptureSourceManager),
+            46 => Ok(WlObjectId::ImageCopyCaptureManager),
+            47 => Ok(WlObjectId::ImageCopyCaptureSession),
+            48 => Ok(WlObjectId::ImageCopyCaptureFrame),
+            49 => Ok(WlObjectId::LinuxDrmSyncobjManager),
+            50 => Ok(WlObjectId::LinuxDrmSyncobjTimeline),
+            51 => Ok(WlObjectId::LinuxDrmSyncobjSurface),
+            52 => Ok(WlObjectId::PointerConstraints),
+            53 => Ok(WlObjectId::LockedPointer),
+            54 => Ok(WlObjectId::RelativePointerManager),
+            55 => Ok(WlObjectId::RelativePointer),
+            56 => Ok(WlObjectId::IdleInhibitManager),
+            57 => Ok(WlObjectId::IdleInhibitor),
+            58 => Ok(WlObjectId::XdgOutputManager),
+            59 => Ok(WlObjectId::XdgOutput),
+            60 => Ok(WlObjectId::ColorManager),
+            61 => Ok(WlObjectId::ColorManagementSurface),
+            62 => Ok(WlObjectId::ColorManagementSurfaceFeedback),
+            63 => Ok(WlObjectId::ImageDescriptionCreatorParams),
+            64 => Ok(WlObjectId::ImageDescription),
+            65 => Ok(WlObjectId::ImageDescriptionCreatorIcc),
+            66 => Ok(WlObjectId::LinuxExplicitSynchronization),
+            67 => Ok(WlObjectId::LinuxSurfaceSynchronization),
+            68 => Ok(WlObjectId::LinuxBufferRelease),
+            69 => Ok(WlObjectId::CursorShapeManager),
+            70 => Ok(WlObjectId::CursorShapeDevice),
+            71 => Ok(WlObjectId::DecorationManager),
+            72 => Ok(WlObjectId::ToplevelDecoration),
+            73 => Ok(WlObjectId::LayerShell),
+            74 => Ok(WlObjectId::LayerSurface),
+            75 => Ok(WlObjectId::SessionLockManager),
+            76 => Ok(WlObjectId::SessionLock),
+            77 => Ok(WlObjectId::SessionLockSurface),
             _ => Err(anyhow!("WlObjectID: Invalid id")),
         }
     }
@@ -0,0 +1,66 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{output::OutputState, types::WlString},
+};
+
+/// Represents a `wl_output.description` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="description" since="4">
+///   <description summary="human-readable description of this output">
+///     Many compositors can produce human-readable descriptions of their
+///     outputs. The client may wish to know this description as well, e.g. for
+///     output selection purposes.
+///
+///     The description is a UTF-8 string with no convention defined for its
+///     contents. The description is not guaranteed to be unique among all
+///     wl_output globals. Examples might include 'Foocorp 11" Display' or
+///     'Virtual X11 output via :1'.
+///
+///     The description event is sent after binding the output object and
+///     whenever the description changes. The description is optional, and may
+///     not be sent at all.
+///
+///     The description event will be followed by a done event.
+///   </description>
+///   <arg name="description" type="string" summary="output description"/>
+/// </event>
+/// ```
+#[derive(Debug)]
+pub struct Description {
+    /// The output description.
+    pub description: WlString,
+}
+
+impl TryFrom<&[u8]> for Description {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Description` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0+: `description` (WlString) - The description with length prefix
+    fn try_from(buf: &[u8]) -> Result<Description> {
+        Ok(Description {
+            description: buf.try_into()?,
+        })
+    }
+}
+
+/// Handles a `wl_output.description` event by recording the description as pending.
+pub(super) fn handle_wl_output_description(
+    conn: &Connection,
+    output_id: u32,
+    event: Description,
+) -> Result<()> {
+    let description = conn.decode_string(&event.description)?;
+
+    if let Some(state) = conn.object_data::<OutputState>(output_id) {
+        state.update(conn, output_id, |info| {
+            info.description = Some(description);
+        });
+    }
+
+    Ok(())
+}
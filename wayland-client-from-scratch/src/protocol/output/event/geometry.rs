@@ -0,0 +1,178 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        output::OutputState,
+        types::{WL_TYPE_INT_LEN, WlInt, WlString},
+    },
+    wl_enum,
+};
+
+wl_enum! {
+    /// The arrangement of the subpixels of an output, see `wl_output.geometry`.
+    Subpixel {
+        /// The arrangement is not known.
+        Unknown = 0,
+        /// The output has no subpixels.
+        None = 1,
+        /// Horizontal, in red, green, blue order.
+        HorizontalRgb = 2,
+        /// Horizontal, in blue, green, red order.
+        HorizontalBgr = 3,
+        /// Vertical, in red, green, blue order.
+        VerticalRgb = 4,
+        /// Vertical, in blue, green, red order.
+        VerticalBgr = 5,
+    }
+}
+
+wl_enum! {
+    /// A rotation and/or flip of the content shown on an output.
+    ///
+    /// Rotations are counter-clockwise; flips are around the vertical axis
+    /// and applied before the rotation.
+    Transform {
+        /// No transform.
+        Normal = 0,
+        /// Rotated by 90 degrees.
+        Rotate90 = 1,
+        /// Rotated by 180 degrees.
+        Rotate180 = 2,
+        /// Rotated by 270 degrees.
+        Rotate270 = 3,
+        /// Flipped.
+        Flipped = 4,
+        /// Flipped, then rotated by 90 degrees.
+        Flipped90 = 5,
+        /// Flipped, then rotated by 180 degrees.
+        Flipped180 = 6,
+        /// Flipped, then rotated by 270 degrees.
+        Flipped270 = 7,
+    }
+}
+
+impl Transform {
+    /// Returns `true` if the transform rotates by 90 or 270 degrees, so the
+    /// width and height of a buffer become the height and width of the
+    /// surface.
+    pub fn swaps_axes(self) -> bool {
+        (self as u32) & 1 == 1
+    }
+
+    /// Returns the size of a buffer pre-rotated with the transform, for
+    /// content of `width` x `height` surface pixels.
+    pub fn buffer_size(self, width: i32, height: i32) -> (i32, i32) {
+        if self.swaps_axes() {
+            (height, width)
+        } else {
+            (width, height)
+        }
+    }
+}
+
+/// Represents a `wl_output.geometry` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="geometry">
+///   <description summary="properties of the output">
+///     The geometry event describes geometric properties of the output.
+///     The event is sent when binding to the output object and whenever
+///     any of the properties change.
+///
+///     The physical size can be set to zero if it doesn't make sense for this
+///     output (e.g. for projectors or virtual outputs).
+///
+///     The geometry event will be followed by a done event (starting from
+///     version 2).
+///   </description>
+///   <arg name="x" type="int" summary="x position within the global compositor space"/>
+///   <arg name="y" type="int" summary="y position within the global compositor space"/>
+///   <arg name="physical_width" type="int" summary="width in millimeters of the output"/>
+///   <arg name="physical_height" type="int" summary="height in millimeters of the output"/>
+///   <arg name="subpixel" type="int" enum="subpixel" summary="subpixel orientation of the output"/>
+///   <arg name="make" type="string" summary="textual description of the manufacturer"/>
+///   <arg name="model" type="string" summary="textual description of the model"/>
+///   <arg name="transform" type="int" enum="transform" summary="additional transformation applied to buffer contents during presentation"/>
+/// </event>
+/// ```
+#[derive(Debug)]
+pub struct Geometry {
+    /// The horizontal position in the global compositor space.
+    pub x: WlInt,
+    /// The vertical position in the global compositor space.
+    pub y: WlInt,
+    /// The width in millimeters.
+    pub physical_width: WlInt,
+    /// The height in millimeters.
+    pub physical_height: WlInt,
+    /// The subpixel orientation, as a `Subpixel` value.
+    pub subpixel: WlInt,
+    /// The manufacturer.
+    pub make: WlString,
+    /// The model.
+    pub model: WlString,
+    /// The transform applied to buffers, as a `Transform` value.
+    pub transform: WlInt,
+}
+
+impl TryFrom<&[u8]> for Geometry {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Geometry` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `x` (i32) - The horizontal position
+    /// - Bytes 4-7: `y` (i32) - The vertical position
+    /// - Bytes 8-11: `physical_width` (i32) - The width in millimeters
+    /// - Bytes 12-15: `physical_height` (i32) - The height in millimeters
+    /// - Bytes 16-19: `subpixel` (i32) - The subpixel orientation
+    /// - Bytes 20+: `make` (WlString) - The manufacturer with length prefix
+    /// - Next: `model` (WlString) - The model with length prefix
+    /// - Last 4 bytes: `transform` (i32) - The buffer transform
+    fn try_from(buf: &[u8]) -> Result<Geometry> {
+        let make_start_pos = 5 * WL_TYPE_INT_LEN;
+        let make = WlString::try_from(buf.get(make_start_pos..).unwrap_or_default())?;
+
+        let model_start_pos = make_start_pos + make.buffer_size();
+        let model = WlString::try_from(buf.get(model_start_pos..).unwrap_or_default())?;
+
+        Ok(Geometry {
+            x: WlInt::read(buf, 0)?,
+            y: WlInt::read(buf, WL_TYPE_INT_LEN)?,
+            physical_width: WlInt::read(buf, 2 * WL_TYPE_INT_LEN)?,
+            physical_height: WlInt::read(buf, 3 * WL_TYPE_INT_LEN)?,
+            subpixel: WlInt::read(buf, 4 * WL_TYPE_INT_LEN)?,
+            transform: WlInt::read(buf, model_start_pos + model.buffer_size())?,
+            make,
+            model,
+        })
+    }
+}
+
+/// Handles a `wl_output.geometry` event by recording the properties as pending.
+pub(super) fn handle_wl_output_geometry(
+    conn: &Connection,
+    output_id: u32,
+    geometry: Geometry,
+) -> Result<()> {
+    let make = conn.decode_string(&geometry.make)?;
+    let model = conn.decode_string(&geometry.model)?;
+
+    if let Some(state) = conn.object_data::<OutputState>(output_id) {
+        state.update(conn, output_id, |info| {
+            info.x = geometry.x.get();
+            info.y = geometry.y.get();
+            info.physical_width = geometry.physical_width.get();
+            info.physical_height = geometry.physical_height.get();
+            info.subpixel =
+                Subpixel::try_from(geometry.subpixel.get() as u32).unwrap_or(Subpixel::Unknown);
+            info.make = make;
+            info.model = model;
+            info.transform =
+                Transform::try_from(geometry.transform.get() as u32).unwrap_or(Transform::Normal);
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,160 @@
+pub mod description;
+pub mod geometry;
+pub mod mode;
+pub mod name;
+pub mod scale;
+
+use crate::{
+    connection::Connection,
+    error::{Error, Result, anyhow},
+    protocol::{message::WlMessage, output::OutputState},
+};
+
+/// Represents the event types that can be emitted by a `wl_output` object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    /// Describes the position, physical size, make, model and transform of the output.
+    ///
+    /// # Event Arguments
+    /// - `x`, `y`: The position in the global compositor space
+    /// - `physical_width`, `physical_height`: The physical size in millimeters
+    /// - `subpixel`: The subpixel orientation
+    /// - `make`, `model`: Textual descriptions of the device
+    /// - `transform`: The transform applied to buffers shown on the output
+    Geometry = 0,
+
+    /// Describes a video mode of the output.
+    ///
+    /// # Event Arguments
+    /// - `flags`: Whether the mode is the current and/or preferred one
+    /// - `width`, `height`: The size in hardware pixels
+    /// - `refresh`: The vertical refresh rate in mHz
+    Mode = 1,
+
+    /// Ends a batch of property changes (since version 2).
+    Done = 2,
+
+    /// Announces the scale factor of the output (since version 2).
+    ///
+    /// # Event Arguments
+    /// - `factor`: The integer scale factor
+    Scale = 3,
+
+    /// Announces the name of the output, such as `DP-1` (since version 4).
+    ///
+    /// # Event Arguments
+    /// - `name`: The output name
+    Name = 4,
+
+    /// Announces a human-readable description of the output (since version 4).
+    ///
+    /// # Event Arguments
+    /// - `description`: The output description
+    Description = 5,
+}
+
+impl TryFrom<u16> for Opcode {
+    type Error = Error;
+
+    /// Attempts to convert a raw opcode value into a structured `WlOutputEvent`.
+    fn try_from(value: u16) -> Result<Opcode> {
+        match value {
+            0 => Ok(Opcode::Geometry),
+            1 => Ok(Opcode::Mode),
+            2 => Ok(Opcode::Done),
+            3 => Ok(Opcode::Scale),
+            4 => Ok(Opcode::Name),
+            5 => Ok(Opcode::Description),
+            _ => Err(anyhow!("Invalid wl_output event opcode: {}", value)),
+        }
+    }
+}
+
+impl Opcode {
+    /// Returns the interface version the event was introduced in.
+    pub fn since(self) -> u32 {
+        match self {
+            Opcode::Geometry | Opcode::Mode => 1,
+            Opcode::Done | Opcode::Scale => 2,
+            Opcode::Name | Opcode::Description => 4,
+        }
+    }
+}
+
+/// A decoded `wl_output` event, with its arguments.
+#[derive(Debug)]
+pub enum Event {
+    /// Describes the position, physical size, make, model and transform of the output.
+    Geometry(geometry::Geometry),
+    /// Describes a video mode of the output.
+    Mode(mode::Mode),
+    /// Ends a batch of property changes (since version 2).
+    Done,
+    /// Announces the scale factor of the output (since version 2).
+    Scale(scale::Scale),
+    /// Announces the name of the output, such as `DP-1` (since version 4).
+    Name(name::Name),
+    /// Announces a human-readable description of the output (since version 4).
+    Description(description::Description),
+}
+
+impl Event {
+    /// Decodes a `wl_output` event from its message.
+    ///
+    /// # Errors
+    /// Returns an error if the opcode is unknown or the arguments are malformed.
+    pub fn decode(_conn: &Connection, msg: &WlMessage) -> Result<Event> {
+        let buf: &[u8] = &msg.data;
+
+        Ok(match Opcode::try_from(msg.header.opcode)? {
+            Opcode::Geometry => Event::Geometry(geometry::Geometry::try_from(buf)?),
+            Opcode::Mode => Event::Mode(mode::Mode::try_from(buf)?),
+            Opcode::Done => Event::Done,
+            Opcode::Scale => Event::Scale(scale::Scale::try_from(buf)?),
+            Opcode::Name => Event::Name(name::Name::try_from(buf)?),
+            Opcode::Description => Event::Description(description::Description::try_from(buf)?),
+        })
+    }
+
+    /// Returns the opcode of the event.
+    pub fn opcode(&self) -> Opcode {
+        match self {
+            Event::Geometry(_) => Opcode::Geometry,
+            Event::Mode(_) => Opcode::Mode,
+            Event::Done => Opcode::Done,
+            Event::Scale(_) => Opcode::Scale,
+            Event::Name(_) => Opcode::Name,
+            Event::Description(_) => Opcode::Description,
+        }
+    }
+}
+
+/// Dispatches incoming `wl_output` events to their appropriate handler functions.
+///
+/// # Event Routing
+/// * `Geometry` events are routed to `geometry::handle_wl_output_geometry`
+/// * `Mode` events are routed to `mode::handle_wl_output_mode`
+/// * `Done` events apply the pending properties through `OutputState::done`
+/// * `Scale` events are routed to `scale::handle_wl_output_scale`
+/// * `Name` events are routed to `name::handle_wl_output_name`
+/// * `Description` events are routed to `description::handle_wl_output_description`
+pub fn handle_wl_output_event(conn: &Connection, msg: WlMessage) -> Result<()> {
+    let output_id = msg.header.object_id;
+
+    // Route the event to the appropriate handler based on type
+    match Event::decode(conn, &msg)? {
+        Event::Geometry(event) => geometry::handle_wl_output_geometry(conn, output_id, event),
+        Event::Mode(event) => mode::handle_wl_output_mode(conn, output_id, event),
+        Event::Done => {
+            if let Some(state) = conn.object_data::<OutputState>(output_id) {
+                state.done(conn, output_id);
+            }
+            Ok(())
+        }
+        Event::Scale(event) => scale::handle_wl_output_scale(conn, output_id, event),
+        Event::Name(event) => name::handle_wl_output_name(conn, output_id, event),
+        Event::Description(event) => {
+            description::handle_wl_output_description(conn, output_id, event)
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        output::{OutputMode, OutputState},
+        types::{WL_TYPE_INT_LEN, WL_TYPE_UINT_LEN, WlInt, WlUInt},
+    },
+    wl_bitfield,
+};
+
+wl_bitfield! {
+    /// The flags of a `wl_output.mode` event.
+    ModeFlags {
+        /// The mode is the current one.
+        CURRENT = 0x1,
+        /// The mode is the preferred one.
+        PREFERRED = 0x2,
+    }
+}
+
+/// Represents a `wl_output.mode` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="mode">
+///   <description summary="advertise available modes for the output">
+///     The mode event describes an available mode for the output.
+///
+///     The event is sent when binding to the output object and there
+///     will always be one mode, the current mode.  The event is sent
+///     again if an output changes mode, for the mode that is now
+///     current.  In other words, the current mode is always the last
+///     mode that was received with the current flag set.
+///
+///     Non-current modes are deprecated. A compositor can decide to only
+///     advertise the current mode and never send other modes. Clients
+///     should not rely on non-current modes.
+///
+///     The size of a mode is given in physical hardware units of
+///     the output device. This is not necessarily the same as
+///     the output size in the global compositor space. For instance,
+///     the output may be scaled, as described in wl_output.scale,
+///     or transformed, as described in wl_output.transform. Clients
+///     willing to retrieve the output size in the global compositor
+///     space should use xdg_output.logical_size instead.
+///
+///     The vertical refresh rate can be set to zero if it doesn't make
+///     sense for this output (e.g. for virtual outputs).
+///
+///     The mode event will be followed by a done event (starting from
+///     version 2).
+///   </description>
+///   <arg name="flags" type="uint" enum="mode" summary="bitfield of mode flags"/>
+///   <arg name="width" type="int" summary="width of the mode in hardware units"/>
+///   <arg name="height" type="int" summary="height of the mode in hardware units"/>
+///   <arg name="refresh" type="int" summary="vertical refresh rate in mHz"/>
+/// </event>
+/// ```
+#[derive(Debug)]
+pub struct Mode {
+    /// Whether the mode is the current and/or preferred one, see [`ModeFlags`].
+    pub flags: WlUInt,
+    /// The width in hardware pixels.
+    pub width: WlInt,
+    /// The height in hardware pixels.
+    pub height: WlInt,
+    /// The vertical refresh rate in mHz.
+    pub refresh: WlInt,
+}
+
+impl TryFrom<&[u8]> for Mode {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Mode` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `flags` (u32) - The mode flags
+    /// - Bytes 4-7: `width` (i32) - The width in hardware pixels
+    /// - Bytes 8-11: `height` (i32) - The height in hardware pixels
+    /// - Bytes 12-15: `refresh` (i32) - The refresh rate in mHz
+    fn try_from(buf: &[u8]) -> Result<Mode> {
+        Ok(Mode {
+            flags: WlUInt::read(buf, 0)?,
+            width: WlInt::read(buf, WL_TYPE_UINT_LEN)?,
+            height: WlInt::read(buf, WL_TYPE_UINT_LEN + WL_TYPE_INT_LEN)?,
+            refresh: WlInt::read(buf, WL_TYPE_UINT_LEN + 2 * WL_TYPE_INT_LEN)?,
+        })
+    }
+}
+
+/// Handles a `wl_output.mode` event by recording the current mode as
+/// pending. Modes other than the current one are deprecated and ignored.
+pub(super) fn handle_wl_output_mode(conn: &Connection, output_id: u32, mode: Mode) -> Result<()> {
+    if !ModeFlags::from_bits(mode.flags.get()).contains(ModeFlags::CURRENT) {
+        return Ok(());
+    }
+
+    if let Some(state) = conn.object_data::<OutputState>(output_id) {
+        state.update(conn, output_id, |info| {
+            info.mode = Some(OutputMode {
+                width: mode.width.get(),
+                height: mode.height.get(),
+                refresh_mhz: mode.refresh.get(),
+            });
+        });
+    }
+
+    Ok(())
+}
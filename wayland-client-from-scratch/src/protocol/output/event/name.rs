@@ -0,0 +1,70 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{output::OutputState, types::WlString},
+};
+
+/// Represents a `wl_output.name` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="name" since="4">
+///   <description summary="name of this output">
+///     Many compositors will assign user-friendly names to their outputs, show
+///     them to the user, allow the user to refer to an output, etc. The client
+///     may wish to know this name as well to offer the user similar behaviors.
+///
+///     The name is a UTF-8 string with no convention defined for its contents.
+///     Each name is unique among all wl_output globals. The name is only
+///     guaranteed to be unique for the compositor instance.
+///
+///     The same output name is used for all clients for a given wl_output
+///     global. Thus, the name can be shared across processes to refer to a
+///     specific wl_output global.
+///
+///     The name is not guaranteed to be persistent across sessions, thus cannot
+///     be used to reliably identify an output in e.g. configuration files.
+///
+///     Examples of names include 'HDMI-A-1', 'WL-1', 'X11-1', etc. However, do
+///     not assume that the name is a reflection of an underlying DRM connector,
+///     X11 connection, etc.
+///
+///     The name event is sent after binding the output object. This event is
+///     only sent once per output object, and the name does not change over the
+///     lifetime of the wl_output global.
+///   </description>
+///   <arg name="name" type="string" summary="output name"/>
+/// </event>
+/// ```
+#[derive(Debug)]
+pub struct Name {
+    /// The output name.
+    pub name: WlString,
+}
+
+impl TryFrom<&[u8]> for Name {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Name` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0+: `name` (WlString) - The output name with length prefix
+    fn try_from(buf: &[u8]) -> Result<Name> {
+        Ok(Name {
+            name: buf.try_into()?,
+        })
+    }
+}
+
+/// Handles a `wl_output.name` event by recording the name as pending.
+pub(super) fn handle_wl_output_name(conn: &Connection, output_id: u32, event: Name) -> Result<()> {
+    let name = conn.decode_string(&event.name)?;
+
+    if let Some(state) = conn.object_data::<OutputState>(output_id) {
+        state.update(conn, output_id, |info| {
+            info.name = Some(name);
+        });
+    }
+
+    Ok(())
+}
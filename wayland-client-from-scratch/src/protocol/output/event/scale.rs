@@ -0,0 +1,68 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{output::OutputState, types::WlInt},
+};
+
+/// Represents a `wl_output.scale` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="scale" since="2">
+///   <description summary="output scaling properties">
+///     This event contains scaling geometry information
+///     that is not in the geometry event. It may be sent after
+///     binding the output object or if the output scale changes
+///     later. The compositor will emit a non-zero, positive
+///     value for scale. If it is not sent, the client should
+///     assume a scale of 1.
+///
+///     A scale larger than 1 means that the compositor will
+///     automatically scale surface buffers by this amount
+///     when rendering. This is used for very high resolution
+///     displays where applications rendering at the native
+///     resolution would be too small to be legible.
+///
+///     Clients should use wl_surface.preferred_buffer_scale
+///     instead of this event to find the preferred buffer
+///     scale for use in wl_surface.set_buffer_scale.
+///
+///     The scale event will be followed by a done event.
+///   </description>
+///   <arg name="factor" type="int" summary="scaling factor of output"/>
+/// </event>
+/// ```
+#[derive(Debug)]
+pub struct Scale {
+    /// The integer scale factor.
+    pub factor: WlInt,
+}
+
+impl TryFrom<&[u8]> for Scale {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Scale` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `factor` (i32) - The scale factor
+    fn try_from(buf: &[u8]) -> Result<Scale> {
+        Ok(Scale {
+            factor: WlInt::read(buf, 0)?,
+        })
+    }
+}
+
+/// Handles a `wl_output.scale` event by recording the scale as pending.
+pub(super) fn handle_wl_output_scale(
+    conn: &Connection,
+    output_id: u32,
+    scale: Scale,
+) -> Result<()> {
+    if let Some(state) = conn.object_data::<OutputState>(output_id) {
+        state.update(conn, output_id, |info| {
+            info.scale = scale.factor.get().max(1)
+        });
+    }
+
+    Ok(())
+}
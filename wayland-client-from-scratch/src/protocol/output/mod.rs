@@ -0,0 +1,257 @@
+pub mod event;
+pub mod request;
+
+use std::sync::Arc;
+
+use crate::{
+    connection::{Connection, Listener},
+    error::Result,
+    protocol::{WlObjectId, xdg_output},
+    sync::{Lock, LockCell},
+};
+
+pub use event::{
+    geometry::{Subpixel, Transform},
+    mode::ModeFlags,
+};
+
+/// The highest `wl_output` version this crate implements.
+pub const WL_OUTPUT_VERSION: u32 = 4;
+
+/// An event delivered to the handler of an [`Output`].
+pub enum OutputEvent {
+    /// A batch of property changes was applied.
+    Done(OutputInfo),
+}
+
+/// The current video mode of an output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutputMode {
+    /// The width in hardware pixels.
+    pub width: i32,
+    /// The height in hardware pixels.
+    pub height: i32,
+    /// The vertical refresh rate in mHz, 0 if not meaningful.
+    pub refresh_mhz: i32,
+}
+
+/// The properties of an output, as announced by the compositor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputInfo {
+    /// The name of the output, such as `DP-1` (since version 4).
+    pub name: Option<String>,
+    /// A human-readable description (since version 4).
+    pub description: Option<String>,
+    /// The manufacturer.
+    pub make: String,
+    /// The model.
+    pub model: String,
+    /// The horizontal position in the global compositor space.
+    pub x: i32,
+    /// The vertical position in the global compositor space.
+    pub y: i32,
+    /// The physical width in millimeters, 0 if not meaningful.
+    pub physical_width: i32,
+    /// The physical height in millimeters, 0 if not meaningful.
+    pub physical_height: i32,
+    /// The subpixel orientation.
+    pub subpixel: Subpixel,
+    /// The transform applied to buffers shown on the output.
+    pub transform: Transform,
+    /// The current mode, once announced.
+    pub mode: Option<OutputMode>,
+    /// The integer scale factor.
+    pub scale: i32,
+}
+
+impl Default for OutputInfo {
+    fn default() -> OutputInfo {
+        OutputInfo {
+            name: None,
+            description: None,
+            make: String::new(),
+            model: String::new(),
+            x: 0,
+            y: 0,
+            physical_width: 0,
+            physical_height: 0,
+            subpixel: Subpixel::Unknown,
+            transform: Transform::Normal,
+            mode: None,
+            scale: 1,
+        }
+    }
+}
+
+/// State of an output updated by its events.
+pub(crate) struct OutputState {
+    /// The numeric name of the `wl_output` global.
+    global_name: u32,
+    /// The interface version the output was bound with.
+    version: u32,
+    /// The properties changed since the last `done` event.
+    pending: Lock<OutputInfo>,
+    /// The properties applied by the last `done` event.
+    current: Lock<OutputInfo>,
+    /// Whether a `done` event applied the properties at least once.
+    announced: LockCell<bool>,
+}
+
+impl OutputState {
+    /// Changes the pending properties.
+    ///
+    /// Version 1 outputs have no `done` event, so changes apply immediately.
+    pub(crate) fn update(
+        &self,
+        conn: &Connection,
+        output_id: u32,
+        f: impl FnOnce(&mut OutputInfo),
+    ) {
+        f(&mut self.pending.borrow_mut());
+
+        if self.version < 2 {
+            self.done(conn, output_id);
+        }
+    }
+
+    /// Applies the pending properties and notifies the listener.
+    pub(crate) fn done(&self, conn: &Connection, output_id: u32) {
+        let info = self.pending.borrow().clone();
+        *self.current.borrow_mut() = info.clone();
+        self.announced.set(true);
+
+        // The xdg outputs are up to date when the listener sees the output
+        xdg_output::output_done(conn, output_id);
+        conn.emit(output_id, OutputEvent::Done(info));
+    }
+
+    /// Returns the numeric name of the `wl_output` global.
+    pub(crate) fn global_name(&self) -> u32 {
+        self.global_name
+    }
+
+    /// Returns `true` once a `done` event applied the properties.
+    pub(crate) fn is_announced(&self) -> bool {
+        self.announced.get()
+    }
+
+    /// Returns the properties applied by the last `done` event.
+    pub(crate) fn info(&self) -> OutputInfo {
+        self.current.borrow().clone()
+    }
+
+    /// Returns the current scale factor.
+    pub(crate) fn scale(&self) -> i32 {
+        self.current.borrow().scale
+    }
+}
+
+/// A bound `wl_output` global, a monitor or other display area.
+pub struct Output {
+    /// The object ID of the bound output.
+    id: u32,
+    /// The numeric name of the global.
+    global_name: u32,
+    /// The negotiated interface version.
+    version: u32,
+    /// The connection the output was bound on.
+    conn: Connection,
+    /// The state shared with the event handlers.
+    state: Arc<OutputState>,
+}
+
+impl Output {
+    /// Binds the `wl_output` global with the numeric name `global_name`.
+    ///
+    /// The properties are known after the next roundtrip.
+    pub fn bind(conn: &Connection, global_name: u32) -> Result<Output> {
+        let (id, version) = conn.bind_global(WlObjectId::Output, global_name, WL_OUTPUT_VERSION)?;
+
+        let state = Arc::new(OutputState {
+            global_name,
+            version,
+            pending: Lock::new(OutputInfo::default()),
+            current: Lock::new(OutputInfo::default()),
+            announced: LockCell::new(false),
+        });
+        conn.set_object_data(id, state.clone());
+
+        Ok(Output {
+            id,
+            global_name,
+            version,
+            conn: conn.clone(),
+            state,
+        })
+    }
+
+    /// Binds every `wl_output` global advertised by the compositor.
+    pub fn bind_all(conn: &Connection) -> Result<Vec<Output>> {
+        conn.globals()
+            .into_iter()
+            .filter(|global| global.interface.as_str() == WlObjectId::Output.interface_name())
+            .map(|global| Output::bind(conn, global.name.get()))
+            .collect()
+    }
+
+    /// Returns the object ID of the output.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the numeric name of the global the output was bound from.
+    pub fn global_name(&self) -> u32 {
+        self.global_name
+    }
+
+    /// Returns the negotiated interface version.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Returns the connection the output was bound on.
+    pub(crate) fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    /// Returns the properties of the output.
+    pub fn info(&self) -> OutputInfo {
+        self.state.info()
+    }
+
+    /// Returns the integer scale factor of the output.
+    pub fn scale(&self) -> i32 {
+        self.state.scale()
+    }
+
+    /// Registers the handler receiving the events of the output, replacing
+    /// any previous one.
+    pub fn set_event_handler<F>(&self, handler: F)
+    where
+        F: FnMut(&Connection, OutputEvent) + Send + 'static,
+    {
+        let listener: Listener<OutputEvent> = Box::new(handler);
+        self.conn.set_listener(self.id, listener);
+    }
+
+    /// Releases the output object. Before version 3 the object cannot be
+    /// destroyed, so it only stops delivering events to the handler.
+    ///
+    /// Dropping the handle does the same.
+    pub fn release(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl Drop for Output {
+    fn drop(&mut self) {
+        self.conn.remove_listener(self.id);
+
+        if self.version >= 3 {
+            self.conn
+                .destroy_on_drop(|conn| request::release(conn, self.id));
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use crate::{connection::Connection, error::Result, wl_request_opcode};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to a `wl_output` object.
+    Opcode {
+        /// Releases the output object (since version 3).
+        Release = 0 (destructor),
+    }
+}
+
+/// Sends a `wl_output.release` request to the compositor.
+pub fn release(conn: &Connection, output: u32) -> Result<()> {
+    conn.send_request(output, Opcode::Release, &())?;
+
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        pointer::update_frame,
+        types::{WL_TYPE_ENUM_LEN, WL_TYPE_UINT_LEN, WlEnum, WlFixed, WlUInt},
+    },
+    wl_enum,
+};
+
+wl_enum! {
+    /// Scroll axes of a pointer.
+    Axis {
+        /// Vertical scrolling, positive downwards.
+        VerticalScroll = 0,
+        /// Horizontal scrolling, positive to the right.
+        HorizontalScroll = 1,
+    }
+}
+
+/// Represents a `wl_pointer.axis` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="axis">
+///   <description summary="axis event">
+///     Scroll and other axis notifications.
+///
+///     For scroll events (vertical and horizontal scroll axes), the
+///     value parameter is the length of a vector along the specified
+///     axis in a coordinate space identical to those of motion events,
+///     representing a relative movement along the specified axis.
+///
+///     For devices that support movements non-parallel to axes multiple
+///     axis events will be emitted.
+///   </description>
+///   <arg name="time" type="uint" summary="timestamp with millisecond granularity"/>
+///   <arg name="axis" type="uint" enum="axis" summary="axis type"/>
+///   <arg name="value" type="fixed" summary="length of vector in surface-local coordinate space"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AxisEvent {
+    /// The timestamp with millisecond granularity.
+    pub time: WlUInt,
+    /// The scrolled axis.
+    pub axis: Axis,
+    /// The scroll distance in surface-local coordinates.
+    pub value: WlFixed,
+}
+
+impl TryFrom<&[u8]> for AxisEvent {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `AxisEvent`.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `time` (u32) - The timestamp
+    /// - Bytes 4-7: `axis` (u32) - The scrolled axis
+    /// - Bytes 8-11: `value` (fixed) - The scroll distance
+    fn try_from(buf: &[u8]) -> Result<AxisEvent> {
+        Ok(AxisEvent {
+            time: WlUInt::read(buf, 0)?,
+            axis: WlEnum::read(buf, WL_TYPE_UINT_LEN)?.get().try_into()?,
+            value: WlFixed::read(buf, WL_TYPE_UINT_LEN + WL_TYPE_ENUM_LEN)?,
+        })
+    }
+}
+
+/// Handles a `wl_pointer.axis` event by adding the distance to the pending frame's scroll.
+pub(super) fn handle_wl_pointer_axis(
+    conn: &Connection,
+    pointer_id: u32,
+    event: AxisEvent,
+) -> Result<()> {
+    update_frame(conn, pointer_id, |_, frame| {
+        frame.scroll.time = Some(event.time.get());
+        frame.scroll.axis_mut(event.axis).value += event.value.to_f64();
+    });
+
+    Ok(())
+}
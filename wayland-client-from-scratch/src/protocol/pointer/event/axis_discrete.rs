@@ -0,0 +1,69 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        pointer::{event::axis::Axis, update_frame},
+        types::{WL_TYPE_ENUM_LEN, WlEnum, WlInt},
+    },
+    scroll::WHEEL_DETENT_VALUE120,
+};
+
+/// Represents a `wl_pointer.axis_discrete` event.
+///
+/// Only sent up to version 7; later versions use `axis_value120` instead.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="axis_discrete" since="5" deprecated-since="8">
+///   <description summary="axis click event">
+///     Discrete step information for scroll and other axes.
+///
+///     This event carries the axis value of the wl_pointer.axis event in
+///     discrete steps (e.g. mouse wheel clicks).
+///
+///     The discrete value carries the directional information. e.g. a value
+///     of -2 is two steps towards the negative direction of this axis.
+///   </description>
+///   <arg name="axis" type="uint" enum="axis" summary="axis type"/>
+///   <arg name="discrete" type="int" summary="number of steps"/>
+/// </event>
+/// ```
+#[derive(Debug)]
+pub struct AxisDiscrete {
+    /// The scrolled axis.
+    pub axis: Axis,
+    /// The number of wheel detents.
+    pub discrete: WlInt,
+}
+
+impl TryFrom<&[u8]> for AxisDiscrete {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `AxisDiscrete` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `axis` (u32) - The scrolled axis
+    /// - Bytes 4-7: `discrete` (i32) - The number of steps
+    fn try_from(buf: &[u8]) -> Result<AxisDiscrete> {
+        Ok(AxisDiscrete {
+            axis: WlEnum::read(buf, 0)?.get().try_into()?,
+            discrete: WlInt::read(buf, WL_TYPE_ENUM_LEN)?,
+        })
+    }
+}
+
+/// Handles a `wl_pointer.axis_discrete` event.
+///
+/// The steps are converted to the `axis_value120` scale, so applications see
+/// the same values regardless of the negotiated version.
+pub(super) fn handle_wl_pointer_axis_discrete(
+    conn: &Connection,
+    pointer_id: u32,
+    event: AxisDiscrete,
+) -> Result<()> {
+    update_frame(conn, pointer_id, |_, frame| {
+        frame.scroll.axis_mut(event.axis).value120 += event.discrete.get() * WHEEL_DETENT_VALUE120;
+    });
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        pointer::{event::axis::Axis, update_frame},
+        types::{WL_TYPE_ENUM_LEN, WlEnum},
+    },
+    wl_enum,
+};
+
+wl_enum! {
+    /// Direction of the physical motion relative to the scroll direction.
+    AxisRelativeDirection {
+        /// The physical motion matches the axis direction.
+        Identical = 0,
+        /// The physical motion is the inverse of the axis direction, as with
+        /// "natural scrolling".
+        Inverted = 1,
+    }
+}
+
+/// Represents a `wl_pointer.axis_relative_direction` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="axis_relative_direction" since="9">
+///   <description summary="axis relative physical direction event">
+///     Relative directional information of the entity causing the axis
+///     motion.
+///
+///     For a wl_pointer.axis event, the wl_pointer.axis_relative_direction
+///     event specifies the movement direction of the entity causing the
+///     wl_pointer.axis event. For example:
+///     - if a user's fingers on a touchpad move down and this
+///       causes a wl_pointer.axis vertical_scroll down event, the physical
+///       direction is 'identical'
+///     - if a user's fingers on a touchpad move down and this causes a
+///       wl_pointer.axis vertical_scroll up scroll up event ('natural
+///       scrolling'), the physical direction is 'inverted'.
+///   </description>
+///   <arg name="axis" type="uint" enum="axis" summary="axis type"/>
+///   <arg name="direction" type="uint" enum="axis_relative_direction" summary="physical direction relative to axis motion"/>
+/// </event>
+/// ```
+#[derive(Debug)]
+pub struct AxisRelativeDirectionEvent {
+    /// The scrolled axis.
+    pub axis: Axis,
+    /// The physical direction relative to the axis motion.
+    pub direction: AxisRelativeDirection,
+}
+
+impl TryFrom<&[u8]> for AxisRelativeDirectionEvent {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `AxisRelativeDirectionEvent`.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `axis` (u32) - The scrolled axis
+    /// - Bytes 4-7: `direction` (u32) - The relative direction
+    fn try_from(buf: &[u8]) -> Result<AxisRelativeDirectionEvent> {
+        Ok(AxisRelativeDirectionEvent {
+            axis: WlEnum::read(buf, 0)?.get().try_into()?,
+            direction: WlEnum::read(buf, WL_TYPE_ENUM_LEN)?.get().try_into()?,
+        })
+    }
+}
+
+/// Handles a `wl_pointer.axis_relative_direction` event by recording the direction.
+pub(super) fn handle_wl_pointer_axis_relative_direction(
+    conn: &Connection,
+    pointer_id: u32,
+    event: AxisRelativeDirectionEvent,
+) -> Result<()> {
+    update_frame(conn, pointer_id, |_, frame| {
+        frame.scroll.set_direction(event.axis, event.direction);
+    });
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{pointer::update_frame, types::WlEnum},
+    wl_enum,
+};
+
+wl_enum! {
+    /// The kind of device generating scroll events.
+    AxisSource {
+        /// A physical wheel rotation.
+        Wheel = 0,
+        /// Finger on a touch surface, such as a touchpad.
+        Finger = 1,
+        /// Continuous coordinate space, such as button-based scrolling.
+        Continuous = 2,
+        /// A physical wheel tilt (since version 6).
+        WheelTilt = 3,
+    }
+}
+
+/// Represents a `wl_pointer.axis_source` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="axis_source" since="5">
+///   <description summary="axis source event">
+///     Source information for scroll and other axes.
+///
+///     This event does not occur on its own. It is sent before a
+///     wl_pointer.frame event and carries the source information for
+///     all events within that frame.
+///
+///     The source specifies how this event was generated. If the source is
+///     wl_pointer.axis_source.finger, a wl_pointer.axis_stop event will be
+///     sent when the user lifts the finger off the device.
+///   </description>
+///   <arg name="axis_source" type="uint" enum="axis_source" summary="source of the axis event"/>
+/// </event>
+/// ```
+#[derive(Debug)]
+pub struct AxisSourceEvent {
+    /// The device generating the scroll events of the frame.
+    pub axis_source: AxisSource,
+}
+
+impl TryFrom<&[u8]> for AxisSourceEvent {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `AxisSourceEvent`.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `axis_source` (u32) - The source of the axis events
+    fn try_from(buf: &[u8]) -> Result<AxisSourceEvent> {
+        Ok(AxisSourceEvent {
+            axis_source: WlEnum::read(buf, 0)?.get().try_into()?,
+        })
+    }
+}
+
+/// Handles a `wl_pointer.axis_source` event by recording the source of the frame's scroll.
+pub(super) fn handle_wl_pointer_axis_source(
+    conn: &Connection,
+    pointer_id: u32,
+    event: AxisSourceEvent,
+) -> Result<()> {
+    update_frame(conn, pointer_id, |_, frame| {
+        frame.scroll.source = Some(event.axis_source);
+    });
+
+    Ok(())
+}
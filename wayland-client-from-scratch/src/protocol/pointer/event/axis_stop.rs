@@ -0,0 +1,62 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        pointer::{event::axis::Axis, update_frame},
+        types::{WL_TYPE_UINT_LEN, WlEnum, WlUInt},
+    },
+};
+
+/// Represents a `wl_pointer.axis_stop` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="axis_stop" since="5">
+///   <description summary="axis stop event">
+///     Stop notification for scroll and other axes.
+///
+///     For some wl_pointer.axis_source types, a wl_pointer.axis_stop event
+///     is sent to notify a client that the axis sequence has terminated.
+///     This enables the client to implement kinetic scrolling.
+///   </description>
+///   <arg name="time" type="uint" summary="timestamp with millisecond granularity"/>
+///   <arg name="axis" type="uint" enum="axis" summary="the axis stopped with this event"/>
+/// </event>
+/// ```
+#[derive(Debug)]
+pub struct AxisStop {
+    /// The timestamp with millisecond granularity.
+    pub time: WlUInt,
+    /// The axis whose scroll sequence stopped.
+    pub axis: Axis,
+}
+
+impl TryFrom<&[u8]> for AxisStop {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `AxisStop` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `time` (u32) - The timestamp
+    /// - Bytes 4-7: `axis` (u32) - The stopped axis
+    fn try_from(buf: &[u8]) -> Result<AxisStop> {
+        Ok(AxisStop {
+            time: WlUInt::read(buf, 0)?,
+            axis: WlEnum::read(buf, WL_TYPE_UINT_LEN)?.get().try_into()?,
+        })
+    }
+}
+
+/// Handles a `wl_pointer.axis_stop` event by flagging the axis in the frame's scroll.
+pub(super) fn handle_wl_pointer_axis_stop(
+    conn: &Connection,
+    pointer_id: u32,
+    event: AxisStop,
+) -> Result<()> {
+    update_frame(conn, pointer_id, |_, frame| {
+        frame.scroll.time = Some(event.time.get());
+        frame.scroll.axis_mut(event.axis).stop = true;
+    });
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        pointer::{event::axis::Axis, update_frame},
+        types::{WL_TYPE_ENUM_LEN, WlEnum, WlInt},
+    },
+};
+
+/// Represents a `wl_pointer.axis_value120` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="axis_value120" since="8">
+///   <description summary="axis high-resolution scroll event">
+///     Discrete high-resolution scroll information.
+///
+///     This event carries high-resolution wheel scroll information,
+///     with each multiple of 120 representing one logical scroll step
+///     (a wheel detent). For example, an axis_value120 of 30 is one quarter of
+///     a logical scroll step in the positive direction, a value120 of
+///     -240 are two logical scroll steps in the negative direction within the
+///     same hardware event.
+///     Clients that rely on discrete scrolling should accumulate the
+///     value120 to multiples of 120 before processing the event.
+///
+///     The value120 must not be zero.
+///   </description>
+///   <arg name="axis" type="uint" enum="axis" summary="axis type"/>
+///   <arg name="value120" type="int" summary="scroll distance as fraction of 120"/>
+/// </event>
+/// ```
+#[derive(Debug)]
+pub struct AxisValue120 {
+    /// The scrolled axis.
+    pub axis: Axis,
+    /// The scroll distance, 120 per wheel detent.
+    pub value120: WlInt,
+}
+
+impl TryFrom<&[u8]> for AxisValue120 {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `AxisValue120` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `axis` (u32) - The scrolled axis
+    /// - Bytes 4-7: `value120` (i32) - The scroll distance in 1/120 detents
+    fn try_from(buf: &[u8]) -> Result<AxisValue120> {
+        Ok(AxisValue120 {
+            axis: WlEnum::read(buf, 0)?.get().try_into()?,
+            value120: WlInt::read(buf, WL_TYPE_ENUM_LEN)?,
+        })
+    }
+}
+
+/// Handles a `wl_pointer.axis_value120` event by adding to the frame's wheel motion.
+pub(super) fn handle_wl_pointer_axis_value120(
+    conn: &Connection,
+    pointer_id: u32,
+    event: AxisValue120,
+) -> Result<()> {
+    update_frame(conn, pointer_id, |_, frame| {
+        frame.scroll.axis_mut(event.axis).value120 += event.value120.get();
+    });
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        pointer::update_frame,
+        types::{WL_TYPE_UINT_LEN, WlEnum, WlUInt},
+    },
+    wl_enum,
+};
+
+wl_enum! {
+    /// Physical state of a pointer button.
+    ButtonState {
+        /// The button is not pressed.
+        Released = 0,
+        /// The button is pressed.
+        Pressed = 1,
+    }
+}
+
+/// Represents a `wl_pointer.button` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="button">
+///   <description summary="pointer button event">
+///     Mouse button click and release notifications.
+///
+///     The location of the click is given by the last motion or
+///     enter event.
+///     The time argument is a timestamp with millisecond
+///     granularity, with an undefined base.
+///
+///     The button is a button code as defined in the Linux kernel's
+///     linux/input-event-codes.h header file, e.g. BTN_LEFT.
+///   </description>
+///   <arg name="serial" type="uint" summary="serial number of the button event"/>
+///   <arg name="time" type="uint" summary="timestamp with millisecond granularity"/>
+///   <arg name="button" type="uint" summary="button that produced the event"/>
+///   <arg name="state" type="uint" enum="button_state" summary="physical state of the button"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Button {
+    /// The serial number of the button event, needed for grabs and moves.
+    pub serial: WlUInt,
+    /// The timestamp with millisecond granularity.
+    pub time: WlUInt,
+    /// The Linux evdev button code, such as `BTN_LEFT` (0x110).
+    pub button: WlUInt,
+    /// Whether the button was pressed or released.
+    pub state: ButtonState,
+}
+
+impl TryFrom<&[u8]> for Button {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Button` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `serial` (u32) - The serial number of the event
+    /// - Bytes 4-7: `time` (u32) - The timestamp
+    /// - Bytes 8-11: `button` (u32) - The button code
+    /// - Bytes 12-15: `state` (u32) - The button state
+    fn try_from(buf: &[u8]) -> Result<Button> {
+        Ok(Button {
+            serial: WlUInt::read(buf, 0)?,
+            time: WlUInt::read(buf, WL_TYPE_UINT_LEN)?,
+            button: WlUInt::read(buf, 2 * WL_TYPE_UINT_LEN)?,
+            state: WlEnum::read(buf, 3 * WL_TYPE_UINT_LEN)?.get().try_into()?,
+        })
+    }
+}
+
+/// Handles a `wl_pointer.button` event by adding it to the pending frame.
+pub(super) fn handle_wl_pointer_button(
+    conn: &Connection,
+    pointer_id: u32,
+    button: Button,
+) -> Result<()> {
+    update_frame(conn, pointer_id, |state, frame| {
+        match button.state {
+            ButtonState::Pressed => state.seat.set_grab_serial(button.serial.get()),
+            ButtonState::Released => state.seat.set_serial(button.serial.get()),
+        }
+        frame.buttons.push(button);
+    });
+
+    Ok(())
+}
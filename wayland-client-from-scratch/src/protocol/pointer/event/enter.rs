@@ -0,0 +1,82 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        pointer::update_frame,
+        types::{
+            WL_TYPE_FIXED_LEN, WL_TYPE_OBJECT_LEN, WL_TYPE_UINT_LEN, WlFixed, WlObject, WlUInt,
+        },
+    },
+};
+
+/// Represents a `wl_pointer.enter` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="enter">
+///   <description summary="enter event">
+///     Notification that this seat's pointer is focused on a certain
+///     surface.
+///
+///     When a seat's focus enters a surface, the pointer image
+///     is undefined and a client should respond to this event by setting
+///     an appropriate pointer image with the set_cursor request.
+///   </description>
+///   <arg name="serial" type="uint" summary="serial number of the enter event"/>
+///   <arg name="surface" type="object" interface="wl_surface" summary="surface entered by the pointer"/>
+///   <arg name="surface_x" type="fixed" summary="surface-local x coordinate"/>
+///   <arg name="surface_y" type="fixed" summary="surface-local y coordinate"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Enter {
+    /// The serial number of the enter event, needed for `set_cursor`.
+    pub serial: WlUInt,
+    /// The surface entered by the pointer.
+    pub surface: WlObject,
+    /// The surface-local x coordinate.
+    pub surface_x: WlFixed,
+    /// The surface-local y coordinate.
+    pub surface_y: WlFixed,
+}
+
+impl TryFrom<&[u8]> for Enter {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Enter` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `serial` (u32) - The serial number of the event
+    /// - Bytes 4-7: `surface` (u32) - The surface entered
+    /// - Bytes 8-11: `surface_x` (fixed) - The x coordinate
+    /// - Bytes 12-15: `surface_y` (fixed) - The y coordinate
+    fn try_from(buf: &[u8]) -> Result<Enter> {
+        let surface_x_pos = WL_TYPE_UINT_LEN + WL_TYPE_OBJECT_LEN;
+
+        Ok(Enter {
+            serial: WlUInt::read(buf, 0)?,
+            surface: WlObject::read(buf, WL_TYPE_UINT_LEN)?,
+            surface_x: WlFixed::read(buf, surface_x_pos)?,
+            surface_y: WlFixed::read(buf, surface_x_pos + WL_TYPE_FIXED_LEN)?,
+        })
+    }
+}
+
+/// Handles a `wl_pointer.enter` event by recording the new focus in the pending frame.
+pub(super) fn handle_wl_pointer_enter(
+    conn: &Connection,
+    pointer_id: u32,
+    enter: Enter,
+) -> Result<()> {
+    update_frame(conn, pointer_id, |state, frame| {
+        state.seat.set_serial(enter.serial.get());
+        state.enter_serial.set(Some(enter.serial.get()));
+        state.focus.set(Some(enter.surface.get()));
+        state
+            .position
+            .set((enter.surface_x.to_f64(), enter.surface_y.to_f64()));
+        frame.enter = Some(enter);
+    });
+
+    Ok(())
+}
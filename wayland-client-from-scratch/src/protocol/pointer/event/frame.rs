@@ -0,0 +1,35 @@
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::pointer::{PointerState, emit_frame},
+};
+
+/// Handles a `wl_pointer.frame` event.
+///
+/// The frame ends a group of events that belong together, so the pending
+/// frame accumulated from them is delivered now.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="frame" since="5">
+///   <description summary="end of a pointer event sequence">
+///     Indicates the end of a set of events that logically belong together.
+///     A client is expected to accumulate the data in all events within the
+///     frame before proceeding.
+///
+///     All wl_pointer events before a wl_pointer.frame event belong
+///     logically together. For example, in a diagonal scroll motion the
+///     compositor will send an optional wl_pointer.axis_source event, two
+///     wl_pointer.axis events (horizontal and vertical) and finally a
+///     wl_pointer.frame event. The client may use this information to
+///     calculate a diagonal vector for scrolling.
+///   </description>
+/// </event>
+/// ```
+pub(super) fn handle_wl_pointer_frame(conn: &Connection, pointer_id: u32) -> Result<()> {
+    if let Some(state) = conn.object_data::<PointerState>(pointer_id) {
+        emit_frame(conn, pointer_id, &state);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        pointer::update_frame,
+        types::{WL_TYPE_UINT_LEN, WlObject, WlUInt},
+    },
+};
+
+/// Represents a `wl_pointer.leave` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="leave">
+///   <description summary="leave event">
+///     Notification that this seat's pointer is no longer focused on
+///     a certain surface.
+///
+///     The leave notification is sent before the enter notification
+///     for the new focus.
+///   </description>
+///   <arg name="serial" type="uint" summary="serial number of the leave event"/>
+///   <arg name="surface" type="object" interface="wl_surface" summary="surface left by the pointer"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Leave {
+    /// The serial number of the leave event.
+    pub serial: WlUInt,
+    /// The surface left by the pointer.
+    pub surface: WlObject,
+}
+
+impl TryFrom<&[u8]> for Leave {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Leave` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `serial` (u32) - The serial number of the event
+    /// - Bytes 4-7: `surface` (u32) - The surface left
+    fn try_from(buf: &[u8]) -> Result<Leave> {
+        Ok(Leave {
+            serial: WlUInt::read(buf, 0)?,
+            surface: WlObject::read(buf, WL_TYPE_UINT_LEN)?,
+        })
+    }
+}
+
+/// Handles a `wl_pointer.leave` event by clearing the focus in the pending frame.
+pub(super) fn handle_wl_pointer_leave(
+    conn: &Connection,
+    pointer_id: u32,
+    leave: Leave,
+) -> Result<()> {
+    update_frame(conn, pointer_id, |state, frame| {
+        state.focus.set(None);
+        frame.leave = Some(leave);
+    });
+
+    Ok(())
+}
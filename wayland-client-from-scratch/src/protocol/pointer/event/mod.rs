@@ -0,0 +1,273 @@
+pub mod axis;
+pub mod axis_discrete;
+pub mod axis_relative_direction;
+pub mod axis_source;
+pub mod axis_stop;
+pub mod axis_value120;
+pub mod button;
+pub mod enter;
+pub mod frame;
+pub mod leave;
+pub mod motion;
+
+use crate::{
+    connection::Connection,
+    error::{Error, Result, anyhow},
+    protocol::message::WlMessage,
+};
+
+/// Represents the event types that can be emitted by a Wayland pointer object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    /// The pointer entered a surface.
+    ///
+    /// # Event Arguments
+    /// - `serial`: The serial number of the event
+    /// - `surface`: The surface entered
+    /// - `surface_x`, `surface_y`: The surface-local position
+    Enter = 0,
+
+    /// The pointer left a surface.
+    ///
+    /// # Event Arguments
+    /// - `serial`: The serial number of the event
+    /// - `surface`: The surface left
+    Leave = 1,
+
+    /// The pointer moved within the focused surface.
+    ///
+    /// # Event Arguments
+    /// - `time`: The timestamp in milliseconds
+    /// - `surface_x`, `surface_y`: The surface-local position
+    Motion = 2,
+
+    /// A button was pressed or released.
+    ///
+    /// # Event Arguments
+    /// - `serial`: The serial number of the event
+    /// - `time`: The timestamp in milliseconds
+    /// - `button`: The button code
+    /// - `state`: The physical button state
+    Button = 3,
+
+    /// Continuous scroll motion along an axis.
+    ///
+    /// # Event Arguments
+    /// - `time`: The timestamp in milliseconds
+    /// - `axis`: The scrolled axis
+    /// - `value`: The distance in surface-local coordinates
+    Axis = 4,
+
+    /// Ends a group of events that belong together (since version 5).
+    Frame = 5,
+
+    /// The kind of device scrolling in this frame (since version 5).
+    ///
+    /// # Event Arguments
+    /// - `axis_source`: The source of the axis events
+    AxisSource = 6,
+
+    /// A scroll sequence stopped (since version 5).
+    ///
+    /// # Event Arguments
+    /// - `time`: The timestamp in milliseconds
+    /// - `axis`: The stopped axis
+    AxisStop = 7,
+
+    /// Wheel scroll in detents (versions 5 to 7).
+    ///
+    /// # Event Arguments
+    /// - `axis`: The scrolled axis
+    /// - `discrete`: The number of detents
+    AxisDiscrete = 8,
+
+    /// High-resolution wheel scroll (since version 8).
+    ///
+    /// # Event Arguments
+    /// - `axis`: The scrolled axis
+    /// - `value120`: The distance, 120 per detent
+    AxisValue120 = 9,
+
+    /// The physical direction of the scroll (since version 9).
+    ///
+    /// # Event Arguments
+    /// - `axis`: The scrolled axis
+    /// - `direction`: Whether the motion is inverted
+    AxisRelativeDirection = 10,
+}
+
+impl TryFrom<u16> for Opcode {
+    type Error = Error;
+
+    /// Attempts to convert a raw opcode value into a structured `WlPointerEvent`.
+    fn try_from(value: u16) -> Result<Opcode> {
+        match value {
+            0 => Ok(Opcode::Enter),
+            1 => Ok(Opcode::Leave),
+            2 => Ok(Opcode::Motion),
+            3 => Ok(Opcode::Button),
+            4 => Ok(Opcode::Axis),
+            5 => Ok(Opcode::Frame),
+            6 => Ok(Opcode::AxisSource),
+            7 => Ok(Opcode::AxisStop),
+            8 => Ok(Opcode::AxisDiscrete),
+            9 => Ok(Opcode::AxisValue120),
+            10 => Ok(Opcode::AxisRelativeDirection),
+            _ => Err(anyhow!("Invalid wl_pointer event opcode: {}", value)),
+        }
+    }
+}
+
+impl Opcode {
+    /// Returns the interface version the event was introduced in.
+    pub fn since(self) -> u32 {
+        match self {
+            Opcode::Enter | Opcode::Leave | Opcode::Motion | Opcode::Button | Opcode::Axis => 1,
+            Opcode::Frame | Opcode::AxisSource | Opcode::AxisStop | Opcode::AxisDiscrete => 5,
+            Opcode::AxisValue120 => 8,
+            Opcode::AxisRelativeDirection => 9,
+        }
+    }
+}
+
+/// A decoded `wl_pointer` event, with its arguments.
+#[derive(Debug)]
+pub enum Event {
+    /// The pointer entered a surface.
+    Enter(enter::Enter),
+    /// The pointer left a surface.
+    Leave(leave::Leave),
+    /// The pointer moved within the focused surface.
+    Motion(motion::Motion),
+    /// A button was pressed or released.
+    Button(button::Button),
+    /// Continuous scroll motion along an axis.
+    Axis(axis::AxisEvent),
+    /// Ends a group of events that belong together (since version 5).
+    Frame,
+    /// The kind of device scrolling in this frame (since version 5).
+    AxisSource(axis_source::AxisSourceEvent),
+    /// A scroll sequence stopped (since version 5).
+    AxisStop(axis_stop::AxisStop),
+    /// Wheel scroll in detents (versions 5 to 7).
+    AxisDiscrete(axis_discrete::AxisDiscrete),
+    /// High-resolution wheel scroll (since version 8).
+    AxisValue120(axis_value120::AxisValue120),
+    /// The physical direction of the scroll (since version 9).
+    AxisRelativeDirection(axis_relative_direction::AxisRelativeDirectionEvent),
+}
+
+impl Event {
+    /// Decodes a `wl_pointer` event from its message.
+    ///
+    /// # Errors
+    /// Returns an error if the opcode is unknown or the arguments are malformed.
+    pub fn decode(_conn: &Connection, msg: &WlMessage) -> Result<Event> {
+        let buf: &[u8] = &msg.data;
+
+        Ok(match Opcode::try_from(msg.header.opcode)? {
+            Opcode::Enter => Event::Enter(enter::Enter::try_from(buf)?),
+            Opcode::Leave => Event::Leave(leave::Leave::try_from(buf)?),
+            Opcode::Motion => Event::Motion(motion::Motion::try_from(buf)?),
+            Opcode::Button => Event::Button(button::Button::try_from(buf)?),
+            Opcode::Axis => Event::Axis(axis::AxisEvent::try_from(buf)?),
+            Opcode::Frame => Event::Frame,
+            Opcode::AxisSource => Event::AxisSource(axis_source::AxisSourceEvent::try_from(buf)?),
+            Opcode::AxisStop => Event::AxisStop(axis_stop::AxisStop::try_from(buf)?),
+            Opcode::AxisDiscrete => {
+                Event::AxisDiscrete(axis_discrete::AxisDiscrete::try_from(buf)?)
+            }
+            Opcode::AxisValue120 => {
+                Event::AxisValue120(axis_value120::AxisValue120::try_from(buf)?)
+            }
+            Opcode::AxisRelativeDirection => Event::AxisRelativeDirection(
+                axis_relative_direction::AxisRelativeDirectionEvent::try_from(buf)?,
+            ),
+        })
+    }
+
+    /// Returns the opcode of the event.
+    pub fn opcode(&self) -> Opcode {
+        match self {
+            Event::Enter(_) => Opcode::Enter,
+            Event::Leave(_) => Opcode::Leave,
+            Event::Motion(_) => Opcode::Motion,
+            Event::Button(_) => Opcode::Button,
+            Event::Axis(_) => Opcode::Axis,
+            Event::Frame => Opcode::Frame,
+            Event::AxisSource(_) => Opcode::AxisSource,
+            Event::AxisStop(_) => Opcode::AxisStop,
+            Event::AxisDiscrete(_) => Opcode::AxisDiscrete,
+            Event::AxisValue120(_) => Opcode::AxisValue120,
+            Event::AxisRelativeDirection(_) => Opcode::AxisRelativeDirection,
+        }
+    }
+}
+
+/// Dispatches incoming Wayland pointer events to their appropriate handler functions.
+///
+/// # Event Routing
+/// * `Enter`, `Leave`, `Motion`, `Button` and all axis events are
+///   accumulated into the pending `PointerFrame`
+/// * `Frame` events deliver the pending frame to the listener
+pub fn handle_wl_pointer_event(conn: &Connection, msg: WlMessage) -> Result<()> {
+    let pointer_id = msg.header.object_id;
+
+    // Route the event to the appropriate handler based on type
+    match Event::decode(conn, &msg)? {
+        Event::Enter(event) => enter::handle_wl_pointer_enter(conn, pointer_id, event),
+        Event::Leave(event) => leave::handle_wl_pointer_leave(conn, pointer_id, event),
+        Event::Motion(event) => motion::handle_wl_pointer_motion(conn, pointer_id, event),
+        Event::Button(event) => button::handle_wl_pointer_button(conn, pointer_id, event),
+        Event::Axis(event) => axis::handle_wl_pointer_axis(conn, pointer_id, event),
+        Event::Frame => frame::handle_wl_pointer_frame(conn, pointer_id),
+        Event::AxisSource(event) => {
+            axis_source::handle_wl_pointer_axis_source(conn, pointer_id, event)
+        }
+        Event::AxisStop(event) => axis_stop::handle_wl_pointer_axis_stop(conn, pointer_id, event),
+        Event::AxisDiscrete(event) => {
+            axis_discrete::handle_wl_pointer_axis_discrete(conn, pointer_id, event)
+        }
+        Event::AxisValue120(event) => {
+            axis_value120::handle_wl_pointer_axis_value120(conn, pointer_id, event)
+        }
+        Event::AxisRelativeDirection(event) => {
+            axis_relative_direction::handle_wl_pointer_axis_relative_direction(
+                conn, pointer_id, event,
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::os::unix::net::UnixStream;
+
+    use super::*;
+
+    #[test]
+    fn events_decode_into_typed_variants() {
+        let (client, _compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+
+        let mut data = 1234u32.to_ne_bytes().to_vec();
+        data.extend(256i32.to_ne_bytes());
+        data.extend((-512i32).to_ne_bytes());
+        let msg = WlMessage::new(5, Opcode::Motion as u16, &data).unwrap();
+
+        let event = Event::decode(&conn, &msg).unwrap();
+        assert_eq!(event.opcode(), Opcode::Motion);
+        let Event::Motion(motion) = event else {
+            panic!("expected a motion event, got {event:?}");
+        };
+        assert_eq!(motion.time.get(), 1234);
+        assert_eq!(motion.surface_x.to_f64(), 1.0);
+        assert_eq!(motion.surface_y.to_f64(), -2.0);
+
+        let msg = WlMessage::new(5, Opcode::Frame as u16, &[]).unwrap();
+        assert!(matches!(Event::decode(&conn, &msg), Ok(Event::Frame)));
+
+        let msg = WlMessage::new(5, Opcode::Motion as u16, &data[..4]).unwrap();
+        assert!(Event::decode(&conn, &msg).is_err());
+    }
+}
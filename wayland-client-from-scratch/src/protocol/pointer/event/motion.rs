@@ -0,0 +1,67 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        pointer::update_frame,
+        types::{WL_TYPE_FIXED_LEN, WL_TYPE_UINT_LEN, WlFixed, WlUInt},
+    },
+};
+
+/// Represents a `wl_pointer.motion` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="motion">
+///   <description summary="pointer motion event">
+///     Notification of pointer location change. The arguments
+///     surface_x and surface_y are the location relative to the
+///     focused surface.
+///   </description>
+///   <arg name="time" type="uint" summary="timestamp with millisecond granularity"/>
+///   <arg name="surface_x" type="fixed" summary="surface-local x coordinate"/>
+///   <arg name="surface_y" type="fixed" summary="surface-local y coordinate"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Motion {
+    /// The timestamp with millisecond granularity.
+    pub time: WlUInt,
+    /// The surface-local x coordinate.
+    pub surface_x: WlFixed,
+    /// The surface-local y coordinate.
+    pub surface_y: WlFixed,
+}
+
+impl TryFrom<&[u8]> for Motion {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Motion` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `time` (u32) - The timestamp
+    /// - Bytes 4-7: `surface_x` (fixed) - The x coordinate
+    /// - Bytes 8-11: `surface_y` (fixed) - The y coordinate
+    fn try_from(buf: &[u8]) -> Result<Motion> {
+        Ok(Motion {
+            time: WlUInt::read(buf, 0)?,
+            surface_x: WlFixed::read(buf, WL_TYPE_UINT_LEN)?,
+            surface_y: WlFixed::read(buf, WL_TYPE_UINT_LEN + WL_TYPE_FIXED_LEN)?,
+        })
+    }
+}
+
+/// Handles a `wl_pointer.motion` event by recording the position in the pending frame.
+pub(super) fn handle_wl_pointer_motion(
+    conn: &Connection,
+    pointer_id: u32,
+    motion: Motion,
+) -> Result<()> {
+    update_frame(conn, pointer_id, |state, frame| {
+        state
+            .position
+            .set((motion.surface_x.to_f64(), motion.surface_y.to_f64()));
+        frame.motion = Some(motion);
+    });
+
+    Ok(())
+}
@@ -0,0 +1,511 @@
+pub mod event;
+pub mod request;
+
+use std::sync::Arc;
+
+use crate::{
+    connection::{Connection, Listener},
+    cursor::{CursorTheme, NamedCursor},
+    error::{Result, anyhow},
+    protocol::{
+        WlObjectId,
+        cursor_shape::Shape,
+        seat::SeatState,
+        surface::{Surface, SurfaceRole, SurfaceState},
+        types::{WlInt, WlObject, WlUInt},
+    },
+    scroll::Scroll,
+    sync::{Lock, LockCell},
+};
+
+use event::{button::Button, enter::Enter, leave::Leave, motion::Motion};
+
+/// The pointer input of one `wl_pointer.frame`, delivered to the handler of a [`Pointer`].
+///
+/// The compositor sends pointer events in bursts that logically belong
+/// together, such as a leave and an enter when the focus moves between
+/// surfaces, or the horizontal and vertical parts of a diagonal scroll. All
+/// events of a burst are combined into a single frame.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PointerFrame {
+    /// The surface the pointer left in this frame; always before `enter`.
+    pub leave: Option<Leave>,
+    /// The surface the pointer entered in this frame.
+    pub enter: Option<Enter>,
+    /// The last motion within the focused surface in this frame.
+    pub motion: Option<Motion>,
+    /// The button presses and releases of this frame, in order.
+    pub buttons: Vec<Button>,
+    /// The scroll motion of this frame, empty if nothing scrolled.
+    pub scroll: Scroll,
+    /// The surface focused at the end of the frame, if any.
+    pub focus: Option<u32>,
+    /// The surface-local pointer position at the end of the frame.
+    pub position: (f64, f64),
+}
+
+impl PointerFrame {
+    /// Returns `true` if the frame carries no event.
+    pub fn is_empty(&self) -> bool {
+        self.leave.is_none()
+            && self.enter.is_none()
+            && self.motion.is_none()
+            && self.buttons.is_empty()
+            && self.scroll.is_empty()
+    }
+}
+
+/// State of a pointer updated by its events.
+#[derive(Default)]
+pub(crate) struct PointerState {
+    /// The frame accumulated from the events received since the last frame.
+    pending: Lock<PointerFrame>,
+    /// The surface the pointer is on, if any.
+    focus: LockCell<Option<u32>>,
+    /// The serial of the last `wl_pointer.enter` event.
+    enter_serial: LockCell<Option<u32>>,
+    /// The last known surface-local position.
+    position: LockCell<(f64, f64)>,
+    /// The state of the seat the pointer belongs to, which records event serials.
+    seat: Arc<SeatState>,
+}
+
+impl PointerState {
+    /// Returns the serial of the last `wl_pointer.enter` event, if the
+    /// pointer is on a surface of the client.
+    pub(crate) fn focused_enter_serial(&self) -> Option<u32> {
+        self.focus.get().and(self.enter_serial.get())
+    }
+}
+
+/// Applies an event to the pending frame of a pointer.
+///
+/// Before version 5 there is no `wl_pointer.frame` event, so every event
+/// forms a frame of its own and is delivered right away.
+fn update_frame<F>(conn: &Connection, pointer_id: u32, update: F)
+where
+    F: FnOnce(&PointerState, &mut PointerFrame),
+{
+    let Some(state) = conn.object_data::<PointerState>(pointer_id) else {
+        return;
+    };
+
+    update(&state, &mut state.pending.borrow_mut());
+
+    let has_frames = conn
+        .object(pointer_id)
+        .is_some_and(|object| object.version >= 5);
+    if !has_frames {
+        emit_frame(conn, pointer_id, &state);
+    }
+}
+
+/// Delivers the pending frame of a pointer, if it is not empty, and starts a new one.
+fn emit_frame(conn: &Connection, pointer_id: u32, state: &PointerState) {
+    let mut frame = state.pending.take();
+    if frame.is_empty() {
+        return;
+    }
+
+    frame.focus = state.focus.get();
+    frame.position = state.position.get();
+    conn.emit(pointer_id, frame);
+}
+
+/// A `wl_pointer`, the pointing devices of a seat.
+///
+/// Created with [`Seat::get_pointer`](crate::protocol::seat::Seat::get_pointer).
+pub struct Pointer {
+    /// The object ID of the pointer.
+    id: u32,
+    /// The interface version, inherited from the seat.
+    version: u32,
+    /// The connection the pointer was created on.
+    conn: Connection,
+    /// The cursors set by name.
+    named_cursor: Lock<NamedCursor>,
+}
+
+impl Pointer {
+    /// Wraps a pointer object that was just created on `conn`.
+    pub(crate) fn new(
+        id: u32,
+        version: u32,
+        conn: &Connection,
+        seat: Arc<SeatState>,
+        handler: Listener<PointerFrame>,
+    ) -> Pointer {
+        let state = PointerState {
+            seat,
+            ..PointerState::default()
+        };
+        conn.set_object_data(id, Arc::new(state));
+        conn.set_listener(id, handler);
+
+        Pointer {
+            id,
+            version,
+            conn: conn.clone(),
+            named_cursor: Lock::new(NamedCursor::default()),
+        }
+    }
+
+    /// Returns the object ID of the pointer.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the connection the pointer was created on.
+    pub(crate) fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    /// Returns the serial of the last time the pointer entered a surface of
+    /// the client, which requests such as
+    /// [`PointerWarp::warp_pointer`](crate::protocol::pointer_warp::PointerWarp::warp_pointer)
+    /// are checked against.
+    pub fn enter_serial(&self) -> Option<u32> {
+        self.conn
+            .object_data::<PointerState>(self.id)
+            .and_then(|state| state.enter_serial.get())
+    }
+
+    /// Sets the image of the pointer while it is on the surfaces of the
+    /// client: `cursor` is the surface holding the image and the hotspot,
+    /// the point of the surface at the pointer position. `None` hides the
+    /// pointer.
+    ///
+    /// `serial` is the serial of the `wl_pointer.enter` event the cursor
+    /// answers; `None` uses the latest one, see [`Pointer::enter_serial`].
+    /// The surface is given the cursor role, and setting the current
+    /// cursor surface again only moves the hotspot. The image is the
+    /// buffer committed to the surface, before or after this call.
+    ///
+    /// # Errors
+    /// Returns an error, without sending the request, if the pointer is not
+    /// on a surface of the client, if `serial` is not the one of the
+    /// latest enter event, which the compositor would ignore, or if the
+    /// surface already has another role, which is a protocol error.
+    pub fn set_cursor(
+        &self,
+        serial: Option<u32>,
+        cursor: Option<(&Surface, (i32, i32))>,
+    ) -> Result<()> {
+        let latest = self
+            .conn
+            .object_data::<PointerState>(self.id)
+            .and_then(|state| state.focused_enter_serial())
+            .ok_or_else(|| anyhow!("wl_pointer {} is not on a surface of the client", self.id))?;
+        if let Some(serial) = serial
+            && serial != latest
+        {
+            return Err(anyhow!(
+                "wl_pointer {}: serial {serial} is not the one of the latest enter event ({latest})",
+                self.id
+            ));
+        }
+
+        let (surface, (hotspot_x, hotspot_y)) = match cursor {
+            Some((surface, hotspot)) => {
+                self.conn.check_same_connection(
+                    surface.connection(),
+                    WlObjectId::Surface,
+                    surface.id(),
+                )?;
+                surface
+                    .state()
+                    .assign_role(surface.id(), SurfaceRole::Cursor)?;
+                (surface.id(), hotspot)
+            }
+            None => (0, (0, 0)),
+        };
+
+        request::set_cursor(
+            &self.conn,
+            self.id,
+            WlUInt(latest),
+            WlObject(surface),
+            WlInt(hotspot_x),
+            WlInt(hotspot_y),
+        )
+    }
+
+    /// Shows the cursor named `name`, such as `text` or `ew-resize`, in
+    /// one call: the shape drawn by the compositor when it offers
+    /// `wp_cursor_shape_manager_v1` and `name` is the CSS name of a
+    /// [`Shape`], otherwise the cursor of the theme, see
+    /// [`Pointer::set_cursor_theme`].
+    ///
+    /// Theme cursors are loaded for the integer scale of the surface the
+    /// pointer is on, and animate on their own. Like
+    /// [`Pointer::set_cursor`], the cursor answers the latest enter event,
+    /// so it is usually set whenever the pointer enters a surface.
+    ///
+    /// # Errors
+    /// Returns an error if the pointer is not on a surface of the client,
+    /// or if the theme has no such cursor or it cannot be loaded.
+    pub fn set_named_cursor(&self, name: &str) -> Result<()> {
+        let (serial, focus) = self
+            .conn
+            .object_data::<PointerState>(self.id)
+            .and_then(|state| Some((state.focused_enter_serial()?, state.focus.get()?)))
+            .ok_or_else(|| anyhow!("wl_pointer {} is not on a surface of the client", self.id))?;
+
+        let mut named_cursor = self.named_cursor.borrow_mut();
+        let shape = Shape::from_name(name);
+        if let Some(shape) = shape
+            && let Some(device) = named_cursor.shape_device(self)?
+            && shape.since() <= device.version()
+        {
+            device.set_shape(serial, shape)?;
+            named_cursor.clear_surface();
+            return Ok(());
+        }
+
+        let scale = self
+            .conn
+            .object_data::<SurfaceState>(focus)
+            .map_or(1, |surface| surface.integer_scale(&self.conn))
+            .max(1) as u32;
+        let (surface, hotspot) = named_cursor.surface(&self.conn, name, scale)?;
+        self.set_cursor(Some(serial), Some((surface, hotspot)))
+    }
+
+    /// Sets the theme [`Pointer::set_named_cursor`] loads cursors from
+    /// when the compositor does not draw them, instead of the one of the
+    /// environment ([`CursorTheme::from_env`]).
+    pub fn set_cursor_theme(&self, theme: CursorTheme) {
+        self.named_cursor.borrow_mut().set_theme(theme);
+    }
+
+    /// Releases the pointer.
+    ///
+    /// Before version 3 the pointer cannot be released and the object is
+    /// kept alive on the compositor side.
+    ///
+    /// Dropping the handle does the same.
+    pub fn release(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl Drop for Pointer {
+    fn drop(&mut self) {
+        self.conn.remove_listener(self.id);
+
+        if self.version >= 3 {
+            self.conn
+                .destroy_on_drop(|conn| request::release(conn, self.id));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        os::unix::net::UnixStream,
+    };
+
+    use super::*;
+    use crate::protocol::{
+        cursor_shape,
+        endian::Endian,
+        message::{WL_MESSAGE_HEADER_LEN, WlMessage, WlMessageHeader},
+        registry::event::global::Global,
+        types::WlString,
+    };
+
+    #[test]
+    fn set_cursor_uses_the_latest_enter_serial_and_role() {
+        let (client, mut compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+        let surface = Surface::new(conn.new_object(WlObjectId::Surface, 6).unwrap(), &conn);
+        let cursor = Surface::new(conn.new_object(WlObjectId::Surface, 6).unwrap(), &conn);
+        let pointer_id = conn.new_object(WlObjectId::Pointer, 9).unwrap();
+        let seat = Arc::new(SeatState::default());
+        let pointer = Pointer::new(pointer_id, 9, &conn, seat, Box::new(|_, _| {}));
+        assert!(pointer.set_cursor(None, None).is_err());
+
+        // `wl_pointer.enter` with serial 7, then `wl_pointer.frame`
+        let data: Vec<u8> = [7, surface.id(), 0, 0]
+            .iter()
+            .flat_map(|arg| arg.to_ne_bytes())
+            .collect();
+        let mut events: Vec<u8> = WlMessage::new(pointer_id, 0, &data).unwrap().into();
+        events.extend(Vec::<u8>::from(WlMessage::new(pointer_id, 5, &[]).unwrap()));
+        compositor.write_all(&events).unwrap();
+        conn.dispatch().unwrap();
+
+        assert!(
+            pointer
+                .set_cursor(Some(6), Some((&cursor, (1, 2))))
+                .is_err()
+        );
+        pointer
+            .set_cursor(Some(7), Some((&cursor, (1, 2))))
+            .unwrap();
+        pointer.set_cursor(None, Some((&cursor, (3, 4)))).unwrap();
+        assert_eq!(cursor.role(), Some(SurfaceRole::Cursor));
+        surface
+            .state()
+            .assign_role(surface.id(), SurfaceRole::XdgSurface)
+            .unwrap();
+        assert!(pointer.set_cursor(None, Some((&surface, (0, 0)))).is_err());
+
+        let cursor_id = cursor.id();
+        drop((pointer, cursor, surface, conn));
+        let mut received = Vec::new();
+        compositor.read_to_end(&mut received).unwrap();
+        let mut requests = Vec::new();
+        while !received.is_empty() {
+            let header = WlMessageHeader::decode(&received, Endian::NATIVE).unwrap();
+            let size = header.size as usize;
+            if header.object_id == pointer_id && header.opcode == request::Opcode::SetCursor as u16
+            {
+                let args: Vec<u32> = received[WL_MESSAGE_HEADER_LEN..size]
+                    .chunks(4)
+                    .map(|arg| u32::from_ne_bytes(arg.try_into().unwrap()))
+                    .collect();
+                requests.push(args);
+            }
+            received.drain(..size);
+        }
+        assert_eq!(requests, [[7, cursor_id, 1, 2], [7, cursor_id, 3, 4]]);
+    }
+
+    #[test]
+    fn named_cursor_prefers_the_cursor_shape() {
+        let (client, mut compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+        conn.insert_global(Global {
+            name: WlUInt(1),
+            interface: WlString::new("wp_cursor_shape_manager_v1").unwrap(),
+            version: WlUInt(1),
+        });
+        let surface = Surface::new(conn.new_object(WlObjectId::Surface, 6).unwrap(), &conn);
+        let pointer_id = conn.new_object(WlObjectId::Pointer, 9).unwrap();
+        let seat = Arc::new(SeatState::default());
+        let pointer = Pointer::new(pointer_id, 9, &conn, seat, Box::new(|_, _| {}));
+        assert!(pointer.set_named_cursor("text").is_err());
+
+        let data: Vec<u8> = [11, surface.id(), 0, 0]
+            .iter()
+            .flat_map(|arg| arg.to_ne_bytes())
+            .collect();
+        let events: Vec<u8> = WlMessage::new(pointer_id, 0, &data).unwrap().into();
+        compositor.write_all(&events).unwrap();
+        conn.dispatch().unwrap();
+
+        pointer.set_named_cursor("text").unwrap();
+        pointer.set_cursor_theme(CursorTheme::with_search_path("none", 24, Vec::new()));
+        // Version 1 lacks the shape, and the theme the cursor
+        assert!(pointer.set_named_cursor("all-resize").is_err());
+
+        // The manager and the device take the next IDs
+        let device_id = pointer_id + 2;
+        drop((pointer, surface, conn));
+        let mut received = Vec::new();
+        compositor.read_to_end(&mut received).unwrap();
+        let mut shapes = Vec::new();
+        while !received.is_empty() {
+            let header = WlMessageHeader::decode(&received, Endian::NATIVE).unwrap();
+            let size = header.size as usize;
+            if header.object_id == device_id
+                && header.opcode == cursor_shape::request::DeviceOpcode::SetShape as u16
+            {
+                shapes.push(u32::from_ne_bytes(received[12..16].try_into().unwrap()));
+            }
+            received.drain(..size);
+        }
+        assert_eq!(shapes, [Shape::Text as u32]);
+    }
+
+    #[test]
+    fn button_presses_record_the_grab_serial() {
+        let (client, mut compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+        let pointer_id = conn.new_object(WlObjectId::Pointer, 9).unwrap();
+        let seat = Arc::new(SeatState::default());
+        let _pointer = Pointer::new(pointer_id, 9, &conn, seat.clone(), Box::new(|_, _| {}));
+
+        // `wl_pointer.button` pressed with serial 4, then released with
+        // serial 5, each followed by `wl_pointer.frame`
+        let mut events = Vec::new();
+        for (serial, state) in [(4u32, 1u32), (5, 0)] {
+            let data: Vec<u8> = [serial, 0, 0x110, state]
+                .iter()
+                .flat_map(|arg| arg.to_ne_bytes())
+                .collect();
+            events.extend(Vec::<u8>::from(
+                WlMessage::new(pointer_id, 3, &data).unwrap(),
+            ));
+            events.extend(Vec::<u8>::from(WlMessage::new(pointer_id, 5, &[]).unwrap()));
+        }
+        compositor.write_all(&events).unwrap();
+        conn.dispatch().unwrap();
+
+        assert_eq!(seat.serial(), 5);
+        assert_eq!(seat.grab_serial(), Some(4));
+    }
+
+    #[test]
+    fn events_accumulate_until_the_frame() {
+        let (client, mut compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+        let pointer_id = conn.new_object(WlObjectId::Pointer, 9).unwrap();
+        let frames = Arc::new(Lock::new(Vec::new()));
+        let received = frames.clone();
+        let _pointer = Pointer::new(
+            pointer_id,
+            9,
+            &conn,
+            Arc::new(SeatState::default()),
+            Box::new(move |_, frame| received.borrow_mut().push(frame)),
+        );
+
+        let event = |opcode: event::Opcode, args: &[u32]| -> Vec<u8> {
+            let data: Vec<u8> = args.iter().flat_map(|arg| arg.to_ne_bytes()).collect();
+            WlMessage::new(pointer_id, opcode as u16, &data)
+                .unwrap()
+                .into()
+        };
+        // An enter, two motions, a press and a wheel detent scrolling down,
+        // all with 24.8 fixed-point coordinates
+        let events = [
+            event(event::Opcode::Enter, &[3, 20, 256, 512]),
+            event(event::Opcode::Motion, &[10, 512, 768]),
+            event(event::Opcode::Motion, &[11, 1024, 1280]),
+            event(event::Opcode::Button, &[4, 12, 272, 1]),
+            event(event::Opcode::AxisSource, &[0]),
+            event(event::Opcode::Axis, &[13, 0, 1280]),
+            event(event::Opcode::Axis, &[14, 0, 1280]),
+            event(event::Opcode::AxisValue120, &[0, 120]),
+        ];
+        compositor.write_all(&events.concat()).unwrap();
+        conn.dispatch().unwrap();
+        assert!(frames.borrow().is_empty());
+
+        compositor
+            .write_all(&event(event::Opcode::Frame, &[]))
+            .unwrap();
+        conn.dispatch().unwrap();
+
+        let frames = frames.borrow();
+        let [frame] = frames.as_slice() else {
+            panic!("expected a single frame, got {frames:?}");
+        };
+        assert_eq!(frame.enter.map(|enter| enter.serial.get()), Some(3));
+        assert_eq!(frame.motion.map(|motion| motion.time.get()), Some(11));
+        assert_eq!(frame.buttons.len(), 1);
+        assert_eq!(frame.buttons[0].state, event::button::ButtonState::Pressed);
+        assert_eq!(frame.scroll.time, Some(14));
+        assert_eq!(frame.scroll.vertical.value, 10.0);
+        assert_eq!(frame.scroll.vertical.value120, 120);
+        assert!(frame.scroll.horizontal.is_empty());
+        assert_eq!(frame.focus, Some(20));
+        assert_eq!(frame.position, (4.0, 5.0));
+    }
+}
@@ -0,0 +1,109 @@
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::types::{WlInt, WlObject, WlUInt},
+    wl_request_opcode, wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to a Wayland pointer object.
+    Opcode {
+        /// Sets the surface used as the pointer image.
+        SetCursor = 0,
+
+        /// Releases the pointer object (since version 3).
+        Release = 1 (destructor),
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wl_pointer.set_cursor` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_cursor">
+    ///   <description summary="set the pointer surface">
+    ///     Set the pointer surface, i.e., the surface that contains the
+    ///     pointer image (cursor). This request gives the surface the role
+    ///     of a cursor. If the surface already has another role, it raises
+    ///     a protocol error.
+    ///
+    ///     The cursor actually changes only if the pointer
+    ///     focus for this device is one of the requesting client's surfaces
+    ///     or the surface parameter is the current pointer surface. If
+    ///     there was a previous surface set with this request it is
+    ///     replaced. If surface is NULL, the pointer image is hidden.
+    ///
+    ///     The parameters hotspot_x and hotspot_y define the position of
+    ///     the pointer surface relative to the pointer location. Its
+    ///     top-left corner is always at (x, y) - (hotspot_x, hotspot_y),
+    ///     where (x, y) are the coordinates of the pointer location, in
+    ///     surface-local coordinates.
+    ///
+    ///     On wl_surface.offset requests to the pointer surface, hotspot_x
+    ///     and hotspot_y are decremented by the x and y parameters
+    ///     passed to the request. The offset must be applied by
+    ///     wl_surface.commit as usual.
+    ///
+    ///     The hotspot can also be updated by passing the currently set
+    ///     pointer surface to this request with new values for hotspot_x
+    ///     and hotspot_y.
+    ///
+    ///     The input region is ignored for wl_surfaces with the role of
+    ///     a cursor. When the use as a cursor ends, the wl_surface is
+    ///     unmapped.
+    ///
+    ///     The serial parameter must match the latest wl_pointer.enter
+    ///     serial number sent to the client. Otherwise the request will be
+    ///     ignored.
+    ///   </description>
+    ///   <arg name="serial" type="uint" summary="serial number of the enter event"/>
+    ///   <arg name="surface" type="object" interface="wl_surface" allow-null="true"
+    ///        summary="pointer surface"/>
+    ///   <arg name="hotspot_x" type="int" summary="surface-local x coordinate"/>
+    ///   <arg name="hotspot_y" type="int" summary="surface-local y coordinate"/>
+    /// </request>
+    /// ```
+    SetCursorParam {
+        /// The serial of the latest enter event of the pointer.
+        serial: WlUInt,
+        /// The cursor surface, `WlObject(0)` to hide the cursor.
+        surface: WlObject,
+        /// The horizontal position of the hotspot in the cursor surface.
+        hotspot_x: WlInt,
+        /// The vertical position of the hotspot in the cursor surface.
+        hotspot_y: WlInt,
+    }
+}
+
+/// Sends a `wl_pointer.set_cursor` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `pointer` - The ID of the pointer
+/// * `serial` - The serial of the latest enter event of the pointer
+/// * `surface` - The cursor surface, `WlObject(0)` to hide the cursor
+/// * `hotspot_x`, `hotspot_y` - The position of the hotspot in the cursor surface
+pub fn set_cursor(
+    conn: &Connection,
+    pointer: u32,
+    serial: WlUInt,
+    surface: WlObject,
+    hotspot_x: WlInt,
+    hotspot_y: WlInt,
+) -> Result<()> {
+    conn.send_request(
+        pointer,
+        Opcode::SetCursor,
+        &SetCursorParam::new(serial, surface, hotspot_x, hotspot_y),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `wl_pointer.release` request to the compositor.
+pub fn release(conn: &Connection, pointer: u32) -> Result<()> {
+    conn.send_request(pointer, Opcode::Release, &())?;
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result, anyhow},
+    protocol::message::WlMessage,
+};
+
+/// Represents the event types that can be emitted by a `zwp_locked_pointer_v1` object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockedPointerOpcode {
+    /// The lock was activated; the pointer no longer moves.
+    Locked = 0,
+
+    /// The lock was deactivated, e.g. because the surface lost the pointer
+    /// focus. A oneshot lock is defunct from then on.
+    Unlocked = 1,
+}
+
+impl TryFrom<u16> for LockedPointerOpcode {
+    type Error = Error;
+
+    /// Attempts to convert a raw opcode value into a structured `ZwpLockedPointerEvent`.
+    fn try_from(value: u16) -> Result<LockedPointerOpcode> {
+        match value {
+            0 => Ok(LockedPointerOpcode::Locked),
+            1 => Ok(LockedPointerOpcode::Unlocked),
+            _ => Err(anyhow!(
+                "Invalid zwp_locked_pointer_v1 event opcode: {}",
+                value
+            )),
+        }
+    }
+}
+
+impl LockedPointerOpcode {
+    /// Returns the interface version the event was introduced in.
+    pub fn since(self) -> u32 {
+        match self {
+            LockedPointerOpcode::Locked | LockedPointerOpcode::Unlocked => 1,
+        }
+    }
+}
+
+/// A decoded `zwp_locked_pointer_v1` event. Neither event carries arguments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockedPointerEvent {
+    /// The lock was activated; the pointer no longer moves.
+    Locked,
+    /// The lock was deactivated. A oneshot lock is defunct from then on.
+    Unlocked,
+}
+
+impl LockedPointerEvent {
+    /// Decodes a `zwp_locked_pointer_v1` event from its message.
+    ///
+    /// # Errors
+    /// Returns an error if the opcode is unknown.
+    pub fn decode(_conn: &Connection, msg: &WlMessage) -> Result<LockedPointerEvent> {
+        Ok(match LockedPointerOpcode::try_from(msg.header.opcode)? {
+            LockedPointerOpcode::Locked => LockedPointerEvent::Locked,
+            LockedPointerOpcode::Unlocked => LockedPointerEvent::Unlocked,
+        })
+    }
+
+    /// Returns the opcode of the event.
+    pub fn opcode(&self) -> LockedPointerOpcode {
+        match self {
+            LockedPointerEvent::Locked => LockedPointerOpcode::Locked,
+            LockedPointerEvent::Unlocked => LockedPointerOpcode::Unlocked,
+        }
+    }
+}
+
+/// Dispatches incoming `zwp_locked_pointer_v1` events to the listener of the lock.
+pub fn handle_zwp_locked_pointer_event(conn: &Connection, msg: WlMessage) -> Result<()> {
+    let event = LockedPointerEvent::decode(conn, &msg)?;
+    conn.emit(msg.header.object_id, event);
+
+    Ok(())
+}
@@ -0,0 +1,169 @@
+pub mod event;
+pub mod request;
+
+use crate::{
+    connection::{Connection, Listener},
+    error::Result,
+    protocol::{
+        WlObjectId,
+        pointer::Pointer,
+        region::Region,
+        surface::Surface,
+        types::{WlFixed, WlNewId, WlObject, WlUInt},
+    },
+};
+
+pub use event::LockedPointerEvent;
+pub use request::Lifetime;
+
+/// The highest `zwp_pointer_constraints_v1` version this crate implements.
+pub const ZWP_POINTER_CONSTRAINTS_VERSION: u32 = 1;
+
+/// A bound `zwp_pointer_constraints_v1` global, which lets clients lock the
+/// pointer in place, e.g. for first-person camera controls.
+pub struct PointerConstraints {
+    /// The object ID of the bound pointer constraints object.
+    id: u32,
+    /// The negotiated interface version.
+    version: u32,
+    /// The connection the object was bound on.
+    conn: Connection,
+}
+
+impl PointerConstraints {
+    /// Binds the `zwp_pointer_constraints_v1` global advertised by the compositor.
+    pub fn bind(conn: &Connection) -> Result<PointerConstraints> {
+        let (id, version) = conn.bind(
+            WlObjectId::PointerConstraints,
+            ZWP_POINTER_CONSTRAINTS_VERSION,
+        )?;
+
+        Ok(PointerConstraints {
+            id,
+            version,
+            conn: conn.clone(),
+        })
+    }
+
+    /// Returns the object ID of the pointer constraints object.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Asks the compositor to lock `pointer` in place while it is on
+    /// `surface`, within `region` or the input region of the surface.
+    ///
+    /// The lock activates when the compositor sees fit, usually once the
+    /// surface has the pointer focus, which `handler` is told with
+    /// [`LockedPointerEvent::Locked`]. While locked, the pointer sends no
+    /// motion events; relative motion is still reported by
+    /// [`RelativePointer`](crate::protocol::relative_pointer::RelativePointer).
+    ///
+    /// A surface can only have one pointer constraint per seat at a time.
+    pub fn lock_pointer<F>(
+        &self,
+        surface: &Surface,
+        pointer: &Pointer,
+        region: Option<&Region>,
+        lifetime: Lifetime,
+        handler: F,
+    ) -> Result<LockedPointer>
+    where
+        F: FnMut(&Connection, LockedPointerEvent) + Send + 'static,
+    {
+        self.conn
+            .check_same_connection(surface.connection(), WlObjectId::Surface, surface.id())?;
+        if let Some(region) = region {
+            self.conn.check_same_connection(
+                region.connection(),
+                WlObjectId::Region,
+                region.id(),
+            )?;
+        }
+
+        let id = self
+            .conn
+            .new_object(WlObjectId::LockedPointer, self.version)?;
+        request::lock_pointer(
+            &self.conn,
+            self.id,
+            WlNewId(id),
+            WlObject(surface.id()),
+            WlObject(pointer.id()),
+            WlObject(region.map_or(0, Region::id)),
+            WlUInt(lifetime as u32),
+        )?;
+
+        let listener: Listener<LockedPointerEvent> = Box::new(handler);
+        self.conn.set_listener(id, listener);
+
+        Ok(LockedPointer {
+            id,
+            conn: self.conn.clone(),
+        })
+    }
+
+    /// Destroys the pointer constraints object; existing locks keep working.
+    ///
+    /// Dropping the handle does the same.
+    pub fn destroy(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl Drop for PointerConstraints {
+    fn drop(&mut self) {
+        self.conn
+            .destroy_on_drop(|conn| request::destroy(conn, self.id));
+    }
+}
+
+/// A `zwp_locked_pointer_v1`, a request to keep the pointer in place.
+pub struct LockedPointer {
+    /// The object ID of the lock.
+    id: u32,
+    /// The connection the lock was created on.
+    conn: Connection,
+}
+
+impl LockedPointer {
+    /// Returns the object ID of the lock.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Tells the compositor where to show the pointer once unlocked, in the
+    /// surface-local coordinates of the locked surface, e.g. the position
+    /// of a cursor the client draws itself.
+    ///
+    /// Like all surface state, the hint applies on the next commit of the
+    /// surface.
+    pub fn set_cursor_position_hint(&self, x: f64, y: f64) -> Result<()> {
+        request::set_cursor_position_hint(
+            &self.conn,
+            self.id,
+            WlFixed::from_f64(x),
+            WlFixed::from_f64(y),
+        )
+    }
+
+    /// Destroys the lock, releasing the pointer.
+    ///
+    /// Dropping the handle does the same.
+    pub fn destroy(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl Drop for LockedPointer {
+    fn drop(&mut self) {
+        self.conn.remove_listener(self.id);
+
+        self.conn
+            .destroy_on_drop(|conn| request::destroy_locked_pointer(conn, self.id));
+    }
+}
@@ -0,0 +1,220 @@
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::types::{WlFixed, WlNewId, WlObject, WlUInt},
+    wl_enum, wl_request_opcode, wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to the `zwp_pointer_constraints_v1` object.
+    Opcode {
+        /// Destroys the pointer constraints object; existing constraints keep working.
+        Destroy = 0 (destructor),
+
+        /// Locks the pointer at its position on a surface.
+        LockPointer = 1,
+
+        /// Confines the pointer to a region of a surface.
+        ConfinePointer = 2,
+    }
+}
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to a `zwp_locked_pointer_v1` object.
+    LockedPointerOpcode {
+        /// Destroys the lock, releasing the pointer.
+        Destroy = 0 (destructor),
+
+        /// Tells the compositor where the pointer should appear once unlocked.
+        SetCursorPositionHint = 1,
+
+        /// Sets the region of the surface the lock can activate in.
+        SetRegion = 2,
+    }
+}
+
+wl_enum! {
+    /// How long a pointer constraint lasts.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <enum name="lifetime">
+    ///   <description summary="constraint lifetime">
+    ///     These values represent different lifetime semantics. They are passed
+    ///     as arguments to the factory requests to specify how the constraint
+    ///     lifetimes should be managed.
+    ///   </description>
+    ///   <entry name="oneshot" value="1"/>
+    ///   <entry name="persistent" value="2"/>
+    /// </enum>
+    /// ```
+    Lifetime {
+        /// The constraint is defunct once deactivated, e.g. when the surface
+        /// loses the pointer focus, and must be destroyed.
+        Oneshot = 1,
+        /// The constraint activates again whenever the surface regains the
+        /// pointer focus.
+        Persistent = 2,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `zwp_pointer_constraints_v1.lock_pointer` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="lock_pointer">
+    ///   <description summary="lock pointer to a position">
+    ///     The lock_pointer request lets the client request to disable movements of
+    ///     the virtual pointer (i.e. the cursor), effectively locking the pointer
+    ///     to a position. This request may not take effect immediately; in the
+    ///     future, when the compositor deems implementation-specific constraints
+    ///     are satisfied, the pointer lock will be activated and the compositor
+    ///     sends a locked event.
+    ///
+    ///     The protocol provides no guarantee that the constraints are ever
+    ///     satisfied, and does not require the compositor to send an error if the
+    ///     constraints cannot ever be satisfied. It is thus possible to request a
+    ///     lock that will never activate.
+    ///
+    ///     There may not be another pointer constraint of any kind requested or
+    ///     active on the surface for any of the wl_pointer objects of the seat of
+    ///     the passed pointer when requesting a lock. If there is, an error will be
+    ///     raised. See general pointer lock documentation for more details.
+    ///
+    ///     The intersection of the region passed with this request and the input
+    ///     region of the surface is used to determine where the pointer must be
+    ///     in order for the lock to activate. It is up to the compositor whether to
+    ///     warp the pointer or require some kind of user interaction for the lock
+    ///     to activate. If the region is null the surface input region is used.
+    ///
+    ///     A surface may receive pointer focus without the lock being activated.
+    ///
+    ///     The request creates a new object wp_locked_pointer which is used to
+    ///     interact with the lock as well as receive updates about its state. See
+    ///     the the description of wp_locked_pointer for further information.
+    ///
+    ///     Note that while a pointer is locked, the wl_pointer objects of the
+    ///     corresponding seat will not emit any wl_pointer.motion events, but
+    ///     relative motion events will still be emitted via wp_relative_pointer
+    ///     objects of the same seat. wl_pointer.axis and wl_pointer.button events
+    ///     are unaffected.
+    ///   </description>
+    ///   <arg name="id" type="new_id" interface="zwp_locked_pointer_v1"/>
+    ///   <arg name="surface" type="object" interface="wl_surface"
+    ///        summary="surface to lock pointer to"/>
+    ///   <arg name="pointer" type="object" interface="wl_pointer"
+    ///        summary="the pointer that should be locked"/>
+    ///   <arg name="region" type="object" interface="wl_region" allow-null="true"
+    ///        summary="region of surface"/>
+    ///   <arg name="lifetime" type="uint" enum="lifetime" summary="lock lifetime"/>
+    /// </request>
+    /// ```
+    LockPointerParam {
+        /// The object ID to assign to the new lock.
+        id: WlNewId,
+        /// The surface to lock the pointer to.
+        surface: WlObject,
+        /// The pointer to lock.
+        pointer: WlObject,
+        /// The region the lock can activate in, `WlObject(0)` for the input region.
+        region: WlObject,
+        /// The lifetime of the lock.
+        lifetime: WlUInt,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `zwp_locked_pointer_v1.set_cursor_position_hint` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_cursor_position_hint">
+    ///   <description summary="set the pointer cursor position hint">
+    ///     Set the cursor position hint relative to the top left corner of the
+    ///     surface.
+    ///
+    ///     If the client is drawing its own cursor, it should update the position
+    ///     hint to the position of its own cursor. A compositor may use this
+    ///     information to warp the pointer upon unlock in order to avoid pointer
+    ///     jumps.
+    ///
+    ///     The cursor position hint is double-buffered state, see
+    ///     wl_surface.commit.
+    ///   </description>
+    ///   <arg name="surface_x" type="fixed"
+    ///        summary="surface-local x coordinate"/>
+    ///   <arg name="surface_y" type="fixed"
+    ///        summary="surface-local y coordinate"/>
+    /// </request>
+    /// ```
+    SetCursorPositionHintParam {
+        /// The surface-local x coordinate of the hint.
+        surface_x: WlFixed,
+        /// The surface-local y coordinate of the hint.
+        surface_y: WlFixed,
+    }
+}
+
+/// Sends a `zwp_pointer_constraints_v1.destroy` request to the compositor.
+pub fn destroy(conn: &Connection, pointer_constraints: u32) -> Result<()> {
+    conn.send_request(pointer_constraints, Opcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends a `zwp_pointer_constraints_v1.lock_pointer` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `pointer_constraints` - The ID of the bound pointer constraints object
+/// * `id` - The object ID to assign to the new lock
+/// * `surface` - The surface to lock the pointer to
+/// * `pointer` - The pointer to lock
+/// * `region` - The region the lock can activate in, `WlObject(0)` for the input region
+/// * `lifetime` - The lifetime of the lock
+pub fn lock_pointer(
+    conn: &Connection,
+    pointer_constraints: u32,
+    id: WlNewId,
+    surface: WlObject,
+    pointer: WlObject,
+    region: WlObject,
+    lifetime: WlUInt,
+) -> Result<()> {
+    conn.send_request(
+        pointer_constraints,
+        Opcode::LockPointer,
+        &LockPointerParam::new(id, surface, pointer, region, lifetime),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `zwp_locked_pointer_v1.destroy` request to the compositor.
+pub fn destroy_locked_pointer(conn: &Connection, locked_pointer: u32) -> Result<()> {
+    conn.send_request(locked_pointer, LockedPointerOpcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends a `zwp_locked_pointer_v1.set_cursor_position_hint` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `locked_pointer` - The ID of the lock
+/// * `surface_x`, `surface_y` - The surface-local position of the hint
+pub fn set_cursor_position_hint(
+    conn: &Connection,
+    locked_pointer: u32,
+    surface_x: WlFixed,
+    surface_y: WlFixed,
+) -> Result<()> {
+    conn.send_request(
+        locked_pointer,
+        LockedPointerOpcode::SetCursorPositionHint,
+        &SetCursorPositionHintParam::new(surface_x, surface_y),
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        pointer_gestures::{GestureEvent, GestureKind},
+        types::{WL_TYPE_OBJECT_LEN, WL_TYPE_UINT_LEN, WlObject, WlUInt},
+    },
+};
+
+/// Represents the `begin` event of a swipe, pinch or hold gesture.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="begin">
+///   <description summary="multi-finger swipe begin">
+///     This event is sent when a multi-finger swipe gesture is detected
+///     on the device.
+///   </description>
+///   <arg name="serial" type="uint"/>
+///   <arg name="time" type="uint" summary="timestamp with millisecond granularity"/>
+///   <arg name="surface" type="object" interface="wl_surface"/>
+///   <arg name="fingers" type="uint" summary="number of fingers"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Begin {
+    /// The serial number of the begin event.
+    pub serial: WlUInt,
+    /// The timestamp with millisecond granularity.
+    pub time: WlUInt,
+    /// The surface under the pointer when the gesture started.
+    pub surface: WlObject,
+    /// The number of fingers taking part in the gesture.
+    pub fingers: WlUInt,
+}
+
+impl TryFrom<&[u8]> for Begin {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Begin` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `serial` (u32) - The serial number of the event
+    /// - Bytes 4-7: `time` (u32) - The timestamp
+    /// - Bytes 8-11: `surface` (u32) - The surface under the pointer
+    /// - Bytes 12-15: `fingers` (u32) - The number of fingers
+    fn try_from(buf: &[u8]) -> Result<Begin> {
+        Ok(Begin {
+            serial: WlUInt::read(buf, 0)?,
+            time: WlUInt::read(buf, WL_TYPE_UINT_LEN)?,
+            surface: WlObject::read(buf, 2 * WL_TYPE_UINT_LEN)?,
+            fingers: WlUInt::read(buf, 2 * WL_TYPE_UINT_LEN + WL_TYPE_OBJECT_LEN)?,
+        })
+    }
+}
+
+/// Handles the `begin` event of a gesture by passing it to the listener.
+pub(super) fn handle_gesture_begin(
+    conn: &Connection,
+    gesture_id: u32,
+    kind: GestureKind,
+    begin: Begin,
+) -> Result<()> {
+    let event = match kind {
+        GestureKind::Swipe => GestureEvent::SwipeBegin(begin),
+        GestureKind::Pinch => GestureEvent::PinchBegin(begin),
+        GestureKind::Hold => GestureEvent::HoldBegin(begin),
+    };
+    conn.emit(gesture_id, event);
+
+    Ok(())
+}
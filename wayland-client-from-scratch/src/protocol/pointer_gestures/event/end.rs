@@ -0,0 +1,79 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        pointer_gestures::{GestureEvent, GestureKind},
+        types::{WL_TYPE_UINT_LEN, WlInt, WlUInt},
+    },
+};
+
+/// Represents the `end` event of a swipe, pinch or hold gesture.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="end">
+///   <description summary="multi-finger swipe end">
+///     This event is sent when a multi-finger swipe gesture ceases to
+///     be valid. This may happen when one or more fingers are lifted or
+///     the gesture is cancelled.
+///
+///     When a gesture is cancelled, the client should undo state changes
+///     caused by this gesture. What causes a gesture to be cancelled is
+///     implementation-dependent.
+///   </description>
+///   <arg name="serial" type="uint"/>
+///   <arg name="time" type="uint" summary="timestamp with millisecond granularity"/>
+///   <arg name="cancelled" type="int" summary="1 if the gesture was cancelled, 0 otherwise"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct End {
+    /// The serial number of the end event.
+    pub serial: WlUInt,
+    /// The timestamp with millisecond granularity.
+    pub time: WlUInt,
+    /// 1 if the gesture was cancelled, 0 otherwise.
+    pub cancelled: WlInt,
+}
+
+impl End {
+    /// Returns `true` if the gesture was cancelled and its effects should be undone.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.get() != 0
+    }
+}
+
+impl TryFrom<&[u8]> for End {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `End` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `serial` (u32) - The serial number of the event
+    /// - Bytes 4-7: `time` (u32) - The timestamp
+    /// - Bytes 8-11: `cancelled` (i32) - Whether the gesture was cancelled
+    fn try_from(buf: &[u8]) -> Result<End> {
+        Ok(End {
+            serial: WlUInt::read(buf, 0)?,
+            time: WlUInt::read(buf, WL_TYPE_UINT_LEN)?,
+            cancelled: WlInt::read(buf, 2 * WL_TYPE_UINT_LEN)?,
+        })
+    }
+}
+
+/// Handles the `end` event of a gesture by passing it to the listener.
+pub(super) fn handle_gesture_end(
+    conn: &Connection,
+    gesture_id: u32,
+    kind: GestureKind,
+    end: End,
+) -> Result<()> {
+    let event = match kind {
+        GestureKind::Swipe => GestureEvent::SwipeEnd(end),
+        GestureKind::Pinch => GestureEvent::PinchEnd(end),
+        GestureKind::Hold => GestureEvent::HoldEnd(end),
+    };
+    conn.emit(gesture_id, event);
+
+    Ok(())
+}
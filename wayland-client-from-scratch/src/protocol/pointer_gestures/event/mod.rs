@@ -0,0 +1,264 @@
+pub mod begin;
+pub mod end;
+pub mod pinch_update;
+pub mod swipe_update;
+
+use crate::{
+    connection::Connection,
+    error::{Error, Result, anyhow},
+    protocol::{message::WlMessage, pointer_gestures::GestureKind},
+};
+
+/// Represents the event types that can be emitted by a swipe or pinch gesture object.
+///
+/// Both interfaces share their opcodes; only the arguments of `update` differ.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    /// The gesture started.
+    ///
+    /// # Event Arguments
+    /// - `serial`: The serial number of the event
+    /// - `time`: The timestamp in milliseconds
+    /// - `surface`: The surface under the pointer
+    /// - `fingers`: The number of fingers
+    Begin = 0,
+
+    /// The gesture moved, and for pinches scaled or rotated.
+    ///
+    /// # Event Arguments
+    /// - `time`: The timestamp in milliseconds
+    /// - `dx`, `dy`: The motion of the gesture center
+    /// - `scale`, `rotation`: The pinch scale and rotation (pinch only)
+    Update = 1,
+
+    /// The gesture ended or was cancelled.
+    ///
+    /// # Event Arguments
+    /// - `serial`: The serial number of the event
+    /// - `time`: The timestamp in milliseconds
+    /// - `cancelled`: 1 if the gesture was cancelled
+    End = 2,
+}
+
+impl TryFrom<u16> for Opcode {
+    type Error = Error;
+
+    /// Attempts to convert a raw opcode value into a structured swipe or pinch event.
+    fn try_from(value: u16) -> Result<Opcode> {
+        match value {
+            0 => Ok(Opcode::Begin),
+            1 => Ok(Opcode::Update),
+            2 => Ok(Opcode::End),
+            _ => Err(anyhow!("Invalid pointer gesture event opcode: {}", value)),
+        }
+    }
+}
+
+impl Opcode {
+    /// Returns the interface version the event was introduced in.
+    pub fn since(self) -> u32 {
+        match self {
+            Opcode::Begin | Opcode::Update | Opcode::End => 1,
+        }
+    }
+}
+
+/// Represents the event types that can be emitted by a hold gesture object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HoldOpcode {
+    /// Fingers were put down and are holding still.
+    Begin = 0,
+
+    /// The hold ended or was cancelled by motion.
+    End = 1,
+}
+
+impl TryFrom<u16> for HoldOpcode {
+    type Error = Error;
+
+    /// Attempts to convert a raw opcode value into a structured hold event.
+    fn try_from(value: u16) -> Result<HoldOpcode> {
+        match value {
+            0 => Ok(HoldOpcode::Begin),
+            1 => Ok(HoldOpcode::End),
+            _ => Err(anyhow!(
+                "Invalid zwp_pointer_gesture_hold_v1 event opcode: {}",
+                value
+            )),
+        }
+    }
+}
+
+impl HoldOpcode {
+    /// Returns the interface version the event was introduced in.
+    pub fn since(self) -> u32 {
+        match self {
+            HoldOpcode::Begin | HoldOpcode::End => 3,
+        }
+    }
+}
+
+/// A decoded `zwp_pointer_gesture_swipe_v1` event, with its arguments.
+#[derive(Debug)]
+pub enum SwipeEvent {
+    /// The gesture started.
+    Begin(begin::Begin),
+    /// The gesture moved, and for pinches scaled or rotated.
+    Update(swipe_update::SwipeUpdate),
+    /// The gesture ended or was cancelled.
+    End(end::End),
+}
+
+impl SwipeEvent {
+    /// Decodes a `zwp_pointer_gesture_swipe_v1` event from its message.
+    ///
+    /// # Errors
+    /// Returns an error if the opcode is unknown or the arguments are malformed.
+    pub fn decode(_conn: &Connection, msg: &WlMessage) -> Result<SwipeEvent> {
+        let buf: &[u8] = &msg.data;
+
+        Ok(match Opcode::try_from(msg.header.opcode)? {
+            Opcode::Begin => SwipeEvent::Begin(begin::Begin::try_from(buf)?),
+            Opcode::Update => SwipeEvent::Update(swipe_update::SwipeUpdate::try_from(buf)?),
+            Opcode::End => SwipeEvent::End(end::End::try_from(buf)?),
+        })
+    }
+
+    /// Returns the opcode of the event.
+    pub fn opcode(&self) -> Opcode {
+        match self {
+            SwipeEvent::Begin(_) => Opcode::Begin,
+            SwipeEvent::Update(_) => Opcode::Update,
+            SwipeEvent::End(_) => Opcode::End,
+        }
+    }
+}
+
+/// Dispatches incoming `zwp_pointer_gesture_swipe_v1` events.
+///
+/// # Event Routing
+/// * `Begin` events are routed to `begin::handle_gesture_begin`
+/// * `Update` events are routed to `swipe_update::handle_swipe_update`
+/// * `End` events are routed to `end::handle_gesture_end`
+pub fn handle_zwp_pointer_gesture_swipe_event(conn: &Connection, msg: WlMessage) -> Result<()> {
+    let gesture_id = msg.header.object_id;
+
+    // Route the event to the appropriate handler based on type
+    match SwipeEvent::decode(conn, &msg)? {
+        SwipeEvent::Begin(event) => {
+            begin::handle_gesture_begin(conn, gesture_id, GestureKind::Swipe, event)
+        }
+        SwipeEvent::Update(event) => swipe_update::handle_swipe_update(conn, gesture_id, event),
+        SwipeEvent::End(event) => {
+            end::handle_gesture_end(conn, gesture_id, GestureKind::Swipe, event)
+        }
+    }
+}
+
+/// A decoded `zwp_pointer_gesture_pinch_v1` event, with its arguments.
+#[derive(Debug)]
+pub enum PinchEvent {
+    /// The gesture started.
+    Begin(begin::Begin),
+    /// The gesture moved, and for pinches scaled or rotated.
+    Update(pinch_update::PinchUpdate),
+    /// The gesture ended or was cancelled.
+    End(end::End),
+}
+
+impl PinchEvent {
+    /// Decodes a `zwp_pointer_gesture_pinch_v1` event from its message.
+    ///
+    /// # Errors
+    /// Returns an error if the opcode is unknown or the arguments are malformed.
+    pub fn decode(_conn: &Connection, msg: &WlMessage) -> Result<PinchEvent> {
+        let buf: &[u8] = &msg.data;
+
+        Ok(match Opcode::try_from(msg.header.opcode)? {
+            Opcode::Begin => PinchEvent::Begin(begin::Begin::try_from(buf)?),
+            Opcode::Update => PinchEvent::Update(pinch_update::PinchUpdate::try_from(buf)?),
+            Opcode::End => PinchEvent::End(end::End::try_from(buf)?),
+        })
+    }
+
+    /// Returns the opcode of the event.
+    pub fn opcode(&self) -> Opcode {
+        match self {
+            PinchEvent::Begin(_) => Opcode::Begin,
+            PinchEvent::Update(_) => Opcode::Update,
+            PinchEvent::End(_) => Opcode::End,
+        }
+    }
+}
+
+/// Dispatches incoming `zwp_pointer_gesture_pinch_v1` events.
+///
+/// # Event Routing
+/// * `Begin` events are routed to `begin::handle_gesture_begin`
+/// * `Update` events are routed to `pinch_update::handle_pinch_update`
+/// * `End` events are routed to `end::handle_gesture_end`
+pub fn handle_zwp_pointer_gesture_pinch_event(conn: &Connection, msg: WlMessage) -> Result<()> {
+    let gesture_id = msg.header.object_id;
+
+    // Route the event to the appropriate handler based on type
+    match PinchEvent::decode(conn, &msg)? {
+        PinchEvent::Begin(event) => {
+            begin::handle_gesture_begin(conn, gesture_id, GestureKind::Pinch, event)
+        }
+        PinchEvent::Update(event) => pinch_update::handle_pinch_update(conn, gesture_id, event),
+        PinchEvent::End(event) => {
+            end::handle_gesture_end(conn, gesture_id, GestureKind::Pinch, event)
+        }
+    }
+}
+
+/// A decoded `zwp_pointer_gesture_hold_v1` event, with its arguments.
+#[derive(Debug)]
+pub enum HoldEvent {
+    /// Fingers were put down and are holding still.
+    Begin(begin::Begin),
+    /// The hold ended or was cancelled by motion.
+    End(end::End),
+}
+
+impl HoldEvent {
+    /// Decodes a `zwp_pointer_gesture_hold_v1` event from its message.
+    ///
+    /// # Errors
+    /// Returns an error if the opcode is unknown or the arguments are malformed.
+    pub fn decode(_conn: &Connection, msg: &WlMessage) -> Result<HoldEvent> {
+        let buf: &[u8] = &msg.data;
+
+        Ok(match HoldOpcode::try_from(msg.header.opcode)? {
+            HoldOpcode::Begin => HoldEvent::Begin(begin::Begin::try_from(buf)?),
+            HoldOpcode::End => HoldEvent::End(end::End::try_from(buf)?),
+        })
+    }
+
+    /// Returns the opcode of the event.
+    pub fn opcode(&self) -> HoldOpcode {
+        match self {
+            HoldEvent::Begin(_) => HoldOpcode::Begin,
+            HoldEvent::End(_) => HoldOpcode::End,
+        }
+    }
+}
+
+/// Dispatches incoming `zwp_pointer_gesture_hold_v1` events.
+///
+/// # Event Routing
+/// * `Begin` events are routed to `begin::handle_gesture_begin`
+/// * `End` events are routed to `end::handle_gesture_end`
+pub fn handle_zwp_pointer_gesture_hold_event(conn: &Connection, msg: WlMessage) -> Result<()> {
+    let gesture_id = msg.header.object_id;
+
+    // Route the event to the appropriate handler based on type
+    match HoldEvent::decode(conn, &msg)? {
+        HoldEvent::Begin(event) => {
+            begin::handle_gesture_begin(conn, gesture_id, GestureKind::Hold, event)
+        }
+        HoldEvent::End(event) => {
+            end::handle_gesture_end(conn, gesture_id, GestureKind::Hold, event)
+        }
+    }
+}
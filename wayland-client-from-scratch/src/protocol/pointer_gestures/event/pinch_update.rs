@@ -0,0 +1,81 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        pointer_gestures::GestureEvent,
+        types::{WL_TYPE_FIXED_LEN, WL_TYPE_UINT_LEN, WlFixed, WlUInt},
+    },
+};
+
+/// Represents a `zwp_pointer_gesture_pinch_v1.update` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="update">
+///   <description summary="multi-finger pinch motion">
+///     This event is sent when a multi-finger pinch gesture changes the
+///     position of the logical center, the rotation or the relative scale.
+///
+///     The dx and dy coordinates are relative coordinates in the
+///     surface coordinate space of the logical center of the gesture.
+///
+///     The scale factor is an absolute scale compared to the
+///     pointer_gesture_pinch.begin event, e.g. a scale of 2 means the fingers
+///     are now twice as far apart as on pointer_gesture_pinch.begin.
+///
+///     The rotation is the relative angle in degrees clockwise compared to the previous
+///     pointer_gesture_pinch.begin or pointer_gesture_pinch.update event.
+///   </description>
+///   <arg name="time" type="uint" summary="timestamp with millisecond granularity"/>
+///   <arg name="dx" type="fixed" summary="delta x coordinate in surface coordinate space"/>
+///   <arg name="dy" type="fixed" summary="delta y coordinate in surface coordinate space"/>
+///   <arg name="scale" type="fixed" summary="scale relative to the initial finger position"/>
+///   <arg name="rotation" type="fixed" summary="angle in degrees cw relative to the previous event"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PinchUpdate {
+    /// The timestamp with millisecond granularity.
+    pub time: WlUInt,
+    /// The horizontal motion of the gesture center since the previous event.
+    pub dx: WlFixed,
+    /// The vertical motion of the gesture center since the previous event.
+    pub dy: WlFixed,
+    /// The finger spread relative to the begin event; absolute, not incremental.
+    pub scale: WlFixed,
+    /// The clockwise rotation in degrees since the previous event.
+    pub rotation: WlFixed,
+}
+
+impl TryFrom<&[u8]> for PinchUpdate {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `PinchUpdate` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `time` (u32) - The timestamp
+    /// - Bytes 4-7: `dx` (fixed) - The horizontal delta
+    /// - Bytes 8-11: `dy` (fixed) - The vertical delta
+    /// - Bytes 12-15: `scale` (fixed) - The absolute scale
+    /// - Bytes 16-19: `rotation` (fixed) - The relative rotation
+    fn try_from(buf: &[u8]) -> Result<PinchUpdate> {
+        Ok(PinchUpdate {
+            time: WlUInt::read(buf, 0)?,
+            dx: WlFixed::read(buf, WL_TYPE_UINT_LEN)?,
+            dy: WlFixed::read(buf, WL_TYPE_UINT_LEN + WL_TYPE_FIXED_LEN)?,
+            scale: WlFixed::read(buf, WL_TYPE_UINT_LEN + 2 * WL_TYPE_FIXED_LEN)?,
+            rotation: WlFixed::read(buf, WL_TYPE_UINT_LEN + 3 * WL_TYPE_FIXED_LEN)?,
+        })
+    }
+}
+
+/// Handles a `zwp_pointer_gesture_pinch_v1.update` event by passing it to the listener.
+pub(super) fn handle_pinch_update(
+    conn: &Connection,
+    gesture_id: u32,
+    update: PinchUpdate,
+) -> Result<()> {
+    conn.emit(gesture_id, GestureEvent::PinchUpdate(update));
+
+    Ok(())
+}
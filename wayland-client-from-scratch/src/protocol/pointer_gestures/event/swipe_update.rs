@@ -0,0 +1,64 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        pointer_gestures::GestureEvent,
+        types::{WL_TYPE_FIXED_LEN, WL_TYPE_UINT_LEN, WlFixed, WlUInt},
+    },
+};
+
+/// Represents a `zwp_pointer_gesture_swipe_v1.update` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="update">
+///   <description summary="multi-finger swipe motion">
+///     This event is sent when a multi-finger swipe gesture changes the
+///     position of the logical center.
+///
+///     The dx and dy coordinates are relative coordinates of the logical
+///     center of the gesture compared to the previous event.
+///   </description>
+///   <arg name="time" type="uint" summary="timestamp with millisecond granularity"/>
+///   <arg name="dx" type="fixed" summary="delta x coordinate in surface coordinate space"/>
+///   <arg name="dy" type="fixed" summary="delta y coordinate in surface coordinate space"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SwipeUpdate {
+    /// The timestamp with millisecond granularity.
+    pub time: WlUInt,
+    /// The horizontal motion of the gesture center since the previous event.
+    pub dx: WlFixed,
+    /// The vertical motion of the gesture center since the previous event.
+    pub dy: WlFixed,
+}
+
+impl TryFrom<&[u8]> for SwipeUpdate {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `SwipeUpdate` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `time` (u32) - The timestamp
+    /// - Bytes 4-7: `dx` (fixed) - The horizontal delta
+    /// - Bytes 8-11: `dy` (fixed) - The vertical delta
+    fn try_from(buf: &[u8]) -> Result<SwipeUpdate> {
+        Ok(SwipeUpdate {
+            time: WlUInt::read(buf, 0)?,
+            dx: WlFixed::read(buf, WL_TYPE_UINT_LEN)?,
+            dy: WlFixed::read(buf, WL_TYPE_UINT_LEN + WL_TYPE_FIXED_LEN)?,
+        })
+    }
+}
+
+/// Handles a `zwp_pointer_gesture_swipe_v1.update` event by passing it to the listener.
+pub(super) fn handle_swipe_update(
+    conn: &Connection,
+    gesture_id: u32,
+    update: SwipeUpdate,
+) -> Result<()> {
+    conn.emit(gesture_id, GestureEvent::SwipeUpdate(update));
+
+    Ok(())
+}
@@ -0,0 +1,212 @@
+pub mod event;
+pub mod request;
+
+use crate::{
+    connection::{Connection, Listener},
+    error::{Result, anyhow},
+    protocol::{
+        WlObjectId,
+        pointer::Pointer,
+        types::{WlNewId, WlObject},
+    },
+};
+
+use event::{begin::Begin, end::End, pinch_update::PinchUpdate, swipe_update::SwipeUpdate};
+
+/// The highest `zwp_pointer_gestures_v1` version this crate implements.
+pub const ZWP_POINTER_GESTURES_VERSION: u32 = 3;
+
+/// The kinds of touchpad gestures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GestureKind {
+    /// Several fingers moving in the same direction.
+    Swipe,
+    /// Fingers moving towards or away from each other, possibly rotating.
+    Pinch,
+    /// Fingers resting on the touchpad without moving.
+    Hold,
+}
+
+/// An event delivered to the handler of a [`Gesture`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GestureEvent {
+    /// A swipe started.
+    SwipeBegin(Begin),
+    /// The fingers of a swipe moved.
+    SwipeUpdate(SwipeUpdate),
+    /// A swipe ended or was cancelled.
+    SwipeEnd(End),
+    /// A pinch started.
+    PinchBegin(Begin),
+    /// The fingers of a pinch moved, spread or rotated.
+    PinchUpdate(PinchUpdate),
+    /// A pinch ended or was cancelled.
+    PinchEnd(End),
+    /// Fingers started holding still.
+    HoldBegin(Begin),
+    /// The hold ended or was cancelled.
+    HoldEnd(End),
+}
+
+/// A bound `zwp_pointer_gestures_v1` global, the factory for touchpad gesture objects.
+pub struct PointerGestures {
+    /// The object ID of the bound gestures object.
+    id: u32,
+    /// The negotiated interface version.
+    version: u32,
+    /// The connection the gestures object was bound on.
+    conn: Connection,
+}
+
+impl PointerGestures {
+    /// Binds the `zwp_pointer_gestures_v1` global advertised by the compositor.
+    pub fn bind(conn: &Connection) -> Result<PointerGestures> {
+        let (id, version) = conn.bind(WlObjectId::PointerGestures, ZWP_POINTER_GESTURES_VERSION)?;
+
+        Ok(PointerGestures {
+            id,
+            version,
+            conn: conn.clone(),
+        })
+    }
+
+    /// Returns the object ID of the gestures object.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Subscribes to the swipe gestures performed with `pointer`.
+    pub fn get_swipe_gesture<F>(&self, pointer: &Pointer, handler: F) -> Result<Gesture>
+    where
+        F: FnMut(&Connection, GestureEvent) + Send + 'static,
+    {
+        self.get_gesture(GestureKind::Swipe, pointer, Box::new(handler))
+    }
+
+    /// Subscribes to the pinch gestures performed with `pointer`.
+    pub fn get_pinch_gesture<F>(&self, pointer: &Pointer, handler: F) -> Result<Gesture>
+    where
+        F: FnMut(&Connection, GestureEvent) + Send + 'static,
+    {
+        self.get_gesture(GestureKind::Pinch, pointer, Box::new(handler))
+    }
+
+    /// Subscribes to the hold gestures performed with `pointer`.
+    ///
+    /// # Errors
+    /// Returns an error if the compositor implements a version older than 3.
+    pub fn get_hold_gesture<F>(&self, pointer: &Pointer, handler: F) -> Result<Gesture>
+    where
+        F: FnMut(&Connection, GestureEvent) + Send + 'static,
+    {
+        self.get_gesture(GestureKind::Hold, pointer, Box::new(handler))
+    }
+
+    /// Destroys the gestures object; existing gesture objects keep working.
+    ///
+    /// Before version 2 the object cannot be released and is kept alive on
+    /// the compositor side.
+    ///
+    /// Dropping the handle does the same.
+    pub fn release(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+
+    /// Creates a gesture object of the given kind and registers its handler.
+    fn get_gesture(
+        &self,
+        kind: GestureKind,
+        pointer: &Pointer,
+        handler: Listener<GestureEvent>,
+    ) -> Result<Gesture> {
+        let (interface, opcode) = match kind {
+            GestureKind::Swipe => (
+                WlObjectId::PointerGestureSwipe,
+                request::Opcode::GetSwipeGesture,
+            ),
+            GestureKind::Pinch => (
+                WlObjectId::PointerGesturePinch,
+                request::Opcode::GetPinchGesture,
+            ),
+            GestureKind::Hold => {
+                if self.version < 3 {
+                    return Err(anyhow!(
+                        "Hold gestures need zwp_pointer_gestures_v1 version 3, bound version {}",
+                        self.version
+                    ));
+                }
+                (
+                    WlObjectId::PointerGestureHold,
+                    request::Opcode::GetHoldGesture,
+                )
+            }
+        };
+
+        let id = self.conn.new_object(interface, self.version)?;
+        request::get_gesture(
+            &self.conn,
+            self.id,
+            opcode,
+            WlNewId(id),
+            WlObject(pointer.id()),
+        )?;
+        self.conn.set_listener(id, handler);
+
+        Ok(Gesture {
+            id,
+            kind,
+            conn: self.conn.clone(),
+        })
+    }
+}
+
+impl Drop for PointerGestures {
+    fn drop(&mut self) {
+        if self.version >= 2 {
+            self.conn
+                .destroy_on_drop(|conn| request::release(conn, self.id));
+        }
+    }
+}
+
+/// A swipe, pinch or hold gesture object reporting the gestures of one pointer.
+pub struct Gesture {
+    /// The object ID of the gesture object.
+    id: u32,
+    /// The kind of gestures reported.
+    kind: GestureKind,
+    /// The connection the gesture object was created on.
+    conn: Connection,
+}
+
+impl Gesture {
+    /// Returns the object ID of the gesture object.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the kind of gestures reported.
+    pub fn kind(&self) -> GestureKind {
+        self.kind
+    }
+
+    /// Destroys the gesture object; events still in flight are ignored.
+    ///
+    /// Dropping the handle does the same.
+    pub fn destroy(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl Drop for Gesture {
+    fn drop(&mut self) {
+        self.conn.remove_listener(self.id);
+
+        self.conn
+            .destroy_on_drop(|conn| request::destroy_gesture(conn, self.id));
+    }
+}
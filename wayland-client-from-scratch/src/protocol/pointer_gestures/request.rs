@@ -0,0 +1,88 @@
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::types::{WlNewId, WlObject},
+    wl_request_opcode, wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to the `zwp_pointer_gestures_v1` object.
+    Opcode {
+        /// Creates a swipe gesture object for a pointer.
+        GetSwipeGesture = 0,
+
+        /// Creates a pinch gesture object for a pointer.
+        GetPinchGesture = 1,
+
+        /// Destroys the pointer gesture object (since version 2).
+        Release = 2 (destructor),
+
+        /// Creates a hold gesture object for a pointer (since version 3).
+        GetHoldGesture = 3,
+    }
+}
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to a swipe, pinch or hold gesture object.
+    GestureOpcode {
+        /// Destroys the gesture object.
+        Destroy = 0 (destructor),
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `get_swipe_gesture`, `get_pinch_gesture` and
+    /// `get_hold_gesture` requests, which share their signature.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="get_swipe_gesture">
+    ///   <description summary="get swipe gesture">
+    ///     Create a swipe gesture object. See the
+    ///     wl_pointer_gesture_swipe interface for details.
+    ///   </description>
+    ///   <arg name="id" type="new_id" interface="zwp_pointer_gesture_swipe_v1"/>
+    ///   <arg name="pointer" type="object" interface="wl_pointer"/>
+    /// </request>
+    /// ```
+    GetGestureParam {
+        /// The object ID to assign to the new gesture object.
+        id: WlNewId,
+        /// The pointer whose gestures are reported.
+        pointer: WlObject,
+    }
+}
+
+/// Sends one of the `get_*_gesture` requests to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `gestures` - The ID of the `zwp_pointer_gestures_v1` object
+/// * `opcode` - The request creating the gesture of the wanted kind
+/// * `id` - The object ID to assign to the new gesture object
+/// * `pointer` - The pointer whose gestures are reported
+pub fn get_gesture(
+    conn: &Connection,
+    gestures: u32,
+    opcode: Opcode,
+    id: WlNewId,
+    pointer: WlObject,
+) -> Result<()> {
+    conn.send_request(gestures, opcode, &GetGestureParam::new(id, pointer))?;
+
+    Ok(())
+}
+
+/// Sends a `zwp_pointer_gestures_v1.release` request to the compositor.
+pub fn release(conn: &Connection, gestures: u32) -> Result<()> {
+    conn.send_request(gestures, Opcode::Release, &())?;
+
+    Ok(())
+}
+
+/// Sends the `destroy` request of a swipe, pinch or hold gesture object.
+pub fn destroy_gesture(conn: &Connection, gesture: u32) -> Result<()> {
+    conn.send_request(gesture, GestureOpcode::Destroy, &())?;
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+pub mod request;
+
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::{
+        WlObjectId,
+        pointer::Pointer,
+        surface::Surface,
+        types::{WlFixed, WlObject, WlUInt},
+    },
+};
+
+/// The highest `wp_pointer_warp_v1` version this crate implements.
+pub const WP_POINTER_WARP_VERSION: u32 = 1;
+
+/// A bound `wp_pointer_warp_v1` global, which lets clients move the pointer
+/// within their own surfaces.
+pub struct PointerWarp {
+    /// The object ID of the bound pointer warp object.
+    id: u32,
+    /// The connection the object was bound on.
+    conn: Connection,
+}
+
+impl PointerWarp {
+    /// Binds the `wp_pointer_warp_v1` global advertised by the compositor.
+    pub fn bind(conn: &Connection) -> Result<PointerWarp> {
+        let (id, _) = conn.bind(WlObjectId::PointerWarp, WP_POINTER_WARP_VERSION)?;
+
+        Ok(PointerWarp {
+            id,
+            conn: conn.clone(),
+        })
+    }
+
+    /// Returns the object ID of the pointer warp object.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Asks the compositor to move `pointer` to `(x, y)` in the
+    /// surface-local coordinates of `surface`, such as back to where it was
+    /// locked once a pointer constraint is released.
+    ///
+    /// `serial` is the serial of an enter event of the pointer, usually
+    /// [`Pointer::enter_serial`]. Compositors only honor the request while
+    /// the surface has the pointer focus and the position lies within it,
+    /// and the new position is announced with a motion event.
+    pub fn warp_pointer(
+        &self,
+        surface: &Surface,
+        pointer: &Pointer,
+        x: f64,
+        y: f64,
+        serial: u32,
+    ) -> Result<()> {
+        request::warp_pointer(
+            &self.conn,
+            self.id,
+            WlObject(surface.id()),
+            WlObject(pointer.id()),
+            WlFixed::from_f64(x),
+            WlFixed::from_f64(y),
+            WlUInt(serial),
+        )
+    }
+
+    /// Destroys the pointer warp object.
+    ///
+    /// Dropping the handle does the same.
+    pub fn destroy(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl Drop for PointerWarp {
+    fn drop(&mut self) {
+        self.conn
+            .destroy_on_drop(|conn| request::destroy(conn, self.id));
+    }
+}
@@ -0,0 +1,92 @@
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::types::{WlFixed, WlObject, WlUInt},
+    wl_request_opcode, wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to the `wp_pointer_warp_v1` object.
+    Opcode {
+        /// Destroys the pointer warp object.
+        Destroy = 0 (destructor),
+
+        /// Moves the pointer to a surface-local position.
+        WarpPointer = 1,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wp_pointer_warp_v1.warp_pointer` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="warp_pointer">
+    ///   <description summary="reposition the pointer">
+    ///     Request the compositor to move the pointer to a surface-local position.
+    ///     Whether or not the compositor honors the request is implementation defined,
+    ///     but it should
+    ///     - honor it if the surface has pointer focus, including
+    ///       when it has an implicit pointer grab
+    ///     - reject it if the enter serial is incorrect
+    ///     - reject it if the requested position is outside of the surface
+    ///
+    ///     Note that the enter serial is valid for any surface of the client,
+    ///     and does not have to be from the surface the pointer is warped to.
+    ///   </description>
+    ///   <arg name="surface" type="object" interface="wl_surface"
+    ///        summary="surface to position the pointer on"/>
+    ///   <arg name="pointer" type="object" interface="wl_pointer"
+    ///        summary="the pointer that should be repositioned"/>
+    ///   <arg name="x" type="fixed"/>
+    ///   <arg name="y" type="fixed"/>
+    ///   <arg name="serial" type="uint" summary="serial number of the enter event"/>
+    /// </request>
+    /// ```
+    WarpPointerParam {
+        /// The surface to position the pointer on.
+        surface: WlObject,
+        /// The pointer to move.
+        pointer: WlObject,
+        /// The surface-local x coordinate of the new position.
+        x: WlFixed,
+        /// The surface-local y coordinate of the new position.
+        y: WlFixed,
+        /// The serial of the enter event of the pointer.
+        serial: WlUInt,
+    }
+}
+
+/// Sends a `wp_pointer_warp_v1.destroy` request to the compositor.
+pub fn destroy(conn: &Connection, pointer_warp: u32) -> Result<()> {
+    conn.send_request(pointer_warp, Opcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends a `wp_pointer_warp_v1.warp_pointer` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `pointer_warp` - The ID of the bound pointer warp object
+/// * `surface` - The surface to position the pointer on
+/// * `pointer` - The pointer to move
+/// * `x`, `y` - The surface-local position to move the pointer to
+/// * `serial` - The serial of the enter event of the pointer
+pub fn warp_pointer(
+    conn: &Connection,
+    pointer_warp: u32,
+    surface: WlObject,
+    pointer: WlObject,
+    x: WlFixed,
+    y: WlFixed,
+    serial: WlUInt,
+) -> Result<()> {
+    conn.send_request(
+        pointer_warp,
+        Opcode::WarpPointer,
+        &WarpPointerParam::new(surface, pointer, x, y, serial),
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{presentation::PresentationState, types::WlUInt},
+};
+
+/// Represents a `wp_presentation.clock_id` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="clock_id">
+///   <description summary="clock ID for timestamps">
+///     This event tells the client in which clock domain the
+///     compositor interprets the timestamps used by the presentation
+///     extension. This clock is called the presentation clock.
+///
+///     The compositor sends this event when the client binds to the
+///     presentation interface. The presentation clock does not change
+///     during the lifetime of the client connection.
+///
+///     The clock identifier is platform dependent. On POSIX platforms, the
+///     identifier value is one of the clockid_t values accepted by
+///     clock_gettime(). clock_gettime() is defined by POSIX.1-2001.
+///   </description>
+///   <arg name="clk_id" type="uint" summary="platform clock identifier"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockId {
+    /// The `clockid_t` of the presentation clock.
+    pub clk_id: WlUInt,
+}
+
+impl TryFrom<&[u8]> for ClockId {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `ClockId` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `clk_id` (u32) - The platform clock identifier
+    fn try_from(buf: &[u8]) -> Result<ClockId> {
+        Ok(ClockId {
+            clk_id: WlUInt::read(buf, 0)?,
+        })
+    }
+}
+
+/// Handles a `wp_presentation.clock_id` event by recording the clock.
+pub(super) fn handle_wp_presentation_clock_id(
+    conn: &Connection,
+    presentation_id: u32,
+    event: ClockId,
+) -> Result<()> {
+    if let Some(state) = conn.object_data::<PresentationState>(presentation_id) {
+        state.clock_id.set(Some(event.clk_id.get()));
+    }
+
+    Ok(())
+}
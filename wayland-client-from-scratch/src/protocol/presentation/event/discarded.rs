@@ -0,0 +1,22 @@
+use crate::{
+    connection::Connection, error::Result, protocol::presentation::PresentationFeedbackEvent,
+};
+
+/// Handles a `wp_presentation_feedback.discarded` event by notifying the listener.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="discarded">
+///   <description summary="the content update was not displayed">
+///     The content update was never displayed to the user.
+///   </description>
+/// </event>
+/// ```
+pub(super) fn handle_wp_presentation_feedback_discarded(
+    conn: &Connection,
+    feedback_id: u32,
+) -> Result<()> {
+    conn.emit(feedback_id, PresentationFeedbackEvent::Discarded);
+
+    Ok(())
+}
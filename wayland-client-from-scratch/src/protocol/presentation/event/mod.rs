@@ -0,0 +1,193 @@
+pub mod clock_id;
+pub mod discarded;
+pub mod presented;
+pub mod sync_output;
+
+use crate::{
+    connection::Connection,
+    error::{Error, Result, anyhow},
+    protocol::message::WlMessage,
+};
+
+/// Represents the event types that can be emitted by the `wp_presentation` object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    /// Announces the clock of the presentation timestamps.
+    ///
+    /// # Event Arguments
+    /// - `clk_id`: The platform clock identifier
+    ClockId = 0,
+}
+
+impl TryFrom<u16> for Opcode {
+    type Error = Error;
+
+    /// Attempts to convert a raw opcode value into a structured `WpPresentationEvent`.
+    fn try_from(value: u16) -> Result<Opcode> {
+        match value {
+            0 => Ok(Opcode::ClockId),
+            _ => Err(anyhow!("Invalid wp_presentation event opcode: {}", value)),
+        }
+    }
+}
+
+impl Opcode {
+    /// Returns the interface version the event was introduced in.
+    pub fn since(self) -> u32 {
+        match self {
+            Opcode::ClockId => 1,
+        }
+    }
+}
+
+/// Represents the event types that can be emitted by a `wp_presentation_feedback` object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedbackOpcode {
+    /// Announces an output the content was presented on.
+    ///
+    /// # Event Arguments
+    /// - `output`: The presentation output
+    SyncOutput = 0,
+
+    /// Announces that the content was presented.
+    ///
+    /// # Event Arguments
+    /// - `tv_sec_hi`, `tv_sec_lo`, `tv_nsec`: The presentation timestamp
+    /// - `refresh`: The nanoseconds until the next refresh, 0 if unknown
+    /// - `seq_hi`, `seq_lo`: The vertical retrace counter
+    /// - `flags`: How the presentation was done
+    Presented = 1,
+
+    /// Announces that the content was never displayed.
+    Discarded = 2,
+}
+
+impl TryFrom<u16> for FeedbackOpcode {
+    type Error = Error;
+
+    /// Attempts to convert a raw opcode value into a structured `WpPresentationFeedbackEvent`.
+    fn try_from(value: u16) -> Result<FeedbackOpcode> {
+        match value {
+            0 => Ok(FeedbackOpcode::SyncOutput),
+            1 => Ok(FeedbackOpcode::Presented),
+            2 => Ok(FeedbackOpcode::Discarded),
+            _ => Err(anyhow!(
+                "Invalid wp_presentation_feedback event opcode: {}",
+                value
+            )),
+        }
+    }
+}
+
+impl FeedbackOpcode {
+    /// Returns the interface version the event was introduced in.
+    pub fn since(self) -> u32 {
+        match self {
+            FeedbackOpcode::SyncOutput | FeedbackOpcode::Presented | FeedbackOpcode::Discarded => 1,
+        }
+    }
+}
+
+/// A decoded `wp_presentation` event, with its arguments.
+#[derive(Debug)]
+pub enum Event {
+    /// Announces the clock of the presentation timestamps.
+    ClockId(clock_id::ClockId),
+}
+
+impl Event {
+    /// Decodes a `wp_presentation` event from its message.
+    ///
+    /// # Errors
+    /// Returns an error if the opcode is unknown or the arguments are malformed.
+    pub fn decode(_conn: &Connection, msg: &WlMessage) -> Result<Event> {
+        let buf: &[u8] = &msg.data;
+
+        Ok(match Opcode::try_from(msg.header.opcode)? {
+            Opcode::ClockId => Event::ClockId(clock_id::ClockId::try_from(buf)?),
+        })
+    }
+
+    /// Returns the opcode of the event.
+    pub fn opcode(&self) -> Opcode {
+        match self {
+            Event::ClockId(_) => Opcode::ClockId,
+        }
+    }
+}
+
+/// Dispatches incoming `wp_presentation` events to their appropriate handler functions.
+///
+/// # Event Routing
+/// * `ClockId` events are routed to `clock_id::handle_wp_presentation_clock_id`
+pub fn handle_wp_presentation_event(conn: &Connection, msg: WlMessage) -> Result<()> {
+    // Route the event to the appropriate handler based on type
+    match Event::decode(conn, &msg)? {
+        Event::ClockId(event) => {
+            clock_id::handle_wp_presentation_clock_id(conn, msg.header.object_id, event)
+        }
+    }
+}
+
+/// A decoded `wp_presentation_feedback` event, with its arguments.
+#[derive(Debug)]
+pub enum FeedbackEvent {
+    /// Announces an output the content was presented on.
+    SyncOutput(sync_output::SyncOutput),
+    /// Announces that the content was presented.
+    Presented(presented::Presented),
+    /// Announces that the content was never displayed.
+    Discarded,
+}
+
+impl FeedbackEvent {
+    /// Decodes a `wp_presentation_feedback` event from its message.
+    ///
+    /// # Errors
+    /// Returns an error if the opcode is unknown or the arguments are malformed.
+    pub fn decode(_conn: &Connection, msg: &WlMessage) -> Result<FeedbackEvent> {
+        let buf: &[u8] = &msg.data;
+
+        Ok(match FeedbackOpcode::try_from(msg.header.opcode)? {
+            FeedbackOpcode::SyncOutput => {
+                FeedbackEvent::SyncOutput(sync_output::SyncOutput::try_from(buf)?)
+            }
+            FeedbackOpcode::Presented => {
+                FeedbackEvent::Presented(presented::Presented::try_from(buf)?)
+            }
+            FeedbackOpcode::Discarded => FeedbackEvent::Discarded,
+        })
+    }
+
+    /// Returns the opcode of the event.
+    pub fn opcode(&self) -> FeedbackOpcode {
+        match self {
+            FeedbackEvent::SyncOutput(_) => FeedbackOpcode::SyncOutput,
+            FeedbackEvent::Presented(_) => FeedbackOpcode::Presented,
+            FeedbackEvent::Discarded => FeedbackOpcode::Discarded,
+        }
+    }
+}
+
+/// Dispatches incoming `wp_presentation_feedback` events to their appropriate handler functions.
+///
+/// # Event Routing
+/// * `SyncOutput` events are routed to `sync_output::handle_wp_presentation_feedback_sync_output`
+/// * `Presented` events are routed to `presented::handle_wp_presentation_feedback_presented`
+/// * `Discarded` events are routed to `discarded::handle_wp_presentation_feedback_discarded`
+pub fn handle_wp_presentation_feedback_event(conn: &Connection, msg: WlMessage) -> Result<()> {
+    let feedback_id = msg.header.object_id;
+
+    // Route the event to the appropriate handler based on type
+    match FeedbackEvent::decode(conn, &msg)? {
+        FeedbackEvent::SyncOutput(event) => {
+            sync_output::handle_wp_presentation_feedback_sync_output(conn, feedback_id, event)
+        }
+        FeedbackEvent::Presented(event) => {
+            presented::handle_wp_presentation_feedback_presented(conn, feedback_id, event)
+        }
+        FeedbackEvent::Discarded => {
+            discarded::handle_wp_presentation_feedback_discarded(conn, feedback_id)
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use std::time::Duration;
+
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{presentation::PresentationFeedbackEvent, types::WlUInt},
+    wl_bitfield,
+};
+
+wl_bitfield! {
+    /// How a content update was presented, as reported by
+    /// `wp_presentation_feedback.presented`.
+    PresentationFlags {
+        /// The presentation was synchronized to the vertical retrace.
+        VSYNC = 0x1,
+        /// The timestamp was taken from a hardware clock.
+        HW_CLOCK = 0x2,
+        /// The hardware signaled the start of the presentation.
+        HW_COMPLETION = 0x4,
+        /// The buffer was scanned out directly, without a copy.
+        ZERO_COPY = 0x8,
+    }
+}
+
+/// Represents a `wp_presentation_feedback.presented` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="presented">
+///   <description summary="the content update was displayed">
+///     The associated content update was displayed to the user at the
+///     indicated time (tv_sec_hi/lo, tv_nsec). For the interpretation of
+///     the timestamp, see presentation.clock_id event.
+///
+///     The timestamp corresponds to the time when the content update
+///     turned into light the first time on the surface's main output.
+///     Compositors may approximate this from the framebuffer flip
+///     completion events from the system, and the latency of the
+///     physical display path if known.
+///
+///     The refresh argument gives the compositor's prediction of how
+///     many nanoseconds after tv_sec, tv_nsec the very next output
+///     refresh may occur. This is to further aid clients in
+///     predicting future refreshes, i.e., estimating the timestamps
+///     targeting the next few vblanks. If such prediction cannot
+///     usefully be done, the argument is zero.
+///
+///     The 64-bit value combined from seq_hi and seq_lo is the value
+///     of the output's vertical retrace counter when the content
+///     update was first scanned out to the display. This value must
+///     be compatible with the definition of MSC in
+///     GLX_OML_sync_control specification. Note, that if the display
+///     path has a non-zero latency, the time instant specified by
+///     this counter may differ from the timestamp's.
+///
+///     If the output does not have a constant refresh rate, explicit
+///     video mode switches excluded, then the refresh argument must
+///     be either an appropriate rate picked by the compositor (e.g.
+///     fastest rate), or 0.
+///   </description>
+///   <arg name="tv_sec_hi" type="uint"
+///        summary="high 32 bits of the seconds part of the presentation timestamp"/>
+///   <arg name="tv_sec_lo" type="uint"
+///        summary="low 32 bits of the seconds part of the presentation timestamp"/>
+///   <arg name="tv_nsec" type="uint"
+///        summary="nanoseconds part of the presentation timestamp"/>
+///   <arg name="refresh" type="uint" summary="nanoseconds till next refresh"/>
+///   <arg name="seq_hi" type="uint"
+///        summary="high 32 bits of refresh counter"/>
+///   <arg name="seq_lo" type="uint"
+///        summary="low 32 bits of refresh counter"/>
+///   <arg name="flags" type="uint" enum="kind" summary="combination of 'kind' values"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Presented {
+    /// The high 32 bits of the seconds of the timestamp.
+    pub tv_sec_hi: WlUInt,
+    /// The low 32 bits of the seconds of the timestamp.
+    pub tv_sec_lo: WlUInt,
+    /// The nanoseconds of the timestamp.
+    pub tv_nsec: WlUInt,
+    /// The nanoseconds until the next refresh, 0 if unknown.
+    pub refresh: WlUInt,
+    /// The high 32 bits of the vertical retrace counter.
+    pub seq_hi: WlUInt,
+    /// The low 32 bits of the vertical retrace counter.
+    pub seq_lo: WlUInt,
+    /// How the presentation was done, see [`PresentationFlags`].
+    pub flags: WlUInt,
+}
+
+impl Presented {
+    /// Returns the time the content turned into light, on the clock
+    /// announced by `wp_presentation.clock_id`.
+    pub fn timestamp(&self) -> Duration {
+        let secs = u64::from(self.tv_sec_hi.get()) << 32 | u64::from(self.tv_sec_lo.get());
+        Duration::new(secs, self.tv_nsec.get())
+    }
+
+    /// Returns the predicted time from the presentation to the next
+    /// refresh, or `None` if the compositor cannot predict it.
+    pub fn refresh(&self) -> Option<Duration> {
+        match self.refresh.get() {
+            0 => None,
+            refresh => Some(Duration::from_nanos(refresh.into())),
+        }
+    }
+
+    /// Returns the vertical retrace counter of the output when the content
+    /// was first scanned out.
+    pub fn sequence(&self) -> u64 {
+        u64::from(self.seq_hi.get()) << 32 | u64::from(self.seq_lo.get())
+    }
+
+    /// Returns how the presentation was done.
+    pub fn flags(&self) -> PresentationFlags {
+        PresentationFlags::from_bits(self.flags.get())
+    }
+}
+
+impl TryFrom<&[u8]> for Presented {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Presented` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `tv_sec_hi` (u32) - The high 32 bits of the seconds
+    /// - Bytes 4-7: `tv_sec_lo` (u32) - The low 32 bits of the seconds
+    /// - Bytes 8-11: `tv_nsec` (u32) - The nanoseconds
+    /// - Bytes 12-15: `refresh` (u32) - The nanoseconds until the next refresh
+    /// - Bytes 16-19: `seq_hi` (u32) - The high 32 bits of the retrace counter
+    /// - Bytes 20-23: `seq_lo` (u32) - The low 32 bits of the retrace counter
+    /// - Bytes 24-27: `flags` (u32) - The presentation flags
+    fn try_from(buf: &[u8]) -> Result<Presented> {
+        Ok(Presented {
+            tv_sec_hi: WlUInt::read(buf, 0)?,
+            tv_sec_lo: WlUInt::read(buf, 4)?,
+            tv_nsec: WlUInt::read(buf, 8)?,
+            refresh: WlUInt::read(buf, 12)?,
+            seq_hi: WlUInt::read(buf, 16)?,
+            seq_lo: WlUInt::read(buf, 20)?,
+            flags: WlUInt::read(buf, 24)?,
+        })
+    }
+}
+
+/// Handles a `wp_presentation_feedback.presented` event by notifying the listener.
+pub(super) fn handle_wp_presentation_feedback_presented(
+    conn: &Connection,
+    feedback_id: u32,
+    event: Presented,
+) -> Result<()> {
+    conn.emit(feedback_id, PresentationFeedbackEvent::Presented(event));
+
+    Ok(())
+}
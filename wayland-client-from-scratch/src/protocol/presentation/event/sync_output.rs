@@ -0,0 +1,58 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{presentation::PresentationFeedbackEvent, types::WlObject},
+};
+
+/// Represents a `wp_presentation_feedback.sync_output` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="sync_output">
+///   <description summary="presentation synchronized to this output">
+///     As presentation can be synchronized to only one output at a
+///     time, this event tells which output it was. This event is only
+///     sent prior to the presented event.
+///
+///     As clients may bind to the same global wl_output multiple
+///     times, this event is sent for each bound instance that matches
+///     the synchronized output. If a client has not bound to the
+///     right wl_output global at all, this event is not sent.
+///   </description>
+///   <arg name="output" type="object" interface="wl_output"
+///        summary="presentation output"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyncOutput {
+    /// The output the presentation was synchronized to.
+    pub output: WlObject,
+}
+
+impl TryFrom<&[u8]> for SyncOutput {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `SyncOutput` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `output` (u32) - The object ID of the output
+    fn try_from(buf: &[u8]) -> Result<SyncOutput> {
+        Ok(SyncOutput {
+            output: WlObject::read(buf, 0)?,
+        })
+    }
+}
+
+/// Handles a `wp_presentation_feedback.sync_output` event by notifying the listener.
+pub(super) fn handle_wp_presentation_feedback_sync_output(
+    conn: &Connection,
+    feedback_id: u32,
+    event: SyncOutput,
+) -> Result<()> {
+    conn.emit(
+        feedback_id,
+        PresentationFeedbackEvent::SyncOutput(event.output.get()),
+    );
+
+    Ok(())
+}
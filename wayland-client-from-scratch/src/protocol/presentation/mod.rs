@@ -0,0 +1,129 @@
+pub mod event;
+pub mod request;
+
+use std::{sync::Arc, time::Duration};
+
+use crate::{
+    connection::{Connection, Listener},
+    error::Result,
+    protocol::{
+        WlObjectId,
+        surface::Surface,
+        types::{WlNewId, WlObject},
+    },
+    sync::LockCell,
+    sys,
+};
+
+use event::presented::Presented;
+
+/// The highest `wp_presentation` version this crate implements.
+///
+/// Version 2 only allows a zero refresh for variable refresh rate outputs,
+/// which [`Presented::refresh`] reports as unknown.
+pub const WP_PRESENTATION_VERSION: u32 = 2;
+
+/// The `CLOCK_MONOTONIC` clock, which the presentation clock is assumed to
+/// be until `wp_presentation.clock_id` is received.
+const CLOCK_MONOTONIC: u32 = 1;
+
+/// An event delivered to the handler of a presentation feedback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PresentationFeedbackEvent {
+    /// The content was presented in sync with the output with this object ID.
+    /// Only sent for outputs the client bound, before `Presented`.
+    SyncOutput(u32),
+    /// The content was presented; the last event of the feedback.
+    Presented(Presented),
+    /// The content was never displayed; the last event of the feedback.
+    Discarded,
+}
+
+/// State of a presentation object updated by its events.
+#[derive(Default)]
+pub(crate) struct PresentationState {
+    /// The clock of the timestamps, once announced.
+    pub(crate) clock_id: LockCell<Option<u32>>,
+}
+
+/// A bound `wp_presentation` global, which reports when the content of
+/// surfaces turned into light.
+#[derive(Clone)]
+pub struct Presentation {
+    /// The object ID of the bound presentation object.
+    id: u32,
+    /// The negotiated interface version.
+    version: u32,
+    /// The connection the presentation object was bound on.
+    conn: Connection,
+    /// The presentation clock, shared with the event handler.
+    state: Arc<PresentationState>,
+}
+
+impl Presentation {
+    /// Binds the `wp_presentation` global advertised by the compositor.
+    ///
+    /// The presentation clock is announced right after binding, so it is
+    /// known after the next [`Connection::roundtrip`].
+    pub fn bind(conn: &Connection) -> Result<Presentation> {
+        let (id, version) = conn.bind(WlObjectId::Presentation, WP_PRESENTATION_VERSION)?;
+
+        let state = Arc::new(PresentationState::default());
+        conn.set_object_data(id, state.clone());
+
+        Ok(Presentation {
+            id,
+            version,
+            conn: conn.clone(),
+            state,
+        })
+    }
+
+    /// Returns the object ID of the presentation object.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the `clockid_t` of the presentation timestamps, once announced.
+    pub fn clock_id(&self) -> Option<u32> {
+        self.state.clock_id.get()
+    }
+
+    /// Returns the current time on the presentation clock, comparable to
+    /// [`Presented::timestamp`].
+    ///
+    /// `CLOCK_MONOTONIC` is used until the clock is announced.
+    pub fn now(&self) -> Result<Duration> {
+        let clock_id = self.clock_id().unwrap_or(CLOCK_MONOTONIC);
+        Ok(sys::clock_gettime(clock_id)?)
+    }
+
+    /// Requests feedback on the presentation of the next commit of `surface`.
+    ///
+    /// `handler` receives the [`PresentationFeedbackEvent`]s of that
+    /// commit, ending with either `Presented` or `Discarded`, after which
+    /// the compositor destroys the feedback object. Request the feedback
+    /// right before committing.
+    pub fn feedback<F>(&self, surface: &Surface, handler: F) -> Result<()>
+    where
+        F: FnMut(&Connection, PresentationFeedbackEvent) + Send + 'static,
+    {
+        self.conn
+            .check_same_connection(surface.connection(), WlObjectId::Surface, surface.id())?;
+
+        let id = self
+            .conn
+            .new_object(WlObjectId::PresentationFeedback, self.version)?;
+        request::feedback(&self.conn, self.id, WlObject(surface.id()), WlNewId(id))?;
+
+        let listener: Listener<PresentationFeedbackEvent> = Box::new(handler);
+        self.conn.set_listener(id, listener);
+
+        Ok(())
+    }
+
+    /// Destroys the presentation object; pending feedback is still delivered.
+    pub fn destroy(self) -> Result<()> {
+        request::destroy(&self.conn, self.id)
+    }
+}
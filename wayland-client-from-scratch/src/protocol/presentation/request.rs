@@ -0,0 +1,76 @@
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::types::{WlNewId, WlObject},
+    wl_request_opcode, wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to the `wp_presentation` object.
+    Opcode {
+        /// Destroys the presentation object; existing feedback objects keep working.
+        Destroy = 0 (destructor),
+
+        /// Requests presentation feedback for the next commit of a surface.
+        Feedback = 1,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wp_presentation.feedback` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="feedback">
+    ///   <description summary="request presentation feedback information">
+    ///     Request presentation feedback for the current content submission
+    ///     on the given surface. This creates a new presentation_feedback
+    ///     object, which will deliver the feedback information once. If
+    ///     multiple presentation_feedback objects are created for the same
+    ///     submission, they will all deliver the same information.
+    ///
+    ///     For details on what information is returned, see the
+    ///     presentation_feedback interface.
+    ///   </description>
+    ///   <arg name="surface" type="object" interface="wl_surface"
+    ///        summary="target surface"/>
+    ///   <arg name="callback" type="new_id" interface="wp_presentation_feedback"
+    ///        summary="new feedback object"/>
+    /// </request>
+    /// ```
+    FeedbackParam {
+        /// The surface whose next content submission is reported on.
+        surface: WlObject,
+        /// The object ID to assign to the new feedback object.
+        callback: WlNewId,
+    }
+}
+
+/// Sends a `wp_presentation.destroy` request to the compositor.
+pub fn destroy(conn: &Connection, presentation: u32) -> Result<()> {
+    conn.send_request(presentation, Opcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends a `wp_presentation.feedback` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `presentation` - The ID of the bound presentation object
+/// * `surface` - The surface whose next content submission is reported on
+/// * `callback` - The object ID to assign to the new feedback object
+pub fn feedback(
+    conn: &Connection,
+    presentation: u32,
+    surface: WlObject,
+    callback: WlNewId,
+) -> Result<()> {
+    conn.send_request(
+        presentation,
+        Opcode::Feedback,
+        &FeedbackParam::new(surface, callback),
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,117 @@
+pub mod request;
+
+use crate::{connection::Connection, error::Result, geometry::Rect, protocol::types::WlInt};
+
+/// A `wl_region`, a set of rectangles used as the opaque or input region
+/// of a surface.
+///
+/// The region is copied when set on a surface, so it can be changed or
+/// dropped afterwards without affecting the surface.
+pub struct Region {
+    /// The object ID of the region.
+    id: u32,
+    /// The connection the region was created on.
+    conn: Connection,
+}
+
+impl Region {
+    /// Wraps a region object that was just created on `conn`.
+    pub(crate) fn new(id: u32, conn: &Connection) -> Region {
+        Region {
+            id,
+            conn: conn.clone(),
+        }
+    }
+
+    /// Returns the connection the region was created on.
+    pub(crate) fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    /// Returns the object ID of the region.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Adds `rect` to the region, in surface-local coordinates.
+    pub fn add(&self, rect: Rect) -> Result<()> {
+        request::add(
+            &self.conn,
+            self.id,
+            WlInt(rect.x),
+            WlInt(rect.y),
+            WlInt(rect.width),
+            WlInt(rect.height),
+        )
+    }
+
+    /// Removes `rect` from the region, in surface-local coordinates.
+    pub fn subtract(&self, rect: Rect) -> Result<()> {
+        request::subtract(
+            &self.conn,
+            self.id,
+            WlInt(rect.x),
+            WlInt(rect.y),
+            WlInt(rect.width),
+            WlInt(rect.height),
+        )
+    }
+
+    /// Destroys the region.
+    ///
+    /// Dropping the handle does the same.
+    pub fn destroy(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl Drop for Region {
+    fn drop(&mut self) {
+        self.conn
+            .destroy_on_drop(|conn| request::destroy(conn, self.id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Read, os::unix::net::UnixStream};
+
+    use super::*;
+    use crate::protocol::{
+        WlObjectId,
+        endian::Endian,
+        message::{WL_MESSAGE_HEADER_LEN, WlMessageHeader},
+    };
+
+    #[test]
+    fn region_requests_encode_their_rectangles() {
+        let (client, mut compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+
+        let id = conn.new_object(WlObjectId::Region, 1).unwrap();
+        let region = Region::new(id, &conn);
+        region.add(Rect::new(-4, 8, 16, 32)).unwrap();
+        drop(region);
+        drop(conn);
+
+        let mut received = Vec::new();
+        compositor.read_to_end(&mut received).unwrap();
+
+        // Skip `wl_display.get_registry`, queued when connecting
+        let registry = WlMessageHeader::decode(&received, Endian::NATIVE).unwrap();
+        let received = &received[registry.size as usize..];
+
+        let add = WlMessageHeader::decode(received, Endian::NATIVE).unwrap();
+        assert_eq!((add.object_id, add.opcode, add.size), (id, 1, 24));
+        let args: Vec<i32> = received[WL_MESSAGE_HEADER_LEN..24]
+            .chunks_exact(4)
+            .map(|word| i32::from_ne_bytes(word.try_into().unwrap()))
+            .collect();
+        assert_eq!(args, [-4, 8, 16, 32]);
+
+        let destroy = WlMessageHeader::decode(&received[24..], Endian::NATIVE).unwrap();
+        assert_eq!((destroy.object_id, destroy.opcode), (id, 0));
+    }
+}
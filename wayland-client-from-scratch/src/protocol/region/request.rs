@@ -0,0 +1,121 @@
+use crate::{
+    connection::Connection, error::Result, protocol::types::WlInt, wl_request_opcode,
+    wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to a Wayland region object.
+    Opcode {
+        /// Destroys the region.
+        Destroy = 0 (destructor),
+
+        /// Adds a rectangle to the region.
+        Add = 1,
+
+        /// Subtracts a rectangle from the region.
+        Subtract = 2,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wl_region.add` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="add">
+    ///   <description summary="add rectangle to region">
+    ///     Add the specified rectangle to the region.
+    ///   </description>
+    ///   <arg name="x" type="int" summary="region-local x coordinate"/>
+    ///   <arg name="y" type="int" summary="region-local y coordinate"/>
+    ///   <arg name="width" type="int" summary="rectangle width"/>
+    ///   <arg name="height" type="int" summary="rectangle height"/>
+    /// </request>
+    /// ```
+    AddParam {
+        /// The left edge of the rectangle.
+        x: WlInt,
+        /// The top edge of the rectangle.
+        y: WlInt,
+        /// The width of the rectangle.
+        width: WlInt,
+        /// The height of the rectangle.
+        height: WlInt,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wl_region.subtract` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="subtract">
+    ///   <description summary="subtract rectangle from region">
+    ///     Subtract the specified rectangle from the region.
+    ///   </description>
+    ///   <arg name="x" type="int" summary="region-local x coordinate"/>
+    ///   <arg name="y" type="int" summary="region-local y coordinate"/>
+    ///   <arg name="width" type="int" summary="rectangle width"/>
+    ///   <arg name="height" type="int" summary="rectangle height"/>
+    /// </request>
+    /// ```
+    SubtractParam {
+        /// The left edge of the rectangle.
+        x: WlInt,
+        /// The top edge of the rectangle.
+        y: WlInt,
+        /// The width of the rectangle.
+        width: WlInt,
+        /// The height of the rectangle.
+        height: WlInt,
+    }
+}
+
+/// Sends a `wl_region.destroy` request to the compositor.
+pub fn destroy(conn: &Connection, region: u32) -> Result<()> {
+    conn.send_request(region, Opcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends a `wl_region.add` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `region` - The ID of the region
+/// * `x`, `y`, `width`, `height` - The rectangle to add
+pub fn add(
+    conn: &Connection,
+    region: u32,
+    x: WlInt,
+    y: WlInt,
+    width: WlInt,
+    height: WlInt,
+) -> Result<()> {
+    conn.send_request(region, Opcode::Add, &AddParam::new(x, y, width, height))?;
+
+    Ok(())
+}
+
+/// Sends a `wl_region.subtract` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `region` - The ID of the region
+/// * `x`, `y`, `width`, `height` - The rectangle to subtract
+pub fn subtract(
+    conn: &Connection,
+    region: u32,
+    x: WlInt,
+    y: WlInt,
+    width: WlInt,
+    height: WlInt,
+) -> Result<()> {
+    conn.send_request(
+        region,
+        Opcode::Subtract,
+        &SubtractParam::new(x, y, width, height),
+    )?;
+
+    Ok(())
+}
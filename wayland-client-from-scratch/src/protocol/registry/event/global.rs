@@ -1,7 +1,10 @@
 use std::fmt::Display;
 
-use crate::protocol::types::{WL_TYPE_UINT_LEN, WlString, WlUInt};
-use anyhow::anyhow;
+use crate::{
+    connection::{Connection, GlobalChange},
+    error::{Error, Result, anyhow},
+    protocol::types::{WL_TYPE_UINT_LEN, WlString, WlUInt},
+};
 
 /// Represents a global object advertisement from the Wayland registry.
 ///
@@ -23,6 +26,7 @@ use anyhow::anyhow;
 ///   <arg name="version" type="uint" summary="interface version"/>
 /// </event>
 /// ```
+#[derive(Debug, Clone)]
 pub struct Global {
     /// The unique numeric identifier for this global object.
     ///
@@ -47,7 +51,7 @@ pub struct Global {
 }
 
 impl TryFrom<&[u8]> for Global {
-    type Error = anyhow::Error;
+    type Error = Error;
 
     /// Deserializes a `wl_registry.global` event from the Wayland wire format.
     ///
@@ -61,7 +65,7 @@ impl TryFrom<&[u8]> for Global {
     ///
     /// # Returns
     /// * `Ok(WlRegistryGlobal)` if the buffer contains valid global event data
-    /// * `Err(anyhow::Error)` if the buffer is malformed or incomplete
+    /// * `Err(Error)` if the buffer is malformed or incomplete
     ///
     /// # Buffer Layout
     /// The global event data is structured as:
@@ -75,7 +79,7 @@ impl TryFrom<&[u8]> for Global {
     /// - Buffer is too short for the interface string parsing
     /// - Buffer is too short for the version field after parsing the interface
     /// - The interface string contains invalid data or missing NUL terminator
-    fn try_from(buf: &[u8]) -> anyhow::Result<Global> {
+    fn try_from(buf: &[u8]) -> Result<Global> {
         // Extract name(u32) from buffer - the unique numeric identifier
         if buf.len() < WL_TYPE_UINT_LEN {
             return Err(anyhow!(
@@ -134,11 +138,12 @@ impl Display for Global {
 /// removed during the session.
 ///
 /// # Arguments
-/// * `buf` - The raw byte buffer containing the global event data
+/// * `conn` - The connection the event was received on
+/// * `global` - The decoded global event
 ///
 /// # Returns
-/// * `Ok(())` if the event was successfully parsed and processed
-/// * `Err(anyhow::Error)` if the event data is malformed or cannot be parsed
+/// * `Ok(())` if the event was successfully parsed and recorded
+/// * `Err(Error)` if the event data is malformed or cannot be parsed
 ///
 /// # Protocol Behavior
 /// - Global events are emitted for each available global when the registry is created
@@ -152,10 +157,12 @@ impl Display for Global {
 /// - Determine which interfaces to bind based on application needs
 /// - Check interface versions to use appropriate feature sets
 /// - Track available resources for dynamic environments
-pub(super) fn handle_wl_registry_global(buf: &[u8]) -> anyhow::Result<()> {
-    let global: Global = buf.try_into()?;
+pub(super) fn handle_wl_registry_global(conn: &Connection, global: Global) -> Result<()> {
+    // The interface stays a WlString, it is only checked in strict mode
+    conn.decode_string(&global.interface)?;
 
-    println!("{global}");
+    conn.insert_global(global.clone());
+    conn.notify_globals(GlobalChange::Added(&global));
 
     Ok(())
 }
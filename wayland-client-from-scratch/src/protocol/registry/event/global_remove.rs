@@ -1,3 +1,79 @@
-pub(super) fn handle_wl_registry_global_remove(_buf: &[u8]) -> anyhow::Result<()> {
-    todo!()
+use std::fmt::Display;
+
+use crate::{
+    connection::{Connection, GlobalChange},
+    error::{Error, Result, anyhow},
+    protocol::{
+        surface,
+        types::{WL_TYPE_UINT_LEN, WlUInt},
+    },
+};
+
+/// Represents the removal of a global object from the Wayland registry.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="global_remove">
+///   <description summary="announce removal of global object">
+///     Notify the client of removed global objects.
+///
+///     This event notifies the client that the global identified
+///     by name is no longer available. If the client bound to
+///     the global using the bind request, the client should now
+///     destroy that object.
+///   </description>
+///   <arg name="name" type="uint" summary="numeric name of the global object"/>
+/// </event>
+/// ```
+#[derive(Debug)]
+pub struct GlobalRemove {
+    /// The numeric name of the global that is no longer available.
+    pub name: WlUInt,
+}
+
+impl TryFrom<&[u8]> for GlobalRemove {
+    type Error = Error;
+
+    /// Deserializes a `wl_registry.global_remove` event from the Wayland wire format.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `name` (u32) - Numeric name of the removed global
+    fn try_from(buf: &[u8]) -> Result<GlobalRemove> {
+        if buf.len() < WL_TYPE_UINT_LEN {
+            return Err(anyhow!(
+                "Buffer too short for WlRegistryGlobalRemove name: expected {} bytes, got {}",
+                WL_TYPE_UINT_LEN,
+                buf.len()
+            ));
+        }
+        let name = WlUInt::from_bytes(buf[..WL_TYPE_UINT_LEN].try_into()?);
+
+        Ok(GlobalRemove { name })
+    }
+}
+
+impl Display for GlobalRemove {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "WlRegistryGlobalRemove {{ name: {} }}", self.name)
+    }
+}
+
+/// Handles a `wl_registry.global_remove` event.
+///
+/// Forgets the global so it is no longer offered for binding. Objects the
+/// client already bound to it stay alive until the client destroys them;
+/// surfaces on a removed output leave it, then the global watchers are
+/// notified.
+pub(super) fn handle_wl_registry_global_remove(
+    conn: &Connection,
+    global_remove: GlobalRemove,
+) -> Result<()> {
+    let global = conn.remove_global(global_remove.name.get());
+    surface::remove_output_global(conn, global_remove.name.get());
+
+    if let Some(global) = global {
+        conn.notify_globals(GlobalChange::Removed(&global));
+    }
+
+    Ok(())
 }
@@ -1,6 +1,8 @@
-use anyhow::anyhow;
-
-use crate::protocol::message::WlMessage;
+use crate::{
+    connection::Connection,
+    error::{Error, Result, anyhow},
+    protocol::message::WlMessage,
+};
 
 pub mod global;
 pub mod global_remove;
@@ -13,7 +15,7 @@ pub mod global_remove;
 /// functionality. The registry emits events to notify clients of available globals
 /// and their removal due to device hotplugs, reconfiguration, or other system events.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum Event {
+pub enum Opcode {
     /// Announces the availability of a new global object.
     ///
     /// This event notifies the client that a global object with the given name is
@@ -39,18 +41,18 @@ pub enum Event {
     GlobalRemove = 1,
 }
 
-impl From<Event> for u16 {
+impl From<Opcode> for u16 {
     /// Converts a `WlRegistryEvent` variant to its corresponding protocol opcode.
     ///
     /// # Returns
     /// The numeric opcode value used in Wayland protocol messages for this event type.
-    fn from(value: Event) -> u16 {
+    fn from(value: Opcode) -> u16 {
         value as u16
     }
 }
 
-impl TryFrom<u16> for Event {
-    type Error = anyhow::Error;
+impl TryFrom<u16> for Opcode {
+    type Error = Error;
 
     /// Attempts to convert a raw opcode value into a structured `WlRegistryEvent`.
     ///
@@ -59,20 +61,63 @@ impl TryFrom<u16> for Event {
     ///
     /// # Returns
     /// * `Ok(WlRegistryEvent)` if the opcode corresponds to a known registry event type
-    /// * `Err(anyhow::Error)` if the opcode is unrecognized
+    /// * `Err(Error)` if the opcode is unrecognized
     ///
     /// # Protocol Context
     /// The registry object uses opcodes 0 for global announcements and 1 for global
     /// removal notifications as defined in the Wayland protocol specification.
-    fn try_from(value: u16) -> anyhow::Result<Self> {
+    fn try_from(value: u16) -> Result<Self> {
         match value {
-            0 => Ok(Event::Global),
-            1 => Ok(Event::GlobalRemove),
+            0 => Ok(Opcode::Global),
+            1 => Ok(Opcode::GlobalRemove),
             _ => Err(anyhow!("Invalid wl_registry event opcode: {}", value)),
         }
     }
 }
 
+impl Opcode {
+    /// Returns the interface version the event was introduced in.
+    pub fn since(self) -> u32 {
+        match self {
+            Opcode::Global | Opcode::GlobalRemove => 1,
+        }
+    }
+}
+
+/// A decoded `wl_registry` event, with its arguments.
+#[derive(Debug)]
+pub enum Event {
+    /// Announces the availability of a new global object.
+    Global(global::Global),
+    /// Announces the removal of a previously advertised global object.
+    GlobalRemove(global_remove::GlobalRemove),
+}
+
+impl Event {
+    /// Decodes a `wl_registry` event from its message.
+    ///
+    /// # Errors
+    /// Returns an error if the opcode is unknown or the arguments are malformed.
+    pub fn decode(_conn: &Connection, msg: &WlMessage) -> Result<Event> {
+        let buf: &[u8] = &msg.data;
+
+        Ok(match Opcode::try_from(msg.header.opcode)? {
+            Opcode::Global => Event::Global(global::Global::try_from(buf)?),
+            Opcode::GlobalRemove => {
+                Event::GlobalRemove(global_remove::GlobalRemove::try_from(buf)?)
+            }
+        })
+    }
+
+    /// Returns the opcode of the event.
+    pub fn opcode(&self) -> Opcode {
+        match self {
+            Event::Global(_) => Opcode::Global,
+            Event::GlobalRemove(_) => Opcode::GlobalRemove,
+        }
+    }
+}
+
 /// Dispatches incoming Wayland registry events to their appropriate handler functions.
 ///
 /// This function serves as the main entry point for processing events targeted at
@@ -80,11 +125,12 @@ impl TryFrom<u16> for Event {
 /// the message data to the corresponding event handler.
 ///
 /// # Arguments
+/// * `conn` - The connection the event was received on
 /// * `msg` - The complete Wayland message containing both header and payload data
 ///
 /// # Returns
 /// * `Ok(())` if the event was successfully processed
-/// * `Err(anyhow::Error)` if the event opcode is invalid or event processing fails
+/// * `Err(Error)` if the event opcode is invalid or event processing fails
 ///
 /// # Event Routing
 /// * `Global` events are routed to `global::handle_wl_registry_global`
@@ -95,13 +141,10 @@ impl TryFrom<u16> for Event {
 /// `Global` events for all currently available globals. The client can mark the end
 /// of this initial burst by using `wl_display.sync` after calling `wl_display.get_registry`.
 /// Subsequent global additions and removals are communicated via additional events.
-pub fn handle_wl_registry_event(msg: WlMessage) -> anyhow::Result<()> {
-    // Decode the event type from the message opcode
-    let event_code: Event = msg.header.opcode.try_into()?;
-
+pub fn handle_wl_registry_event(conn: &Connection, msg: WlMessage) -> Result<()> {
     // Route the event to the appropriate handler based on type
-    match event_code {
-        Event::Global => global::handle_wl_registry_global(&msg.data),
-        Event::GlobalRemove => global_remove::handle_wl_registry_global_remove(&msg.data),
+    match Event::decode(conn, &msg)? {
+        Event::Global(event) => global::handle_wl_registry_global(conn, event),
+        Event::GlobalRemove(event) => global_remove::handle_wl_registry_global_remove(conn, event),
     }
 }
@@ -1,2 +1,48 @@
 pub mod event;
 pub mod request;
+
+use crate::{connection::Connection, error::Result, protocol::WlObjectId};
+
+/// The `wl_registry` of a connection, which advertises the globals of the
+/// compositor.
+///
+/// Obtained with [`Connection::registry`]. The typed proxies, such as
+/// [`Compositor::bind`](crate::protocol::compositor::Compositor::bind), bind
+/// their globals on their own; the registry is for picking a specific global
+/// among several, or for interfaces this crate has no proxy for.
+#[derive(Clone)]
+pub struct Registry {
+    /// The object ID of the registry.
+    id: u32,
+    /// The connection the registry belongs to.
+    conn: Connection,
+}
+
+impl Registry {
+    /// Wraps the registry object `id` of `conn`.
+    pub(crate) fn new(id: u32, conn: &Connection) -> Registry {
+        Registry {
+            id,
+            conn: conn.clone(),
+        }
+    }
+
+    /// Returns the object ID of the registry.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Binds the global with the numeric `name`, as listed by
+    /// [`Connection::globals`], at the lower of `version` and the
+    /// advertised version.
+    ///
+    /// # Returns
+    /// The new object ID and the negotiated version.
+    ///
+    /// # Errors
+    /// Returns an error if no global with this name is advertised, if it
+    /// does not implement `interface`, or if the registry was destroyed.
+    pub fn bind(&self, name: u32, interface: WlObjectId, version: u32) -> Result<(u32, u32)> {
+        self.conn.bind_global(interface, name, version)
+    }
+}
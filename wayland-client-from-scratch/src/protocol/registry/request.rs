@@ -0,0 +1,54 @@
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::types::{WlUInt, WlUntypedNewId},
+    wl_request_opcode, wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to the Wayland registry object.
+    Opcode {
+        /// Binds a new, client-created object to a global advertised by the registry.
+        Bind = 0,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wl_registry.bind` request.
+    ///
+    /// The `id` argument is an untyped `new_id`, so on the wire it is preceded by
+    /// the interface name and version the client wants to bind, see
+    /// [`WlUntypedNewId`].
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="bind">
+    ///   <description summary="bind an object to the display">
+    ///     Binds a new, client-created object to the server using the
+    ///     specified name as the identifier.
+    ///   </description>
+    ///   <arg name="name" type="uint" summary="unique numeric name of the object"/>
+    ///   <arg name="id" type="new_id" summary="bounded object"/>
+    /// </request>
+    /// ```
+    BindParam {
+        /// The numeric name of the global to bind.
+        name: WlUInt,
+        /// The interface, version and object ID of the newly bound object.
+        id: WlUntypedNewId,
+    }
+}
+
+/// Sends a `wl_registry.bind` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `registry` - The ID of the registry that advertised the global
+/// * `name` - The numeric name of the global to bind
+/// * `id` - The interface of the global, the version to bind, at most the
+///   advertised version, and the object ID to assign to the newly bound object
+pub fn bind(conn: &Connection, registry: u32, name: WlUInt, id: WlUntypedNewId) -> Result<()> {
+    conn.send_request(registry, Opcode::Bind, &BindParam::new(name, id))?;
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+pub mod relative_motion;
+
+use crate::{
+    connection::Connection,
+    error::{Error, Result, anyhow},
+    protocol::message::WlMessage,
+};
+
+/// Represents the event types that can be emitted by a `zwp_relative_pointer_v1` object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    /// The pointer moved, regardless of pointer locks and screen edges.
+    ///
+    /// # Event Arguments
+    /// - `utime_hi`, `utime_lo`: The timestamp in microseconds
+    /// - `dx`, `dy`: The accelerated motion
+    /// - `dx_unaccel`, `dy_unaccel`: The unaccelerated motion
+    RelativeMotion = 0,
+}
+
+impl TryFrom<u16> for Opcode {
+    type Error = Error;
+
+    /// Attempts to convert a raw opcode value into a structured `ZwpRelativePointerEvent`.
+    fn try_from(value: u16) -> Result<Opcode> {
+        match value {
+            0 => Ok(Opcode::RelativeMotion),
+            _ => Err(anyhow!(
+                "Invalid zwp_relative_pointer_v1 event opcode: {}",
+                value
+            )),
+        }
+    }
+}
+
+impl Opcode {
+    /// Returns the interface version the event was introduced in.
+    pub fn since(self) -> u32 {
+        match self {
+            Opcode::RelativeMotion => 1,
+        }
+    }
+}
+
+/// A decoded `zwp_relative_pointer_v1` event, with its arguments.
+#[derive(Debug)]
+pub enum Event {
+    /// The pointer moved, regardless of pointer locks and screen edges.
+    RelativeMotion(relative_motion::RelativeMotion),
+}
+
+impl Event {
+    /// Decodes a `zwp_relative_pointer_v1` event from its message.
+    ///
+    /// # Errors
+    /// Returns an error if the opcode is unknown or the arguments are malformed.
+    pub fn decode(_conn: &Connection, msg: &WlMessage) -> Result<Event> {
+        let buf: &[u8] = &msg.data;
+
+        Ok(match Opcode::try_from(msg.header.opcode)? {
+            Opcode::RelativeMotion => {
+                Event::RelativeMotion(relative_motion::RelativeMotion::try_from(buf)?)
+            }
+        })
+    }
+
+    /// Returns the opcode of the event.
+    pub fn opcode(&self) -> Opcode {
+        match self {
+            Event::RelativeMotion(_) => Opcode::RelativeMotion,
+        }
+    }
+}
+
+/// Dispatches incoming `zwp_relative_pointer_v1` events to their appropriate handler functions.
+///
+/// # Event Routing
+/// * `RelativeMotion` events are routed to `relative_motion::handle_zwp_relative_pointer_relative_motion`
+pub fn handle_zwp_relative_pointer_event(conn: &Connection, msg: WlMessage) -> Result<()> {
+    // Route the event to the appropriate handler based on type
+    match Event::decode(conn, &msg)? {
+        Event::RelativeMotion(event) => {
+            relative_motion::handle_zwp_relative_pointer_relative_motion(
+                conn,
+                msg.header.object_id,
+                event,
+            )
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use std::time::Duration;
+
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::types::{WL_TYPE_FIXED_LEN, WL_TYPE_UINT_LEN, WlFixed, WlUInt},
+};
+
+/// Represents a `zwp_relative_pointer_v1.relative_motion` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="relative_motion">
+///   <description summary="relative pointer motion">
+///     Relative x/y pointer motion from the pointer of the seat associated with
+///     this object.
+///
+///     A relative motion is in the same dimension as regular wl_pointer motion
+///     events, except they do not represent an absolute position. For example,
+///     moving a pointer from (x, y) to (x', y') would have the equivalent
+///     relative motion (x' - x, y' - y). If a pointer motion caused the
+///     absolute pointer position to be clipped by for example the edge of the
+///     monitor, the relative motion is unaffected by the clipping and will
+///     represent the unclipped motion.
+///
+///     This event also contains non-accelerated motion deltas. The
+///     non-accelerated delta is, when applicable, the regular pointer motion
+///     delta as it was before having applied motion acceleration and other
+///     transformations such as normalization.
+///
+///     Note that the non-accelerated delta does not represent 'raw' events as
+///     they were read from some device. Pointer motion acceleration is device-
+///     and configuration-specific and non-accelerated deltas and accelerated
+///     deltas may have the same value on some devices.
+///
+///     Relative motions are not coupled to wl_pointer.motion events, and can be
+///     sent in combination with such events, but also independently. There may
+///     also be scenarios where wl_pointer.motion is sent, but there is no
+///     relative motion. The order of an absolute and relative motion event
+///     originating from the same physical motion is not guaranteed.
+///
+///     If the client needs button events or focus state, it can receive them
+///     from a wl_pointer object of the same seat that the wp_relative_pointer
+///     object is associated with.
+///   </description>
+///   <arg name="utime_hi" type="uint"
+///        summary="high 32 bits of a 64 bit timestamp with microsecond granularity"/>
+///   <arg name="utime_lo" type="uint"
+///        summary="low 32 bits of a 64 bit timestamp with microsecond granularity"/>
+///   <arg name="dx" type="fixed"
+///        summary="the x component of the motion vector"/>
+///   <arg name="dy" type="fixed"
+///        summary="the y component of the motion vector"/>
+///   <arg name="dx_unaccel" type="fixed"
+///        summary="the x component of the unaccelerated motion vector"/>
+///   <arg name="dy_unaccel" type="fixed"
+///        summary="the y component of the unaccelerated motion vector"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RelativeMotion {
+    /// The high 32 bits of the timestamp in microseconds.
+    pub utime_hi: WlUInt,
+    /// The low 32 bits of the timestamp in microseconds.
+    pub utime_lo: WlUInt,
+    /// The horizontal motion, with acceleration applied.
+    pub dx: WlFixed,
+    /// The vertical motion, with acceleration applied.
+    pub dy: WlFixed,
+    /// The horizontal motion before acceleration.
+    pub dx_unaccel: WlFixed,
+    /// The vertical motion before acceleration.
+    pub dy_unaccel: WlFixed,
+}
+
+impl RelativeMotion {
+    /// Returns the timestamp of the motion, with an undefined base.
+    pub fn time(&self) -> Duration {
+        Duration::from_micros((u64::from(self.utime_hi.0) << 32) | u64::from(self.utime_lo.0))
+    }
+}
+
+impl TryFrom<&[u8]> for RelativeMotion {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `RelativeMotion` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `utime_hi` (u32) - The high 32 bits of the timestamp
+    /// - Bytes 4-7: `utime_lo` (u32) - The low 32 bits of the timestamp
+    /// - Bytes 8-15: `dx`, `dy` (fixed) - The accelerated motion
+    /// - Bytes 16-23: `dx_unaccel`, `dy_unaccel` (fixed) - The unaccelerated motion
+    fn try_from(buf: &[u8]) -> Result<RelativeMotion> {
+        let motion = 2 * WL_TYPE_UINT_LEN;
+
+        Ok(RelativeMotion {
+            utime_hi: WlUInt::read(buf, 0)?,
+            utime_lo: WlUInt::read(buf, WL_TYPE_UINT_LEN)?,
+            dx: WlFixed::read(buf, motion)?,
+            dy: WlFixed::read(buf, motion + WL_TYPE_FIXED_LEN)?,
+            dx_unaccel: WlFixed::read(buf, motion + 2 * WL_TYPE_FIXED_LEN)?,
+            dy_unaccel: WlFixed::read(buf, motion + 3 * WL_TYPE_FIXED_LEN)?,
+        })
+    }
+}
+
+/// Handles a `zwp_relative_pointer_v1.relative_motion` event by notifying the listener.
+pub(super) fn handle_zwp_relative_pointer_relative_motion(
+    conn: &Connection,
+    relative_pointer_id: u32,
+    event: RelativeMotion,
+) -> Result<()> {
+    conn.emit(relative_pointer_id, event);
+
+    Ok(())
+}
@@ -0,0 +1,118 @@
+pub mod event;
+pub mod request;
+
+use crate::{
+    connection::{Connection, Listener},
+    error::Result,
+    protocol::{
+        WlObjectId,
+        pointer::Pointer,
+        types::{WlNewId, WlObject},
+    },
+};
+
+use event::relative_motion::RelativeMotion;
+
+/// The highest `zwp_relative_pointer_manager_v1` version this crate implements.
+pub const ZWP_RELATIVE_POINTER_MANAGER_VERSION: u32 = 1;
+
+/// A bound `zwp_relative_pointer_manager_v1` global, which reports pointer
+/// motion unaffected by locks and screen edges.
+pub struct RelativePointerManager {
+    /// The object ID of the bound manager.
+    id: u32,
+    /// The negotiated interface version.
+    version: u32,
+    /// The connection the manager was bound on.
+    conn: Connection,
+}
+
+impl RelativePointerManager {
+    /// Binds the `zwp_relative_pointer_manager_v1` global advertised by the compositor.
+    pub fn bind(conn: &Connection) -> Result<RelativePointerManager> {
+        let (id, version) = conn.bind(
+            WlObjectId::RelativePointerManager,
+            ZWP_RELATIVE_POINTER_MANAGER_VERSION,
+        )?;
+
+        Ok(RelativePointerManager {
+            id,
+            version,
+            conn: conn.clone(),
+        })
+    }
+
+    /// Returns the object ID of the manager.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Subscribes to the relative motion of `pointer`, which `handler`
+    /// receives while a surface of the client has the pointer focus.
+    pub fn get_relative_pointer<F>(&self, pointer: &Pointer, handler: F) -> Result<RelativePointer>
+    where
+        F: FnMut(&Connection, RelativeMotion) + Send + 'static,
+    {
+        let id = self
+            .conn
+            .new_object(WlObjectId::RelativePointer, self.version)?;
+        request::get_relative_pointer(&self.conn, self.id, WlNewId(id), WlObject(pointer.id()))?;
+
+        let listener: Listener<RelativeMotion> = Box::new(handler);
+        self.conn.set_listener(id, listener);
+
+        Ok(RelativePointer {
+            id,
+            conn: self.conn.clone(),
+        })
+    }
+
+    /// Destroys the manager; existing relative pointers keep working.
+    ///
+    /// Dropping the handle does the same.
+    pub fn destroy(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl Drop for RelativePointerManager {
+    fn drop(&mut self) {
+        self.conn
+            .destroy_on_drop(|conn| request::destroy(conn, self.id));
+    }
+}
+
+/// A `zwp_relative_pointer_v1`, reporting the relative motion of a pointer.
+pub struct RelativePointer {
+    /// The object ID of the relative pointer.
+    id: u32,
+    /// The connection the relative pointer was created on.
+    conn: Connection,
+}
+
+impl RelativePointer {
+    /// Returns the object ID of the relative pointer.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Destroys the relative pointer; events still in flight are ignored.
+    ///
+    /// Dropping the handle does the same.
+    pub fn destroy(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl Drop for RelativePointer {
+    fn drop(&mut self) {
+        self.conn.remove_listener(self.id);
+
+        self.conn
+            .destroy_on_drop(|conn| request::destroy_relative_pointer(conn, self.id));
+    }
+}
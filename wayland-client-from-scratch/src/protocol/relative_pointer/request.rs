@@ -0,0 +1,83 @@
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::types::{WlNewId, WlObject},
+    wl_request_opcode, wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to the `zwp_relative_pointer_manager_v1` object.
+    Opcode {
+        /// Destroys the manager; existing relative pointers keep working.
+        Destroy = 0 (destructor),
+
+        /// Creates the relative pointer of a pointer.
+        GetRelativePointer = 1,
+    }
+}
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to a `zwp_relative_pointer_v1` object.
+    RelativePointerOpcode {
+        /// Destroys the relative pointer.
+        Destroy = 0 (destructor),
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `zwp_relative_pointer_manager_v1.get_relative_pointer` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="get_relative_pointer">
+    ///   <description summary="get a relative pointer object">
+    ///     Create a relative pointer interface given a wl_pointer object. See the
+    ///     wp_relative_pointer interface for more details.
+    ///   </description>
+    ///   <arg name="id" type="new_id" interface="zwp_relative_pointer_v1"/>
+    ///   <arg name="pointer" type="object" interface="wl_pointer"/>
+    /// </request>
+    /// ```
+    GetRelativePointerParam {
+        /// The object ID to assign to the new relative pointer.
+        id: WlNewId,
+        /// The pointer to report the relative motion of.
+        pointer: WlObject,
+    }
+}
+
+/// Sends a `zwp_relative_pointer_manager_v1.destroy` request to the compositor.
+pub fn destroy(conn: &Connection, manager: u32) -> Result<()> {
+    conn.send_request(manager, Opcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends a `zwp_relative_pointer_manager_v1.get_relative_pointer` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `manager` - The ID of the bound relative pointer manager
+/// * `id` - The object ID to assign to the new relative pointer
+/// * `pointer` - The pointer to report the relative motion of
+pub fn get_relative_pointer(
+    conn: &Connection,
+    manager: u32,
+    id: WlNewId,
+    pointer: WlObject,
+) -> Result<()> {
+    conn.send_request(
+        manager,
+        Opcode::GetRelativePointer,
+        &GetRelativePointerParam::new(id, pointer),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `zwp_relative_pointer_v1.destroy` request to the compositor.
+pub fn destroy_relative_pointer(conn: &Connection, relative_pointer: u32) -> Result<()> {
+    conn.send_request(relative_pointer, RelativePointerOpcode::Destroy, &())?;
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        seat::{SeatCapabilities, SeatEvent, SeatState},
+        types::WlUInt,
+    },
+};
+
+/// Represents a `wl_seat.capabilities` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="capabilities">
+///   <description summary="seat capabilities changed">
+///     This is sent on binding to the seat global or whenever a seat gains
+///     or loses the pointer, keyboard or touch capabilities.
+///     The argument is a capability enum containing the complete set of
+///     capabilities this seat has.
+///   </description>
+///   <arg name="capabilities" type="uint" enum="capability" summary="capabilities of the seat"/>
+/// </event>
+/// ```
+#[derive(Debug)]
+pub struct Capabilities {
+    /// The complete set of capabilities, see [`SeatCapabilities`].
+    pub capabilities: WlUInt,
+}
+
+impl TryFrom<&[u8]> for Capabilities {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Capabilities` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `capabilities` (u32) - The capability bitmask
+    fn try_from(buf: &[u8]) -> Result<Capabilities> {
+        Ok(Capabilities {
+            capabilities: WlUInt::read(buf, 0)?,
+        })
+    }
+}
+
+/// Handles a `wl_seat.capabilities` event by recording the new capability set
+/// and notifying the listener.
+pub(super) fn handle_wl_seat_capabilities(
+    conn: &Connection,
+    seat_id: u32,
+    event: Capabilities,
+) -> Result<()> {
+    let capabilities = SeatCapabilities::from_bits(event.capabilities.get());
+
+    if let Some(state) = conn.object_data::<SeatState>(seat_id) {
+        state.capabilities.set(capabilities);
+        conn.emit(seat_id, SeatEvent::Capabilities(capabilities));
+    }
+
+    Ok(())
+}
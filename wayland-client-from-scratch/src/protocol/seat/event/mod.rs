@@ -0,0 +1,94 @@
+pub mod capabilities;
+pub mod name;
+
+use crate::{
+    connection::Connection,
+    error::{Error, Result, anyhow},
+    protocol::message::WlMessage,
+};
+
+/// Represents the event types that can be emitted by a Wayland seat object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    /// The seat gained or lost input device capabilities.
+    ///
+    /// # Event Arguments
+    /// - `capabilities`: The complete set of capabilities of the seat
+    Capabilities = 0,
+
+    /// The name identifying the seat (since version 2).
+    ///
+    /// # Event Arguments
+    /// - `name`: The seat identifier
+    Name = 1,
+}
+
+impl TryFrom<u16> for Opcode {
+    type Error = Error;
+
+    /// Attempts to convert a raw opcode value into a structured `WlSeatEvent`.
+    fn try_from(value: u16) -> Result<Opcode> {
+        match value {
+            0 => Ok(Opcode::Capabilities),
+            1 => Ok(Opcode::Name),
+            _ => Err(anyhow!("Invalid wl_seat event opcode: {}", value)),
+        }
+    }
+}
+
+impl Opcode {
+    /// Returns the interface version the event was introduced in.
+    pub fn since(self) -> u32 {
+        match self {
+            Opcode::Capabilities => 1,
+            Opcode::Name => 2,
+        }
+    }
+}
+
+/// A decoded `wl_seat` event, with its arguments.
+#[derive(Debug)]
+pub enum Event {
+    /// The seat gained or lost input device capabilities.
+    Capabilities(capabilities::Capabilities),
+    /// The name identifying the seat (since version 2).
+    Name(name::Name),
+}
+
+impl Event {
+    /// Decodes a `wl_seat` event from its message.
+    ///
+    /// # Errors
+    /// Returns an error if the opcode is unknown or the arguments are malformed.
+    pub fn decode(_conn: &Connection, msg: &WlMessage) -> Result<Event> {
+        let buf: &[u8] = &msg.data;
+
+        Ok(match Opcode::try_from(msg.header.opcode)? {
+            Opcode::Capabilities => Event::Capabilities(capabilities::Capabilities::try_from(buf)?),
+            Opcode::Name => Event::Name(name::Name::try_from(buf)?),
+        })
+    }
+
+    /// Returns the opcode of the event.
+    pub fn opcode(&self) -> Opcode {
+        match self {
+            Event::Capabilities(_) => Opcode::Capabilities,
+            Event::Name(_) => Opcode::Name,
+        }
+    }
+}
+
+/// Dispatches incoming Wayland seat events to their appropriate handler functions.
+///
+/// # Event Routing
+/// * `Capabilities` events are routed to `capabilities::handle_wl_seat_capabilities`
+/// * `Name` events are routed to `name::handle_wl_seat_name`
+pub fn handle_wl_seat_event(conn: &Connection, msg: WlMessage) -> Result<()> {
+    // Route the event to the appropriate handler based on type
+    match Event::decode(conn, &msg)? {
+        Event::Capabilities(event) => {
+            capabilities::handle_wl_seat_capabilities(conn, msg.header.object_id, event)
+        }
+        Event::Name(event) => name::handle_wl_seat_name(conn, msg.header.object_id, event),
+    }
+}
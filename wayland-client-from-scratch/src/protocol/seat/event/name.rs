@@ -0,0 +1,57 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        seat::{SeatEvent, SeatState},
+        types::WlString,
+    },
+};
+
+/// Represents a `wl_seat.name` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="name" since="2">
+///   <description summary="unique identifier for this seat">
+///     In a multi-seat configuration the seat name can be used by clients to
+///     help identify which physical devices the seat represents.
+///
+///     The seat name is a UTF-8 string with no convention defined for its
+///     contents. Each name is unique among all wl_seat globals. The name is
+///     only guaranteed to be unique for the current compositor instance.
+///   </description>
+///   <arg name="name" type="string" summary="seat identifier"/>
+/// </event>
+/// ```
+#[derive(Debug)]
+pub struct Name {
+    /// The seat identifier.
+    pub name: WlString,
+}
+
+impl TryFrom<&[u8]> for Name {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Name` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0+: `name` (WlString) - The seat identifier with length prefix
+    fn try_from(buf: &[u8]) -> Result<Name> {
+        Ok(Name {
+            name: buf.try_into()?,
+        })
+    }
+}
+
+/// Handles a `wl_seat.name` event by recording the seat name and
+/// notifying the listener.
+pub(super) fn handle_wl_seat_name(conn: &Connection, seat_id: u32, event: Name) -> Result<()> {
+    let name = conn.decode_string(&event.name)?;
+
+    if let Some(state) = conn.object_data::<SeatState>(seat_id) {
+        *state.name.borrow_mut() = Some(name.clone());
+        conn.emit(seat_id, SeatEvent::Name(name));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,443 @@
+pub mod event;
+pub mod request;
+
+use std::sync::Arc;
+
+use crate::{
+    connection::Connection,
+    error::{Result, anyhow},
+    protocol::{
+        WlObjectId,
+        keyboard::{Keyboard, KeyboardEvent},
+        pointer::{Pointer, PointerFrame},
+        touch::{Touch, TouchFrame},
+        types::WlNewId,
+    },
+    sync::{Lock, LockCell},
+    wl_bitfield, wl_enum,
+};
+
+/// The highest `wl_seat` version this crate implements.
+///
+/// Version 10 lets the compositor send repeated keys itself; this crate
+/// repeats keys on the client side instead, so it stops at version 9.
+pub const WL_SEAT_VERSION: u32 = 9;
+
+wl_enum! {
+    /// Input device capabilities of a seat, combined as a bitmask.
+    Capability {
+        /// The seat has pointer devices.
+        Pointer = 1,
+        /// The seat has one or more keyboards.
+        Keyboard = 2,
+        /// The seat has touch devices.
+        Touch = 4,
+    }
+}
+
+wl_bitfield! {
+    /// The set of input device capabilities of a seat, as announced by
+    /// `wl_seat.capabilities`.
+    SeatCapabilities {
+        /// The seat has pointer devices.
+        POINTER = 1,
+        /// The seat has one or more keyboards.
+        KEYBOARD = 2,
+        /// The seat has touch devices.
+        TOUCH = 4,
+    }
+}
+
+impl From<Capability> for SeatCapabilities {
+    fn from(capability: Capability) -> Self {
+        SeatCapabilities::from_bits(capability as u32)
+    }
+}
+
+/// An event delivered to the handler of a [`Seat`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SeatEvent {
+    /// The capabilities changed, or were announced after binding.
+    Capabilities(SeatCapabilities),
+    /// The seat name was announced.
+    Name(String),
+    /// The keyboard focus moved to the surface with this object ID, or left
+    /// the surfaces of the client if `None`, see [`Seat::keyboard_focus`].
+    KeyboardFocus(Option<u32>),
+}
+
+/// The surface holding the keyboard focus of a seat.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyboardFocus {
+    /// The object ID of the focused surface.
+    pub surface: u32,
+    /// The serial of the `wl_keyboard.enter` event.
+    pub serial: u32,
+    /// The Linux evdev codes of the keys held down, starting with those
+    /// listed by the `wl_keyboard.enter` event.
+    pub pressed: Vec<u32>,
+}
+
+/// State of a seat updated by its events.
+#[derive(Default)]
+pub(crate) struct SeatState {
+    /// The object ID of the seat, which receives the focus events.
+    id: u32,
+    /// The current capabilities.
+    capabilities: LockCell<SeatCapabilities>,
+    /// The seat name, once announced.
+    name: Lock<Option<String>>,
+    /// The serial of the latest input event received on the seat's devices.
+    serial: LockCell<u32>,
+    /// The serial of the latest pointer button press or touch down.
+    grab_serial: LockCell<Option<u32>>,
+    /// The surface holding the keyboard focus, if it is one of the client.
+    keyboard_focus: Lock<Option<KeyboardFocus>>,
+}
+
+impl SeatState {
+    /// Returns the serial of the latest input event, as required by requests
+    /// such as `wl_data_device.set_selection`.
+    pub(crate) fn serial(&self) -> u32 {
+        self.serial.get()
+    }
+
+    /// Records the serial of an input event received on one of the seat's devices.
+    pub(crate) fn set_serial(&self, serial: u32) {
+        self.serial.set(serial);
+    }
+
+    /// Returns the serial of the latest pointer button press or touch down,
+    /// the events compositors accept to start an interactive move or resize.
+    pub(crate) fn grab_serial(&self) -> Option<u32> {
+        self.grab_serial.get()
+    }
+
+    /// Records the serial of a pointer button press or touch down.
+    pub(crate) fn set_grab_serial(&self, serial: u32) {
+        self.serial.set(serial);
+        self.grab_serial.set(Some(serial));
+    }
+
+    /// Records that the keyboard focus entered `focus.surface`, then
+    /// notifies the seat handler.
+    pub(crate) fn focus_keyboard(&self, conn: &Connection, focus: KeyboardFocus) {
+        let surface = focus.surface;
+        self.keyboard_focus.replace(Some(focus));
+
+        conn.emit(self.id, SeatEvent::KeyboardFocus(Some(surface)));
+    }
+
+    /// Records that the keyboard focus left `surface`, then notifies the
+    /// seat handler if `surface` had the focus.
+    pub(crate) fn unfocus_keyboard(&self, conn: &Connection, surface: u32) {
+        let focused = self
+            .keyboard_focus
+            .borrow()
+            .as_ref()
+            .is_some_and(|focus| focus.surface == surface);
+        if focused {
+            self.keyboard_focus.replace(None);
+            conn.emit(self.id, SeatEvent::KeyboardFocus(None));
+        }
+    }
+
+    /// Records a key press or release on the focused surface.
+    pub(crate) fn update_pressed_key(&self, key: u32, pressed: bool) {
+        if let Some(focus) = self.keyboard_focus.borrow_mut().as_mut() {
+            focus.pressed.retain(|&held| held != key);
+            if pressed {
+                focus.pressed.push(key);
+            }
+        }
+    }
+}
+
+/// A bound `wl_seat` global, a group of input devices used by one user.
+///
+/// Capabilities and the name are sent by the compositor right after binding,
+/// so they are known after the next [`Connection::roundtrip`].
+///
+/// `Seat` is a cheap handle; clones refer to the same seat.
+#[derive(Clone)]
+pub struct Seat {
+    /// The object ID of the bound seat.
+    id: u32,
+    /// The numeric name of the `wl_seat` global.
+    global_name: u32,
+    /// The negotiated interface version.
+    version: u32,
+    /// The connection the seat was bound on.
+    conn: Connection,
+    /// The state shared with the event handlers.
+    state: Arc<SeatState>,
+}
+
+impl Seat {
+    /// Binds the first `wl_seat` global advertised by the compositor.
+    pub fn bind(conn: &Connection) -> Result<Seat> {
+        let global_name = Seat::global_names(conn).next().ok_or_else(|| {
+            anyhow!(
+                "Compositor does not advertise {}",
+                WlObjectId::Seat.interface_name()
+            )
+        })?;
+
+        Seat::bind_global(conn, global_name)
+    }
+
+    /// Binds the `wl_seat` global with the numeric name `global_name`.
+    pub fn bind_global(conn: &Connection, global_name: u32) -> Result<Seat> {
+        let (id, version) = conn.bind_global(WlObjectId::Seat, global_name, WL_SEAT_VERSION)?;
+
+        let state = Arc::new(SeatState {
+            id,
+            ..SeatState::default()
+        });
+        conn.set_object_data(id, state.clone());
+
+        Ok(Seat {
+            id,
+            global_name,
+            version,
+            conn: conn.clone(),
+            state,
+        })
+    }
+
+    /// Binds every `wl_seat` global advertised by the compositor, for
+    /// setups where several users share one session.
+    pub fn bind_all(conn: &Connection) -> Result<Vec<Seat>> {
+        Seat::global_names(conn)
+            .map(|global_name| Seat::bind_global(conn, global_name))
+            .collect()
+    }
+
+    /// Returns the numeric names of the advertised `wl_seat` globals, in
+    /// ascending order.
+    fn global_names(conn: &Connection) -> impl Iterator<Item = u32> {
+        let mut names: Vec<u32> = conn
+            .globals()
+            .into_iter()
+            .filter(|global| global.interface.as_str() == WlObjectId::Seat.interface_name())
+            .map(|global| global.name.get())
+            .collect();
+        names.sort_unstable();
+
+        names.into_iter()
+    }
+
+    /// Returns the object ID of the seat.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the numeric name of the `wl_seat` global.
+    pub fn global_name(&self) -> u32 {
+        self.global_name
+    }
+
+    /// Returns the negotiated interface version.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Returns the current capabilities.
+    pub fn capabilities(&self) -> SeatCapabilities {
+        self.state.capabilities.get()
+    }
+
+    /// Returns `true` if the seat currently has the given capability.
+    pub fn has_capability(&self, capability: Capability) -> bool {
+        self.capabilities().contains(capability.into())
+    }
+
+    /// Returns the seat name, if the compositor announced one.
+    pub fn name(&self) -> Option<String> {
+        self.state.name.borrow().clone()
+    }
+
+    /// Returns the surface holding the keyboard focus of the seat, if it is
+    /// one of the client, with the keys held down on it.
+    ///
+    /// Changes are announced with [`SeatEvent::KeyboardFocus`]; they are
+    /// only tracked while a [`Keyboard`] of the seat exists.
+    pub fn keyboard_focus(&self) -> Option<KeyboardFocus> {
+        self.state.keyboard_focus.borrow().clone()
+    }
+
+    /// Registers the handler receiving the events of the seat, replacing
+    /// the previous one.
+    pub fn set_event_handler<F>(&self, handler: F)
+    where
+        F: FnMut(&Connection, SeatEvent) + Send + 'static,
+    {
+        self.conn
+            .set_listener::<SeatEvent>(self.id, Box::new(handler));
+    }
+
+    /// Returns the state shared with the seat's devices.
+    pub(crate) fn state(&self) -> Arc<SeatState> {
+        self.state.clone()
+    }
+
+    /// Creates the pointer of the seat.
+    ///
+    /// `handler` receives one [`PointerFrame`] per `wl_pointer.frame`,
+    /// combining all pointer events that logically belong together.
+    ///
+    /// # Errors
+    /// Returns an error if the seat has no pointer capability.
+    pub fn get_pointer<F>(&self, handler: F) -> Result<Pointer>
+    where
+        F: FnMut(&Connection, PointerFrame) + Send + 'static,
+    {
+        if !self.has_capability(Capability::Pointer) {
+            return Err(anyhow!("Seat {} has no pointer", self.id));
+        }
+
+        let id = self.conn.new_object(WlObjectId::Pointer, self.version)?;
+        request::get_pointer(&self.conn, self.id, WlNewId(id))?;
+
+        Ok(Pointer::new(
+            id,
+            self.version,
+            &self.conn,
+            self.state.clone(),
+            Box::new(handler),
+        ))
+    }
+
+    /// Creates the keyboard of the seat.
+    ///
+    /// `handler` receives every keyboard event, including the key repeats
+    /// synthesized from `wl_keyboard.repeat_info` while a key is held.
+    ///
+    /// # Errors
+    /// Returns an error if the seat has no keyboard capability.
+    pub fn get_keyboard<F>(&self, handler: F) -> Result<Keyboard>
+    where
+        F: FnMut(&Connection, KeyboardEvent) + Send + 'static,
+    {
+        if !self.has_capability(Capability::Keyboard) {
+            return Err(anyhow!("Seat {} has no keyboard", self.id));
+        }
+
+        let id = self.conn.new_object(WlObjectId::Keyboard, self.version)?;
+        request::get_keyboard(&self.conn, self.id, WlNewId(id))?;
+
+        Ok(Keyboard::new(
+            id,
+            self.version,
+            &self.conn,
+            self.state.clone(),
+            Box::new(handler),
+        ))
+    }
+
+    /// Creates the touch device of the seat.
+    ///
+    /// `handler` receives one [`TouchFrame`] per `wl_touch.frame`,
+    /// combining the events of all touch points that changed together.
+    ///
+    /// # Errors
+    /// Returns an error if the seat has no touch capability.
+    pub fn get_touch<F>(&self, handler: F) -> Result<Touch>
+    where
+        F: FnMut(&Connection, TouchFrame) + Send + 'static,
+    {
+        if !self.has_capability(Capability::Touch) {
+            return Err(anyhow!("Seat {} has no touch device", self.id));
+        }
+
+        let id = self.conn.new_object(WlObjectId::Touch, self.version)?;
+        request::get_touch(&self.conn, self.id, WlNewId(id))?;
+
+        Ok(Touch::new(
+            id,
+            self.version,
+            &self.conn,
+            self.state.clone(),
+            Box::new(handler),
+        ))
+    }
+
+    /// Releases the seat.
+    ///
+    /// Before version 5 the seat cannot be released and the object is kept
+    /// alive on the compositor side.
+    pub fn release(self) -> Result<()> {
+        if self.version >= 5 {
+            request::release(&self.conn, self.id)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Write, os::unix::net::UnixStream};
+
+    use super::*;
+    use crate::protocol::message::WlMessage;
+
+    #[test]
+    fn keyboard_focus_follows_enter_key_and_leave() {
+        let (client, mut compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+        let seat_id = conn.new_object(WlObjectId::Seat, 9).unwrap();
+        let state = Arc::new(SeatState {
+            id: seat_id,
+            ..SeatState::default()
+        });
+        let focus_events = Arc::new(Lock::new(Vec::new()));
+        let events = focus_events.clone();
+        conn.set_listener::<SeatEvent>(
+            seat_id,
+            Box::new(move |_, event| events.borrow_mut().push(event)),
+        );
+        let keyboard_id = conn.new_object(WlObjectId::Keyboard, 9).unwrap();
+        let _keyboard = Keyboard::new(keyboard_id, 9, &conn, state.clone(), Box::new(|_, _| {}));
+
+        // `wl_keyboard.enter` with serial 3 on surface 20 holding key 30,
+        // then `wl_keyboard.key` pressing key 31 and releasing key 30
+        let words =
+            |args: &[u32]| -> Vec<u8> { args.iter().flat_map(|arg| arg.to_ne_bytes()).collect() };
+        let mut events: Vec<u8> = WlMessage::new(keyboard_id, 1, &words(&[3, 20, 4, 30]))
+            .unwrap()
+            .into();
+        for (key, pressed) in [(31, 1), (30, 0)] {
+            events.extend(Vec::<u8>::from(
+                WlMessage::new(keyboard_id, 3, &words(&[4, 0, key, pressed])).unwrap(),
+            ));
+        }
+        compositor.write_all(&events).unwrap();
+        conn.dispatch().unwrap();
+
+        assert_eq!(
+            state.keyboard_focus.borrow().clone(),
+            Some(KeyboardFocus {
+                surface: 20,
+                serial: 3,
+                pressed: vec![31],
+            })
+        );
+
+        // `wl_keyboard.leave` of surface 20
+        let events: Vec<u8> = WlMessage::new(keyboard_id, 2, &words(&[5, 20]))
+            .unwrap()
+            .into();
+        compositor.write_all(&events).unwrap();
+        conn.dispatch().unwrap();
+
+        assert!(state.keyboard_focus.borrow().is_none());
+        assert_eq!(
+            *focus_events.borrow(),
+            [
+                SeatEvent::KeyboardFocus(Some(20)),
+                SeatEvent::KeyboardFocus(None)
+            ]
+        );
+    }
+}
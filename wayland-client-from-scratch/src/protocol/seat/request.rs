@@ -0,0 +1,139 @@
+use crate::{
+    connection::Connection, error::Result, protocol::types::WlNewId, wl_request_opcode,
+    wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to a Wayland seat object.
+    Opcode {
+        /// Creates a `wl_pointer` for the seat.
+        GetPointer = 0,
+
+        /// Creates a `wl_keyboard` for the seat.
+        GetKeyboard = 1,
+
+        /// Creates a `wl_touch` for the seat.
+        GetTouch = 2,
+
+        /// Releases the seat object (since version 5).
+        Release = 3 (destructor),
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wl_seat.get_pointer` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="get_pointer">
+    ///   <description summary="return pointer object">
+    ///     The ID provided will be initialized to the wl_pointer interface
+    ///     for this seat.
+    ///
+    ///     This request only takes effect if the seat has the pointer
+    ///     capability, or has had the pointer capability in the past.
+    ///     It is a protocol violation to issue this request on a seat that has
+    ///     never had the pointer capability. The missing_capability error will
+    ///     be sent in this case.
+    ///   </description>
+    ///   <arg name="id" type="new_id" interface="wl_pointer" summary="seat pointer"/>
+    /// </request>
+    /// ```
+    GetPointerParam {
+        /// The object ID to assign to the new pointer.
+        id: WlNewId,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wl_seat.get_keyboard` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="get_keyboard">
+    ///   <description summary="return keyboard object">
+    ///     The ID provided will be initialized to the wl_keyboard interface
+    ///     for this seat.
+    ///
+    ///     This request only takes effect if the seat has the keyboard
+    ///     capability, or has had the keyboard capability in the past.
+    ///     It is a protocol violation to issue this request on a seat that has
+    ///     never had the keyboard capability. The missing_capability error will
+    ///     be sent in this case.
+    ///   </description>
+    ///   <arg name="id" type="new_id" interface="wl_keyboard" summary="seat keyboard"/>
+    /// </request>
+    /// ```
+    GetKeyboardParam {
+        /// The object ID to assign to the new keyboard.
+        id: WlNewId,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wl_seat.get_touch` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="get_touch">
+    ///   <description summary="return touch object">
+    ///     The ID provided will be initialized to the wl_touch interface
+    ///     for this seat.
+    ///
+    ///     This request only takes effect if the seat has the touch
+    ///     capability, or has had the touch capability in the past.
+    ///     It is a protocol violation to issue this request on a seat that has
+    ///     never had the touch capability. The missing_capability error will
+    ///     be sent in this case.
+    ///   </description>
+    ///   <arg name="id" type="new_id" interface="wl_touch" summary="seat touch interface"/>
+    /// </request>
+    /// ```
+    GetTouchParam {
+        /// The object ID to assign to the new touch device.
+        id: WlNewId,
+    }
+}
+
+/// Sends a `wl_seat.get_pointer` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `seat` - The ID of the seat
+/// * `id` - The object ID to assign to the new pointer
+pub fn get_pointer(conn: &Connection, seat: u32, id: WlNewId) -> Result<()> {
+    conn.send_request(seat, Opcode::GetPointer, &GetPointerParam::new(id))?;
+
+    Ok(())
+}
+
+/// Sends a `wl_seat.get_keyboard` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `seat` - The ID of the seat
+/// * `id` - The object ID to assign to the new keyboard
+pub fn get_keyboard(conn: &Connection, seat: u32, id: WlNewId) -> Result<()> {
+    conn.send_request(seat, Opcode::GetKeyboard, &GetKeyboardParam::new(id))?;
+
+    Ok(())
+}
+
+/// Sends a `wl_seat.get_touch` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `seat` - The ID of the seat
+/// * `id` - The object ID to assign to the new touch device
+pub fn get_touch(conn: &Connection, seat: u32, id: WlNewId) -> Result<()> {
+    conn.send_request(seat, Opcode::GetTouch, &GetTouchParam::new(id))?;
+
+    Ok(())
+}
+
+/// Sends a `wl_seat.release` request to the compositor.
+pub fn release(conn: &Connection, seat: u32) -> Result<()> {
+    conn.send_request(seat, Opcode::Release, &())?;
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        session_lock::{LockConfigure, LockSurfaceState},
+        types::{WL_TYPE_UINT_LEN, WlUInt},
+    },
+};
+
+/// Represents an `ext_session_lock_surface_v1.configure` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="configure">
+///   <description summary="the client should resize its surface">
+///     This event is sent once on binding the interface and may be sent again
+///     at the compositor's discretion, for example if output geometry changes.
+///
+///     The width and height are in surface-local coordinates and are exact
+///     requirements. Failing to match these surface dimensions in the next
+///     commit after acking a configure is a protocol error.
+///   </description>
+///   <arg name="serial" type="uint" summary="serial for use in ack_configure"/>
+///   <arg name="width" type="uint"/>
+///   <arg name="height" type="uint"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Configure {
+    /// The serial to acknowledge.
+    pub serial: WlUInt,
+    /// The width the surface must have.
+    pub width: WlUInt,
+    /// The height the surface must have.
+    pub height: WlUInt,
+}
+
+impl TryFrom<&[u8]> for Configure {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Configure` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `serial` (u32) - The serial to acknowledge
+    /// - Bytes 4-7: `width` (u32) - The required width
+    /// - Bytes 8-11: `height` (u32) - The required height
+    fn try_from(buf: &[u8]) -> Result<Configure> {
+        Ok(Configure {
+            serial: WlUInt::read(buf, 0)?,
+            width: WlUInt::read(buf, WL_TYPE_UINT_LEN)?,
+            height: WlUInt::read(buf, 2 * WL_TYPE_UINT_LEN)?,
+        })
+    }
+}
+
+/// Handles an `ext_session_lock_surface_v1.configure` event by recording it
+/// as the latest configuration before notifying the listener.
+pub(super) fn handle_ext_session_lock_surface_configure(
+    conn: &Connection,
+    lock_surface_id: u32,
+    configure: Configure,
+) -> Result<()> {
+    if let Some(state) = conn.object_data::<LockSurfaceState>(lock_surface_id) {
+        *state.ready.borrow_mut() = Some(LockConfigure::from(configure));
+    }
+
+    conn.emit(lock_surface_id, configure);
+
+    Ok(())
+}
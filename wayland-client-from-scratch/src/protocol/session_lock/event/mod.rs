@@ -0,0 +1,181 @@
+pub mod configure;
+
+use crate::{
+    connection::Connection,
+    error::{Error, Result, anyhow},
+    protocol::{
+        message::WlMessage,
+        session_lock::{LockState, SessionLockEvent},
+    },
+};
+
+/// Represents the event types that can be emitted by an `ext_session_lock_v1` object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockOpcode {
+    /// The session is locked; only lock surfaces are shown.
+    Locked = 0,
+
+    /// The lock was denied or the compositor ended it.
+    Finished = 1,
+}
+
+impl TryFrom<u16> for LockOpcode {
+    type Error = Error;
+
+    /// Attempts to convert a raw opcode value into a structured `ExtSessionLockEvent`.
+    fn try_from(value: u16) -> Result<LockOpcode> {
+        match value {
+            0 => Ok(LockOpcode::Locked),
+            1 => Ok(LockOpcode::Finished),
+            _ => Err(anyhow!(
+                "Invalid ext_session_lock_v1 event opcode: {}",
+                value
+            )),
+        }
+    }
+}
+
+impl LockOpcode {
+    /// Returns the interface version the event was introduced in.
+    pub fn since(self) -> u32 {
+        match self {
+            LockOpcode::Locked | LockOpcode::Finished => 1,
+        }
+    }
+}
+
+/// Represents the event types that can be emitted by an `ext_session_lock_surface_v1` object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SurfaceOpcode {
+    /// Sets the size of the lock surface.
+    ///
+    /// # Event Arguments
+    /// - `serial`: The serial to acknowledge
+    /// - `width`, `height`: The size of the output
+    Configure = 0,
+}
+
+impl TryFrom<u16> for SurfaceOpcode {
+    type Error = Error;
+
+    /// Attempts to convert a raw opcode value into a structured `ExtSessionLockSurfaceEvent`.
+    fn try_from(value: u16) -> Result<SurfaceOpcode> {
+        match value {
+            0 => Ok(SurfaceOpcode::Configure),
+            _ => Err(anyhow!(
+                "Invalid ext_session_lock_surface_v1 event opcode: {}",
+                value
+            )),
+        }
+    }
+}
+
+impl SurfaceOpcode {
+    /// Returns the interface version the event was introduced in.
+    pub fn since(self) -> u32 {
+        match self {
+            SurfaceOpcode::Configure => 1,
+        }
+    }
+}
+
+/// A decoded `ext_session_lock_v1` event, with its arguments.
+#[derive(Debug)]
+pub enum LockEvent {
+    /// The session is locked; only lock surfaces are shown.
+    Locked,
+    /// The lock was denied or the compositor ended it.
+    Finished,
+}
+
+impl LockEvent {
+    /// Decodes an `ext_session_lock_v1` event from its message.
+    ///
+    /// # Errors
+    /// Returns an error if the opcode is unknown.
+    pub fn decode(_conn: &Connection, msg: &WlMessage) -> Result<LockEvent> {
+        Ok(match LockOpcode::try_from(msg.header.opcode)? {
+            LockOpcode::Locked => LockEvent::Locked,
+            LockOpcode::Finished => LockEvent::Finished,
+        })
+    }
+
+    /// Returns the opcode of the event.
+    pub fn opcode(&self) -> LockOpcode {
+        match self {
+            LockEvent::Locked => LockOpcode::Locked,
+            LockEvent::Finished => LockOpcode::Finished,
+        }
+    }
+}
+
+/// Dispatches incoming `ext_session_lock_v1` events to their appropriate handler functions.
+///
+/// # Event Routing
+/// * `Locked` and `Finished` events update the state of the lock and are
+///   forwarded to the listener
+pub fn handle_ext_session_lock_event(conn: &Connection, msg: WlMessage) -> Result<()> {
+    let lock_id = msg.header.object_id;
+    let state = conn.object_data::<LockState>(lock_id);
+
+    // Route the event to the appropriate handler based on type
+    match LockEvent::decode(conn, &msg)? {
+        LockEvent::Locked => {
+            if let Some(state) = state {
+                state.locked.set(true);
+            }
+            conn.emit(lock_id, SessionLockEvent::Locked);
+        }
+        LockEvent::Finished => {
+            if let Some(state) = state {
+                state.finished.set(true);
+            }
+            conn.emit(lock_id, SessionLockEvent::Finished);
+        }
+    }
+
+    Ok(())
+}
+
+/// A decoded `ext_session_lock_surface_v1` event, with its arguments.
+#[derive(Debug)]
+pub enum SurfaceEvent {
+    /// Sets the size of the lock surface.
+    Configure(configure::Configure),
+}
+
+impl SurfaceEvent {
+    /// Decodes an `ext_session_lock_surface_v1` event from its message.
+    ///
+    /// # Errors
+    /// Returns an error if the opcode is unknown or the arguments are malformed.
+    pub fn decode(_conn: &Connection, msg: &WlMessage) -> Result<SurfaceEvent> {
+        let buf: &[u8] = &msg.data;
+
+        Ok(match SurfaceOpcode::try_from(msg.header.opcode)? {
+            SurfaceOpcode::Configure => {
+                SurfaceEvent::Configure(configure::Configure::try_from(buf)?)
+            }
+        })
+    }
+
+    /// Returns the opcode of the event.
+    pub fn opcode(&self) -> SurfaceOpcode {
+        match self {
+            SurfaceEvent::Configure(_) => SurfaceOpcode::Configure,
+        }
+    }
+}
+
+/// Dispatches incoming `ext_session_lock_surface_v1` events to their appropriate handler functions.
+///
+/// # Event Routing
+/// * `Configure` events are routed to `configure::handle_ext_session_lock_surface_configure`
+pub fn handle_ext_session_lock_surface_event(conn: &Connection, msg: WlMessage) -> Result<()> {
+    // Route the event to the appropriate handler based on type
+    match SurfaceEvent::decode(conn, &msg)? {
+        SurfaceEvent::Configure(event) => {
+            configure::handle_ext_session_lock_surface_configure(conn, msg.header.object_id, event)
+        }
+    }
+}
@@ -0,0 +1,326 @@
+pub mod event;
+pub mod request;
+
+use std::sync::Arc;
+
+use crate::{
+    connection::{Connection, Listener},
+    error::{Result, anyhow},
+    protocol::{
+        WlObjectId,
+        output::Output,
+        surface::{Surface, SurfaceRole, SurfaceState},
+        types::{WlNewId, WlObject, WlUInt},
+    },
+    sync::{Lock, LockCell},
+};
+
+use event::configure::Configure;
+
+/// The highest `ext_session_lock_manager_v1` version this crate implements.
+pub const EXT_SESSION_LOCK_MANAGER_VERSION: u32 = 1;
+
+/// An event delivered to the handler of a [`SessionLock`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionLockEvent {
+    /// The session is locked: the compositor shows only lock surfaces,
+    /// until [`SessionLock::unlock_and_destroy`].
+    Locked,
+    /// The compositor denied the lock, e.g. because another client holds
+    /// one, or ended it; the lock should be destroyed.
+    Finished,
+}
+
+/// The size requested by an `ext_session_lock_surface_v1.configure` event.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LockConfigure {
+    /// The serial acknowledging the configuration.
+    pub serial: u32,
+    /// The width the surface must have.
+    pub width: u32,
+    /// The height the surface must have.
+    pub height: u32,
+}
+
+impl From<Configure> for LockConfigure {
+    fn from(configure: Configure) -> LockConfigure {
+        LockConfigure {
+            serial: configure.serial.get(),
+            width: configure.width.get(),
+            height: configure.height.get(),
+        }
+    }
+}
+
+/// State of a session lock updated by its events.
+#[derive(Default)]
+pub(crate) struct LockState {
+    /// Whether the compositor sent `locked`.
+    pub(crate) locked: LockCell<bool>,
+    /// Whether the compositor sent `finished`.
+    pub(crate) finished: LockCell<bool>,
+}
+
+/// State of a lock surface updated by its events.
+#[derive(Default)]
+pub(crate) struct LockSurfaceState {
+    /// The latest configuration not taken by the application yet.
+    pub(crate) ready: Lock<Option<LockConfigure>>,
+}
+
+/// A bound `ext_session_lock_manager_v1` global, which lets a screen locker
+/// lock the session.
+#[derive(Clone)]
+pub struct SessionLockManager {
+    /// The object ID of the bound global.
+    id: u32,
+    /// The negotiated interface version.
+    version: u32,
+    /// The connection the global was bound on.
+    conn: Connection,
+}
+
+impl SessionLockManager {
+    /// Binds the `ext_session_lock_manager_v1` global advertised by the compositor.
+    pub fn bind(conn: &Connection) -> Result<SessionLockManager> {
+        let (id, version) = conn.bind(
+            WlObjectId::SessionLockManager,
+            EXT_SESSION_LOCK_MANAGER_VERSION,
+        )?;
+
+        Ok(SessionLockManager {
+            id,
+            version,
+            conn: conn.clone(),
+        })
+    }
+
+    /// Returns the object ID of the global.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Asks the compositor to lock the session.
+    ///
+    /// `handler` receives [`SessionLockEvent::Locked`] once every output is
+    /// covered, or [`SessionLockEvent::Finished`] if the lock was denied.
+    /// Lock surfaces should be created for every output right away.
+    pub fn lock<F>(&self, handler: F) -> Result<SessionLock>
+    where
+        F: FnMut(&Connection, SessionLockEvent) + Send + 'static,
+    {
+        let id = self
+            .conn
+            .new_object(WlObjectId::SessionLock, self.version)?;
+        request::lock(&self.conn, self.id, WlNewId(id))?;
+
+        let state = Arc::new(LockState::default());
+        self.conn.set_object_data(id, state.clone());
+        let listener: Listener<SessionLockEvent> = Box::new(handler);
+        self.conn.set_listener(id, listener);
+
+        Ok(SessionLock {
+            id,
+            version: self.version,
+            conn: self.conn.clone(),
+            state,
+            unlocked: false,
+        })
+    }
+
+    /// Destroys the manager; existing locks keep working.
+    pub fn destroy(self) -> Result<()> {
+        request::destroy(&self.conn, self.id)
+    }
+}
+
+/// An `ext_session_lock_v1`, a request to lock the session.
+///
+/// Created with [`SessionLockManager::lock`]. Once locked, the session only
+/// ends up unlocked through [`SessionLock::unlock_and_destroy`]: dropping a
+/// locked handle, or the client exiting, leaves the session locked.
+pub struct SessionLock {
+    /// The object ID of the lock.
+    id: u32,
+    /// The interface version, inherited from `ext_session_lock_manager_v1`.
+    version: u32,
+    /// The connection the lock was created on.
+    conn: Connection,
+    /// The state shared with the event handlers.
+    state: Arc<LockState>,
+    /// Whether the lock was destroyed by unlocking the session.
+    unlocked: bool,
+}
+
+impl SessionLock {
+    /// Returns the object ID of the lock.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns `true` once the compositor locked the session, until it
+    /// finishes the lock.
+    pub fn is_locked(&self) -> bool {
+        self.state.locked.get() && !self.state.finished.get()
+    }
+
+    /// Gives `surface` the lock surface role, covering `output` while the
+    /// session is locked.
+    ///
+    /// The compositor configures the surface with the size of the output,
+    /// taken with [`LockSurface::take_configure`], before the first buffer.
+    /// `handler` is notified of each configure.
+    ///
+    /// # Errors
+    /// Returns an error if the surface already has another role or a buffer.
+    pub fn get_lock_surface<F>(
+        &self,
+        surface: &Surface,
+        output: &Output,
+        handler: F,
+    ) -> Result<LockSurface>
+    where
+        F: FnMut(&Connection, Configure) + Send + 'static,
+    {
+        self.conn
+            .check_same_connection(surface.connection(), WlObjectId::Surface, surface.id())?;
+        self.conn
+            .check_same_connection(output.connection(), WlObjectId::Output, output.id())?;
+
+        let surface_state = surface.state();
+        if surface_state.has_buffer() {
+            return Err(anyhow!(
+                "wl_surface {} must not have a buffer when its lock surface is created",
+                surface.id()
+            ));
+        }
+        surface_state.assign_role(surface.id(), SurfaceRole::LockSurface)?;
+
+        let id = self
+            .conn
+            .new_object(WlObjectId::SessionLockSurface, self.version)?;
+        request::get_lock_surface(
+            &self.conn,
+            self.id,
+            WlNewId(id),
+            WlObject(surface.id()),
+            WlObject(output.id()),
+        )?;
+
+        let state = Arc::new(LockSurfaceState::default());
+        self.conn.set_object_data(id, state.clone());
+        let listener: Listener<Configure> = Box::new(handler);
+        self.conn.set_listener(id, listener);
+
+        Ok(LockSurface {
+            id,
+            conn: self.conn.clone(),
+            state,
+            surface: surface_state,
+        })
+    }
+
+    /// Unlocks the session and destroys the lock.
+    ///
+    /// # Errors
+    /// Returns an error if the session was not locked, as the compositor
+    /// would treat the request as a protocol error.
+    pub fn unlock_and_destroy(mut self) -> Result<()> {
+        if !self.state.locked.get() {
+            return Err(anyhow!(
+                "ext_session_lock_v1 {}: cannot unlock a session that was never locked",
+                self.id
+            ));
+        }
+
+        self.unlocked = true;
+        request::unlock_and_destroy(&self.conn, self.id)
+    }
+
+    /// Destroys a lock that was denied or finished.
+    ///
+    /// Dropping the handle does the same.
+    pub fn destroy(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl Drop for SessionLock {
+    fn drop(&mut self) {
+        self.conn.remove_listener(self.id);
+
+        // Destroying a lock that is still locked is a protocol error, and
+        // unlocking it on drop would defeat the lock.
+        if self.unlocked || self.is_locked() {
+            return;
+        }
+
+        self.conn
+            .destroy_on_drop(|conn| request::destroy_lock(conn, self.id));
+    }
+}
+
+/// An `ext_session_lock_surface_v1`, the content of an output while the
+/// session is locked.
+///
+/// Created with [`SessionLock::get_lock_surface`].
+pub struct LockSurface {
+    /// The object ID of the lock surface.
+    id: u32,
+    /// The connection the lock surface was created on.
+    conn: Connection,
+    /// The state shared with the event handlers.
+    state: Arc<LockSurfaceState>,
+    /// The state of the underlying `wl_surface`.
+    surface: Arc<SurfaceState>,
+}
+
+impl LockSurface {
+    /// Returns the object ID of the lock surface.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the latest size requested by the compositor that was not
+    /// taken yet, without taking it.
+    pub fn pending_configure(&self) -> Option<LockConfigure> {
+        *self.state.ready.borrow()
+    }
+
+    /// Takes the latest size requested by the compositor, which the next
+    /// buffer must match exactly.
+    ///
+    /// Its serial is acknowledged automatically on the next
+    /// [`Surface::commit`].
+    pub fn take_configure(&self) -> Option<LockConfigure> {
+        let configure = self.state.ready.take()?;
+
+        let (id, serial) = (self.id, configure.serial);
+        self.surface.ack_on_commit(Box::new(move |conn| {
+            request::ack_configure(conn, id, WlUInt(serial))
+        }));
+
+        Some(configure)
+    }
+
+    /// Destroys the lock surface.
+    ///
+    /// Dropping the handle does the same.
+    pub fn destroy(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl Drop for LockSurface {
+    fn drop(&mut self) {
+        self.conn.remove_listener(self.id);
+        self.surface.cancel_ack();
+
+        self.conn
+            .destroy_on_drop(|conn| request::destroy_surface(conn, self.id));
+    }
+}
@@ -0,0 +1,198 @@
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::types::{WlNewId, WlObject, WlUInt},
+    wl_request_opcode, wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to the `ext_session_lock_manager_v1` object.
+    Opcode {
+        /// Destroys the session lock manager.
+        Destroy = 0 (destructor),
+
+        /// Locks the session.
+        Lock = 1,
+    }
+}
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to an `ext_session_lock_v1` object.
+    LockOpcode {
+        /// Destroys a lock that was never locked or was finished.
+        Destroy = 0 (destructor),
+
+        /// Creates the lock surface of an output.
+        GetLockSurface = 1,
+
+        /// Unlocks the session and destroys the lock.
+        UnlockAndDestroy = 2 (destructor),
+    }
+}
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to an `ext_session_lock_surface_v1` object.
+    SurfaceOpcode {
+        /// Destroys the lock surface.
+        Destroy = 0 (destructor),
+
+        /// Acknowledges a configure event.
+        AckConfigure = 1,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `ext_session_lock_manager_v1.lock` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="lock">
+    ///   <description summary="attempt to lock the session">
+    ///     This request creates a session lock and asks the compositor to lock the
+    ///     session. The compositor will send either the ext_session_lock_v1.locked
+    ///     or ext_session_lock_v1.finished event on the created object in
+    ///     response to this request.
+    ///   </description>
+    ///   <arg name="id" type="new_id" interface="ext_session_lock_v1"/>
+    /// </request>
+    /// ```
+    LockParam {
+        /// The ID of the new session lock.
+        id: WlNewId,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `ext_session_lock_v1.get_lock_surface` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="get_lock_surface">
+    ///   <description summary="create a lock surface for a given output">
+    ///     The client is expected to create lock surfaces for all outputs
+    ///     currently present and any new outputs as they are advertised. These
+    ///     won't be displayed by the compositor unless the lock is successful
+    ///     and the locked event is sent.
+    ///
+    ///     Providing a wl_buffer attached to the wl_surface is a protocol error.
+    ///
+    ///     It is a protocol error to create two lock surfaces for the same output.
+    ///   </description>
+    ///   <arg name="id" type="new_id" interface="ext_session_lock_surface_v1"/>
+    ///   <arg name="surface" type="object" interface="wl_surface"/>
+    ///   <arg name="output" type="object" interface="wl_output"/>
+    /// </request>
+    /// ```
+    GetLockSurfaceParam {
+        /// The ID of the new lock surface.
+        id: WlNewId,
+        /// The surface given the lock surface role.
+        surface: WlObject,
+        /// The output the surface covers.
+        output: WlObject,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `ext_session_lock_surface_v1.ack_configure` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="ack_configure">
+    ///   <description summary="ack a configure event">
+    ///     When a configure event is received, if a client commits the surface
+    ///     in response to the configure event, then the client must make an
+    ///     ack_configure request sometime before the commit request, passing
+    ///     along the serial of the configure event.
+    ///
+    ///     If the client receives multiple configure events before it can
+    ///     respond to one, it only has to ack the last configure event.
+    ///   </description>
+    ///   <arg name="serial" type="uint" summary="serial from the configure event"/>
+    /// </request>
+    /// ```
+    AckConfigureParam {
+        /// The serial of the acknowledged configure event.
+        serial: WlUInt,
+    }
+}
+
+/// Sends an `ext_session_lock_manager_v1.destroy` request to the compositor.
+pub fn destroy(conn: &Connection, manager: u32) -> Result<()> {
+    conn.send_request(manager, Opcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends an `ext_session_lock_manager_v1.lock` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `manager` - The ID of the bound session lock manager
+/// * `id` - The ID of the new session lock
+pub fn lock(conn: &Connection, manager: u32, id: WlNewId) -> Result<()> {
+    conn.send_request(manager, Opcode::Lock, &LockParam::new(id))?;
+
+    Ok(())
+}
+
+/// Sends an `ext_session_lock_v1.destroy` request to the compositor.
+pub fn destroy_lock(conn: &Connection, lock: u32) -> Result<()> {
+    conn.send_request(lock, LockOpcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends an `ext_session_lock_v1.get_lock_surface` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `lock` - The ID of the session lock
+/// * `id` - The ID of the new lock surface
+/// * `surface` - The surface given the lock surface role
+/// * `output` - The output the surface covers
+pub fn get_lock_surface(
+    conn: &Connection,
+    lock: u32,
+    id: WlNewId,
+    surface: WlObject,
+    output: WlObject,
+) -> Result<()> {
+    conn.send_request(
+        lock,
+        LockOpcode::GetLockSurface,
+        &GetLockSurfaceParam::new(id, surface, output),
+    )?;
+
+    Ok(())
+}
+
+/// Sends an `ext_session_lock_v1.unlock_and_destroy` request to the compositor.
+pub fn unlock_and_destroy(conn: &Connection, lock: u32) -> Result<()> {
+    conn.send_request(lock, LockOpcode::UnlockAndDestroy, &())?;
+
+    Ok(())
+}
+
+/// Sends an `ext_session_lock_surface_v1.destroy` request to the compositor.
+pub fn destroy_surface(conn: &Connection, lock_surface: u32) -> Result<()> {
+    conn.send_request(lock_surface, SurfaceOpcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends an `ext_session_lock_surface_v1.ack_configure` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `lock_surface` - The ID of the lock surface
+/// * `serial` - The serial of the acknowledged configure event
+pub fn ack_configure(conn: &Connection, lock_surface: u32, serial: WlUInt) -> Result<()> {
+    conn.send_request(
+        lock_surface,
+        SurfaceOpcode::AckConfigure,
+        &AckConfigureParam::new(serial),
+    )?;
+
+    Ok(())
+}
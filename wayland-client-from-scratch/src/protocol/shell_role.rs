@@ -0,0 +1,234 @@
+use crate::{
+    error::Result,
+    protocol::{
+        WlObjectId,
+        layer_shell::{LayerConfigure, LayerSurface},
+        session_lock::{LockConfigure, LockSurface},
+        xdg_popup::{PopupConfigure, XdgPopup},
+        xdg_toplevel::{ToplevelConfigure, XdgToplevel},
+    },
+};
+
+/// A configuration requested by the compositor for a shell surface.
+pub trait RoleConfigure {
+    /// Returns the serial acknowledging the configuration.
+    fn serial(&self) -> u32;
+
+    /// Returns the suggested size in surface-local coordinates; a zero
+    /// width or height leaves that dimension to the client.
+    fn size(&self) -> (i32, i32);
+
+    /// Applies the suggested size to `current`, keeping the dimensions left
+    /// to the client.
+    fn apply_size(&self, current: (i32, i32)) -> (i32, i32) {
+        let (width, height) = self.size();
+
+        (
+            if width > 0 { width } else { current.0 },
+            if height > 0 { height } else { current.1 },
+        )
+    }
+}
+
+/// The role object making a surface part of a desktop shell, e.g. an
+/// `xdg_toplevel` window, a `zwlr_layer_surface_v1` panel or the
+/// `ext_session_lock_surface_v1` of a screen locker.
+///
+/// Each shell has its own configure sequence; this trait exposes the parts
+/// all of them share, so code sizing buffers and answering configures does
+/// not depend on the shell a tool targets. The configuration taken is
+/// acknowledged on the next commit of the surface, which must carry content
+/// matching it.
+///
+/// `zwp_fullscreen_shell_v1` has no counterpart: it presents surfaces on an
+/// output without giving them a role object or a configure sequence.
+pub trait ShellRole {
+    /// The configuration the compositor requests for the role.
+    type Configure: RoleConfigure;
+
+    /// Returns the interface of the role object.
+    fn interface(&self) -> WlObjectId;
+
+    /// Returns the object ID of the role object.
+    fn id(&self) -> u32;
+
+    /// Returns the latest configuration that was not taken yet, without
+    /// taking it.
+    fn pending_configure(&self) -> Option<Self::Configure>;
+
+    /// Takes the latest configuration, to be acknowledged on the next
+    /// commit.
+    fn take_configure(&self) -> Option<Self::Configure>;
+
+    /// Destroys the role object; the surface is unmapped.
+    fn destroy(self) -> Result<()>
+    where
+        Self: Sized;
+}
+
+impl RoleConfigure for ToplevelConfigure {
+    fn serial(&self) -> u32 {
+        self.serial
+    }
+
+    fn size(&self) -> (i32, i32) {
+        (self.width, self.height)
+    }
+}
+
+impl RoleConfigure for PopupConfigure {
+    fn serial(&self) -> u32 {
+        self.serial
+    }
+
+    fn size(&self) -> (i32, i32) {
+        (self.rect.width, self.rect.height)
+    }
+}
+
+impl RoleConfigure for LayerConfigure {
+    fn serial(&self) -> u32 {
+        self.serial
+    }
+
+    fn size(&self) -> (i32, i32) {
+        (wire_size(self.width), wire_size(self.height))
+    }
+}
+
+impl RoleConfigure for LockConfigure {
+    fn serial(&self) -> u32 {
+        self.serial
+    }
+
+    fn size(&self) -> (i32, i32) {
+        (wire_size(self.width), wire_size(self.height))
+    }
+}
+
+/// Converts a size sent as a `uint` into the signed size of
+/// [`RoleConfigure::size`].
+fn wire_size(size: u32) -> i32 {
+    i32::try_from(size).unwrap_or(i32::MAX)
+}
+
+impl ShellRole for XdgToplevel {
+    type Configure = ToplevelConfigure;
+
+    fn interface(&self) -> WlObjectId {
+        WlObjectId::XdgToplevel
+    }
+
+    fn id(&self) -> u32 {
+        XdgToplevel::id(self)
+    }
+
+    fn pending_configure(&self) -> Option<ToplevelConfigure> {
+        XdgToplevel::pending_configure(self)
+    }
+
+    fn take_configure(&self) -> Option<ToplevelConfigure> {
+        XdgToplevel::take_configure(self)
+    }
+
+    fn destroy(self) -> Result<()> {
+        XdgToplevel::destroy(self)
+    }
+}
+
+impl ShellRole for XdgPopup {
+    type Configure = PopupConfigure;
+
+    fn interface(&self) -> WlObjectId {
+        WlObjectId::XdgPopup
+    }
+
+    fn id(&self) -> u32 {
+        XdgPopup::id(self)
+    }
+
+    fn pending_configure(&self) -> Option<PopupConfigure> {
+        XdgPopup::pending_configure(self)
+    }
+
+    fn take_configure(&self) -> Option<PopupConfigure> {
+        XdgPopup::take_configure(self)
+    }
+
+    fn destroy(self) -> Result<()> {
+        XdgPopup::destroy(self)
+    }
+}
+
+impl ShellRole for LayerSurface {
+    type Configure = LayerConfigure;
+
+    fn interface(&self) -> WlObjectId {
+        WlObjectId::LayerSurface
+    }
+
+    fn id(&self) -> u32 {
+        LayerSurface::id(self)
+    }
+
+    fn pending_configure(&self) -> Option<LayerConfigure> {
+        LayerSurface::pending_configure(self)
+    }
+
+    fn take_configure(&self) -> Option<LayerConfigure> {
+        LayerSurface::take_configure(self)
+    }
+
+    fn destroy(self) -> Result<()> {
+        LayerSurface::destroy(self)
+    }
+}
+
+impl ShellRole for LockSurface {
+    type Configure = LockConfigure;
+
+    fn interface(&self) -> WlObjectId {
+        WlObjectId::SessionLockSurface
+    }
+
+    fn id(&self) -> u32 {
+        LockSurface::id(self)
+    }
+
+    fn pending_configure(&self) -> Option<LockConfigure> {
+        LockSurface::pending_configure(self)
+    }
+
+    fn take_configure(&self) -> Option<LockConfigure> {
+        LockSurface::take_configure(self)
+    }
+
+    fn destroy(self) -> Result<()> {
+        LockSurface::destroy(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Rect;
+
+    #[test]
+    fn apply_size_keeps_the_dimensions_left_to_the_client() {
+        let toplevel = ToplevelConfigure {
+            width: 800,
+            ..Default::default()
+        };
+        assert_eq!(toplevel.apply_size((640, 480)), (800, 480));
+
+        let popup = PopupConfigure {
+            serial: 3,
+            rect: Rect::new(10, 20, 200, 150),
+        };
+        assert_eq!(popup.apply_size((1, 1)), (200, 150));
+        assert_eq!(
+            ToplevelConfigure::default().apply_size((640, 480)),
+            (640, 480)
+        );
+    }
+}
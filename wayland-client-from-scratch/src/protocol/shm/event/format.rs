@@ -0,0 +1,108 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result, anyhow},
+    protocol::{
+        shm::ShmState,
+        types::{WL_TYPE_ENUM_LEN, WlEnum},
+    },
+    wl_enum,
+};
+
+wl_enum! {
+    /// Pixel formats for shared memory buffers.
+    ///
+    /// Apart from `Argb8888` and `Xrgb8888`, the values are the DRM fourcc codes
+    /// defined in `drm_fourcc.h`. All formats are little-endian: `Argb8888`
+    /// stores the bytes B, G, R, A in memory order.
+    Format {
+        /// 32-bit ARGB format, `[31:0] A:R:G:B 8:8:8:8` little endian.
+        Argb8888 = 0,
+        /// 32-bit RGB format, `[31:0] x:R:G:B 8:8:8:8` little endian.
+        Xrgb8888 = 1,
+        /// 16-bit RGB format, `[15:0] R:G:B 5:6:5` little endian.
+        Rgb565 = 0x36314752,
+        /// 24-bit RGB format, `[23:0] R:G:B` little endian.
+        Rgb888 = 0x34324752,
+        /// 24-bit BGR format, `[23:0] B:G:R` little endian.
+        Bgr888 = 0x34324742,
+        /// 32-bit BGR format, `[31:0] x:B:G:R 8:8:8:8` little endian.
+        Xbgr8888 = 0x34324258,
+        /// 32-bit ABGR format, `[31:0] A:B:G:R 8:8:8:8` little endian.
+        Abgr8888 = 0x34324241,
+        /// 32-bit RGBX format, `[31:0] R:G:B:x 8:8:8:8` little endian.
+        Rgbx8888 = 0x34325852,
+        /// 32-bit RGBA format, `[31:0] R:G:B:A 8:8:8:8` little endian.
+        Rgba8888 = 0x34324152,
+        /// 32-bit BGRX format, `[31:0] B:G:R:x 8:8:8:8` little endian.
+        Bgrx8888 = 0x34325842,
+        /// 32-bit BGRA format, `[31:0] B:G:R:A 8:8:8:8` little endian.
+        Bgra8888 = 0x34324142,
+        /// 32-bit RGB format, `[31:0] x:R:G:B 2:10:10:10` little endian.
+        Xrgb2101010 = 0x30335258,
+        /// 32-bit ARGB format, `[31:0] A:R:G:B 2:10:10:10` little endian.
+        Argb2101010 = 0x30335241,
+        /// 32-bit BGR format, `[31:0] x:B:G:R 2:10:10:10` little endian.
+        Xbgr2101010 = 0x30334258,
+        /// 32-bit ABGR format, `[31:0] A:B:G:R 2:10:10:10` little endian.
+        Abgr2101010 = 0x30334241,
+    }
+}
+
+/// Represents a `wl_shm.format` event.
+///
+/// The raw code is kept as-is because compositors advertise many formats this
+/// crate has no [`Format`] variant for.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="format">
+///   <description summary="pixel format description">
+///     Informs the client about a valid pixel format that
+///     can be used for buffers. Known formats include
+///     argb8888 and xrgb8888.
+///   </description>
+///   <arg name="format" type="uint" enum="format" summary="buffer pixel format"/>
+/// </event>
+/// ```
+#[derive(Debug)]
+pub struct FormatEvent {
+    /// The advertised format code.
+    pub format: WlEnum,
+}
+
+impl TryFrom<&[u8]> for FormatEvent {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `FormatEvent`.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `format` (u32) - The pixel format code
+    fn try_from(buf: &[u8]) -> Result<FormatEvent> {
+        if buf.len() < WL_TYPE_ENUM_LEN {
+            return Err(anyhow!(
+                "Buffer too short for WlShmFormat format: expected {} bytes, got {}",
+                WL_TYPE_ENUM_LEN,
+                buf.len()
+            ));
+        }
+        let format = WlEnum::from_bytes(buf[..WL_TYPE_ENUM_LEN].try_into()?);
+
+        Ok(FormatEvent { format })
+    }
+}
+
+/// Handles a `wl_shm.format` event.
+///
+/// The format is added to the set of the [`Shm`](crate::protocol::shm::Shm)
+/// object, even if this crate has no [`Format`] variant for it.
+pub(super) fn handle_wl_shm_format(
+    conn: &Connection,
+    shm_id: u32,
+    event: FormatEvent,
+) -> Result<()> {
+    if let Some(state) = conn.object_data::<ShmState>(shm_id) {
+        state.formats.borrow_mut().insert(event.format.get());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+pub mod format;
+
+use crate::{
+    connection::Connection,
+    error::{Error, Result, anyhow},
+    protocol::message::WlMessage,
+};
+
+/// Represents the event types that can be emitted by the Wayland shm object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    /// Announces a pixel format supported for shared memory buffers.
+    ///
+    /// # Event Arguments
+    /// - `format`: The supported pixel format
+    Format = 0,
+}
+
+impl TryFrom<u16> for Opcode {
+    type Error = Error;
+
+    /// Attempts to convert a raw opcode value into a structured `WlShmEvent`.
+    fn try_from(value: u16) -> Result<Opcode> {
+        match value {
+            0 => Ok(Opcode::Format),
+            _ => Err(anyhow!("Invalid wl_shm event opcode: {}", value)),
+        }
+    }
+}
+
+impl Opcode {
+    /// Returns the interface version the event was introduced in.
+    pub fn since(self) -> u32 {
+        match self {
+            Opcode::Format => 1,
+        }
+    }
+}
+
+/// A decoded `wl_shm` event, with its arguments.
+#[derive(Debug)]
+pub enum Event {
+    /// Announces a pixel format supported for shared memory buffers.
+    Format(format::FormatEvent),
+}
+
+impl Event {
+    /// Decodes a `wl_shm` event from its message.
+    ///
+    /// # Errors
+    /// Returns an error if the opcode is unknown or the arguments are malformed.
+    pub fn decode(_conn: &Connection, msg: &WlMessage) -> Result<Event> {
+        let buf: &[u8] = &msg.data;
+
+        Ok(match Opcode::try_from(msg.header.opcode)? {
+            Opcode::Format => Event::Format(format::FormatEvent::try_from(buf)?),
+        })
+    }
+
+    /// Returns the opcode of the event.
+    pub fn opcode(&self) -> Opcode {
+        match self {
+            Event::Format(_) => Opcode::Format,
+        }
+    }
+}
+
+/// Dispatches incoming Wayland shm events to their appropriate handler functions.
+///
+/// # Event Routing
+/// * `Format` events are routed to `format::handle_wl_shm_format`
+pub fn handle_wl_shm_event(conn: &Connection, msg: WlMessage) -> Result<()> {
+    // Route the event to the appropriate handler based on type
+    match Event::decode(conn, &msg)? {
+        Event::Format(event) => format::handle_wl_shm_format(conn, msg.header.object_id, event),
+    }
+}
@@ -0,0 +1,172 @@
+pub mod event;
+pub mod request;
+
+use std::{
+    collections::{BTreeSet, HashSet},
+    fs::File,
+    os::fd::{AsFd, OwnedFd},
+    sync::Arc,
+};
+
+use crate::{
+    connection::Connection,
+    error::{Result, anyhow},
+    protocol::{
+        WlObjectId,
+        shm::event::format::Format,
+        shm_pool::ShmPool,
+        types::{WlInt, WlNewId},
+    },
+    sync::Lock,
+    sys::{self, MemMap},
+};
+
+/// The highest `wl_shm` version this crate implements.
+pub const WL_SHM_VERSION: u32 = 1;
+
+/// The formats every compositor supports, whether advertised or not.
+const ALWAYS_SUPPORTED_FORMATS: [Format; 2] = [Format::Argb8888, Format::Xrgb8888];
+
+/// State of a shm object updated by its events.
+#[derive(Default)]
+pub(crate) struct ShmState {
+    /// The raw codes of the formats advertised with `wl_shm.format`.
+    pub(crate) formats: Lock<BTreeSet<u32>>,
+}
+
+impl ShmState {
+    /// Checks that buffers can be created in `format`.
+    ///
+    /// # Errors
+    /// Returns an error listing the supported formats if the compositor
+    /// did not advertise `format`.
+    pub(crate) fn check_format(&self, format: Format) -> Result<()> {
+        if ALWAYS_SUPPORTED_FORMATS.contains(&format)
+            || self.formats.borrow().contains(&(format as u32))
+        {
+            return Ok(());
+        }
+
+        let supported: Vec<String> = self
+            .formats
+            .borrow()
+            .iter()
+            .map(|&code| match Format::try_from(code) {
+                Ok(format) => format.to_string(),
+                Err(_) => format!("{code:#010x}"),
+            })
+            .collect();
+
+        Err(anyhow!(
+            "Compositor does not support the shm format {}, supported formats are: {}",
+            format,
+            supported.join(", ")
+        ))
+    }
+}
+
+/// A bound `wl_shm` global, the factory for shared memory pools.
+#[derive(Clone)]
+pub struct Shm {
+    /// The object ID of the bound shm object.
+    id: u32,
+    /// The connection the shm object was bound on.
+    conn: Connection,
+    /// The formats advertised so far, shared with the event handler.
+    state: Arc<ShmState>,
+}
+
+impl Shm {
+    /// Binds the `wl_shm` global advertised by the compositor.
+    ///
+    /// The supported formats are announced right after binding, so they
+    /// are known after the next [`Connection::roundtrip`].
+    pub fn bind(conn: &Connection) -> Result<Shm> {
+        let (id, _) = conn.bind(WlObjectId::Shm, WL_SHM_VERSION)?;
+
+        let state = Arc::new(ShmState::default());
+        conn.set_object_data(id, state.clone());
+
+        Ok(Shm {
+            id,
+            conn: conn.clone(),
+            state,
+        })
+    }
+
+    /// Returns the pixel formats the compositor advertised for shared
+    /// memory buffers.
+    ///
+    /// [`Format::Argb8888`] and [`Format::Xrgb8888`] are always included,
+    /// as every compositor must support them. Advertised formats this crate
+    /// has no [`Format`] variant for are left out.
+    pub fn supported_formats(&self) -> HashSet<Format> {
+        self.state
+            .formats
+            .borrow()
+            .iter()
+            .filter_map(|&code| Format::try_from(code).ok())
+            .chain(ALWAYS_SUPPORTED_FORMATS)
+            .collect()
+    }
+
+    /// Returns `true` if buffers can be created in `format`.
+    pub fn supports_format(&self, format: Format) -> bool {
+        self.state.check_format(format).is_ok()
+    }
+
+    /// Returns the object ID of the shm object.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Creates a pool of `size` bytes backed by a new memfd.
+    ///
+    /// The memory is mapped into this process and shared with the compositor,
+    /// which maps the same file on its side. The pool keeps its own handle
+    /// to the memfd, so it can grow with [`ShmPool::resize`].
+    pub fn create_pool(&self, size: usize) -> Result<ShmPool> {
+        let wire_size = i32::try_from(size)
+            .map_err(|_| anyhow!("wl_shm pool size {} does not fit in an int", size))?;
+
+        let file = File::from(sys::memfd_create("wl_shm_pool")?);
+        file.set_len(size as u64)?;
+        let map = MemMap::new(file.as_fd(), size)?;
+
+        let id = self.conn.new_object(WlObjectId::ShmPool, 1)?;
+        request::create_pool(
+            &self.conn,
+            self.id,
+            WlNewId(id),
+            OwnedFd::from(file.try_clone()?),
+            WlInt(wire_size),
+        )?;
+
+        Ok(ShmPool::new(id, &self.conn, file, map, self.state.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unsupported_formats_list_the_alternatives() {
+        let state = ShmState::default();
+        state.formats.borrow_mut().extend([
+            Format::Argb8888 as u32,
+            Format::Rgb565 as u32,
+            0x3231_5559,
+        ]);
+
+        assert!(state.check_format(Format::Xrgb8888).is_ok());
+        assert!(state.check_format(Format::Rgb565).is_ok());
+
+        let error = state.check_format(Format::Abgr8888).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Compositor does not support the shm format Format::Abgr8888, supported formats \
+             are: Format::Argb8888, 0x32315559, Format::Rgb565"
+        );
+    }
+}
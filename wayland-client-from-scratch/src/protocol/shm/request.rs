@@ -0,0 +1,73 @@
+use std::os::fd::OwnedFd;
+
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::types::{WlInt, WlNewId},
+    wl_request_opcode, wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to the Wayland shm object.
+    Opcode {
+        /// Creates a pool of memory shared with the compositor.
+        CreatePool = 0,
+
+        /// Releases the shm object (since version 2).
+        Release = 1 (destructor),
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wl_shm.create_pool` request.
+    ///
+    /// The `fd` argument is not part of the payload: file descriptors travel
+    /// out of band as ancillary data of the socket message.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="create_pool">
+    ///   <description summary="create a shm pool">
+    ///     Create a new wl_shm_pool object.
+    ///
+    ///     The pool can be used to create shared memory based buffer
+    ///     objects.  The server will mmap size bytes of the passed file
+    ///     descriptor, to use as backing memory for the pool.
+    ///   </description>
+    ///   <arg name="id" type="new_id" interface="wl_shm_pool" summary="pool to create"/>
+    ///   <arg name="fd" type="fd" summary="file descriptor for the pool"/>
+    ///   <arg name="size" type="int" summary="pool size, in bytes"/>
+    /// </request>
+    /// ```
+    CreatePoolParam {
+        /// The object ID to assign to the newly created wl_shm_pool object.
+        id: WlNewId,
+        /// The size of the pool in bytes.
+        size: WlInt,
+    }
+}
+
+/// Sends a `wl_shm.create_pool` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `shm` - The ID of the bound wl_shm object
+/// * `id` - The object ID to assign to the new pool
+/// * `fd` - The file backing the pool, closed once sent
+/// * `size` - The number of bytes of `fd` the compositor maps
+pub fn create_pool(
+    conn: &Connection,
+    shm: u32,
+    id: WlNewId,
+    fd: OwnedFd,
+    size: WlInt,
+) -> Result<()> {
+    conn.send_request_with_fds(
+        shm,
+        Opcode::CreatePool,
+        &CreatePoolParam::new(id, size),
+        vec![fd],
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,195 @@
+pub mod request;
+
+use std::{fs::File, os::fd::AsFd, sync::Arc};
+
+use crate::{
+    connection::Connection,
+    error::{Result, anyhow},
+    protocol::{
+        WlObjectId,
+        buffer::Buffer,
+        shm::{ShmState, event::format::Format},
+        types::{WlEnum, WlInt, WlNewId},
+    },
+    sys::MemMap,
+};
+
+use request::CreateBufferParam;
+
+/// A `wl_shm_pool`, a block of memory shared with the compositor.
+///
+/// The pool keeps the mapping of the backing memory alive; buffers created
+/// from it are views into this memory.
+pub struct ShmPool {
+    /// The object ID of the pool.
+    id: u32,
+    /// The connection the pool was created on.
+    conn: Connection,
+    /// The memfd backing the pool, kept to grow it.
+    file: File,
+    /// The mapping of the whole pool into this process.
+    map: MemMap,
+    /// The state of the shm object the pool was created from.
+    shm: Arc<ShmState>,
+}
+
+impl ShmPool {
+    /// Wraps a pool object that was just created on `conn`.
+    pub(crate) fn new(
+        id: u32,
+        conn: &Connection,
+        file: File,
+        map: MemMap,
+        shm: Arc<ShmState>,
+    ) -> ShmPool {
+        ShmPool {
+            id,
+            conn: conn.clone(),
+            file,
+            map,
+            shm,
+        }
+    }
+
+    /// Returns the object ID of the pool.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the size of the pool in bytes.
+    pub fn size(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns the pool memory.
+    pub fn data(&self) -> &[u8] {
+        self.map.as_slice()
+    }
+
+    /// Returns the pool memory for drawing.
+    pub fn data_mut(&mut self) -> &mut [u8] {
+        self.map.as_mut_slice()
+    }
+
+    /// Creates a buffer covering `height` rows of `stride` bytes starting at `offset`.
+    ///
+    /// # Errors
+    /// Returns an error if the buffer does not fit inside the pool, or if
+    /// the compositor does not support `format`.
+    pub fn create_buffer(
+        &self,
+        offset: usize,
+        width: i32,
+        height: i32,
+        stride: i32,
+        format: Format,
+    ) -> Result<Buffer> {
+        let len = stride as usize * height as usize;
+        if width <= 0 || height <= 0 || stride < width || offset + len > self.size() {
+            return Err(anyhow!(
+                "Buffer {}x{} (stride {}) at offset {} does not fit in a pool of {} bytes",
+                width,
+                height,
+                stride,
+                offset,
+                self.size()
+            ));
+        }
+        self.shm.check_format(format)?;
+
+        let id = self.conn.new_object(WlObjectId::Buffer, 1)?;
+        request::create_buffer(
+            &self.conn,
+            self.id,
+            CreateBufferParam::new(
+                WlNewId(id),
+                WlInt(offset as i32),
+                WlInt(width),
+                WlInt(height),
+                WlInt(stride),
+                WlEnum(format as u32),
+            ),
+        )?;
+
+        Ok(Buffer::new(id, &self.conn, offset, len))
+    }
+
+    /// Grows the pool to `size` bytes.
+    ///
+    /// The memfd is enlarged and remapped, and the compositor remaps it too.
+    /// Buffers created from the pool keep their offsets, and the contents
+    /// of the pool are preserved. Resizing to the current size does nothing.
+    ///
+    /// # Errors
+    /// Returns an error if `size` is smaller than the pool, since pools
+    /// cannot shrink, or if the memfd cannot be enlarged or remapped.
+    pub fn resize(&mut self, size: usize) -> Result<()> {
+        if size == self.size() {
+            return Ok(());
+        }
+        if size < self.size() {
+            return Err(anyhow!(
+                "wl_shm pool of {} bytes cannot shrink to {} bytes",
+                self.size(),
+                size
+            ));
+        }
+        let wire_size = i32::try_from(size)
+            .map_err(|_| anyhow!("wl_shm pool size {} does not fit in an int", size))?;
+
+        // The file must be large enough before the compositor remaps it
+        self.file.set_len(size as u64)?;
+        let map = MemMap::new(self.file.as_fd(), size)?;
+        request::resize(&self.conn, self.id, WlInt(wire_size))?;
+        self.map = map;
+
+        Ok(())
+    }
+
+    /// Destroys the pool.
+    ///
+    /// Buffers created from the pool remain valid, but the memory is unmapped
+    /// from this process, so they can no longer be drawn into.
+    ///
+    /// Dropping the handle does the same.
+    pub fn destroy(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl Drop for ShmPool {
+    fn drop(&mut self) {
+        self.conn
+            .destroy_on_drop(|conn| request::destroy(conn, self.id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::os::unix::net::UnixStream;
+
+    use super::*;
+    use crate::sys;
+
+    #[test]
+    fn resize_grows_and_keeps_contents() {
+        let (client, _compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+
+        let file = File::from(sys::memfd_create("test").unwrap());
+        file.set_len(16).unwrap();
+        let map = MemMap::new(file.as_fd(), 16).unwrap();
+        let id = conn.new_object(WlObjectId::ShmPool, 1).unwrap();
+        let mut pool = ShmPool::new(id, &conn, file, map, Arc::default());
+
+        pool.data_mut()[..4].copy_from_slice(b"wl\0\x01");
+        pool.resize(64).unwrap();
+
+        assert_eq!(pool.size(), 64);
+        assert_eq!(&pool.data()[..4], b"wl\0\x01");
+        assert!(pool.data()[16..].iter().all(|&byte| byte == 0));
+        assert!(pool.resize(32).is_err());
+    }
+}
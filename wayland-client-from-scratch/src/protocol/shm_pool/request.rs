@@ -0,0 +1,112 @@
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::types::{WlEnum, WlInt, WlNewId},
+    wl_request_opcode, wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to a Wayland shm pool object.
+    Opcode {
+        /// Creates a buffer from a region of the pool.
+        CreateBuffer = 0,
+
+        /// Destroys the pool; buffers created from it stay valid.
+        Destroy = 1 (destructor),
+
+        /// Grows the pool to a larger size.
+        Resize = 2,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wl_shm_pool.create_buffer` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="create_buffer">
+    ///   <description summary="create a buffer from the pool">
+    ///     Create a wl_buffer object from the pool.
+    ///
+    ///     The buffer is created offset bytes into the pool and has
+    ///     width and height as specified.  The stride argument specifies
+    ///     the number of bytes from the beginning of one row to the beginning
+    ///     of the next.  The format is the pixel format of the buffer and
+    ///     must be one of those advertised through the wl_shm.format event.
+    ///   </description>
+    ///   <arg name="id" type="new_id" interface="wl_buffer" summary="buffer to create"/>
+    ///   <arg name="offset" type="int" summary="buffer byte offset within the pool"/>
+    ///   <arg name="width" type="int" summary="buffer width, in pixels"/>
+    ///   <arg name="height" type="int" summary="buffer height, in pixels"/>
+    ///   <arg name="stride" type="int" summary="number of bytes from the beginning of one row to the beginning of the next row"/>
+    ///   <arg name="format" type="uint" enum="wl_shm.format" summary="buffer pixel format"/>
+    /// </request>
+    /// ```
+    CreateBufferParam {
+        /// The object ID to assign to the newly created wl_buffer object.
+        id: WlNewId,
+        /// The byte offset of the buffer within the pool.
+        offset: WlInt,
+        /// The buffer width in pixels.
+        width: WlInt,
+        /// The buffer height in pixels.
+        height: WlInt,
+        /// The number of bytes between the starts of two consecutive rows.
+        stride: WlInt,
+        /// The pixel format of the buffer.
+        format: WlEnum,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wl_shm_pool.resize` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="resize">
+    ///   <description summary="change the size of the pool mapping">
+    ///     This request will cause the server to remap the backing memory
+    ///     for the pool from the file descriptor passed when the pool was
+    ///     created, but using the new size.  This request can only be
+    ///     used to make the pool bigger.
+    ///
+    ///     This request only changes the amount of bytes that are mmapped
+    ///     by the server and does not touch the file corresponding to the
+    ///     file descriptor passed at creation time. It is the client's
+    ///     responsibility to ensure that the file is at least as big as
+    ///     the new pool size.
+    ///   </description>
+    ///   <arg name="size" type="int" summary="new size of the pool, in bytes"/>
+    /// </request>
+    /// ```
+    ResizeParam {
+        /// The new size of the pool in bytes.
+        size: WlInt,
+    }
+}
+
+/// Sends a `wl_shm_pool.create_buffer` request to the compositor.
+pub fn create_buffer(conn: &Connection, pool: u32, param: CreateBufferParam) -> Result<()> {
+    conn.send_request(pool, Opcode::CreateBuffer, &param)?;
+
+    Ok(())
+}
+
+/// Sends a `wl_shm_pool.destroy` request to the compositor.
+pub fn destroy(conn: &Connection, pool: u32) -> Result<()> {
+    conn.send_request(pool, Opcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends a `wl_shm_pool.resize` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `pool` - The ID of the pool
+/// * `size` - The new size of the pool, which must not be smaller
+pub fn resize(conn: &Connection, pool: u32, size: WlInt) -> Result<()> {
+    conn.send_request(pool, Opcode::Resize, &ResizeParam::new(size))?;
+
+    Ok(())
+}
@@ -0,0 +1,169 @@
+pub mod request;
+
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::{
+        WlObjectId,
+        surface::{Surface, SurfaceRole},
+        types::{WlInt, WlNewId, WlObject},
+    },
+};
+
+/// The highest `wl_subcompositor` version this crate implements.
+pub const WL_SUBCOMPOSITOR_VERSION: u32 = 1;
+
+/// A bound `wl_subcompositor` global, which nests surfaces inside other
+/// surfaces.
+#[derive(Clone)]
+pub struct SubCompositor {
+    /// The object ID of the bound subcompositor.
+    id: u32,
+    /// The negotiated interface version.
+    version: u32,
+    /// The connection the subcompositor was bound on.
+    conn: Connection,
+}
+
+impl SubCompositor {
+    /// Binds the `wl_subcompositor` global advertised by the compositor.
+    pub fn bind(conn: &Connection) -> Result<SubCompositor> {
+        let (id, version) = conn.bind(WlObjectId::SubCompositor, WL_SUBCOMPOSITOR_VERSION)?;
+
+        Ok(SubCompositor {
+            id,
+            version,
+            conn: conn.clone(),
+        })
+    }
+
+    /// Returns the object ID of the subcompositor.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Gives `surface` the subsurface role, attaching it to `parent`.
+    ///
+    /// The subsurface shows once `parent` is committed, stacked above its
+    /// siblings and the parent, and starts in synchronized mode.
+    ///
+    /// # Errors
+    /// Returns an error if `surface` already has another role.
+    pub fn get_subsurface(&self, surface: &Surface, parent: &Surface) -> Result<SubSurface> {
+        self.conn
+            .check_same_connection(surface.connection(), WlObjectId::Surface, surface.id())?;
+        self.conn
+            .check_same_connection(parent.connection(), WlObjectId::Surface, parent.id())?;
+
+        self.get_subsurface_of(surface, parent.id())
+    }
+
+    /// Gives `surface` the subsurface role, attaching it to the surface
+    /// with the object ID `parent`, which is owned elsewhere.
+    pub(crate) fn get_subsurface_of(&self, surface: &Surface, parent: u32) -> Result<SubSurface> {
+        surface
+            .state()
+            .assign_role(surface.id(), SurfaceRole::Subsurface)?;
+
+        let id = self.conn.new_object(WlObjectId::SubSurface, self.version)?;
+        request::get_subsurface(
+            &self.conn,
+            self.id,
+            WlNewId(id),
+            WlObject(surface.id()),
+            WlObject(parent),
+        )?;
+
+        Ok(SubSurface {
+            id,
+            conn: self.conn.clone(),
+        })
+    }
+
+    /// Destroys the subcompositor; existing subsurfaces keep working.
+    pub fn destroy(self) -> Result<()> {
+        request::destroy(&self.conn, self.id)
+    }
+}
+
+/// A `wl_subsurface`, the role of a surface shown as part of its parent.
+///
+/// The position and stacking order are applied with the next commit of the
+/// parent. In synchronized mode, the default, the commits of the surface
+/// itself are cached until then too, so that the parent and its children
+/// change at once; in desynchronized mode they apply right away.
+pub struct SubSurface {
+    /// The object ID of the subsurface.
+    id: u32,
+    /// The connection the subsurface was created on.
+    conn: Connection,
+}
+
+impl SubSurface {
+    /// Returns the object ID of the subsurface.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Moves the subsurface to `(x, y)` in the surface-local coordinates of
+    /// its parent. Negative positions and positions outside the parent are
+    /// allowed.
+    pub fn set_position(&self, x: i32, y: i32) -> Result<()> {
+        request::set_position(&self.conn, self.id, WlInt(x), WlInt(y))
+    }
+
+    /// Stacks the subsurface right above `sibling`, which must be another
+    /// subsurface of the same parent or the parent itself.
+    pub fn place_above(&self, sibling: &Surface) -> Result<()> {
+        self.conn
+            .check_same_connection(sibling.connection(), WlObjectId::Surface, sibling.id())?;
+
+        self.place(sibling.id(), true)
+    }
+
+    /// Stacks the subsurface right below `sibling`, which must be another
+    /// subsurface of the same parent or the parent itself.
+    pub fn place_below(&self, sibling: &Surface) -> Result<()> {
+        self.conn
+            .check_same_connection(sibling.connection(), WlObjectId::Surface, sibling.id())?;
+
+        self.place(sibling.id(), false)
+    }
+
+    /// Stacks the subsurface right above or below the surface with the
+    /// object ID `sibling`, which is owned elsewhere.
+    pub(crate) fn place(&self, sibling: u32, above: bool) -> Result<()> {
+        if above {
+            request::place_above(&self.conn, self.id, WlObject(sibling))
+        } else {
+            request::place_below(&self.conn, self.id, WlObject(sibling))
+        }
+    }
+
+    /// Caches the commits of the surface until its parent is committed.
+    pub fn set_sync(&self) -> Result<()> {
+        request::set_sync(&self.conn, self.id)
+    }
+
+    /// Applies the commits of the surface right away, unless an ancestor
+    /// is in synchronized mode.
+    pub fn set_desync(&self) -> Result<()> {
+        request::set_desync(&self.conn, self.id)
+    }
+
+    /// Removes the subsurface role; the surface is hidden right away.
+    ///
+    /// Dropping the handle does the same.
+    pub fn destroy(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl Drop for SubSurface {
+    fn drop(&mut self) {
+        self.conn
+            .destroy_on_drop(|conn| request::destroy_subsurface(conn, self.id));
+    }
+}
@@ -0,0 +1,270 @@
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::types::{WlInt, WlNewId, WlObject},
+    wl_request_opcode, wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to the `wl_subcompositor` object.
+    Opcode {
+        /// Destroys the subcompositor; existing subsurfaces keep working.
+        Destroy = 0 (destructor),
+
+        /// Gives a surface the subsurface role.
+        GetSubsurface = 1,
+    }
+}
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to a `wl_subsurface` object.
+    SubsurfaceOpcode {
+        /// Removes the subsurface role; the surface is unmapped.
+        Destroy = 0 (destructor),
+
+        /// Sets the position of the subsurface relative to its parent.
+        SetPosition = 1,
+
+        /// Stacks the subsurface right above a sibling or the parent.
+        PlaceAbove = 2,
+
+        /// Stacks the subsurface right below a sibling or the parent.
+        PlaceBelow = 3,
+
+        /// Makes the commits of the subsurface wait for its parent.
+        SetSync = 4,
+
+        /// Makes the commits of the subsurface apply on their own.
+        SetDesync = 5,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wl_subcompositor.get_subsurface` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="get_subsurface">
+    ///   <description summary="give a surface the role sub-surface">
+    ///     Create a sub-surface interface for the given surface, and
+    ///     associate it with the given parent surface. This turns a
+    ///     plain wl_surface into a sub-surface.
+    ///
+    ///     The to-be sub-surface must not already have another role, and it
+    ///     must not have an existing wl_subsurface object. Otherwise the
+    ///     bad_surface protocol error is raised.
+    ///
+    ///     Adding sub-surfaces to a parent is a double-buffered operation on the
+    ///     parent (see wl_surface.commit). The effect of adding a sub-surface
+    ///     becomes visible on the next time the state of the parent surface is
+    ///     applied.
+    ///
+    ///     The parent surface must not be one of the child surface's descendants,
+    ///     and the parent must be different from the child surface, otherwise the
+    ///     bad_parent protocol error is raised.
+    ///
+    ///     This request modifies the behaviour of wl_surface.commit request on
+    ///     the sub-surface, see the documentation on wl_subsurface interface.
+    ///   </description>
+    ///   <arg name="id" type="new_id" interface="wl_subsurface"
+    ///        summary="the new sub-surface object ID"/>
+    ///   <arg name="surface" type="object" interface="wl_surface"
+    ///        summary="the surface to be turned into a sub-surface"/>
+    ///   <arg name="parent" type="object" interface="wl_surface"
+    ///        summary="the parent surface"/>
+    /// </request>
+    /// ```
+    GetSubsurfaceParam {
+        /// The object ID to assign to the new subsurface.
+        id: WlNewId,
+        /// The surface to give the subsurface role.
+        surface: WlObject,
+        /// The surface the subsurface is attached to.
+        parent: WlObject,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wl_subsurface.set_position` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_position">
+    ///   <description summary="reposition the sub-surface">
+    ///     This schedules a sub-surface position change.
+    ///     The sub-surface will be moved so that its origin (top left
+    ///     corner pixel) will be at the location x, y of the parent surface
+    ///     coordinate system. The coordinates are not restricted to the parent
+    ///     surface area. Negative values are allowed.
+    ///
+    ///     The scheduled coordinates will take effect whenever the state of the
+    ///     parent surface is applied.
+    ///
+    ///     If more than one set_position request is invoked by the client before
+    ///     the commit of the parent surface, the position of a new request always
+    ///     replaces the scheduled position from any previous request.
+    ///
+    ///     The initial position is 0, 0.
+    ///   </description>
+    ///   <arg name="x" type="int" summary="x coordinate in the parent surface"/>
+    ///   <arg name="y" type="int" summary="y coordinate in the parent surface"/>
+    /// </request>
+    /// ```
+    SetPositionParam {
+        /// The horizontal position in the coordinates of the parent.
+        x: WlInt,
+        /// The vertical position in the coordinates of the parent.
+        y: WlInt,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wl_subsurface.place_above` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="place_above">
+    ///   <description summary="restack the sub-surface">
+    ///     This sub-surface is taken from the stack, and put back just
+    ///     above the reference surface, changing the z-order of the sub-surfaces.
+    ///     The reference surface must be one of the sibling surfaces, or the
+    ///     parent surface. Using any other surface, including this sub-surface,
+    ///     will cause a protocol error.
+    ///
+    ///     The z-order is double-buffered. Requests are handled in order and
+    ///     applied immediately to a pending state. The final pending state is
+    ///     copied to the active state the next time the state of the parent
+    ///     surface is applied.
+    ///
+    ///     A new sub-surface is initially added as the top-most in the stack
+    ///     of its siblings and parent.
+    ///   </description>
+    ///   <arg name="sibling" type="object" interface="wl_surface"
+    ///        summary="the reference surface"/>
+    /// </request>
+    /// ```
+    PlaceAboveParam {
+        /// The sibling or parent surface to stack above.
+        sibling: WlObject,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wl_subsurface.place_below` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="place_below">
+    ///   <description summary="restack the sub-surface">
+    ///     The sub-surface is placed just below the reference surface.
+    ///     See wl_subsurface.place_above.
+    ///   </description>
+    ///   <arg name="sibling" type="object" interface="wl_surface"
+    ///        summary="the reference surface"/>
+    /// </request>
+    /// ```
+    PlaceBelowParam {
+        /// The sibling or parent surface to stack below.
+        sibling: WlObject,
+    }
+}
+
+/// Sends a `wl_subcompositor.destroy` request to the compositor.
+pub fn destroy(conn: &Connection, subcompositor: u32) -> Result<()> {
+    conn.send_request(subcompositor, Opcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends a `wl_subcompositor.get_subsurface` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `subcompositor` - The ID of the bound subcompositor
+/// * `id` - The object ID to assign to the new subsurface
+/// * `surface` - The surface to give the subsurface role
+/// * `parent` - The surface the subsurface is attached to
+pub fn get_subsurface(
+    conn: &Connection,
+    subcompositor: u32,
+    id: WlNewId,
+    surface: WlObject,
+    parent: WlObject,
+) -> Result<()> {
+    conn.send_request(
+        subcompositor,
+        Opcode::GetSubsurface,
+        &GetSubsurfaceParam::new(id, surface, parent),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `wl_subsurface.destroy` request to the compositor.
+pub fn destroy_subsurface(conn: &Connection, subsurface: u32) -> Result<()> {
+    conn.send_request(subsurface, SubsurfaceOpcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends a `wl_subsurface.set_position` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `subsurface` - The ID of the subsurface
+/// * `x`, `y` - The position in the coordinates of the parent
+pub fn set_position(conn: &Connection, subsurface: u32, x: WlInt, y: WlInt) -> Result<()> {
+    conn.send_request(
+        subsurface,
+        SubsurfaceOpcode::SetPosition,
+        &SetPositionParam::new(x, y),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `wl_subsurface.place_above` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `subsurface` - The ID of the subsurface
+/// * `sibling` - The sibling or parent surface to stack above
+pub fn place_above(conn: &Connection, subsurface: u32, sibling: WlObject) -> Result<()> {
+    conn.send_request(
+        subsurface,
+        SubsurfaceOpcode::PlaceAbove,
+        &PlaceAboveParam::new(sibling),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `wl_subsurface.place_below` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `subsurface` - The ID of the subsurface
+/// * `sibling` - The sibling or parent surface to stack below
+pub fn place_below(conn: &Connection, subsurface: u32, sibling: WlObject) -> Result<()> {
+    conn.send_request(
+        subsurface,
+        SubsurfaceOpcode::PlaceBelow,
+        &PlaceBelowParam::new(sibling),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `wl_subsurface.set_sync` request to the compositor.
+pub fn set_sync(conn: &Connection, subsurface: u32) -> Result<()> {
+    conn.send_request(subsurface, SubsurfaceOpcode::SetSync, &())?;
+
+    Ok(())
+}
+
+/// Sends a `wl_subsurface.set_desync` request to the compositor.
+pub fn set_desync(conn: &Connection, subsurface: u32) -> Result<()> {
+    conn.send_request(subsurface, SubsurfaceOpcode::SetDesync, &())?;
+
+    Ok(())
+}
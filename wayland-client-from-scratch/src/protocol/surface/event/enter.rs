@@ -0,0 +1,54 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{surface::SurfaceState, types::WlObject},
+};
+
+/// Represents a `wl_surface.enter` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="enter">
+///   <description summary="surface enters an output">
+///     This is emitted whenever a surface's creation, movement, or resizing
+///     results in some part of it being within the scanout region of an
+///     output.
+///
+///     Note that a surface may be overlapping with zero or more outputs.
+///   </description>
+///   <arg name="output" type="object" interface="wl_output" summary="output entered by the surface"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Enter {
+    /// The output entered by the surface.
+    pub output: WlObject,
+}
+
+impl TryFrom<&[u8]> for Enter {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Enter` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `output` (u32) - The ID of the entered output
+    fn try_from(buf: &[u8]) -> Result<Enter> {
+        Ok(Enter {
+            output: WlObject::read(buf, 0)?,
+        })
+    }
+}
+
+/// Handles a `wl_surface.enter` event by adding the output to the outputs
+/// the surface is on and notifying the listener.
+pub(super) fn handle_wl_surface_enter(
+    conn: &Connection,
+    surface_id: u32,
+    event: Enter,
+) -> Result<()> {
+    if let Some(state) = conn.object_data::<SurfaceState>(surface_id) {
+        state.enter_output(conn, surface_id, event.output.get());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{surface::SurfaceState, types::WlObject},
+};
+
+/// Represents a `wl_surface.leave` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="leave">
+///   <description summary="surface leaves an output">
+///     This is emitted whenever a surface's creation, movement, or resizing
+///     results in it no longer having any part of it within the scanout region
+///     of an output.
+///
+///     Clients should not use the number of outputs the surface is on for frame
+///     throttling purposes. The surface might be hidden even if no leave event
+///     has been sent, and the compositor might expect new surface content
+///     updates even if no enter event has been sent. The frame event should be
+///     used instead.
+///   </description>
+///   <arg name="output" type="object" interface="wl_output" summary="output left by the surface"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Leave {
+    /// The output left by the surface.
+    pub output: WlObject,
+}
+
+impl TryFrom<&[u8]> for Leave {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Leave` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `output` (u32) - The ID of the left output
+    fn try_from(buf: &[u8]) -> Result<Leave> {
+        Ok(Leave {
+            output: WlObject::read(buf, 0)?,
+        })
+    }
+}
+
+/// Handles a `wl_surface.leave` event by removing the output from the
+/// outputs the surface is on and notifying the listener.
+pub(super) fn handle_wl_surface_leave(
+    conn: &Connection,
+    surface_id: u32,
+    event: Leave,
+) -> Result<()> {
+    if let Some(state) = conn.object_data::<SurfaceState>(surface_id) {
+        state.leave_output(conn, surface_id, event.output.get());
+    }
+
+    Ok(())
+}
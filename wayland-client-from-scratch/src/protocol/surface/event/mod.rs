@@ -0,0 +1,138 @@
+pub mod enter;
+pub mod leave;
+pub mod preferred_buffer_scale;
+pub mod preferred_buffer_transform;
+
+use crate::{
+    connection::Connection,
+    error::{Error, Result, anyhow},
+    protocol::message::WlMessage,
+};
+
+/// Represents the event types that can be emitted by a Wayland surface object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    /// The surface now overlaps the given output.
+    ///
+    /// # Event Arguments
+    /// - `output`: The output entered by the surface
+    Enter = 0,
+
+    /// The surface no longer overlaps the given output.
+    ///
+    /// # Event Arguments
+    /// - `output`: The output left by the surface
+    Leave = 1,
+
+    /// The preferred buffer scale for this surface (since version 6).
+    ///
+    /// # Event Arguments
+    /// - `factor`: The preferred integer scale
+    PreferredBufferScale = 2,
+
+    /// The preferred buffer transform for this surface (since version 6).
+    ///
+    /// # Event Arguments
+    /// - `transform`: The preferred `wl_output.transform`
+    PreferredBufferTransform = 3,
+}
+
+impl TryFrom<u16> for Opcode {
+    type Error = Error;
+
+    /// Attempts to convert a raw opcode value into a structured `WlSurfaceEvent`.
+    fn try_from(value: u16) -> Result<Opcode> {
+        match value {
+            0 => Ok(Opcode::Enter),
+            1 => Ok(Opcode::Leave),
+            2 => Ok(Opcode::PreferredBufferScale),
+            3 => Ok(Opcode::PreferredBufferTransform),
+            _ => Err(anyhow!("Invalid wl_surface event opcode: {}", value)),
+        }
+    }
+}
+
+impl Opcode {
+    /// Returns the interface version the event was introduced in.
+    pub fn since(self) -> u32 {
+        match self {
+            Opcode::Enter | Opcode::Leave => 1,
+            Opcode::PreferredBufferScale | Opcode::PreferredBufferTransform => 6,
+        }
+    }
+}
+
+/// A decoded `wl_surface` event, with its arguments.
+#[derive(Debug)]
+pub enum Event {
+    /// The surface now overlaps the given output.
+    Enter(enter::Enter),
+    /// The surface no longer overlaps the given output.
+    Leave(leave::Leave),
+    /// The preferred buffer scale for this surface (since version 6).
+    PreferredBufferScale(preferred_buffer_scale::PreferredBufferScale),
+    /// The preferred buffer transform for this surface (since version 6).
+    PreferredBufferTransform(preferred_buffer_transform::PreferredBufferTransform),
+}
+
+impl Event {
+    /// Decodes a `wl_surface` event from its message.
+    ///
+    /// # Errors
+    /// Returns an error if the opcode is unknown or the arguments are malformed.
+    pub fn decode(_conn: &Connection, msg: &WlMessage) -> Result<Event> {
+        let buf: &[u8] = &msg.data;
+
+        Ok(match Opcode::try_from(msg.header.opcode)? {
+            Opcode::Enter => Event::Enter(enter::Enter::try_from(buf)?),
+            Opcode::Leave => Event::Leave(leave::Leave::try_from(buf)?),
+            Opcode::PreferredBufferScale => Event::PreferredBufferScale(
+                preferred_buffer_scale::PreferredBufferScale::try_from(buf)?,
+            ),
+            Opcode::PreferredBufferTransform => Event::PreferredBufferTransform(
+                preferred_buffer_transform::PreferredBufferTransform::try_from(buf)?,
+            ),
+        })
+    }
+
+    /// Returns the opcode of the event.
+    pub fn opcode(&self) -> Opcode {
+        match self {
+            Event::Enter(_) => Opcode::Enter,
+            Event::Leave(_) => Opcode::Leave,
+            Event::PreferredBufferScale(_) => Opcode::PreferredBufferScale,
+            Event::PreferredBufferTransform(_) => Opcode::PreferredBufferTransform,
+        }
+    }
+}
+
+/// Dispatches incoming Wayland surface events.
+///
+/// # Event Routing
+/// * `Enter` events are routed to `enter::handle_wl_surface_enter`
+/// * `Leave` events are routed to `leave::handle_wl_surface_leave`
+/// * `PreferredBufferScale` events are routed to
+///   `preferred_buffer_scale::handle_wl_surface_preferred_buffer_scale`
+/// * `PreferredBufferTransform` events are routed to
+///   `preferred_buffer_transform::handle_wl_surface_preferred_buffer_transform`
+pub fn handle_wl_surface_event(conn: &Connection, msg: WlMessage) -> Result<()> {
+    // Route the event to the appropriate handler based on type
+    match Event::decode(conn, &msg)? {
+        Event::Enter(event) => enter::handle_wl_surface_enter(conn, msg.header.object_id, event),
+        Event::Leave(event) => leave::handle_wl_surface_leave(conn, msg.header.object_id, event),
+        Event::PreferredBufferScale(event) => {
+            preferred_buffer_scale::handle_wl_surface_preferred_buffer_scale(
+                conn,
+                msg.header.object_id,
+                event,
+            )
+        }
+        Event::PreferredBufferTransform(event) => {
+            preferred_buffer_transform::handle_wl_surface_preferred_buffer_transform(
+                conn,
+                msg.header.object_id,
+                event,
+            )
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        surface::{SurfaceEvent, SurfaceState},
+        types::WlInt,
+    },
+};
+
+/// Represents a `wl_surface.preferred_buffer_scale` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="preferred_buffer_scale" since="6">
+///   <description summary="preferred buffer scale for the surface">
+///     This event indicates the preferred buffer scale for this surface. It is
+///     sent whenever the compositor's preference changes.
+///
+///     Before receiving this event the preferred buffer scale for this surface
+///     is 1.
+///
+///     It is intended that scaling aware clients use this event to scale their
+///     content and use wl_surface.set_buffer_scale to indicate the scale they
+///     have rendered with. This allows clients to supply a higher detail
+///     buffer.
+///
+///     The compositor shall emit a scale value greater than 0.
+///   </description>
+///   <arg name="factor" type="int" summary="preferred scaling factor"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PreferredBufferScale {
+    /// The preferred integer scale factor.
+    pub factor: WlInt,
+}
+
+impl TryFrom<&[u8]> for PreferredBufferScale {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `PreferredBufferScale` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `factor` (i32) - The preferred scale factor
+    fn try_from(buf: &[u8]) -> Result<PreferredBufferScale> {
+        Ok(PreferredBufferScale {
+            factor: WlInt::read(buf, 0)?,
+        })
+    }
+}
+
+/// Handles a `wl_surface.preferred_buffer_scale` event by recording the scale
+/// and notifying the listener when it changed.
+pub(super) fn handle_wl_surface_preferred_buffer_scale(
+    conn: &Connection,
+    surface_id: u32,
+    event: PreferredBufferScale,
+) -> Result<()> {
+    let factor = event.factor.get();
+
+    if let Some(state) = conn.object_data::<SurfaceState>(surface_id)
+        && state.preferred_scale.replace(Some(factor)) != Some(factor)
+    {
+        state.scale_inputs_changed();
+        conn.emit(surface_id, SurfaceEvent::PreferredBufferScale(factor));
+    }
+
+    Ok(())
+}
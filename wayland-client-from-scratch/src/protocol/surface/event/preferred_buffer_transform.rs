@@ -0,0 +1,70 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        output::event::geometry::Transform,
+        surface::{SurfaceEvent, SurfaceState},
+        types::WlUInt,
+    },
+};
+
+/// Represents a `wl_surface.preferred_buffer_transform` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="preferred_buffer_transform" since="6">
+///   <description summary="preferred buffer transform for the surface">
+///     This event indicates the preferred buffer transform for this surface.
+///     It is sent whenever the compositor's preference changes.
+///
+///     Before receiving this event the preferred buffer transform for this
+///     surface is normal.
+///
+///     Applying this transformation to the surface buffer contents and using
+///     wl_surface.set_buffer_transform might allow the compositor to use the
+///     surface buffer more efficiently.
+///   </description>
+///   <arg name="transform" type="uint" enum="wl_output.transform"
+///        summary="preferred transform"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PreferredBufferTransform {
+    /// The preferred transform, as a `Transform` value.
+    pub transform: WlUInt,
+}
+
+impl TryFrom<&[u8]> for PreferredBufferTransform {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `PreferredBufferTransform` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `transform` (u32) - The `wl_output.transform` value
+    fn try_from(buf: &[u8]) -> Result<PreferredBufferTransform> {
+        Ok(PreferredBufferTransform {
+            transform: WlUInt::read(buf, 0)?,
+        })
+    }
+}
+
+/// Handles a `wl_surface.preferred_buffer_transform` event by recording the
+/// transform and notifying the listener when it changed.
+pub(super) fn handle_wl_surface_preferred_buffer_transform(
+    conn: &Connection,
+    surface_id: u32,
+    event: PreferredBufferTransform,
+) -> Result<()> {
+    let transform = Transform::try_from(event.transform.get()).unwrap_or(Transform::Normal);
+
+    if let Some(state) = conn.object_data::<SurfaceState>(surface_id)
+        && state.preferred_transform.replace(Some(transform)) != Some(transform)
+    {
+        conn.emit(
+            surface_id,
+            SurfaceEvent::PreferredBufferTransform(transform),
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,734 @@
+pub mod event;
+pub mod request;
+
+use std::{
+    any::Any,
+    sync::{Arc, Weak},
+};
+
+use crate::{
+    connection::Connection,
+    error::{Result, anyhow},
+    geometry::Rect,
+    protocol::{
+        WlObjectId,
+        buffer::Buffer,
+        idle_inhibit::InhibitState,
+        output::{OutputState, event::geometry::Transform},
+        region::Region,
+        types::{WlInt, WlNewId, WlObject},
+        xdg_surface::XdgSurfaceState,
+    },
+    scale::ScaleState,
+    sync::{Lock, LockCell},
+};
+
+/// The first `wl_surface` version supporting `set_buffer_transform`.
+const WL_SURFACE_SET_BUFFER_TRANSFORM_SINCE: u32 = 2;
+
+/// The first `wl_surface` version supporting `set_buffer_scale`.
+pub(crate) const WL_SURFACE_SET_BUFFER_SCALE_SINCE: u32 = 3;
+
+/// The first `wl_surface` version supporting `damage_buffer`.
+const WL_SURFACE_DAMAGE_BUFFER_SINCE: u32 = 4;
+
+/// The first `wl_surface` version supporting `offset`.
+const WL_SURFACE_OFFSET_SINCE: u32 = 5;
+
+/// An event delivered to the handler of a [`Surface`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SurfaceEvent {
+    /// Part of the surface is now shown on the output with this object ID.
+    Enter(u32),
+    /// The surface is no longer shown on the output with this object ID,
+    /// also sent when the output is unplugged.
+    Leave(u32),
+    /// The compositor prefers buffers rendered at this integer scale.
+    PreferredBufferScale(i32),
+    /// The compositor prefers buffers pre-rotated with this transform,
+    /// usually the transform of the output the surface is on.
+    PreferredBufferTransform(Transform),
+}
+
+/// The role of a surface, which decides how the compositor shows it.
+///
+/// A surface gets its role from the first role object created for it and
+/// keeps it for its whole lifetime, even once the role object is destroyed:
+/// it may be given the same role again, but never another one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SurfaceRole {
+    /// A pointer image, given by `wl_pointer.set_cursor`.
+    Cursor,
+    /// A desktop surface, given by `xdg_wm_base.get_xdg_surface`.
+    XdgSurface,
+    /// Part of another surface, given by `wl_subcompositor.get_subsurface`.
+    Subsurface,
+    /// A desktop shell component, given by
+    /// `zwlr_layer_shell_v1.get_layer_surface`.
+    LayerSurface,
+    /// The content of an output while the session is locked, given by
+    /// `ext_session_lock_v1.get_lock_surface`.
+    LockSurface,
+}
+
+impl SurfaceRole {
+    /// Returns the name of the role in error messages.
+    fn name(self) -> &'static str {
+        match self {
+            SurfaceRole::Cursor => "cursor",
+            SurfaceRole::XdgSurface => "xdg_surface",
+            SurfaceRole::Subsurface => "subsurface",
+            SurfaceRole::LayerSurface => "layer surface",
+            SurfaceRole::LockSurface => "lock surface",
+        }
+    }
+}
+
+/// Sends the acknowledgement of a configure of the role object of a surface.
+pub(crate) type RoleAck = Box<dyn FnOnce(&Connection) -> Result<()> + Send>;
+
+/// Client-side state of a surface, used to catch role protocol misuse
+/// before the compositor disconnects the client for it.
+pub(crate) struct SurfaceState {
+    /// The buffer attached since the last commit: `Some(true)` for a buffer,
+    /// `Some(false)` for a null buffer, `None` if nothing was attached.
+    pending_buffer: LockCell<Option<bool>>,
+    /// The ID of the buffer attached since the last commit, marked busy
+    /// once a commit carries it to the compositor.
+    attached_buffer: LockCell<Option<u32>>,
+    /// Whether the committed state of the surface includes a buffer.
+    has_buffer: LockCell<bool>,
+    /// The role of the surface, once given one.
+    role: LockCell<Option<SurfaceRole>>,
+    /// The state of the `xdg_surface` created for this surface, if any.
+    pub(crate) xdg_surface: Lock<Option<Arc<XdgSurfaceState>>>,
+    /// The acknowledgement of the configure taken from a role object
+    /// acknowledging its configures itself, sent before the next commit.
+    role_ack: Lock<Option<RoleAck>>,
+    /// The buffer scale preferred by the compositor, once announced.
+    pub(crate) preferred_scale: LockCell<Option<i32>>,
+    /// The buffer transform preferred by the compositor, once announced.
+    pub(crate) preferred_transform: LockCell<Option<Transform>>,
+    /// The IDs of the outputs the surface is on, in the order entered.
+    pub(crate) outputs: Lock<Vec<u32>>,
+    /// The scale tracker of the surface, notified when the preferred scale
+    /// or the outputs change.
+    pub(crate) scale: Lock<Weak<ScaleState>>,
+    /// The idle inhibitors tied to the surface, re-created when the
+    /// surface is mapped again.
+    pub(crate) inhibitors: Lock<Vec<Weak<InhibitState>>>,
+    /// The object the surface belongs to, such as a window, which input
+    /// events on the surface are routed to.
+    owner: Lock<Option<Weak<dyn Any + Send + Sync>>>,
+}
+
+impl Default for SurfaceState {
+    fn default() -> SurfaceState {
+        SurfaceState {
+            pending_buffer: LockCell::new(None),
+            attached_buffer: LockCell::new(None),
+            has_buffer: LockCell::new(false),
+            role: LockCell::new(None),
+            xdg_surface: Lock::new(None),
+            role_ack: Lock::new(None),
+            preferred_scale: LockCell::new(None),
+            preferred_transform: LockCell::new(None),
+            outputs: Lock::new(Vec::new()),
+            scale: Lock::new(Weak::new()),
+            inhibitors: Lock::new(Vec::new()),
+            owner: Lock::new(None),
+        }
+    }
+}
+
+impl SurfaceState {
+    /// Records the object the surface belongs to, replacing the previous one.
+    pub(crate) fn set_owner(&self, owner: Weak<dyn Any + Send + Sync>) {
+        self.owner.replace(Some(owner));
+    }
+
+    /// Returns the object the surface belongs to, if it has the expected
+    /// type and still exists.
+    pub(crate) fn owner<T: Any + Send + Sync>(&self) -> Option<Arc<T>> {
+        let owner = self.owner.borrow().as_ref()?.upgrade()?;
+        owner.downcast::<T>().ok()
+    }
+
+    /// Returns `true` if the committed state of the surface includes a buffer.
+    pub(crate) fn has_buffer(&self) -> bool {
+        self.has_buffer.get()
+    }
+
+    /// Returns the integer scale the surface should be rendered at: the
+    /// preferred buffer scale once announced, otherwise the highest scale
+    /// of the outputs it is on.
+    pub(crate) fn integer_scale(&self, conn: &Connection) -> i32 {
+        if let Some(preferred) = self.preferred_scale.get() {
+            return preferred;
+        }
+
+        self.outputs
+            .borrow()
+            .iter()
+            .filter_map(|&output| conn.object_data::<OutputState>(output))
+            .map(|output| output.scale())
+            .max()
+            .unwrap_or(1)
+    }
+
+    /// Gives the surface `role`, which it may already have.
+    ///
+    /// # Errors
+    /// Returns an error if the surface already has another role, which the
+    /// compositor would treat as a protocol error.
+    pub(crate) fn assign_role(&self, surface_id: u32, role: SurfaceRole) -> Result<()> {
+        match self.role.get() {
+            Some(current) if current != role => Err(anyhow!(
+                "wl_surface {surface_id} already has the {} role and cannot become a {}",
+                current.name(),
+                role.name()
+            )),
+            _ => {
+                self.role.set(Some(role));
+                Ok(())
+            }
+        }
+    }
+
+    /// Schedules `ack` to be sent right before the next commit, replacing
+    /// the acknowledgement of an older configure.
+    pub(crate) fn ack_on_commit(&self, ack: RoleAck) {
+        self.role_ack.replace(Some(ack));
+    }
+
+    /// Drops the scheduled acknowledgement, once its role object is destroyed.
+    pub(crate) fn cancel_ack(&self) {
+        self.role_ack.take();
+    }
+
+    /// Records that the surface entered `output`, notifying the listener.
+    pub(crate) fn enter_output(&self, conn: &Connection, surface_id: u32, output: u32) {
+        {
+            let mut outputs = self.outputs.borrow_mut();
+            if outputs.contains(&output) {
+                return;
+            }
+            outputs.push(output);
+        }
+
+        self.scale_inputs_changed();
+        conn.emit(surface_id, SurfaceEvent::Enter(output));
+    }
+
+    /// Records that the surface left `output`, notifying the listener.
+    pub(crate) fn leave_output(&self, conn: &Connection, surface_id: u32, output: u32) {
+        {
+            let mut outputs = self.outputs.borrow_mut();
+            let Some(index) = outputs.iter().position(|&entered| entered == output) else {
+                return;
+            };
+            outputs.remove(index);
+        }
+
+        self.scale_inputs_changed();
+        conn.emit(surface_id, SurfaceEvent::Leave(output));
+    }
+
+    /// Lets the idle inhibitors of the surface follow a commit that mapped
+    /// or unmapped it.
+    fn mapped_changed(&self, mapped: bool) -> Result<()> {
+        let inhibitors: Vec<_> = {
+            let mut inhibitors = self.inhibitors.borrow_mut();
+            inhibitors.retain(|inhibitor| inhibitor.strong_count() > 0);
+            inhibitors.iter().filter_map(Weak::upgrade).collect()
+        };
+
+        for inhibitor in inhibitors {
+            inhibitor.mapped_changed(mapped)?;
+        }
+
+        Ok(())
+    }
+
+    /// Lets the scale tracker of the surface, if any, recompute the scale.
+    pub(crate) fn scale_inputs_changed(&self) {
+        let scale = self.scale.borrow().upgrade();
+        if let Some(scale) = scale {
+            scale.update();
+        }
+    }
+}
+
+/// Makes every surface leave the outputs bound to the removed `wl_output`
+/// global `global_name`.
+///
+/// Compositors usually send `wl_surface.leave` before removing an output,
+/// but are not required to, so the surfaces would otherwise keep
+/// referring to an output that no longer exists.
+pub(crate) fn remove_output_global(conn: &Connection, global_name: u32) {
+    let outputs: Vec<u32> = conn
+        .objects_of(WlObjectId::Output)
+        .into_iter()
+        .filter(|&output| {
+            conn.object_data::<OutputState>(output)
+                .is_some_and(|state| state.global_name() == global_name)
+        })
+        .collect();
+    if outputs.is_empty() {
+        return;
+    }
+
+    for surface_id in conn.objects_of(WlObjectId::Surface) {
+        let Some(state) = conn.object_data::<SurfaceState>(surface_id) else {
+            continue;
+        };
+        for &output in &outputs {
+            state.leave_output(conn, surface_id, output);
+        }
+    }
+}
+
+/// A `wl_surface`, a rectangular area that can display buffers and receive input.
+pub struct Surface {
+    /// The object ID of the surface.
+    id: u32,
+    /// The connection the surface was created on.
+    conn: Connection,
+    /// The client-side state of the surface.
+    state: Arc<SurfaceState>,
+}
+
+impl Surface {
+    /// Wraps a surface object that was just created on `conn`.
+    pub(crate) fn new(id: u32, conn: &Connection) -> Surface {
+        let state = Arc::new(SurfaceState::default());
+        conn.set_object_data(id, state.clone());
+
+        Surface {
+            id,
+            conn: conn.clone(),
+            state,
+        }
+    }
+
+    /// Returns the connection the surface was created on.
+    pub(crate) fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    /// Returns the client-side state of the surface.
+    pub(crate) fn state(&self) -> Arc<SurfaceState> {
+        self.state.clone()
+    }
+
+    /// Returns the object IDs of the outputs the surface is shown on, in
+    /// the order it entered them, to be matched with
+    /// [`Output::id`](crate::protocol::output::Output::id).
+    ///
+    /// A surface may be on several outputs at once, or on none while it is
+    /// hidden. Changes are announced with [`SurfaceEvent::Enter`] and
+    /// [`SurfaceEvent::Leave`].
+    pub fn outputs(&self) -> Vec<u32> {
+        self.state.outputs.borrow().clone()
+    }
+
+    /// Returns the object ID of the surface.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the role of the surface, if it was given one.
+    pub fn role(&self) -> Option<SurfaceRole> {
+        self.state.role.get()
+    }
+
+    /// Registers the handler receiving the events of the surface, replacing
+    /// any previous one.
+    pub fn set_event_handler<F>(&self, handler: F)
+    where
+        F: FnMut(&Connection, SurfaceEvent) + Send + 'static,
+    {
+        self.conn
+            .set_listener::<SurfaceEvent>(self.id, Box::new(handler));
+    }
+
+    /// Returns the buffer scale preferred by the compositor, 1 until announced.
+    pub fn preferred_buffer_scale(&self) -> i32 {
+        self.state.preferred_scale.get().unwrap_or(1)
+    }
+
+    /// Returns the buffer transform preferred by the compositor,
+    /// [`Transform::Normal`] until announced.
+    ///
+    /// Rendering buffers pre-rotated with this transform and declaring it
+    /// with [`Surface::set_buffer_transform`] lets the compositor show them
+    /// on a rotated output without rotating them itself, e.g. by scanning
+    /// them out directly.
+    pub fn preferred_buffer_transform(&self) -> Transform {
+        self.state
+            .preferred_transform
+            .get()
+            .unwrap_or(Transform::Normal)
+    }
+
+    /// Schedules `callback` to run when it is a good time to draw the next frame.
+    ///
+    /// Creates a `wl_callback` through `wl_surface.frame` and stores the closure
+    /// until the compositor fires it. The closure receives the connection and
+    /// the frame timestamp in milliseconds. Frame callbacks are single-shot and
+    /// the callback object is destroyed by the compositor, so continuous
+    /// animation requests the next frame from inside the closure.
+    ///
+    /// Like all double-buffered surface state, the request only takes effect
+    /// on the next [`Surface::commit`].
+    pub fn request_frame<F>(&self, callback: F) -> Result<()>
+    where
+        F: FnOnce(&Connection, u32) + Send + 'static,
+    {
+        let callback_id = self.conn.new_object(WlObjectId::Callback, self.version())?;
+
+        self.conn.add_callback(callback_id, Box::new(callback));
+        request::frame(&self.conn, self.id, WlNewId(callback_id))
+    }
+
+    /// Returns the interface version the surface was created with.
+    pub fn version(&self) -> u32 {
+        self.conn.object(self.id).map_or(1, |object| object.version)
+    }
+
+    /// Sets the buffer displayed by the surface on the next commit.
+    ///
+    /// Passing `None` unmaps the surface. The buffer is marked busy once the
+    /// next [`Surface::commit`] is sent, until the compositor releases it;
+    /// a buffer replaced by another attach before the commit stays free.
+    pub fn attach(&self, buffer: Option<&Buffer>) -> Result<()> {
+        if let Some(buffer) = buffer {
+            self.conn.check_same_connection(
+                buffer.connection(),
+                WlObjectId::Buffer,
+                buffer.id(),
+            )?;
+        }
+        let buffer_id = buffer.map_or(0, |buffer| buffer.id());
+        request::attach(&self.conn, self.id, WlObject(buffer_id), WlInt(0), WlInt(0))?;
+
+        self.state.attached_buffer.set(buffer.map(Buffer::id));
+        self.state.pending_buffer.set(Some(buffer.is_some()));
+
+        Ok(())
+    }
+
+    /// Marks a rectangle of the surface as damaged, in surface-local
+    /// coordinates.
+    ///
+    /// Prefer [`Surface::damage_buffer`], which is not affected by the
+    /// buffer scale and transform, when the surface version supports it.
+    pub fn damage(&self, rect: Rect) -> Result<()> {
+        request::damage(
+            &self.conn,
+            self.id,
+            WlInt(rect.x),
+            WlInt(rect.y),
+            WlInt(rect.width),
+            WlInt(rect.height),
+        )
+    }
+
+    /// Marks a rectangle of the pending buffer as damaged, in buffer coordinates.
+    ///
+    /// # Errors
+    /// Returns an error if the surface version predates `damage_buffer`.
+    pub fn damage_buffer(&self, rect: Rect) -> Result<()> {
+        if self.version() < WL_SURFACE_DAMAGE_BUFFER_SINCE {
+            return Err(anyhow!(
+                "wl_surface.damage_buffer requires version {}, surface {} has version {}",
+                WL_SURFACE_DAMAGE_BUFFER_SINCE,
+                self.id,
+                self.version()
+            ));
+        }
+
+        request::damage_buffer(
+            &self.conn,
+            self.id,
+            WlInt(rect.x),
+            WlInt(rect.y),
+            WlInt(rect.width),
+            WlInt(rect.height),
+        )
+    }
+
+    /// Sets the part of the surface whose content is opaque, letting the
+    /// compositor skip drawing what is behind it. `None` empties it.
+    ///
+    /// The region is copied, so it can be dropped right away. Like all
+    /// double-buffered surface state, it applies on the next commit.
+    pub fn set_opaque_region(&self, region: Option<&Region>) -> Result<()> {
+        if let Some(region) = region {
+            self.conn.check_same_connection(
+                region.connection(),
+                WlObjectId::Region,
+                region.id(),
+            )?;
+        }
+        let region_id = region.map_or(0, |region| region.id());
+
+        request::set_opaque_region(&self.conn, self.id, WlObject(region_id))
+    }
+
+    /// Sets the part of the surface accepting pointer and touch input.
+    /// `None` makes the whole surface accept it again.
+    ///
+    /// The region is copied, so it can be dropped right away. Like all
+    /// double-buffered surface state, it applies on the next commit.
+    pub fn set_input_region(&self, region: Option<&Region>) -> Result<()> {
+        if let Some(region) = region {
+            self.conn.check_same_connection(
+                region.connection(),
+                WlObjectId::Region,
+                region.id(),
+            )?;
+        }
+        let region_id = region.map_or(0, |region| region.id());
+
+        request::set_input_region(&self.conn, self.id, WlObject(region_id))
+    }
+
+    /// Declares that the attached buffers are rendered at `scale`, so the
+    /// compositor shows them `scale` times smaller.
+    ///
+    /// The size of the buffers must be a multiple of the scale. Like all
+    /// double-buffered surface state, it applies on the next commit.
+    ///
+    /// # Errors
+    /// Returns an error if the scale is not positive or the surface version
+    /// predates `set_buffer_scale`.
+    pub fn set_buffer_scale(&self, scale: i32) -> Result<()> {
+        if scale <= 0 {
+            return Err(anyhow!(
+                "wl_surface {}: buffer scale must be positive, got {scale}",
+                self.id
+            ));
+        }
+        if self.version() < WL_SURFACE_SET_BUFFER_SCALE_SINCE {
+            return Err(anyhow!(
+                "wl_surface.set_buffer_scale requires version {}, surface {} has version {}",
+                WL_SURFACE_SET_BUFFER_SCALE_SINCE,
+                self.id,
+                self.version()
+            ));
+        }
+
+        request::set_buffer_scale(&self.conn, self.id, WlInt(scale))
+    }
+
+    /// Declares the transform already applied to the contents of the
+    /// attached buffers, which the compositor undoes when showing them.
+    ///
+    /// With a transform rotating by 90 or 270 degrees, the width of the
+    /// buffers becomes the height of the surface and the other way around;
+    /// see [`Transform::buffer_size`]. Like all double-buffered surface
+    /// state, it applies on the next commit.
+    ///
+    /// # Errors
+    /// Returns an error if the surface version predates
+    /// `set_buffer_transform`.
+    pub fn set_buffer_transform(&self, transform: Transform) -> Result<()> {
+        if self.version() < WL_SURFACE_SET_BUFFER_TRANSFORM_SINCE {
+            return Err(anyhow!(
+                "wl_surface.set_buffer_transform requires version {}, surface {} has version {}",
+                WL_SURFACE_SET_BUFFER_TRANSFORM_SINCE,
+                self.id,
+                self.version()
+            ));
+        }
+
+        request::set_buffer_transform(&self.conn, self.id, WlInt(transform as i32))
+    }
+
+    /// Moves the content of the next commit by `x`, `y` surface-local
+    /// units relative to the current content, without attaching a buffer.
+    ///
+    /// What the offset means depends on the role of the surface: it moves
+    /// a cursor or drag icon relative to its hotspot, and is ignored for
+    /// toplevels. Like all double-buffered surface state, it applies on the
+    /// next commit.
+    ///
+    /// # Errors
+    /// Returns an error if the surface version predates `offset`. Older
+    /// surfaces take the offset as arguments of `wl_surface.attach`.
+    pub fn offset(&self, x: i32, y: i32) -> Result<()> {
+        if self.version() < WL_SURFACE_OFFSET_SINCE {
+            return Err(anyhow!(
+                "wl_surface.offset requires version {}, surface {} has version {}",
+                WL_SURFACE_OFFSET_SINCE,
+                self.id,
+                self.version()
+            ));
+        }
+
+        request::offset(&self.conn, self.id, WlInt(x), WlInt(y))
+    }
+
+    /// Atomically applies the pending state of the surface.
+    ///
+    /// A configure taken with
+    /// [`ShellRole::take_configure`](crate::protocol::shell_role::ShellRole::take_configure),
+    /// such as the one of
+    /// [`XdgToplevel::take_configure`](crate::protocol::xdg_toplevel::XdgToplevel::take_configure),
+    /// is acknowledged right before the commit.
+    ///
+    /// # Errors
+    /// Returns an error, without sending the request, if the commit would
+    /// violate the protocol of the surface role, such as committing a buffer
+    /// to an `xdg_surface` before acknowledging its first configure.
+    pub fn commit(&self) -> Result<()> {
+        let pending_buffer = self.state.pending_buffer.get();
+
+        let role_ack = self.state.role_ack.take();
+        if let Some(ack) = role_ack {
+            ack(&self.conn)?;
+        }
+        if let Some(xdg_surface) = self.state.xdg_surface.borrow().as_ref() {
+            xdg_surface.flush_ack(&self.conn)?;
+            xdg_surface.check_commit(self.id, pending_buffer)?;
+        }
+
+        request::commit(&self.conn, self.id)?;
+
+        if let Some(buffer_id) = self.state.attached_buffer.take() {
+            self.conn.mark_buffer_busy(buffer_id);
+        }
+
+        if let Some(has_buffer) = self.state.pending_buffer.take()
+            && self.state.has_buffer.replace(has_buffer) != has_buffer
+        {
+            self.state.mapped_changed(has_buffer)?;
+        }
+
+        Ok(())
+    }
+
+    /// Destroys the surface.
+    ///
+    /// Dropping the handle does the same.
+    pub fn destroy(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl Drop for Surface {
+    fn drop(&mut self) {
+        self.conn
+            .destroy_on_drop(|conn| request::destroy(conn, self.id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        os::unix::net::UnixStream,
+    };
+
+    use super::*;
+    use crate::protocol::{
+        endian::Endian,
+        message::{WL_MESSAGE_HEADER_LEN, WlMessage, WlMessageHeader},
+    };
+
+    #[test]
+    fn transform_and_offset_check_the_surface_version() {
+        let (client, mut compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+
+        let old = Surface::new(conn.new_object(WlObjectId::Surface, 4).unwrap(), &conn);
+        assert!(old.offset(1, 2).is_err());
+        let old_id = old.id();
+        drop(old);
+
+        let id = conn.new_object(WlObjectId::Surface, 6).unwrap();
+        let surface = Surface::new(id, &conn);
+        surface.set_buffer_transform(Transform::Rotate90).unwrap();
+        surface.offset(-3, 5).unwrap();
+        drop(surface);
+        drop(conn);
+
+        let mut received = Vec::new();
+        compositor.read_to_end(&mut received).unwrap();
+
+        // Skip `wl_display.get_registry`, queued when connecting
+        let registry = WlMessageHeader::decode(&received, Endian::NATIVE).unwrap();
+        let mut received = &received[registry.size as usize..];
+
+        let mut requests = Vec::new();
+        while !received.is_empty() {
+            let header = WlMessageHeader::decode(received, Endian::NATIVE).unwrap();
+            let args: Vec<i32> = received[WL_MESSAGE_HEADER_LEN..header.size as usize]
+                .chunks_exact(4)
+                .map(|word| i32::from_ne_bytes(word.try_into().unwrap()))
+                .collect();
+            requests.push((header.object_id, header.opcode, args));
+            received = &received[header.size as usize..];
+        }
+        assert_eq!(
+            requests,
+            [
+                (old_id, 0, vec![]),
+                (id, 7, vec![1]),
+                (id, 10, vec![-3, 5]),
+                (id, 0, vec![]),
+            ]
+        );
+
+        assert_eq!(Transform::Rotate90.buffer_size(640, 480), (480, 640));
+        assert_eq!(Transform::Flipped180.buffer_size(640, 480), (640, 480));
+    }
+
+    #[test]
+    fn only_committed_buffers_are_busy() {
+        let (client, mut compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+        let surface = Surface::new(conn.new_object(WlObjectId::Surface, 6).unwrap(), &conn);
+        let replaced = Buffer::new(conn.new_object(WlObjectId::Buffer, 1).unwrap(), &conn, 0, 0);
+        let committed = Buffer::new(conn.new_object(WlObjectId::Buffer, 1).unwrap(), &conn, 0, 0);
+        let (replaced_id, committed_id) = (replaced.id(), committed.id());
+
+        surface.attach(Some(&replaced)).unwrap();
+        surface.attach(Some(&committed)).unwrap();
+        assert!(!committed.is_busy());
+        surface.commit().unwrap();
+        assert!(!replaced.is_busy());
+        assert!(committed.is_busy());
+
+        // The replaced buffer never reached the compositor, so it is
+        // destroyed right away; the committed one waits for its release,
+        // which comes after another commit
+        drop(replaced);
+        drop(committed);
+        surface.commit().unwrap();
+        let release: Vec<u8> = WlMessage::new(committed_id, 0, &[]).unwrap().into();
+        compositor.write_all(&release).unwrap();
+        conn.dispatch().unwrap();
+        let surface_id = surface.id();
+        drop(surface);
+        drop(conn);
+
+        let mut received = Vec::new();
+        compositor.read_to_end(&mut received).unwrap();
+        let mut received = &received[..];
+        let mut requests = Vec::new();
+        while !received.is_empty() {
+            let header = WlMessageHeader::decode(received, Endian::NATIVE).unwrap();
+            requests.push((header.object_id, header.opcode));
+            received = &received[header.size as usize..];
+        }
+
+        let position = |request| requests.iter().rposition(|&sent| sent == request).unwrap();
+        let second_commit = position((surface_id, 6));
+        assert!(position((replaced_id, 0)) < second_commit);
+        assert!(position((committed_id, 0)) > second_commit);
+    }
+}
@@ -0,0 +1,512 @@
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::types::{WlInt, WlNewId, WlObject},
+    wl_request_opcode, wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to a Wayland surface object.
+    Opcode {
+        /// Deletes the surface and invalidates its object ID.
+        Destroy = 0 (destructor),
+
+        /// Sets a buffer as the content of the surface.
+        Attach = 1,
+
+        /// Marks part of the surface as damaged, in surface-local coordinates.
+        Damage = 2,
+
+        /// Requests a notification when it is a good time to start drawing a new frame.
+        Frame = 3,
+
+        /// Sets the opaque region of the surface.
+        SetOpaqueRegion = 4,
+
+        /// Sets the input region of the surface.
+        SetInputRegion = 5,
+
+        /// Atomically applies the pending surface state.
+        Commit = 6,
+
+        /// Sets the transform applied to the buffer contents.
+        SetBufferTransform = 7,
+
+        /// Sets the scale of the attached buffers.
+        SetBufferScale = 8,
+
+        /// Marks part of the surface as damaged, in buffer coordinates.
+        DamageBuffer = 9,
+
+        /// Sets the offset of the surface relative to its previous position.
+        Offset = 10,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wl_surface.attach` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="attach">
+    ///   <description summary="set the surface contents">
+    ///     Set a buffer as the content of this surface.
+    ///
+    ///     The new size of the surface is calculated based on the buffer
+    ///     size transformed by the inverse buffer_transform and the
+    ///     inverse buffer_scale.
+    ///
+    ///     Surface contents are double-buffered state, see wl_surface.commit.
+    ///   </description>
+    ///   <arg name="buffer" type="object" interface="wl_buffer" allow-null="true"
+    ///        summary="buffer of surface contents"/>
+    ///   <arg name="x" type="int" summary="surface-local x coordinate"/>
+    ///   <arg name="y" type="int" summary="surface-local y coordinate"/>
+    /// </request>
+    /// ```
+    AttachParam {
+        /// The buffer to attach, or object 0 to remove the surface content.
+        buffer: WlObject,
+        /// The horizontal offset, which must be 0 since version 5.
+        x: WlInt,
+        /// The vertical offset, which must be 0 since version 5.
+        y: WlInt,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wl_surface.damage` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="damage">
+    ///   <description summary="mark part of the surface damaged">
+    ///     This request is used to describe the regions where the pending
+    ///     buffer is different from the current surface contents, and where
+    ///     the surface therefore needs to be repainted.
+    ///
+    ///     Damage is double-buffered state, see wl_surface.commit.
+    ///
+    ///     The damage rectangle is specified in surface-local coordinates,
+    ///     where x and y specify the upper left corner of the damage rectangle.
+    ///
+    ///     Note! New clients should not use this request. Instead damage can be
+    ///     posted with wl_surface.damage_buffer which uses buffer coordinates
+    ///     instead of surface coordinates.
+    ///   </description>
+    ///   <arg name="x" type="int" summary="surface-local x coordinate"/>
+    ///   <arg name="y" type="int" summary="surface-local y coordinate"/>
+    ///   <arg name="width" type="int" summary="width of damage rectangle"/>
+    ///   <arg name="height" type="int" summary="height of damage rectangle"/>
+    /// </request>
+    /// ```
+    DamageParam {
+        /// The left edge of the damaged rectangle.
+        x: WlInt,
+        /// The top edge of the damaged rectangle.
+        y: WlInt,
+        /// The width of the damaged rectangle.
+        width: WlInt,
+        /// The height of the damaged rectangle.
+        height: WlInt,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wl_surface.frame` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="frame">
+    ///   <description summary="request a frame throttling hint">
+    ///     Request a notification when it is a good time to start drawing a new
+    ///     frame, by creating a frame callback. This is useful for throttling
+    ///     redrawing operations, and driving animations.
+    ///
+    ///     The frame request will take effect on the next wl_surface.commit.
+    ///   </description>
+    ///   <arg name="callback" type="new_id" interface="wl_callback"
+    ///        summary="callback object for the frame request"/>
+    /// </request>
+    /// ```
+    FrameParam {
+        /// The object ID to assign to the newly created wl_callback object.
+        callback: WlNewId,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wl_surface.set_opaque_region` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_opaque_region">
+    ///   <description summary="set opaque region">
+    ///     This request sets the region of the surface that contains
+    ///     opaque content.
+    ///
+    ///     The opaque region is an optimization hint for the compositor
+    ///     that lets it optimize the redrawing of content behind opaque
+    ///     regions. Setting an opaque region is not required for correct
+    ///     behaviour, but marking transparent content as opaque will result
+    ///     in repaint artifacts.
+    ///
+    ///     Opaque region is double-buffered state, see wl_surface.commit.
+    ///
+    ///     The initial value for an opaque region is empty. Setting the pending
+    ///     opaque region has copy semantics, and the wl_region object can be
+    ///     destroyed immediately. A NULL wl_region causes the pending opaque
+    ///     region to be set to empty.
+    ///   </description>
+    ///   <arg name="region" type="object" interface="wl_region" allow-null="true"
+    ///        summary="opaque region of the surface"/>
+    /// </request>
+    /// ```
+    SetOpaqueRegionParam {
+        /// The opaque region, or object 0 for an empty one.
+        region: WlObject,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wl_surface.set_input_region` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_input_region">
+    ///   <description summary="set input region">
+    ///     This request sets the region of the surface that can receive
+    ///     pointer and touch events.
+    ///
+    ///     Input events happening outside of this region will try the next
+    ///     surface in the server surface stack. The compositor ignores the
+    ///     parts of the input region that fall outside of the surface.
+    ///
+    ///     Input region is double-buffered state, see wl_surface.commit.
+    ///
+    ///     The initial value for an input region is infinite. That means the
+    ///     whole surface will accept input. Setting the pending input region
+    ///     has copy semantics, and the wl_region object can be destroyed
+    ///     immediately. A NULL wl_region causes the input region to be set
+    ///     to infinite.
+    ///   </description>
+    ///   <arg name="region" type="object" interface="wl_region" allow-null="true"
+    ///        summary="input region of the surface"/>
+    /// </request>
+    /// ```
+    SetInputRegionParam {
+        /// The input region, or object 0 for an infinite one.
+        region: WlObject,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wl_surface.set_buffer_transform` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_buffer_transform" since="2">
+    ///   <description summary="sets the buffer transformation">
+    ///     This request sets the transformation that the client has already
+    ///     applied to the content of the buffer. The accepted values for
+    ///     the transform parameter are the values for wl_output.transform.
+    ///
+    ///     The compositor applies the inverse of this transformation whenever
+    ///     it uses the buffer contents.
+    ///
+    ///     Buffer transform is double-buffered state, see wl_surface.commit.
+    ///
+    ///     A newly created surface has its buffer transformation set to normal.
+    ///
+    ///     The purpose of this request is to allow clients to render content
+    ///     according to the output transform, thus permitting the compositor to
+    ///     use certain optimizations even if the display is rotated. Using
+    ///     hardware overlays and scanning out a client buffer for fullscreen
+    ///     surfaces are examples of such optimizations. Those optimizations are
+    ///     highly dependent on the compositor implementation, so the use of this
+    ///     request should be considered on a case-by-case basis.
+    ///
+    ///     Note that if the transform value includes 90 or 270 degree rotation,
+    ///     the width of the buffer will become the surface height and the height
+    ///     of the buffer will become the surface width.
+    ///
+    ///     If transform is not one of the values from the
+    ///     wl_output.transform enum the invalid_transform protocol error
+    ///     is raised.
+    ///   </description>
+    ///   <arg name="transform" type="int" enum="wl_output.transform"
+    ///        summary="transform for interpreting buffer contents"/>
+    /// </request>
+    /// ```
+    SetBufferTransformParam {
+        /// The transform already applied to the buffer contents, as a
+        /// `Transform` value.
+        transform: WlInt,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wl_surface.set_buffer_scale` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_buffer_scale" since="3">
+    ///   <description summary="sets the buffer scaling factor">
+    ///     This request sets an optional scaling factor on how the compositor
+    ///     interprets the contents of the buffer attached to the window.
+    ///
+    ///     Buffer scale is double-buffered state, see wl_surface.commit.
+    ///
+    ///     A newly created surface has its buffer scale set to 1.
+    ///
+    ///     wl_surface.set_buffer_scale changes the pending buffer scale.
+    ///     wl_surface.commit copies the pending buffer scale to the current one.
+    ///     Otherwise, the pending and current values are never changed.
+    ///
+    ///     The purpose of this request is to allow clients to supply higher
+    ///     resolution buffer data for use on high resolution outputs. It is
+    ///     intended that you pick the same buffer scale as the scale of the
+    ///     output that the surface is displayed on. This means the compositor
+    ///     can avoid scaling when rendering the surface on that output.
+    ///
+    ///     Note that if the scale is larger than 1, then you have to attach
+    ///     a buffer that is larger (by a factor of scale in each dimension)
+    ///     than the desired surface size.
+    ///
+    ///     If scale is not greater than 0 the invalid_scale protocol error is
+    ///     raised.
+    ///   </description>
+    ///   <arg name="scale" type="int" summary="scale for interpreting buffer contents"/>
+    /// </request>
+    /// ```
+    SetBufferScaleParam {
+        /// The scale the attached buffers are rendered at.
+        scale: WlInt,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wl_surface.damage_buffer` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="damage_buffer" since="4">
+    ///   <description summary="mark part of the surface damaged using buffer coordinates">
+    ///     This request is used to describe the regions where the pending
+    ///     buffer is different from the current surface contents, and where
+    ///     the surface therefore needs to be repainted. The compositor
+    ///     ignores the parts of the damage that fall outside of the surface.
+    ///   </description>
+    ///   <arg name="x" type="int" summary="buffer-local x coordinate"/>
+    ///   <arg name="y" type="int" summary="buffer-local y coordinate"/>
+    ///   <arg name="width" type="int" summary="width of damage rectangle"/>
+    ///   <arg name="height" type="int" summary="height of damage rectangle"/>
+    /// </request>
+    /// ```
+    DamageBufferParam {
+        /// The left edge of the damaged rectangle.
+        x: WlInt,
+        /// The top edge of the damaged rectangle.
+        y: WlInt,
+        /// The width of the damaged rectangle.
+        width: WlInt,
+        /// The height of the damaged rectangle.
+        height: WlInt,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wl_surface.offset` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="offset" since="5">
+    ///   <description summary="set the surface contents offset">
+    ///     The x and y arguments specify the location of the new pending
+    ///     buffer's upper left corner, relative to the current buffer's upper
+    ///     left corner, in surface-local coordinates. In other words, the
+    ///     x and y, combined with the new surface size define in which
+    ///     directions the surface's size changes.
+    ///
+    ///     The exact semantics of wl_surface.offset are role-specific. Refer to
+    ///     the documentation of specific roles for more information.
+    ///
+    ///     Surface location offset is double-buffered state, see
+    ///     wl_surface.commit.
+    ///
+    ///     This request is semantically equivalent to and the replaces the x and y
+    ///     arguments in the wl_surface.attach request in wl_surface versions prior
+    ///     to 5. See wl_surface.attach for details.
+    ///   </description>
+    ///   <arg name="x" type="int" summary="surface-local x coordinate"/>
+    ///   <arg name="y" type="int" summary="surface-local y coordinate"/>
+    /// </request>
+    /// ```
+    OffsetParam {
+        /// The horizontal offset of the new content relative to the old one.
+        x: WlInt,
+        /// The vertical offset of the new content relative to the old one.
+        y: WlInt,
+    }
+}
+
+/// Sends a `wl_surface.destroy` request to the compositor.
+pub fn destroy(conn: &Connection, surface: u32) -> Result<()> {
+    conn.send_request(surface, Opcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends a `wl_surface.attach` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `surface` - The ID of the surface
+/// * `buffer` - The buffer to attach, `WlObject(0)` for none
+/// * `x`, `y` - The offset of the new content relative to the old one
+pub fn attach(conn: &Connection, surface: u32, buffer: WlObject, x: WlInt, y: WlInt) -> Result<()> {
+    conn.send_request(surface, Opcode::Attach, &AttachParam::new(buffer, x, y))?;
+
+    Ok(())
+}
+
+/// Sends a `wl_surface.damage` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `surface` - The ID of the damaged surface
+/// * `x`, `y`, `width`, `height` - The damaged rectangle in surface-local coordinates
+pub fn damage(
+    conn: &Connection,
+    surface: u32,
+    x: WlInt,
+    y: WlInt,
+    width: WlInt,
+    height: WlInt,
+) -> Result<()> {
+    conn.send_request(
+        surface,
+        Opcode::Damage,
+        &DamageParam::new(x, y, width, height),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `wl_surface.frame` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `surface` - The ID of the surface to throttle
+/// * `callback` - The object ID to assign to the new wl_callback
+pub fn frame(conn: &Connection, surface: u32, callback: WlNewId) -> Result<()> {
+    conn.send_request(surface, Opcode::Frame, &FrameParam::new(callback))?;
+
+    Ok(())
+}
+
+/// Sends a `wl_surface.set_opaque_region` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `surface` - The ID of the surface
+/// * `region` - The opaque region, `WlObject(0)` for an empty one
+pub fn set_opaque_region(conn: &Connection, surface: u32, region: WlObject) -> Result<()> {
+    conn.send_request(
+        surface,
+        Opcode::SetOpaqueRegion,
+        &SetOpaqueRegionParam::new(region),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `wl_surface.set_input_region` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `surface` - The ID of the surface
+/// * `region` - The input region, `WlObject(0)` for an infinite one
+pub fn set_input_region(conn: &Connection, surface: u32, region: WlObject) -> Result<()> {
+    conn.send_request(
+        surface,
+        Opcode::SetInputRegion,
+        &SetInputRegionParam::new(region),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `wl_surface.set_buffer_transform` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `surface` - The ID of the surface
+/// * `transform` - The transform already applied to the buffer contents
+pub fn set_buffer_transform(conn: &Connection, surface: u32, transform: WlInt) -> Result<()> {
+    conn.send_request(
+        surface,
+        Opcode::SetBufferTransform,
+        &SetBufferTransformParam::new(transform),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `wl_surface.set_buffer_scale` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `surface` - The ID of the surface
+/// * `scale` - The scale the attached buffers are rendered at
+pub fn set_buffer_scale(conn: &Connection, surface: u32, scale: WlInt) -> Result<()> {
+    conn.send_request(
+        surface,
+        Opcode::SetBufferScale,
+        &SetBufferScaleParam::new(scale),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `wl_surface.damage_buffer` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `surface` - The ID of the damaged surface
+/// * `x`, `y`, `width`, `height` - The damaged rectangle in buffer coordinates
+pub fn damage_buffer(
+    conn: &Connection,
+    surface: u32,
+    x: WlInt,
+    y: WlInt,
+    width: WlInt,
+    height: WlInt,
+) -> Result<()> {
+    conn.send_request(
+        surface,
+        Opcode::DamageBuffer,
+        &DamageBufferParam::new(x, y, width, height),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `wl_surface.commit` request to the compositor.
+pub fn commit(conn: &Connection, surface: u32) -> Result<()> {
+    conn.send_request(surface, Opcode::Commit, &())?;
+
+    Ok(())
+}
+
+/// Sends a `wl_surface.offset` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `surface` - The ID of the surface
+/// * `x`, `y` - The offset of the new content relative to the old one
+pub fn offset(conn: &Connection, surface: u32, x: WlInt, y: WlInt) -> Result<()> {
+    conn.send_request(surface, Opcode::Offset, &OffsetParam::new(x, y))?;
+
+    Ok(())
+}
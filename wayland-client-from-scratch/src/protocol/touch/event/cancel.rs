@@ -0,0 +1,37 @@
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::touch::{TouchState, emit_frame},
+};
+
+/// Handles a `wl_touch.cancel` event.
+///
+/// All touch points are moved to the lifted points and the pending frame is delivered right
+/// away, marked as cancelled, since no frame event follows.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="cancel">
+///   <description summary="touch session cancelled">
+///     Sent if the compositor decides the touch stream is a global
+///     gesture. No further events are sent to the clients from that
+///     particular gesture. Touch cancellation applies to all touch points
+///     currently active on this client's surface. The client is
+///     responsible for finalizing the touch points, future touch points on
+///     this surface may reuse the touch point ID.
+///
+///     No frame event is required after the cancel event.
+///   </description>
+/// </event>
+/// ```
+pub(super) fn handle_wl_touch_cancel(conn: &Connection, touch_id: u32) -> Result<()> {
+    if let Some(state) = conn.object_data::<TouchState>(touch_id) {
+        let mut pending = state.pending.borrow_mut();
+        pending.lifted.append(&mut state.points.borrow_mut());
+        pending.cancelled = true;
+        drop(pending);
+        emit_frame(conn, touch_id, &state);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        touch::{TouchPoint, update_frame},
+        types::{
+            WL_TYPE_FIXED_LEN, WL_TYPE_INT_LEN, WL_TYPE_OBJECT_LEN, WL_TYPE_UINT_LEN, WlFixed,
+            WlInt, WlObject, WlUInt,
+        },
+    },
+};
+
+/// Represents a `wl_touch.down` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="down">
+///   <description summary="touch down event and beginning of a touch sequence">
+///     A new touch point has appeared on the surface. This touch point is
+///     assigned a unique ID. Future events from this touch point reference
+///     this ID. The ID ceases to be valid after a touch up event and may be
+///     reused in the future.
+///   </description>
+///   <arg name="serial" type="uint" summary="serial number of the touch down event"/>
+///   <arg name="time" type="uint" summary="timestamp with millisecond granularity"/>
+///   <arg name="surface" type="object" interface="wl_surface" summary="surface touched"/>
+///   <arg name="id" type="int" summary="the unique ID of this touch point"/>
+///   <arg name="x" type="fixed" summary="surface-local x coordinate"/>
+///   <arg name="y" type="fixed" summary="surface-local y coordinate"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Down {
+    /// The serial number of the touch down event.
+    pub serial: WlUInt,
+    /// The timestamp with millisecond granularity.
+    pub time: WlUInt,
+    /// The surface touched.
+    pub surface: WlObject,
+    /// The unique ID of the touch point.
+    pub id: WlInt,
+    /// The surface-local x coordinate.
+    pub x: WlFixed,
+    /// The surface-local y coordinate.
+    pub y: WlFixed,
+}
+
+impl TryFrom<&[u8]> for Down {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Down` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `serial` (u32) - The serial number of the event
+    /// - Bytes 4-7: `time` (u32) - The timestamp
+    /// - Bytes 8-11: `surface` (u32) - The surface touched
+    /// - Bytes 12-15: `id` (i32) - The touch point ID
+    /// - Bytes 16-19: `x` (fixed) - The x coordinate
+    /// - Bytes 20-23: `y` (fixed) - The y coordinate
+    fn try_from(buf: &[u8]) -> Result<Down> {
+        let id_pos = 2 * WL_TYPE_UINT_LEN + WL_TYPE_OBJECT_LEN;
+        let x_pos = id_pos + WL_TYPE_INT_LEN;
+
+        Ok(Down {
+            serial: WlUInt::read(buf, 0)?,
+            time: WlUInt::read(buf, WL_TYPE_UINT_LEN)?,
+            surface: WlObject::read(buf, 2 * WL_TYPE_UINT_LEN)?,
+            id: WlInt::read(buf, id_pos)?,
+            x: WlFixed::read(buf, x_pos)?,
+            y: WlFixed::read(buf, x_pos + WL_TYPE_FIXED_LEN)?,
+        })
+    }
+}
+
+/// Handles a `wl_touch.down` event by adding the touch point and recording
+/// the event in the pending frame.
+pub(super) fn handle_wl_touch_down(conn: &Connection, touch_id: u32, down: Down) -> Result<()> {
+    update_frame(conn, touch_id, |state, points, frame| {
+        state.seat.set_grab_serial(down.serial.get());
+
+        let point = TouchPoint {
+            id: down.id.get(),
+            surface: down.surface.get(),
+            position: (down.x.to_f64(), down.y.to_f64()),
+        };
+        points.retain(|active| active.id != point.id);
+        points.push(point);
+
+        frame.down.push(down);
+    });
+
+    Ok(())
+}
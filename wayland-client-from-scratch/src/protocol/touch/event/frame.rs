@@ -0,0 +1,33 @@
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::touch::{TouchState, emit_frame},
+};
+
+/// Handles a `wl_touch.frame` event.
+///
+/// The frame ends a group of events that belong together, so the pending
+/// frame accumulated from them is delivered now.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="frame">
+///   <description summary="end of touch frame event">
+///     Indicates the end of a set of events that logically belong together.
+///     A client is expected to accumulate the data in all events within the
+///     frame before proceeding.
+///
+///     A wl_touch.frame terminates at least one event but otherwise no
+///     guarantee is provided about the set of events within a frame. A client
+///     must assume that any state not updated in a frame is unchanged from the
+///     previously known state.
+///   </description>
+/// </event>
+/// ```
+pub(super) fn handle_wl_touch_frame(conn: &Connection, touch_id: u32) -> Result<()> {
+    if let Some(state) = conn.object_data::<TouchState>(touch_id) {
+        emit_frame(conn, touch_id, &state);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,167 @@
+pub mod cancel;
+pub mod down;
+pub mod frame;
+pub mod motion;
+pub mod orientation;
+pub mod shape;
+pub mod up;
+
+use crate::{
+    connection::Connection,
+    error::{Error, Result, anyhow},
+    protocol::message::WlMessage,
+};
+
+/// Represents the event types that can be emitted by a Wayland touch object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    /// A new touch point appeared on a surface.
+    ///
+    /// # Event Arguments
+    /// - `serial`: The serial number of the event
+    /// - `time`: The timestamp in milliseconds
+    /// - `surface`: The surface touched
+    /// - `id`: The unique ID of the touch point
+    /// - `x`, `y`: The surface-local position
+    Down = 0,
+
+    /// A touch point disappeared.
+    ///
+    /// # Event Arguments
+    /// - `serial`: The serial number of the event
+    /// - `time`: The timestamp in milliseconds
+    /// - `id`: The unique ID of the touch point
+    Up = 1,
+
+    /// A touch point moved.
+    ///
+    /// # Event Arguments
+    /// - `time`: The timestamp in milliseconds
+    /// - `id`: The unique ID of the touch point
+    /// - `x`, `y`: The surface-local position
+    Motion = 2,
+
+    /// Ends a group of events that belong together.
+    Frame = 3,
+
+    /// The compositor took over the touch sequence.
+    Cancel = 4,
+
+    /// The contact shape of a touch point changed (since version 6).
+    ///
+    /// # Event Arguments
+    /// - `id`: The unique ID of the touch point
+    /// - `major`, `minor`: The axis lengths of the contact ellipse
+    Shape = 5,
+
+    /// The contact orientation of a touch point changed (since version 6).
+    ///
+    /// # Event Arguments
+    /// - `id`: The unique ID of the touch point
+    /// - `orientation`: The angle of the major axis in degrees
+    Orientation = 6,
+}
+
+impl TryFrom<u16> for Opcode {
+    type Error = Error;
+
+    /// Attempts to convert a raw opcode value into a structured `WlTouchEvent`.
+    fn try_from(value: u16) -> Result<Opcode> {
+        match value {
+            0 => Ok(Opcode::Down),
+            1 => Ok(Opcode::Up),
+            2 => Ok(Opcode::Motion),
+            3 => Ok(Opcode::Frame),
+            4 => Ok(Opcode::Cancel),
+            5 => Ok(Opcode::Shape),
+            6 => Ok(Opcode::Orientation),
+            _ => Err(anyhow!("Invalid wl_touch event opcode: {}", value)),
+        }
+    }
+}
+
+impl Opcode {
+    /// Returns the interface version the event was introduced in.
+    pub fn since(self) -> u32 {
+        match self {
+            Opcode::Down | Opcode::Up | Opcode::Motion | Opcode::Frame | Opcode::Cancel => 1,
+            Opcode::Shape | Opcode::Orientation => 6,
+        }
+    }
+}
+
+/// A decoded `wl_touch` event, with its arguments.
+#[derive(Debug)]
+pub enum Event {
+    /// A new touch point appeared on a surface.
+    Down(down::Down),
+    /// A touch point disappeared.
+    Up(up::Up),
+    /// A touch point moved.
+    Motion(motion::Motion),
+    /// Ends a group of events that belong together.
+    Frame,
+    /// The compositor took over the touch sequence.
+    Cancel,
+    /// The contact shape of a touch point changed (since version 6).
+    Shape(shape::Shape),
+    /// The contact orientation of a touch point changed (since version 6).
+    Orientation(orientation::Orientation),
+}
+
+impl Event {
+    /// Decodes a `wl_touch` event from its message.
+    ///
+    /// # Errors
+    /// Returns an error if the opcode is unknown or the arguments are malformed.
+    pub fn decode(_conn: &Connection, msg: &WlMessage) -> Result<Event> {
+        let buf: &[u8] = &msg.data;
+
+        Ok(match Opcode::try_from(msg.header.opcode)? {
+            Opcode::Down => Event::Down(down::Down::try_from(buf)?),
+            Opcode::Up => Event::Up(up::Up::try_from(buf)?),
+            Opcode::Motion => Event::Motion(motion::Motion::try_from(buf)?),
+            Opcode::Frame => Event::Frame,
+            Opcode::Cancel => Event::Cancel,
+            Opcode::Shape => Event::Shape(shape::Shape::try_from(buf)?),
+            Opcode::Orientation => Event::Orientation(orientation::Orientation::try_from(buf)?),
+        })
+    }
+
+    /// Returns the opcode of the event.
+    pub fn opcode(&self) -> Opcode {
+        match self {
+            Event::Down(_) => Opcode::Down,
+            Event::Up(_) => Opcode::Up,
+            Event::Motion(_) => Opcode::Motion,
+            Event::Frame => Opcode::Frame,
+            Event::Cancel => Opcode::Cancel,
+            Event::Shape(_) => Opcode::Shape,
+            Event::Orientation(_) => Opcode::Orientation,
+        }
+    }
+}
+
+/// Dispatches incoming Wayland touch events to their appropriate handler functions.
+///
+/// # Event Routing
+/// * `Down`, `Up`, `Motion`, `Shape` and `Orientation` events are
+///   accumulated into the pending `TouchFrame`
+/// * `Frame` events deliver the pending frame to the listener
+/// * `Cancel` events drop all touch points and deliver the pending frame
+pub fn handle_wl_touch_event(conn: &Connection, msg: WlMessage) -> Result<()> {
+    let touch_id = msg.header.object_id;
+
+    // Route the event to the appropriate handler based on type
+    match Event::decode(conn, &msg)? {
+        Event::Down(event) => down::handle_wl_touch_down(conn, touch_id, event),
+        Event::Up(event) => up::handle_wl_touch_up(conn, touch_id, event),
+        Event::Motion(event) => motion::handle_wl_touch_motion(conn, touch_id, event),
+        Event::Frame => frame::handle_wl_touch_frame(conn, touch_id),
+        Event::Cancel => cancel::handle_wl_touch_cancel(conn, touch_id),
+        Event::Shape(event) => shape::handle_wl_touch_shape(conn, touch_id, event),
+        Event::Orientation(event) => {
+            orientation::handle_wl_touch_orientation(conn, touch_id, event)
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        touch::update_frame,
+        types::{WL_TYPE_FIXED_LEN, WL_TYPE_INT_LEN, WL_TYPE_UINT_LEN, WlFixed, WlInt, WlUInt},
+    },
+};
+
+/// Represents a `wl_touch.motion` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="motion">
+///   <description summary="update of touch point coordinates">
+///     A touch point has changed coordinates.
+///   </description>
+///   <arg name="time" type="uint" summary="timestamp with millisecond granularity"/>
+///   <arg name="id" type="int" summary="the unique ID of this touch point"/>
+///   <arg name="x" type="fixed" summary="surface-local x coordinate"/>
+///   <arg name="y" type="fixed" summary="surface-local y coordinate"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Motion {
+    /// The timestamp with millisecond granularity.
+    pub time: WlUInt,
+    /// The ID of the touch point that moved.
+    pub id: WlInt,
+    /// The surface-local x coordinate.
+    pub x: WlFixed,
+    /// The surface-local y coordinate.
+    pub y: WlFixed,
+}
+
+impl TryFrom<&[u8]> for Motion {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Motion` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `time` (u32) - The timestamp
+    /// - Bytes 4-7: `id` (i32) - The touch point ID
+    /// - Bytes 8-11: `x` (fixed) - The x coordinate
+    /// - Bytes 12-15: `y` (fixed) - The y coordinate
+    fn try_from(buf: &[u8]) -> Result<Motion> {
+        let x_pos = WL_TYPE_UINT_LEN + WL_TYPE_INT_LEN;
+
+        Ok(Motion {
+            time: WlUInt::read(buf, 0)?,
+            id: WlInt::read(buf, WL_TYPE_UINT_LEN)?,
+            x: WlFixed::read(buf, x_pos)?,
+            y: WlFixed::read(buf, x_pos + WL_TYPE_FIXED_LEN)?,
+        })
+    }
+}
+
+/// Handles a `wl_touch.motion` event by moving the touch point and recording
+/// the event in the pending frame.
+pub(super) fn handle_wl_touch_motion(
+    conn: &Connection,
+    touch_id: u32,
+    motion: Motion,
+) -> Result<()> {
+    update_frame(conn, touch_id, |_, points, frame| {
+        if let Some(point) = points.iter_mut().find(|point| point.id == motion.id.get()) {
+            point.position = (motion.x.to_f64(), motion.y.to_f64());
+        }
+        frame.motion.push(motion);
+    });
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        touch::update_frame,
+        types::{WL_TYPE_INT_LEN, WlFixed, WlInt},
+    },
+};
+
+/// Represents a `wl_touch.orientation` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="orientation" since="6">
+///   <description summary="update orientation of touch point">
+///     Sent when a touchpoint has changed its orientation.
+///
+///     This event does not occur on its own. It is sent before a
+///     wl_touch.frame event and carries the new shape information for
+///     any previously reported, or new touch points of that frame.
+///
+///     Orientation describes the clockwise angle of a touchpoint's major axis
+///     to the positive surface y-axis and is normalized to the -180 to +180
+///     degree range. The granularity of orientation depends on the touch
+///     device, some devices only support binary rotation values between 0 and
+///     90 degrees.
+///   </description>
+///   <arg name="id" type="int" summary="the unique ID of this touch point"/>
+///   <arg name="orientation" type="fixed" summary="angle between major axis and positive surface y-axis in degrees"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Orientation {
+    /// The ID of the touch point.
+    pub id: WlInt,
+    /// The clockwise angle in degrees between the major axis of the contact
+    /// and the positive surface y-axis.
+    pub orientation: WlFixed,
+}
+
+impl TryFrom<&[u8]> for Orientation {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Orientation` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `id` (i32) - The touch point ID
+    /// - Bytes 4-7: `orientation` (fixed) - The angle in degrees
+    fn try_from(buf: &[u8]) -> Result<Orientation> {
+        Ok(Orientation {
+            id: WlInt::read(buf, 0)?,
+            orientation: WlFixed::read(buf, WL_TYPE_INT_LEN)?,
+        })
+    }
+}
+
+/// Handles a `wl_touch.orientation` event by recording it in the pending frame.
+pub(super) fn handle_wl_touch_orientation(
+    conn: &Connection,
+    touch_id: u32,
+    orientation: Orientation,
+) -> Result<()> {
+    update_frame(conn, touch_id, |_, _, frame| {
+        frame.orientation.push(orientation)
+    });
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        touch::update_frame,
+        types::{WL_TYPE_FIXED_LEN, WL_TYPE_INT_LEN, WlFixed, WlInt},
+    },
+};
+
+/// Represents a `wl_touch.shape` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="shape" since="6">
+///   <description summary="update shape of touch point">
+///     Sent when a touchpoint has changed its shape.
+///
+///     This event does not occur on its own. It is sent before a
+///     wl_touch.frame event and carries the new shape information for
+///     any previously reported, or new touch points of that frame.
+///
+///     A touchpoint shape is approximated by an ellipse through the major and
+///     minor axis length. The major axis length describes the longer diameter
+///     of the ellipse, while the minor axis length describes the shorter
+///     diameter. Major and minor are orthogonal and both are specified in
+///     surface-local coordinates. The center of the ellipse is always at the
+///     touchpoint location as reported by wl_touch.down or wl_touch.move.
+///   </description>
+///   <arg name="id" type="int" summary="the unique ID of this touch point"/>
+///   <arg name="major" type="fixed" summary="length of the major axis in surface-local coordinates"/>
+///   <arg name="minor" type="fixed" summary="length of the minor axis in surface-local coordinates"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Shape {
+    /// The ID of the touch point.
+    pub id: WlInt,
+    /// The length of the major axis of the contact ellipse.
+    pub major: WlFixed,
+    /// The length of the minor axis of the contact ellipse.
+    pub minor: WlFixed,
+}
+
+impl TryFrom<&[u8]> for Shape {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Shape` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `id` (i32) - The touch point ID
+    /// - Bytes 4-7: `major` (fixed) - The major axis length
+    /// - Bytes 8-11: `minor` (fixed) - The minor axis length
+    fn try_from(buf: &[u8]) -> Result<Shape> {
+        Ok(Shape {
+            id: WlInt::read(buf, 0)?,
+            major: WlFixed::read(buf, WL_TYPE_INT_LEN)?,
+            minor: WlFixed::read(buf, WL_TYPE_INT_LEN + WL_TYPE_FIXED_LEN)?,
+        })
+    }
+}
+
+/// Handles a `wl_touch.shape` event by recording it in the pending frame.
+pub(super) fn handle_wl_touch_shape(conn: &Connection, touch_id: u32, shape: Shape) -> Result<()> {
+    update_frame(conn, touch_id, |_, _, frame| frame.shape.push(shape));
+
+    Ok(())
+}
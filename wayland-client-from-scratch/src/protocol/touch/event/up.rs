@@ -0,0 +1,65 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        touch::update_frame,
+        types::{WL_TYPE_UINT_LEN, WlInt, WlUInt},
+    },
+};
+
+/// Represents a `wl_touch.up` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="up">
+///   <description summary="end of a touch event sequence">
+///     The touch point has disappeared. No further events will be sent for
+///     this touch point and the touch point's ID is released and may be
+///     reused in a future touch down event.
+///   </description>
+///   <arg name="serial" type="uint" summary="serial number of the touch up event"/>
+///   <arg name="time" type="uint" summary="timestamp with millisecond granularity"/>
+///   <arg name="id" type="int" summary="the unique ID of this touch point"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Up {
+    /// The serial number of the touch up event.
+    pub serial: WlUInt,
+    /// The timestamp with millisecond granularity.
+    pub time: WlUInt,
+    /// The ID of the touch point that disappeared.
+    pub id: WlInt,
+}
+
+impl TryFrom<&[u8]> for Up {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Up` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `serial` (u32) - The serial number of the event
+    /// - Bytes 4-7: `time` (u32) - The timestamp
+    /// - Bytes 8-11: `id` (i32) - The touch point ID
+    fn try_from(buf: &[u8]) -> Result<Up> {
+        Ok(Up {
+            serial: WlUInt::read(buf, 0)?,
+            time: WlUInt::read(buf, WL_TYPE_UINT_LEN)?,
+            id: WlInt::read(buf, 2 * WL_TYPE_UINT_LEN)?,
+        })
+    }
+}
+
+/// Handles a `wl_touch.up` event by moving the touch point to the lifted
+/// points and recording the event in the pending frame.
+pub(super) fn handle_wl_touch_up(conn: &Connection, touch_id: u32, up: Up) -> Result<()> {
+    update_frame(conn, touch_id, |state, points, frame| {
+        state.seat.set_serial(up.serial.get());
+        if let Some(index) = points.iter().position(|active| active.id == up.id.get()) {
+            frame.lifted.push(points.remove(index));
+        }
+        frame.up.push(up);
+    });
+
+    Ok(())
+}
@@ -0,0 +1,211 @@
+pub mod event;
+pub mod request;
+
+use std::sync::Arc;
+
+use crate::{
+    connection::{Connection, Listener},
+    error::Result,
+    protocol::seat::SeatState,
+    sync::Lock,
+};
+
+use event::{down::Down, motion::Motion, orientation::Orientation, shape::Shape, up::Up};
+
+/// A touch point currently in contact with a surface.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TouchPoint {
+    /// The ID of the touch point, unique among the active points.
+    pub id: i32,
+    /// The surface the point went down on, which keeps receiving its
+    /// events until it goes up.
+    pub surface: u32,
+    /// The surface-local position.
+    pub position: (f64, f64),
+}
+
+/// The touch input of one `wl_touch.frame`, delivered to the handler of a [`Touch`].
+///
+/// Touch events of several points that changed at the same time are sent
+/// in a burst ended by a frame, and combined here into a single frame.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TouchFrame {
+    /// The points that went down in this frame.
+    pub down: Vec<Down>,
+    /// The points that went up in this frame.
+    pub up: Vec<Up>,
+    /// The motions of the points in this frame, in order.
+    pub motion: Vec<Motion>,
+    /// The contact shapes announced in this frame.
+    pub shape: Vec<Shape>,
+    /// The contact orientations announced in this frame.
+    pub orientation: Vec<Orientation>,
+    /// Whether the compositor took over the touch sequence, e.g. for a
+    /// gesture. All points were dropped and the frame ends the sequence.
+    pub cancelled: bool,
+    /// The points in contact at the end of the frame.
+    pub points: Vec<TouchPoint>,
+    /// The points that went up or were cancelled in this frame, at their
+    /// last position.
+    pub lifted: Vec<TouchPoint>,
+}
+
+impl TouchFrame {
+    /// Returns `true` if the frame carries no event.
+    pub fn is_empty(&self) -> bool {
+        self.down.is_empty()
+            && self.up.is_empty()
+            && self.motion.is_empty()
+            && self.shape.is_empty()
+            && self.orientation.is_empty()
+            && !self.cancelled
+    }
+
+    /// Returns the surfaces the points of the frame are on, in the order
+    /// they went down.
+    pub fn surfaces(&self) -> Vec<u32> {
+        let mut surfaces = Vec::new();
+        for point in self.lifted.iter().chain(&self.points) {
+            if !surfaces.contains(&point.surface) {
+                surfaces.push(point.surface);
+            }
+        }
+
+        surfaces
+    }
+
+    /// Returns the part of the frame concerning the points on `surface`,
+    /// e.g. to deliver it to the window owning the surface.
+    pub fn for_surface(&self, surface: u32) -> TouchFrame {
+        let on_surface = |point: &&TouchPoint| point.surface == surface;
+        let ids: Vec<i32> = self
+            .lifted
+            .iter()
+            .chain(&self.points)
+            .filter(on_surface)
+            .map(|point| point.id)
+            .collect();
+
+        TouchFrame {
+            down: filter_points(&self.down, &ids, |down| down.id.get()),
+            up: filter_points(&self.up, &ids, |up| up.id.get()),
+            motion: filter_points(&self.motion, &ids, |motion| motion.id.get()),
+            shape: filter_points(&self.shape, &ids, |shape| shape.id.get()),
+            orientation: filter_points(&self.orientation, &ids, |orientation| orientation.id.get()),
+            cancelled: self.cancelled,
+            points: self.points.iter().filter(on_surface).copied().collect(),
+            lifted: self.lifted.iter().filter(on_surface).copied().collect(),
+        }
+    }
+}
+
+/// Returns the events of `events` concerning the touch points `ids`.
+fn filter_points<E: Clone>(events: &[E], ids: &[i32], id: impl Fn(&E) -> i32) -> Vec<E> {
+    events
+        .iter()
+        .filter(|event| ids.contains(&id(event)))
+        .cloned()
+        .collect()
+}
+
+/// State of a touch device updated by its events.
+pub(crate) struct TouchState {
+    /// The frame accumulated from the events received since the last frame.
+    pending: Lock<TouchFrame>,
+    /// The points currently in contact, in the order they went down.
+    points: Lock<Vec<TouchPoint>>,
+    /// The state of the seat the touch device belongs to, which records event serials.
+    seat: Arc<SeatState>,
+}
+
+/// Applies an event to the pending frame of a touch device.
+fn update_frame<F>(conn: &Connection, touch_id: u32, update: F)
+where
+    F: FnOnce(&TouchState, &mut Vec<TouchPoint>, &mut TouchFrame),
+{
+    let Some(state) = conn.object_data::<TouchState>(touch_id) else {
+        return;
+    };
+
+    update(
+        &state,
+        &mut state.points.borrow_mut(),
+        &mut state.pending.borrow_mut(),
+    );
+}
+
+/// Delivers the pending frame of a touch device, if it is not empty, and starts a new one.
+fn emit_frame(conn: &Connection, touch_id: u32, state: &TouchState) {
+    let mut frame = state.pending.take();
+    if frame.is_empty() {
+        return;
+    }
+
+    frame.points = state.points.borrow().clone();
+    conn.emit(touch_id, frame);
+}
+
+/// A `wl_touch`, the touchscreens of a seat.
+///
+/// Created with [`Seat::get_touch`](crate::protocol::seat::Seat::get_touch).
+pub struct Touch {
+    /// The object ID of the touch device.
+    id: u32,
+    /// The interface version, inherited from the seat.
+    version: u32,
+    /// The connection the touch device was created on.
+    conn: Connection,
+}
+
+impl Touch {
+    /// Wraps a touch object that was just created on `conn`.
+    pub(crate) fn new(
+        id: u32,
+        version: u32,
+        conn: &Connection,
+        seat: Arc<SeatState>,
+        handler: Listener<TouchFrame>,
+    ) -> Touch {
+        let state = TouchState {
+            pending: Lock::new(TouchFrame::default()),
+            points: Lock::new(Vec::new()),
+            seat,
+        };
+        conn.set_object_data(id, Arc::new(state));
+        conn.set_listener(id, handler);
+
+        Touch {
+            id,
+            version,
+            conn: conn.clone(),
+        }
+    }
+
+    /// Returns the object ID of the touch device.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Releases the touch device.
+    ///
+    /// Before version 3 the touch device cannot be released and the object
+    /// is kept alive on the compositor side.
+    ///
+    /// Dropping the handle does the same.
+    pub fn release(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl Drop for Touch {
+    fn drop(&mut self) {
+        self.conn.remove_listener(self.id);
+
+        if self.version >= 3 {
+            self.conn
+                .destroy_on_drop(|conn| request::release(conn, self.id));
+        }
+    }
+}
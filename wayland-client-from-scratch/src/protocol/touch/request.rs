@@ -0,0 +1,16 @@
+use crate::{connection::Connection, error::Result, wl_request_opcode};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to a Wayland touch object.
+    Opcode {
+        /// Releases the touch object (since version 3).
+        Release = 0 (destructor),
+    }
+}
+
+/// Sends a `wl_touch.release` request to the compositor.
+pub fn release(conn: &Connection, touch: u32) -> Result<()> {
+    conn.send_request(touch, Opcode::Release, &())?;
+
+    Ok(())
+}
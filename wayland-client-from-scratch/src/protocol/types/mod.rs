@@ -1,21 +1,40 @@
 pub mod wlarray;
+pub mod wlnewid;
 pub mod wlstring;
 
 use crate::wl_primitive_type;
 
 #[allow(unused)]
 pub use wlarray::WlArray;
-pub use wlstring::WlString;
+pub use wlnewid::WlUntypedNewId;
+pub use wlstring::{Utf8Mode, WlString};
 
-wl_primitive_type!(WlUInt(i32));
+wl_primitive_type!(WlUInt(u32));
 wl_primitive_type!(WlInt(i32));
 wl_primitive_type!(WlObject(u32));
 wl_primitive_type!(WlNewId(u32));
 wl_primitive_type!(WlEnum(u32));
+wl_primitive_type!(
+    /// A signed 24.8 fixed-point number, used for coordinates and scroll values.
+    WlFixed(i32)
+);
+
+impl WlFixed {
+    /// Converts a floating-point number, rounding to the nearest 1/256.
+    pub fn from_f64(value: f64) -> WlFixed {
+        WlFixed((value * 256.0).round() as i32)
+    }
+
+    /// Returns the value as a floating-point number.
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / 256.0
+    }
+}
 
 pub const WL_TYPE_UINT_LEN: usize = WlUInt::type_size();
+pub const WL_TYPE_INT_LEN: usize = WlInt::type_size();
+pub const WL_TYPE_FIXED_LEN: usize = WlFixed::type_size();
 pub const WL_TYPE_OBJECT_LEN: usize = WlObject::type_size();
-#[allow(dead_code)]
 pub const WL_TYPE_NEWID_LEN: usize = WlNewId::type_size();
 pub const WL_TYPE_ENUM_LEN: usize = WlEnum::type_size();
 
@@ -34,7 +53,7 @@ pub const WL_TYPE_ENUM_LEN: usize = WlEnum::type_size();
 ///
 /// # Examples
 ///
-/// ```
+/// ```ignore
 /// assert_eq!(roundup_4(5), 8);
 /// assert_eq!(roundup_4(8), 8);
 /// assert_eq!(roundup_4(9), 12);
@@ -1,4 +1,11 @@
 use super::roundup_4;
+use crate::{
+    error::{Error, Result, anyhow},
+    protocol::{
+        encoder::{Encode, Encoder},
+        endian::Endian,
+    },
+};
 
 /// The size of the array length prefix in bytes (32-bit integer).
 const WL_ARRAY_PREFIX_LEN: usize = size_of::<u32>();
@@ -89,6 +96,21 @@ impl std::fmt::Display for WlArray {
     }
 }
 
+impl std::fmt::Debug for WlArray {
+    /// Formats the array content, without the padding.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self.as_slice(), f)
+    }
+}
+
+impl Encode for WlArray {
+    /// Writes the length prefix followed by the padded content.
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.write_uint(self.size);
+        encoder.write_raw(&self.data);
+    }
+}
+
 impl From<WlArray> for Vec<u8> {
     /// Serializes the array into the Wayland wire format.
     ///
@@ -102,7 +124,7 @@ impl From<WlArray> for Vec<u8> {
         let mut buffer = Vec::with_capacity(WL_ARRAY_PREFIX_LEN + array.data.len());
 
         // Add 32-bit length prefix (array bytes excluding padding)
-        buffer.extend_from_slice(&array.size.to_ne_bytes());
+        buffer.extend_from_slice(&Endian::NATIVE.encode_u32(array.size));
 
         // Add array content bytes (already padded during construction)
         buffer.extend_from_slice(&array.data);
@@ -112,7 +134,7 @@ impl From<WlArray> for Vec<u8> {
 }
 
 impl TryFrom<&[u8]> for WlArray {
-    type Error = anyhow::Error;
+    type Error = Error;
 
     /// Deserializes a Wayland array from the wire format.
     ///
@@ -126,15 +148,15 @@ impl TryFrom<&[u8]> for WlArray {
     ///
     /// # Returns
     /// * `Ok(WlArray)` if the buffer contains valid array data
-    /// * `Err(anyhow::Error)` if the buffer is malformed or incomplete
+    /// * `Err(Error)` if the buffer is malformed or incomplete
     ///
     /// # Errors
     /// Returns an error if:
     /// - Buffer is too short for the length prefix (less than 4 bytes)
     /// - Buffer is too short for the declared array content
-    fn try_from(buffer: &[u8]) -> anyhow::Result<WlArray> {
+    fn try_from(buffer: &[u8]) -> Result<WlArray> {
         if buffer.len() < WL_ARRAY_PREFIX_LEN {
-            return Err(anyhow::anyhow!(
+            return Err(anyhow!(
                 "Buffer too short for WlArray length field: expected at least {} bytes, got {}",
                 WL_ARRAY_PREFIX_LEN,
                 buffer.len()
@@ -143,14 +165,14 @@ impl TryFrom<&[u8]> for WlArray {
 
         // Extract 32-bit length prefix from first 4 bytes
         // This is the array bytes excluding padding
-        let content_len = u32::from_ne_bytes(buffer[..WL_ARRAY_PREFIX_LEN].try_into()?) as usize;
+        let content_len = Endian::NATIVE.decode_u32_at(buffer) as usize;
 
         // Calculate padded length for buffer extraction
         let padded_len = roundup_4(content_len);
         let total_buffer_len = WL_ARRAY_PREFIX_LEN + padded_len;
 
         if buffer.len() < total_buffer_len {
-            return Err(anyhow::anyhow!(
+            return Err(anyhow!(
                 "Buffer too short for WlArray content: expected at least {} bytes, got {}",
                 total_buffer_len,
                 buffer.len()
@@ -0,0 +1,131 @@
+use crate::{
+    error::{Error, Result, anyhow},
+    protocol::{
+        encoder::{Encode, Encoder},
+        types::{WL_TYPE_NEWID_LEN, WL_TYPE_UINT_LEN, WlNewId, WlString, WlUInt},
+    },
+};
+
+/// Represents a `new_id` argument whose interface is not fixed by the
+/// request, such as the one of `wl_registry.bind`.
+///
+/// Since the receiver cannot tell which interface the new object implements,
+/// the ID is preceded on the wire by the interface name and version.
+///
+/// # Specification
+/// Untyped new IDs are transmitted as:
+/// - The interface name, as a `string`
+/// - The interface version, as a `uint`
+/// - The object ID, as a `new_id`
+#[derive(Debug)]
+pub struct WlUntypedNewId {
+    /// The name of the interface the new object implements.
+    interface: WlString,
+    /// The version of the interface.
+    version: WlUInt,
+    /// The ID of the new object.
+    id: WlNewId,
+}
+
+impl WlUntypedNewId {
+    /// Creates a new ID for an object implementing `version` of `interface`.
+    ///
+    /// # Errors
+    /// Returns an error if `interface` contains a NUL byte.
+    pub fn new(interface: &str, version: u32, id: u32) -> Result<Self> {
+        Ok(Self {
+            interface: WlString::new(interface)?,
+            version: WlUInt(version),
+            id: WlNewId(id),
+        })
+    }
+
+    /// Returns the name of the interface the new object implements.
+    pub fn interface(&self) -> &WlString {
+        &self.interface
+    }
+
+    /// Returns the version of the interface.
+    pub fn version(&self) -> u32 {
+        self.version.get()
+    }
+
+    /// Returns the ID of the new object.
+    pub fn id(&self) -> u32 {
+        self.id.get()
+    }
+
+    /// Returns the number of bytes the argument takes on the wire.
+    pub fn buffer_size(&self) -> usize {
+        self.interface.buffer_size() + WL_TYPE_UINT_LEN + WL_TYPE_NEWID_LEN
+    }
+
+    /// Returns the wire representation of the argument.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buffer = self.interface.to_bytes();
+        buffer.extend_from_slice(&self.version.as_bytes());
+        buffer.extend_from_slice(&self.id.as_bytes());
+
+        buffer
+    }
+}
+
+impl Encode for WlUntypedNewId {
+    /// Writes the interface name, the version and the ID in order.
+    fn encode(&self, encoder: &mut Encoder) {
+        self.interface.encode(encoder);
+        self.version.encode(encoder);
+        self.id.encode(encoder);
+    }
+}
+
+impl From<WlUntypedNewId> for Vec<u8> {
+    /// Serializes the argument into the Wayland wire format.
+    fn from(new_id: WlUntypedNewId) -> Vec<u8> {
+        new_id.to_bytes()
+    }
+}
+
+impl TryFrom<&[u8]> for WlUntypedNewId {
+    type Error = Error;
+
+    /// Deserializes an untyped new ID from the start of `buf`.
+    ///
+    /// # Errors
+    /// Returns an error if the interface name is not a valid string, or if
+    /// `buf` ends before the version or the ID.
+    fn try_from(buf: &[u8]) -> Result<WlUntypedNewId> {
+        let interface = WlString::try_from(buf)
+            .map_err(|error| anyhow!("Invalid interface of untyped new_id: {error}"))?;
+        let offset = interface.buffer_size();
+        let version = WlUInt::read(buf, offset)?;
+        let id = WlNewId::read(buf, offset + WL_TYPE_UINT_LEN)?;
+
+        Ok(WlUntypedNewId {
+            interface,
+            version,
+            id,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn untyped_new_ids_round_trip_through_the_encoder() {
+        let new_id = WlUntypedNewId::new("wl_compositor", 6, 3).unwrap();
+
+        let mut encoder = Encoder::new(2, 0);
+        new_id.encode(&mut encoder);
+        let bytes = encoder.finish().unwrap()[8..].to_vec();
+        assert_eq!(bytes, new_id.to_bytes());
+        assert_eq!(bytes.len(), new_id.buffer_size());
+
+        let decoded = WlUntypedNewId::try_from(&bytes[..]).unwrap();
+        assert_eq!(decoded.interface().as_str(), "wl_compositor");
+        assert_eq!((decoded.version(), decoded.id()), (6, 3));
+        assert!(WlUntypedNewId::try_from(&bytes[..bytes.len() - 1]).is_err());
+    }
+}
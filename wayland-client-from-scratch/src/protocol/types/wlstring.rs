@@ -1,10 +1,33 @@
+use std::{borrow::Cow, ffi::CStr};
+
 use super::roundup_4;
+use crate::{
+    error::{Error, Result, anyhow},
+    protocol::{
+        encoder::{Encode, Encoder},
+        endian::Endian,
+    },
+};
 
 /// The size of the string length prefix in bytes (32-bit integer).
 const WL_STRING_PREFIX_LEN: usize = 4;
 /// The NUL terminator byte value used in Wayland strings.
 const WL_NUL: u8 = 0;
 
+/// How the content of a received [`WlString`] is decoded as UTF-8.
+///
+/// The protocol requires UTF-8 but compositors do not always validate what
+/// they forward, e.g. output names read from EDID data or MIME types set by
+/// other clients.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Utf8Mode {
+    /// Invalid sequences are replaced with `U+FFFD REPLACEMENT CHARACTER`.
+    #[default]
+    Lossy,
+    /// Invalid sequences are a decode error reporting their byte offset.
+    Strict,
+}
+
 /// Represents a Wayland protocol string type.
 ///
 /// A string, prefixed with a 32-bit integer specifying its length (in bytes),
@@ -17,7 +40,7 @@ const WL_NUL: u8 = 0;
 /// - String content bytes (UTF-8 encoded)
 /// - NUL terminator byte
 /// - Padding bytes to reach 32-bit alignment boundary
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct WlString {
     /// The size of the string content in bytes, including NUL terminator but excluding padding.
     ///
@@ -39,8 +62,17 @@ impl WlString {
     ///
     /// # Arguments
     /// * `s` - The string content to store
-    pub fn new(s: &str) -> Self {
+    ///
+    /// # Errors
+    /// Returns an error if `s` contains a NUL byte, which the receiver would
+    /// take for the end of the string.
+    pub fn new(s: &str) -> Result<Self> {
         let string_bytes = s.as_bytes();
+        if let Some(offset) = string_bytes.iter().position(|&byte| byte == WL_NUL) {
+            return Err(anyhow!(
+                "Cannot encode a string with an interior NUL at byte offset {offset}"
+            ));
+        }
 
         let mut data = string_bytes.to_vec();
         data.push(WL_NUL);
@@ -51,7 +83,7 @@ impl WlString {
         // Size is the string bytes + NUL terminator (excluding padding)
         let size = (string_bytes.len() + 1) as u32;
 
-        Self { size, data }
+        Ok(Self { size, data })
     }
 
     /// Returns the total buffer size required for serialization.
@@ -64,14 +96,63 @@ impl WlString {
         WL_STRING_PREFIX_LEN + self.data.len()
     }
 
+    /// Returns the string content bytes, without NUL terminator and padding.
+    pub fn as_bytes(&self) -> &[u8] {
+        // The actual string content is everything before the NUL terminator
+        // which is at position (self.size - 1) since size includes the NUL
+        let string_len = self.size.saturating_sub(1) as usize;
+        &self.data[..string_len]
+    }
+
+    /// Returns the string content as a NUL-terminated C string, borrowed
+    /// from the received bytes, for passing to C APIs such as xkbcommon.
+    ///
+    /// The content has no interior NUL, as it is checked on encoding and
+    /// decoding.
+    pub fn as_cstr(&self) -> &CStr {
+        CStr::from_bytes_with_nul(&self.data[..self.size as usize]).unwrap_or_default()
+    }
+
     /// Returns the actual string content as a Rust string slice.
     ///
-    /// Uses lossy UTF-8 conversion to handle any encoding errors gracefully.
+    /// Returns an empty string if the content is not valid UTF-8, see
+    /// [`WlString::to_str`] and [`WlString::to_string_lossy`] to tell such
+    /// strings apart.
     pub fn as_str(&self) -> &str {
-        // The actual string content is everything before the NUL terminator
-        // which is at position (self.size - 1) since size includes the NUL
-        let string_len = (self.size - 1) as usize;
-        std::str::from_utf8(&self.data[..string_len]).unwrap_or("")
+        self.to_str().unwrap_or("")
+    }
+
+    /// Returns the string content, validated as UTF-8.
+    ///
+    /// # Errors
+    /// Returns an error with the byte offset of the first invalid sequence
+    /// if the content is not valid UTF-8.
+    pub fn to_str(&self) -> Result<&str> {
+        std::str::from_utf8(self.as_bytes()).map_err(|error| {
+            anyhow!(
+                "Invalid UTF-8 in WlString at byte offset {} of {}",
+                error.valid_up_to(),
+                self.as_bytes().len()
+            )
+        })
+    }
+
+    /// Returns the string content, with invalid UTF-8 sequences replaced by
+    /// `U+FFFD REPLACEMENT CHARACTER`.
+    pub fn to_string_lossy(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(self.as_bytes())
+    }
+
+    /// Returns the string content decoded according to `mode`.
+    ///
+    /// # Errors
+    /// Returns an error in [`Utf8Mode::Strict`] if the content is not valid
+    /// UTF-8.
+    pub fn decode(&self, mode: Utf8Mode) -> Result<Cow<'_, str>> {
+        match mode {
+            Utf8Mode::Lossy => Ok(self.to_string_lossy()),
+            Utf8Mode::Strict => self.to_str().map(Cow::Borrowed),
+        }
     }
 
     /// Returns the complete wire format bytes including length prefix, content, NUL terminator and padding.
@@ -88,7 +169,7 @@ impl WlString {
         let mut buffer = Vec::with_capacity(WL_STRING_PREFIX_LEN + self.data.len());
 
         // Add 32-bit length prefix (string bytes + NUL, excluding padding)
-        buffer.extend_from_slice(&self.size.to_ne_bytes());
+        buffer.extend_from_slice(&Endian::NATIVE.encode_u32(self.size));
 
         // Add string content bytes (including NUL terminator and padding)
         buffer.extend_from_slice(&self.data);
@@ -97,6 +178,15 @@ impl WlString {
     }
 }
 
+impl Encode for WlString {
+    /// Writes the length prefix followed by the content, NUL terminator
+    /// and padding, without building an intermediate buffer.
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.write_uint(self.size);
+        encoder.write_raw(&self.data);
+    }
+}
+
 impl std::fmt::Display for WlString {
     /// Formats the string for human-readable display.
     ///
@@ -106,14 +196,27 @@ impl std::fmt::Display for WlString {
             f,
             "WlString {{ size: {}, data: {} }}",
             self.size,
-            self.as_str()
+            self.to_string_lossy()
         )
     }
 }
 
-impl From<String> for WlString {
+impl std::fmt::Debug for WlString {
+    /// Formats the string content like a Rust string, with invalid UTF-8
+    /// sequences replaced.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(&self.to_string_lossy(), f)
+    }
+}
+
+impl TryFrom<String> for WlString {
+    type Error = Error;
+
     /// Converts a Rust String to a Wayland protocol string.
-    fn from(s: String) -> Self {
+    ///
+    /// # Errors
+    /// Returns an error if the string contains a NUL byte.
+    fn try_from(s: String) -> Result<Self> {
         Self::new(&s)
     }
 }
@@ -123,7 +226,7 @@ impl From<&WlString> for String {
     ///
     /// Uses lossy UTF-8 conversion to handle any encoding errors gracefully.
     fn from(wls: &WlString) -> String {
-        wls.as_str().to_string()
+        wls.to_string_lossy().into_owned()
     }
 }
 
@@ -144,7 +247,7 @@ impl From<WlString> for Vec<u8> {
 }
 
 impl TryFrom<&[u8]> for WlString {
-    type Error = anyhow::Error;
+    type Error = Error;
 
     /// Deserializes a Wayland string from the wire format.
     ///
@@ -159,16 +262,19 @@ impl TryFrom<&[u8]> for WlString {
     ///
     /// # Returns
     /// * `Ok(WlString)` if the buffer contains valid string data
-    /// * `Err(anyhow::Error)` if the buffer is malformed or incomplete
+    /// * `Err(Error)` if the buffer is malformed or incomplete
     ///
     /// # Errors
     /// Returns an error if:
     /// - Buffer is too short for the length prefix (less than 4 bytes)
     /// - Buffer is too short for the declared string content
     /// - NUL terminator is missing from the string content
-    fn try_from(buf: &[u8]) -> anyhow::Result<WlString> {
+    /// - A NUL byte appears before the terminator
+    ///
+    /// The content is not validated as UTF-8 here, see [`WlString::decode`].
+    fn try_from(buf: &[u8]) -> Result<WlString> {
         if buf.len() < WL_STRING_PREFIX_LEN {
-            return Err(anyhow::anyhow!(
+            return Err(anyhow!(
                 "Buffer too short for WlString length field: expected at least {} bytes, got {}",
                 WL_STRING_PREFIX_LEN,
                 buf.len()
@@ -177,7 +283,7 @@ impl TryFrom<&[u8]> for WlString {
 
         // Extract 32-bit length prefix from first 4 bytes
         // This is the string bytes + NUL terminator (excluding padding)
-        let content_len = u32::from_ne_bytes(buf[..WL_STRING_PREFIX_LEN].try_into()?) as usize;
+        let content_len = Endian::NATIVE.decode_u32_at(buf) as usize;
 
         // Calculate padded length for buffer extraction
         let padded_len = roundup_4(content_len);
@@ -185,7 +291,7 @@ impl TryFrom<&[u8]> for WlString {
 
         // Check if we have enough data for the content section (with padding)
         if buf.len() < total_buffer_len {
-            return Err(anyhow::anyhow!(
+            return Err(anyhow!(
                 "Buffer too short for WlString content: expected at least {} bytes, got {}",
                 total_buffer_len,
                 buf.len()
@@ -196,13 +302,101 @@ impl TryFrom<&[u8]> for WlString {
         let content_section = &buf[WL_STRING_PREFIX_LEN..total_buffer_len];
 
         // Validate NUL terminator is at the expected position
-        if content_len > 0 && content_section[content_len - 1] == WL_NUL {
-            Ok(WlString {
-                size: content_len as u32,
-                data: content_section.to_vec(),
-            })
-        } else {
-            Err(anyhow::anyhow!("Missing NUL terminator in WlString"))
+        if content_len == 0 || content_section[content_len - 1] != WL_NUL {
+            return Err(anyhow!(
+                "Missing NUL terminator in WlString at byte offset {}",
+                WL_STRING_PREFIX_LEN + content_len.saturating_sub(1)
+            ));
         }
+
+        // Validate no NUL precedes the terminator, as C receivers would
+        // silently truncate the string there
+        if let Some(offset) = content_section[..content_len - 1]
+            .iter()
+            .position(|&byte| byte == WL_NUL)
+        {
+            return Err(anyhow!(
+                "Interior NUL in WlString at byte offset {}",
+                WL_STRING_PREFIX_LEN + offset
+            ));
+        }
+
+        Ok(WlString {
+            size: content_len as u32,
+            data: content_section.to_vec(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Encodes a string with the given content bytes, bypassing `WlString::new`.
+    fn encode(content: &[u8]) -> Vec<u8> {
+        let mut buf = Endian::NATIVE.encode_u32(content.len() as u32 + 1).to_vec();
+        buf.extend_from_slice(content);
+        buf.push(WL_NUL);
+        buf.resize(WL_STRING_PREFIX_LEN + roundup_4(content.len() + 1), 0);
+        buf
+    }
+
+    #[test]
+    fn lossy_replaces_invalid_utf8() {
+        let string = WlString::try_from(encode(b"ab\xffc").as_slice()).unwrap();
+
+        assert_eq!(string.decode(Utf8Mode::Lossy).unwrap(), "ab\u{FFFD}c");
+        assert_eq!(String::from(&string), "ab\u{FFFD}c");
+    }
+
+    #[test]
+    fn strict_reports_offset_of_invalid_utf8() {
+        let string = WlString::try_from(encode(b"ab\xffc").as_slice()).unwrap();
+
+        let error = string.decode(Utf8Mode::Strict).unwrap_err();
+
+        assert!(error.to_string().contains("byte offset 2"), "{error}");
+    }
+
+    #[test]
+    fn strict_accepts_valid_utf8() {
+        let string = WlString::try_from(encode("seat-é".as_bytes()).as_slice()).unwrap();
+
+        assert_eq!(string.decode(Utf8Mode::Strict).unwrap(), "seat-é");
+    }
+
+    #[test]
+    fn rejects_misplaced_terminator_with_offset() {
+        let mut buf = encode(b"abc");
+        buf[WL_STRING_PREFIX_LEN + 3] = b'd';
+
+        let Err(error) = WlString::try_from(buf.as_slice()) else {
+            panic!("misplaced terminator was accepted");
+        };
+
+        assert!(error.to_string().contains("byte offset 7"), "{error}");
+    }
+
+    #[test]
+    fn rejects_interior_nul_with_offset() {
+        let Err(error) = WlString::try_from(encode(b"ab\0c").as_slice()) else {
+            panic!("interior NUL was accepted");
+        };
+
+        assert!(error.to_string().contains("byte offset 6"), "{error}");
+    }
+
+    #[test]
+    fn new_rejects_interior_nul() {
+        assert!(WlString::new("ab\0c").is_err());
+    }
+
+    #[test]
+    fn as_cstr_borrows_content() {
+        let string = WlString::new("xdg_wm_base").unwrap();
+        let decoded = WlString::try_from(string.to_bytes().as_slice()).unwrap();
+
+        assert_eq!(decoded.as_cstr(), c"xdg_wm_base");
+        assert_eq!(WlString::default().as_cstr(), c"");
     }
 }
@@ -0,0 +1,148 @@
+pub mod request;
+
+use crate::{
+    connection::Connection,
+    error::{Result, anyhow},
+    protocol::{
+        WlObjectId,
+        surface::Surface,
+        types::{WlFixed, WlInt, WlNewId, WlObject},
+    },
+};
+
+/// The highest `wp_viewporter` version this crate implements.
+pub const WP_VIEWPORTER_VERSION: u32 = 1;
+
+/// A bound `wp_viewporter` global, which lets surfaces crop and scale
+/// their buffers.
+#[derive(Clone)]
+pub struct Viewporter {
+    /// The object ID of the bound viewporter.
+    id: u32,
+    /// The negotiated interface version.
+    version: u32,
+    /// The connection the viewporter was bound on.
+    conn: Connection,
+}
+
+impl Viewporter {
+    /// Binds the `wp_viewporter` global advertised by the compositor.
+    pub fn bind(conn: &Connection) -> Result<Viewporter> {
+        let (id, version) = conn.bind(WlObjectId::Viewporter, WP_VIEWPORTER_VERSION)?;
+
+        Ok(Viewporter {
+            id,
+            version,
+            conn: conn.clone(),
+        })
+    }
+
+    /// Returns the object ID of the viewporter.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Creates the viewport of `surface`. A surface can only have one
+    /// viewport at a time.
+    pub fn get_viewport(&self, surface: &Surface) -> Result<Viewport> {
+        self.conn
+            .check_same_connection(surface.connection(), WlObjectId::Surface, surface.id())?;
+
+        let id = self.conn.new_object(WlObjectId::Viewport, self.version)?;
+        request::get_viewport(&self.conn, self.id, WlNewId(id), WlObject(surface.id()))?;
+
+        Ok(Viewport {
+            id,
+            conn: self.conn.clone(),
+        })
+    }
+
+    /// Destroys the viewporter; existing viewports keep working.
+    pub fn destroy(self) -> Result<()> {
+        request::destroy(&self.conn, self.id)
+    }
+}
+
+/// A `wp_viewport`, the crop and scale state of a surface.
+///
+/// Like all surface state, changes apply on the next commit of the surface.
+pub struct Viewport {
+    /// The object ID of the viewport.
+    id: u32,
+    /// The connection the viewport was created on.
+    conn: Connection,
+}
+
+impl Viewport {
+    /// Returns the object ID of the viewport.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Shows only the part `(x, y, width, height)` of the buffer, in buffer
+    /// coordinates after the buffer transform and scale.
+    ///
+    /// # Errors
+    /// Returns an error if the origin is negative or the size is not positive.
+    pub fn set_source(&self, x: f64, y: f64, width: f64, height: f64) -> Result<()> {
+        if x < 0.0 || y < 0.0 || width <= 0.0 || height <= 0.0 {
+            return Err(anyhow!(
+                "wp_viewport {}: invalid source rectangle {x},{y} {width}x{height}",
+                self.id
+            ));
+        }
+
+        request::set_source(
+            &self.conn,
+            self.id,
+            WlFixed::from_f64(x),
+            WlFixed::from_f64(y),
+            WlFixed::from_f64(width),
+            WlFixed::from_f64(height),
+        )
+    }
+
+    /// Shows the whole buffer again.
+    pub fn unset_source(&self) -> Result<()> {
+        let unset = WlFixed::from_f64(-1.0);
+
+        request::set_source(&self.conn, self.id, unset, unset, unset, unset)
+    }
+
+    /// Scales the content to a surface of `width` x `height`, in
+    /// surface-local coordinates.
+    ///
+    /// # Errors
+    /// Returns an error if the size is empty.
+    pub fn set_destination(&self, width: i32, height: i32) -> Result<()> {
+        if width <= 0 || height <= 0 {
+            return Err(anyhow!(
+                "wp_viewport {}: destination size must not be empty, got {width}x{height}",
+                self.id
+            ));
+        }
+
+        request::set_destination(&self.conn, self.id, WlInt(width), WlInt(height))
+    }
+
+    /// Sizes the surface after its buffer again.
+    pub fn unset_destination(&self) -> Result<()> {
+        request::set_destination(&self.conn, self.id, WlInt(-1), WlInt(-1))
+    }
+
+    /// Destroys the viewport, removing the crop and scale of the surface.
+    ///
+    /// Dropping the handle does the same.
+    pub fn destroy(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl Drop for Viewport {
+    fn drop(&mut self) {
+        self.conn
+            .destroy_on_drop(|conn| request::destroy_viewport(conn, self.id));
+    }
+}
@@ -0,0 +1,201 @@
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::types::{WlFixed, WlInt, WlNewId, WlObject},
+    wl_request_opcode, wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to the `wp_viewporter` object.
+    Opcode {
+        /// Destroys the viewporter; existing viewports keep working.
+        Destroy = 0 (destructor),
+
+        /// Creates the viewport of a surface.
+        GetViewport = 1,
+    }
+}
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to a `wp_viewport` object.
+    ViewportOpcode {
+        /// Destroys the viewport, removing the crop and scale of the surface.
+        Destroy = 0 (destructor),
+
+        /// Sets the part of the buffer shown by the surface.
+        SetSource = 1,
+
+        /// Sets the size of the surface.
+        SetDestination = 2,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wp_viewporter.get_viewport` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="get_viewport">
+    ///   <description summary="extend surface interface for crop and scale">
+    ///     Instantiate an interface extension for the given wl_surface to
+    ///     crop and scale its content. If the given wl_surface already has
+    ///     a wp_viewport object associated, the viewport_exists
+    ///     protocol error is raised.
+    ///   </description>
+    ///   <arg name="id" type="new_id" interface="wp_viewport" summary="the new viewport interface id"/>
+    ///   <arg name="surface" type="object" interface="wl_surface" summary="the surface"/>
+    /// </request>
+    /// ```
+    GetViewportParam {
+        /// The object ID to assign to the new viewport.
+        id: WlNewId,
+        /// The surface to crop and scale.
+        surface: WlObject,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wp_viewport.set_source` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_source">
+    ///   <description summary="set the source rectangle for cropping">
+    ///     Set the source rectangle of the associated wl_surface. See
+    ///     wp_viewport for the description, and relation to the wl_buffer
+    ///     size.
+    ///
+    ///     If all of x, y, width and height are -1.0, the source rectangle is
+    ///     unset instead. Any other set of values where width or height are zero
+    ///     or negative, or x or y are negative, raise the bad_value protocol
+    ///     error.
+    ///
+    ///     The crop and scale state is double-buffered, see wl_surface.commit.
+    ///   </description>
+    ///   <arg name="x" type="fixed" summary="source rectangle x"/>
+    ///   <arg name="y" type="fixed" summary="source rectangle y"/>
+    ///   <arg name="width" type="fixed" summary="source rectangle width"/>
+    ///   <arg name="height" type="fixed" summary="source rectangle height"/>
+    /// </request>
+    /// ```
+    SetSourceParam {
+        /// The left edge of the source rectangle, in buffer coordinates.
+        x: WlFixed,
+        /// The top edge of the source rectangle, in buffer coordinates.
+        y: WlFixed,
+        /// The width of the source rectangle.
+        width: WlFixed,
+        /// The height of the source rectangle.
+        height: WlFixed,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `wp_viewport.set_destination` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_destination">
+    ///   <description summary="set the surface size for scaling">
+    ///     Set the destination size of the associated wl_surface. See
+    ///     wp_viewport for the description, and relation to the wl_buffer
+    ///     size.
+    ///
+    ///     If width is -1 and height is -1, the destination size is unset
+    ///     instead. Any other pair of values for width and height that
+    ///     contains zero or negative values raises the bad_value protocol
+    ///     error.
+    ///
+    ///     The crop and scale state is double-buffered, see wl_surface.commit.
+    ///   </description>
+    ///   <arg name="width" type="int" summary="surface width"/>
+    ///   <arg name="height" type="int" summary="surface height"/>
+    /// </request>
+    /// ```
+    SetDestinationParam {
+        /// The width of the surface in surface-local coordinates.
+        width: WlInt,
+        /// The height of the surface in surface-local coordinates.
+        height: WlInt,
+    }
+}
+
+/// Sends a `wp_viewporter.destroy` request to the compositor.
+pub fn destroy(conn: &Connection, viewporter: u32) -> Result<()> {
+    conn.send_request(viewporter, Opcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends a `wp_viewporter.get_viewport` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `viewporter` - The ID of the bound viewporter
+/// * `id` - The object ID to assign to the new viewport
+/// * `surface` - The surface to crop and scale
+pub fn get_viewport(
+    conn: &Connection,
+    viewporter: u32,
+    id: WlNewId,
+    surface: WlObject,
+) -> Result<()> {
+    conn.send_request(
+        viewporter,
+        Opcode::GetViewport,
+        &GetViewportParam::new(id, surface),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `wp_viewport.destroy` request to the compositor.
+pub fn destroy_viewport(conn: &Connection, viewport: u32) -> Result<()> {
+    conn.send_request(viewport, ViewportOpcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends a `wp_viewport.set_source` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `viewport` - The ID of the viewport
+/// * `x`, `y`, `width`, `height` - The source rectangle, or all -1.0 to unset it
+pub fn set_source(
+    conn: &Connection,
+    viewport: u32,
+    x: WlFixed,
+    y: WlFixed,
+    width: WlFixed,
+    height: WlFixed,
+) -> Result<()> {
+    conn.send_request(
+        viewport,
+        ViewportOpcode::SetSource,
+        &SetSourceParam::new(x, y, width, height),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `wp_viewport.set_destination` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `viewport` - The ID of the viewport
+/// * `width`, `height` - The surface size, or both -1 to unset it
+pub fn set_destination(
+    conn: &Connection,
+    viewport: u32,
+    width: WlInt,
+    height: WlInt,
+) -> Result<()> {
+    conn.send_request(
+        viewport,
+        ViewportOpcode::SetDestination,
+        &SetDestinationParam::new(width, height),
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::types::WlUInt,
+};
+
+/// Represents a `zxdg_toplevel_decoration_v1.configure` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="configure">
+///   <description summary="notify a decoration mode change">
+///     The configure event configures the effective decoration mode. The
+///     configured state should not be applied immediately. Clients must send an
+///     ack_configure in response to this event. See xdg_surface.configure and
+///     xdg_surface.ack_configure for details.
+///
+///     A configure event can be sent at any time. The specified mode must be
+///     obeyed by the client.
+///   </description>
+///   <arg name="mode" type="uint" enum="mode" summary="the decoration mode"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Configure {
+    /// The decoration mode to use, a [`Mode`](super::super::Mode) value.
+    pub mode: WlUInt,
+}
+
+impl TryFrom<&[u8]> for Configure {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Configure` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `mode` (u32) - The decoration mode to use
+    fn try_from(buf: &[u8]) -> Result<Configure> {
+        Ok(Configure {
+            mode: WlUInt::read(buf, 0)?,
+        })
+    }
+}
+
+/// Handles a `zxdg_toplevel_decoration_v1.configure` event by notifying the listener.
+pub(super) fn handle_zxdg_toplevel_decoration_configure(
+    conn: &Connection,
+    decoration_id: u32,
+    event: Configure,
+) -> Result<()> {
+    conn.emit(decoration_id, event);
+
+    Ok(())
+}
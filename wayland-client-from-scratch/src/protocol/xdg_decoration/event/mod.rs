@@ -0,0 +1,82 @@
+pub mod configure;
+
+use crate::{
+    connection::Connection,
+    error::{Error, Result, anyhow},
+    protocol::message::WlMessage,
+};
+
+/// Represents the event types that can be emitted by a `zxdg_toplevel_decoration_v1` object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    /// Announces the decoration mode the client must use.
+    ///
+    /// # Event Arguments
+    /// - `mode`: The decoration mode
+    Configure = 0,
+}
+
+impl TryFrom<u16> for Opcode {
+    type Error = Error;
+
+    /// Attempts to convert a raw opcode value into a structured `ZxdgToplevelDecorationEvent`.
+    fn try_from(value: u16) -> Result<Opcode> {
+        match value {
+            0 => Ok(Opcode::Configure),
+            _ => Err(anyhow!(
+                "Invalid zxdg_toplevel_decoration_v1 event opcode: {}",
+                value
+            )),
+        }
+    }
+}
+
+impl Opcode {
+    /// Returns the interface version the event was introduced in.
+    pub fn since(self) -> u32 {
+        match self {
+            Opcode::Configure => 1,
+        }
+    }
+}
+
+/// A decoded `zxdg_toplevel_decoration_v1` event, with its arguments.
+#[derive(Debug)]
+pub enum Event {
+    /// Announces the decoration mode the client must use.
+    Configure(configure::Configure),
+}
+
+impl Event {
+    /// Decodes a `zxdg_toplevel_decoration_v1` event from its message.
+    ///
+    /// # Errors
+    /// Returns an error if the opcode is unknown or the arguments are malformed.
+    pub fn decode(_conn: &Connection, msg: &WlMessage) -> Result<Event> {
+        let buf: &[u8] = &msg.data;
+
+        Ok(match Opcode::try_from(msg.header.opcode)? {
+            Opcode::Configure => Event::Configure(configure::Configure::try_from(buf)?),
+        })
+    }
+
+    /// Returns the opcode of the event.
+    pub fn opcode(&self) -> Opcode {
+        match self {
+            Event::Configure(_) => Opcode::Configure,
+        }
+    }
+}
+
+/// Dispatches incoming `zxdg_toplevel_decoration_v1` events to their appropriate handler functions.
+///
+/// # Event Routing
+/// * `Configure` events are routed to `configure::handle_zxdg_toplevel_decoration_configure`
+pub fn handle_zxdg_toplevel_decoration_event(conn: &Connection, msg: WlMessage) -> Result<()> {
+    // Route the event to the appropriate handler based on type
+    match Event::decode(conn, &msg)? {
+        Event::Configure(event) => {
+            configure::handle_zxdg_toplevel_decoration_configure(conn, msg.header.object_id, event)
+        }
+    }
+}
@@ -0,0 +1,165 @@
+pub mod event;
+pub mod request;
+
+use std::sync::Arc;
+
+use crate::{
+    connection::{Connection, Listener},
+    error::Result,
+    protocol::{
+        WlObjectId,
+        types::{WlNewId, WlObject, WlUInt},
+        xdg_toplevel::XdgToplevel,
+    },
+    sync::LockCell,
+    wl_enum,
+};
+
+use event::configure::Configure;
+
+/// The highest `zxdg_decoration_manager_v1` version this crate implements.
+pub const ZXDG_DECORATION_MANAGER_VERSION: u32 = 1;
+
+wl_enum! {
+    /// Who draws the decorations of a window, such as its title bar and
+    /// borders, see `zxdg_toplevel_decoration_v1.mode`.
+    Mode {
+        /// The client draws the decorations itself.
+        ClientSide = 1,
+        /// The compositor draws the decorations.
+        ServerSide = 2,
+    }
+}
+
+/// A bound `zxdg_decoration_manager_v1` global, which lets clients and the
+/// compositor agree on who draws window decorations.
+#[derive(Clone)]
+pub struct DecorationManager {
+    /// The object ID of the bound global.
+    id: u32,
+    /// The negotiated interface version.
+    version: u32,
+    /// The connection the global was bound on.
+    conn: Connection,
+}
+
+impl DecorationManager {
+    /// Binds the `zxdg_decoration_manager_v1` global advertised by the compositor.
+    pub fn bind(conn: &Connection) -> Result<DecorationManager> {
+        let (id, version) = conn.bind(
+            WlObjectId::DecorationManager,
+            ZXDG_DECORATION_MANAGER_VERSION,
+        )?;
+
+        Ok(DecorationManager {
+            id,
+            version,
+            conn: conn.clone(),
+        })
+    }
+
+    /// Returns the object ID of the global.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Creates the decoration object of `toplevel`.
+    ///
+    /// It must be created before a buffer is attached to the surface, i.e.
+    /// before the first configure is answered, and destroyed before the
+    /// toplevel.
+    pub fn get_toplevel_decoration(&self, toplevel: &XdgToplevel) -> Result<ToplevelDecoration> {
+        self.conn.check_same_connection(
+            toplevel.connection(),
+            WlObjectId::XdgToplevel,
+            toplevel.id(),
+        )?;
+
+        let id = self
+            .conn
+            .new_object(WlObjectId::ToplevelDecoration, self.version)?;
+        request::get_toplevel_decoration(
+            &self.conn,
+            self.id,
+            WlNewId(id),
+            WlObject(toplevel.id()),
+        )?;
+
+        let mode = Arc::new(LockCell::new(None));
+        let configured = mode.clone();
+        let listener: Listener<Configure> = Box::new(move |_, event| {
+            // Unknown modes are left to a newer protocol version.
+            if let Ok(mode) = Mode::try_from(event.mode.get()) {
+                configured.set(Some(mode));
+            }
+        });
+        self.conn.set_listener(id, listener);
+
+        Ok(ToplevelDecoration {
+            id,
+            conn: self.conn.clone(),
+            mode,
+        })
+    }
+
+    /// Destroys the manager; existing decoration objects keep working.
+    pub fn destroy(self) -> Result<()> {
+        request::destroy(&self.conn, self.id)
+    }
+}
+
+/// A `zxdg_toplevel_decoration_v1`, the decoration mode of a toplevel.
+pub struct ToplevelDecoration {
+    /// The object ID of the decoration object.
+    id: u32,
+    /// The connection the object was created on.
+    conn: Connection,
+    /// The mode configured by the compositor, shared with the listener.
+    mode: Arc<LockCell<Option<Mode>>>,
+}
+
+impl ToplevelDecoration {
+    /// Returns the object ID of the decoration object.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the mode the compositor configured, which the window must
+    /// follow from the `xdg_surface.configure` ending the sequence on.
+    ///
+    /// # Returns
+    /// `None` until the first configure.
+    pub fn mode(&self) -> Option<Mode> {
+        self.mode.get()
+    }
+
+    /// Tells the compositor which mode the client prefers; the compositor
+    /// answers with a configure, possibly of another mode.
+    pub fn set_mode(&self, mode: Mode) -> Result<()> {
+        request::set_mode(&self.conn, self.id, WlUInt(mode as u32))
+    }
+
+    /// Lets the compositor pick the mode.
+    pub fn unset_mode(&self) -> Result<()> {
+        request::unset_mode(&self.conn, self.id)
+    }
+
+    /// Destroys the decoration object, going back to client-side
+    /// decorations.
+    ///
+    /// Dropping the handle does the same.
+    pub fn destroy(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl Drop for ToplevelDecoration {
+    fn drop(&mut self) {
+        self.conn.remove_listener(self.id);
+
+        self.conn
+            .destroy_on_drop(|conn| request::destroy_decoration(conn, self.id));
+    }
+}
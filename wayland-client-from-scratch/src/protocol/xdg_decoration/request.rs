@@ -0,0 +1,152 @@
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::types::{WlNewId, WlObject, WlUInt},
+    wl_request_opcode, wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to the `zxdg_decoration_manager_v1` object.
+    Opcode {
+        /// Destroys the decoration manager.
+        Destroy = 0 (destructor),
+
+        /// Creates the decoration object of a toplevel.
+        GetToplevelDecoration = 1,
+    }
+}
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to the `zxdg_toplevel_decoration_v1` object.
+    DecorationOpcode {
+        /// Destroys the decoration object.
+        Destroy = 0 (destructor),
+
+        /// Sets the preferred decoration mode.
+        SetMode = 1,
+
+        /// Lets the compositor pick the decoration mode.
+        UnsetMode = 2,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `zxdg_decoration_manager_v1.get_toplevel_decoration` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="get_toplevel_decoration">
+    ///   <description summary="create a new toplevel decoration object">
+    ///     Create a new decoration object associated with the given toplevel.
+    ///
+    ///     Creating an xdg_toplevel_decoration from an xdg_toplevel which has a
+    ///     buffer attached or committed is a client error, and any attempts by a
+    ///     client to attach or manipulate a buffer prior to the first
+    ///     xdg_toplevel_decoration.configure event must also be treated as
+    ///     errors.
+    ///   </description>
+    ///   <arg name="id" type="new_id" interface="zxdg_toplevel_decoration_v1"/>
+    ///   <arg name="toplevel" type="object" interface="xdg_toplevel"/>
+    /// </request>
+    /// ```
+    GetToplevelDecorationParam {
+        /// The ID of the new decoration object.
+        id: WlNewId,
+        /// The toplevel the decoration object is for.
+        toplevel: WlObject,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `zxdg_toplevel_decoration_v1.set_mode` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_mode">
+    ///   <description summary="set the decoration mode">
+    ///     Set the toplevel surface decoration mode. This informs the compositor
+    ///     that the client prefers the provided decoration mode.
+    ///
+    ///     After requesting a decoration mode, the compositor will respond by
+    ///     emitting an xdg_surface.configure event. The client should then update
+    ///     its content, drawing it without decorations if the received mode is
+    ///     server-side decorations. The client must also acknowledge the configure
+    ///     when committing the new content (see xdg_surface.ack_configure).
+    ///
+    ///     The compositor can decide not to use the client's mode and enforce a
+    ///     different mode instead.
+    ///
+    ///     Clients whose decoration mode depend on the xdg_toplevel state may send
+    ///     a set_mode request in response to an xdg_surface.configure event and wait
+    ///     for the next xdg_surface.configure event to prevent unwanted state.
+    ///     Such clients are responsible for preventing configure loops and must
+    ///     make sure not to send multiple successive set_mode requests with the
+    ///     same decoration mode.
+    ///   </description>
+    ///   <arg name="mode" type="uint" enum="mode" summary="the decoration mode"/>
+    /// </request>
+    /// ```
+    SetModeParam {
+        /// The preferred mode, a [`Mode`](super::Mode) value.
+        mode: WlUInt,
+    }
+}
+
+/// Sends a `zxdg_decoration_manager_v1.destroy` request to the compositor.
+pub fn destroy(conn: &Connection, manager: u32) -> Result<()> {
+    conn.send_request(manager, Opcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends a `zxdg_decoration_manager_v1.get_toplevel_decoration` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `manager` - The ID of the bound decoration manager
+/// * `id` - The ID of the new decoration object
+/// * `toplevel` - The toplevel the decoration object is for
+pub fn get_toplevel_decoration(
+    conn: &Connection,
+    manager: u32,
+    id: WlNewId,
+    toplevel: WlObject,
+) -> Result<()> {
+    conn.send_request(
+        manager,
+        Opcode::GetToplevelDecoration,
+        &GetToplevelDecorationParam::new(id, toplevel),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `zxdg_toplevel_decoration_v1.destroy` request to the compositor.
+pub fn destroy_decoration(conn: &Connection, decoration: u32) -> Result<()> {
+    conn.send_request(decoration, DecorationOpcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends a `zxdg_toplevel_decoration_v1.set_mode` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `decoration` - The ID of the decoration object
+/// * `mode` - The preferred decoration mode
+pub fn set_mode(conn: &Connection, decoration: u32, mode: WlUInt) -> Result<()> {
+    conn.send_request(
+        decoration,
+        DecorationOpcode::SetMode,
+        &SetModeParam::new(mode),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `zxdg_toplevel_decoration_v1.unset_mode` request to the compositor.
+pub fn unset_mode(conn: &Connection, decoration: u32) -> Result<()> {
+    conn.send_request(decoration, DecorationOpcode::UnsetMode, &())?;
+
+    Ok(())
+}
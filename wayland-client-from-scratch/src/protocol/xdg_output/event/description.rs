@@ -0,0 +1,71 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{types::WlString, xdg_output::XdgOutputState},
+};
+
+/// Represents a `zxdg_output_v1.description` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="description" since="2">
+///   <description summary="human-readable description of this output">
+///     Many compositors can produce human-readable descriptions of their
+///     outputs. The client may wish to know this description as well, to
+///     communicate the user for various purposes.
+///
+///     The description is a UTF-8 string with no convention defined for its
+///     contents. Examples might include 'Foocorp 11" Display' or 'Virtual X11
+///     output via :1'.
+///
+///     The description event is sent after creating an xdg_output (see
+///     xdg_output_manager.get_xdg_output) and whenever the description
+///     changes. The description is optional, and may not be sent at all.
+///
+///     For objects of version 2 and lower, this event is only sent once per
+///     xdg_output, and the description does not change over the lifetime of
+///     the wl_output global.
+///
+///     This event is deprecated, instead clients should use
+///     wl_output.description. Compositors must still support this event.
+///   </description>
+///   <arg name="description" type="string" summary="output description"/>
+/// </event>
+/// ```
+#[derive(Debug)]
+pub struct Description {
+    /// The output description.
+    pub description: WlString,
+}
+
+impl TryFrom<&[u8]> for Description {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Description` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0+: `description` (WlString) - The description with length prefix
+    fn try_from(buf: &[u8]) -> Result<Description> {
+        Ok(Description {
+            description: buf.try_into()?,
+        })
+    }
+}
+
+/// Handles a `zxdg_output_v1.description` event by recording the
+/// description as pending.
+pub(super) fn handle_zxdg_output_description(
+    conn: &Connection,
+    xdg_output_id: u32,
+    event: Description,
+) -> Result<()> {
+    let description = conn.decode_string(&event.description)?;
+
+    if let Some(state) = conn.object_data::<XdgOutputState>(xdg_output_id) {
+        state.update(|info| {
+            info.description = Some(description);
+        });
+    }
+
+    Ok(())
+}
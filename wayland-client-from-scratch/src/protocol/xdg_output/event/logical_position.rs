@@ -0,0 +1,68 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        types::{WL_TYPE_INT_LEN, WlInt},
+        xdg_output::XdgOutputState,
+    },
+};
+
+/// Represents a `zxdg_output_v1.logical_position` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="logical_position">
+///   <description summary="position of the output within the global compositor space">
+///     The position event describes the location of the wl_output within
+///     the global compositor space.
+///
+///     The logical_position event is sent after creating an xdg_output
+///     (see xdg_output_manager.get_xdg_output) and whenever the location
+///     of the output changes within the global compositor space.
+///   </description>
+///   <arg name="x" type="int"
+///        summary="x position within the global compositor space"/>
+///   <arg name="y" type="int"
+///        summary="y position within the global compositor space"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogicalPosition {
+    /// The horizontal position in the global compositor space.
+    pub x: WlInt,
+    /// The vertical position in the global compositor space.
+    pub y: WlInt,
+}
+
+impl TryFrom<&[u8]> for LogicalPosition {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `LogicalPosition` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `x` (i32) - The horizontal position
+    /// - Bytes 4-7: `y` (i32) - The vertical position
+    fn try_from(buf: &[u8]) -> Result<LogicalPosition> {
+        Ok(LogicalPosition {
+            x: WlInt::read(buf, 0)?,
+            y: WlInt::read(buf, WL_TYPE_INT_LEN)?,
+        })
+    }
+}
+
+/// Handles a `zxdg_output_v1.logical_position` event by recording the
+/// position as pending.
+pub(super) fn handle_zxdg_output_logical_position(
+    conn: &Connection,
+    xdg_output_id: u32,
+    event: LogicalPosition,
+) -> Result<()> {
+    if let Some(state) = conn.object_data::<XdgOutputState>(xdg_output_id) {
+        state.update(|info| {
+            info.x = event.x.get();
+            info.y = event.y.get();
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        types::{WL_TYPE_INT_LEN, WlInt},
+        xdg_output::XdgOutputState,
+    },
+};
+
+/// Represents a `zxdg_output_v1.logical_size` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="logical_size">
+///   <description summary="size of the output in the global compositor space">
+///     The logical_size event describes the size of the output in the
+///     global compositor space.
+///
+///     Most regular Wayland clients should not pay attention to the
+///     logical size and would rather rely on xdg_shell interfaces.
+///
+///     Some clients such as Xwayland, however, need this to configure
+///     their surfaces in the global compositor space as the compositor
+///     may apply a different scale from what is advertised by the output
+///     scaling property (to achieve fractional scaling, for example).
+///
+///     For example, for a wl_output mode 3840×2160 and a scale factor 2:
+///
+///     - A compositor not scaling the monitor viewport in its compositing space
+///       will advertise a logical size of 3840×2160,
+///
+///     - A compositor scaling the monitor viewport with scale factor 2 will
+///       advertise a logical size of 1920×1080,
+///
+///     - A compositor scaling the monitor viewport using a fractional scale of
+///       1.5 will advertise a logical size of 2560×1440.
+///
+///     For example, for a wl_output mode 1920×1080 and a 90 degree rotation,
+///     the compositor will advertise a logical size of 1080x1920.
+///
+///     The logical_size event is sent after creating an xdg_output
+///     (see xdg_output_manager.get_xdg_output) and whenever the logical
+///     size of the output changes, either as a result of a change in the
+///     applied scale or because of a change in the corresponding output
+///     mode(see wl_output.mode) or transform (see wl_output.transform).
+///   </description>
+///   <arg name="width" type="int"
+///        summary="width in global compositor space"/>
+///   <arg name="height" type="int"
+///        summary="height in global compositor space"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogicalSize {
+    /// The width in the global compositor space.
+    pub width: WlInt,
+    /// The height in the global compositor space.
+    pub height: WlInt,
+}
+
+impl TryFrom<&[u8]> for LogicalSize {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `LogicalSize` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `width` (i32) - The logical width
+    /// - Bytes 4-7: `height` (i32) - The logical height
+    fn try_from(buf: &[u8]) -> Result<LogicalSize> {
+        Ok(LogicalSize {
+            width: WlInt::read(buf, 0)?,
+            height: WlInt::read(buf, WL_TYPE_INT_LEN)?,
+        })
+    }
+}
+
+/// Handles a `zxdg_output_v1.logical_size` event by recording the size as
+/// pending.
+pub(super) fn handle_zxdg_output_logical_size(
+    conn: &Connection,
+    xdg_output_id: u32,
+    event: LogicalSize,
+) -> Result<()> {
+    if let Some(state) = conn.object_data::<XdgOutputState>(xdg_output_id) {
+        state.update(|info| {
+            info.width = event.width.get();
+            info.height = event.height.get();
+        });
+    }
+
+    Ok(())
+}
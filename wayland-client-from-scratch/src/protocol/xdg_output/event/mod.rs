@@ -0,0 +1,146 @@
+pub mod description;
+pub mod logical_position;
+pub mod logical_size;
+pub mod name;
+
+use crate::{
+    connection::Connection,
+    error::{Error, Result, anyhow},
+    protocol::{message::WlMessage, xdg_output::XdgOutputState},
+};
+
+/// Represents the event types that can be emitted by a `zxdg_output_v1` object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    /// Describes the position of the output in the global compositor space.
+    ///
+    /// # Event Arguments
+    /// - `x`, `y`: The position in the global compositor space
+    LogicalPosition = 0,
+
+    /// Describes the size of the output in the global compositor space.
+    ///
+    /// # Event Arguments
+    /// - `width`, `height`: The size in the global compositor space
+    LogicalSize = 1,
+
+    /// Ends a batch of property changes (deprecated since version 3, where
+    /// `wl_output.done` ends the batch instead).
+    Done = 2,
+
+    /// Announces the name of the output (since version 2).
+    ///
+    /// # Event Arguments
+    /// - `name`: The output name
+    Name = 3,
+
+    /// Announces a human-readable description of the output (since version 2).
+    ///
+    /// # Event Arguments
+    /// - `description`: The output description
+    Description = 4,
+}
+
+impl TryFrom<u16> for Opcode {
+    type Error = Error;
+
+    /// Attempts to convert a raw opcode value into a structured `ZxdgOutputEvent`.
+    fn try_from(value: u16) -> Result<Opcode> {
+        match value {
+            0 => Ok(Opcode::LogicalPosition),
+            1 => Ok(Opcode::LogicalSize),
+            2 => Ok(Opcode::Done),
+            3 => Ok(Opcode::Name),
+            4 => Ok(Opcode::Description),
+            _ => Err(anyhow!("Invalid zxdg_output_v1 event opcode: {}", value)),
+        }
+    }
+}
+
+impl Opcode {
+    /// Returns the interface version the event was introduced in.
+    pub fn since(self) -> u32 {
+        match self {
+            Opcode::LogicalPosition | Opcode::LogicalSize | Opcode::Done => 1,
+            Opcode::Name | Opcode::Description => 2,
+        }
+    }
+}
+
+/// A decoded `zxdg_output_v1` event, with its arguments.
+#[derive(Debug)]
+pub enum Event {
+    /// Describes the position of the output in the global compositor space.
+    LogicalPosition(logical_position::LogicalPosition),
+    /// Describes the size of the output in the global compositor space.
+    LogicalSize(logical_size::LogicalSize),
+    /// Ends a batch of property changes (deprecated since version 3).
+    Done,
+    /// Announces the name of the output (since version 2).
+    Name(name::Name),
+    /// Announces a human-readable description of the output (since version 2).
+    Description(description::Description),
+}
+
+impl Event {
+    /// Decodes a `zxdg_output_v1` event from its message.
+    ///
+    /// # Errors
+    /// Returns an error if the opcode is unknown or the arguments are malformed.
+    pub fn decode(_conn: &Connection, msg: &WlMessage) -> Result<Event> {
+        let buf: &[u8] = &msg.data;
+
+        Ok(match Opcode::try_from(msg.header.opcode)? {
+            Opcode::LogicalPosition => {
+                Event::LogicalPosition(logical_position::LogicalPosition::try_from(buf)?)
+            }
+            Opcode::LogicalSize => Event::LogicalSize(logical_size::LogicalSize::try_from(buf)?),
+            Opcode::Done => Event::Done,
+            Opcode::Name => Event::Name(name::Name::try_from(buf)?),
+            Opcode::Description => Event::Description(description::Description::try_from(buf)?),
+        })
+    }
+
+    /// Returns the opcode of the event.
+    pub fn opcode(&self) -> Opcode {
+        match self {
+            Event::LogicalPosition(_) => Opcode::LogicalPosition,
+            Event::LogicalSize(_) => Opcode::LogicalSize,
+            Event::Done => Opcode::Done,
+            Event::Name(_) => Opcode::Name,
+            Event::Description(_) => Opcode::Description,
+        }
+    }
+}
+
+/// Dispatches incoming `zxdg_output_v1` events to their appropriate handler functions.
+///
+/// # Event Routing
+/// * `LogicalPosition` events are routed to `logical_position::handle_zxdg_output_logical_position`
+/// * `LogicalSize` events are routed to `logical_size::handle_zxdg_output_logical_size`
+/// * `Done` events apply the pending properties through `XdgOutputState::done`
+/// * `Name` events are routed to `name::handle_zxdg_output_name`
+/// * `Description` events are routed to `description::handle_zxdg_output_description`
+pub fn handle_zxdg_output_event(conn: &Connection, msg: WlMessage) -> Result<()> {
+    let xdg_output_id = msg.header.object_id;
+
+    // Route the event to the appropriate handler based on type
+    match Event::decode(conn, &msg)? {
+        Event::LogicalPosition(event) => {
+            logical_position::handle_zxdg_output_logical_position(conn, xdg_output_id, event)
+        }
+        Event::LogicalSize(event) => {
+            logical_size::handle_zxdg_output_logical_size(conn, xdg_output_id, event)
+        }
+        Event::Done => {
+            if let Some(state) = conn.object_data::<XdgOutputState>(xdg_output_id) {
+                state.done(conn, xdg_output_id);
+            }
+            Ok(())
+        }
+        Event::Name(event) => name::handle_zxdg_output_name(conn, xdg_output_id, event),
+        Event::Description(event) => {
+            description::handle_zxdg_output_description(conn, xdg_output_id, event)
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{types::WlString, xdg_output::XdgOutputState},
+};
+
+/// Represents a `zxdg_output_v1.name` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="name" since="2">
+///   <description summary="name of this output">
+///     Many compositors will assign names to their outputs, show them to the
+///     user, allow them to be configured by name, etc. The client may wish to
+///     know this name as well to offer the user similar behaviors.
+///
+///     The naming convention is compositor defined, but limited to
+///     alphanumeric characters and dashes (-). Each name is unique among all
+///     wl_output globals, but if a wl_output global is destroyed the same name
+///     may be reused later. The names will also remain consistent across
+///     sessions with the same hardware and software configuration.
+///
+///     Examples of names include 'HDMI-A-1', 'WL-1', 'X11-1', etc. However, do
+///     not assume that the name is a reflection of an underlying DRM
+///     connector, X11 connection, etc.
+///
+///     The name event is sent after creating an xdg_output (see
+///     xdg_output_manager.get_xdg_output). This event is only sent once per
+///     xdg_output, and the name does not change over the lifetime of the
+///     wl_output global.
+///
+///     This event is deprecated, instead clients should use wl_output.name.
+///     Compositors must still support this event.
+///   </description>
+///   <arg name="name" type="string" summary="output name"/>
+/// </event>
+/// ```
+#[derive(Debug)]
+pub struct Name {
+    /// The output name.
+    pub name: WlString,
+}
+
+impl TryFrom<&[u8]> for Name {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Name` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0+: `name` (WlString) - The output name with length prefix
+    fn try_from(buf: &[u8]) -> Result<Name> {
+        Ok(Name {
+            name: buf.try_into()?,
+        })
+    }
+}
+
+/// Handles a `zxdg_output_v1.name` event by recording the name as pending.
+pub(super) fn handle_zxdg_output_name(
+    conn: &Connection,
+    xdg_output_id: u32,
+    event: Name,
+) -> Result<()> {
+    let name = conn.decode_string(&event.name)?;
+
+    if let Some(state) = conn.object_data::<XdgOutputState>(xdg_output_id) {
+        state.update(|info| {
+            info.name = Some(name);
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,212 @@
+pub mod event;
+pub mod request;
+
+use std::sync::Arc;
+
+use crate::{
+    connection::{Connection, Listener},
+    error::Result,
+    protocol::{
+        WlObjectId,
+        output::Output,
+        types::{WlNewId, WlObject},
+    },
+    sync::Lock,
+};
+
+/// The highest `zxdg_output_manager_v1` version this crate implements.
+pub const ZXDG_OUTPUT_MANAGER_VERSION: u32 = 3;
+
+/// An event delivered to the handler of an [`XdgOutput`].
+pub enum XdgOutputEvent {
+    /// A batch of property changes was applied.
+    Done(XdgOutputInfo),
+}
+
+/// The properties of an output in the global compositor space, as
+/// announced by `zxdg_output_v1`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct XdgOutputInfo {
+    /// The horizontal position in the global compositor space.
+    pub x: i32,
+    /// The vertical position in the global compositor space.
+    pub y: i32,
+    /// The width in the global compositor space, 0 until announced.
+    pub width: i32,
+    /// The height in the global compositor space, 0 until announced.
+    pub height: i32,
+    /// The name of the output, such as `DP-1` (since version 2).
+    pub name: Option<String>,
+    /// A human-readable description (since version 2).
+    pub description: Option<String>,
+}
+
+/// State of an xdg output updated by its events.
+pub(crate) struct XdgOutputState {
+    /// The object ID of the `wl_output` described.
+    output: u32,
+    /// The interface version the xdg output was created with.
+    version: u32,
+    /// The properties changed since the last batch was applied.
+    pending: Lock<XdgOutputInfo>,
+    /// The properties applied by the last batch.
+    current: Lock<XdgOutputInfo>,
+}
+
+impl XdgOutputState {
+    /// Changes the pending properties, applied by the next `done` event.
+    pub(crate) fn update(&self, f: impl FnOnce(&mut XdgOutputInfo)) {
+        f(&mut self.pending.borrow_mut());
+    }
+
+    /// Applies the pending properties and notifies the listener.
+    pub(crate) fn done(&self, conn: &Connection, xdg_output_id: u32) {
+        let info = self.pending.borrow().clone();
+        *self.current.borrow_mut() = info.clone();
+
+        conn.emit(xdg_output_id, XdgOutputEvent::Done(info));
+    }
+
+    /// Returns the object ID of the `wl_output` described.
+    pub(crate) fn output(&self) -> u32 {
+        self.output
+    }
+
+    /// Returns the properties applied by the last batch.
+    pub(crate) fn info(&self) -> XdgOutputInfo {
+        self.current.borrow().clone()
+    }
+}
+
+/// Applies the pending properties of the version 3 xdg outputs of the
+/// output `output_id`, on its `wl_output.done` event.
+///
+/// Since version 3, `zxdg_output_v1.done` is no longer sent and the
+/// changes are applied along with the ones of the `wl_output`.
+pub(crate) fn output_done(conn: &Connection, output_id: u32) {
+    for xdg_output_id in conn.objects_of(WlObjectId::XdgOutput) {
+        let Some(state) = conn.object_data::<XdgOutputState>(xdg_output_id) else {
+            continue;
+        };
+        if state.output == output_id && state.version >= 3 {
+            state.done(conn, xdg_output_id);
+        }
+    }
+}
+
+/// A bound `zxdg_output_manager_v1` global, which describes outputs in the
+/// global compositor space.
+#[derive(Clone)]
+pub struct XdgOutputManager {
+    /// The object ID of the bound manager.
+    id: u32,
+    /// The negotiated interface version.
+    version: u32,
+    /// The connection the manager was bound on.
+    conn: Connection,
+}
+
+impl XdgOutputManager {
+    /// Binds the `zxdg_output_manager_v1` global advertised by the compositor.
+    pub fn bind(conn: &Connection) -> Result<XdgOutputManager> {
+        let (id, version) = conn.bind(WlObjectId::XdgOutputManager, ZXDG_OUTPUT_MANAGER_VERSION)?;
+
+        Ok(XdgOutputManager {
+            id,
+            version,
+            conn: conn.clone(),
+        })
+    }
+
+    /// Returns the object ID of the manager.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Creates the xdg output describing `output`.
+    ///
+    /// The properties are known after the next roundtrip.
+    pub fn get_xdg_output(&self, output: &Output) -> Result<XdgOutput> {
+        self.conn
+            .check_same_connection(output.connection(), WlObjectId::Output, output.id())?;
+
+        let id = self.conn.new_object(WlObjectId::XdgOutput, self.version)?;
+        request::get_xdg_output(&self.conn, self.id, WlNewId(id), WlObject(output.id()))?;
+
+        let state = Arc::new(XdgOutputState {
+            output: output.id(),
+            version: self.version,
+            pending: Lock::new(XdgOutputInfo::default()),
+            current: Lock::new(XdgOutputInfo::default()),
+        });
+        self.conn.set_object_data(id, state.clone());
+
+        Ok(XdgOutput {
+            id,
+            conn: self.conn.clone(),
+            state,
+        })
+    }
+
+    /// Destroys the manager; existing xdg outputs keep working.
+    pub fn destroy(self) -> Result<()> {
+        request::destroy(&self.conn, self.id)
+    }
+}
+
+/// A `zxdg_output_v1`, describing the logical position and size of an
+/// output, i.e. its area in the global compositor space after scaling and
+/// transforming.
+pub struct XdgOutput {
+    /// The object ID of the xdg output.
+    id: u32,
+    /// The connection the xdg output was created on.
+    conn: Connection,
+    /// The state shared with the event handlers.
+    state: Arc<XdgOutputState>,
+}
+
+impl XdgOutput {
+    /// Returns the object ID of the xdg output.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the object ID of the `wl_output` described.
+    pub fn output(&self) -> u32 {
+        self.state.output()
+    }
+
+    /// Returns the properties of the output in the global compositor space.
+    pub fn info(&self) -> XdgOutputInfo {
+        self.state.info()
+    }
+
+    /// Registers the handler receiving the events of the xdg output,
+    /// replacing any previous one.
+    pub fn set_event_handler<F>(&self, handler: F)
+    where
+        F: FnMut(&Connection, XdgOutputEvent) + Send + 'static,
+    {
+        let listener: Listener<XdgOutputEvent> = Box::new(handler);
+        self.conn.set_listener(self.id, listener);
+    }
+
+    /// Destroys the xdg output.
+    ///
+    /// Dropping the handle does the same.
+    pub fn destroy(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl Drop for XdgOutput {
+    fn drop(&mut self) {
+        self.conn.remove_listener(self.id);
+
+        self.conn
+            .destroy_on_drop(|conn| request::destroy_xdg_output(conn, self.id));
+    }
+}
@@ -0,0 +1,82 @@
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::types::{WlNewId, WlObject},
+    wl_request_opcode, wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to the `zxdg_output_manager_v1` object.
+    Opcode {
+        /// Destroys the manager; existing xdg outputs keep working.
+        Destroy = 0 (destructor),
+
+        /// Creates the xdg output of an output.
+        GetXdgOutput = 1,
+    }
+}
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to a `zxdg_output_v1` object.
+    XdgOutputOpcode {
+        /// Destroys the xdg output.
+        Destroy = 0 (destructor),
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `zxdg_output_manager_v1.get_xdg_output` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="get_xdg_output">
+    ///   <description summary="create an xdg output from a wl_output">
+    ///     This creates a new xdg_output object for the given wl_output.
+    ///   </description>
+    ///   <arg name="id" type="new_id" interface="zxdg_output_v1"/>
+    ///   <arg name="output" type="object" interface="wl_output"/>
+    /// </request>
+    /// ```
+    GetXdgOutputParam {
+        /// The object ID to assign to the new xdg output.
+        id: WlNewId,
+        /// The output to describe.
+        output: WlObject,
+    }
+}
+
+/// Sends a `zxdg_output_manager_v1.destroy` request to the compositor.
+pub fn destroy(conn: &Connection, manager: u32) -> Result<()> {
+    conn.send_request(manager, Opcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends a `zxdg_output_manager_v1.get_xdg_output` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `manager` - The ID of the bound xdg output manager
+/// * `id` - The object ID to assign to the new xdg output
+/// * `output` - The output to describe
+pub fn get_xdg_output(
+    conn: &Connection,
+    manager: u32,
+    id: WlNewId,
+    output: WlObject,
+) -> Result<()> {
+    conn.send_request(
+        manager,
+        Opcode::GetXdgOutput,
+        &GetXdgOutputParam::new(id, output),
+    )?;
+
+    Ok(())
+}
+
+/// Sends a `zxdg_output_v1.destroy` request to the compositor.
+pub fn destroy_xdg_output(conn: &Connection, xdg_output: u32) -> Result<()> {
+    conn.send_request(xdg_output, XdgOutputOpcode::Destroy, &())?;
+
+    Ok(())
+}
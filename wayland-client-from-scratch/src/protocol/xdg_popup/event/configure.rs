@@ -0,0 +1,89 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    geometry::Rect,
+    protocol::{
+        types::{WL_TYPE_INT_LEN, WlInt},
+        xdg_popup::{PopupEvent, PopupState},
+    },
+};
+
+/// Represents an `xdg_popup.configure` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="configure">
+///   <description summary="configure the popup surface">
+///     This event asks the popup surface to configure itself given the
+///     configuration. The configured state should not be applied immediately.
+///     See xdg_surface.configure for details.
+///
+///     The x and y arguments represent the position the popup was placed at
+///     given the xdg_positioner rule, relative to the upper left corner of the
+///     window geometry of the parent surface.
+///
+///     For version 2 or older, the configure event for an xdg_popup is only
+///     ever sent once for the initial configuration. Starting with version 3,
+///     it may be sent again if the popup is setup with an xdg_positioner with
+///     set_reactive requested, or in response to xdg_popup.reposition requests.
+///   </description>
+///   <arg name="x" type="int"
+///        summary="x position relative to parent surface window geometry"/>
+///   <arg name="y" type="int"
+///        summary="y position relative to parent surface window geometry"/>
+///   <arg name="width" type="int" summary="window geometry width"/>
+///   <arg name="height" type="int" summary="window geometry height"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Configure {
+    /// The horizontal position relative to the window geometry of the parent.
+    pub x: WlInt,
+    /// The vertical position relative to the window geometry of the parent.
+    pub y: WlInt,
+    /// The width of the window geometry of the popup.
+    pub width: WlInt,
+    /// The height of the window geometry of the popup.
+    pub height: WlInt,
+}
+
+impl TryFrom<&[u8]> for Configure {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Configure` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `x` (i32) - The horizontal position
+    /// - Bytes 4-7: `y` (i32) - The vertical position
+    /// - Bytes 8-11: `width` (i32) - The width
+    /// - Bytes 12-15: `height` (i32) - The height
+    fn try_from(buf: &[u8]) -> Result<Configure> {
+        Ok(Configure {
+            x: WlInt::read(buf, 0)?,
+            y: WlInt::read(buf, WL_TYPE_INT_LEN)?,
+            width: WlInt::read(buf, 2 * WL_TYPE_INT_LEN)?,
+            height: WlInt::read(buf, 3 * WL_TYPE_INT_LEN)?,
+        })
+    }
+}
+
+/// Handles an `xdg_popup.configure` event by recording the placement in the
+/// configure sequence being built before notifying the listener.
+pub(super) fn handle_xdg_popup_configure(
+    conn: &Connection,
+    popup_id: u32,
+    configure: Configure,
+) -> Result<()> {
+    if let Some(state) = conn.object_data::<PopupState>(popup_id) {
+        state.building.borrow_mut().rect = Rect::new(
+            configure.x.get(),
+            configure.y.get(),
+            configure.width.get(),
+            configure.height.get(),
+        );
+    }
+
+    conn.emit(popup_id, PopupEvent::Configure(configure));
+
+    Ok(())
+}
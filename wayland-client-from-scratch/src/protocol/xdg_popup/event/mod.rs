@@ -0,0 +1,110 @@
+pub mod configure;
+pub mod repositioned;
+
+use crate::{
+    connection::Connection,
+    error::{Error, Result, anyhow},
+    protocol::{message::WlMessage, xdg_popup::PopupEvent},
+};
+
+/// Represents the event types that can be emitted by an `xdg_popup` object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    /// Sets the position and size of the popup relative to its parent.
+    ///
+    /// # Event Arguments
+    /// - `x`, `y`: The position relative to the window geometry of the parent
+    /// - `width`, `height`: The size of the window geometry of the popup
+    Configure = 0,
+
+    /// The compositor dismissed the popup.
+    PopupDone = 1,
+
+    /// The popup was moved in answer to a `reposition` request (since version 3).
+    ///
+    /// # Event Arguments
+    /// - `token`: The token of the `reposition` request
+    Repositioned = 2,
+}
+
+impl TryFrom<u16> for Opcode {
+    type Error = Error;
+
+    /// Attempts to convert a raw opcode value into a structured `XdgPopupEvent`.
+    fn try_from(value: u16) -> Result<Opcode> {
+        match value {
+            0 => Ok(Opcode::Configure),
+            1 => Ok(Opcode::PopupDone),
+            2 => Ok(Opcode::Repositioned),
+            _ => Err(anyhow!("Invalid xdg_popup event opcode: {}", value)),
+        }
+    }
+}
+
+impl Opcode {
+    /// Returns the interface version the event was introduced in.
+    pub fn since(self) -> u32 {
+        match self {
+            Opcode::Configure | Opcode::PopupDone => 1,
+            Opcode::Repositioned => 3,
+        }
+    }
+}
+
+/// A decoded `xdg_popup` event, with its arguments.
+#[derive(Debug)]
+pub enum Event {
+    /// Sets the position and size of the popup relative to its parent.
+    Configure(configure::Configure),
+    /// The compositor dismissed the popup.
+    PopupDone,
+    /// The popup was moved in answer to a `reposition` request (since version 3).
+    Repositioned(repositioned::Repositioned),
+}
+
+impl Event {
+    /// Decodes a `xdg_popup` event from its message.
+    ///
+    /// # Errors
+    /// Returns an error if the opcode is unknown or the arguments are malformed.
+    pub fn decode(_conn: &Connection, msg: &WlMessage) -> Result<Event> {
+        let buf: &[u8] = &msg.data;
+
+        Ok(match Opcode::try_from(msg.header.opcode)? {
+            Opcode::Configure => Event::Configure(configure::Configure::try_from(buf)?),
+            Opcode::PopupDone => Event::PopupDone,
+            Opcode::Repositioned => Event::Repositioned(repositioned::Repositioned::try_from(buf)?),
+        })
+    }
+
+    /// Returns the opcode of the event.
+    pub fn opcode(&self) -> Opcode {
+        match self {
+            Event::Configure(_) => Opcode::Configure,
+            Event::PopupDone => Opcode::PopupDone,
+            Event::Repositioned(_) => Opcode::Repositioned,
+        }
+    }
+}
+
+/// Dispatches incoming `xdg_popup` events to their appropriate handler functions.
+///
+/// # Event Routing
+/// * `Configure` events are routed to `configure::handle_xdg_popup_configure`
+/// * `PopupDone` events are forwarded to the listener directly
+/// * `Repositioned` events are routed to `repositioned::handle_xdg_popup_repositioned`
+pub fn handle_xdg_popup_event(conn: &Connection, msg: WlMessage) -> Result<()> {
+    let popup_id = msg.header.object_id;
+
+    // Route the event to the appropriate handler based on type
+    match Event::decode(conn, &msg)? {
+        Event::Configure(event) => configure::handle_xdg_popup_configure(conn, popup_id, event),
+        Event::PopupDone => {
+            conn.emit(popup_id, PopupEvent::Done);
+            Ok(())
+        }
+        Event::Repositioned(event) => {
+            repositioned::handle_xdg_popup_repositioned(conn, popup_id, event)
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{types::WlUInt, xdg_popup::PopupEvent},
+};
+
+/// Represents an `xdg_popup.repositioned` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="repositioned" since="3">
+///   <description summary="signal the completion of a repositioned request">
+///     The repositioned event is sent as part of a popup configuration
+///     sequence, together with xdg_popup.configure and lastly
+///     xdg_surface.configure to notify the completion of a reposition request.
+///
+///     The repositioned event is to notify about the completion of a
+///     xdg_popup.reposition request. The token argument is the token passed
+///     in the xdg_popup.reposition request.
+///
+///     Immediately after this event is emitted, xdg_popup.configure and
+///     xdg_surface.configure will be sent with the updated size and position,
+///     as well as a new configure serial.
+///
+///     The client should optionally update the content of the popup, but must
+///     acknowledge the new popup configuration for the new position to take
+///     effect. See xdg_surface.ack_configure for details.
+///   </description>
+///   <arg name="token" type="uint" summary="reposition request token"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Repositioned {
+    /// The token of the completed `reposition` request.
+    pub token: WlUInt,
+}
+
+impl TryFrom<&[u8]> for Repositioned {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Repositioned` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `token` (u32) - The token of the reposition request
+    fn try_from(buf: &[u8]) -> Result<Repositioned> {
+        Ok(Repositioned {
+            token: WlUInt::read(buf, 0)?,
+        })
+    }
+}
+
+/// Handles an `xdg_popup.repositioned` event by notifying the listener.
+pub(super) fn handle_xdg_popup_repositioned(
+    conn: &Connection,
+    popup_id: u32,
+    repositioned: Repositioned,
+) -> Result<()> {
+    conn.emit(popup_id, PopupEvent::Repositioned(repositioned));
+
+    Ok(())
+}
@@ -0,0 +1,181 @@
+pub mod event;
+pub mod request;
+
+use std::sync::Arc;
+
+use crate::{
+    connection::{Connection, Listener},
+    error::Result,
+    geometry::Rect,
+    protocol::{
+        seat::Seat,
+        types::{WlObject, WlUInt},
+        xdg_positioner::XdgPositioner,
+        xdg_surface::XdgSurfaceState,
+    },
+    sync::Lock,
+};
+
+use event::{configure::Configure, repositioned::Repositioned};
+
+/// An event delivered to the handler of an [`XdgPopup`].
+pub enum PopupEvent {
+    /// The compositor placed the popup.
+    ///
+    /// Applies once the `xdg_surface.configure` ending the sequence arrives.
+    Configure(Configure),
+    /// The compositor dismissed the popup, which should be destroyed.
+    Done,
+    /// The popup was moved in answer to [`XdgPopup::reposition`].
+    Repositioned(Repositioned),
+}
+
+/// The placement requested by one configure sequence of a popup, i.e. the
+/// popup events followed by `xdg_surface.configure`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PopupConfigure {
+    /// The serial of the `xdg_surface.configure` that ended the sequence.
+    pub serial: u32,
+    /// The position relative to the window geometry of the parent, and the
+    /// size of the window geometry of the popup.
+    pub rect: Rect,
+}
+
+/// State of a popup updated by its events.
+#[derive(Default)]
+pub(crate) struct PopupState {
+    /// The configuration accumulated from the popup events of the current
+    /// configure sequence.
+    building: Lock<PopupConfigure>,
+    /// The latest completed configuration not taken by the application yet.
+    ready: Lock<Option<PopupConfigure>>,
+}
+
+impl PopupState {
+    /// Completes the current configure sequence, replacing a completed
+    /// configuration the application did not take yet.
+    pub(crate) fn finish_configure(&self, serial: u32) {
+        let mut configure = *self.building.borrow();
+        configure.serial = serial;
+        *self.ready.borrow_mut() = Some(configure);
+    }
+}
+
+/// An `xdg_popup`, a short-lived surface such as a menu, placed relative to
+/// a parent surface.
+///
+/// Created with
+/// [`XdgSurface::get_popup`](crate::protocol::xdg_surface::XdgSurface::get_popup).
+pub struct XdgPopup {
+    /// The object ID of the popup.
+    id: u32,
+    /// The interface version, inherited from `xdg_wm_base`.
+    version: u32,
+    /// The connection the popup was created on.
+    conn: Connection,
+    /// The state shared with the event handlers.
+    state: Arc<PopupState>,
+    /// The state of the xdg_surface the popup was created from.
+    xdg_surface: Arc<XdgSurfaceState>,
+}
+
+impl XdgPopup {
+    /// Wraps a popup object that was just created on `conn`.
+    pub(crate) fn new(
+        id: u32,
+        version: u32,
+        conn: &Connection,
+        xdg_surface: Arc<XdgSurfaceState>,
+        handler: Listener<PopupEvent>,
+    ) -> XdgPopup {
+        let state = Arc::new(PopupState::default());
+        conn.set_object_data(id, state.clone());
+        conn.set_listener(id, handler);
+
+        XdgPopup {
+            id,
+            version,
+            conn: conn.clone(),
+            state,
+            xdg_surface,
+        }
+    }
+
+    /// Returns the state shared with the event handlers.
+    pub(crate) fn state(&self) -> Arc<PopupState> {
+        self.state.clone()
+    }
+
+    /// Returns the object ID of the popup.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the negotiated interface version.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Returns the latest placement requested by the compositor that was not
+    /// taken yet, without taking it.
+    pub fn pending_configure(&self) -> Option<PopupConfigure> {
+        *self.state.ready.borrow()
+    }
+
+    /// Takes the latest placement requested by the compositor.
+    ///
+    /// Its serial is acknowledged automatically on the next
+    /// [`Surface::commit`](crate::protocol::surface::Surface::commit).
+    pub fn take_configure(&self) -> Option<PopupConfigure> {
+        let configure = self.state.ready.take()?;
+        self.xdg_surface.ack_on_commit(configure.serial);
+
+        Some(configure)
+    }
+
+    /// Makes the popup take an explicit input grab, so it is dismissed when
+    /// the user clicks outside of it.
+    ///
+    /// Must be called before the initial commit of the popup, with the
+    /// serial of the input event that opened it.
+    pub fn grab(&self, seat: &Seat, serial: u32) -> Result<()> {
+        request::grab(&self.conn, self.id, WlObject(seat.id()), WlUInt(serial))
+    }
+
+    /// Moves the mapped popup according to the rules of `positioner`.
+    ///
+    /// A [`PopupEvent::Repositioned`] carrying `token` starts the configure
+    /// sequence of the new placement. Ignored by compositors older than
+    /// version 3.
+    pub fn reposition(&self, positioner: &XdgPositioner, token: u32) -> Result<()> {
+        if self.version < 3 {
+            return Ok(());
+        }
+
+        request::reposition(
+            &self.conn,
+            self.id,
+            WlObject(positioner.id()),
+            WlUInt(token),
+        )
+    }
+
+    /// Destroys the popup, unmapping it. Popups opened from it must be
+    /// destroyed first.
+    ///
+    /// Dropping the handle does the same.
+    pub fn destroy(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl Drop for XdgPopup {
+    fn drop(&mut self) {
+        self.conn.remove_listener(self.id);
+
+        self.conn
+            .destroy_on_drop(|conn| request::destroy(conn, self.id));
+    }
+}
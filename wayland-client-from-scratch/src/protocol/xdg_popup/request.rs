@@ -0,0 +1,133 @@
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::types::{WlObject, WlUInt},
+    wl_request_opcode, wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to an `xdg_popup` object.
+    Opcode {
+        /// Destroys the popup, unmapping it.
+        Destroy = 0 (destructor),
+
+        /// Makes the popup take an explicit input grab.
+        Grab = 1,
+
+        /// Moves the popup according to a new positioner (since version 3).
+        Reposition = 2,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `xdg_popup.grab` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="grab">
+    ///   <description summary="make the popup take an explicit grab">
+    ///     This request makes the created popup take an explicit grab. An explicit
+    ///     grab will be dismissed when the user dismisses the popup, or when the
+    ///     client destroys the xdg_popup. This can be done by the user clicking
+    ///     outside the surface, using the keyboard, or even locking the screen
+    ///     through closing the lid or a timeout.
+    ///
+    ///     If the compositor denies the grab, the popup will be immediately
+    ///     dismissed.
+    ///
+    ///     This request must be used in response to some sort of user action like a
+    ///     button press, key press, or touch down event. The serial number of the
+    ///     event should be passed as 'serial'.
+    ///
+    ///     The parent of a grabbing popup must either be an xdg_toplevel surface or
+    ///     another xdg_popup with an explicit grab. If the parent is another
+    ///     xdg_popup it means that the popups are nested, with this popup now being
+    ///     the topmost popup.
+    ///
+    ///     This request must be sent before the initial commit of the popup.
+    ///   </description>
+    ///   <arg name="seat" type="object" interface="wl_seat"
+    ///        summary="the wl_seat of the user event"/>
+    ///   <arg name="serial" type="uint" summary="the serial of the user event"/>
+    /// </request>
+    /// ```
+    GrabParam {
+        /// The seat of the user event.
+        seat: WlObject,
+        /// The serial of the user event.
+        serial: WlUInt,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `xdg_popup.reposition` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="reposition" since="3">
+    ///   <description summary="recalculate the popup's location">
+    ///     Reposition an already-mapped popup. The popup will be placed given the
+    ///     details in the passed xdg_positioner object, and a
+    ///     xdg_popup.repositioned followed by xdg_popup.configure and
+    ///     xdg_surface.configure will be emitted in response. Any parameters set
+    ///     by the previous positioner will be discarded.
+    ///
+    ///     The passed token will be sent in the corresponding
+    ///     xdg_popup.repositioned event. The new popup position will not take
+    ///     effect until the corresponding configure event is acknowledged by the
+    ///     client. See xdg_popup.repositioned for details. The token itself is
+    ///     opaque, and has no other special meaning.
+    ///   </description>
+    ///   <arg name="positioner" type="object" interface="xdg_positioner"/>
+    ///   <arg name="token" type="uint" summary="reposition request token"/>
+    /// </request>
+    /// ```
+    RepositionParam {
+        /// The positioner with the new placement rules.
+        positioner: WlObject,
+        /// The token echoed by the `repositioned` event.
+        token: WlUInt,
+    }
+}
+
+/// Sends an `xdg_popup.destroy` request to the compositor.
+pub fn destroy(conn: &Connection, popup: u32) -> Result<()> {
+    conn.send_request(popup, Opcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends an `xdg_popup.grab` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `popup` - The ID of the popup
+/// * `seat` - The seat of the user event
+/// * `serial` - The serial of the user event
+pub fn grab(conn: &Connection, popup: u32, seat: WlObject, serial: WlUInt) -> Result<()> {
+    conn.send_request(popup, Opcode::Grab, &GrabParam::new(seat, serial))?;
+
+    Ok(())
+}
+
+/// Sends an `xdg_popup.reposition` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `popup` - The ID of the popup
+/// * `positioner` - The positioner with the new placement rules
+/// * `token` - The token echoed by the `repositioned` event
+pub fn reposition(
+    conn: &Connection,
+    popup: u32,
+    positioner: WlObject,
+    token: WlUInt,
+) -> Result<()> {
+    conn.send_request(
+        popup,
+        Opcode::Reposition,
+        &RepositionParam::new(positioner, token),
+    )?;
+
+    Ok(())
+}
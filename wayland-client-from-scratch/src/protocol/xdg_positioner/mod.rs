@@ -0,0 +1,150 @@
+pub mod request;
+
+use crate::{
+    connection::Connection,
+    error::{Result, anyhow},
+    geometry::Rect,
+    protocol::types::{WlInt, WlUInt},
+};
+
+pub use request::{Anchor, ConstraintAdjustment, Gravity};
+
+use request::Opcode;
+
+/// An `xdg_positioner`, the placement rules of a popup relative to its parent.
+///
+/// Created with
+/// [`XdgWmBase::create_positioner`](crate::protocol::xdg_wm_base::XdgWmBase::create_positioner).
+/// The rules are copied when a popup is created, so the positioner can be
+/// destroyed or reused right after.
+pub struct XdgPositioner {
+    /// The object ID of the positioner.
+    id: u32,
+    /// The interface version, inherited from `xdg_wm_base`.
+    version: u32,
+    /// The connection the positioner was created on.
+    conn: Connection,
+}
+
+impl XdgPositioner {
+    /// Wraps a positioner object that was just created on `conn`.
+    pub(crate) fn new(id: u32, version: u32, conn: &Connection) -> XdgPositioner {
+        XdgPositioner {
+            id,
+            version,
+            conn: conn.clone(),
+        }
+    }
+
+    /// Returns the object ID of the positioner.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Sets the size of the positioned surface, in window geometry coordinates.
+    ///
+    /// # Errors
+    /// Returns an error if the size is empty.
+    pub fn set_size(&self, width: i32, height: i32) -> Result<()> {
+        if width <= 0 || height <= 0 {
+            return Err(anyhow!(
+                "xdg_positioner {}: size must not be empty, got {width}x{height}",
+                self.id
+            ));
+        }
+
+        request::set_size(&self.conn, self.id, WlInt(width), WlInt(height))
+    }
+
+    /// Sets the rectangle of the parent the surface is placed relative to,
+    /// in the window geometry coordinates of the parent.
+    ///
+    /// # Errors
+    /// Returns an error if the rectangle has a negative size.
+    pub fn set_anchor_rect(&self, rect: Rect) -> Result<()> {
+        if rect.width < 0 || rect.height < 0 {
+            return Err(anyhow!(
+                "xdg_positioner {}: anchor rectangle must not have a negative size, got {}x{}",
+                self.id,
+                rect.width,
+                rect.height
+            ));
+        }
+
+        request::set_anchor_rect(
+            &self.conn,
+            self.id,
+            WlInt(rect.x),
+            WlInt(rect.y),
+            WlInt(rect.width),
+            WlInt(rect.height),
+        )
+    }
+
+    /// Sets the point of the anchor rectangle the surface is placed at.
+    pub fn set_anchor(&self, anchor: Anchor) -> Result<()> {
+        request::set_enum(
+            &self.conn,
+            self.id,
+            Opcode::SetAnchor,
+            WlUInt(anchor as u32),
+        )
+    }
+
+    /// Sets the direction the surface extends in from the anchor point.
+    pub fn set_gravity(&self, gravity: Gravity) -> Result<()> {
+        request::set_enum(
+            &self.conn,
+            self.id,
+            Opcode::SetGravity,
+            WlUInt(gravity as u32),
+        )
+    }
+
+    /// Sets the ways the compositor may adjust the position to keep the
+    /// surface on screen. An empty slice forbids any adjustment.
+    pub fn set_constraint_adjustment(&self, adjustments: &[ConstraintAdjustment]) -> Result<()> {
+        let mask = adjustments
+            .iter()
+            .fold(0, |mask, &adjustment| mask | adjustment as u32);
+
+        request::set_enum(
+            &self.conn,
+            self.id,
+            Opcode::SetConstraintAdjustment,
+            WlUInt(mask),
+        )
+    }
+
+    /// Sets the offset of the surface from the anchor point.
+    pub fn set_offset(&self, x: i32, y: i32) -> Result<()> {
+        request::set_offset(&self.conn, self.id, WlInt(x), WlInt(y))
+    }
+
+    /// Makes popups using the positioner follow changes of their parent.
+    ///
+    /// Ignored by compositors older than version 3.
+    pub fn set_reactive(&self) -> Result<()> {
+        if self.version < 3 {
+            return Ok(());
+        }
+
+        request::set_reactive(&self.conn, self.id)
+    }
+
+    /// Destroys the positioner. Popups created with it are not affected.
+    ///
+    /// Dropping the handle does the same.
+    pub fn destroy(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl Drop for XdgPositioner {
+    fn drop(&mut self) {
+        self.conn
+            .destroy_on_drop(|conn| request::destroy(conn, self.id));
+    }
+}
@@ -0,0 +1,311 @@
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::types::{WlInt, WlUInt},
+    wl_enum, wl_request_opcode, wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to an `xdg_positioner` object.
+    Opcode {
+        /// Destroys the `xdg_positioner` object.
+        Destroy = 0 (destructor),
+
+        /// Sets the size of the surface to position.
+        SetSize = 1,
+
+        /// Sets the rectangle of the parent the surface is anchored to.
+        SetAnchorRect = 2,
+
+        /// Sets the edge or corner of the anchor rectangle to anchor to.
+        SetAnchor = 3,
+
+        /// Sets the direction the surface extends in from the anchor point.
+        SetGravity = 4,
+
+        /// Sets how the position is adjusted when the surface would be constrained.
+        SetConstraintAdjustment = 5,
+
+        /// Sets the offset of the surface from the anchor point.
+        SetOffset = 6,
+
+        /// Makes the position follow changes of the parent (since version 3).
+        SetReactive = 7,
+
+        /// Sets the parent size the position is computed for (since version 3).
+        SetParentSize = 8,
+
+        /// Sets the parent configure the position is computed for (since version 3).
+        SetParentConfigure = 9,
+    }
+}
+
+wl_enum! {
+    /// An edge or corner of the anchor rectangle, see `xdg_positioner.set_anchor`.
+    Anchor {
+        /// The center of the anchor rectangle.
+        None = 0,
+        /// The middle of the top edge.
+        Top = 1,
+        /// The middle of the bottom edge.
+        Bottom = 2,
+        /// The middle of the left edge.
+        Left = 3,
+        /// The middle of the right edge.
+        Right = 4,
+        /// The top left corner.
+        TopLeft = 5,
+        /// The bottom left corner.
+        BottomLeft = 6,
+        /// The top right corner.
+        TopRight = 7,
+        /// The bottom right corner.
+        BottomRight = 8,
+    }
+}
+
+wl_enum! {
+    /// The direction a positioned surface extends in from the anchor point,
+    /// see `xdg_positioner.set_gravity`.
+    Gravity {
+        /// Centered on the anchor point.
+        None = 0,
+        /// Above the anchor point.
+        Top = 1,
+        /// Below the anchor point.
+        Bottom = 2,
+        /// Left of the anchor point.
+        Left = 3,
+        /// Right of the anchor point.
+        Right = 4,
+        /// Above and left of the anchor point.
+        TopLeft = 5,
+        /// Below and left of the anchor point.
+        BottomLeft = 6,
+        /// Above and right of the anchor point.
+        TopRight = 7,
+        /// Below and right of the anchor point.
+        BottomRight = 8,
+    }
+}
+
+wl_enum! {
+    /// A way the compositor may adjust a position that would leave the
+    /// surface partly off screen, see `xdg_positioner.set_constraint_adjustment`.
+    ///
+    /// The values are bit flags and are combined into a mask.
+    ConstraintAdjustment {
+        /// Don't move the surface.
+        None = 0,
+        /// Move the surface horizontally until it is unconstrained.
+        SlideX = 1,
+        /// Move the surface vertically until it is unconstrained.
+        SlideY = 2,
+        /// Mirror the anchor and gravity horizontally.
+        FlipX = 4,
+        /// Mirror the anchor and gravity vertically.
+        FlipY = 8,
+        /// Shrink the surface horizontally.
+        ResizeX = 16,
+        /// Shrink the surface vertically.
+        ResizeY = 32,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `xdg_positioner.set_size` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_size">
+    ///   <description summary="set the size of the to-be positioned rectangle">
+    ///     Set the size of the surface that is to be positioned with the positioner
+    ///     object. The size is in surface-local coordinates and corresponds to the
+    ///     window geometry. See xdg_surface.set_window_geometry.
+    ///
+    ///     If a zero or negative size is set the invalid_input error is raised.
+    ///   </description>
+    ///   <arg name="width" type="int" summary="width of positioned rectangle"/>
+    ///   <arg name="height" type="int" summary="height of positioned rectangle"/>
+    /// </request>
+    /// ```
+    SetSizeParam {
+        /// The width of the positioned surface.
+        width: WlInt,
+        /// The height of the positioned surface.
+        height: WlInt,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `xdg_positioner.set_anchor_rect` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_anchor_rect">
+    ///   <description summary="set the anchor rectangle within the parent surface">
+    ///     Specify the anchor rectangle within the parent surface that the child
+    ///     surface will be placed relative to. The rectangle is relative to the
+    ///     window geometry as defined by xdg_surface.set_window_geometry of the
+    ///     parent surface.
+    ///
+    ///     When the xdg_positioner object is used to position a child surface, the
+    ///     anchor rectangle may not extend outside the window geometry of the
+    ///     positioned child's parent surface.
+    ///
+    ///     If a negative size is set the invalid_input error is raised.
+    ///   </description>
+    ///   <arg name="x" type="int" summary="x position of anchor rectangle"/>
+    ///   <arg name="y" type="int" summary="y position of anchor rectangle"/>
+    ///   <arg name="width" type="int" summary="width of anchor rectangle"/>
+    ///   <arg name="height" type="int" summary="height of anchor rectangle"/>
+    /// </request>
+    /// ```
+    SetAnchorRectParam {
+        /// The left edge of the anchor rectangle.
+        x: WlInt,
+        /// The top edge of the anchor rectangle.
+        y: WlInt,
+        /// The width of the anchor rectangle.
+        width: WlInt,
+        /// The height of the anchor rectangle.
+        height: WlInt,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `xdg_positioner.set_anchor`,
+    /// `xdg_positioner.set_gravity` and
+    /// `xdg_positioner.set_constraint_adjustment` requests, which all carry a
+    /// single enum value.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_anchor">
+    ///   <description summary="set anchor rectangle anchor">
+    ///     Defines the anchor point for the anchor rectangle. The specified anchor
+    ///     is used derive an anchor point that the child surface will be
+    ///     positioned relative to. If a corner anchor is set (e.g. 'top_left' or
+    ///     'bottom_right'), the anchor point will be at the specified corner;
+    ///     otherwise, the derived anchor point will be centered on the specified
+    ///     edge, or in the center of the anchor rectangle if no edge is specified.
+    ///   </description>
+    ///   <arg name="anchor" type="uint" enum="anchor"
+    ///        summary="anchor"/>
+    /// </request>
+    /// ```
+    SetEnumParam {
+        /// The enum value.
+        value: WlUInt,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `xdg_positioner.set_offset` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_offset">
+    ///   <description summary="set surface position offset">
+    ///     Specify the surface position offset relative to the position of the
+    ///     anchor on the anchor rectangle and the anchor on the surface. For
+    ///     example if the anchor of the anchor rectangle is at (x, y), the surface
+    ///     has the gravity bottom|right, and the offset is (ox, oy), the calculated
+    ///     surface position will be (x + ox, y + oy). The offset position of the
+    ///     surface is the one used for constraint testing. See
+    ///     set_constraint_adjustment.
+    ///
+    ///     An example use case is placing a popup menu on top of a user interface
+    ///     element, while aligning the user interface element of the parent surface
+    ///     with some user interface element placed somewhere in the popup surface.
+    ///   </description>
+    ///   <arg name="x" type="int" summary="surface position x offset"/>
+    ///   <arg name="y" type="int" summary="surface position y offset"/>
+    /// </request>
+    /// ```
+    SetOffsetParam {
+        /// The horizontal offset.
+        x: WlInt,
+        /// The vertical offset.
+        y: WlInt,
+    }
+}
+
+/// Sends an `xdg_positioner.destroy` request to the compositor.
+pub fn destroy(conn: &Connection, positioner: u32) -> Result<()> {
+    conn.send_request(positioner, Opcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends an `xdg_positioner.set_size` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `positioner` - The ID of the positioner
+/// * `width`, `height` - The size of the positioned surface
+pub fn set_size(conn: &Connection, positioner: u32, width: WlInt, height: WlInt) -> Result<()> {
+    conn.send_request(
+        positioner,
+        Opcode::SetSize,
+        &SetSizeParam::new(width, height),
+    )?;
+
+    Ok(())
+}
+
+/// Sends an `xdg_positioner.set_anchor_rect` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `positioner` - The ID of the positioner
+/// * `x`, `y`, `width`, `height` - The anchor rectangle, relative to the
+///   window geometry of the parent
+pub fn set_anchor_rect(
+    conn: &Connection,
+    positioner: u32,
+    x: WlInt,
+    y: WlInt,
+    width: WlInt,
+    height: WlInt,
+) -> Result<()> {
+    conn.send_request(
+        positioner,
+        Opcode::SetAnchorRect,
+        &SetAnchorRectParam::new(x, y, width, height),
+    )?;
+
+    Ok(())
+}
+
+/// Sends one of the `xdg_positioner` requests carrying a single enum value.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `positioner` - The ID of the positioner
+/// * `opcode` - `SetAnchor`, `SetGravity` or `SetConstraintAdjustment`
+/// * `value` - The enum value or mask
+pub fn set_enum(conn: &Connection, positioner: u32, opcode: Opcode, value: WlUInt) -> Result<()> {
+    conn.send_request(positioner, opcode, &SetEnumParam::new(value))?;
+
+    Ok(())
+}
+
+/// Sends an `xdg_positioner.set_offset` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `positioner` - The ID of the positioner
+/// * `x`, `y` - The offset from the anchor point
+pub fn set_offset(conn: &Connection, positioner: u32, x: WlInt, y: WlInt) -> Result<()> {
+    conn.send_request(positioner, Opcode::SetOffset, &SetOffsetParam::new(x, y))?;
+
+    Ok(())
+}
+
+/// Sends an `xdg_positioner.set_reactive` request to the compositor.
+pub fn set_reactive(conn: &Connection, positioner: u32) -> Result<()> {
+    conn.send_request(positioner, Opcode::SetReactive, &())?;
+
+    Ok(())
+}
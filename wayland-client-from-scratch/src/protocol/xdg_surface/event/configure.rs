@@ -0,0 +1,67 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{types::WlUInt, xdg_surface::XdgSurfaceState},
+};
+
+/// Represents an `xdg_surface.configure` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="configure">
+///   <description summary="suggest a surface change">
+///     The configure event marks the end of a configure sequence. A configure
+///     sequence is a set of one or more events configuring the state of the
+///     xdg_surface, including the final xdg_surface.configure event.
+///
+///     Where applicable, xdg_surface surface roles will during a configure
+///     sequence extend this event as a latched state sent as events before the
+///     xdg_surface.configure event. Such events should be considered to make up
+///     a set of atomically applied configuration states, where the
+///     xdg_surface.configure commits the accumulated state.
+///
+///     Clients should arrange their surface for the new states, and then send
+///     an ack_configure request with the serial sent in this configure event at
+///     some point before committing the new surface.
+///
+///     If the client receives multiple configure events before it can respond
+///     to one, it is free to discard all but the last event it received.
+///   </description>
+///   <arg name="serial" type="uint" summary="serial of the configure event"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Configure {
+    /// The serial to acknowledge with `ack_configure`.
+    pub serial: WlUInt,
+}
+
+impl TryFrom<&[u8]> for Configure {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Configure` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `serial` (u32) - The serial of the configure event
+    fn try_from(buf: &[u8]) -> Result<Configure> {
+        Ok(Configure {
+            serial: WlUInt::read(buf, 0)?,
+        })
+    }
+}
+
+/// Handles an `xdg_surface.configure` event by recording its serial as
+/// awaiting acknowledgement before notifying the listener.
+pub(super) fn handle_xdg_surface_configure(
+    conn: &Connection,
+    xdg_surface_id: u32,
+    configure: Configure,
+) -> Result<()> {
+    if let Some(state) = conn.object_data::<XdgSurfaceState>(xdg_surface_id) {
+        state.receive_configure(configure.serial.get());
+    }
+
+    conn.emit(xdg_surface_id, configure);
+
+    Ok(())
+}
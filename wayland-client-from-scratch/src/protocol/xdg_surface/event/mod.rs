@@ -0,0 +1,79 @@
+pub mod configure;
+
+use crate::{
+    connection::Connection,
+    error::{Error, Result, anyhow},
+    protocol::message::WlMessage,
+};
+
+/// Represents the event types that can be emitted by an `xdg_surface` object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    /// Ends a configure sequence.
+    ///
+    /// # Event Arguments
+    /// - `serial`: The serial to acknowledge
+    Configure = 0,
+}
+
+impl TryFrom<u16> for Opcode {
+    type Error = Error;
+
+    /// Attempts to convert a raw opcode value into a structured `XdgSurfaceEvent`.
+    fn try_from(value: u16) -> Result<Opcode> {
+        match value {
+            0 => Ok(Opcode::Configure),
+            _ => Err(anyhow!("Invalid xdg_surface event opcode: {}", value)),
+        }
+    }
+}
+
+impl Opcode {
+    /// Returns the interface version the event was introduced in.
+    pub fn since(self) -> u32 {
+        match self {
+            Opcode::Configure => 1,
+        }
+    }
+}
+
+/// A decoded `xdg_surface` event, with its arguments.
+#[derive(Debug)]
+pub enum Event {
+    /// Ends a configure sequence.
+    Configure(configure::Configure),
+}
+
+impl Event {
+    /// Decodes a `xdg_surface` event from its message.
+    ///
+    /// # Errors
+    /// Returns an error if the opcode is unknown or the arguments are malformed.
+    pub fn decode(_conn: &Connection, msg: &WlMessage) -> Result<Event> {
+        let buf: &[u8] = &msg.data;
+
+        Ok(match Opcode::try_from(msg.header.opcode)? {
+            Opcode::Configure => Event::Configure(configure::Configure::try_from(buf)?),
+        })
+    }
+
+    /// Returns the opcode of the event.
+    pub fn opcode(&self) -> Opcode {
+        match self {
+            Event::Configure(_) => Opcode::Configure,
+        }
+    }
+}
+
+/// Dispatches incoming `xdg_surface` events to their appropriate handler functions.
+///
+/// # Event Routing
+/// * `Configure` events are routed to `configure::handle_xdg_surface_configure`
+pub fn handle_xdg_surface_event(conn: &Connection, msg: WlMessage) -> Result<()> {
+    // Route the event to the appropriate handler based on type
+    match Event::decode(conn, &msg)? {
+        Event::Configure(event) => {
+            configure::handle_xdg_surface_configure(conn, msg.header.object_id, event)
+        }
+    }
+}
@@ -0,0 +1,439 @@
+pub mod event;
+pub mod request;
+
+use std::sync::Arc;
+
+use crate::{
+    connection::{Connection, Listener},
+    error::{Result, anyhow},
+    geometry::Rect,
+    protocol::{
+        WlObjectId,
+        surface::{Surface, SurfaceState},
+        types::{WlInt, WlNewId, WlObject, WlUInt},
+        xdg_popup::{PopupEvent, PopupState, XdgPopup},
+        xdg_positioner::XdgPositioner,
+        xdg_toplevel::{ToplevelEvent, ToplevelState, XdgToplevel},
+    },
+    sync::{Lock, LockCell},
+};
+
+use event::configure::Configure;
+
+/// The state of the role object of an xdg_surface.
+enum RoleState {
+    /// The surface is a toplevel window.
+    Toplevel(Arc<ToplevelState>),
+    /// The surface is a popup.
+    Popup(Arc<PopupState>),
+}
+
+/// Client-side view of the configure/ack handshake of an `xdg_surface`.
+///
+/// Compositors disconnect clients that get the handshake wrong, with an
+/// error that rarely points at the faulty call. Tracking it here lets the
+/// offending request fail on the client side instead.
+pub(crate) struct XdgSurfaceState {
+    /// The object ID of the xdg_surface.
+    id: u32,
+    /// Whether a role object, such as an `xdg_toplevel`, was created.
+    has_role: LockCell<bool>,
+    /// The state of the role object, if one was created.
+    role: Lock<Option<RoleState>>,
+    /// Serials of the configure events not acknowledged yet, oldest first.
+    pending: Lock<Vec<u32>>,
+    /// The serial of the last acknowledged configure since the surface was
+    /// last unmapped.
+    acked: LockCell<Option<u32>>,
+    /// The serial of the configure taken by the application, acknowledged
+    /// automatically on the next commit.
+    ack_on_commit: LockCell<Option<u32>>,
+}
+
+impl XdgSurfaceState {
+    /// Creates the state of the xdg_surface with the given ID.
+    fn new(id: u32) -> XdgSurfaceState {
+        XdgSurfaceState {
+            id,
+            has_role: LockCell::new(false),
+            role: Lock::new(None),
+            pending: Lock::new(Vec::new()),
+            acked: LockCell::new(None),
+            ack_on_commit: LockCell::new(None),
+        }
+    }
+
+    /// Records a configure event awaiting acknowledgement and completes the
+    /// configure sequence of the role object.
+    pub(crate) fn receive_configure(&self, serial: u32) {
+        self.pending.borrow_mut().push(serial);
+
+        match self.role.borrow().as_ref() {
+            Some(RoleState::Toplevel(toplevel)) => toplevel.finish_configure(serial),
+            Some(RoleState::Popup(popup)) => popup.finish_configure(serial),
+            None => {}
+        }
+    }
+
+    /// Schedules `serial` to be acknowledged right before the next commit.
+    pub(crate) fn ack_on_commit(&self, serial: u32) {
+        self.ack_on_commit.set(Some(serial));
+    }
+
+    /// Sends the acknowledgement scheduled with [`XdgSurfaceState::ack_on_commit`], if any.
+    pub(crate) fn flush_ack(&self, conn: &Connection) -> Result<()> {
+        let Some(serial) = self.ack_on_commit.take() else {
+            return Ok(());
+        };
+
+        self.ack(serial)?;
+        request::ack_configure(conn, self.id, WlUInt(serial))
+    }
+
+    /// Validates and records the acknowledgement of `serial`.
+    ///
+    /// Acknowledging a configure also consumes every configure received
+    /// before it.
+    ///
+    /// # Errors
+    /// Returns an error if `serial` is not the serial of a configure event
+    /// that is still waiting for acknowledgement.
+    pub(crate) fn ack(&self, serial: u32) -> Result<()> {
+        let xdg_surface_id = self.id;
+        let mut pending = self.pending.borrow_mut();
+        let Some(index) = pending.iter().position(|&pending| pending == serial) else {
+            return Err(if self.acked.get() == Some(serial) {
+                anyhow!("xdg_surface {xdg_surface_id}: configure {serial} was already acknowledged")
+            } else if pending.is_empty() && self.acked.get().is_none() {
+                anyhow!(
+                    "xdg_surface {xdg_surface_id}: cannot acknowledge configure {serial} before receiving a configure"
+                )
+            } else {
+                anyhow!(
+                    "xdg_surface {xdg_surface_id}: configure {serial} is stale or was never sent"
+                )
+            });
+        };
+
+        pending.drain(..=index);
+        self.acked.set(Some(serial));
+
+        // A scheduled acknowledgement consumed by this one must not be sent again.
+        if let Some(scheduled) = self.ack_on_commit.get()
+            && !pending.contains(&scheduled)
+        {
+            self.ack_on_commit.set(None);
+        }
+
+        Ok(())
+    }
+
+    /// Validates a commit of the surface.
+    ///
+    /// # Arguments
+    /// * `surface_id` - The ID of the committed `wl_surface`
+    /// * `pending_buffer` - The buffer attached since the last commit, see
+    ///   [`SurfaceState`]
+    ///
+    /// # Errors
+    /// Returns an error if the surface has no role yet, or if a buffer is
+    /// committed before a configure was acknowledged.
+    pub(crate) fn check_commit(&self, surface_id: u32, pending_buffer: Option<bool>) -> Result<()> {
+        if !self.has_role.get() {
+            return Err(anyhow!(
+                "wl_surface {surface_id}: committed before a role was assigned to its xdg_surface"
+            ));
+        }
+
+        match pending_buffer {
+            Some(true) if self.acked.get().is_none() => Err(match self.pending.borrow().last() {
+                Some(serial) => anyhow!(
+                    "wl_surface {surface_id}: buffer committed without acknowledging configure {serial}"
+                ),
+                None => {
+                    anyhow!("wl_surface {surface_id}: buffer committed before the first configure")
+                }
+            }),
+            Some(false) => {
+                // Unmapping resets the surface: it must go through the
+                // initial commit and configure again before the next buffer.
+                self.pending.borrow_mut().clear();
+                self.acked.set(None);
+                self.ack_on_commit.set(None);
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// An `xdg_surface`, the base of desktop window roles such as [`XdgToplevel`].
+///
+/// Created with
+/// [`XdgWmBase::get_xdg_surface`](crate::protocol::xdg_wm_base::XdgWmBase::get_xdg_surface).
+///
+/// After the role object is created, the surface must be committed without a
+/// buffer. The compositor answers with a configure sequence, which must be
+/// acknowledged with [`XdgSurface::ack_configure`] before a buffer is
+/// attached and committed. Violations are reported as errors by the requests
+/// involved rather than by the compositor.
+pub struct XdgSurface {
+    /// The object ID of the xdg_surface.
+    id: u32,
+    /// The interface version, inherited from `xdg_wm_base`.
+    version: u32,
+    /// The connection the xdg_surface was created on.
+    conn: Connection,
+    /// The state of the underlying `wl_surface`.
+    surface: Arc<SurfaceState>,
+    /// The state shared with the event handlers.
+    state: Arc<XdgSurfaceState>,
+}
+
+impl XdgSurface {
+    /// Wraps an xdg_surface object that was just created on `conn` for `surface`.
+    pub(crate) fn new(
+        id: u32,
+        version: u32,
+        conn: &Connection,
+        surface: &Surface,
+        handler: Listener<Configure>,
+    ) -> XdgSurface {
+        let surface = surface.state();
+
+        let state = Arc::new(XdgSurfaceState::new(id));
+        conn.set_object_data(id, state.clone());
+        conn.set_listener(id, handler);
+        *surface.xdg_surface.borrow_mut() = Some(state.clone());
+
+        XdgSurface {
+            id,
+            version,
+            conn: conn.clone(),
+            surface,
+            state,
+        }
+    }
+
+    /// Returns the object ID of the xdg_surface.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Gives the surface the toplevel role, making it a desktop window.
+    ///
+    /// `handler` receives the toplevel events; the configure sequence they
+    /// belong to ends with the `xdg_surface.configure` event.
+    ///
+    /// # Errors
+    /// Returns an error if the surface already has a role.
+    pub fn get_toplevel<F>(&self, handler: F) -> Result<XdgToplevel>
+    where
+        F: FnMut(&Connection, ToplevelEvent) + Send + 'static,
+    {
+        if self.state.has_role.get() {
+            return Err(anyhow!("xdg_surface {} already has a role", self.id));
+        }
+
+        let id = self
+            .conn
+            .new_object(WlObjectId::XdgToplevel, self.version)?;
+        request::get_toplevel(&self.conn, self.id, WlNewId(id))?;
+
+        let toplevel = XdgToplevel::new(
+            id,
+            self.version,
+            &self.conn,
+            self.state.clone(),
+            Box::new(handler),
+        );
+        self.state.has_role.set(true);
+        *self.state.role.borrow_mut() = Some(RoleState::Toplevel(toplevel.state()));
+
+        Ok(toplevel)
+    }
+
+    /// Gives the surface the popup role, placing it relative to `parent`
+    /// according to the rules of `positioner`.
+    ///
+    /// `parent` may only be `None` when the parent is set through another
+    /// protocol, such as layer-shell. `handler` receives the popup events; the
+    /// configure sequence they belong to ends with the `xdg_surface.configure`
+    /// event.
+    ///
+    /// # Errors
+    /// Returns an error if the surface already has a role, or if `parent` is
+    /// this xdg_surface.
+    pub fn get_popup<F>(
+        &self,
+        parent: Option<&XdgSurface>,
+        positioner: &XdgPositioner,
+        handler: F,
+    ) -> Result<XdgPopup>
+    where
+        F: FnMut(&Connection, PopupEvent) + Send + 'static,
+    {
+        if self.state.has_role.get() {
+            return Err(anyhow!("xdg_surface {} already has a role", self.id));
+        }
+        if parent.is_some_and(|parent| parent.id == self.id) {
+            return Err(anyhow!(
+                "xdg_surface {} cannot be the parent of its own popup",
+                self.id
+            ));
+        }
+
+        let id = self.conn.new_object(WlObjectId::XdgPopup, self.version)?;
+        request::get_popup(
+            &self.conn,
+            self.id,
+            WlNewId(id),
+            WlObject(parent.map_or(0, XdgSurface::id)),
+            WlObject(positioner.id()),
+        )?;
+
+        let popup = XdgPopup::new(
+            id,
+            self.version,
+            &self.conn,
+            self.state.clone(),
+            Box::new(handler),
+        );
+        self.state.has_role.set(true);
+        *self.state.role.borrow_mut() = Some(RoleState::Popup(popup.state()));
+
+        Ok(popup)
+    }
+
+    /// Sets the part of the surface that makes up the window, excluding
+    /// decorations such as drop shadows.
+    ///
+    /// # Errors
+    /// Returns an error if the rectangle is empty.
+    pub fn set_window_geometry(&self, rect: Rect) -> Result<()> {
+        if rect.width <= 0 || rect.height <= 0 {
+            return Err(anyhow!(
+                "xdg_surface {}: window geometry must not be empty, got {}x{}",
+                self.id,
+                rect.width,
+                rect.height
+            ));
+        }
+
+        request::set_window_geometry(
+            &self.conn,
+            self.id,
+            WlInt(rect.x),
+            WlInt(rect.y),
+            WlInt(rect.width),
+            WlInt(rect.height),
+        )
+    }
+
+    /// Acknowledges the configure event with the given serial.
+    ///
+    /// # Errors
+    /// Returns an error, without sending the request, if no configure with
+    /// this serial is waiting for acknowledgement, e.g. because a later
+    /// configure was already acknowledged.
+    pub fn ack_configure(&self, serial: u32) -> Result<()> {
+        self.state.ack(serial)?;
+
+        request::ack_configure(&self.conn, self.id, WlUInt(serial))
+    }
+
+    /// Destroys the xdg_surface. Its role object must be destroyed first.
+    ///
+    /// Dropping the handle does the same.
+    pub fn destroy(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl Drop for XdgSurface {
+    fn drop(&mut self) {
+        self.surface.xdg_surface.borrow_mut().take();
+
+        self.conn
+            .destroy_on_drop(|conn| request::destroy(conn, self.id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the state of an xdg_surface that was given a role.
+    fn with_role() -> XdgSurfaceState {
+        let state = XdgSurfaceState::new(3);
+        state.has_role.set(true);
+
+        state
+    }
+
+    #[test]
+    fn acks_must_name_a_pending_configure() {
+        let state = with_role();
+        let error = state.ack(7).unwrap_err().to_string();
+        assert!(error.contains("before receiving a configure"), "{error}");
+
+        state.receive_configure(7);
+        state.receive_configure(8);
+        state.receive_configure(9);
+        state.ack(8).unwrap();
+
+        // Acknowledging 8 consumed 7 as well
+        let error = state.ack(7).unwrap_err().to_string();
+        assert!(error.contains("stale"), "{error}");
+        let error = state.ack(8).unwrap_err().to_string();
+        assert!(error.contains("already acknowledged"), "{error}");
+        let error = state.ack(10).unwrap_err().to_string();
+        assert!(error.contains("stale or was never sent"), "{error}");
+        state.ack(9).unwrap();
+    }
+
+    #[test]
+    fn buffers_need_an_acknowledged_configure() {
+        let state = XdgSurfaceState::new(3);
+        assert!(state.check_commit(5, None).is_err());
+        state.has_role.set(true);
+        state.check_commit(5, None).unwrap();
+
+        let error = state.check_commit(5, Some(true)).unwrap_err().to_string();
+        assert!(error.contains("before the first configure"), "{error}");
+
+        state.receive_configure(7);
+        let error = state.check_commit(5, Some(true)).unwrap_err().to_string();
+        assert!(
+            error.contains("without acknowledging configure 7"),
+            "{error}"
+        );
+
+        state.ack(7).unwrap();
+        state.check_commit(5, Some(true)).unwrap();
+    }
+
+    #[test]
+    fn unmapping_resets_the_configure_sequence() {
+        let state = with_role();
+        state.receive_configure(7);
+        state.receive_configure(8);
+        state.ack(7).unwrap();
+        state.ack_on_commit(8);
+        state.check_commit(5, Some(true)).unwrap();
+
+        state.check_commit(5, Some(false)).unwrap();
+        assert!(state.pending.borrow().is_empty());
+        assert_eq!(state.acked.get(), None);
+        assert_eq!(state.ack_on_commit.get(), None);
+
+        // The next buffer waits for a new configure
+        assert!(state.check_commit(5, Some(true)).is_err());
+        assert!(state.ack(8).is_err());
+        state.receive_configure(9);
+        state.ack(9).unwrap();
+        state.check_commit(5, Some(true)).unwrap();
+    }
+}
@@ -0,0 +1,246 @@
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::types::{WlInt, WlNewId, WlObject, WlUInt},
+    wl_request_opcode, wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to an `xdg_surface` object.
+    Opcode {
+        /// Destroys the `xdg_surface` object.
+        Destroy = 0 (destructor),
+
+        /// Assigns the `xdg_toplevel` role to the surface.
+        GetToplevel = 1,
+
+        /// Assigns the `xdg_popup` role to the surface.
+        GetPopup = 2,
+
+        /// Sets the part of the surface that is the window, excluding decorations such as shadows.
+        SetWindowGeometry = 3,
+
+        /// Acknowledges a configure event.
+        AckConfigure = 4,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `xdg_surface.get_toplevel` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="get_toplevel">
+    ///   <description summary="assign the xdg_toplevel surface role">
+    ///     This creates an xdg_toplevel object for the given xdg_surface and gives
+    ///     the associated wl_surface the xdg_toplevel role.
+    ///
+    ///     See the documentation of xdg_toplevel for more details about what an
+    ///     xdg_toplevel is and how it is used.
+    ///   </description>
+    ///   <arg name="id" type="new_id" interface="xdg_toplevel"/>
+    /// </request>
+    /// ```
+    GetToplevelParam {
+        /// The object ID to assign to the new xdg_toplevel.
+        id: WlNewId,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `xdg_surface.get_popup` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="get_popup">
+    ///   <description summary="assign the xdg_popup surface role">
+    ///     This creates an xdg_popup object for the given xdg_surface and gives
+    ///     the associated wl_surface the xdg_popup role.
+    ///
+    ///     If null is passed as a parent, a parent surface must be specified using
+    ///     some other protocol, before committing the initial state.
+    ///
+    ///     See the documentation of xdg_popup for more details about what an
+    ///     xdg_popup is and how it is used.
+    ///   </description>
+    ///   <arg name="id" type="new_id" interface="xdg_popup"/>
+    ///   <arg name="parent" type="object" interface="xdg_surface" allow-null="true"/>
+    ///   <arg name="positioner" type="object" interface="xdg_positioner"/>
+    /// </request>
+    /// ```
+    GetPopupParam {
+        /// The object ID to assign to the new xdg_popup.
+        id: WlNewId,
+        /// The xdg_surface of the parent, or 0.
+        parent: WlObject,
+        /// The positioner with the placement rules.
+        positioner: WlObject,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `xdg_surface.set_window_geometry` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_window_geometry">
+    ///   <description summary="set the new window geometry">
+    ///     The window geometry of a surface is its "visible bounds" from the
+    ///     user's perspective. Client-side decorations often have invisible
+    ///     portions like drop-shadows which should be ignored for the
+    ///     purposes of aligning, placing and constraining windows.
+    ///
+    ///     The window geometry is double-buffered state, see wl_surface.commit.
+    ///
+    ///     The width and height of the effective window geometry must be
+    ///     greater than zero. Setting an invalid size will raise an
+    ///     invalid_size error.
+    ///   </description>
+    ///   <arg name="x" type="int"/>
+    ///   <arg name="y" type="int"/>
+    ///   <arg name="width" type="int"/>
+    ///   <arg name="height" type="int"/>
+    /// </request>
+    /// ```
+    SetWindowGeometryParam {
+        /// The left edge of the window geometry in surface-local coordinates.
+        x: WlInt,
+        /// The top edge of the window geometry in surface-local coordinates.
+        y: WlInt,
+        /// The width of the window geometry.
+        width: WlInt,
+        /// The height of the window geometry.
+        height: WlInt,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `xdg_surface.ack_configure` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="ack_configure">
+    ///   <description summary="ack a configure event">
+    ///     When a configure event is received, if a client commits the
+    ///     surface in response to the configure event, then the client
+    ///     must make an ack_configure request sometime before the commit
+    ///     request, passing along the serial of the configure event.
+    ///
+    ///     For instance, for toplevel surfaces the compositor might use this
+    ///     information to move a surface to the top left only when the client has
+    ///     drawn itself for the maximized or fullscreen state.
+    ///
+    ///     If the client receives multiple configure events before it
+    ///     can respond to one, it only has to ack the last configure event.
+    ///     Acking a configure event that was never sent raises an invalid_serial
+    ///     error.
+    ///
+    ///     A client is not required to commit immediately after sending
+    ///     an ack_configure request - it may even ack_configure several times
+    ///     before its next surface commit.
+    ///
+    ///     A client may send multiple ack_configure requests before committing, but
+    ///     only the last request sent before a commit indicates which configure
+    ///     event the client really is responding to.
+    ///
+    ///     Sending an ack_configure request consumes the serial number sent with
+    ///     the request, as well as serial numbers sent by all configure events
+    ///     sent on this xdg_surface prior to the configure event referenced by
+    ///     the committed serial.
+    ///
+    ///     It is an error to issue multiple ack_configure requests referencing a
+    ///     serial from the same configure event, or to issue an ack_configure
+    ///     request referencing a serial from a configure event issued before the
+    ///     event identified by the last ack_configure request for the same
+    ///     xdg_surface. Doing so will raise an invalid_serial error.
+    ///   </description>
+    ///   <arg name="serial" type="uint" summary="the serial from the configure event"/>
+    /// </request>
+    /// ```
+    AckConfigureParam {
+        /// The serial of the acknowledged configure event.
+        serial: WlUInt,
+    }
+}
+
+/// Sends an `xdg_surface.destroy` request to the compositor.
+pub fn destroy(conn: &Connection, xdg_surface: u32) -> Result<()> {
+    conn.send_request(xdg_surface, Opcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends an `xdg_surface.get_toplevel` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `xdg_surface` - The ID of the xdg_surface
+/// * `id` - The object ID to assign to the new xdg_toplevel
+pub fn get_toplevel(conn: &Connection, xdg_surface: u32, id: WlNewId) -> Result<()> {
+    conn.send_request(xdg_surface, Opcode::GetToplevel, &GetToplevelParam::new(id))?;
+
+    Ok(())
+}
+
+/// Sends an `xdg_surface.get_popup` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `xdg_surface` - The ID of the xdg_surface
+/// * `id` - The object ID to assign to the new xdg_popup
+/// * `parent` - The xdg_surface of the parent, or 0
+/// * `positioner` - The positioner with the placement rules
+pub fn get_popup(
+    conn: &Connection,
+    xdg_surface: u32,
+    id: WlNewId,
+    parent: WlObject,
+    positioner: WlObject,
+) -> Result<()> {
+    conn.send_request(
+        xdg_surface,
+        Opcode::GetPopup,
+        &GetPopupParam::new(id, parent, positioner),
+    )?;
+
+    Ok(())
+}
+
+/// Sends an `xdg_surface.set_window_geometry` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `xdg_surface` - The ID of the xdg_surface
+/// * `x`, `y`, `width`, `height` - The window geometry in surface-local coordinates
+pub fn set_window_geometry(
+    conn: &Connection,
+    xdg_surface: u32,
+    x: WlInt,
+    y: WlInt,
+    width: WlInt,
+    height: WlInt,
+) -> Result<()> {
+    conn.send_request(
+        xdg_surface,
+        Opcode::SetWindowGeometry,
+        &SetWindowGeometryParam::new(x, y, width, height),
+    )?;
+
+    Ok(())
+}
+
+/// Sends an `xdg_surface.ack_configure` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `xdg_surface` - The ID of the xdg_surface
+/// * `serial` - The serial of the acknowledged configure event
+pub fn ack_configure(conn: &Connection, xdg_surface: u32, serial: WlUInt) -> Result<()> {
+    conn.send_request(
+        xdg_surface,
+        Opcode::AckConfigure,
+        &AckConfigureParam::new(serial),
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        endian::Endian,
+        types::{WL_TYPE_INT_LEN, WL_TYPE_UINT_LEN, WlArray, WlInt},
+        xdg_toplevel::{ToplevelEvent, ToplevelState},
+    },
+    wl_enum,
+};
+
+wl_enum! {
+    /// A state of a toplevel window, as listed by `xdg_toplevel.configure`.
+    State {
+        /// The window is maximized and should not draw shadows or rounded corners.
+        Maximized = 1,
+        /// The window is fullscreen.
+        Fullscreen = 2,
+        /// The window is being interactively resized.
+        Resizing = 3,
+        /// The window has keyboard focus and should look active.
+        Activated = 4,
+        /// The left edge touches another window or the screen edge (since version 2).
+        TiledLeft = 5,
+        /// The right edge touches another window or the screen edge (since version 2).
+        TiledRight = 6,
+        /// The top edge touches another window or the screen edge (since version 2).
+        TiledTop = 7,
+        /// The bottom edge touches another window or the screen edge (since version 2).
+        TiledBottom = 8,
+        /// The window is not visible and may stop drawing (since version 6).
+        Suspended = 9,
+    }
+}
+
+/// Represents an `xdg_toplevel.configure` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="configure">
+///   <description summary="suggest a surface change">
+///     This configure event asks the client to resize its toplevel surface or
+///     to change its state. The configured state should not be applied
+///     immediately. See xdg_surface.configure for details.
+///
+///     The width and height arguments specify a hint to the window
+///     about how its surface should be resized in window geometry
+///     coordinates. See set_window_geometry.
+///
+///     If the width or height arguments are zero, it means the client
+///     should decide its own window dimension. This may happen when the
+///     compositor needs to configure the state of the surface but doesn't
+///     have any information about any previous or expected dimension.
+///
+///     The states listed in the event specify how the width/height
+///     arguments should be interpreted, and possibly how it should be
+///     drawn.
+///
+///     Clients must send an ack_configure in response to this event. See
+///     xdg_surface.configure and xdg_surface.ack_configure for details.
+///   </description>
+///   <arg name="width" type="int"/>
+///   <arg name="height" type="int"/>
+///   <arg name="states" type="array"/>
+/// </event>
+/// ```
+#[derive(Debug)]
+pub struct Configure {
+    /// The suggested width, or 0 to let the client decide.
+    pub width: WlInt,
+    /// The suggested height, or 0 to let the client decide.
+    pub height: WlInt,
+    /// The active states, as an array of `State` values.
+    pub states: WlArray,
+}
+
+impl Configure {
+    /// Returns the active states, skipping values newer than this crate.
+    pub fn states(&self) -> Vec<State> {
+        self.states
+            .as_slice()
+            .chunks_exact(WL_TYPE_UINT_LEN)
+            .filter_map(|state| State::try_from(Endian::NATIVE.decode_u32_at(state)).ok())
+            .collect()
+    }
+}
+
+impl TryFrom<&[u8]> for Configure {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Configure` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `width` (i32) - The suggested width
+    /// - Bytes 4-7: `height` (i32) - The suggested height
+    /// - Bytes 8+: `states` (WlArray) - The active states
+    fn try_from(buf: &[u8]) -> Result<Configure> {
+        let states_start_pos = 2 * WL_TYPE_INT_LEN;
+
+        Ok(Configure {
+            width: WlInt::read(buf, 0)?,
+            height: WlInt::read(buf, WL_TYPE_INT_LEN)?,
+            states: WlArray::try_from(buf.get(states_start_pos..).unwrap_or_default())?,
+        })
+    }
+}
+
+/// Handles an `xdg_toplevel.configure` event by recording the suggested
+/// size and states in the current configure sequence before forwarding it to
+/// the listener.
+pub(super) fn handle_xdg_toplevel_configure(
+    conn: &Connection,
+    toplevel_id: u32,
+    configure: Configure,
+) -> Result<()> {
+    if let Some(state) = conn.object_data::<ToplevelState>(toplevel_id) {
+        let mut building = state.building.borrow_mut();
+        building.width = configure.width.get();
+        building.height = configure.height.get();
+        building.states = configure.states();
+    }
+
+    conn.emit(toplevel_id, ToplevelEvent::Configure(configure));
+
+    Ok(())
+}
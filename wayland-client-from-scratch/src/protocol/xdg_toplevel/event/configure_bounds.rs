@@ -0,0 +1,78 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        types::{WL_TYPE_INT_LEN, WlInt},
+        xdg_toplevel::{ToplevelEvent, ToplevelState},
+    },
+};
+
+/// Represents an `xdg_toplevel.configure_bounds` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="configure_bounds" since="4">
+///   <description summary="recommended window geometry bounds">
+///     The configure_bounds event may be sent prior to a xdg_toplevel.configure
+///     event to communicate the bounds a window geometry size is recommended
+///     to constrain to.
+///
+///     The passed width and height are in surface coordinate space. If width
+///     and height are 0, it means bounds is unknown and equivalent to as if no
+///     configure_bounds event was ever sent for this surface.
+///
+///     The bounds can for example correspond to the size of a monitor excluding
+///     any panels or other shell components, so that a surface isn't created in
+///     a way that it cannot fit.
+///
+///     The bounds may change at any point, and in such a case, a new
+///     xdg_toplevel.configure_bounds will be sent, followed by
+///     xdg_toplevel.configure and xdg_surface.configure.
+///   </description>
+///   <arg name="width" type="int"/>
+///   <arg name="height" type="int"/>
+/// </event>
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfigureBounds {
+    /// The recommended maximum width, or 0 if unknown.
+    pub width: WlInt,
+    /// The recommended maximum height, or 0 if unknown.
+    pub height: WlInt,
+}
+
+impl TryFrom<&[u8]> for ConfigureBounds {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `ConfigureBounds` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `width` (i32) - The recommended maximum width
+    /// - Bytes 4-7: `height` (i32) - The recommended maximum height
+    fn try_from(buf: &[u8]) -> Result<ConfigureBounds> {
+        Ok(ConfigureBounds {
+            width: WlInt::read(buf, 0)?,
+            height: WlInt::read(buf, WL_TYPE_INT_LEN)?,
+        })
+    }
+}
+
+/// Handles an `xdg_toplevel.configure_bounds` event by recording the bounds
+/// before forwarding them to the listener.
+///
+/// Bounds stay in effect for later configure sequences until the compositor
+/// sends new ones; 0x0 bounds mean the bounds are unknown.
+pub(super) fn handle_xdg_toplevel_configure_bounds(
+    conn: &Connection,
+    toplevel_id: u32,
+    bounds: ConfigureBounds,
+) -> Result<()> {
+    if let Some(state) = conn.object_data::<ToplevelState>(toplevel_id) {
+        let (width, height) = (bounds.width.get(), bounds.height.get());
+        state.building.borrow_mut().bounds = (width != 0 || height != 0).then_some((width, height));
+    }
+
+    conn.emit(toplevel_id, ToplevelEvent::ConfigureBounds(bounds));
+
+    Ok(())
+}
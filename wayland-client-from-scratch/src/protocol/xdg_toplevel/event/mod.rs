@@ -0,0 +1,133 @@
+pub mod configure;
+pub mod configure_bounds;
+pub mod wm_capabilities;
+
+use crate::{
+    connection::Connection,
+    error::{Error, Result, anyhow},
+    protocol::{message::WlMessage, xdg_toplevel::ToplevelEvent},
+};
+
+/// Represents the event types that can be emitted by an `xdg_toplevel` object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    /// Suggests a new size and state for the window.
+    ///
+    /// # Event Arguments
+    /// - `width`, `height`: The suggested size, 0 to let the client decide
+    /// - `states`: The active window states
+    Configure = 0,
+
+    /// The user wants to close the window.
+    Close = 1,
+
+    /// The recommended bounds of the window size (since version 4).
+    ///
+    /// # Event Arguments
+    /// - `width`, `height`: The recommended maximum size
+    ConfigureBounds = 2,
+
+    /// The window management features supported by the compositor (since version 5).
+    ///
+    /// # Event Arguments
+    /// - `capabilities`: The supported features
+    WmCapabilities = 3,
+}
+
+impl TryFrom<u16> for Opcode {
+    type Error = Error;
+
+    /// Attempts to convert a raw opcode value into a structured `XdgToplevelEvent`.
+    fn try_from(value: u16) -> Result<Opcode> {
+        match value {
+            0 => Ok(Opcode::Configure),
+            1 => Ok(Opcode::Close),
+            2 => Ok(Opcode::ConfigureBounds),
+            3 => Ok(Opcode::WmCapabilities),
+            _ => Err(anyhow!("Invalid xdg_toplevel event opcode: {}", value)),
+        }
+    }
+}
+
+impl Opcode {
+    /// Returns the interface version the event was introduced in.
+    pub fn since(self) -> u32 {
+        match self {
+            Opcode::Configure | Opcode::Close => 1,
+            Opcode::ConfigureBounds => 4,
+            Opcode::WmCapabilities => 5,
+        }
+    }
+}
+
+/// A decoded `xdg_toplevel` event, with its arguments.
+#[derive(Debug)]
+pub enum Event {
+    /// Suggests a new size and state for the window.
+    Configure(configure::Configure),
+    /// The user wants to close the window.
+    Close,
+    /// The recommended bounds of the window size (since version 4).
+    ConfigureBounds(configure_bounds::ConfigureBounds),
+    /// The window management features supported by the compositor (since version 5).
+    WmCapabilities(wm_capabilities::WmCapabilities),
+}
+
+impl Event {
+    /// Decodes a `xdg_toplevel` event from its message.
+    ///
+    /// # Errors
+    /// Returns an error if the opcode is unknown or the arguments are malformed.
+    pub fn decode(_conn: &Connection, msg: &WlMessage) -> Result<Event> {
+        let buf: &[u8] = &msg.data;
+
+        Ok(match Opcode::try_from(msg.header.opcode)? {
+            Opcode::Configure => Event::Configure(configure::Configure::try_from(buf)?),
+            Opcode::Close => Event::Close,
+            Opcode::ConfigureBounds => {
+                Event::ConfigureBounds(configure_bounds::ConfigureBounds::try_from(buf)?)
+            }
+            Opcode::WmCapabilities => {
+                Event::WmCapabilities(wm_capabilities::WmCapabilities::try_from(buf)?)
+            }
+        })
+    }
+
+    /// Returns the opcode of the event.
+    pub fn opcode(&self) -> Opcode {
+        match self {
+            Event::Configure(_) => Opcode::Configure,
+            Event::Close => Opcode::Close,
+            Event::ConfigureBounds(_) => Opcode::ConfigureBounds,
+            Event::WmCapabilities(_) => Opcode::WmCapabilities,
+        }
+    }
+}
+
+/// Dispatches incoming `xdg_toplevel` events to their appropriate handler functions.
+///
+/// # Event Routing
+/// * `Configure` events are routed to `configure::handle_xdg_toplevel_configure`
+/// * `Close` events are forwarded to the listener directly
+/// * `ConfigureBounds` events are routed to `configure_bounds::handle_xdg_toplevel_configure_bounds`
+/// * `WmCapabilities` events are routed to `wm_capabilities::handle_xdg_toplevel_wm_capabilities`
+pub fn handle_xdg_toplevel_event(conn: &Connection, msg: WlMessage) -> Result<()> {
+    let toplevel_id = msg.header.object_id;
+
+    // Route the event to the appropriate handler based on type
+    match Event::decode(conn, &msg)? {
+        Event::Configure(event) => {
+            configure::handle_xdg_toplevel_configure(conn, toplevel_id, event)
+        }
+        Event::Close => {
+            conn.emit(toplevel_id, ToplevelEvent::Close);
+            Ok(())
+        }
+        Event::ConfigureBounds(event) => {
+            configure_bounds::handle_xdg_toplevel_configure_bounds(conn, toplevel_id, event)
+        }
+        Event::WmCapabilities(event) => {
+            wm_capabilities::handle_xdg_toplevel_wm_capabilities(conn, toplevel_id, event)
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{
+        endian::Endian,
+        types::{WL_TYPE_UINT_LEN, WlArray},
+        xdg_toplevel::ToplevelEvent,
+    },
+    wl_enum,
+};
+
+wl_enum! {
+    /// A window management feature supported by the compositor.
+    WmCapability {
+        /// `show_window_menu` is available.
+        WindowMenu = 1,
+        /// `set_maximized` and `unset_maximized` are available.
+        Maximize = 2,
+        /// `set_fullscreen` and `unset_fullscreen` are available.
+        Fullscreen = 3,
+        /// `set_minimized` is available.
+        Minimize = 4,
+    }
+}
+
+/// Represents an `xdg_toplevel.wm_capabilities` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="wm_capabilities" since="5">
+///   <description summary="compositor capabilities">
+///     This event advertises the capabilities supported by the compositor. If
+///     a capability isn't supported, clients should hide or disable the UI
+///     elements that expose this functionality. For instance, if the
+///     compositor doesn't advertise support for minimized toplevels, a button
+///     triggering the set_minimized request should not be displayed.
+///
+///     The compositor will ignore requests it doesn't support. For instance,
+///     a compositor which doesn't advertise support for minimized will ignore
+///     set_minimized requests.
+///
+///     Compositors must send this event once before the first
+///     xdg_surface.configure event. When the capabilities change, compositors
+///     must send this event again and then send an xdg_surface.configure
+///     event.
+///
+///     The configured state should not be applied immediately. See
+///     xdg_surface.configure for details.
+///
+///     The capabilities are sent as an array of 32-bit unsigned integers in
+///     native endianness.
+///   </description>
+///   <arg name="capabilities" type="array" summary="array of 32-bit capabilities"/>
+/// </event>
+/// ```
+#[derive(Debug)]
+pub struct WmCapabilities {
+    /// The supported features, as an array of `WmCapability` values.
+    pub capabilities: WlArray,
+}
+
+impl WmCapabilities {
+    /// Returns the supported features, skipping values newer than this crate.
+    pub fn capabilities(&self) -> Vec<WmCapability> {
+        self.capabilities
+            .as_slice()
+            .chunks_exact(WL_TYPE_UINT_LEN)
+            .filter_map(|capability| {
+                WmCapability::try_from(Endian::NATIVE.decode_u32_at(capability)).ok()
+            })
+            .collect()
+    }
+}
+
+impl TryFrom<&[u8]> for WmCapabilities {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `WmCapabilities` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0+: `capabilities` (WlArray) - The supported features
+    fn try_from(buf: &[u8]) -> Result<WmCapabilities> {
+        Ok(WmCapabilities {
+            capabilities: WlArray::try_from(buf)?,
+        })
+    }
+}
+
+/// Handles an `xdg_toplevel.wm_capabilities` event by forwarding it to the listener.
+pub(super) fn handle_xdg_toplevel_wm_capabilities(
+    conn: &Connection,
+    toplevel_id: u32,
+    capabilities: WmCapabilities,
+) -> Result<()> {
+    conn.emit(toplevel_id, ToplevelEvent::WmCapabilities(capabilities));
+
+    Ok(())
+}
@@ -0,0 +1,471 @@
+pub mod event;
+pub mod request;
+
+use std::sync::Arc;
+
+use crate::{
+    connection::{Connection, Listener},
+    error::{Result, anyhow},
+    protocol::{
+        WlObjectId,
+        seat::Seat,
+        types::{WlInt, WlObject, WlString, WlUInt},
+        xdg_surface::XdgSurfaceState,
+    },
+    sync::Lock,
+};
+
+pub use event::configure::State;
+pub use request::ResizeEdge;
+
+use event::{
+    configure::Configure, configure_bounds::ConfigureBounds, wm_capabilities::WmCapabilities,
+};
+use request::Opcode;
+
+/// An event delivered to the handler of an [`XdgToplevel`].
+pub enum ToplevelEvent {
+    /// The compositor suggests a new size and state.
+    ///
+    /// Applies once the `xdg_surface.configure` ending the sequence arrives.
+    Configure(Configure),
+    /// The user asked to close the window.
+    Close,
+    /// The recommended bounds of the window size.
+    ConfigureBounds(ConfigureBounds),
+    /// The window management features supported by the compositor.
+    WmCapabilities(WmCapabilities),
+}
+
+/// The window configuration requested by one configure sequence, i.e. the
+/// toplevel events followed by `xdg_surface.configure`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ToplevelConfigure {
+    /// The serial of the `xdg_surface.configure` that ended the sequence.
+    pub serial: u32,
+    /// The suggested width in window geometry coordinates, or 0 to let the
+    /// client decide.
+    pub width: i32,
+    /// The suggested height in window geometry coordinates, or 0 to let the
+    /// client decide.
+    pub height: i32,
+    /// The active window states.
+    pub states: Vec<State>,
+    /// The recommended maximum size, if the compositor announced one.
+    pub bounds: Option<(i32, i32)>,
+}
+
+impl ToplevelConfigure {
+    /// Returns `true` if `state` is active.
+    pub fn has_state(&self, state: State) -> bool {
+        self.states.contains(&state)
+    }
+
+    /// Returns the active states as a [`WindowState`].
+    pub fn window_state(&self) -> WindowState {
+        WindowState::from_states(&self.states)
+    }
+}
+
+/// The edges of a window touching another window or the screen edge, from
+/// the `tiled_*` states.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TiledEdges {
+    /// The left edge is tiled.
+    pub left: bool,
+    /// The right edge is tiled.
+    pub right: bool,
+    /// The top edge is tiled.
+    pub top: bool,
+    /// The bottom edge is tiled.
+    pub bottom: bool,
+}
+
+impl TiledEdges {
+    /// Returns `true` if any edge is tiled.
+    pub fn any(&self) -> bool {
+        self.left || self.right || self.top || self.bottom
+    }
+}
+
+/// The state of a window as configured by the compositor, which decides
+/// how it should be drawn, e.g. without shadows when maximized.
+///
+/// The protocol has no minimized state; a minimized window is usually
+/// reported as [`WindowState::suspended`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WindowState {
+    /// The window is maximized.
+    pub maximized: bool,
+    /// The window is fullscreen.
+    pub fullscreen: bool,
+    /// The window has keyboard focus and should look active.
+    pub activated: bool,
+    /// The window is being interactively resized.
+    pub resizing: bool,
+    /// The edges touching another window or the screen edge (since
+    /// version 2).
+    pub tiled_edges: TiledEdges,
+    /// The window is not visible and may stop drawing (since version 6).
+    pub suspended: bool,
+}
+
+impl WindowState {
+    /// Collects the states listed by an `xdg_toplevel.configure` event.
+    pub fn from_states(states: &[State]) -> WindowState {
+        let mut window_state = WindowState::default();
+        for state in states {
+            match state {
+                State::Maximized => window_state.maximized = true,
+                State::Fullscreen => window_state.fullscreen = true,
+                State::Resizing => window_state.resizing = true,
+                State::Activated => window_state.activated = true,
+                State::TiledLeft => window_state.tiled_edges.left = true,
+                State::TiledRight => window_state.tiled_edges.right = true,
+                State::TiledTop => window_state.tiled_edges.top = true,
+                State::TiledBottom => window_state.tiled_edges.bottom = true,
+                State::Suspended => window_state.suspended = true,
+            }
+        }
+
+        window_state
+    }
+}
+
+/// State of a toplevel updated by its events.
+#[derive(Default)]
+pub(crate) struct ToplevelState {
+    /// The configuration accumulated from the toplevel events of the
+    /// current configure sequence.
+    building: Lock<ToplevelConfigure>,
+    /// The latest completed configuration not taken by the application yet.
+    ready: Lock<Option<ToplevelConfigure>>,
+}
+
+impl ToplevelState {
+    /// Completes the current configure sequence.
+    ///
+    /// A completed configuration the application did not take yet is
+    /// replaced, so a burst of configures coalesces into the last one.
+    pub(crate) fn finish_configure(&self, serial: u32) {
+        let mut configure = self.building.borrow().clone();
+        configure.serial = serial;
+        *self.ready.borrow_mut() = Some(configure);
+    }
+}
+
+/// An `xdg_toplevel`, a regular desktop window.
+///
+/// Created with
+/// [`XdgSurface::get_toplevel`](crate::protocol::xdg_surface::XdgSurface::get_toplevel).
+pub struct XdgToplevel {
+    /// The object ID of the toplevel.
+    id: u32,
+    /// The interface version, inherited from `xdg_wm_base`.
+    version: u32,
+    /// The connection the toplevel was created on.
+    conn: Connection,
+    /// The state shared with the event handlers.
+    state: Arc<ToplevelState>,
+    /// The state of the xdg_surface the toplevel was created from.
+    xdg_surface: Arc<XdgSurfaceState>,
+}
+
+impl XdgToplevel {
+    /// Wraps a toplevel object that was just created on `conn`.
+    pub(crate) fn new(
+        id: u32,
+        version: u32,
+        conn: &Connection,
+        xdg_surface: Arc<XdgSurfaceState>,
+        handler: Listener<ToplevelEvent>,
+    ) -> XdgToplevel {
+        let state = Arc::new(ToplevelState::default());
+        conn.set_object_data(id, state.clone());
+        conn.set_listener(id, handler);
+
+        XdgToplevel {
+            id,
+            version,
+            conn: conn.clone(),
+            state,
+            xdg_surface,
+        }
+    }
+
+    /// Returns the state shared with the event handlers.
+    pub(crate) fn state(&self) -> Arc<ToplevelState> {
+        self.state.clone()
+    }
+
+    /// Returns the connection the toplevel was created on.
+    pub(crate) fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    /// Returns the object ID of the toplevel.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the negotiated interface version.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Returns the latest configuration requested by the compositor that was
+    /// not taken yet, without taking it.
+    pub fn pending_configure(&self) -> Option<ToplevelConfigure> {
+        self.state.ready.borrow().clone()
+    }
+
+    /// Takes the latest configuration requested by the compositor.
+    ///
+    /// When several configure sequences arrived since the last call, only
+    /// the last one is returned. Its serial is acknowledged automatically on
+    /// the next [`Surface::commit`](crate::protocol::surface::Surface::commit),
+    /// which must carry content matching the configuration.
+    pub fn take_configure(&self) -> Option<ToplevelConfigure> {
+        let configure = self.state.ready.take()?;
+        self.xdg_surface.ack_on_commit(configure.serial);
+
+        Some(configure)
+    }
+
+    /// Makes the window a child of `parent`, such as a dialog of its main
+    /// window, stacked above it; `None` unsets the parent.
+    ///
+    /// # Errors
+    /// Returns an error, without sending the request, if `parent` is the
+    /// toplevel itself or belongs to another connection.
+    pub fn set_parent(&self, parent: Option<&XdgToplevel>) -> Result<()> {
+        let parent = match parent {
+            Some(parent) => {
+                self.conn.check_same_connection(
+                    parent.connection(),
+                    WlObjectId::XdgToplevel,
+                    parent.id(),
+                )?;
+                if parent.id == self.id {
+                    return Err(anyhow!("xdg_toplevel {} cannot be its own parent", self.id));
+                }
+                parent.id
+            }
+            None => 0,
+        };
+
+        request::set_parent(&self.conn, self.id, WlObject(parent))
+    }
+
+    /// Sets the window title shown by task bars and window lists.
+    pub fn set_title(&self, title: &str) -> Result<()> {
+        request::set_title(&self.conn, self.id, WlString::new(title)?)
+    }
+
+    /// Sets the application identifier, typically the desktop file name.
+    pub fn set_app_id(&self, app_id: &str) -> Result<()> {
+        request::set_app_id(&self.conn, self.id, WlString::new(app_id)?)
+    }
+
+    /// Starts an interactive move of the window, driven by the compositor
+    /// until the user releases the button or lifts the finger.
+    ///
+    /// `serial` is the serial of the pointer button press or touch down
+    /// that started the move, e.g. on a title bar drawn by the client.
+    pub fn start_move(&self, seat: &Seat, serial: u32) -> Result<()> {
+        request::r#move(&self.conn, self.id, WlObject(seat.id()), WlUInt(serial))
+    }
+
+    /// Starts an interactive resize of the window by dragging `edge`.
+    ///
+    /// `serial` is the serial of the pointer button press or touch down
+    /// that started the resize. The configures that follow carry the
+    /// [`State::Resizing`] state until the resize ends.
+    pub fn start_resize(&self, seat: &Seat, serial: u32, edge: ResizeEdge) -> Result<()> {
+        request::resize(
+            &self.conn,
+            self.id,
+            WlObject(seat.id()),
+            WlUInt(serial),
+            WlUInt(edge as u32),
+        )
+    }
+
+    /// Sets the maximum window size; 0 means no limit. Applies on the next commit.
+    pub fn set_max_size(&self, width: i32, height: i32) -> Result<()> {
+        request::set_size_limit(
+            &self.conn,
+            self.id,
+            Opcode::SetMaxSize,
+            WlInt(width),
+            WlInt(height),
+        )
+    }
+
+    /// Sets the minimum window size; 0 means no limit. Applies on the next commit.
+    pub fn set_min_size(&self, width: i32, height: i32) -> Result<()> {
+        request::set_size_limit(
+            &self.conn,
+            self.id,
+            Opcode::SetMinSize,
+            WlInt(width),
+            WlInt(height),
+        )
+    }
+
+    /// Asks the compositor to maximize the window.
+    pub fn set_maximized(&self) -> Result<()> {
+        request::set_state(&self.conn, self.id, Opcode::SetMaximized)
+    }
+
+    /// Asks the compositor to restore the window from maximized.
+    pub fn unset_maximized(&self) -> Result<()> {
+        request::set_state(&self.conn, self.id, Opcode::UnsetMaximized)
+    }
+
+    /// Asks the compositor to make the window fullscreen.
+    ///
+    /// # Arguments
+    /// * `output` - The ID of the preferred `wl_output`, or `None` to let the
+    ///   compositor choose
+    pub fn set_fullscreen(&self, output: Option<u32>) -> Result<()> {
+        request::set_fullscreen(&self.conn, self.id, WlObject(output.unwrap_or(0)))
+    }
+
+    /// Asks the compositor to restore the window from fullscreen.
+    pub fn unset_fullscreen(&self) -> Result<()> {
+        request::set_state(&self.conn, self.id, Opcode::UnsetFullscreen)
+    }
+
+    /// Asks the compositor to minimize the window.
+    pub fn set_minimized(&self) -> Result<()> {
+        request::set_state(&self.conn, self.id, Opcode::SetMinimized)
+    }
+
+    /// Destroys the toplevel, unmapping the window.
+    ///
+    /// Dropping the handle does the same.
+    pub fn destroy(self) -> Result<()> {
+        // Dropping the handle sends the request
+        drop(self);
+        Ok(())
+    }
+}
+
+impl Drop for XdgToplevel {
+    fn drop(&mut self) {
+        self.conn.remove_listener(self.id);
+
+        self.conn
+            .destroy_on_drop(|conn| request::destroy(conn, self.id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        os::unix::net::UnixStream,
+    };
+
+    use super::*;
+    use crate::protocol::{
+        endian::Endian,
+        message::{WL_MESSAGE_HEADER_LEN, WlMessage, WlMessageHeader},
+        registry::event::global::Global,
+        surface::Surface,
+        xdg_surface,
+        xdg_wm_base::XdgWmBase,
+    };
+
+    #[test]
+    fn window_state_collects_the_configured_states() {
+        let configure = ToplevelConfigure {
+            states: vec![State::Activated, State::TiledLeft, State::TiledTop],
+            ..Default::default()
+        };
+        let state = configure.window_state();
+        assert!(state.activated && !state.maximized && !state.fullscreen);
+        assert_eq!(
+            state.tiled_edges,
+            TiledEdges {
+                left: true,
+                top: true,
+                ..Default::default()
+            }
+        );
+        assert!(state.tiled_edges.any());
+
+        let state =
+            WindowState::from_states(&[State::Maximized, State::Resizing, State::Suspended]);
+        assert!(state.maximized && state.resizing && state.suspended);
+        assert!(!state.tiled_edges.any());
+        assert_eq!(WindowState::from_states(&[]), WindowState::default());
+    }
+
+    #[test]
+    fn only_the_last_configure_of_a_burst_is_applied_and_acked() {
+        let (client, mut compositor) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+        conn.insert_global(Global {
+            name: WlUInt(1),
+            interface: WlString::new("xdg_wm_base").unwrap(),
+            version: WlUInt(6),
+        });
+        let wm_base = XdgWmBase::bind(&conn).unwrap();
+        let surface = Surface::new(conn.new_object(WlObjectId::Surface, 6).unwrap(), &conn);
+        let xdg_surface = wm_base.get_xdg_surface(&surface, |_, _| {}).unwrap();
+        let toplevel = xdg_surface.get_toplevel(|_, _| {}).unwrap();
+
+        // Two configure sequences, the second one activating the window
+        let words =
+            |args: &[u32]| -> Vec<u8> { args.iter().flat_map(|arg| arg.to_ne_bytes()).collect() };
+        let mut events = Vec::new();
+        for (serial, width, states) in [(7, 100, &[][..]), (8, 200, &[State::Activated as u32][..])]
+        {
+            let mut args = vec![width, 80, (states.len() * 4) as u32];
+            args.extend(states);
+            events.extend(Vec::<u8>::from(
+                WlMessage::new(toplevel.id(), 0, &words(&args)).unwrap(),
+            ));
+            events.extend(Vec::<u8>::from(
+                WlMessage::new(xdg_surface.id(), 0, &words(&[serial])).unwrap(),
+            ));
+        }
+        compositor.write_all(&events).unwrap();
+        conn.dispatch().unwrap();
+
+        assert_eq!(
+            toplevel
+                .pending_configure()
+                .map(|configure| configure.serial),
+            Some(8)
+        );
+        let configure = toplevel.take_configure().unwrap();
+        assert_eq!(
+            (configure.serial, configure.width, configure.height),
+            (8, 200, 80)
+        );
+        assert!(configure.window_state().activated);
+        assert_eq!(toplevel.take_configure(), None);
+        surface.commit().unwrap();
+        surface.commit().unwrap();
+
+        let xdg_surface_id = xdg_surface.id();
+        drop((toplevel, xdg_surface, surface, wm_base, conn));
+        let mut received = Vec::new();
+        compositor.read_to_end(&mut received).unwrap();
+        let mut acks = Vec::new();
+        while !received.is_empty() {
+            let header = WlMessageHeader::decode(&received, Endian::NATIVE).unwrap();
+            if header.object_id == xdg_surface_id
+                && header.opcode == xdg_surface::request::Opcode::AckConfigure as u16
+            {
+                let serial = &received[WL_MESSAGE_HEADER_LEN..WL_MESSAGE_HEADER_LEN + 4];
+                acks.push(u32::from_ne_bytes(serial.try_into().unwrap()));
+            }
+            received.drain(..header.size as usize);
+        }
+        assert_eq!(acks, [8]);
+    }
+}
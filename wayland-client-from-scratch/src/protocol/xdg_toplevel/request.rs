@@ -0,0 +1,438 @@
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::types::{WlInt, WlObject, WlString, WlUInt},
+    wl_enum, wl_request_opcode, wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to an `xdg_toplevel` object.
+    Opcode {
+        /// Destroys the toplevel, unmapping the window.
+        Destroy = 0 (destructor),
+
+        /// Sets the parent window of a dialog.
+        SetParent = 1,
+
+        /// Sets the window title.
+        SetTitle = 2,
+
+        /// Sets the application identifier.
+        SetAppId = 3,
+
+        /// Shows the window menu of the compositor.
+        ShowWindowMenu = 4,
+
+        /// Starts an interactive move.
+        Move = 5,
+
+        /// Starts an interactive resize.
+        Resize = 6,
+
+        /// Sets the maximum window size.
+        SetMaxSize = 7,
+
+        /// Sets the minimum window size.
+        SetMinSize = 8,
+
+        /// Maximizes the window.
+        SetMaximized = 9,
+
+        /// Restores the window from maximized.
+        UnsetMaximized = 10,
+
+        /// Makes the window fullscreen.
+        SetFullscreen = 11,
+
+        /// Restores the window from fullscreen.
+        UnsetFullscreen = 12,
+
+        /// Minimizes the window.
+        SetMinimized = 13,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `xdg_toplevel.set_parent` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_parent">
+    ///   <description summary="set the parent of this surface">
+    ///     Set the "parent" of this surface. This surface should be stacked
+    ///     above the parent surface and all other ancestor surfaces.
+    ///
+    ///     Parent surfaces should be set on dialogs, toolboxes, or other
+    ///     "auxiliary" surfaces, so that the parent is raised when the dialog
+    ///     is raised.
+    ///
+    ///     Setting a null parent for a child surface unsets its parent. Setting
+    ///     a null parent for a surface which currently has no parent is a no-op.
+    ///
+    ///     Only mapped surfaces can have child surfaces. Setting a parent which
+    ///     is not mapped is equivalent to setting a null parent. If a surface
+    ///     becomes unmapped, its children's parent is set to the parent of
+    ///     the now-unmapped surface. If the now-unmapped surface has no parent,
+    ///     its children's parent is unset. If the now-unmapped surface becomes
+    ///     mapped again, its parent-child relationship is not restored.
+    ///
+    ///     The parent toplevel must not be one of the child toplevel's
+    ///     descendants, and the parent must be different from the child toplevel,
+    ///     otherwise the invalid_parent protocol error is raised.
+    ///   </description>
+    ///   <arg name="parent" type="object" interface="xdg_toplevel" allow-null="true"/>
+    /// </request>
+    /// ```
+    SetParentParam {
+        /// The parent toplevel, or 0 to unset the parent.
+        parent: WlObject,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `xdg_toplevel.set_title` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_title">
+    ///   <description summary="set surface title">
+    ///     Set a short title for the surface.
+    ///
+    ///     This string may be used to identify the surface in a task bar,
+    ///     window list, or other user interface elements provided by the
+    ///     compositor.
+    ///
+    ///     The string must be encoded in UTF-8.
+    ///   </description>
+    ///   <arg name="title" type="string"/>
+    /// </request>
+    /// ```
+    SetTitleParam {
+        /// The window title.
+        title: WlString,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `xdg_toplevel.set_app_id` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_app_id">
+    ///   <description summary="set application ID">
+    ///     Set an application identifier for the surface.
+    ///
+    ///     The app ID identifies the general class of applications to which
+    ///     the surface belongs. The compositor can use this to group multiple
+    ///     surfaces together, or to determine how to launch a new application.
+    ///
+    ///     For D-Bus activatable applications, the app ID is used as the D-Bus
+    ///     service name.
+    ///   </description>
+    ///   <arg name="app_id" type="string"/>
+    /// </request>
+    /// ```
+    SetAppIdParam {
+        /// The application identifier, typically the desktop file name.
+        app_id: WlString,
+    }
+}
+
+wl_enum! {
+    /// The edge or corner of a window being dragged by an interactive
+    /// resize, see `xdg_toplevel.resize`.
+    ResizeEdge {
+        /// No edge.
+        None = 0,
+        /// The top edge.
+        Top = 1,
+        /// The bottom edge.
+        Bottom = 2,
+        /// The left edge.
+        Left = 4,
+        /// The top left corner.
+        TopLeft = 5,
+        /// The bottom left corner.
+        BottomLeft = 6,
+        /// The right edge.
+        Right = 8,
+        /// The top right corner.
+        TopRight = 9,
+        /// The bottom right corner.
+        BottomRight = 10,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `xdg_toplevel.move` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="move">
+    ///   <description summary="start an interactive move">
+    ///     Start an interactive, user-driven move of the surface.
+    ///
+    ///     This request must be used in response to some sort of user action
+    ///     like a button press, key press, or touch down event. The passed
+    ///     serial is used to determine the type of interactive move (touch,
+    ///     pointer, etc).
+    ///
+    ///     The server may ignore move requests depending on the state of
+    ///     the surface (e.g. fullscreen or maximized), or if the passed serial
+    ///     is no longer valid.
+    ///
+    ///     If triggered, the surface will lose the focus of the device
+    ///     (wl_pointer, wl_touch, etc) used for the move. It is up to the
+    ///     compositor to visually indicate that the move is taking place, such as
+    ///     updating a pointer cursor, during the move. There is no guarantee
+    ///     that the device focus will return when the move is completed.
+    ///   </description>
+    ///   <arg name="seat" type="object" interface="wl_seat" summary="the wl_seat of the user event"/>
+    ///   <arg name="serial" type="uint" summary="the serial of the user event"/>
+    /// </request>
+    /// ```
+    MoveParam {
+        /// The seat of the user event.
+        seat: WlObject,
+        /// The serial of the user event.
+        serial: WlUInt,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `xdg_toplevel.resize` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="resize">
+    ///   <description summary="start an interactive resize">
+    ///     Start a user-driven, interactive resize of the surface.
+    ///
+    ///     This request must be used in response to some sort of user action
+    ///     like a button press, key press, or touch down event. The passed
+    ///     serial is used to determine the type of interactive resize (touch,
+    ///     pointer, etc).
+    ///
+    ///     The server may ignore resize requests depending on the state of
+    ///     the surface (e.g. fullscreen or maximized).
+    ///
+    ///     If triggered, the client will receive configure events with the
+    ///     "resize" state enum value and the expected sizes. See the "resize"
+    ///     enum value for more details about what is required. The client
+    ///     must also acknowledge configure events using "ack_configure". After
+    ///     the resize is completed, the client will receive another "configure"
+    ///     event without the resize state.
+    ///
+    ///     If triggered, the surface also will lose the focus of the device
+    ///     (wl_pointer, wl_touch, etc) used for the resize. It is up to the
+    ///     compositor to visually indicate that the resize is taking place,
+    ///     such as updating a pointer cursor, during the resize. There is no
+    ///     guarantee that the device focus will return when the resize is
+    ///     completed.
+    ///
+    ///     The edges parameter specifies how the surface should be resized, and
+    ///     is one of the values of the resize_edge enum. Values not matching
+    ///     a variant of the enum will cause the invalid_resize_edge protocol error.
+    ///     The compositor may use this information to update the surface position
+    ///     for example when dragging the top left corner. The compositor may also
+    ///     use this information to adapt its behavior, e.g. choose an appropriate
+    ///     cursor image.
+    ///   </description>
+    ///   <arg name="seat" type="object" interface="wl_seat" summary="the wl_seat of the user event"/>
+    ///   <arg name="serial" type="uint" summary="the serial of the user event"/>
+    ///   <arg name="edges" type="uint" enum="resize_edge" summary="which edge or corner is being dragged"/>
+    /// </request>
+    /// ```
+    ResizeParam {
+        /// The seat of the user event.
+        seat: WlObject,
+        /// The serial of the user event.
+        serial: WlUInt,
+        /// The dragged edge or corner, a `ResizeEdge` value.
+        edges: WlUInt,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `xdg_toplevel.set_max_size` and
+    /// `xdg_toplevel.set_min_size` requests, which share their signature.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_max_size">
+    ///   <description summary="set the maximum size">
+    ///     Set a maximum size for the window.
+    ///
+    ///     The client can specify a maximum size so that the compositor does
+    ///     not try to configure the window beyond this size.
+    ///
+    ///     The width and height arguments are in window geometry coordinates.
+    ///     See xdg_surface.set_window_geometry.
+    ///
+    ///     Values set in this way are double-buffered, see wl_surface.commit.
+    ///
+    ///     A value of zero in width or height means no limit.
+    ///   </description>
+    ///   <arg name="width" type="int"/>
+    ///   <arg name="height" type="int"/>
+    /// </request>
+    /// ```
+    SetSizeParam {
+        /// The size limit width, or 0 for no limit.
+        width: WlInt,
+        /// The size limit height, or 0 for no limit.
+        height: WlInt,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `xdg_toplevel.set_fullscreen` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="set_fullscreen">
+    ///   <description summary="set the window as fullscreen on an output">
+    ///     Make the surface fullscreen.
+    ///
+    ///     After requesting that the surface should be fullscreened, the
+    ///     compositor will respond by emitting a configure event. Whether the
+    ///     client is actually put into a fullscreen state is subject to compositor
+    ///     policies. The client must also acknowledge the configure when
+    ///     committing the new content (see ack_configure).
+    ///
+    ///     The output passed by the request indicates the client's preference as
+    ///     to which display it should be set fullscreen on. If this value is NULL,
+    ///     it's up to the compositor to choose which display will be used to map
+    ///     this surface.
+    ///   </description>
+    ///   <arg name="output" type="object" interface="wl_output" allow-null="true"/>
+    /// </request>
+    /// ```
+    SetFullscreenParam {
+        /// The preferred output, or 0 to let the compositor choose.
+        output: WlObject,
+    }
+}
+
+/// Sends an `xdg_toplevel.destroy` request to the compositor.
+pub fn destroy(conn: &Connection, toplevel: u32) -> Result<()> {
+    conn.send_request(toplevel, Opcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends an `xdg_toplevel.set_parent` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `toplevel` - The ID of the toplevel
+/// * `parent` - The parent toplevel, `WlObject(0)` to unset the parent
+pub fn set_parent(conn: &Connection, toplevel: u32, parent: WlObject) -> Result<()> {
+    conn.send_request(toplevel, Opcode::SetParent, &SetParentParam::new(parent))?;
+
+    Ok(())
+}
+
+/// Sends an `xdg_toplevel.set_title` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `toplevel` - The ID of the toplevel
+/// * `title` - The window title
+pub fn set_title(conn: &Connection, toplevel: u32, title: WlString) -> Result<()> {
+    conn.send_request(toplevel, Opcode::SetTitle, &SetTitleParam::new(title))?;
+
+    Ok(())
+}
+
+/// Sends an `xdg_toplevel.set_app_id` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `toplevel` - The ID of the toplevel
+/// * `app_id` - The application identifier
+pub fn set_app_id(conn: &Connection, toplevel: u32, app_id: WlString) -> Result<()> {
+    conn.send_request(toplevel, Opcode::SetAppId, &SetAppIdParam::new(app_id))?;
+
+    Ok(())
+}
+
+/// Sends an `xdg_toplevel.move` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `toplevel` - The ID of the toplevel
+/// * `seat` - The seat of the user event
+/// * `serial` - The serial of the user event
+pub fn r#move(conn: &Connection, toplevel: u32, seat: WlObject, serial: WlUInt) -> Result<()> {
+    conn.send_request(toplevel, Opcode::Move, &MoveParam::new(seat, serial))?;
+
+    Ok(())
+}
+
+/// Sends an `xdg_toplevel.resize` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `toplevel` - The ID of the toplevel
+/// * `seat` - The seat of the user event
+/// * `serial` - The serial of the user event
+/// * `edges` - The dragged edge or corner
+pub fn resize(
+    conn: &Connection,
+    toplevel: u32,
+    seat: WlObject,
+    serial: WlUInt,
+    edges: WlUInt,
+) -> Result<()> {
+    conn.send_request(
+        toplevel,
+        Opcode::Resize,
+        &ResizeParam::new(seat, serial, edges),
+    )?;
+
+    Ok(())
+}
+
+/// Sends an `xdg_toplevel.set_max_size` or `xdg_toplevel.set_min_size` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `toplevel` - The ID of the toplevel
+/// * `opcode` - Either `Opcode::SetMaxSize` or `Opcode::SetMinSize`
+/// * `width`, `height` - The size limit, 0 for no limit
+pub fn set_size_limit(
+    conn: &Connection,
+    toplevel: u32,
+    opcode: Opcode,
+    width: WlInt,
+    height: WlInt,
+) -> Result<()> {
+    conn.send_request(toplevel, opcode, &SetSizeParam::new(width, height))?;
+
+    Ok(())
+}
+
+/// Sends an `xdg_toplevel.set_fullscreen` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `toplevel` - The ID of the toplevel
+/// * `output` - The preferred output, `WlObject(0)` to let the compositor choose
+pub fn set_fullscreen(conn: &Connection, toplevel: u32, output: WlObject) -> Result<()> {
+    conn.send_request(
+        toplevel,
+        Opcode::SetFullscreen,
+        &SetFullscreenParam::new(output),
+    )?;
+
+    Ok(())
+}
+
+/// Sends one of the argument-less state requests (`set_maximized`,
+/// `unset_maximized`, `unset_fullscreen`, `set_minimized`) to the compositor.
+pub fn set_state(conn: &Connection, toplevel: u32, opcode: Opcode) -> Result<()> {
+    conn.send_request(toplevel, opcode, &())?;
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+pub mod ping;
+
+use crate::{
+    connection::Connection,
+    error::{Error, Result, anyhow},
+    protocol::message::WlMessage,
+};
+
+/// Represents the event types that can be emitted by the `xdg_wm_base` object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    /// The compositor checks whether the client is alive.
+    ///
+    /// # Event Arguments
+    /// - `serial`: The serial to answer with
+    Ping = 0,
+}
+
+impl TryFrom<u16> for Opcode {
+    type Error = Error;
+
+    /// Attempts to convert a raw opcode value into a structured `XdgWmBaseEvent`.
+    fn try_from(value: u16) -> Result<Opcode> {
+        match value {
+            0 => Ok(Opcode::Ping),
+            _ => Err(anyhow!("Invalid xdg_wm_base event opcode: {}", value)),
+        }
+    }
+}
+
+impl Opcode {
+    /// Returns the interface version the event was introduced in.
+    pub fn since(self) -> u32 {
+        match self {
+            Opcode::Ping => 1,
+        }
+    }
+}
+
+/// A decoded `xdg_wm_base` event, with its arguments.
+#[derive(Debug)]
+pub enum Event {
+    /// The compositor checks whether the client is alive.
+    Ping(ping::Ping),
+}
+
+impl Event {
+    /// Decodes a `xdg_wm_base` event from its message.
+    ///
+    /// # Errors
+    /// Returns an error if the opcode is unknown or the arguments are malformed.
+    pub fn decode(_conn: &Connection, msg: &WlMessage) -> Result<Event> {
+        let buf: &[u8] = &msg.data;
+
+        Ok(match Opcode::try_from(msg.header.opcode)? {
+            Opcode::Ping => Event::Ping(ping::Ping::try_from(buf)?),
+        })
+    }
+
+    /// Returns the opcode of the event.
+    pub fn opcode(&self) -> Opcode {
+        match self {
+            Event::Ping(_) => Opcode::Ping,
+        }
+    }
+}
+
+/// Dispatches incoming `xdg_wm_base` events to their appropriate handler functions.
+///
+/// # Event Routing
+/// * `Ping` events are routed to `ping::handle_xdg_wm_base_ping`
+pub fn handle_xdg_wm_base_event(conn: &Connection, msg: WlMessage) -> Result<()> {
+    // Route the event to the appropriate handler based on type
+    match Event::decode(conn, &msg)? {
+        Event::Ping(event) => ping::handle_xdg_wm_base_ping(conn, msg.header.object_id, event),
+    }
+}
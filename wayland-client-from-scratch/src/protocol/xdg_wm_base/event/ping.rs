@@ -0,0 +1,57 @@
+use crate::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::{types::WlUInt, xdg_wm_base::request},
+};
+
+/// Represents an `xdg_wm_base.ping` event.
+///
+/// # Specification Reference
+/// ```xml
+/// <event name="ping">
+///   <description summary="check if the client is alive">
+///     The ping event asks the client if it's still alive. Pass the
+///     serial specified in the event back to the compositor by sending
+///     a "pong" request back with the specified serial. See xdg_wm_base.pong.
+///
+///     Compositors can use this to determine if the client is still
+///     alive. It's unspecified what will happen if the client doesn't
+///     respond to the ping request, or in what timeframe. Clients should
+///     try to respond in a reasonable amount of time. The “unresponsive”
+///     error is provided for compositors that wish to disconnect unresponsive
+///     clients.
+///
+///     A compositor is free to ping in any way it wants, but a client must
+///     always respond to any xdg_wm_base object it created.
+///   </description>
+///   <arg name="serial" type="uint" summary="pass this to the pong request"/>
+/// </event>
+/// ```
+#[derive(Debug)]
+pub struct Ping {
+    /// The serial to pass back with the pong request.
+    pub serial: WlUInt,
+}
+
+impl TryFrom<&[u8]> for Ping {
+    type Error = Error;
+
+    /// Parses a raw byte buffer into a structured `Ping` event.
+    ///
+    /// # Buffer Layout
+    /// - Bytes 0-3: `serial` (u32) - The serial to answer with
+    fn try_from(buf: &[u8]) -> Result<Ping> {
+        Ok(Ping {
+            serial: WlUInt::read(buf, 0)?,
+        })
+    }
+}
+
+/// Handles an `xdg_wm_base.ping` event by answering with a pong right away.
+pub(super) fn handle_xdg_wm_base_ping(
+    conn: &Connection,
+    wm_base_id: u32,
+    ping: Ping,
+) -> Result<()> {
+    request::pong(conn, wm_base_id, ping.serial)
+}
@@ -0,0 +1,107 @@
+pub mod event;
+pub mod request;
+
+use crate::{
+    connection::Connection,
+    error::{Result, anyhow},
+    protocol::{
+        WlObjectId,
+        surface::{Surface, SurfaceRole},
+        types::{WlNewId, WlObject},
+        xdg_positioner::XdgPositioner,
+        xdg_surface::{XdgSurface, event::configure::Configure},
+    },
+};
+
+/// The highest `xdg_wm_base` version this crate implements.
+pub const XDG_WM_BASE_VERSION: u32 = 6;
+
+/// A bound `xdg_wm_base` global, which turns surfaces into desktop windows.
+///
+/// Pings from the compositor are answered automatically while events are
+/// dispatched.
+#[derive(Clone)]
+pub struct XdgWmBase {
+    /// The object ID of the bound global.
+    id: u32,
+    /// The negotiated interface version.
+    version: u32,
+    /// The connection the global was bound on.
+    conn: Connection,
+}
+
+impl XdgWmBase {
+    /// Binds the `xdg_wm_base` global advertised by the compositor.
+    pub fn bind(conn: &Connection) -> Result<XdgWmBase> {
+        let (id, version) = conn.bind(WlObjectId::XdgWmBase, XDG_WM_BASE_VERSION)?;
+
+        Ok(XdgWmBase {
+            id,
+            version,
+            conn: conn.clone(),
+        })
+    }
+
+    /// Returns the object ID of the global.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Creates a positioner, the placement rules of a popup.
+    pub fn create_positioner(&self) -> Result<XdgPositioner> {
+        let id = self
+            .conn
+            .new_object(WlObjectId::XdgPositioner, self.version)?;
+        request::create_positioner(&self.conn, self.id, WlNewId(id))?;
+
+        Ok(XdgPositioner::new(id, self.version, &self.conn))
+    }
+
+    /// Creates the `xdg_surface` of `surface`.
+    ///
+    /// `handler` receives every `xdg_surface.configure` event, which ends a
+    /// sequence of role-specific configure events and must be acknowledged
+    /// with [`XdgSurface::ack_configure`].
+    ///
+    /// # Errors
+    /// Returns an error if `surface` already has an `xdg_surface`, another
+    /// role or a committed buffer.
+    pub fn get_xdg_surface<F>(&self, surface: &Surface, handler: F) -> Result<XdgSurface>
+    where
+        F: FnMut(&Connection, Configure) + Send + 'static,
+    {
+        self.conn
+            .check_same_connection(surface.connection(), WlObjectId::Surface, surface.id())?;
+
+        let surface_state = surface.state();
+        if surface_state.xdg_surface.borrow().is_some() {
+            return Err(anyhow!(
+                "wl_surface {} already has an xdg_surface",
+                surface.id()
+            ));
+        }
+        if surface_state.has_buffer() {
+            return Err(anyhow!(
+                "wl_surface {} must not have a buffer when its xdg_surface is created",
+                surface.id()
+            ));
+        }
+        surface_state.assign_role(surface.id(), SurfaceRole::XdgSurface)?;
+
+        let id = self.conn.new_object(WlObjectId::XdgSurface, self.version)?;
+        request::get_xdg_surface(&self.conn, self.id, WlNewId(id), WlObject(surface.id()))?;
+
+        Ok(XdgSurface::new(
+            id,
+            self.version,
+            &self.conn,
+            surface,
+            Box::new(handler),
+        ))
+    }
+
+    /// Destroys the global. Surfaces created from it must be destroyed first.
+    pub fn destroy(self) -> Result<()> {
+        request::destroy(&self.conn, self.id)
+    }
+}
@@ -0,0 +1,153 @@
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::types::{WlNewId, WlObject, WlUInt},
+    wl_request_opcode, wl_request_param,
+};
+
+wl_request_opcode! {
+    /// Represents the request types that can be sent to the `xdg_wm_base` object.
+    Opcode {
+        /// Destroys the `xdg_wm_base` object.
+        Destroy = 0 (destructor),
+
+        /// Creates an `xdg_positioner` for placing popups.
+        CreatePositioner = 1,
+
+        /// Assigns the `xdg_surface` role to a surface.
+        GetXdgSurface = 2,
+
+        /// Answers a ping event.
+        Pong = 3,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `xdg_wm_base.create_positioner` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="create_positioner">
+    ///   <description summary="create a positioner object">
+    ///     Create a positioner object. A positioner object is used to position
+    ///     surfaces relative to some parent surface. See the interface description
+    ///     and xdg_surface.get_popup for details.
+    ///   </description>
+    ///   <arg name="id" type="new_id" interface="xdg_positioner"/>
+    /// </request>
+    /// ```
+    CreatePositionerParam {
+        /// The object ID to assign to the new xdg_positioner.
+        id: WlNewId,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `xdg_wm_base.get_xdg_surface` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="get_xdg_surface">
+    ///   <description summary="create a shell surface from a surface">
+    ///     This creates an xdg_surface for the given surface. While xdg_surface
+    ///     itself is not a role, the corresponding surface may only be assigned
+    ///     a role extending xdg_surface, such as xdg_toplevel or xdg_popup. It is
+    ///     illegal to create an xdg_surface for a wl_surface which already has an
+    ///     assigned role and this will result in a role error.
+    ///
+    ///     This creates an xdg_surface for the given surface. An xdg_surface is
+    ///     used as basis to define a role to a given surface, such as xdg_toplevel
+    ///     or xdg_popup. It also manages functionality shared between xdg_surface
+    ///     based surface roles.
+    ///
+    ///     See the documentation of xdg_surface for more details about what an
+    ///     xdg_surface is and how it is used.
+    ///   </description>
+    ///   <arg name="id" type="new_id" interface="xdg_surface"/>
+    ///   <arg name="surface" type="object" interface="wl_surface"/>
+    /// </request>
+    /// ```
+    GetXdgSurfaceParam {
+        /// The object ID to assign to the new xdg_surface.
+        id: WlNewId,
+        /// The surface to assign the role to.
+        surface: WlObject,
+    }
+}
+
+wl_request_param! {
+    /// Parameters for the `xdg_wm_base.pong` request.
+    ///
+    /// # Specification Reference
+    /// ```xml
+    /// <request name="pong">
+    ///   <description summary="respond to a ping event">
+    ///     A client must respond to a ping event with a pong request or
+    ///     the client may be deemed unresponsive. See xdg_wm_base.ping
+    ///     and xdg_wm_base.error.unresponsive.
+    ///   </description>
+    ///   <arg name="serial" type="uint" summary="serial of the ping event"/>
+    /// </request>
+    /// ```
+    PongParam {
+        /// The serial of the ping being answered.
+        serial: WlUInt,
+    }
+}
+
+/// Sends an `xdg_wm_base.destroy` request to the compositor.
+pub fn destroy(conn: &Connection, wm_base: u32) -> Result<()> {
+    conn.send_request(wm_base, Opcode::Destroy, &())?;
+
+    Ok(())
+}
+
+/// Sends an `xdg_wm_base.create_positioner` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `wm_base` - The ID of the bound xdg_wm_base
+/// * `id` - The object ID to assign to the new xdg_positioner
+pub fn create_positioner(conn: &Connection, wm_base: u32, id: WlNewId) -> Result<()> {
+    conn.send_request(
+        wm_base,
+        Opcode::CreatePositioner,
+        &CreatePositionerParam::new(id),
+    )?;
+
+    Ok(())
+}
+
+/// Sends an `xdg_wm_base.get_xdg_surface` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `wm_base` - The ID of the bound xdg_wm_base
+/// * `id` - The object ID to assign to the new xdg_surface
+/// * `surface` - The surface to assign the role to
+pub fn get_xdg_surface(
+    conn: &Connection,
+    wm_base: u32,
+    id: WlNewId,
+    surface: WlObject,
+) -> Result<()> {
+    conn.send_request(
+        wm_base,
+        Opcode::GetXdgSurface,
+        &GetXdgSurfaceParam::new(id, surface),
+    )?;
+
+    Ok(())
+}
+
+/// Sends an `xdg_wm_base.pong` request to the compositor.
+///
+/// # Arguments
+/// * `conn` - The connection to queue the request on
+/// * `wm_base` - The ID of the bound xdg_wm_base
+/// * `serial` - The serial of the ping being answered
+pub fn pong(conn: &Connection, wm_base: u32, serial: WlUInt) -> Result<()> {
+    conn.send_request(wm_base, Opcode::Pong, &PongParam::new(serial))?;
+
+    Ok(())
+}
@@ -0,0 +1,282 @@
+use std::sync::{Arc, Weak};
+
+use crate::{
+    connection::Connection,
+    error::Result,
+    protocol::{
+        WlObjectId,
+        fractional_scale::{FRACTIONAL_SCALE_DENOMINATOR, FractionalScale, FractionalScaleManager},
+        output::Output,
+        surface::{Surface, SurfaceState, WL_SURFACE_SET_BUFFER_SCALE_SINCE},
+        viewporter::{Viewport, Viewporter},
+    },
+    sync::{Lock, LockCell},
+};
+
+/// The handler receiving the new effective scale of a surface.
+type ScaleHandler = Box<dyn FnMut(f64) + Send>;
+
+/// The scale inputs of a surface, shared with the event handlers feeding them.
+pub(crate) struct ScaleState {
+    /// The connection the surface was created on.
+    conn: Connection,
+    /// The state of the surface, holding its preferred scale and outputs.
+    surface: Arc<SurfaceState>,
+    /// Whether the surface can declare a buffer scale, which integer scales
+    /// need unless a viewport is used.
+    integer_scale_supported: bool,
+    /// The fractional scale preferred by the compositor, multiplied by 120,
+    /// once announced.
+    fractional: LockCell<Option<u32>>,
+    /// The effective scale computed on the last change.
+    current: LockCell<f64>,
+    /// The handler notified when the effective scale changes, taken out
+    /// while it runs.
+    handler: Lock<Option<ScaleHandler>>,
+}
+
+impl ScaleState {
+    /// Combines the scale inputs, from the most to the least precise: the
+    /// fractional scale, the preferred buffer scale of the surface, and the
+    /// highest scale of the outputs the surface is on.
+    fn compute(&self) -> f64 {
+        if let Some(fractional) = self.fractional.get() {
+            return f64::from(fractional) / f64::from(FRACTIONAL_SCALE_DENOMINATOR);
+        }
+        if !self.integer_scale_supported {
+            return 1.0;
+        }
+        f64::from(self.surface.integer_scale(&self.conn))
+    }
+
+    /// Recomputes the effective scale, notifying the handler if it changed.
+    pub(crate) fn update(&self) {
+        let scale = self.compute();
+        if self.current.replace(scale) == scale {
+            return;
+        }
+
+        let Some(mut handler) = self.handler.borrow_mut().take() else {
+            return;
+        };
+        handler(scale);
+        self.handler.borrow_mut().get_or_insert(handler);
+    }
+}
+
+/// The globals shared by the surfaces a [`ScaleManager`] tracks.
+struct ScaleManagerInner {
+    /// The connection the globals were bound on.
+    conn: Connection,
+    /// The fractional scale manager, bound together with the viewporter.
+    fractional: Option<FractionalScaleManager>,
+    /// The viewporter, which presents buffers of fractionally scaled size.
+    viewporter: Option<Viewporter>,
+    /// The outputs whose scale applies to the surfaces on them.
+    outputs: Vec<Output>,
+    /// The tracked surfaces, updated when an output changes its scale.
+    surfaces: Lock<Vec<Weak<ScaleState>>>,
+}
+
+impl ScaleManagerInner {
+    /// Recomputes the scale of every tracked surface.
+    fn update_all(&self) {
+        let surfaces: Vec<_> = {
+            let mut surfaces = self.surfaces.borrow_mut();
+            surfaces.retain(|surface| surface.strong_count() > 0);
+            surfaces.iter().filter_map(Weak::upgrade).collect()
+        };
+
+        for surface in surfaces {
+            surface.update();
+        }
+    }
+}
+
+/// Tracks the scale surfaces should be rendered at for crisp output on
+/// HiDPI monitors.
+///
+/// Each tracked surface gets one effective scale, combined from the sources
+/// the compositor supports, from the most to the least precise:
+///
+/// - `wp_fractional_scale_v1`, for scales such as 1.5, presented through a
+///   `wp_viewport`; only used when both globals are advertised,
+/// - `wl_surface.preferred_buffer_scale`, since `wl_surface` version 6,
+/// - the highest `wl_output.scale` of the outputs the surface is on.
+///
+/// The manager binds the outputs advertised when it is created, so it
+/// should be created before the surfaces, followed by a roundtrip.
+#[derive(Clone)]
+pub struct ScaleManager {
+    inner: Arc<ScaleManagerInner>,
+}
+
+impl ScaleManager {
+    /// Binds the globals the scale is derived from.
+    ///
+    /// Fractional scaling is used only if the compositor advertises both
+    /// `wp_fractional_scale_manager_v1` and `wp_viewporter`.
+    pub fn new(conn: &Connection) -> Result<ScaleManager> {
+        let globals = conn.globals();
+        let advertised = |interface: WlObjectId| {
+            globals
+                .iter()
+                .any(|global| global.interface.as_str() == interface.interface_name())
+        };
+
+        let (fractional, viewporter) = if advertised(WlObjectId::FractionalScaleManager)
+            && advertised(WlObjectId::Viewporter)
+        {
+            (
+                Some(FractionalScaleManager::bind(conn)?),
+                Some(Viewporter::bind(conn)?),
+            )
+        } else {
+            (None, None)
+        };
+
+        let inner = Arc::new(ScaleManagerInner {
+            conn: conn.clone(),
+            fractional,
+            viewporter,
+            outputs: Output::bind_all(conn)?,
+            surfaces: Lock::new(Vec::new()),
+        });
+
+        for output in &inner.outputs {
+            let manager = Arc::downgrade(&inner);
+            output.set_event_handler(move |_, _| {
+                if let Some(manager) = manager.upgrade() {
+                    manager.update_all();
+                }
+            });
+        }
+
+        Ok(ScaleManager { inner })
+    }
+
+    /// Returns `true` if fractional scales are supported.
+    pub fn is_fractional(&self) -> bool {
+        self.inner.fractional.is_some()
+    }
+
+    /// Returns the outputs the manager bound.
+    pub fn outputs(&self) -> &[Output] {
+        &self.inner.outputs
+    }
+
+    /// Starts tracking the scale of `surface`.
+    ///
+    /// `handler` receives the new effective scale whenever it changes. A
+    /// surface can only be tracked once at a time.
+    pub fn track<F>(&self, surface: &Surface, handler: F) -> Result<SurfaceScale>
+    where
+        F: FnMut(f64) + Send + 'static,
+    {
+        let surface_state = surface.state();
+        let state = Arc::new(ScaleState {
+            conn: self.inner.conn.clone(),
+            surface: surface_state.clone(),
+            integer_scale_supported: surface.version() >= WL_SURFACE_SET_BUFFER_SCALE_SINCE,
+            fractional: LockCell::new(None),
+            current: LockCell::new(1.0),
+            handler: Lock::new(None),
+        });
+        state.current.set(state.compute());
+
+        let (fractional, viewport) = match (&self.inner.fractional, &self.inner.viewporter) {
+            (Some(fractional), Some(viewporter)) => {
+                let weak = Arc::downgrade(&state);
+                let fractional = fractional.get_fractional_scale(surface, move |_, event| {
+                    if let Some(state) = weak.upgrade() {
+                        state.fractional.set(Some(event.scale.get()));
+                        state.update();
+                    }
+                })?;
+
+                (Some(fractional), Some(viewporter.get_viewport(surface)?))
+            }
+            _ => (None, None),
+        };
+
+        *state.handler.borrow_mut() = Some(Box::new(handler));
+        *surface_state.scale.borrow_mut() = Arc::downgrade(&state);
+        self.inner
+            .surfaces
+            .borrow_mut()
+            .push(Arc::downgrade(&state));
+
+        Ok(SurfaceScale {
+            state,
+            fractional,
+            viewport,
+        })
+    }
+}
+
+/// The scale tracking of one surface, created with [`ScaleManager::track`].
+pub struct SurfaceScale {
+    /// The scale inputs of the surface.
+    state: Arc<ScaleState>,
+    /// The fractional scale object, when fractional scaling is supported.
+    fractional: Option<FractionalScale>,
+    /// The viewport presenting the buffers, when fractional scaling is supported.
+    viewport: Option<Viewport>,
+}
+
+impl SurfaceScale {
+    /// Returns the scale the surface should be rendered at.
+    pub fn effective_scale(&self) -> f64 {
+        self.state.current.get()
+    }
+
+    /// Returns the size in pixels of the buffers for a surface of `width` x
+    /// `height` in surface-local coordinates.
+    pub fn buffer_size(&self, width: i32, height: i32) -> (i32, i32) {
+        let scale = self.effective_scale();
+
+        (
+            (f64::from(width) * scale).round() as i32,
+            (f64::from(height) * scale).round() as i32,
+        )
+    }
+
+    /// Returns the viewport presenting the buffers, when fractional scaling
+    /// is supported. A surface has a single viewport, which other users of
+    /// the surface must share.
+    pub(crate) fn viewport(&self) -> Option<&Viewport> {
+        self.viewport.as_ref()
+    }
+
+    /// Tells the compositor how to present buffers of
+    /// [`SurfaceScale::buffer_size`] as a surface of `width` x `height`.
+    ///
+    /// Issues `wp_viewport.set_destination` when fractional scaling is
+    /// supported, `wl_surface.set_buffer_scale` otherwise. Like all surface
+    /// state, it applies on the next commit of `surface`, which must be the
+    /// tracked surface.
+    pub fn apply(&self, surface: &Surface, width: i32, height: i32) -> Result<()> {
+        match &self.viewport {
+            Some(viewport) => viewport.set_destination(width, height),
+            None if self.state.integer_scale_supported => {
+                surface.set_buffer_scale(self.effective_scale() as i32)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Stops tracking the scale. Must be called before the surface is destroyed.
+    pub fn destroy(self) -> Result<()> {
+        self.state.handler.borrow_mut().take();
+        *self.state.surface.scale.borrow_mut() = Weak::new();
+
+        if let Some(viewport) = self.viewport {
+            viewport.destroy()?;
+        }
+        if let Some(fractional) = self.fractional {
+            fractional.destroy()?;
+        }
+
+        Ok(())
+    }
+}
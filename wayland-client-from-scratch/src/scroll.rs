@@ -0,0 +1,118 @@
+use crate::protocol::pointer::event::{
+    axis::Axis, axis_relative_direction::AxisRelativeDirection, axis_source::AxisSource,
+};
+
+/// The `axis_value120` value of one wheel detent.
+pub const WHEEL_DETENT_VALUE120: i32 = 120;
+
+/// The scroll motion along one axis within a pointer frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AxisScroll {
+    /// The continuous scroll distance in surface-local units, from `wl_pointer.axis`.
+    pub value: f64,
+    /// The wheel motion in fractions of a detent, 120 per detent.
+    ///
+    /// Filled from `axis_value120`, or from `axis_discrete` on compositors
+    /// older than `wl_pointer` version 8. Zero for non-wheel sources.
+    pub value120: i32,
+    /// Set when the scroll sequence stopped on this axis (`axis_stop`), the
+    /// cue to start kinetic scrolling.
+    pub stop: bool,
+    /// Set when the physical motion is opposite to the scroll direction,
+    /// as with "natural scrolling".
+    pub inverted: bool,
+}
+
+impl AxisScroll {
+    /// Returns `true` if the axis carries no motion and no stop.
+    pub fn is_empty(&self) -> bool {
+        self.value == 0.0 && self.value120 == 0 && !self.stop
+    }
+
+    /// Returns the number of wheel detents, fractional for high-resolution wheels.
+    pub fn detents(&self) -> f64 {
+        self.value120 as f64 / WHEEL_DETENT_VALUE120 as f64
+    }
+}
+
+/// The scroll motion reported by one pointer frame.
+///
+/// Wheels and precision touchpads report scrolling differently: wheels move
+/// in detents (and fractions of a detent on high-resolution wheels), while
+/// touchpads report a continuous distance. [`Scroll::lines`] and
+/// [`Scroll::pixels`] hide that difference behind a single delta.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Scroll {
+    /// The timestamp of the last axis event, in milliseconds.
+    pub time: Option<u32>,
+    /// The device that produced the scroll, if the compositor reported it.
+    pub source: Option<AxisSource>,
+    /// The motion along the vertical axis, positive downwards.
+    pub vertical: AxisScroll,
+    /// The motion along the horizontal axis, positive to the right.
+    pub horizontal: AxisScroll,
+}
+
+impl Scroll {
+    /// Returns `true` if the frame carried no scroll information.
+    pub fn is_empty(&self) -> bool {
+        self.source.is_none() && self.vertical.is_empty() && self.horizontal.is_empty()
+    }
+
+    /// Returns the motion along `axis`.
+    pub fn axis(&self, axis: Axis) -> &AxisScroll {
+        match axis {
+            Axis::VerticalScroll => &self.vertical,
+            Axis::HorizontalScroll => &self.horizontal,
+        }
+    }
+
+    /// Returns the motion along `axis`, for updating.
+    pub(crate) fn axis_mut(&mut self, axis: Axis) -> &mut AxisScroll {
+        match axis {
+            Axis::VerticalScroll => &mut self.vertical,
+            Axis::HorizontalScroll => &mut self.horizontal,
+        }
+    }
+
+    /// Returns `true` if the motion comes from a wheel moving in detents.
+    pub fn is_wheel(&self) -> bool {
+        match self.source {
+            Some(AxisSource::Wheel | AxisSource::WheelTilt) => true,
+            Some(AxisSource::Finger | AxisSource::Continuous) => false,
+            None => self.vertical.value120 != 0 || self.horizontal.value120 != 0,
+        }
+    }
+
+    /// Returns the scroll delta along `axis` in lines.
+    ///
+    /// Wheels scroll one line per detent (fractionally for high-resolution
+    /// wheels); continuous sources are converted with `pixels_per_line`.
+    pub fn lines(&self, axis: Axis, pixels_per_line: f64) -> f64 {
+        let motion = self.axis(axis);
+        if self.is_wheel() && motion.value120 != 0 {
+            motion.detents()
+        } else {
+            motion.value / pixels_per_line
+        }
+    }
+
+    /// Returns the scroll delta along `axis` in surface-local pixels.
+    ///
+    /// Wheel detents are scaled by `pixels_per_line`, so a click moves the
+    /// same distance regardless of how the compositor weighs its `axis`
+    /// value; continuous sources are returned unchanged.
+    pub fn pixels(&self, axis: Axis, pixels_per_line: f64) -> f64 {
+        let motion = self.axis(axis);
+        if self.is_wheel() && motion.value120 != 0 {
+            motion.detents() * pixels_per_line
+        } else {
+            motion.value
+        }
+    }
+
+    /// Records an `axis_relative_direction` event for `axis`.
+    pub(crate) fn set_direction(&mut self, axis: Axis, direction: AxisRelativeDirection) {
+        self.axis_mut(axis).inverted = direction == AxisRelativeDirection::Inverted;
+    }
+}
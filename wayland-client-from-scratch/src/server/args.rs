@@ -0,0 +1,93 @@
+use crate::{
+    error::{Result, anyhow},
+    protocol::types::{WlArray, WlFixed, WlInt, WlString, WlUInt, WlUntypedNewId},
+};
+
+/// Reads the arguments of a received request in order.
+///
+/// The wire format does not describe itself, so the caller reads the
+/// arguments in the order of the signature of the request, as the event
+/// handlers of the client do.
+pub struct Args<'a> {
+    /// The arguments of the request, without the header.
+    buf: &'a [u8],
+    /// The position of the next argument in `buf`.
+    offset: usize,
+}
+
+impl<'a> Args<'a> {
+    /// Starts reading the arguments `buf` of a request.
+    pub fn new(buf: &'a [u8]) -> Args<'a> {
+        Args { buf, offset: 0 }
+    }
+
+    /// Reads a `uint` argument.
+    ///
+    /// # Errors
+    /// Returns an error if the arguments end before the value does, as for
+    /// every other argument type.
+    pub fn uint(&mut self) -> Result<u32> {
+        let value = WlUInt::read(self.buf, self.offset)?;
+        self.offset += WlUInt::type_size();
+        Ok(value.get())
+    }
+
+    /// Reads an `int` argument.
+    pub fn int(&mut self) -> Result<i32> {
+        let value = WlInt::read(self.buf, self.offset)?;
+        self.offset += WlInt::type_size();
+        Ok(value.get())
+    }
+
+    /// Reads a `fixed` argument.
+    pub fn fixed(&mut self) -> Result<WlFixed> {
+        let value = WlFixed::read(self.buf, self.offset)?;
+        self.offset += WlFixed::type_size();
+        Ok(value)
+    }
+
+    /// Reads a nullable `object` argument, `None` for the null object.
+    pub fn object(&mut self) -> Result<Option<u32>> {
+        Ok(Some(self.uint()?).filter(|&id| id != 0))
+    }
+
+    /// Reads a `new_id` argument of an interface fixed by the request.
+    pub fn new_id(&mut self) -> Result<u32> {
+        match self.uint()? {
+            0 => Err(anyhow!("Request creates an object with the null ID")),
+            id => Ok(id),
+        }
+    }
+
+    /// Reads a `new_id` argument whose interface is not fixed by the
+    /// request, preceded by the interface name and version.
+    pub fn untyped_new_id(&mut self) -> Result<WlUntypedNewId> {
+        let value = WlUntypedNewId::try_from(self.remaining()?)?;
+        if value.id() == 0 {
+            return Err(anyhow!("Request creates an object with the null ID"));
+        }
+        self.offset += value.buffer_size();
+        Ok(value)
+    }
+
+    /// Reads a `string` argument.
+    pub fn string(&mut self) -> Result<WlString> {
+        let value = WlString::try_from(self.remaining()?)?;
+        self.offset += value.buffer_size();
+        Ok(value)
+    }
+
+    /// Reads an `array` argument.
+    pub fn array(&mut self) -> Result<WlArray> {
+        let value = WlArray::try_from(self.remaining()?)?;
+        self.offset += value.buffer_size();
+        Ok(value)
+    }
+
+    /// Returns the bytes after the arguments read so far.
+    fn remaining(&self) -> Result<&'a [u8]> {
+        self.buf
+            .get(self.offset..)
+            .ok_or_else(|| anyhow!("Request arguments end at byte {}", self.buf.len()))
+    }
+}
@@ -0,0 +1,342 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    io,
+    os::{
+        fd::{AsFd, BorrowedFd, OwnedFd},
+        unix::net::UnixStream,
+    },
+};
+
+use crate::{
+    connection::{
+        PeerCredentials,
+        object_map::{ObjectEntry, WL_CLIENT_ID_MAX, WL_DISPLAY_ID},
+    },
+    error::{Result, anyhow},
+    protocol::{
+        WlObjectId,
+        display::{self, event::error::ErrorId},
+        encoder::{Encode, Encoder},
+        message::{WL_MESSAGE_HEADER_LEN, WL_MESSAGE_MAX_LEN, WlMessage, WlMessageHeader},
+        types::WlUInt,
+    },
+    sys,
+};
+
+/// The number of bytes read from the socket at once.
+const READ_CHUNK_LEN: usize = 4096;
+
+/// The first object ID the server hands out.
+const WL_SERVER_ID_MIN: u32 = WL_CLIENT_ID_MAX + 1;
+
+/// The server side of the connection of one client.
+///
+/// The client owns the socket and the objects the client and the server
+/// created on it. Requests are read with [`Client::read_requests`] and
+/// interpreted by the caller, which records the objects they create with
+/// [`Client::insert_object`]. Events are buffered until [`Client::flush`],
+/// which never blocks: what the socket does not take stays queued.
+pub struct Client {
+    /// The socket connected to the client.
+    stream: UnixStream,
+    /// The live objects of the client, keyed by object ID.
+    objects: HashMap<u32, ObjectEntry>,
+    /// The next never-used server-side ID.
+    next_server_id: u32,
+    /// The largest request accepted in bytes, header included.
+    max_request_len: usize,
+    /// Received bytes that do not form a complete request yet.
+    in_buf: Vec<u8>,
+    /// Received file descriptors not yet claimed by a request.
+    in_fds: VecDeque<OwnedFd>,
+    /// Serialized events waiting to be written to the socket.
+    out_buf: Vec<u8>,
+    /// File descriptors to pass along with the queued events, with the
+    /// offset of the event carrying each.
+    out_fds: VecDeque<(usize, OwnedFd)>,
+    /// Set once the client closed its end of the socket.
+    closed: bool,
+}
+
+impl Client {
+    /// Wraps the socket of a newly accepted client.
+    ///
+    /// The socket is switched to non-blocking mode and gets the
+    /// close-on-exec flag, like the sockets of client connections.
+    ///
+    /// # Errors
+    /// Returns an error if the socket flags cannot be set.
+    pub fn from_stream(stream: UnixStream) -> Result<Client> {
+        sys::set_nonblocking(stream.as_fd())?;
+        sys::set_cloexec(stream.as_fd())?;
+
+        let display = ObjectEntry {
+            interface: WlObjectId::Display,
+            version: 1,
+        };
+
+        Ok(Client {
+            stream,
+            objects: HashMap::from([(WL_DISPLAY_ID, display)]),
+            next_server_id: WL_SERVER_ID_MIN,
+            max_request_len: WL_MESSAGE_MAX_LEN,
+            in_buf: Vec::new(),
+            in_fds: VecDeque::new(),
+            out_buf: Vec::new(),
+            out_fds: VecDeque::new(),
+            closed: false,
+        })
+    }
+
+    /// Returns the process, user and group of the client.
+    ///
+    /// # Errors
+    /// Returns an error if the credentials cannot be queried.
+    pub fn peer_credentials(&self) -> Result<PeerCredentials> {
+        let (pid, uid, gid) = sys::peer_credentials(self.stream.as_fd())?;
+
+        Ok(PeerCredentials { pid, uid, gid })
+    }
+
+    /// Returns `true` once the client closed the connection.
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    /// Closes the connection, e.g. after a protocol error was flushed.
+    ///
+    /// Requests still unread are dropped.
+    pub fn close(&mut self) {
+        let _ = self.stream.shutdown(std::net::Shutdown::Both);
+        self.closed = true;
+    }
+
+    /// Sets the size in bytes, header included, of the largest request
+    /// accepted, by default [`WL_MESSAGE_MAX_LEN`], which is also the
+    /// upper bound. Sizes below [`WL_MESSAGE_HEADER_LEN`] are raised to it,
+    /// so requests without arguments are always accepted.
+    pub fn set_max_request_len(&mut self, len: usize) {
+        self.max_request_len = len.clamp(WL_MESSAGE_HEADER_LEN, WL_MESSAGE_MAX_LEN);
+    }
+
+    /// Reads from the socket and returns the complete requests received.
+    ///
+    /// Returns no request if none is available yet. File descriptors sent
+    /// along are kept until claimed with [`Client::take_fd`], in the order
+    /// of the requests carrying them.
+    ///
+    /// # Errors
+    /// Returns an error if the socket fails, or an
+    /// [`InvalidHeader`](crate::protocol::message::InvalidHeader) if the
+    /// size of a request is malformed or exceeds the limit set with
+    /// [`Client::set_max_request_len`]. The stream cannot be split into
+    /// requests anymore then, so the protocol error is queued for the
+    /// client, which is to be flushed and closed.
+    pub fn read_requests(&mut self) -> Result<Vec<WlMessage>> {
+        let mut read_buf = [0u8; READ_CHUNK_LEN];
+        let mut fds = Vec::new();
+        let read_len = match sys::recv_with_fds(self.stream.as_fd(), &mut read_buf, &mut fds) {
+            Ok(read_len) => read_len,
+            Err(error) if error.kind() == io::ErrorKind::WouldBlock => return Ok(Vec::new()),
+            Err(error) if error.kind() == io::ErrorKind::ConnectionReset => 0,
+            Err(error) => return Err(error.into()),
+        };
+        self.in_fds.extend(fds);
+        if read_len == 0 {
+            self.closed = true;
+            return Ok(Vec::new());
+        }
+        self.in_buf.extend_from_slice(&read_buf[..read_len]);
+
+        let mut requests = Vec::new();
+        let mut consumed = 0;
+
+        while self.in_buf.len() - consumed >= WL_MESSAGE_HEADER_LEN {
+            let remaining = &self.in_buf[consumed..];
+            let header = WlMessageHeader::try_from(&remaining[..WL_MESSAGE_HEADER_LEN])?;
+            if let Err(error) = header.validate_size(self.max_request_len) {
+                let code = ErrorId::InvalidMethod as u32;
+                self.post_error(WL_DISPLAY_ID, code, &error.to_string())?;
+                return Err(error.into());
+            }
+            if remaining.len() < header.size as usize {
+                break;
+            }
+
+            requests.push(WlMessage::try_from(remaining)?);
+            consumed += header.size as usize;
+        }
+        self.in_buf.drain(..consumed);
+
+        Ok(requests)
+    }
+
+    /// Claims the next file descriptor received along with the requests.
+    pub fn take_fd(&mut self) -> Option<OwnedFd> {
+        self.in_fds.pop_front()
+    }
+
+    /// Looks up a live object of the client.
+    pub fn object(&self, id: u32) -> Option<ObjectEntry> {
+        self.objects.get(&id).copied()
+    }
+
+    /// Records an object the client created through a `new_id` argument.
+    ///
+    /// # Errors
+    /// Returns an error if `id` is outside the client ID range or already
+    /// in use, which is a protocol error of the client.
+    pub fn insert_object(&mut self, id: u32, interface: WlObjectId, version: u32) -> Result<()> {
+        if id == 0 || id > WL_CLIENT_ID_MAX {
+            return Err(anyhow!(
+                "Client created {} with invalid ID {}",
+                interface.interface_name(),
+                id
+            ));
+        }
+        if self.objects.contains_key(&id) {
+            return Err(anyhow!(
+                "Client created {} with ID {}, which is already in use",
+                interface.interface_name(),
+                id
+            ));
+        }
+
+        self.objects.insert(id, ObjectEntry { interface, version });
+
+        Ok(())
+    }
+
+    /// Allocates a server-side ID for an object created by an event, such
+    /// as `wl_data_device.data_offer`, and records its interface.
+    ///
+    /// # Errors
+    /// Returns an error if the server ID space is exhausted.
+    pub fn create_object(&mut self, interface: WlObjectId, version: u32) -> Result<u32> {
+        let id = self.next_server_id;
+        self.next_server_id = id
+            .checked_add(1)
+            .ok_or_else(|| anyhow!("Server object ID space exhausted"))?;
+
+        self.objects.insert(id, ObjectEntry { interface, version });
+
+        Ok(id)
+    }
+
+    /// Forgets a destroyed object.
+    ///
+    /// The deletion of client-side IDs is confirmed with
+    /// `wl_display.delete_id`, after which the client may reuse the ID.
+    ///
+    /// # Errors
+    /// Returns an error if the confirmation cannot be encoded.
+    pub fn remove_object(&mut self, id: u32) -> Result<()> {
+        if self.objects.remove(&id).is_some() && id <= WL_CLIENT_ID_MAX {
+            let opcode = display::event::Opcode::DeleteId as u16;
+            self.send_event(WL_DISPLAY_ID, opcode, &WlUInt(id))?;
+        }
+
+        Ok(())
+    }
+
+    /// Queues an event from the object `object_id`.
+    ///
+    /// # Errors
+    /// Returns an error if the event is too long to be encoded.
+    pub fn send_event(
+        &mut self,
+        object_id: u32,
+        opcode: impl Into<u16>,
+        args: &impl Encode,
+    ) -> Result<()> {
+        self.send_event_with_fds(object_id, opcode, args, Vec::new())
+    }
+
+    /// Like [`Client::send_event`], for an event carrying file
+    /// descriptors, which are closed once they have been sent.
+    ///
+    /// # Errors
+    /// Returns an error if the event is too long to be encoded.
+    pub fn send_event_with_fds(
+        &mut self,
+        object_id: u32,
+        opcode: impl Into<u16>,
+        args: &impl Encode,
+        fds: Vec<OwnedFd>,
+    ) -> Result<()> {
+        let mut encoder = Encoder::new(object_id, opcode.into());
+        args.encode(&mut encoder);
+
+        let offset = self.out_buf.len();
+        self.out_buf.extend_from_slice(encoder.finish()?);
+        self.out_fds.extend(fds.into_iter().map(|fd| (offset, fd)));
+
+        Ok(())
+    }
+
+    /// Queues a `wl_display.error` event, the fatal error a client cannot
+    /// recover from. The caller closes the connection once it is flushed.
+    ///
+    /// # Errors
+    /// Returns an error if `message` cannot be encoded as a string.
+    pub fn post_error(&mut self, object_id: u32, code: u32, message: &str) -> Result<()> {
+        let mut encoder = Encoder::new(WL_DISPLAY_ID, display::event::Opcode::Error as u16);
+        encoder.write_object(object_id);
+        encoder.write_uint(code);
+        encoder.write_string(message)?;
+        self.out_buf.extend_from_slice(encoder.finish()?);
+
+        Ok(())
+    }
+
+    /// Writes as many queued events as the socket takes.
+    ///
+    /// # Errors
+    /// Returns an error if the socket fails or an event carries more
+    /// descriptors than a single write can pass.
+    pub fn flush(&mut self) -> Result<()> {
+        while !self.out_buf.is_empty() {
+            // At most `MAX_FDS_OUT` descriptors go out at once, and the write
+            // stops before the first event whose descriptors must wait
+            let batch_len = self.out_fds.len().min(sys::MAX_FDS_OUT);
+            let chunk_len = self
+                .out_fds
+                .get(batch_len)
+                .map_or(self.out_buf.len(), |(offset, _)| *offset);
+            if chunk_len == 0 {
+                return Err(anyhow!(
+                    "Event carries more than {} file descriptors",
+                    sys::MAX_FDS_OUT
+                ));
+            }
+            let fds: Vec<BorrowedFd<'_>> = self
+                .out_fds
+                .iter()
+                .take(batch_len)
+                .map(|(_, fd)| fd.as_fd())
+                .collect();
+
+            match sys::send_with_fds(self.stream.as_fd(), &self.out_buf[..chunk_len], &fds) {
+                Ok(written) => {
+                    self.out_buf.drain(..written);
+                    self.out_fds.drain(..batch_len);
+                    for (offset, _) in self.out_fds.iter_mut() {
+                        *offset -= written;
+                    }
+                }
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(error) if error.kind() == io::ErrorKind::WouldBlock => break,
+                Err(error) => return Err(error.into()),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl AsFd for Client {
+    /// Returns the socket, to be polled for incoming requests.
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.stream.as_fd()
+    }
+}
@@ -0,0 +1,812 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    os::fd::{AsFd, OwnedFd},
+    path::{Path, PathBuf},
+    rc::Rc,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    thread::JoinHandle,
+    time::Instant,
+};
+
+use crate::{
+    connection::Connection,
+    error::{Result, anyhow},
+    protocol::{
+        WlObjectId, buffer, callback, compositor,
+        display::{self, event::error::ErrorId},
+        message::WlMessage,
+        registry, shm, shm_pool, surface,
+        types::{WlArray, WlInt, WlString, WlUInt},
+        xdg_surface, xdg_toplevel, xdg_wm_base,
+    },
+    server::{Args, Client, Server},
+    sys::{self, Interest, MemMap},
+};
+
+/// The globals advertised to every client, with their numeric name.
+const GLOBALS: [(u32, WlObjectId, u32); 3] = [
+    (1, WlObjectId::Compositor, 6),
+    (2, WlObjectId::Shm, 2),
+    (3, WlObjectId::XdgWmBase, 6),
+];
+
+/// The pixel formats advertised by `wl_shm`: the two every compositor must
+/// support, and the byte order of tiny-skia pixmaps.
+const SHM_FORMATS: [u32; 3] = [
+    shm::event::format::Format::Argb8888 as u32,
+    shm::event::format::Format::Xrgb8888 as u32,
+    shm::event::format::Format::Abgr8888 as u32,
+];
+
+/// The ID of the next compositor, to give each its own socket.
+static NEXT_COMPOSITOR: AtomicUsize = AtomicUsize::new(0);
+
+/// The pixels of the buffer a surface committed, copied out of the shared
+/// memory pool of the client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShmContents {
+    /// The width of the buffer in pixels.
+    pub width: i32,
+    /// The height of the buffer in pixels.
+    pub height: i32,
+    /// The number of bytes between the starts of two rows.
+    pub stride: i32,
+    /// The `wl_shm` format code of the pixels.
+    pub format: u32,
+    /// The `stride * height` bytes of the buffer.
+    pub pixels: Vec<u8>,
+}
+
+/// The committed state of a surface, as seen by a [`HeadlessCompositor`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommittedSurface {
+    /// The number of the client, counting connections from 0.
+    pub client: usize,
+    /// The object ID of the surface on its client.
+    pub surface: u32,
+    /// The number of commits of the surface.
+    pub commits: u32,
+    /// The buffer attached at the last commit that attached one, `None`
+    /// before that or once a null buffer was committed.
+    pub contents: Option<ShmContents>,
+    /// The title of the toplevel of the surface, if it has one.
+    pub title: Option<String>,
+    /// The application ID of the toplevel of the surface, if it has one.
+    pub app_id: Option<String>,
+}
+
+/// The committed surfaces of all clients, shared with the compositor thread.
+type Surfaces = Arc<Mutex<Vec<CommittedSurface>>>;
+
+/// A compositor without a screen or input, serving clients from a thread of
+/// its own, for tests of the client side against a real socket.
+///
+/// It advertises `wl_compositor`, `wl_shm` and `xdg_wm_base`, configures
+/// every toplevel once, at its initial commit, with a size left to the
+/// client, and copies the shared memory buffers surfaces commit, which are
+/// released right away. Frame callbacks are done at the next commit.
+/// Popups are never configured. A client sending an invalid request gets a
+/// `wl_display.error` and is disconnected.
+///
+/// The compositor stops when dropped.
+pub struct HeadlessCompositor {
+    /// The path of the socket clients connect to.
+    path: PathBuf,
+    /// The committed surfaces, updated by the thread.
+    surfaces: Surfaces,
+    /// The write end of the pipe telling the thread to stop.
+    stop: Option<OwnedFd>,
+    /// The thread running the compositor.
+    thread: Option<JoinHandle<Result<()>>>,
+}
+
+impl HeadlessCompositor {
+    /// Starts a compositor listening on a new socket in the temporary
+    /// directory.
+    ///
+    /// # Errors
+    /// Returns an error if the socket or the thread cannot be created.
+    pub fn spawn() -> Result<HeadlessCompositor> {
+        let number = NEXT_COMPOSITOR.fetch_add(1, Ordering::Relaxed);
+        let path =
+            std::env::temp_dir().join(format!("wayland-headless-{}-{number}", std::process::id()));
+        // A socket left behind by a crashed run of the same process ID
+        let _ = std::fs::remove_file(&path);
+        let server = Server::bind(&path)?;
+
+        let (stop_read, stop_write) = sys::pipe()?;
+        let surfaces = Surfaces::default();
+        let shared = surfaces.clone();
+        let thread = std::thread::Builder::new()
+            .name("headless-compositor".to_string())
+            .spawn(move || run(server, stop_read, shared))?;
+
+        Ok(HeadlessCompositor {
+            path,
+            surfaces,
+            stop: Some(stop_write),
+            thread: Some(thread),
+        })
+    }
+
+    /// Returns the path of the socket.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Connects a new client and discovers the globals.
+    ///
+    /// # Errors
+    /// Returns an error if the connection cannot be established.
+    pub fn connect(&self) -> Result<Connection> {
+        let conn = Connection::connect_to(&self.path)?;
+        conn.roundtrip()?;
+
+        Ok(conn)
+    }
+
+    /// Returns the live surfaces of all clients, ordered by client and ID.
+    ///
+    /// A [`Connection::roundtrip`] of a client makes sure its earlier
+    /// commits are included.
+    pub fn surfaces(&self) -> Vec<CommittedSurface> {
+        let mut surfaces = self
+            .surfaces
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        surfaces.sort_by_key(|surface| (surface.client, surface.surface));
+        surfaces
+    }
+
+    /// Stops the compositor and returns the error it stopped on, if any.
+    ///
+    /// # Errors
+    /// Returns the error that ended the compositor thread early.
+    pub fn shutdown(mut self) -> Result<()> {
+        self.stop_thread()
+    }
+
+    /// Wakes the thread up to stop and waits for it.
+    fn stop_thread(&mut self) -> Result<()> {
+        if let Some(stop) = self.stop.take() {
+            sys::write(stop.as_fd(), &[1])?;
+        }
+        match self.thread.take().map(JoinHandle::join) {
+            Some(Ok(result)) => result,
+            Some(Err(_)) => Err(anyhow!("Headless compositor thread panicked")),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for HeadlessCompositor {
+    fn drop(&mut self) {
+        let _ = self.stop_thread();
+    }
+}
+
+/// Serves clients until a byte arrives on `stop`.
+fn run(server: Server, stop: OwnedFd, surfaces: Surfaces) -> Result<()> {
+    let started = Instant::now();
+    let mut clients: Vec<ClientState> = Vec::new();
+    let mut next_client = 0;
+
+    loop {
+        let mut fds = vec![
+            (stop.as_fd(), Interest::Readable),
+            (server.as_fd(), Interest::Readable),
+        ];
+        fds.extend(
+            clients
+                .iter()
+                .map(|state| (state.client.as_fd(), Interest::Readable)),
+        );
+        let ready = sys::poll(&fds, None)?;
+        if ready[0] {
+            return Ok(());
+        }
+
+        // Clients accepted now are polled from the next round on
+        for (state, _) in clients
+            .iter_mut()
+            .zip(&ready[2..])
+            .filter(|(_, ready)| **ready)
+        {
+            state.process(&surfaces, started);
+        }
+        if ready[1] {
+            while let Some(client) = server.accept()? {
+                clients.push(ClientState::new(next_client, client));
+                next_client += 1;
+            }
+        }
+
+        clients.retain(|state| {
+            let alive = !state.client.is_closed();
+            if !alive {
+                state.forget_surfaces(&surfaces);
+            }
+            alive
+        });
+    }
+}
+
+/// A `wl_shm_pool` and the memory it maps.
+struct Pool {
+    /// The shared memory file sent by the client.
+    fd: OwnedFd,
+    /// The mapping of the file, replaced when the pool grows.
+    map: MemMap,
+}
+
+/// A `wl_buffer` created from a pool.
+struct BufferInfo {
+    /// The pool, kept mapped as long as the buffer lives.
+    pool: Rc<RefCell<Pool>>,
+    /// The offset of the first pixel in the pool.
+    offset: i32,
+    /// The width in pixels.
+    width: i32,
+    /// The height in pixels.
+    height: i32,
+    /// The number of bytes between the starts of two rows.
+    stride: i32,
+    /// The `wl_shm` format code.
+    format: u32,
+}
+
+/// The state of a `wl_surface` on the compositor side.
+#[derive(Default)]
+struct SurfaceInfo {
+    /// The buffer attached since the last commit: `None` if nothing was
+    /// attached, `Some(None)` for a null buffer.
+    pending_buffer: Option<Option<u32>>,
+    /// The frame callbacks requested since the last commit.
+    pending_frames: Vec<u32>,
+    /// The frame callbacks to run at the next commit.
+    frames: Vec<u32>,
+    /// The `xdg_toplevel` of the surface, if it got the role.
+    toplevel: Option<(u32, u32)>,
+    /// Whether the initial configure was sent.
+    configured: bool,
+    /// The title set on the toplevel.
+    title: Option<String>,
+    /// The application ID set on the toplevel.
+    app_id: Option<String>,
+}
+
+/// A connected client and the compositor-side state of its objects.
+struct ClientState {
+    /// The number of the client.
+    number: usize,
+    /// The connection to the client.
+    client: Client,
+    /// The shared memory pools, keyed by object ID.
+    pools: HashMap<u32, Rc<RefCell<Pool>>>,
+    /// The buffers, keyed by object ID.
+    buffers: HashMap<u32, BufferInfo>,
+    /// The surfaces, keyed by object ID.
+    surfaces: HashMap<u32, SurfaceInfo>,
+    /// The surface of each `xdg_surface`, keyed by object ID.
+    xdg_surfaces: HashMap<u32, u32>,
+    /// The surface of each `xdg_toplevel`, keyed by object ID.
+    toplevels: HashMap<u32, u32>,
+    /// The serial of the last configure.
+    serial: u32,
+}
+
+impl ClientState {
+    /// Starts serving a newly accepted client.
+    fn new(number: usize, client: Client) -> ClientState {
+        ClientState {
+            number,
+            client,
+            pools: HashMap::new(),
+            buffers: HashMap::new(),
+            surfaces: HashMap::new(),
+            xdg_surfaces: HashMap::new(),
+            toplevels: HashMap::new(),
+            serial: 0,
+        }
+    }
+
+    /// Handles the requests the client sent, disconnecting it after a
+    /// protocol error.
+    fn process(&mut self, surfaces: &Surfaces, started: Instant) {
+        let result = self.client.read_requests().and_then(|requests| {
+            for request in requests {
+                if let Err(error) = self.handle(&request, surfaces, started) {
+                    // The error is for the client; the compositor carries on
+                    let code = ErrorId::InvalidMethod as u32;
+                    let _ = self
+                        .client
+                        .post_error(request.object_id(), code, &error.to_string());
+                    return Err(error);
+                }
+            }
+            Ok(())
+        });
+
+        let flushed = self.client.flush();
+        if result.is_err() || flushed.is_err() {
+            self.client.close();
+        }
+    }
+
+    /// Removes the surfaces of the client from the shared list.
+    fn forget_surfaces(&self, surfaces: &Surfaces) {
+        let mut surfaces = surfaces.lock().unwrap_or_else(|e| e.into_inner());
+        surfaces.retain(|surface| surface.client != self.number);
+    }
+
+    /// Handles a single request.
+    fn handle(&mut self, request: &WlMessage, surfaces: &Surfaces, started: Instant) -> Result<()> {
+        let id = request.object_id();
+        let opcode = request.opcode();
+        let object = self
+            .client
+            .object(id)
+            .ok_or_else(|| anyhow!("Request to unknown object {id}"))?;
+        let unknown = || {
+            anyhow!(
+                "Unknown {} request opcode {opcode}",
+                object.interface.interface_name()
+            )
+        };
+        let mut args = Args::new(request.args());
+
+        match object.interface {
+            WlObjectId::Display => {
+                match display::request::Opcode::from_opcode(opcode).ok_or_else(unknown)? {
+                    display::request::Opcode::Sync => {
+                        let callback = args.new_id()?;
+                        self.client
+                            .insert_object(callback, WlObjectId::Callback, 1)?;
+                        self.done(callback, 0)?;
+                    }
+                    display::request::Opcode::GetRegistry => {
+                        let registry = args.new_id()?;
+                        self.client
+                            .insert_object(registry, WlObjectId::Registry, 1)?;
+                        for (name, interface, version) in GLOBALS {
+                            let global = (
+                                WlUInt(name),
+                                WlString::new(interface.interface_name())?,
+                                WlUInt(version),
+                            );
+                            self.client.send_event(
+                                registry,
+                                registry::event::Opcode::Global as u16,
+                                &global,
+                            )?;
+                        }
+                    }
+                }
+            }
+            WlObjectId::Registry => {
+                registry::request::Opcode::from_opcode(opcode).ok_or_else(unknown)?;
+                self.bind(&mut args)?;
+            }
+            WlObjectId::Compositor => {
+                let interface =
+                    match compositor::request::Opcode::from_opcode(opcode).ok_or_else(unknown)? {
+                        compositor::request::Opcode::CreateSurface => WlObjectId::Surface,
+                        compositor::request::Opcode::CreateRegion => WlObjectId::Region,
+                    };
+                let new_id = args.new_id()?;
+                self.client
+                    .insert_object(new_id, interface, object.version)?;
+                if interface == WlObjectId::Surface {
+                    self.surfaces.insert(new_id, SurfaceInfo::default());
+                    surfaces
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .push(CommittedSurface {
+                            client: self.number,
+                            surface: new_id,
+                            commits: 0,
+                            contents: None,
+                            title: None,
+                            app_id: None,
+                        });
+                }
+            }
+            WlObjectId::Surface => {
+                self.handle_surface(id, opcode, &mut args, surfaces, started)?;
+            }
+            WlObjectId::Shm => {
+                if let shm::request::Opcode::CreatePool =
+                    shm::request::Opcode::from_opcode(opcode).ok_or_else(unknown)?
+                {
+                    let pool = args.new_id()?;
+                    let size = args.int()?;
+                    let fd = self
+                        .client
+                        .take_fd()
+                        .ok_or_else(|| anyhow!("wl_shm.create_pool without a file descriptor"))?;
+                    let map = MemMap::new(fd.as_fd(), pool_size(size)?)?;
+                    self.client.insert_object(pool, WlObjectId::ShmPool, 1)?;
+                    self.pools
+                        .insert(pool, Rc::new(RefCell::new(Pool { fd, map })));
+                }
+            }
+            WlObjectId::ShmPool => {
+                match shm_pool::request::Opcode::from_opcode(opcode).ok_or_else(unknown)? {
+                    shm_pool::request::Opcode::CreateBuffer => self.create_buffer(id, &mut args)?,
+                    shm_pool::request::Opcode::Resize => {
+                        let size = pool_size(args.int()?)?;
+                        let mut pool = self.pools[&id].borrow_mut();
+                        if size < pool.map.len() {
+                            return Err(anyhow!("wl_shm_pool.resize cannot shrink the pool"));
+                        }
+                        pool.map = MemMap::new(pool.fd.as_fd(), size)?;
+                    }
+                    shm_pool::request::Opcode::Destroy => {
+                        self.pools.remove(&id);
+                    }
+                }
+            }
+            WlObjectId::Buffer => {
+                buffer::request::Opcode::from_opcode(opcode).ok_or_else(unknown)?;
+                self.buffers.remove(&id);
+            }
+            WlObjectId::XdgWmBase => {
+                match xdg_wm_base::request::Opcode::from_opcode(opcode).ok_or_else(unknown)? {
+                    xdg_wm_base::request::Opcode::CreatePositioner => {
+                        let positioner = args.new_id()?;
+                        self.client.insert_object(
+                            positioner,
+                            WlObjectId::XdgPositioner,
+                            object.version,
+                        )?;
+                    }
+                    xdg_wm_base::request::Opcode::GetXdgSurface => {
+                        let xdg_surface = args.new_id()?;
+                        let surface = args.object()?.unwrap_or_default();
+                        if !self.surfaces.contains_key(&surface) {
+                            return Err(anyhow!("xdg_wm_base.get_xdg_surface without a surface"));
+                        }
+                        self.client.insert_object(
+                            xdg_surface,
+                            WlObjectId::XdgSurface,
+                            object.version,
+                        )?;
+                        self.xdg_surfaces.insert(xdg_surface, surface);
+                    }
+                    xdg_wm_base::request::Opcode::Destroy | xdg_wm_base::request::Opcode::Pong => {}
+                }
+            }
+            WlObjectId::XdgSurface => {
+                match xdg_surface::request::Opcode::from_opcode(opcode).ok_or_else(unknown)? {
+                    xdg_surface::request::Opcode::GetToplevel => {
+                        let toplevel = args.new_id()?;
+                        self.client.insert_object(
+                            toplevel,
+                            WlObjectId::XdgToplevel,
+                            object.version,
+                        )?;
+                        let surface = self.xdg_surfaces[&id];
+                        self.toplevels.insert(toplevel, surface);
+                        if let Some(info) = self.surfaces.get_mut(&surface) {
+                            info.toplevel = Some((id, toplevel));
+                        }
+                    }
+                    xdg_surface::request::Opcode::GetPopup => {
+                        let popup = args.new_id()?;
+                        self.client
+                            .insert_object(popup, WlObjectId::XdgPopup, object.version)?;
+                    }
+                    xdg_surface::request::Opcode::Destroy => {
+                        self.xdg_surfaces.remove(&id);
+                    }
+                    xdg_surface::request::Opcode::SetWindowGeometry
+                    | xdg_surface::request::Opcode::AckConfigure => {}
+                }
+            }
+            WlObjectId::XdgToplevel => {
+                let surface = self.toplevels.get(&id).copied();
+                let info = surface.and_then(|surface| self.surfaces.get_mut(&surface));
+                match xdg_toplevel::request::Opcode::from_opcode(opcode).ok_or_else(unknown)? {
+                    xdg_toplevel::request::Opcode::SetTitle => {
+                        let title = args.string()?.to_string_lossy().into_owned();
+                        if let Some(info) = info {
+                            info.title = Some(title);
+                        }
+                    }
+                    xdg_toplevel::request::Opcode::SetAppId => {
+                        let app_id = args.string()?.to_string_lossy().into_owned();
+                        if let Some(info) = info {
+                            info.app_id = Some(app_id);
+                        }
+                    }
+                    xdg_toplevel::request::Opcode::Destroy => {
+                        if let Some(info) = info {
+                            info.toplevel = None;
+                        }
+                        self.toplevels.remove(&id);
+                    }
+                    _ => {}
+                }
+            }
+            // Regions, positioners and popups have no effect without a screen
+            _ => {}
+        }
+
+        if object.interface.is_destructor(opcode) {
+            self.client.remove_object(id)?;
+        }
+
+        Ok(())
+    }
+
+    /// Handles a `wl_surface` request.
+    fn handle_surface(
+        &mut self,
+        id: u32,
+        opcode: u16,
+        args: &mut Args<'_>,
+        surfaces: &Surfaces,
+        started: Instant,
+    ) -> Result<()> {
+        let opcode = surface::request::Opcode::from_opcode(opcode)
+            .ok_or_else(|| anyhow!("Unknown wl_surface request opcode {opcode}"))?;
+        let info = self
+            .surfaces
+            .get_mut(&id)
+            .ok_or_else(|| anyhow!("Surface {id} is not known"))?;
+
+        match opcode {
+            surface::request::Opcode::Attach => {
+                info.pending_buffer = Some(args.object()?);
+            }
+            surface::request::Opcode::Frame => {
+                let callback = args.new_id()?;
+                info.pending_frames.push(callback);
+                self.client
+                    .insert_object(callback, WlObjectId::Callback, 1)?;
+            }
+            surface::request::Opcode::Commit => self.commit(id, surfaces, started)?,
+            surface::request::Opcode::Destroy => {
+                self.surfaces.remove(&id);
+                let mut surfaces = surfaces.lock().unwrap_or_else(|e| e.into_inner());
+                surfaces.retain(|surface| (surface.client, surface.surface) != (self.number, id));
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Applies the pending state of a surface: copies the attached buffer,
+    /// runs the frame callbacks of the previous commit and sends the
+    /// initial configure of a toplevel.
+    fn commit(&mut self, id: u32, surfaces: &Surfaces, started: Instant) -> Result<()> {
+        let info = self.surfaces.get_mut(&id).expect("surface was looked up");
+        let pending_buffer = info.pending_buffer.take();
+        let frames = std::mem::replace(&mut info.frames, std::mem::take(&mut info.pending_frames));
+        let (title, app_id) = (info.title.clone(), info.app_id.clone());
+        let configure = match info.toplevel {
+            Some(toplevel) if !info.configured => {
+                info.configured = true;
+                Some(toplevel)
+            }
+            _ => None,
+        };
+
+        let contents = match pending_buffer {
+            Some(Some(buffer)) => {
+                let contents = self.copy_buffer(buffer)?;
+                self.client
+                    .send_event(buffer, buffer::event::Opcode::Release as u16, &())?;
+                Some(Some(contents))
+            }
+            Some(None) => Some(None),
+            None => None,
+        };
+
+        {
+            let mut surfaces = surfaces.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(committed) = surfaces
+                .iter_mut()
+                .find(|surface| (surface.client, surface.surface) == (self.number, id))
+            {
+                committed.commits += 1;
+                committed.title = title;
+                committed.app_id = app_id;
+                if let Some(contents) = contents {
+                    committed.contents = contents;
+                }
+            }
+        }
+
+        let time = started.elapsed().as_millis() as u32;
+        for callback in frames {
+            self.done(callback, time)?;
+        }
+
+        if let Some((xdg_surface, toplevel)) = configure {
+            self.serial += 1;
+            let toplevel_configure = (WlInt(0), WlInt(0), WlArray::new(&[]));
+            self.client.send_event(
+                toplevel,
+                xdg_toplevel::event::Opcode::Configure as u16,
+                &toplevel_configure,
+            )?;
+            self.client.send_event(
+                xdg_surface,
+                xdg_surface::event::Opcode::Configure as u16,
+                &(WlUInt(self.serial),),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Copies the pixels of a buffer out of its pool.
+    fn copy_buffer(&self, buffer: u32) -> Result<ShmContents> {
+        let info = self
+            .buffers
+            .get(&buffer)
+            .ok_or_else(|| anyhow!("Committed buffer {buffer} is not a wl_shm buffer"))?;
+        let pool = info.pool.borrow();
+        let start = info.offset as usize;
+        let end = start + info.stride as usize * info.height as usize;
+        let pixels = pool
+            .map
+            .as_slice()
+            .get(start..end)
+            .ok_or_else(|| anyhow!("Buffer {buffer} exceeds its pool"))?;
+
+        Ok(ShmContents {
+            width: info.width,
+            height: info.height,
+            stride: info.stride,
+            format: info.format,
+            pixels: pixels.to_vec(),
+        })
+    }
+
+    /// Handles `wl_shm_pool.create_buffer`.
+    fn create_buffer(&mut self, pool: u32, args: &mut Args<'_>) -> Result<()> {
+        let buffer = args.new_id()?;
+        let (offset, width, height, stride) = (args.int()?, args.int()?, args.int()?, args.int()?);
+        let format = args.uint()?;
+
+        if !SHM_FORMATS.contains(&format) {
+            return Err(anyhow!("Unsupported wl_shm format {format:#x}"));
+        }
+        let pool = self.pools[&pool].clone();
+        let fits = offset >= 0
+            && width > 0
+            && height > 0
+            && stride >= width * 4
+            && (offset as usize + stride as usize * height as usize) <= pool.borrow().map.len();
+        if !fits {
+            return Err(anyhow!(
+                "Invalid buffer of {width}x{height} pixels with stride {stride} at offset {offset}"
+            ));
+        }
+
+        self.client.insert_object(buffer, WlObjectId::Buffer, 1)?;
+        self.buffers.insert(
+            buffer,
+            BufferInfo {
+                pool,
+                offset,
+                width,
+                height,
+                stride,
+                format,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Handles `wl_registry.bind`, whose `new_id` names its interface.
+    fn bind(&mut self, args: &mut Args<'_>) -> Result<()> {
+        let name = args.uint()?;
+        let new_id = args.untyped_new_id()?;
+        let (interface, version, id) = (new_id.interface(), new_id.version(), new_id.id());
+
+        let (_, global, advertised) = GLOBALS
+            .into_iter()
+            .find(|(global_name, _, _)| *global_name == name)
+            .ok_or_else(|| anyhow!("No global with name {name}"))?;
+        if interface.to_string_lossy() != global.interface_name() || version > advertised {
+            return Err(anyhow!(
+                "Cannot bind global {name} ({} v{advertised}) as {} v{version}",
+                global.interface_name(),
+                interface.to_string_lossy()
+            ));
+        }
+        self.client.insert_object(id, global, version)?;
+
+        if global == WlObjectId::Shm {
+            for format in SHM_FORMATS {
+                self.client
+                    .send_event(id, shm::event::Opcode::Format as u16, &WlUInt(format))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sends `wl_callback.done` and destroys the callback.
+    fn done(&mut self, callback: u32, data: u32) -> Result<()> {
+        self.client.send_event(
+            callback,
+            callback::event::Opcode::Done as u16,
+            &WlUInt(data),
+        )?;
+        self.client.remove_object(callback)
+    }
+}
+
+/// Checks the size of a `wl_shm_pool`.
+fn pool_size(size: i32) -> Result<usize> {
+    match size {
+        1.. => Ok(size as usize),
+        _ => Err(anyhow!("Invalid wl_shm_pool size {size}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        protocol::{compositor::Compositor, shm::Shm, xdg_wm_base::XdgWmBase},
+        sync::Lock,
+        window::{Window, WindowBuilder, WindowEvent},
+    };
+
+    #[test]
+    fn windows_commit_their_pixels_to_the_headless_compositor() {
+        let compositor = HeadlessCompositor::spawn().unwrap();
+        let conn = compositor.connect().unwrap();
+        let wl_compositor = Compositor::bind(&conn).unwrap();
+        let wm_base = XdgWmBase::bind(&conn).unwrap();
+        let shm = Shm::bind(&conn).unwrap();
+
+        let events = Arc::new(Lock::new(Vec::new()));
+        let window = WindowBuilder::new(4, 2)
+            .title("headless")
+            .app_id("test")
+            .build(&conn, &wl_compositor, &wm_base, &shm, {
+                let events = events.clone();
+                move |_: &Window, event| events.borrow_mut().push(event)
+            })
+            .unwrap();
+        conn.roundtrip().unwrap();
+        assert!(
+            events
+                .borrow()
+                .iter()
+                .any(|event| matches!(event, WindowEvent::Configure(_)))
+        );
+
+        let drawn = window.draw(|buffer| buffer.pixels().fill(0x7f)).unwrap();
+        assert!(drawn);
+        conn.roundtrip().unwrap();
+
+        let surfaces = compositor.surfaces();
+        assert_eq!(surfaces.len(), 1);
+        let surface = &surfaces[0];
+        assert_eq!(surface.commits, 2);
+        assert_eq!(surface.title.as_deref(), Some("headless"));
+        assert_eq!(surface.app_id.as_deref(), Some("test"));
+        let contents = surface.contents.as_ref().unwrap();
+        assert_eq!((contents.width, contents.height), (4, 2));
+        assert!(contents.pixels.iter().all(|&byte| byte == 0x7f));
+
+        drop(window);
+        conn.roundtrip().unwrap();
+        assert!(compositor.surfaces().is_empty());
+        compositor.shutdown().unwrap();
+    }
+}
@@ -0,0 +1,194 @@
+//! The compositor side of the protocol.
+//!
+//! A [`Server`] listens on a socket and accepts [`Client`]s, whose requests
+//! are split with the same wire code the client uses for events, and whose
+//! events are encoded like requests. Interpreting the requests is up to the
+//! compositor built on top, which reads their arguments with [`Args`].
+
+pub mod args;
+pub mod client;
+pub mod headless;
+
+use std::{
+    io,
+    os::{
+        fd::{AsFd, BorrowedFd},
+        unix::net::UnixListener,
+    },
+    path::{Path, PathBuf},
+};
+
+use crate::{error::Result, sys};
+
+pub use args::Args;
+pub use client::Client;
+pub use headless::HeadlessCompositor;
+
+/// A listening socket clients connect to.
+///
+/// The socket file is removed when the server is dropped.
+pub struct Server {
+    /// The listening socket.
+    listener: UnixListener,
+    /// The path of the socket file.
+    path: PathBuf,
+}
+
+impl Server {
+    /// Listens on the display `name`, such as `wayland-1`, whose socket is
+    /// created in `$XDG_RUNTIME_DIR` unless `name` is an absolute path, as
+    /// resolved by [`Connection::connect_to_display`](crate::connection::Connection::connect_to_display).
+    ///
+    /// # Errors
+    /// Returns an error if `name` is relative and `$XDG_RUNTIME_DIR` is
+    /// unset, or if the socket cannot be created.
+    pub fn bind_display(name: &str) -> Result<Server> {
+        if Path::new(name).is_absolute() {
+            return Server::bind(name);
+        }
+
+        let xdg_runtime_dir = std::env::var("XDG_RUNTIME_DIR")?;
+        Server::bind(Path::new(&xdg_runtime_dir).join(name))
+    }
+
+    /// Listens on a socket created at `path`.
+    ///
+    /// The socket is non-blocking, so [`Server::accept`] can be called
+    /// whenever the server is polled as readable.
+    ///
+    /// # Errors
+    /// Returns an error if the socket cannot be created, e.g. because the
+    /// file exists.
+    pub fn bind(path: impl AsRef<Path>) -> Result<Server> {
+        let listener = UnixListener::bind(path.as_ref())?;
+        sys::set_nonblocking(listener.as_fd())?;
+        sys::set_cloexec(listener.as_fd())?;
+
+        Ok(Server {
+            listener,
+            path: path.as_ref().to_path_buf(),
+        })
+    }
+
+    /// Returns the path of the socket.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Accepts a pending client, `None` if no client is waiting.
+    ///
+    /// # Errors
+    /// Returns an error if the connection cannot be accepted.
+    pub fn accept(&self) -> Result<Option<Client>> {
+        match self.listener.accept() {
+            Ok((stream, _)) => Ok(Some(Client::from_stream(stream)?)),
+            Err(error) if error.kind() == io::ErrorKind::WouldBlock => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+}
+
+impl AsFd for Server {
+    /// Returns the listening socket, to be polled for new clients.
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.listener.as_fd()
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Write, os::unix::net::UnixStream};
+
+    use super::*;
+    use crate::{
+        connection::Connection,
+        protocol::{
+            WlObjectId,
+            encoder::{Encode, Encoder},
+            message::WlMessage,
+            registry,
+            types::{WlString, WlUInt},
+        },
+    };
+
+    /// The arguments of `wl_registry.global`.
+    struct Global(WlUInt, WlString, WlUInt);
+
+    impl Encode for Global {
+        fn encode(&self, encoder: &mut Encoder) {
+            self.0.encode(encoder);
+            self.1.encode(encoder);
+            self.2.encode(encoder);
+        }
+    }
+
+    #[test]
+    fn client_requests_are_parsed_and_events_delivered() {
+        let path = std::env::temp_dir().join(format!("wayland-server-{}", std::process::id()));
+        let server = Server::bind(&path).unwrap();
+        let conn = Connection::connect_to(server.path()).unwrap();
+        conn.flush().unwrap();
+
+        let mut client = server.accept().unwrap().unwrap();
+        assert!(server.accept().unwrap().is_none());
+
+        // The connection asks for the registry first
+        let requests = client.read_requests().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!((requests[0].object_id(), requests[0].opcode()), (1, 1));
+        let registry_id = Args::new(requests[0].args()).new_id().unwrap();
+        client
+            .insert_object(registry_id, WlObjectId::Registry, 1)
+            .unwrap();
+        assert!(
+            client
+                .insert_object(registry_id, WlObjectId::Registry, 1)
+                .is_err()
+        );
+
+        let global = Global(
+            WlUInt(1),
+            WlString::new("wl_compositor").unwrap(),
+            WlUInt(6),
+        );
+        let opcode = registry::event::Opcode::Global as u16;
+        client.send_event(registry_id, opcode, &global).unwrap();
+        client.flush().unwrap();
+        conn.dispatch().unwrap();
+        assert_eq!(conn.supports("wl_compositor"), Some(6));
+
+        client.post_error(registry_id, 3, "no such global").unwrap();
+        client.flush().unwrap();
+        let error = conn.dispatch().unwrap_err().to_string();
+        assert!(error.contains("no such global"), "{error}");
+
+        drop(conn);
+        while !client.is_closed() {
+            client.read_requests().unwrap();
+        }
+        drop(server);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn request_limits_below_the_header_length_accept_empty_requests() {
+        let (stream, mut peer) = UnixStream::pair().unwrap();
+        let mut client = Client::from_stream(stream).unwrap();
+        client.set_max_request_len(0);
+
+        // `wl_display.sync` without its argument, then with it
+        let header: Vec<u8> = WlMessage::new(1, 0, &[]).unwrap().into();
+        let sync: Vec<u8> = WlMessage::new(1, 0, &2u32.to_ne_bytes()).unwrap().into();
+        peer.write_all(&header).unwrap();
+        assert_eq!(client.read_requests().unwrap().len(), 1);
+
+        peer.write_all(&sync).unwrap();
+        assert!(client.read_requests().is_err());
+    }
+}
@@ -0,0 +1,408 @@
+use crate::{
+    error::{Result, anyhow},
+    protocol::{
+        compositor::Compositor,
+        subcompositor::{SubCompositor, SubSurface},
+        surface::Surface,
+    },
+};
+
+/// The handle of a subsurface in a [`SurfaceTree`], stable across removals
+/// of other subsurfaces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubsurfaceId(u64);
+
+/// An entry of the stacking order of the children of a surface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Layer {
+    /// The parent surface itself.
+    Parent,
+    /// A child subsurface.
+    Child(SubsurfaceId),
+}
+
+/// A subsurface of the tree and the surface it gives its role to.
+struct Node {
+    /// The handle of the subsurface.
+    id: SubsurfaceId,
+    /// The name the subsurface was added with.
+    name: String,
+    /// The subsurface it is attached to, `None` for the root surface.
+    parent: Option<SubsurfaceId>,
+    /// The role object, destroyed before the surface.
+    subsurface: SubSurface,
+    /// The surface the content of the subsurface is attached to.
+    surface: Surface,
+    /// The stacking order of the children of the surface and the surface
+    /// itself, from bottom to top.
+    stack: Vec<Layer>,
+    /// Whether the subsurface is in synchronized mode.
+    sync: bool,
+}
+
+/// The named subsurfaces nested in a surface, e.g. the video, controls and
+/// subtitles of a player window.
+///
+/// Subsurfaces are added under the root surface or another subsurface of
+/// the tree, and referred to by their [`SubsurfaceId`] or name. Their
+/// position, stacking order and synchronization mode are changed here and
+/// kept track of, so the stacking can be validated on the client and
+/// inspected with [`SurfaceTree::stack`].
+///
+/// All of these changes are double-buffered on the parent of a subsurface:
+/// they show with the next commit of the tree, made with
+/// [`SurfaceTree::commit`] or [`Window::commit_tree`](crate::window::Window::commit_tree).
+/// It commits the subsurfaces from the leaves up, then the root, so the
+/// content of all synchronized subsurfaces and the root changes at once.
+///
+/// Dropping the tree destroys its subsurfaces, the deepest first.
+pub struct SurfaceTree {
+    /// The compositor the surfaces of the subsurfaces are created from.
+    compositor: Compositor,
+    /// The subcompositor giving them the subsurface role.
+    subcompositor: SubCompositor,
+    /// The object ID of the root surface.
+    root: u32,
+    /// The stacking order of the children of the root and the root itself,
+    /// from bottom to top.
+    stack: Vec<Layer>,
+    /// The subsurfaces, each added after its parent.
+    nodes: Vec<Node>,
+    /// The handle of the next subsurface added.
+    next_id: u64,
+}
+
+impl SurfaceTree {
+    /// Creates an empty tree of subsurfaces nested in `root`.
+    ///
+    /// `root` stays owned by the caller and must outlive the tree; a
+    /// [`Window`](crate::window::Window) creates its tree with
+    /// [`Window::create_surface_tree`](crate::window::Window::create_surface_tree).
+    pub fn new(
+        compositor: &Compositor,
+        subcompositor: &SubCompositor,
+        root: &Surface,
+    ) -> SurfaceTree {
+        SurfaceTree {
+            compositor: compositor.clone(),
+            subcompositor: subcompositor.clone(),
+            root: root.id(),
+            stack: vec![Layer::Parent],
+            nodes: Vec::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Returns the object ID of the root surface.
+    pub fn root(&self) -> u32 {
+        self.root
+    }
+
+    /// Adds a subsurface named `name` under `parent`, or under the root if
+    /// `None`, stacked above its siblings and parent.
+    ///
+    /// The subsurface starts at position `(0, 0)` in synchronized mode and
+    /// shows once a buffer is attached to its surface and the tree is
+    /// committed.
+    ///
+    /// # Errors
+    /// Returns an error if the name is taken, the parent is not in the tree
+    /// or a protocol object cannot be created.
+    pub fn add(&mut self, name: &str, parent: Option<SubsurfaceId>) -> Result<SubsurfaceId> {
+        if self.find(name).is_some() {
+            return Err(anyhow!("Subsurface {name:?} already exists"));
+        }
+
+        let surface = self.compositor.create_surface()?;
+        let subsurface = match parent {
+            Some(parent) => self
+                .subcompositor
+                .get_subsurface(&surface, &self.node(parent)?.surface)?,
+            None => self.subcompositor.get_subsurface_of(&surface, self.root)?,
+        };
+
+        let id = SubsurfaceId(self.next_id);
+        self.next_id += 1;
+        self.stack_mut(parent)?.push(Layer::Child(id));
+        self.nodes.push(Node {
+            id,
+            name: name.to_string(),
+            parent,
+            subsurface,
+            surface,
+            stack: vec![Layer::Parent],
+            sync: true,
+        });
+
+        Ok(id)
+    }
+
+    /// Returns the handle of the subsurface named `name`.
+    pub fn find(&self, name: &str) -> Option<SubsurfaceId> {
+        self.nodes
+            .iter()
+            .find(|node| node.name == name)
+            .map(|node| node.id)
+    }
+
+    /// Returns the name of the subsurface.
+    pub fn name(&self, id: SubsurfaceId) -> Option<&str> {
+        self.node(id).ok().map(|node| node.name.as_str())
+    }
+
+    /// Returns the surface of the subsurface, to attach its content to.
+    ///
+    /// The surface must not be given another role, nor be committed by
+    /// [`Surface::commit`] expecting the tree to follow.
+    pub fn surface(&self, id: SubsurfaceId) -> Option<&Surface> {
+        self.node(id).ok().map(|node| &node.surface)
+    }
+
+    /// Returns the parent of the subsurface, `None` if it is attached to
+    /// the root or not in the tree.
+    pub fn parent(&self, id: SubsurfaceId) -> Option<SubsurfaceId> {
+        self.node(id).ok().and_then(|node| node.parent)
+    }
+
+    /// Returns the stacking order of the children of `parent`, or of the
+    /// root if `None`, from bottom to top; `None` entries stand for the
+    /// parent itself.
+    ///
+    /// # Errors
+    /// Returns an error if the parent is not in the tree.
+    pub fn stack(&self, parent: Option<SubsurfaceId>) -> Result<Vec<Option<SubsurfaceId>>> {
+        let stack = match parent {
+            Some(parent) => &self.node(parent)?.stack,
+            None => &self.stack,
+        };
+
+        Ok(stack
+            .iter()
+            .map(|layer| match layer {
+                Layer::Parent => None,
+                Layer::Child(id) => Some(*id),
+            })
+            .collect())
+    }
+
+    /// Moves the subsurface to `(x, y)` in the surface-local coordinates of
+    /// its parent.
+    pub fn set_position(&self, id: SubsurfaceId, x: i32, y: i32) -> Result<()> {
+        self.node(id)?.subsurface.set_position(x, y)
+    }
+
+    /// Stacks the subsurface right above `sibling`, another child of the
+    /// same parent, or above the parent itself if `None`.
+    ///
+    /// # Errors
+    /// Returns an error if either subsurface is not in the tree or they do
+    /// not share a parent.
+    pub fn place_above(&mut self, id: SubsurfaceId, sibling: Option<SubsurfaceId>) -> Result<()> {
+        self.restack(id, sibling, true)
+    }
+
+    /// Stacks the subsurface right below `sibling`, another child of the
+    /// same parent, or below the parent itself if `None`.
+    ///
+    /// # Errors
+    /// Returns an error if either subsurface is not in the tree or they do
+    /// not share a parent.
+    pub fn place_below(&mut self, id: SubsurfaceId, sibling: Option<SubsurfaceId>) -> Result<()> {
+        self.restack(id, sibling, false)
+    }
+
+    /// Switches the subsurface between synchronized mode, where the commits
+    /// of its surface wait for the next commit of the tree, and
+    /// desynchronized mode, where they show right away.
+    ///
+    /// A subsurface behaves as synchronized while any of its ancestors is,
+    /// whatever its own mode.
+    pub fn set_sync(&mut self, id: SubsurfaceId, sync: bool) -> Result<()> {
+        let node = self.node_mut(id)?;
+        if node.sync == sync {
+            return Ok(());
+        }
+
+        if sync {
+            node.subsurface.set_sync()?;
+        } else {
+            node.subsurface.set_desync()?;
+        }
+        node.sync = sync;
+
+        Ok(())
+    }
+
+    /// Returns whether the subsurface is in synchronized mode, as set with
+    /// [`SurfaceTree::set_sync`].
+    pub fn is_sync(&self, id: SubsurfaceId) -> Option<bool> {
+        self.node(id).ok().map(|node| node.sync)
+    }
+
+    /// Destroys the subsurface and the subsurfaces nested in it, hiding
+    /// them right away.
+    ///
+    /// # Errors
+    /// Returns an error if the subsurface is not in the tree.
+    pub fn remove(&mut self, id: SubsurfaceId) -> Result<()> {
+        let parent = self.node(id)?.parent;
+
+        // Children are added after their parent, so collecting in order
+        // finds the whole subtree in one pass.
+        let mut removed = vec![id];
+        for node in &self.nodes {
+            if node.parent.is_some_and(|parent| removed.contains(&parent)) {
+                removed.push(node.id);
+            }
+        }
+
+        // The deepest subsurfaces go first.
+        for index in (0..self.nodes.len()).rev() {
+            if removed.contains(&self.nodes[index].id) {
+                self.nodes.remove(index);
+            }
+        }
+        self.stack_mut(parent)?
+            .retain(|layer| *layer != Layer::Child(id));
+
+        Ok(())
+    }
+
+    /// Commits the surfaces of the subsurfaces from the leaves up, without
+    /// the root.
+    ///
+    /// The state of synchronized subsurfaces is cached until the root is
+    /// committed, which [`SurfaceTree::commit`] does right after.
+    pub fn commit_children(&self) -> Result<()> {
+        for node in self.nodes.iter().rev() {
+            node.surface.commit()?;
+        }
+
+        Ok(())
+    }
+
+    /// Commits the whole tree: the subsurfaces from the leaves up, then
+    /// `root`, applying the content of all synchronized subsurfaces, their
+    /// positions and stacking order at once.
+    ///
+    /// # Errors
+    /// Returns an error if `root` is not the root of the tree.
+    pub fn commit(&self, root: &Surface) -> Result<()> {
+        if root.id() != self.root {
+            return Err(anyhow!(
+                "wl_surface {} is not the root of the surface tree, wl_surface {} is",
+                root.id(),
+                self.root
+            ));
+        }
+
+        self.commit_children()?;
+        root.commit()
+    }
+
+    /// Stacks `id` above or below `sibling`, see [`SurfaceTree::place_above`].
+    fn restack(
+        &mut self,
+        id: SubsurfaceId,
+        sibling: Option<SubsurfaceId>,
+        above: bool,
+    ) -> Result<()> {
+        let parent = self.node(id)?.parent;
+        let reference = match sibling {
+            Some(sibling) if sibling == id => {
+                return Err(anyhow!(
+                    "Subsurface {:?} cannot be stacked relative to itself",
+                    self.node(id)?.name
+                ));
+            }
+            Some(sibling) => {
+                let node = self.node(sibling)?;
+                if node.parent != parent {
+                    return Err(anyhow!(
+                        "Subsurfaces {:?} and {:?} do not share a parent",
+                        self.node(id)?.name,
+                        node.name
+                    ));
+                }
+                node.surface.id()
+            }
+            None => match parent {
+                Some(parent) => self.node(parent)?.surface.id(),
+                None => self.root,
+            },
+        };
+
+        self.node(id)?.subsurface.place(reference, above)?;
+
+        let sibling = sibling.map_or(Layer::Parent, Layer::Child);
+        restack(self.stack_mut(parent)?, Layer::Child(id), sibling, above);
+
+        Ok(())
+    }
+
+    /// Returns the subsurface with the handle `id`.
+    fn node(&self, id: SubsurfaceId) -> Result<&Node> {
+        self.nodes
+            .iter()
+            .find(|node| node.id == id)
+            .ok_or_else(|| anyhow!("Subsurface {id:?} is not in the surface tree"))
+    }
+
+    /// Returns the subsurface with the handle `id`, mutably.
+    fn node_mut(&mut self, id: SubsurfaceId) -> Result<&mut Node> {
+        self.nodes
+            .iter_mut()
+            .find(|node| node.id == id)
+            .ok_or_else(|| anyhow!("Subsurface {id:?} is not in the surface tree"))
+    }
+
+    /// Returns the stacking order of the children of `parent`.
+    fn stack_mut(&mut self, parent: Option<SubsurfaceId>) -> Result<&mut Vec<Layer>> {
+        match parent {
+            Some(parent) => Ok(&mut self.node_mut(parent)?.stack),
+            None => Ok(&mut self.stack),
+        }
+    }
+}
+
+impl Drop for SurfaceTree {
+    fn drop(&mut self) {
+        // Subsurfaces are added after their parent; destroy the deepest first.
+        while self.nodes.pop().is_some() {}
+    }
+}
+
+/// Moves `layer` right above or below `reference` in `stack`, which holds
+/// both.
+fn restack(stack: &mut Vec<Layer>, layer: Layer, reference: Layer, above: bool) {
+    stack.retain(|entry| *entry != layer);
+    let Some(index) = stack.iter().position(|entry| *entry == reference) else {
+        return;
+    };
+
+    stack.insert(if above { index + 1 } else { index }, layer);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restack_moves_a_layer_next_to_its_reference() {
+        let (a, b, c) = (
+            Layer::Child(SubsurfaceId(0)),
+            Layer::Child(SubsurfaceId(1)),
+            Layer::Child(SubsurfaceId(2)),
+        );
+        let mut stack = vec![Layer::Parent, a, b, c];
+
+        restack(&mut stack, c, Layer::Parent, false);
+        assert_eq!(stack, [c, Layer::Parent, a, b]);
+
+        restack(&mut stack, a, b, true);
+        assert_eq!(stack, [c, Layer::Parent, b, a]);
+
+        restack(&mut stack, c, a, false);
+        assert_eq!(stack, [Layer::Parent, b, c, a]);
+    }
+}
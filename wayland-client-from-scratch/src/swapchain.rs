@@ -0,0 +1,607 @@
+use crate::{
+    damage::DamageTracker,
+    error::{Result, anyhow},
+    geometry::Rect,
+    protocol::{
+        buffer::Buffer, shm::Shm, shm::event::format::Format, shm_pool::ShmPool, surface::Surface,
+    },
+};
+
+/// The number of bytes per pixel of the formats a swapchain can allocate.
+const SWAPCHAIN_BYTES_PER_PIXEL: i32 = 4;
+
+/// The step, in pixels, buffers grow by in [`Swapchain::fit`].
+const SWAPCHAIN_FIT_STEP: i32 = 256;
+
+/// A set of equally sized shared memory buffers drawn into in turn.
+///
+/// All buffers live in a single `wl_shm_pool`. [`Swapchain::acquire`] hands
+/// out the next buffer the compositor is not reading from; committing a
+/// surface it is attached to marks it busy until the compositor sends
+/// `wl_buffer.release`.
+/// When every buffer is busy, the client should wait for the next frame
+/// callback or release instead of drawing.
+///
+/// [`Swapchain::acquire_damaged`] keeps the buffers up to date for clients
+/// redrawing only what changed: the regions drawn since a buffer was last
+/// handed out are copied into it from the buffer drawn last, instead of the
+/// whole frame.
+pub struct Swapchain {
+    /// The shm global used to allocate pools.
+    shm: Shm,
+    /// The pool holding every buffer of the swapchain.
+    pool: ShmPool,
+    /// The buffers, laid out back to back in the pool.
+    buffers: Vec<Buffer>,
+    /// The index of the buffer handed out last.
+    current: usize,
+    /// Per buffer, the regions where it differs from the buffer drawn last.
+    outdated: Vec<DamageTracker>,
+    /// The buffer drawn last, `None` until a buffer is handed out after
+    /// the buffers were allocated.
+    last: Option<usize>,
+    /// Per buffer, the frame it was last handed out for, `None` while its
+    /// content is undefined.
+    drawn: Vec<Option<u64>>,
+    /// The number of frames handed out.
+    frame: u64,
+    /// The width of every buffer in pixels.
+    width: i32,
+    /// The height of every buffer in pixels.
+    height: i32,
+    /// The part of the buffers handed out for drawing, smaller than the
+    /// buffers after [`Swapchain::fit`].
+    content_size: (i32, i32),
+    /// The pixel format of every buffer.
+    format: Format,
+}
+
+/// A buffer handed out by [`Swapchain::acquire`], ready to be drawn into.
+pub struct SwapchainBuffer<'a> {
+    /// The index of the buffer within the swapchain.
+    index: usize,
+    /// The wl_buffer to attach once drawing is done.
+    buffer: &'a Buffer,
+    /// The pixels of the buffer.
+    pixels: &'a mut [u8],
+    /// The width of the part to draw in pixels.
+    width: i32,
+    /// The height of the part to draw in pixels.
+    height: i32,
+    /// The number of bytes per row.
+    stride: i32,
+    /// The number of frames since the buffer was last drawn.
+    age: u64,
+    /// The pixel format of the buffer.
+    format: Format,
+}
+
+impl Swapchain {
+    /// Allocates `count` buffers of `width` x `height` pixels in `format`.
+    ///
+    /// # Errors
+    /// Returns an error if `count` is zero, the format is not a 32-bit format
+    /// or the pool cannot be created.
+    pub fn new(
+        shm: &Shm,
+        count: usize,
+        width: i32,
+        height: i32,
+        format: Format,
+    ) -> Result<Swapchain> {
+        if count == 0 {
+            return Err(anyhow!("Swapchain needs at least one buffer"));
+        }
+
+        let (pool, buffers) = allocate(shm, count, width, height, format)?;
+
+        Ok(Swapchain {
+            shm: shm.clone(),
+            pool,
+            buffers,
+            current: count - 1,
+            outdated: (0..count)
+                .map(|_| DamageTracker::new(width, height))
+                .collect(),
+            last: None,
+            drawn: vec![None; count],
+            frame: 0,
+            width,
+            height,
+            content_size: (width, height),
+            format,
+        })
+    }
+
+    /// Returns the width of the buffers in pixels.
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    /// Returns the height of the buffers in pixels.
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    /// Returns the number of bytes per row.
+    pub fn stride(&self) -> i32 {
+        self.width * SWAPCHAIN_BYTES_PER_PIXEL
+    }
+
+    /// Returns the size of the part of the buffers handed out by
+    /// [`Swapchain::acquire`], the whole buffers unless set with
+    /// [`Swapchain::fit`].
+    pub fn content_size(&self) -> (i32, i32) {
+        self.content_size
+    }
+
+    /// Returns `true` if the buffers are larger than their content, which
+    /// must then be cropped, e.g. with `wp_viewport`.
+    pub fn is_oversized(&self) -> bool {
+        self.content_size != (self.width, self.height)
+    }
+
+    /// Returns the pixel format of the buffers.
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Returns the number of buffers in the swapchain.
+    pub fn len(&self) -> usize {
+        self.buffers.len()
+    }
+
+    /// Returns `true` if the swapchain holds no buffers, which never happens.
+    pub fn is_empty(&self) -> bool {
+        self.buffers.is_empty()
+    }
+
+    /// Hands out the next buffer the compositor is not using.
+    ///
+    /// Buffers are tried in round-robin order starting after the one handed
+    /// out last. Returns `None` if every buffer is still busy.
+    pub fn acquire(&mut self) -> Option<SwapchainBuffer<'_>> {
+        let index = self.next_free()?;
+
+        // Whatever is drawn may change every pixel
+        for tracker in &mut self.outdated {
+            tracker.damage_all();
+        }
+        self.outdated[index].take();
+        self.last = Some(index);
+
+        Some(self.hand_out(index))
+    }
+
+    /// Hands out the next buffer the compositor is not using, holding the
+    /// frame drawn last everywhere but in `damage`, the regions the caller
+    /// is about to redraw.
+    ///
+    /// The regions drawn into the other buffers since this one was handed
+    /// out are copied from the buffer drawn last, so only the damage of the
+    /// missed frames is copied rather than the whole frame. `damage` is
+    /// recorded for the other buffers in turn, and is then expected to be
+    /// drawn and applied to the surface with [`DamageTracker::apply`].
+    ///
+    /// Returns `None` if every buffer is still busy.
+    pub fn acquire_damaged(&mut self, damage: &DamageTracker) -> Option<SwapchainBuffer<'_>> {
+        let index = self.next_free()?;
+
+        let outdated = self.outdated[index].take();
+        if let Some(last) = self.last.filter(|&last| last != index) {
+            let (len, stride) = (self.buffers[index].len(), self.stride() as usize);
+            copy_regions(
+                self.pool.data_mut(),
+                self.buffers[last].offset(),
+                self.buffers[index].offset(),
+                len,
+                stride,
+                &outdated,
+            );
+        }
+        for (other, tracker) in self.outdated.iter_mut().enumerate() {
+            if other != index {
+                damage.rects().iter().for_each(|rect| tracker.add(*rect));
+            }
+        }
+        self.last = Some(index);
+
+        Some(self.hand_out(index))
+    }
+
+    /// Returns the index of the next buffer the compositor is not using,
+    /// in round-robin order starting after the one handed out last.
+    fn next_free(&mut self) -> Option<usize> {
+        let count = self.buffers.len();
+        let index = (1..=count)
+            .map(|step| (self.current + step) % count)
+            .find(|&index| !self.buffers[index].is_busy())?;
+        self.current = index;
+
+        Some(index)
+    }
+
+    /// Hands out the buffer at `index`.
+    fn hand_out(&mut self, index: usize) -> SwapchainBuffer<'_> {
+        self.frame += 1;
+        let age = self.drawn[index]
+            .replace(self.frame)
+            .map_or(0, |drawn| self.frame - drawn);
+
+        let buffer = &self.buffers[index];
+        let range = buffer.offset()..buffer.offset() + buffer.len();
+
+        let (width, height) = self.content_size;
+        SwapchainBuffer {
+            index,
+            buffer,
+            pixels: &mut self.pool.data_mut()[range],
+            width,
+            height,
+            stride: self.width * SWAPCHAIN_BYTES_PER_PIXEL,
+            age,
+            format: self.format,
+        }
+    }
+
+    /// Reallocates every buffer for a new size.
+    ///
+    /// If the compositor uses none of the buffers, they are recreated in the
+    /// same pool, grown with [`ShmPool::resize`] if needed. Otherwise a new
+    /// pool is created and the old pool and buffers are destroyed, the busy
+    /// ones once they are released. Resizing to the current size only
+    /// drops the content size set with [`Swapchain::fit`].
+    pub fn resize(&mut self, width: i32, height: i32) -> Result<()> {
+        validate(width, height, self.format)?;
+
+        self.content_size = (width, height);
+        if width == self.width && height == self.height {
+            return Ok(());
+        }
+
+        let count = self.buffers.len();
+        if self.buffers.iter().any(Buffer::is_busy) {
+            // The new buffers must not overlap memory the compositor reads
+            let (pool, buffers) = allocate(&self.shm, count, width, height, self.format)?;
+
+            let old_pool = std::mem::replace(&mut self.pool, pool);
+            self.buffers = buffers;
+            old_pool.destroy()?;
+        } else {
+            // The old buffers are destroyed before their memory is reused
+            self.buffers.clear();
+            self.buffers = allocate_in(&mut self.pool, count, width, height, self.format)?;
+        }
+
+        self.current = self.buffers.len() - 1;
+        self.width = width;
+        self.height = height;
+        for tracker in &mut self.outdated {
+            tracker.resize(width, height);
+        }
+        self.last = None;
+        self.drawn.fill(None);
+
+        Ok(())
+    }
+
+    /// Hands out `width` x `height` pixels of the buffers, reallocating them
+    /// only if they are too small, for sizes changing with every frame such
+    /// as during an interactive resize.
+    ///
+    /// Buffers that are large enough are kept and [`Swapchain::acquire`]
+    /// hands out their top left corner; the rest of the buffers must be
+    /// cropped away, e.g. with `wp_viewport`. Buffers that are too small
+    /// grow in steps of 256 pixels, so a growing window reallocates them
+    /// only every few frames. [`Swapchain::resize`] returns to buffers of
+    /// the exact size.
+    pub fn fit(&mut self, width: i32, height: i32) -> Result<()> {
+        validate(width, height, self.format)?;
+
+        if width > self.width || height > self.height {
+            let grow = |size: i32, current: i32| {
+                if size > current {
+                    // Sizes were validated as positive
+                    (size as u32).next_multiple_of(SWAPCHAIN_FIT_STEP as u32) as i32
+                } else {
+                    current
+                }
+            };
+            self.resize(grow(width, self.width), grow(height, self.height))?;
+        }
+        self.content_size = (width, height);
+
+        Ok(())
+    }
+}
+
+impl SwapchainBuffer<'_> {
+    /// Returns the index of the buffer within the swapchain.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the number of frames since the buffer was last drawn, the
+    /// shm counterpart of `EGL_EXT_buffer_age`.
+    ///
+    /// A buffer of age 1 holds the previous frame, and one of age `n` the
+    /// frame `n` frames back, so only the union of the damage of the last
+    /// `n - 1` frames and the new damage has to be redrawn. An age of 0
+    /// means the content is undefined, e.g. after the buffers were
+    /// reallocated, and everything has to be redrawn.
+    pub fn age(&self) -> u64 {
+        self.age
+    }
+
+    /// Returns the width to draw in pixels, which is less than the width of
+    /// the buffer after [`Swapchain::fit`].
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    /// Returns the height to draw in pixels, which is less than the height
+    /// of the buffer after [`Swapchain::fit`].
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    /// Returns the number of bytes per row.
+    pub fn stride(&self) -> i32 {
+        self.stride
+    }
+
+    /// Returns the pixels to draw into, in rows of
+    /// [`SwapchainBuffer::stride`] bytes.
+    pub fn pixels(&mut self) -> &mut [u8] {
+        self.pixels
+    }
+
+    /// Returns the pixel format of the buffer.
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Returns the pixels as a tiny-skia pixmap to draw into.
+    ///
+    /// tiny-skia stores premultiplied RGBA in memory order, which is the
+    /// layout of [`Format::Abgr8888`] only, so `None` is returned for
+    /// swapchains of any other format. The pixmap spans whole rows of
+    /// [`SwapchainBuffer::stride`] bytes, so after [`Swapchain::fit`] it is
+    /// wider than [`SwapchainBuffer::width`], the part to draw.
+    #[cfg(feature = "tiny-skia")]
+    pub fn pixmap(&mut self) -> Option<tiny_skia::PixmapMut<'_>> {
+        if self.format != Format::Abgr8888 {
+            return None;
+        }
+
+        let row_width = (self.stride / SWAPCHAIN_BYTES_PER_PIXEL) as u32;
+        let len = self.stride as usize * self.height as usize;
+        tiny_skia::PixmapMut::from_bytes(&mut self.pixels[..len], row_width, self.height as u32)
+    }
+
+    /// Attaches the buffer to `surface`.
+    ///
+    /// The surface still has to be damaged and committed; the commit marks
+    /// the buffer busy until released.
+    pub fn attach(self, surface: &Surface) -> Result<()> {
+        surface.attach(Some(self.buffer))
+    }
+}
+
+/// Creates a pool holding `count` buffers and the buffers themselves.
+fn allocate(
+    shm: &Shm,
+    count: usize,
+    width: i32,
+    height: i32,
+    format: Format,
+) -> Result<(ShmPool, Vec<Buffer>)> {
+    validate(width, height, format)?;
+
+    let buffer_len = (width * SWAPCHAIN_BYTES_PER_PIXEL) as usize * height as usize;
+
+    let mut pool = shm.create_pool(buffer_len * count)?;
+    let buffers = allocate_in(&mut pool, count, width, height, format)?;
+
+    Ok((pool, buffers))
+}
+
+/// Creates `count` buffers at the start of `pool`, growing it if needed.
+///
+/// The parameters must have been checked with [`validate`].
+fn allocate_in(
+    pool: &mut ShmPool,
+    count: usize,
+    width: i32,
+    height: i32,
+    format: Format,
+) -> Result<Vec<Buffer>> {
+    let stride = width * SWAPCHAIN_BYTES_PER_PIXEL;
+    let buffer_len = stride as usize * height as usize;
+
+    if pool.size() < buffer_len * count {
+        pool.resize(buffer_len * count)?;
+    }
+
+    (0..count)
+        .map(|index| pool.create_buffer(index * buffer_len, width, height, stride, format))
+        .collect()
+}
+
+/// Copies the pixels of `rects` between the buffers of `len` bytes at the
+/// offsets `from` and `to` of the pool `data`, which do not overlap.
+fn copy_regions(
+    data: &mut [u8],
+    from: usize,
+    to: usize,
+    len: usize,
+    stride: usize,
+    rects: &[Rect],
+) {
+    let (src, dst) = if from < to {
+        let (head, tail) = data.split_at_mut(to);
+        (&head[from..from + len], &mut tail[..len])
+    } else {
+        let (head, tail) = data.split_at_mut(from);
+        (&tail[..len], &mut head[to..to + len])
+    };
+
+    let bytes_per_pixel = SWAPCHAIN_BYTES_PER_PIXEL as usize;
+    for rect in rects {
+        // Rectangles were clipped to the buffer bounds by the tracker
+        for row in rect.y as usize..rect.bottom() as usize {
+            let start = row * stride + rect.x as usize * bytes_per_pixel;
+            let end = start + rect.width as usize * bytes_per_pixel;
+            dst[start..end].copy_from_slice(&src[start..end]);
+        }
+    }
+}
+
+/// Checks that buffers of `width` x `height` pixels in `format` can be allocated.
+fn validate(width: i32, height: i32, format: Format) -> Result<()> {
+    if width <= 0 || height <= 0 {
+        return Err(anyhow!("Invalid swapchain size {}x{}", width, height));
+    }
+    if !matches!(
+        format,
+        Format::Argb8888
+            | Format::Xrgb8888
+            | Format::Xbgr8888
+            | Format::Abgr8888
+            | Format::Rgbx8888
+            | Format::Rgba8888
+            | Format::Bgrx8888
+            | Format::Bgra8888
+            | Format::Xrgb2101010
+            | Format::Argb2101010
+            | Format::Xbgr2101010
+            | Format::Abgr2101010
+    ) {
+        return Err(anyhow!(
+            "Swapchain only supports 32-bit formats, got {}",
+            format
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{connection::Connection, server::HeadlessCompositor};
+
+    /// Binds `wl_shm` on a connection to a headless compositor.
+    fn bind_shm() -> (HeadlessCompositor, Connection, Shm) {
+        let compositor = HeadlessCompositor::spawn().unwrap();
+        let conn = compositor.connect().unwrap();
+        let shm = Shm::bind(&conn).unwrap();
+        conn.roundtrip().unwrap();
+
+        (compositor, conn, shm)
+    }
+
+    #[test]
+    fn fit_reuses_large_enough_buffers() {
+        let (_compositor, _conn, shm) = bind_shm();
+        let mut swapchain = Swapchain::new(&shm, 2, 300, 200, Format::Argb8888).unwrap();
+
+        // Growing past the buffers reallocates them in steps
+        swapchain.fit(310, 190).unwrap();
+        assert_eq!((swapchain.width(), swapchain.height()), (512, 200));
+        assert_eq!(swapchain.content_size(), (310, 190));
+        assert!(swapchain.is_oversized());
+
+        let buffer = swapchain.acquire().unwrap();
+        assert_eq!((buffer.width(), buffer.height()), (310, 190));
+        assert_eq!(buffer.stride(), 512 * SWAPCHAIN_BYTES_PER_PIXEL);
+
+        // Smaller sizes keep the buffers
+        swapchain.fit(400, 150).unwrap();
+        assert_eq!((swapchain.width(), swapchain.height()), (512, 200));
+        assert_eq!(swapchain.content_size(), (400, 150));
+
+        swapchain.resize(400, 150).unwrap();
+        assert_eq!((swapchain.width(), swapchain.height()), (400, 150));
+        assert!(!swapchain.is_oversized());
+    }
+
+    #[test]
+    fn damaged_acquires_copy_only_the_missed_damage() {
+        let (_compositor, _conn, shm) = bind_shm();
+        let mut swapchain = Swapchain::new(&shm, 2, 4, 4, Format::Argb8888).unwrap();
+        let pixel_at = |buffer: &mut SwapchainBuffer<'_>, x: usize, y: usize| {
+            let stride = buffer.stride() as usize;
+            buffer.pixels()[y * stride + x * 4]
+        };
+
+        // The first frame is drawn in full
+        let mut damage = DamageTracker::new(4, 4);
+        let mut buffer = swapchain.acquire_damaged(&damage).unwrap();
+        assert_eq!(buffer.index(), 0);
+        buffer.pixels().fill(1);
+        damage.take();
+
+        // The second buffer catches up with the first frame, then the top
+        // left corner is redrawn
+        damage.add(Rect::new(0, 0, 2, 2));
+        let mut buffer = swapchain.acquire_damaged(&damage).unwrap();
+        assert_eq!(buffer.index(), 1);
+        assert!(buffer.pixels().iter().all(|&byte| byte == 1));
+        let stride = buffer.stride() as usize;
+        for row in 0..2 {
+            buffer.pixels()[row * stride..row * stride + 8].fill(2);
+        }
+        damage.take();
+
+        // The first buffer only misses the corner, the rest of it is left
+        // alone
+        let pixels = swapchain.pool.data_mut();
+        pixels[..64].fill(7);
+        damage.add(Rect::new(3, 3, 1, 1));
+        let mut buffer = swapchain.acquire_damaged(&damage).unwrap();
+        assert_eq!(buffer.index(), 0);
+        assert_eq!(pixel_at(&mut buffer, 1, 1), 2);
+        assert_eq!(pixel_at(&mut buffer, 2, 2), 7);
+    }
+
+    #[test]
+    fn buffer_age_counts_the_frames_since_a_buffer_was_drawn() {
+        let (_compositor, _conn, shm) = bind_shm();
+        let mut swapchain = Swapchain::new(&shm, 3, 4, 4, Format::Argb8888).unwrap();
+
+        let ages: Vec<u64> = (0..5).map(|_| swapchain.acquire().unwrap().age()).collect();
+        assert_eq!(ages, [0, 0, 0, 3, 3]);
+
+        // Reallocated buffers start over
+        swapchain.resize(8, 8).unwrap();
+        assert_eq!(swapchain.acquire().unwrap().age(), 0);
+    }
+
+    #[cfg(feature = "tiny-skia")]
+    #[test]
+    fn abgr_buffers_are_tiny_skia_pixmaps() {
+        use crate::protocol::compositor::Compositor;
+
+        let (compositor, conn, shm) = bind_shm();
+        assert!(shm.supports_format(Format::Abgr8888));
+        let mut swapchain = Swapchain::new(&shm, 1, 4, 4, Format::Argb8888).unwrap();
+        assert!(swapchain.acquire().unwrap().pixmap().is_none());
+
+        let surface = Compositor::bind(&conn).unwrap().create_surface().unwrap();
+        let mut swapchain = Swapchain::new(&shm, 1, 4, 4, Format::Abgr8888).unwrap();
+        let mut buffer = swapchain.acquire().unwrap();
+        let mut pixmap = buffer.pixmap().unwrap();
+        pixmap.fill(tiny_skia::Color::from_rgba8(255, 0, 0, 128));
+
+        // Premultiplied red, stored as R, G, B, A
+        assert_eq!(buffer.pixels()[..4], [128, 0, 0, 128]);
+        buffer.attach(&surface).unwrap();
+        surface.commit().unwrap();
+        conn.roundtrip().unwrap();
+
+        let contents = compositor.surfaces()[0].contents.clone().unwrap();
+        assert_eq!(contents.format, Format::Abgr8888 as u32);
+        assert_eq!(contents.pixels[..4], [128, 0, 0, 128]);
+    }
+}
@@ -0,0 +1,333 @@
+//! The cells holding the state shared by the handles of a connection and its
+//! proxies, which may be used from several threads.
+//!
+//! [`Lock`] and [`LockCell`] stand in for `RefCell` and `Cell`: on a single
+//! thread they behave the same, re-entrant borrows included, so the state is
+//! borrowed the way it always was, and other threads wait for their turn.
+
+use std::{
+    cell::{BorrowMutError, Ref, RefCell, RefMut},
+    fmt::{self, Debug, Formatter},
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    sync::{Condvar, Mutex, MutexGuard, PoisonError},
+    thread::{self, ThreadId},
+};
+
+/// A `RefCell` shared between threads.
+///
+/// A thread owns the lock from its first borrow until its last borrow ends,
+/// and other threads borrowing meanwhile wait until it is released. Within
+/// the owning thread, borrows follow the rules of `RefCell`: shared borrows
+/// nest, and a conflicting borrow panics. A thread can therefore call back
+/// into code borrowing the state it already borrows, as callbacks do, and
+/// never waits on itself.
+pub(crate) struct Lock<T: ?Sized> {
+    /// The thread owning the lock and the number of its live borrows.
+    owner: Mutex<Owner>,
+    /// Signalled whenever the owning thread releases the lock.
+    released: Condvar,
+    /// The value, only touched by the owning thread.
+    cell: RefCell<T>,
+}
+
+// SAFETY: the cell, including its borrow flag, is only touched by the thread
+// owning the lock, and ownership is handed over under the `owner` mutex, so
+// no two threads ever access it at once. The guards are not `Send`, so a
+// borrow ends on the thread that took it. Like for a `Mutex`, sharing the
+// lock moves the value between threads, hence `T: Send`.
+unsafe impl<T: ?Sized + Send> Sync for Lock<T> {}
+
+/// The owner of a [`Lock`].
+#[derive(Default)]
+struct Owner {
+    /// The thread holding the lock, if any.
+    thread: Option<ThreadId>,
+    /// The number of borrows of that thread.
+    borrows: usize,
+}
+
+/// The ownership of a [`Lock`] by the current thread, for one borrow.
+struct Hold<'a> {
+    /// The owner of the lock, released when its last borrow ends.
+    owner: &'a Mutex<Owner>,
+    /// Signalled when the lock is released.
+    released: &'a Condvar,
+    /// Keeps the hold on the thread that took it.
+    _thread: PhantomData<*const ()>,
+}
+
+impl Drop for Hold<'_> {
+    fn drop(&mut self) {
+        let mut owner = lock(self.owner);
+        owner.borrows -= 1;
+        if owner.borrows == 0 {
+            owner.thread = None;
+            self.released.notify_all();
+        }
+    }
+}
+
+/// Locks the owner of a [`Lock`].
+///
+/// The owner is only updated by code that cannot panic, so a poisoned mutex
+/// still holds consistent data and is used as is.
+fn lock(owner: &Mutex<Owner>) -> MutexGuard<'_, Owner> {
+    owner.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+impl<T> Lock<T> {
+    /// Creates a lock holding `value`.
+    pub(crate) const fn new(value: T) -> Lock<T> {
+        Lock {
+            owner: Mutex::new(Owner {
+                thread: None,
+                borrows: 0,
+            }),
+            released: Condvar::new(),
+            cell: RefCell::new(value),
+        }
+    }
+
+    /// Replaces the value, returning the old one, like `RefCell::replace`.
+    pub(crate) fn replace(&self, value: T) -> T {
+        std::mem::replace(&mut *self.borrow_mut(), value)
+    }
+
+    /// Takes the value, leaving the default one, like `RefCell::take`.
+    pub(crate) fn take(&self) -> T
+    where
+        T: Default,
+    {
+        self.replace(T::default())
+    }
+}
+
+impl<T: ?Sized> Lock<T> {
+    /// Borrows the value, waiting for other threads to release the lock.
+    ///
+    /// # Panics
+    /// Panics if the current thread borrows the value mutably.
+    pub(crate) fn borrow(&self) -> LockRef<'_, T> {
+        let hold = self.hold(|| false).expect("borrows wait until released");
+        LockRef {
+            value: self.cell.borrow(),
+            _hold: hold,
+        }
+    }
+
+    /// Borrows the value mutably, waiting for other threads to release the
+    /// lock.
+    ///
+    /// # Panics
+    /// Panics if the current thread borrows the value already.
+    pub(crate) fn borrow_mut(&self) -> LockRefMut<'_, T> {
+        let hold = self.hold(|| false).expect("borrows wait until released");
+        LockRefMut {
+            value: self.cell.borrow_mut(),
+            _hold: hold,
+        }
+    }
+
+    /// Borrows the value mutably, waiting for other threads to release the
+    /// lock, unless the current thread borrows it already.
+    ///
+    /// # Errors
+    /// Returns an error if the current thread borrows the value already.
+    pub(crate) fn try_borrow_mut(&self) -> Result<LockRefMut<'_, T>, BorrowMutError> {
+        let hold = self.hold(|| false).expect("borrows wait until released");
+        Ok(LockRefMut {
+            value: self.cell.try_borrow_mut()?,
+            _hold: hold,
+        })
+    }
+
+    /// Like [`Lock::borrow`], but gives up waiting for another thread once
+    /// `done` returns `true`, which is checked every time the lock is
+    /// released.
+    ///
+    /// # Returns
+    /// `None` if `done` returned `true` first.
+    pub(crate) fn borrow_unless(&self, done: impl Fn() -> bool) -> Option<LockRef<'_, T>> {
+        let hold = self.hold(done)?;
+        Some(LockRef {
+            value: self.cell.borrow(),
+            _hold: hold,
+        })
+    }
+
+    /// Makes the current thread the owner of the lock for one more borrow,
+    /// waiting for another owner to release it unless `done`.
+    fn hold(&self, done: impl Fn() -> bool) -> Option<Hold<'_>> {
+        let current = thread::current().id();
+        let mut owner = lock(&self.owner);
+        while owner.thread.is_some_and(|thread| thread != current) {
+            if done() {
+                return None;
+            }
+            owner = self
+                .released
+                .wait(owner)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+
+        owner.thread = Some(current);
+        owner.borrows += 1;
+        Some(Hold {
+            owner: &self.owner,
+            released: &self.released,
+            _thread: PhantomData,
+        })
+    }
+}
+
+impl<T: Default> Default for Lock<T> {
+    fn default() -> Lock<T> {
+        Lock::new(T::default())
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for Lock<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Lock").field(&&*self.borrow()).finish()
+    }
+}
+
+/// A shared borrow of the value of a [`Lock`].
+pub(crate) struct LockRef<'a, T: ?Sized> {
+    /// The borrow, ended before the hold.
+    value: Ref<'a, T>,
+    /// The ownership of the lock for the borrow.
+    _hold: Hold<'a>,
+}
+
+impl<T: ?Sized> Deref for LockRef<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for LockRef<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+/// An exclusive borrow of the value of a [`Lock`].
+pub(crate) struct LockRefMut<'a, T: ?Sized> {
+    /// The borrow, ended before the hold.
+    value: RefMut<'a, T>,
+    /// The ownership of the lock for the borrow.
+    _hold: Hold<'a>,
+}
+
+impl<T: ?Sized> Deref for LockRefMut<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: ?Sized> DerefMut for LockRefMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+/// A `Cell` shared between threads.
+///
+/// Every access locks the value only for as long as it is copied or
+/// swapped, so it never waits on another lock.
+#[derive(Default)]
+pub(crate) struct LockCell<T>(Mutex<T>);
+
+impl<T> LockCell<T> {
+    /// Creates a cell holding `value`.
+    pub(crate) const fn new(value: T) -> LockCell<T> {
+        LockCell(Mutex::new(value))
+    }
+
+    /// Locks the value; nothing can panic while it is locked, so a poisoned
+    /// lock is used as is.
+    fn value(&self) -> MutexGuard<'_, T> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Sets the value.
+    pub(crate) fn set(&self, value: T) {
+        // The old value is dropped once the lock is released
+        let _old = self.replace(value);
+    }
+
+    /// Replaces the value, returning the old one.
+    pub(crate) fn replace(&self, value: T) -> T {
+        std::mem::replace(&mut *self.value(), value)
+    }
+
+    /// Takes the value, leaving the default one.
+    pub(crate) fn take(&self) -> T
+    where
+        T: Default,
+    {
+        self.replace(T::default())
+    }
+}
+
+impl<T: Copy> LockCell<T> {
+    /// Returns a copy of the value.
+    pub(crate) fn get(&self) -> T {
+        *self.value()
+    }
+}
+
+impl<T: Copy> Clone for LockCell<T> {
+    fn clone(&self) -> LockCell<T> {
+        LockCell::new(self.get())
+    }
+}
+
+impl<T: Copy + Debug> Debug for LockCell<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("LockCell").field(&self.get()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Barrier};
+
+    use super::*;
+
+    #[test]
+    fn borrows_nest_on_one_thread_and_wait_on_others() {
+        let lock = Arc::new(Lock::new(Vec::new()));
+        let outer = lock.borrow();
+        let inner = lock.borrow();
+        assert!(lock.try_borrow_mut().is_err());
+        assert!(lock.borrow_unless(|| true).is_some());
+        drop((outer, inner));
+
+        let mut held = lock.borrow_mut();
+        let (started, checked) = (Arc::new(Barrier::new(2)), Arc::new(Barrier::new(2)));
+        let other = std::thread::spawn({
+            let (lock, started, checked) = (lock.clone(), started.clone(), checked.clone());
+            move || {
+                started.wait();
+                assert!(lock.borrow_unless(|| true).is_none());
+                checked.wait();
+                // Waits for the borrow of the main thread to end
+                lock.borrow_mut().push(2);
+            }
+        });
+        started.wait();
+        checked.wait();
+        held.push(1);
+        drop(held);
+        other.join().unwrap();
+
+        assert_eq!(*lock.borrow(), [1, 2]);
+    }
+}
@@ -0,0 +1,143 @@
+// Hand-written declarations of the libc symbols and structures used by the
+// wrappers in `sys`, with the layouts used by glibc on Linux.
+
+use std::ffi::{c_char, c_int, c_long, c_short, c_uint, c_ulong, c_void};
+
+/// `memfd_create` flag: set close-on-exec on the new descriptor.
+pub const MFD_CLOEXEC: c_uint = 0x0001;
+
+/// `mmap` protection flag: pages may be read.
+pub const PROT_READ: c_int = 0x1;
+/// `mmap` protection flag: pages may be written.
+pub const PROT_WRITE: c_int = 0x2;
+/// `mmap` flag: share the mapping with other processes mapping the same file.
+pub const MAP_SHARED: c_int = 0x01;
+/// The value returned by `mmap` on failure.
+pub const MAP_FAILED: *mut c_void = !0 as *mut c_void;
+
+/// Socket-level option and control message level.
+pub const SOL_SOCKET: c_int = 1;
+/// Control message type carrying file descriptors.
+pub const SCM_RIGHTS: c_int = 1;
+/// Socket option: the credentials of the peer process.
+pub const SO_PEERCRED: c_int = 17;
+/// `send` flag: do not raise `SIGPIPE` when the peer closed the connection.
+pub const MSG_NOSIGNAL: c_int = 0x4000;
+/// `recvmsg` flag: set close-on-exec on received file descriptors.
+pub const MSG_CMSG_CLOEXEC: c_int = 0x4000_0000;
+/// `recvmsg` output flag: the control buffer was too small.
+pub const MSG_CTRUNC: c_int = 0x8;
+
+/// `pipe2`/`open` flag: set close-on-exec on the new descriptors.
+pub const O_CLOEXEC: c_int = 0o2000000;
+/// File status flag: operations fail with `EAGAIN` instead of blocking.
+pub const O_NONBLOCK: c_int = 0o4000;
+/// `fcntl` command: get the file descriptor flags.
+pub const F_GETFD: c_int = 1;
+/// `fcntl` command: set the file descriptor flags.
+pub const F_SETFD: c_int = 2;
+/// File descriptor flag: close the descriptor on `exec`.
+pub const FD_CLOEXEC: c_int = 1;
+/// `fcntl` command: get the file status flags.
+pub const F_GETFL: c_int = 3;
+/// `fcntl` command: set the file status flags.
+pub const F_SETFL: c_int = 4;
+
+/// `poll` event: there is data to read.
+pub const POLLIN: c_short = 0x1;
+/// `poll` event: writing will not block.
+pub const POLLOUT: c_short = 0x4;
+
+/// Polled descriptor, `struct pollfd`.
+#[repr(C)]
+pub struct PollFd {
+    pub fd: c_int,
+    pub events: c_short,
+    pub revents: c_short,
+}
+
+/// Scatter/gather element, `struct iovec`.
+#[repr(C)]
+pub struct IoVec {
+    pub iov_base: *mut c_void,
+    pub iov_len: usize,
+}
+
+/// Message header for `sendmsg`/`recvmsg`, `struct msghdr`.
+#[repr(C)]
+pub struct MsgHdr {
+    pub msg_name: *mut c_void,
+    pub msg_namelen: u32,
+    pub msg_iov: *mut IoVec,
+    pub msg_iovlen: usize,
+    pub msg_control: *mut c_void,
+    pub msg_controllen: usize,
+    pub msg_flags: c_int,
+}
+
+/// Ancillary data header, `struct cmsghdr`.
+#[repr(C)]
+pub struct CmsgHdr {
+    pub cmsg_len: usize,
+    pub cmsg_level: c_int,
+    pub cmsg_type: c_int,
+}
+
+/// Equivalent of the `CMSG_ALIGN` macro.
+pub const fn cmsg_align(len: usize) -> usize {
+    let align = std::mem::size_of::<usize>();
+    (len + align - 1) & !(align - 1)
+}
+
+/// Equivalent of the `CMSG_SPACE` macro.
+pub const fn cmsg_space(len: usize) -> usize {
+    cmsg_align(len) + cmsg_align(std::mem::size_of::<CmsgHdr>())
+}
+
+/// Equivalent of the `CMSG_LEN` macro.
+pub const fn cmsg_len(len: usize) -> usize {
+    cmsg_align(std::mem::size_of::<CmsgHdr>()) + len
+}
+
+/// Process credentials, `struct ucred`.
+#[repr(C)]
+pub struct UCred {
+    pub pid: c_int,
+    pub uid: c_uint,
+    pub gid: c_uint,
+}
+
+/// Time value with nanosecond precision, `struct timespec`.
+#[repr(C)]
+pub struct TimeSpec {
+    pub tv_sec: c_long,
+    pub tv_nsec: c_long,
+}
+
+unsafe extern "C" {
+    pub fn memfd_create(name: *const c_char, flags: c_uint) -> c_int;
+    pub fn mmap(
+        addr: *mut c_void,
+        len: usize,
+        prot: c_int,
+        flags: c_int,
+        fd: c_int,
+        offset: c_long,
+    ) -> *mut c_void;
+    pub fn munmap(addr: *mut c_void, len: usize) -> c_int;
+    pub fn sendmsg(socket: c_int, msg: *const MsgHdr, flags: c_int) -> isize;
+    pub fn recvmsg(socket: c_int, msg: *mut MsgHdr, flags: c_int) -> isize;
+    pub fn getsockopt(
+        socket: c_int,
+        level: c_int,
+        name: c_int,
+        value: *mut c_void,
+        len: *mut u32,
+    ) -> c_int;
+    pub fn pipe2(fds: *mut c_int, flags: c_int) -> c_int;
+    pub fn read(fd: c_int, buf: *mut c_void, count: usize) -> isize;
+    pub fn write(fd: c_int, buf: *const c_void, count: usize) -> isize;
+    pub fn fcntl(fd: c_int, cmd: c_int, ...) -> c_int;
+    pub fn poll(fds: *mut PollFd, nfds: c_ulong, timeout: c_int) -> c_int;
+    pub fn clock_gettime(clock_id: c_int, time: *mut TimeSpec) -> c_int;
+}
@@ -0,0 +1,472 @@
+mod ffi;
+
+use std::{
+    ffi::CString,
+    io,
+    os::fd::{AsRawFd, BorrowedFd, FromRawFd, OwnedFd},
+    ptr::NonNull,
+    time::Duration,
+};
+
+/// The maximum number of file descriptors accepted with a single `recvmsg`.
+///
+/// This mirrors libwayland's limit on the number of descriptors a single
+/// socket message may carry.
+const MAX_FDS_IN: usize = 28;
+
+/// The maximum number of file descriptors sent with a single `sendmsg`.
+///
+/// Compositors built on libwayland only receive [`MAX_FDS_IN`] descriptors
+/// at a time, so requests carrying more are split over several messages.
+pub const MAX_FDS_OUT: usize = MAX_FDS_IN;
+
+/// Creates an anonymous, memory-backed file with the close-on-exec flag set.
+///
+/// # Arguments
+/// * `name` - A name for debugging purposes, shown in `/proc/<pid>/fd`
+pub fn memfd_create(name: &str) -> io::Result<OwnedFd> {
+    let name = CString::new(name).map_err(|_| io::Error::from(io::ErrorKind::InvalidInput))?;
+
+    // SAFETY: `name` is a valid NUL-terminated string for the duration of the call.
+    let fd = unsafe { ffi::memfd_create(name.as_ptr(), ffi::MFD_CLOEXEC) };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    // SAFETY: the kernel just returned this descriptor and nothing else owns it.
+    Ok(unsafe { OwnedFd::from_raw_fd(fd) })
+}
+
+/// A shared, writable memory mapping of a file, unmapped on drop.
+pub struct MemMap {
+    /// The start of the mapping.
+    ptr: NonNull<u8>,
+    /// The length of the mapping in bytes.
+    len: usize,
+}
+
+impl MemMap {
+    /// Maps the first `len` bytes of `fd` read-write and shared.
+    ///
+    /// # Errors
+    /// Returns an error if `len` is zero or the kernel refuses the mapping.
+    pub fn new(fd: BorrowedFd<'_>, len: usize) -> io::Result<MemMap> {
+        if len == 0 {
+            return Err(io::Error::from(io::ErrorKind::InvalidInput));
+        }
+
+        // SAFETY: a fresh mapping is requested (null hint), so no existing
+        // memory is affected; the result is checked against MAP_FAILED.
+        let ptr = unsafe {
+            ffi::mmap(
+                std::ptr::null_mut(),
+                len,
+                ffi::PROT_READ | ffi::PROT_WRITE,
+                ffi::MAP_SHARED,
+                fd.as_raw_fd(),
+                0,
+            )
+        };
+        if ptr == ffi::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(MemMap {
+            ptr: NonNull::new(ptr.cast()).ok_or_else(io::Error::last_os_error)?,
+            len,
+        })
+    }
+
+    /// Returns the length of the mapping in bytes.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the mapped memory.
+    pub fn as_slice(&self) -> &[u8] {
+        // SAFETY: the mapping is valid for `len` bytes for as long as `self` lives.
+        unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+
+    /// Returns the mapped memory for writing.
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        // SAFETY: the mapping is valid for `len` bytes and `&mut self` guarantees
+        // exclusive access from this process.
+        unsafe { std::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+// SAFETY: the mapping is owned like the buffer of a `Vec<u8>`: it is only
+// read through `&self` and written through `&mut self`, so it can move to
+// and be shared with other threads.
+unsafe impl Send for MemMap {}
+unsafe impl Sync for MemMap {}
+
+impl Drop for MemMap {
+    fn drop(&mut self) {
+        // SAFETY: `ptr` and `len` describe a mapping created by `mmap` that is
+        // unmapped exactly once.
+        unsafe {
+            ffi::munmap(self.ptr.as_ptr().cast(), self.len);
+        }
+    }
+}
+
+/// Sends `bytes` over a Unix socket, attaching `fds` as `SCM_RIGHTS` ancillary data.
+///
+/// # Returns
+/// The number of bytes written, which may be less than `bytes.len()`. The
+/// file descriptors are always sent with the first byte.
+pub fn send_with_fds(
+    socket: BorrowedFd<'_>,
+    bytes: &[u8],
+    fds: &[BorrowedFd<'_>],
+) -> io::Result<usize> {
+    let mut iov = ffi::IoVec {
+        iov_base: bytes.as_ptr() as *mut _,
+        iov_len: bytes.len(),
+    };
+
+    let fds_len = std::mem::size_of_val(fds);
+    let mut control = vec![0u8; ffi::cmsg_space(fds_len)];
+
+    let msg = ffi::MsgHdr {
+        msg_name: std::ptr::null_mut(),
+        msg_namelen: 0,
+        msg_iov: &mut iov,
+        msg_iovlen: 1,
+        msg_control: if fds.is_empty() {
+            std::ptr::null_mut()
+        } else {
+            control.as_mut_ptr().cast()
+        },
+        msg_controllen: if fds.is_empty() { 0 } else { control.len() },
+        msg_flags: 0,
+    };
+
+    if !fds.is_empty() {
+        let header = ffi::CmsgHdr {
+            cmsg_len: ffi::cmsg_len(fds_len),
+            cmsg_level: ffi::SOL_SOCKET,
+            cmsg_type: ffi::SCM_RIGHTS,
+        };
+        let header_len = ffi::cmsg_align(std::mem::size_of::<ffi::CmsgHdr>());
+
+        // SAFETY: `control` holds `cmsg_space(fds_len)` bytes, which fits the
+        // header followed by the descriptor array; unaligned writes are used
+        // because a Vec<u8> gives no alignment guarantee.
+        unsafe {
+            std::ptr::write_unaligned(control.as_mut_ptr().cast::<ffi::CmsgHdr>(), header);
+            let data = control.as_mut_ptr().add(header_len).cast::<i32>();
+            for (index, fd) in fds.iter().enumerate() {
+                std::ptr::write_unaligned(data.add(index), fd.as_raw_fd());
+            }
+        }
+    }
+
+    // SAFETY: every pointer in `msg` refers to memory that outlives the call.
+    let sent = unsafe { ffi::sendmsg(socket.as_raw_fd(), &msg, ffi::MSG_NOSIGNAL) };
+    if sent < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(sent as usize)
+}
+
+/// Receives bytes from a Unix socket, collecting `SCM_RIGHTS` file descriptors.
+///
+/// Received descriptors are appended to `fds` in the order they were sent and
+/// have the close-on-exec flag set.
+///
+/// # Returns
+/// The number of bytes received, 0 meaning the peer closed the connection.
+pub fn recv_with_fds(
+    socket: BorrowedFd<'_>,
+    buf: &mut [u8],
+    fds: &mut Vec<OwnedFd>,
+) -> io::Result<usize> {
+    let mut iov = ffi::IoVec {
+        iov_base: buf.as_mut_ptr().cast(),
+        iov_len: buf.len(),
+    };
+
+    let mut control = vec![0u8; ffi::cmsg_space(MAX_FDS_IN * std::mem::size_of::<i32>())];
+
+    let mut msg = ffi::MsgHdr {
+        msg_name: std::ptr::null_mut(),
+        msg_namelen: 0,
+        msg_iov: &mut iov,
+        msg_iovlen: 1,
+        msg_control: control.as_mut_ptr().cast(),
+        msg_controllen: control.len(),
+        msg_flags: 0,
+    };
+
+    // SAFETY: every pointer in `msg` refers to memory that outlives the call.
+    let received = unsafe { ffi::recvmsg(socket.as_raw_fd(), &mut msg, ffi::MSG_CMSG_CLOEXEC) };
+    if received < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let header_len = ffi::cmsg_align(std::mem::size_of::<ffi::CmsgHdr>());
+    let mut offset = 0;
+
+    while offset + header_len <= msg.msg_controllen {
+        // SAFETY: the kernel wrote `msg_controllen` bytes of well-formed
+        // control messages; the header lies within that range.
+        let header = unsafe {
+            std::ptr::read_unaligned(control.as_ptr().add(offset).cast::<ffi::CmsgHdr>())
+        };
+        if header.cmsg_len < header_len || offset + header.cmsg_len > msg.msg_controllen {
+            break;
+        }
+
+        if header.cmsg_level == ffi::SOL_SOCKET && header.cmsg_type == ffi::SCM_RIGHTS {
+            let count = (header.cmsg_len - header_len) / std::mem::size_of::<i32>();
+            for index in 0..count {
+                // SAFETY: `count` descriptors follow the header inside the
+                // message, and each one is owned by this process from now on.
+                let fd = unsafe {
+                    std::ptr::read_unaligned(
+                        control
+                            .as_ptr()
+                            .add(offset + header_len)
+                            .cast::<i32>()
+                            .add(index),
+                    )
+                };
+                fds.push(unsafe { OwnedFd::from_raw_fd(fd) });
+            }
+        }
+
+        offset += ffi::cmsg_align(header.cmsg_len);
+    }
+
+    if msg.msg_flags & ffi::MSG_CTRUNC != 0 {
+        return Err(io::Error::other(
+            "File descriptors were truncated by the kernel",
+        ));
+    }
+
+    Ok(received as usize)
+}
+
+/// Reads the credentials of the process at the other end of the Unix
+/// socket `fd`, as they were when it connected.
+///
+/// # Returns
+/// The `(pid, uid, gid)` of the peer.
+pub fn peer_credentials(fd: BorrowedFd<'_>) -> io::Result<(i32, u32, u32)> {
+    let mut cred = ffi::UCred {
+        pid: 0,
+        uid: 0,
+        gid: 0,
+    };
+    let mut len = std::mem::size_of::<ffi::UCred>() as u32;
+
+    // SAFETY: `cred` is writable for `len` bytes, the size of `struct ucred`.
+    let result = unsafe {
+        ffi::getsockopt(
+            fd.as_raw_fd(),
+            ffi::SOL_SOCKET,
+            ffi::SO_PEERCRED,
+            (&raw mut cred).cast(),
+            &mut len,
+        )
+    };
+    if result < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok((cred.pid, cred.uid, cred.gid))
+}
+
+/// Creates a pipe with the close-on-exec flag set on both ends.
+///
+/// # Returns
+/// The `(read, write)` ends of the pipe.
+pub fn pipe() -> io::Result<(OwnedFd, OwnedFd)> {
+    let mut fds = [-1; 2];
+
+    // SAFETY: `fds` has room for the two descriptors written by the kernel.
+    if unsafe { ffi::pipe2(fds.as_mut_ptr(), ffi::O_CLOEXEC) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    // SAFETY: the kernel just returned these descriptors and nothing else owns them.
+    Ok(unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) })
+}
+
+/// Reads from `fd` into `buf` without taking ownership of the descriptor.
+///
+/// # Returns
+/// The number of bytes read, 0 at end of file.
+pub fn read(fd: BorrowedFd<'_>, buf: &mut [u8]) -> io::Result<usize> {
+    // SAFETY: `fd` is a valid descriptor and `buf` is writable for its length.
+    let read_len = unsafe { ffi::read(fd.as_raw_fd(), buf.as_mut_ptr().cast(), buf.len()) };
+    if read_len < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(read_len as usize)
+}
+
+/// Writes `buf` to `fd` without taking ownership of the descriptor.
+///
+/// # Returns
+/// The number of bytes written, which may be less than `buf.len()`.
+pub fn write(fd: BorrowedFd<'_>, buf: &[u8]) -> io::Result<usize> {
+    // SAFETY: `fd` is a valid descriptor and `buf` is readable for its length.
+    let written = unsafe { ffi::write(fd.as_raw_fd(), buf.as_ptr().cast(), buf.len()) };
+    if written < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(written as usize)
+}
+
+/// Switches `fd` to non-blocking mode, so reads and writes fail with
+/// `WouldBlock` instead of waiting.
+pub fn set_nonblocking(fd: BorrowedFd<'_>) -> io::Result<()> {
+    // SAFETY: `fd` is a valid descriptor for the duration of the calls.
+    let flags = unsafe { ffi::fcntl(fd.as_raw_fd(), ffi::F_GETFL) };
+    if flags < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    // SAFETY: as above; F_SETFL takes the new flags as an int argument.
+    if unsafe { ffi::fcntl(fd.as_raw_fd(), ffi::F_SETFL, flags | ffi::O_NONBLOCK) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(())
+}
+
+/// Sets the close-on-exec flag of `fd`, for descriptors the crate did not
+/// create itself, such as a socket passed to
+/// [`Connection::from_stream`](crate::connection::Connection::from_stream).
+pub fn set_cloexec(fd: BorrowedFd<'_>) -> io::Result<()> {
+    // SAFETY: `fd` is a valid descriptor for the duration of the calls.
+    let flags = unsafe { ffi::fcntl(fd.as_raw_fd(), ffi::F_GETFD) };
+    if flags < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    // SAFETY: as above; F_SETFD takes the new flags as an int argument.
+    if unsafe { ffi::fcntl(fd.as_raw_fd(), ffi::F_SETFD, flags | ffi::FD_CLOEXEC) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(())
+}
+
+/// Reads the clock `clock_id`, such as the clock of presentation
+/// timestamps announced by `wp_presentation.clock_id`.
+///
+/// # Returns
+/// The time elapsed since the epoch of the clock.
+pub fn clock_gettime(clock_id: u32) -> io::Result<Duration> {
+    let clock_id =
+        i32::try_from(clock_id).map_err(|_| io::Error::from(io::ErrorKind::InvalidInput))?;
+    let mut time = ffi::TimeSpec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+
+    // SAFETY: `time` is writable for the duration of the call.
+    if unsafe { ffi::clock_gettime(clock_id, &mut time) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(Duration::new(time.tv_sec as u64, time.tv_nsec as u32))
+}
+
+/// The readiness a descriptor is polled for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interest {
+    /// Reading will not block.
+    Readable,
+    /// Writing will not block.
+    Writable,
+}
+
+/// Waits until any of `fds` is ready for its interest or the timeout expires.
+///
+/// A `None` timeout waits forever. Timeouts are rounded up to whole
+/// milliseconds so the caller never wakes up before its deadline. A signal
+/// interrupting the wait is reported as a timeout.
+///
+/// # Returns
+/// One flag per descriptor, in order, telling whether it is ready (or hung up).
+pub fn poll(
+    fds: &[(BorrowedFd<'_>, Interest)],
+    timeout: Option<Duration>,
+) -> io::Result<Vec<bool>> {
+    let timeout_ms = match timeout {
+        None => -1,
+        Some(timeout) => {
+            let millis = timeout.as_nanos().div_ceil(1_000_000);
+            millis.min(i32::MAX as u128) as i32
+        }
+    };
+
+    let mut poll_fds: Vec<ffi::PollFd> = fds
+        .iter()
+        .map(|(fd, interest)| ffi::PollFd {
+            fd: fd.as_raw_fd(),
+            events: match interest {
+                Interest::Readable => ffi::POLLIN,
+                Interest::Writable => ffi::POLLOUT,
+            },
+            revents: 0,
+        })
+        .collect();
+
+    // SAFETY: `poll_fds` holds `poll_fds.len()` valid pollfd structures.
+    let ready = unsafe {
+        ffi::poll(
+            poll_fds.as_mut_ptr(),
+            poll_fds.len() as std::ffi::c_ulong,
+            timeout_ms,
+        )
+    };
+    if ready < 0 {
+        let error = io::Error::last_os_error();
+        if error.kind() == io::ErrorKind::Interrupted {
+            return Ok(vec![false; fds.len()]);
+        }
+        return Err(error);
+    }
+
+    Ok(poll_fds.iter().map(|fd| fd.revents != 0).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::os::{fd::AsFd, unix::net::UnixStream};
+
+    use super::*;
+
+    /// Returns `true` if the close-on-exec flag of `fd` is set.
+    fn is_cloexec(fd: BorrowedFd<'_>) -> bool {
+        // SAFETY: `fd` is a valid descriptor for the duration of the call.
+        let flags = unsafe { ffi::fcntl(fd.as_raw_fd(), ffi::F_GETFD) };
+        flags >= 0 && flags & ffi::FD_CLOEXEC != 0
+    }
+
+    #[test]
+    fn created_descriptors_are_close_on_exec() {
+        let memfd = memfd_create("test").unwrap();
+        let (read, write) = pipe().unwrap();
+        assert!(is_cloexec(memfd.as_fd()));
+        assert!(is_cloexec(read.as_fd()));
+        assert!(is_cloexec(write.as_fd()));
+
+        let (socket, _peer) = UnixStream::pair().unwrap();
+        // SAFETY: `socket` is valid; clearing its flags mimics an inherited socket.
+        unsafe { ffi::fcntl(socket.as_raw_fd(), ffi::F_SETFD, 0) };
+        assert!(!is_cloexec(socket.as_fd()));
+        set_cloexec(socket.as_fd()).unwrap();
+        assert!(is_cloexec(socket.as_fd()));
+    }
+}
@@ -0,0 +1,1712 @@
+use std::sync::{Arc, Weak};
+
+use crate::{
+    connection::Connection,
+    error::{Error, Result, anyhow},
+    geometry::Rect,
+    pointer_capture::{CaptureEvent, PointerCapture},
+    protocol::{
+        WlObjectId,
+        color_management::{
+            ColorManagementSurface, ColorManager, ImageDescription, ImageDescriptionEvent,
+            ImageDescriptionParams, RenderIntent,
+        },
+        compositor::Compositor,
+        idle_inhibit::{IdleInhibitManager, InhibitGuard},
+        output::Output,
+        pointer::{Pointer, PointerFrame},
+        pointer_constraints::PointerConstraints,
+        relative_pointer::RelativePointerManager,
+        seat::Seat,
+        shell_role::{RoleConfigure, ShellRole},
+        shm::{Shm, event::format::Format},
+        subcompositor::SubCompositor,
+        surface::{Surface, SurfaceEvent, SurfaceState, WL_SURFACE_SET_BUFFER_SCALE_SINCE},
+        touch::TouchFrame,
+        viewporter::{Viewport, Viewporter},
+        xdg_decoration::{DecorationManager, Mode as DecorationMode, ToplevelDecoration},
+        xdg_popup::{PopupConfigure, PopupEvent, XdgPopup},
+        xdg_positioner::{Anchor, ConstraintAdjustment, Gravity},
+        xdg_surface::XdgSurface,
+        xdg_toplevel::{ResizeEdge, ToplevelConfigure, ToplevelEvent, WindowState, XdgToplevel},
+        xdg_wm_base::XdgWmBase,
+    },
+    scale::{ScaleManager, SurfaceScale},
+    subsurface::SurfaceTree,
+    swapchain::{Swapchain, SwapchainBuffer},
+    sync::{Lock, LockCell},
+};
+
+/// The number of buffers of the swapchain of a window.
+const BUFFER_COUNT: usize = 2;
+
+/// The pixel format the content of a window is drawn in.
+const BUFFER_FORMAT: Format = Format::Argb8888;
+
+/// An event delivered to the handler of a [`Window`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum WindowEvent {
+    /// The compositor configured the window. The window was already resized
+    /// to the new size, if one was suggested; a [`WindowEvent::Redraw`]
+    /// follows.
+    Configure(ToplevelConfigure),
+    /// The compositor placed the popup, relative to its parent. The popup
+    /// was already resized; a [`WindowEvent::Redraw`] follows.
+    PopupConfigure(PopupConfigure),
+    /// It is a good time to draw the next frame with [`Window::draw`].
+    Redraw,
+    /// The window should be rendered at this scale.
+    ///
+    /// Without [`Window::track_scale`], this is the integer scale preferred
+    /// by the compositor, which the application applies itself. Otherwise
+    /// the buffers were already resized and a [`WindowEvent::Redraw`]
+    /// follows.
+    ScaleChanged(f64),
+    /// The window is now shown on the output with this object ID, see
+    /// [`Window::outputs`].
+    OutputEnter(u32),
+    /// The window is no longer shown on the output with this object ID.
+    OutputLeave(u32),
+    /// Pointer input on the window, routed by [`route_pointer`].
+    ///
+    /// A frame in which the pointer moved to another surface is split: the
+    /// window it left receives the leave alone.
+    Pointer(PointerFrame),
+    /// Touch input on the window, routed by [`route_touch`], limited to
+    /// the touch points on the window.
+    Touch(TouchFrame),
+    /// The user asked to close the window.
+    ///
+    /// A popup receives it when the compositor dismissed it, e.g. because
+    /// the user clicked elsewhere; the popup is destroyed right after.
+    Close,
+}
+
+/// How the buffers of a window follow its size, see
+/// [`Window::set_resize_mode`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ResizeMode {
+    /// The buffers are reallocated to the exact size of every configure.
+    #[default]
+    Exact,
+    /// During an interactive resize, the buffers are only reallocated when
+    /// they get too small, growing in steps, and the part matching the
+    /// window size is shown through `wp_viewport`. The buffers shrink back
+    /// to the exact size once the resize ends.
+    Overallocate,
+}
+
+/// The handler receiving the events of a window.
+type WindowHandler = Box<dyn FnMut(&Window, WindowEvent) + Send>;
+
+/// The protocol objects making up a window, destroyed together.
+struct Shell {
+    /// The surface the content is attached to.
+    surface: Surface,
+    /// The xdg_surface giving the surface its desktop role.
+    xdg_surface: XdgSurface,
+    /// The role object.
+    role: Role,
+}
+
+/// The role object of a window.
+enum Role {
+    /// A regular desktop window.
+    Toplevel(XdgToplevel),
+    /// A popup opened with [`Window::open_popup`].
+    Popup(XdgPopup),
+}
+
+/// The properties of a [`Window`] that are set before it is first shown.
+///
+/// The compositor picks the initial size and placement of a window from
+/// the state set before its initial commit, so a window meant to start
+/// maximized or fullscreen, or with size limits, should be built with
+/// them instead of changing them afterwards:
+///
+/// ```no_run
+/// # fn run(
+/// #     conn: &wayland_client_from_scratch::connection::Connection,
+/// #     compositor: &wayland_client_from_scratch::protocol::compositor::Compositor,
+/// #     wm_base: &wayland_client_from_scratch::protocol::xdg_wm_base::XdgWmBase,
+/// #     shm: &wayland_client_from_scratch::protocol::shm::Shm,
+/// # ) -> wayland_client_from_scratch::error::Result<()> {
+/// use wayland_client_from_scratch::window::WindowBuilder;
+///
+/// let window = WindowBuilder::new(800, 600)
+///     .title("Editor")
+///     .app_id("org.example.Editor")
+///     .min_size(320, 240)
+///     .build(conn, compositor, wm_base, shm, |_, _| {})?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct WindowBuilder {
+    /// The size used until the compositor suggests one.
+    size: (i32, i32),
+    /// The window title.
+    title: Option<String>,
+    /// The application identifier.
+    app_id: Option<String>,
+    /// The minimum size, 0 for no limit.
+    min_size: Option<(i32, i32)>,
+    /// The maximum size, 0 for no limit.
+    max_size: Option<(i32, i32)>,
+    /// Whether the window starts maximized.
+    maximized: bool,
+    /// Whether the window starts fullscreen, with the object ID of the
+    /// preferred output if any.
+    fullscreen: Option<Option<u32>>,
+    /// The preferred decoration mode.
+    decorations: Option<DecorationMode>,
+    /// The window the window is a child of.
+    parent: Option<Window>,
+}
+
+impl WindowBuilder {
+    /// Starts a window of `width` x `height` pixels, used until the
+    /// compositor suggests a size.
+    pub fn new(width: i32, height: i32) -> WindowBuilder {
+        WindowBuilder {
+            size: (width, height),
+            title: None,
+            app_id: None,
+            min_size: None,
+            max_size: None,
+            maximized: false,
+            fullscreen: None,
+            decorations: None,
+            parent: None,
+        }
+    }
+
+    /// Sets the window title shown by task bars and window lists.
+    pub fn title(mut self, title: impl Into<String>) -> WindowBuilder {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the application identifier, typically the desktop file name,
+    /// which compositors use to group windows and find their icon.
+    pub fn app_id(mut self, app_id: impl Into<String>) -> WindowBuilder {
+        self.app_id = Some(app_id.into());
+        self
+    }
+
+    /// Sets the minimum size of the window; 0 means no limit.
+    pub fn min_size(mut self, width: i32, height: i32) -> WindowBuilder {
+        self.min_size = Some((width, height));
+        self
+    }
+
+    /// Sets the maximum size of the window; 0 means no limit.
+    pub fn max_size(mut self, width: i32, height: i32) -> WindowBuilder {
+        self.max_size = Some((width, height));
+        self
+    }
+
+    /// Asks for the window to start maximized.
+    pub fn maximized(mut self, maximized: bool) -> WindowBuilder {
+        self.maximized = maximized;
+        self
+    }
+
+    /// Asks for the window to start fullscreen, on `output` or on the one
+    /// the compositor picks.
+    pub fn fullscreen(mut self, output: Option<&Output>) -> WindowBuilder {
+        self.fullscreen = Some(output.map(Output::id));
+        self
+    }
+
+    /// Tells the compositor who should draw the decorations of the window.
+    ///
+    /// Only compositors offering `zxdg_decoration_manager_v1` draw
+    /// decorations, and they may still pick the other mode; see
+    /// [`Window::decoration_mode`].
+    pub fn decorations(mut self, mode: DecorationMode) -> WindowBuilder {
+        self.decorations = Some(mode);
+        self
+    }
+
+    /// Makes the window a child of `parent`, such as a dialog of the main
+    /// window, stacked above it.
+    pub fn parent(mut self, parent: &Window) -> WindowBuilder {
+        self.parent = Some(parent.clone());
+        self
+    }
+
+    /// Creates the window, issuing the requests for its properties before
+    /// the initial commit, in the order `xdg_toplevel` expects them.
+    ///
+    /// See [`Window::new`] for how the window is then mapped; `handler`
+    /// receives the events of the window.
+    ///
+    /// # Errors
+    /// Returns an error if the size is empty, the size limits contradict
+    /// each other, the parent is a popup or was destroyed, or a protocol
+    /// object cannot be created.
+    pub fn build<F>(
+        self,
+        conn: &Connection,
+        compositor: &Compositor,
+        wm_base: &XdgWmBase,
+        shm: &Shm,
+        handler: F,
+    ) -> Result<Window>
+    where
+        F: FnMut(&Window, WindowEvent) + Send + 'static,
+    {
+        self.check_size_limits()?;
+
+        let (width, height) = self.size;
+        let globals = (compositor, wm_base, shm);
+
+        Window::build(
+            conn,
+            globals,
+            width,
+            height,
+            handler,
+            |xdg_surface, weak| {
+                let close = weak.clone();
+                let toplevel = xdg_surface.get_toplevel(move |_, event| {
+                    if let ToplevelEvent::Close = event {
+                        emit(&close, WindowEvent::Close);
+                    }
+                })?;
+                let decoration = self.apply(conn, &toplevel)?;
+                if let Some(inner) = weak.upgrade() {
+                    inner.decoration.replace(decoration);
+                }
+
+                Ok(Role::Toplevel(toplevel))
+            },
+        )
+    }
+
+    /// Checks that the size limits are not negative and the minimum size
+    /// does not exceed the maximum size.
+    fn check_size_limits(&self) -> Result<()> {
+        let min = self.min_size.unwrap_or((0, 0));
+        let max = self.max_size.unwrap_or((0, 0));
+        if min.0 < 0 || min.1 < 0 || max.0 < 0 || max.1 < 0 {
+            return Err(anyhow!(
+                "Window size limits must not be negative, got {min:?} to {max:?}"
+            ));
+        }
+        // A limit of 0 means no limit
+        if (max.0 > 0 && min.0 > max.0) || (max.1 > 0 && min.1 > max.1) {
+            return Err(anyhow!(
+                "Window minimum size {min:?} exceeds the maximum size {max:?}"
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Issues the requests setting the properties on `toplevel`, returning
+    /// its decoration object if a decoration mode was asked for and the
+    /// compositor supports it.
+    fn apply(
+        &self,
+        conn: &Connection,
+        toplevel: &XdgToplevel,
+    ) -> Result<Option<ToplevelDecoration>> {
+        if let Some(parent) = &self.parent {
+            parent.with_shell(|shell| match &shell.role {
+                Role::Toplevel(parent) => toplevel.set_parent(Some(parent)),
+                Role::Popup(_) => Err(anyhow!("A popup cannot be the parent of a window")),
+            })?;
+        }
+        if let Some(title) = &self.title {
+            toplevel.set_title(title)?;
+        }
+        if let Some(app_id) = &self.app_id {
+            toplevel.set_app_id(app_id)?;
+        }
+        if let Some((width, height)) = self.min_size {
+            toplevel.set_min_size(width, height)?;
+        }
+        if let Some((width, height)) = self.max_size {
+            toplevel.set_max_size(width, height)?;
+        }
+        if self.maximized {
+            toplevel.set_maximized()?;
+        }
+        if let Some(output) = self.fullscreen {
+            toplevel.set_fullscreen(output)?;
+        }
+
+        let Some(mode) = self.decorations else {
+            return Ok(None);
+        };
+        if !conn.capabilities().has(WlObjectId::DecorationManager) {
+            // Without the protocol, clients draw their decorations.
+            return Ok(None);
+        }
+        let manager = DecorationManager::bind(conn)?;
+        let decoration = manager.get_toplevel_decoration(toplevel)?;
+        manager.destroy()?;
+        decoration.set_mode(mode)?;
+
+        Ok(Some(decoration))
+    }
+}
+
+/// The state of a window, shared with the handlers of its protocol objects.
+struct WindowInner {
+    /// The connection the window was created on.
+    conn: Connection,
+    /// The protocol objects, `None` once the window is destroyed.
+    shell: Lock<Option<Shell>>,
+    /// The compositor popup surfaces are created from.
+    compositor: Compositor,
+    /// The xdg_wm_base global popups are created from.
+    wm_base: XdgWmBase,
+    /// The shm global the swapchain is allocated from.
+    shm: Shm,
+    /// The popups opened from the window, destroyed along with it.
+    popups: Lock<Vec<Weak<WindowInner>>>,
+    /// The buffers of the window, allocated by the first configure.
+    swapchain: Lock<Option<Swapchain>>,
+    /// The scale manager of the window, inherited by its popups.
+    scale_manager: Lock<Option<ScaleManager>>,
+    /// The scale tracking of the surface, if enabled with
+    /// [`Window::track_scale`].
+    scale: Lock<Option<SurfaceScale>>,
+    /// The size of the window in surface-local coordinates, which are
+    /// pixels unless the scale is tracked.
+    size: LockCell<(i32, i32)>,
+    /// The resolution of the buffers relative to the scale of the window,
+    /// set with [`Window::set_content_scale`].
+    content_scale: LockCell<f64>,
+    /// The part of the buffers shown, set with [`Window::crop`].
+    crop: LockCell<Option<Rect>>,
+    /// The viewport of the surface when the scale tracking does not provide
+    /// one, created for a content scale or crop.
+    viewport: Lock<Option<Viewport>>,
+    /// Whether the last committed state went through the viewport.
+    view_applied: LockCell<bool>,
+    /// The color management object of the surface, created by
+    /// [`Window::set_hdr_metadata`].
+    color_surface: Lock<Option<ColorManagementSurface>>,
+    /// The image description set with [`Window::set_hdr_metadata`] until
+    /// the compositor makes it ready.
+    pending_description: Lock<Option<ImageDescription>>,
+    /// The decoration object of the toplevel, created when a
+    /// [`WindowBuilder`] asked for a decoration mode.
+    decoration: Lock<Option<ToplevelDecoration>>,
+    /// The state of the toplevel from the latest configure.
+    state: LockCell<WindowState>,
+    /// How the buffers follow the size during interactive resizes.
+    resize_mode: LockCell<ResizeMode>,
+    /// Whether the first configure was received, allowing buffers to be
+    /// committed.
+    configured: LockCell<bool>,
+    /// Whether a frame callback is in flight.
+    frame_pending: LockCell<bool>,
+    /// The error raised while applying a configure, returned by the next
+    /// [`Window::draw`].
+    error: Lock<Option<Error>>,
+    /// The handler receiving the window events, taken out while it runs.
+    handler: Lock<Option<WindowHandler>>,
+}
+
+/// A desktop window: a surface with the toplevel role and the buffers its
+/// content is drawn into.
+///
+/// Bundles the `wl_surface`, `xdg_surface` and `xdg_toplevel` objects with a
+/// [`Swapchain`] and takes care of the configure handshake, so an
+/// application only reacts to the [`WindowEvent`]s delivered to its handler:
+///
+/// - [`WindowEvent::Redraw`] is answered with [`Window::draw`], which also
+///   acknowledges the configuration the content was drawn for.
+/// - [`WindowEvent::Close`] is answered with [`Window::destroy`], or ignored.
+///
+/// Menus and other popups are windows too, opened with [`Window::open_popup`].
+///
+/// `Window` is a cheap handle; clones refer to the same window, which is
+/// destroyed when the last of them is dropped.
+#[derive(Clone)]
+pub struct Window {
+    inner: Arc<WindowInner>,
+}
+
+impl Window {
+    /// Creates a window of `width` x `height` pixels, used until the
+    /// compositor suggests a size.
+    ///
+    /// The window is mapped by the first [`Window::draw`] after the first
+    /// configure, which the compositor sends in answer to the initial
+    /// commit made here. `handler` receives the events of the window.
+    ///
+    /// Use a [`WindowBuilder`] to set a title, size limits or an initial
+    /// state before the window is first shown.
+    ///
+    /// # Errors
+    /// Returns an error if the size is empty or a protocol object cannot be
+    /// created.
+    pub fn new<F>(
+        conn: &Connection,
+        compositor: &Compositor,
+        wm_base: &XdgWmBase,
+        shm: &Shm,
+        width: i32,
+        height: i32,
+        handler: F,
+    ) -> Result<Window>
+    where
+        F: FnMut(&Window, WindowEvent) + Send + 'static,
+    {
+        WindowBuilder::new(width, height).build(conn, compositor, wm_base, shm, handler)
+    }
+
+    /// Opens a popup, such as a context menu, of `size` pixels next to
+    /// `anchor`, a rectangle in the coordinates of this window.
+    ///
+    /// The popup is placed below and right of the bottom left corner of
+    /// `anchor`, and flipped or slid by the compositor to stay on screen.
+    /// With a `grab` seat, the popup takes the input of the seat and is
+    /// dismissed when the user clicks elsewhere; it must then be opened in
+    /// answer to an input event of that seat, such as a button press.
+    ///
+    /// The popup is a [`Window`] of its own, receiving its events through
+    /// `handler`. A dismissed popup gets a [`WindowEvent::Close`] and is
+    /// destroyed right after; open popups are destroyed with this window.
+    ///
+    /// # Errors
+    /// Returns an error if this window was destroyed, the size is empty or
+    /// a protocol object cannot be created.
+    pub fn open_popup<F>(
+        &self,
+        anchor: Rect,
+        size: (i32, i32),
+        grab: Option<&Seat>,
+        handler: F,
+    ) -> Result<Window>
+    where
+        F: FnMut(&Window, WindowEvent) + Send + 'static,
+    {
+        let (width, height) = size;
+        let inner = &self.inner;
+
+        let positioner = inner.wm_base.create_positioner()?;
+        positioner.set_size(width, height)?;
+        positioner.set_anchor_rect(anchor)?;
+        positioner.set_anchor(Anchor::BottomLeft)?;
+        positioner.set_gravity(Gravity::BottomRight)?;
+        positioner.set_constraint_adjustment(&[
+            ConstraintAdjustment::SlideX,
+            ConstraintAdjustment::SlideY,
+            ConstraintAdjustment::FlipX,
+            ConstraintAdjustment::FlipY,
+        ])?;
+
+        let globals = (&inner.compositor, &inner.wm_base, &inner.shm);
+        let popup = Window::build(
+            &inner.conn,
+            globals,
+            width,
+            height,
+            handler,
+            |xdg_surface, weak| {
+                let popup = self.with_shell(|parent| {
+                    xdg_surface.get_popup(
+                        Some(&parent.xdg_surface),
+                        &positioner,
+                        move |_, event| {
+                            if let PopupEvent::Done = event {
+                                dismiss(&weak);
+                            }
+                        },
+                    )
+                })?;
+                if let Some(seat) = grab {
+                    popup.grab(seat, seat.state().serial())?;
+                }
+
+                Ok(Role::Popup(popup))
+            },
+        )?;
+        positioner.destroy()?;
+
+        if let Some(manager) = inner.scale_manager.borrow().as_ref() {
+            popup.track_scale(manager)?;
+        }
+
+        let mut popups = inner.popups.borrow_mut();
+        popups.retain(|popup| popup.strong_count() > 0);
+        popups.push(Arc::downgrade(&popup.inner));
+
+        Ok(popup)
+    }
+
+    /// Creates the surface, xdg_surface and role object of a window, then
+    /// makes the initial commit.
+    ///
+    /// `role` creates the role object; its handlers reach the window
+    /// through the weak reference it receives.
+    fn build<F, R>(
+        conn: &Connection,
+        (compositor, wm_base, shm): (&Compositor, &XdgWmBase, &Shm),
+        width: i32,
+        height: i32,
+        handler: F,
+        role: R,
+    ) -> Result<Window>
+    where
+        F: FnMut(&Window, WindowEvent) + Send + 'static,
+        R: FnOnce(&XdgSurface, Weak<WindowInner>) -> Result<Role>,
+    {
+        if width <= 0 || height <= 0 {
+            return Err(anyhow!(
+                "Window size must not be empty, got {width}x{height}"
+            ));
+        }
+
+        let inner = Arc::new(WindowInner {
+            conn: conn.clone(),
+            shell: Lock::new(None),
+            compositor: compositor.clone(),
+            wm_base: wm_base.clone(),
+            shm: shm.clone(),
+            popups: Lock::new(Vec::new()),
+            swapchain: Lock::new(None),
+            scale_manager: Lock::new(None),
+            scale: Lock::new(None),
+            size: LockCell::new((width, height)),
+            content_scale: LockCell::new(1.0),
+            crop: LockCell::new(None),
+            viewport: Lock::new(None),
+            view_applied: LockCell::new(false),
+            color_surface: Lock::new(None),
+            pending_description: Lock::new(None),
+            decoration: Lock::new(None),
+            state: LockCell::new(WindowState::default()),
+            resize_mode: LockCell::new(ResizeMode::Exact),
+            configured: LockCell::new(false),
+            frame_pending: LockCell::new(false),
+            error: Lock::new(None),
+            handler: Lock::new(Some(Box::new(handler))),
+        });
+
+        let surface = compositor.create_surface()?;
+        let owner: Weak<WindowInner> = Arc::downgrade(&inner);
+        surface.state().set_owner(owner);
+        let weak = Arc::downgrade(&inner);
+        surface.set_event_handler(move |_, event| match event {
+            SurfaceEvent::PreferredBufferScale(scale) => {
+                // A tracked scale is reported once combined with the other sources.
+                if weak
+                    .upgrade()
+                    .is_some_and(|inner| inner.scale.borrow().is_none())
+                {
+                    emit(&weak, WindowEvent::ScaleChanged(f64::from(scale)));
+                }
+            }
+            SurfaceEvent::Enter(output) => emit(&weak, WindowEvent::OutputEnter(output)),
+            SurfaceEvent::Leave(output) => emit(&weak, WindowEvent::OutputLeave(output)),
+            // Windows draw unrotated buffers, which every compositor accepts.
+            SurfaceEvent::PreferredBufferTransform(_) => {}
+        });
+
+        let weak = Arc::downgrade(&inner);
+        let xdg_surface = wm_base.get_xdg_surface(&surface, move |_, _| {
+            if let Some(inner) = weak.upgrade() {
+                let window = Window { inner };
+                if let Err(error) = window.configure() {
+                    window.inner.error.replace(Some(error));
+                }
+            }
+        })?;
+
+        let role = role(&xdg_surface, Arc::downgrade(&inner))?;
+
+        surface.commit()?;
+        *inner.shell.borrow_mut() = Some(Shell {
+            surface,
+            xdg_surface,
+            role,
+        });
+
+        Ok(Window { inner })
+    }
+
+    /// Returns the window whose surface has the object ID `surface`, such
+    /// as the surface of a `wl_pointer.enter` event, unless it was destroyed.
+    ///
+    /// Subsurfaces of a [`SurfaceTree`] are not windows and are not found.
+    pub fn from_surface(conn: &Connection, surface: u32) -> Option<Window> {
+        let inner = conn
+            .object_data::<SurfaceState>(surface)?
+            .owner::<WindowInner>()
+            .filter(|inner| inner.shell.borrow().is_some())?;
+
+        Some(Window { inner })
+    }
+
+    /// Returns the size of the window in surface-local coordinates.
+    pub fn size(&self) -> (i32, i32) {
+        self.inner.size.get()
+    }
+
+    /// Returns the object IDs of the outputs the window is shown on, to be
+    /// matched with [`Output::id`](crate::protocol::output::Output::id),
+    /// e.g. to adapt the frame rate or colors to the monitor it moved to.
+    ///
+    /// Empty once the window is destroyed.
+    pub fn outputs(&self) -> Vec<u32> {
+        self.inner
+            .shell
+            .borrow()
+            .as_ref()
+            .map_or_else(Vec::new, |shell| shell.surface.outputs())
+    }
+
+    /// Returns the scale the window is rendered at, 1.0 unless the scale
+    /// is tracked.
+    pub fn scale(&self) -> f64 {
+        self.inner
+            .scale
+            .borrow()
+            .as_ref()
+            .map_or(1.0, SurfaceScale::effective_scale)
+    }
+
+    /// Returns the size of the buffers of the window in pixels, which is
+    /// the size of the window multiplied by its scale and content scale.
+    pub fn buffer_size(&self) -> (i32, i32) {
+        let (width, height) = self.inner.size.get();
+        let (width, height) = match self.inner.scale.borrow().as_ref() {
+            Some(scale) => scale.buffer_size(width, height),
+            None => (width, height),
+        };
+
+        let content_scale = self.inner.content_scale.get();
+        if content_scale == 1.0 {
+            return (width, height);
+        }
+        (
+            ((f64::from(width) * content_scale).round() as i32).max(1),
+            ((f64::from(height) * content_scale).round() as i32).max(1),
+        )
+    }
+
+    /// Returns the resolution of the buffers relative to the scale of the
+    /// window, 1.0 unless set with [`Window::set_content_scale`].
+    pub fn content_scale(&self) -> f64 {
+        self.inner.content_scale.get()
+    }
+
+    /// Renders the content at `scale` times the resolution of the window,
+    /// letting the compositor scale the buffers to the window size with
+    /// `wp_viewport`.
+    ///
+    /// A scale below 1.0 trades sharpness for drawing time, e.g. to keep a
+    /// game at its frame rate; above 1.0 it supersamples. The buffers are
+    /// resized to the new [`Window::buffer_size`] and a
+    /// [`WindowEvent::Redraw`] is requested; the scale shows with the next
+    /// [`Window::draw`]. A crop set with [`Window::crop`] is kept, in the
+    /// pixels of the new buffers.
+    ///
+    /// # Errors
+    /// Returns an error if the scale is not a positive number, the
+    /// compositor does not support `wp_viewporter` or the buffers cannot be
+    /// reallocated.
+    pub fn set_content_scale(&self, scale: f64) -> Result<()> {
+        if !(scale.is_finite() && scale > 0.0) {
+            return Err(anyhow!("Content scale must be positive, got {scale}"));
+        }
+        if self.inner.content_scale.replace(scale) == scale {
+            return Ok(());
+        }
+        if scale != 1.0 {
+            self.ensure_viewport()?;
+        }
+
+        let (width, height) = self.buffer_size();
+        if let Some(swapchain) = self.inner.swapchain.borrow_mut().as_mut() {
+            swapchain.resize(width, height)?;
+        }
+        self.request_redraw()
+    }
+
+    /// Shows only `rect` of the content, in the pixels of the buffers
+    /// handed to [`Window::draw`], scaled up to fill the window; `None`
+    /// shows the whole content again.
+    ///
+    /// The crop is applied with `wp_viewport` and committed right away on
+    /// the content already shown, without redrawing, which makes it suited
+    /// to zooming into an image. A crop reaching past the buffers, e.g.
+    /// after the window shrank, is cut to them.
+    ///
+    /// # Errors
+    /// Returns an error if the rectangle is empty, the compositor does not
+    /// support `wp_viewporter` or the window was destroyed.
+    pub fn crop(&self, rect: Option<Rect>) -> Result<()> {
+        if let Some(rect) = rect
+            && (rect.is_empty() || rect.x < 0 || rect.y < 0)
+        {
+            return Err(anyhow!(
+                "Window crop must be a non-empty rectangle within the content, got {},{} {}x{}",
+                rect.x,
+                rect.y,
+                rect.width,
+                rect.height
+            ));
+        }
+        if rect.is_some() {
+            self.ensure_viewport()?;
+        }
+        self.inner.crop.set(rect);
+
+        self.with_shell(|shell| {
+            if !self.inner.configured.get() || !shell.surface.state().has_buffer() {
+                return Ok(());
+            }
+            self.apply_view(shell)?;
+            shell.surface.commit()
+        })
+    }
+
+    /// Renders the window at the scale of the outputs it is on, as tracked
+    /// by `manager`, for crisp content on HiDPI monitors.
+    ///
+    /// The buffers are resized to [`Window::buffer_size`] whenever the
+    /// scale changes, followed by a [`WindowEvent::ScaleChanged`] and a
+    /// [`WindowEvent::Redraw`]. Popups opened afterwards are tracked too.
+    ///
+    /// # Errors
+    /// Returns an error if the window was destroyed, its scale is already
+    /// tracked or a protocol object cannot be created.
+    pub fn track_scale(&self, manager: &ScaleManager) -> Result<()> {
+        if self.inner.scale.borrow().is_some() {
+            return Err(anyhow!("Window scale is already tracked"));
+        }
+
+        // The tracking may need the single viewport of the surface; one
+        // is created again for the content scale or crop if it does not.
+        self.inner.viewport.replace(None);
+        self.inner.view_applied.set(false);
+
+        let weak = Arc::downgrade(&self.inner);
+        let scale = self.with_shell(|shell| {
+            manager.track(&shell.surface, move |scale| {
+                if let Some(inner) = weak.upgrade() {
+                    let window = Window { inner };
+                    if let Err(error) = window.rescale(scale) {
+                        window.inner.error.replace(Some(error));
+                    }
+                }
+            })
+        })?;
+
+        self.inner.scale.replace(Some(scale));
+        self.inner.scale_manager.replace(Some(manager.clone()));
+
+        // The scale may already differ from 1.0 if the outputs are known.
+        let (width, height) = self.buffer_size();
+        match self.inner.swapchain.borrow_mut().as_mut() {
+            Some(swapchain) => swapchain.resize(width, height),
+            None => Ok(()),
+        }
+    }
+
+    /// Creates an empty tree of subsurfaces nested in the window, e.g. for
+    /// a video layer under the content drawn with [`Window::draw`].
+    ///
+    /// The tree must be dropped before the window is destroyed. Changes to
+    /// it show with [`Window::commit_tree`].
+    ///
+    /// # Errors
+    /// Returns an error if the window was destroyed.
+    pub fn create_surface_tree(&self, subcompositor: &SubCompositor) -> Result<SurfaceTree> {
+        self.with_shell(|shell| {
+            Ok(SurfaceTree::new(
+                &self.inner.compositor,
+                subcompositor,
+                &shell.surface,
+            ))
+        })
+    }
+
+    /// Commits `tree` and the window in one go, so the positions, stacking
+    /// order and content of its synchronized subsurfaces change together
+    /// with the window, see [`SurfaceTree::commit`].
+    ///
+    /// # Errors
+    /// Returns an error if the window was destroyed or `tree` was not
+    /// created for it.
+    pub fn commit_tree(&self, tree: &SurfaceTree) -> Result<()> {
+        self.with_shell(|shell| tree.commit(&shell.surface))
+    }
+
+    /// Captures `pointer` on the window for game-style input: the pointer
+    /// is locked in place, the cursor hidden and its motion reported as
+    /// relative deltas, see [`PointerCapture`].
+    ///
+    /// The capture ends when the window loses the pointer focus or the
+    /// guard is dropped.
+    ///
+    /// # Errors
+    /// Returns an error if the window was destroyed or already constrains
+    /// the pointer, or a protocol object cannot be created.
+    pub fn capture_pointer<F>(
+        &self,
+        pointer: &Pointer,
+        constraints: &PointerConstraints,
+        relative_pointers: &RelativePointerManager,
+        handler: F,
+    ) -> Result<PointerCapture>
+    where
+        F: FnMut(CaptureEvent) + Send + 'static,
+    {
+        self.with_shell(|shell| {
+            PointerCapture::new(
+                &shell.surface,
+                pointer,
+                constraints,
+                relative_pointers,
+                handler,
+            )
+        })
+    }
+
+    /// Keeps the system from idling while the window is visible, e.g. while
+    /// it plays a video, until the returned guard is dropped.
+    ///
+    /// # Errors
+    /// Returns an error if the window was destroyed or the inhibitor cannot
+    /// be created.
+    pub fn inhibit_idle(&self, manager: &IdleInhibitManager) -> Result<InhibitGuard> {
+        self.with_shell(|shell| manager.inhibit(&shell.surface))
+    }
+
+    /// Tags the content of the window with the color space and HDR
+    /// metadata of `params`, e.g. [`ImageDescriptionParams::hdr10`] for a
+    /// video player showing HDR10 video, so the compositor can tone map it
+    /// to the outputs.
+    ///
+    /// The compositor creates the image description asynchronously; once it
+    /// is ready, it applies with the next [`Window::draw`] and a
+    /// [`WindowEvent::Redraw`] is requested. If the compositor refuses it,
+    /// the next [`Window::draw`] returns the error. Calling this again
+    /// replaces the metadata.
+    ///
+    /// # Errors
+    /// Returns an error if the window was destroyed, `params` are invalid or
+    /// not supported by the compositor, or a protocol object cannot be
+    /// created.
+    pub fn set_hdr_metadata(
+        &self,
+        manager: &ColorManager,
+        params: &ImageDescriptionParams,
+    ) -> Result<()> {
+        if self.inner.color_surface.borrow().is_none() {
+            let color_surface = self.with_shell(|shell| manager.get_surface(&shell.surface))?;
+            self.inner.color_surface.replace(Some(color_surface));
+        }
+
+        let description = manager.create_parametric(params)?;
+        let weak = Arc::downgrade(&self.inner);
+        description.set_event_handler(move |_, event| {
+            if let Some(inner) = weak.upgrade() {
+                let window = Window { inner };
+                if let Err(error) = window.apply_description(event) {
+                    window.inner.error.replace(Some(error));
+                }
+            }
+        });
+
+        // Replacing a description still in flight destroys it, so only the
+        // latest one is applied.
+        self.inner.pending_description.replace(Some(description));
+        Ok(())
+    }
+
+    /// Returns who draws the decorations of the window, as configured by
+    /// the compositor.
+    ///
+    /// Windows are decorated by the client unless they were built with a
+    /// [`WindowBuilder::decorations`] preference the compositor follows.
+    /// The mode may change with any configure, so it is best checked on
+    /// [`WindowEvent::Configure`].
+    pub fn decoration_mode(&self) -> DecorationMode {
+        self.inner
+            .decoration
+            .borrow()
+            .as_ref()
+            .and_then(ToplevelDecoration::mode)
+            .unwrap_or(DecorationMode::ClientSide)
+    }
+
+    /// Sets the window title shown by task bars and window lists.
+    ///
+    /// # Errors
+    /// Returns an error for popups, which have no title.
+    pub fn set_title(&self, title: &str) -> Result<()> {
+        self.with_shell(|shell| match &shell.role {
+            Role::Toplevel(toplevel) => toplevel.set_title(title),
+            Role::Popup(_) => Err(anyhow!("Popups have no title")),
+        })
+    }
+
+    /// Returns the state of the window from the latest configure, e.g. to
+    /// drop shadows while maximized or dim the title bar while inactive.
+    ///
+    /// Popups and windows that were not configured yet have the default
+    /// state. It is updated before [`WindowEvent::Configure`] is delivered.
+    pub fn state(&self) -> WindowState {
+        self.inner.state.get()
+    }
+
+    /// Asks the compositor to maximize the window.
+    ///
+    /// The window is maximized once a configure reports it in
+    /// [`Window::state`].
+    ///
+    /// # Errors
+    /// Returns an error for popups or if the window was destroyed.
+    pub fn set_maximized(&self) -> Result<()> {
+        self.with_toplevel(XdgToplevel::set_maximized)
+    }
+
+    /// Asks the compositor to restore the window from maximized.
+    ///
+    /// # Errors
+    /// Returns an error for popups or if the window was destroyed.
+    pub fn unset_maximized(&self) -> Result<()> {
+        self.with_toplevel(XdgToplevel::unset_maximized)
+    }
+
+    /// Asks the compositor to make the window fullscreen, on `output` or on
+    /// the one the compositor picks.
+    ///
+    /// # Errors
+    /// Returns an error for popups or if the window was destroyed.
+    pub fn set_fullscreen(&self, output: Option<&Output>) -> Result<()> {
+        self.with_toplevel(|toplevel| toplevel.set_fullscreen(output.map(Output::id)))
+    }
+
+    /// Asks the compositor to restore the window from fullscreen.
+    ///
+    /// # Errors
+    /// Returns an error for popups or if the window was destroyed.
+    pub fn unset_fullscreen(&self) -> Result<()> {
+        self.with_toplevel(XdgToplevel::unset_fullscreen)
+    }
+
+    /// Asks the compositor to minimize the window.
+    ///
+    /// The protocol does not report the window as minimized, nor restores
+    /// it; the user brings it back through the compositor.
+    ///
+    /// # Errors
+    /// Returns an error for popups or if the window was destroyed.
+    pub fn set_minimized(&self) -> Result<()> {
+        self.with_toplevel(XdgToplevel::set_minimized)
+    }
+
+    /// Sets how the buffers follow the size of the window during
+    /// interactive resizes.
+    ///
+    /// [`ResizeMode::Overallocate`] keeps resizing smooth for applications
+    /// that redraw on every configure, by not reallocating the buffers for
+    /// every pixel the user drags the border by. It falls back to exact
+    /// buffers on compositors without `wp_viewporter`.
+    pub fn set_resize_mode(&self, mode: ResizeMode) {
+        self.inner.resize_mode.set(mode);
+    }
+
+    /// Lets the user move the window by dragging it, e.g. from a title bar
+    /// drawn by the application.
+    ///
+    /// Must be called in answer to a pointer button press or touch down on
+    /// `seat`, whose serial is taken from the seat; the compositor then
+    /// moves the window until the button is released or the finger lifted.
+    ///
+    /// # Errors
+    /// Returns an error for popups, if the window was destroyed or if the
+    /// seat had no button press or touch down yet.
+    pub fn begin_move(&self, seat: &Seat) -> Result<()> {
+        let serial = grab_serial(seat)?;
+        self.with_toplevel(|toplevel| toplevel.start_move(seat, serial))
+    }
+
+    /// Lets the user resize the window by dragging `edge`, e.g. from a
+    /// border drawn by the application.
+    ///
+    /// Must be called in answer to a pointer button press or touch down on
+    /// `seat`, whose serial is taken from the seat. The configures sent
+    /// during the resize report [`WindowState::resizing`].
+    ///
+    /// # Errors
+    /// Returns an error for popups, if the window was destroyed or if the
+    /// seat had no button press or touch down yet.
+    pub fn begin_resize(&self, seat: &Seat, edge: ResizeEdge) -> Result<()> {
+        let serial = grab_serial(seat)?;
+        self.with_toplevel(|toplevel| toplevel.start_resize(seat, serial, edge))
+    }
+
+    /// Resizes the window to `width` x `height` in surface-local coordinates.
+    ///
+    /// The new size shows with the next [`Window::draw`]. The compositor
+    /// may override it with the next configure, e.g. for maximized windows.
+    ///
+    /// # Errors
+    /// Returns an error if the size is empty or the buffers cannot be
+    /// reallocated.
+    pub fn resize(&self, width: i32, height: i32) -> Result<()> {
+        if width <= 0 || height <= 0 {
+            return Err(anyhow!(
+                "Window size must not be empty, got {width}x{height}"
+            ));
+        }
+
+        self.inner.size.set((width, height));
+
+        let (buffer_width, buffer_height) = self.buffer_size();
+        match self.inner.swapchain.borrow_mut().as_mut() {
+            Some(swapchain) => swapchain.resize(buffer_width, buffer_height),
+            None => Ok(()),
+        }
+    }
+
+    /// Draws the next frame of the window and presents it.
+    ///
+    /// `draw` receives a free buffer of [`Window::buffer_size`], which is
+    /// then attached, damaged as a whole and committed along with the
+    /// acknowledgement of the latest configure. It must not call back into
+    /// the window.
+    ///
+    /// # Returns
+    /// `false`, without calling `draw`, if the window was not configured
+    /// yet or all buffers are still used by the compositor. A
+    /// [`WindowEvent::Redraw`] is delivered once drawing is possible.
+    ///
+    /// # Errors
+    /// Returns an error if the window was destroyed, the last configure
+    /// could not be applied or the frame cannot be presented.
+    pub fn draw<F>(&self, draw: F) -> Result<bool>
+    where
+        F: FnOnce(&mut SwapchainBuffer<'_>),
+    {
+        if let Some(error) = self.inner.error.take() {
+            return Err(error.context("Failed to apply window configure"));
+        }
+        if !self.inner.configured.get() {
+            return Ok(false);
+        }
+
+        let drawn = self.with_shell(|shell| {
+            let mut swapchain = self.inner.swapchain.borrow_mut();
+            let Some(mut buffer) = swapchain.as_mut().and_then(Swapchain::acquire) else {
+                return Ok(false);
+            };
+
+            draw(&mut buffer);
+            let damage = Rect::new(0, 0, buffer.width(), buffer.height());
+
+            buffer.attach(&shell.surface)?;
+            drop(swapchain);
+            shell.surface.damage_buffer(damage)?;
+            self.apply_view(shell)?;
+            shell.surface.commit()?;
+
+            Ok(true)
+        })?;
+
+        if !drawn {
+            self.request_redraw()?;
+        }
+
+        Ok(drawn)
+    }
+
+    /// Requests a [`WindowEvent::Redraw`] when it is a good time to draw the
+    /// next frame, as decided by the compositor's frame callbacks.
+    ///
+    /// Requests made while one is in flight are merged. Before the first
+    /// configure this does nothing, as the configure is followed by a redraw.
+    pub fn request_redraw(&self) -> Result<()> {
+        if !self.inner.configured.get() || self.inner.frame_pending.replace(true) {
+            return Ok(());
+        }
+
+        let weak = Arc::downgrade(&self.inner);
+        self.with_shell(|shell| {
+            shell.surface.request_frame(move |_, _| {
+                if let Some(inner) = weak.upgrade() {
+                    inner.frame_pending.set(false);
+                    emit(&Arc::downgrade(&inner), WindowEvent::Redraw);
+                }
+            })?;
+            shell.surface.commit()
+        })
+    }
+
+    /// Destroys the window, its buffers and the popups opened from it.
+    ///
+    /// Other handles to the window stay valid, but their requests fail.
+    /// Dropping the last handle destroys the window too.
+    pub fn destroy(&self) -> Result<()> {
+        self.inner.destroy()
+    }
+
+    /// Applies the configuration ending with an `xdg_surface.configure`
+    /// event, then asks the application to redraw.
+    fn configure(&self) -> Result<()> {
+        let current = self.inner.size.get();
+        let configure = self.with_shell(|shell| {
+            Ok(match &shell.role {
+                Role::Toplevel(toplevel) => {
+                    take_configure(toplevel, current, WindowEvent::Configure)
+                }
+                Role::Popup(popup) => take_configure(popup, current, WindowEvent::PopupConfigure),
+            })
+        })?;
+        let Some((size, event)) = configure else {
+            return Ok(());
+        };
+        if let WindowEvent::Configure(configure) = &event {
+            self.inner.state.set(configure.window_state());
+        }
+
+        self.inner.size.set(size);
+        let (buffer_width, buffer_height) = self.buffer_size();
+
+        // Configures arrive for every step of an interactive resize.
+        let fit = self.inner.resize_mode.get() == ResizeMode::Overallocate
+            && self.inner.state.get().resizing
+            && self.inner.conn.capabilities().has(WlObjectId::Viewporter);
+        if fit {
+            self.ensure_viewport()?;
+        }
+
+        let mut swapchain = self.inner.swapchain.borrow_mut();
+        match swapchain.as_mut() {
+            Some(swapchain) if fit => swapchain.fit(buffer_width, buffer_height)?,
+            Some(swapchain) => swapchain.resize(buffer_width, buffer_height)?,
+            None => {
+                *swapchain = Some(Swapchain::new(
+                    &self.inner.shm,
+                    BUFFER_COUNT,
+                    buffer_width,
+                    buffer_height,
+                    BUFFER_FORMAT,
+                )?)
+            }
+        }
+        drop(swapchain);
+
+        self.inner.configured.set(true);
+
+        let weak = Arc::downgrade(&self.inner);
+        emit(&weak, event);
+        emit(&weak, WindowEvent::Redraw);
+
+        Ok(())
+    }
+
+    /// Resizes the buffers for a new scale, then asks the application to
+    /// redraw.
+    fn rescale(&self, scale: f64) -> Result<()> {
+        let (width, height) = self.buffer_size();
+        if let Some(swapchain) = self.inner.swapchain.borrow_mut().as_mut() {
+            swapchain.resize(width, height)?;
+        }
+
+        emit(
+            &Arc::downgrade(&self.inner),
+            WindowEvent::ScaleChanged(scale),
+        );
+        self.request_redraw()
+    }
+
+    /// Returns `true` if the content is presented through the viewport,
+    /// for a content scale, a crop or buffers larger than the content.
+    fn has_view(&self) -> bool {
+        self.inner.content_scale.get() != 1.0
+            || self.inner.crop.get().is_some()
+            || self.is_oversized()
+    }
+
+    /// Returns `true` if the buffers are larger than the content during an
+    /// interactive resize, see [`ResizeMode::Overallocate`].
+    fn is_oversized(&self) -> bool {
+        self.inner
+            .swapchain
+            .borrow()
+            .as_ref()
+            .is_some_and(Swapchain::is_oversized)
+    }
+
+    /// Creates the viewport of the surface, unless the scale tracking
+    /// already provides one.
+    ///
+    /// # Errors
+    /// Returns an error if the compositor does not support `wp_viewporter`
+    /// or the window was destroyed.
+    fn ensure_viewport(&self) -> Result<()> {
+        let shared = self
+            .inner
+            .scale
+            .borrow()
+            .as_ref()
+            .is_some_and(|scale| scale.viewport().is_some());
+        if shared || self.inner.viewport.borrow().is_some() {
+            return Ok(());
+        }
+
+        let viewport = self.with_shell(|shell| {
+            let viewporter = Viewporter::bind(&self.inner.conn)?;
+            let viewport = viewporter.get_viewport(&shell.surface)?;
+            viewporter.destroy()?;
+            Ok(viewport)
+        })?;
+        self.inner.viewport.replace(Some(viewport));
+
+        Ok(())
+    }
+
+    /// Issues the buffer scale, crop and destination of the surface for the
+    /// next commit, through the viewport if the content is scaled or
+    /// cropped and through the scale tracking otherwise.
+    fn apply_view(&self, shell: &Shell) -> Result<()> {
+        let (width, height) = self.inner.size.get();
+        let scale = self.inner.scale.borrow();
+        let shared = scale.as_ref().and_then(SurfaceScale::viewport);
+
+        if !self.has_view() {
+            if self.inner.view_applied.replace(false) {
+                match shared {
+                    Some(viewport) => viewport.unset_source()?,
+                    None => drop(self.inner.viewport.take()),
+                }
+            }
+            return match scale.as_ref() {
+                Some(scale) => scale.apply(&shell.surface, width, height),
+                None => Ok(()),
+            };
+        }
+
+        // The destination sets the surface size, so buffers of any size
+        // work, but an integer buffer scale would still constrain them.
+        if !self.inner.view_applied.replace(true)
+            && scale.is_some()
+            && shared.is_none()
+            && shell.surface.version() >= WL_SURFACE_SET_BUFFER_SCALE_SINCE
+        {
+            shell.surface.set_buffer_scale(1)?;
+        }
+        drop(scale);
+        self.ensure_viewport()?;
+
+        let scale = self.inner.scale.borrow();
+        let own = self.inner.viewport.borrow();
+        let Some(viewport) = scale
+            .as_ref()
+            .and_then(SurfaceScale::viewport)
+            .or(own.as_ref())
+        else {
+            return Err(anyhow!("Window has no viewport"));
+        };
+
+        let (buffer_width, buffer_height) = self.buffer_size();
+        let content = Rect::new(0, 0, buffer_width, buffer_height);
+        let crop = self
+            .inner
+            .crop
+            .get()
+            .map(|crop| crop.intersection(&content))
+            .filter(|crop| !crop.is_empty())
+            .or_else(|| self.is_oversized().then_some(content));
+        match crop {
+            Some(crop) => viewport.set_source(
+                f64::from(crop.x),
+                f64::from(crop.y),
+                f64::from(crop.width),
+                f64::from(crop.height),
+            )?,
+            None => viewport.unset_source()?,
+        }
+        viewport.set_destination(width, height)
+    }
+
+    /// Applies the image description set with [`Window::set_hdr_metadata`]
+    /// once the compositor reports on it.
+    fn apply_description(&self, event: ImageDescriptionEvent) -> Result<()> {
+        let Some(description) = self.inner.pending_description.take() else {
+            return Ok(());
+        };
+
+        match event {
+            ImageDescriptionEvent::Ready(_) => {
+                if let Some(color_surface) = self.inner.color_surface.borrow().as_ref() {
+                    // Perceptual is the one intent every compositor supports.
+                    color_surface.set_image_description(&description, RenderIntent::Perceptual)?;
+                }
+                // The surface keeps its description once it is set.
+                description.destroy()?;
+                self.request_redraw()
+            }
+            ImageDescriptionEvent::Failed { cause, message } => Err(anyhow!(
+                "Compositor refused the HDR metadata ({cause}): {message}"
+            )),
+        }
+    }
+
+    /// Runs `f` with the protocol objects of the window.
+    ///
+    /// # Errors
+    /// Returns an error if the window was destroyed.
+    fn with_shell<T>(&self, f: impl FnOnce(&Shell) -> Result<T>) -> Result<T> {
+        match self.inner.shell.borrow().as_ref() {
+            Some(shell) => f(shell),
+            None => Err(anyhow!("Window was destroyed")),
+        }
+    }
+
+    /// Runs `f` with the toplevel of the window.
+    ///
+    /// # Errors
+    /// Returns an error for popups or if the window was destroyed.
+    fn with_toplevel<T>(&self, f: impl FnOnce(&XdgToplevel) -> Result<T>) -> Result<T> {
+        self.with_shell(|shell| match &shell.role {
+            Role::Toplevel(toplevel) => f(toplevel),
+            Role::Popup(_) => Err(anyhow!("Window is a popup, not a toplevel")),
+        })
+    }
+}
+
+impl WindowInner {
+    /// Destroys the protocol objects of the window, see [`Window::destroy`].
+    fn destroy(&self) -> Result<()> {
+        // Popups must be destroyed before their parent, the most recent first.
+        let popups = self.popups.take();
+        for popup in popups.iter().rev().filter_map(Weak::upgrade) {
+            popup.destroy()?;
+        }
+
+        let Some(shell) = self.shell.borrow_mut().take() else {
+            return Ok(());
+        };
+
+        self.swapchain.borrow_mut().take();
+        self.viewport.borrow_mut().take();
+        self.pending_description.borrow_mut().take();
+        self.color_surface.borrow_mut().take();
+        // The decoration object must not outlive its toplevel.
+        self.decoration.borrow_mut().take();
+        self.handler.borrow_mut().take();
+        self.scale_manager.borrow_mut().take();
+        if let Some(scale) = self.scale.borrow_mut().take() {
+            scale.destroy()?;
+        }
+
+        match shell.role {
+            Role::Toplevel(toplevel) => toplevel.destroy()?,
+            Role::Popup(popup) => popup.destroy()?,
+        }
+        shell.xdg_surface.destroy()?;
+        shell.surface.destroy()?;
+
+        self.conn.flush()
+    }
+}
+
+impl Drop for WindowInner {
+    fn drop(&mut self) {
+        // Queuing requests cannot fail; errors only surface on flush.
+        let _ = self.destroy();
+    }
+}
+
+/// Takes the latest configuration of `role`, returning the window size it
+/// leads to from `current` and the event announcing it.
+fn take_configure<R: ShellRole>(
+    role: &R,
+    current: (i32, i32),
+    event: impl FnOnce(R::Configure) -> WindowEvent,
+) -> Option<((i32, i32), WindowEvent)> {
+    let configure = role.take_configure()?;
+
+    Some((configure.apply_size(current), event(configure)))
+}
+
+/// Returns the serial of the latest pointer button press or touch down on
+/// `seat`, which starts interactive moves and resizes.
+fn grab_serial(seat: &Seat) -> Result<u32> {
+    seat.state().grab_serial().ok_or_else(|| {
+        anyhow!(
+            "Seat {} had no button press or touch down to start the drag",
+            seat.id()
+        )
+    })
+}
+
+/// Delivers `event` to the handler of the window, if the window still exists.
+///
+/// The handler is taken out while it runs, so it may call back into the
+/// window.
+fn emit(window: &Weak<WindowInner>, event: WindowEvent) {
+    let Some(inner) = window.upgrade() else {
+        return;
+    };
+    let Some(mut handler) = inner.handler.borrow_mut().take() else {
+        return;
+    };
+
+    let window = Window { inner };
+    handler(&window, event);
+
+    // The window may have been destroyed by the handler.
+    if window.inner.shell.borrow().is_some() {
+        window.inner.handler.borrow_mut().get_or_insert(handler);
+    }
+}
+
+/// Delivers a pointer frame to the window under the pointer as a
+/// [`WindowEvent::Pointer`], so applications with several windows need not
+/// match surface IDs themselves:
+///
+/// ```no_run
+/// # fn run(seat: &wayland_client_from_scratch::protocol::seat::Seat) -> wayland_client_from_scratch::error::Result<()> {
+/// use wayland_client_from_scratch::window::route_pointer;
+///
+/// let pointer = seat.get_pointer(route_pointer)?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Frames on surfaces that are not windows are dropped.
+pub fn route_pointer(conn: &Connection, mut frame: PointerFrame) {
+    // The window the pointer left gets the leave on its own.
+    if let Some(leave) = frame.leave
+        && Some(leave.surface.get()) != frame.focus
+    {
+        frame.leave = None;
+        if let Some(window) = Window::from_surface(conn, leave.surface.get()) {
+            let left = PointerFrame {
+                leave: Some(leave),
+                ..PointerFrame::default()
+            };
+            emit(&Arc::downgrade(&window.inner), WindowEvent::Pointer(left));
+        }
+    }
+
+    if frame.is_empty() {
+        return;
+    }
+    if let Some(window) = frame
+        .focus
+        .and_then(|surface| Window::from_surface(conn, surface))
+    {
+        emit(&Arc::downgrade(&window.inner), WindowEvent::Pointer(frame));
+    }
+}
+
+/// Delivers the part of a touch frame concerning each window to that window
+/// as a [`WindowEvent::Touch`], see [`route_pointer`].
+///
+/// Touch points on surfaces that are not windows are dropped.
+pub fn route_touch(conn: &Connection, frame: TouchFrame) {
+    for surface in frame.surfaces() {
+        if let Some(window) = Window::from_surface(conn, surface) {
+            let event = WindowEvent::Touch(frame.for_surface(surface));
+            emit(&Arc::downgrade(&window.inner), event);
+        }
+    }
+}
+
+/// Notifies a popup dismissed by the compositor, then destroys it.
+fn dismiss(popup: &Weak<WindowInner>) {
+    emit(popup, WindowEvent::Close);
+
+    if let Some(inner) = popup.upgrade() {
+        let window = Window { inner };
+        if let Err(error) = window.destroy() {
+            window.inner.error.replace(Some(error));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Read, os::unix::net::UnixStream};
+
+    use super::*;
+    use crate::protocol::{
+        endian::Endian,
+        message::WlMessageHeader,
+        pointer::event::{enter::Enter, leave::Leave},
+        registry::event::global::Global,
+        surface::request::Opcode as SurfaceOpcode,
+        types::{WlFixed, WlObject, WlString, WlUInt},
+        xdg_decoration::request::DecorationOpcode,
+        xdg_toplevel::request::Opcode as ToplevelOpcode,
+    };
+    use crate::server::HeadlessCompositor;
+
+    /// Returns a connection advertising `globals`, with the compositor end
+    /// of its socket.
+    fn connection_with(globals: &[(u32, &str, u32)]) -> (Connection, UnixStream) {
+        let (client, compositor_end) = UnixStream::pair().unwrap();
+        let conn = Connection::from_stream(client).unwrap();
+        for &(name, interface, version) in globals {
+            conn.insert_global(Global {
+                name: WlUInt(name),
+                interface: WlString::new(interface).unwrap(),
+                version: WlUInt(version),
+            });
+        }
+
+        (conn, compositor_end)
+    }
+
+    #[test]
+    fn builder_sets_the_properties_before_the_initial_commit() {
+        let (conn, mut compositor_end) = connection_with(&[
+            (1, "wl_compositor", 6),
+            (2, "xdg_wm_base", 6),
+            (3, "wl_shm", 1),
+            (4, "zxdg_decoration_manager_v1", 1),
+        ]);
+        let compositor = Compositor::bind(&conn).unwrap();
+        let wm_base = XdgWmBase::bind(&conn).unwrap();
+        let shm = Shm::bind(&conn).unwrap();
+
+        let invalid = WindowBuilder::new(640, 480)
+            .min_size(800, 0)
+            .max_size(600, 0)
+            .build(&conn, &compositor, &wm_base, &shm, |_, _| {});
+        assert!(invalid.is_err());
+
+        let window = WindowBuilder::new(640, 480)
+            .title("Editor")
+            .app_id("org.example.Editor")
+            .min_size(320, 240)
+            .max_size(0, 0)
+            .maximized(true)
+            .decorations(DecorationMode::ServerSide)
+            .build(&conn, &compositor, &wm_base, &shm, |_, _| {})
+            .unwrap();
+        assert_eq!(window.decoration_mode(), DecorationMode::ClientSide);
+
+        // The objects of the window take the next IDs of the fresh connection
+        let surface = shm.id() + 1;
+        let toplevel = surface + 2;
+        let decoration = toplevel + 2;
+        drop(window);
+        drop((compositor, wm_base, shm, conn));
+
+        let mut received = Vec::new();
+        compositor_end.read_to_end(&mut received).unwrap();
+        let mut requests = Vec::new();
+        while !received.is_empty() {
+            let header = WlMessageHeader::decode(&received, Endian::NATIVE).unwrap();
+            requests.push((header.object_id, header.opcode));
+            received.drain(..header.size as usize);
+        }
+        let commit = requests
+            .iter()
+            .position(|&request| request == (surface, SurfaceOpcode::Commit as u16))
+            .unwrap();
+        let before_commit: Vec<_> = requests[..commit]
+            .iter()
+            .filter(|&&(object, _)| object == toplevel || object == decoration)
+            .copied()
+            .collect();
+        assert_eq!(
+            before_commit,
+            [
+                (toplevel, ToplevelOpcode::SetTitle as u16),
+                (toplevel, ToplevelOpcode::SetAppId as u16),
+                (toplevel, ToplevelOpcode::SetMinSize as u16),
+                (toplevel, ToplevelOpcode::SetMaxSize as u16),
+                (toplevel, ToplevelOpcode::SetMaximized as u16),
+                (decoration, DecorationOpcode::SetMode as u16),
+            ]
+        );
+        // The decoration object is destroyed before its toplevel
+        let destroyed = |object| {
+            requests
+                .iter()
+                .position(|&request| request == (object, 0))
+                .unwrap()
+        };
+        assert!(destroyed(decoration) < destroyed(toplevel));
+    }
+
+    #[test]
+    fn pointer_frames_are_routed_to_their_windows() {
+        let headless = HeadlessCompositor::spawn().unwrap();
+        let conn = headless.connect().unwrap();
+        let compositor = Compositor::bind(&conn).unwrap();
+        let wm_base = XdgWmBase::bind(&conn).unwrap();
+        let shm = Shm::bind(&conn).unwrap();
+
+        let events = Arc::new(Lock::new(Vec::new()));
+        let open = |name: &'static str| {
+            let events = events.clone();
+            Window::new(
+                &conn,
+                &compositor,
+                &wm_base,
+                &shm,
+                640,
+                480,
+                move |_, event| {
+                    if let WindowEvent::Pointer(frame) = event {
+                        events.borrow_mut().push((name, frame));
+                    }
+                },
+            )
+            .unwrap()
+        };
+        let (first, second) = (open("first"), open("second"));
+        conn.roundtrip().unwrap();
+        let surface = |window: &Window| window.with_shell(|shell| Ok(shell.surface.id())).unwrap();
+        let (first_surface, second_surface) = (surface(&first), surface(&second));
+        assert!(Window::from_surface(&conn, second_surface).is_some());
+        assert!(Window::from_surface(&conn, shm.id()).is_none());
+
+        // The pointer moves from the first window to the second
+        let leave = Leave {
+            serial: WlUInt(1),
+            surface: WlObject(first_surface),
+        };
+        let enter = Enter {
+            serial: WlUInt(2),
+            surface: WlObject(second_surface),
+            surface_x: WlFixed::from_f64(3.0),
+            surface_y: WlFixed::from_f64(4.0),
+        };
+        route_pointer(
+            &conn,
+            PointerFrame {
+                leave: Some(leave),
+                enter: Some(enter),
+                focus: Some(second_surface),
+                position: (3.0, 4.0),
+                ..PointerFrame::default()
+            },
+        );
+
+        let events = events.borrow();
+        assert_eq!(events.len(), 2);
+        assert_eq!((events[0].0, events[0].1.leave), ("first", Some(leave)));
+        assert_eq!(events[0].1.enter, None);
+        assert_eq!((events[1].0, events[1].1.enter), ("second", Some(enter)));
+        assert_eq!(events[1].1.leave, None);
+
+        // Destroyed windows are no longer found
+        first.destroy().unwrap();
+        assert!(Window::from_surface(&conn, first_surface).is_none());
+        conn.roundtrip().unwrap();
+        let surfaces = headless.surfaces();
+        assert_eq!(surfaces.len(), 1);
+        assert_eq!(surfaces[0].surface, second_surface);
+    }
+}
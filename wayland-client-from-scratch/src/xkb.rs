@@ -0,0 +1,287 @@
+use std::{
+    ffi::{c_char, c_void},
+    fs::File,
+    os::unix::fs::FileExt,
+    ptr::NonNull,
+};
+
+use crate::{
+    error::{Context, Result, anyhow},
+    keysym::Keysym,
+    protocol::keyboard::event::{
+        keymap::{Keymap, KeymapFormat},
+        modifiers::Modifiers,
+    },
+};
+
+/// The offset between Linux evdev codes, used by `wl_keyboard.key`, and
+/// XKB keycodes.
+const EVDEV_OFFSET: u32 = 8;
+
+/// The size of the buffer the UTF-8 text of a key is first written to;
+/// longer texts are retried with a buffer of the exact size.
+const UTF8_BUFFER_LEN: usize = 64;
+
+// Hand-written declarations of the libxkbcommon symbols used below, from
+// `xkbcommon/xkbcommon.h`.
+mod ffi {
+    use std::ffi::{c_char, c_int, c_void};
+
+    /// `xkb_context_flags` value requesting the default behavior.
+    pub const XKB_CONTEXT_NO_FLAGS: c_int = 0;
+    /// `xkb_keymap_format` value of the text format sent by compositors.
+    pub const XKB_KEYMAP_FORMAT_TEXT_V1: c_int = 1;
+    /// `xkb_keymap_compile_flags` value requesting the default behavior.
+    pub const XKB_KEYMAP_COMPILE_NO_FLAGS: c_int = 0;
+
+    #[link(name = "xkbcommon")]
+    unsafe extern "C" {
+        pub fn xkb_context_new(flags: c_int) -> *mut c_void;
+        pub fn xkb_context_unref(context: *mut c_void);
+        pub fn xkb_keymap_new_from_buffer(
+            context: *mut c_void,
+            buffer: *const c_char,
+            length: usize,
+            format: c_int,
+            flags: c_int,
+        ) -> *mut c_void;
+        pub fn xkb_keymap_unref(keymap: *mut c_void);
+        pub fn xkb_state_new(keymap: *mut c_void) -> *mut c_void;
+        pub fn xkb_state_unref(state: *mut c_void);
+        pub fn xkb_state_update_mask(
+            state: *mut c_void,
+            depressed_mods: u32,
+            latched_mods: u32,
+            locked_mods: u32,
+            depressed_layout: u32,
+            latched_layout: u32,
+            locked_layout: u32,
+        ) -> c_int;
+        pub fn xkb_state_key_get_one_sym(state: *mut c_void, key: u32) -> u32;
+        pub fn xkb_state_key_get_utf8(
+            state: *mut c_void,
+            key: u32,
+            buffer: *mut c_char,
+            size: usize,
+        ) -> c_int;
+    }
+}
+
+/// The symbol and text a key press produces in the active keymap.
+///
+/// Delivered as [`KeyboardEvent::Symbol`](crate::protocol::keyboard::KeyboardEvent::Symbol)
+/// right after the press or repeat it belongs to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeySymbol {
+    /// The Linux evdev code of the key.
+    pub key: u32,
+    /// The keysym the key produces with the current modifiers.
+    pub keysym: Keysym,
+    /// The text the key types, if any.
+    pub utf8: Option<String>,
+}
+
+/// A compiled XKB keymap with its modifier and layout state.
+///
+/// Built from the keymap sent with `wl_keyboard.keymap` and updated by the
+/// `wl_keyboard.modifiers` events, it resolves key codes to keysyms and
+/// text the way every other Wayland client does.
+pub struct XkbState {
+    /// The `xkb_context` the keymap was compiled in.
+    context: NonNull<c_void>,
+    /// The compiled `xkb_keymap`.
+    keymap: NonNull<c_void>,
+    /// The `xkb_state` tracking the modifiers and layout.
+    state: NonNull<c_void>,
+}
+
+impl XkbState {
+    /// Compiles the keymap of a `wl_keyboard.keymap` event.
+    ///
+    /// # Errors
+    /// Returns an error if the keymap is not in the XKB v1 format, cannot be
+    /// read or fails to compile.
+    pub fn from_keymap(keymap: &Keymap) -> Result<XkbState> {
+        if keymap.format != KeymapFormat::XkbV1 {
+            return Err(anyhow!("Unsupported keymap format {}", keymap.format));
+        }
+
+        // Read with pread, as the file offset may be shared with other
+        // clients receiving the same descriptor
+        let file = File::from(keymap.fd.try_clone()?);
+        let mut text = vec![0; keymap.size.get() as usize];
+        file.read_exact_at(&mut text, 0)
+            .context("Failed to read the keymap")?;
+
+        // The keymap is a NUL-terminated string
+        let len = text
+            .iter()
+            .position(|&byte| byte == 0)
+            .unwrap_or(text.len());
+
+        XkbState::from_text(&text[..len])
+    }
+
+    /// Compiles a keymap in the XKB v1 text format.
+    ///
+    /// # Errors
+    /// Returns an error if the keymap fails to compile.
+    pub fn from_text(text: &[u8]) -> Result<XkbState> {
+        // SAFETY: the context is created without arguments and checked below.
+        let context = NonNull::new(unsafe { ffi::xkb_context_new(ffi::XKB_CONTEXT_NO_FLAGS) })
+            .ok_or_else(|| anyhow!("Failed to create the XKB context"))?;
+
+        // SAFETY: `context` is valid and `text` is valid for `text.len()` bytes.
+        let keymap = NonNull::new(unsafe {
+            ffi::xkb_keymap_new_from_buffer(
+                context.as_ptr(),
+                text.as_ptr().cast::<c_char>(),
+                text.len(),
+                ffi::XKB_KEYMAP_FORMAT_TEXT_V1,
+                ffi::XKB_KEYMAP_COMPILE_NO_FLAGS,
+            )
+        });
+        let Some(keymap) = keymap else {
+            // SAFETY: the context is owned here and not used afterwards.
+            unsafe { ffi::xkb_context_unref(context.as_ptr()) };
+            return Err(anyhow!("Failed to compile the XKB keymap"));
+        };
+
+        // SAFETY: `keymap` is a valid compiled keymap.
+        let state = NonNull::new(unsafe { ffi::xkb_state_new(keymap.as_ptr()) });
+        let Some(state) = state else {
+            // SAFETY: the keymap and context are owned here and not used afterwards.
+            unsafe {
+                ffi::xkb_keymap_unref(keymap.as_ptr());
+                ffi::xkb_context_unref(context.as_ptr());
+            }
+            return Err(anyhow!("Failed to create the XKB state"));
+        };
+
+        Ok(XkbState {
+            context,
+            keymap,
+            state,
+        })
+    }
+
+    /// Applies the modifier and layout state of a `wl_keyboard.modifiers` event.
+    pub fn update_modifiers(&mut self, modifiers: &Modifiers) {
+        // SAFETY: `state` is valid for the lifetime of `self`.
+        unsafe {
+            ffi::xkb_state_update_mask(
+                self.state.as_ptr(),
+                modifiers.mods_depressed.get(),
+                modifiers.mods_latched.get(),
+                modifiers.mods_locked.get(),
+                0,
+                0,
+                modifiers.group.get(),
+            );
+        }
+    }
+
+    /// Returns the keysym of the key with the evdev code `key`, as sent by
+    /// `wl_keyboard.key`.
+    pub fn keysym(&self, key: u32) -> Keysym {
+        // SAFETY: `state` is valid for the lifetime of `self`.
+        Keysym(unsafe { ffi::xkb_state_key_get_one_sym(self.state.as_ptr(), key + EVDEV_OFFSET) })
+    }
+
+    /// Returns the text typed by the key with the evdev code `key`, if any.
+    pub fn utf8(&self, key: u32) -> Option<String> {
+        let mut buffer = vec![0u8; UTF8_BUFFER_LEN];
+
+        loop {
+            // SAFETY: `state` is valid and `buffer` is writable for its length.
+            let len = unsafe {
+                ffi::xkb_state_key_get_utf8(
+                    self.state.as_ptr(),
+                    key + EVDEV_OFFSET,
+                    buffer.as_mut_ptr().cast::<c_char>(),
+                    buffer.len(),
+                )
+            };
+            let len = usize::try_from(len).ok().filter(|&len| len > 0)?;
+
+            // The text is truncated if it does not fit with its NUL terminator
+            if len < buffer.len() {
+                buffer.truncate(len);
+                return String::from_utf8(buffer).ok();
+            }
+            buffer.resize(len + 1, 0);
+        }
+    }
+
+    /// Resolves a key press or repeat to the symbol delivered with it.
+    pub fn key_symbol(&self, key: u32) -> KeySymbol {
+        KeySymbol {
+            key,
+            keysym: self.keysym(key),
+            utf8: self.utf8(key),
+        }
+    }
+}
+
+// SAFETY: libxkbcommon objects are not tied to the thread that created
+// them, only unsynchronized, and the three objects are owned by `self` alone,
+// so moving them to another thread cannot race with anything.
+unsafe impl Send for XkbState {}
+
+impl Drop for XkbState {
+    fn drop(&mut self) {
+        // SAFETY: each object is owned by `self` and released exactly once,
+        // dependents first.
+        unsafe {
+            ffi::xkb_state_unref(self.state.as_ptr());
+            ffi::xkb_keymap_unref(self.keymap.as_ptr());
+            ffi::xkb_context_unref(self.context.as_ptr());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::types::WlUInt;
+
+    /// A self-contained keymap with Escape and a two-level `a` key.
+    const KEYMAP: &str = r#"xkb_keymap {
+        xkb_keycodes { <ESC> = 9; <AC01> = 38; <LFSH> = 50; };
+        xkb_types {
+            type "ONE_LEVEL" { modifiers = none; level_name[Level1] = "Any"; };
+            type "ALPHABETIC" {
+                modifiers = Shift + Lock;
+                map[Shift] = Level2;
+                map[Lock] = Level2;
+                level_name[Level1] = "Base";
+                level_name[Level2] = "Caps";
+            };
+        };
+        xkb_compatibility { };
+        xkb_symbols {
+            key <ESC> { [ Escape ] };
+            key <AC01> { type = "ALPHABETIC", [ a, A ] };
+            key <LFSH> { [ Shift_L ] };
+            modifier_map Shift { <LFSH> };
+        };
+    };"#;
+
+    #[test]
+    fn resolves_keys_with_modifiers() {
+        let mut xkb = XkbState::from_text(KEYMAP.as_bytes()).unwrap();
+
+        assert_eq!(xkb.keysym(1), Keysym::ESCAPE);
+        assert_eq!(xkb.key_symbol(30).utf8.as_deref(), Some("a"));
+
+        xkb.update_modifiers(&Modifiers {
+            serial: WlUInt(0),
+            mods_depressed: WlUInt(1),
+            mods_latched: WlUInt(0),
+            mods_locked: WlUInt(0),
+            group: WlUInt(0),
+        });
+        assert_eq!(xkb.keysym(30), Keysym('A' as u32));
+        assert_eq!(xkb.utf8(30).as_deref(), Some("A"));
+    }
+}